@@ -0,0 +1,217 @@
+//! Helpers shared by the benchmarks of components.
+
+use bytes::Bytes;
+use criterion::{black_box, measurement::WallTime, BatchSize, BenchmarkGroup};
+use futures::{future, stream, SinkExt, StreamExt};
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server,
+};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+use vector::{
+    buffers::Acker,
+    config::{GlobalOptions, SinkConfig, SinkContext, TransformConfig},
+    event::Event,
+    sinks::util::{
+        batch::{Batch, BatchSize as BufferSize, PushResult},
+        http::HttpSink,
+        Buffer, Compression, PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer,
+    },
+    test_util::{next_addr, runtime, wait_for_tcp},
+};
+
+/// Helpers to benchmark components, and the work a sink does before a request
+/// hits the wire.
+///
+/// Every helper takes its input events by reference and clones them per
+/// iteration, so the cloning cost is part of the setup, not the measurement.
+pub trait BenchmarkGroupExt {
+    /// Benchmark encoding `events` with `sink`, batching them into `batch`
+    /// and building one request per finished batch.
+    fn bench_http_sink<S, B>(&mut self, id: &str, sink: &S, batch: B, events: &[Event])
+    where
+        S: HttpSink<Input = B::Input, Output = B::Output>,
+        B: Batch;
+
+    /// Benchmark encoding `events` with `encode` and feeding the result into
+    /// a partitioned batch sink of compressed buffers.
+    fn bench_partitioned_buffer<F>(
+        &mut self,
+        id: &str,
+        encode: F,
+        batch: BufferSize<Buffer>,
+        compression: Compression,
+        events: &[Event],
+    ) where
+        F: Fn(Event) -> Option<PartitionInnerBuffer<Vec<u8>, Bytes>> + Clone + Send + 'static;
+
+    /// Benchmark running `events` through the task transform built from
+    /// `config`, draining its output.
+    fn bench_task_transform<C>(&mut self, id: &str, config: &C, events: &[Event])
+    where
+        C: TransformConfig;
+
+    /// Benchmark running `events` through the sink built from `config`, which
+    /// is given the address of a mock HTTP downstream answering every request
+    /// with `200 OK`. A run ends once the sink has flushed all its requests.
+    fn bench_sink<C, F>(&mut self, id: &str, config: F, events: &[Event])
+    where
+        C: SinkConfig,
+        F: FnOnce(SocketAddr) -> C;
+}
+
+impl BenchmarkGroupExt for BenchmarkGroup<'_, WallTime> {
+    fn bench_http_sink<S, B>(&mut self, id: &str, sink: &S, batch: B, events: &[Event])
+    where
+        S: HttpSink<Input = B::Input, Output = B::Output>,
+        B: Batch,
+    {
+        self.bench_function(id, |b| {
+            b.iter_batched(
+                || (runtime(), batch.fresh(), events.to_vec()),
+                |(mut rt, mut batch, events)| {
+                    let mut outputs = Vec::new();
+
+                    for event in events {
+                        let mut item = match sink.encode_event(event) {
+                            Some(item) => item,
+                            None => continue,
+                        };
+
+                        loop {
+                            match batch.push(item) {
+                                PushResult::Ok(false) => break,
+                                PushResult::Ok(true) => {
+                                    outputs.push(batch.fresh_replace().finish());
+                                    break;
+                                }
+                                PushResult::Overflow(overflow) => {
+                                    outputs.push(batch.fresh_replace().finish());
+                                    item = overflow;
+                                }
+                            }
+                        }
+                    }
+
+                    if !batch.is_empty() {
+                        outputs.push(batch.finish());
+                    }
+
+                    rt.block_on(async {
+                        for output in outputs {
+                            sink.build_request(output).await.unwrap();
+                        }
+                    })
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    fn bench_partitioned_buffer<F>(
+        &mut self,
+        id: &str,
+        encode: F,
+        batch: BufferSize<Buffer>,
+        compression: Compression,
+        events: &[Event],
+    ) where
+        F: Fn(Event) -> Option<PartitionInnerBuffer<Vec<u8>, Bytes>> + Clone + Send + 'static,
+    {
+        self.bench_function(id, |b| {
+            b.iter_batched(
+                || {
+                    let rt = runtime();
+                    let (acker, _) = Acker::new_for_testing();
+                    let encode = encode.clone();
+                    let batch_sink = PartitionBatchSink::new(
+                        tower::service_fn(|_| future::ok::<_, Infallible>(())),
+                        PartitionBuffer::new(Buffer::new(batch, compression)),
+                        Duration::from_secs(1),
+                        acker,
+                    )
+                    .with_flat_map(move |event| stream::iter(encode(event)).map(Ok))
+                    .sink_map_err(|error| panic!(error));
+
+                    (rt, stream::iter(events.to_vec()).map(Ok), batch_sink)
+                },
+                |(mut rt, input, batch_sink)| rt.block_on(input.forward(batch_sink)).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    fn bench_task_transform<C>(&mut self, id: &str, config: &C, events: &[Event])
+    where
+        C: TransformConfig,
+    {
+        let mut rt = runtime();
+
+        self.bench_function(id, |b| {
+            // The transform is consumed by each run, so it's built anew for
+            // each of them, outside of the measurement.
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::default();
+                for _ in 0..iters {
+                    let transform = rt
+                        .block_on(config.build(&GlobalOptions::default()))
+                        .unwrap()
+                        .into_task();
+                    let input = Box::pin(stream::iter(events.to_vec()));
+
+                    let start = Instant::now();
+                    let output = rt.block_on(transform.transform(input).count());
+                    elapsed += start.elapsed();
+
+                    black_box(output);
+                }
+                elapsed
+            })
+        });
+    }
+
+    fn bench_sink<C, F>(&mut self, id: &str, config: F, events: &[Event])
+    where
+        C: SinkConfig,
+        F: FnOnce(SocketAddr) -> C,
+    {
+        let mut rt = runtime();
+        let addr = next_addr();
+        rt.spawn(mock_http_downstream(addr));
+        rt.block_on(wait_for_tcp(addr));
+        let config = config(addr);
+
+        self.bench_function(id, |b| {
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::default();
+                for _ in 0..iters {
+                    let (sink, _healthcheck) =
+                        rt.block_on(config.build(SinkContext::new_test())).unwrap();
+                    let input = stream::iter(events.to_vec());
+
+                    let start = Instant::now();
+                    rt.block_on(sink.run(input)).unwrap();
+                    elapsed += start.elapsed();
+                }
+                elapsed
+            })
+        });
+    }
+}
+
+/// Serves every request with an empty `200 OK` response, standing in for the
+/// downstream service of a sink.
+async fn mock_http_downstream(addr: SocketAddr) {
+    let service = make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(|request: Request<Body>| async move {
+            hyper::body::to_bytes(request.into_body()).await?;
+            Ok::<_, hyper::Error>(Response::new(Body::empty()))
+        }))
+    });
+
+    Server::bind(&addr).serve(service).await.unwrap();
+}
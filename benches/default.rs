@@ -1,6 +1,7 @@
 use criterion::criterion_main;
 
 mod batch;
+mod bench_util;
 mod buffering;
 mod event;
 mod files;
@@ -10,8 +11,10 @@ mod lookup;
 mod lua;
 mod metrics_snapshot;
 mod regex;
+mod sinks;
 mod template;
 mod topology;
+mod transforms;
 
 criterion_main!(
     batch::benches,
@@ -24,6 +27,8 @@ criterion_main!(
     lua::benches,
     metrics_snapshot::benches,
     regex::benches,
+    sinks::benches,
     template::benches,
     topology::benches,
+    transforms::benches,
 );
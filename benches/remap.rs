@@ -1,7 +1,6 @@
 use chrono::{DateTime, Utc};
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use indexmap::IndexMap;
-use remap::prelude::*;
 use vector::transforms::{
     add_fields::AddFields,
     coercer::CoercerConfig,
@@ -10,7 +9,7 @@ use vector::transforms::{
     FunctionTransform,
 };
 use vector::{
-    config::TransformConfig,
+    config::{GlobalOptions, TransformConfig},
     event::{Event, Value},
     test_util::runtime,
 };
@@ -20,37 +19,10 @@ criterion_group!(
     // encapsulates CI noise we saw in
     // https://github.com/timberio/vector/issues/5394
     config = Criterion::default().noise_threshold(0.02);
-    targets = benchmark_remap, upcase, downcase, parse_json
+    targets = benchmark_remap
 );
 criterion_main!(benches);
 
-bench_function! {
-    upcase => remap_functions::Upcase;
-
-    literal_value {
-        args: func_args![value: "foo"],
-        want: Ok("FOO")
-    }
-}
-
-bench_function! {
-    downcase => remap_functions::Downcase;
-
-    literal_value {
-        args: func_args![value: "FOO"],
-        want: Ok("foo")
-    }
-}
-
-bench_function! {
-    parse_json => remap_functions::ParseJson;
-
-    literal_value {
-        args: func_args![value: r#"{"key": "value"}"#],
-        want: Ok(value!({"key": "value"})),
-    }
-}
-
 fn benchmark_remap(c: &mut Criterion) {
     let mut rt = runtime();
     let add_fields_runner = |tform: &mut Box<dyn FunctionTransform>, event: Event| {
@@ -73,6 +45,7 @@ fn benchmark_remap(c: &mut Criterion) {
             .copy = .copy_from"#
                     .to_string(),
                 drop_on_err: true,
+                ..Default::default()
             })
             .unwrap(),
         );
@@ -133,6 +106,7 @@ fn benchmark_remap(c: &mut Criterion) {
             Remap::new(RemapConfig {
                 source: ".bar = parse_json!(.foo)".to_owned(),
                 drop_on_err: false,
+                ..Default::default()
             })
             .unwrap(),
         );
@@ -202,6 +176,7 @@ fn benchmark_remap(c: &mut Criterion) {
                 "#
                 .to_owned(),
                 drop_on_err: true,
+                ..Default::default()
             })
             .unwrap(),
         );
@@ -240,7 +215,7 @@ fn benchmark_remap(c: &mut Criterion) {
                    "#,
                 )
                 .unwrap()
-                .build()
+                .build(&GlobalOptions::default())
                 .await
                 .unwrap()
             })
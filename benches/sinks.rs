@@ -0,0 +1,163 @@
+use crate::bench_util::BenchmarkGroupExt;
+use criterion::{criterion_group, Criterion, SamplingMode, Throughput};
+use std::convert::TryFrom;
+use vector::{
+    event::Event,
+    sinks::{
+        aws_s3,
+        elasticsearch::{ElasticSearchCommon, ElasticSearchConfig},
+        http::HttpSinkConfig,
+        loki::{LokiConfig, LokiSink, OutOfOrderAction},
+        util::{
+            batch::BatchSettings,
+            buffer::loki::{GlobalTimestamps, LokiBuffer},
+            encoding::EncodingConfig,
+            Buffer, Compression, PartitionBuffer,
+        },
+    },
+    template::Template,
+    test_util::events::{LogGenerator, LogShape},
+};
+
+const EVENT_LEN: usize = 100;
+const NUM_EVENTS: usize = 10_000;
+
+fn benchmark_loki(c: &mut Criterion) {
+    let events = events_with_streams(5);
+
+    let mut group = c.benchmark_group("sinks/loki");
+    group.throughput(Throughput::Bytes((EVENT_LEN * NUM_EVENTS) as u64));
+    group.sampling_mode(SamplingMode::Flat);
+
+    for encoding in &["json", "text"] {
+        let config: LokiConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "http://localhost:3100"
+            encoding = "{}"
+
+            [labels]
+            stream = "{{{{ stream }}}}"
+            "#,
+            encoding
+        ))
+        .unwrap();
+
+        let batch = BatchSettings::default().bytes(102_400).events(100_000).size;
+        let buffer = PartitionBuffer::new(LokiBuffer::new(
+            batch,
+            GlobalTimestamps::default(),
+            OutOfOrderAction::Drop,
+        ));
+
+        group.bench_http_sink(
+            &format!("encoding_{}", encoding),
+            &LokiSink::new(config),
+            buffer,
+            &events,
+        );
+    }
+}
+
+fn benchmark_elasticsearch(c: &mut Criterion) {
+    let events = events_with_streams(1);
+
+    let mut group = c.benchmark_group("sinks/elasticsearch");
+    group.throughput(Throughput::Bytes((EVENT_LEN * NUM_EVENTS) as u64));
+    group.sampling_mode(SamplingMode::Flat);
+
+    for compression in &[Compression::None, Compression::gzip_default()] {
+        let config = ElasticSearchConfig {
+            endpoint: "http://localhost:9200".into(),
+            compression: *compression,
+            ..Default::default()
+        };
+        let common = ElasticSearchCommon::parse_config(&config).unwrap();
+
+        let batch = BatchSettings::default().bytes(bytesize::mib(10u64)).size;
+
+        group.bench_http_sink(
+            &format!("compression_{}", compression),
+            &common,
+            Buffer::new(batch, *compression),
+            &events,
+        );
+    }
+}
+
+fn benchmark_s3(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sinks/aws_s3");
+    group.throughput(Throughput::Bytes((EVENT_LEN * NUM_EVENTS) as u64));
+    group.sampling_mode(SamplingMode::Flat);
+
+    let cases = [
+        (aws_s3::Encoding::Text, Compression::None, 1),
+        (aws_s3::Encoding::Ndjson, Compression::None, 1),
+        (aws_s3::Encoding::Ndjson, Compression::gzip_default(), 1),
+        (aws_s3::Encoding::Ndjson, Compression::gzip_default(), 10),
+    ];
+
+    for (encoding, compression, partitions) in cases.iter() {
+        let events = events_with_streams(*partitions);
+        let key_prefix = Template::try_from("{{ stream }}/date=%F/").unwrap();
+        let id = format!("{:?}_{}_{}_partitions", encoding, compression, partitions);
+        let encoding: EncodingConfig<aws_s3::Encoding> = encoding.clone().into();
+
+        let batch = BatchSettings::default().bytes(10_000_000).size;
+
+        group.bench_partitioned_buffer(
+            &id.to_lowercase(),
+            move |event| aws_s3::encode_event(event, &key_prefix, &encoding),
+            batch,
+            *compression,
+            &events,
+        );
+    }
+}
+
+fn benchmark_http(c: &mut Criterion) {
+    let events = events_with_streams(1);
+
+    let mut group = c.benchmark_group("sinks/http");
+    group.throughput(Throughput::Bytes((EVENT_LEN * NUM_EVENTS) as u64));
+    group.sampling_mode(SamplingMode::Flat);
+
+    for (encoding, compression) in &[("ndjson", "none"), ("json", "none"), ("ndjson", "gzip")] {
+        group.bench_sink(
+            &format!("{}_{}", encoding, compression),
+            |addr| -> HttpSinkConfig {
+                toml::from_str(&format!(
+                    r#"
+                    uri = "http://{}/"
+                    encoding = "{}"
+                    compression = "{}"
+                    "#,
+                    addr, encoding, compression
+                ))
+                .unwrap()
+            },
+            &events,
+        );
+    }
+}
+
+/// Generate random log events, spread evenly over `streams` distinct values
+/// of the `stream` field.
+fn events_with_streams(streams: usize) -> Vec<Event> {
+    LogGenerator::new(LogShape::Message)
+        .message_len(EVENT_LEN)
+        .take(NUM_EVENTS)
+        .enumerate()
+        .map(|(i, mut event)| {
+            event
+                .as_mut_log()
+                .insert("stream", format!("stream-{}", i % streams));
+            event
+        })
+        .collect()
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().noise_threshold(0.10);
+    targets = benchmark_loki, benchmark_elasticsearch, benchmark_s3, benchmark_http
+);
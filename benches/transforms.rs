@@ -0,0 +1,43 @@
+use crate::bench_util::BenchmarkGroupExt;
+use criterion::{criterion_group, Criterion, SamplingMode, Throughput};
+use vector::{
+    test_util::events::{LogGenerator, LogShape},
+    transforms::dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+};
+
+const EVENT_LEN: usize = 100;
+const NUM_EVENTS: usize = 10_000;
+
+fn benchmark_dedupe(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transforms/dedupe");
+    group.throughput(Throughput::Elements(NUM_EVENTS as u64));
+    group.sampling_mode(SamplingMode::Flat);
+
+    let config = DedupeConfig {
+        fields: Some(FieldMatchConfig::MatchFields(vec!["message".into()])),
+        cache: CacheConfig { num_events: 5000 },
+    };
+
+    // All events are unique, so the cache is always full and evicting.
+    let unique = LogGenerator::new(LogShape::Message)
+        .message_len(EVENT_LEN)
+        .take(NUM_EVENTS)
+        .collect::<Vec<_>>();
+    group.bench_task_transform("unique", &config, &unique);
+
+    // Most events are duplicates of the few distinct ones in the cache.
+    let duplicates = LogGenerator::new(LogShape::Message)
+        .message_len(EVENT_LEN)
+        .cardinality(100)
+        .take(NUM_EVENTS)
+        .collect::<Vec<_>>();
+    group.bench_task_transform("duplicates", &config, &duplicates);
+}
+
+criterion_group!(
+    name = benches;
+    // encapsulates CI noise we saw in
+    // https://github.com/timberio/vector/issues/5394
+    config = Criterion::default().noise_threshold(0.05);
+    targets = benchmark_dedupe
+);
@@ -85,6 +85,7 @@ end
 "#
                     .to_string(),
                     drop_on_err: false,
+                    ..Default::default()
                 })
                 .unwrap(),
             ),
@@ -14,5 +14,31 @@ fn main() {
             &["proto/"],
         )
         .unwrap();
+
+    #[cfg(feature = "tonic-build")]
+    {
+        println!("cargo:rerun-if-changed=proto/plugin.proto");
+        let mut prost_build = prost_build::Config::new();
+        prost_build.btree_map(&["."]);
+        prost_build.extern_path(".event.proto", "crate::event::proto");
+        tonic_build::configure()
+            .compile_with_config(prost_build, &["proto/plugin.proto"], &["proto/"])
+            .unwrap();
+    }
+
+    #[cfg(any(feature = "sinks-opentelemetry", feature = "sources-opentelemetry"))]
+    {
+        println!("cargo:rerun-if-changed=proto/opentelemetry");
+        tonic_build::configure()
+            .compile(
+                &[
+                    "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+                ],
+                &["proto/"],
+            )
+            .unwrap();
+    }
+
     built::write_built_file().expect("Failed to acquire build-time information");
 }
@@ -50,6 +50,120 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		health_path: {
+			common:   false
+			required: false
+			type: string: {
+				default: "/health"
+				examples: ["/healthz"]
+				syntax: "literal"
+			}
+			description: """
+				The path of the liveness endpoint, such as for the `livenessProbe` of
+				a Kubernetes pod.
+				"""
+		}
+		ready_path: {
+			common:   false
+			required: false
+			type: string: {
+				default: "/ready"
+				examples: ["/readyz"]
+				syntax: "literal"
+			}
+			description: """
+				The path of the readiness endpoint, such as for the `readinessProbe`
+				of a Kubernetes pod.
+				"""
+		}
+		tls: {
+			common:      false
+			required:    false
+			description: "Configures TLS for the API server, so that it's served over HTTPS."
+			type: object: options: {
+				enabled: {
+					common:      false
+					required:    false
+					description: "Serve the API over HTTPS. If this is set, an identity certificate is also required."
+					type: bool: default: false
+				}
+				crt_file: {
+					common:      false
+					required:    false
+					description: "Absolute path to a certificate file used to identify the API server, in DER or PEM format (X.509) or PKCS#12, or an in-line certificate in PEM format. If this is set, and is not a PKCS#12 archive, `key_file` must also be set."
+					type: string: {
+						default: null
+						examples: ["/path/to/host_certificate.crt"]
+						syntax: "literal"
+					}
+				}
+				key_file: {
+					common:      false
+					required:    false
+					description: "Absolute path to a private key file used to identify the API server, in DER or PEM format (PKCS#8), or an in-line private key in PEM format."
+					type: string: {
+						default: null
+						examples: ["/path/to/host_certificate.key"]
+						syntax: "literal"
+					}
+				}
+				key_pass: {
+					common:      false
+					required:    false
+					description: "Pass phrase used to unlock the encrypted key file. This has no effect unless `key_file` is set."
+					type: string: {
+						default: null
+						examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+						syntax: "literal"
+					}
+				}
+				ca_file: {
+					common:      false
+					required:    false
+					description: "Absolute path to an additional CA certificate file, in DER or PEM format (X.509), or an in-line CA certificate in PEM format."
+					type: string: {
+						default: null
+						examples: ["/path/to/certificate_authority.crt"]
+						syntax: "literal"
+					}
+				}
+				verify_certificate: {
+					common:      false
+					required:    false
+					description: "If `true`, the API server requires a valid TLS certificate from connecting clients."
+					type: bool: default: false
+				}
+			}
+		}
+		auth: {
+			common:   false
+			required: false
+			description: """
+				Requires requests to the API to be authorized with a bearer token,
+				sent in an `Authorization: Bearer <token>` header. The `vector top`
+				and `vector tap` commands send it with their `--token` option.
+				"""
+			type: object: options: {
+				token: {
+					common:      true
+					required:    true
+					description: "The token requests have to be authorized with."
+					type: string: {
+						examples: ["${VECTOR_API_TOKEN}"]
+						syntax: "literal"
+					}
+				}
+				exempt_probes: {
+					common:   false
+					required: false
+					description: """
+						Whether the liveness and readiness endpoints answer requests
+						without a token, for orchestrators that can't send one.
+						"""
+					type: bool: default: true
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -69,18 +183,64 @@ api: {
 							there were any errors in your query.
 							"""
 					}
+					"401": {
+						description: """
+							The request wasn't authorized with the token set
+							by the `auth` option.
+							"""
+					}
 				}
 			}
 		}
 		"/health": {
 			GET: {
 				description: """
-					Healthcheck endpoint. Useful to verify that
-					Vector is up and running.
+					Liveness endpoint. Useful to verify that Vector
+					is up, including while the topology is built and
+					disk buffers are recovered. Its path is set by
+					the `health_path` option. The response lists the
+					status of each component, under `components`.
 					"""
 				responses: {
 					"200": {
-						description: "Vector is initialized and running."
+						description: "Vector is up."
+					}
+					"503": {
+						description: """
+							A component failed, such as a source that
+							couldn't bind its address, and Vector is
+							shutting down.
+							"""
+					}
+				}
+			}
+		}
+		"/ready": {
+			GET: {
+				description: """
+					Readiness endpoint. Useful to verify that Vector
+					is running its topology, meaning it was built,
+					its required healthchecks passed and its disk
+					buffers were recovered. Its path is set by the
+					`ready_path` option. The response lists the
+					status of each component, under `components`:
+					`running`, `healthy` or `unhealthy` for sinks
+					that passed or failed their healthcheck, or
+					`failed`.
+					"""
+				responses: {
+					"200": {
+						description: """
+							Vector is initialized and running, and all
+							of its components are running or healthy.
+							"""
+					}
+					"503": {
+						description: """
+							Vector is building its topology, recovering
+							its buffers or shutting down, or one of its
+							components is unhealthy or failed.
+							"""
 					}
 				}
 			}
@@ -13,6 +13,32 @@ _default_flags: {
 	}
 }
 
+// Options of the commands connecting to the API of a local or remote Vector
+// instance
+_api_options: {
+	"url": {
+		_short: "u"
+		description: """
+			The URL for the GraphQL endpoint of the running Vector instance. Use an
+			`https://` URL to connect over TLS. Defaults to the local API server
+			"""
+		type: "string"
+	}
+	"token": {
+		description: "Bearer token to authenticate with, for API servers that require one"
+		type:        "string"
+		env_var:     "VECTOR_API_TOKEN"
+	}
+	"ca-file": {
+		description: """
+			PEM encoded CA certificate to verify an `https://` API server with,
+			besides the system ones. Useful for API servers with self-signed
+			certificates
+			"""
+		type: "string"
+	}
+}
+
 cli: {
 	#Args: [Arg=string]: {
 		description: !=""
@@ -164,6 +190,17 @@ cli: {
 			type:    "integer"
 			env_var: "VECTOR_THREADS"
 		}
+		"expose-metrics-port": {
+			description: """
+				Expose the internal metrics of Vector in the Prometheus format on
+				the given port, in addition to the components of the configuration.
+				This adds an `internal_metrics` source named `vector_internal_metrics`
+				and a `prometheus_exporter` sink named `vector_prometheus_exporter`
+				to the configuration
+				"""
+			type:    "integer"
+			env_var: "VECTOR_EXPOSE_METRICS_PORT"
+		}
 		"log-format": {
 			description: "Set the logging format [default: text]"
 			default:     "text"
@@ -175,6 +212,92 @@ cli: {
 	}
 
 	commands: {
+		"benchmark": {
+			description: """
+				Run a config with generated events in place of its sources, and report
+				its throughput, latency and resource use, then exit. Sinks are replaced by
+				stubs that count the events they receive, and measure their latency from
+				their timestamp. Sources that only emit metrics are kept
+				"""
+
+			options: {
+				"duration": {
+					_short:      "d"
+					description: "How long to run the benchmark for, in seconds"
+					type:        "integer"
+					default:     10
+				}
+				"format": {
+					_short:      "f"
+					description: "The format of the events generated in place of the sources"
+					default:     "json"
+					enum: {
+						apache_common: "Apache common log lines."
+						apache_error:  "Apache error log lines."
+						syslog:        "RFC 5424 syslog lines."
+						bsd_syslog:    "RFC 3164 syslog lines."
+						json:          "JSON log lines."
+					}
+				}
+				"config-toml": {
+					description: """
+						Any number of Vector config files to benchmark.
+						TOML file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-json": {
+					description: """
+						Any number of Vector config files to benchmark.
+						JSON file format is assumed.
+						"""
+					type: "string"
+				}
+				"config-yaml": {
+					description: """
+						Any number of Vector config files to benchmark.
+						YAML file format is assumed.
+						"""
+					type: "string"
+				}
+			}
+
+			args: {
+				paths: _paths_arg & {
+					description: """
+						Any number of Vector config files to benchmark. If none are specified
+						the default config path `/etc/vector/vector.toml` will be targeted
+						"""
+				}
+			}
+		}
+
+		"buffers": {
+			description: """
+				Display the buffers of components and how full they are in the console,
+				for a local or remote Vector instance
+				"""
+
+			flags: _default_flags & {
+				"human-metrics": {
+					_short: "h"
+					description: """
+						Humanize metrics, using numeric suffixes - e.g. 1,100 = 1.10 k,
+						1,000,000 = 1.00 M
+						"""
+				}
+			}
+
+			options: _api_options & {
+				"refresh-interval": {
+					_short:      "i"
+					description: "How often the screen refreshes (in milliseconds)"
+					type:        "integer"
+					default:     500
+				}
+			}
+		}
+
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
 
@@ -230,6 +353,15 @@ cli: {
 				out: https://vector.dev/docs/setup/guides/unit-testing/
 				"""
 
+			flags: _default_flags & {
+				"update-golden-files": {
+					description: """
+						Write the payloads encoded by the sinks of tests with `payloads` checks
+						to their golden files, instead of checking them against the files
+						"""
+				}
+			}
+
 			options: {
 				"config-toml": {
 					description: """
@@ -267,6 +399,48 @@ cli: {
 			}
 		}
 
+		"tap": {
+			description: """
+				Print samples of the events flowing into or out of a component, for a
+				local or remote Vector instance
+				"""
+
+			flags: _default_flags & {
+				"inputs": {
+					description: """
+						Tap the events received by the component from its inputs, rather than
+						the events it sends
+						"""
+				}
+				"compact": {
+					_short:      "c"
+					description: "Print each event as a single line of JSON, rather than pretty-printed"
+				}
+			}
+
+			options: _api_options & {
+				"interval": {
+					_short:      "i"
+					description: "Interval to send batches of sampled events at (in milliseconds)"
+					type:        "integer"
+					default:     500
+				}
+				"limit": {
+					_short:      "l"
+					description: "Maximum number of events to sample each interval"
+					type:        "integer"
+					default:     100
+				}
+			}
+
+			args: {
+				component: {
+					description: "Name of the component to tap"
+					type:        "string"
+				}
+			}
+		}
+
 		"top": {
 			description: """
 				Display topology and metrics in the console, for a local or remote Vector
@@ -283,17 +457,30 @@ cli: {
 				}
 			}
 
-			options: {
+			options: _api_options & {
 				"refresh-interval": {
 					_short:      "i"
 					description: "How often the screen refreshes (in milliseconds)"
 					type:        "integer"
 					default:     500
 				}
-				"url": {
-					_short:      "u"
-					description: "The URL for the GraphQL endpoint of the running Vector instance"
-					type:        "string"
+				"sort": {
+					_short: "s"
+					description: """
+						Column to initially sort components by. Press `s` in the dashboard to
+						sort by the next column, and `r` to reverse the order
+						"""
+					type:    "string"
+					default: "name"
+					enum: {
+						name:   "Component name"
+						kind:   "Component kind"
+						type:   "Component type"
+						events: "Events processed per second, then in total"
+						bytes:  "Bytes processed per second, then in total"
+						errors: "Errors per second, then in total"
+						buffer: "How full the buffer of the component is"
+					}
 				}
 			}
 		}
@@ -313,6 +500,14 @@ cli: {
 					_short:      "d"
 					description: "Fail validation on warnings"
 				}
+				"verbose": {
+					_short: "v"
+					description: """
+						Print the inferred type of the events emitted by transforms
+						that support it, such as the fields assigned by `remap`
+						programs and the kinds of their values
+						"""
+				}
 			}
 
 			options: {
@@ -386,7 +581,8 @@ cli: {
 				program: {
 					description: #"""
 						The program to execute. For example, `".foo = true"` sets the object's `foo`
-						field to `true`.
+						field to `true`. Leave empty, and don't set `--program`, to start an
+						interactive REPL instead.
 						"""#
 					type: "string"
 				}
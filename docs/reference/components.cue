@@ -801,18 +801,67 @@ components: {
 				}
 			}
 
+			_timezone: {
+				common:      false
+				description: """
+					The name of the time zone to apply to timestamp conversions that do not contain an explicit
+					time zone, overriding the global [`timezone`](\(urls.vector_configuration)#timezone) option.
+					The time zone name may be any name in the [TZ database](\(urls.iana_time_zones)), or `local`
+					to indicate system local time.
+					"""
+				required:    false
+				warnings: []
+				type: string: {
+					default: "local"
+					examples: ["local", "America/New_York", "EST5EDT"]
+					syntax: "literal"
+				}
+			}
+
 			if Kind != "source" {
 				inputs: {
-					description: "A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms)) IDs. See [configuration](\(urls.vector_configuration)) for more info."
+					description: """
+						A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms)) IDs.
+						IDs can be glob patterns (`*`, `?` and `[...]`), which are expanded to all matching sources and
+						transforms, excluding the component itself. A pattern that doesn't match any component, or that
+						creates a cycle, is a configuration error. See [configuration](\(urls.vector_configuration)) for
+						more info.
+						"""
 					required:    true
 					sort:        -1
 					type: array: items: type: string: {
-						examples: ["my-source-or-transform-id"]
+						examples: ["my-source-or-transform-id", "prefix-*"]
 						syntax: "literal"
 					}
 				}
 			}
 
+			if Kind != "source" {
+				runtime: {
+					common:      false
+					description: """
+						Runs the component on a runtime of its own, with dedicated worker threads, instead of the
+						worker threads shared by all components. This keeps CPU-intensive components from starving
+						the others, such as the sources reading from sockets, on instances with few cores.
+						"""
+					required:    false
+					type: object: {
+						examples: []
+						options: {
+							threads: {
+								common:      true
+								description: "The number of worker threads of the runtime."
+								required:    false
+								type: uint: {
+									default: 1
+									unit:    null
+								}
+							}
+						}
+					}
+				}
+			}
+
 			"type": {
 				description: "The component type. This is a required field for all components and tells Vector which component to use."
 				required:    true
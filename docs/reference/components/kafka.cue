@@ -99,5 +99,16 @@ components: _kafka: {
 				this dependency is packaged with Vector, meaning you do not need to install it.
 				"""
 		}
+
+		librdkafka_statistics: {
+			title: "Client statistics"
+			body:  """
+				The statistics of `librdkafka`, like the consumer lag of each topic partition
+				and the state of the connections to the brokers, are collected every second
+				and reported as internal metrics of the component. Set the
+				`statistics.interval.ms` option of `librdkafka_options` to collect them at a
+				different interval, or to `0` to disable them.
+				"""
+		}
 	}
 }
@@ -0,0 +1,92 @@
+package metadata
+
+components: _plugin: {
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		component: {
+			description: "The name of the component to run, as declared by the plugin."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["my_component"]
+				syntax: "literal"
+			}
+		}
+		endpoint: {
+			description: "The address of the gRPC server of the plugin."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["http://127.0.0.1:50051"]
+				syntax: "literal"
+			}
+		}
+		options: {
+			common:      true
+			description: "The configuration of the component. It is sent to the plugin encoded as JSON, and its shape is defined by the plugin."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [{"interval_secs": 10, "tags": ["a", "b"]}]
+				options: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		protocol: {
+			title: "Plugin Protocol"
+			body: """
+				A plugin is a separate process running a gRPC server that implements the
+				`Plugin` service defined in `proto/plugin.proto` of the Vector repository.
+				Events are exchanged in the same encoding as the `vector` source and sink,
+				so plugins can be written in any language with gRPC support.
+
+				When Vector starts the component it calls `Describe` and fails to start if
+				the plugin doesn't declare a component of the configured name and kind.
+				Each component then opens a bidirectional stream whose first message is
+				the `component` and `options` of the configuration.
+				"""
+		}
+		reconnection: {
+			title: "Reconnection"
+			body: """
+				If the stream fails, Vector opens a new one after a delay that grows
+				exponentially up to one minute. Sources and sinks keep track of
+				acknowledged events across streams, so events are only lost when the
+				transform stream fails.
+				"""
+		}
+		event_types: {
+			title: "Event Types"
+			body: """
+				Every component declares the type of events it accepts. Events of other
+				types are dropped before they are sent to the plugin and are counted in
+				the `events_discarded_total` metric.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:  components.sources.internal_metrics.output.metrics.events_discarded_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+		request_errors_total:    components.sources.internal_metrics.output.metrics.request_errors_total
+	}
+}
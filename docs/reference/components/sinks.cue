@@ -20,7 +20,7 @@ components: sinks: [Name=string]: {
 							if sinks[Name].features.send.batch.max_bytes != null {
 								max_bytes: {
 									common:      true
-									description: "The maximum size of a batch, in bytes, before it is flushed."
+									description: "The maximum size of a batch, in bytes of the encoded payload before compression, before it is flushed."
 									required:    false
 									type: uint: {
 										default: sinks[Name].features.send.batch.max_bytes
@@ -74,10 +74,25 @@ components: sinks: [Name=string]: {
 								unit:    "events"
 							}
 						}
+						fsync: {
+							common:        false
+							description:   "When the buffer syncs the events written to it to disk. Events that aren't synced yet are lost if the host crashes, but are kept if only Vector does."
+							required:      false
+							relevant_when: "type = \"disk_v2\""
+							type: string: {
+								default: "periodic"
+								enum: {
+									always:   "Syncs after each batch of events written, and each acknowledgement of events by the sink."
+									periodic: "Syncs at most once a second."
+									never:    "Only syncs the data files once they're full, and leaves the rest to the operating system."
+								}
+								syntax: "literal"
+							}
+						}
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"disk_v2\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
@@ -90,8 +105,9 @@ components: sinks: [Name=string]: {
 							type: string: {
 								default: "memory"
 								enum: {
-									memory: "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
-									disk:   "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									memory:  "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
+									disk:    "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									disk_v2: "Stores the sink's buffer on disk, in data files of checksummed records instead of a LevelDB database. Corrupted data files are detected and skipped. It uses a separate directory from the `disk` buffer, so events buffered by the latter aren't carried over when switching."
 								}
 								syntax: "literal"
 							}
@@ -210,10 +226,16 @@ components: sinks: [Name=string]: {
 						options: {
 							enabled: {
 								common:      true
-								description: "Enables/disables the healthcheck upon Vector boot."
+								description: "Enables/disables the healthcheck upon Vector boot. Overrides the global `healthchecks.enabled` option."
 								required:    false
 								type: bool: default: true
 							}
+							require_healthy: {
+								common:      false
+								description: "Exit on startup if the health check of this sink fails. Overrides the global `healthchecks.require_healthy` option."
+								required:    false
+								type: bool: default: false
+							}
 						}
 					}
 				}
@@ -379,11 +401,64 @@ components: sinks: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							interval_secs: {
+								common:      false
+								description: "The time between TCP keepalive probes, once the connection has been idle for `time_secs`. Only supported on Linux."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    "seconds"
+								}
+							}
+							retries: {
+								common:      false
+								description: "The number of unanswered TCP keepalive probes after which the connection is considered broken. Only supported on Linux."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    null
+								}
+							}
 						}
 					}
 				}
 			}
 
+			if sinks[Name].features.send.keepalive != _|_ {
+				receive_buffer_bytes: {
+					common:      false
+					description: "Configures the receive buffer size using the `SO_RCVBUF` option on the socket."
+					required:    false
+					type: uint: {
+						examples: [65536]
+					}
+				}
+
+				nodelay: {
+					common:      false
+					description: "Disables Nagle's algorithm using the `TCP_NODELAY` option on the socket, so that small writes are sent immediately."
+					required:    false
+					type: bool: default: false
+				}
+
+				user_timeout_secs: {
+					common: false
+					description: """
+						The time sent data may remain unacknowledged before the connection is
+						closed and reopened, using the `TCP_USER_TIMEOUT` option on the socket.
+						This detects half-open connections, e.g. after a silent failover of the
+						downstream service, which would otherwise only fail once the operating
+						system gives up retransmitting. Only supported on Linux.
+						"""
+					required: false
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+			}
+
 			if sinks[Name].features.send.tls.enabled {
 				tls: configuration._tls_connect & {_args: {
 					can_enable:             sinks[Name].features.send.tls.can_enable
@@ -465,6 +540,12 @@ components: sinks: [Name=string]: {
 								If you'd like to disable health checks for this
 								sink you can set the `healthcheck` option to
 								`false`.
+
+								Health checks of all sinks can be disabled at
+								once with the global `healthchecks.enabled`
+								option, which is handy for CI and air-gapped
+								validation runs. Options set on a sink take
+								precedence over the global ones.
 								"""
 					},
 				]
@@ -0,0 +1,108 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      false
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: {
+				service: services.amqp
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				Messages are published with [publisher confirms](\(urls.amqp_publisher_confirms)); events are only
+				acknowledged once the broker has confirmed their message. Up to 128 messages are published before
+				being confirmed. Messages the broker negatively acknowledges are published again, and so are the
+				unconfirmed messages once the connection is reestablished after a failure.
+				""",
+		]
+	}
+
+	configuration: {
+		connection: components.sources.amqp.configuration.connection
+		exchange: {
+			description: "The exchange to publish messages to. It must already exist, except for the default exchange `\"\"`."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["logs", "logs-{{ application }}"]
+				syntax: "template"
+			}
+		}
+		routing_key: {
+			common:      true
+			description: "The routing key to publish messages with, which the exchange routes them by. If unspecified, messages are published with an empty routing key."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ host }}", "web.access"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	telemetry: metrics: {
+		missing_keys_total:     components.sources.internal_metrics.output.metrics.missing_keys_total
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:      components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+}
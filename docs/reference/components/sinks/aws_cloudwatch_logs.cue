@@ -104,6 +104,37 @@ components: sinks: aws_cloudwatch_logs: components._aws & {
 				syntax: "template"
 			}
 		}
+		group_tags: {
+			common:      false
+			description: "The tags added to log groups created by this sink, see [`create_missing_group`](#create_missing_group)."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [{"Team": "observability"}]
+				options: {}
+			}
+		}
+		kms_key: {
+			common:      false
+			description: "The ARN of the KMS key used to encrypt the log data of log groups created by this sink, see [`create_missing_group`](#create_missing_group)."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["arn:aws:kms:us-east-1:123456789012:key/1234abcd-12ab-34cd-56ef-1234567890ab"]
+			}
+		}
+		retention_days: {
+			common:      false
+			description: "The number of days log events are kept in log groups created by this sink. Must be one of 1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1827 or 3653. Existing log groups keep their retention policy."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [30]
+				unit: null
+			}
+		}
 		stream_name: {
 			description: "The [stream name](\(urls.aws_cloudwatch_logs_stream_name)) of the target CloudWatch Logs stream."
 			required:    true
@@ -129,6 +160,10 @@ components: sinks: aws_cloudwatch_logs: components._aws & {
 					_action:       "CreateLogGroup"
 					required_when: "[`create_missing_group`](#create_missing_group) is set to `true`"
 				},
+				{
+					_action:       "PutRetentionPolicy"
+					required_when: "[`retention_days`](#retention_days) is set"
+				},
 				{
 					_action:       "CreateLogStream"
 					required_when: "[`create_missing_stream`](#create_missing_stream) is set to `true`"
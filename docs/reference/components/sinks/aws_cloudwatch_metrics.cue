@@ -83,6 +83,12 @@ components: sinks: aws_cloudwatch_metrics: components._aws & {
 				In Vector only the latter is used to allow lossless statistics
 				calculations on CloudWatch side.
 				""",
+			"""
+				Distributions are sent as values and counts arrays. CloudWatch
+				accepts up to 150 distinct values per metric, so larger
+				distributions are sent as more than one metric, and batches of more
+				than 1000 metrics are sent in more than one request.
+				""",
 		]
 	}
 
@@ -90,13 +96,28 @@ components: sinks: aws_cloudwatch_metrics: components._aws & {
 		default_namespace: {
 			description: """
 				A [namespace](https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/cloudwatch_concepts.html#Namespace) that will isolate different metrics from each other.
-				Used as a namespace for metrics that don't have it.
+				Used as a namespace for metrics that don't have it. Templates can use the `name` and `namespace`
+				of the metric, and its tags as `tags.<name>`.
 				"""
 			required: true
 			warnings: []
 			type: string: {
-				examples: ["service"]
-				syntax: "literal"
+				examples: ["service", "{{ tags.service }}"]
+				syntax: "template"
+			}
+		}
+		dimensions: {
+			common:      false
+			description: """
+				Dimensions added to every metric, in addition to its tags. The values are templates rendered
+				from each metric, and replace the tags with the same name. Dimensions with missing template
+				keys are skipped. CloudWatch accepts up to 10 dimensions per metric.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [{"environment": "production", "host": "{{ tags.host }}"}]
+				options: {}
 			}
 		}
 	}
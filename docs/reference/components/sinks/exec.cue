@@ -0,0 +1,147 @@
+package metadata
+
+components: sinks: exec: {
+	title: "Exec"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      false
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled:     false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		command: {
+			description: "The command to run, followed by its arguments. The command is not run through a shell."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["/usr/local/bin/deliver.sh", "--verbose"]
+				syntax: "literal"
+			}
+		}
+		environment: {
+			common:      false
+			description: "Environment variables to set for the command, in addition to the ones of Vector."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [{"API_TOKEN": "${API_TOKEN}"}]
+				options: {
+					"*": {
+						common:      false
+						description: "The value of the environment variable."
+						required:    false
+						warnings: []
+						type: string: {
+							default: null
+							examples: ["${API_TOKEN}"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+		working_directory: {
+			common:      false
+			description: "The directory to run the command in. Defaults to the working directory of Vector."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["/var/lib/deliver"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		process_lifecycle: {
+			title: "Process Lifecycle"
+			body: """
+				The command is started when the first event arrives and keeps running for
+				as long as the sink does. Every event is encoded on a single line and
+				written to the standard input of the command.
+
+				If the command can't be started, or exits while Vector is writing to it,
+				it is started again after a delay that grows exponentially up to one
+				minute. The event that failed to be written is retried, but events the
+				command read without handling are lost.
+
+				On shutdown, the standard input of the command is closed and Vector waits
+				for the command to exit.
+				"""
+		}
+		backpressure: {
+			title: "Backpressure"
+			body: """
+				Vector waits when the pipe to the command is full, so a command that can't
+				keep up slows down the upstream components instead of losing events.
+				"""
+		}
+		output: {
+			title: "Command Output"
+			body: """
+				Every line the command writes to its standard error is written to the
+				Vector logs at the `info` level. The standard output of the command is
+				discarded.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		command_exited_total:    components.sources.internal_metrics.output.metrics.command_exited_total
+		command_started_total:   components.sources.internal_metrics.output.metrics.command_started_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
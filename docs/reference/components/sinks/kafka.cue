@@ -53,11 +53,50 @@ components: sinks: kafka: {
 
 	configuration: {
 		bootstrap_servers: components._kafka.configuration.bootstrap_servers
+		headers: {
+			common:      false
+			description: "Headers to attach to each message, by header name. Header values are templates, so they can include event fields. Headers whose template references a missing field are left out."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						"source": "vector"
+						"tenant": "{{ tenant_id }}"
+					},
+				]
+				options: {}
+			}
+		}
+		headers_field: {
+			common:      false
+			description: "The log field name holding a map of headers to attach to each message, by header name. Headers set by the `headers` option take precedence."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["headers"]
+				syntax: "literal"
+			}
+		}
+		key: {
+			common:      false
+			description: "Template of the message key, as an alternative to `key_field`. If a field referenced by the template does not exist, a blank value will be used. Can't be used together with `key_field`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ user_id }}", "{{ host }}-{{ service }}"]
+				syntax: "template"
+			}
+		}
 		key_field: {
+			common:      true
 			description: "The log field name or tags key to use for the topic key. If unspecified, the key will be randomly generated. If the field does not exist on the log or in tags, a blank value will be used."
-			required:    true
+			required:    false
 			warnings: []
 			type: string: {
+				default: null
 				examples: ["user_id"]
 				syntax: "literal"
 			}
@@ -76,30 +115,100 @@ components: sinks: kafka: {
 		}
 		sasl: {
 			common:      false
-			description: "Options for SASL/SCRAM authentication support."
+			description: "Options for SASL authentication support."
 			required:    false
 			warnings: []
 			type: object: {
 				examples: []
 				options: {
+					aws_msk_iam: {
+						common:      false
+						description: "Options of the `AWS_MSK_IAM` mechanism, which authenticates with [AWS MSK][urls.aws_msk] using IAM credentials. Vector signs a token with the credentials, and refreshes it before it expires."
+						required:    false
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								auth: components._aws.configuration.auth
+								region: {
+									description: "The AWS region of the MSK cluster."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["us-east-1"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
 					enabled: {
 						common:      true
-						description: "Enable SASL/SCRAM authentication to the remote. (Not supported on Windows at this time.)"
+						description: "Enable SASL authentication to the remote. (Not supported on Windows at this time.)"
 						required:    false
 						warnings: []
 						type: bool: default: null
 					}
 					mechanism: {
 						common:      true
-						description: "The Kafka SASL/SCRAM mechanisms."
+						description: "The Kafka SASL mechanism. Besides the mechanisms of librdkafka, `AWS_MSK_IAM` authenticates with AWS MSK using IAM credentials."
 						required:    false
 						warnings: []
 						type: string: {
 							default: null
-							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512"]
+							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512", "OAUTHBEARER", "AWS_MSK_IAM"]
 							syntax: "literal"
 						}
 					}
+					oauthbearer: {
+						common:      false
+						description: "Options of the `OAUTHBEARER` mechanism. Vector requests tokens from an OAuth 2.0 token endpoint with the client credentials grant, and refreshes them before they expire. Without these options, tokens are left to librdkafka, e.g. with its `sasl.oauthbearer.config` option."
+						required:    false
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								client_id: {
+									description: "The OAuth 2.0 client ID."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["vector"]
+										syntax: "literal"
+									}
+								}
+								client_secret: {
+									description: "The OAuth 2.0 client secret."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["${OAUTH_CLIENT_SECRET}"]
+										syntax: "literal"
+									}
+								}
+								scope: {
+									common:      false
+									description: "The scope to request the token for."
+									required:    false
+									warnings: []
+									type: string: {
+										default: null
+										examples: ["kafka"]
+										syntax: "literal"
+									}
+								}
+								token_endpoint: {
+									description: "The URL of the OAuth 2.0 token endpoint."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["https://auth.example.com/oauth2/token"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
 					password: {
 						common:      true
 						description: "The Kafka SASL/SCRAM authentication password."
@@ -149,5 +258,21 @@ components: sinks: kafka: {
 		}
 	}
 
+	telemetry: metrics: {
+		delivery_errors_total:               components.sources.internal_metrics.output.metrics.delivery_errors_total
+		kafka_broker_connected:              components.sources.internal_metrics.output.metrics.kafka_broker_connected
+		kafka_broker_errors_total:           components.sources.internal_metrics.output.metrics.kafka_broker_errors_total
+		kafka_broker_request_timeouts_total: components.sources.internal_metrics.output.metrics.kafka_broker_request_timeouts_total
+		kafka_produced_messages_bytes_total: components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
+		kafka_produced_messages_total:       components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
+		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
+		kafka_queue_messages_bytes:          components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
+		kafka_requests_bytes_total:          components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
+		kafka_requests_total:                components.sources.internal_metrics.output.metrics.kafka_requests_total
+		kafka_responses_bytes_total:         components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
+		kafka_responses_total:               components.sources.internal_metrics.output.metrics.kafka_responses_total
+		kafka_token_refresh_failures_total:  components.sources.internal_metrics.output.metrics.kafka_token_refresh_failures_total
+	}
+
 	how_it_works: components._kafka.how_it_works
 }
@@ -58,6 +58,13 @@ components: sinks: nats: {
 	}
 
 	configuration: {
+		jetstream: {
+			common:      false
+			description: "Publish messages to [JetStream][urls.nats_jetstream], and wait for them to be stored in the stream of their subject. A stream must capture the subject."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		url: {
 			description: "The NATS URL to connect to. The url _must_ take the form of `nats://server:port`."
 			required:    true
@@ -0,0 +1,170 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs and metrics over the
+		[OpenTelemetry protocol](\(urls.opentelemetry_protocol)) (OTLP) with
+		gRPC, to an [OpenTelemetry collector](\(urls.opentelemetry_collector))
+		or any other OTLP compatible backend.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1000000
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled:                    true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             5
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               30
+				headers:                    false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry protocol"
+							url:   urls.opentelemetry_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The gRPC endpoint of the OTLP receiver. TLS is used for `https` endpoints."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["http://localhost:4317", "https://otlp.example.com:4317"]
+				syntax: "literal"
+			}
+		}
+		resource_attributes: {
+			common:      true
+			description: """
+				Attributes added to the resource of every exported event. The values are templates
+				rendered from each event, and replace the resource attributes with the same name.
+				Attributes with missing template keys are skipped.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [{"service.name": "checkout", "host.name": "{{ host }}"}]
+				options: {}
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		logs: {
+			title: "Log records"
+			body: """
+				The `message` field is exported as the body of the log record, and
+				the `timestamp` field as its time. The `observed_timestamp`,
+				`severity_text`, `severity_number`, `trace_id`, `span_id`, `flags`,
+				`resources` and `scope` fields, as set by the
+				[`opentelemetry` source][docs.sources.opentelemetry], are mapped
+				back onto the record. The `attributes` field and any other field
+				are exported as attributes of the record.
+				"""
+		}
+		metric_types: {
+			title: "Metric type mapping"
+			body: """
+				Counters are exported as monotonic sums, and gauges as gauges.
+				Incremental metrics have a delta aggregation temporality, and
+				absolute ones a cumulative one, except for incremental gauges,
+				which are exported as non-monotonic delta sums. Aggregated
+				histograms and summaries are exported as histograms and
+				summaries, distributions as histograms with a single bucket, and
+				sets as gauges of their size.
+
+				Tags named `resource.<attribute>`, `scope.name` and
+				`scope.version` are mapped back onto the resource and the
+				instrumentation scope, other tags are exported as attributes of
+				the data point. The namespace of a metric is prefixed to its
+				name, separated with a `.`.
+				"""
+		}
+		retries: {
+			title: "Retries"
+			body: """
+				Failed connections, and requests failing with one of the gRPC
+				status codes the OTLP specification lists as retryable, such as
+				`UNAVAILABLE` or `RESOURCE_EXHAUSTED`, are retried with
+				exponential backoff. Other failures are not retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -0,0 +1,63 @@
+package metadata
+
+components: sinks: plugin: {
+	title: "Plugin"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: enabled:    false
+			request: enabled:     false
+			tls: enabled:         false
+		}
+	}
+
+	support: components._plugin.support
+
+	configuration: components._plugin.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: components._plugin.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Every event is sent with an increasing `id`, and the plugin acknowledges
+				all events up to an id at once. Events are removed from the buffer once
+				acknowledged, and events that were not acknowledged are sent again when
+				the stream is reopened. No more than 1024 events wait for their
+				acknowledgement at once.
+				"""
+		}
+		healthcheck: {
+			title: "Health Checks"
+			body: """
+				The health check calls `Health` with the configuration of the component,
+				and fails if the plugin reports that it is unhealthy.
+				"""
+		}
+	}
+
+	telemetry: components._plugin.telemetry
+}
@@ -89,6 +89,17 @@ components: sinks: splunk_hec: {
 	}
 
 	configuration: {
+		channel: {
+			common:      false
+			description: "The channel to send requests on, with the `X-Splunk-Request-Channel` header. Splunk requires a channel for the raw endpoint, and for tokens with indexer acknowledgements enabled. If unset, a random channel is generated when the sink starts."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["FE0ECFAD-13D5-401B-847D-77833BD77131"]
+				syntax: "literal"
+			}
+		}
 		endpoint: {
 			description: "The base URL of the Splunk instance."
 			required:    true
@@ -97,6 +108,20 @@ components: sinks: splunk_hec: {
 				syntax: "literal"
 			}
 		}
+		endpoint_target: {
+			common:      false
+			description: "The HEC endpoint to send events to."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "event"
+				enum: {
+					event: "Send events to the [event endpoint](\(urls.splunk_hec_event_endpoint)), along with their timestamp, host, index, source, sourcetype and `indexed_fields`."
+					raw:   "Send events to the [raw endpoint](\(urls.splunk_hec_raw_endpoint)), one per line, as Splunk-native forwarders do. Requests take their host, index, source and sourcetype in their query, so events are batched per combination of those. The timestamp of events is extracted by Splunk, and `indexed_fields` are ignored."
+				}
+				syntax: "literal"
+			}
+		}
 		host_key: {
 			common:      true
 			description: "The name of the log field to be used as the hostname sent to Splunk HEC. This overrides the [global `host_key` option][docs.reference.configuration.global-options#host_key]."
@@ -115,8 +140,8 @@ components: sinks: splunk_hec: {
 			warnings: []
 			type: string: {
 				default: null
-				examples: ["custom_index"]
-				syntax: "literal"
+				examples: ["custom_index", "{{ splunk_index }}"]
+				syntax: "template"
 			}
 		}
 		indexed_fields: {
@@ -139,8 +164,8 @@ components: sinks: splunk_hec: {
 			warnings: []
 			type: string: {
 				default: null
-				examples: ["/var/log/syslog", "UDP:514"]
-				syntax: "literal"
+				examples: ["/var/log/syslog", "UDP:514", "{{ splunk_source }}"]
+				syntax: "template"
 			}
 		}
 		sourcetype: {
@@ -150,8 +175,8 @@ components: sinks: splunk_hec: {
 			warnings: []
 			type: string: {
 				default: null
-				examples: ["_json", "httpevent"]
-				syntax: "literal"
+				examples: ["_json", "httpevent", "{{ splunk_sourcetype }}"]
+				syntax: "template"
 			}
 		}
 		token: {
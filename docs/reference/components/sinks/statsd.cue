@@ -64,6 +64,25 @@ components: sinks: statsd: {
 				syntax: "literal"
 			}
 		}
+		normalize_counters: {
+			common: false
+			description: """
+				The kind counters are converted to before being sent. StatsD servers expect
+				increments, so set it to `incremental` when receiving absolute counters, for example
+				scraped from Prometheus. These are then converted to the increment since their
+				previous value, so the first value of every series is dropped.
+				"""
+			required: false
+			warnings: []
+			type: string: {
+				default: "none"
+				enum: {
+					incremental: "Absolute counters are converted to increments, as expected by StatsD servers."
+					absolute:    "Incremental counters are summed into the total of their series."
+					none:        "Counters are sent as they are received."
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -4,6 +4,46 @@ components: sources: [Name=string]: {
 	kind: "source"
 
 	configuration: {
+		clock_skew: {
+			common:      false
+			description: "Corrects or flags events whose timestamp is too far in the future or in the past of the time Vector received them, as sent by agents with skewed clocks. If not specified, timestamps are left as they are."
+			required:    false
+			type: object: options: {
+				action: {
+					common:      true
+					description: "What to do with the events whose timestamp is out of the tolerance."
+					required:    false
+					type: string: {
+						default: "correct"
+						enum: {
+							correct: "Replace the timestamp with the receive time. The original timestamp of logs is kept in the `vector.original_timestamp` metadata field."
+							flag:    "Keep the timestamp, and set the `flag_key` field of logs, or tag of metrics, to `true`."
+						}
+						syntax: "literal"
+					}
+				}
+				flag_key: {
+					common:        false
+					description:   "The field of logs, or the tag of metrics, set to `true` by the `flag` action."
+					relevant_when: "action = \"flag\""
+					required:      false
+					type: string: {
+						default: "clock_skewed"
+						syntax:  "literal"
+					}
+				}
+				tolerance_secs: {
+					common:      true
+					description: "How far a timestamp may be in the future or in the past of the receive time."
+					required:    false
+					type: uint: {
+						default: 300
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+
 		if sources[Name].features.collect != _|_ {
 			if sources[Name].features.collect.checkpoint.enabled {
 				data_dir: {
@@ -135,6 +175,24 @@ components: sources: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							interval_secs: {
+								common:      false
+								description: "The time between TCP keepalive probes, once the connection has been idle for `time_secs`. Only supported on Linux."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    "seconds"
+								}
+							}
+							retries: {
+								common:      false
+								description: "The number of unanswered TCP keepalive probes after which the connection is considered broken. Only supported on Linux."
+								required:    false
+								type: uint: {
+									default: null
+									unit:    null
+								}
+							}
 						}
 					}
 				}
@@ -205,6 +263,20 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		clock_skew: {
+			title: "Clock skew"
+			body:  """
+				Vector records the time it received each event, which remap programs
+				can read with `get_metadata_field("vector.ingest_timestamp")`. Events
+				timestamped by agents with skewed clocks can land in time partitions
+				far away from the others, such as the daily indexes or object
+				prefixes of sinks. The `clock_skew` option compares the timestamp of
+				each event with its receive time, and corrects or flags the events
+				out of `tolerance_secs`, counting them in the
+				`clock_skewed_events_total` internal metric.
+				"""
+		}
+
 		context: {
 			title: "Context"
 			body:  """
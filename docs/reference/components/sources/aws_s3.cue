@@ -102,6 +102,13 @@ components: sources: aws_s3: components._aws & {
 							unit:    "seconds"
 						}
 					}
+					acknowledgements: {
+						common:      false
+						description: "Only delete a message once all of its events are delivered by the sinks, for at-least-once delivery through the whole topology. Otherwise, messages are deleted as soon as their objects are read. Messages whose events aren't delivered reappear in the queue once their visibility timeout expires."
+						required:    false
+						warnings: []
+						type: bool: default: false
+					}
 					delete_message: {
 						common:      true
 						description: "Whether to delete the message once Vector processes it. It can be useful to set this to `false` to debug or during initial Vector setup."
@@ -276,6 +283,7 @@ components: sources: aws_s3: components._aws & {
 	telemetry: metrics: {
 		sqs_message_delete_failed_total:        components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
 		sqs_message_delete_succeeded_total:     components.sources.internal_metrics.output.metrics.sqs_message_delete_succeeded_total
+		sqs_message_delivery_failed_total:      components.sources.internal_metrics.output.metrics.sqs_message_delivery_failed_total
 		sqs_message_processing_failed_total:    components.sources.internal_metrics.output.metrics.sqs_message_processing_failed_total
 		sqs_message_processing_succeeded_total: components.sources.internal_metrics.output.metrics.sqs_message_processing_succeeded_total
 		sqs_message_receive_failed_total:       components.sources.internal_metrics.output.metrics.sqs_message_receive_failed_total
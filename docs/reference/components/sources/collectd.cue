@@ -0,0 +1,122 @@
+package metadata
+
+components: sources: collectd: {
+	_port: 25826
+
+	title: "collectd"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.collectd
+				interface: socket: {
+					api: {
+						title: "collectd"
+						url:   urls.collectd_binary_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled:       true
+				relevant_when: "os = `unix`"
+			}
+			keepalive: enabled: false
+			tls: enabled:       false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: ["Encrypted packets aren't supported, and the signatures of signed packets aren't verified."]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The UDP address to listen for packets of collectd's `network` plugin on."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+				syntax: "literal"
+			}
+		}
+		typesdb: {
+			common:      true
+			description: "The [`types.db`][urls.collectd_types_db] files listing the data sources of collectd's types, to name the metrics of types with several values."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/usr/share/collectd/types.db"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
+	output: metrics: {
+		counter: output._passthrough_counter
+		gauge:   output._passthrough_gauge
+	}
+
+	how_it_works: {
+		metric_names: {
+			title: "Metric names and tags"
+			body: """
+				Metrics are named `<plugin>_<type>_<data source>`, leaving out
+				the type when it's the same as the plugin, and the data source
+				when the type only has one value. Data sources are named after
+				the `typesdb` files, or numbered when their type isn't listed in
+				them. The `host`, `plugin_instance` and `type_instance` of the
+				values are added as tags.
+				"""
+		}
+		metric_types: {
+			title: "Metric types"
+			body: """
+				`GAUGE` values are received as gauges, `COUNTER` and `DERIVE`
+				values as absolute counters, and `ABSOLUTE` values as
+				incremental counters. Unknown gauge values, sent as `NaN`, are
+				dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total:    components.sources.internal_metrics.output.metrics.connection_errors_total
+		invalid_record_total:       components.sources.internal_metrics.output.metrics.invalid_record_total
+		invalid_record_bytes_total: components.sources.internal_metrics.output.metrics.invalid_record_bytes_total
+		processed_bytes_total:      components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:     components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -0,0 +1,318 @@
+package metadata
+
+components: sources: dns_query_log: {
+	_directory: "/var/log"
+
+	title: "DNS Query Log"
+
+	description: """
+		Tails the query logs of DNS resolvers and parses every query and response
+		into a structured event. This is intended for resolvers that can't emit
+		dnstap, like dnsmasq, unbound and BIND.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.files
+
+				interface: file_system: {
+					directory: _directory
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: components.sources.file.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		exclude:               components.sources.file.configuration.exclude
+		format: {
+			description: "The format of the query log."
+			required:    true
+			warnings: []
+			type: string: {
+				enum: {
+					bind:    "The `queries` logging category of BIND."
+					dnsmasq: "The output of dnsmasq with `log-queries` enabled, with or without `log-queries=extra`."
+					unbound: "The output of unbound with `log-queries` or `log-replies` enabled."
+				}
+				syntax: "literal"
+			}
+		}
+		glob_minimum_cooldown: components.sources.file.configuration.glob_minimum_cooldown
+		include: {
+			description: "Array of file patterns of the query logs to tail. [Globbing](#globbing) is supported."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["\(_directory)/dnsmasq.log", "\(_directory)/named/queries.log*"]
+				syntax: "literal"
+			}
+		}
+		read_from: components.sources.file.configuration.read_from
+	}
+
+	output: logs: {
+		query: {
+			description: "A query received by the resolver."
+			fields: {
+				client: {
+					description: "The address of the client sending the query."
+					required:    true
+					type: string: {
+						examples: ["192.168.1.10"]
+						syntax: "literal"
+					}
+				}
+				client_port: {
+					description: "The port of the client, logged by BIND and by dnsmasq with `log-queries=extra`."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [52311]
+						unit: null
+					}
+				}
+				file:    components.sources.file.output.logs.line.fields.file
+				flags: {
+					description: "The flags of the query, as logged by BIND."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["+E(0)K"]
+						syntax: "literal"
+					}
+				}
+				host:    fields._local_host
+				message: {
+					description: "The raw line of the query log."
+					required:    true
+					type: string: {
+						examples: ["Jan 12 10:00:01 dnsmasq[1234]: query[A] example.com from 192.168.1.10"]
+						syntax: "literal"
+					}
+				}
+				name: {
+					description: "The queried name, without the trailing dot."
+					required:    true
+					type: string: {
+						examples: ["example.com"]
+						syntax: "literal"
+					}
+				}
+				query_class: {
+					description: "The class of the query, logged by BIND and unbound."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["IN"]
+						syntax: "literal"
+					}
+				}
+				query_type: {
+					description: "The type of the queried record."
+					required:    true
+					type: string: {
+						examples: ["A", "AAAA", "MX"]
+						syntax: "literal"
+					}
+				}
+				server: {
+					description: "The address the query was received on, as logged by BIND."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["192.168.1.1"]
+						syntax: "literal"
+					}
+				}
+				timestamp: {
+					description: "The time of the query, when logged by the resolver, or the time the line was read otherwise."
+					required:    true
+					type: timestamp: {}
+				}
+				type: {
+					description: "The kind of the line."
+					required:    true
+					type: string: {
+						enum: {
+							query: "A query received by the resolver."
+						}
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		response: {
+			description: "An answer of the resolver, or a query forwarded upstream by dnsmasq."
+			fields: {
+				answer: {
+					description: "The answer of dnsmasq."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["93.184.216.34", "NXDOMAIN"]
+						syntax: "literal"
+					}
+				}
+				cached: {
+					description: "Whether unbound answered from its cache."
+					required:    false
+					common:      true
+					type: bool: default: null
+				}
+				duration_seconds: {
+					description: "The time unbound took to answer."
+					required:    false
+					common:      true
+					type: float: {
+						default: null
+						examples: [0.012503]
+					}
+				}
+				origin: {
+					description: "Where dnsmasq got the answer from: `reply` for upstream servers, `cached`, `config`, or the path of a hosts file."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["reply", "cached", "/etc/hosts"]
+						syntax: "literal"
+					}
+				}
+				rcode: {
+					description: "The response code of unbound."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["NOERROR", "NXDOMAIN"]
+						syntax: "literal"
+					}
+				}
+				response_size: {
+					description: "The size of the response of unbound, in bytes."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						examples: [45]
+						unit: "bytes"
+					}
+				}
+				type: {
+					description: "The kind of the line."
+					required:    true
+					type: string: {
+						enum: {
+							forward:  "A query forwarded upstream by dnsmasq, with the `server` it was forwarded to."
+							response: "An answer of the resolver."
+						}
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
+	examples: [
+		{
+			_file: "\(_directory)/dnsmasq.log"
+			_line: "Jan 12 10:00:01 dnsmasq[1234]: query[A] example.com from 192.168.1.10"
+			title: "dnsmasq query"
+
+			configuration: {
+				include: [_file]
+				format: "dnsmasq"
+			}
+			input: """
+				```text filename="\(_file)"
+				\(_line)
+				```
+				"""
+			output: log: {
+				client:     "192.168.1.10"
+				file:       _file
+				host:       _values.local_host
+				message:    _line
+				name:       "example.com"
+				query_type: "A"
+				timestamp:  _values.current_timestamp
+				type:       "query"
+			}
+		},
+		{
+			_file: "\(_directory)/unbound.log"
+			_line: "[1610445600] unbound[1234:0] reply: 192.168.1.10 example.com. A IN NOERROR 0.012503 0 45"
+			title: "unbound reply"
+
+			configuration: {
+				include: [_file]
+				format: "unbound"
+			}
+			input: """
+				```text filename="\(_file)"
+				\(_line)
+				```
+				"""
+			output: log: {
+				cached:           false
+				client:           "192.168.1.10"
+				duration_seconds: 0.012503
+				file:             _file
+				host:             _values.local_host
+				message:          _line
+				name:             "example.com"
+				query_class:      "IN"
+				query_type:       "A"
+				rcode:            "NOERROR"
+				response_size:    45
+				timestamp:        "2021-01-12T10:00:00Z"
+				type:             "response"
+			}
+		},
+	]
+
+	how_it_works: {
+		other_lines: {
+			title: "Other Lines"
+			body: """
+				Resolvers log more than queries, like their startup banner or DHCP
+				leases for dnsmasq. Lines that are neither a query nor a response are
+				skipped.
+				"""
+		}
+		timestamps: {
+			title: "Timestamps"
+			body: """
+				The timestamps of unbound are Unix timestamps, and the ones of BIND are
+				interpreted in the local time zone of Vector. Lines without a timestamp
+				the source can read, like the syslog output of dnsmasq, are timestamped
+				when they are read.
+				"""
+		}
+	}
+
+	telemetry: components.sources.file.telemetry
+}
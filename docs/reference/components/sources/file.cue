@@ -50,6 +50,13 @@ components: sources: file: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only move the checkpoint of a file past a line once its event is delivered by the sinks, for at-least-once delivery through the whole topology. The checkpoint of a file never moves past a line whose event wasn't delivered, which is read again once Vector restarts. Otherwise, checkpoints move past lines as soon as they're read. Can't be used along with `multiline` or `message_start_indicator`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
@@ -547,20 +554,24 @@ components: sources: file: {
 				behavior, you can set the `ignore_checkpoints` option to `true`.  This
 				will cause Vector to disregard existing checkpoints when determining the
 				starting read position of a file.
+
+				With the `acknowledgements` option set, the checkpoint of a file only
+				moves past the lines whose events were delivered by the sinks.
 				"""
 		}
 	}
 
 	telemetry: metrics: {
-		checkpoint_write_errors_total: components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
-		checkpoints_total:             components.sources.internal_metrics.output.metrics.checkpoints_total
-		checksum_errors_total:         components.sources.internal_metrics.output.metrics.checksum_errors_total
-		file_delete_errors_total:      components.sources.internal_metrics.output.metrics.file_delete_errors_total
-		file_watch_errors_total:       components.sources.internal_metrics.output.metrics.file_watch_errors_total
-		files_added_total:             components.sources.internal_metrics.output.metrics.files_added_total
-		files_deleted_total:           components.sources.internal_metrics.output.metrics.files_deleted_total
-		files_resumed_total:           components.sources.internal_metrics.output.metrics.files_resumed_total
-		files_unwatched_total:         components.sources.internal_metrics.output.metrics.files_unwatched_total
-		fingerprint_read_errors_total: components.sources.internal_metrics.output.metrics.fingerprint_read_errors_total
+		checkpoint_updates_skipped_total: components.sources.internal_metrics.output.metrics.checkpoint_updates_skipped_total
+		checkpoint_write_errors_total:    components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
+		checkpoints_total:                components.sources.internal_metrics.output.metrics.checkpoints_total
+		checksum_errors_total:            components.sources.internal_metrics.output.metrics.checksum_errors_total
+		file_delete_errors_total:         components.sources.internal_metrics.output.metrics.file_delete_errors_total
+		file_watch_errors_total:          components.sources.internal_metrics.output.metrics.file_watch_errors_total
+		files_added_total:                components.sources.internal_metrics.output.metrics.files_added_total
+		files_deleted_total:              components.sources.internal_metrics.output.metrics.files_deleted_total
+		files_resumed_total:              components.sources.internal_metrics.output.metrics.files_resumed_total
+		files_unwatched_total:            components.sources.internal_metrics.output.metrics.files_unwatched_total
+		fingerprint_read_errors_total:    components.sources.internal_metrics.output.metrics.fingerprint_read_errors_total
 	}
 }
@@ -0,0 +1,168 @@
+package metadata
+
+components: sources: fluent: {
+	_port: 24224
+
+	title: "Fluent"
+
+	description: """
+		Receives data from Fluentd or Fluent Bit instances using the
+		[Fluentd forward protocol](\(urls.fluentd_forward_protocol)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.fluent
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The TCP address to listen for connections on, or `systemd#N to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#1"]
+				syntax: "literal"
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum bytes size of a message, and of the entries of a compressed message once decompressed. Connections sending longer messages are closed."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 16777216
+				unit:    "bytes"
+			}
+		}
+		self_hostname: {
+			common:      false
+			description: "The hostname Vector introduces itself with in the handshake. If unspecified, the hostname of the machine is used."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector-aggregator"]
+				syntax: "literal"
+			}
+		}
+		shared_key: {
+			common:      true
+			description: "The key clients must authenticate with in the handshake. If unspecified, clients aren't authenticated."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["${FLUENT_SHARED_KEY_ENV_VAR}", "secret"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An individual record of a forwarded message."
+		fields: {
+			host: fields._local_host
+			tag: {
+				description: "The tag of the message the record was forwarded in."
+				required:    true
+				type: string: {
+					examples: ["dummy.0"]
+					syntax: "literal"
+				}
+			}
+			timestamp: {
+				description: "The time of the record, as set by the client."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the record."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		forward_modes: {
+			title: "Forward Modes"
+			body: """
+				All the modes of the forward protocol are supported: Message,
+				Forward, PackedForward and CompressedPackedForward, with gzip
+				compressed entries.
+				"""
+		}
+		message_acknowledgement: {
+			title: "Message Acknowledgement"
+			body: """
+				Messages sent with a `chunk` option are acknowledged once their
+				records are accepted by Vector, which is enabled by the
+				`require_ack_response` option of Fluentd and Fluent Bit.
+				"""
+		}
+		handshake: {
+			title: "Handshake"
+			body: """
+				When `shared_key` is set, clients must authenticate through the
+				handshake of the forward protocol before sending messages, and
+				are disconnected if they use the wrong key. Authentication with
+				usernames and passwords isn't supported.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
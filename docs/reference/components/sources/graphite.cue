@@ -0,0 +1,147 @@
+package metadata
+
+components: sources: graphite: {
+	_port: 2003
+
+	title: "Graphite"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.graphite
+				interface: socket: {
+					api: {
+						title: "Graphite"
+						url:   urls.graphite_plaintext_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp", "udp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp` && os = `unix`"
+			}
+			keepalive: enabled: true
+			tls: enabled:       true
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			relevant_when: "mode = `tcp` or `udp`"
+			required:      true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "0.0.0.0:2004", "systemd", "systemd#3"]
+				syntax: "literal"
+			}
+		}
+		mode: {
+			description: "The type of socket to use."
+			required:    true
+			warnings: []
+			type: string: {
+				enum: {
+					tcp: "TCP Socket."
+					udp: "UDP Socket, for the plaintext protocol only."
+				}
+				syntax: "literal"
+			}
+		}
+		protocol: {
+			common:        true
+			description:   "The protocol of the metrics received, as accepted by carbon's receivers."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			warnings: []
+			type: string: {
+				default: "plaintext"
+				enum: {
+					plaintext: "Lines of `<path> <value> <timestamp>`, as received by carbon on port 2003."
+					pickle:    "Pickled lists of `(path, (timestamp, value))` datapoints, each prefixed with its length, as received by carbon on port 2004."
+				}
+				syntax: "literal"
+			}
+		}
+		shutdown_timeout_secs: {
+			common:        false
+			description:   "The timeout before a connection is forcefully closed during shutdown."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			warnings: []
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: metrics: {
+		gauge: output._passthrough_gauge
+	}
+
+	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body: """
+				Graphite doesn't have metric types, so each datapoint is received
+				as a gauge named after its path, like `servers.web-1.cpu.load`.
+				Metric paths in the [tagged series][urls.graphite_tags] format,
+				like `cpu.load;host=web-1;region=us-east-1`, are received with
+				their tags.
+				"""
+		}
+		timestamps: {
+			title: "Timestamps"
+			body: """
+				Metrics keep the timestamp they're sent with. Negative
+				timestamps, like the `-1` some clients send to stand for the
+				current time, are received as `null`, which downstream sinks
+				substitute with the current time like for other real time
+				metrics.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total:    components.sources.internal_metrics.output.metrics.connection_errors_total
+		invalid_record_total:       components.sources.internal_metrics.output.metrics.invalid_record_total
+		invalid_record_bytes_total: components.sources.internal_metrics.output.metrics.invalid_record_bytes_total
+		processed_bytes_total:      components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:     components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -69,7 +69,42 @@ components: sources: heroku_logs: {
 
 	configuration: {
 		address:          sources.http.configuration.address
-		auth:             sources.http.configuration.auth
+		auth: sources.http.configuration.auth
+		drain_tokens: {
+			common:      false
+			description: "The drain tokens of the Logplex drains to accept requests from. Requests with any other `Logplex-Drain-Token` header are rejected with a `401` status. If empty, requests from any drain are accepted."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["d.fc6a4a4b-8a7b-4bd5-9a5a-5b3e1d2f0f6c"]
+					syntax: "literal"
+				}
+			}
+		}
+		drain_token_key: {
+			category:    "Context"
+			common:      false
+			description: "The key name added to each event for the token of the drain the event was sent by."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "drain_token"
+				syntax:  "literal"
+			}
+		}
+		frame_id_key: {
+			category:    "Context"
+			common:      false
+			description: "The key name added to each event for the ID of the frame the event was sent in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "frame_id"
+				syntax:  "literal"
+			}
+		}
 		query_parameters: sources.http.configuration.query_parameters
 	}
 
@@ -84,6 +119,22 @@ components: sources: heroku_logs: {
 					syntax: "literal"
 				}
 			}
+			drain_token: {
+				description: "The token of the drain the message was sent by, from the `Logplex-Drain-Token` header."
+				required:    true
+				type: string: {
+					examples: ["d.fc6a4a4b-8a7b-4bd5-9a5a-5b3e1d2f0f6c"]
+					syntax: "literal"
+				}
+			}
+			frame_id: {
+				description: "The ID of the frame the message was sent in, from the `Logplex-Frame-Id` header."
+				required:    true
+				type: string: {
+					examples: ["09C557EAFCFB6CF2740EE62F62971098"]
+					syntax: "literal"
+				}
+			}
 			host: fields._local_host
 			message: {
 				description: "The message field, containing the plain text message."
@@ -78,6 +78,7 @@ components: sources: http: {
 					text:   "Newline-delimited text, with each line forming a message."
 					ndjson: "Newline-delimited JSON objects, where each line must contain a JSON object."
 					json:   "Array of JSON objects, which must be a JSON array containing JSON objects."
+					binary: "The whole body, with each request forming a single message."
 				}
 				syntax: "literal"
 			}
@@ -95,6 +96,46 @@ components: sources: http: {
 			}
 		}
 		auth: configuration._http_basic_auth
+		methods: {
+			common:      false
+			description: "The HTTP methods to accept requests with. Requests with other methods are rejected with `405 Method Not Allowed`."
+			required:    false
+			type: array: {
+				default: ["POST"]
+				items: type: string: {
+					enum: {
+						GET:     "The `GET` method."
+						HEAD:    "The `HEAD` method."
+						POST:    "The `POST` method."
+						PUT:     "The `PUT` method."
+						PATCH:   "The `PATCH` method."
+						DELETE:  "The `DELETE` method."
+						OPTIONS: "The `OPTIONS` method."
+					}
+					syntax: "literal"
+				}
+			}
+		}
+		path: {
+			common:      false
+			description: "The URL path to accept requests on. Requests to other paths are rejected with `404 Not Found`."
+			required:    false
+			type: string: {
+				default: "/"
+				examples: ["/event/path", "/logs"]
+				syntax: "literal"
+			}
+		}
+		path_key: {
+			common:      false
+			description: "The log field name to use for the path the request was sent to. If unspecified, the path is discarded."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector_http_path"]
+				syntax: "literal"
+			}
+		}
 		query_parameters: {
 			common:      false
 			description: "A list of URL query parameters to include in the log event. These will override any values included in the body with conflicting names."
@@ -107,6 +148,12 @@ components: sources: http: {
 				}
 			}
 		}
+		strict_path: {
+			common:      false
+			description: "Whether requests must be sent to `path` exactly. If false, requests to any of its subpaths are accepted too, such as `/webhooks/github` for a `path` of `/webhooks`; set `path_key` to tell them apart."
+			required:    false
+			type: bool: default: true
+		}
 	}
 
 	output: logs: {
@@ -208,8 +255,13 @@ components: sources: http: {
 		decompression: {
 			title: "Decompression"
 			body: """
-				Received body is decompressed according to `Content-Encoding` header.
-				Supported algorithms are `gzip`, `deflate`, and `snappy`.
+				Received body is decompressed according to `Content-Encoding` header, before it's decoded.
+				Supported algorithms are `gzip` (or `x-gzip`), `deflate`, `snappy`, and `zstd`. Bodies
+				compressed more than once list their algorithms in the order they were applied, such as
+				`Content-Encoding: gzip, deflate`. Requests with other algorithms are rejected with
+				`415 Unsupported Media Type`, bodies that fail to decompress with
+				`422 Unprocessable Entity`, and bodies that decompress to more than 100 MiB with
+				`413 Payload Too Large`.
 				"""
 		}
 	}
@@ -74,6 +74,34 @@ components: sources: internal_logs: {
 				type: object: {
 					examples: []
 					options: {
+						component_kind: {
+							description: "The kind of the component that emitted the log or trace, if any."
+							required:    false
+							type: string: {
+								enum: {
+									source:    "The log or trace was emitted by a source."
+									transform: "The log or trace was emitted by a transform."
+									sink:      "The log or trace was emitted by a sink."
+								}
+								syntax: "literal"
+							}
+						}
+						component_name: {
+							description: "The name of the component that emitted the log or trace, if any."
+							required:    false
+							type: string: {
+								examples: ["my_source", "my_sink"]
+								syntax: "literal"
+							}
+						}
+						component_type: {
+							description: "The type of the component that emitted the log or trace, if any."
+							required:    false
+							type: string: {
+								examples: ["file", "http"]
+								syntax: "literal"
+							}
+						}
 						kind: {
 							description: "What kind of call site caused this log or trace."
 							required:    true
@@ -122,6 +150,15 @@ components: sources: internal_logs: {
 	}
 
 	how_it_works: {
+		component_metadata: {
+			title: "Component metadata"
+			body: """
+				    Logs emitted while a component is running, such as the
+				    errors and warnings of a sink, carry the kind, name and
+				    type of that component under `metadata`, so that they can
+				    be routed and filtered by component like any other logs.
+				"""
+		}
 		limited_logs: {
 			title: "Logs are limited by startup options"
 			body: """
@@ -45,6 +45,55 @@ components: sources: internal_metrics: {
 		platform_name: null
 	}
 
+	configuration: {
+		scrape_interval_secs: {
+			description: "The interval between metric gathering, in seconds."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 2
+				unit:    "seconds"
+			}
+		}
+		tags: {
+			description: "Tags added to every internal metric, so that the metrics of many Vector instances can be told apart. Each tag is only added if its key is set, and doesn't replace a tag of the same key the metric already has."
+			common:      false
+			required:    false
+			type: object: options: {
+				host_key: {
+					description: "The key of the tag holding the hostname of the Vector instance."
+					common:      true
+					required:    false
+					type: string: {
+						default: null
+						examples: ["host"]
+						syntax: "literal"
+					}
+				}
+				pod_name_key: {
+					description: "The key of the tag holding the name of the Kubernetes pod Vector runs in, as read from the `VECTOR_SELF_POD_NAME` environment variable. Left out if the variable isn't set."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["pod_name"]
+						syntax: "literal"
+					}
+				}
+				version_key: {
+					description: "The key of the tag holding the version of Vector."
+					common:      false
+					required:    false
+					type: string: {
+						default: null
+						examples: ["version"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
 	output: metrics: {
 		// Default internal metrics tags
 		_internal_metrics_tags: {
@@ -61,6 +110,38 @@ components: sources: internal_metrics: {
 		}
 
 		// Instance-level "process" metrics
+		allocator_active_bytes: {
+			description:       "The number of bytes in the memory pages the allocator holds allocations of Vector in."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				allocator: _allocator
+			}
+		}
+		allocator_allocated_bytes: {
+			description:       "The number of bytes allocated by Vector. Only reported by the `jemalloc` allocator."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				allocator: _allocator
+			}
+		}
+		allocator_fragmentation_ratio: {
+			description:       "The ratio of `allocator_active_bytes` to `allocator_allocated_bytes`, which grows as the memory held by the allocator gets fragmented. Only reported by the `jemalloc` allocator."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				allocator: _allocator
+			}
+		}
+		allocator_resident_bytes: {
+			description:       "The number of bytes of physical memory the allocator holds."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				allocator: _allocator
+			}
+		}
 		api_started_total: {
 			description:       "The number of times the Vector GraphQL API has been started."
 			type:              "counter"
@@ -77,7 +158,9 @@ components: sources: internal_metrics: {
 			description:       "The total number of connection errors for this Vector instance."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		connection_established_total: {
 			description:       "The total number of times a connection has been established."
@@ -95,7 +178,9 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors sending data via the connection."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		connection_shutdown_total: {
 			description:       "The total number of times the connection has been shut down."
@@ -166,11 +251,90 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		batch_size_events: {
+			description:       "The number of events in each batch a sink sends in a request."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_byte_size: {
+			description:       "The number of bytes of the events in the disk buffer of this component, that haven't been acknowledged yet."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		buffer_corruptions_total: {
+			description:       "The total number of corruptions found in the data files of disk buffers, each skipping the rest of a data file."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		buffer_discarded_events_total: {
+			description:       "The total number of events dropped because the buffer of this component was full."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		buffer_disk_usage_bytes: {
+			description:       "The number of bytes the disk buffer of this component uses on disk, including events awaiting compaction."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		buffer_events: {
+			description:       "The number of events in the buffer of this component, that the component hasn't read yet."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		buffer_io_errors_total: {
+			description:       "The total number of failures to read or write the files of disk buffers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+				stage: _stage
+			}
+		}
+		buffer_max_byte_size: {
+			description:       "The number of bytes of events the disk buffer of this component holds at most."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		buffer_max_event_size: {
+			description:       "The number of events the memory buffer of this component holds at most."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				buffer_type: _buffer_type
+			}
+		}
+		checkpoint_updates_skipped_total: {
+			description:       "The total number of file checkpoints not updated because their events weren't delivered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		checkpoints_total: {
 			description:       "The total number of files checkpointed."
@@ -184,6 +348,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags & {
 				file: _file
+				stage: _stage
+			}
+		}
+		clock_skewed_events_total: {
+			description:       "The total number of events whose timestamp was out of the `clock_skew` tolerance of this source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				action: {
+					description: "What was done with the events."
+					required:    true
+					enum: {
+						"correct": "The timestamp was replaced with the receive time."
+						"flag":    "The event was flagged."
+					}
+				}
+				direction: {
+					description: "Whether the timestamp was ahead or behind the receive time."
+					required:    true
+					enum: {
+						"future": "The timestamp was ahead of the receive time."
+						"past":   "The timestamp was behind the receive time."
+					}
+				}
 			}
 		}
 		collect_completed_total: {
@@ -198,10 +386,36 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		command_exited_total: {
+			description:       "The total number of times a command run by the `exec` sink has exited."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		command_started_total: {
+			description:       "The total number of times a command run by the `exec` sink has been started."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		communication_errors_total: {
 			description:       "The total number of errors stemming from communication with the Docker daemon."
 			type:              "counter"
 			default_namespace: "vector"
+			tags:              _component_tags & {
+				stage: _stage
+			}
+		}
+		component_failed: {
+			description:       "Whether the current task of this component failed, `1` if it did and `0` otherwise."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		component_failures_total: {
+			description:       "The number of times the task of this component failed, such as a source that couldn't bind its address."
+			type:              "counter"
+			default_namespace: "vector"
 			tags:              _component_tags
 		}
 		connection_read_errors_total: {
@@ -216,14 +430,33 @@ components: sources: internal_metrics: {
 						udp: "User Datagram Protocol"
 					}
 				}
+				stage: _stage
 			}
 		}
+		consumer_acknowledgements_failed_total: {
+			description:       "The total number of failures to acknowledge a Pulsar message."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		consumer_negative_acknowledgements_total: {
+			description:       "The total number of Pulsar messages negatively acknowledged, because their events weren't delivered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		consumer_offset_updates_failed_total: {
 			description:       "The total number of failures to update a Kafka consumer offset."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		consumer_offset_updates_skipped_total: {
+			description:       "The total number of Kafka consumer offsets not updated because their events weren't delivered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		container_processed_events_total: {
 			description:       "The total number of container events processed."
 			type:              "counter"
@@ -234,7 +467,9 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors encountered when fetching container metadata."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				stage: _stage
+			}
 		}
 		containers_unwatched_total: {
 			description:       "The total number of times Vector stopped watching for container logs."
@@ -248,6 +483,129 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		kafka_broker_connected: {
+			description:       "Whether the connection to a Kafka broker is up (1) or not (0)."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				broker: {
+					description: "The Kafka broker, as named by librdkafka."
+					required:    true
+				}
+			}
+		}
+		kafka_broker_errors_total: {
+			description:       "The total number of transmit and receive errors with a Kafka broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				broker: {
+					description: "The Kafka broker, as named by librdkafka."
+					required:    true
+				}
+				error_type: {
+					description: "The direction of the failed request."
+					required:    true
+					enum: {
+						receive:  "Receiving a response failed."
+						transmit: "Transmitting a request failed."
+					}
+				}
+				stage: _stage
+			}
+		}
+		kafka_broker_request_timeouts_total: {
+			description:       "The total number of requests to a Kafka broker that timed out."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				broker: {
+					description: "The Kafka broker, as named by librdkafka."
+					required:    true
+				}
+			}
+		}
+		kafka_consumed_messages_bytes_total: {
+			description:       "The total number of message bytes consumed from Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_consumed_messages_total: {
+			description:       "The total number of messages consumed from Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_consumer_lag: {
+			description:       "The number of messages a Kafka consumer is behind, per topic partition."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				topic: {
+					description: "The Kafka topic."
+					required:    true
+				}
+				partition: {
+					description: "The partition of the topic."
+					required:    true
+				}
+			}
+		}
+		kafka_produced_messages_bytes_total: {
+			description:       "The total number of message bytes produced to Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_produced_messages_total: {
+			description:       "The total number of messages produced to Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_queue_messages: {
+			description:       "The number of messages waiting in the queue of a Kafka client."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_queue_messages_bytes: {
+			description:       "The size of the messages waiting in the queue of a Kafka client."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_requests_bytes_total: {
+			description:       "The total number of bytes sent to Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_requests_total: {
+			description:       "The total number of requests sent to Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_responses_bytes_total: {
+			description:       "The total number of bytes received from Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_responses_total: {
+			description:       "The total number of responses received from Kafka brokers."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		kafka_token_refresh_failures_total: {
+			description:       "The total number of failures to refresh the SASL token of a Kafka client."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		k8s_format_picker_edge_cases_total: {
 			description:       "The total number of edge cases encountered while picking format of the Kubernetes log message."
 			type:              "counter"
@@ -266,17 +624,29 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		delivery_errors_total: {
+			description:       "The total number of messages that couldn't be delivered to Kafka."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				stage: _stage
+			}
+		}
 		encode_errors_total: {
 			description:       "The total number of errors encountered when encoding an event."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		events_discarded_total: {
 			description:       "The total number of events discarded by this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _component_tags & {
+				reason: _reason
+			}
 		}
 		events_failed_total: {
 			description:       "The total number of failures to read a Kafka message."
@@ -298,6 +668,7 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags & {
 				file: _file
+				stage: _stage
 			}
 		}
 		file_watch_errors_total: {
@@ -306,6 +677,7 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags & {
 				file: _file
+				stage: _stage
 			}
 		}
 		files_added_total: {
@@ -346,8 +718,15 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags & {
 				file: _file
+				stage: _stage
 			}
 		}
+		healthcheck_healthy: {
+			description:       "Whether the latest healthcheck of this sink passed (`1`) or failed (`0`)."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		http_bad_requests_total: {
 			description:       "The total number of HTTP `400 Bad Request` errors encountered."
 			type:              "counter"
@@ -364,7 +743,9 @@ components: sources: internal_metrics: {
 			description:       "The total number of HTTP request errors for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		http_requests_total: {
 			description:       "The total number of HTTP requests issued by this component."
@@ -391,7 +772,9 @@ components: sources: internal_metrics: {
 				"""
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				stage: _stage
+			}
 		}
 		memory_used_bytes: {
 			description:       "The total memory currently being used by Vector (in bytes)."
@@ -427,7 +810,9 @@ components: sources: internal_metrics: {
 			description:       "The total number of errors parsing metrics for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		processed_bytes_total: {
 			description:       "The total number of bytes processed by the component."
@@ -441,22 +826,39 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {
 				error_type: _error_type
+				stage:      _stage
 			}
 		}
 		protobuf_decode_errors_total: {
 			description:       "The total number of [Protocol Buffers](\(urls.protobuf)) errors thrown during communication between Vector instances."
 			type:              "counter"
 			default_namespace: "vector"
+			tags:              _component_tags & {
+				stage: _stage
+			}
+		}
+		received_events_total: {
+			description:       "The total number of events this component received, from its origin or from its inputs."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		received_event_bytes_total: {
+			description:       "The total estimated size in bytes of the events this component received."
+			type:              "counter"
+			default_namespace: "vector"
 			tags:              _component_tags
 		}
 		request_errors_total: {
 			description:       "The total number of requests errors for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _internal_metrics_tags
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
 		}
 		request_duration_nanoseconds: {
-			description:       "The total request duration in nanoseconds."
+			description:       "The total request duration in nanoseconds, of the requests of sources scraping metrics from services and of batching sinks."
 			type:              "histogram"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
@@ -465,7 +867,9 @@ components: sources: internal_metrics: {
 			description:       "The total number of request read errors for this component."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				stage: _stage
+			}
 		}
 		requests_completed_total: {
 			description:       "The total number of requests completed by this component."
@@ -473,17 +877,41 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		requests_not_acknowledged_total: {
+			description:       "The total number of requests whose events weren't delivered, so that they weren't acknowledged."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		requests_received_total: {
 			description:       "The total number of requests received by this component."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sent_event_bytes_total: {
+			description:       "The estimated total number of bytes of the events this component passed to a downstream component."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				to: _to
+			}
+		}
+		sent_events_total: {
+			description:       "The total number of events this component passed to a downstream component."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				to: _to
+			}
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				stage: _stage
+			}
 		}
 		sqs_message_delete_failed_total: {
 			description:       "The total number of failures to delete SQS messages."
@@ -497,6 +925,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sqs_message_delivery_failed_total: {
+			description:       "The total number of SQS messages not deleted because their events weren't delivered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		sqs_message_processing_failed_total: {
 			description:       "The total number of failures to process SQS messages."
 			type:              "counter"
@@ -532,7 +966,7 @@ components: sources: internal_metrics: {
 			type:              "counter"
 			default_namespace: "vector"
 
-			tags: _component_tags & {
+			tags:              _component_tags & {
 				ignore_type: {
 					description: "The reason for ignoring the S3 record"
 					required:    true
@@ -563,11 +997,55 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tenant_bytes_total: {
+			description:       "The total size of the events counted against the quota of a tenant, as encoded in JSON."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				status: _tenant_quota_status
+				tenant: _tenant
+			}
+		}
+		target_file_errors_total: {
+			description:       "The total number of errors loading the targets files of the `prometheus_scrape` source."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				stage: _stage
+			}
+		}
+		tenant_events_total: {
+			description:       "The total number of events counted against the quota of a tenant."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				status: _tenant_quota_status
+				tenant: _tenant
+			}
+		}
+		tenant_quota_window_bytes: {
+			description:       "The size of the events of a tenant within the rolling window of its quota."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: _tenant
+			}
+		}
+		tenant_quota_window_events: {
+			description:       "The number of events of a tenant within the rolling window of its quota."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: _tenant
+			}
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				stage: _stage
+			}
 		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
@@ -587,6 +1065,7 @@ components: sources: internal_metrics: {
 						udp: "User Datagram Protocol"
 					}
 				}
+				stage: _stage
 			}
 		}
 		value_limit_reached_total: {
@@ -663,6 +1142,22 @@ components: sources: internal_metrics: {
 		}
 
 		// All available tags
+		_allocator: {
+			description: "The memory allocator used by Vector."
+			required:    true
+			enum: {
+				"jemalloc": "The jemalloc allocator."
+				"mimalloc": "The mimalloc allocator."
+			}
+		}
+		_buffer_type: {
+			description: "The type of the buffer the component reads from."
+			required:    true
+			enum: {
+				"disk":   "A buffer on disk, configured for a sink."
+				"memory": "A buffer in memory."
+			}
+		}
 		_collector: {
 			description: "Which collector this metric comes from."
 			required:    true
@@ -695,12 +1190,20 @@ components: sources: internal_metrics: {
 			description: "The type of the error"
 			required:    true
 			enum: {
+				"build_failed":                "The component couldn't be built for the event."
+				"event_processing_failed":     "The processing of the event failed."
+				"failed_condition":            "The condition couldn't be evaluated."
 				"field_missing":               "The event field was missing."
+				"frame_too_long":              "The frame was longer than the maximum length."
 				"invalid_metric":              "The metric was invalid."
 				"mapping_failed":              "The mapping failed."
 				"match_failed":                "The match operation failed."
 				"parse_failed":                "The parsing operation failed."
 				"render_error":                "The rendering operation failed."
+				"script_failed":               "The script failed."
+				"strip_failed":                "The escape sequences couldn't be stripped."
+				"substring_failed":            "The substring couldn't be taken."
+				"template_error":              "The template was invalid."
 				"type_conversion_failed":      "The type conversion operating failed."
 				"type_field_does_not_exist":   "The type field does not exist."
 				"type_ip_address_parse_error": "The IP address did not parse."
@@ -726,5 +1229,49 @@ components: sources: internal_metrics: {
 			required:    true
 			default:     "vector"
 		}
+		_reason: {
+			description: "Why the events were discarded."
+			required:    true
+			enum: {
+				"buffer_full":       "The buffer of the sink was full, and configured to drop new events."
+				"duplicate":         "The event was a duplicate of an event seen before."
+				"encoding_failed":   "The event couldn't be encoded for the destination."
+				"filtered":          "The event didn't match the condition of the component."
+				"limit_exceeded":    "The event would have exceeded a configured limit."
+				"missing_fields":    "The event is missing fields needed to process or send it."
+				"out_of_order":      "The event was older than events already sent for the same stream."
+				"processing_failed": "The event couldn't be processed by the component."
+				"request_failed":    "The request containing the event failed, or exhausted its retries."
+				"sampled":           "The event was left out by sampling."
+				"too_large":         "The event is larger than the maximum batch size."
+			}
+		}
+		_stage: {
+			description: "The stage of the handling of events the error happened in."
+			required:    true
+			enum: {
+				"receiving":  "While receiving events, such as a source reading from its origin."
+				"processing": "While processing events, such as a transform parsing a field."
+				"sending":    "While sending events, such as a sink writing to its destination."
+			}
+		}
+		_to: {
+			description: "The name of the downstream component the events were passed to."
+			required:    true
+			examples: ["my_sink"]
+		}
+		_tenant: {
+			description: "The tenant of the events, or `other` once the transform tags `max_tagged_tenants` tenants."
+			required:    true
+			examples: ["acme", "other"]
+		}
+		_tenant_quota_status: {
+			description: "Whether the events were within the quota of their tenant."
+			required:    true
+			enum: {
+				"over_quota":   "The events exceeded the quota of their tenant."
+				"within_quota": "The events were within the quota of their tenant."
+			}
+		}
 	}
 }
@@ -34,6 +34,13 @@ components: sources: kafka: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only store the offset of a message once all of its events are delivered by the sinks, for at-least-once delivery through the whole topology. The offset of a partition never moves past a message whose events weren't delivered, which is read again once Vector restarts. Otherwise, offsets are stored as soon as the events are read."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		auto_offset_reset: {
 			common:      false
 			description: "If offsets for consumer group do not exist, set them using this strategy. [librdkafka documentation][urls.librdkafka_config] for `auto.offset.reset` option for explanation."
@@ -121,33 +128,114 @@ components: sources: kafka: {
 				syntax: "literal"
 			}
 		}
+		headers_key: {
+			common:      false
+			description: "The log field name to use for the headers of the Kafka message, by header name. If unspecified, the headers would not be added to the log event. They are always available in the `kafka.headers` metadata, e.g. for routing."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["headers"]
+				syntax: "literal"
+			}
+		}
 		librdkafka_options: components._kafka.configuration.librdkafka_options
 		sasl: {
 			common:      false
-			description: "Options for SASL/SCRAM authentication support."
+			description: "Options for SASL authentication support."
 			required:    false
 			warnings: []
 			type: object: {
 				examples: []
 				options: {
+					aws_msk_iam: {
+						common:      false
+						description: "Options of the `AWS_MSK_IAM` mechanism, which authenticates with [AWS MSK][urls.aws_msk] using IAM credentials. Vector signs a token with the credentials, and refreshes it before it expires."
+						required:    false
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								auth: components._aws.configuration.auth
+								region: {
+									description: "The AWS region of the MSK cluster."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["us-east-1"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
 					enabled: {
 						common:      true
-						description: "Enable SASL/SCRAM authentication to the remote. (Not supported on Windows at this time.)"
+						description: "Enable SASL authentication to the remote. (Not supported on Windows at this time.)"
 						required:    false
 						warnings: []
 						type: bool: default: null
 					}
 					mechanism: {
 						common:      true
-						description: "The Kafka SASL/SCRAM mechanisms."
+						description: "The Kafka SASL mechanism. Besides the mechanisms of librdkafka, `AWS_MSK_IAM` authenticates with AWS MSK using IAM credentials."
 						required:    false
 						warnings: []
 						type: string: {
 							default: null
-							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512"]
+							examples: ["SCRAM-SHA-256", "SCRAM-SHA-512", "OAUTHBEARER", "AWS_MSK_IAM"]
 							syntax: "literal"
 						}
 					}
+					oauthbearer: {
+						common:      false
+						description: "Options of the `OAUTHBEARER` mechanism. Vector requests tokens from an OAuth 2.0 token endpoint with the client credentials grant, and refreshes them before they expire. Without these options, tokens are left to librdkafka, e.g. with its `sasl.oauthbearer.config` option."
+						required:    false
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								client_id: {
+									description: "The OAuth 2.0 client ID."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["vector"]
+										syntax: "literal"
+									}
+								}
+								client_secret: {
+									description: "The OAuth 2.0 client secret."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["${OAUTH_CLIENT_SECRET}"]
+										syntax: "literal"
+									}
+								}
+								scope: {
+									common:      false
+									description: "The scope to request the token for."
+									required:    false
+									warnings: []
+									type: string: {
+										default: null
+										examples: ["kafka"]
+										syntax: "literal"
+									}
+								}
+								token_endpoint: {
+									description: "The URL of the OAuth 2.0 token endpoint."
+									required:    true
+									warnings: []
+									type: string: {
+										examples: ["https://auth.example.com/oauth2/token"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
 					password: {
 						common:      true
 						description: "The Kafka SASL/SCRAM authentication password."
@@ -199,6 +287,14 @@ components: sources: kafka: {
 	output: logs: record: {
 		description: "An individual Kafka record"
 		fields: {
+			headers: {
+				description: "The headers of the Kafka record, by header name, if `headers_key` is set."
+				required:    false
+				type: object: {
+					examples: [{"route": "payments"}]
+					options: {}
+				}
+			}
 			message: {
 				description: "The raw line from the Kafka record."
 				required:    true
@@ -224,7 +320,7 @@ components: sources: kafka: {
 				}
 			}
 			timestamp: fields._current_timestamp & {
-				description: "If the [Splunk HEC event endpoint](\(urls.splunk_hec_event_endpoint)) is used then the value of the `time` field will be used. If the [Splunk HEC raw endpoint](\(urls.splunk_hec_raw_endpoint)) is used, then the current time the event was received will be used."
+				description: "The timestamp of the Kafka record, or the time it was read if the record has none."
 			}
 			topic: {
 				description: "The Kafka topic that the record came from."
@@ -238,10 +334,24 @@ components: sources: kafka: {
 	}
 
 	telemetry: metrics: {
-		consumer_offset_updates_failed_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
-		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
-		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
+		consumer_offset_updates_failed_total:  components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
+		consumer_offset_updates_skipped_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_skipped_total
+		events_failed_total:                   components.sources.internal_metrics.output.metrics.events_failed_total
+		kafka_broker_connected:                components.sources.internal_metrics.output.metrics.kafka_broker_connected
+		kafka_broker_errors_total:             components.sources.internal_metrics.output.metrics.kafka_broker_errors_total
+		kafka_broker_request_timeouts_total:   components.sources.internal_metrics.output.metrics.kafka_broker_request_timeouts_total
+		kafka_consumed_messages_bytes_total:   components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
+		kafka_consumed_messages_total:         components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
+		kafka_consumer_lag:                    components.sources.internal_metrics.output.metrics.kafka_consumer_lag
+		kafka_queue_messages:                  components.sources.internal_metrics.output.metrics.kafka_queue_messages
+		kafka_queue_messages_bytes:            components.sources.internal_metrics.output.metrics.kafka_queue_messages_bytes
+		kafka_requests_bytes_total:            components.sources.internal_metrics.output.metrics.kafka_requests_bytes_total
+		kafka_requests_total:                  components.sources.internal_metrics.output.metrics.kafka_requests_total
+		kafka_responses_bytes_total:           components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
+		kafka_responses_total:                 components.sources.internal_metrics.output.metrics.kafka_responses_total
+		kafka_token_refresh_failures_total:    components.sources.internal_metrics.output.metrics.kafka_token_refresh_failures_total
+		processed_bytes_total:                 components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                components.sources.internal_metrics.output.metrics.processed_events_total
 	}
 
 	how_it_works: components._kafka.how_it_works
@@ -0,0 +1,129 @@
+package metadata
+
+components: sources: logstash: {
+	_port: 5044
+
+	title: "Logstash"
+
+	description: """
+		Receives data from Beats, such as Filebeat and Winlogbeat, or from
+		Logstash instances using the [Lumberjack v2 protocol](\(urls.lumberjack_protocol)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.logstash
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The TCP address to listen for connections on, or `systemd#N to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#1"]
+				syntax: "literal"
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum bytes size of a frame, and of the frames of a compressed frame once decompressed. Connections sending longer frames are closed."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 16777216
+				unit:    "bytes"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An individual event sent by a Beat."
+		fields: {
+			host: fields._local_host
+			timestamp: {
+				description: "The time of the event, from its `@timestamp` field. If the event has none, the time it was received at is used."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the event."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		message_acknowledgement: {
+			title: "Message Acknowledgement"
+			body: """
+				Clients send their events in windows, the size of which they
+				announce before each window. Vector acknowledges a window once
+				all of its events are accepted, after which the client sends the
+				next one.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				Frames compressed by clients, with the `compression_level`
+				option of Beats, are decompressed by Vector.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -0,0 +1,162 @@
+package metadata
+
+components: sources: nats: {
+	title: "NATS"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.nats
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						port:      4222
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: [
+			"""
+				Core NATS subjects are best effort: messages published while Vector is disconnected are lost. Use
+				`jetstream` for at-least-once delivery.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only acknowledge a JetStream message once all of its events are delivered by the sinks, for at-least-once delivery through the whole topology, so that the ack floor of the consumer only moves past delivered messages. Messages whose events aren't delivered are negatively acknowledged, so that JetStream redelivers them. Otherwise, messages are acknowledged as soon as they are read. Requires `jetstream`."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		jetstream: {
+			common:      false
+			description: "Consume the messages of a [JetStream][urls.nats_jetstream] stream through a durable consumer, created if it doesn't exist, instead of the messages published to the subject while Vector is connected."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					durable_name: {
+						common:      true
+						description: "The name of the durable consumer, which tracks the messages acknowledged by Vector across restarts."
+						required:    false
+						warnings: []
+						type: string: {
+							default: "vector"
+							examples: ["vector", "logs-archive"]
+							syntax: "literal"
+						}
+					}
+					stream: {
+						description: "The stream to consume. The consumer filters its messages by `subject`."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["LOGS"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+		name: components.sinks.nats.configuration.name
+		queue: {
+			common:      false
+			description: "The queue group to subscribe with. Messages are spread over the subscribers of a queue group, instead of each receiving all of them."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		subject: {
+			description: "The NATS subject to consume, which may contain wildcards."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["foo", "time.us.east", "time.*.east", "time.>", ">"]
+				syntax: "literal"
+			}
+		}
+		subject_key: {
+			common:      false
+			description: "The log field name to use for the subject of the message. If unspecified, the subject is discarded."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["subject"]
+				syntax: "literal"
+			}
+		}
+		url: components.sinks.nats.configuration.url
+	}
+
+	output: logs: record: {
+		description: "An individual NATS message"
+		fields: {
+			message: {
+				description: "The payload of the NATS message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			subject: {
+				description: "The subject the NATS message was published to, if `subject_key` is set."
+				required:    false
+				type: string: {
+					examples: ["time.us.east"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	telemetry: metrics: {
+		consumer_acknowledgements_failed_total:   components.sources.internal_metrics.output.metrics.consumer_acknowledgements_failed_total
+		consumer_negative_acknowledgements_total: components.sources.internal_metrics.output.metrics.consumer_negative_acknowledgements_total
+		processed_bytes_total:                    components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                   components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -0,0 +1,281 @@
+package metadata
+
+components: sources: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs and metrics from OpenTelemetry collectors and SDKs over
+		the [OpenTelemetry protocol](\(urls.opentelemetry_protocol)) (OTLP),
+		with gRPC or HTTP/protobuf.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					api: {
+						title: "OpenTelemetry protocol"
+						url:   urls.opentelemetry_protocol
+					}
+					direction: "incoming"
+					port:      4317
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		grpc: {
+			common:      true
+			description: "Options of the OTLP/gRPC server. At least one of `grpc` or `http` must be set."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: "The address to accept gRPC connections on. The address _must_ include a port."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["0.0.0.0:4317"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+		http: {
+			common:      true
+			description: "Options of the OTLP/HTTP server, which accepts protobuf requests on `/v1/logs` and `/v1/metrics`."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: "The address to accept HTTP connections on. The address _must_ include a port."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["0.0.0.0:4318"]
+							syntax: "literal"
+						}
+					}
+					auth: configuration._http_basic_auth
+					tls: {
+						common:      false
+						description: "Configures the TLS options of the HTTP server."
+						required:    false
+						warnings: []
+						type: object: {
+							examples: []
+							options: {
+								enabled: {
+									common:      false
+									description: "Require TLS for incoming connections."
+									required:    false
+									warnings: []
+									type: bool: default: false
+								}
+								crt_file: {
+									common:      false
+									description: "Absolute path to the certificate file used to identify this server, in DER or PEM format."
+									required:    false
+									warnings: []
+									type: string: {
+										default: null
+										examples: ["/path/to/host_certificate.crt"]
+										syntax: "literal"
+									}
+								}
+								key_file: {
+									common:      false
+									description: "Absolute path to the private key file matching `crt_file`, in DER or PEM format."
+									required:    false
+									warnings: []
+									type: string: {
+										default: null
+										examples: ["/path/to/host_certificate.key"]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An OTLP log record."
+		fields: {
+			message: {
+				description: "The body of the log record."
+				required:    false
+				common:      true
+				type: "*": {}
+			}
+			timestamp: {
+				description: "The time of the log record. If it has none, its observed time or the time it was received at is used."
+				required:    true
+				type: timestamp: {}
+			}
+			observed_timestamp: {
+				description: "The time the log record was observed by the collector or SDK."
+				required:    false
+				common:      false
+				type: timestamp: {}
+			}
+			attributes: {
+				description: "The attributes of the log record."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"http.method": "GET"}]
+					options: {}
+				}
+			}
+			resources: {
+				description: "The attributes of the resource that emitted the log record."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"service.name": "checkout"}]
+					options: {}
+				}
+			}
+			scope: {
+				description: "The `name`, `version` and `attributes` of the instrumentation scope of the log record."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"name": "my.library", "version": "1.2.0"}]
+					options: {}
+				}
+			}
+			severity_text: {
+				description: "The severity of the log record, as named by its source."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["INFO", "error"]
+					syntax: "literal"
+				}
+			}
+			severity_number: {
+				description: "The normalized severity of the log record, from 1 (`TRACE`) to 24 (`FATAL4`)."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [9, 17]
+					unit: null
+				}
+			}
+			trace_id: {
+				description: "The hex encoded trace ID the log record belongs to."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["4bf92f3577b34da6a3ce929d0e0e4736"]
+					syntax: "literal"
+				}
+			}
+			span_id: {
+				description: "The hex encoded span ID the log record belongs to."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["00f067aa0ba902b7"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
+	output: metrics: {
+		counter:   output._passthrough_counter
+		gauge:     output._passthrough_gauge
+		histogram: output._passthrough_histogram
+		summary:   output._passthrough_summary
+	}
+
+	how_it_works: {
+		metric_types: {
+			title: "Metric type interpretation"
+			body: """
+				OTLP gauges and non-monotonic sums are emitted as gauges,
+				monotonic sums as counters, and histograms and summaries as
+				aggregated histograms and summaries. Metrics with a delta
+				aggregation temporality are incremental, others are absolute.
+
+				Exponential histograms are not supported, and are dropped.
+				"""
+		}
+		resource_attributes: {
+			title: "Resource and scope attributes"
+			body: """
+				For log records, the attributes of the resource and of the
+				instrumentation scope are kept under the `resources` and
+				`scope` fields. For metrics, they are added as tags, named
+				`resource.<attribute>`, `scope.name` and `scope.version`,
+				alongside the attributes of each data point.
+				"""
+		}
+		json: {
+			title: "JSON encoding"
+			body: """
+				Only the protobuf encoding of OTLP/HTTP is accepted. Requests
+				with a `Content-Type` other than `application/x-protobuf` are
+				rejected with `415 Unsupported Media Type`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		http_bad_requests_total: components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		parse_errors_total:      components.sources.internal_metrics.output.metrics.parse_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -0,0 +1,60 @@
+package metadata
+
+components: sources: plugin: {
+	title: "Plugin"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		collect: checkpoint: enabled: false
+		multiline: enabled: false
+	}
+
+	support: components._plugin.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._plugin.configuration
+
+	output: {
+		logs: event: {
+			description: "An event emitted by the plugin."
+			fields: {
+				"*": {
+					description: "Vector forwards the events emitted by the plugin as they are. The `plugin` source will not modify or add fields."
+					required:    true
+					type: "*": {}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+	}
+
+	how_it_works: components._plugin.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Every event sent by the plugin carries an increasing `id`. Vector
+				acknowledges each id once the event has been accepted by the pipeline, so
+				the plugin can checkpoint its progress.
+				"""
+		}
+	}
+
+	telemetry: components._plugin.telemetry
+}
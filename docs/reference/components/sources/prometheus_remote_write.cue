@@ -80,15 +80,26 @@ components: sources: prometheus_remote_write: {
 			title: "Metric type interpretation"
 			body: """
 				The remote_write protocol used by this source transmits
-				only the metric tags, timestamp, and numerical value. No
-				explicit information about the original type of the
-				metric (i.e. counter, histogram, etc) is included. As
-				such, this source makes a guess as to what the original
-				metric type was.
+				the metric tags, timestamp, and numerical value of each
+				sample, along with the type of the metric families when
+				Prometheus sends their metadata.
 
-				For metrics named with a suffix of `_total`, this source
-				emits the value as a counter metric. All other metrics
-				are emitted as gauges.
+				Samples of counters, and the buckets, sums and counts of
+				histograms and summaries, are emitted as counter metrics.
+				All other samples, including the quantiles of summaries,
+				are emitted as gauges. For metrics without metadata, this
+				source makes a guess as to what the original metric type
+				was: metrics named with a suffix of `_total` are emitted
+				as counters, and all other metrics as gauges.
+				"""
+		}
+		staleness: {
+			title: "Staleness"
+			body: """
+				Prometheus marks the series that go stale, such as those of
+				targets that are no longer scraped, with a special `NaN`
+				sample. As metrics don't go stale, this source drops these
+				markers.
 				"""
 		}
 	}
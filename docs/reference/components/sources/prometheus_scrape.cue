@@ -62,16 +62,185 @@ components: sources: prometheus_scrape: {
 
 	configuration: {
 		endpoints: {
-			description: "Endpoints to scrape metrics from."
-			required:    true
+			common:      true
+			description: "Endpoints to scrape metrics from. At least one of `endpoints`, `targets` or `target_files` must be set."
+			required:    false
 			warnings: ["You must explicitly add the path to your endpoints. Vector will _not_ automatically add `/metics`."]
 			type: array: {
+				default: []
 				items: type: string: {
 					examples: ["http://localhost:9090/metrics"]
 					syntax: "literal"
 				}
 			}
 		}
+		targets: {
+			common:      false
+			description: "Endpoints to scrape metrics from, each with its own set of labels to attach to the scraped metrics."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						endpoint: {
+							description: "The endpoint to scrape metrics from."
+							required:    true
+							warnings: []
+							type: string: {
+								examples: ["http://localhost:9100/metrics"]
+								syntax: "literal"
+							}
+						}
+						labels: {
+							common:      false
+							description: "Labels to attach to every metric scraped from this endpoint."
+							required:    false
+							warnings: []
+							type: object: {
+								examples: [{"env": "production", "job": "node"}]
+								options: {}
+							}
+						}
+					}
+				}
+			}
+		}
+		target_files: {
+			common:      false
+			description: "Files listing more `targets` to scrape, in the TOML, YAML or JSON format given by their extension. They are reloaded whenever they change."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/etc/vector/prometheus_targets.toml"]
+					syntax: "literal"
+				}
+			}
+		}
+		instance_tag: {
+			common:      true
+			description: "The tag name added to each metric with the `host:port` of the scraped endpoint. If unspecified, no tag is added."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["instance"]
+				syntax: "literal"
+			}
+		}
+		endpoint_tag: {
+			common:      false
+			description: "The tag name added to each metric with the full URL of the scraped endpoint. If unspecified, no tag is added."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["endpoint"]
+				syntax: "literal"
+			}
+		}
+		honor_labels: {
+			common: false
+			description: """
+				Controls how conflicts between the scraped tags and the tags
+				added by Vector (`labels`, `instance_tag` and `endpoint_tag`)
+				are resolved. If `true`, the scraped value is kept. If `false`,
+				the scraped value is renamed to `exported_<tag>` and Vector's
+				value is used.
+				"""
+			required: false
+			warnings: []
+			type: bool: default: false
+		}
+		relabel_configs: {
+			common:      false
+			description: "Rules applied in order to each scraped metric, after the target tags are attached, in the manner of Prometheus' `metric_relabel_configs`."
+			required:    false
+			warnings: []
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						action: {
+							common:      true
+							description: "The action to perform when the rule matches."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "replace"
+								enum: {
+									replace:   "Sets `target_label` to `replacement`, expanded with the regex captures, if `regex` matches. A `target_label` of `__name__` renames the metric."
+									keep:      "Drops the metric unless `regex` matches."
+									drop:      "Drops the metric if `regex` matches."
+									labeldrop: "Removes every tag whose name matches `regex`."
+									labelkeep: "Removes every tag whose name does not match `regex`."
+								}
+								syntax: "literal"
+							}
+						}
+						source_labels: {
+							common:      true
+							description: "The tags whose values are joined with `separator` and matched against `regex`. Use `__name__` for the metric name."
+							required:    false
+							warnings: []
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["__name__", "instance"]
+									syntax: "literal"
+								}
+							}
+						}
+						separator: {
+							common:      false
+							description: "The separator placed between the joined `source_labels` values."
+							required:    false
+							warnings: []
+							type: string: {
+								default: ";"
+								syntax:  "literal"
+							}
+						}
+						regex: {
+							common:      true
+							description: "The regular expression to match against. It is anchored on both ends."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "(.*)"
+								examples: ["go_.*", "(.+);(.+)"]
+								syntax: "regex"
+							}
+						}
+						target_label: {
+							common:      true
+							description: "The tag to set for the `replace` action."
+							required:    false
+							warnings: []
+							type: string: {
+								default: null
+								examples: ["host", "__name__"]
+								syntax: "literal"
+							}
+						}
+						replacement: {
+							common:      false
+							description: "The value to set for the `replace` action. Captures of `regex` can be referenced as `$1`, `$2` and so on."
+							required:    false
+							warnings: []
+							type: string: {
+								default: "$1"
+								syntax:  "literal"
+							}
+						}
+					}
+				}
+			}
+		}
 		scrape_interval_secs: {
 			common:      true
 			description: "The interval between scrapes, in seconds."
@@ -88,6 +257,54 @@ components: sources: prometheus_scrape: {
 		}}
 	}
 
+	how_it_works: {
+		target_labels: {
+			title: "Target labels"
+			body: """
+				Like Prometheus, Vector can attach tags identifying the
+				scraped target to every metric. With `instance_tag` set, the
+				`host:port` of the endpoint is added, filling in the default
+				port of the scheme. `endpoint_tag` adds the full URL, and each
+				entry of `targets` can carry its own `labels`.
+
+				When a scraped metric already has one of these tags, the
+				`honor_labels` option decides which value is kept. By default
+				the scraped value is preserved as `exported_<tag>`.
+				"""
+		}
+		target_files: {
+			title: "Target files"
+			body: """
+				Targets can be discovered by another process that writes
+				them to the files listed in `target_files`, in the manner of
+				Prometheus' `file_sd_configs`. Each file holds a `targets`
+				array with the same `endpoint` and `labels` options as the
+				`targets` option:
+
+				```toml
+				[[targets]]
+				endpoint = "http://10.0.0.1:9100/metrics"
+				labels = { job = "node" }
+				```
+
+				Before each scrape, the files modified since they were last
+				read are reloaded, so targets can be added or removed
+				without restarting Vector. When a file can't be read or
+				parsed, an error is logged and its previous targets are kept.
+				"""
+		}
+		relabeling: {
+			title: "Relabeling"
+			body: """
+				The `relabel_configs` rules run on every scraped metric in
+				the order they are listed, after the target tags have been
+				attached. They follow the semantics of Prometheus'
+				`metric_relabel_configs`: a metric dropped by a `keep` or
+				`drop` rule is not processed by the later rules.
+				"""
+		}
+	}
+
 	output: metrics: {
 		counter:   output._passthrough_counter
 		gauge:     output._passthrough_gauge
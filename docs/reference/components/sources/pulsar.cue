@@ -0,0 +1,223 @@
+package metadata
+
+components: sources: pulsar: {
+	title: "Apache Pulsar"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: false
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			from: {
+				service: services.pulsar
+
+				interface: {
+					socket: {
+						api: {
+							title: "Pulsar protocol"
+							url:   urls.pulsar_protocol
+						}
+						direction: "incoming"
+						port:      6650
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only acknowledge a message once all of its events are delivered by the sinks, for at-least-once delivery through the whole topology. Messages whose events aren't delivered are negatively acknowledged, so that Pulsar redelivers them. Otherwise, messages are acknowledged as soon as they are read."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		auth: components.sinks.pulsar.configuration.auth
+		consumer_name: {
+			common:      false
+			description: "The name of the consumer. If unspecified, Pulsar generates one."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector-1"]
+				syntax: "literal"
+			}
+		}
+		endpoint: {
+			description: "Endpoint to which the pulsar client should connect to. Use a `pulsar+ssl://` endpoint to connect over TLS."
+			required:    true
+			type: string: {
+				examples: ["pulsar://127.0.0.1:6650", "pulsar+ssl://127.0.0.1:6651"]
+				syntax: "literal"
+			}
+		}
+		key_field: {
+			common:      false
+			description: "The log field name to use for the partition key of the message. If unspecified, the key is discarded."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["message_key"]
+				syntax: "literal"
+			}
+		}
+		properties_key: {
+			common:      false
+			description: "The log field name to use for the properties of the message, by property name. If unspecified, the properties are discarded."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["properties"]
+				syntax: "literal"
+			}
+		}
+		subscription_name: {
+			common:      true
+			description: "The name of the subscription to consume the topics with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "vector"
+				examples: ["vector", "logs-archive"]
+				syntax: "literal"
+			}
+		}
+		subscription_type: {
+			common:      true
+			description: "How messages are distributed over the consumers of the subscription."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "exclusive"
+				enum: {
+					exclusive: "Only one consumer of the subscription receives messages."
+					shared:    "Messages are spread over the consumers of the subscription."
+					failover:  "One consumer of the subscription receives messages, others take over if it disconnects."
+				}
+				syntax: "literal"
+			}
+		}
+		tls: {
+			common:      false
+			description: "TLS options of `pulsar+ssl://` endpoints."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					ca_file: {
+						description: "Absolute path to an additional CA certificate file, in PEM format, to verify the certificate of the broker with."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["/path/to/certificate_authority.crt"]
+							syntax: "literal"
+						}
+					}
+				}
+			}
+		}
+		topic_key: {
+			common:      false
+			description: "The log field name to use for the topic of the message. If unspecified, the topic is discarded."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["topic"]
+				syntax: "literal"
+			}
+		}
+		topics: {
+			description: "The Pulsar topics to consume."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["topic-1234", "persistent://public/default/logs"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Pulsar message"
+		fields: {
+			message: {
+				description: "The payload of the Pulsar message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			properties: {
+				description: "The properties of the Pulsar message, by property name, if `properties_key` is set."
+				required:    false
+				type: object: {
+					examples: [{"route": "payments"}]
+					options: {}
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the Pulsar message was published."
+			}
+			topic: {
+				description: "The fully qualified topic the Pulsar message came from, if `topic_key` is set."
+				required:    false
+				type: string: {
+					examples: ["persistent://public/default/logs"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		consumer_acknowledgements_failed_total:   components.sources.internal_metrics.output.metrics.consumer_acknowledgements_failed_total
+		consumer_negative_acknowledgements_total: components.sources.internal_metrics.output.metrics.consumer_negative_acknowledgements_total
+		events_failed_total:                      components.sources.internal_metrics.output.metrics.events_failed_total
+		processed_bytes_total:                    components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                   components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
@@ -71,6 +71,18 @@ components: sources: socket: {
 				syntax: "literal"
 			}
 		}
+		delimiter: {
+			common:        false
+			description:   "The sequence of bytes separating the messages of a stream, such as `\\r\\n` or the `\\u0000` NUL byte. Messages longer than `max_length` are discarded up to the next delimiter."
+			relevant_when: "mode = `tcp`, `unix_datagram` or `unix_stream`"
+			required:      false
+			warnings: []
+			type: string: {
+				default: "\\n"
+				examples: ["\\r\\n", "\\u0000", "||"]
+				syntax: "literal"
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -62,6 +62,23 @@ components: sources: splunk_hec: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Emulate the [indexer acknowledgements](\(urls.splunk_hec_indexer_acknowledgement)) of Splunk, for Splunk forwarders configured with `useACK=true`. Requests are then given an `ackId`, which is only acknowledged on the `/services/collector/ack` endpoint once all of the events of the request are delivered by the sinks. Requests must have a channel, set with the `X-Splunk-Request-Channel` header."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		ack_idle_timeout_secs: {
+			common:      false
+			description: "How long the acknowledgement IDs of a channel are kept once its client stops sending requests to it, when `acknowledgements` is enabled."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
 		address: {
 			common:      true
 			description: "The address to accept connections on."
@@ -72,6 +89,26 @@ components: sources: splunk_hec: {
 				syntax:  "literal"
 			}
 		}
+		max_ack_channels: {
+			common:      false
+			description: "The most channels acknowledgement IDs are kept for at once, when `acknowledgements` is enabled. Requests to other channels are rejected with a `503` status until idle channels are forgotten."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+		max_pending_acks_per_channel: {
+			common:      false
+			description: "The most acknowledgement IDs a channel may have that its client hasn't queried yet, when `acknowledgements` is enabled. Further requests to the channel are rejected with a `503` status."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
 		token: {
 			common:      true
 			description: "If supplied, incoming requests must supply this token in the `Authorization` header, just as a client would if it was communicating with the Splunk HEC endpoint directly. If _not_ supplied, the `Authorization` header will be ignored and requests will not be authenticated."
@@ -99,7 +136,8 @@ components: sources: splunk_hec: {
 	}
 
 	telemetry: metrics: {
-		http_request_errors_total: components.sources.internal_metrics.output.metrics.http_request_errors_total
-		requests_received_total:   components.sources.internal_metrics.output.metrics.requests_received_total
+		http_request_errors_total:       components.sources.internal_metrics.output.metrics.http_request_errors_total
+		requests_not_acknowledged_total: components.sources.internal_metrics.output.metrics.requests_not_acknowledged_total
+		requests_received_total:         components.sources.internal_metrics.output.metrics.requests_received_total
 	}
 }
@@ -107,6 +107,144 @@ components: sources: statsd: {
 
 	}
 
+	output: logs: {
+		event: {
+			description: "A DogStatsD event, `_e{<title length>,<text length>}:<title>|<text>|<fields>`."
+			fields: {
+				aggregation_key: {
+					description: "The key the event is aggregated by, from the `k:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["deploys"]
+						syntax: "literal"
+					}
+				}
+				alert_type: {
+					description: "The alert type of the event, from the `t:` field."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["error", "warning", "info", "success"]
+						syntax: "literal"
+					}
+				}
+				host: {
+					description: "The hostname of the event, from the `h:` field."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["web-1"]
+						syntax: "literal"
+					}
+				}
+				message: {
+					description: "The text of the event."
+					required:    true
+					type: string: {
+						examples: ["Version 2 was deployed"]
+						syntax: "literal"
+					}
+				}
+				priority: {
+					description: "The priority of the event, from the `p:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["normal", "low"]
+						syntax: "literal"
+					}
+				}
+				source_type_name: {
+					description: "The source type of the event, from the `s:` field."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["jenkins"]
+						syntax: "literal"
+					}
+				}
+				tags: {
+					description: "The tags of the event, from the `#` field."
+					required:    false
+					common:      true
+					type: object: {
+						examples: [{"env": "prod"}]
+						options: {}
+					}
+				}
+				timestamp: {
+					description: "The time of the event, from the `d:` field, or the time it was received at."
+					required:    true
+					type: timestamp: {}
+				}
+				title: {
+					description: "The title of the event."
+					required:    true
+					type: string: {
+						examples: ["Deploy"]
+						syntax: "literal"
+					}
+				}
+				type: {
+					description: "The kind of DogStatsD message."
+					required:    true
+					type: string: {
+						enum: event: "A DogStatsD event."
+						syntax: "literal"
+					}
+				}
+			}
+		}
+		service_check: {
+			description: "A DogStatsD service check, `_sc|<name>|<status>|<fields>`."
+			fields: {
+				host: components.sources.statsd.output.logs.event.fields.host
+				message: {
+					description: "The message of the service check, from the `m:` field."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["Connection timed out"]
+						syntax: "literal"
+					}
+				}
+				name: {
+					description: "The name of the service check."
+					required:    true
+					type: string: {
+						examples: ["redis.can_connect"]
+						syntax: "literal"
+					}
+				}
+				status: {
+					description: "The status of the service check: 0 for OK, 1 for warning, 2 for critical and 3 for unknown."
+					required:    true
+					type: uint: {
+						examples: [0, 2]
+						unit: null
+					}
+				}
+				tags:      components.sources.statsd.output.logs.event.fields.tags
+				timestamp: components.sources.statsd.output.logs.event.fields.timestamp
+				type: {
+					description: "The kind of DogStatsD message."
+					required:    true
+					type: string: {
+						enum: service_check: "A DogStatsD service check."
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
 	output: metrics: {
 		counter:      output._passthrough_counter
 		distribution: output._passthrough_distribution
@@ -119,14 +257,24 @@ components: sources: statsd: {
 			title: "Timestamps"
 			body: """
 				StatsD protocol does not provide support for sending metric
-				timestamps. You'll notice that each parsed metric is assigned a
-				`null` timestamp, which is a special value which means "a real
-				time metric", i.e. not a historical one. Normally such `null`
+				timestamps, except for the `T<timestamp>` field of DogStatsD. You'll
+				notice that other parsed metrics are assigned a `null` timestamp,
+				which is a special value which means "a real time metric", i.e. not
+				a historical one. Normally such `null`
 				timestamps will be substituted by current time by downstream
 				sinks or 3rd party services during sending/ingestion. See the
 				[metric][docs.data-model.metric] data model page for more info.
 				"""
 		}
+		dogstatsd: {
+			title: "DogStatsD Extensions"
+			body: """
+				The [DogStatsD](\(urls.dogstatsd)) extensions are supported: tags,
+				histograms, distributions and packets with several values of a metric,
+				`<name>:<value>:<value>|<type>`, the values of which are combined into a
+				single metric. Events and service checks are output as log events.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -45,6 +45,48 @@ components: sources: stdin: {
 	}
 
 	configuration: {
+		decoding: {
+			common:      false
+			description: "How events are decoded from the frames of the input."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "bytes"
+				enum: {
+					bytes: "The frame is the `message` of the event, as is."
+					json:  "The frame is a JSON object, whose fields are output as separate fields. Frames that aren't JSON objects are dropped."
+				}
+				syntax: "literal"
+			}
+		}
+		end_of_input: {
+			common:      false
+			description: "What to do once all the input was read."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "shutdown"
+				enum: {
+					shutdown:   "Finish the source. Vector shuts down once all of its sources finished."
+					stay_alive: "Keep the source running until Vector is shut down."
+				}
+				syntax: "literal"
+			}
+		}
+		framing: {
+			common:      false
+			description: "How the input is split into frames, each of which is an event."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "newline_delimited"
+				enum: {
+					newline_delimited: "Every line is a frame. A trailing `\\r` is removed from the lines."
+					length_delimited:  "Every frame is prefixed by its length in bytes, encoded as a 4 bytes big-endian integer."
+				}
+				syntax: "literal"
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -58,7 +100,7 @@ components: sources: stdin: {
 		}
 		max_length: {
 			common:      false
-			description: "The maximum bytes size of a message before rest of it will be discarded."
+			description: "The maximum bytes size of a frame. Longer frames are discarded, and counted as processing errors."
 			required:    false
 			warnings: []
 			type: uint: {
@@ -104,11 +146,29 @@ components: sources: stdin: {
 				Each line is read until a new line delimiter, the `0xA` byte, is found.
 				"""
 		}
+
+		shell_pipelines: {
+			title: "Shell Pipelines"
+			body: """
+				The input is read as bytes, so binary frames, like `length_delimited`
+				Protobuf messages, are passed through unchanged. Combined with
+				`decoding = "json"` and the `stdout` of another program, this lets Vector
+				be a stage of a shell pipeline:
+
+				```bash
+				jq -c '.items[]' export.json | vector --config ndjson.toml
+				```
+
+				Set `end_of_input` to `stay_alive` to keep Vector running once its input
+				ends, for example to leave time to scrape its metrics.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
 		processed_bytes_total:    components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:   components.sources.internal_metrics.output.metrics.processed_events_total
+		processing_errors_total:  components.sources.internal_metrics.output.metrics.processing_errors_total
 		stdin_reads_failed_total: components.sources.internal_metrics.output.metrics.stdin_reads_failed_total
 	}
 }
@@ -48,6 +48,7 @@ components: sources: syslog: {
 
 	configuration: sources.socket.configuration & {
 		"type": "type": string: enum: syslog: "The type of this component."
+		timezone: configuration._timezone
 	}
 
 	output: logs: line: {
@@ -178,7 +179,10 @@ components: sources: syslog: {
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
-				Each line is read until a new line delimiter, the `0xA` byte, is found.
+				Each message that isn't [octet counted][urls.syslog_6587] is read until a
+				new line delimiter, the `0xA` byte, is found. Appliances terminating their
+				messages differently, for example with a NUL byte, are supported by setting
+				the `delimiter` option.
 				"""
 		}
 
@@ -199,6 +203,16 @@ components: sources: syslog: {
 				requesting support for your specific format.
 				"""
 		}
+
+		timezones: {
+			title: "Time zones"
+			body: """
+				[RFC 3164][urls.syslog_3164] timestamps carry neither a year nor a time
+				zone. They are read as local times in the time zone set by the `timezone`
+				option, or the global `timezone` option, while the timestamps with an
+				explicit offset are kept as they are.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
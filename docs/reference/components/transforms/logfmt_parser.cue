@@ -65,7 +65,8 @@ components: transforms: logfmt_parser: {
 				syntax: "literal"
 			}
 		}
-		types: configuration._types
+		timezone: configuration._timezone
+		types:    configuration._types
 	}
 
 	input: {
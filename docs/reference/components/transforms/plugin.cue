@@ -0,0 +1,59 @@
+package metadata
+
+components: transforms: plugin: {
+	title: "Plugin"
+
+	description: """
+		Sends events to a transform provided by an out-of-process plugin, and
+		forwards the events it responds with.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "gRPC plugin"
+				url:     urls.grpc
+				version: null
+			}
+		}
+	}
+
+	support: components._plugin.support
+
+	configuration: components._plugin.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: components._plugin.how_it_works & {
+		ordering: {
+			title: "Ordering"
+			body: """
+				The plugin can respond with any number of events for each event it
+				receives, in any order. When the input ends, Vector closes the request
+				stream and waits for the plugin to end its response. If the stream
+				fails before then, it is reopened with backoff and the rest of the
+				input is sent to the new stream; the events the plugin hadn't responded
+				to yet are lost.
+				"""
+		}
+	}
+
+	telemetry: components._plugin.telemetry
+}
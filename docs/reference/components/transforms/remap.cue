@@ -45,6 +45,48 @@ components: transforms: "remap": {
 	}
 
 	configuration: {
+		drop_on_abort: {
+			common:      false
+			description: "Drop the event if the VRL program is stopped by an `abort` expression. Set this to `false` to emit the event as it was before the program ran."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		drop_on_err: {
+			common:      false
+			description: "Drop the event if the VRL program fails with an error. By default, the event is emitted as modified up to the error."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		output_type: {
+			common:      false
+			description: """
+				Convert the events to logs or metrics. Metrics are converted to logs before the program runs, and logs
+				are converted to metrics after it ran. See [converting events](#converting-events) for the structure of
+				the converted events. By default, events keep their type.
+				"""
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				enum: {
+					log:    "Emit log events."
+					metric: "Emit metric events."
+				}
+			}
+		}
+		reroute_dropped: {
+			common:      false
+			description: """
+				Instead of discarding the events dropped by `drop_on_abort` or `drop_on_err`, emit them, as they were
+				before the program ran, to a separate `<transform>.dropped` output. Other components can use this output
+				as an input, like `my_remap.dropped`.
+				"""
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
 		source: {
 			description: """
 				The [Vector Remap Language](\(urls.vrl_reference)) (VRL) program to execute for each event.
@@ -64,6 +106,7 @@ components: transforms: "remap": {
 				syntax: "remap_program"
 			}
 		}
+		timezone: configuration._timezone
 	}
 
 	input: {
@@ -105,6 +148,93 @@ components: transforms: "remap": {
 				[Vector Remap Language reference](\(urls.vector_remap_language_reference)).
 				"""#
 		}
+
+		emitting_multiple_events: {
+			title: "Emitting multiple events"
+			body: #"""
+				A program can turn one log event into many by assigning an array to the
+				root path, for example with the `unnest` function:
+
+				```vrl
+				. = unnest!(.events)
+				```
+
+				From then on, the program operates on the array, and each of its elements
+				is emitted as a separate event. Elements that aren't maps are stored in
+				the message field of their event, as configured by
+				`log_schema.message_key`. Assigning an empty array drops the event.
+
+				This allows splitting batched payloads entirely within the program. For
+				example, to emit each log event of a CloudWatch Logs subscription message
+				as a separate event, along with the name of its log group:
+
+				```vrl
+				parsed = parse_aws_cloudwatch_log_subscription_message!(.message)
+				. = map_values(parsed.log_events) -> |event| {
+				  merge!(event, {"log_group": parsed.log_group})
+				}
+				```
+				"""#
+		}
+
+		aborting: {
+			title: "Aborting"
+			body: #"""
+				A program can stop processing an event with the `abort` expression:
+
+				```vrl
+				if .level == "debug" {
+				  abort
+				}
+				```
+
+				Unlike errors, an abort can't be handled within the program. By default, the
+				event is dropped. With `drop_on_abort` set to `false`, it is emitted as it was
+				before the program ran instead, discarding any changes the program made.
+
+				With `reroute_dropped` enabled, dropped events are sent to the
+				`<transform>.dropped` output, to inspect or store them elsewhere.
+				"""#
+		}
+
+		converting_events: {
+			title: "Converting events"
+			body: #"""
+				With `output_type` set, a program can turn logs into metrics, and metrics into
+				logs, covering simple uses of the `log_to_metric` and `metric_to_log`
+				transforms. Metrics converted to logs have the following structure, which logs
+				converted to metrics must have once the program ran:
+
+				```json
+				{
+				  "name": "requests_total",
+				  "namespace": "app",
+				  "tags": {"status": "200"},
+				  "timestamp": "2021-02-01T12:00:00Z",
+				  "kind": "incremental",
+				  "counter": {"value": 1.0}
+				}
+				```
+
+				`namespace`, `tags` and `timestamp` are optional. `kind` is either `incremental`
+				or `absolute`. The value is held by one of the `counter`, `gauge`, `set`,
+				`distribution`, `aggregated_histogram` or `aggregated_summary` fields, in the
+				format `metric_to_log` emits. For example, to count HTTP requests by status:
+
+				```vrl
+				. = {
+				  "name": "requests_total",
+				  "kind": "incremental",
+				  "tags": {"status": to_string!(.status)},
+				  "timestamp": .timestamp,
+				  "counter": {"value": 1}
+				}
+				```
+
+				Programs assigning values of the wrong type to these fields are rejected. Logs
+				that don't describe a valid metric once the program ran are dropped.
+				"""#
+		}
 	}
 
 	telemetry: metrics: {
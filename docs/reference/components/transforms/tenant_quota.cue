@@ -0,0 +1,184 @@
+package metadata
+
+components: transforms: tenant_quota: {
+	title: "Tenant Quota"
+
+	description: """
+		Counts events against per-tenant event and byte budgets over a rolling
+		window, and drops or reroutes the events that exceed the budget of their
+		tenant.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		max_bytes: {
+			common:      true
+			description: "The maximum size of the events of a tenant within the window, as encoded in JSON. Unlimited if not set."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [10_000_000]
+				unit: "bytes"
+			}
+		}
+		max_events: {
+			common:      true
+			description: "The maximum number of events of a tenant within the window. Unlimited if not set."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: null
+				examples: [10_000]
+				unit: null
+			}
+		}
+		max_tagged_tenants: {
+			common:      false
+			description: "The number of tenants the internal metrics of the transform are tagged with. Beyond it, the metrics of tenants seen later are tagged as `other`. The tenants of the `tenants` option are always tagged."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
+		reroute_over_quota: {
+			common:      false
+			description: """
+				Instead of discarding the events over quota, emit them to a separate `<transform>.over_quota` output.
+				Other components can use this output as an input, like `my_quota.over_quota`.
+				"""
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		tenant_key: {
+			description: "The field of log events, or the tag of metric events, holding the tenant of the event."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["tenant", "customer.id"]
+			}
+		}
+		tenants: {
+			common:      false
+			description: "Budgets of specific tenants, replacing the `max_events` and `max_bytes` options for them."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						acme: {
+							max_events: 100_000
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "The budgets of the tenant. Budgets that aren't set fall back to the top-level ones."
+						required:    true
+						warnings: []
+						type: object: options: {
+							max_bytes: {
+								description: "The maximum size of the events of the tenant within the window."
+								required:    false
+								warnings: []
+								type: uint: {
+									default: null
+									unit:    "bytes"
+								}
+							}
+							max_events: {
+								description: "The maximum number of events of the tenant within the window."
+								required:    false
+								warnings: []
+								type: uint: {
+									default: null
+									unit:    null
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+		window_secs: {
+			common:      false
+			description: "The length of the rolling window the budgets apply to."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		rolling_window: {
+			title: "Rolling Window"
+			body: """
+				The usage of each tenant is counted in one-second buckets, and the
+				buckets older than `window_secs` stop counting against the budgets.
+				Events over quota don't count against the budgets, so a tenant
+				sending too much data gets its events through again as soon as its
+				usage over the last `window_secs` drops below the budgets.
+				"""
+		}
+
+		events_without_tenant: {
+			title: "Events Without A Tenant"
+			body: """
+				Events without the `tenant_key` field, or tag, aren't subject to
+				quotas, and are always emitted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:     components.sources.internal_metrics.output.metrics.events_discarded_total
+		tenant_bytes_total:         components.sources.internal_metrics.output.metrics.tenant_bytes_total
+		tenant_events_total:        components.sources.internal_metrics.output.metrics.tenant_events_total
+		tenant_quota_window_bytes:  components.sources.internal_metrics.output.metrics.tenant_quota_window_bytes
+		tenant_quota_window_events: components.sources.internal_metrics.output.metrics.tenant_quota_window_events
+	}
+}
@@ -74,7 +74,8 @@ components: transforms: tokenizer: {
 				syntax: "literal"
 			}
 		}
-		types: configuration._types
+		timezone: configuration._timezone
+		types:    configuration._types
 	}
 
 	examples: [
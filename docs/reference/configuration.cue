@@ -26,7 +26,8 @@ configuration: {
 		healthchecks: {
 			common: false
 			description: """
-				Configures health checks for all sinks.
+				Configures the default health check options of all sinks. The
+				`healthcheck` options of a sink take precedence over these.
 				"""
 			required: false
 			warnings: []
@@ -36,8 +37,8 @@ configuration: {
 					enabled: {
 						common: true
 						description: """
-							Disables all health checks if false, otherwise sink specific
-							option overrides it.
+							Disables the health checks of all sinks that don't set
+							`healthcheck.enabled` themselves.
 							"""
 						required: false
 						warnings: []
@@ -49,7 +50,8 @@ configuration: {
 					require_healthy: {
 						common: false
 						description: """
-							Exit on startup if any sinks' health check fails. Overridden by
+							Exit on startup if the health check of any sink that doesn't
+							set `healthcheck.require_healthy` itself fails. Overridden by
 							`--require-healthy` command line flag.
 							"""
 						required: false
@@ -61,6 +63,46 @@ configuration: {
 				}
 			}
 		}
+
+		templates: {
+			common: false
+			description: """
+				Defines reusable blocks of component options. Components, and other templates, inherit the options
+				of the templates they list in their `use` option. See the [templates](#templates) section for
+				more info.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						standard_batch: {
+							batch: {
+								max_bytes:    1049000
+								timeout_secs: 1
+							}
+						}
+					},
+				]
+				options: {}
+			}
+		}
+
+		timezone: {
+			common: false
+			description: """
+				The name of the time zone to apply to timestamp conversions that do not contain an explicit
+				time zone. The time zone name may be any name in the [TZ database](\(urls.iana_time_zones)),
+				or `local` to indicate system local time. Components that parse timestamps can override this
+				with their own `timezone` option.
+				"""
+			required: false
+			type: string: {
+				default: "local"
+				examples: ["local", "America/New_York", "EST5EDT"]
+				syntax: "literal"
+			}
+		}
 	}
 
 	how_it_works: {
@@ -133,5 +175,47 @@ configuration: {
 				```
 				"""
 		}
+		templates: {
+			title: "Templates"
+			body: """
+				Options shared by many components, such as TLS, buffer, and batch settings, can be defined
+				once as a template in the `templates` section, and used by any number of components with the
+				`use` option:
+
+				```toml title="vector.toml"
+				[templates.standard_sink]
+				  healthcheck = false
+
+				  [templates.standard_sink.buffer]
+				    type = "memory"
+				    max_events = 1000
+
+				  [templates.standard_sink.batch]
+				    max_bytes = 1049000
+				    timeout_secs = 1
+
+				[templates.standard_tls.tls]
+				  ca_file = "/etc/ssl/certs/ca.pem"
+				  verify_certificate = true
+
+				[sinks.es]
+				  use = ["standard_sink", "standard_tls"]
+				  type = "elasticsearch"
+				  inputs = ["in"]
+				  endpoint = "https://es.example.com"
+
+				  [sinks.es.batch]
+				    timeout_secs = 5 # overrides the template
+				```
+
+				Templates are applied in the order they are listed in `use`, and the options of the
+				component itself are applied last. Tables are merged option by option, so a component can
+				override a single option of a template, while any other value, including arrays, replaces
+				the value of the template. Templates can `use` other templates, and can be defined in any
+				of the configuration files Vector is started with.
+
+				In YAML configuration files, anchors and aliases can be used as well.
+				"""
+		}
 	}
 }
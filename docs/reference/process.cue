@@ -8,6 +8,13 @@ process: {
 
 	#ExitCodes: [Name=string]: #ExitCode
 
+	#EnvVar: {
+		description: string
+		name:        string
+	}
+
+	#EnvVars: [Name=string]: #EnvVar & {name: Name}
+
 	#Signal: {
 		description: string
 		name:        string
@@ -15,9 +22,20 @@ process: {
 
 	#Signals: [Name=string]: #Signal & {name: Name}
 
+	env_vars:   #EnvVars
 	exit_codes: #ExitCodes
 	signals:    #Signals
 
+	env_vars: {
+		VECTOR_ALLOCATOR: {
+			description: """
+				Selects the memory allocator on Unix, among `system`, `jemalloc`, and `mimalloc`,
+				as far as they are built in. Defaults to the first allocator built in, which is
+				`jemalloc` for the official builds.
+				"""
+		}
+	}
+
 	exit_codes: {
 		"0": {
 			code:        0
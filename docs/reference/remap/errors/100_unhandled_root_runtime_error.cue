@@ -23,7 +23,7 @@ remap: errors: "100": {
 				get_env_var("HOST")
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E100]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ 	(5 / 2)
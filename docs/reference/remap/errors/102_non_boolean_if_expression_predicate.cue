@@ -28,7 +28,7 @@ remap: errors: "102": {
 				}
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E102]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ 	if .message {
@@ -24,7 +24,7 @@ remap: errors: "103": {
 			. |= parse_key_value(.message)
 			"""#
 		raises: compiletime: #"""
-			error: \#(title)
+			error[E103]: \#(title)
 			  ┌─ :1:1
 			  │
 			1 │ . |= parse_key_value(.message)
@@ -21,7 +21,7 @@ remap: errors: "104": {
 				.message, err = downcase(.message)
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E104]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ .message, err = downcase(.message)
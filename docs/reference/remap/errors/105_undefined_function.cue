@@ -18,7 +18,7 @@ remap: errors: "105": {
 				parse_keyvalue(.message)
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E105]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ parse_keyvalue(.message)
@@ -18,7 +18,7 @@ remap: errors: "106": {
 				parse_json(.message, pretty: true)
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E106]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ parse_json(.message, pretty: true)
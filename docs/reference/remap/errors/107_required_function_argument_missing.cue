@@ -18,7 +18,7 @@ remap: errors: "107": {
 				parse_timestamp(.timestamp)
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E107]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ parse_timestamp(.timestamp)
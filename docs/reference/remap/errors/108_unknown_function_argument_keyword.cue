@@ -18,7 +18,7 @@ remap: errors: "108": {
 				parse_timestamp(.timestamp, fmt: "%D")
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E108]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ parse_timestamp(.timestamp, fmt: "%D")
@@ -19,7 +19,7 @@ remap: errors: "109": {
 				downcase!(.message)
 				"""#
 			raises: compiletime: #"""
-				error: \#(title)
+				error[E109]: \#(title)
 				  ┌─ :1:1
 				  │
 				1 │ downcase!(.message)
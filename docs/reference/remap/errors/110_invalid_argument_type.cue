@@ -20,7 +20,7 @@ remap: errors: "110": {
 			downcase(.message)
 			"""#
 		raises: compiletime: #"""
-			error: \#(title)
+			error[E110]: \#(title)
 			  ┌─ :1:1
 			  │
 			1 │ downcase(.message)
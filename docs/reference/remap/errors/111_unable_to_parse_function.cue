@@ -0,0 +1,39 @@
+package metadata
+
+remap: errors: "111": {
+	title:       "Unable to parse function"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor)) passes
+		arguments that the function cannot compile, such as an invalid pattern.
+		"""
+	rationale:   """
+		Functions check their literal arguments upon compilation, so that a VRL program fails to load rather than
+		fail on every event once deployed.
+		"""
+	resolution: """
+		The label of the error describes what the function could not parse. Correcting the argument will resolve this.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (unknown grok pattern)"
+			source: #"""
+				parse_grok!(.message, "%{NOG}")
+				"""#
+			raises: compiletime: #"""
+				error[E111]: \#(title)
+				  ┌─ :1:1
+				  │
+				1 │ parse_grok!(.message, "%{NOG}")
+				  │ ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+				  │ │
+				  │ The given pattern definition name "NOG" could not be found in the definition map
+				  │
+				"""#
+			diff: #"""
+				-parse_grok!(.message, "%{NOG}")
+				+parse_grok!(.message, "%{LOGLEVEL:level} %{GREEDYDATA:message}")
+				"""#
+		},
+	]
+}
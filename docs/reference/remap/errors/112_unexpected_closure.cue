@@ -0,0 +1,36 @@
+package metadata
+
+remap: errors: "112": {
+	title:       "Unexpected closure"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor)) is followed
+		by a closure, but the function does not accept one.
+		"""
+	rationale:   null
+	resolution: """
+		Remove the closure, or call a function that iterates over its argument, such as `for_each`, instead.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (function without closure)"
+			source: #"""
+				upcase(.message) -> |value| { value }
+				"""#
+			raises: compiletime: #"""
+				error[E112]: \#(title)
+				  ┌─ :1:18
+				  │
+				1 │ upcase(.message) -> |value| { value }
+				  │                  ^^^^^^^^^^^^^^^^^^^^
+				  │                  │
+				  │                  this function does not accept a closure
+				  │
+				"""#
+			diff: #"""
+				-upcase(.message) -> |value| { value }
+				+upcase(.message)
+				"""#
+		},
+	]
+}
@@ -0,0 +1,36 @@
+package metadata
+
+remap: errors: "113": {
+	title:       "Function closure missing"
+	description: """
+		A [function call expression](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor)) invokes a
+		function that requires a closure, without one.
+		"""
+	rationale:   null
+	resolution: """
+		Add a closure after the closing parenthesis of the function call.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (for_each)"
+			source: #"""
+				for_each!(.values)
+				"""#
+			raises: compiletime: #"""
+				error[E113]: \#(title)
+				  ┌─ :1:1
+				  │
+				1 │ for_each!(.values)
+				  │ ^^^^^^^^^^^^^^^^^^
+				  │ │
+				  │ required closure missing
+				  │
+				"""#
+			diff: #"""
+				-for_each!(.values)
+				+for_each!(.values) -> |value| { .sum = to_int(.sum) ?? 0 + to_int(value) ?? 0 }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,39 @@
+package metadata
+
+remap: errors: "114": {
+	title:       "Closure variable arity mismatch"
+	description: """
+		The closure of a [function call expression](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor))
+		declares more or fewer variables than the function passes to it.
+		"""
+	rationale:   null
+	resolution: """
+		Declare as many variables as the function passes. The context of the error gives the number of variables
+		the function accepts. For example, `for_each` passes the value of each item, or the index or key of each
+		item along with its value.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (too many variables)"
+			source: #"""
+				for_each!(.values) -> |index, value, extra| { .last = value }
+				"""#
+			raises: compiletime: #"""
+				error[E114]: \#(title)
+				  ┌─ :1:20
+				  │
+				1 │ for_each!(.values) -> |index, value, extra| { .last = value }
+				  │                    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+				  │                    │
+				  │                    got: 3
+				  │                    expected: 1 to 2
+				  │
+				"""#
+			diff: #"""
+				-for_each!(.values) -> |index, value, extra| { .last = value }
+				+for_each!(.values) -> |index, value| { .last = value }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,39 @@
+package metadata
+
+remap: errors: "115": {
+	title:       "Path-based variable assignment"
+	description: """
+		An [assignment expression](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor)) assigns to a
+		path of a variable, rather than to the variable itself.
+		"""
+	rationale:   """
+		Assigning to a path of a variable is currently unsupported.
+		"""
+	resolution: """
+		Assign the whole value to the variable instead, or assign to a path of the event.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (object field)"
+			source: #"""
+				foo.bar = "baz"
+				"""#
+			raises: compiletime: #"""
+				error[E115]: \#(title)
+				  ┌─ :1:4
+				  │
+				1 │ foo.bar = "baz"
+				  │ ---^^^^
+				  │ │  │
+				  │ │  assignment to variable path currently unsupported
+				  │ assign to "foo" instead
+				  │
+				"""#
+			diff: #"""
+				-foo.bar = "baz"
+				+foo = { "bar": "baz" }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,39 @@
+package metadata
+
+remap: errors: "116": {
+	title:       "Wildcard path assignment"
+	description: """
+		An [assignment expression](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor)) assigns to a
+		path containing a wildcard.
+		"""
+	rationale:   """
+		A wildcard can match any number of fields, so there is no single field to assign the value to.
+		"""
+	resolution: """
+		Assign to each field explicitly, or iterate over the fields with `for_each`.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (array wildcard)"
+			source: #"""
+				.values[*] = 0
+				"""#
+			raises: compiletime: #"""
+				error[E116]: \#(title)
+				  ┌─ :1:1
+				  │
+				1 │ .values[*] = 0
+				  │ ^^^^^^^^^^
+				  │ │
+				  │ assignment to wildcard path unsupported
+				  │
+				"""#
+			diff: #"""
+				-.values[*] = 0
+				+.values[0] = 0
+				+.values[1] = 0
+				"""#
+		},
+	]
+}
@@ -0,0 +1,39 @@
+package metadata
+
+remap: errors: "117": {
+	title:       "Invalid return value"
+	description: """
+		The last expression of a VRL program resolves to a
+		[regex literal](\(urls.vrl_expressions)#\(remap.literals.regular_expression.anchor)).
+		"""
+	rationale:   """
+		Regular expressions are only meant to be passed to functions. They cannot be stored in an event, so a program
+		cannot return one.
+		"""
+	resolution: """
+		Pass the regular expression to a function, such as `match`, or end the program with another expression.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (regex literal)"
+			source: #"""
+				r'^foo'
+				"""#
+			raises: compiletime: #"""
+				error[E117]: \#(title)
+				  ┌─ :1:1
+				  │
+				1 │ r'^foo'
+				  │ ^^^^^^^
+				  │ │
+				  │ regex return value not allowed
+				  │
+				"""#
+			diff: #"""
+				-r'^foo'
+				+match(.message, r'^foo')
+				"""#
+		},
+	]
+}
@@ -0,0 +1,40 @@
+package metadata
+
+remap: errors: "118": {
+	title:       "Unexpected return value"
+	description: """
+		The last expression of a VRL program resolves to a type that the component running the program does not
+		accept. For example, the conditions of the `filter` and `route` transforms must resolve to a boolean.
+		"""
+	rationale:   """
+		VRL is [type-safe](\(urls.vrl_type_safety)) and requires that types align upon compilation, ensuring that
+		VRL programs are reliable once deployed.
+		"""
+	resolution: """
+		End the program with an expression resolving to the expected type, as given by the context of the error.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (condition)"
+			source: #"""
+				.status
+				"""#
+			raises: compiletime: #"""
+				error[E118]: \#(title)
+				  ┌─ :1:1
+				  │
+				1 │ .status
+				  │ ^^^^^^^
+				  │ │
+				  │ got: any
+				  │ expected: boolean
+				  │
+				"""#
+			diff: #"""
+				-.status
+				+.status == 200
+				"""#
+		},
+	]
+}
@@ -0,0 +1,37 @@
+package metadata
+
+remap: errors: "119": {
+	title:       "Syntax error"
+	description: """
+		The VRL program is syntactically invalid, and cannot be parsed into
+		[expressions](\(urls.vrl_expressions)).
+		"""
+	rationale:   null
+	resolution: """
+		The labels of the error point to the invalid token, along with the grammar rules expected in its place.
+		This is typically due to a typo, or a missing delimiter.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (missing parenthesis)"
+			source: #"""
+				.message = upcase(.message
+				"""#
+			raises: compiletime: #"""
+				error[E119]: \#(title)
+				  ┌─ :1:27
+				  │
+				1 │ .message = upcase(.message
+				  │                           ^
+				  │                           │
+				  │                           invalid token
+				  │
+				"""#
+			diff: #"""
+				-.message = upcase(.message
+				+.message = upcase(.message)
+				"""#
+		},
+	]
+}
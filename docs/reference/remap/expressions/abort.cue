@@ -0,0 +1,37 @@
+package metadata
+
+remap: expressions: abort: {
+	title: "Abort"
+	description: """
+		An _abort_ expression stops the program immediately, without making any further changes to the event.
+
+		An abort isn't an error, and can't be handled with the error coalescing operator (`??`) or an error
+		assignment. How the aborted event is handled depends on the component running the program. The `remap`
+		transform drops it by default.
+		"""
+	return: """
+		Doesn't return, the program stops at the expression.
+		"""
+
+	examples: [
+		{
+			title: "Abort on debug logs"
+			input: log: {
+				level:   "info"
+				message: "connection opened"
+			}
+			source: #"""
+				if .level == "debug" {
+					abort
+				}
+				.processed = true
+				"""#
+			return: true
+			output: log: {
+				level:     "info"
+				message:   "connection opened"
+				processed: true
+			}
+		},
+	]
+}
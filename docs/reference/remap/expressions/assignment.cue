@@ -8,7 +8,8 @@ remap: expressions: assignment: {
 		"""
 	return: """
 		Returns the value of the right-hand side expression only if the expression succeeds. If the expression errors,
-		then the error must be [handled](\(urls.vrl_errors_reference)) and null is returned.
+		then the error must be [handled](\(urls.vrl_errors_reference)) and the value assigned to the `target` is
+		returned.
 		"""
 
 	grammar: {
@@ -26,6 +27,25 @@ remap: expressions: assignment: {
 				description: """
 					The `error` allows for optional assignment to errors when the right-hand side expression is
 					fallible. This is commonly used when invoking fallible functions.
+
+					If the expression succeeds, the `error` is assigned `null`. If it errors, the `error` is assigned
+					the error message, and the `target` is assigned the empty value of the expression's type, such as
+					`""` for strings, `0` for integers or `{}` for maps. If the expression can return more than one
+					type, the `target` is assigned `null`:
+
+					```vrl
+					.message, err = downcase(.message) # .message is "" if it is not a string
+					parsed, err = parse_json(.message) # parsed is null if .message is not valid JSON
+					```
+
+					The program continues after the error, so each field can fall back on its own value:
+
+					```vrl
+					.status, err = to_int(.status)
+					if err != null {
+					  .status = 0
+					}
+					```
 					"""
 			}
 			operator: {
@@ -118,5 +138,14 @@ remap: expressions: assignment: {
 				"""#
 			return: null
 		},
+		{
+			title: "Fallible assignment (error, single type)"
+			source: #"""
+				.message, err = downcase(.message)
+				"""#
+			input: log: message: 42
+			return: ""
+			output: log: message: ""
+		},
 	]
 }
@@ -27,7 +27,8 @@ remap: expressions: coalesce: {
 				enum: {
 					"??": """
 						The `??` operator performs error coalescing, short-circutiing on the first expression that does not
-						error and returning its result.
+						error and returning its result. The result can be of the type of any of the expressions, and
+						is infallible if the last expression is infallible.
 						"""
 				}
 			}
@@ -42,5 +43,17 @@ remap: expressions: coalesce: {
 				"""#
 			return: "malformed"
 		},
+		{
+			title: "Error coalescing with a default"
+			source: #"""
+				.structured = parse_json(.message) ?? {}
+				"""#
+			input: log: message: "malformed"
+			return: {}
+			output: log: {
+				message:    "malformed"
+				structured: {}
+			}
+		},
 	]
 }
@@ -15,7 +15,7 @@ remap: expressions: function_call: {
 
 	grammar: {
 		source: """
-			function ~ abort? ~ "(" ~ arguments? ~ ")"
+			function ~ abort? ~ "(" ~ arguments? ~ ")" ~ closure?
 			"""
 		definitions: {
 			function: {
@@ -95,6 +95,26 @@ remap: expressions: function_call: {
 					}
 				}
 			}
+			closure: {
+				description: """
+					Some functions, such as `map_values` and `filter`, accept a `closure`: a block of expressions
+					that the function runs for each element of a map or array. The closure starts with `->`,
+					followed by the closure variables between pipes (`|`), and the block:
+
+					```vrl
+					map_values!(.tags) -> |tag| { downcase(tag) }
+					```
+
+					The closure variables are only visible inside the block. When the closure binds two
+					variables, the first one holds the key (for maps) or index (for arrays) of the element:
+
+					```vrl
+					map_values!(.labels) -> |key, value| { key + "=" + value }
+					```
+
+					If the block can fail, the function call can fail as well.
+					"""
+			}
 		}
 	}
 
@@ -120,5 +140,12 @@ remap: expressions: function_call: {
 				"""#
 			return: ["hello", "world!"]
 		},
+		{
+			title: "Function invocation with a closure"
+			source: #"""
+				filter!([1, 5, 10]) -> |number| { number > 3 }
+				"""#
+			return: [5, 10]
+		},
 	]
 }
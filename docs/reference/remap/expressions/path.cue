@@ -67,6 +67,18 @@ remap: expressions: path: {
 							```
 							"""
 					}
+					wildcards: {
+						title: "Array wildcard paths"
+						description: """
+							The `[*]` index matches every element of an array, and resolves to the list of the values
+							it matches. Wildcard paths can't be assigned to, but they can be passed to the `del` and
+							`exists` functions:
+
+							```vrl
+							del(.tags[*].internal)
+							```
+							"""
+					}
 					nonexistent: {
 						title: "Non-existent paths"
 						description: """
@@ -146,6 +158,14 @@ remap: expressions: path: {
 				"""#
 			return: input.log.array[1]
 		},
+		{
+			title: "Array wildcard path"
+			input: log: array: [{name: "first"}, {name: "second"}]
+			source: #"""
+				.array[*].name
+				"""#
+			return: ["first", "second"]
+		},
 		{
 			title: "Quoted path"
 			input: log: "parent.key.with.special characters": child: "Hello, World!"
@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: any: {
+	category: "Enumerate"
+	description: """
+		Returns `true` if the closure returns `true` for at least one element of the `value` map or array.
+
+		The closure is called with the element as `|value|`, or with its key (for maps) or index (for
+		arrays) and the element as `|key_or_index, value|`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map or array to check."
+			required:    true
+			type: ["array", "map"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure fails for one of the elements.",
+		"The closure returns a value that isn't a boolean.",
+	]
+	return: {
+		types: ["boolean"]
+		rules: [
+			"Stops at the first element for which the closure returns `true`.",
+			"Returns `false` for an empty map or array.",
+		]
+	}
+	examples: [
+		{
+			title: "Check for server errors"
+			source: #"""
+				any!([200, 404, 503]) -> |code| { code >= 500 }
+				"""#
+			return: true
+		},
+		{
+			title: "Check the map keys"
+			source: #"""
+				any({"env": "prod", "region": "eu"}) -> |key, value| { key != "region" }
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: camelcase: {
+	category: "String"
+	description: """
+		Converts the `value` to camel case, like `camelCase`.
+
+		The `value` is split into words on non-alphanumeric characters, like spaces, `-` or `_`, and on
+		transitions from lowercase to uppercase letters.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to convert to camel case."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Camel case a key"
+			source: #"""
+				camelcase("user_id")
+				"""#
+			return: "userId"
+		},
+		{
+			title: "Camel case words"
+			source: #"""
+				camelcase("HTTP response code")
+				"""#
+			return: "httpResponseCode"
+		},
+	]
+}
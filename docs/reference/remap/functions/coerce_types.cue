@@ -0,0 +1,87 @@
+package metadata
+
+remap: functions: coerce_types: {
+	category: "Coerce"
+	description: """
+		Coerces the strings within the `value` into the types they represent, like `"200"` into `200` or
+		`"true"` into `true`. Maps and arrays are walked recursively, which makes it possible to coerce a whole
+		event at once.
+
+		Which types strings are coerced into can be specified with the parameters. Strings that can't be
+		coerced into any of them are kept as-is.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to coerce."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "integers"
+			description: "Should integer strings, like `\"42\"`, be coerced into integers."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "floats"
+			description: "Should float strings, like `\"1.5\"` or `\"2e3\"`, be coerced into floats."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "booleans"
+			description: "Should the `\"true\"` and `\"false\"` strings be coerced into booleans."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "timestamps"
+			description: "Should [RFC 3339](\(urls.rfc_3339)) strings be coerced into timestamps."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["any"]
+		rules: [
+			"If `value` is a map or an array, its values are coerced recursively.",
+			"If `value` is a string, it is coerced into the first enabled type it represents, tried in the order booleans, integers, floats and timestamps.",
+			"Other values are returned as-is.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Coerce an event"
+			input: log: {
+				status:   "200"
+				duration: "1.5"
+				success:  "true"
+				message:  "done"
+			}
+			source: #"""
+				. = coerce_types(.)
+				"""#
+			output: log: {
+				status:   200
+				duration: 1.5
+				success:  true
+				message:  "done"
+			}
+		},
+		{
+			title: "Coerce only integers"
+			source: #"""
+				coerce_types(["1", "1.5", "true"], floats: false, booleans: false)
+				"""#
+			return: [1, "1.5", "true"]
+		},
+	]
+}
@@ -0,0 +1,80 @@
+package metadata
+
+remap: functions: community_id: {
+	category: "IP"
+	description: """
+		Generates a [Community ID](\(urls.community_id)) flow hash from the `source_ip`, `destination_ip`,
+		`protocol` and, if supplied, the `source_port` and `destination_port` of a network flow. Both directions
+		of a flow get the same ID, so flows can be correlated across tools like Zeek and Suricata.
+		"""
+	notices: [
+		"""
+			For ICMP and ICMPv6 flows, the `source_port` is the ICMP type and the `destination_port` is the ICMP
+			code.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "source_ip"
+			description: "The source IP address, either a v4 or a v6 address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "destination_ip"
+			description: "The destination IP address, of the same IP version as the `source_ip`."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "protocol"
+			description: "The IANA protocol number, or one of the names `icmp`, `tcp`, `udp`, `icmp6` or `sctp`."
+			required:    true
+			type: ["integer", "string"]
+		},
+		{
+			name:        "source_port"
+			description: "The source port. Only used for the `icmp`, `tcp`, `udp`, `icmp6` and `sctp` protocols."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "destination_port"
+			description: "The destination port. Only used for the `icmp`, `tcp`, `udp`, `icmp6` and `sctp` protocols."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "seed"
+			description: "The seed to hash the flow with, to keep IDs of different networks apart."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`source_ip` or `destination_ip` is not a valid IP address.",
+		"`source_ip` and `destination_ip` are of different IP versions.",
+		"`protocol` is not a known protocol name or not a number between 0 and 255.",
+		"`source_port`, `destination_port` or `seed` is not a number between 0 and 65535.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "TCP flow"
+			source: #"""
+				community_id("128.232.110.120", "66.35.250.204", "tcp", 34855, 80)
+				"""#
+			return: "1:LQU9qZlK+B5F3KDmev6m5PMibrg="
+		},
+		{
+			title: "ICMP echo reply"
+			source: #"""
+				community_id("192.168.0.1", "192.168.0.89", protocol: 1, source_port: 0, destination_port: 0)
+				"""#
+			return: "1:X0snYXpgwiv9TZtqg64sgzUn6Dk="
+		},
+	]
+}
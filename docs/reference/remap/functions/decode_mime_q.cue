@@ -0,0 +1,41 @@
+package metadata
+
+remap: functions: decode_mime_q: {
+	category:    "Codec"
+	description: """
+		Decodes the [RFC 2047 encoded-words](\(urls.rfc_2047)) of the `value`, like `=?UTF-8?B?SGVsbG8=?=`, such as
+		the ones found in the subjects and addresses of emails. Both the `B` (Base64) and `Q` (quoted-printable)
+		encodings are supported. The text around the encoded-words is kept as is.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string with encoded-words to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"An encoded-word isn't properly encoded.",
+		"The charset of an encoded-word isn't known.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode a Base64 encoded-word"
+			source: """
+				decode_mime_q("=?UTF-8?B?SGVsbG8gV29ybGQ=?=")
+				"""
+			return: "Hello World"
+		},
+		{
+			title: "Decode a quoted-printable encoded-word"
+			source: """
+				decode_mime_q("Subject: =?ISO-8859-1?Q?Caf=E9_ouvert?=")
+				"""
+			return: "Subject: Café ouvert"
+		},
+	]
+}
@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: decode_punycode: {
+	category:    "Codec"
+	description: """
+		Decodes the [punycode](\(urls.punycode)) labels of the domain `value`, like `xn--bcher-kva`, into Unicode.
+		Labels that aren't encoded are kept as is.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The domain to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid punycode encoded domain.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode a punycode domain"
+			source: """
+				decode_punycode("www.xn--bcher-kva.de")
+				"""
+			return: "www.bücher.de"
+		},
+	]
+}
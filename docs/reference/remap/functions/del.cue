@@ -9,7 +9,7 @@ remap: functions: del: {
 	arguments: [
 		{
 			name:        "path"
-			description: "The path of the field to delete."
+			description: "The path of the field to delete. Array wildcards (`[*]`) delete every matching field."
 			required:    true
 			type: ["string"]
 		},
@@ -24,6 +24,7 @@ remap: functions: del: {
 		types: ["any"]
 		rules: [
 			"The return is the value of the field being deleted. If the field does not exist, `null` is returned.",
+			"If the `path` contains a wildcard, the return is the list of the deleted values.",
 		]
 	}
 
@@ -37,6 +38,12 @@ remap: functions: del: {
 			source: "del(.field1)"
 			output: log: field2: 2
 		},
+		{
+			title: "Delete nested array fields"
+			input: log: tags: [{name: "a", internal: true}, {name: "b", internal: false}]
+			source: "del(.tags[*].internal)"
+			output: log: tags: [{name: "a"}, {name: "b"}]
+		},
 		{
 			title: "Rename a field"
 			input: log: old_field: "please rename me"
@@ -4,6 +4,8 @@ remap: functions: exists: {
 	category: "Event"
 	description: """
 		Checks if the `path` exists for the current event.
+
+		A `path` with array wildcards (`[*]`) exists if any of the fields it matches exists.
 		"""
 
 	arguments: [
@@ -35,5 +37,13 @@ remap: functions: exists: {
 				"""#
 			return: true
 		},
+		{
+			title: "Exists (array wildcard)"
+			input: log: tags: [{name: "a"}, {name: "b", internal: true}]
+			source: #"""
+				exists(.tags[*].internal)
+				"""#
+			return: true
+		},
 	]
 }
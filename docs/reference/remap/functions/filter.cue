@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: filter: {
+	category: "Enumerate"
+	description: """
+		Keeps the elements of the `value` map or array for which the closure returns `true`, and removes
+		all others.
+
+		The closure is called with the element as `|value|`, or with its key (for maps) or index (for
+		arrays) and the element as `|key_or_index, value|`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map or array to filter."
+			required:    true
+			type: ["array", "map"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure fails for one of the elements.",
+		"The closure returns a value that isn't a boolean.",
+	]
+	return: {
+		types: ["array", "map"]
+		rules: [
+			"The return type will match the `value` type.",
+			"The order of the remaining array elements is kept.",
+		]
+	}
+	examples: [
+		{
+			title: "Keep server errors"
+			source: #"""
+				filter!([200, 404, 500, 503]) -> |code| { code >= 500 }
+				"""#
+			return: [500, 503]
+		},
+		{
+			title: "Remove internal fields"
+			source: #"""
+				filter!({"_id": 1, "message": "hello"}) -> |key, value| { !starts_with(key, "_") }
+				"""#
+			return: {
+				message: "hello"
+			}
+		},
+	]
+}
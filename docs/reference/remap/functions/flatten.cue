@@ -4,6 +4,9 @@ remap: functions: flatten: {
 	category: "Enumerate"
 	description: #"""
 		Flattens the `value` into a single-level representation.
+
+		Nested arrays are merged into the outer array, and the keys of nested maps are joined to the keys
+		of their parents with the `separator`.
 		"""#
 
 	arguments: [
@@ -13,6 +16,22 @@ remap: functions: flatten: {
 			required:    true
 			type: ["array", "map"]
 		},
+		{
+			name:        "separator"
+			description: "The separator used to join the keys of nested maps."
+			required:    false
+			default:     "."
+			type: ["string"]
+		},
+		{
+			name:        "max_depth"
+			description: """
+				The maximum number of nested levels to flatten. Values nested deeper are kept as-is. A
+				`max_depth` of `0` or less doesn't flatten anything. By default, all levels are flattened.
+				"""
+			required: false
+			type: ["integer"]
+		},
 	]
 	internal_failure_reasons: []
 	return: {
@@ -44,9 +63,21 @@ remap: functions: flatten: {
 				})
 				"""#
 			return: {
-				"grandparent.parent1.child1": 1
-				"grandparent.parent1.child2": 2
-				"grandparent.parent2.child1": 2
+				"parent1.child1": 1
+				"parent1.child2": 2
+				"parent2.child3": 3
+			}
+		},
+		{
+			title: "Flatten map with a separator and a maximum depth"
+			source: #"""
+				flatten({"user": {"name": "foo", "address": {"city": "bar"}}}, separator: "_", max_depth: 1)
+				"""#
+			return: {
+				user_name: "foo"
+				user_address: {
+					city: "bar"
+				}
 			}
 		},
 	]
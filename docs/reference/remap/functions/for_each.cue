@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: for_each: {
+	category: "Enumerate"
+	description: """
+		Runs the closure for each element of the `value` map or array, for its side effects, like
+		assigning to variables or to event fields. The value returned by the closure is discarded.
+
+		The closure is called with the element as `|value|`, or with its key (for maps) or index (for
+		arrays) and the element as `|key_or_index, value|`. Map elements are visited in key order.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map or array to iterate over."
+			required:    true
+			type: ["array", "map"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure fails for one of the elements.",
+	]
+	return: {
+		types: ["null"]
+	}
+	examples: [
+		{
+			title: "Sum the elements of an array"
+			source: #"""
+				sum = 0
+				for_each!([1, 2, 3]) -> |value| { sum = sum + value }
+				sum
+				"""#
+			return: 6
+		},
+		{
+			title: "Collect the elements of a map"
+			source: #"""
+				keys = []
+				for_each!({"env": "prod", "region": "eu"}) -> |key, value| { keys = push(keys, key + "=" + value) }
+				keys
+				"""#
+			return: ["env=prod", "region=eu"]
+		},
+	]
+}
@@ -19,8 +19,20 @@ remap: functions: format_timestamp: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "timezone"
+			description: """
+				The [time zone](\(urls.iana_time_zones)) to render the timestamp in, such as `America/New_York`, or
+				`local` for the system time zone. The timestamp is rendered in UTC when not set.
+				"""
+			required:    false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`format` is not a valid format string",
+		"`timezone` is not a known time zone",
 	]
-	internal_failure_reasons: []
 	return: types: ["string"]
 
 	examples: [
@@ -38,5 +50,12 @@ remap: functions: format_timestamp: {
 				"""#
 			return: "10-Oct-2020 16:00"
 		},
+		{
+			title: "Format a timestamp in another time zone"
+			source: #"""
+				format_timestamp(to_timestamp("2020-10-21T16:00:00Z"), format: "%+", timezone: "America/New_York")
+				"""#
+			return: "2020-10-21T12:00:00-04:00"
+		},
 	]
 }
@@ -0,0 +1,60 @@
+package metadata
+
+remap: functions: from_unix_timestamp: {
+	category:    "Coerce"
+	description: """
+		Converts the `value`, a [Unix timestamp](\(urls.unix_timestamp)), into a timestamp.
+
+		By default, the `value` is the number of seconds since the Unix epoch, but milliseconds or nanoseconds can be
+		given via the `unit` argument.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The Unix timestamp to convert."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "unit"
+			description: "The time unit of the `value`"
+			type: ["string"]
+			required: false
+			enum: {
+				seconds:      "Read Unix time in seconds"
+				milliseconds: "Read Unix time in milliseconds"
+				nanoseconds:  "Read Unix time in nanoseconds"
+			}
+			default: "seconds"
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is out of the range of supported timestamps",
+	]
+	return: types: ["timestamp"]
+
+	examples: [
+		{
+			title: "Convert from a Unix timestamp (seconds)"
+			source: #"""
+				from_unix_timestamp(1609459200)
+				"""#
+			return: "2021-01-01T00:00:00Z"
+		},
+		{
+			title: "Convert from a Unix timestamp (milliseconds)"
+			source: #"""
+				from_unix_timestamp(1609459200123, unit: "milliseconds")
+				"""#
+			return: "2021-01-01T00:00:00.123Z"
+		},
+		{
+			title: "Convert from a Unix timestamp (nanoseconds)"
+			source: #"""
+				from_unix_timestamp(1609459200123456789, unit: "nanoseconds")
+				"""#
+			return: "2021-01-01T00:00:00.123456789Z"
+		},
+	]
+}
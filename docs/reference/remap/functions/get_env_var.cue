@@ -3,7 +3,8 @@ package metadata
 remap: functions: get_env_var: {
 	category: "System"
 	description: """
-		Gets the value of the environment variable specifed by `name`.
+		Gets the value of the environment variable specifed by `name`. The variable is
+		read every time the function is called, unless `cache` is `true`.
 		"""
 
 	arguments: [
@@ -13,6 +14,13 @@ remap: functions: get_env_var: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "cache"
+			description: "If `true`, every variable is only read the first time it is found, and its value is reused afterwards. Must be a literal."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
 		"Environment variable `name` does not exist",
@@ -28,5 +36,13 @@ remap: functions: get_env_var: {
 				"""#
 			return: "/root"
 		},
+		{
+			title: "Stamp events with the region"
+			input: log: {}
+			source: #"""
+				.region = get_env_var!("AWS_REGION", cache: true)
+				"""#
+			output: log: region: "us-east-1"
+		},
 	]
 }
@@ -3,10 +3,19 @@ package metadata
 remap: functions: get_hostname: {
 	category: "System"
 	description: """
-		Gets the local system's hostname.
+		Gets the local system's hostname. The hostname is read every time the function
+		is called, unless `cache` is `true`.
 		"""
 
-	arguments: []
+	arguments: [
+		{
+			name:        "cache"
+			description: "If `true`, the hostname is only read by the first successful call, and reused afterwards. Must be a literal."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
 	internal_failure_reasons: []
 	return: types: ["string"]
 
@@ -0,0 +1,63 @@
+package metadata
+
+remap: functions: get_metadata_field: {
+	category: "Event"
+	description: """
+		Gets the value of the given metadata `key` of the event, or `null` if the event
+		carries no such metadata.
+
+		Metadata holds transport-level information that isn't part of the event itself:
+
+		* `vector.source_type`: the type of the source that received the event.
+		* `vector.ingest_timestamp`: when the event was received by Vector.
+		* `vector.original_timestamp`: the timestamp the event had before it was
+		  corrected by the `clock_skew` option of its source.
+		* `kafka.topic`, `kafka.partition`, `kafka.offset` and `kafka.headers`: where
+		  the event was read from, for events received by the `kafka` source.
+		* `http.headers`: the headers of the request, by their lowercase name, for
+		  events received by the `http` source.
+
+		Metrics only carry `vector.ingest_timestamp`.
+
+		Metadata is kept in memory only, so it isn't available anymore once the event
+		went through a disk buffer or was forwarded to another Vector instance.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The metadata key to get."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["any"]
+		rules: [
+			"If the event carries no metadata for the `key`, `null` is returned.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Get the Kafka partition"
+			input: log: {}
+			source: #"""
+				.partition = get_metadata_field("kafka.partition")
+				"""#
+			output: log: partition: 3
+		},
+		{
+			title: "Get the request headers"
+			input: log: {}
+			source: #"""
+				.headers = get_metadata_field("http.headers")
+				"""#
+			output: log: headers: {
+				"user-agent":  "curl/7.68.0"
+				"x-tenant-id": "acme"
+			}
+		},
+	]
+}
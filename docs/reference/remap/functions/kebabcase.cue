@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: kebabcase: {
+	category: "String"
+	description: """
+		Converts the `value` to kebab case, like `kebab-case`.
+
+		The `value` is split into words on non-alphanumeric characters, like spaces, `-` or `_`, and on
+		transitions from lowercase to uppercase letters.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to convert to kebab case."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Kebab case a key"
+			source: #"""
+				kebabcase("user_id")
+				"""#
+			return: "user-id"
+		},
+		{
+			title: "Kebab case words"
+			source: #"""
+				kebabcase("HTTP response code")
+				"""#
+			return: "http-response-code"
+		},
+	]
+}
@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: levenshtein: {
+	category: "String"
+	description: """
+		Computes the [Levenshtein distance](\(urls.levenshtein_distance)) of the strings `a`
+		and `b`: the number of single-character insertions, deletions and substitutions
+		needed to turn `a` into `b`.
+		"""
+
+	arguments: [
+		{
+			name:        "a"
+			description: "The first string to compare."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "b"
+			description: "The second string to compare."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Distance of two words"
+			source: #"""
+				levenshtein("kitten", "sitting")
+				"""#
+			return: 3
+		},
+		{
+			title: "Detect a typo-squatted domain"
+			input: log: domain: "paypa1.com"
+			source: #"""
+				.suspicious = .domain != "paypal.com" && levenshtein!(.domain, "paypal.com") <= 2
+				"""#
+			output: log: {
+				domain:     "paypa1.com"
+				suspicious: true
+			}
+		},
+	]
+}
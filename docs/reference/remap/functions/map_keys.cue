@@ -0,0 +1,59 @@
+package metadata
+
+remap: functions: map_keys: {
+	category: "Enumerate"
+	description: """
+		Renames the keys of the `value` map to the value returned by the closure, which is called with
+		each key as `|key|`. The values are kept as-is.
+
+		If the closure returns the same key for more than one field, the value of the last field (in
+		key order) is kept.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map to rename the keys of."
+			required:    true
+			type: ["map"]
+		},
+		{
+			name:        "recursive"
+			description: "Whether to also rename the keys of nested maps, including maps within arrays."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure fails for one of the keys.",
+		"The closure returns a value that isn't a string.",
+	]
+	return: {
+		types: ["map"]
+	}
+	examples: [
+		{
+			title: "Lowercase all keys"
+			source: #"""
+				map_keys!({"Host": "foo", "USER_AGENT": "curl"}) -> |key| { downcase(key) }
+				"""#
+			return: {
+				host:       "foo"
+				user_agent: "curl"
+			}
+		},
+		{
+			title: "Prefix all keys, recursively"
+			source: #"""
+				map_keys!({"id": 1, "user": {"name": "foo"}}, recursive: true) -> |key| { "app_" + key }
+				"""#
+			return: {
+				app_id: 1
+				app_user: {
+					app_name: "foo"
+				}
+			}
+		},
+	]
+}
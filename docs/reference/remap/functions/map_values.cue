@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: map_values: {
+	category: "Enumerate"
+	description: """
+		Runs the closure for each element of the `value` map or array, and replaces the element with the
+		value the closure returns.
+
+		The closure is called with the element as `|value|`, or with its key (for maps) or index (for
+		arrays) and the element as `|key_or_index, value|`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map or array whose elements are transformed."
+			required:    true
+			type: ["array", "map"]
+		},
+	]
+	internal_failure_reasons: [
+		"The closure fails for one of the elements.",
+	]
+	return: {
+		types: ["array", "map"]
+		rules: [
+			"The return type will match the `value` type.",
+			"Map keys are left untouched.",
+		]
+	}
+	examples: [
+		{
+			title: "Upcase the elements of an array"
+			source: #"""
+				map_values!(["foo", "bar"]) -> |item| { upcase(item) }
+				"""#
+			return: ["FOO", "BAR"]
+		},
+		{
+			title: "Use the map keys"
+			source: #"""
+				map_values!({"env": "prod", "region": "eu"}) -> |key, value| { key + "=" + value }
+				"""#
+			return: {
+				env:    "env=prod"
+				region: "region=eu"
+			}
+		},
+	]
+}
@@ -0,0 +1,66 @@
+package metadata
+
+remap: functions: match_query: {
+	category: "Map"
+	description: """
+		Determines if the `value` matches the search `query`.
+
+		The query uses a familiar search syntax:
+
+		* `field:value` matches a field exactly, with nested fields addressed as `parent.child`.
+		* `field:"quoted value"` allows values containing whitespace or special characters.
+		* `field:web*` and `field:web?` match using wildcards.
+		* `field:*` matches if the field exists and isn't `null`.
+		* `field:>400`, `field:>=400`, `field:<400` and `field:<=400` compare numbers.
+		* A term without a field, such as `timeout`, is searched for in the `message` field.
+		* Terms are combined with `AND`, `OR` and `NOT` (or a leading `-`), and grouped with parentheses.
+		  Terms separated by whitespace only are combined with `AND`, and `AND` binds tighter than `OR`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map to match, usually the event itself (`.`)."
+			required:    true
+			type: ["map"]
+		},
+		{
+			name:        "query"
+			description: "The search query, which must be a string literal."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+		rules: [
+			"A field holding an array matches if any of its elements match.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Match an event"
+			input: log: {
+				status:  "error"
+				service: "web-frontend"
+			}
+			source: #"""
+				match_query!(., "status:error AND service:web*")
+				"""#
+			return: true
+		},
+		{
+			title: "Match nested fields and ranges"
+			input: log: {
+				http: status_code: 503
+				message: "upstream timeout"
+			}
+			source: #"""
+				match_query!(., "http.status_code:>=500 AND NOT (timeout OR refused)")
+				"""#
+			return: false
+		},
+	]
+}
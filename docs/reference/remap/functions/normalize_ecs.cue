@@ -0,0 +1,82 @@
+package metadata
+
+remap: functions: normalize_ecs: {
+	category: "Map"
+	description: """
+		Normalizes commonly used field names in the `value` to their [Elastic Common Schema](\(urls.ecs))
+		equivalents, converting the values to the type ECS expects.
+
+		| Fields | ECS field |
+		| :----- | :-------- |
+		| `@timestamp`, `time`, `timestamp` | `@timestamp` |
+		| `message`, `msg` | `message` |
+		| `level`, `log_level`, `severity` | `log.level` (lowercased) |
+		| `host`, `hostname` | `host.name` |
+		| `client_ip`, `clientip`, `ip`, `remote_addr`, `source_ip`, `src_ip`, `srcip` | `source.ip` |
+		| `source_port`, `src_port`, `srcport` | `source.port` (integer) |
+		| `destination_ip`, `dst_ip`, `dstip` | `destination.ip` |
+		| `destination_port`, `dst_port`, `dstport` | `destination.port` (integer) |
+		| `proto`, `protocol`, `transport` | `network.transport` (lowercased) |
+		| `user`, `user_name`, `username` | `user.name` |
+		| `http_method`, `method`, `request_method` | `http.request.method` (uppercased) |
+		| `http_referer`, `referer`, `referrer` | `http.request.referrer` |
+		| `http_status`, `status`, `status_code` | `http.response.status_code` (integer) |
+		| `body_bytes_sent`, `bytes`, `bytes_sent`, `size` | `http.response.body.bytes` (integer) |
+		| `agent`, `http_user_agent`, `user_agent`, `useragent` | `user_agent.original` |
+		| `path`, `request_path`, `uri_path` | `url.path` |
+		| `query`, `query_string`, `uri_query` | `url.query` |
+		| `request_uri`, `request_url`, `uri`, `url` | `url.original`, and `url.scheme`, `url.domain`, `url.port`, `url.path`, `url.query` and `url.fragment` |
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map to normalize, usually the event itself (`.`)."
+			required:    true
+			type: ["map"]
+		},
+		{
+			name:        "keep_unmapped"
+			description: "Should fields that aren't normalized be kept (unchanged) in the result."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["map"]
+		rules: [
+			"Only top-level fields of the `value` are normalized.",
+			"A field whose value can't be converted to the expected type is left untouched.",
+			"Normalized fields are merged into existing maps, so `hostname` is added to an existing `host` map as `host.name`.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Normalize an access log"
+			input: log: {
+				remote_addr: "10.0.0.1"
+				method:      "get"
+				status:      "404"
+				uri:         "/index.html?page=1"
+			}
+			source: #"""
+				. = normalize_ecs!(.)
+				"""#
+			return: {
+				source: ip: "10.0.0.1"
+				http: {
+					request: method: "GET"
+					response: status_code: 404
+				}
+				url: {
+					original: "/index.html?page=1"
+					path:     "/index.html"
+					query:    "page=1"
+				}
+			}
+		},
+	]
+}
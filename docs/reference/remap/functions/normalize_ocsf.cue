@@ -0,0 +1,97 @@
+package metadata
+
+remap: functions: normalize_ocsf: {
+	category: "Map"
+	description: """
+		Normalizes commonly used field names in the `value` to the attributes of the given
+		[Open Cybersecurity Schema Framework](\(urls.ocsf)) `class`, converting the values to the type OCSF
+		expects, and sets the class, category, activity and severity identifiers.
+
+		| Fields | OCSF attribute | Classes |
+		| :----- | :------------- | :------ |
+		| `@timestamp`, `time`, `timestamp` | `time` (milliseconds since the Unix epoch) | all |
+		| `message`, `msg` | `message` | all |
+		| `level`, `log_level`, `severity` | `severity` (lowercased), and `severity_id` | all |
+		| `host`, `hostname` | `device.hostname` | all |
+		| `client_ip`, `clientip`, `source_ip`, `src_ip`, `srcip` | `src_endpoint.ip` | all |
+		| `source_port`, `src_port`, `srcport` | `src_endpoint.port` (integer) | all |
+		| `destination_ip`, `dst_ip`, `dstip` | `dst_endpoint.ip` | all |
+		| `destination_port`, `dst_port`, `dstport` | `dst_endpoint.port` (integer) | all |
+		| `action`, `activity`, `event_type` | `activity_name` (lowercased), and `activity_id` | all |
+		| `user`, `user_name`, `username` | `user.name` | `authentication` |
+		| `auth_method`, `auth_protocol`, `method` | `auth_protocol` | `authentication` |
+		| `outcome`, `result`, `status` | `status` (lowercased), and `status_id` | `authentication` |
+		| `proto`, `protocol`, `transport` | `connection_info.protocol_name` (lowercased) | `network_activity` |
+		| `bytes_in`, `bytes_received`, `rcvd_bytes` | `traffic.bytes_in` (integer) | `network_activity` |
+		| `bytes_out`, `bytes_sent`, `sent_bytes` | `traffic.bytes_out` (integer) | `network_activity` |
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map to normalize, usually the event itself (`.`)."
+			required:    true
+			type: ["map"]
+		},
+		{
+			name:        "class"
+			description: "The OCSF class to normalize the `value` to."
+			required:    true
+			type: ["string"]
+			enum: {
+				authentication:   "The Authentication class (`3002`), for logon and logoff attempts."
+				network_activity: "The Network Activity class (`4001`), for connections and network traffic."
+			}
+		},
+		{
+			name:        "keep_unmapped"
+			description: "Should fields that aren't normalized be kept, in the `unmapped` attribute."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`class` is not a known OCSF class",
+		"An attribute required by the `class` is missing: `time` and `user.name` for `authentication`, `time`, `src_endpoint.ip` and `dst_endpoint.ip` for `network_activity`.",
+	]
+	return: {
+		types: ["map"]
+		rules: [
+			"Only top-level fields of the `value` are normalized.",
+			"A field whose value can't be converted to the expected type is kept in the `unmapped` attribute.",
+			"The `activity_id` is `0` (unknown) if no activity is found, and `99` (other) if it isn't one of the class' activities.",
+			"The `severity_id` is `0` (unknown) if no severity is found, and `99` (other) if it isn't a known severity or log level.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Normalize an authentication log"
+			input: log: {
+				timestamp: "2021-02-03T04:05:06Z"
+				username:  "ben"
+				action:    "login"
+				outcome:   "success"
+			}
+			source: #"""
+				. = normalize_ocsf!(., class: "authentication")
+				"""#
+			return: {
+				time: 1612325106000
+				user: name: "ben"
+				activity_name: "login"
+				status:        "success"
+				status_id:     1
+				class_uid:     3002
+				class_name:    "Authentication"
+				category_uid:  3
+				category_name: "Identity & Access Management"
+				activity_id:   1
+				type_uid:      300201
+				severity_id:   0
+				metadata: version: "1.0.0"
+			}
+		},
+	]
+}
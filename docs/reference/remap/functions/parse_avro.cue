@@ -0,0 +1,75 @@
+package metadata
+
+remap: functions: parse_avro: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as an [Avro](\(urls.avro)) record, written with the given `schema`.
+
+		This handles Avro payloads in events that are otherwise read as raw bytes, such as the
+		messages of a Kafka topic only some producers write Avro to.
+		"""
+	notices: [
+		"""
+			The schema isn't fetched from a schema registry, it must be provided as a string literal. For payloads
+			in the [Confluent wire format](\(urls.confluent_wire_format)), set `schema_id` to the registry ID of the
+			schema to strip and check the header of the payloads.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The Avro payload to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "schema"
+			description: "The JSON Avro schema the payload was written with, which must describe a record and be a string literal."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "schema_id"
+			description: "The schema registry ID of `schema`. If set, the payload must start with the Confluent wire format header naming this ID."
+			required:    false
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not a valid Avro payload for `schema`",
+		"`value` doesn't start with the Confluent wire format header for `schema_id`",
+		"`value` contains decimal or duration values, which aren't supported",
+	]
+	return: types: ["map"]
+
+	examples: [
+		{
+			title: "Parse Avro"
+			source: #"""
+				parse_avro!(
+					decode_base64!("BmZvbwo="),
+					schema: "{\"type\": \"record\", \"name\": \"event\", \"fields\": [{\"name\": \"name\", \"type\": \"string\"}, {\"name\": \"count\", \"type\": \"long\"}]}"
+				)
+				"""#
+			return: {
+				name:  "foo"
+				count: 5
+			}
+		},
+		{
+			title: "Parse Avro in the Confluent wire format"
+			source: #"""
+				parse_avro!(
+					decode_base64!("AAAAAAEGZm9vCg=="),
+					schema: "{\"type\": \"record\", \"name\": \"event\", \"fields\": [{\"name\": \"name\", \"type\": \"string\"}, {\"name\": \"count\", \"type\": \"long\"}]}",
+					schema_id: 1
+				)
+				"""#
+			return: {
+				name:  "foo"
+				count: 5
+			}
+		},
+	]
+}
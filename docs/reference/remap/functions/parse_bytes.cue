@@ -0,0 +1,65 @@
+package metadata
+
+remap: functions: parse_bytes: {
+	category: "Parse"
+	description: """
+		Parses the `value` in a human readable size format, like `1.5GiB` or `300k`, and returns it as a number
+		of `unit`.
+
+		Units with an `i`, like `KiB`, are binary multiples of 1024 bytes, the others, like `kB` or `k`, are
+		decimal multiples of 1000 bytes. Units are case insensitive. A size without a unit is a number of bytes.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string of the size."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "unit"
+			description: "The unit the number should be output as."
+			required:    false
+			default:     "B"
+			type: ["string"]
+			enum: {
+				B:   "Bytes"
+				kB:  "Kilobytes (1000 bytes)"
+				MB:  "Megabytes (1000 kilobytes)"
+				GB:  "Gigabytes (1000 megabytes)"
+				TB:  "Terabytes (1000 gigabytes)"
+				PB:  "Petabytes (1000 terabytes)"
+				EB:  "Exabytes (1000 petabytes)"
+				KiB: "Kibibytes (1024 bytes)"
+				MiB: "Mebibytes (1024 kibibytes)"
+				GiB: "Gibibytes (1024 mebibytes)"
+				TiB: "Tebibytes (1024 gibibytes)"
+				PiB: "Pebibytes (1024 tebibytes)"
+				EiB: "Exbibytes (1024 pebibytes)"
+			}
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not a properly formatted size",
+		"`unit` is not a known unit",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Parse bytes"
+			source: #"""
+				parse_bytes("1.5KiB")
+				"""#
+			return: 1536.0
+		},
+		{
+			title: "Parse bytes (megabytes)"
+			source: #"""
+				parse_bytes("300k", unit: "MB")
+				"""#
+			return: 0.3
+		},
+	]
+}
@@ -23,9 +23,9 @@ remap: functions: parse_grok: {
 		},
 		{
 			name:        "pattern"
-			description: "The [Grok pattern](https://github.com/daschl/grok/tree/master/patterns)."
+			description: "The [Grok pattern](https://github.com/daschl/grok/tree/master/patterns), or an array of patterns that are tried in order until one matches."
 			required:    true
-			type: ["string"]
+			type: ["array", "string"]
 		},
 		{
 			name:        "remove_empty"
@@ -34,9 +34,15 @@ remap: functions: parse_grok: {
 			default:     true
 			type: ["boolean"]
 		},
+		{
+			name:        "aliases"
+			description: "A map of user-defined pattern definitions, which can be referenced by name from the `pattern` and from each other, like the files in Logstash's `patterns_dir`."
+			required:    false
+			type: ["map"]
+		},
 	]
 	internal_failure_reasons: [
-		"`value` fails to parse via the provided `pattern`",
+		"`value` fails to parse via any of the provided patterns",
 	]
 	return: types: ["map"]
 
@@ -55,5 +61,25 @@ remap: functions: parse_grok: {
 				message:   "Hello world"
 			}
 		},
+		{
+			title: "Parse via multiple Grok patterns and aliases"
+			source: #"""
+				parse_grok(
+					"user=ben action=login",
+					[
+						"%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+						"%{USER_FIELD} %{ACTION_FIELD}"
+					],
+					aliases: {
+						"USER_FIELD": "user=%{USERNAME:user}",
+						"ACTION_FIELD": "action=%{WORD:action}"
+					}
+				)
+				"""#
+			return: {
+				user:   "ben"
+				action: "login"
+			}
+		},
 	]
 }
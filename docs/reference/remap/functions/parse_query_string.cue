@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: parse_query_string: {
+	category:    "Parse"
+	description: #"""
+		Parses the `value` as a query string.
+
+		* Keys and values are percent-decoded, and `+` is decoded as a space.
+		* A leading `?` is ignored, so the query part of a URL can be passed in directly.
+		"""#
+	notices: [
+		"""
+			All values are returned as strings. Keys that appear more than once are returned as an array of strings,
+			in the order they appear in the query string.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["map"]
+
+	examples: [
+		{
+			title: "Parse query string"
+			source: #"""
+				parse_query_string("foo=%2B1&bar=2&bar=3&xyz")
+				"""#
+			return: {
+				foo: "+1"
+				bar: ["2", "3"]
+				xyz: ""
+			}
+		},
+	]
+}
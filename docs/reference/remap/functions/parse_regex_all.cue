@@ -23,14 +23,13 @@ remap: functions: parse_regex_all: {
 			type: ["regex"]
 		},
 	]
-	internal_failure_reasons: [
-		"`value` fails to parse via the provided `pattern`",
-	]
+	internal_failure_reasons: []
 	return: {
 		types: ["array"]
 		rules: [
 			"Matches will return all capture groups corresponding to the leftmost matches in the text.",
-			"If no match is found an empty map is returned.",
+			"Matches never overlap, each match starts after the end of the previous one.",
+			"If no match is found an empty array is returned.",
 		]
 	}
 
@@ -53,5 +52,34 @@ remap: functions: parse_regex_all: {
 				},
 			]
 		},
+		{
+			title: "Extract all key/value pairs"
+			source: #"""
+				parse_regex_all("user=ben action=login status=ok", /(?P<key>\w+)=(?P<value>\w+)/)
+				"""#
+			return: [
+				{
+					key:   "user"
+					value: "ben"
+					"0":   "user=ben"
+					"1":   "user"
+					"2":   "ben"
+				},
+				{
+					key:   "action"
+					value: "login"
+					"0":   "action=login"
+					"1":   "action"
+					"2":   "login"
+				},
+				{
+					key:   "status"
+					value: "ok"
+					"0":   "status=ok"
+					"1":   "status"
+					"2":   "ok"
+				},
+			]
+		},
 	]
 }
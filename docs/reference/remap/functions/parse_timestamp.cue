@@ -19,10 +19,21 @@ remap: functions: parse_timestamp: {
 			required:    true
 			type: ["string"]
 		},
-
+		{
+			name:        "timezone"
+			description: """
+				The [time zone](\(urls.iana_time_zones)) of timestamps that don't carry an offset, such as
+				`America/New_York`, or `local` for the system time zone. By default, the `timezone` of the `remap`
+				transform is used. An offset in the timestamp itself always takes precedence.
+				"""
+			required:    false
+			default:     "local"
+			type: ["string"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse via the provided `format`",
+		"`timezone` is not a known time zone",
 	]
 	return: types: ["timestamp"]
 
@@ -34,5 +45,12 @@ remap: functions: parse_timestamp: {
 				"""#
 			return: "2020-10-10T16:00:00Z"
 		},
+		{
+			title: "Parse timestamp in a time zone"
+			source: #"""
+				parse_timestamp("10-Oct-2020 16:00", format: "%v %R", timezone: "America/New_York")
+				"""#
+			return: "2020-10-10T20:00:00Z"
+		},
 	]
 }
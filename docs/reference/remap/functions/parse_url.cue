@@ -13,11 +13,31 @@ remap: functions: parse_url: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "default_known_ports"
+			description: "If `true`, the port is set to the well-known port of the scheme (e.g. `443` for `https`) when the url doesn't specify one."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+		{
+			name:        "decode_punycode"
+			description: "If `true`, internationalized domain names encoded in [Punycode](\(urls.punycode)) (`xn--`) are decoded to their unicode form."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` is not a properly formatted URL",
 	]
-	return: types: ["map"]
+	return: {
+		types: ["map"]
+		rules: [
+			"`path_segments` contains the non-empty segments of the `path`, in order.",
+			"`port` is `null` if the url doesn't specify a port, unless `default_known_ports` is `true` and the scheme has a well-known port.",
+		]
+	}
 
 	examples: [
 		{
@@ -32,9 +52,27 @@ remap: functions: parse_url: {
 				host:     "vector.dev"
 				port:     4343
 				path:     "/foobar"
+				path_segments: ["foobar"]
 				query: hello: "world"
 				fragment: "123"
 			}
 		},
+		{
+			title: "Parse URL with default port and unicode host"
+			source: #"""
+				parse_url("https://www.xn--caf-dma.com/menu/drinks", default_known_ports: true, decode_punycode: true)
+				"""#
+			return: {
+				scheme:   "https"
+				username: ""
+				password: ""
+				host:     "www.café.com"
+				port:     443
+				path:     "/menu/drinks"
+				path_segments: ["menu", "drinks"]
+				query: {}
+				fragment: null
+			}
+		},
 	]
 }
@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: pascalcase: {
+	category: "String"
+	description: """
+		Converts the `value` to Pascal case, like `PascalCase`.
+
+		The `value` is split into words on non-alphanumeric characters, like spaces, `-` or `_`, and on
+		transitions from lowercase to uppercase letters.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to convert to Pascal case."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Pascal case a key"
+			source: #"""
+				pascalcase("user_id")
+				"""#
+			return: "UserId"
+		},
+		{
+			title: "Pascal case words"
+			source: #"""
+				pascalcase("HTTP response code")
+				"""#
+			return: "HttpResponseCode"
+		},
+	]
+}
@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: random_bytes: {
+	category:    "Random"
+	description: """
+		Returns a string of `length` random bytes, which can be encoded, for example
+		with `encode_base64`, to generate identifiers.
+		"""
+
+	arguments: [
+		{
+			name:        "length"
+			description: "The number of bytes to generate, at most 65536."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`length` is negative or larger than 65536.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Generate a trace ID"
+			source: #"""
+				encode_base64(random_bytes!(16))
+				"""#
+			return: "yxjZ3K2d0pGmXHz0oXbq9w=="
+		},
+	]
+}
@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: random_int: {
+	category:    "Random"
+	description: """
+		Returns a random integer between `min`, inclusive, and `max`, exclusive.
+		"""
+
+	arguments: [
+		{
+			name:        "min"
+			description: "The smallest integer that can be returned."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "max"
+			description: "The integer above the largest one that can be returned."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`max` is not greater than `min`.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Sample one event out of ten"
+			source: #"""
+				if random_int!(0, 10) != 0 {
+					abort
+				}
+				"""#
+			return: null
+		},
+		{
+			title: "Generate a random integer"
+			source: #"""
+				random_int!(1, 7)
+				"""#
+			return: 4
+		},
+	]
+}
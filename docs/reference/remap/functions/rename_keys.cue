@@ -0,0 +1,76 @@
+package metadata
+
+remap: functions: rename_keys: {
+	category: "Enumerate"
+	description: """
+		Renames the keys of the `value` in bulk, using a `mapping` table, a regular expression `pattern`
+		and its replacement, or both.
+
+		This is useful to normalize vendor-specific field names without writing an assignment per field.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map or array whose keys are renamed."
+			required:    true
+			type: ["array", "map"]
+		},
+		{
+			name:        "mapping"
+			description: "A map from the original key to its new name."
+			required:    false
+			type: ["map"]
+		},
+		{
+			name:        "pattern"
+			description: "Rename all keys matching this regular expression. Requires `with`."
+			required:    false
+			type: ["regex"]
+		},
+		{
+			name:        "with"
+			description: "The replacement for the matches of `pattern`. Capture groups can be referenced with `$1` or `$name`."
+			required:    false
+			type: ["string"]
+		},
+		{
+			name:        "recursive"
+			description: "Should the keys of nested maps, including maps within arrays, be renamed."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array", "map"]
+		rules: [
+			"The return type will match the `value` type.",
+			"A key found in `mapping` is renamed to its mapped name, and `pattern` isn't applied to it.",
+			"If two keys are renamed to the same name, the value of the last key, in alphabetical order, is kept.",
+		]
+	}
+	examples: [
+		{
+			title: "Rename keys using a mapping"
+			source: #"""
+				rename_keys({"srcip": "10.0.0.1", "dstport": 443}, mapping: {"srcip": "source.ip", "dstport": "destination.port"})
+				"""#
+			return: {
+				"source.ip":        "10.0.0.1"
+				"destination.port": 443
+			}
+		},
+		{
+			title: "Rename keys using a pattern"
+			source: #"""
+				rename_keys({"http-method": "GET", "request": {"user-agent": "curl"}}, pattern: /-/, with: "_")
+				"""#
+			return: {
+				http_method: "GET"
+				request: user_agent: "curl"
+			}
+		},
+	]
+}
@@ -0,0 +1,71 @@
+package metadata
+
+remap: functions: rename_keys_case: {
+	category: "Enumerate"
+	description: """
+		Converts all the keys of the `value` to the given `case`, like `snakecase` does for a single string.
+
+		This is useful to normalize the field naming convention of a producer to the one of the downstream
+		schema.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map or array whose keys are renamed."
+			required:    true
+			type: ["array", "map"]
+		},
+		{
+			name:        "case"
+			description: "The case to convert the keys to."
+			required:    true
+			type: ["string"]
+			enum: {
+				camel:  "Camel case, like `camelCase`"
+				kebab:  "Kebab case, like `kebab-case`"
+				pascal: "Pascal case, like `PascalCase`"
+				snake:  "Snake case, like `snake_case`"
+			}
+		},
+		{
+			name:        "recursive"
+			description: "Should the keys of nested maps, including maps within arrays, be renamed."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`case` is not a known case",
+	]
+	return: {
+		types: ["array", "map"]
+		rules: [
+			"The return type will match the `value` type.",
+			"If two keys are renamed to the same name, the value of the last key, in alphabetical order, is kept.",
+		]
+	}
+	examples: [
+		{
+			title: "Convert keys to snake case"
+			source: #"""
+				rename_keys_case!({"userId": 1, "request": {"userAgent": "curl"}}, case: "snake")
+				"""#
+			return: {
+				user_id: 1
+				request: user_agent: "curl"
+			}
+		},
+		{
+			title: "Convert top-level keys to camel case"
+			source: #"""
+				rename_keys_case!({"user_id": 1, "request": {"user_agent": "curl"}}, case: "camel", recursive: false)
+				"""#
+			return: {
+				userId: 1
+				request: user_agent: "curl"
+			}
+		},
+	]
+}
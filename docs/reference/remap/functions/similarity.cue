@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: similarity: {
+	category: "String"
+	description: """
+		Computes how similar the strings `a` and `b` are, from `0.0` when they have
+		nothing in common to `1.0` when they are equal. This is their
+		[Levenshtein distance](\(urls.levenshtein_distance)) normalized by the length of
+		the longest one, which makes it suited to group near-duplicate messages of any
+		length.
+		"""
+
+	arguments: [
+		{
+			name:        "a"
+			description: "The first string to compare."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "b"
+			description: "The second string to compare."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Similarity of two messages"
+			source: #"""
+				similarity("Connection reset by peer 10.0.0.1", "Connection reset by peer 10.0.0.2")
+				"""#
+			return: 0.9696969696969697
+		},
+	]
+}
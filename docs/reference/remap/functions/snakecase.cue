@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: snakecase: {
+	category: "String"
+	description: """
+		Converts the `value` to snake case, like `snake_case`.
+
+		The `value` is split into words on non-alphanumeric characters, like spaces, `-` or `_`, and on
+		transitions from lowercase to uppercase letters.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to convert to snake case."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Snake case a key"
+			source: #"""
+				snakecase("user_id")
+				"""#
+			return: "user_id"
+		},
+		{
+			title: "Snake case words"
+			source: #"""
+				snakecase("HTTP response code")
+				"""#
+			return: "http_response_code"
+		},
+	]
+}
@@ -3,7 +3,8 @@ package metadata
 remap: functions: strip_whitespace: {
 	category:    "String"
 	description: """
-		Strips whitespace from the start and end of the `value`.
+		Strips whitespace from the start and end of the `value`, or the given
+		`characters` if any.
 
 		Whitespace is as defined by [Unicode `White_Space` property](\(urls.unicode_whitespace))
 		"""
@@ -15,6 +16,12 @@ remap: functions: strip_whitespace: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "characters"
+			description: "The characters to strip instead of whitespace. Every character of the string is stripped, in any order."
+			required:    false
+			type: ["string"]
+		},
 	]
 	internal_failure_reasons: []
 	return: types: ["string"]
@@ -27,5 +34,12 @@ remap: functions: strip_whitespace: {
 				"""#
 			return: "A sentence."
 		},
+		{
+			title: "Strip characters"
+			source: #"""
+				strip_whitespace("--== A title ==--", characters: "=- ")
+				"""#
+			return: "A title"
+		},
 	]
 }
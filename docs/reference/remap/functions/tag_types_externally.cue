@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: tag_types_externally: {
+	category: "Type"
+	description: """
+		Tags the values within the `value` with their type, by wrapping each of them in a map with the name of
+		its type as the single key, like `{"integer": 200}`.
+
+		This keeps the type information of the values when encoding them in a format that loses it, like
+		strings and timestamps that both end up as JSON strings.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to tag with its type."
+			required:    true
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["map", "array", "null"]
+		rules: [
+			"If `value` is a map or an array, its values are tagged recursively.",
+			"If `value` is `null`, then `null` is returned.",
+			"Other values are wrapped in a map keyed by their type: `string`, `integer`, `float`, `boolean`, `timestamp` or `regex`.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Tag types externally (scalar)"
+			source: #"""
+				tag_types_externally(123)
+				"""#
+			return: {
+				integer: 123
+			}
+		},
+		{
+			title: "Tag types externally (map)"
+			source: #"""
+				tag_types_externally({"message": "foo", "tags": ["bar", 1.5]})
+				"""#
+			return: {
+				message: {
+					string: "foo"
+				}
+				tags: [{string: "bar"}, {float: 1.5}]
+			}
+		},
+	]
+}
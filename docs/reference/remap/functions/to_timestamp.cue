@@ -20,6 +20,7 @@ remap: functions: to_timestamp: {
 		types: ["timestamp"]
 		rules: [
 			"If `value` is a `string`, the timestamp is parsed in these formats.",
+			"If `value` is a `string` without an offset, it is read in the `timezone` of the `remap` transform.",
 			"If `value` is an `integer`, it assumed to be a Unix representation of the timestamp (the number of seconds after January 1st, 1970).",
 		]
 	}
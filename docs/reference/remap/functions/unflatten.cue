@@ -0,0 +1,72 @@
+package metadata
+
+remap: functions: unflatten: {
+	category: "Enumerate"
+	description: #"""
+		Unflattens the `value` map, by splitting its keys on the `separator` and nesting the values in
+		maps under each part of the key. This is the reverse of [`flatten`](#flatten).
+
+		Keys are handled in order. If a key is a prefix of another key, like `a` and `a.b`, the value of
+		the shorter key is replaced by a map of the nested values, unless it already is a map.
+		"""#
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The map to unflatten."
+			required:    true
+			type: ["map"]
+		},
+		{
+			name:        "separator"
+			description: "The separator to split the keys on. An empty separator doesn't split keys."
+			required:    false
+			default:     "."
+			type: ["string"]
+		},
+		{
+			name:        "max_depth"
+			description: """
+				The maximum number of nested levels to create. The rest of longer keys is kept as-is in the
+				deepest map. A `max_depth` of `0` or less doesn't unflatten anything. By default, keys are split
+				on every separator.
+				"""
+			required: false
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["map"]
+	}
+
+	examples: [
+		{
+			title: "Unflatten map"
+			source: #"""
+				unflatten({"user.name": "foo", "user.address.city": "bar", "status": 200})
+				"""#
+			return: {
+				status: 200
+				user: {
+					name: "foo"
+					address: {
+						city: "bar"
+					}
+				}
+			}
+		},
+		{
+			title: "Unflatten map with a separator and a maximum depth"
+			source: #"""
+				unflatten({"user_name": "foo", "user_address_city": "bar"}, separator: "_", max_depth: 1)
+				"""#
+			return: {
+				user: {
+					name:         "foo"
+					address_city: "bar"
+				}
+			}
+		},
+	]
+}
@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: unnest: {
+	category: "Event"
+	description: """
+		Returns an array of copies of the current event, one for each element of the
+		array at `path`. In each copy, the array at `path` is replaced by one of its
+		elements.
+
+		Assigning the result to the root path, with `. = unnest!(.field)`, makes the
+		`remap` transform emit each element as a separate event.
+		"""
+
+	arguments: [
+		{
+			name:        "path"
+			description: "The path of the array field to unnest."
+			required:    true
+			multiple:    false
+			type: ["path"]
+		},
+	]
+	internal_failure_reasons: [
+		"The field at `path` doesn't exist.",
+		"The field at `path` isn't an array.",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Unnest an array field"
+			input: log: {
+				host: "localhost"
+				events: [{id: 1}, {id: 2}]
+			}
+			source: #"""
+				unnest!(.events)
+				"""#
+			return: [
+				{host: "localhost", events: {id: 1}},
+				{host: "localhost", events: {id: 2}},
+			]
+		},
+		{
+			title: "Emit one event per element"
+			input: log: {
+				host: "localhost"
+				events: [{id: 1}, {id: 2}]
+			}
+			source: #"""
+				. = unnest!(.events)
+				"""#
+			notes: [
+				"The `remap` transform emits two events, one with `events.id` set to `1`, and one with `events.id` set to `2`.",
+			]
+		},
+	]
+}
@@ -0,0 +1,24 @@
+package metadata
+
+remap: functions: uuid_v7: {
+	category:    "Random"
+	description: """
+		Generates a time-ordered [UUIDv7](\(urls.uuidv7)) string. The first 48 bits
+		are the current Unix timestamp in milliseconds, so UUIDs generated later sort
+		after the earlier ones, and the remaining bits are random.
+		"""
+
+	arguments: []
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Create a UUIDv7"
+			source: #"""
+				uuid_v7()
+				"""#
+			return: "01773d5c-8e2e-7b3a-9f1c-4a6f0d3e2b71"
+		},
+	]
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: collectd: {
+	name:     "collectd"
+	thing:    "a \(name) agent"
+	url:      urls.collectd
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: fluent: {
+	name:     "Fluentd and Fluent Bit"
+	thing:    "a Fluentd or Fluent Bit instance"
+	url:      urls.fluentd
+	versions: null
+
+	description: "[Fluentd](\(urls.fluentd)) and [Fluent Bit](\(urls.fluent_bit)) are open source data collectors, which forward data to each other with the forward protocol."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: graphite: {
+	name:     "Graphite"
+	thing:    "a \(name) client"
+	url:      urls.graphite
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: logstash: {
+	name:     "Beats and Logstash"
+	thing:    "a Beats or Logstash instance"
+	url:      urls.elastic_beats
+	versions: null
+
+	description: "[Beats](\(urls.elastic_beats)) are lightweight data shippers of the Elastic stack, which send data to [Logstash](\(urls.logstash)) with the Lumberjack protocol."
+}
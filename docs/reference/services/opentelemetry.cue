@@ -0,0 +1,10 @@
+package metadata
+
+services: opentelemetry: {
+	name:     "OpenTelemetry"
+	thing:    "an OpenTelemetry collector or SDK"
+	url:      urls.opentelemetry
+	versions: null
+
+	description: "[OpenTelemetry](\(urls.opentelemetry)) is a collection of APIs, SDKs and tools to instrument, generate and export telemetry data, which the [OpenTelemetry Collector](\(urls.opentelemetry_collector)) receives, processes and forwards."
+}
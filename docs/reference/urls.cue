@@ -4,6 +4,8 @@ urls: {
 	affine_type_system:                                       "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:                        "/blog/adaptive-request-concurrency/"
 	amazon_linux:                                             "https://aws.amazon.com/amazon-linux-ami/"
+	amqp:                                                     "https://www.amqp.org/"
+	amqp_publisher_confirms:                                  "https://www.rabbitmq.com/confirms.html#publisher-confirms"
 	ansi_escape_codes:                                        "\(wikipedia)/wiki/ANSI_escape_code"
 	apache:                                                   "https://httpd.apache.org"
 	apache_common:                                            "\(apache)/docs/1.3/logs.html#common"
@@ -13,6 +15,7 @@ urls: {
 	apache_mod_status:                                        "http://httpd.apache.org/docs/current/mod/mod_status.html"
 	apt:                                                      "\(wikipedia)/wiki/APT_(software)"
 	arm:                                                      "\(wikipedia)/wiki/ARM_architecture"
+	avro:                                                     "https://avro.apache.org/docs/current/spec.html"
 	aws_access_keys:                                          "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
 	aws_arm_g2_announcement:                                  "https://aws.amazon.com/about-aws/whats-new/2019/12/announcing-new-amazon-ec2-m6g-c6g-and-r6g-instances-powered-by-next-generation-arm-based-aws-graviton2-processors/"
 	aws_athena:                                               "https://aws.amazon.com/athena/"
@@ -58,6 +61,7 @@ urls: {
 	aws_kinesis_streams_api:                                  "\(aws_docs)/kinesis/latest/APIReference/API_PutRecords.html"
 	aws_kinesis_streams_service_limits:                       "\(aws_docs)/streams/latest/dev/service-sizes-and-limits.html"
 	aws_kinesis_split_shards:                                 "\(aws_docs)/streams/latest/dev/kinesis-using-sdk-java-resharding-split.html"
+	aws_msk:                                                  "\(aws_docs)/msk/latest/developerguide/iam-access-control.html"
 	aws_regions:                                              "\(aws_docs)/AmazonRDS/latest/UserGuide/Concepts.RegionsAndAvailabilityZones.html"
 	aws_s3:                                                   "https://aws.amazon.com/s3/"
 	aws_s3_acl:                                               "\(aws_docs)/AmazonS3/latest/dev/acl-overview.html"
@@ -93,7 +97,12 @@ urls: {
 	clickhouse:                                               "https://clickhouse.yandex/"
 	clickhouse_http:                                          "https://clickhouse.yandex/docs/en/interfaces/http/"
 	cloudsmith:                                               "https://cloudsmith.io/~timber/repos/vector/packages/"
+	collectd:                                                 "https://collectd.org"
+	collectd_binary_protocol:                                 "https://collectd.org/wiki/index.php/Binary_protocol"
+	collectd_types_db:                                        "https://collectd.org/documentation/manpages/types.db.5.shtml"
+	community_id:                                             "https://github.com/corelight/community-id-spec"
 	console:                                                  "\(wikipedia)/wiki/System_console"
+	confluent_wire_format:                                    "https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format"
 	conventional_commits:                                     "https://www.conventionalcommits.org"
 	contributing:                                             "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
 	crc:                                                      "\(wikipedia)/wiki/Cyclic_redundancy_check"
@@ -137,6 +146,8 @@ urls: {
 	dpkg:                                                     "https://wiki.debian.org/dpkg"
 	dry_code:                                                 "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                                     "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
+	ecs:                                                      "https://www.elastic.co/guide/en/ecs/current/index.html"
+	elastic_beats:                                            "https://www.elastic.co/beats/"
 	elasticsearch:                                            "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                                       "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
 	elasticsearch_data_streams:                               "https://www.elastic.co/guide/en/elasticsearch/reference/current/data-streams.html"
@@ -155,6 +166,9 @@ urls: {
 	ffi:                                                      "https://en.wikipedia.org/wiki/Foreign_function_interface"
 	file:                                                     "\(wikipedia)/wiki/Computer_file"
 	file_system:                                              "\(wikipedia)/wiki/File_system"
+	fluent_bit:                                               "https://fluentbit.io/"
+	fluentd:                                                  "https://www.fluentd.org/"
+	fluentd_forward_protocol:                                 "https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1"
 	freebsd:                                                  "https://www.freebsd.org/"
 	gcp_authentication:                                       "https://cloud.google.com/docs/authentication/"
 	gcp_authentication_api_key:                               "https://cloud.google.com/docs/authentication/api-keys"
@@ -181,7 +195,12 @@ urls: {
 	globbing:                                                 "\(wikipedia)/wiki/Glob_(programming)"
 	graphql:                                                  "https://graphql.org"
 	graphql_playground:                                       "\(github)/graphql/graphql-playground"
+	graphite:                                                 "https://graphiteapp.org"
+	graphite_pickle_protocol:                                 "https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-pickle-protocol"
+	graphite_plaintext_protocol:                              "https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-plaintext-protocol"
+	graphite_tags:                                            "https://graphite.readthedocs.io/en/latest/tags.html"
 	grok:                                                     "https://grokdebug.herokuapp.com/"
+	grpc:                                                     "https://grpc.io"
 	grok_debugger:                                            "https://grokdebug.herokuapp.com/"
 	grok_patterns:                                            "\(github)/daschl/grok/tree/master/patterns"
 	gzip:                                                     "https://www.gzip.org/"
@@ -241,6 +260,7 @@ urls: {
 	leveldb:                                                  "\(github)/google/leveldb"
 	leveldb_sys_2:                                            "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                                            "\(github)/timberio/leveldb-sys/tree/v3.0.0"
+	levenshtein_distance:                                     "\(wikipedia)/wiki/Levenshtein_distance"
 	librdkafka:                                               "\(github)/edenhill/librdkafka"
 	librdkafka_config:                                        "\(github)/edenhill/librdkafka/blob/master/CONFIGURATION.md"
 	logdna:                                                   "https://logdna.com/"
@@ -250,6 +270,7 @@ urls: {
 	log_event_source:                                         "\(vector_repo)/blob/master/src/event/"
 	logplex:                                                  "https://devcenter.heroku.com/articles/logplex"
 	logplex_protocol:                                         "\(github)/heroku/logplex/blob/master/doc/README.http_drains.md"
+	logstash:                                                 "https://www.elastic.co/logstash"
 	lua:                                                      "https://www.lua.org"
 	lua_boolean:                                              "\(lua)/pil/2.2.html"
 	lua_csv_repo:                                             "\(github)/geoffleyland/lua-csv"
@@ -269,6 +290,7 @@ urls: {
 	lua_sequence:                                             "\(lua)/pil/11.1.html"
 	lua_string:                                               "\(lua)/pil/2.4.html"
 	lua_tonumber:                                             "\(lua)/manual/5.3/manual.html#pdf-tonumber"
+	lumberjack_protocol:                                      "https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md"
 	lz4:                                                      "https://lz4.github.io/lz4/"
 	macos:                                                    "\(wikipedia)/wiki/MacOS"
 	mailing_list:                                             "\(vector_website)/community/"
@@ -282,11 +304,13 @@ urls: {
 	memory_safety:                                            "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                                       "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
 	metric_event_source:                                      "\(vector_repo)/blob/master/src/event/metric.rs"
+	mimalloc:                                                 "\(github)/microsoft/mimalloc"
 	mongodb:                                                  "https://www.mongodb.com"
 	mongodb_command_server_status:                            "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:                     "https://docs.mongodb.com/manual/reference/connection-string/"
 	musl_builder_docker_image:                                "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	nats:                                                     "https://nats.io/"
+	nats_jetstream:                                           "https://docs.nats.io/jetstream/jetstream"
 	new_bug_report:                                           "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                                      "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                                "https://newrelic.com/"
@@ -301,7 +325,11 @@ urls: {
 	nix:                                                      "https://nixos.org/nix/"
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
+	ocsf:                                                     "https://schema.ocsf.io"
 	openssl:                                                  "https://www.openssl.org/"
+	opentelemetry:                                            "https://opentelemetry.io/"
+	opentelemetry_collector:                                  "https://opentelemetry.io/docs/collector/"
+	opentelemetry_protocol:                                   "https://opentelemetry.io/docs/specs/otlp/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
 	papertrail_syslog:                                        "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
@@ -324,12 +352,15 @@ urls: {
 	protobuf:                                                 "https://developers.google.com/protocol-buffers"
 	pulsar:                                                   "https://pulsar.apache.org/"
 	pulsar_protocol:                                          "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	punycode:                                                 "https://en.wikipedia.org/wiki/Punycode"
+	rabbitmq:                                                 "https://www.rabbitmq.com/"
 	raspbian:                                                 "https://www.raspbian.org/"
 	rdkafka:                                                  "\(github)/edenhill/librdkafka"
 	regex:                                                    "\(wikipedia)/wiki/Regular_expression"
 	regex_grouping_and_flags:                                 "https://docs.rs/regex/latest/regex/#grouping-and-flags"
 	regex_tester:                                             "https://rustexp.lpil.uk/"
 	rfc_822:                                                  "https://tools.ietf.org/html/rfc822#section-5"
+	rfc_2047:                                                 "https://tools.ietf.org/html/rfc2047"
 	rfc_2064:                                                 "\(vector_repo)/blob/master/rfcs/2020-03-17-2064-event-driven-observability.md"
 	rfc_2822:                                                 "https://tools.ietf.org/html/rfc2822#section-3.3"
 	rfc_3339:                                                 "https://tools.ietf.org/html/rfc3339"
@@ -366,6 +397,7 @@ urls: {
 	splunk_hec:                                               "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_event_endpoint:                                "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fevent"
 	splunk_hec_indexed_fields:                                "https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/IFXandHEC"
+	splunk_hec_indexer_acknowledgement:                       "https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/AboutHECIDXAck"
 	splunk_hec_protocol:                                      "https://docs.splunk.com/Documentation/Splunk/8.0.0/Data/HECRESTendpoints"
 	splunk_hec_raw_endpoint:                                  "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fraw"
 	splunk_hec_setup:                                         "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
@@ -405,6 +437,7 @@ urls: {
 	unix_timestamp:                                           "\(wikipedia)/wiki/Unix_time"
 	utf8:                                                     "\(wikipedia)/wiki/UTF-8"
 	uuidv4:                                                   "\(wikipedia)/wiki/Universally_unique_identifier#Version_4_(random)"
+	uuidv7:                                                   "https://datatracker.ietf.org/doc/html/draft-peabody-dispatch-new-uuid-format"
 	url:                                                      "https://en.wikipedia.org/wiki/URL"
 	vector_aggregator_role:                                   "\(vector_website)/docs/setup/deployment/roles/#aggregator"
 	vector_blog:                                              "\(vector_website)/blog/"
@@ -3,11 +3,11 @@ extern crate tracing;
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use std::{cmp, io, usize};
-use tokio_util::codec::{Decoder, Encoder};
+use tokio_util::codec::{Decoder, Encoder, LinesCodecError};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct BytesDelimitedCodec {
-    delim: u8,
+    delim: Bytes,
     max_length: usize,
     is_discarding: bool,
     next_index: usize,
@@ -16,12 +16,7 @@ pub struct BytesDelimitedCodec {
 impl BytesDelimitedCodec {
     /// Returns a `BytesDelimitedCodec` with the specified delimiter.
     pub fn new(delim: u8) -> Self {
-        BytesDelimitedCodec {
-            delim,
-            max_length: usize::MAX,
-            is_discarding: false,
-            next_index: 0,
-        }
+        BytesDelimitedCodec::new_with_sequence(vec![delim], usize::MAX)
     }
 
     /// Returns a `BytesDelimitedCodec` with a maximum frame length limit.
@@ -32,10 +27,42 @@ impl BytesDelimitedCodec {
         }
     }
 
+    /// Returns a `BytesDelimitedCodec` splitting frames on a sequence of
+    /// bytes, like `\r\n`, with a maximum frame length limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delimiter is empty.
+    pub fn new_with_sequence(delim: impl Into<Bytes>, max_length: usize) -> Self {
+        let delim = delim.into();
+        assert!(!delim.is_empty(), "delimiter must not be empty");
+
+        BytesDelimitedCodec {
+            delim,
+            max_length,
+            is_discarding: false,
+            next_index: 0,
+        }
+    }
+
     /// Returns the maximum frame length when decoding.
     pub fn max_length(&self) -> usize {
         self.max_length
     }
+
+    /// Returns the delimiter frames are split on.
+    pub fn delimiter(&self) -> &[u8] {
+        &self.delim
+    }
+
+    fn find_delimiter(&self, haystack: &[u8]) -> Option<usize> {
+        match self.delim.len() {
+            1 => haystack.iter().position(|b| *b == self.delim[0]),
+            len => haystack
+                .windows(len)
+                .position(|window| window == &self.delim[..]),
+        }
+    }
 }
 
 impl Decoder for BytesDelimitedCodec {
@@ -43,51 +70,53 @@ impl Decoder for BytesDelimitedCodec {
     type Error = io::Error;
 
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        let delim_len = self.delim.len();
+
         loop {
-            // Determine how far into the buffer we'll search for a newline. If
-            // there's no max_length set, we'll read to the end of the buffer.
-            let read_to = cmp::min(self.max_length.saturating_add(1), buf.len());
+            // Determine how far into the buffer we'll search for a delimiter.
+            // If there's no max_length set, we'll read to the end of the buffer.
+            let read_to = cmp::min(self.max_length.saturating_add(delim_len), buf.len());
 
-            let newline_pos = buf[self.next_index..read_to]
-                .iter()
-                .position(|b| *b == self.delim);
+            let delim_pos = self.find_delimiter(&buf[self.next_index..read_to]);
 
-            match (self.is_discarding, newline_pos) {
+            match (self.is_discarding, delim_pos) {
                 (true, Some(offset)) => {
-                    // If we found a newline, discard up to that offset and
+                    // If we found a delimiter, discard up to that offset and
                     // then stop discarding. On the next iteration, we'll try
-                    // to read a line normally.
-                    buf.advance(offset + self.next_index + 1);
+                    // to read a frame normally.
+                    buf.advance(offset + self.next_index + delim_len);
                     self.is_discarding = false;
                     self.next_index = 0;
                 }
                 (true, None) => {
-                    // Otherwise, we didn't find a newline, so we'll discard
-                    // everything we read. On the next iteration, we'll continue
-                    // discarding up to max_len bytes unless we find a newline.
-                    buf.advance(read_to);
+                    // Otherwise, we didn't find a delimiter, so we'll discard
+                    // everything we read except for a possibly incomplete
+                    // delimiter at the end. On the next iteration, we'll
+                    // continue discarding up to max_len bytes unless we find
+                    // a delimiter.
+                    buf.advance(read_to.saturating_sub(delim_len - 1));
                     self.next_index = 0;
-                    if buf.is_empty() {
+                    if buf.len() < delim_len {
                         return Ok(None);
                     }
                 }
                 (false, Some(pos)) => {
                     // We found a correct frame
 
-                    let newpos_index = pos + self.next_index;
+                    let delim_index = pos + self.next_index;
                     self.next_index = 0;
-                    let mut frame = buf.split_to(newpos_index + 1);
+                    let mut frame = buf.split_to(delim_index + delim_len);
 
                     trace!(
                         message = "Decoding the frame.",
                         bytes_proccesed = frame.len()
                     );
 
-                    let frame = frame.split_to(frame.len() - 1);
+                    let frame = frame.split_to(delim_index);
 
                     return Ok(Some(frame.freeze()));
                 }
-                (false, None) if buf.len() > self.max_length => {
+                (false, None) if buf.len() >= self.max_length.saturating_add(delim_len) => {
                     // We reached the max length without finding the
                     // delimiter so must discard the rest until we
                     // reach the next delimiter
@@ -101,9 +130,11 @@ impl Decoder for BytesDelimitedCodec {
                     return Ok(None);
                 }
                 (false, None) => {
-                    // We didn't find the delimiter and didn't
-                    // reach the max frame length.
-                    self.next_index = read_to;
+                    // We didn't find the delimiter and didn't reach the max
+                    // frame length. The last bytes might be the start of a
+                    // delimiter, so they are searched again once more data
+                    // arrives.
+                    self.next_index = read_to.saturating_sub(delim_len - 1);
                     return Ok(None);
                 }
             }
@@ -134,9 +165,71 @@ where
 
     fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), io::Error> {
         let item = item.as_ref();
-        buf.reserve(item.len() + 1);
+        buf.reserve(item.len() + self.delim.len());
         buf.put(item);
-        buf.put_u8(self.delim);
+        buf.put(&self.delim[..]);
         Ok(())
     }
 }
+
+/// Decodes the frames of a `BytesDelimitedCodec` into UTF-8 strings, as a
+/// drop-in replacement of `LinesCodec` supporting any delimiter.
+///
+/// Like `LinesCodec`, a trailing `\r` is removed from the frames when they are
+/// delimited by `\n`.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct StringDelimitedCodec {
+    inner: BytesDelimitedCodec,
+}
+
+impl StringDelimitedCodec {
+    /// Returns a `StringDelimitedCodec` splitting frames on a sequence of
+    /// bytes, with a maximum frame length limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delimiter is empty.
+    pub fn new_with_sequence(delim: impl Into<Bytes>, max_length: usize) -> Self {
+        StringDelimitedCodec {
+            inner: BytesDelimitedCodec::new_with_sequence(delim, max_length),
+        }
+    }
+
+    /// Returns the maximum frame length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.inner.max_length()
+    }
+
+    fn decode_frame(&self, frame: Bytes) -> Result<String, LinesCodecError> {
+        let frame = match (self.inner.delimiter(), frame.last()) {
+            (b"\n", Some(&b'\r')) => &frame[..frame.len() - 1],
+            _ => &frame[..],
+        };
+
+        String::from_utf8(frame.to_vec()).map_err(|_| {
+            LinesCodecError::Io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Unable to decode frame as UTF8",
+            ))
+        })
+    }
+}
+
+impl Decoder for StringDelimitedCodec {
+    type Item = String;
+    type Error = LinesCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<String>, LinesCodecError> {
+        match self.inner.decode(buf)? {
+            Some(frame) => self.decode_frame(frame).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<String>, LinesCodecError> {
+        match self.inner.decode_eof(buf)? {
+            Some(frame) => self.decode_frame(frame).map(Some),
+            None => Ok(None),
+        }
+    }
+}
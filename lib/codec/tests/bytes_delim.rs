@@ -21,6 +21,53 @@ fn bytes_delim_encode() {
     assert_eq!(b"abc\n", &buf[..]);
 }
 
+#[test]
+fn bytes_sequence_delim_decode() {
+    let mut codec = BytesDelimitedCodec::new_with_sequence(&b"\r\n"[..], usize::MAX);
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"abc\r\ndef\nghi\r");
+    assert_eq!(Some("abc".into()), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+
+    // The delimiter is split across two reads.
+    buf.put_slice(b"\njkl");
+    assert_eq!(Some("def\nghi".into()), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Some("jkl".into()), codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn bytes_sequence_delim_encode() {
+    let mut codec = BytesDelimitedCodec::new_with_sequence(&b"\0"[..], usize::MAX);
+
+    let mut buf = BytesMut::new();
+    codec.encode(b"abc", &mut buf).unwrap();
+
+    assert_eq!(b"abc\0", &buf[..]);
+}
+
+#[test]
+fn bytes_sequence_decode_max_length() {
+    const MAX_LENGTH: usize = 6;
+
+    let mut codec = BytesDelimitedCodec::new_with_sequence(&b"||"[..], MAX_LENGTH);
+    let buf = &mut BytesMut::new();
+
+    buf.put_slice(b"123456||1234567|");
+    assert_eq!(Some("123456".into()), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+
+    buf.put_slice(b"|abc||");
+    assert_eq!(Some("abc".into()), codec.decode(buf).unwrap());
+    assert!(buf.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn bytes_sequence_empty_delim() {
+    BytesDelimitedCodec::new_with_sequence(&b""[..], usize::MAX);
+}
+
 #[test]
 fn bytes_decode_max_length() {
     const MAX_LENGTH: usize = 6;
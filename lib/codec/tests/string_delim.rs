@@ -0,0 +1,33 @@
+use bytes::{BufMut, BytesMut};
+use codec::StringDelimitedCodec;
+use tokio_util::codec::Decoder;
+
+#[test]
+fn string_delim_decode_newline_trims_carriage_return() {
+    let mut codec = StringDelimitedCodec::new_with_sequence(&b"\n"[..], usize::MAX);
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"abc\r\ndef\n");
+    assert_eq!(Some("abc".to_owned()), codec.decode(buf).unwrap());
+    assert_eq!(Some("def".to_owned()), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+}
+
+#[test]
+fn string_delim_decode_nul() {
+    let mut codec = StringDelimitedCodec::new_with_sequence(&b"\0"[..], usize::MAX);
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"abc\r\0def\nghi\0jkl");
+    assert_eq!(Some("abc\r".to_owned()), codec.decode(buf).unwrap());
+    assert_eq!(Some("def\nghi".to_owned()), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Some("jkl".to_owned()), codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn string_delim_decode_invalid_utf8() {
+    let mut codec = StringDelimitedCodec::new_with_sequence(&b"\n"[..], usize::MAX);
+    let buf = &mut BytesMut::new();
+    buf.put_slice(b"\xff\nabc\n");
+    assert!(codec.decode(buf).is_err());
+    assert_eq!(Some("abc".to_owned()), codec.decode(buf).unwrap());
+}
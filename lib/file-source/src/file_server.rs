@@ -32,6 +32,10 @@ use crate::paths_provider::PathsProvider;
 /// `FileServer` is configured on a path to watch. The files do _not_ need to
 /// exist at startup. `FileServer` will discover new files which match
 /// its path in at most 60 seconds.
+///
+/// The checkpoint of a file moves past its lines as they're read, unless
+/// `acknowledgements` is set. The receiver of the lines is then left to
+/// update the checkpoints with the positions of the lines it's done with.
 pub struct FileServer<PP, E: FileSourceInternalEvents>
 where
     PP: PathsProvider,
@@ -43,11 +47,11 @@ where
     pub ignore_before: Option<DateTime<Utc>>,
     pub max_line_bytes: usize,
     pub line_delimiter: Bytes,
-    pub data_dir: PathBuf,
     pub glob_minimum_cooldown: Duration,
     pub fingerprinter: Fingerprinter,
     pub oldest_first: bool,
     pub remove_after: Option<Duration>,
+    pub acknowledgements: bool,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
 }
@@ -74,10 +78,11 @@ where
         self,
         mut chans: C,
         shutdown: S,
-    ) -> Result<Shutdown, <C as Sink<Vec<(Bytes, String)>>>::Error>
+        mut checkpointer: Checkpointer,
+    ) -> Result<Shutdown, <C as Sink<Vec<Line>>>::Error>
     where
-        C: Sink<Vec<(Bytes, String)>> + Unpin,
-        <C as Sink<Vec<(Bytes, String)>>>::Error: std::error::Error,
+        C: Sink<Vec<Line>> + Unpin,
+        <C as Sink<Vec<Line>>>::Error: std::error::Error,
         S: Future + Unpin + Send + 'static,
         <S as Future>::Output: Clone + Send + Sync,
     {
@@ -88,7 +93,6 @@ where
         let mut backoff_cap: usize = 1;
         let mut lines = Vec::new();
 
-        let mut checkpointer = Checkpointer::new(&self.data_dir);
         checkpointer.read_checkpoints(self.ignore_before);
 
         let mut known_small_files = HashSet::new();
@@ -282,10 +286,12 @@ where
 
                     bytes_read += sz;
 
-                    lines.push((
-                        line,
-                        watcher.path.to_str().expect("not a valid path").to_owned(),
-                    ));
+                    lines.push(Line {
+                        text: line,
+                        filename: watcher.path.to_str().expect("not a valid path").to_owned(),
+                        file_id,
+                        offset: watcher.get_file_position(),
+                    });
 
                     if bytes_read > self.max_read_bytes {
                         maxed_out_reading_single_file = true;
@@ -296,7 +302,9 @@ where
 
                 if bytes_read > 0 {
                     global_bytes_read = global_bytes_read.saturating_add(bytes_read);
-                    checkpoints.update(file_id, watcher.get_file_position());
+                    if !self.acknowledgements {
+                        checkpoints.update(file_id, watcher.get_file_position());
+                    }
                 } else {
                     // Should the file be removed
                     if let Some(grace_period) = self.remove_after {
@@ -434,6 +442,16 @@ where
     }
 }
 
+/// A line read from a file, along with the position in the file right after
+/// it.
+#[derive(Clone, Debug)]
+pub struct Line {
+    pub text: Bytes,
+    pub filename: String,
+    pub file_id: FileFingerprint,
+    pub offset: u64,
+}
+
 /// A sentinel type to signal that file server was gracefully shut down.
 ///
 /// The purpose of this type is to clarify the semantics of the result values
@@ -95,7 +95,7 @@ fn execute(object: &mut impl Object, source: String) -> Result<Value, Error> {
         .map_err(|err| Error::Runtime(err.to_string()))
 }
 
-fn read_program(source: Option<&str>, file: Option<&PathBuf>) -> Result<String, Error> {
+pub(crate) fn read_program(source: Option<&str>, file: Option<&PathBuf>) -> Result<String, Error> {
     match source {
         Some(source) => Ok(source.to_owned()),
         None => match file {
@@ -105,7 +105,7 @@ fn read_program(source: Option<&str>, file: Option<&PathBuf>) -> Result<String,
     }
 }
 
-fn read_into_objects(input: Option<&PathBuf>) -> Result<Vec<Value>, Error> {
+pub(crate) fn read_into_objects(input: Option<&PathBuf>) -> Result<Vec<Value>, Error> {
     let input = match input {
         Some(path) => read(File::open(path)?),
         None => read(io::stdin()),
@@ -1,7 +1,10 @@
-use crate::Error;
+use crate::{
+    cmd::{read_into_objects, read_program},
+    Error,
+};
 use prettytable::{format, Cell, Row, Table};
 use regex::Regex;
-use remap::{state, Formatter, Object, Program, Runtime, Value};
+use remap::{state, Expression, Formatter, InnerTypeDef, Object, Program, Runtime, TypeDef, Value};
 use remap_functions::all as funcs;
 use rustyline::completion::Completer;
 use rustyline::error::ReadlineError;
@@ -10,12 +13,17 @@ use rustyline::hint::{Hinter, HistoryHinter};
 use rustyline::validate::{self, MatchingBracketValidator, ValidationResult, Validator};
 use rustyline::{Context, Editor, Helper};
 use std::borrow::Cow::{self, Borrowed, Owned};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 const HELP_TEXT: &str = r#"
 VRL REPL commands:
   help functions    Display a list of currently available VRL functions (aliases: ["help funcs", "help fs"])
   help docs         Navigate to the VRL docs on the Vector website
   help docs <func>  Navigate to the VRL docs for the specified function
+  type <expr>       Display the type definition of the expression, without resolving it
+  load <file>       Replace the objects with the object(s) in the file (JSON or jsonl)
+  run <file>        Resolve the program in the file against the current object
   next              Load the next object or create a new one
   prev              Load the previous object
   exit              Terminate the program
@@ -63,6 +71,9 @@ pub(crate) fn run(mut objects: Vec<Value>) -> Result<(), Error> {
 >
 > VRL REPL commands:
 >   help              Learn more about VRL
+>   type <expr>       Display the type definition of an expression
+>   load <file>       Load the object(s) from a file
+>   run <file>        Resolve the program from a file
 >   next              Load the next object or create a new one
 >   prev              Load the previous object
 >   exit              Terminate the program
@@ -84,6 +95,36 @@ pub(crate) fn run(mut objects: Vec<Value>) -> Result<(), Error> {
             Ok(line) if line == "help docs" => open_url(DOCS_URL),
             // Capture "help docs <func_name>"
             Ok(line) if func_docs_regex.is_match(line) => show_func_docs(line, &func_docs_regex),
+            Ok(line) if line.starts_with("type ") => {
+                rl.add_history_entry(line);
+                println!("{}\n", type_def(&line[5..], &compiler_state));
+            }
+            Ok(line) if line.starts_with("load ") => {
+                rl.add_history_entry(line);
+
+                match read_into_objects(Some(&PathBuf::from(line[5..].trim()))) {
+                    Ok(loaded) => {
+                        println!("loaded {} object(s)\n", loaded.len());
+                        objects = loaded;
+                        index = 0;
+                    }
+                    Err(err) => println!("unable to load objects: {}\n", err),
+                }
+            }
+            Ok(line) if line.starts_with("run ") => {
+                rl.add_history_entry(line);
+
+                let value = match read_program(None, Some(&PathBuf::from(line[4..].trim()))) {
+                    Ok(program) => resolve(
+                        objects.get_mut(index),
+                        &mut rt,
+                        &program,
+                        &mut compiler_state,
+                    ),
+                    Err(err) => format!("unable to read program: {}", err),
+                };
+                println!("{}\n", value);
+            }
             Ok(line) => {
                 rl.add_history_entry(line);
 
@@ -163,6 +204,60 @@ fn resolve(
     }
 }
 
+/// Compiles the expression without running it, and formats the type definition of its result.
+///
+/// The expression is compiled against a copy of the compiler state, so that any assignments in it
+/// don't affect the types known to the next expressions.
+fn type_def(source: &str, state: &state::Compiler) -> String {
+    let mut state = state.clone();
+
+    match Program::new_with_state(
+        source.to_owned(),
+        &remap_functions::all(),
+        None,
+        true,
+        &mut state,
+    ) {
+        Ok((program, _)) => match program.expressions().last() {
+            Some(expression) => format_type_def(&expression.type_def(&state)),
+            None => Value::Null.to_string(),
+        },
+        Err(diagnostics) => Formatter::new(source, diagnostics).colored().to_string(),
+    }
+}
+
+fn format_type_def(type_def: &TypeDef) -> String {
+    let format_map = |map: &BTreeMap<String, TypeDef>| {
+        let fields = map
+            .iter()
+            .map(|(key, type_def)| format!("{}: {}", key, format_type_def(type_def)))
+            .collect::<Vec<_>>();
+
+        format!("{{ {} }}", fields.join(", "))
+    };
+
+    let mut string = type_def.kind.to_string();
+
+    match &type_def.inner_type_def {
+        Some(InnerTypeDef::Array(array)) => {
+            string.push_str(&format!(" [{}]", format_type_def(array)))
+        }
+        Some(InnerTypeDef::Map(map)) => string.push_str(&format!(" {}", format_map(map))),
+        Some(InnerTypeDef::Both { map, array }) => string.push_str(&format!(
+            " [{}] {}",
+            format_type_def(array),
+            format_map(map)
+        )),
+        None => {}
+    }
+
+    if type_def.is_fallible() {
+        string.push_str(" (fallible)");
+    }
+
+    string
+}
+
 struct Repl {
     highlighter: MatchingBracketHighlighter,
     validator: MatchingBracketValidator,
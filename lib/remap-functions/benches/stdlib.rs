@@ -0,0 +1,41 @@
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use remap::prelude::*;
+
+criterion_group!(
+    name = benches;
+    // encapsulates CI noise we saw in
+    // https://github.com/timberio/vector/issues/5394
+    config = Criterion::default().noise_threshold(0.02);
+    targets = functions
+);
+criterion_main!(benches);
+
+trait BenchmarkGroupExt {
+    /// Benchmark running the compiled function call `expression` against an
+    /// empty event.
+    fn bench_vrl_function(&mut self, id: &str, expression: &dyn Expression);
+}
+
+impl BenchmarkGroupExt for BenchmarkGroup<'_, WallTime> {
+    fn bench_vrl_function(&mut self, id: &str, expression: &dyn Expression) {
+        let mut state = state::Program::default();
+        let mut object: Value = std::collections::BTreeMap::default().into();
+
+        self.bench_function(id, |b| {
+            b.iter(|| expression.execute(&mut state, &mut object))
+        });
+    }
+}
+
+/// Benchmarks each function with the cases of its `test_function!` tests.
+fn functions(c: &mut Criterion) {
+    for (function, cases) in remap_functions::bench_cases() {
+        let mut group = c.benchmark_group(function);
+        for (case, expression) in cases {
+            group.bench_vrl_function(case, expression.as_ref());
+        }
+        group.finish();
+    }
+}
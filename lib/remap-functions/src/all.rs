@@ -0,0 +1,165 @@
+use crate::util;
+use remap::prelude::*;
+use value::Kind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct All;
+
+impl Function for All {
+    fn identifier(&self) -> &'static str {
+        "all"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Map(_) | Value::Array(_)),
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureParameter> {
+        Some(util::ELEMENT_CLOSURE)
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(AllFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AllFn {
+    value: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl Expression for AllFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let elements = util::collection_elements(self.value.execute(state, object)?)?;
+
+        // Stops at the first element that doesn't match.
+        for (key, value) in elements {
+            if !util::run_element_closure(&self.closure, key, value, state, object)?
+                .try_boolean()?
+            {
+                return Ok(false.into());
+            }
+        }
+
+        Ok(true.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let closure_def = self.closure.type_def(state).fallible_unless(Kind::Boolean);
+        let type_def = self
+            .value
+            .type_def(state)
+            .fallible_unless(Kind::Map | Kind::Array);
+        let fallible = type_def.is_fallible() || closure_def.is_fallible();
+
+        type_def
+            .into_fallible(fallible)
+            .with_constraint(Kind::Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use remap::{expression::Arithmetic, Operator};
+
+    /// `|value| { value > 1 }`, or `|key, value| { key == value }`.
+    fn closure(variables: &[&str]) -> Closure {
+        let lhs = Variable::new(variables[0].to_owned(), None);
+        let (rhs, op): (Expr, _) = match variables.get(1) {
+            Some(ident) => (
+                Variable::new((*ident).to_owned(), None).into(),
+                Operator::Equal,
+            ),
+            None => (Literal::from(1).into(), Operator::Greater),
+        };
+
+        Closure::new(
+            variables.iter().map(|v| (*v).to_owned()).collect(),
+            Block::new(vec![Arithmetic::new(
+                Box::new(lhs.into()),
+                Box::new(rhs),
+                op,
+            )
+            .into()]),
+        )
+    }
+
+    fn run(value: Value, variables: &[&str]) -> Result<Value> {
+        let function = AllFn {
+            value: Literal::from(value).boxed(),
+            closure: closure(variables),
+        };
+
+        function.execute(&mut state::Program::default(), &mut Value::Null)
+    }
+
+    remap::test_type_def![
+        boolean_closure {
+            expr: |_| AllFn {
+                value: array![1, 2].boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(true).into()])),
+            },
+            def: TypeDef { kind: Kind::Boolean, ..Default::default() },
+        }
+
+        non_boolean_closure {
+            expr: |_| AllFn {
+                value: array![1, 2].boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Boolean, ..Default::default() },
+        }
+
+        value_non_collection {
+            expr: |_| AllFn {
+                value: Literal::from(1).boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(true).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Boolean, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn array() {
+        assert_eq!(run(value!([2, 3, 4]), &["v"]), Ok(value!(true)));
+        assert_eq!(run(value!([2, 1, 4]), &["v"]), Ok(value!(false)));
+    }
+
+    #[test]
+    fn array_with_index() {
+        assert_eq!(run(value!([0, 1, 2]), &["i", "v"]), Ok(value!(true)));
+        assert_eq!(run(value!([0, 2, 1]), &["i", "v"]), Ok(value!(false)));
+    }
+
+    #[test]
+    fn map_with_key() {
+        assert_eq!(
+            run(value!({"a": "a", "b": "b"}), &["k", "v"]),
+            Ok(value!(true))
+        );
+        assert_eq!(
+            run(value!({"a": "a", "b": "c"}), &["k", "v"]),
+            Ok(value!(false))
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(run(value!([]), &["v"]), Ok(value!(true)));
+    }
+
+    #[test]
+    fn stops_at_first_mismatch() {
+        // The string would fail the comparison, but is never reached.
+        assert_eq!(run(value!([0, "foo"]), &["v"]), Ok(value!(false)));
+    }
+}
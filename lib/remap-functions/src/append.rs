@@ -112,6 +112,11 @@ mod tests {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         append => Append;
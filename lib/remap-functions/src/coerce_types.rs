@@ -0,0 +1,329 @@
+use chrono::{DateTime, Utc};
+use remap::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CoerceTypes;
+
+impl Function for CoerceTypes {
+    fn identifier(&self) -> &'static str {
+        "coerce_types"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |_| true,
+                required: true,
+            },
+            Parameter {
+                keyword: "integers",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "floats",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "booleans",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "timestamps",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let integers = arguments.optional("integers").map(Expr::boxed);
+        let floats = arguments.optional("floats").map(Expr::boxed);
+        let booleans = arguments.optional("booleans").map(Expr::boxed);
+        let timestamps = arguments.optional("timestamps").map(Expr::boxed);
+
+        Ok(Box::new(CoerceTypesFn {
+            value,
+            integers,
+            floats,
+            booleans,
+            timestamps,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CoerceTypesFn {
+    value: Box<dyn Expression>,
+    integers: Option<Box<dyn Expression>>,
+    floats: Option<Box<dyn Expression>>,
+    booleans: Option<Box<dyn Expression>>,
+    timestamps: Option<Box<dyn Expression>>,
+}
+
+impl CoerceTypesFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>, options: CoerceOptions) -> Self {
+        let option = |enabled: bool| Some(Literal::from(enabled).boxed());
+
+        Self {
+            value,
+            integers: option(options.integers),
+            floats: option(options.floats),
+            booleans: option(options.booleans),
+            timestamps: option(options.timestamps),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CoerceOptions {
+    integers: bool,
+    floats: bool,
+    booleans: bool,
+    timestamps: bool,
+}
+
+impl Default for CoerceOptions {
+    fn default() -> Self {
+        Self {
+            integers: true,
+            floats: true,
+            booleans: true,
+            timestamps: true,
+        }
+    }
+}
+
+impl CoerceOptions {
+    /// Coerce the string to the first enabled type it can be parsed as,
+    /// keeping it as-is if none match.
+    fn coerce_string(&self, string: &str) -> Option<Value> {
+        if self.booleans {
+            match string {
+                "true" => return Some(true.into()),
+                "false" => return Some(false.into()),
+                _ => {}
+            }
+        }
+
+        if self.integers {
+            if let Ok(integer) = string.parse::<i64>() {
+                return Some(integer.into());
+            }
+        }
+
+        // Only accept the decimal notation, so that words like `inf` or `NaN`
+        // stay strings.
+        if self.floats
+            && string.bytes().any(|b| b.is_ascii_digit())
+            && string
+                .bytes()
+                .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'+' | b'e' | b'E'))
+        {
+            if let Ok(float) = string.parse::<f64>() {
+                return Some(float.into());
+            }
+        }
+
+        if self.timestamps {
+            if let Ok(timestamp) = DateTime::parse_from_rfc3339(string) {
+                return Some(timestamp.with_timezone(&Utc).into());
+            }
+        }
+
+        None
+    }
+
+    fn coerce(&self, value: Value) -> Value {
+        match value {
+            Value::Bytes(bytes) => {
+                let coerced = std::str::from_utf8(&bytes)
+                    .ok()
+                    .and_then(|string| self.coerce_string(string));
+
+                coerced.unwrap_or(Value::Bytes(bytes))
+            }
+            Value::Map(map) => map
+                .into_iter()
+                .map(|(key, value)| (key, self.coerce(value)))
+                .collect::<BTreeMap<_, _>>()
+                .into(),
+            Value::Array(array) => array
+                .into_iter()
+                .map(|value| self.coerce(value))
+                .collect::<Vec<_>>()
+                .into(),
+            _ => value,
+        }
+    }
+}
+
+impl Expression for CoerceTypesFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let options = CoerceOptions {
+            integers: match &self.integers {
+                Some(expr) => expr.execute(state, object)?.try_boolean()?,
+                None => true,
+            },
+
+            floats: match &self.floats {
+                Some(expr) => expr.execute(state, object)?.try_boolean()?,
+                None => true,
+            },
+
+            booleans: match &self.booleans {
+                Some(expr) => expr.execute(state, object)?.try_boolean()?,
+                None => true,
+            },
+
+            timestamps: match &self.timestamps {
+                Some(expr) => expr.execute(state, object)?.try_boolean()?,
+                None => true,
+            },
+        };
+
+        let value = self.value.execute(state, object)?;
+
+        Ok(options.coerce(value))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        use value::Kind;
+
+        let option_def = |expr: &Option<Box<dyn Expression>>| {
+            expr.as_ref()
+                .map(|expr| expr.type_def(state).fallible_unless(Kind::Boolean))
+        };
+
+        let mut kind = self.value.type_def(state).kind;
+        if kind.contains_bytes() {
+            kind |= Kind::Integer | Kind::Float | Kind::Boolean | Kind::Timestamp;
+        }
+
+        self.value
+            .type_def(state)
+            .merge_optional(option_def(&self.integers))
+            .merge_optional(option_def(&self.floats))
+            .merge_optional(option_def(&self.booleans))
+            .merge_optional(option_def(&self.timestamps))
+            .with_constraint(kind)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use shared::btreemap;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| CoerceTypesFn::new(Literal::from("foo").boxed(), CoerceOptions::default()),
+            def: TypeDef {
+                kind: Kind::Bytes | Kind::Integer | Kind::Float | Kind::Boolean | Kind::Timestamp,
+                ..Default::default()
+            },
+        }
+
+        value_integer {
+            expr: |_| CoerceTypesFn::new(Literal::from(1).boxed(), CoerceOptions::default()),
+            def: TypeDef { kind: Kind::Integer, ..Default::default() },
+        }
+
+        value_array {
+            expr: |_| CoerceTypesFn::new(Array::from(vec!["foo"]).boxed(), CoerceOptions::default()),
+            def: TypeDef { kind: Kind::Array, ..Default::default() },
+        }
+
+        optional_expression {
+            expr: |_| CoerceTypesFn {
+                value: Array::from(vec!["foo"]).boxed(),
+                integers: Some(Box::new(Noop)),
+                floats: None,
+                booleans: None,
+                timestamps: None,
+            },
+            def: TypeDef { fallible: true, kind: Kind::Array, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn coerce_types() {
+        let timestamp = Utc.ymd(2021, 2, 3).and_hms_milli(4, 5, 6, 789);
+
+        let cases = vec![
+            (
+                btreemap! {
+                    "event" => value!({
+                        message: "foo",
+                        status: "200",
+                        duration: "1.5",
+                        ratio: "-2e3",
+                        success: "true",
+                        failure: "false",
+                        timestamp: "2021-02-03T05:05:06.789+01:00",
+                        untouched: 404,
+                        words: ["inf", "NaN", "True", ""],
+                        nested: { count: "1" },
+                    }),
+                },
+                Ok(value!({
+                    message: "foo",
+                    status: 200,
+                    duration: 1.5,
+                    ratio: (-2000.0),
+                    success: true,
+                    failure: false,
+                    timestamp: timestamp,
+                    untouched: 404,
+                    words: ["inf", "NaN", "True", ""],
+                    nested: { count: 1 },
+                })),
+                CoerceTypesFn::new(Box::new(Path::from("event")), CoerceOptions::default()),
+            ),
+            (
+                btreemap! {},
+                Ok(42.0.into()),
+                CoerceTypesFn::new(
+                    Literal::from("42").boxed(),
+                    CoerceOptions {
+                        integers: false,
+                        ..Default::default()
+                    },
+                ),
+            ),
+            (
+                btreemap! {},
+                Ok(value!(["true", 1, "2021-02-03T04:05:06Z"])),
+                CoerceTypesFn::new(
+                    Array::from(vec!["true", "1", "2021-02-03T04:05:06Z"]).boxed(),
+                    CoerceOptions {
+                        booleans: false,
+                        floats: false,
+                        timestamps: false,
+                        ..Default::default()
+                    },
+                ),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (object, exp, func) in cases {
+            let mut object: Value = object.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
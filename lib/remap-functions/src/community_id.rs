@@ -0,0 +1,391 @@
+use ::sha1::{Digest, Sha1};
+use remap::prelude::*;
+use std::convert::TryFrom;
+use std::net::IpAddr;
+
+const ICMP: u8 = 1;
+const TCP: u8 = 6;
+const UDP: u8 = 17;
+const ICMP6: u8 = 58;
+const SCTP: u8 = 132;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CommunityId;
+
+impl Function for CommunityId {
+    fn identifier(&self) -> &'static str {
+        "community_id"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "source_ip",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "destination_ip",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "protocol",
+                accepts: |v| matches!(v, Value::Integer(_) | Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "source_port",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "destination_port",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "seed",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let source_ip = arguments.required("source_ip")?.boxed();
+        let destination_ip = arguments.required("destination_ip")?.boxed();
+        let protocol = arguments.required("protocol")?.boxed();
+        let source_port = arguments.optional("source_port").map(Expr::boxed);
+        let destination_port = arguments.optional("destination_port").map(Expr::boxed);
+        let seed = arguments.optional("seed").map(Expr::boxed);
+
+        Ok(Box::new(CommunityIdFn {
+            source_ip,
+            destination_ip,
+            protocol,
+            source_port,
+            destination_port,
+            seed,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CommunityIdFn {
+    source_ip: Box<dyn Expression>,
+    destination_ip: Box<dyn Expression>,
+    protocol: Box<dyn Expression>,
+    source_port: Option<Box<dyn Expression>>,
+    destination_port: Option<Box<dyn Expression>>,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl CommunityIdFn {
+    #[cfg(test)]
+    fn new(
+        source_ip: &str,
+        destination_ip: &str,
+        protocol: impl Into<Value>,
+        ports: Option<(i64, i64)>,
+        seed: Option<i64>,
+    ) -> Self {
+        Self {
+            source_ip: Literal::from(source_ip).boxed(),
+            destination_ip: Literal::from(destination_ip).boxed(),
+            protocol: Literal::from(protocol).boxed(),
+            source_port: ports.map(|(port, _)| Literal::from(port).boxed()),
+            destination_port: ports.map(|(_, port)| Literal::from(port).boxed()),
+            seed: seed.map(|seed| Literal::from(seed).boxed()),
+        }
+    }
+}
+
+impl Expression for CommunityIdFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let source_ip = ip_address(self.source_ip.execute(state, object)?)?;
+        let destination_ip = ip_address(self.destination_ip.execute(state, object)?)?;
+
+        let protocol = match self.protocol.execute(state, object)? {
+            Value::Bytes(bytes) => {
+                let name = String::from_utf8_lossy(&bytes);
+                protocol_number(&name).ok_or(format!("unknown protocol: '{}'", name))?
+            }
+            value => {
+                let number = value.try_integer()?;
+                u8::try_from(number).map_err(|_| format!("invalid protocol number: {}", number))?
+            }
+        };
+
+        let source_port = match &self.source_port {
+            Some(expr) => Some(port(expr.execute(state, object)?)?),
+            None => None,
+        };
+
+        let destination_port = match &self.destination_port {
+            Some(expr) => Some(port(expr.execute(state, object)?)?),
+            None => None,
+        };
+
+        let seed = match &self.seed {
+            Some(expr) => {
+                let seed = expr.execute(state, object)?.try_integer()?;
+                u16::try_from(seed).map_err(|_| format!("invalid seed: {}", seed))?
+            }
+            None => 0,
+        };
+
+        let ports = match (source_port, destination_port) {
+            (Some(source), Some(destination)) => Some((source, destination)),
+            _ => None,
+        };
+
+        Ok(hash(source_ip, destination_ip, protocol, ports, seed)?.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.source_ip
+            .type_def(state)
+            .merge(self.destination_ip.type_def(state))
+            .merge(self.protocol.type_def(state))
+            .merge_optional(self.source_port.as_ref().map(|port| port.type_def(state)))
+            .merge_optional(
+                self.destination_port
+                    .as_ref()
+                    .map(|port| port.type_def(state)),
+            )
+            .merge_optional(self.seed.as_ref().map(|seed| seed.type_def(state)))
+            .into_fallible(true) // invalid IP addresses, protocols and ports
+            .with_constraint(value::Kind::Bytes)
+    }
+}
+
+fn ip_address(value: Value) -> Result<IpAddr> {
+    let bytes = value.try_bytes()?;
+    let ip = String::from_utf8_lossy(&bytes);
+
+    Ok(ip
+        .parse()
+        .map_err(|err| format!("unable to parse IP address '{}': {}", ip, err))?)
+}
+
+fn port(value: Value) -> Result<u16> {
+    let port = value.try_integer()?;
+
+    Ok(u16::try_from(port).map_err(|_| format!("invalid port: {}", port))?)
+}
+
+fn protocol_number(name: &str) -> Option<u8> {
+    match name.to_lowercase().as_str() {
+        "icmp" => Some(ICMP),
+        "tcp" => Some(TCP),
+        "udp" => Some(UDP),
+        "icmp6" | "icmpv6" | "ipv6-icmp" => Some(ICMP6),
+        "sctp" => Some(SCTP),
+        _ => None,
+    }
+}
+
+/// Returns the ICMP message type that is the counterpart of the given one,
+/// like an echo reply for an echo request, or `None` if the message has no
+/// counterpart.
+fn icmp_counterpart(protocol: u8, message_type: u16) -> Option<u16> {
+    match (protocol, message_type) {
+        (ICMP, 8) => Some(0), // echo
+        (ICMP, 0) => Some(8),
+        (ICMP, 13) => Some(14), // timestamp
+        (ICMP, 14) => Some(13),
+        (ICMP, 15) => Some(16), // information
+        (ICMP, 16) => Some(15),
+        (ICMP, 10) => Some(9), // router solicitation and advertisement
+        (ICMP, 9) => Some(10),
+        (ICMP, 17) => Some(18), // address mask
+        (ICMP, 18) => Some(17),
+        (ICMP6, 128) => Some(129), // echo
+        (ICMP6, 129) => Some(128),
+        (ICMP6, 133) => Some(134), // router solicitation and advertisement
+        (ICMP6, 134) => Some(133),
+        (ICMP6, 135) => Some(136), // neighbor solicitation and advertisement
+        (ICMP6, 136) => Some(135),
+        (ICMP6, 130) => Some(131), // multicast listener query and report
+        (ICMP6, 131) => Some(130),
+        (ICMP6, 144) => Some(145), // home agent address discovery
+        (ICMP6, 145) => Some(144),
+        _ => None,
+    }
+}
+
+/// Computes the version 1 [Community ID][spec] flow hash.
+///
+/// The endpoints are ordered before hashing, so both directions of a flow
+/// get the same ID. For ICMP, the ports are the message type and code.
+///
+/// [spec]: https://github.com/corelight/community-id-spec
+fn hash(
+    source_ip: IpAddr,
+    destination_ip: IpAddr,
+    protocol: u8,
+    ports: Option<(u16, u16)>,
+    seed: u16,
+) -> Result<String> {
+    let (mut source_ip, mut destination_ip) = match (source_ip, destination_ip) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            (source.octets().to_vec(), destination.octets().to_vec())
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination)) => {
+            (source.octets().to_vec(), destination.octets().to_vec())
+        }
+        _ => return Err("source and destination IP addresses must be of the same family".into()),
+    };
+
+    // Only these protocols have ports.
+    let mut ports = ports.filter(|_| matches!(protocol, ICMP | TCP | UDP | ICMP6 | SCTP));
+
+    let mut one_way = false;
+    if let Some((message_type, _)) = ports.filter(|_| matches!(protocol, ICMP | ICMP6)) {
+        match icmp_counterpart(protocol, message_type) {
+            Some(counterpart) => ports = Some((message_type, counterpart)),
+            None => one_way = true,
+        }
+    }
+
+    let ordered = match ports {
+        Some((source_port, destination_port)) => {
+            (&source_ip, source_port) < (&destination_ip, destination_port)
+        }
+        None => source_ip < destination_ip,
+    };
+
+    if !one_way && !ordered {
+        std::mem::swap(&mut source_ip, &mut destination_ip);
+        ports = ports.map(|(source_port, destination_port)| (destination_port, source_port));
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(seed.to_be_bytes());
+    hasher.update(&source_ip);
+    hasher.update(&destination_ip);
+    hasher.update([protocol, 0]);
+    if let Some((source_port, destination_port)) = ports {
+        hasher.update(source_port.to_be_bytes());
+        hasher.update(destination_port.to_be_bytes());
+    }
+
+    Ok(format!("1:{}", base64::encode(hasher.finalize())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    remap::test_type_def![value_string {
+        expr: |_| CommunityIdFn::new("1.2.3.4", "5.6.7.8", 6, Some((1234, 80)), None),
+        def: TypeDef {
+            fallible: true,
+            kind: value::Kind::Bytes,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn community_id() {
+        let cases = vec![
+            (
+                btreemap! {},
+                Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg=".into()),
+                CommunityIdFn::new(
+                    "128.232.110.120",
+                    "66.35.250.204",
+                    6,
+                    Some((34855, 80)),
+                    None,
+                ),
+            ),
+            (
+                // The reverse direction of the same flow.
+                btreemap! {},
+                Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg=".into()),
+                CommunityIdFn::new(
+                    "66.35.250.204",
+                    "128.232.110.120",
+                    "TCP",
+                    Some((80, 34855)),
+                    None,
+                ),
+            ),
+            (
+                btreemap! {},
+                Ok("1:3V71V58M3Ksw/yuFALMcW0LAHvc=".into()),
+                CommunityIdFn::new(
+                    "128.232.110.120",
+                    "66.35.250.204",
+                    6,
+                    Some((34855, 80)),
+                    Some(1),
+                ),
+            ),
+            (
+                btreemap! {},
+                Ok("1:RaNxzxOEId7sVzMH2xfyqv7Oin4=".into()),
+                CommunityIdFn::new("1.2.3.4", "5.6.7.8", "udp", Some((53, 1234)), None),
+            ),
+            (
+                // ICMP echo request.
+                btreemap! {},
+                Ok("1:X0snYXpgwiv9TZtqg64sgzUn6Dk=".into()),
+                CommunityIdFn::new("192.168.0.89", "192.168.0.1", 1, Some((8, 0)), None),
+            ),
+            (
+                // ICMP echo reply.
+                btreemap! {},
+                Ok("1:X0snYXpgwiv9TZtqg64sgzUn6Dk=".into()),
+                CommunityIdFn::new("192.168.0.1", "192.168.0.89", 1, Some((0, 0)), None),
+            ),
+            (
+                // ICMPv6 neighbor solicitation.
+                btreemap! {},
+                Ok("1:dGHyGvjMfljg6Bppwm3bg0LO8TY=".into()),
+                CommunityIdFn::new(
+                    "fe80::200:86ff:fe05:80da",
+                    "fe80::260:97ff:fe07:69ea",
+                    58,
+                    Some((135, 0)),
+                    None,
+                ),
+            ),
+            (
+                // GRE has no ports.
+                btreemap! {},
+                Ok("1:+KlEHDT0vJgzs/eNmzHq0aSpRYw=".into()),
+                CommunityIdFn::new("10.0.0.1", "10.0.0.2", 47, None, None),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: source and destination IP addresses must be of the same family".into()),
+                CommunityIdFn::new("10.0.0.1", "::1", 6, Some((1, 2)), None),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: invalid port: 70000".into()),
+                CommunityIdFn::new("10.0.0.1", "10.0.0.2", 6, Some((70000, 2)), None),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unknown protocol: 'foo'".into()),
+                CommunityIdFn::new("10.0.0.1", "10.0.0.2", "foo", None, None),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (object, exp, func) in cases {
+            let mut object: Value = object.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
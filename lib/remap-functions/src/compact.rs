@@ -358,6 +358,12 @@ mod test {
             assert_eq!(expected, compact_map(original, &options))
         }
     }
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use shared::btreemap;
 
     test_function![
         compact => Compact;
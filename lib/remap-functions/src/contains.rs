@@ -177,3 +177,22 @@ mod tests {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        contains => Contains;
+
+        case_sensitive {
+            args: func_args![value: "The Needle In The Haystack", substring: "Needle"],
+            want: Ok(true),
+        }
+
+        case_insensitive {
+            args: func_args![value: "The Needle In The Haystack", substring: "needle", case_sensitive: false],
+            want: Ok(true),
+        }
+    ];
+}
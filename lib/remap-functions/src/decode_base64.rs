@@ -114,6 +114,11 @@ mod test {
             def: TypeDef { fallible: true, kind: Kind::Bytes, ..Default::default() },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         decode_base64 => DecodeBase64;
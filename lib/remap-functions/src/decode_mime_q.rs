@@ -0,0 +1,209 @@
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use remap::prelude::*;
+
+lazy_static! {
+    static ref ENCODED_WORD: Regex = Regex::new(
+        r"(?x)                          # x: ignore whitespace + comments
+            =\?
+            (?P<charset>[^?*]+)         # charset, like UTF-8
+            (?:\*[^?]*)?                # optional language, like *en
+            \?
+            (?P<encoding>[BbQq])        # B(ase64) or Q(uoted-printable)
+            \?
+            (?P<text>[^?\ ]*)           # encoded text
+            \?="
+    )
+    .unwrap();
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeMimeQ;
+
+impl Function for DecodeMimeQ {
+    fn identifier(&self) -> &'static str {
+        "decode_mime_q"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Bytes(_)),
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+
+        Ok(Box::new(DecodeMimeQFn { value }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeMimeQFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeMimeQFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.value.execute(state, object)?.try_bytes()?;
+        let value = String::from_utf8_lossy(&bytes);
+
+        Ok(decode_encoded_words(&value)?.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .into_fallible(true) // decoding errors
+            .with_constraint(value::Kind::Bytes)
+    }
+}
+
+/// Decodes the RFC 2047 encoded-words of the value. Whitespace between two
+/// adjacent encoded-words is removed, as it only separates them.
+fn decode_encoded_words(value: &str) -> Result<String> {
+    let mut decoded = String::with_capacity(value.len());
+    let mut last_end = 0;
+
+    for captures in ENCODED_WORD.captures_iter(value) {
+        let word = captures.get(0).expect("capture 0 is the whole match");
+
+        let between = &value[last_end..word.start()];
+        if last_end == 0 || !between.chars().all(char::is_whitespace) {
+            decoded.push_str(between);
+        }
+
+        decoded.push_str(&decode_encoded_word(&captures)?);
+        last_end = word.end();
+    }
+
+    decoded.push_str(&value[last_end..]);
+
+    Ok(decoded)
+}
+
+fn decode_encoded_word(captures: &Captures) -> Result<String> {
+    let text = &captures["text"];
+    let bytes = match &captures["encoding"] {
+        "B" | "b" => base64::decode(text)
+            .map_err(|_| format!("unable to decode base64 encoded-word: '{}'", text))?,
+        _ => decode_q(text)?,
+    };
+
+    let charset = &captures["charset"];
+    let encoding = encoding_rs::Encoding::for_label(charset.as_bytes())
+        .ok_or(format!("unknown charset: '{}'", charset))?;
+
+    Ok(encoding.decode_without_bom_handling(&bytes).0.into_owned())
+}
+
+/// Decodes the `Q` encoding, a variant of quoted-printable where `_` stands
+/// for a space.
+fn decode_q(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut input = text.bytes();
+
+    while let Some(byte) = input.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [input.next(), input.next()];
+                let byte = match hex {
+                    [Some(high), Some(low)]
+                        if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() =>
+                    {
+                        std::str::from_utf8(&[high, low])
+                            .ok()
+                            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    }
+                    _ => None,
+                }
+                .ok_or(format!("unable to decode Q encoded-word: '{}'", text))?;
+
+                bytes.push(byte);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    remap::test_type_def![value_string {
+        expr: |_| DecodeMimeQFn {
+            value: Literal::from("foo").boxed()
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: value::Kind::Bytes,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn decode_mime_q() {
+        let cases = vec![
+            (
+                btreemap! {},
+                Ok("¡Hola, señor!".into()),
+                DecodeMimeQFn {
+                    value: Literal::from("=?utf-8?Q?=C2=A1Hola,_se=C3=B1or!?=").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Ok("Subject: Hello World".into()),
+                DecodeMimeQFn {
+                    value: Literal::from("Subject: =?UTF-8?B?SGVsbG8g?= =?UTF-8?B?V29ybGQ=?=")
+                        .boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Ok("Café, at noon".into()),
+                DecodeMimeQFn {
+                    value: Literal::from("=?ISO-8859-1*fr?Q?Caf=E9?=, at noon").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Ok("no encoded words".into()),
+                DecodeMimeQFn {
+                    value: Literal::from("no encoded words").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unknown charset: 'foo'".into()),
+                DecodeMimeQFn {
+                    value: Literal::from("=?foo?Q?bar?=").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unable to decode Q encoded-word: 'bar=Z'".into()),
+                DecodeMimeQFn {
+                    value: Literal::from("=?utf-8?Q?bar=Z?=").boxed(),
+                },
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (object, exp, func) in cases {
+            let mut object: Value = object.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
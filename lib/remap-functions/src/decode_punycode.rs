@@ -0,0 +1,110 @@
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodePunycode;
+
+impl Function for DecodePunycode {
+    fn identifier(&self) -> &'static str {
+        "decode_punycode"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Bytes(_)),
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+
+        Ok(Box::new(DecodePunycodeFn { value }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodePunycodeFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodePunycodeFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.value.execute(state, object)?.try_bytes()?;
+        let value = String::from_utf8_lossy(&bytes);
+
+        match idna::domain_to_unicode(&value) {
+            (domain, Ok(())) => Ok(domain.into()),
+            (_, Err(_)) => Err(format!("unable to decode punycode domain: '{}'", value).into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .into_fallible(true) // decoding errors
+            .with_constraint(value::Kind::Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    remap::test_type_def![value_string {
+        expr: |_| DecodePunycodeFn {
+            value: Literal::from("foo").boxed()
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: value::Kind::Bytes,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn decode_punycode() {
+        let cases = vec![
+            (
+                btreemap! {},
+                Ok("www.bücher.de".into()),
+                DecodePunycodeFn {
+                    value: Literal::from("www.xn--bcher-kva.de").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Ok("例え.テスト".into()),
+                DecodePunycodeFn {
+                    value: Literal::from("xn--r8jz45g.xn--zckzah").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Ok("example.com".into()),
+                DecodePunycodeFn {
+                    value: Literal::from("example.com").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unable to decode punycode domain: 'xn--!!.com'".into()),
+                DecodePunycodeFn {
+                    value: Literal::from("xn--!!.com").boxed(),
+                },
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (object, exp, func) in cases {
+            let mut object: Value = object.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
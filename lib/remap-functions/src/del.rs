@@ -51,6 +51,22 @@ impl Expression for DelFn {
         // future to potentially improve this situation.
         //
         // see tracking issue: https://github.com/timberio/vector/issues/5887
+        if self.path.as_ref().has_wildcard() {
+            // Remove the matches from last to first, so that removing an array
+            // element doesn't shift the indices of the ones left to remove.
+            let mut values = self
+                .path
+                .as_ref()
+                .expand(object)
+                .iter()
+                .rev()
+                .filter_map(|path| object.remove(path, false).ok().flatten())
+                .collect::<Vec<_>>();
+            values.reverse();
+
+            return Ok(values.into());
+        }
+
         Ok(object
             .remove(self.path.as_ref(), false)
             .ok()
@@ -117,6 +133,30 @@ mod tests {
                 Ok(value!(2)),
                 DelFn::new(remap::Path::from_str(".exists[1]").unwrap().into()),
             ),
+            (
+                // Array index doesn't exist
+                btreemap! { "exists" => value!([1, 2, 3]) },
+                Ok(value!(null)),
+                DelFn::new(remap::Path::from_str(".exists[5]").unwrap().into()),
+            ),
+            (
+                // Wildcard on nested fields
+                btreemap! { "tags" => value!([{name: "a", internal: true}, {name: "b"}, {internal: false}]) },
+                Ok(value!([true, false])),
+                DelFn::new(remap::Path::from_str(".tags[*].internal").unwrap().into()),
+            ),
+            (
+                // Wildcard on array elements
+                btreemap! { "exists" => value!([1, 2, 3]) },
+                Ok(value!([1, 2, 3])),
+                DelFn::new(remap::Path::from_str(".exists[*]").unwrap().into()),
+            ),
+            (
+                // Wildcard on non-array field
+                btreemap! { "exists" => "value" },
+                Ok(value!([])),
+                DelFn::new(remap::Path::from_str(".exists[*]").unwrap().into()),
+            ),
         ];
 
         let mut state = state::Program::default();
@@ -130,4 +170,15 @@ mod tests {
             assert_eq!(got, exp);
         }
     }
+
+    #[test]
+    fn del_wildcard_removes_fields() {
+        let mut object = value!({tags: [{name: "a", internal: true}, {name: "b"}]});
+        let func = DelFn::new(remap::Path::from_str(".tags[*].internal").unwrap().into());
+
+        func.execute(&mut state::Program::default(), &mut object)
+            .unwrap();
+
+        assert_eq!(object, value!({tags: [{name: "a"}, {name: "b"}]}));
+    }
 }
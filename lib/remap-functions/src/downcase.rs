@@ -90,3 +90,17 @@ mod tests {
         }
     ];
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        downcase => Downcase;
+
+        literal_value {
+            args: func_args![value: "FOO"],
+            want: Ok("foo"),
+        }
+    ];
+}
@@ -154,6 +154,11 @@ mod test {
             def: TypeDef { fallible: true, kind: Kind::Bytes, ..Default::default() },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         encode_base64 => EncodeBase64;
@@ -49,108 +49,114 @@ impl Expression for EncodeJsonFn {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{DateTime, Utc};
     use regex::Regex;
     use value::Kind;
 
-    test_function![
-        encode_json => EncodeJson;
-
+    test_type_def![
         bytes {
-            args: func_args![value: r#"hello"#],
-            want: Ok(r#""hello""#),
+            expr: |_| EncodeJsonFn { value: Literal::from("foo").boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         integer {
-            args: func_args![value: 42],
-            want: Ok("42"),
+            expr: |_| EncodeJsonFn { value: Literal::from(42).boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         float {
-            args: func_args![value: 42f64],
-            want: Ok("42.0"),
+            expr: |_| EncodeJsonFn { value: Literal::from(42f64).boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         boolean {
-            args: func_args![value: false],
-            want: Ok("false"),
+            expr: |_| EncodeJsonFn { value: Literal::from(true).boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         map {
-            args: func_args![value: map!["field": "value"]],
-            want: Ok(r#"{"field":"value"}"#),
+            expr: |_| EncodeJsonFn { value: map!{}.boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         array {
-            args: func_args![value: vec![1, 2, 3]],
-            want: Ok("[1,2,3]"),
+            expr: |_| EncodeJsonFn { value: array![].boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         timestamp {
-            args: func_args![
-                value: DateTime::parse_from_str("1983 Apr 13 12:09:14.274 +0000", "%Y %b %d %H:%M:%S%.3f %z")
-                    .unwrap()
-                    .with_timezone(&Utc)
-            ],
-            want: Ok(r#""1983-04-13 12:09:14.274 UTC""#),
-
+            expr: |_| EncodeJsonFn { value: Literal::from(chrono::Utc::now()).boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         regex {
-            args: func_args![value: Regex::new("^a\\d+$").unwrap()],
-            want: Ok(r#""^a\\d+$""#),
+            expr: |_| EncodeJsonFn { value: Literal::from(Regex::new("^a\\d+$").unwrap()).boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
 
         null {
-            args: func_args![value: Value::Null],
-            want: Ok("null"),
+            expr: |_| EncodeJsonFn { value: Literal::from(()).boxed() },
+            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use regex::Regex;
+
+    test_function![
+        encode_json => EncodeJson;
 
-    test_type_def![
         bytes {
-            expr: |_| EncodeJsonFn { value: Literal::from("foo").boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: r#"hello"#],
+            want: Ok(r#""hello""#),
         }
 
         integer {
-            expr: |_| EncodeJsonFn { value: Literal::from(42).boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: 42],
+            want: Ok("42"),
         }
 
         float {
-            expr: |_| EncodeJsonFn { value: Literal::from(42f64).boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: 42f64],
+            want: Ok("42.0"),
         }
 
         boolean {
-            expr: |_| EncodeJsonFn { value: Literal::from(true).boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: false],
+            want: Ok("false"),
         }
 
         map {
-            expr: |_| EncodeJsonFn { value: map!{}.boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: map!["field": "value"]],
+            want: Ok(r#"{"field":"value"}"#),
         }
 
         array {
-            expr: |_| EncodeJsonFn { value: array![].boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: vec![1, 2, 3]],
+            want: Ok("[1,2,3]"),
         }
 
         timestamp {
-            expr: |_| EncodeJsonFn { value: Literal::from(chrono::Utc::now()).boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![
+                value: DateTime::parse_from_str("1983 Apr 13 12:09:14.274 +0000", "%Y %b %d %H:%M:%S%.3f %z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            ],
+            want: Ok(r#""1983-04-13 12:09:14.274 UTC""#),
+
         }
 
         regex {
-            expr: |_| EncodeJsonFn { value: Literal::from(Regex::new("^a\\d+$").unwrap()).boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: Regex::new("^a\\d+$").unwrap()],
+            want: Ok(r#""^a\\d+$""#),
         }
 
         null {
-            expr: |_| EncodeJsonFn { value: Literal::from(()).boxed() },
-            def: TypeDef { kind: Kind::Bytes, ..Default::default() },
+            args: func_args![value: Value::Null],
+            want: Ok("null"),
         }
     ];
 }
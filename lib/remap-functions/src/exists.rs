@@ -37,8 +37,15 @@ impl ExistsFn {
 
 impl Expression for ExistsFn {
     fn execute(&self, _: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
-        let find = object.get(self.field.as_ref());
-        Ok(Value::from(!(find.is_err() || find.unwrap().is_none())))
+        // A wildcard path exists if any of the fields it matches exists.
+        let exists = self
+            .field
+            .as_ref()
+            .expand(object)
+            .iter()
+            .any(|path| matches!(object.get(path), Ok(Some(_))));
+
+        Ok(exists.into())
     }
 
     fn type_def(&self, _state: &state::Compiler) -> TypeDef {
@@ -54,6 +61,7 @@ impl Expression for ExistsFn {
 mod tests {
     use super::*;
     use shared::btreemap;
+    use std::str::FromStr;
 
     #[test]
     fn exists() {
@@ -68,6 +76,26 @@ mod tests {
                 Ok(true.into()),
                 ExistsFn::new(Path::from("foo")),
             ),
+            (
+                btreemap! { "foo" => value!([1, 2]) },
+                Ok(true.into()),
+                ExistsFn::new(remap::Path::from_str(".foo[1]").unwrap().into()),
+            ),
+            (
+                btreemap! { "foo" => value!([1, 2]) },
+                Ok(false.into()),
+                ExistsFn::new(remap::Path::from_str(".foo[2]").unwrap().into()),
+            ),
+            (
+                btreemap! { "tags" => value!([{name: "a"}, {name: "b", internal: true}]) },
+                Ok(true.into()),
+                ExistsFn::new(remap::Path::from_str(".tags[*].internal").unwrap().into()),
+            ),
+            (
+                btreemap! { "tags" => value!([{name: "a"}, {name: "b"}]) },
+                Ok(false.into()),
+                ExistsFn::new(remap::Path::from_str(".tags[*].internal").unwrap().into()),
+            ),
         ];
 
         let mut state = state::Program::default();
@@ -0,0 +1,200 @@
+use crate::util;
+use remap::prelude::*;
+use value::Kind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Filter;
+
+impl Function for Filter {
+    fn identifier(&self) -> &'static str {
+        "filter"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Map(_) | Value::Array(_)),
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureParameter> {
+        Some(util::ELEMENT_CLOSURE)
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(FilterFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FilterFn {
+    value: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl FilterFn {
+    /// Returns `true` if the element is kept.
+    fn keep(
+        &self,
+        key: Value,
+        value: Value,
+        state: &mut state::Program,
+        object: &mut dyn Object,
+    ) -> Result<bool> {
+        util::run_element_closure(&self.closure, key, value, state, object)?
+            .try_boolean()
+            .map_err(Into::into)
+    }
+}
+
+impl Expression for FilterFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        match self.value.execute(state, object)? {
+            Value::Map(map) => {
+                let mut filtered = std::collections::BTreeMap::new();
+
+                for (key, value) in map {
+                    if self.keep(key.clone().into(), value.clone(), state, object)? {
+                        filtered.insert(key, value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            Value::Array(array) => {
+                let mut filtered = Vec::with_capacity(array.len());
+
+                for (index, value) in array.into_iter().enumerate() {
+                    if self.keep((index as i64).into(), value.clone(), state, object)? {
+                        filtered.push(value);
+                    }
+                }
+
+                Ok(filtered.into())
+            }
+            v => Err(value::Error::Expected(Kind::Map | Kind::Array, v.kind()).into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let closure_def = self.closure.type_def(state).fallible_unless(Kind::Boolean);
+        let type_def = self
+            .value
+            .type_def(state)
+            .fallible_unless(Kind::Map | Kind::Array);
+        let fallible = type_def.is_fallible() || closure_def.is_fallible();
+
+        type_def
+            .into_fallible(fallible)
+            .with_constraint(Kind::Map | Kind::Array)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use remap::{expression::Arithmetic, Operator};
+
+    /// `|value| { value > 1 }`, or `|key, value| { key == value }`.
+    fn closure(variables: &[&str]) -> Closure {
+        let lhs = Variable::new(variables[0].to_owned(), None);
+        let (rhs, op): (Expr, _) = match variables.get(1) {
+            Some(ident) => (
+                Variable::new((*ident).to_owned(), None).into(),
+                Operator::Equal,
+            ),
+            None => (Literal::from(1).into(), Operator::Greater),
+        };
+
+        Closure::new(
+            variables.iter().map(|v| (*v).to_owned()).collect(),
+            Block::new(vec![Arithmetic::new(
+                Box::new(lhs.into()),
+                Box::new(rhs),
+                op,
+            )
+            .into()]),
+        )
+    }
+
+    fn run(value: Value, variables: &[&str]) -> Result<Value> {
+        let function = FilterFn {
+            value: Literal::from(value).boxed(),
+            closure: closure(variables),
+        };
+
+        function.execute(&mut state::Program::default(), &mut Value::Null)
+    }
+
+    remap::test_type_def![
+        boolean_closure {
+            expr: |_| FilterFn {
+                value: array![1, 2].boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(true).into()])),
+            },
+            def: TypeDef { kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+
+        non_boolean_closure {
+            expr: |_| FilterFn {
+                value: array![1, 2].boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+
+        value_non_collection {
+            expr: |_| FilterFn {
+                value: Literal::from(1).boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(true).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn array() {
+        assert_eq!(run(value!([1, 2, 0, 3]), &["v"]), Ok(value!([2, 3])));
+    }
+
+    #[test]
+    fn array_with_index() {
+        assert_eq!(
+            run(value!([0, 5, 2, 3]), &["i", "v"]),
+            Ok(value!([0, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn map() {
+        assert_eq!(run(value!({"a": 1, "b": 2}), &["v"]), Ok(value!({"b": 2})));
+    }
+
+    #[test]
+    fn map_with_key() {
+        assert_eq!(
+            run(value!({"a": "a", "b": "c"}), &["k", "v"]),
+            Ok(value!({"a": "a"}))
+        );
+    }
+
+    #[test]
+    fn non_boolean_predicate() {
+        let function = FilterFn {
+            value: array![1, 2].boxed(),
+            closure: Closure::new(
+                vec!["v".to_owned()],
+                Block::new(vec![Literal::from("yes").into()]),
+            ),
+        };
+
+        assert!(function
+            .execute(&mut state::Program::default(), &mut Value::Null)
+            .is_err());
+    }
+}
@@ -10,32 +10,67 @@ impl Function for Flatten {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            accepts: |v| matches!(v, Value::Array(_) | Value::Map(_)),
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Array(_) | Value::Map(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "separator",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "max_depth",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: false,
+            },
+        ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
-        Ok(Box::new(FlattenFn { value }))
+        let separator = arguments.optional("separator").map(Expr::boxed);
+        let max_depth = arguments.optional("max_depth").map(Expr::boxed);
+
+        Ok(Box::new(FlattenFn {
+            value,
+            separator,
+            max_depth,
+        }))
     }
 }
 
 #[derive(Debug, Clone)]
 struct FlattenFn {
     value: Box<dyn Expression>,
+    separator: Option<Box<dyn Expression>>,
+    max_depth: Option<Box<dyn Expression>>,
 }
 
 impl Expression for FlattenFn {
     fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let separator = match &self.separator {
+            Some(expr) => expr
+                .execute(state, object)?
+                .try_bytes_utf8_lossy()?
+                .into_owned(),
+            None => ".".to_owned(),
+        };
+
+        // A negative depth doesn't flatten anything, same as a depth of zero.
+        let max_depth = match &self.max_depth {
+            Some(expr) => Some(expr.execute(state, object)?.try_integer()?.max(0) as usize),
+            None => None,
+        };
+
         match self.value.execute(state, object)? {
             Value::Array(arr) => Ok(Value::Array(
-                ArrayFlatten::new(arr.iter()).cloned().collect(),
+                ArrayFlatten::new(arr.iter(), max_depth).cloned().collect(),
             )),
             Value::Map(map) => Ok(Value::Map(
-                MapFlatten::new(map.iter())
+                MapFlatten::new(map.iter(), &separator, max_depth)
                     .map(|(k, v)| (k, v.clone()))
                     .collect(),
             )),
@@ -44,9 +79,23 @@ impl Expression for FlattenFn {
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let separator_def = self.separator.as_ref().map(|separator| {
+            separator
+                .type_def(state)
+                .fallible_unless(value::Kind::Bytes)
+        });
+
+        let max_depth_def = self.max_depth.as_ref().map(|max_depth| {
+            max_depth
+                .type_def(state)
+                .fallible_unless(value::Kind::Integer)
+        });
+
         self.value
             .type_def(state)
             .fallible_unless(value::Kind::Map | value::Kind::Array)
+            .merge_optional(separator_def)
+            .merge_optional(max_depth_def)
     }
 }
 
@@ -55,22 +104,33 @@ struct MapFlatten<'a> {
     values: btree_map::Iter<'a, String, Value>,
     inner: Option<Box<MapFlatten<'a>>>,
     parent: Option<String>,
+    separator: &'a str,
+    /// The number of levels of nested maps left to flatten, if limited.
+    depth: Option<usize>,
 }
 
 impl<'a> MapFlatten<'a> {
-    fn new(values: btree_map::Iter<'a, String, Value>) -> Self {
+    fn new(
+        values: btree_map::Iter<'a, String, Value>,
+        separator: &'a str,
+        depth: Option<usize>,
+    ) -> Self {
         Self {
             values,
             inner: None,
             parent: None,
+            separator,
+            depth,
         }
     }
 
-    fn new_from_parent(parent: String, values: btree_map::Iter<'a, String, Value>) -> Self {
+    fn new_from_parent(&self, parent: String, values: btree_map::Iter<'a, String, Value>) -> Self {
         Self {
             values,
             inner: None,
             parent: Some(parent),
+            separator: self.separator,
+            depth: self.depth.map(|depth| depth - 1),
         }
     }
 
@@ -78,7 +138,7 @@ impl<'a> MapFlatten<'a> {
     fn new_key(&self, key: &str) -> String {
         match self.parent {
             None => key.to_string(),
-            Some(ref parent) => format!("{}.{}", parent, key),
+            Some(ref parent) => format!("{}{}{}", parent, self.separator, key),
         }
     }
 }
@@ -97,11 +157,10 @@ impl<'a> std::iter::Iterator for MapFlatten<'a> {
 
         let next = self.values.next();
         match next {
-            Some((key, Value::Map(value))) => {
-                self.inner = Some(Box::new(MapFlatten::new_from_parent(
-                    self.new_key(key),
-                    value.iter(),
-                )));
+            Some((key, Value::Map(value))) if self.depth != Some(0) => {
+                self.inner = Some(Box::new(
+                    self.new_from_parent(self.new_key(key), value.iter()),
+                ));
                 self.next()
             }
             Some((key, value)) => Some((self.new_key(key), value)),
@@ -115,13 +174,16 @@ impl<'a> std::iter::Iterator for MapFlatten<'a> {
 struct ArrayFlatten<'a> {
     values: std::slice::Iter<'a, Value>,
     inner: Option<Box<ArrayFlatten<'a>>>,
+    /// The number of levels of nested arrays left to flatten, if limited.
+    depth: Option<usize>,
 }
 
 impl<'a> ArrayFlatten<'a> {
-    fn new(values: std::slice::Iter<'a, Value>) -> Self {
+    fn new(values: std::slice::Iter<'a, Value>, depth: Option<usize>) -> Self {
         ArrayFlatten {
             values,
             inner: None,
+            depth,
         }
     }
 }
@@ -145,9 +207,12 @@ impl<'a> std::iter::Iterator for ArrayFlatten<'a> {
         // Then iterate over our values.
         let next = self.values.next();
         match next {
-            Some(Value::Array(next)) => {
+            Some(Value::Array(next)) if self.depth != Some(0) => {
                 // Create a new iterator for this child list.
-                self.inner = Some(Box::new(ArrayFlatten::new(next.iter())));
+                self.inner = Some(Box::new(ArrayFlatten::new(
+                    next.iter(),
+                    self.depth.map(|depth| depth - 1),
+                )));
                 self.next()
             }
             _ => next,
@@ -155,8 +220,8 @@ impl<'a> std::iter::Iterator for ArrayFlatten<'a> {
     }
 }
 
-#[cfg(test)]
-mod test {
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
     use super::*;
 
     test_function![
@@ -245,6 +310,31 @@ mod test {
             ])),
         }
 
+        map_separator {
+            args: func_args![value: value!({parent: {child1: 1, child2: {grandchild: 2}}}), separator: "_"],
+            want: Ok(value!({parent_child1: 1, parent_child2_grandchild: 2})),
+        }
+
+        map_max_depth {
+            args: func_args![value: value!({parent: {child1: 1, child2: {grandchild: 2}}}), max_depth: 1],
+            want: Ok(value!({"parent.child1": 1, "parent.child2": {grandchild: 2}})),
+        }
+
+        map_max_depth_zero {
+            args: func_args![value: value!({parent: {child: 1}}), max_depth: 0],
+            want: Ok(value!({parent: {child: 1}})),
+        }
+
+        map_max_depth_negative {
+            args: func_args![value: value!({parent: {child: 1}}), max_depth: -1],
+            want: Ok(value!({parent: {child: 1}})),
+        }
+
+        array_max_depth {
+            args: func_args![value: value!([42, [43, [44, [45]]]]), max_depth: 2],
+            want: Ok(value!([42, 43, 44, [45]])),
+        }
+
         triple_nested_map {
             args: func_args![value: value!({
                 parent1: {
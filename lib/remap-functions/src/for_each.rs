@@ -0,0 +1,147 @@
+use crate::util;
+use remap::prelude::*;
+use value::Kind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ForEach;
+
+impl Function for ForEach {
+    fn identifier(&self) -> &'static str {
+        "for_each"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Map(_) | Value::Array(_)),
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureParameter> {
+        Some(util::ELEMENT_CLOSURE)
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(ForEachFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ForEachFn {
+    value: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl Expression for ForEachFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let elements = util::collection_elements(self.value.execute(state, object)?)?;
+
+        // The closure is only run for its side effects, such as assigning to
+        // variables or to the event. Its return value is discarded.
+        for (key, value) in elements {
+            util::run_element_closure(&self.closure, key, value, state, object)?;
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let closure_def = self.closure.type_def(state);
+        let type_def = self
+            .value
+            .type_def(state)
+            .fallible_unless(Kind::Map | Kind::Array);
+        let fallible = type_def.is_fallible() || closure_def.is_fallible();
+
+        type_def
+            .into_fallible(fallible)
+            .with_constraint(Kind::Null)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use remap::{
+        expression::{Arithmetic, Assignment, Target},
+        Operator,
+    };
+
+    /// `|value| { sum = sum + value }`, or `|key, value| { sum = sum + value }`.
+    fn sum(variables: &[&str]) -> Closure {
+        let rhs = Arithmetic::new(
+            Box::new(Variable::new("sum".to_owned(), None).into()),
+            Box::new(Variable::new(variables[variables.len() - 1].to_owned(), None).into()),
+            Operator::Add,
+        );
+
+        let target = Target::Variable(Variable::new("sum".to_owned(), None));
+        let assignment = Assignment::new(
+            target,
+            Box::new(rhs.into()),
+            &mut state::Compiler::default(),
+        );
+
+        Closure::new(
+            variables.iter().map(|v| (*v).to_owned()).collect(),
+            Block::new(vec![assignment.into()]),
+        )
+    }
+
+    fn run(value: Value, variables: &[&str]) -> Result<Value> {
+        let function = ForEachFn {
+            value: Literal::from(value).boxed(),
+            closure: sum(variables),
+        };
+
+        let mut state = state::Program::default();
+        state.variables_mut().insert("sum".to_owned(), 0.into());
+
+        function.execute(&mut state, &mut Value::Null)?;
+
+        Ok(state.variable("sum").cloned().unwrap_or(Value::Null))
+    }
+
+    remap::test_type_def![
+        value_array {
+            expr: |_| ForEachFn {
+                value: array![1, 2].boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { kind: Kind::Null, ..Default::default() },
+        }
+
+        value_non_collection {
+            expr: |_| ForEachFn {
+                value: Literal::from(1).boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Null, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn array() {
+        assert_eq!(run(value!([1, 2, 3]), &["v"]), Ok(6.into()));
+    }
+
+    #[test]
+    fn array_with_index() {
+        assert_eq!(run(value!([5, 5, 5]), &["i", "v"]), Ok(15.into()));
+    }
+
+    #[test]
+    fn map() {
+        assert_eq!(run(value!({"a": 1, "b": 2}), &["v"]), Ok(3.into()));
+    }
+
+    #[test]
+    fn closure_error() {
+        assert!(run(value!([1, true]), &["v"]).is_err());
+    }
+}
@@ -1,6 +1,8 @@
+use crate::util;
 use chrono::format::{strftime::StrftimeItems, Item};
 use chrono::{DateTime, Utc};
 use remap::prelude::*;
+use shared::datetime::TimeZone;
 
 #[derive(Clone, Copy, Debug)]
 pub struct FormatTimestamp;
@@ -22,14 +24,24 @@ impl Function for FormatTimestamp {
                 accepts: |v| matches!(v, Value::Bytes(_)),
                 required: true,
             },
+            Parameter {
+                keyword: "timezone",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
         ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
         let format = arguments.required("format")?.boxed();
+        let timezone = arguments.optional("timezone").map(Expr::boxed);
 
-        Ok(Box::new(FormatTimestampFn { value, format }))
+        Ok(Box::new(FormatTimestampFn {
+            value,
+            format,
+            timezone,
+        }))
     }
 }
 
@@ -37,14 +49,20 @@ impl Function for FormatTimestamp {
 struct FormatTimestampFn {
     value: Box<dyn Expression>,
     format: Box<dyn Expression>,
+    timezone: Option<Box<dyn Expression>>,
 }
 
 impl FormatTimestampFn {
     #[cfg(test)]
-    fn new(value: Box<dyn Expression>, format: &str) -> Self {
+    fn new(value: Box<dyn Expression>, format: &str, timezone: Option<&str>) -> Self {
         let format = Box::new(Literal::from(Value::from(format)));
+        let timezone = timezone.map(|tz| Literal::from(tz).boxed());
 
-        Self { value, format }
+        Self {
+            value,
+            format,
+            timezone,
+        }
     }
 }
 
@@ -53,8 +71,9 @@ impl Expression for FormatTimestampFn {
         let bytes = self.format.execute(state, object)?.try_bytes()?;
         let format = String::from_utf8_lossy(&bytes);
         let ts = self.value.execute(state, object)?.try_timestamp()?;
+        let tz = util::timezone(self.timezone.as_deref(), state, object)?;
 
-        try_format(&ts, &format).map(Into::into)
+        try_format(&ts, &format, tz).map(Into::into)
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
@@ -72,7 +91,8 @@ impl Expression for FormatTimestampFn {
     }
 }
 
-fn try_format(dt: &DateTime<Utc>, format: &str) -> Result<String> {
+/// Formats the timestamp in the given time zone, or in UTC if none is given.
+fn try_format(dt: &DateTime<Utc>, format: &str, tz: Option<TimeZone>) -> Result<String> {
     let items = StrftimeItems::new(format)
         .map(|item| match item {
             Item::Error => Err("invalid format".into()),
@@ -80,13 +100,23 @@ fn try_format(dt: &DateTime<Utc>, format: &str) -> Result<String> {
         })
         .collect::<Result<Vec<_>>>()?;
 
-    Ok(dt.format_with_items(items.into_iter()).to_string())
+    let items = items.into_iter();
+    let formatted = match tz {
+        None => dt.format_with_items(items).to_string(),
+        Some(TimeZone::Local) => dt
+            .with_timezone(&chrono::Local)
+            .format_with_items(items)
+            .to_string(),
+        Some(TimeZone::Named(tz)) => dt.with_timezone(&tz).format_with_items(items).to_string(),
+    };
+
+    Ok(formatted)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use chrono::TimeZone as _;
     use shared::btreemap;
     use value::Kind;
 
@@ -95,6 +125,7 @@ mod tests {
             expr: |_| FormatTimestampFn {
                 value: Literal::from(chrono::Utc::now()).boxed(),
                 format: Literal::from("%s").boxed(),
+                timezone: None,
             },
             def: TypeDef { fallible: true, kind: Kind::Bytes, ..Default::default() },
         }
@@ -103,6 +134,7 @@ mod tests {
             expr: |_| FormatTimestampFn {
                 value: Box::new(Noop),
                 format: Literal::from("%s").boxed(),
+                timezone: None,
             },
             def: TypeDef { fallible: true, kind: Kind::Bytes, ..Default::default() },
         }
@@ -117,6 +149,7 @@ mod tests {
                 FormatTimestampFn::new(
                     Box::new(Literal::from(Value::from(Utc.timestamp(10, 0)))),
                     "%Q INVALID",
+                    None,
                 ),
             ),
             (
@@ -125,6 +158,7 @@ mod tests {
                 FormatTimestampFn::new(
                     Box::new(Literal::from(Value::from(Utc.timestamp(10, 0)))),
                     "%s",
+                    None,
                 ),
             ),
             (
@@ -133,6 +167,25 @@ mod tests {
                 FormatTimestampFn::new(
                     Box::new(Literal::from(Value::from(Utc.timestamp(10, 0)))),
                     "%+",
+                    None,
+                ),
+            ),
+            (
+                btreemap! {},
+                Ok("1969-12-31T19:00:10-05:00".into()),
+                FormatTimestampFn::new(
+                    Box::new(Literal::from(Value::from(Utc.timestamp(10, 0)))),
+                    "%+",
+                    Some("America/New_York"),
+                ),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unknown timezone: Nowhere/Special".into()),
+                FormatTimestampFn::new(
+                    Box::new(Literal::from(Value::from(Utc.timestamp(10, 0)))),
+                    "%+",
+                    Some("Nowhere/Special"),
                 ),
             ),
         ];
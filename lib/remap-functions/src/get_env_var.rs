@@ -1,4 +1,6 @@
 use remap::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
 
 #[derive(Clone, Copy, Debug)]
 pub struct GetEnvVar;
@@ -8,24 +10,54 @@ impl Function for GetEnvVar {
         "get_env_var"
     }
 
+    fn is_pure(&self) -> bool {
+        false
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "name",
-            accepts: |v| matches!(v, Value::Bytes(_)),
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "name",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "cache",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let name = arguments.required("name")?.boxed();
+        let cache = match arguments.optional_literal("cache")? {
+            Some(cache) => cache.into_value().try_boolean()?,
+            None => false,
+        };
 
-        Ok(Box::new(GetEnvVarFn { name }))
+        Ok(Box::new(GetEnvVarFn::new(name, cache)))
     }
 }
 
 #[derive(Debug, Clone)]
 struct GetEnvVarFn {
     name: Box<dyn Expression>,
+
+    // The values read so far, by name, if they are only read once.
+    cache: Option<Arc<RwLock<BTreeMap<String, Value>>>>,
+}
+
+impl GetEnvVarFn {
+    fn new(name: Box<dyn Expression>, cache: bool) -> Self {
+        let cache = if cache {
+            Some(Default::default())
+        } else {
+            None
+        };
+
+        Self { name, cache }
+    }
 }
 
 impl Expression for GetEnvVarFn {
@@ -33,8 +65,24 @@ impl Expression for GetEnvVarFn {
         let bytes = self.name.execute(state, object)?.try_bytes()?;
         let name = String::from_utf8_lossy(&bytes);
 
-        let value = std::env::var(name.as_ref()).map_err(|e| e.to_string())?;
-        Ok(value.into())
+        if let Some(cache) = &self.cache {
+            if let Some(value) = cache.read().unwrap().get(name.as_ref()) {
+                return Ok(value.clone());
+            }
+        }
+
+        let value: Value = std::env::var(name.as_ref())
+            .map_err(|e| e.to_string())?
+            .into();
+
+        if let Some(cache) = &self.cache {
+            cache
+                .write()
+                .unwrap()
+                .insert(name.into_owned(), value.clone());
+        }
+
+        Ok(value)
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
@@ -52,12 +100,12 @@ mod tests {
 
     remap::test_type_def![
         value_string {
-            expr: |_| GetEnvVarFn { name: Literal::from("foo").boxed() },
+            expr: |_| GetEnvVarFn::new(Literal::from("foo").boxed(), false),
             def: TypeDef { fallible: true, kind: value::Kind::Bytes, ..Default::default() },
         }
 
         fallible_expression {
-            expr: |_| GetEnvVarFn { name: Literal::from(10).boxed() },
+            expr: |_| GetEnvVarFn::new(Literal::from(10).boxed(), false),
             def: TypeDef { fallible: true, kind: value::Kind::Bytes, ..Default::default() },
         }
     ];
@@ -65,9 +113,7 @@ mod tests {
     #[test]
     fn get_env_var() {
         let mut state = state::Program::default();
-        let func = GetEnvVarFn {
-            name: Box::new(Path::from("foo")),
-        };
+        let func = GetEnvVarFn::new(Box::new(Path::from("foo")), false);
         std::env::set_var("VAR2", "var");
 
         let cases = vec![
@@ -84,4 +130,22 @@ mod tests {
             assert_eq!(got, expected);
         }
     }
+
+    #[test]
+    fn cache() {
+        let mut state = state::Program::default();
+        let mut object: Value = btreemap! {}.into();
+        let cached = GetEnvVarFn::new(Literal::from("VAR_CACHED").boxed(), true);
+        let uncached = GetEnvVarFn::new(Literal::from("VAR_CACHED").boxed(), false);
+
+        std::env::set_var("VAR_CACHED", "first");
+        assert_eq!(cached.execute(&mut state, &mut object), Ok("first".into()));
+
+        std::env::set_var("VAR_CACHED", "second");
+        assert_eq!(cached.execute(&mut state, &mut object), Ok("first".into()));
+        assert_eq!(
+            uncached.execute(&mut state, &mut object),
+            Ok("second".into())
+        );
+    }
 }
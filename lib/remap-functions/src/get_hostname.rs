@@ -1,4 +1,5 @@
 use remap::prelude::*;
+use std::sync::{Arc, RwLock};
 
 #[derive(Clone, Copy, Debug)]
 pub struct GetHostname;
@@ -8,20 +9,62 @@ impl Function for GetHostname {
         "get_hostname"
     }
 
-    fn compile(&self, _: ArgumentList) -> Result<Box<dyn Expression>> {
-        Ok(Box::new(GetHostnameFn))
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "cache",
+            accepts: |v| matches!(v, Value::Boolean(_)),
+            required: false,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let cache = match arguments.optional_literal("cache")? {
+            Some(cache) => cache.into_value().try_boolean()?,
+            None => false,
+        };
+
+        Ok(Box::new(GetHostnameFn::new(cache)))
     }
 }
 
 #[derive(Debug, Clone)]
-struct GetHostnameFn;
+struct GetHostnameFn {
+    // The hostname read by the first call, if it is only read once.
+    cache: Option<Arc<RwLock<Option<Value>>>>,
+}
+
+impl GetHostnameFn {
+    fn new(cache: bool) -> Self {
+        let cache = if cache {
+            Some(Default::default())
+        } else {
+            None
+        };
+
+        Self { cache }
+    }
+}
 
 impl Expression for GetHostnameFn {
     fn execute(&self, _: &mut state::Program, _: &mut dyn Object) -> Result<Value> {
-        Ok(hostname::get()
+        if let Some(value) = self.cache.as_ref().and_then(|c| c.read().unwrap().clone()) {
+            return Ok(value);
+        }
+
+        let value: Value = hostname::get()
             .map_err(|error| format!("failed to get hostname: {}", error))?
             .to_string_lossy()
-            .into())
+            .into();
+
+        if let Some(cache) = &self.cache {
+            *cache.write().unwrap() = Some(value.clone());
+        }
+
+        Ok(value)
     }
 
     fn type_def(&self, _: &state::Compiler) -> TypeDef {
@@ -39,7 +82,7 @@ mod tests {
     use shared::btreemap;
 
     remap::test_type_def![static_def {
-        expr: |_| GetHostnameFn,
+        expr: |_| GetHostnameFn::new(false),
         def: TypeDef {
             fallible: true,
             kind: value::Kind::Bytes,
@@ -51,8 +94,24 @@ mod tests {
     fn get_hostname() {
         let mut state = state::Program::default();
         let mut object: Value = btreemap! {}.into();
-        let value = GetHostnameFn.execute(&mut state, &mut object).unwrap();
+        let value = GetHostnameFn::new(false)
+            .execute(&mut state, &mut object)
+            .unwrap();
 
         assert!(matches!(&value, Value::Bytes(_)));
     }
+
+    #[test]
+    fn cache() {
+        let mut state = state::Program::default();
+        let mut object: Value = btreemap! {}.into();
+        let func = GetHostnameFn::new(true);
+
+        let value = func.execute(&mut state, &mut object).unwrap();
+        assert_eq!(
+            func.cache.as_ref().unwrap().read().unwrap().clone(),
+            Some(value.clone())
+        );
+        assert_eq!(func.execute(&mut state, &mut object), Ok(value));
+    }
 }
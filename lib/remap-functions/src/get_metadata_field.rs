@@ -0,0 +1,127 @@
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetMetadataField;
+
+impl Function for GetMetadataField {
+    fn identifier(&self) -> &'static str {
+        "get_metadata_field"
+    }
+
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            accepts: |v| matches!(v, Value::Bytes(_)),
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let key = arguments.required("key")?.boxed();
+
+        Ok(Box::new(GetMetadataFieldFn { key }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetMetadataFieldFn {
+    key: Box<dyn Expression>,
+}
+
+impl Expression for GetMetadataFieldFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.key.execute(state, object)?.try_bytes()?;
+        let key = String::from_utf8_lossy(&bytes);
+
+        Ok(object.get_metadata(&key).unwrap_or(Value::Null))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.key
+            .type_def(state)
+            .fallible_unless(value::Kind::Bytes)
+            .with_constraint(value::Kind::all())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| GetMetadataFieldFn { key: Literal::from("http.headers").boxed() },
+            def: TypeDef { kind: value::Kind::all(), ..Default::default() },
+        }
+
+        fallible_expression {
+            expr: |_| GetMetadataFieldFn { key: Literal::from(10).boxed() },
+            def: TypeDef { fallible: true, kind: value::Kind::all(), ..Default::default() },
+        }
+    ];
+
+    /// An object that carries nothing but metadata.
+    #[derive(Debug, Default)]
+    struct MetadataObject(BTreeMap<String, Value>);
+
+    impl Object for MetadataObject {
+        fn insert(&mut self, _: &remap::Path, _: Value) -> std::result::Result<(), String> {
+            Ok(())
+        }
+
+        fn get(&self, _: &remap::Path) -> std::result::Result<Option<Value>, String> {
+            Ok(None)
+        }
+
+        fn remove(
+            &mut self,
+            _: &remap::Path,
+            _: bool,
+        ) -> std::result::Result<Option<Value>, String> {
+            Ok(None)
+        }
+
+        fn get_metadata(&self, key: &str) -> Option<Value> {
+            self.0.get(key).cloned()
+        }
+    }
+
+    #[test]
+    fn get_metadata_field() {
+        let mut state = state::Program::default();
+        let mut object = MetadataObject::default();
+        object
+            .0
+            .insert("kafka.partition".to_owned(), Value::from(3));
+
+        let cases = vec![
+            ("kafka.partition", Value::from(3)),
+            ("kafka.offset", Value::Null),
+        ];
+
+        for (key, want) in cases {
+            let func = GetMetadataFieldFn {
+                key: Literal::from(key).boxed(),
+            };
+            let got = func.execute(&mut state, &mut object).unwrap();
+
+            assert_eq!(got, want);
+        }
+    }
+
+    #[test]
+    fn object_without_metadata() {
+        let mut state = state::Program::default();
+        let mut object = Value::from(BTreeMap::new());
+        let func = GetMetadataFieldFn {
+            key: Literal::from("vector.source_type").boxed(),
+        };
+
+        assert_eq!(func.execute(&mut state, &mut object).unwrap(), Value::Null);
+    }
+}
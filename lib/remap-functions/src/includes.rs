@@ -80,6 +80,11 @@ mod tests {
             def: TypeDef { fallible: true, kind: Kind::Boolean, ..Default::default() },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         includes => Includes;
@@ -70,6 +70,11 @@ mod tests {
             ..Default::default()
         },
     }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         ipv6_to_ipv4 => Ipv6ToIpV4;
@@ -93,6 +93,11 @@ mod tests {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         is_nullish => IsNullish;
@@ -0,0 +1,97 @@
+use crate::util::Case;
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct KebabCase;
+
+impl Function for KebabCase {
+    fn identifier(&self) -> &'static str {
+        "kebabcase"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Bytes(_)),
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+
+        Ok(Box::new(KebabCaseFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KebabCaseFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for KebabCaseFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.value.execute(state, object)?.try_bytes()?;
+        let value = String::from_utf8_lossy(&bytes);
+
+        Ok(Case::Kebab.convert(&value).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Bytes)
+            .with_constraint(value::Kind::Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| KebabCaseFn { value: Literal::from("foo").boxed() },
+            def: TypeDef { kind: value::Kind::Bytes, ..Default::default() },
+        }
+
+        value_non_string {
+            expr: |_| KebabCaseFn { value: Literal::from(1).boxed() },
+            def: TypeDef { fallible: true, kind: value::Kind::Bytes, ..Default::default() },
+        }
+    ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        kebabcase => KebabCase;
+
+        camel {
+            args: func_args![value: "userId"],
+            want: Ok("user-id"),
+        }
+
+        snake {
+            args: func_args![value: "user_id"],
+            want: Ok("user-id"),
+        }
+
+        kebab {
+            args: func_args![value: "user-id"],
+            want: Ok("user-id"),
+        }
+
+        pascal_acronym {
+            args: func_args![value: "UserID"],
+            want: Ok("user-id"),
+        }
+
+        words {
+            args: func_args![value: "HTTP response code"],
+            want: Ok("http-response-code"),
+        }
+    ];
+}
@@ -99,6 +99,11 @@ mod tests {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         length => Length;
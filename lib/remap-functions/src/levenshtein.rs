@@ -0,0 +1,116 @@
+use crate::util;
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Levenshtein;
+
+impl Function for Levenshtein {
+    fn identifier(&self) -> &'static str {
+        "levenshtein"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "a",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "b",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let a = arguments.required("a")?.boxed();
+        let b = arguments.required("b")?.boxed();
+
+        Ok(Box::new(LevenshteinFn { a, b }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LevenshteinFn {
+    a: Box<dyn Expression>,
+    b: Box<dyn Expression>,
+}
+
+impl LevenshteinFn {
+    #[cfg(test)]
+    fn new(a: &str, b: &str) -> Self {
+        Self {
+            a: Literal::from(a).boxed(),
+            b: Literal::from(b).boxed(),
+        }
+    }
+}
+
+impl Expression for LevenshteinFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let a = self.a.execute(state, object)?;
+        let b = self.b.execute(state, object)?;
+
+        let distance =
+            util::levenshtein_distance(&a.try_bytes_utf8_lossy()?, &b.try_bytes_utf8_lossy()?);
+
+        Ok((distance as i64).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.a
+            .type_def(state)
+            .fallible_unless(value::Kind::Bytes)
+            .merge(self.b.type_def(state).fallible_unless(value::Kind::Bytes))
+            .with_constraint(value::Kind::Integer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| LevenshteinFn::new("foo", "bar"),
+            def: TypeDef { kind: value::Kind::Integer, ..Default::default() },
+        }
+
+        fallible_expression {
+            expr: |_| LevenshteinFn {
+                a: Literal::from("foo").boxed(),
+                b: Literal::from(10).boxed(),
+            },
+            def: TypeDef { fallible: true, kind: value::Kind::Integer, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn levenshtein() {
+        let cases = vec![
+            ("", "", 0),
+            ("", "abc", 3),
+            ("abc", "", 3),
+            ("kitten", "sitting", 3),
+            ("flaw", "lawn", 2),
+            ("google.com", "gooogle.com", 1),
+            ("paypal.com", "paypa1.com", 1),
+            ("ab", "ba", 2),
+            ("héllo", "hello", 1),
+            ("same", "same", 0),
+        ];
+
+        let mut state = state::Program::default();
+        let mut object = Value::Map(Default::default());
+
+        for (a, b, want) in cases {
+            let got = LevenshteinFn::new(a, b)
+                .execute(&mut state, &mut object)
+                .unwrap();
+
+            assert_eq!(got, Value::from(want), "{} -> {}", a, b);
+        }
+    }
+}
@@ -1,17 +1,31 @@
 mod util;
 
+#[cfg(feature = "all")]
+mod all;
+#[cfg(feature = "any")]
+mod any;
 #[cfg(feature = "append")]
 mod append;
 #[cfg(feature = "assert")]
 mod assert;
+#[cfg(feature = "camelcase")]
+mod camelcase;
 #[cfg(feature = "ceil")]
 mod ceil;
+#[cfg(feature = "coerce_types")]
+mod coerce_types;
+#[cfg(feature = "community_id")]
+mod community_id;
 #[cfg(feature = "compact")]
 mod compact;
 #[cfg(feature = "contains")]
 mod contains;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
+#[cfg(feature = "decode_mime_q")]
+mod decode_mime_q;
+#[cfg(feature = "decode_punycode")]
+mod decode_punycode;
 #[cfg(feature = "del")]
 mod del;
 #[cfg(feature = "downcase")]
@@ -24,18 +38,26 @@ mod encode_json;
 mod ends_with;
 #[cfg(feature = "exists")]
 mod exists;
+#[cfg(feature = "filter")]
+mod filter;
 #[cfg(feature = "flatten")]
 mod flatten;
 #[cfg(feature = "floor")]
 mod floor;
+#[cfg(feature = "for_each")]
+mod for_each;
 #[cfg(feature = "format_number")]
 mod format_number;
 #[cfg(feature = "format_timestamp")]
 mod format_timestamp;
+#[cfg(feature = "from_unix_timestamp")]
+mod from_unix_timestamp;
 #[cfg(feature = "get_env_var")]
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "get_metadata_field")]
+mod get_metadata_field;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "ip_cidr_contains")]
@@ -48,24 +70,42 @@ mod ip_to_ipv6;
 mod ipv6_to_ipv4;
 #[cfg(feature = "is_nullish")]
 mod is_nullish;
+#[cfg(feature = "kebabcase")]
+mod kebabcase;
 #[cfg(feature = "length")]
 mod length;
+#[cfg(feature = "levenshtein")]
+mod levenshtein;
 #[cfg(feature = "log")]
 mod log;
+#[cfg(feature = "map_keys")]
+mod map_keys;
+#[cfg(feature = "map_values")]
+mod map_values;
 #[cfg(feature = "match")]
 mod r#match;
+#[cfg(feature = "match_query")]
+mod match_query;
 #[cfg(feature = "md5")]
 mod md5;
 #[cfg(feature = "merge")]
 mod merge;
+#[cfg(feature = "normalize_ecs")]
+mod normalize_ecs;
+#[cfg(feature = "normalize_ocsf")]
+mod normalize_ocsf;
 #[cfg(feature = "now")]
 mod now;
+#[cfg(feature = "parse_avro")]
+mod parse_avro;
 #[cfg(feature = "parse_aws_alb_log")]
 mod parse_aws_alb_log;
 #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
 mod parse_aws_cloudwatch_log_subscription_message;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
+#[cfg(feature = "parse_bytes")]
+mod parse_bytes;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
 #[cfg(feature = "parse_grok")]
@@ -74,6 +114,8 @@ mod parse_grok;
 mod parse_json;
 #[cfg(feature = "parse_key_value")]
 mod parse_key_value;
+#[cfg(feature = "parse_query_string")]
+mod parse_query_string;
 #[cfg(feature = "parse_regex")]
 mod parse_regex;
 #[cfg(feature = "parse_regex_all")]
@@ -86,10 +128,20 @@ mod parse_timestamp;
 mod parse_tokens;
 #[cfg(feature = "parse_url")]
 mod parse_url;
+#[cfg(feature = "pascalcase")]
+mod pascalcase;
 #[cfg(feature = "push")]
 mod push;
+#[cfg(feature = "random_bytes")]
+mod random_bytes;
+#[cfg(feature = "random_int")]
+mod random_int;
 #[cfg(feature = "redact")]
 mod redact;
+#[cfg(feature = "rename_keys")]
+mod rename_keys;
+#[cfg(feature = "rename_keys_case")]
+mod rename_keys_case;
 #[cfg(feature = "replace")]
 mod replace;
 #[cfg(feature = "round")]
@@ -100,8 +152,12 @@ mod sha1;
 mod sha2;
 #[cfg(feature = "sha3")]
 mod sha3;
+#[cfg(feature = "similarity")]
+mod similarity;
 #[cfg(feature = "slice")]
 mod slice;
+#[cfg(feature = "snakecase")]
+mod snakecase;
 #[cfg(feature = "split")]
 mod split;
 #[cfg(feature = "starts_with")]
@@ -110,6 +166,8 @@ mod starts_with;
 mod strip_ansi_escape_codes;
 #[cfg(feature = "strip_whitespace")]
 mod strip_whitespace;
+#[cfg(feature = "tag_types_externally")]
+mod tag_types_externally;
 #[cfg(feature = "to_bool")]
 mod to_bool;
 #[cfg(feature = "to_float")]
@@ -130,10 +188,16 @@ mod to_timestamp;
 mod to_unix_timestamp;
 #[cfg(feature = "truncate")]
 mod truncate;
+#[cfg(feature = "unflatten")]
+mod unflatten;
+#[cfg(feature = "unnest")]
+mod unnest;
 #[cfg(feature = "upcase")]
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "uuid_v7")]
+mod uuid_v7;
 
 // -----------------------------------------------------------------------------
 
@@ -141,18 +205,32 @@ mod uuid_v4;
 pub use crate::md5::Md5;
 #[cfg(feature = "sha1")]
 pub use crate::sha1::Sha1;
+#[cfg(feature = "all")]
+pub use all::All;
+#[cfg(feature = "any")]
+pub use any::Any;
 #[cfg(feature = "append")]
 pub use append::Append;
 #[cfg(feature = "assert")]
 pub use assert::Assert;
+#[cfg(feature = "camelcase")]
+pub use camelcase::CamelCase;
 #[cfg(feature = "ceil")]
 pub use ceil::Ceil;
+#[cfg(feature = "coerce_types")]
+pub use coerce_types::CoerceTypes;
+#[cfg(feature = "community_id")]
+pub use community_id::CommunityId;
 #[cfg(feature = "compact")]
 pub use compact::Compact;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
+#[cfg(feature = "decode_mime_q")]
+pub use decode_mime_q::DecodeMimeQ;
+#[cfg(feature = "decode_punycode")]
+pub use decode_punycode::DecodePunycode;
 #[cfg(feature = "del")]
 pub use del::Del;
 #[cfg(feature = "downcase")]
@@ -165,18 +243,26 @@ pub use encode_json::EncodeJson;
 pub use ends_with::EndsWith;
 #[cfg(feature = "exists")]
 pub use exists::Exists;
+#[cfg(feature = "filter")]
+pub use filter::Filter;
 #[cfg(feature = "flatten")]
 pub use flatten::Flatten;
 #[cfg(feature = "floor")]
 pub use floor::Floor;
+#[cfg(feature = "for_each")]
+pub use for_each::ForEach;
 #[cfg(feature = "format_number")]
 pub use format_number::FormatNumber;
 #[cfg(feature = "format_timestamp")]
 pub use format_timestamp::FormatTimestamp;
+#[cfg(feature = "from_unix_timestamp")]
+pub use from_unix_timestamp::FromUnixTimestamp;
 #[cfg(feature = "get_env_var")]
 pub use get_env_var::GetEnvVar;
 #[cfg(feature = "get_hostname")]
 pub use get_hostname::GetHostname;
+#[cfg(feature = "get_metadata_field")]
+pub use get_metadata_field::GetMetadataField;
 #[cfg(feature = "includes")]
 pub use includes::Includes;
 #[cfg(feature = "ip_cidr_contains")]
@@ -189,20 +275,38 @@ pub use ip_to_ipv6::IpToIpv6;
 pub use ipv6_to_ipv4::Ipv6ToIpV4;
 #[cfg(feature = "is_nullish")]
 pub use is_nullish::IsNullish;
+#[cfg(feature = "kebabcase")]
+pub use kebabcase::KebabCase;
 #[cfg(feature = "length")]
 pub use length::Length;
+#[cfg(feature = "levenshtein")]
+pub use levenshtein::Levenshtein;
 #[cfg(feature = "log")]
 pub use log::Log;
+#[cfg(feature = "map_keys")]
+pub use map_keys::MapKeys;
+#[cfg(feature = "map_values")]
+pub use map_values::MapValues;
+#[cfg(feature = "match_query")]
+pub use match_query::MatchQuery;
 #[cfg(feature = "merge")]
 pub use merge::Merge;
+#[cfg(feature = "normalize_ecs")]
+pub use normalize_ecs::NormalizeEcs;
+#[cfg(feature = "normalize_ocsf")]
+pub use normalize_ocsf::NormalizeOcsf;
 #[cfg(feature = "now")]
 pub use now::Now;
+#[cfg(feature = "parse_avro")]
+pub use parse_avro::ParseAvro;
 #[cfg(feature = "parse_aws_alb_log")]
 pub use parse_aws_alb_log::ParseAwsAlbLog;
 #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
+#[cfg(feature = "parse_bytes")]
+pub use parse_bytes::ParseBytes;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
 #[cfg(feature = "parse_grok")]
@@ -211,6 +315,8 @@ pub use parse_grok::ParseGrok;
 pub use parse_json::ParseJson;
 #[cfg(feature = "parse_key_value")]
 pub use parse_key_value::ParseKeyValue;
+#[cfg(feature = "parse_query_string")]
+pub use parse_query_string::ParseQueryString;
 #[cfg(feature = "parse_regex")]
 pub use parse_regex::ParseRegex;
 #[cfg(feature = "parse_regex_all")]
@@ -223,12 +329,22 @@ pub use parse_timestamp::ParseTimestamp;
 pub use parse_tokens::ParseTokens;
 #[cfg(feature = "parse_url")]
 pub use parse_url::ParseUrl;
+#[cfg(feature = "pascalcase")]
+pub use pascalcase::PascalCase;
 #[cfg(feature = "push")]
 pub use push::Push;
 #[cfg(feature = "match")]
 pub use r#match::Match;
+#[cfg(feature = "random_bytes")]
+pub use random_bytes::RandomBytes;
+#[cfg(feature = "random_int")]
+pub use random_int::RandomInt;
 #[cfg(feature = "redact")]
 pub use redact::Redact;
+#[cfg(feature = "rename_keys")]
+pub use rename_keys::RenameKeys;
+#[cfg(feature = "rename_keys_case")]
+pub use rename_keys_case::RenameKeysCase;
 #[cfg(feature = "replace")]
 pub use replace::Replace;
 #[cfg(feature = "round")]
@@ -237,8 +353,12 @@ pub use round::Round;
 pub use sha2::Sha2;
 #[cfg(feature = "sha3")]
 pub use sha3::Sha3;
+#[cfg(feature = "similarity")]
+pub use similarity::Similarity;
 #[cfg(feature = "slice")]
 pub use slice::Slice;
+#[cfg(feature = "snakecase")]
+pub use snakecase::SnakeCase;
 #[cfg(feature = "split")]
 pub use split::Split;
 #[cfg(feature = "starts_with")]
@@ -247,6 +367,8 @@ pub use starts_with::StartsWith;
 pub use strip_ansi_escape_codes::StripAnsiEscapeCodes;
 #[cfg(feature = "strip_whitespace")]
 pub use strip_whitespace::StripWhitespace;
+#[cfg(feature = "tag_types_externally")]
+pub use tag_types_externally::TagTypesExternally;
 #[cfg(feature = "to_bool")]
 pub use to_bool::ToBool;
 #[cfg(feature = "to_float")]
@@ -267,25 +389,45 @@ pub use to_timestamp::ToTimestamp;
 pub use to_unix_timestamp::ToUnixTimestamp;
 #[cfg(feature = "truncate")]
 pub use truncate::Truncate;
+#[cfg(feature = "unflatten")]
+pub use unflatten::Unflatten;
+#[cfg(feature = "unnest")]
+pub use unnest::Unnest;
 #[cfg(feature = "upcase")]
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "uuid_v7")]
+pub use uuid_v7::UuidV7;
 
 pub fn all() -> Vec<Box<dyn remap::Function>> {
     vec![
+        #[cfg(feature = "all")]
+        Box::new(All),
+        #[cfg(feature = "any")]
+        Box::new(Any),
         #[cfg(feature = "append")]
         Box::new(Append),
         #[cfg(feature = "assert")]
         Box::new(Assert),
+        #[cfg(feature = "camelcase")]
+        Box::new(CamelCase),
         #[cfg(feature = "ceil")]
         Box::new(Ceil),
+        #[cfg(feature = "coerce_types")]
+        Box::new(CoerceTypes),
+        #[cfg(feature = "community_id")]
+        Box::new(CommunityId),
         #[cfg(feature = "compact")]
         Box::new(Compact),
         #[cfg(feature = "contains")]
         Box::new(Contains),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
+        #[cfg(feature = "decode_mime_q")]
+        Box::new(DecodeMimeQ),
+        #[cfg(feature = "decode_punycode")]
+        Box::new(DecodePunycode),
         #[cfg(feature = "del")]
         Box::new(Del),
         #[cfg(feature = "downcase")]
@@ -298,6 +440,8 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(EndsWith),
         #[cfg(feature = "exists")]
         Box::new(Exists),
+        #[cfg(feature = "filter")]
+        Box::new(Filter),
         #[cfg(feature = "parse_regex")]
         Box::new(ParseRegex),
         #[cfg(feature = "parse_regex_all")]
@@ -306,14 +450,20 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(Flatten),
         #[cfg(feature = "floor")]
         Box::new(Floor),
+        #[cfg(feature = "for_each")]
+        Box::new(ForEach),
         #[cfg(feature = "format_number")]
         Box::new(FormatNumber),
         #[cfg(feature = "format_timestamp")]
         Box::new(FormatTimestamp),
+        #[cfg(feature = "from_unix_timestamp")]
+        Box::new(FromUnixTimestamp),
         #[cfg(feature = "get_env_var")]
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "get_metadata_field")]
+        Box::new(GetMetadataField),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "ip_cidr_contains")]
@@ -326,22 +476,38 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(Ipv6ToIpV4),
         #[cfg(feature = "is_nullish")]
         Box::new(IsNullish),
+        #[cfg(feature = "kebabcase")]
+        Box::new(KebabCase),
         #[cfg(feature = "length")]
         Box::new(Length),
+        #[cfg(feature = "levenshtein")]
+        Box::new(Levenshtein),
         #[cfg(feature = "log")]
         Box::new(Log),
+        #[cfg(feature = "map_keys")]
+        Box::new(MapKeys),
+        #[cfg(feature = "map_values")]
+        Box::new(MapValues),
         #[cfg(feature = "md5")]
         Box::new(Md5),
         #[cfg(feature = "merge")]
         Box::new(Merge),
+        #[cfg(feature = "normalize_ecs")]
+        Box::new(NormalizeEcs),
+        #[cfg(feature = "normalize_ocsf")]
+        Box::new(NormalizeOcsf),
         #[cfg(feature = "now")]
         Box::new(Now),
+        #[cfg(feature = "parse_avro")]
+        Box::new(ParseAvro),
         #[cfg(feature = "parse_aws_alb_log")]
         Box::new(ParseAwsAlbLog),
         #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_bytes")]
+        Box::new(ParseBytes),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
         #[cfg(feature = "parse_grok")]
@@ -350,6 +516,8 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(ParseJson),
         #[cfg(feature = "parse_key_value")]
         Box::new(ParseKeyValue),
+        #[cfg(feature = "parse_query_string")]
+        Box::new(ParseQueryString),
         #[cfg(feature = "parse_syslog")]
         Box::new(ParseSyslog),
         #[cfg(feature = "parse_timestamp")]
@@ -358,12 +526,24 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(ParseTokens),
         #[cfg(feature = "parse_url")]
         Box::new(ParseUrl),
+        #[cfg(feature = "pascalcase")]
+        Box::new(PascalCase),
         #[cfg(feature = "push")]
         Box::new(Push),
+        #[cfg(feature = "random_bytes")]
+        Box::new(RandomBytes),
+        #[cfg(feature = "random_int")]
+        Box::new(RandomInt),
         #[cfg(feature = "match")]
         Box::new(Match),
+        #[cfg(feature = "match_query")]
+        Box::new(MatchQuery),
         #[cfg(feature = "redact")]
         Box::new(Redact),
+        #[cfg(feature = "rename_keys")]
+        Box::new(RenameKeys),
+        #[cfg(feature = "rename_keys_case")]
+        Box::new(RenameKeysCase),
         #[cfg(feature = "replace")]
         Box::new(Replace),
         #[cfg(feature = "round")]
@@ -374,8 +554,12 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(Sha2),
         #[cfg(feature = "sha3")]
         Box::new(Sha3),
+        #[cfg(feature = "similarity")]
+        Box::new(Similarity),
         #[cfg(feature = "slice")]
         Box::new(Slice),
+        #[cfg(feature = "snakecase")]
+        Box::new(SnakeCase),
         #[cfg(feature = "split")]
         Box::new(Split),
         #[cfg(feature = "starts_with")]
@@ -384,6 +568,8 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(StripAnsiEscapeCodes),
         #[cfg(feature = "strip_whitespace")]
         Box::new(StripWhitespace),
+        #[cfg(feature = "tag_types_externally")]
+        Box::new(TagTypesExternally),
         #[cfg(feature = "to_bool")]
         Box::new(ToBool),
         #[cfg(feature = "to_float")]
@@ -404,9 +590,116 @@ pub fn all() -> Vec<Box<dyn remap::Function>> {
         Box::new(ToUnixTimestamp),
         #[cfg(feature = "truncate")]
         Box::new(Truncate),
+        #[cfg(feature = "unflatten")]
+        Box::new(Unflatten),
+        #[cfg(feature = "unnest")]
+        Box::new(Unnest),
         #[cfg(feature = "upcase")]
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "uuid_v7")]
+        Box::new(UuidV7),
+    ]
+}
+
+/// Returns the test cases of the functions, compiled, along with the names of
+/// the functions, for the benchmarks to run.
+#[cfg(feature = "benches")]
+pub fn bench_cases() -> Vec<(
+    &'static str,
+    Vec<(&'static str, Box<dyn remap::Expression>)>,
+)> {
+    vec![
+        #[cfg(feature = "append")]
+        append::cases::cases(),
+        #[cfg(feature = "camelcase")]
+        camelcase::cases::cases(),
+        #[cfg(feature = "compact")]
+        compact::cases::cases(),
+        #[cfg(feature = "contains")]
+        contains::cases::cases(),
+        #[cfg(feature = "decode_base64")]
+        decode_base64::cases::cases(),
+        #[cfg(feature = "downcase")]
+        downcase::cases::cases(),
+        #[cfg(feature = "encode_base64")]
+        encode_base64::cases::cases(),
+        #[cfg(feature = "encode_json")]
+        encode_json::cases::cases(),
+        #[cfg(feature = "flatten")]
+        flatten::cases::cases(),
+        #[cfg(feature = "includes")]
+        includes::cases::cases(),
+        #[cfg(feature = "ipv6_to_ipv4")]
+        ipv6_to_ipv4::cases::cases(),
+        #[cfg(feature = "is_nullish")]
+        is_nullish::cases::cases(),
+        #[cfg(feature = "kebabcase")]
+        kebabcase::cases::cases(),
+        #[cfg(feature = "length")]
+        length::cases::cases(),
+        #[cfg(feature = "match_query")]
+        match_query::cases::cases(),
+        #[cfg(feature = "md5")]
+        md5::cases::cases(),
+        #[cfg(feature = "merge")]
+        merge::cases::cases(),
+        #[cfg(feature = "normalize_ecs")]
+        normalize_ecs::cases::cases(),
+        #[cfg(feature = "normalize_ocsf")]
+        normalize_ocsf::cases::cases(),
+        #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
+        parse_aws_cloudwatch_log_subscription_message::cases::cases(),
+        #[cfg(feature = "parse_grok")]
+        parse_grok::cases::cases(),
+        #[cfg(feature = "parse_json")]
+        parse_json::cases::cases(),
+        #[cfg(feature = "parse_key_value")]
+        parse_key_value::cases::cases(),
+        #[cfg(feature = "parse_query_string")]
+        parse_query_string::cases::cases(),
+        #[cfg(feature = "parse_regex")]
+        parse_regex::cases::cases(),
+        #[cfg(feature = "parse_regex_all")]
+        parse_regex_all::cases::cases(),
+        #[cfg(feature = "parse_syslog")]
+        parse_syslog::cases::cases(),
+        #[cfg(feature = "parse_timestamp")]
+        parse_timestamp::cases::cases(),
+        #[cfg(feature = "parse_url")]
+        parse_url::cases::cases(),
+        #[cfg(feature = "pascalcase")]
+        pascalcase::cases::cases(),
+        #[cfg(feature = "push")]
+        push::cases::cases(),
+        #[cfg(feature = "random_bytes")]
+        random_bytes::cases::cases(),
+        #[cfg(feature = "random_int")]
+        random_int::cases::cases(),
+        #[cfg(feature = "rename_keys")]
+        rename_keys::cases::cases(),
+        #[cfg(feature = "rename_keys_case")]
+        rename_keys_case::cases::cases(),
+        #[cfg(feature = "replace")]
+        replace::cases::cases(),
+        #[cfg(feature = "sha1")]
+        sha1::cases::cases(),
+        #[cfg(feature = "snakecase")]
+        snakecase::cases::cases(),
+        #[cfg(feature = "split")]
+        split::cases::cases(),
+        #[cfg(feature = "to_int")]
+        to_int::cases::cases(),
+        #[cfg(feature = "to_syslog_facility")]
+        to_syslog_facility::cases::cases(),
+        #[cfg(feature = "to_syslog_level")]
+        to_syslog_level::cases::cases(),
+        #[cfg(feature = "to_syslog_severity")]
+        to_syslog_severity::cases::cases(),
+        #[cfg(feature = "unflatten")]
+        unflatten::cases::cases(),
+        #[cfg(feature = "upcase")]
+        upcase::cases::cases(),
     ]
 }
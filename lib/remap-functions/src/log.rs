@@ -11,6 +11,10 @@ impl Function for Log {
         "log"
     }
 
+    fn is_pure(&self) -> bool {
+        false
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
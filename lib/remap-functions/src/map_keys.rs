@@ -0,0 +1,245 @@
+use remap::prelude::*;
+use std::collections::BTreeMap;
+use value::Kind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapKeys;
+
+impl Function for MapKeys {
+    fn identifier(&self) -> &'static str {
+        "map_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn closure(&self) -> Option<ClosureParameter> {
+        Some(ClosureParameter {
+            required: true,
+            min_variables: 1,
+            max_variables: 1,
+        })
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let recursive = arguments.optional("recursive").map(Expr::boxed);
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(MapKeysFn {
+            value,
+            recursive,
+            closure,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapKeysFn {
+    value: Box<dyn Expression>,
+    recursive: Option<Box<dyn Expression>>,
+    closure: Closure,
+}
+
+impl MapKeysFn {
+    /// Rename all keys of the map. If two keys are renamed to the same key,
+    /// the value of the last one (in key order) is kept.
+    fn map_keys(
+        &self,
+        map: BTreeMap<String, Value>,
+        recursive: bool,
+        state: &mut state::Program,
+        object: &mut dyn Object,
+    ) -> Result<BTreeMap<String, Value>> {
+        map.into_iter()
+            .map(|(key, value)| {
+                let key = self
+                    .closure
+                    .run(vec![key.into()], state, object)?
+                    .try_bytes_utf8_lossy()?
+                    .into_owned();
+
+                let value = if recursive {
+                    self.recurse(value, state, object)?
+                } else {
+                    value
+                };
+
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    /// Rename the keys of maps nested in the value, including maps in arrays.
+    fn recurse(
+        &self,
+        value: Value,
+        state: &mut state::Program,
+        object: &mut dyn Object,
+    ) -> Result<Value> {
+        match value {
+            Value::Map(map) => self.map_keys(map, true, state, object).map(Into::into),
+            Value::Array(array) => array
+                .into_iter()
+                .map(|value| self.recurse(value, state, object))
+                .collect::<Result<Vec<_>>>()
+                .map(Into::into),
+            value => Ok(value),
+        }
+    }
+}
+
+impl Expression for MapKeysFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let recursive = match &self.recursive {
+            Some(expr) => expr.execute(state, object)?.try_boolean()?,
+            None => false,
+        };
+
+        let map = self.value.execute(state, object)?.try_map()?;
+
+        self.map_keys(map, recursive, state, object).map(Into::into)
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let closure_def = self.closure.type_def(state).fallible_unless(Kind::Bytes);
+        let recursive_def = self
+            .recursive
+            .as_ref()
+            .map(|recursive| recursive.type_def(state).fallible_unless(Kind::Boolean));
+        let type_def = self
+            .value
+            .type_def(state)
+            .fallible_unless(Kind::Map)
+            .merge_optional(recursive_def);
+        let fallible = type_def.is_fallible() || closure_def.is_fallible();
+
+        type_def
+            .into_fallible(fallible)
+            .with_constraint(Kind::Map)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use remap::{expression::Arithmetic, Operator};
+
+    /// `|key| { "prefix_" + key }`
+    fn prefix() -> Closure {
+        Closure::new(
+            vec!["key".to_owned()],
+            Block::new(vec![Arithmetic::new(
+                Box::new(Literal::from("prefix_").into()),
+                Box::new(Variable::new("key".to_owned(), None).into()),
+                Operator::Add,
+            )
+            .into()]),
+        )
+    }
+
+    fn run(value: Value, recursive: bool) -> Result<Value> {
+        let function = MapKeysFn {
+            value: Literal::from(value).boxed(),
+            recursive: Some(Literal::from(recursive).boxed()),
+            closure: prefix(),
+        };
+
+        function.execute(&mut state::Program::default(), &mut Value::Null)
+    }
+
+    remap::test_type_def![
+        bytes_closure {
+            expr: |_| MapKeysFn {
+                value: map!["a": 1].boxed(),
+                recursive: None,
+                closure: Closure::new(vec!["k".to_owned()], Block::new(vec![Literal::from("b").into()])),
+            },
+            def: TypeDef { kind: Kind::Map, ..Default::default() },
+        }
+
+        non_bytes_closure {
+            expr: |_| MapKeysFn {
+                value: map!["a": 1].boxed(),
+                recursive: None,
+                closure: Closure::new(vec!["k".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map, ..Default::default() },
+        }
+
+        value_non_map {
+            expr: |_| MapKeysFn {
+                value: array![1].boxed(),
+                recursive: None,
+                closure: Closure::new(vec!["k".to_owned()], Block::new(vec![Literal::from("b").into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn map() {
+        assert_eq!(
+            run(value!({"a": 1, "b": {"c": 2}}), false),
+            Ok(value!({"prefix_a": 1, "prefix_b": {"c": 2}}))
+        );
+    }
+
+    #[test]
+    fn recursive() {
+        assert_eq!(
+            run(value!({"a": 1, "b": {"c": 2}, "d": [{"e": 3}, 4]}), true),
+            Ok(value!({
+                "prefix_a": 1,
+                "prefix_b": {"prefix_c": 2},
+                "prefix_d": [{"prefix_e": 3}, 4]
+            }))
+        );
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let function = MapKeysFn {
+            value: map!["a": 1, "b": 2].boxed(),
+            recursive: None,
+            closure: Closure::new(
+                vec!["k".to_owned()],
+                Block::new(vec![Literal::from("c").into()]),
+            ),
+        };
+
+        assert_eq!(
+            function.execute(&mut state::Program::default(), &mut Value::Null),
+            Ok(value!({"c": 2}))
+        );
+    }
+
+    #[test]
+    fn non_bytes_key() {
+        let function = MapKeysFn {
+            value: map!["a": 1].boxed(),
+            recursive: None,
+            closure: Closure::new(
+                vec!["k".to_owned()],
+                Block::new(vec![Literal::from(1).into()]),
+            ),
+        };
+
+        assert!(function
+            .execute(&mut state::Program::default(), &mut Value::Null)
+            .is_err());
+    }
+}
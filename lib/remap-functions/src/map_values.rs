@@ -0,0 +1,175 @@
+use crate::util;
+use remap::prelude::*;
+use value::Kind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MapValues;
+
+impl Function for MapValues {
+    fn identifier(&self) -> &'static str {
+        "map_values"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Map(_) | Value::Array(_)),
+            required: true,
+        }]
+    }
+
+    fn closure(&self) -> Option<ClosureParameter> {
+        Some(util::ELEMENT_CLOSURE)
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let closure = arguments.required_closure()?;
+
+        Ok(Box::new(MapValuesFn { value, closure }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapValuesFn {
+    value: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl Expression for MapValuesFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let closure = &self.closure;
+
+        match self.value.execute(state, object)? {
+            Value::Map(map) => map
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = util::run_element_closure(
+                        closure,
+                        key.clone().into(),
+                        value,
+                        state,
+                        object,
+                    )?;
+
+                    Ok((key, value))
+                })
+                .collect::<Result<std::collections::BTreeMap<_, _>>>()
+                .map(Into::into),
+            Value::Array(array) => array
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    util::run_element_closure(closure, (index as i64).into(), value, state, object)
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(Into::into),
+            v => Err(value::Error::Expected(Kind::Map | Kind::Array, v.kind()).into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let closure_def = self.closure.type_def(state);
+        let type_def = self
+            .value
+            .type_def(state)
+            .fallible_unless(Kind::Map | Kind::Array);
+        let fallible = type_def.is_fallible() || closure_def.is_fallible();
+
+        type_def
+            .into_fallible(fallible)
+            .with_constraint(Kind::Map | Kind::Array)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use remap::{expression::Arithmetic, Operator};
+
+    /// `|value| { value + 1 }`, or `|key, value| { key + value }`.
+    fn closure(variables: &[&str]) -> Closure {
+        let lhs = Variable::new(variables[0].to_owned(), None);
+        let rhs: Expr = match variables.get(1) {
+            Some(ident) => Variable::new((*ident).to_owned(), None).into(),
+            None => Literal::from(1).into(),
+        };
+
+        Closure::new(
+            variables.iter().map(|v| (*v).to_owned()).collect(),
+            Block::new(vec![Arithmetic::new(
+                Box::new(lhs.into()),
+                Box::new(rhs),
+                Operator::Add,
+            )
+            .into()]),
+        )
+    }
+
+    fn run(value: Value, variables: &[&str]) -> Result<Value> {
+        let function = MapValuesFn {
+            value: Literal::from(value).boxed(),
+            closure: closure(variables),
+        };
+
+        function.execute(&mut state::Program::default(), &mut Value::Null)
+    }
+
+    remap::test_type_def![
+        value_array_infallible_closure {
+            expr: |_| MapValuesFn {
+                value: array![1, 2].boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+
+        value_array_fallible_closure {
+            expr: |_| MapValuesFn {
+                value: array![1, 2].boxed(),
+                closure: closure(&["v"]),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+
+        value_non_collection {
+            expr: |_| MapValuesFn {
+                value: Literal::from(1).boxed(),
+                closure: Closure::new(vec!["v".to_owned()], Block::new(vec![Literal::from(1).into()])),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn array() {
+        assert_eq!(run(value!([1, 2, 3]), &["v"]), Ok(value!([2, 3, 4])));
+    }
+
+    #[test]
+    fn array_with_index() {
+        assert_eq!(run(value!([1, 2, 3]), &["i", "v"]), Ok(value!([1, 3, 5])));
+    }
+
+    #[test]
+    fn map() {
+        assert_eq!(
+            run(value!({"a": 1, "b": 2}), &["v"]),
+            Ok(value!({"a": 2, "b": 3}))
+        );
+    }
+
+    #[test]
+    fn map_with_key() {
+        assert_eq!(
+            run(value!({"a": "b", "c": "d"}), &["k", "v"]),
+            Ok(value!({"a": "ab", "c": "cd"}))
+        );
+    }
+
+    #[test]
+    fn closure_error() {
+        assert!(run(value!([1, true]), &["v"]).is_err());
+    }
+}
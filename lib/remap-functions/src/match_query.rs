@@ -0,0 +1,521 @@
+use nom::{
+    self,
+    branch::alt,
+    bytes::complete::{escaped, tag, take_while1},
+    character::complete::{char, multispace0, multispace1, satisfy},
+    combinator::{all_consuming, map, not, opt, peek, verify},
+    error::VerboseError,
+    multi::many0,
+    number::complete::double,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+use regex::Regex;
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchQuery;
+
+impl Function for MatchQuery {
+    fn identifier(&self) -> &'static str {
+        "match_query"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "query",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let query = arguments.required_literal("query")?.into_value();
+        let query = parse(&query.try_bytes_utf8_lossy()?)?;
+
+        Ok(Box::new(MatchQueryFn { value, query }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MatchQueryFn {
+    value: Box<dyn Expression>,
+    query: Query,
+}
+
+impl Expression for MatchQueryFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let value = self.value.execute(state, object)?;
+
+        Ok(self.query.matches(&value).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Map)
+            .with_constraint(value::Kind::Boolean)
+    }
+}
+
+/// A parsed search query.
+#[derive(Debug, Clone)]
+enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+
+    /// `field:matcher`, where the field is a `.` separated path into the map.
+    Field(Vec<String>, Matcher),
+
+    /// A term without a field, searched for in the `message` field.
+    Text(Regex),
+}
+
+impl Query {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            Query::And(lhs, rhs) => lhs.matches(value) && rhs.matches(value),
+            Query::Or(lhs, rhs) => lhs.matches(value) || rhs.matches(value),
+            Query::Not(query) => !query.matches(value),
+            Query::Field(path, matcher) => {
+                lookup(value, path).map_or(false, |value| matcher.matches(value))
+            }
+            Query::Text(regex) => match value {
+                Value::Map(map) => match map.get("message") {
+                    Some(Value::Bytes(bytes)) => regex.is_match(&String::from_utf8_lossy(bytes)),
+                    _ => false,
+                },
+                _ => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// `field:*`, matches if the field exists and isn't `null`.
+    Exists,
+
+    /// `field:value`, matches the value exactly.
+    Exact(String),
+
+    /// `field:val*`, where `*` matches any number of characters and `?`
+    /// matches a single character.
+    Wildcard(Regex),
+
+    /// `field:>10`, `field:<=10`, etc.
+    Range(Comparison, f64),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl Matcher {
+    fn matches(&self, value: &Value) -> bool {
+        // A field holding an array matches if any of its elements match.
+        if let Value::Array(values) = value {
+            return values.iter().any(|value| self.matches(value));
+        }
+
+        match self {
+            Matcher::Exists => !matches!(value, Value::Null),
+            Matcher::Exact(expected) => match value {
+                Value::Bytes(bytes) => bytes == expected.as_bytes(),
+                Value::Integer(int) => expected.parse::<i64>().map_or(false, |n| n == *int),
+                Value::Float(float) => expected
+                    .parse::<f64>()
+                    .map_or(false, |n| (n - float).abs() < f64::EPSILON),
+                Value::Boolean(boolean) => {
+                    expected.parse::<bool>().map_or(false, |b| b == *boolean)
+                }
+                _ => false,
+            },
+            Matcher::Wildcard(regex) => match value {
+                Value::Bytes(bytes) => regex.is_match(&String::from_utf8_lossy(bytes)),
+                _ => false,
+            },
+            Matcher::Range(comparison, bound) => {
+                let number = match value {
+                    Value::Integer(int) => *int as f64,
+                    Value::Float(float) => *float,
+                    Value::Bytes(bytes) => match String::from_utf8_lossy(bytes).parse() {
+                        Ok(number) => number,
+                        Err(_) => return false,
+                    },
+                    _ => return false,
+                };
+
+                match comparison {
+                    Comparison::Gt => number > *bound,
+                    Comparison::Gte => number >= *bound,
+                    Comparison::Lt => number < *bound,
+                    Comparison::Lte => number <= *bound,
+                }
+            }
+        }
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Map(map) => map.get(segment),
+        _ => None,
+    })
+}
+
+/// Convert a search term into a regular expression, where `*` matches any
+/// number of characters and `?` matches a single character.
+///
+/// Field values have to match in full, free text only has to be found
+/// somewhere in the `message` field.
+fn wildcard_regex(term: &str, anchored: bool) -> Regex {
+    let pattern = term
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+
+    let pattern = if anchored {
+        format!("^(?s:{})$", pattern)
+    } else {
+        format!("(?s:{})", pattern)
+    };
+
+    Regex::new(&pattern).expect("escaped wildcard pattern is a valid regex")
+}
+
+type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+fn parse(input: &str) -> Result<Query> {
+    let (_, query) = all_consuming(delimited(multispace0, parse_or, multispace0))(input).map_err(
+        |e| match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                // Create a descriptive error message if possible.
+                nom::error::convert_error(input, e)
+            }
+            _ => format!("{}", e),
+        },
+    )?;
+
+    Ok(query)
+}
+
+/// Parses one or more `AND` expressions, separated by `OR`.
+fn parse_or(input: &str) -> ParseResult<Query> {
+    let (input, first) = parse_and(input)?;
+    let (input, rest) = many0(preceded(
+        tuple((multispace1, tag("OR"), multispace1)),
+        parse_and,
+    ))(input)?;
+
+    let query = rest
+        .into_iter()
+        .fold(first, |lhs, rhs| Query::Or(Box::new(lhs), Box::new(rhs)));
+
+    Ok((input, query))
+}
+
+/// Parses one or more terms, separated by `AND` or just whitespace.
+fn parse_and(input: &str) -> ParseResult<Query> {
+    let (input, first) = parse_not(input)?;
+    let (input, rest) = many0(preceded(
+        alt((
+            map(tuple((multispace1, tag("AND"), multispace1)), |_| ()),
+            map(pair(multispace1, not(keyword("OR"))), |_| ()),
+        )),
+        parse_not,
+    ))(input)?;
+
+    let query = rest
+        .into_iter()
+        .fold(first, |lhs, rhs| Query::And(Box::new(lhs), Box::new(rhs)));
+
+    Ok((input, query))
+}
+
+/// Parses a term, optionally negated using `NOT` or `-`.
+fn parse_not(input: &str) -> ParseResult<Query> {
+    alt((
+        map(
+            preceded(alt((keyword("NOT"), tag("-"))), parse_not),
+            |query| Query::Not(Box::new(query)),
+        ),
+        parse_primary,
+    ))(input)
+}
+
+/// Parses a parenthesized query or a single term.
+fn parse_primary(input: &str) -> ParseResult<Query> {
+    alt((
+        delimited(
+            pair(char('('), multispace0),
+            parse_or,
+            pair(multispace0, char(')')),
+        ),
+        parse_term,
+    ))(input)
+}
+
+fn parse_term(input: &str) -> ParseResult<Query> {
+    alt((
+        map(
+            separated_pair(parse_field, char(':'), parse_matcher),
+            |(field, matcher)| Query::Field(field, matcher),
+        ),
+        map(
+            alt((parse_quoted, map(parse_word, str::to_owned))),
+            |text| Query::Text(wildcard_regex(&text, false)),
+        ),
+    ))(input)
+}
+
+fn parse_field(input: &str) -> ParseResult<Vec<String>> {
+    map(
+        take_while1(|c: char| c.is_alphanumeric() || "_-.@".contains(c)),
+        |field: &str| field.split('.').map(str::to_owned).collect(),
+    )(input)
+}
+
+fn parse_matcher(input: &str) -> ParseResult<Matcher> {
+    alt((
+        map(preceded(tag(">="), double), |n| {
+            Matcher::Range(Comparison::Gte, n)
+        }),
+        map(preceded(tag(">"), double), |n| {
+            Matcher::Range(Comparison::Gt, n)
+        }),
+        map(preceded(tag("<="), double), |n| {
+            Matcher::Range(Comparison::Lte, n)
+        }),
+        map(preceded(tag("<"), double), |n| {
+            Matcher::Range(Comparison::Lt, n)
+        }),
+        map(parse_quoted, Matcher::Exact),
+        map(parse_word, |word| match word {
+            "*" => Matcher::Exists,
+            word if word.contains(|c: char| c == '*' || c == '?') => {
+                Matcher::Wildcard(wildcard_regex(word, true))
+            }
+            word => Matcher::Exact(word.to_owned()),
+        }),
+    ))(input)
+}
+
+/// Parses a string delimited by `"`, which can be escaped using `\`.
+fn parse_quoted(input: &str) -> ParseResult<String> {
+    map(
+        delimited(
+            char('"'),
+            opt(escaped(
+                take_while1(|c: char| c != '\\' && c != '"'),
+                '\\',
+                satisfy(|c| c == '\\' || c == '"'),
+            )),
+            char('"'),
+        ),
+        |quoted: Option<&str>| unescape(quoted.unwrap_or_default()),
+    )(input)
+}
+
+/// Parses an unquoted term, up to the next whitespace or parenthesis.
+fn parse_word(input: &str) -> ParseResult<&str> {
+    verify(
+        take_while1(|c: char| !c.is_whitespace() && !"():\"".contains(c)),
+        |word: &str| !matches!(word, "AND" | "OR" | "NOT"),
+    )(input)
+}
+
+/// Parses an operator, which has to be followed by whitespace or a
+/// parenthesis so it isn't confused with a term starting with the same
+/// characters.
+fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> ParseResult<'a, &'a str> {
+    terminated(tag(word), alt((multispace1, peek(tag("(")))))
+}
+
+fn unescape(quoted: &str) -> String {
+    let mut unescaped = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_map {
+            expr: |_| MatchQueryFn {
+                value: map!{"status": "error"}.boxed(),
+                query: parse("status:error").unwrap(),
+            },
+            def: TypeDef { kind: Kind::Boolean, ..Default::default() },
+        }
+
+        value_non_map {
+            expr: |_| MatchQueryFn {
+                value: Literal::from("error").boxed(),
+                query: parse("status:error").unwrap(),
+            },
+            def: TypeDef { fallible: true, kind: Kind::Boolean, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn parse_invalid() {
+        for query in &["", "status:", "(status:error", "status:error AND", "OR foo"] {
+            assert!(parse(query).is_err(), "{:?} should not parse", query);
+        }
+    }
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        match_query => MatchQuery;
+
+        exact {
+            args: func_args![value: value!({status: "error"}), query: "status:error"],
+            want: Ok(true),
+        }
+
+        exact_mismatch {
+            args: func_args![value: value!({status: "info"}), query: "status:error"],
+            want: Ok(false),
+        }
+
+        missing_field {
+            args: func_args![value: value!({level: "error"}), query: "status:error"],
+            want: Ok(false),
+        }
+
+        nested_field {
+            args: func_args![value: value!({http: {status_code: 500}}), query: "http.status_code:500"],
+            want: Ok(true),
+        }
+
+        quoted {
+            args: func_args![value: value!({title: "hello \"world\""}), query: r#"title:"hello \"world\"""#],
+            want: Ok(true),
+        }
+
+        wildcard {
+            args: func_args![value: value!({service: "web-frontend"}), query: "service:web*"],
+            want: Ok(true),
+        }
+
+        wildcard_single_character {
+            args: func_args![value: value!({service: "web2"}), query: "service:web?"],
+            want: Ok(true),
+        }
+
+        wildcard_anchored {
+            args: func_args![value: value!({service: "my-web"}), query: "service:web*"],
+            want: Ok(false),
+        }
+
+        exists {
+            args: func_args![value: value!({user: "ben"}), query: "user:*"],
+            want: Ok(true),
+        }
+
+        exists_null {
+            args: func_args![value: value!({user: null}), query: "user:*"],
+            want: Ok(false),
+        }
+
+        range {
+            args: func_args![value: value!({duration: 1500}), query: "duration:>=1000 duration:<2000"],
+            want: Ok(true),
+        }
+
+        range_string {
+            args: func_args![value: value!({duration: "999.5"}), query: "duration:>1000"],
+            want: Ok(false),
+        }
+
+        array {
+            args: func_args![value: value!({tags: ["env:prod", "team:core"]}), query: r#"tags:"team:core""#],
+            want: Ok(true),
+        }
+
+        and {
+            args: func_args![value: value!({status: "error", service: "web"}), query: "status:error AND service:web*"],
+            want: Ok(true),
+        }
+
+        implicit_and {
+            args: func_args![value: value!({status: "error", service: "db"}), query: "status:error service:web"],
+            want: Ok(false),
+        }
+
+        or {
+            args: func_args![value: value!({status: "warn"}), query: "status:error OR status:warn"],
+            want: Ok(true),
+        }
+
+        not {
+            args: func_args![value: value!({status: "warn", service: "web"}), query: "service:web AND NOT status:error"],
+            want: Ok(true),
+        }
+
+        not_dash {
+            args: func_args![value: value!({status: "error"}), query: "-status:error"],
+            want: Ok(false),
+        }
+
+        precedence {
+            args: func_args![value: value!({a: 1, b: 2, c: 3}), query: "a:0 AND b:0 OR c:3"],
+            want: Ok(true),
+        }
+
+        parentheses {
+            args: func_args![value: value!({a: 1, b: 2, c: 3}), query: "a:0 AND (b:0 OR c:3)"],
+            want: Ok(false),
+        }
+
+        text {
+            args: func_args![value: value!({message: "connection refused by upstream"}), query: "refused"],
+            want: Ok(true),
+        }
+
+        text_wildcard {
+            args: func_args![value: value!({message: "connection refused by upstream"}), query: r#""upstream" AND conn*by"#],
+            want: Ok(true),
+        }
+
+        boolean {
+            args: func_args![value: value!({secure: true}), query: "secure:true"],
+            want: Ok(true),
+        }
+    ];
+}
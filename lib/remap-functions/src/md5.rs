@@ -94,3 +94,17 @@ mod tests {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        md5 => Md5;
+
+        literal_value {
+            args: func_args![value: "foo"],
+            want: Ok("acbd18db4cc2f85cedef654fccc4a4d8"),
+        }
+    ];
+}
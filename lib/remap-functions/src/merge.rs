@@ -15,7 +15,7 @@ impl Function for Merge {
             Parameter {
                 keyword: "to",
                 accepts: |v| matches!(v, Value::Map(_)),
-                required: false,
+                required: true,
             },
             Parameter {
                 keyword: "from",
@@ -81,13 +81,13 @@ impl Expression for MergeFn {
     }
 }
 
-/// Merges two BTreeMaps of Symbol’s value as variable is void: Values.
+/// Merges two BTreeMaps of `Value`s.
 /// The second map is merged into the first one.
 ///
-/// If Symbol’s value as variable is void: deep is true, only the top level values are merged in. If both maps contain a field
+/// If `deep` is false, only the top level values are merged in. If both maps contain a field
 /// with the same name, the field from the first is overwritten with the field from the second.
 ///
-/// If Symbol’s value as variable is void: deep is false, should both maps contain a field with the same name, and both those
+/// If `deep` is true, should both maps contain a field with the same name, and both those
 /// fields are also maps, the function will recurse and will merge the child fields from the second
 /// into the child fields from the first.
 ///
@@ -147,7 +147,6 @@ fn merge_inner_type_defs(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use shared::btreemap;
     use value::Kind;
 
     test_type_def![
@@ -186,6 +185,12 @@ mod tests {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use shared::btreemap;
 
     test_function! [
         merge => Merge;
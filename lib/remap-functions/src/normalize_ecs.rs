@@ -0,0 +1,397 @@
+use crate::util;
+use remap::prelude::*;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// Field names commonly used by web servers, load balancers and firewalls,
+/// and the [Elastic Common Schema][ecs] field they map to.
+///
+/// [ecs]: https://www.elastic.co/guide/en/ecs/current/index.html
+const FIELDS: &[(&[&str], &str, Coercion)] = &[
+    (
+        &["@timestamp", "time", "timestamp"],
+        "@timestamp",
+        Coercion::None,
+    ),
+    (&["message", "msg"], "message", Coercion::String),
+    (
+        &["level", "log_level", "severity"],
+        "log.level",
+        Coercion::Lowercase,
+    ),
+    (&["host", "hostname"], "host.name", Coercion::String),
+    (
+        &[
+            "client_ip",
+            "clientip",
+            "ip",
+            "remote_addr",
+            "source_ip",
+            "src_ip",
+            "srcip",
+        ],
+        "source.ip",
+        Coercion::String,
+    ),
+    (
+        &["source_port", "src_port", "srcport"],
+        "source.port",
+        Coercion::Integer,
+    ),
+    (
+        &["destination_ip", "dst_ip", "dstip"],
+        "destination.ip",
+        Coercion::String,
+    ),
+    (
+        &["destination_port", "dst_port", "dstport"],
+        "destination.port",
+        Coercion::Integer,
+    ),
+    (
+        &["proto", "protocol", "transport"],
+        "network.transport",
+        Coercion::Lowercase,
+    ),
+    (
+        &["user", "user_name", "username"],
+        "user.name",
+        Coercion::String,
+    ),
+    (
+        &["http_method", "method", "request_method"],
+        "http.request.method",
+        Coercion::Uppercase,
+    ),
+    (
+        &["http_referer", "referer", "referrer"],
+        "http.request.referrer",
+        Coercion::String,
+    ),
+    (
+        &["http_status", "status", "status_code"],
+        "http.response.status_code",
+        Coercion::Integer,
+    ),
+    (
+        &["body_bytes_sent", "bytes", "bytes_sent", "size"],
+        "http.response.body.bytes",
+        Coercion::Integer,
+    ),
+    (
+        &["agent", "http_user_agent", "user_agent", "useragent"],
+        "user_agent.original",
+        Coercion::String,
+    ),
+    (
+        &["path", "request_path", "uri_path"],
+        "url.path",
+        Coercion::String,
+    ),
+    (
+        &["query", "query_string", "uri_query"],
+        "url.query",
+        Coercion::String,
+    ),
+    (
+        &["request_uri", "request_url", "uri", "url"],
+        "url.original",
+        Coercion::Url,
+    ),
+];
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeEcs;
+
+impl Function for NormalizeEcs {
+    fn identifier(&self) -> &'static str {
+        "normalize_ecs"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "keep_unmapped",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let keep_unmapped = arguments.optional("keep_unmapped").map(Expr::boxed);
+
+        Ok(Box::new(NormalizeEcsFn {
+            value,
+            keep_unmapped,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeEcsFn {
+    value: Box<dyn Expression>,
+    keep_unmapped: Option<Box<dyn Expression>>,
+}
+
+impl Expression for NormalizeEcsFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let map = self.value.execute(state, object)?.try_map()?;
+        let keep_unmapped = match &self.keep_unmapped {
+            Some(expr) => expr.execute(state, object)?.try_boolean()?,
+            None => true,
+        };
+
+        Ok(normalize(map, keep_unmapped).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Map)
+            .merge_optional(self.keep_unmapped.as_ref().map(|keep_unmapped| {
+                keep_unmapped
+                    .type_def(state)
+                    .fallible_unless(value::Kind::Boolean)
+            }))
+            .with_constraint(value::Kind::Map)
+            .with_inner_type(None)
+    }
+}
+
+/// How the value of a field is converted to the type ECS expects.
+#[derive(Clone, Copy, Debug)]
+enum Coercion {
+    None,
+    String,
+    Lowercase,
+    Uppercase,
+    Integer,
+
+    /// Stores the original URL, and the URL pieces in their own fields.
+    Url,
+}
+
+impl Coercion {
+    /// Returns the coerced value, or `None` if the value can't be converted.
+    fn coerce(self, value: Value) -> Option<Value> {
+        match self {
+            Coercion::None => Some(value),
+            Coercion::String | Coercion::Url => util::scalar_to_string(value).map(Into::into),
+            Coercion::Lowercase => util::scalar_to_string(value).map(|s| s.to_lowercase().into()),
+            Coercion::Uppercase => util::scalar_to_string(value).map(|s| s.to_uppercase().into()),
+            Coercion::Integer => match value {
+                Value::Integer(_) => Some(value),
+                Value::Float(f) => Some((f as i64).into()),
+                Value::Bytes(bytes) => String::from_utf8_lossy(&bytes)
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .map(Into::into),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn normalize(map: BTreeMap<String, Value>, keep_unmapped: bool) -> BTreeMap<String, Value> {
+    let mut unmapped = BTreeMap::new();
+    let mut mapped = Vec::new();
+
+    for (key, value) in map {
+        let field = FIELDS
+            .iter()
+            .find(|(names, _, _)| names.contains(&key.as_str()));
+
+        match field {
+            Some((_, path, coercion)) => match coercion.coerce(value.clone()) {
+                Some(value) => mapped.push((*path, *coercion, value)),
+                // Keep values that can't be converted as-is, instead of
+                // silently dropping them.
+                None => {
+                    unmapped.insert(key, value);
+                }
+            },
+            None => {
+                unmapped.insert(key, value);
+            }
+        }
+    }
+
+    let mut normalized = if keep_unmapped {
+        unmapped
+    } else {
+        BTreeMap::new()
+    };
+
+    for (path, coercion, value) in mapped {
+        if let Coercion::Url = coercion {
+            if let Value::Bytes(bytes) = &value {
+                for (path, value) in url_fields(&String::from_utf8_lossy(bytes)) {
+                    util::insert_dotted(&mut normalized, path, value);
+                }
+            }
+        }
+
+        util::insert_dotted(&mut normalized, path, value);
+    }
+
+    normalized
+}
+
+/// Split a URL into its ECS `url.*` fields.
+///
+/// Request URIs are often relative (`/index.html?page=1`), in which case only
+/// the path, query and fragment are set.
+fn url_fields(original: &str) -> Vec<(&'static str, Value)> {
+    let mut fields = Vec::new();
+
+    match Url::parse(original) {
+        Ok(url) => {
+            fields.push(("url.scheme", url.scheme().into()));
+            if let Some(domain) = url.host_str() {
+                fields.push(("url.domain", domain.into()));
+            }
+            if let Some(port) = url.port() {
+                fields.push(("url.port", (port as i64).into()));
+            }
+            fields.push(("url.path", url.path().into()));
+            if let Some(query) = url.query() {
+                fields.push(("url.query", query.into()));
+            }
+            if let Some(fragment) = url.fragment() {
+                fields.push(("url.fragment", fragment.into()));
+            }
+        }
+        Err(_) => {
+            let (rest, fragment) = split_once(original, '#');
+            let (path, query) = split_once(rest, '?');
+
+            fields.push(("url.path", path.into()));
+            if let Some(query) = query {
+                fields.push(("url.query", query.into()));
+            }
+            if let Some(fragment) = fragment {
+                fields.push(("url.fragment", fragment.into()));
+            }
+        }
+    }
+
+    fields
+}
+
+fn split_once(s: &str, delimiter: char) -> (&str, Option<&str>) {
+    match s.find(delimiter) {
+        Some(index) => (&s[..index], Some(&s[index + 1..])),
+        None => (s, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_map {
+            expr: |_| NormalizeEcsFn {
+                value: map!{"status": "200"}.boxed(),
+                keep_unmapped: None,
+            },
+            def: TypeDef { kind: Kind::Map, ..Default::default() },
+        }
+
+        value_non_map {
+            expr: |_| NormalizeEcsFn {
+                value: Literal::from("foo").boxed(),
+                keep_unmapped: None,
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map, ..Default::default() },
+        }
+    ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        normalize_ecs => NormalizeEcs;
+
+        access_log {
+            args: func_args![value: value!({
+                "remote_addr": "10.0.0.1",
+                "method": "get",
+                "status": "404",
+                "bytes": 512,
+                "user_agent": "curl/7.64.1",
+                "uri": "/index.html?page=1",
+                "upstream": "web-1",
+            })],
+            want: Ok(value!({
+                "source": {"ip": "10.0.0.1"},
+                "http": {
+                    "request": {"method": "GET"},
+                    "response": {"status_code": 404, "body": {"bytes": 512}},
+                },
+                "user_agent": {"original": "curl/7.64.1"},
+                "url": {"original": "/index.html?page=1", "path": "/index.html", "query": "page=1"},
+                "upstream": "web-1",
+            })),
+        }
+
+        absolute_url {
+            args: func_args![value: value!({"url": "https://example.com:8443/search?q=vector#results"})],
+            want: Ok(value!({
+                "url": {
+                    "original": "https://example.com:8443/search?q=vector#results",
+                    "scheme": "https",
+                    "domain": "example.com",
+                    "port": 8443,
+                    "path": "/search",
+                    "query": "q=vector",
+                    "fragment": "results",
+                },
+            })),
+        }
+
+        firewall {
+            args: func_args![value: value!({
+                "srcip": "10.0.0.1",
+                "srcport": "51234",
+                "dstip": "10.0.0.2",
+                "dstport": 443,
+                "proto": "TCP",
+            })],
+            want: Ok(value!({
+                "source": {"ip": "10.0.0.1", "port": 51234},
+                "destination": {"ip": "10.0.0.2", "port": 443},
+                "network": {"transport": "tcp"},
+            })),
+        }
+
+        merges_existing_fields {
+            args: func_args![value: value!({"host": {"os": "linux"}, "hostname": "web-1"})],
+            want: Ok(value!({"host": {"os": "linux", "name": "web-1"}})),
+        }
+
+        invalid_type_kept {
+            args: func_args![value: value!({"status": "unknown", "level": "WARN"})],
+            want: Ok(value!({"status": "unknown", "log": {"level": "warn"}})),
+        }
+
+        drop_unmapped {
+            args: func_args![
+                value: value!({"msg": "hello", "upstream": "web-1"}),
+                keep_unmapped: false,
+            ],
+            want: Ok(value!({"message": "hello"})),
+        }
+    ];
+}
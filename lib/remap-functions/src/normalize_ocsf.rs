@@ -0,0 +1,519 @@
+use crate::util;
+use chrono::DateTime;
+use remap::prelude::*;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The version of the [Open Cybersecurity Schema Framework][ocsf] the events
+/// are normalized to.
+///
+/// [ocsf]: https://schema.ocsf.io
+const OCSF_VERSION: &str = "1.0.0";
+
+/// Field names common to all classes, and the OCSF attribute they map to.
+const COMMON_FIELDS: &[(&[&str], &str, Coercion)] = &[
+    (&["@timestamp", "time", "timestamp"], "time", Coercion::Time),
+    (&["message", "msg"], "message", Coercion::String),
+    (
+        &["level", "log_level", "severity"],
+        "severity",
+        Coercion::Lowercase,
+    ),
+    (&["host", "hostname"], "device.hostname", Coercion::String),
+    (
+        &["client_ip", "clientip", "source_ip", "src_ip", "srcip"],
+        "src_endpoint.ip",
+        Coercion::String,
+    ),
+    (
+        &["source_port", "src_port", "srcport"],
+        "src_endpoint.port",
+        Coercion::Integer,
+    ),
+    (
+        &["destination_ip", "dst_ip", "dstip"],
+        "dst_endpoint.ip",
+        Coercion::String,
+    ),
+    (
+        &["destination_port", "dst_port", "dstport"],
+        "dst_endpoint.port",
+        Coercion::Integer,
+    ),
+    (
+        &["action", "activity", "event_type"],
+        "activity_name",
+        Coercion::Lowercase,
+    ),
+];
+
+const AUTHENTICATION_FIELDS: &[(&[&str], &str, Coercion)] = &[
+    (
+        &["user", "user_name", "username"],
+        "user.name",
+        Coercion::String,
+    ),
+    (
+        &["auth_method", "auth_protocol", "method"],
+        "auth_protocol",
+        Coercion::String,
+    ),
+    (
+        &["outcome", "result", "status"],
+        "status",
+        Coercion::Lowercase,
+    ),
+];
+
+const NETWORK_ACTIVITY_FIELDS: &[(&[&str], &str, Coercion)] = &[
+    (
+        &["proto", "protocol", "transport"],
+        "connection_info.protocol_name",
+        Coercion::Lowercase,
+    ),
+    (
+        &["bytes_in", "bytes_received", "rcvd_bytes"],
+        "traffic.bytes_in",
+        Coercion::Integer,
+    ),
+    (
+        &["bytes_out", "bytes_sent", "sent_bytes"],
+        "traffic.bytes_out",
+        Coercion::Integer,
+    ),
+];
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeOcsf;
+
+impl Function for NormalizeOcsf {
+    fn identifier(&self) -> &'static str {
+        "normalize_ocsf"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "class",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "keep_unmapped",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let class = arguments.required("class")?.boxed();
+        let keep_unmapped = arguments.optional("keep_unmapped").map(Expr::boxed);
+
+        Ok(Box::new(NormalizeOcsfFn {
+            value,
+            class,
+            keep_unmapped,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeOcsfFn {
+    value: Box<dyn Expression>,
+    class: Box<dyn Expression>,
+    keep_unmapped: Option<Box<dyn Expression>>,
+}
+
+impl Expression for NormalizeOcsfFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let map = self.value.execute(state, object)?.try_map()?;
+        let class = self.class.execute(state, object)?.try_bytes_utf8_lossy()?;
+        let class = Class::from_str(&class)?;
+        let keep_unmapped = match &self.keep_unmapped {
+            Some(expr) => expr.execute(state, object)?.try_boolean()?,
+            None => true,
+        };
+
+        Ok(normalize(map, class, keep_unmapped)?.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Map)
+            .merge(self.class.type_def(state))
+            .merge_optional(self.keep_unmapped.as_ref().map(|keep_unmapped| {
+                keep_unmapped
+                    .type_def(state)
+                    .fallible_unless(value::Kind::Boolean)
+            }))
+            .into_fallible(true) // unknown class, or missing required attributes
+            .with_constraint(value::Kind::Map)
+            .with_inner_type(None)
+    }
+}
+
+/// The OCSF event classes events can be normalized to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Class {
+    Authentication,
+    NetworkActivity,
+}
+
+impl FromStr for Class {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "authentication" => Ok(Class::Authentication),
+            "network_activity" => Ok(Class::NetworkActivity),
+            _ => Err(format!("unknown OCSF class: '{}'", s)),
+        }
+    }
+}
+
+impl Class {
+    fn name(self) -> &'static str {
+        match self {
+            Class::Authentication => "Authentication",
+            Class::NetworkActivity => "Network Activity",
+        }
+    }
+
+    fn uid(self) -> i64 {
+        match self {
+            Class::Authentication => 3002,
+            Class::NetworkActivity => 4001,
+        }
+    }
+
+    fn category_name(self) -> &'static str {
+        match self {
+            Class::Authentication => "Identity & Access Management",
+            Class::NetworkActivity => "Network Activity",
+        }
+    }
+
+    fn category_uid(self) -> i64 {
+        self.uid() / 1000
+    }
+
+    fn fields(self) -> &'static [(&'static [&'static str], &'static str, Coercion)] {
+        match self {
+            Class::Authentication => AUTHENTICATION_FIELDS,
+            Class::NetworkActivity => NETWORK_ACTIVITY_FIELDS,
+        }
+    }
+
+    /// The attributes that can't be derived, and have to be present in the
+    /// source event.
+    fn required_attributes(self) -> &'static [&'static str] {
+        match self {
+            Class::Authentication => &["time", "user.name"],
+            Class::NetworkActivity => &["time", "src_endpoint.ip", "dst_endpoint.ip"],
+        }
+    }
+
+    /// Returns the `activity_id` of the activity name, `0` (unknown) if there
+    /// is no activity, or `99` (other) for unknown activities.
+    fn activity_id(self, activity: Option<&str>) -> i64 {
+        let activity = match activity {
+            Some(activity) => activity,
+            None => return 0,
+        };
+
+        match self {
+            Class::Authentication => match activity {
+                "login" | "logon" | "log_in" | "signin" | "sign_in" => 1,
+                "logout" | "logoff" | "log_out" | "signout" | "sign_out" => 2,
+                _ => 99,
+            },
+            Class::NetworkActivity => match activity {
+                "open" | "start" | "established" => 1,
+                "close" | "closed" | "end" => 2,
+                "reset" => 3,
+                "fail" | "failed" => 4,
+                "refuse" | "refused" | "deny" | "denied" | "drop" | "reject" => 5,
+                "traffic" => 6,
+                _ => 99,
+            },
+        }
+    }
+}
+
+/// How the value of a field is converted to the type OCSF expects.
+#[derive(Clone, Copy, Debug)]
+enum Coercion {
+    String,
+    Lowercase,
+    Integer,
+
+    /// Milliseconds since the Unix epoch.
+    Time,
+}
+
+impl Coercion {
+    /// Returns the coerced value, or `None` if the value can't be converted.
+    fn coerce(self, value: Value) -> Option<Value> {
+        match self {
+            Coercion::String => util::scalar_to_string(value).map(Into::into),
+            Coercion::Lowercase => util::scalar_to_string(value).map(|s| s.to_lowercase().into()),
+            Coercion::Integer => match value {
+                Value::Integer(_) => Some(value),
+                Value::Float(f) => Some((f as i64).into()),
+                Value::Bytes(bytes) => String::from_utf8_lossy(&bytes)
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .map(Into::into),
+                _ => None,
+            },
+            Coercion::Time => match value {
+                Value::Timestamp(timestamp) => Some(timestamp.timestamp_millis().into()),
+                Value::Integer(_) => Some(value),
+                Value::Bytes(bytes) => {
+                    DateTime::parse_from_rfc3339(&String::from_utf8_lossy(&bytes))
+                        .ok()
+                        .map(|timestamp| timestamp.timestamp_millis().into())
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+fn normalize(
+    map: BTreeMap<String, Value>,
+    class: Class,
+    keep_unmapped: bool,
+) -> Result<BTreeMap<String, Value>> {
+    let mut unmapped = BTreeMap::new();
+    let mut normalized = BTreeMap::new();
+
+    for (key, value) in map {
+        let field = COMMON_FIELDS
+            .iter()
+            .chain(class.fields())
+            .find(|(names, _, _)| names.contains(&key.as_str()));
+
+        match field.and_then(|(_, path, coercion)| Some((path, coercion.coerce(value.clone())?))) {
+            Some((path, value)) => util::insert_dotted(&mut normalized, path, value),
+            // Keep values that can't be converted as-is, instead of silently
+            // dropping them.
+            None => {
+                unmapped.insert(key, value);
+            }
+        }
+    }
+
+    for attribute in class.required_attributes() {
+        if get_dotted(&normalized, attribute).is_none() {
+            return Err(format!(
+                "missing required attribute for OCSF class {}: '{}'",
+                class.name(),
+                attribute
+            )
+            .into());
+        }
+    }
+
+    let activity_id = class.activity_id(
+        get_dotted(&normalized, "activity_name")
+            .and_then(Value::as_bytes)
+            .map(|bytes| String::from_utf8_lossy(bytes))
+            .as_deref(),
+    );
+
+    let severity_id = severity_id(
+        get_dotted(&normalized, "severity")
+            .and_then(Value::as_bytes)
+            .map(|bytes| String::from_utf8_lossy(bytes))
+            .as_deref(),
+    );
+
+    if class == Class::Authentication {
+        let status_id = status_id(
+            get_dotted(&normalized, "status")
+                .and_then(Value::as_bytes)
+                .map(|bytes| String::from_utf8_lossy(bytes))
+                .as_deref(),
+        );
+
+        normalized.insert("status_id".to_owned(), status_id.into());
+    }
+
+    normalized.insert("class_uid".to_owned(), class.uid().into());
+    normalized.insert("class_name".to_owned(), class.name().into());
+    normalized.insert("category_uid".to_owned(), class.category_uid().into());
+    normalized.insert("category_name".to_owned(), class.category_name().into());
+    normalized.insert("activity_id".to_owned(), activity_id.into());
+    normalized.insert(
+        "type_uid".to_owned(),
+        (class.uid() * 100 + activity_id).into(),
+    );
+    normalized.insert("severity_id".to_owned(), severity_id.into());
+    util::insert_dotted(&mut normalized, "metadata.version", OCSF_VERSION.into());
+
+    if keep_unmapped && !unmapped.is_empty() {
+        normalized.insert("unmapped".to_owned(), unmapped.into());
+    }
+
+    Ok(normalized)
+}
+
+/// Returns the `severity_id` of the severity, or log level, name.
+fn severity_id(severity: Option<&str>) -> i64 {
+    match severity {
+        None => 0,
+        Some("trace") | Some("debug") | Some("info") | Some("informational") | Some("notice") => 1,
+        Some("low") => 2,
+        Some("warn") | Some("warning") | Some("medium") => 3,
+        Some("err") | Some("error") | Some("high") => 4,
+        Some("crit") | Some("critical") | Some("alert") => 5,
+        Some("fatal") | Some("emerg") | Some("emergency") => 6,
+        Some(_) => 99,
+    }
+}
+
+/// Returns the `status_id` of the authentication outcome.
+fn status_id(status: Option<&str>) -> i64 {
+    match status {
+        None => 0,
+        Some("success") | Some("succeeded") | Some("ok") | Some("allowed") => 1,
+        Some("failure") | Some("failed") | Some("fail") | Some("denied") => 2,
+        Some(_) => 99,
+    }
+}
+
+/// Get the value at the `.` separated path.
+fn get_dotted<'a>(map: &'a BTreeMap<String, Value>, path: &str) -> Option<&'a Value> {
+    match path.find('.') {
+        None => map.get(path),
+        Some(index) => match map.get(&path[..index]) {
+            Some(Value::Map(child)) => get_dotted(child, &path[index + 1..]),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![value_map {
+        expr: |_| NormalizeOcsfFn {
+            value: map! {"user": "ben"}.boxed(),
+            class: Literal::from("authentication").boxed(),
+            keep_unmapped: None,
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: Kind::Map,
+            ..Default::default()
+        },
+    }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        normalize_ocsf => NormalizeOcsf;
+
+        authentication {
+            args: func_args![
+                value: value!({
+                    "timestamp": "2021-02-03T04:05:06Z",
+                    "username": "ben",
+                    "src_ip": "10.0.0.1",
+                    "action": "Login",
+                    "outcome": "failure",
+                    "level": "WARN",
+                    "session": "abc",
+                }),
+                class: "authentication",
+            ],
+            want: Ok(value!({
+                "time": 1612325106000_i64,
+                "user": {"name": "ben"},
+                "src_endpoint": {"ip": "10.0.0.1"},
+                "activity_name": "login",
+                "status": "failure",
+                "severity": "warn",
+                "status_id": 2,
+                "class_uid": 3002,
+                "class_name": "Authentication",
+                "category_uid": 3,
+                "category_name": "Identity & Access Management",
+                "activity_id": 1,
+                "type_uid": 300201,
+                "severity_id": 3,
+                "metadata": {"version": "1.0.0"},
+                "unmapped": {"session": "abc"},
+            })),
+        }
+
+        network_activity {
+            args: func_args![
+                value: value!({
+                    "time": 1612325106000_i64,
+                    "srcip": "10.0.0.1",
+                    "srcport": "51234",
+                    "dstip": "10.0.0.2",
+                    "dstport": 443,
+                    "proto": "TCP",
+                    "bytes_out": 512,
+                    "action": "deny",
+                }),
+                class: "network_activity",
+                keep_unmapped: false,
+            ],
+            want: Ok(value!({
+                "time": 1612325106000_i64,
+                "src_endpoint": {"ip": "10.0.0.1", "port": 51234},
+                "dst_endpoint": {"ip": "10.0.0.2", "port": 443},
+                "connection_info": {"protocol_name": "tcp"},
+                "traffic": {"bytes_out": 512},
+                "activity_name": "deny",
+                "class_uid": 4001,
+                "class_name": "Network Activity",
+                "category_uid": 4,
+                "category_name": "Network Activity",
+                "activity_id": 5,
+                "type_uid": 400105,
+                "severity_id": 0,
+                "metadata": {"version": "1.0.0"},
+            })),
+        }
+
+        missing_required_attribute {
+            args: func_args![
+                value: value!({"time": 1612325106000_i64, "srcip": "10.0.0.1"}),
+                class: "network_activity",
+            ],
+            want: Err("function call error: missing required attribute for OCSF class Network Activity: 'dst_endpoint.ip'"),
+        }
+
+        invalid_time {
+            args: func_args![
+                value: value!({"time": "yesterday", "user": "ben"}),
+                class: "authentication",
+            ],
+            want: Err("function call error: missing required attribute for OCSF class Authentication: 'time'"),
+        }
+
+        unknown_class {
+            args: func_args![value: value!({}), class: "file_activity"],
+            want: Err("function call error: unknown OCSF class: 'file_activity'"),
+        }
+    ];
+}
@@ -9,6 +9,10 @@ impl Function for Now {
         "now"
     }
 
+    fn is_pure(&self) -> bool {
+        false
+    }
+
     fn compile(&self, _: ArgumentList) -> Result<Box<dyn Expression>> {
         Ok(Box::new(NowFn))
     }
@@ -0,0 +1,328 @@
+use avro_rs::{types::Value as AvroValue, Schema};
+use chrono::{TimeZone, Utc};
+use remap::prelude::*;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// The magic byte starting the Confluent Schema Registry wire format.
+const CONFLUENT_MAGIC_BYTE: u8 = 0;
+
+/// The length of the Confluent wire format header, the magic byte followed by
+/// the schema ID as a big-endian `u32`.
+const CONFLUENT_HEADER_LEN: usize = 5;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAvro;
+
+impl Function for ParseAvro {
+    fn identifier(&self) -> &'static str {
+        "parse_avro"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "schema",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "schema_id",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let schema = arguments.required_literal("schema")?.into_value();
+        let schema = parse_schema(&schema.try_bytes_utf8_lossy()?)?;
+
+        let schema_id = match arguments.optional_literal("schema_id")? {
+            Some(schema_id) => {
+                let schema_id = schema_id.into_value().try_integer()?;
+                let schema_id = u32::try_from(schema_id)
+                    .map_err(|_| format!("invalid schema ID: {}", schema_id))?;
+
+                Some(schema_id)
+            }
+            None => None,
+        };
+
+        Ok(Box::new(ParseAvroFn {
+            value,
+            schema: Arc::new(schema),
+            schema_id,
+        }))
+    }
+}
+
+/// Parse the writer schema of the payloads, which has to describe a record,
+/// so payloads are always parsed into a map.
+fn parse_schema(schema: &str) -> Result<Schema> {
+    let schema = Schema::parse_str(schema).map_err(|e| format!("invalid Avro schema: {}", e))?;
+
+    match schema {
+        Schema::Record { .. } => Ok(schema),
+        _ => Err("Avro schema must describe a record".into()),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAvroFn {
+    value: Box<dyn Expression>,
+    // Wrapping the schema in an Arc, as cloning it could otherwise be expensive.
+    schema: Arc<Schema>,
+    // The registry ID of the schema, if payloads start with the Confluent wire
+    // format header.
+    schema_id: Option<u32>,
+}
+
+impl ParseAvroFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>, schema: &str, schema_id: Option<u32>) -> Result<Self> {
+        Ok(Self {
+            value,
+            schema: Arc::new(parse_schema(schema)?),
+            schema_id,
+        })
+    }
+}
+
+impl Expression for ParseAvroFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.value.execute(state, object)?.try_bytes()?;
+
+        let mut datum = match self.schema_id {
+            Some(schema_id) => strip_confluent_header(&bytes, schema_id)?,
+            None => &bytes[..],
+        };
+
+        let value = avro_rs::from_avro_datum(&self.schema, &mut datum, None)
+            .map_err(|e| format!("unable to parse Avro payload: {}", e))?;
+
+        avro_to_value(value)
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .into_fallible(true)
+            .with_constraint(value::Kind::Map)
+    }
+}
+
+/// Strip the Confluent wire format header off the payload, checking that it was
+/// written with the schema of the given registry ID.
+fn strip_confluent_header(bytes: &[u8], schema_id: u32) -> Result<&[u8]> {
+    if bytes.len() < CONFLUENT_HEADER_LEN || bytes[0] != CONFLUENT_MAGIC_BYTE {
+        return Err("payload doesn't start with a Confluent wire format header".into());
+    }
+
+    let mut id = [0; 4];
+    id.copy_from_slice(&bytes[1..CONFLUENT_HEADER_LEN]);
+    let id = u32::from_be_bytes(id);
+
+    if id != schema_id {
+        return Err(format!(
+            "payload was written with schema ID {}, expected {}",
+            id, schema_id
+        )
+        .into());
+    }
+
+    Ok(&bytes[CONFLUENT_HEADER_LEN..])
+}
+
+fn avro_to_value(value: AvroValue) -> Result<Value> {
+    let value = match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(v) => v.into(),
+        AvroValue::Int(v) => (v as i64).into(),
+        AvroValue::Long(v) => v.into(),
+        AvroValue::Float(v) => (v as f64).into(),
+        AvroValue::Double(v) => v.into(),
+        AvroValue::Bytes(v) | AvroValue::Fixed(_, v) => v.into(),
+        AvroValue::String(v) | AvroValue::Enum(_, v) => v.into(),
+        AvroValue::Uuid(v) => v.to_string().into(),
+        AvroValue::Union(v) => avro_to_value(*v)?,
+        AvroValue::Array(values) => values
+            .into_iter()
+            .map(avro_to_value)
+            .collect::<Result<Vec<_>>>()?
+            .into(),
+        AvroValue::Map(values) => values
+            .into_iter()
+            .map(|(k, v)| Ok((k, avro_to_value(v)?)))
+            .collect::<Result<BTreeMap<_, _>>>()?
+            .into(),
+        AvroValue::Record(fields) => fields
+            .into_iter()
+            .map(|(k, v)| Ok((k, avro_to_value(v)?)))
+            .collect::<Result<BTreeMap<_, _>>>()?
+            .into(),
+        AvroValue::Date(days) => Utc
+            .timestamp_opt(0, 0)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::days(days as i64))
+            .ok_or("date out of range")?
+            .into(),
+        AvroValue::TimeMillis(v) => (v as i64).into(),
+        AvroValue::TimeMicros(v) => v.into(),
+        AvroValue::TimestampMillis(v) => Utc
+            .timestamp_millis_opt(v)
+            .single()
+            .ok_or("timestamp out of range")?
+            .into(),
+        AvroValue::TimestampMicros(v) => Utc
+            .timestamp_opt(
+                v.div_euclid(1_000_000),
+                (v.rem_euclid(1_000_000) * 1_000) as u32,
+            )
+            .single()
+            .ok_or("timestamp out of range")?
+            .into(),
+        AvroValue::Decimal(_) => return Err("Avro decimals are not supported".into()),
+        AvroValue::Duration(_) => return Err("Avro durations are not supported".into()),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use avro_rs::types::Record;
+    use shared::btreemap;
+
+    const SCHEMA: &str = r#"
+        {
+            "type": "record",
+            "name": "event",
+            "fields": [
+                { "name": "name", "type": "string" },
+                { "name": "count", "type": "long" },
+                { "name": "tags", "type": { "type": "array", "items": "string" } },
+                { "name": "parent", "type": ["null", "string"] }
+            ]
+        }
+    "#;
+
+    fn encode(framing: Option<u32>) -> Vec<u8> {
+        let schema = Schema::parse_str(SCHEMA).unwrap();
+        let mut record = Record::new(&schema).unwrap();
+        record.put("name", "foo");
+        record.put("count", 5i64);
+        record.put(
+            "tags",
+            AvroValue::Array(vec![AvroValue::String("bar".to_owned())]),
+        );
+        record.put("parent", AvroValue::Union(Box::new(AvroValue::Null)));
+
+        let datum = avro_rs::to_avro_datum(&schema, record).unwrap();
+
+        match framing {
+            Some(schema_id) => {
+                let mut bytes = vec![CONFLUENT_MAGIC_BYTE];
+                bytes.extend_from_slice(&schema_id.to_be_bytes());
+                bytes.extend(datum);
+                bytes
+            }
+            None => datum,
+        }
+    }
+
+    remap::test_type_def![value_string {
+        expr: |_| ParseAvroFn::new(Literal::from("foo").boxed(), SCHEMA, None).unwrap(),
+        def: TypeDef {
+            kind: value::Kind::Map,
+            fallible: true,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn check_invalid_schema() {
+        let error = ParseAvroFn::new(Literal::from("foo").boxed(), r#""string""#, None);
+
+        assert_eq!(
+            error.unwrap_err(),
+            Error::from("Avro schema must describe a record")
+        );
+    }
+
+    #[test]
+    fn check_parse_avro() {
+        let cases = vec![
+            (
+                Ok(btreemap! {
+                    "name" => "foo",
+                    "count" => 5,
+                    "tags" => vec!["bar"],
+                    "parent" => Value::Null,
+                }
+                .into()),
+                ParseAvroFn::new(Literal::from(encode(None)).boxed(), SCHEMA, None).unwrap(),
+            ),
+            (
+                Ok(btreemap! {
+                    "name" => "foo",
+                    "count" => 5,
+                    "tags" => vec!["bar"],
+                    "parent" => Value::Null,
+                }
+                .into()),
+                ParseAvroFn::new(Literal::from(encode(Some(7))).boxed(), SCHEMA, Some(7))
+                    .unwrap(),
+            ),
+            (
+                Err("function call error: payload was written with schema ID 7, expected 8".into()),
+                ParseAvroFn::new(Literal::from(encode(Some(7))).boxed(), SCHEMA, Some(8))
+                    .unwrap(),
+            ),
+            (
+                Err(
+                    "function call error: payload doesn't start with a Confluent wire format header"
+                        .into(),
+                ),
+                ParseAvroFn::new(Literal::from(encode(None)).boxed(), SCHEMA, Some(7)).unwrap(),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (exp, func) in cases {
+            let mut object: Value = btreemap! {}.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+
+    #[test]
+    fn check_out_of_range_timestamps() {
+        for value in vec![
+            AvroValue::TimestampMillis(i64::MAX),
+            AvroValue::TimestampMicros(i64::MIN),
+        ] {
+            assert_eq!(
+                avro_to_value(value).unwrap_err(),
+                Error::from("timestamp out of range")
+            );
+        }
+
+        assert_eq!(
+            avro_to_value(AvroValue::TimestampMillis(1_000)).unwrap(),
+            Value::from(Utc.timestamp(1, 0))
+        );
+    }
+}
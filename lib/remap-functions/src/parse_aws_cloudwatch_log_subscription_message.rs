@@ -84,9 +84,25 @@ fn inner_type_def() -> Option<InnerTypeDef> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{TimeZone, Utc};
     use value::Kind;
 
+    test_type_def![value_string {
+        expr: |_| ParseAwsCloudWatchLogSubscriptionMessageFn {
+            value: Literal::from("foo").boxed(),
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: Kind::Map,
+            inner_type_def: inner_type_def(),
+        },
+    }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
     test_function![
         parse_aws_cloudwatch_log_subscription_message => ParseAwsCloudWatchLogSubscriptionMessage;
 
@@ -142,15 +158,4 @@ mod tests {
             want: Err("function call error: unable to parse: key must be a string at line 1 column 3"),
         }
     ];
-
-    test_type_def![value_string {
-        expr: |_| ParseAwsCloudWatchLogSubscriptionMessageFn {
-            value: Literal::from("foo").boxed(),
-        },
-        def: TypeDef {
-            fallible: true,
-            kind: Kind::Map,
-            inner_type_def: inner_type_def(),
-        },
-    }];
 }
@@ -0,0 +1,222 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use remap::prelude::*;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+lazy_static! {
+    static ref RE: Regex = Regex::new(
+        r"(?ix)                        # i: case-insensitive, x: ignore whitespace + comments
+            \A
+            (?P<value>[0-9]*\.?[0-9]+) # value: integer or float
+            \s?                        # optional space between value and unit
+            (?P<unit>[a-z]{0,3})       # unit: up to three letters, bytes if omitted
+            \z"
+    )
+    .unwrap();
+    static ref UNITS: HashMap<String, Decimal> = {
+        let si = Decimal::new(1_000, 0);
+        let binary = Decimal::new(1_024, 0);
+
+        let mut units = HashMap::new();
+        units.insert("b".to_owned(), Decimal::new(1, 0));
+        for (exponent, prefix) in ["k", "m", "g", "t", "p", "e"].iter().enumerate() {
+            let si = (0..=exponent).fold(Decimal::new(1, 0), |unit, _| unit * si);
+            let binary = (0..=exponent).fold(Decimal::new(1, 0), |unit, _| unit * binary);
+
+            units.insert(prefix.to_string(), si);
+            units.insert(format!("{}b", prefix), si);
+            units.insert(format!("{}i", prefix), binary);
+            units.insert(format!("{}ib", prefix), binary);
+        }
+
+        units
+    };
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseBytes;
+
+impl Function for ParseBytes {
+    fn identifier(&self) -> &'static str {
+        "parse_bytes"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let unit = arguments.optional("unit").map(Expr::boxed);
+
+        Ok(Box::new(ParseBytesFn { value, unit }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseBytesFn {
+    value: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
+}
+
+impl ParseBytesFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>, unit: Option<&str>) -> Self {
+        let unit = unit.map(|unit| Literal::from(unit).boxed());
+
+        Self { value, unit }
+    }
+}
+
+impl Expression for ParseBytesFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.value.execute(state, object)?.try_bytes()?;
+        let value = String::from_utf8_lossy(&bytes);
+
+        let conversion_factor = match &self.unit {
+            Some(unit) => {
+                let bytes = unit.execute(state, object)?.try_bytes()?;
+                let string = String::from_utf8_lossy(&bytes);
+
+                UNITS
+                    .get(&string.to_lowercase())
+                    .ok_or(format!("unknown output unit: '{}'", string))?
+            }
+            None => &UNITS["b"],
+        };
+
+        let captures = RE
+            .captures(&value)
+            .ok_or(format!("unable to parse bytes: '{}'", value))?;
+
+        let value = Decimal::from_str(&captures["value"])
+            .map_err(|error| format!("unable to parse number: {}", error))?;
+
+        let unit = match &captures["unit"] {
+            "" => &UNITS["b"],
+            unit => UNITS
+                .get(&unit.to_lowercase())
+                .ok_or(format!("unknown bytes unit: '{}'", unit))?,
+        };
+
+        let number = value
+            .checked_mul(*unit)
+            .ok_or(format!("bytes value too large: '{}'", value))?
+            / conversion_factor;
+        let number = number
+            .to_f64()
+            .ok_or(format!("unable to format bytes: '{}'", number))?;
+
+        Ok(number.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .merge_optional(
+                self.unit
+                    .as_ref()
+                    .map(|unit| unit.type_def(state).fallible_unless(value::Kind::Bytes)),
+            )
+            .into_fallible(true) // parsing errors
+            .with_constraint(value::Kind::Float)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| ParseBytesFn::new(Literal::from("foo").boxed(), None),
+            def: TypeDef { fallible: true, kind: value::Kind::Float, ..Default::default() },
+        }
+
+        optional_expression {
+            expr: |_| ParseBytesFn::new(Box::new(Noop), Some("KiB")),
+            def: TypeDef { fallible: true, kind: value::Kind::Float, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn parse_bytes() {
+        let cases = vec![
+            (
+                btreemap! {},
+                Ok(1536.0.into()),
+                ParseBytesFn::new(Box::new(Literal::from("1.5KiB")), None),
+            ),
+            (
+                btreemap! {},
+                Ok(300000.0.into()),
+                ParseBytesFn::new(Box::new(Literal::from("300k")), Some("B")),
+            ),
+            (
+                btreemap! {},
+                Ok(1.5.into()),
+                ParseBytesFn::new(Box::new(Literal::from("1536 MiB")), Some("GiB")),
+            ),
+            (
+                btreemap! {},
+                Ok(1.5.into()),
+                ParseBytesFn::new(Box::new(Literal::from("1.5GB")), Some("gb")),
+            ),
+            (
+                btreemap! {},
+                Ok(1.024.into()),
+                ParseBytesFn::new(Box::new(Literal::from("1ki")), Some("kb")),
+            ),
+            (
+                btreemap! {},
+                Ok(42.0.into()),
+                ParseBytesFn::new(Box::new(Literal::from("42")), None),
+            ),
+            (
+                btreemap! {},
+                Ok(1024.0.into()),
+                ParseBytesFn::new(Box::new(Literal::from("1EiB")), Some("PiB")),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unable to parse bytes: 'foo'".into()),
+                ParseBytesFn::new(Box::new(Literal::from("foo")), None),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unknown bytes unit: 'w'".into()),
+                ParseBytesFn::new(Box::new(Literal::from("1w")), None),
+            ),
+            (
+                btreemap! {},
+                Err("function call error: unknown output unit: 'w'".into()),
+                ParseBytesFn::new(Box::new(Literal::from("1B")), Some("w")),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (object, exp, func) in cases {
+            let mut object: Value = object.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use remap::prelude::*;
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 #[derive(Clone, Copy, Debug)]
@@ -19,7 +20,7 @@ impl Function for ParseGrok {
             },
             Parameter {
                 keyword: "pattern",
-                accepts: |v| matches!(v, Value::Bytes(_)),
+                accepts: |v| matches!(v, Value::Bytes(_) | Value::Array(_)),
                 required: true,
             },
             Parameter {
@@ -27,37 +28,90 @@ impl Function for ParseGrok {
                 accepts: |v| matches!(v, Value::Boolean(_)),
                 required: false,
             },
+            Parameter {
+                keyword: "aliases",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: false,
+            },
         ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
+        let patterns = literal_patterns(arguments.required("pattern")?)?;
 
-        let pattern = arguments
-            .required_literal("pattern")?
-            .as_value()
-            .clone()
-            .try_bytes_utf8_lossy()?
-            .into_owned();
+        let aliases = match arguments.optional("aliases") {
+            Some(expr) => Map::try_from(expr)?
+                .into_iter()
+                .map(|(name, expr)| {
+                    let definition = Literal::try_from(expr)?.into_value();
+                    let definition = definition.try_bytes_utf8_lossy()?.into_owned();
 
-        let mut grok = grok::Grok::with_patterns();
-        let pattern = Arc::new(grok.compile(&pattern, true).map_err(|e| e.to_string())?);
+                    Ok((name, definition))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        let patterns = Arc::new(compile_patterns(&patterns, &aliases)?);
 
         let remove_empty = arguments.optional("remove_empty").map(Expr::boxed);
 
         Ok(Box::new(ParseGrokFn {
             value,
-            pattern,
+            patterns,
             remove_empty,
         }))
     }
 }
 
+/// Read the pattern argument, which is either a single pattern, or an array
+/// of patterns, as string literals.
+fn literal_patterns(expr: Expr) -> Result<Vec<String>> {
+    let value = match Array::try_from(expr.clone()) {
+        Ok(array) => Value::try_from(array)?,
+        Err(_) => Literal::try_from(expr)?.into_value(),
+    };
+
+    match value {
+        Value::Bytes(_) => Ok(vec![value.try_bytes_utf8_lossy()?.into_owned()]),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| Ok(value.try_bytes_utf8_lossy()?.into_owned()))
+            .collect(),
+        v => {
+            let want = value::Kind::Bytes | value::Kind::Array;
+            Err(value::Error::Expected(want, v.kind()).into())
+        }
+    }
+}
+
+/// Compile the patterns, with the user-defined `aliases` available to them
+/// next to the default pattern definitions.
+fn compile_patterns(
+    patterns: &[String],
+    aliases: &[(String, String)],
+) -> Result<Vec<grok::Pattern>> {
+    let mut grok = grok::Grok::with_patterns();
+
+    for (name, definition) in aliases {
+        grok.insert_definition(name.as_str(), definition.as_str());
+    }
+
+    patterns
+        .iter()
+        .map(|pattern| {
+            grok.compile(pattern, true)
+                .map_err(|e| Error::from(e.to_string()))
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 struct ParseGrokFn {
     value: Box<dyn Expression>,
-    // Wrapping pattern in an Arc, as cloning the pattern could otherwise be expensive.
-    pattern: Arc<grok::Pattern>,
+    // Wrapping patterns in an Arc, as cloning the patterns could otherwise be expensive.
+    patterns: Arc<Vec<grok::Pattern>>,
     remove_empty: Option<Box<dyn Expression>>,
 }
 
@@ -68,15 +122,21 @@ impl ParseGrokFn {
         pattern: String,
         remove_empty: Option<Box<dyn Expression>>,
     ) -> Result<Self> {
-        let mut grok = grok::Grok::with_patterns();
-        let pattern = Arc::new(
-            grok.compile(&pattern, true)
-                .map_err(|e| Error::from(e.to_string()))?,
-        );
+        Self::with_aliases(value, vec![pattern], vec![], remove_empty)
+    }
+
+    #[cfg(test)]
+    fn with_aliases(
+        value: Box<dyn Expression>,
+        patterns: Vec<String>,
+        aliases: Vec<(String, String)>,
+        remove_empty: Option<Box<dyn Expression>>,
+    ) -> Result<Self> {
+        let patterns = Arc::new(compile_patterns(&patterns, &aliases)?);
 
         Ok(Self {
             value,
-            pattern,
+            patterns,
             remove_empty,
         })
     }
@@ -91,7 +151,12 @@ impl Expression for ParseGrokFn {
             None => false,
         };
 
-        match self.pattern.match_against(&value) {
+        // The patterns are tried in order, the first one to match wins.
+        match self
+            .patterns
+            .iter()
+            .find_map(|pattern| pattern.match_against(&value))
+        {
             Some(matches) => {
                 let mut result = BTreeMap::new();
 
@@ -123,11 +188,9 @@ mod test {
     remap::test_type_def![string {
         expr: |_| ParseGrokFn {
             value: Literal::from("foo").boxed(),
-            pattern: Arc::new(
-                grok::Grok::with_patterns()
-                    .compile("%{LOGLEVEL:level}", true)
-                    .unwrap()
-            ),
+            patterns: Arc::new(vec![grok::Grok::with_patterns()
+                .compile("%{LOGLEVEL:level}", true)
+                .unwrap()]),
             remove_empty: Some(Literal::from(false).boxed()),
         },
         def: TypeDef {
@@ -231,6 +294,66 @@ mod test {
                 )
                 .unwrap(),
             ),
+            (
+                btreemap! { "message" => "2020-10-02T23:22:12.223222Z info Hello world" },
+                Ok(Value::from(btreemap! {
+                    "timestamp" => "2020-10-02T23:22:12.223222Z",
+                    "level" => "info",
+                    "message" => "Hello world",
+                })),
+                ParseGrokFn::with_aliases(
+                    Box::new(Path::from("message")),
+                    vec![
+                        "%{NUMBER:status} %{GREEDYDATA:message}".to_string(),
+                        "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}"
+                            .to_string(),
+                    ],
+                    vec![],
+                    None,
+                )
+                .unwrap(),
+            ),
+            (
+                btreemap! { "message" => "404 Not Found" },
+                Ok(Value::from(btreemap! {
+                    "status" => "404",
+                    "message" => "Not Found",
+                })),
+                ParseGrokFn::with_aliases(
+                    Box::new(Path::from("message")),
+                    vec![
+                        "%{NUMBER:status} %{GREEDYDATA:message}".to_string(),
+                        "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}"
+                            .to_string(),
+                    ],
+                    vec![],
+                    None,
+                )
+                .unwrap(),
+            ),
+            (
+                btreemap! { "message" => "user=ben action=login" },
+                Ok(Value::from(btreemap! {
+                    "user" => "ben",
+                    "action" => "login",
+                })),
+                ParseGrokFn::with_aliases(
+                    Box::new(Path::from("message")),
+                    vec!["%{USER_FIELD} %{ACTION_FIELD}".to_string()],
+                    vec![
+                        (
+                            "USER_FIELD".to_string(),
+                            "user=%{USERNAME:user}".to_string(),
+                        ),
+                        (
+                            "ACTION_FIELD".to_string(),
+                            "action=%{WORD:action}".to_string(),
+                        ),
+                    ],
+                    None,
+                )
+                .unwrap(),
+            ),
         ];
 
         let mut state = state::Program::default();
@@ -245,3 +368,24 @@ mod test {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        parse_grok => ParseGrok;
+
+        simple {
+            args: func_args![
+                value: "2020-10-02T23:22:12.223222Z info Hello world",
+                pattern: "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+            ],
+            want: Ok(value!({
+                timestamp: "2020-10-02T23:22:12.223222Z",
+                level: "info",
+                message: "Hello world",
+            })),
+        }
+    ];
+}
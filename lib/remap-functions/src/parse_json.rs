@@ -59,9 +59,31 @@ impl Expression for ParseJsonFn {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use shared::btreemap;
     use value::Kind;
 
+    test_type_def![value_string {
+        expr: |_| ParseJsonFn {
+            value: lit!("foo").boxed(),
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: Kind::Bytes
+                | Kind::Boolean
+                | Kind::Integer
+                | Kind::Float
+                | Kind::Array
+                | Kind::Map
+                | Kind::Null,
+            ..Default::default()
+        },
+    }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use shared::btreemap;
+
     test_function![
         parse_json => ParseJson;
 
@@ -92,21 +114,4 @@ mod tests {
             want: Err("function call error: unable to parse json: key must be a string at line 1 column 3"),
         }
     ];
-
-    test_type_def![value_string {
-        expr: |_| ParseJsonFn {
-            value: lit!("foo").boxed(),
-        },
-        def: TypeDef {
-            fallible: true,
-            kind: Kind::Bytes
-                | Kind::Boolean
-                | Kind::Integer
-                | Kind::Float
-                | Kind::Array
-                | Kind::Map
-                | Kind::Null,
-            ..Default::default()
-        },
-    }];
 }
@@ -292,6 +292,12 @@ mod test {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use remap::value;
 
     test_function![
         parse_key_value => ParseKeyValue;
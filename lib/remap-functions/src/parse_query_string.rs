@@ -0,0 +1,134 @@
+use remap::prelude::*;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use url::form_urlencoded;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseQueryString;
+
+impl Function for ParseQueryString {
+    fn identifier(&self) -> &'static str {
+        "parse_query_string"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Bytes(_)),
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+
+        Ok(Box::new(ParseQueryStringFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseQueryStringFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseQueryStringFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let bytes = self.value.execute(state, object)?.try_bytes()?;
+
+        // Allow passing in the query part of a URL, including the leading `?`.
+        let bytes = match bytes.first() {
+            Some(b'?') => &bytes[1..],
+            _ => &bytes[..],
+        };
+
+        let mut result = BTreeMap::new();
+
+        for (key, value) in form_urlencoded::parse(bytes) {
+            let value: Value = value.into_owned().into();
+
+            // Repeated keys are collected into an array, in the order they
+            // appear in the query string.
+            match result.entry(key.into_owned()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(mut entry) => match entry.get_mut() {
+                    Value::Array(values) => values.push(value),
+                    existing => {
+                        let first = std::mem::replace(existing, Value::Null);
+                        *existing = vec![first, value].into();
+                    }
+                },
+            }
+        }
+
+        Ok(result.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Bytes)
+            .with_constraint(value::Kind::Map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| ParseQueryStringFn { value: Literal::from("foo=bar").boxed() },
+            def: TypeDef { kind: Kind::Map, ..Default::default() },
+        }
+
+        value_non_string {
+            expr: |_| ParseQueryStringFn { value: Literal::from(1).boxed() },
+            def: TypeDef { fallible: true, kind: Kind::Map, ..Default::default() },
+        }
+    ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        parse_query_string => ParseQueryString;
+
+        complete {
+            args: func_args![value: "foo=%2B1&bar=2&xyz=&abc"],
+            want: Ok(value!({
+                foo: "+1",
+                bar: "2",
+                xyz: "",
+                abc: "",
+            })),
+        }
+
+        multiple_values {
+            args: func_args![value: "a=1&b=two%20words&b=3"],
+            want: Ok(value!({
+                a: "1",
+                b: ["two words", "3"],
+            })),
+        }
+
+        plus_as_space {
+            args: func_args![value: "q=hello+world"],
+            want: Ok(value!({ q: "hello world" })),
+        }
+
+        leading_question_mark {
+            args: func_args![value: "?foo=bar"],
+            want: Ok(value!({ foo: "bar" })),
+        }
+
+        empty_string {
+            args: func_args![value: ""],
+            want: Ok(value!({})),
+        }
+    ];
+}
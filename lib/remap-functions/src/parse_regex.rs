@@ -111,6 +111,12 @@ mod tests {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+#[allow(clippy::trivial_regex)]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         find => ParseRegex;
@@ -114,6 +114,12 @@ mod tests {
             },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+#[allow(clippy::trivial_regex)]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         find_all => ParseRegexAll;
@@ -135,6 +141,23 @@ mod tests {
                               "2": "peas"}]))
         }
 
+        non_overlapping {
+            args: func_args![
+                value: "contact ben@example.com or ops@example.com",
+                pattern: Regex::new(r#"(?P<user>\w+)@(?P<domain>[\w\.]+)"#).unwrap()
+            ],
+            want: Ok(value!([{"user": "ben",
+                              "domain": "example.com",
+                              "0": "ben@example.com",
+                              "1": "ben",
+                              "2": "example.com"},
+                             {"user": "ops",
+                              "domain": "example.com",
+                              "0": "ops@example.com",
+                              "1": "ops",
+                              "2": "example.com"}]))
+        }
+
         no_matches {
             args: func_args![
                 value: "I don't match",
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Datelike, Utc};
 use remap::prelude::*;
 use remap::value::Kind;
-use std::collections::BTreeMap;
+use std::{cell::Cell, collections::BTreeMap};
 use syslog_loose::{IncompleteDate, Message, ProcId};
 
 #[derive(Clone, Copy, Debug)]
@@ -51,8 +51,14 @@ fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
     }
 }
 
-/// Create a Value::Map from the fields of the given syslog message.
-fn message_to_value(message: Message<&str>) -> Value {
+/// Create a Value::Map from the fields of the given syslog message. The
+/// timestamp is `local` if it carried no offset, and is then read in
+/// `timezone`.
+fn message_to_value(
+    message: Message<&str>,
+    local: bool,
+    timezone: shared::datetime::TimeZone,
+) -> Value {
     let mut result = BTreeMap::new();
 
     result.insert("message".to_string(), message.msg.to_string().into());
@@ -78,7 +84,14 @@ fn message_to_value(message: Message<&str>) -> Value {
     }
 
     if let Some(timestamp) = message.timestamp {
-        let timestamp: DateTime<Utc> = timestamp.into();
+        let utc: DateTime<Utc> = timestamp.into();
+        let timestamp = if local {
+            timezone
+                .datetime_from_local(&timestamp.naive_local())
+                .unwrap_or(utc)
+        } else {
+            utc
+        };
         result.insert("timestamp".to_string(), timestamp.into());
     }
 
@@ -105,9 +118,15 @@ impl Expression for ParseSyslogFn {
         let bytes = self.value.execute(state, object)?.try_bytes()?;
         let message = String::from_utf8_lossy(&bytes);
 
-        let parsed = syslog_loose::parse_message_with_year_exact(&message, resolve_year)?;
+        // Only the RFC 3164 timestamps lack the year, and they lack an offset
+        // as well.
+        let local = Cell::new(false);
+        let parsed = syslog_loose::parse_message_with_year_exact(&message, |date| {
+            local.set(true);
+            resolve_year(date)
+        })?;
 
-        Ok(message_to_value(parsed))
+        Ok(message_to_value(parsed, local.get(), state.timezone()))
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
@@ -135,7 +154,6 @@ fn inner_type_def() -> Option<InnerTypeDef> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::prelude::*;
     use shared::btreemap;
 
     remap::test_type_def![
@@ -164,54 +182,6 @@ mod tests {
         }
     ];
 
-    remap::test_function![
-        parse_syslog => ParseSyslog;
-
-        valid {
-            args: func_args![value: r#"<13>1 2020-03-13T20:45:38.119Z dynamicwireless.name non 2426 ID931 [exampleSDID@32473 iut="3" eventSource= "Application" eventID="1011"] Try to override the THX port, maybe it will reboot the neural interface!"#],
-            want: Ok(btreemap! {
-                "severity" => "notice",
-                "facility" => "user",
-                "timestamp" => chrono::Utc.ymd(2020, 3, 13).and_hms_milli(20, 45, 38, 119),
-                "hostname" => "dynamicwireless.name",
-                "appname" => "non",
-                "procid" => 2426,
-                "msgid" => "ID931",
-                "exampleSDID@32473.iut" => "3",
-                "exampleSDID@32473.eventSource" => "Application",
-                "exampleSDID@32473.eventID" => "1011",
-                "message" => "Try to override the THX port, maybe it will reboot the neural interface!",
-            })
-        }
-
-        invalid {
-            args: func_args![value: "not much of a syslog message"],
-            want: Err("function call error: unable to parse input as valid syslog message".to_string())
-        }
-
-        haproxy {
-            args: func_args![value: r#"<133>Jun 13 16:33:35 haproxy[73411]: Proxy sticky-servers started."#],
-            want: Ok(btreemap! {
-                    "facility" => "local0",
-                    "severity" => "notice",
-                    "message" => "Proxy sticky-servers started.",
-                    "timestamp" => DateTime::<Utc>::from(chrono::Local.ymd(Utc::now().year(), 6, 13).and_hms_milli(16, 33, 35, 0)),
-                    "appname" => "haproxy",
-                    "procid" => 73411,
-            })
-        }
-
-        missing_pri {
-            args: func_args![value: r#"Jun 13 16:33:35 haproxy[73411]: I am missing a pri."#],
-            want: Ok(btreemap! {
-                "message" => "I am missing a pri.",
-                "timestamp" => DateTime::<Utc>::from(chrono::Local.ymd(Utc::now().year(), 6, 13).and_hms_milli(16, 33, 35, 0)),
-                "appname" => "haproxy",
-                "procid" => 73411,
-            })
-        }
-    ];
-
     #[test]
     fn handles_empty_sd_element() {
         fn there_is_map_called_empty(value: Value) -> Result<bool> {
@@ -263,3 +233,58 @@ mod tests {
         assert!(!there_is_map_called_empty(value).unwrap());
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use chrono::prelude::*;
+    use shared::btreemap;
+
+    remap::test_function![
+        parse_syslog => ParseSyslog;
+
+        valid {
+            args: func_args![value: r#"<13>1 2020-03-13T20:45:38.119Z dynamicwireless.name non 2426 ID931 [exampleSDID@32473 iut="3" eventSource= "Application" eventID="1011"] Try to override the THX port, maybe it will reboot the neural interface!"#],
+            want: Ok(btreemap! {
+                "severity" => "notice",
+                "facility" => "user",
+                "timestamp" => chrono::Utc.ymd(2020, 3, 13).and_hms_milli(20, 45, 38, 119),
+                "hostname" => "dynamicwireless.name",
+                "appname" => "non",
+                "procid" => 2426,
+                "msgid" => "ID931",
+                "exampleSDID@32473.iut" => "3",
+                "exampleSDID@32473.eventSource" => "Application",
+                "exampleSDID@32473.eventID" => "1011",
+                "message" => "Try to override the THX port, maybe it will reboot the neural interface!",
+            })
+        }
+
+        invalid {
+            args: func_args![value: "not much of a syslog message"],
+            want: Err("function call error: unable to parse input as valid syslog message".to_string())
+        }
+
+        haproxy {
+            args: func_args![value: r#"<133>Jun 13 16:33:35 haproxy[73411]: Proxy sticky-servers started."#],
+            want: Ok(btreemap! {
+                    "facility" => "local0",
+                    "severity" => "notice",
+                    "message" => "Proxy sticky-servers started.",
+                    "timestamp" => DateTime::<Utc>::from(chrono::Local.ymd(Utc::now().year(), 6, 13).and_hms_milli(16, 33, 35, 0)),
+                    "appname" => "haproxy",
+                    "procid" => 73411,
+            })
+        }
+
+        missing_pri {
+            args: func_args![value: r#"Jun 13 16:33:35 haproxy[73411]: I am missing a pri."#],
+            want: Ok(btreemap! {
+                "message" => "I am missing a pri.",
+                "timestamp" => DateTime::<Utc>::from(chrono::Local.ymd(Utc::now().year(), 6, 13).and_hms_milli(16, 33, 35, 0)),
+                "appname" => "haproxy",
+                "procid" => 73411,
+            })
+        }
+    ];
+}
@@ -1,3 +1,4 @@
+use crate::util;
 use remap::prelude::*;
 use shared::conversion::Conversion;
 
@@ -21,14 +22,24 @@ impl Function for ParseTimestamp {
                 accepts: |v| matches!(v, Value::Bytes(_)),
                 required: true,
             },
+            Parameter {
+                keyword: "timezone",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
         ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
         let format = arguments.required("format")?.boxed();
+        let timezone = arguments.optional("timezone").map(Expr::boxed);
 
-        Ok(Box::new(ParseTimestampFn { value, format }))
+        Ok(Box::new(ParseTimestampFn {
+            value,
+            format,
+            timezone,
+        }))
     }
 }
 
@@ -36,14 +47,20 @@ impl Function for ParseTimestamp {
 struct ParseTimestampFn {
     value: Box<dyn Expression>,
     format: Box<dyn Expression>,
+    timezone: Option<Box<dyn Expression>>,
 }
 
 impl ParseTimestampFn {
     #[cfg(test)]
-    fn new(format: &str, value: Box<dyn Expression>) -> Self {
+    fn new(format: &str, value: Box<dyn Expression>, timezone: Option<&str>) -> Self {
         let format = Box::new(Literal::from(format));
+        let timezone = timezone.map(|tz| Literal::from(tz).boxed());
 
-        Self { value, format }
+        Self {
+            value,
+            format,
+            timezone,
+        }
     }
 }
 
@@ -51,12 +68,15 @@ impl Expression for ParseTimestampFn {
     fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
         let value = self.value.execute(state, object)?;
         let format = self.format.execute(state, object);
+        let tz = util::timezone(self.timezone.as_deref(), state, object)?
+            .unwrap_or_else(|| state.timezone());
 
         match value {
             Value::Bytes(v) => format
-                .map(|v| format!("timestamp|{}", String::from_utf8_lossy(&v.unwrap_bytes())))?
-                .parse::<Conversion>()
-                .map_err(|e| format!("{}", e))?
+                .map(|v| format!("timestamp|{}", String::from_utf8_lossy(&v.unwrap_bytes())))
+                .and_then(|format| {
+                    Conversion::parse(&format, tz).map_err(|e| e.to_string().into())
+                })?
                 .convert(v)
                 .map_err(|e| e.to_string().into()),
             Value::Timestamp(_) => Ok(value),
@@ -78,7 +98,7 @@ impl Expression for ParseTimestampFn {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, TimeZone, Utc};
     use shared::btreemap;
 
     remap::test_type_def![
@@ -86,6 +106,7 @@ mod tests {
             expr: |_| ParseTimestampFn {
                 value: lit!("<timestamp>").boxed(),
                 format: lit!("<format>").boxed(),
+                timezone: None,
             },
             def: TypeDef {
                 fallible: true,
@@ -98,6 +119,7 @@ mod tests {
             expr: |_| ParseTimestampFn {
                 value: Literal::from(chrono::Utc::now()).boxed(),
                 format: lit!("<format>").boxed(),
+                timezone: None,
             },
             def: TypeDef {
                 fallible: true,
@@ -110,6 +132,7 @@ mod tests {
             expr: |_| ParseTimestampFn {
                 value: lit!(127).boxed(),
                 format: lit!("<format>").boxed(),
+                timezone: None,
             },
             def: TypeDef {
                 fallible: true,
@@ -134,7 +157,7 @@ mod tests {
                         .with_timezone(&Utc)
                         .into(),
                 ),
-                ParseTimestampFn::new("%d/%m/%Y:%H:%M:%S %z", Box::new(Path::from("foo"))),
+                ParseTimestampFn::new("%d/%m/%Y:%H:%M:%S %z", Box::new(Path::from("foo")), None),
             ),
             (
                 btreemap! { "foo" => "16/10/2019:12:00:00 +0000" },
@@ -144,7 +167,7 @@ mod tests {
                         .with_timezone(&Utc)
                         .into(),
                 ),
-                ParseTimestampFn::new("%d/%m/%Y:%H:%M:%S %z", Box::new(Path::from("foo"))),
+                ParseTimestampFn::new("%d/%m/%Y:%H:%M:%S %z", Box::new(Path::from("foo")), None),
             ),
         ];
 
@@ -159,4 +182,33 @@ mod tests {
             assert_eq!(got, exp);
         }
     }
+
+    #[test]
+    fn parse_timestamp_in_program_timezone() {
+        let func = ParseTimestampFn::new("%F %T", Box::new(Path::from("foo")), None);
+        let timezone = shared::datetime::TimeZone::parse("America/New_York").unwrap();
+        let mut state = state::Program::with_timezone(timezone);
+        let mut object: Value = btreemap! { "foo" => "2021-01-15 08:30:00" }.into();
+
+        assert_eq!(
+            func.execute(&mut state, &mut object)
+                .map_err(|e| e.to_string()),
+            Ok(Utc.ymd(2021, 1, 15).and_hms(13, 30, 0).into())
+        );
+    }
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        parse_timestamp => ParseTimestamp;
+
+        format {
+            args: func_args![value: "16/10/2019:12:00:00 +0000", format: "%d/%m/%Y:%H:%M:%S %z"],
+            want: Ok(Utc.ymd(2019, 10, 16).and_hms(12, 0, 0)),
+        }
+    ];
 }
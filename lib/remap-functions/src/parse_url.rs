@@ -13,40 +13,85 @@ impl Function for ParseUrl {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            accepts: |v| matches!(v, Value::Bytes(_)),
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "default_known_ports",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "decode_punycode",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
+        let default_known_ports = arguments.optional("default_known_ports").map(Expr::boxed);
+        let decode_punycode = arguments.optional("decode_punycode").map(Expr::boxed);
 
-        Ok(Box::new(ParseUrlFn { value }))
+        Ok(Box::new(ParseUrlFn {
+            value,
+            default_known_ports,
+            decode_punycode,
+        }))
     }
 }
 
 #[derive(Debug, Clone)]
 struct ParseUrlFn {
     value: Box<dyn Expression>,
+    default_known_ports: Option<Box<dyn Expression>>,
+    decode_punycode: Option<Box<dyn Expression>>,
 }
 
 impl ParseUrlFn {
     #[cfg(test)]
     fn new(value: Box<dyn Expression>) -> Self {
-        Self { value }
+        Self {
+            value,
+            default_known_ports: None,
+            decode_punycode: None,
+        }
     }
 }
 
+/// Options controlling how a parsed [`Url`] is turned into a [`Value`].
+#[derive(Debug, Default)]
+struct UrlOptions {
+    /// Use the well-known port of the scheme if the URL doesn't specify one.
+    default_known_ports: bool,
+
+    /// Decode IDNA ("xn--") encoded hosts into their unicode representation.
+    decode_punycode: bool,
+}
+
 impl Expression for ParseUrlFn {
     fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
         let value = self.value.execute(state, object)?;
         let string = value.try_bytes_utf8_lossy()?;
 
+        let options = UrlOptions {
+            default_known_ports: match &self.default_known_ports {
+                Some(expr) => expr.execute(state, object)?.try_boolean()?,
+                None => false,
+            },
+            decode_punycode: match &self.decode_punycode {
+                Some(expr) => expr.execute(state, object)?.try_boolean()?,
+                None => false,
+            },
+        };
+
         Url::parse(&string)
             .map_err(|e| format!("unable to parse url: {}", e).into())
-            .map(url_to_value)
+            .map(|url| url_to_value(url, &options))
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
@@ -65,14 +110,15 @@ fn inner_type_def() -> Option<InnerTypeDef> {
         "username": Kind::Bytes,
         "password": Kind::Bytes,
         "path": Kind::Bytes | Kind::Null,
-        "host": Kind::Bytes,
-        "port": Kind::Bytes,
+        "path_segments": TypeDef::from(Kind::Array).with_inner_type(Some(inner_type_def!([Kind::Bytes]))),
+        "host": Kind::Bytes | Kind::Null,
+        "port": Kind::Integer | Kind::Null,
         "fragment": Kind::Bytes | Kind::Null,
         "query": Kind::Map,
     }))
 }
 
-fn url_to_value(url: Url) -> Value {
+fn url_to_value(url: Url, options: &UrlOptions) -> Value {
     let mut map = BTreeMap::<&str, Value>::new();
 
     map.insert("scheme", url.scheme().to_owned().into());
@@ -85,8 +131,40 @@ fn url_to_value(url: Url) -> Value {
             .into(),
     );
     map.insert("path", url.path().to_owned().into());
-    map.insert("host", url.host_str().map(ToOwned::to_owned).into());
-    map.insert("port", url.port().map(|v| v as i64).into());
+    map.insert(
+        "path_segments",
+        url.path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| Value::from(segment.to_owned()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+            .into(),
+    );
+    map.insert(
+        "host",
+        url.host_str()
+            .map(|host| {
+                if options.decode_punycode {
+                    // `domain_to_unicode` only errors on invalid labels, in
+                    // which case it still returns a best-effort result that
+                    // keeps those labels as-is.
+                    idna::domain_to_unicode(host).0
+                } else {
+                    host.to_owned()
+                }
+            })
+            .into(),
+    );
+
+    let port = if options.default_known_ports {
+        url.port_or_known_default()
+    } else {
+        url.port()
+    };
+    map.insert("port", port.map(|v| v as i64).into());
     map.insert("fragment", url.fragment().map(ToOwned::to_owned).into());
     map.insert(
         "query",
@@ -107,12 +185,12 @@ mod tests {
 
     remap::test_type_def![
         value_string {
-            expr: |_| ParseUrlFn { value: Literal::from("foo").boxed() },
+            expr: |_| ParseUrlFn::new(Literal::from("foo").boxed()),
             def: TypeDef { fallible: true, kind: value::Kind::Map, inner_type_def: inner_type_def() },
         }
 
         value_optional {
-            expr: |_| ParseUrlFn { value: Box::new(Noop) },
+            expr: |_| ParseUrlFn::new(Box::new(Noop)),
             def: TypeDef { fallible: true, kind: value::Kind::Map, inner_type_def: inner_type_def() },
         }
     ];
@@ -129,6 +207,7 @@ mod tests {
                         "host" => "vector.dev",
                         "port" => Value::Null,
                         "path" => "/",
+                        "path_segments" => Vec::<Value>::new(),
                         "query" => btreemap!{},
                         "fragment" => Value::Null,
                 }
@@ -144,6 +223,7 @@ mod tests {
                         "host" => "vector.dev",
                         "port" => 4343,
                         "path" => "/foobar",
+                        "path_segments" => vec!["foobar"],
                         "query" => btreemap!{ "hello" => "world" },
                         "fragment" => "123",
                 }
@@ -171,3 +251,87 @@ mod tests {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        parse_url => ParseUrl;
+
+        path_segments {
+            args: func_args![value: "https://vector.dev/docs/reference/?foo=bar"],
+            want: Ok(value!({
+                scheme: "https",
+                username: "",
+                password: "",
+                host: "vector.dev",
+                port: null,
+                path: "/docs/reference/",
+                path_segments: ["docs", "reference"],
+                query: { foo: "bar" },
+                fragment: null,
+            })),
+        }
+
+        default_known_ports {
+            args: func_args![value: "https://vector.dev", default_known_ports: true],
+            want: Ok(value!({
+                scheme: "https",
+                username: "",
+                password: "",
+                host: "vector.dev",
+                port: 443,
+                path: "/",
+                path_segments: [],
+                query: {},
+                fragment: null,
+            })),
+        }
+
+        default_known_ports_explicit_port {
+            args: func_args![value: "http://vector.dev:8080/", default_known_ports: true],
+            want: Ok(value!({
+                scheme: "http",
+                username: "",
+                password: "",
+                host: "vector.dev",
+                port: 8080,
+                path: "/",
+                path_segments: [],
+                query: {},
+                fragment: null,
+            })),
+        }
+
+        punycode_encoded {
+            args: func_args![value: "https://www.xn--caf-dma.com/"],
+            want: Ok(value!({
+                scheme: "https",
+                username: "",
+                password: "",
+                host: "www.xn--caf-dma.com",
+                port: null,
+                path: "/",
+                path_segments: [],
+                query: {},
+                fragment: null,
+            })),
+        }
+
+        punycode_decoded {
+            args: func_args![value: "https://www.xn--caf-dma.com/", decode_punycode: true],
+            want: Ok(value!({
+                scheme: "https",
+                username: "",
+                password: "",
+                host: "www.café.com",
+                port: null,
+                path: "/",
+                path_segments: [],
+                query: {},
+                fragment: null,
+            })),
+        }
+    ];
+}
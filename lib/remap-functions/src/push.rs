@@ -94,6 +94,11 @@ mod tests {
             def: TypeDef { kind: Kind::Array, fallible: true, ..Default::default() },
         }
     ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         push => Push;
@@ -0,0 +1,129 @@
+use bytes::Bytes;
+use rand::RngCore;
+use remap::prelude::*;
+
+/// The largest number of bytes that can be generated at once.
+const MAX_LENGTH: i64 = 64 * 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RandomBytes;
+
+impl Function for RandomBytes {
+    fn identifier(&self) -> &'static str {
+        "random_bytes"
+    }
+
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "length",
+            accepts: |v| matches!(v, Value::Integer(_)),
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let length = arguments.required("length")?.boxed();
+
+        Ok(Box::new(RandomBytesFn { length }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RandomBytesFn {
+    length: Box<dyn Expression>,
+}
+
+impl Expression for RandomBytesFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let length = self.length.execute(state, object)?.try_integer()?;
+
+        if length < 0 || length > MAX_LENGTH {
+            return Err(format!(
+                "length must be between 0 and {} bytes, got {}",
+                MAX_LENGTH, length
+            )
+            .into());
+        }
+
+        let mut bytes = vec![0; length as usize];
+        rand::thread_rng().fill_bytes(&mut bytes);
+
+        Ok(Bytes::from(bytes).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        use value::Kind;
+
+        self.length
+            .type_def(state)
+            .fallible_unless(Kind::Integer)
+            .into_fallible(true)
+            .with_constraint(Kind::Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    remap::test_type_def![static_def {
+        expr: |_| RandomBytesFn {
+            length: Literal::from(16).boxed(),
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: value::Kind::Bytes,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn random_bytes() {
+        let mut state = state::Program::default();
+        let mut object: Value = btreemap! {}.into();
+        let func = RandomBytesFn {
+            length: Literal::from(16).boxed(),
+        };
+
+        let first = func.execute(&mut state, &mut object).unwrap();
+        let second = func.execute(&mut state, &mut object).unwrap();
+
+        match (&first, &second) {
+            (Value::Bytes(first), Value::Bytes(second)) => {
+                assert_eq!(first.len(), 16);
+                assert_eq!(second.len(), 16);
+                assert_ne!(first, second);
+            }
+            _ => panic!("unexpected values: {:?}, {:?}", first, second),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        random_bytes => RandomBytes;
+
+        empty {
+            args: func_args![length: 0],
+            want: Ok(""),
+        }
+
+        negative_length {
+            args: func_args![length: -1],
+            want: Err("function call error: length must be between 0 and 65536 bytes, got -1"),
+        }
+
+        too_long {
+            args: func_args![length: 65537],
+            want: Err("function call error: length must be between 0 and 65536 bytes, got 65537"),
+        }
+    ];
+}
@@ -0,0 +1,140 @@
+use rand::Rng;
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RandomInt;
+
+impl Function for RandomInt {
+    fn identifier(&self) -> &'static str {
+        "random_int"
+    }
+
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "min",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "max",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let min = arguments.required("min")?.boxed();
+        let max = arguments.required("max")?.boxed();
+
+        Ok(Box::new(RandomIntFn { min, max }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RandomIntFn {
+    min: Box<dyn Expression>,
+    max: Box<dyn Expression>,
+}
+
+impl Expression for RandomIntFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let min = self.min.execute(state, object)?.try_integer()?;
+        let max = self.max.execute(state, object)?.try_integer()?;
+
+        if min >= max {
+            return Err(format!("max ({}) must be greater than min ({})", max, min).into());
+        }
+
+        Ok(rand::thread_rng().gen_range(min..max).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        use value::Kind;
+
+        self.min
+            .type_def(state)
+            .fallible_unless(Kind::Integer)
+            .merge(self.max.type_def(state).fallible_unless(Kind::Integer))
+            .into_fallible(true)
+            .with_constraint(Kind::Integer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+
+    remap::test_type_def![
+        integers {
+            expr: |_| RandomIntFn {
+                min: Literal::from(0).boxed(),
+                max: Literal::from(10).boxed(),
+            },
+            def: TypeDef {
+                fallible: true,
+                kind: value::Kind::Integer,
+                ..Default::default()
+            },
+        }
+
+        non_integer {
+            expr: |_| RandomIntFn {
+                min: Literal::from("foo").boxed(),
+                max: Literal::from(10).boxed(),
+            },
+            def: TypeDef {
+                fallible: true,
+                kind: value::Kind::Integer,
+                ..Default::default()
+            },
+        }
+    ];
+
+    #[test]
+    fn random_int() {
+        let mut state = state::Program::default();
+        let mut object: Value = btreemap! {}.into();
+
+        for _ in 0..100 {
+            let func = RandomIntFn {
+                min: Literal::from(-5).boxed(),
+                max: Literal::from(5).boxed(),
+            };
+            match func.execute(&mut state, &mut object).unwrap() {
+                Value::Integer(i) => assert!((-5..5).contains(&i)),
+                value => panic!("unexpected value: {:?}", value),
+            }
+        }
+    }
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        random_int => RandomInt;
+
+        empty_range {
+            args: func_args![min: 5, max: 5],
+            want: Err("function call error: max (5) must be greater than min (5)"),
+        }
+
+        reversed_range {
+            args: func_args![min: 10, max: -10],
+            want: Err("function call error: max (-10) must be greater than min (10)"),
+        }
+
+        single_value {
+            args: func_args![min: 3, max: 4],
+            want: Ok(3),
+        }
+    ];
+}
@@ -1,3 +1,4 @@
+use crate::util::{Pattern, PatternValue};
 use remap::prelude::*;
 use std::str::FromStr;
 
@@ -49,7 +50,12 @@ impl Function for Redact {
             .map(|s| Redactor::from_str(&s).expect("validated enum"))
             .unwrap_or_default();
 
-        let patterns = arguments.optional_array("patterns")?.map(Into::into);
+        let patterns = arguments.optional_array("patterns")?.map(|array| {
+            Vec::<Expr>::from(array)
+                .into_iter()
+                .map(Into::into)
+                .collect()
+        });
 
         Ok(Box::new(RedactFn {
             value,
@@ -67,7 +73,7 @@ struct RedactFn {
     value: Box<dyn Expression>,
     filters: Vec<Filter>,
     redactor: Redactor,
-    patterns: Option<Vec<Expr>>,
+    patterns: Option<Vec<Pattern>>,
 }
 
 impl Expression for RedactFn {
@@ -82,24 +88,21 @@ impl Expression for RedactFn {
                     .as_deref()
                     .unwrap_or_default()
                     .iter()
-                    .try_for_each::<_, Result<()>>(|expr| match expr.execute(state, object)? {
-                        Value::Bytes(bytes) => {
-                            let pattern = String::from_utf8_lossy(&bytes);
-
-                            input = input.replace(pattern.as_ref(), self.redactor.pattern());
-                            Ok(())
-                        }
-                        Value::Regex(regex) => {
-                            input = regex
-                                .replace_all(&input, self.redactor.pattern())
-                                .into_owned();
-                            Ok(())
+                    .try_for_each::<_, Result<()>>(|pattern| {
+                        match pattern.execute(state, object)? {
+                            PatternValue::Bytes(bytes) => {
+                                let pattern = String::from_utf8_lossy(&bytes);
+
+                                input = input.replace(pattern.as_ref(), self.redactor.pattern());
+                            }
+                            PatternValue::Regex(regex) => {
+                                input = regex
+                                    .replace_all(&input, self.redactor.pattern())
+                                    .into_owned();
+                            }
                         }
-                        v => Err(value::Error::Expected(
-                            value::Kind::Bytes | value::Kind::Regex,
-                            v.kind(),
-                        )
-                        .into()),
+
+                        Ok(())
                     })?,
             }
         }
@@ -264,7 +267,7 @@ mod test {
             expr: |_| RedactFn {
                 value: lit!("1111222233334444").boxed(),
                 filters: vec![Filter::Pattern],
-                patterns: Some(vec![Literal::from(Regex::new(r"/[0-9]{16}/").unwrap()).into()]),
+                patterns: Some(vec![Regex::new(r"/[0-9]{16}/").unwrap().into()]),
                 redactor: Redactor::Full,
             },
             def: TypeDef {
@@ -277,7 +280,7 @@ mod test {
             expr: |_| RedactFn {
                 value: lit!("1111222233334444").boxed(),
                 filters: vec![Filter::Pattern],
-                patterns: Some(vec![lit!("i am a teapot").into()]),
+                patterns: Some(vec![lit!("i am a teapot").boxed().into()]),
                 redactor: Redactor::Full,
             },
             def: TypeDef {
@@ -0,0 +1,269 @@
+use crate::util;
+use regex::Regex;
+use remap::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RenameKeys;
+
+impl Function for RenameKeys {
+    fn identifier(&self) -> &'static str {
+        "rename_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_) | Value::Array(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "mapping",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "pattern",
+                accepts: |v| matches!(v, Value::Regex(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "with",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "recursive",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let mapping = arguments.optional("mapping").map(Expr::boxed);
+        let pattern = arguments.optional_regex("pattern")?;
+        let with = match pattern {
+            Some(_) => Some(arguments.required("with")?.boxed()),
+            None => None,
+        };
+        let recursive = arguments.optional("recursive").map(Expr::boxed);
+
+        if mapping.is_none() && pattern.is_none() {
+            return Err("either a mapping or a pattern is required to rename keys".into());
+        }
+
+        Ok(Box::new(RenameKeysFn {
+            value,
+            mapping,
+            pattern,
+            with,
+            recursive,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RenameKeysFn {
+    value: Box<dyn Expression>,
+    mapping: Option<Box<dyn Expression>>,
+    pattern: Option<Regex>,
+    with: Option<Box<dyn Expression>>,
+    recursive: Option<Box<dyn Expression>>,
+}
+
+impl RenameKeysFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>, mapping: Box<dyn Expression>) -> Self {
+        Self {
+            value,
+            mapping: Some(mapping),
+            pattern: None,
+            with: None,
+            recursive: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Renamer<'a> {
+    mapping: BTreeMap<String, Value>,
+    pattern: Option<(&'a Regex, String)>,
+}
+
+impl Renamer<'_> {
+    /// Returns the new name of `key`.
+    ///
+    /// An exact match in the mapping table takes precedence over the pattern.
+    fn rename(&self, key: String) -> Result<String> {
+        if let Some(name) = self.mapping.get(&key) {
+            return Ok(name.try_bytes_utf8_lossy()?.into_owned());
+        }
+
+        match &self.pattern {
+            Some((pattern, with)) => Ok(pattern.replace_all(&key, with.as_str()).into_owned()),
+            None => Ok(key),
+        }
+    }
+}
+
+impl Expression for RenameKeysFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let mapping = match &self.mapping {
+            Some(expr) => expr.execute(state, object)?.try_map()?,
+            None => BTreeMap::new(),
+        };
+
+        let pattern = match (&self.pattern, &self.with) {
+            (Some(pattern), Some(with)) => {
+                let with = with
+                    .execute(state, object)?
+                    .try_bytes_utf8_lossy()?
+                    .into_owned();
+                Some((pattern, with))
+            }
+            _ => None,
+        };
+
+        let recursive = match &self.recursive {
+            Some(expr) => expr.execute(state, object)?.try_boolean()?,
+            None => true,
+        };
+
+        let renamer = Renamer { mapping, pattern };
+
+        let value = self.value.execute(state, object)?;
+
+        util::rename_keys(value, recursive, &|key| renamer.rename(key))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        use value::Kind;
+
+        self.value
+            .type_def(state)
+            .fallible_unless(Kind::Map | Kind::Array)
+            .merge_optional(
+                self.mapping
+                    .as_ref()
+                    .map(|mapping| mapping.type_def(state).fallible_unless(Kind::Map)),
+            )
+            .merge_optional(
+                self.with
+                    .as_ref()
+                    .map(|with| with.type_def(state).fallible_unless(Kind::Bytes)),
+            )
+            .merge_optional(
+                self.recursive
+                    .as_ref()
+                    .map(|recursive| recursive.type_def(state).fallible_unless(Kind::Boolean)),
+            )
+            .with_constraint(Kind::Map | Kind::Array)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_map {
+            expr: |_| RenameKeysFn::new(map!{"foo": 1}.boxed(), map!{"foo": "bar"}.boxed()),
+            def: TypeDef { kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+
+        value_non_collection {
+            expr: |_| RenameKeysFn::new(Literal::from(1).boxed(), map!{"foo": "bar"}.boxed()),
+            def: TypeDef { fallible: true, kind: Kind::Map | Kind::Array, ..Default::default() },
+        }
+    ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        rename_keys => RenameKeys;
+
+        mapping {
+            args: func_args![
+                value: value!({"srcip": "10.0.0.1", "dstport": 443, "proto": "tcp"}),
+                mapping: value!({"srcip": "source.ip", "dstport": "destination.port"}),
+            ],
+            want: Ok(value!({
+                "source.ip": "10.0.0.1",
+                "destination.port": 443,
+                proto: "tcp",
+            })),
+        }
+
+        pattern {
+            args: func_args![
+                value: value!({"http-method": "GET", "http-status-code": 200}),
+                pattern: Regex::new("-").unwrap(),
+                with: "_",
+            ],
+            want: Ok(value!({
+                http_method: "GET",
+                http_status_code: 200,
+            })),
+        }
+
+        pattern_capture_groups {
+            args: func_args![
+                value: value!({"user_name": "ben", "user_id": 1}),
+                pattern: Regex::new("^user_(.*)$").unwrap(),
+                with: "user.$1",
+            ],
+            want: Ok(value!({
+                "user.name": "ben",
+                "user.id": 1,
+            })),
+        }
+
+        mapping_takes_precedence {
+            args: func_args![
+                value: value!({"src-ip": "10.0.0.1", "src-port": 80}),
+                mapping: value!({"src-ip": "source.ip"}),
+                pattern: Regex::new("-").unwrap(),
+                with: "_",
+            ],
+            want: Ok(value!({
+                "source.ip": "10.0.0.1",
+                src_port: 80,
+            })),
+        }
+
+        recursive {
+            args: func_args![
+                value: value!({"A": {"B": [{"C": 1}]}}),
+                pattern: Regex::new("^[A-Z]$").unwrap(),
+                with: "x",
+            ],
+            want: Ok(value!({x: {x: [{x: 1}]}})),
+        }
+
+        non_recursive {
+            args: func_args![
+                value: value!({"A": {"B": 1}}),
+                pattern: Regex::new("^[A-Z]$").unwrap(),
+                with: "x",
+                recursive: false,
+            ],
+            want: Ok(value!({x: {"B": 1}})),
+        }
+
+        array {
+            args: func_args![
+                value: value!([{"a": 1}, {"a": 2}, 3]),
+                mapping: value!({"a": "b"}),
+            ],
+            want: Ok(value!([{b: 1}, {b: 2}, 3])),
+        }
+    ];
+}
@@ -0,0 +1,164 @@
+use crate::util::{self, Case};
+use remap::prelude::*;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RenameKeysCase;
+
+impl Function for RenameKeysCase {
+    fn identifier(&self) -> &'static str {
+        "rename_keys_case"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_) | Value::Array(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "case",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                accepts: |v| matches!(v, Value::Boolean(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let case = arguments.required("case")?.boxed();
+        let recursive = arguments.optional("recursive").map(Expr::boxed);
+
+        Ok(Box::new(RenameKeysCaseFn {
+            value,
+            case,
+            recursive,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RenameKeysCaseFn {
+    value: Box<dyn Expression>,
+    case: Box<dyn Expression>,
+    recursive: Option<Box<dyn Expression>>,
+}
+
+impl Expression for RenameKeysCaseFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let case = self.case.execute(state, object)?.try_bytes_utf8_lossy()?;
+        let case = Case::from_str(&case)?;
+
+        let recursive = match &self.recursive {
+            Some(expr) => expr.execute(state, object)?.try_boolean()?,
+            None => true,
+        };
+
+        let value = self.value.execute(state, object)?;
+
+        util::rename_keys(value, recursive, &|key| Ok(case.convert(&key)))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        use value::Kind;
+
+        self.value
+            .type_def(state)
+            .fallible_unless(Kind::Map | Kind::Array)
+            .merge(self.case.type_def(state).into_fallible(true)) // unknown case
+            .merge_optional(
+                self.recursive
+                    .as_ref()
+                    .map(|recursive| recursive.type_def(state).fallible_unless(Kind::Boolean)),
+            )
+            .with_constraint(Kind::Map | Kind::Array)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![value_map {
+        expr: |_| RenameKeysCaseFn {
+            value: map! {"fooBar": 1}.boxed(),
+            case: Literal::from("snake").boxed(),
+            recursive: None,
+        },
+        def: TypeDef {
+            fallible: true,
+            kind: Kind::Map | Kind::Array,
+            ..Default::default()
+        },
+    }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        rename_keys_case => RenameKeysCase;
+
+        snake {
+            args: func_args![
+                value: value!({"userId": 1, "HTTPStatus": 200, "source-ip": "10.0.0.1"}),
+                case: "snake",
+            ],
+            want: Ok(value!({user_id: 1, http_status: 200, source_ip: "10.0.0.1"})),
+        }
+
+        camel {
+            args: func_args![
+                value: value!({"user_id": 1, "source-ip": "10.0.0.1"}),
+                case: "camel",
+            ],
+            want: Ok(value!({userId: 1, sourceIp: "10.0.0.1"})),
+        }
+
+        kebab {
+            args: func_args![value: value!({"user_id": 1}), case: "kebab"],
+            want: Ok(value!({"user-id": 1})),
+        }
+
+        pascal {
+            args: func_args![value: value!({"user_id": 1}), case: "pascal"],
+            want: Ok(value!({UserId: 1})),
+        }
+
+        recursive {
+            args: func_args![
+                value: value!({"userInfo": {"userId": [{"firstName": "ben"}]}}),
+                case: "snake",
+            ],
+            want: Ok(value!({user_info: {user_id: [{first_name: "ben"}]}})),
+        }
+
+        non_recursive {
+            args: func_args![
+                value: value!({"userInfo": {"userId": 1}}),
+                case: "snake",
+                recursive: false,
+            ],
+            want: Ok(value!({user_info: {"userId": 1}})),
+        }
+
+        array {
+            args: func_args![value: value!([{"userId": 1}, 2]), case: "snake"],
+            want: Ok(value!([{user_id: 1}, 2])),
+        }
+
+        unknown_case {
+            args: func_args![value: value!({"userId": 1}), case: "title"],
+            want: Err("function call error: unknown case: 'title'"),
+        }
+    ];
+}
@@ -1,3 +1,4 @@
+use crate::util::{Pattern, PatternValue};
 use remap::prelude::*;
 
 #[derive(Clone, Copy, Debug)]
@@ -35,7 +36,7 @@ impl Function for Replace {
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
-        let pattern = arguments.required("pattern")?.boxed();
+        let pattern = arguments.required("pattern")?.into();
         let with = arguments.required("with")?.boxed();
         let count = arguments.optional("count").map(Expr::boxed);
 
@@ -51,7 +52,7 @@ impl Function for Replace {
 #[derive(Debug, Clone)]
 struct ReplaceFn {
     value: Box<dyn Expression>,
-    pattern: Box<dyn Expression>,
+    pattern: Pattern,
     with: Box<dyn Expression>,
     count: Option<Box<dyn Expression>>,
 }
@@ -60,7 +61,7 @@ impl ReplaceFn {
     #[cfg(test)]
     fn new(
         value: Box<dyn Expression>,
-        pattern: Box<dyn Expression>,
+        pattern: impl Into<Pattern>,
         with: &str,
         count: Option<i32>,
     ) -> Self {
@@ -69,7 +70,7 @@ impl ReplaceFn {
 
         ReplaceFn {
             value,
-            pattern,
+            pattern: pattern.into(),
             with,
             count,
         }
@@ -89,36 +90,30 @@ impl Expression for ReplaceFn {
             None => -1,
         };
 
-        self.pattern
-            .execute(state, object)
-            .and_then(|pattern| match pattern {
-                Value::Bytes(bytes) => {
-                    let pattern = String::from_utf8_lossy(&bytes);
-                    let replaced = match count {
-                        i if i > 0 => value.replacen(pattern.as_ref(), &with, i as usize),
-                        i if i < 0 => value.replace(pattern.as_ref(), &with),
-                        _ => value.into_owned(),
-                    };
-
-                    Ok(replaced.into())
-                }
-                Value::Regex(regex) => {
-                    let replaced = match count {
-                        i if i > 0 => regex
-                            .replacen(&value, i as usize, with.as_ref())
-                            .as_bytes()
-                            .into(),
-                        i if i < 0 => regex.replace_all(&value, with.as_ref()).as_bytes().into(),
-                        _ => value.into(),
-                    };
-
-                    Ok(replaced)
-                }
-                v => Err(Error::Value(value::Error::Expected(
-                    value::Kind::Bytes | value::Kind::Regex,
-                    v.kind(),
-                ))),
-            })
+        match self.pattern.execute(state, object)? {
+            PatternValue::Bytes(bytes) => {
+                let pattern = String::from_utf8_lossy(&bytes);
+                let replaced = match count {
+                    i if i > 0 => value.replacen(pattern.as_ref(), &with, i as usize),
+                    i if i < 0 => value.replace(pattern.as_ref(), &with),
+                    _ => value.into_owned(),
+                };
+
+                Ok(replaced.into())
+            }
+            PatternValue::Regex(regex) => {
+                let replaced = match count {
+                    i if i > 0 => regex
+                        .replacen(&value, i as usize, with.as_ref())
+                        .as_bytes()
+                        .into(),
+                    i if i < 0 => regex.replace_all(&value, with.as_ref()).as_bytes().into(),
+                    _ => value.into(),
+                };
+
+                Ok(replaced)
+            }
+        }
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
@@ -156,7 +151,7 @@ mod test {
         infallible {
             expr: |_| ReplaceFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 with: Literal::from("foo").boxed(),
                 count: None,
             },
@@ -169,7 +164,7 @@ mod test {
         value_fallible {
             expr: |_| ReplaceFn {
                 value: Literal::from(10).boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 with: Literal::from("foo").boxed(),
                 count: None,
             },
@@ -183,7 +178,7 @@ mod test {
         pattern_expression_infallible {
             expr: |_| ReplaceFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from("foo").boxed(),
+                pattern: Literal::from("foo").boxed().into(),
                 with: Literal::from("foo").boxed(),
                 count: None,
             },
@@ -196,7 +191,7 @@ mod test {
         pattern_expression_fallible {
             expr: |_| ReplaceFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(10).boxed(),
+                pattern: Literal::from(10).boxed().into(),
                 with: Literal::from("foo").boxed(),
                 count: None,
             },
@@ -210,7 +205,7 @@ mod test {
         with_fallible {
             expr: |_| ReplaceFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 with: Literal::from(10).boxed(),
                 count: None,
             },
@@ -224,7 +219,7 @@ mod test {
         count_infallible {
             expr: |_| ReplaceFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 with: Literal::from("foo").boxed(),
                 count: Some(Literal::from(10).boxed()),
             },
@@ -237,7 +232,7 @@ mod test {
         count_fallible {
             expr: |_| ReplaceFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 with: Literal::from("foo").boxed(),
                 count: Some(Literal::from("foo").boxed()),
             },
@@ -249,6 +244,25 @@ mod test {
         }
     ];
 
+    #[test]
+    fn precompile_regex_literal() {
+        let pattern = |value: Value| -> Pattern {
+            expression::Argument::new(
+                Box::new(Literal::from(value).into()),
+                |_| true,
+                "pattern",
+                "replace",
+            )
+            .into()
+        };
+
+        assert!(matches!(
+            pattern(regex::Regex::new("a").unwrap().into()),
+            Pattern::Regex(_)
+        ));
+        assert!(matches!(pattern("a".into()), Pattern::Expression(_)));
+    }
+
     #[test]
     fn check_replace_string() {
         let cases = vec![
@@ -430,3 +444,23 @@ mod test {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use regex::Regex;
+
+    test_function![
+        replace => Replace;
+
+        string {
+            args: func_args![value: "I like apples and bananas", pattern: "a", with: "o"],
+            want: Ok("I like opples ond bononos"),
+        }
+
+        regex {
+            args: func_args![value: "I like apples and bananas", pattern: Regex::new("a").unwrap(), with: "o"],
+            want: Ok("I like opples ond bononos"),
+        }
+    ];
+}
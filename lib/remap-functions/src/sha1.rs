@@ -94,3 +94,17 @@ mod tests {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        sha1 => Sha1;
+
+        literal_value {
+            args: func_args![value: "foo"],
+            want: Ok("0beec7b5ea3f0fdbc95d0dd47f3c5bc275da8a33"),
+        }
+    ];
+}
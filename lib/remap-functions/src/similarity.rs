@@ -0,0 +1,122 @@
+use crate::util;
+use remap::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Similarity;
+
+impl Function for Similarity {
+    fn identifier(&self) -> &'static str {
+        "similarity"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "a",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "b",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let a = arguments.required("a")?.boxed();
+        let b = arguments.required("b")?.boxed();
+
+        Ok(Box::new(SimilarityFn { a, b }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SimilarityFn {
+    a: Box<dyn Expression>,
+    b: Box<dyn Expression>,
+}
+
+impl SimilarityFn {
+    #[cfg(test)]
+    fn new(a: &str, b: &str) -> Self {
+        Self {
+            a: Literal::from(a).boxed(),
+            b: Literal::from(b).boxed(),
+        }
+    }
+}
+
+/// The Levenshtein distance of `a` and `b`, normalized by the length of the
+/// longest one, so `1.0` means equal and `0.0` means nothing in common.
+fn similarity(a: &str, b: &str) -> f64 {
+    let length = a.chars().count().max(b.chars().count());
+    if length == 0 {
+        return 1.0;
+    }
+
+    1.0 - util::levenshtein_distance(a, b) as f64 / length as f64
+}
+
+impl Expression for SimilarityFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let a = self.a.execute(state, object)?;
+        let b = self.b.execute(state, object)?;
+
+        Ok(similarity(&a.try_bytes_utf8_lossy()?, &b.try_bytes_utf8_lossy()?).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.a
+            .type_def(state)
+            .fallible_unless(value::Kind::Bytes)
+            .merge(self.b.type_def(state).fallible_unless(value::Kind::Bytes))
+            .with_constraint(value::Kind::Float)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| SimilarityFn::new("foo", "bar"),
+            def: TypeDef { kind: value::Kind::Float, ..Default::default() },
+        }
+
+        fallible_expression {
+            expr: |_| SimilarityFn {
+                a: Literal::from(10).boxed(),
+                b: Literal::from("foo").boxed(),
+            },
+            def: TypeDef { fallible: true, kind: value::Kind::Float, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn similarity() {
+        let cases = vec![
+            ("", "", 1.0),
+            ("same", "same", 1.0),
+            ("abc", "xyz", 0.0),
+            ("abc", "", 0.0),
+            ("kitten", "sitting", 1.0 - 3.0 / 7.0),
+            ("paypal.com", "paypa1.com", 0.9),
+        ];
+
+        let mut state = state::Program::default();
+        let mut object = Value::Map(Default::default());
+
+        for (a, b, want) in cases {
+            let got = SimilarityFn::new(a, b)
+                .execute(&mut state, &mut object)
+                .unwrap()
+                .try_float()
+                .unwrap();
+
+            assert!((got - want).abs() < f64::EPSILON, "{} -> {}: {}", a, b, got);
+        }
+    }
+}
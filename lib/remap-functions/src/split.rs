@@ -1,3 +1,4 @@
+use crate::util::{Pattern, PatternValue};
 use remap::prelude::*;
 use std::convert::TryFrom;
 
@@ -31,7 +32,7 @@ impl Function for Split {
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
-        let pattern = arguments.required("pattern")?.boxed();
+        let pattern = arguments.required("pattern")?.into();
         let limit = arguments.optional("limit").map(Expr::boxed);
 
         Ok(Box::new(SplitFn {
@@ -45,7 +46,7 @@ impl Function for Split {
 #[derive(Debug, Clone)]
 pub(crate) struct SplitFn {
     value: Box<dyn Expression>,
-    pattern: Box<dyn Expression>,
+    pattern: Pattern,
     limit: Option<Box<dyn Expression>>,
 }
 
@@ -63,26 +64,20 @@ impl Expression for SplitFn {
             .and_then(|i| usize::try_from(i).ok())
             .unwrap_or(usize::MAX);
 
-        self.pattern
-            .execute(state, object)
-            .and_then(|pattern| match pattern {
-                Value::Regex(pattern) => Ok(pattern
-                    .splitn(string.as_ref(), limit as usize)
+        match self.pattern.execute(state, object)? {
+            PatternValue::Regex(pattern) => Ok(pattern
+                .splitn(string.as_ref(), limit as usize)
+                .collect::<Vec<_>>()
+                .into()),
+            PatternValue::Bytes(bytes) => {
+                let pattern = String::from_utf8_lossy(&bytes);
+
+                Ok(string
+                    .splitn(limit, pattern.as_ref())
                     .collect::<Vec<_>>()
-                    .into()),
-                Value::Bytes(bytes) => {
-                    let pattern = String::from_utf8_lossy(&bytes);
-
-                    Ok(string
-                        .splitn(limit, pattern.as_ref())
-                        .collect::<Vec<_>>()
-                        .into())
-                }
-                v => Err(Error::Value(value::Error::Expected(
-                    value::Kind::Bytes | value::Kind::Regex,
-                    v.kind(),
-                ))),
-            })
+                    .into())
+            }
+        }
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
@@ -117,7 +112,7 @@ mod test {
         infallible {
             expr: |_| SplitFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 limit: None,
             },
             def: TypeDef {
@@ -129,7 +124,7 @@ mod test {
         value_fallible {
             expr: |_| SplitFn {
                 value: Literal::from(10).boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 limit: None,
             },
             def: TypeDef {
@@ -142,7 +137,7 @@ mod test {
         pattern_expression_infallible {
             expr: |_| SplitFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from("foo").boxed(),
+                pattern: Literal::from("foo").boxed().into(),
                 limit: None,
             },
             def: TypeDef {
@@ -154,7 +149,7 @@ mod test {
         pattern_expression_fallible {
             expr: |_| SplitFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(10).boxed(),
+                pattern: Literal::from(10).boxed().into(),
                 limit: None,
             },
             def: TypeDef {
@@ -167,7 +162,7 @@ mod test {
         limit_infallible {
             expr: |_| SplitFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 limit: Some(Literal::from(10).boxed()),
             },
             def: TypeDef {
@@ -179,7 +174,7 @@ mod test {
         limit_fallible {
             expr: |_| SplitFn {
                 value: Literal::from("foo").boxed(),
-                pattern: Literal::from(regex::Regex::new("foo").unwrap()).boxed(),
+                pattern: regex::Regex::new("foo").unwrap().into(),
                 limit: Some(Literal::from("foo").boxed()),
             },
             def: TypeDef {
@@ -190,3 +185,23 @@ mod test {
         }
     ];
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+    use regex::Regex;
+
+    test_function![
+        split => Split;
+
+        string {
+            args: func_args![value: "foo,bar,baz", pattern: ","],
+            want: Ok(vec!["foo", "bar", "baz"]),
+        }
+
+        regex {
+            args: func_args![value: "foo, bar,baz", pattern: Regex::new(r",\s*").unwrap()],
+            want: Ok(vec!["foo", "bar", "baz"]),
+        }
+    ];
+}
@@ -98,6 +98,28 @@ mod tests {
                 Ok("foo bar".into()),
                 StripAnsiEscapeCodesFn::new(Box::new(Literal::from("\x1b[=3lfoo bar"))),
             ),
+            (
+                btreemap![],
+                Ok("2021-01-12 INFO ready".into()),
+                StripAnsiEscapeCodesFn::new(Box::new(Literal::from(
+                    "\x1b[2m2021-01-12\x1b[0m \x1b[32m\x1b[1mINFO\x1b[0m ready",
+                ))),
+            ),
+            (
+                btreemap![],
+                Ok(" foo".into()),
+                StripAnsiEscapeCodesFn::new(Box::new(Literal::from("\x1b]0;title\x07 foo"))),
+            ),
+            (
+                btreemap![],
+                Ok("".into()),
+                StripAnsiEscapeCodesFn::new(Box::new(Literal::from("\x1b[0m"))),
+            ),
+            (
+                btreemap! { "foo" => "\x1b[31mfoo\x1b[39m" },
+                Ok("foo".into()),
+                StripAnsiEscapeCodesFn::new(Box::new(Path::from("foo"))),
+            ),
         ];
 
         let mut state = state::Program::default();
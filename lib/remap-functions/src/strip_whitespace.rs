@@ -9,29 +9,40 @@ impl Function for StripWhitespace {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            accepts: |v| matches!(v, Value::Bytes(_)),
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "characters",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
+        ]
     }
 
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required("value")?.boxed();
+        let characters = arguments.optional("characters").map(Expr::boxed);
 
-        Ok(Box::new(StripWhitespaceFn { value }))
+        Ok(Box::new(StripWhitespaceFn { value, characters }))
     }
 }
 
 #[derive(Debug, Clone)]
 struct StripWhitespaceFn {
     value: Box<dyn Expression>,
+    characters: Option<Box<dyn Expression>>,
 }
 
 impl StripWhitespaceFn {
     #[cfg(test)]
-    fn new(value: Box<dyn Expression>) -> Self {
-        Self { value }
+    fn new(value: Box<dyn Expression>, characters: Option<&str>) -> Self {
+        let characters = characters.map(|characters| Literal::from(characters).boxed());
+
+        Self { value, characters }
     }
 }
 
@@ -40,13 +51,29 @@ impl Expression for StripWhitespaceFn {
         let bytes = self.value.execute(state, object)?.try_bytes()?;
         let value = String::from_utf8_lossy(&bytes);
 
-        Ok(value.trim().into())
+        match &self.characters {
+            Some(characters) => {
+                let bytes = characters.execute(state, object)?.try_bytes()?;
+                let characters = String::from_utf8_lossy(&bytes);
+
+                Ok(value
+                    .trim_matches(|c| characters.contains(c))
+                    .to_owned()
+                    .into())
+            }
+            None => Ok(value.trim().into()),
+        }
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
         self.value
             .type_def(state)
             .fallible_unless(value::Kind::Bytes)
+            .merge_optional(self.characters.as_ref().map(|characters| {
+                characters
+                    .type_def(state)
+                    .fallible_unless(value::Kind::Bytes)
+            }))
             .with_constraint(value::Kind::Bytes)
     }
 }
@@ -58,12 +85,25 @@ mod tests {
 
     remap::test_type_def![
         value_string {
-            expr: |_| StripWhitespaceFn { value: Literal::from("foo").boxed() },
+            expr: |_| StripWhitespaceFn::new(Literal::from("foo").boxed(), None),
             def: TypeDef { kind: value::Kind::Bytes, ..Default::default() },
         }
 
         fallible_expression {
-            expr: |_| StripWhitespaceFn { value: Literal::from(10).boxed() },
+            expr: |_| StripWhitespaceFn::new(Literal::from(10).boxed(), None),
+            def: TypeDef { fallible: true, kind: value::Kind::Bytes, ..Default::default() },
+        }
+
+        value_characters {
+            expr: |_| StripWhitespaceFn::new(Literal::from("foo").boxed(), Some("-")),
+            def: TypeDef { kind: value::Kind::Bytes, ..Default::default() },
+        }
+
+        fallible_characters {
+            expr: |_| StripWhitespaceFn {
+                value: Literal::from("foo").boxed(),
+                characters: Some(Literal::from(10).boxed()),
+            },
             def: TypeDef { fallible: true, kind: value::Kind::Bytes, ..Default::default() },
         }
     ];
@@ -74,27 +114,52 @@ mod tests {
             (
                 btreemap! { "foo" => "" },
                 Ok("".into()),
-                StripWhitespaceFn::new(Box::new(Path::from("foo"))),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), None),
             ),
             (
                 btreemap! { "foo" => "     " },
                 Ok("".into()),
-                StripWhitespaceFn::new(Box::new(Path::from("foo"))),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), None),
             ),
             (
                 btreemap! { "foo" => "hi there" },
                 Ok("hi there".into()),
-                StripWhitespaceFn::new(Box::new(Path::from("foo"))),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), None),
             ),
             (
                 btreemap! { "foo" => "           hi there        " },
                 Ok("hi there".into()),
-                StripWhitespaceFn::new(Box::new(Path::from("foo"))),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), None),
             ),
             (
                 btreemap! { "foo" => " \u{3000}\u{205F}\u{202F}\u{A0}\u{9} ❤❤ hi there ❤❤  \u{9}\u{A0}\u{202F}\u{205F}\u{3000} " },
                 Ok("❤❤ hi there ❤❤".into()),
-                StripWhitespaceFn::new(Box::new(Path::from("foo"))),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), None),
+            ),
+            (
+                btreemap! { "foo" => "--==hi there==--" },
+                Ok("hi there".into()),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), Some("=-")),
+            ),
+            (
+                btreemap! { "foo" => " \"hi there\" " },
+                Ok(" \"hi there\" ".into()),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), Some("\"")),
+            ),
+            (
+                btreemap! { "foo" => "❤❤ hi there ❤❤" },
+                Ok(" hi there ".into()),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), Some("❤")),
+            ),
+            (
+                btreemap! { "foo" => "xxxx" },
+                Ok("".into()),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), Some("x")),
+            ),
+            (
+                btreemap! { "foo" => " hi there " },
+                Ok(" hi there ".into()),
+                StripWhitespaceFn::new(Box::new(Path::from("foo")), Some("")),
             ),
         ];
 
@@ -0,0 +1,168 @@
+use remap::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TagTypesExternally;
+
+impl Function for TagTypesExternally {
+    fn identifier(&self) -> &'static str {
+        "tag_types_externally"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |_| true,
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+
+        Ok(Box::new(TagTypesExternallyFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TagTypesExternallyFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for TagTypesExternallyFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let value = self.value.execute(state, object)?;
+
+        Ok(tag_type_externally(value))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        use value::Kind;
+
+        let kind = self.value.type_def(state).kind;
+        let mut tagged = Kind::Map;
+        if kind.contains_array() {
+            tagged |= Kind::Array;
+        }
+        if kind.contains_null() {
+            tagged |= Kind::Null;
+        }
+
+        self.value
+            .type_def(state)
+            .with_constraint(tagged)
+            .with_inner_type(None)
+    }
+}
+
+/// Wraps every scalar of the value in a single-key map, keyed by the name of
+/// its type. Maps and arrays are walked recursively, `null` is kept as-is.
+fn tag_type_externally(value: Value) -> Value {
+    match value {
+        Value::Map(map) => map
+            .into_iter()
+            .map(|(key, value)| (key, tag_type_externally(value)))
+            .collect::<BTreeMap<_, _>>()
+            .into(),
+        Value::Array(array) => array
+            .into_iter()
+            .map(tag_type_externally)
+            .collect::<Vec<_>>()
+            .into(),
+        Value::Null => Value::Null,
+        value => {
+            let mut tagged = BTreeMap::new();
+            tagged.insert(value.kind().as_str().to_owned(), value);
+            tagged.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use shared::btreemap;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_string {
+            expr: |_| TagTypesExternallyFn {
+                value: Literal::from("foo").boxed()
+            },
+            def: TypeDef { kind: Kind::Map, ..Default::default() },
+        }
+
+        value_array {
+            expr: |_| TagTypesExternallyFn {
+                value: Array::from(vec!["foo"]).boxed()
+            },
+            def: TypeDef { kind: Kind::Array, ..Default::default() },
+        }
+
+        value_null {
+            expr: |_| TagTypesExternallyFn {
+                value: Literal::from(()).boxed()
+            },
+            def: TypeDef { kind: Kind::Map | Kind::Null, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn tag_types_externally() {
+        let timestamp = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+
+        let cases = vec![
+            (
+                btreemap! {},
+                Ok(value!({ string: "foo" })),
+                TagTypesExternallyFn {
+                    value: Literal::from("foo").boxed(),
+                },
+            ),
+            (
+                btreemap! {},
+                Ok(Value::Null),
+                TagTypesExternallyFn {
+                    value: Literal::from(()).boxed(),
+                },
+            ),
+            (
+                btreemap! {
+                    "event" => value!({
+                        message: "foo",
+                        status: 200,
+                        duration: 1.5,
+                        success: true,
+                        timestamp: timestamp,
+                        tags: ["bar", null],
+                        nested: { count: 1 },
+                    }),
+                },
+                Ok(value!({
+                    message: { string: "foo" },
+                    status: { integer: 200 },
+                    duration: { float: 1.5 },
+                    success: { boolean: true },
+                    timestamp: { timestamp: timestamp },
+                    tags: [{ string: "bar" }, null],
+                    nested: { count: { integer: 1 } },
+                })),
+                TagTypesExternallyFn {
+                    value: Box::new(Path::from("event")),
+                },
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (object, exp, func) in cases {
+            let mut object: Value = object.into();
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
@@ -158,3 +158,17 @@ mod tests {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        to_int => ToInt;
+
+        string {
+            args: func_args![value: "42"],
+            want: Ok(42),
+        }
+    ];
+}
@@ -89,6 +89,11 @@ mod tests {
             ..Default::default()
         },
     }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         to_syslog_facility => ToSyslogFacility;
@@ -78,6 +78,11 @@ mod tests {
             ..Default::default()
         },
     }];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
 
     test_function![
         to_level => ToSyslogSeverity;
@@ -55,7 +55,7 @@ impl Expression for ToTimestampFn {
             Timestamp(_) => Ok(value),
             Integer(v) => Ok(Timestamp(Utc.timestamp(v, 0))),
             Float(v) => Ok(Timestamp(Utc.timestamp(v.round() as i64, 0))),
-            Bytes(v) => Conversion::Timestamp
+            Bytes(v) => Conversion::Timestamp(state.timezone())
                 .convert(v)
                 .map_err(|e| e.to_string().into()),
             Boolean(_) | Array(_) | Map(_) | Regex(_) | Null => {
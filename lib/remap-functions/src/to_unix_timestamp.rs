@@ -1,3 +1,4 @@
+use crate::util::Unit;
 use remap::prelude::*;
 use std::str::FromStr;
 
@@ -36,55 +37,6 @@ impl Function for ToUnixTimestamp {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Unit {
-    Seconds,
-    Milliseconds,
-    Nanoseconds,
-}
-
-impl Unit {
-    fn all_str() -> Vec<&'static str> {
-        use Unit::*;
-
-        vec![Seconds, Milliseconds, Nanoseconds]
-            .into_iter()
-            .map(|u| u.as_str())
-            .collect::<Vec<_>>()
-    }
-
-    const fn as_str(self) -> &'static str {
-        use Unit::*;
-
-        match self {
-            Seconds => "seconds",
-            Milliseconds => "milliseconds",
-            Nanoseconds => "nanoseconds",
-        }
-    }
-}
-
-impl Default for Unit {
-    fn default() -> Self {
-        Unit::Seconds
-    }
-}
-
-impl FromStr for Unit {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        use Unit::*;
-
-        match s {
-            "seconds" => Ok(Seconds),
-            "milliseconds" => Ok(Milliseconds),
-            "nanoseconds" => Ok(Nanoseconds),
-            _ => Err("unit not recognized"),
-        }
-    }
-}
-
 #[derive(Clone, Debug)]
 struct ToUnixTimestampFn {
     value: Box<dyn Expression>,
@@ -0,0 +1,222 @@
+use remap::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Unflatten;
+
+impl Function for Unflatten {
+    fn identifier(&self) -> &'static str {
+        "unflatten"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::Map(_)),
+                required: true,
+            },
+            Parameter {
+                keyword: "separator",
+                accepts: |v| matches!(v, Value::Bytes(_)),
+                required: false,
+            },
+            Parameter {
+                keyword: "max_depth",
+                accepts: |v| matches!(v, Value::Integer(_)),
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required("value")?.boxed();
+        let separator = arguments.optional("separator").map(Expr::boxed);
+        let max_depth = arguments.optional("max_depth").map(Expr::boxed);
+
+        Ok(Box::new(UnflattenFn {
+            value,
+            separator,
+            max_depth,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UnflattenFn {
+    value: Box<dyn Expression>,
+    separator: Option<Box<dyn Expression>>,
+    max_depth: Option<Box<dyn Expression>>,
+}
+
+impl Expression for UnflattenFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let separator = match &self.separator {
+            Some(expr) => expr
+                .execute(state, object)?
+                .try_bytes_utf8_lossy()?
+                .into_owned(),
+            None => ".".to_owned(),
+        };
+
+        // A negative depth doesn't unflatten anything, same as a depth of zero.
+        let max_depth = match &self.max_depth {
+            Some(expr) => Some(expr.execute(state, object)?.try_integer()?.max(0) as usize),
+            None => None,
+        };
+
+        let map = self.value.execute(state, object)?.try_map()?;
+
+        Ok(unflatten(map, &separator, max_depth).into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let separator_def = self.separator.as_ref().map(|separator| {
+            separator
+                .type_def(state)
+                .fallible_unless(value::Kind::Bytes)
+        });
+
+        let max_depth_def = self.max_depth.as_ref().map(|max_depth| {
+            max_depth
+                .type_def(state)
+                .fallible_unless(value::Kind::Integer)
+        });
+
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Map)
+            .merge_optional(separator_def)
+            .merge_optional(max_depth_def)
+            .with_constraint(value::Kind::Map)
+            .with_inner_type(None)
+    }
+}
+
+/// Split the keys of the map on the separator, and nest the values under each
+/// part of the key, up to `max_depth` levels deep.
+///
+/// Keys are handled in order, so the value of a key that is a prefix of
+/// another key, like `a` and `a.b`, is replaced by a map unless it already is
+/// one.
+fn unflatten(
+    map: BTreeMap<String, Value>,
+    separator: &str,
+    max_depth: Option<usize>,
+) -> BTreeMap<String, Value> {
+    let mut unflattened = BTreeMap::new();
+
+    for (key, value) in map {
+        let mut segments: Vec<&str> = match (separator, max_depth) {
+            ("", _) => vec![key.as_str()],
+            (_, Some(depth)) => key.splitn(depth + 1, separator).collect(),
+            (_, None) => key.split(separator).collect(),
+        };
+
+        let last = segments.pop().expect("at least one segment");
+        let mut target = &mut unflattened;
+
+        for segment in segments {
+            let entry = target
+                .entry(segment.to_owned())
+                .or_insert_with(|| Value::Map(BTreeMap::new()));
+
+            if !matches!(entry, Value::Map(_)) {
+                *entry = Value::Map(BTreeMap::new());
+            }
+
+            target = match entry {
+                Value::Map(map) => map,
+                _ => unreachable!("entry is a map"),
+            };
+        }
+
+        target.insert(last.to_owned(), value);
+    }
+
+    unflattened
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Kind;
+
+    remap::test_type_def![
+        value_map {
+            expr: |_| UnflattenFn {
+                value: map!["a.b": 1].boxed(),
+                separator: None,
+                max_depth: None,
+            },
+            def: TypeDef { kind: Kind::Map, ..Default::default() },
+        }
+
+        value_non_map {
+            expr: |_| UnflattenFn {
+                value: Literal::from(1).boxed(),
+                separator: None,
+                max_depth: None,
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map, ..Default::default() },
+        }
+
+        separator_non_bytes {
+            expr: |_| UnflattenFn {
+                value: map!["a.b": 1].boxed(),
+                separator: Some(Literal::from(1).boxed()),
+                max_depth: None,
+            },
+            def: TypeDef { fallible: true, kind: Kind::Map, ..Default::default() },
+        }
+    ];
+}
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        unflatten => Unflatten;
+
+        flat_map {
+            args: func_args![value: value!({foo: 1, bar: "baz"})],
+            want: Ok(value!({foo: 1, bar: "baz"})),
+        }
+
+        dotted_keys {
+            args: func_args![value: value!({"parent.child1": 1, "parent.child2.grandchild": 2, key: "val"})],
+            want: Ok(value!({parent: {child1: 1, child2: {grandchild: 2}}, key: "val"})),
+        }
+
+        separator {
+            args: func_args![value: value!({parent_child: 1, "parent.other": 2}), separator: "_"],
+            want: Ok(value!({parent: {child: 1}, "parent.other": 2})),
+        }
+
+        empty_separator {
+            args: func_args![value: value!({"parent.child": 1}), separator: ""],
+            want: Ok(value!({"parent.child": 1})),
+        }
+
+        max_depth {
+            args: func_args![value: value!({"a.b.c.d": 1}), max_depth: 2],
+            want: Ok(value!({a: {b: {"c.d": 1}}})),
+        }
+
+        max_depth_zero {
+            args: func_args![value: value!({"a.b": 1}), max_depth: 0],
+            want: Ok(value!({"a.b": 1})),
+        }
+
+        prefix_key {
+            args: func_args![value: value!({a: 1, "a.b": 2})],
+            want: Ok(value!({a: {b: 2}})),
+        }
+
+        existing_map {
+            args: func_args![value: value!({a: {c: 3}, "a.b": 2})],
+            want: Ok(value!({a: {b: 2, c: 3}})),
+        }
+    ];
+}
@@ -0,0 +1,141 @@
+use remap::prelude::*;
+use std::collections::BTreeMap;
+use value::Kind;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Unnest;
+
+impl Function for Unnest {
+    fn identifier(&self) -> &'static str {
+        "unnest"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "path",
+            accepts: |_| true,
+            required: true,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let path = arguments.required_path("path")?;
+
+        Ok(Box::new(UnnestFn { path }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UnnestFn {
+    path: Path,
+}
+
+impl Expression for UnnestFn {
+    fn execute(&self, _: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let path = self.path.as_ref();
+
+        let root = object
+            .get(&remap::Path::root())?
+            .unwrap_or_else(|| Value::Map(BTreeMap::new()));
+
+        let values = object
+            .get(path)?
+            .ok_or_else(|| format!("path {} doesn't exist", path))?
+            .try_array()?;
+
+        // Every element gets a copy of the whole object, with the array at the
+        // path replaced by that element.
+        let objects = values
+            .into_iter()
+            .map(|value| {
+                let mut object = root.clone();
+                object.insert_by_path(path, value);
+                object
+            })
+            .collect::<Vec<_>>();
+
+        Ok(objects.into())
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.path
+            .type_def(state)
+            .fallible_unless(Kind::Array)
+            .with_constraint(Kind::Array)
+            .with_inner_type(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(path: &str, object: &mut Value) -> Result<Value> {
+        let path = remap::Path::from_alternative_string(path.to_owned()).unwrap();
+        let function = UnnestFn { path: path.into() };
+
+        function.execute(&mut state::Program::default(), object)
+    }
+
+    remap::test_type_def![
+        unknown_path {
+            expr: |_| UnnestFn { path: Path::from("events") },
+            def: TypeDef { fallible: true, kind: Kind::Array, ..Default::default() },
+        }
+
+        array_path {
+            expr: |state: &mut state::Compiler| {
+                state.path_query_types_mut().insert(
+                    Path::from("events").into(),
+                    TypeDef { kind: Kind::Array, ..Default::default() },
+                );
+                UnnestFn { path: Path::from("events") }
+            },
+            def: TypeDef { kind: Kind::Array, ..Default::default() },
+        }
+    ];
+
+    #[test]
+    fn array() {
+        let mut object = value!({host: "a", events: [{id: 1}, {id: 2}]});
+
+        assert_eq!(
+            run("events", &mut object),
+            Ok(value!([
+                {host: "a", events: {id: 1}},
+                {host: "a", events: {id: 2}}
+            ]))
+        );
+    }
+
+    #[test]
+    fn nested_path() {
+        let mut object = value!({request: {ids: [1, 2]}});
+
+        assert_eq!(
+            run("request.ids", &mut object),
+            Ok(value!([{request: {ids: 1}}, {request: {ids: 2}}]))
+        );
+    }
+
+    #[test]
+    fn empty_array() {
+        let mut object = value!({events: []});
+
+        assert_eq!(run("events", &mut object), Ok(value!([])));
+    }
+
+    #[test]
+    fn not_an_array() {
+        let mut object = value!({events: "foo"});
+
+        assert!(run("events", &mut object).is_err());
+    }
+
+    #[test]
+    fn missing_path() {
+        let mut object = value!({});
+
+        assert!(run("events", &mut object).is_err());
+    }
+}
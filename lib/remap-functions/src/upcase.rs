@@ -95,3 +95,17 @@ mod tests {
         }
     }
 }
+
+#[cfg(any(test, feature = "benches"))]
+pub(crate) mod cases {
+    use super::*;
+
+    test_function![
+        upcase => Upcase;
+
+        literal_value {
+            args: func_args![value: "foo"],
+            want: Ok("FOO"),
+        }
+    ];
+}
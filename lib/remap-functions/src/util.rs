@@ -26,6 +26,155 @@ where
     fun(num * multiplier as f64) / multiplier
 }
 
+/// The number of single-character insertions, deletions and substitutions
+/// needed to turn `a` into `b`.
+#[cfg(any(feature = "levenshtein", feature = "similarity"))]
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+
+    // Only the previous row of the distance matrix is needed to compute the
+    // current one.
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + if a == *b { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Converts a scalar value to its string representation, or `None` for
+/// non-scalar values.
+#[cfg(any(feature = "normalize_ecs", feature = "normalize_ocsf"))]
+pub(crate) fn scalar_to_string(value: Value) -> Option<String> {
+    match value {
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(&bytes).into_owned()),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Insert the value at the `.` separated path, creating (or replacing) any
+/// intermediate maps.
+#[cfg(any(feature = "normalize_ecs", feature = "normalize_ocsf"))]
+pub(crate) fn insert_dotted(map: &mut BTreeMap<String, Value>, path: &str, value: Value) {
+    match path.find('.') {
+        // `@timestamp` and the like are top-level fields.
+        None => {
+            map.insert(path.to_owned(), value);
+        }
+        Some(index) => {
+            let child = map
+                .entry(path[..index].to_owned())
+                .or_insert_with(|| Value::Map(BTreeMap::new()));
+
+            if !child.is_map() {
+                *child = Value::Map(BTreeMap::new());
+            }
+
+            if let Value::Map(child) = child {
+                insert_dotted(child, &path[index + 1..], value)
+            }
+        }
+    }
+}
+
+/// Renames the keys of the maps in `value`, which is either a map or an array
+/// of maps, with `rename`. The keys of nested collections are renamed too if
+/// `recursive` is set.
+#[cfg(any(feature = "rename_keys", feature = "rename_keys_case"))]
+pub(crate) fn rename_keys<F>(value: Value, recursive: bool, rename: &F) -> remap::Result<Value>
+where
+    F: Fn(String) -> remap::Result<String>,
+{
+    // Rename the keys of nested collections if we are recursing - otherwise,
+    // just return the value untouched.
+    let recurse = |value| {
+        if recursive {
+            rename_keys(value, recursive, rename)
+        } else {
+            Ok(value)
+        }
+    };
+
+    match value {
+        Value::Map(map) => map
+            .into_iter()
+            .map(|(key, value)| Ok((rename(key)?, recurse(value)?)))
+            .collect::<remap::Result<BTreeMap<_, _>>>()
+            .map(Into::into),
+        Value::Array(array) => array
+            .into_iter()
+            .map(recurse)
+            .collect::<remap::Result<Vec<_>>>()
+            .map(Into::into),
+        value => Ok(value),
+    }
+}
+
+/// The naming conventions strings can be converted between.
+#[cfg(any(
+    feature = "camelcase",
+    feature = "kebabcase",
+    feature = "pascalcase",
+    feature = "rename_keys_case",
+    feature = "snakecase"
+))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Case {
+    Camel,
+    Kebab,
+    Pascal,
+    Snake,
+}
+
+#[cfg(any(
+    feature = "camelcase",
+    feature = "kebabcase",
+    feature = "pascalcase",
+    feature = "rename_keys_case",
+    feature = "snakecase"
+))]
+impl Case {
+    /// Converts the string to the case, splitting it into words on
+    /// non-alphanumeric characters and lowercase to uppercase transitions.
+    pub(crate) fn convert(self, s: &str) -> String {
+        use heck::{CamelCase, KebabCase, MixedCase, SnakeCase};
+
+        match self {
+            // heck calls lower camel case "mixed case", and upper camel case
+            // "camel case".
+            Case::Camel => s.to_mixed_case(),
+            Case::Kebab => s.to_kebab_case(),
+            Case::Pascal => s.to_camel_case(),
+            Case::Snake => s.to_snake_case(),
+        }
+    }
+}
+
+#[cfg(feature = "rename_keys_case")]
+impl FromStr for Case {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "camel" => Ok(Case::Camel),
+            "kebab" => Ok(Case::Kebab),
+            "pascal" => Ok(Case::Pascal),
+            "snake" => Ok(Case::Snake),
+            _ => Err(format!("unknown case: '{}'", s)),
+        }
+    }
+}
+
 /// Takes a set of captures that have resulted from matching a regular expression
 /// against some text and fills a BTreeMap with the result.
 ///
@@ -130,3 +279,218 @@ impl FromStr for Base64Charset {
         }
     }
 }
+
+/// The closure accepted by functions that run a closure for each element of a
+/// map or array, either as `|value|` or as `|key_or_index, value|`.
+#[cfg(any(
+    feature = "all",
+    feature = "any",
+    feature = "filter",
+    feature = "for_each",
+    feature = "map_values"
+))]
+pub(crate) const ELEMENT_CLOSURE: remap::ClosureParameter = remap::ClosureParameter {
+    required: true,
+    min_variables: 1,
+    max_variables: 2,
+};
+
+/// Run the closure for a single element of a map or array.
+///
+/// The key (or index) of the element is only bound if the closure asks for
+/// it.
+#[cfg(any(
+    feature = "all",
+    feature = "any",
+    feature = "filter",
+    feature = "for_each",
+    feature = "map_values"
+))]
+pub(crate) fn run_element_closure(
+    closure: &remap::expression::Closure,
+    key: Value,
+    value: Value,
+    state: &mut remap::state::Program,
+    object: &mut dyn remap::Object,
+) -> remap::Result<Value> {
+    let values = match closure.variables().len() {
+        1 => vec![value],
+        _ => vec![key, value],
+    };
+
+    closure.run(values, state, object)
+}
+
+/// Split a map or array into its elements, keyed by their key or index.
+#[cfg(any(feature = "all", feature = "any", feature = "for_each"))]
+pub(crate) fn collection_elements(value: Value) -> remap::Result<Vec<(Value, Value)>> {
+    match value {
+        Value::Map(map) => Ok(map.into_iter().map(|(k, v)| (k.into(), v)).collect()),
+        Value::Array(array) => Ok(array
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| ((i as i64).into(), v))
+            .collect()),
+        v => Err(remap::value::Error::Expected(Kind::Map | Kind::Array, v.kind()).into()),
+    }
+}
+
+/// Resolves the optional `timezone` argument of a function.
+#[cfg(any(feature = "format_timestamp", feature = "parse_timestamp"))]
+pub(crate) fn timezone(
+    expr: Option<&dyn remap::Expression>,
+    state: &mut remap::state::Program,
+    object: &mut dyn remap::Object,
+) -> remap::Result<Option<shared::datetime::TimeZone>> {
+    let expr = match expr {
+        Some(expr) => expr,
+        None => return Ok(None),
+    };
+
+    let name = expr
+        .execute(state, object)?
+        .try_bytes_utf8_lossy()?
+        .into_owned();
+
+    shared::datetime::TimeZone::parse(&name)
+        .map(Some)
+        .ok_or_else(|| format!("unknown timezone: {}", name).into())
+}
+
+/// The unit of a Unix timestamp.
+#[cfg(any(feature = "from_unix_timestamp", feature = "to_unix_timestamp"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Seconds,
+    Milliseconds,
+    Nanoseconds,
+}
+
+#[cfg(any(feature = "from_unix_timestamp", feature = "to_unix_timestamp"))]
+impl Unit {
+    pub(crate) fn all_str() -> Vec<&'static str> {
+        use Unit::*;
+
+        vec![Seconds, Milliseconds, Nanoseconds]
+            .into_iter()
+            .map(|u| u.as_str())
+            .collect::<Vec<_>>()
+    }
+
+    pub(crate) const fn as_str(self) -> &'static str {
+        use Unit::*;
+
+        match self {
+            Seconds => "seconds",
+            Milliseconds => "milliseconds",
+            Nanoseconds => "nanoseconds",
+        }
+    }
+}
+
+#[cfg(any(feature = "from_unix_timestamp", feature = "to_unix_timestamp"))]
+impl Default for Unit {
+    fn default() -> Self {
+        Unit::Seconds
+    }
+}
+
+#[cfg(any(feature = "from_unix_timestamp", feature = "to_unix_timestamp"))]
+impl FromStr for Unit {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use Unit::*;
+
+        match s {
+            "seconds" => Ok(Seconds),
+            "milliseconds" => Ok(Milliseconds),
+            "nanoseconds" => Ok(Nanoseconds),
+            _ => Err("unit not recognized"),
+        }
+    }
+}
+
+/// The pattern argument of a function, matched either as a regular expression,
+/// or as a literal string.
+///
+/// Regular expression literals are taken out of their expression when the
+/// program is compiled, so they aren't cloned, along with their match caches,
+/// for every event.
+#[cfg(any(feature = "redact", feature = "replace", feature = "split"))]
+#[derive(Debug, Clone)]
+pub(crate) enum Pattern {
+    Regex(regex::Regex),
+    Expression(Box<dyn remap::Expression>),
+}
+
+#[cfg(any(feature = "redact", feature = "replace", feature = "split"))]
+pub(crate) enum PatternValue<'a> {
+    Regex(std::borrow::Cow<'a, regex::Regex>),
+    Bytes(bytes::Bytes),
+}
+
+#[cfg(any(feature = "redact", feature = "replace", feature = "split"))]
+impl Pattern {
+    pub(crate) fn execute(
+        &self,
+        state: &mut remap::state::Program,
+        object: &mut dyn remap::Object,
+    ) -> remap::Result<PatternValue<'_>> {
+        use std::borrow::Cow;
+
+        match self {
+            Pattern::Regex(regex) => Ok(PatternValue::Regex(Cow::Borrowed(regex))),
+            Pattern::Expression(expr) => match expr.execute(state, object)? {
+                Value::Regex(regex) => Ok(PatternValue::Regex(Cow::Owned(regex))),
+                Value::Bytes(bytes) => Ok(PatternValue::Bytes(bytes)),
+                v => Err(remap::value::Error::Expected(Kind::Bytes | Kind::Regex, v.kind()).into()),
+            },
+        }
+    }
+
+    pub(crate) fn type_def(&self, state: &remap::state::Compiler) -> remap::TypeDef {
+        match self {
+            Pattern::Regex(_) => remap::TypeDef {
+                kind: Kind::Regex,
+                ..Default::default()
+            },
+            Pattern::Expression(expr) => expr.type_def(state),
+        }
+    }
+}
+
+#[cfg(any(feature = "redact", feature = "replace", feature = "split"))]
+impl From<remap::Expr> for Pattern {
+    fn from(expr: remap::Expr) -> Self {
+        fn literal_regex(expr: &remap::Expr) -> Option<&regex::Regex> {
+            match expr {
+                remap::Expr::Argument(argument) => literal_regex(argument.as_expr()),
+                remap::Expr::Literal(literal) => match literal.as_value() {
+                    Value::Regex(regex) => Some(regex),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+
+        match literal_regex(&expr) {
+            Some(regex) => Pattern::Regex(regex.clone()),
+            None => Pattern::Expression(expr.boxed()),
+        }
+    }
+}
+
+#[cfg(any(feature = "redact", feature = "replace", feature = "split"))]
+impl From<regex::Regex> for Pattern {
+    fn from(regex: regex::Regex) -> Self {
+        Pattern::Regex(regex)
+    }
+}
+
+#[cfg(any(feature = "redact", feature = "replace", feature = "split"))]
+impl From<Box<dyn remap::Expression>> for Pattern {
+    fn from(expr: Box<dyn remap::Expression>) -> Self {
+        Pattern::Expression(expr)
+    }
+}
@@ -9,6 +9,10 @@ impl Function for UuidV4 {
         "uuid_v4"
     }
 
+    fn is_pure(&self) -> bool {
+        false
+    }
+
     fn compile(&self, _: ArgumentList) -> Result<Box<dyn Expression>> {
         Ok(Box::new(UuidV4Fn))
     }
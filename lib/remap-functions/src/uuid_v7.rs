@@ -0,0 +1,104 @@
+use bytes::Bytes;
+use remap::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, Debug)]
+pub struct UuidV7;
+
+impl Function for UuidV7 {
+    fn identifier(&self) -> &'static str {
+        "uuid_v7"
+    }
+
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    fn compile(&self, _: ArgumentList) -> Result<Box<dyn Expression>> {
+        Ok(Box::new(UuidV7Fn))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UuidV7Fn;
+
+/// Build a version 7 UUID: the first 48 bits are the Unix timestamp in
+/// milliseconds, so the UUIDs sort in the order they were generated, and the
+/// remaining bits are random apart from the version and variant.
+fn uuid_v7(millis: u64, random: [u8; 16]) -> uuid::Uuid {
+    let mut bytes = random;
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x70;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    uuid::Uuid::from_bytes(bytes)
+}
+
+impl Expression for UuidV7Fn {
+    fn execute(&self, _: &mut state::Program, _: &mut dyn Object) -> Result<Value> {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut buf = [0; 36];
+        let uuid = uuid_v7(millis, rand::random())
+            .to_hyphenated()
+            .encode_lower(&mut buf);
+
+        Ok(Bytes::copy_from_slice(uuid.as_bytes()).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef {
+            kind: value::Kind::Bytes,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::btreemap;
+    use std::convert::TryFrom;
+
+    remap::test_type_def![static_def {
+        expr: |_| UuidV7Fn,
+        def: TypeDef {
+            kind: value::Kind::Bytes,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn uuid_v7() {
+        let mut state = state::Program::default();
+        let mut object: Value = btreemap! {}.into();
+        let value = UuidV7Fn.execute(&mut state, &mut object).unwrap();
+
+        assert!(matches!(&value, Value::Bytes(_)));
+
+        let uuid = uuid::Uuid::parse_str(&String::try_from(value).unwrap()).expect("valid UUID");
+        assert_eq!(uuid.get_version_num(), 7);
+        assert_eq!(uuid.get_variant(), Some(uuid::Variant::RFC4122));
+    }
+
+    #[test]
+    fn layout() {
+        let uuid = super::uuid_v7(0x0123_4567_89ab, [0xff; 16]);
+
+        assert_eq!(
+            uuid.to_hyphenated().to_string(),
+            "01234567-89ab-7fff-bfff-ffffffffffff"
+        );
+    }
+
+    #[test]
+    fn time_ordered() {
+        let earlier = super::uuid_v7(1_000, [0xff; 16]);
+        let later = super::uuid_v7(1_001, [0x00; 16]);
+
+        assert!(earlier.to_hyphenated().to_string() < later.to_hyphenated().to_string());
+    }
+}
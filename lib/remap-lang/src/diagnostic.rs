@@ -63,6 +63,7 @@ impl<'a> fmt::Display for Formatter<'a> {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Diagnostic {
     severity: Severity,
+    code: Option<usize>,
     message: String,
     labels: Vec<Label>,
     notes: Vec<Note>,
@@ -73,6 +74,10 @@ impl Diagnostic {
         Self::new(Severity::Error, message, vec![], vec![])
     }
 
+    pub fn warning(message: impl ToString) -> Self {
+        Self::new(Severity::Warning, message, vec![], vec![])
+    }
+
     pub fn bug(message: impl ToString) -> Self {
         Self::new(Severity::Bug, message, vec![], vec![])
     }
@@ -85,12 +90,20 @@ impl Diagnostic {
     ) -> Self {
         Self {
             severity,
+            code: None,
             message: message.to_string(),
             labels,
             notes,
         }
     }
 
+    /// Sets the error code of the diagnostic, documented at
+    /// https://vector.dev/docs/reference/vrl/errors/.
+    pub fn with_code(mut self, code: usize) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     pub fn with_primary(self, message: impl ToString, span: impl Into<Span>) -> Self {
         self.with_label(Label::primary(message, span.into()))
     }
@@ -113,6 +126,10 @@ impl Diagnostic {
         self.severity
     }
 
+    pub fn code(&self) -> Option<usize> {
+        self.code
+    }
+
     pub fn message(&self) -> &str {
         &self.message
     }
@@ -161,11 +178,14 @@ impl Diagnostic {
 impl Into<diagnostic::Diagnostic<()>> for Diagnostic {
     fn into(self) -> diagnostic::Diagnostic<()> {
         let mut notes = self.notes.to_vec();
+        if let Some(code) = self.code {
+            notes.push(Note::SeeCodeDocs(code));
+        }
         notes.push(Note::SeeLangDocs);
 
         diagnostic::Diagnostic {
             severity: self.severity.into(),
-            code: None,
+            code: self.code.map(|code| format!("E{}", code)),
             message: self.message.to_string(),
             labels: self.labels.to_vec().into_iter().map(Into::into).collect(),
             notes: notes.iter().map(ToString::to_string).collect(),
@@ -384,6 +404,7 @@ pub enum Note {
     },
     SeeFuncDocs(&'static str),
     SeeErrDocs,
+    SeeCodeDocs(usize),
 
     #[doc(hidden)]
     SeeLangDocs,
@@ -414,6 +435,11 @@ impl fmt::Display for Note {
             SeeErrDocs => f.write_str(
                 "see error handling documentation at: https://vector.dev/docs/reference/vrl/",
             ),
+            SeeCodeDocs(code) => write!(
+                f,
+                "learn more about error code {} at: https://vector.dev/docs/reference/vrl/errors/#{}",
+                code, code
+            ),
             SeeLangDocs => {
                 f.write_str("see language documentation at: https://vector.dev/docs/reference/vrl/")
             }
@@ -18,6 +18,9 @@ pub enum Error {
 
     #[error("assertion failed: {0}")]
     Assert(String),
+
+    #[error("aborted")]
+    Abort,
 }
 
 impl From<String> for Error {
@@ -59,6 +62,7 @@ impl fmt::Display for Rule {
         }
 
         rules_str![
+            abort,
             addition,
             argument,
             arguments,
@@ -70,6 +74,8 @@ impl fmt::Display for Rule {
             boolean_expr,
             call,
             char,
+            closure,
+            closure_variables: "closure variables",
             comparison,
             EOE: "",
             EOI: "",
@@ -2,11 +2,13 @@ use crate::{state, Object, Result, TypeDef, Value};
 use std::convert::TryFrom;
 use std::fmt;
 
+mod abort;
 mod argument;
 mod arithmetic;
 mod array;
 pub(crate) mod assignment;
 mod block;
+mod closure;
 pub(crate) mod function;
 pub(crate) mod if_statement;
 mod literal;
@@ -16,11 +18,13 @@ mod not;
 pub(crate) mod path;
 mod variable;
 
+pub use abort::Abort;
 pub use argument::Argument;
 pub use arithmetic::Arithmetic;
 pub use array::Array;
 pub use assignment::{Assignment, Target};
 pub use block::Block;
+pub use closure::Closure;
 pub use function::Function;
 pub use if_statement::IfStatement;
 pub use literal::Literal;
@@ -145,6 +149,7 @@ macro_rules! expression_dispatch {
 }
 
 expression_dispatch![
+    Abort,
     Argument,
     Arithmetic,
     Array,
@@ -160,6 +165,20 @@ expression_dispatch![
     Variable,
 ];
 
+impl Expr {
+    /// Whether the expression resolves to a value known at compile-time, which
+    /// is the case for literals, and arrays and maps of them.
+    pub fn is_constant(&self) -> bool {
+        match self {
+            Expr::Literal(_) => true,
+            Expr::Argument(argument) => argument.as_expr().is_constant(),
+            Expr::Array(array) => array.expressions().iter().all(Expr::is_constant),
+            Expr::Map(map) => map.expressions().values().all(Expr::is_constant),
+            _ => false,
+        }
+    }
+}
+
 impl<T: Into<Value>> From<T> for Expr {
     fn from(value: T) -> Self {
         let value = value.into();
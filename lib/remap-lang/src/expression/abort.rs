@@ -0,0 +1,43 @@
+use crate::{state, value, Error, Expression, Object, Result, TypeDef, Value};
+
+/// Stops the program, without handling the event any further.
+///
+/// Unlike other errors, an abort can't be handled by the program itself, it
+/// always terminates the program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Abort;
+
+impl Expression for Abort {
+    fn execute(&self, _: &mut state::Program, _: &mut dyn Object) -> Result<Value> {
+        Err(Error::Abort)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef {
+            kind: value::Kind::Null,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_type_def;
+
+    test_type_def![abort {
+        expr: |_| Abort,
+        def: TypeDef {
+            kind: value::Kind::Null,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn execute() {
+        assert_eq!(
+            Abort.execute(&mut state::Program::default(), &mut Value::Null),
+            Err(Error::Abort)
+        );
+    }
+}
@@ -60,6 +60,11 @@ impl Argument {
     pub fn into_expr(self) -> Expr {
         *self.expression
     }
+
+    /// Borrow the inner expression of the argument.
+    pub fn as_expr(&self) -> &Expr {
+        &self.expression
+    }
 }
 
 impl Expression for Argument {
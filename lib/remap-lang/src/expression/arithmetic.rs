@@ -1,5 +1,5 @@
 use super::{Expr, Expression, Object, Result, TypeDef, Value};
-use crate::{state, value, Operator};
+use crate::{state, value, Error, Operator};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Arithmetic {
@@ -19,10 +19,10 @@ impl Expression for Arithmetic {
         use Operator::*;
 
         if matches!(self.op, ErrorOr) {
-            return self
-                .lhs
-                .execute(state, object)
-                .or_else(|_| self.rhs.execute(state, object));
+            return self.lhs.execute(state, object).or_else(|err| match err {
+                Error::Abort => Err(err),
+                _ => self.rhs.execute(state, object),
+            });
         }
 
         let lhs = self.lhs.execute(state, object)?;
@@ -61,7 +61,7 @@ impl Expression for Arithmetic {
             Or if !lhs_def.kind.is_boolean() => lhs_def,
             Or => type_def,
             ErrorOr if !lhs_def.is_fallible() => lhs_def,
-            ErrorOr if !rhs_def.is_fallible() => rhs_def,
+            ErrorOr if !rhs_def.is_fallible() => lhs_def.into_fallible(false) | rhs_def,
             ErrorOr => type_def,
             And if lhs_def.kind.is_null() => lhs_def.with_constraint(Kind::Boolean),
             And => type_def
@@ -310,7 +310,7 @@ mod tests {
                 Operator::ErrorOr,
             ),
             def: TypeDef {
-                kind: Kind::Boolean,
+                kind: Kind::Float | Kind::Boolean,
                 ..Default::default()
             },
         }
@@ -355,7 +355,7 @@ mod tests {
                 Operator::ErrorOr,
             ),
             def: TypeDef {
-                kind: Kind::Bytes,
+                kind: Kind::Bytes | Kind::Float,
                 ..Default::default()
             },
         }
@@ -20,6 +20,10 @@ impl Array {
         Box::new(self)
     }
 
+    pub(crate) fn expressions(&self) -> &[Expr] {
+        &self.expressions
+    }
+
     /// Unwrap the array expression into a [`Value::Array`] type.
     ///
     /// This method panics if the stored expressions do not resolve to a literal
@@ -39,6 +39,10 @@ impl fmt::Display for Target {
 pub struct Assignment {
     target: Target,
     value: Box<Expr>,
+
+    /// The value assigned to the "ok" target of an infallible assignment if
+    /// the expression fails.
+    ok_default: Value,
 }
 
 /// Add the type def for this path to the compiler state.
@@ -71,6 +75,7 @@ fn path_type_def(state: &mut state::Compiler, path: &path::Path, type_def: TypeD
 impl Assignment {
     pub fn new(target: Target, value: Box<Expr>, state: &mut state::Compiler) -> Self {
         let type_def = value.type_def(state);
+        let ok_default = type_def.kind.default_value();
 
         let var_type_def = |state: &mut state::Compiler, var: &Variable, type_def| {
             state
@@ -91,7 +96,13 @@ impl Assignment {
                 // "ok" target takes on the type definition of the value, but is
                 // set to being infallible, as the error will be captured by the
                 // "err" target.
-                let type_def = type_def.into_fallible(false);
+                //
+                // If the expression fails, "ok" is assigned the empty value of
+                // its kind, or `null` if it doesn't have one.
+                let mut type_def = type_def.into_fallible(false);
+                if ok_default.is_none() {
+                    type_def.kind |= Kind::Null;
+                }
 
                 match ok.as_ref() {
                     Target::Variable(var) => var_type_def(state, var, type_def),
@@ -114,7 +125,11 @@ impl Assignment {
             }
         }
 
-        Self { target, value }
+        Self {
+            target,
+            value,
+            ok_default: ok_default.unwrap_or(Value::Null),
+        }
     }
 }
 
@@ -152,7 +167,8 @@ impl Expression for Assignment {
             Target::Infallible { ok, err } => {
                 let (ok_value, err_value) = match value {
                     Ok(value) => (value, Value::Null),
-                    Err(err) => (Value::Null, Value::from(err)),
+                    Err(crate::Error::Abort) => return Err(crate::Error::Abort),
+                    Err(err) => (self.ok_default.clone(), Value::from(err)),
                 };
 
                 match ok.as_ref() {
@@ -167,11 +183,7 @@ impl Expression for Assignment {
                     Target::Infallible { .. } => unimplemented!("nested infallible target"),
                 };
 
-                if err_value.is_null() {
-                    Ok(ok_value)
-                } else {
-                    Ok(err_value)
-                }
+                Ok(ok_value)
             }
         }
     }
@@ -194,29 +206,13 @@ impl Expression for Assignment {
         match &self.target {
             Target::Variable(var) => var_type_def(var),
             Target::Path(path) => path_type_def(path),
-            Target::Infallible { ok, err } => {
-                let ok_type_def = match ok.as_ref() {
-                    Target::Variable(var) => var_type_def(var),
-                    Target::Path(path) => path_type_def(path),
-                    Target::Infallible { .. } => unimplemented!("nested infallible target"),
-                };
-
-                // Technically the parser rejects this invariant, because an
-                // expression that is known to be infallible cannot be assigned
-                // to an infallible target, since the error will always be
-                // `null`.
-                if !ok_type_def.is_fallible() {
-                    return ok_type_def;
-                }
-
-                let err_type_def = match err.as_ref() {
-                    Target::Variable(var) => var_type_def(var),
-                    Target::Path(path) => path_type_def(path),
-                    Target::Infallible { .. } => unimplemented!("nested infallible target"),
-                };
-
-                ok_type_def.merge(err_type_def).into_fallible(false)
-            }
+            // The assignment returns the value of the "ok" target, which is
+            // never fallible.
+            Target::Infallible { ok, .. } => match ok.as_ref() {
+                Target::Variable(var) => var_type_def(var),
+                Target::Path(path) => path_type_def(path),
+                Target::Infallible { .. } => unimplemented!("nested infallible target"),
+            },
         }
     }
 }
@@ -314,9 +310,58 @@ mod tests {
             },
             def: TypeDef {
                 fallible: false,
-                kind: Kind::Bytes | Kind::Integer | Kind::Float,
+                kind: Kind::Bytes | Kind::Integer | Kind::Float | Kind::Null,
+                ..Default::default()
+            },
+        }
+
+        infallible_exact {
+            expr: |state: &mut state::Compiler| {
+                let ok = Box::new(Target::Variable(Variable::new("ok".to_owned(), None)));
+                let err = Box::new(Target::Variable(Variable::new("err".to_owned(), None)));
+
+                let target = Target::Infallible { ok, err };
+                let value = Box::new(Arithmetic::new(
+                    Box::new(lit!(1).into()),
+                    Box::new(lit!(0).into()),
+                    Operator::Divide,
+                ).into());
+
+                Assignment::new(target, value, state)
+            },
+            def: TypeDef {
+                fallible: false,
+                kind: Kind::Float,
                 ..Default::default()
             },
         }
     ];
+
+    #[test]
+    fn infallible_error() {
+        let mut state = state::Compiler::default();
+
+        let ok = Box::new(Target::Variable(Variable::new("ok".to_owned(), None)));
+        let err = Box::new(Target::Variable(Variable::new("err".to_owned(), None)));
+        let target = Target::Infallible { ok, err };
+        let value = Box::new(
+            Arithmetic::new(
+                Box::new(lit!(1).into()),
+                Box::new(lit!(0).into()),
+                Operator::Divide,
+            )
+            .into(),
+        );
+
+        let assignment = Assignment::new(target, value, &mut state);
+
+        let mut program = state::Program::default();
+        let result = assignment.execute(&mut program, &mut Value::Null);
+
+        assert_eq!(result, Ok(Value::Float(0.0)));
+        assert_eq!(program.variable("ok"), Some(&Value::Float(0.0)));
+        assert!(program
+            .variable("err")
+            .map_or(false, |err| matches!(err, Value::Bytes(_))));
+    }
 }
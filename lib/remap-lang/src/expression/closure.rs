@@ -0,0 +1,145 @@
+use crate::{expression::Block, state, Expression, Object, Result, TypeDef, Value};
+
+/// A block of expressions passed to a function call, which the function can
+/// run any number of times, with different values bound to the closure
+/// variables.
+///
+/// ```text
+/// map_values(.items) -> |item| { upcase!(item) }
+/// ```
+///
+/// A closure isn't an expression by itself. It can only be used by the
+/// function it is attached to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Closure {
+    variables: Vec<String>,
+    block: Block,
+}
+
+impl Closure {
+    pub fn new(variables: Vec<String>, block: Block) -> Self {
+        Self { variables, block }
+    }
+
+    /// The names of the variables bound by the closure, in order.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// Run the closure block, binding each of the given values to the
+    /// closure variable at the same position.
+    ///
+    /// The variables are only visible inside the closure. Any existing
+    /// variable with the same name is restored once the block finishes.
+    pub fn run(
+        &self,
+        values: Vec<Value>,
+        state: &mut state::Program,
+        object: &mut dyn Object,
+    ) -> Result<Value> {
+        let shadowed = self
+            .variables
+            .iter()
+            .zip(values)
+            .map(|(ident, value)| {
+                let previous = state.variables_mut().insert(ident.clone(), value);
+                (ident, previous)
+            })
+            .collect::<Vec<_>>();
+
+        let result = self.block.execute(state, object);
+
+        for (ident, previous) in shadowed.into_iter().rev() {
+            match previous {
+                Some(value) => state.variables_mut().insert(ident.clone(), value),
+                None => state.variables_mut().remove(ident),
+            };
+        }
+
+        result
+    }
+
+    /// The type definition of the value returned by the closure block.
+    ///
+    /// The closure variables can hold any value.
+    pub fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let mut state = state.clone();
+
+        for ident in &self.variables {
+            state
+                .variable_types_mut()
+                .insert(ident.clone(), TypeDef::default());
+        }
+
+        self.block.type_def(&state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{Arithmetic, Literal, Variable},
+        value::Kind,
+        Operator,
+    };
+
+    fn add_one() -> Closure {
+        Closure::new(
+            vec!["i".to_owned()],
+            Block::new(vec![Arithmetic::new(
+                Box::new(Variable::new("i".to_owned(), None).into()),
+                Box::new(Literal::from(1).into()),
+                Operator::Add,
+            )
+            .into()]),
+        )
+    }
+
+    #[test]
+    fn run_binds_variables() {
+        let mut state = state::Program::default();
+        let mut object = Value::Null;
+
+        assert_eq!(
+            add_one().run(vec![2.into()], &mut state, &mut object),
+            Ok(3.into())
+        );
+        assert_eq!(state.variable("i"), None);
+    }
+
+    #[test]
+    fn run_restores_shadowed_variables() {
+        let mut state = state::Program::default();
+        let mut object = Value::Null;
+
+        state.variables_mut().insert("i".to_owned(), "foo".into());
+
+        assert_eq!(
+            add_one().run(vec![2.into()], &mut state, &mut object),
+            Ok(3.into())
+        );
+        assert_eq!(state.variable("i"), Some(&"foo".into()));
+    }
+
+    #[test]
+    fn type_def_variables_any() {
+        let state = state::Compiler::default();
+        let closure = Closure::new(
+            vec!["i".to_owned()],
+            Block::new(vec![Variable::new("i".to_owned(), None).into()]),
+        );
+
+        assert_eq!(closure.type_def(&state), TypeDef::default());
+        assert_eq!(state.variable_type("i"), None);
+    }
+
+    #[test]
+    fn type_def_fallible_block() {
+        let state = state::Compiler::default();
+        let type_def = add_one().type_def(&state);
+
+        assert!(type_def.is_fallible());
+        assert_eq!(type_def.kind, Kind::Bytes | Kind::Integer | Kind::Float);
+    }
+}
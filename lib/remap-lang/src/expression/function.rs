@@ -2,6 +2,7 @@ use crate::{
     expression, function::ArgumentList, state, Expr, Expression, Function as Fn, Object, TypeDef,
     Value,
 };
+use std::collections::BTreeMap;
 
 #[derive(thiserror::Error, Clone, Debug, PartialEq)]
 pub enum Error {
@@ -28,6 +29,15 @@ pub enum Error {
 
     #[error(r#"cannot mark infallible function as "abort on error", remove the "!" signature"#)]
     AbortInfallible,
+
+    #[error("function does not accept a closure")]
+    UnexpectedClosure,
+
+    #[error("missing required closure")]
+    MissingClosure,
+
+    #[error("invalid closure variable count (expected {min} to {max}, got {got})")]
+    ClosureArity { min: usize, max: usize, got: usize },
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +47,12 @@ pub struct Function {
     // If set to true, and the function fails at runtime, the program aborts.
     abort_on_error: bool,
 
+    // If set to false, the function has to be called at runtime.
+    pure: bool,
+
+    // If set to true, all arguments are known at compile-time.
+    constant: bool,
+
     // only used for `PartialEq` impl
     ident: &'static str,
 }
@@ -52,6 +68,7 @@ impl Function {
         ident: &str,
         abort_on_error: bool,
         arguments: Vec<(Option<String>, Expr)>,
+        closure: Option<expression::Closure>,
         definitions: &[Box<dyn Fn>],
         state: &state::Compiler,
     ) -> Result<Self, Error> {
@@ -61,6 +78,7 @@ impl Function {
             .ok_or(Error::Undefined)?;
 
         let ident = definition.identifier();
+        let pure = definition.is_pure();
         let parameters = definition.parameters();
 
         // check function arity
@@ -79,6 +97,9 @@ impl Function {
         let mut index = 0;
         let mut list = ArgumentList::default();
 
+        let constant =
+            closure.is_none() && arguments.iter().all(|(_, argument)| argument.is_constant());
+
         for (keyword, argument) in arguments {
             let param = match &keyword {
                 // positional argument
@@ -122,6 +143,25 @@ impl Function {
                 })
             })?;
 
+        // check the closure against the one the function accepts
+        match (definition.closure(), closure) {
+            (None, Some(_)) => return Err(Error::UnexpectedClosure),
+            (Some(param), None) if param.required => return Err(Error::MissingClosure),
+            (Some(param), Some(closure)) => {
+                let got = closure.variables().len();
+                if got < param.min_variables || got > param.max_variables {
+                    return Err(Error::ClosureArity {
+                        min: param.min_variables,
+                        max: param.max_variables,
+                        got,
+                    });
+                }
+
+                list.set_closure(closure);
+            }
+            _ => {}
+        }
+
         let function = definition
             .compile(list)
             .map_err(|err| Error::Compile(err.to_string()))?;
@@ -137,15 +177,40 @@ impl Function {
         Ok(Self {
             function,
             abort_on_error,
+            pure,
+            constant,
             ident,
         })
     }
 
+    /// Evaluate the function call at compile-time, if the function is pure,
+    /// all of its arguments are constant, and the call can't fail.
+    ///
+    /// The call is kept otherwise, including when it raises an error, so the
+    /// error is still raised at runtime.
+    pub fn fold(self, state: &state::Compiler) -> Expr {
+        if !self.pure || !self.constant || self.type_def(state).is_fallible() {
+            return self.into();
+        }
+
+        let mut object = Value::Map(BTreeMap::new());
+        match self.execute(&mut state::Program::default(), &mut object) {
+            Ok(value) => Expr::from(value),
+            Err(_) => self.into(),
+        }
+    }
+
     /// If `true`, the function asks the program to abort when it raises an error.
     pub fn abort_on_error(&self) -> bool {
         self.abort_on_error
     }
 
+    /// If `false`, the function can resolve to a different value every time
+    /// it is called, or has side effects.
+    pub fn is_pure(&self) -> bool {
+        self.pure
+    }
+
     pub fn ident(&self) -> &'static str {
         self.ident
     }
@@ -178,6 +243,8 @@ mod tests {
             Function {
                 function,
                 abort_on_error: false,
+                pure: true,
+                constant: false,
                 ident: "foo",
             }
         },
@@ -15,10 +15,13 @@ impl From<(Span, Error)> for Diagnostic {
         match err {
             Error::Conditional(err) => match err {
                 value::Error::Expected(got, want) => Self::error(message)
+                    .with_code(102)
                     .with_primary(format!("got: {}", want), span)
                     .with_context(format!("expected: {}", got), span)
                     .with_note(Note::CoerceValue),
-                _ => Self::error(message).with_primary("unexpected value", span),
+                _ => Self::error(message)
+                    .with_code(102)
+                    .with_primary("unexpected value", span),
             },
         }
     }
@@ -63,6 +66,24 @@ impl IfStatement {
             false_expression,
         }
     }
+
+    /// Reduce the if-statement to the branch it always takes, if its condition
+    /// is a literal.
+    pub fn fold(self) -> Expr {
+        let condition = match &*self.conditional {
+            Expr::Literal(literal) => match literal.as_value() {
+                Value::Boolean(boolean) => Some(*boolean),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match condition {
+            Some(true) => *self.true_expression,
+            Some(false) => *self.false_expression,
+            None => self.into(),
+        }
+    }
 }
 
 impl Expression for IfStatement {
@@ -85,7 +106,11 @@ impl Expression for IfStatement {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{expression::Noop, lit, test_type_def, value::Kind};
+    use crate::{
+        expression::{Noop, Path},
+        lit, test_type_def,
+        value::Kind,
+    };
 
     test_type_def![
         concrete_type_def {
@@ -116,4 +141,24 @@ mod tests {
             },
         }
     ];
+
+    #[test]
+    fn fold() {
+        let if_statement = |condition: Expr| {
+            IfStatement::new(
+                IfCondition(Box::new(condition)),
+                Box::new(lit!("true").into()),
+                Box::new(lit!("false").into()),
+            )
+        };
+
+        let folded = if_statement(lit!(true).into()).fold();
+        assert_eq!(folded, Expr::from(lit!("true")));
+
+        let folded = if_statement(lit!(false).into()).fold();
+        assert_eq!(folded, Expr::from(lit!("false")));
+
+        let dynamic = if_statement(Path::from("foo").into());
+        assert_eq!(dynamic.clone().fold(), Expr::from(dynamic));
+    }
 }
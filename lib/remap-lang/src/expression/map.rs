@@ -17,6 +17,10 @@ impl Map {
     pub fn boxed(self) -> Box<dyn Expression> {
         Box::new(self)
     }
+
+    pub(crate) fn expressions(&self) -> &BTreeMap<String, Expr> {
+        &self.expressions
+    }
 }
 
 impl fmt::Debug for Map {
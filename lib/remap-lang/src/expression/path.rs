@@ -1,4 +1,4 @@
-use crate::{path, state, Expression, Object, Result, TypeDef, Value};
+use crate::{path, state, value::Kind, Expression, Object, Result, TypeDef, Value};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,6 +52,18 @@ impl Path {
 
 impl Expression for Path {
     fn execute(&self, _: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        // A wildcard path resolves to the list of all the values it matches.
+        if self.path.has_wildcard() {
+            let values = self
+                .path
+                .expand(object)
+                .iter()
+                .filter_map(|path| object.get(path).ok().flatten())
+                .collect::<Vec<_>>();
+
+            return Ok(values.into());
+        }
+
         let value = object.get(&self.path).ok().flatten().unwrap_or(Value::Null);
 
         Ok(value)
@@ -61,6 +73,13 @@ impl Expression for Path {
     /// specific values to paths during its execution, which increases our exact
     /// understanding of the value kind the path contains.
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        if self.path.has_wildcard() {
+            return TypeDef {
+                kind: Kind::Array,
+                ..Default::default()
+            };
+        }
+
         state
             .path_query_type(self)
             .cloned()
@@ -72,7 +91,8 @@ impl Expression for Path {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_type_def, value::Kind};
+    use crate::test_type_def;
+    use std::str::FromStr;
 
     test_type_def![
         ident_match {
@@ -111,5 +131,24 @@ mod tests {
             expr: |_| Path::from("foo"),
             def: TypeDef::default(),
         }
+
+        wildcard {
+            expr: |_| Path::from(path::Path::from_str(".foo[*].bar").unwrap()),
+            def: TypeDef {
+                kind: Kind::Array,
+                ..Default::default()
+            },
+        }
     ];
+
+    #[test]
+    fn wildcard_execute() {
+        let mut object = crate::value!({foo: [{bar: 1}, {baz: 2}, {bar: 3}]});
+        let path = Path::from(path::Path::from_str(".foo[*].bar").unwrap());
+
+        assert_eq!(
+            path.execute(&mut state::Program::default(), &mut object),
+            Ok(crate::value!([1, 3]))
+        );
+    }
 }
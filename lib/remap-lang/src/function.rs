@@ -1,5 +1,5 @@
 use crate::{
-    expression::{self, Array, Literal, Path},
+    expression::{self, Array, Closure, Literal, Path},
     Expr, Expression, Result, Value,
 };
 use core::convert::{TryFrom, TryInto};
@@ -29,6 +29,9 @@ pub enum Error {
 
     #[error("unknown enum variant: {0}, must be one of: {}", .1.join(", "))]
     UnknownEnumVariant(String, Vec<&'static str>),
+
+    #[error("missing required closure")]
+    RequiredClosure,
 }
 
 #[derive(Copy, Clone)]
@@ -60,12 +63,30 @@ impl std::fmt::Debug for Parameter {
     }
 }
 
+/// The closure a function accepts, if any.
+#[derive(Copy, Clone, Debug)]
+pub struct ClosureParameter {
+    /// Whether or not the function has to be called with a closure.
+    pub required: bool,
+
+    /// The minimum number of variables the closure has to bind.
+    pub min_variables: usize,
+
+    /// The maximum number of variables the closure can bind.
+    ///
+    /// For example, `map_values` accepts both `|value|` and `|key, value|`.
+    pub max_variables: usize,
+}
+
 #[derive(Debug, Default)]
-pub struct ArgumentList(HashMap<&'static str, Expr>);
+pub struct ArgumentList {
+    arguments: HashMap<&'static str, Expr>,
+    closure: Option<Closure>,
+}
 
 impl ArgumentList {
     pub fn optional(&mut self, keyword: &str) -> Option<Expr> {
-        self.0.remove(keyword)
+        self.arguments.remove(keyword)
     }
 
     pub fn required(&mut self, keyword: &str) -> Result<Expr> {
@@ -164,12 +185,25 @@ impl ArgumentList {
             .ok_or_else(|| Error::Required(keyword.to_owned()).into())
     }
 
+    pub fn optional_closure(&mut self) -> Option<Closure> {
+        self.closure.take()
+    }
+
+    pub fn required_closure(&mut self) -> Result<Closure> {
+        self.optional_closure()
+            .ok_or_else(|| Error::RequiredClosure.into())
+    }
+
     pub fn keywords(&self) -> Vec<&'static str> {
-        self.0.keys().copied().collect::<Vec<_>>()
+        self.arguments.keys().copied().collect::<Vec<_>>()
     }
 
     pub fn insert(&mut self, k: &'static str, v: Expr) {
-        self.0.insert(k, v);
+        self.arguments.insert(k, v);
+    }
+
+    pub fn set_closure(&mut self, closure: Closure) {
+        self.closure = Some(closure);
     }
 }
 
@@ -208,6 +242,24 @@ pub trait Function: std::fmt::Debug + Sync + CloneFunction {
     fn parameters(&self) -> &'static [Parameter] {
         &[]
     }
+
+    /// The closure the function accepts, if any.
+    ///
+    /// This is used at compile-time to reject closures passed to functions
+    /// that don't take one, and to check the number of closure variables.
+    fn closure(&self) -> Option<ClosureParameter> {
+        None
+    }
+
+    /// Whether the function always resolves to the same value for the same
+    /// arguments, without side effects.
+    ///
+    /// Calls to impure functions, like the ones generating random values, are
+    /// never evaluated ahead of time, even when all of their arguments are
+    /// known at compile-time.
+    fn is_pure(&self) -> bool {
+        true
+    }
 }
 
 pub trait CloneFunction {
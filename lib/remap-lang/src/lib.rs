@@ -18,7 +18,7 @@ pub mod value;
 pub use diagnostic::{Diagnostic, DiagnosticList, Formatter, Span};
 pub use error::Error;
 pub use expression::{Expr, Expression};
-pub use function::{Function, Parameter};
+pub use function::{ClosureParameter, Function, Parameter};
 pub use object::Object;
 pub use operator::Operator;
 pub use path::{Field, Path, Segment};
@@ -302,7 +302,7 @@ mod tests {
             //     Err(r#"remap error: assignment error: the variable "foo" does not need to handle the error-case, because its result is infallible"#),
             //     Ok(().into()),
             // ),
-            ("foo, err = fallible_func()", Ok(()), Ok(value!("function call error: failed!"))),
+            ("foo, err = fallible_func()", Ok(()), Ok(value!(false))),
             // TODO: move to `remap-tests`
             // (
             //     "foo, err = map_printer({})",
@@ -320,7 +320,7 @@ mod tests {
                     [foo, err]
                 ",
                 Ok(()),
-                Ok(value!([null, "function call error: failed!"])),
+                Ok(value!([false, "function call error: failed!"])),
             ),
             (
                 "
@@ -346,6 +346,16 @@ mod tests {
             ("two_param_func(true, true)", Ok(()), Ok(value!(null))),
             ("two_param_func(.foo, param2: true)", Ok(()), Ok(value!(null))),
             ("two_param_func(param2: .foo, param1: true)", Ok(()), Ok(value!(null))),
+            ("apply(1) -> |v| { v }", Ok(()), Ok(value!(1))),
+            ("apply(1) -> |v| { [v, v] }", Ok(()), Ok(value!([1, 1]))),
+            ("apply(.foo.bar)->|v|{ v }", Ok(()), Ok(value!("baz"))),
+            ("apply(1) -> |v| {\n  w = v\n  [w, v]\n}", Ok(()), Ok(value!([1, 1]))),
+            ("v = true\napply(1) -> |v| { v }\nv", Ok(()), Ok(value!(true))),
+            ("abort", Ok(()), Err("aborted")),
+            ("abort ?? true", Ok(()), Err("aborted")),
+            (r#"if .foo.bar == "baz" { abort }\ntrue"#, Ok(()), Err("aborted")),
+            (r#"if .foo.bar == "qux" { abort }\ntrue"#, Ok(()), Ok(value!(true))),
+            ("aborted = true", Ok(()), Ok(value!(true))),
         ];
 
         for (script, compile_expected, runtime_expected) in cases {
@@ -359,6 +369,7 @@ mod tests {
                     Box::new(test_functions::MapPrinter),
                     Box::new(test_functions::FallibleFunc),
                     Box::new(test_functions::TwoParamFunc),
+                    Box::new(test_functions::Apply),
                 ],
                 None,
                 true,
@@ -386,11 +397,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn abort_is_explicit() {
+        let functions: &[Box<dyn Function>] = &[Box::new(test_functions::FallibleFunc)];
+        let mut runtime = Runtime::new(state::Program::default());
+
+        let program = Program::new("abort".to_owned(), functions, None, true)
+            .unwrap()
+            .0;
+        let abort = runtime.run(&mut value!({}), &program).unwrap_err();
+        assert!(abort.is_explicit());
+
+        let program = Program::new("fallible_func!()".to_owned(), functions, None, true)
+            .unwrap()
+            .0;
+        let abort = runtime.run(&mut value!({}), &program).unwrap_err();
+        assert!(!abort.is_explicit());
+    }
+
+    #[test]
+    fn fold_constant_calls() {
+        use std::sync::atomic::Ordering;
+        use test_functions::CALLS;
+
+        let functions: &[Box<dyn Function>] = &[
+            Box::new(test_functions::Counted { pure: true }),
+            Box::new(test_functions::Counted { pure: false }),
+        ];
+
+        let cases = vec![
+            ("counted(1)", value!(1), 0),
+            (
+                r#"counted([1, {"bar": counted(2)}])"#,
+                value!([1, {"bar": 2}]),
+                0,
+            ),
+            ("counted(.foo)", value!(true), 1),
+            ("impure_counted(1)", value!(1), 1),
+            (
+                "if true { counted(.foo) } else { counted(1) }",
+                value!(true),
+                1,
+            ),
+        ];
+
+        for (source, expect, calls) in cases {
+            let program = Program::new(source.to_owned(), functions, None, true)
+                .unwrap()
+                .0;
+            let mut runtime = Runtime::new(state::Program::default());
+
+            let before = CALLS.load(Ordering::SeqCst);
+            let result = runtime.run(&mut value!({"foo": true}), &program);
+
+            assert_eq!(result.ok(), Some(expect), "{}", source);
+            assert_eq!(CALLS.load(Ordering::SeqCst) - before, calls, "{}", source);
+        }
+    }
+
     mod test_functions {
         use super::*;
-        use crate::expression::{Array, Map};
+        use crate::expression::{Array, Closure, Map};
+        use crate::function::ClosureParameter;
         use std::collections::BTreeMap;
         use std::convert::TryFrom;
+        use std::sync::atomic::{AtomicUsize, Ordering};
 
         #[derive(Debug, Clone)]
         pub(super) struct EnumValidator;
@@ -655,5 +726,103 @@ mod tests {
                 TypeDef::default()
             }
         }
+
+        /// The number of times `counted` and `impure_counted` were executed.
+        pub(super) static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, Clone)]
+        pub(super) struct Counted {
+            pub(super) pure: bool,
+        }
+        impl Function for Counted {
+            fn identifier(&self) -> &'static str {
+                if self.pure {
+                    "counted"
+                } else {
+                    "impure_counted"
+                }
+            }
+
+            fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+                Ok(Box::new(CountedFn(arguments.required("value")?)))
+            }
+
+            fn parameters(&self) -> &'static [Parameter] {
+                &[Parameter {
+                    keyword: "value",
+                    accepts: |_| true,
+                    required: true,
+                }]
+            }
+
+            fn is_pure(&self) -> bool {
+                self.pure
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        struct CountedFn(Expr);
+        impl Expression for CountedFn {
+            fn execute(
+                &self,
+                state: &mut state::Program,
+                object: &mut dyn Object,
+            ) -> Result<Value> {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                self.0.execute(state, object)
+            }
+
+            fn type_def(&self, state: &state::Compiler) -> TypeDef {
+                self.0.type_def(state)
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        pub(super) struct Apply;
+        impl Function for Apply {
+            fn identifier(&self) -> &'static str {
+                "apply"
+            }
+
+            fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+                Ok(Box::new(ApplyFn(
+                    arguments.required("value")?,
+                    arguments.required_closure()?,
+                )))
+            }
+
+            fn parameters(&self) -> &'static [Parameter] {
+                &[Parameter {
+                    keyword: "value",
+                    accepts: |_| true,
+                    required: true,
+                }]
+            }
+
+            fn closure(&self) -> Option<ClosureParameter> {
+                Some(ClosureParameter {
+                    required: true,
+                    min_variables: 1,
+                    max_variables: 1,
+                })
+            }
+        }
+
+        #[derive(Debug, Clone)]
+        struct ApplyFn(Expr, Closure);
+        impl Expression for ApplyFn {
+            fn execute(
+                &self,
+                state: &mut state::Program,
+                object: &mut dyn Object,
+            ) -> Result<Value> {
+                let value = self.0.execute(state, object)?;
+                self.1.run(vec![value], state, object)
+            }
+
+            fn type_def(&self, state: &state::Compiler) -> TypeDef {
+                self.1.type_def(state)
+            }
+        }
     }
 }
@@ -51,4 +51,14 @@ pub trait Object: std::fmt::Debug {
     /// If `compact` is true, after deletion, if an empty object or array is
     /// left behind, it should be removed as well.
     fn remove(&mut self, path: &Path, compact: bool) -> Result<Option<Value>, String>;
+
+    /// Get the metadata value for the given key, or `None` if the object
+    /// carries no such metadata.
+    ///
+    /// Metadata holds transport-level information about the object that isn't
+    /// part of the object itself, such as the headers of the request it was
+    /// received in.
+    fn get_metadata(&self, _key: &str) -> Option<Value> {
+        None
+    }
 }
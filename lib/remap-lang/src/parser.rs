@@ -3,10 +3,10 @@
 use crate::{
     diagnostic::{self, Diagnostic, DiagnosticList, Label, Note, Span},
     expression::{
-        self, function, if_statement::IfCondition, Arithmetic, Array, Assignment, Block, Function,
-        IfStatement, Literal, Map, Noop, Not, Path, Target, Variable,
+        self, function, if_statement::IfCondition, Abort, Arithmetic, Array, Assignment, Block,
+        Closure, Function, IfStatement, Literal, Map, Noop, Not, Path, Target, Variable,
     },
-    path, state, Expr, Expression, Function as Fn, Operator, Value,
+    path, state, Expr, Expression, Function as Fn, Operator, TypeDef, Value,
 };
 use pest::error::InputLocation;
 use pest::iterators::{Pair, Pairs};
@@ -40,6 +40,14 @@ pub(super) struct Parser<'a> {
     /// All parsing functions take `self` such that this state cannot leak into
     /// subsequent parsing calls.
     diagnostics: DiagnosticList,
+
+    /// The block currently being parsed, to know whether a variable is
+    /// reassigned in the same block it was assigned in.
+    scope: usize,
+
+    /// The number of blocks parsed so far, used to give each block a unique
+    /// scope.
+    scopes: usize,
 }
 
 impl<'a> From<&Pair<'a, R>> for Span {
@@ -100,17 +108,21 @@ impl From<PestError> for Diagnostic {
 
                 match () {
                     _ if !positives.is_empty() => Diagnostic::error(msg)
+                        .with_code(119)
                         .with_label(label)
                         .with_label(expected),
                     _ if !negatives.is_empty() => Diagnostic::error(msg)
+                        .with_code(119)
                         .with_label(label)
                         .with_label(unexpected),
-                    _ => Diagnostic::error(msg).with_primary("unexpected token", span),
+                    _ => Diagnostic::error(msg)
+                        .with_code(119)
+                        .with_primary("unexpected token", span),
                 }
             }
-            pest::error::ErrorVariant::CustomError { message } => {
-                Diagnostic::error(msg).with_primary(message, span)
-            }
+            pest::error::ErrorVariant::CustomError { message } => Diagnostic::error(msg)
+                .with_code(119)
+                .with_primary(message, span),
         }
     }
 }
@@ -277,6 +289,8 @@ impl<'a> Parser<'a> {
             allow_regex_return,
             compiler_state,
             diagnostics: DiagnosticList::default(),
+            scope: 0,
+            scopes: 0,
         }
     }
 
@@ -292,6 +306,8 @@ impl<'a> Parser<'a> {
             .map(|nodes| nodes.into_iter().map(Into::into).collect::<Vec<_>>())
             .map_err(|err| self.diagnostics.push(err.into()));
 
+        self.check_unused_variables();
+
         match expressions {
             Err(_) => Err(self.diagnostics),
             Ok(_) if self.diagnostics.is_err() => Err(self.diagnostics),
@@ -362,12 +378,15 @@ impl<'a> Parser<'a> {
             }
         }
 
+        self.check_unreachable(&nodes);
+
         if let Some(node) = nodes.last() {
             let td = node.type_def(&self.compiler_state);
 
             if !self.allow_regex_return && !td.kind.is_all() && td.scalar_kind().contains_regex() {
                 self.diagnostics.push(
                     Diagnostic::error("invalid return value")
+                        .with_code(117)
                         .with_primary("regex return value not allowed", node.span),
                 );
             }
@@ -417,11 +436,13 @@ impl<'a> Parser<'a> {
             .take();
 
         let assignment_span = target_span.start..expression_span.end;
+        let mut idents = vec![];
 
         if let Target::Infallible { ok, err } = &target {
             if !expression.type_def(&self.compiler_state).is_fallible() {
                 self.diagnostics.push(
                     Diagnostic::error("unneeded error assignment")
+                        .with_code(104)
                         .with_primary("this error assignment is unneeded", target_span)
                         .with_context("because this expression cannot fail", expression_span)
                         .with_note(Note::InfallibleAssignment {
@@ -434,8 +455,13 @@ impl<'a> Parser<'a> {
             }
         }
 
+        target_idents(&target, &mut idents);
         let assignment = Assignment::new(target, Box::new(expression), &mut self.compiler_state);
 
+        for ident in idents {
+            self.assign_variable(ident, target_span);
+        }
+
         Ok((assignment_span, assignment).into())
     }
 
@@ -457,6 +483,7 @@ impl<'a> Parser<'a> {
 
                     self.diagnostics.push(
                         Diagnostic::error("path-based variable assignment")
+                            .with_code(115)
                             .with_primary(
                                 "assignment to variable path currently unsupported",
                                 path_span,
@@ -474,6 +501,20 @@ impl<'a> Parser<'a> {
             }),
             R::path => {
                 let (span, path) = self.path_from_pair(pair)?.take();
+
+                // Wildcards can match any number of fields, so there's no
+                // single field to assign to.
+                if path.has_wildcard() {
+                    self.diagnostics.push(
+                        Diagnostic::error("wildcard path assignment")
+                            .with_code(116)
+                            .with_primary("assignment to wildcard path unsupported", span),
+                    );
+
+                    let variable = Variable::new("_".to_owned(), None);
+                    return Ok((span, Target::Variable(variable)).into());
+                }
+
                 Ok((span, Target::Path(Path::new(path))).into())
             }
             R::target_infallible => self
@@ -510,13 +551,26 @@ impl<'a> Parser<'a> {
 
     /// Parse block expressions.
     fn block_from_pair(&mut self, pair: Pair<R>) -> IResult<Expr> {
+        self.block_node_from_pair(pair).map(ParsedNode::to_expr)
+    }
+
+    fn block_node_from_pair(&mut self, pair: Pair<R>) -> IResult<Block> {
         let span = Span::from(&pair);
-        let mut expressions = vec![];
+        let mut nodes = vec![];
+
+        let scope = self.scope;
+        self.scopes += 1;
+        self.scope = self.scopes;
 
         for pair in pair.into_inner() {
-            expressions.push(self.expression_from_pair(pair)?.into_inner());
+            nodes.push(self.expression_from_pair(pair)?);
         }
 
+        self.scope = scope;
+        self.check_unreachable(&nodes);
+
+        let expressions = nodes.into_iter().map(ParsedNode::into_inner).collect();
+
         Ok((span, Block::new(expressions)).into())
     }
 
@@ -590,14 +644,15 @@ impl<'a> Parser<'a> {
                 Box::new(true_expression),
                 Box::new(false_expression),
             )
-            .into();
+            .fold();
         }
 
         let node = IfStatement::new(
             conditional,
             Box::new(true_expression),
             Box::new(false_expression),
-        );
+        )
+        .fold();
 
         Ok((span, node).into())
     }
@@ -652,7 +707,18 @@ impl<'a> Parser<'a> {
 
         match pair.as_rule() {
             R::value => self.literal_from_pair(pair.into_inner().next().ok_or(e(R::value, span))?),
-            R::variable => self.variable_from_pair(pair).map(ParsedNode::to_expr),
+            R::abort => Ok((span, Abort).into()),
+            R::variable => self.variable_from_pair(pair).map(|node| {
+                if let Some(usage) = self
+                    .compiler_state
+                    .variable_usages_mut()
+                    .get_mut(node.ident())
+                {
+                    usage.used = true;
+                }
+
+                node.to_expr()
+            }),
             R::path => self.path_from_pair(pair).map(|node| {
                 let (span, path) = node.take();
                 (span, Path::new(path)).into()
@@ -750,58 +816,92 @@ impl<'a> Parser<'a> {
             _ => false,
         };
 
-        let (arguments_span, arguments) = inner
-            .next()
-            .map(|pair| self.arguments_from_pair(pair))
-            .transpose()?
-            .map(|s| s.take())
-            .unwrap_or_else(|| (Span::default(), vec![]));
+        let mut arguments_span = Span::default();
+        let mut arguments = vec![];
+        let mut closure = None;
+
+        for pair in inner {
+            match pair.as_rule() {
+                R::arguments => {
+                    let (span, list) = self.arguments_from_pair(pair)?.take();
+                    arguments_span = span;
+                    arguments = list;
+                }
+                R::closure => closure = Some(self.closure_from_pair(pair)?),
+                _ => return Err(e(R::call, &pair)),
+            }
+        }
+
+        let closure_span = closure.as_ref().map(|node| node.span).unwrap_or(span);
 
         let function = Function::new(
             ident,
             abort_on_error,
             arguments,
+            closure.map(ParsedNode::into_inner),
             &self.function_definitions,
             &self.compiler_state,
         );
 
         let expression: Expr = match function {
-            Ok(function) => function.into(),
+            Ok(function) => function.fold(&self.compiler_state),
             Err(err) => {
                 self.compiler_state.revert_changes();
 
                 self.diagnostics.push(match err {
                     function::Error::Undefined => Diagnostic::error("call to undefined function")
+                        .with_code(105)
                         .with_primary("undefined function", ident_span),
                     function::Error::ArityMismatch { max, got } => {
                         Diagnostic::error("function argument arity mismatch")
+                            .with_code(106)
                             .with_primary(format!("got: {}", got), arguments_span)
                             .with_context(format!("expected: {} (at most)", max), arguments_span)
                     }
                     // TODO: have spans for each individual keyword
                     function::Error::UnknownKeyword(kw) => {
                         Diagnostic::error("unknown function argument keyword")
+                            .with_code(108)
                             .with_primary(format!("unknown keyword: {}", kw), arguments_span)
                     }
                     function::Error::AbortInfallible => {
                         let bang_span = *ident_span.end() + 1..*ident_span.end() + 1;
 
                         Diagnostic::error("cannot abort function that never fails")
+                            .with_code(109)
                             .with_primary("this function cannot fail", ident_span)
                             .with_context("remove this abort-instruction", bang_span)
                             .with_note(Note::SeeErrDocs)
                     }
                     function::Error::MissingArg { argument, .. } => {
                         Diagnostic::error("function argument missing")
+                            .with_code(107)
                             .with_primary(format!("required argument missing: {}", argument), span)
                     }
                     function::Error::Compile(message) => {
-                        Diagnostic::error("unable to parse function").with_primary(message, span)
+                        Diagnostic::error("unable to parse function")
+                            .with_code(111)
+                            .with_primary(message, span)
                     }
                     function::Error::Argument(arg, err) => {
                         Diagnostic::error("function argument error")
+                            .with_code(110)
                             .with_primary(format!("{}: {}", arg, err), arguments_span)
                     }
+                    function::Error::UnexpectedClosure => Diagnostic::error("unexpected closure")
+                        .with_code(112)
+                        .with_primary("this function does not accept a closure", closure_span),
+                    function::Error::MissingClosure => {
+                        Diagnostic::error("function closure missing")
+                            .with_code(113)
+                            .with_primary("required closure missing", span)
+                    }
+                    function::Error::ClosureArity { min, max, got } => {
+                        Diagnostic::error("closure variable arity mismatch")
+                            .with_code(114)
+                            .with_primary(format!("got: {}", got), closure_span)
+                            .with_context(format!("expected: {} to {}", min, max), closure_span)
+                    }
                 });
 
                 Noop.into()
@@ -811,6 +911,66 @@ impl<'a> Parser<'a> {
         Ok((span, expression).into())
     }
 
+    /// Parse a closure attached to a function call.
+    fn closure_from_pair(&mut self, pair: Pair<R>) -> IResult<Closure> {
+        let span = Span::from(&pair);
+        let mut inner = pair.into_inner();
+
+        let variables = inner
+            .next()
+            .ok_or(e(R::closure, span))?
+            .into_inner()
+            .map(|pair| pair.as_str().to_owned())
+            .collect::<Vec<_>>();
+
+        // The closure variables can hold any value, and are only known to the
+        // compiler while parsing the closure block.
+        let shadowed = variables
+            .iter()
+            .map(|ident| {
+                let variable_types = self.compiler_state.variable_types_mut();
+                (
+                    ident,
+                    variable_types.insert(ident.clone(), TypeDef::default()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Uses of the closure variables don't use the variables they shadow.
+        let shadowed_usages = variables
+            .iter()
+            .map(|ident| {
+                let variable_usages = self.compiler_state.variable_usages_mut();
+                (ident, variable_usages.remove(ident))
+            })
+            .collect::<Vec<_>>();
+
+        let block = inner
+            .next()
+            .ok_or(e(R::closure, span))
+            .and_then(|pair| self.block_node_from_pair(pair));
+
+        for (ident, previous) in shadowed.into_iter().rev() {
+            let variable_types = self.compiler_state.variable_types_mut();
+            match previous {
+                Some(type_def) => variable_types.insert(ident.clone(), type_def),
+                None => variable_types.remove(ident),
+            };
+        }
+
+        for (ident, previous) in shadowed_usages.into_iter().rev() {
+            let variable_usages = self.compiler_state.variable_usages_mut();
+            match previous {
+                Some(usage) => variable_usages.insert(ident.clone(), usage),
+                None => variable_usages.remove(ident),
+            };
+        }
+
+        let block = block?.into_inner();
+
+        Ok((span, Closure::new(variables, block)).into())
+    }
+
     /// Parse into a vector of argument properties.
     fn arguments_from_pair(&mut self, pair: Pair<R>) -> IResult<Vec<(Option<String>, Expr)>> {
         let span = Span::from(&pair);
@@ -886,6 +1046,7 @@ impl<'a> Parser<'a> {
                 // Record error diagnostic for invalid regex.
                 self.diagnostics.push(
                     Diagnostic::error("regex parsing unsuccessful")
+                        .with_code(101)
                         .with_primary("invalid regex", span)
                         .with_primary(format!("error: {}", error), span),
                 )
@@ -981,7 +1142,13 @@ impl<'a> Parser<'a> {
             .into_inner()
             .next()
             .ok_or(e(R::path_index, span))?
-            .as_str()
+            .as_str();
+
+        if index == "*" {
+            return Ok((span, path::Segment::Wildcard).into());
+        }
+
+        let index = index
             .parse::<usize>()
             .map_err(|_| e(R::path_index_inner, span))?;
 
@@ -1061,6 +1228,82 @@ impl<'a> Parser<'a> {
         Ok((span, escaped_chars.into_iter().collect::<String>()).into())
     }
 
+    /// Tracks the assignment of a variable, warning if the value previously
+    /// assigned to it in the same block is never used.
+    ///
+    /// Variables starting with an underscore are never tracked, to allow
+    /// explicitly ignoring a value.
+    fn assign_variable(&mut self, ident: String, span: Span) {
+        if ident.starts_with('_') {
+            return;
+        }
+
+        let usage = state::VariableUsage {
+            span,
+            scope: self.scope,
+            used: false,
+        };
+
+        let previous = self
+            .compiler_state
+            .variable_usages_mut()
+            .insert(ident.clone(), usage);
+
+        if let Some(previous) = previous {
+            if !previous.used && previous.scope == self.scope {
+                self.diagnostics.push(
+                    Diagnostic::warning("shadowed variable")
+                        .with_primary(
+                            format!(r#"value assigned to "{}" is never used"#, ident),
+                            previous.span,
+                        )
+                        .with_context("because the variable is reassigned here", span),
+                );
+            }
+        }
+    }
+
+    /// Warns about the variables that were assigned a value that is never
+    /// used, and stops tracking the variables of the parsed program.
+    fn check_unused_variables(&mut self) {
+        let mut unused = self
+            .compiler_state
+            .variable_usages_mut()
+            .drain()
+            .filter(|(_, usage)| !usage.used)
+            .collect::<Vec<_>>();
+
+        unused.sort_by_key(|(_, usage)| usage.span.start);
+
+        for (ident, usage) in unused {
+            self.diagnostics
+                .push(Diagnostic::warning("unused variable").with_primary(
+                    format!(r#"value assigned to "{}" is never used"#, ident),
+                    usage.span,
+                ));
+        }
+    }
+
+    /// Warns about the expressions following an expression that always
+    /// terminates the program.
+    fn check_unreachable(&mut self, nodes: &[ParsedNode<Expr>]) {
+        let terminal = match nodes
+            .iter()
+            .position(|node| matches!(node.inner, Expr::Abort(_)))
+        {
+            Some(terminal) => terminal,
+            None => return,
+        };
+
+        if let (Some(next), Some(last)) = (nodes.get(terminal + 1), nodes.last()) {
+            self.diagnostics.push(
+                Diagnostic::warning("unreachable code")
+                    .with_primary("this code is never run", next.span.start..last.span.end)
+                    .with_context("because the program terminates here", nodes[terminal].span),
+            );
+        }
+    }
+
     /// Allows you to check if a result passed. If it did, the compiler starts
     /// tracking new changes, if it didn't, the existing state is overwritten
     /// with the previously recorded changes (if any).
@@ -1111,6 +1354,18 @@ impl<'a> Parser<'a> {
 
 // -----------------------------------------------------------------------------
 
+/// Collects the variables assigned to by the target.
+fn target_idents(target: &Target, idents: &mut Vec<String>) {
+    match target {
+        Target::Variable(variable) => idents.push(variable.ident().to_owned()),
+        Target::Path(_) => {}
+        Target::Infallible { ok, err } => {
+            target_idents(ok, idents);
+            target_idents(err, idents);
+        }
+    }
+}
+
 #[inline]
 fn e(rule: R, span: impl Into<Span>) -> ParserBug {
     ParserBug(span.into(), rule)
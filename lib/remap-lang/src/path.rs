@@ -1,4 +1,4 @@
-use crate::{diagnostic::Formatter, parser::Parser, state};
+use crate::{diagnostic::Formatter, parser::Parser, state, Object, Value};
 use std::fmt;
 use std::str::FromStr;
 
@@ -87,10 +87,12 @@ impl Path {
                 Segment::Coalesce(fields) => {
                     segments.push(fields.iter().map(|f| handle_field(f)).collect::<Vec<_>>())
                 }
-                Segment::Index(_) => segments.last_mut().into_iter().for_each(|vec| {
-                    vec.iter_mut()
-                        .for_each(|s| s.push_str(&segment.to_string()))
-                }),
+                Segment::Index(_) | Segment::Wildcard => {
+                    segments.last_mut().into_iter().for_each(|vec| {
+                        vec.iter_mut()
+                            .for_each(|s| s.push_str(&segment.to_string()))
+                    })
+                }
             }
         }
 
@@ -209,6 +211,50 @@ impl Path {
         Ok(Self::new_unchecked(segments))
     }
 
+    /// Returns `true` if the path contains a wildcard index (e.g. `.foo[*]`),
+    /// which matches every element of an array.
+    pub fn has_wildcard(&self) -> bool {
+        self.segments.iter().any(Segment::is_wildcard)
+    }
+
+    /// Expand the wildcard indices of the path into the indices of the
+    /// matching arrays in the given object, returning one path per element.
+    ///
+    /// .foo[*].bar   => [".foo[0].bar", ".foo[1].bar"]
+    ///
+    /// A path without wildcards expands to itself. A wildcard that doesn't
+    /// point to an array matches nothing.
+    pub fn expand(&self, object: &dyn Object) -> Vec<Path> {
+        let mut paths = vec![Path::root()];
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Wildcard => {
+                    paths = paths
+                        .into_iter()
+                        .flat_map(|path| {
+                            let len = match object.get(&path) {
+                                Ok(Some(Value::Array(array))) => array.len(),
+                                _ => 0,
+                            };
+
+                            (0..len).map(move |index| {
+                                let mut path = path.clone();
+                                path.append(Segment::Index(index));
+                                path
+                            })
+                        })
+                        .collect();
+                }
+                segment => paths
+                    .iter_mut()
+                    .for_each(|path| path.append(segment.clone())),
+            }
+        }
+
+        paths
+    }
+
     /// Appends a new segment to the end of this path.
     pub fn append(&mut self, segment: Segment) {
         self.segments.push(segment);
@@ -254,6 +300,7 @@ pub enum Segment {
     Field(Field),
     Coalesce(Vec<Field>),
     Index(usize),
+    Wildcard,
 }
 
 impl Segment {
@@ -268,6 +315,10 @@ impl Segment {
     pub fn is_index(&self) -> bool {
         matches!(self, Self::Index(_))
     }
+
+    pub fn is_wildcard(&self) -> bool {
+        matches!(self, Self::Wildcard)
+    }
 }
 
 impl fmt::Display for Segment {
@@ -284,6 +335,7 @@ impl fmt::Display for Segment {
                     .join(" | ")
             ),
             Segment::Index(i) => f.write_str(&format!("[{}]", i)),
+            Segment::Wildcard => f.write_str("[*]"),
         }
     }
 }
@@ -374,6 +426,14 @@ mod tests {
                 ],
             ),
             (".foo[2]", vec![Field(Regular("foo".to_owned())), Index(2)]),
+            (
+                ".foo[*].bar",
+                vec![
+                    Field(Regular("foo".to_owned())),
+                    Wildcard,
+                    Field(Regular("bar".to_owned())),
+                ],
+            ),
             (
                 r#".foo."bar baz""#,
                 vec![
@@ -417,6 +477,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_expand() {
+        let object = crate::value!({foo: [{bar: 1}, {bar: [2, 3]}], baz: "qux"});
+
+        let cases = vec![
+            (".foo[*].bar", vec![".foo[0].bar", ".foo[1].bar"]),
+            (".foo[*].bar[*]", vec![".foo[1].bar[0]", ".foo[1].bar[1]"]),
+            (".foo[1].bar", vec![".foo[1].bar"]),
+            (".baz[*]", vec![]),
+            (".quux[*]", vec![]),
+        ];
+
+        for (path, expected) in cases {
+            let paths = Path::from_str(path)
+                .unwrap()
+                .expand(&object)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            assert_eq!(paths, expected);
+        }
+    }
+
     #[test]
     fn test_to_alternate_components() {
         let path = Path::from_str(r#".a.(b | c | d | e).f.(g | h | i).(j | k)"#).unwrap();
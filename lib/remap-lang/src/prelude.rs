@@ -5,10 +5,10 @@ pub use crate::{expression, function, inner_type_def, state, value};
 pub use crate::{Error, Expr, Expression, Function, InnerTypeDef, Object, Result, TypeDef, Value};
 
 // commonly used expressions
-pub use crate::expression::{Array, Literal, Map, Noop, Path, Variable};
+pub use crate::expression::{Array, Block, Closure, Literal, Map, Noop, Path, Variable};
 
 // commonly used function types
-pub use crate::function::{ArgumentList, Parameter};
+pub use crate::function::{ArgumentList, ClosureParameter, Parameter};
 
 // commonly used macros
 pub use crate::generate_param_list;
@@ -1,7 +1,7 @@
 use crate::{
     diagnostic::{self, Note},
     parser::{ParsedExpression, Parser},
-    state, value, Diagnostic, Expression, Function, TypeDef,
+    state, value, Diagnostic, Expr, Expression, Function, TypeDef,
 };
 
 /// The constraint applied to the result of a program.
@@ -71,6 +71,7 @@ impl Program {
 
                 diagnostics.push(
                     Diagnostic::error("unexpected return value")
+                        .with_code(118)
                         .with_primary(format!("got: {}", got), span)
                         .with_context(format!("expected: {}", want), span),
                 );
@@ -81,8 +82,14 @@ impl Program {
             .iter()
             .filter(|e| e.type_def(&state).is_fallible())
             .for_each(|e| {
+                let code = match **e {
+                    Expr::Assignment(_) => 103,
+                    _ => 100,
+                };
+
                 diagnostics.push(
                     Diagnostic::error("unhandled error")
+                        .with_code(code)
                         .with_primary("expression can result in runtime error", e.span())
                         .with_context("handle the error case to ensure runtime success", e.span())
                         .with_note(Note::SeeErrDocs),
@@ -229,4 +236,53 @@ mod tests {
             assert_eq!(program, expect);
         }
     }
+
+    #[test]
+    fn program_warnings() {
+        let cases = vec![
+            ("foo = true\nfoo", vec![]),
+            ("foo = true", vec!["unused variable"]),
+            ("_foo = true", vec![]),
+            ("foo = true\nfoo = false\nfoo", vec!["shadowed variable"]),
+            ("foo = true\nfoo = !foo\nfoo", vec![]),
+            ("foo = true\nif true { foo = false }\nfoo", vec![]),
+            ("if true { foo = true } else { foo = false }\nfoo", vec![]),
+            ("foo = true\n[foo]", vec![]),
+            ("abort\n.foo = true", vec!["unreachable code"]),
+            ("if true { abort\n.bar = true }", vec!["unreachable code"]),
+            ("if true { abort }\n.bar = true", vec![]),
+        ];
+
+        for (source, expect) in cases {
+            let (_, diagnostics) = Program::new(source.to_owned(), &[], None, false).unwrap();
+            let warnings = diagnostics
+                .warnings()
+                .into_iter()
+                .map(|diagnostic| diagnostic.message())
+                .collect::<Vec<_>>();
+
+            assert_eq!(warnings, expect, "{}", source);
+        }
+    }
+
+    #[test]
+    fn program_error_codes() {
+        let cases = vec![
+            (".foo.", 119),
+            (r#"foo.bar = "baz""#, 115),
+            (r#"if "nope" { true }"#, 102),
+            ("unknown_function()", 105),
+        ];
+
+        for (source, expect) in cases {
+            let diagnostics = Program::new(source.to_owned(), &[], None, false).unwrap_err();
+            let codes = diagnostics
+                .errors()
+                .into_iter()
+                .map(|diagnostic| diagnostic.code())
+                .collect::<Vec<_>>();
+
+            assert_eq!(codes, vec![Some(expect)], "{}", source);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{state, Expression, Object, Program, Value};
+use crate::{state, Error as E, Expression, Object, Program, Value};
 use std::{error::Error, fmt};
 
 pub type RuntimeResult = Result<Value, Abort>;
@@ -10,11 +10,31 @@ pub struct Runtime {
 
 /// The error raised if the runtime is aborted.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Abort(String);
+pub struct Abort {
+    message: String,
+    explicit: bool,
+}
+
+impl Abort {
+    /// Returns `true` if the program was stopped by an `abort` expression,
+    /// instead of an error.
+    pub fn is_explicit(&self) -> bool {
+        self.explicit
+    }
+}
+
+impl From<E> for Abort {
+    fn from(error: E) -> Self {
+        Self {
+            explicit: error == E::Abort,
+            message: error.to_string(),
+        }
+    }
+}
 
 impl fmt::Display for Abort {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.message)
     }
 }
 
@@ -35,10 +55,7 @@ impl Runtime {
         let mut values = program
             .expressions
             .iter()
-            .map(|expr| {
-                expr.execute(&mut self.state, object)
-                    .map_err(|err| Abort(err.to_string()))
-            })
+            .map(|expr| expr.execute(&mut self.state, object).map_err(Abort::from))
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(values.pop().unwrap_or(Value::Null))
@@ -1,12 +1,28 @@
-use crate::{path::Path, TypeDef, Value};
+use crate::{path::Path, Span, TypeDef, Value};
+use shared::datetime::TimeZone;
 use std::collections::HashMap;
 
 #[derive(Debug, Default)]
 pub struct Program {
     variables: HashMap<String, Value>,
+
+    /// The time zone in which functions interpret the timestamps that don't
+    /// carry an offset of their own.
+    timezone: TimeZone,
 }
 
 impl Program {
+    pub fn with_timezone(timezone: TimeZone) -> Self {
+        Self {
+            timezone,
+            ..Self::default()
+        }
+    }
+
+    pub fn timezone(&self) -> TimeZone {
+        self.timezone
+    }
+
     pub fn variable(&self, key: impl AsRef<str>) -> Option<&Value> {
         self.variables.get(key.as_ref())
     }
@@ -17,7 +33,7 @@ impl Program {
 }
 
 /// State held by the compiler as it parses the program source.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Compiler {
     /// The [`Constraint`] each variable is expected to have.
     ///
@@ -38,6 +54,13 @@ pub struct Compiler {
     /// known bug that we need to fix soon.
     path_query_types: HashMap<Path, TypeDef>,
 
+    /// The last assignment to each variable, and whether the assigned value
+    /// has been used since.
+    ///
+    /// This allows the compiler to warn about variables that are assigned a
+    /// value that is never used.
+    variable_usages: HashMap<String, VariableUsage>,
+
     /// On request, the compiler can store its state in this field, which can
     /// later be used to revert the compiler state to the previously stored
     /// state.
@@ -64,17 +87,27 @@ impl Compiler {
         self.path_query_types.get(key.as_ref())
     }
 
+    pub fn path_query_types(&self) -> &HashMap<Path, TypeDef> {
+        &self.path_query_types
+    }
+
     pub fn path_query_types_mut(&mut self) -> &mut HashMap<Path, TypeDef> {
         &mut self.path_query_types
     }
 
+    pub fn variable_usages_mut(&mut self) -> &mut HashMap<String, VariableUsage> {
+        &mut self.variable_usages
+    }
+
     pub fn track_changes(&mut self) {
         let variable_types = self.variable_types.clone();
         let path_query_types = self.path_query_types.clone();
+        let variable_usages = self.variable_usages.clone();
 
         self.track_changes = Some(Box::new(Self {
             variable_types,
             path_query_types,
+            variable_usages,
             track_changes: None,
         }));
     }
@@ -85,3 +118,16 @@ impl Compiler {
         }
     }
 }
+
+/// The assignment of a value to a variable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VariableUsage {
+    /// The span of the assignment target.
+    pub span: Span,
+
+    /// The block the variable was assigned in.
+    pub scope: usize,
+
+    /// True, if the assigned value has been read.
+    pub used: bool,
+}
@@ -29,27 +29,6 @@ macro_rules! func_args {
     };
 }
 
-#[macro_export]
-macro_rules! bench_function {
-    ($name:tt => $func:path; $($case:ident { args: $args:expr, want: $(Ok($ok:expr))? $(Err($err:expr))? $(,)* })+) => {
-        fn $name(c: &mut criterion::Criterion) {
-            $(
-                c.bench_function(&format!("{}: {}", stringify!($name), stringify!($case)), |b| {
-                    let (expression, want) = $crate::__prep_bench_or_test!($func, $args, $(Ok($crate::Value::from($ok)))? $(Err($err.to_owned()))?);
-                    let mut state = $crate::state::Program::default();
-                    let mut object: $crate::Value = ::std::collections::BTreeMap::default().into();
-
-                    b.iter(|| {
-                        let got = expression.execute(&mut state, &mut object).map_err(|e| e.to_string());
-                        debug_assert_eq!(got, want);
-                        got
-                    })
-                });
-            )+
-        }
-    };
-}
-
 #[macro_export]
 macro_rules! test_function {
     ($name:tt => $func:path; $($case:ident { args: $args:expr, want: $(Ok($ok:expr))? $(Err($err:expr))? $(,)* })+) => {
@@ -66,6 +45,16 @@ macro_rules! test_function {
             assert_eq!(got, want);
         }
         )+}
+
+        /// Returns the compiled expression of each case, keyed by the name of
+        /// the case, for the crate benchmarks to run.
+        #[cfg(feature = "benches")]
+        pub(crate) fn cases() -> (&'static str, Vec<(&'static str, Box<dyn $crate::Expression>)>) {
+            (stringify!($name), vec![$({
+                let (expression, _) = $crate::__prep_bench_or_test!($func, $args, ());
+                (stringify!($case), expression)
+            }),+])
+        }
     };
 }
 
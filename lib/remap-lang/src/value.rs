@@ -872,6 +872,7 @@ impl Value {
                 .as_map()
                 .and_then(|map| fields.iter().find_map(|field| map.get(field.as_str()))),
             Index(index) => self.as_array().and_then(|array| array.get(*index)),
+            Wildcard => None,
         }
     }
 
@@ -897,6 +898,7 @@ impl Value {
                     .and_then(move |field| map.get_mut(field.as_str()))
             }),
             Index(index) => self.as_array_mut().and_then(|array| array.get_mut(*index)),
+            Wildcard => None,
         }
     }
 
@@ -940,7 +942,11 @@ impl Value {
                 })
                 .and_then(|field| self.as_map_mut().and_then(|map| map.remove(field.as_str()))),
 
-            Index(index) => self.as_array_mut().map(|array| array.remove(*index)),
+            Index(index) => self
+                .as_array_mut()
+                .filter(|array| *index < array.len())
+                .map(|array| array.remove(*index)),
+            Wildcard => None,
         };
     }
 
@@ -1113,6 +1119,9 @@ impl Value {
                     *self = array.into();
                 }
             },
+
+            // Wildcards don't point to a single value to update.
+            Wildcard => {}
         }
     }
 }
@@ -1,6 +1,7 @@
 #![allow(non_upper_case_globals)]
 
 use super::Value;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::ops::Deref;
 
@@ -35,6 +36,26 @@ impl Kind {
     pub fn is_scalar(self) -> bool {
         self == self.scalar()
     }
+
+    /// Returns the empty value of an exact [`value::Kind`], such as `""` for
+    /// strings or `{}` for maps.
+    ///
+    /// Returns `None` if the kind is not exact, or if it has no empty value,
+    /// as is the case for timestamps and regular expressions.
+    pub fn default_value(self) -> Option<Value> {
+        let value = match self {
+            Kind::Bytes => Value::from(""),
+            Kind::Integer => Value::Integer(0),
+            Kind::Float => Value::Float(0.0),
+            Kind::Boolean => Value::Boolean(false),
+            Kind::Map => Value::Map(BTreeMap::new()),
+            Kind::Array => Value::Array(vec![]),
+            Kind::Null => Value::Null,
+            _ => return None,
+        };
+
+        Some(value)
+    }
 }
 
 macro_rules! impl_kind {
@@ -156,6 +177,20 @@ impl From<&Value> for Kind {
 mod tests {
     use super::Kind;
 
+    #[test]
+    fn kind_default_value() {
+        use super::Value;
+
+        assert_eq!(Kind::Bytes.default_value(), Some(Value::from("")));
+        assert_eq!(Kind::Integer.default_value(), Some(Value::Integer(0)));
+        assert_eq!(
+            Kind::Map.default_value(),
+            Some(Value::Map(Default::default()))
+        );
+        assert_eq!(Kind::Timestamp.default_value(), None);
+        assert_eq!((Kind::Bytes | Kind::Null).default_value(), None);
+    }
+
     #[test]
     fn kind_is_scalar() {
         let scalars = vec![
@@ -1,5 +1,6 @@
+use crate::datetime::{datetime_to_utc, TimeZone};
 use bytes::Bytes;
-use chrono::{DateTime, Local, ParseError as ChronoParseError, TimeZone, Utc};
+use chrono::{DateTime, ParseError as ChronoParseError, TimeZone as _, Utc};
 use snafu::{ResultExt, Snafu};
 use std::collections::{HashMap, HashSet};
 use std::num::{ParseFloatError, ParseIntError};
@@ -20,13 +21,21 @@ pub enum Conversion {
     Integer,
     Float,
     Boolean,
-    Timestamp,
-    TimestampFmt(String),
+    Timestamp(TimeZone),
+    TimestampFmt(String, TimeZone),
     TimestampTZFmt(String),
 }
 
 impl FromStr for Conversion {
     type Err = ConversionError;
+    /// Convert the string into a type conversion, interpreting timestamps
+    /// without a time zone as local time. See `Conversion::parse`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Conversion::parse(s, TimeZone::Local)
+    }
+}
+
+impl Conversion {
     /// Convert the string into a type conversion. The following
     /// conversion names are supported:
     ///
@@ -37,14 +46,15 @@ impl FromStr for Conversion {
     ///  * `"timestamp"` => Timestamp, guessed using a set of formats
     ///  * `"timestamp|FORMAT"` => Timestamp using the given format
     ///
-    /// Timestamp parsing does not yet support time zones.
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Timestamps that don't include a time zone are interpreted as being in
+    /// the time zone `tz`.
+    pub fn parse(s: &str, tz: TimeZone) -> Result<Self, ConversionError> {
         match s {
             "asis" | "bytes" | "string" => Ok(Conversion::Bytes),
             "integer" | "int" => Ok(Conversion::Integer),
             "float" => Ok(Conversion::Float),
             "bool" | "boolean" => Ok(Conversion::Boolean),
-            "timestamp" => Ok(Conversion::Timestamp),
+            "timestamp" => Ok(Conversion::Timestamp(tz)),
             _ if s.starts_with("timestamp|") => {
                 let fmt = &s[10..];
                 // DateTime<Utc> can only convert timestamps without
@@ -54,7 +64,7 @@ impl FromStr for Conversion {
                 if format_has_zone(fmt) {
                     Ok(Conversion::TimestampTZFmt(fmt.into()))
                 } else {
-                    Ok(Conversion::TimestampFmt(fmt.into()))
+                    Ok(Conversion::TimestampFmt(fmt.into(), tz))
                 }
             }
             _ => Err(ConversionError::UnknownConversion { name: s.into() }),
@@ -66,6 +76,7 @@ impl FromStr for Conversion {
 pub fn parse_check_conversion_map(
     types: &HashMap<String, String>,
     names: &[impl AsRef<str>],
+    tz: TimeZone,
 ) -> Result<HashMap<String, Conversion>, ConversionError> {
     // Check if any named type references a nonexistent field
     let names = names.iter().map(|s| s.as_ref()).collect::<HashSet<_>>();
@@ -78,20 +89,17 @@ pub fn parse_check_conversion_map(
         }
     }
 
-    parse_conversion_map(types)
+    parse_conversion_map(types, tz)
 }
 
 /// Helper function to parse a mapping of conversion descriptions into actual Conversion values.
 pub fn parse_conversion_map(
     types: &HashMap<String, String>,
+    tz: TimeZone,
 ) -> Result<HashMap<String, Conversion>, ConversionError> {
     types
         .iter()
-        .map(|(field, typename)| {
-            typename
-                .parse::<Conversion>()
-                .map(|conv| (field.clone(), conv))
-        })
+        .map(|(field, typename)| Conversion::parse(typename, tz).map(|conv| (field.clone(), conv)))
         .collect()
 }
 
@@ -130,14 +138,14 @@ impl Conversion {
                     .into()
             }
             Conversion::Boolean => parse_bool(&String::from_utf8_lossy(&bytes))?.into(),
-            Conversion::Timestamp => parse_timestamp(&String::from_utf8_lossy(&bytes))?.into(),
-            Conversion::TimestampFmt(format) => {
+            Conversion::Timestamp(tz) => {
+                parse_timestamp(*tz, &String::from_utf8_lossy(&bytes))?.into()
+            }
+            Conversion::TimestampFmt(format, tz) => {
                 let s = String::from_utf8_lossy(&bytes);
-                let dt = Local
-                    .datetime_from_str(&s, &format)
-                    .with_context(|| TimestampParseError { s })?;
-
-                datetime_to_utc(dt).into()
+                tz.datetime_from_str(&s, &format)
+                    .with_context(|| TimestampParseError { s })?
+                    .into()
             }
             Conversion::TimestampTZFmt(format) => {
                 let s = String::from_utf8_lossy(&bytes);
@@ -191,12 +199,8 @@ fn format_has_zone(fmt: &str) -> bool {
         || fmt.find("%+").is_some()
 }
 
-/// Convert a timestamp with a non-UTC time zone into UTC
-fn datetime_to_utc<TZ: TimeZone>(ts: DateTime<TZ>) -> DateTime<Utc> {
-    Utc.timestamp(ts.timestamp(), ts.timestamp_subsec_nanos())
-}
-
-/// The list of allowed "automatic" timestamp formats
+/// The list of allowed "automatic" timestamp formats, interpreted in the
+/// configured time zone
 const TIMESTAMP_FORMATS: &[&str] = &[
     "%F %T",           // YYYY-MM-DD HH:MM:SS
     "%v %T",           // DD-Mmm-YYYY HH:MM:SS
@@ -222,11 +226,12 @@ const TIMESTAMP_TZ_FORMATS: &[&str] = &[
     "%a %d %b %T %#z %Y", // `date` command output, numeric TZ
 ];
 
-/// Parse a string into a timestamp using one of a set of formats
-pub fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, Error> {
+/// Parse a string into a timestamp using one of a set of formats. Timestamps
+/// without a time zone are interpreted as being in the time zone `tz`.
+pub fn parse_timestamp(tz: TimeZone, s: &str) -> Result<DateTime<Utc>, Error> {
     for format in TIMESTAMP_FORMATS {
-        if let Ok(result) = Local.datetime_from_str(s, format) {
-            return Ok(datetime_to_utc(result));
+        if let Ok(result) = tz.datetime_from_str(s, format) {
+            return Ok(result);
         }
     }
     for format in TIMESTAMP_UTC_FORMATS {
@@ -257,7 +262,9 @@ mod tests {
     #[cfg(unix)]
     use super::{Conversion, Error};
     #[cfg(unix)]
-    use chrono::prelude::*;
+    use crate::datetime::TimeZone;
+    #[cfg(unix)]
+    use chrono::{prelude::*, TimeZone as _};
 
     #[cfg(unix)]
     const TIMEZONE: &str = "Australia/Brisbane";
@@ -339,20 +346,55 @@ mod tests {
     #[test]
     fn parse_timestamp_auto() {
         std::env::set_var("TZ", TIMEZONE);
-        assert_eq!(parse_timestamp("2001-02-03 14:05:06"), Ok(dateref()));
-        assert_eq!(parse_timestamp("02/03/2001:14:05:06"), Ok(dateref()));
-        assert_eq!(parse_timestamp("2001-02-03T14:05:06"), Ok(dateref()));
-        assert_eq!(parse_timestamp("2001-02-03T04:05:06Z"), Ok(dateref()));
-        assert_eq!(parse_timestamp("Sat, 3 Feb 2001 14:05:06"), Ok(dateref()));
-        assert_eq!(parse_timestamp("Sat Feb 3 14:05:06 2001"), Ok(dateref()));
-        assert_eq!(parse_timestamp("3-Feb-2001 14:05:06"), Ok(dateref()));
-        assert_eq!(parse_timestamp("2001-02-02T22:05:06-06:00"), Ok(dateref()));
+        let parse = |s| parse_timestamp(TimeZone::Local, s);
+        assert_eq!(parse("2001-02-03 14:05:06"), Ok(dateref()));
+        assert_eq!(parse("02/03/2001:14:05:06"), Ok(dateref()));
+        assert_eq!(parse("2001-02-03T14:05:06"), Ok(dateref()));
+        assert_eq!(parse("2001-02-03T04:05:06Z"), Ok(dateref()));
+        assert_eq!(parse("Sat, 3 Feb 2001 14:05:06"), Ok(dateref()));
+        assert_eq!(parse("Sat Feb 3 14:05:06 2001"), Ok(dateref()));
+        assert_eq!(parse("3-Feb-2001 14:05:06"), Ok(dateref()));
+        assert_eq!(parse("2001-02-02T22:05:06-06:00"), Ok(dateref()));
+        assert_eq!(parse("Sat, 03 Feb 2001 07:05:06 +0300"), Ok(dateref()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_timestamp_named_zone() {
+        // The system time zone must not matter for a named time zone, nor
+        // for timestamps that carry their own offset.
+        std::env::set_var("TZ", "America/New_York");
+        let tz = TimeZone::parse(TIMEZONE).unwrap();
+        assert_eq!(parse_timestamp(tz, "2001-02-03 14:05:06"), Ok(dateref()));
+        assert_eq!(parse_timestamp(tz, "2001-02-03T04:05:06Z"), Ok(dateref()));
         assert_eq!(
-            parse_timestamp("Sat, 03 Feb 2001 07:05:06 +0300"),
+            parse_timestamp(tz, "2001-02-02T22:05:06-06:00"),
             Ok(dateref())
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn timestamp_conversion_named_zone() {
+        std::env::set_var("TZ", "America/New_York");
+        let tz = TimeZone::parse(TIMEZONE).unwrap();
+        for (fmt, value) in &[
+            ("timestamp", "02/03/2001:14:05:06"),
+            ("timestamp|%Y-%m-%d %H:%M:%S", "2001-02-03 14:05:06"),
+            (
+                "timestamp|%Y-%m-%d %H:%M:%S %z",
+                "2001-02-03 14:05:06 +1000",
+            ),
+        ] {
+            assert_eq!(
+                Conversion::parse(fmt, tz)
+                    .unwrap()
+                    .convert::<StubValue>(Bytes::from(*value)),
+                Ok(dateref().into())
+            );
+        }
+    }
+
     // These should perhaps each go into an individual test function to be
     // able to determine what part failed, but that would end up really
     // spamming the test logs.
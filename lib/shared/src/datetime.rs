@@ -0,0 +1,149 @@
+use chrono::{DateTime, Local, NaiveDateTime, ParseError, TimeZone as _, Utc};
+use chrono_tz::Tz;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// The time zone used to interpret timestamps that don't carry an offset of
+/// their own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeZone {
+    /// The system time zone of the host Vector is running on.
+    Local,
+
+    /// A time zone from the IANA time zone database, such as
+    /// `America/New_York`.
+    Named(Tz),
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        TimeZone::Local
+    }
+}
+
+impl TimeZone {
+    /// Parse a time zone name. `"local"` selects the system time zone,
+    /// anything else must be a name from the IANA time zone database.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "" | "local" => Some(TimeZone::Local),
+            _ => s.parse::<Tz>().ok().map(TimeZone::Named),
+        }
+    }
+
+    /// Parse the time zone-less string `s` with the given `format`, as a
+    /// local time in this time zone.
+    pub fn datetime_from_str(&self, s: &str, format: &str) -> Result<DateTime<Utc>, ParseError> {
+        match self {
+            TimeZone::Local => Local.datetime_from_str(s, format).map(datetime_to_utc),
+            TimeZone::Named(tz) => tz.datetime_from_str(s, format).map(datetime_to_utc),
+        }
+    }
+
+    /// Interpret `datetime` as a local time in this time zone. A local time
+    /// repeated by a daylight saving time change resolves to the earliest
+    /// one, and `None` is returned for a local time skipped by the change.
+    pub fn datetime_from_local(&self, datetime: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            TimeZone::Local => Local
+                .from_local_datetime(datetime)
+                .earliest()
+                .map(datetime_to_utc),
+            TimeZone::Named(tz) => tz
+                .from_local_datetime(datetime)
+                .earliest()
+                .map(datetime_to_utc),
+        }
+    }
+}
+
+/// Convert a timestamp with a non-UTC time zone into UTC
+pub fn datetime_to_utc<TZ: chrono::TimeZone>(ts: DateTime<TZ>) -> DateTime<Utc> {
+    Utc.timestamp(ts.timestamp(), ts.timestamp_subsec_nanos())
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZone::Local => f.write_str("local"),
+            TimeZone::Named(tz) => f.write_str(tz.name()),
+        }
+    }
+}
+
+impl Serialize for TimeZone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeZone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(TimeZoneVisitor)
+    }
+}
+
+struct TimeZoneVisitor;
+
+impl<'de> de::Visitor<'de> for TimeZoneVisitor {
+    type Value = TimeZone;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a time zone name")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        TimeZone::parse(s).ok_or_else(|| de::Error::custom(format!("No such time zone: {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone as _};
+
+    #[test]
+    fn parse_time_zone() {
+        assert_eq!(TimeZone::parse("local"), Some(TimeZone::Local));
+        assert_eq!(TimeZone::parse(""), Some(TimeZone::Local));
+        assert_eq!(
+            TimeZone::parse("America/New_York"),
+            Some(TimeZone::Named(Tz::America__New_York))
+        );
+        assert_eq!(TimeZone::parse("Nowhere/Special"), None);
+    }
+
+    #[test]
+    fn datetime_from_str_named() {
+        let tz = TimeZone::parse("America/New_York").unwrap();
+        assert_eq!(
+            tz.datetime_from_str("2021-01-15 08:30:00", "%F %T"),
+            Ok(Utc.ymd(2021, 1, 15).and_hms(13, 30, 0))
+        );
+        // Daylight saving time is taken into account.
+        assert_eq!(
+            tz.datetime_from_str("2021-07-15 08:30:00", "%F %T"),
+            Ok(Utc.ymd(2021, 7, 15).and_hms(12, 30, 0))
+        );
+    }
+
+    #[test]
+    fn datetime_from_local_named() {
+        let tz = TimeZone::parse("Europe/Berlin").unwrap();
+        let datetime = NaiveDate::from_ymd(2021, 3, 1).and_hms(10, 0, 0);
+        assert_eq!(
+            tz.datetime_from_local(&datetime),
+            Some(Utc.ymd(2021, 3, 1).and_hms(9, 0, 0))
+        );
+        // Skipped when the clocks move forward.
+        let datetime = NaiveDate::from_ymd(2021, 3, 28).and_hms(2, 30, 0);
+        assert_eq!(tz.datetime_from_local(&datetime), None);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for name in &["local", "Europe/Berlin", "UTC"] {
+            assert_eq!(TimeZone::parse(name).unwrap().to_string(), *name);
+        }
+    }
+}
@@ -7,5 +7,8 @@ pub mod btreemap;
 #[cfg(feature = "conversion")]
 pub mod conversion;
 
+#[cfg(feature = "datetime")]
+pub mod datetime;
+
 #[cfg(feature = "tokenize")]
 pub mod tokenize;
@@ -10,12 +10,37 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    token: Option<String>,
+    http: reqwest::Client,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self {
+            url,
+            token: None,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Trusts the PEM encoded CA certificate `pem` to verify `https` API servers, besides
+    /// the system ones
+    pub fn with_ca_certificate(mut self, pem: &[u8]) -> anyhow::Result<Self> {
+        let certificate =
+            reqwest::Certificate::from_pem(pem).context("Couldn't parse the CA certificate")?;
+        self.http = reqwest::Client::builder()
+            .add_root_certificate(certificate)
+            .build()
+            .context("Couldn't build the HTTP client")?;
+        Ok(self)
+    }
+
+    /// Authenticates queries with `token`, sent as a bearer token to API servers that
+    /// require one
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
     }
 
     /// Issue a GraphQL query using Reqwest, serializing the response to the associated
@@ -24,13 +49,15 @@ impl Client {
         &self,
         request_body: &graphql_client::QueryBody<T::Variables>,
     ) -> QueryResult<T> {
-        let client = reqwest::Client::new();
+        let mut request = self.http.post(self.url.clone()).json(request_body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        request
             .send()
             .await
+            .and_then(reqwest::Response::error_for_status)
             .with_context(|| {
                 format!(
                     "Couldn't send '{}' query to {}",
@@ -10,11 +10,23 @@ use graphql_client::GraphQLQuery;
 )]
 pub struct MetaVersionStringQuery;
 
+/// MetaBuildInfoQuery returns the build information of the queried Vector instance
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/queries/meta_build_info.graphql",
+    response_derives = "Debug"
+)]
+pub struct MetaBuildInfoQuery;
+
 /// Extension methods for meta queries
 #[async_trait]
 pub trait MetaQueryExt {
     /// Executes a meta version string query
     async fn meta_version_string(&self) -> crate::QueryResult<MetaVersionStringQuery>;
+
+    /// Executes a meta build info query
+    async fn meta_build_info(&self) -> crate::QueryResult<MetaBuildInfoQuery>;
 }
 
 #[async_trait]
@@ -26,4 +38,12 @@ impl MetaQueryExt for crate::Client {
         ))
         .await
     }
+
+    /// Executes a meta build info query
+    async fn meta_build_info(&self) -> crate::QueryResult<MetaBuildInfoQuery> {
+        self.query::<MetaBuildInfoQuery>(&MetaBuildInfoQuery::build_query(
+            meta_build_info_query::Variables,
+        ))
+        .await
+    }
 }
@@ -83,6 +83,46 @@ pub struct ComponentProcessedBytesThroughputsSubscription;
 )]
 pub struct ComponentProcessedBytesTotalsSubscription;
 
+/// ComponentErrorsTotalsSubscription contains metrics on the number of errors of a Vector
+/// instance, against specific components
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_errors_totals.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorsTotalsSubscription;
+
+/// ComponentErrorsThroughputsSubscription contains metrics on the number of errors between
+/// `interval` samples, against specific components
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_errors_throughputs.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentErrorsThroughputsSubscription;
+
+/// ComponentBufferUsagesSubscription contains metrics on how full the buffers of components
+/// are, and how many events they dropped
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_buffer_usages.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentBufferUsagesSubscription;
+
+/// ComponentBufferUsageChangesSubscription contains the buffer metrics of the components whose
+/// buffers filled up or drained
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_buffer_usage_changes.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentBufferUsageChangesSubscription;
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription
@@ -129,6 +169,30 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentProcessedBytesThroughputsSubscription>;
+
+    /// Executes a component errors totals subscription
+    fn component_errors_totals_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorsTotalsSubscription>;
+
+    /// Executes a component errors throughputs subscription
+    fn component_errors_throughputs_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentErrorsThroughputsSubscription>;
+
+    /// Executes a component buffer usages subscription
+    fn component_buffer_usages_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentBufferUsagesSubscription>;
+
+    /// Executes a component buffer usage changes subscription
+    fn component_buffer_usage_changes_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentBufferUsageChangesSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -222,4 +286,52 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentProcessedBytesThroughputsSubscription>(&request_body)
     }
+
+    /// Executes an all component errors totals subscription
+    fn component_errors_totals_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorsTotalsSubscription> {
+        let request_body = ComponentErrorsTotalsSubscription::build_query(
+            component_errors_totals_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorsTotalsSubscription>(&request_body)
+    }
+
+    /// Executes an all component errors throughputs subscription
+    fn component_errors_throughputs_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentErrorsThroughputsSubscription> {
+        let request_body = ComponentErrorsThroughputsSubscription::build_query(
+            component_errors_throughputs_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentErrorsThroughputsSubscription>(&request_body)
+    }
+
+    /// Executes an all component buffer usages subscription
+    fn component_buffer_usages_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentBufferUsagesSubscription> {
+        let request_body = ComponentBufferUsagesSubscription::build_query(
+            component_buffer_usages_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentBufferUsagesSubscription>(&request_body)
+    }
+
+    /// Executes a component buffer usage changes subscription
+    fn component_buffer_usage_changes_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentBufferUsageChangesSubscription> {
+        let request_body = ComponentBufferUsageChangesSubscription::build_query(
+            component_buffer_usage_changes_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentBufferUsageChangesSubscription>(&request_body)
+    }
 }
@@ -4,8 +4,10 @@ mod components;
 mod health;
 mod meta;
 mod metrics;
+mod tap;
 
 pub use self::meta::*;
 pub use components::*;
 pub use health::*;
 pub use metrics::*;
+pub use tap::*;
@@ -0,0 +1,47 @@
+//! Tap subscriptions, for sampling the events flowing through a component
+
+use crate::BoxedSubscription;
+use graphql_client::GraphQLQuery;
+
+/// TapSubscription returns samples of the events flowing into or out of a component, along
+/// with notifications when the component is added, removed or rebuilt
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/tap.graphql",
+    response_derives = "Debug"
+)]
+pub struct TapSubscription;
+
+pub use tap_subscription::EventDirection;
+
+pub trait TapSubscriptionExt {
+    /// Executes a tap subscription on `component_name`
+    fn tap_subscription(
+        &self,
+        component_name: String,
+        direction: EventDirection,
+        interval: i64,
+        limit: i64,
+    ) -> crate::BoxedSubscription<TapSubscription>;
+}
+
+impl TapSubscriptionExt for crate::SubscriptionClient {
+    /// Executes a tap subscription on `component_name`
+    fn tap_subscription(
+        &self,
+        component_name: String,
+        direction: EventDirection,
+        interval: i64,
+        limit: i64,
+    ) -> BoxedSubscription<TapSubscription> {
+        let request_body = TapSubscription::build_query(tap_subscription::Variables {
+            component_name,
+            direction: Some(direction),
+            interval,
+            limit,
+        });
+
+        self.start::<TapSubscription>(&request_body)
+    }
+}
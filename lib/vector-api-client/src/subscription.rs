@@ -7,14 +7,27 @@ use std::{
     sync::{Arc, Mutex, Weak},
 };
 use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
     stream::{Stream, StreamExt},
-    sync::{broadcast, mpsc, oneshot},
+    sync::{broadcast, mpsc, oneshot, watch},
+};
+use tokio_tungstenite::{
+    client_async, connect_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::AUTHORIZATION, HeaderValue},
+        Error, Message,
+    },
+    WebSocketStream,
 };
-use tokio_tungstenite::{connect_async, tungstenite::Message};
 use url::Url;
 use uuid::Uuid;
 use weak_table::WeakValueHashMap;
 
+/// Error connecting to a WebSocket GraphQL server endpoint
+pub type ConnectionError = Error;
+
 /// Subscription GraphQL response, returned from an active stream
 pub type StreamResponse<T> = Pin<
     Box<
@@ -58,6 +71,15 @@ impl Payload {
         }
     }
 
+    /// Returns a "complete" payload, which ends the stream of the subscription
+    fn complete(id: Uuid) -> Self {
+        Self {
+            id,
+            payload_type: "complete".to_owned(),
+            payload: serde_json::Value::Null,
+        }
+    }
+
     /// Attempts to return a definitive ResponseData on the `payload` field, matched against
     /// a generated GraphQLQuery
     fn response<T: GraphQLQuery + Send + Sync>(
@@ -143,6 +165,7 @@ impl<T: GraphQLQuery + Send + Sync> Receiver<T> for Subscription {
 pub struct SubscriptionClient {
     tx: mpsc::UnboundedSender<Payload>,
     subscriptions: Arc<Mutex<WeakValueHashMap<Uuid, Weak<Subscription>>>>,
+    closed_rx: watch::Receiver<bool>,
     _shutdown_tx: oneshot::Sender<()>,
 }
 
@@ -156,6 +179,9 @@ impl SubscriptionClient {
         let subscriptions = Arc::new(Mutex::new(WeakValueHashMap::new()));
         let subscriptions_clone = Arc::clone(&subscriptions);
 
+        // Set once the connection to the server closes
+        let (closed_tx, closed_rx) = watch::channel(false);
+
         // Spawn a handler for shutdown, and relaying received `Payload`s back to the relevant
         // subscription
         tokio::spawn(async move {
@@ -166,25 +192,49 @@ impl SubscriptionClient {
                     _ = &mut shutdown_rx => break,
 
                     // Handle receiving payloads back _from_ the server
-                    Some(p) = rx.next() => {
-                        let s = subscriptions_clone.lock().unwrap().get::<Uuid>(&p.id);
-                        if let Some(s) = s
-                            as Option<Arc<Subscription>>
-                        {
-                            let _ = s.receive(p);
+                    p = rx.next() => match p {
+                        Some(p) => {
+                            let s = subscriptions_clone.lock().unwrap().get::<Uuid>(&p.id);
+                            if let Some(s) = s
+                                as Option<Arc<Subscription>>
+                            {
+                                let _ = s.receive(p);
+                            }
+                        }
+                        // The connection closed, so complete every subscription, ending
+                        // their streams
+                        None => {
+                            for (id, s) in subscriptions_clone.lock().unwrap().iter() {
+                                let _ = s.receive(Payload::complete(*id));
+                            }
+                            break;
                         }
                     }
                 }
             }
+
+            let _ = closed_tx.broadcast(true);
         });
 
         Self {
             tx,
             subscriptions,
+            closed_rx,
             _shutdown_tx,
         }
     }
 
+    /// Resolves once the connection to the server closes, after which no subscription
+    /// receives any more payloads
+    pub async fn closed(&self) {
+        let mut closed_rx = self.closed_rx.clone();
+        while let Some(closed) = closed_rx.recv().await {
+            if closed {
+                return;
+            }
+        }
+    }
+
     /// Start a new subscription request
     pub fn start<T: GraphQLQuery + Send + Sync>(
         &self,
@@ -217,10 +267,67 @@ impl SubscriptionClient {
 /// Connect to a new WebSocket GraphQL server endpoint, and return a `SubscriptionClient`.
 /// This method will a) connect to a ws(s):// endpoint, and perform the initial handshake, and b)
 /// set up channel forwarding to expose just the returned `Payload`s to the client.
-pub async fn connect_subscription_client(
+pub async fn connect_subscription_client(url: Url) -> Result<SubscriptionClient, Error> {
+    connect_subscription_client_with_token(url, None).await
+}
+
+/// Connect to a new WebSocket GraphQL server endpoint in the same way as
+/// `connect_subscription_client`, authenticating the handshake with `token`, sent as a bearer
+/// token to API servers that require one.
+pub async fn connect_subscription_client_with_token(
+    url: Url,
+    token: Option<&str>,
+) -> Result<SubscriptionClient, Error> {
+    connect_subscription_client_with_tls(url, token, None).await
+}
+
+/// Connect to a new WebSocket GraphQL server endpoint in the same way as
+/// `connect_subscription_client_with_token`, verifying a wss:// endpoint against the PEM
+/// encoded CA certificate `ca_certificate`, if any, besides the system ones.
+pub async fn connect_subscription_client_with_tls(
     url: Url,
-) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
-    let (ws, _) = connect_async(url).await?;
+    token: Option<&str>,
+    ca_certificate: Option<&[u8]>,
+) -> Result<SubscriptionClient, Error> {
+    let host = url.host_str().unwrap_or_default().to_owned();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let tls = url.scheme() == "wss";
+
+    let mut request = url.into_client_request()?;
+    if let Some(token) = token {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|error| Error::HttpFormat(error.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+
+    match ca_certificate {
+        Some(pem) if tls => {
+            let certificate = native_tls::Certificate::from_pem(pem).map_err(Error::Tls)?;
+            let connector = native_tls::TlsConnector::builder()
+                .add_root_certificate(certificate)
+                .build()
+                .map_err(Error::Tls)?;
+            let stream = TcpStream::connect((host.as_str(), port)).await?;
+            let stream = tokio_native_tls::TlsConnector::from(connector)
+                .connect(&host, stream)
+                .await
+                .map_err(Error::Tls)?;
+
+            let (ws, _) = client_async(request, stream).await?;
+            Ok(spawn_subscription_client(ws))
+        }
+        _ => {
+            let (ws, _) = connect_async(request).await?;
+            Ok(spawn_subscription_client(ws))
+        }
+    }
+}
+
+/// Forwards payloads between a new `SubscriptionClient` and the connected WebSocket.
+fn spawn_subscription_client<S>(ws: WebSocketStream<S>) -> SubscriptionClient
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
     let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
 
     let (send_tx, mut send_rx) = mpsc::unbounded_channel::<Payload>();
@@ -228,25 +335,28 @@ pub async fn connect_subscription_client(
 
     // Forwarded received messages back upstream to the GraphQL server
     tokio::spawn(async move {
-        loop {
-            if let Some(p) = send_rx.next().await {
-                let _ = ws_tx
-                    .send(Message::Text(serde_json::to_string(&p).unwrap()))
-                    .await;
-            }
+        while let Some(p) = send_rx.next().await {
+            let _ = ws_tx
+                .send(Message::Text(serde_json::to_string(&p).unwrap()))
+                .await;
         }
     });
 
-    // Forward received messages to the receiver channel
+    // Forward received messages to the receiver channel, until the connection closes. This
+    // drops `recv_tx`, which tells the client it's disconnected
     tokio::spawn(async move {
-        loop {
-            if let Some(Ok(Message::Text(m))) = ws_rx.next().await {
-                if let Ok(p) = serde_json::from_str::<Payload>(&m) {
-                    let _ = recv_tx.send(p);
+        while let Some(Ok(m)) = ws_rx.next().await {
+            match m {
+                Message::Text(m) => {
+                    if let Ok(p) = serde_json::from_str::<Payload>(&m) {
+                        let _ = recv_tx.send(p);
+                    }
                 }
+                Message::Close(_) => break,
+                _ => (),
             }
         }
     });
 
-    Ok(SubscriptionClient::new(send_tx, recv_rx))
+    SubscriptionClient::new(send_tx, recv_rx)
 }
@@ -0,0 +1,64 @@
+use futures::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use vector::{
+    config::{DataType, GlobalOptions, SourceConfig},
+    event::Event,
+    shutdown::ShutdownSignal,
+    sources::Source,
+    Pipeline,
+};
+
+/// Source fed by the embedding application, through the `EmbeddedInput`
+/// returned by `EmbeddedBuilder::add_input`.
+///
+/// The config is only serializable, so that it isn't registered along with
+/// the sources of config files.
+#[derive(Debug, Serialize)]
+pub(crate) struct EmbeddedInputConfig {
+    #[serde(skip)]
+    receiver: Arc<Mutex<Option<mpsc::Receiver<Event>>>>,
+}
+
+impl EmbeddedInputConfig {
+    pub(crate) fn new(receiver: mpsc::Receiver<Event>) -> Self {
+        Self {
+            receiver: Arc::new(Mutex::new(Some(receiver))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serialize(name = "embedded_input")]
+impl SourceConfig for EmbeddedInputConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> vector::Result<Source> {
+        let receiver = self
+            .receiver
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("Embedded input can only be built once.")?;
+
+        let mut out = out.sink_map_err(|error| error!(message = "Error sending event.", %error));
+        let mut events = receiver.take_until(shutdown).map(Ok);
+
+        Ok(Box::pin(async move { out.send_all(&mut events).await }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "embedded_input"
+    }
+
+    fn typetag_deserialize(&self) {}
+}
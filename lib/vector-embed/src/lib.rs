@@ -0,0 +1,243 @@
+//! The API for running a Vector topology inside another Rust application.
+//!
+//! A topology is described in code with a `ConfigBuilder`, or loaded from a
+//! config string, and wrapped in an `EmbeddedBuilder`. Besides regular
+//! components the builder takes inputs, which the application pushes events
+//! into, and outputs, which hand events back to the application.
+//!
+//! ```ignore
+//! let mut builder = EmbeddedBuilder::new();
+//! let mut input = builder.add_input("in");
+//! let mut output = builder.add_output("out", &["in"]);
+//!
+//! let topology = builder.start().await?;
+//! input.send(event).await?;
+//! let event = output.recv().await;
+//! topology.stop().await;
+//! ```
+//!
+//! Only the types of this crate, and the config and event types it
+//! re-exports, are part of the API. The rest of the `vector` crate is subject
+//! to change.
+
+#[macro_use]
+extern crate tracing;
+
+mod input;
+mod output;
+
+pub use vector::{
+    config::{ConfigBuilder, Format, FormatHint},
+    event::{Event, LogEvent, Metric, Value},
+};
+
+use futures::StreamExt;
+use input::EmbeddedInputConfig;
+use output::EmbeddedOutputConfig;
+use std::{collections::HashMap, fmt};
+use tokio::sync::mpsc;
+use vector::{
+    config::{self, ConfigDiff},
+    topology::{self, RunningTopology},
+};
+
+/// Default number of events buffered between the application and each input
+/// or output.
+const DEFAULT_BUFFER_SIZE: usize = 100;
+
+/// Builds a topology to run embedded in the calling application.
+#[derive(Debug, Default)]
+pub struct EmbeddedBuilder {
+    config: ConfigBuilder,
+    buffer_size: Option<usize>,
+    require_healthy: Option<bool>,
+}
+
+impl EmbeddedBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config_builder(config: ConfigBuilder) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Returns a builder for the topology described by `input`, in the given
+    /// config format.
+    pub fn from_config_str(input: &str, format: FormatHint) -> Result<Self, Vec<String>> {
+        let config = config::load_builder_from_str(input, format)?;
+        Ok(Self::from_config_builder(config))
+    }
+
+    /// Number of events buffered between the application and each input or
+    /// output added after this call.
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = Some(buffer_size);
+        self
+    }
+
+    /// Fail `start` if any sink healthcheck fails.
+    pub fn require_healthy(mut self, require_healthy: bool) -> Self {
+        self.require_healthy = Some(require_healthy);
+        self
+    }
+
+    /// Gives access to the underlying config, to add regular components.
+    pub fn config_mut(&mut self) -> &mut ConfigBuilder {
+        &mut self.config
+    }
+
+    /// Adds a source named `name`, and returns the input used to push events
+    /// into it. The source finishes once every clone of the returned input is
+    /// dropped.
+    pub fn add_input(&mut self, name: impl Into<String>) -> EmbeddedInput {
+        let (sender, receiver) = mpsc::channel(self.channel_size());
+        self.config
+            .add_source(name, EmbeddedInputConfig::new(receiver));
+        EmbeddedInput { sender }
+    }
+
+    /// Adds a sink named `name`, reading from `inputs`, and returns the
+    /// output receiving the events reaching it.
+    pub fn add_output(&mut self, name: impl Into<String>, inputs: &[&str]) -> EmbeddedOutput {
+        let (sender, receiver) = mpsc::channel(self.channel_size());
+        self.config
+            .add_sink(name, inputs, EmbeddedOutputConfig::new(sender));
+        EmbeddedOutput { receiver }
+    }
+
+    /// Validates the config, then builds and starts the topology on the
+    /// current Tokio runtime.
+    pub async fn start(self) -> Result<EmbeddedTopology, Vec<String>> {
+        let mut config = self.config.build()?;
+        config
+            .healthchecks
+            .set_require_healthy(self.require_healthy);
+
+        let diff = ConfigDiff::initial(&config);
+        let pieces = topology::builder::build_pieces(&config, &diff, HashMap::new()).await?;
+
+        let (topology, graceful_crash) = topology::start_validated(config, diff, pieces)
+            .await
+            .ok_or_else(|| vec!["Sinks failed their healthchecks.".to_owned()])?;
+
+        Ok(EmbeddedTopology {
+            topology,
+            graceful_crash,
+        })
+    }
+
+    fn channel_size(&self) -> usize {
+        self.buffer_size.unwrap_or(DEFAULT_BUFFER_SIZE)
+    }
+}
+
+/// Pushes events into a source added by `EmbeddedBuilder::add_input`.
+#[derive(Clone, Debug)]
+pub struct EmbeddedInput {
+    sender: mpsc::Sender<Event>,
+}
+
+impl EmbeddedInput {
+    /// Sends an event into the topology, waiting while the source is busy.
+    pub async fn send(&mut self, event: Event) -> Result<(), InputClosed> {
+        self.sender.send(event).await.map_err(|_| InputClosed)
+    }
+}
+
+/// The topology of an input was stopped.
+#[derive(Debug)]
+pub struct InputClosed;
+
+impl fmt::Display for InputClosed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Embedded input is closed.")
+    }
+}
+
+impl std::error::Error for InputClosed {}
+
+/// Receives the events reaching a sink added by `EmbeddedBuilder::add_output`.
+#[derive(Debug)]
+pub struct EmbeddedOutput {
+    receiver: mpsc::Receiver<Event>,
+}
+
+impl EmbeddedOutput {
+    /// Receives the next event, or `None` once the topology is stopped.
+    pub async fn recv(&mut self) -> Option<Event> {
+        self.receiver.recv().await
+    }
+}
+
+/// A topology started by `EmbeddedBuilder::start`.
+pub struct EmbeddedTopology {
+    topology: RunningTopology,
+    graceful_crash: mpsc::UnboundedReceiver<()>,
+}
+
+impl EmbeddedTopology {
+    /// Resolves once either a component crashed or all sources finished, in
+    /// which case the topology should be stopped.
+    pub async fn finished(&mut self) {
+        let sources_finished = self.topology.sources_finished();
+        tokio::select! {
+            _ = self.graceful_crash.next() => (),
+            _ = sources_finished => (),
+        }
+    }
+
+    /// Gracefully shuts down the topology, resolving once every component has
+    /// finished.
+    pub async fn stop(self) {
+        self.topology.stop().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector::config::log_schema;
+
+    #[tokio::test]
+    async fn passes_events_through() {
+        let mut builder = EmbeddedBuilder::new();
+        let mut input = builder.add_input("in");
+        let mut output = builder.add_output("out", &["in"]);
+        let topology = builder.start().await.unwrap();
+
+        input.send(Event::from("hello")).await.unwrap();
+        let event = output.recv().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "hello".into());
+
+        drop(input);
+        topology.stop().await;
+    }
+
+    #[tokio::test]
+    async fn from_config_str() {
+        let mut builder = EmbeddedBuilder::from_config_str(
+            r#"
+            [transforms.add]
+            type = "add_fields"
+            inputs = ["in"]
+            fields.tag = "embedded"
+            "#,
+            Some(Format::TOML),
+        )
+        .unwrap();
+        let mut input = builder.add_input("in");
+        let mut output = builder.add_output("out", &["add"]);
+        let topology = builder.start().await.unwrap();
+
+        input.send(Event::from("hello")).await.unwrap();
+        let event = output.recv().await.unwrap();
+        assert_eq!(event.as_log()["tag"], "embedded".into());
+
+        drop(input);
+        topology.stop().await;
+    }
+}
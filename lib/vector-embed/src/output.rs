@@ -0,0 +1,71 @@
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use vector::{
+    buffers::Acker,
+    config::{DataType, SinkConfig, SinkContext},
+    event::Event,
+    sinks::{util::StreamSink, Healthcheck, VectorSink},
+};
+
+/// Sink handing events back to the embedding application, through the
+/// `EmbeddedOutput` returned by `EmbeddedBuilder::add_output`.
+///
+/// The config is only serializable, so that it isn't registered along with
+/// the sinks of config files.
+#[derive(Debug, Serialize)]
+pub(crate) struct EmbeddedOutputConfig {
+    #[serde(skip)]
+    sender: mpsc::Sender<Event>,
+}
+
+impl EmbeddedOutputConfig {
+    pub(crate) fn new(sender: mpsc::Sender<Event>) -> Self {
+        Self { sender }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serialize(name = "embedded_output")]
+impl SinkConfig for EmbeddedOutputConfig {
+    async fn build(&self, cx: SinkContext) -> vector::Result<(VectorSink, Healthcheck)> {
+        let sink = EmbeddedOutput {
+            sender: self.sender.clone(),
+            acker: cx.acker(),
+        };
+
+        Ok((VectorSink::Stream(Box::new(sink)), future::ok(()).boxed()))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "embedded_output"
+    }
+
+    fn typetag_deserialize(&self) {}
+}
+
+struct EmbeddedOutput {
+    sender: mpsc::Sender<Event>,
+    acker: Acker,
+}
+
+#[async_trait::async_trait]
+impl StreamSink for EmbeddedOutput {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        while let Some(event) = input.next().await {
+            // A dropped receiver only means the application stopped listening,
+            // so events are still acknowledged to keep the topology flowing.
+            if self.sender.send(event).await.is_err() {
+                debug!(message = "Embedded output receiver dropped; discarding event.");
+            }
+
+            self.acker.ack(1);
+        }
+
+        Ok(())
+    }
+}
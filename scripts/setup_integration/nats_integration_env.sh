@@ -21,13 +21,13 @@ ACTION=$1
 start_podman () {
   podman pod create --replace --name vector-test-integration-nats -p 4222:4222
   podman run -d --pod=vector-test-integration-nats  --name vector_nats \
-	 nats
+	 nats -js
 }
 
 start_docker () {
   docker network create vector-test-integration-nats
   docker run -d --network=vector-test-integration-nats -p 4222:4222 --name vector_nats \
-	 nats
+	 nats -js
 }
 
 stop_podman () {
@@ -0,0 +1,310 @@
+//! Selection of the global memory allocator, and the statistics it reports.
+//!
+//! The allocators Vector can use are picked at build time with the
+//! `allocator-jemalloc` and `allocator-mimalloc` features. When any of them is
+//! built in, the `VECTOR_ALLOCATOR` environment variable selects one of them,
+//! or the system allocator, on startup. The allocator can't be changed later
+//! on, as memory has to be freed by the allocator it was allocated with.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The environment variable selecting the allocator.
+pub const ALLOCATOR_ENV_VAR: &str = "VECTOR_ALLOCATOR";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Allocator {
+    System,
+    #[cfg(feature = "allocator-jemalloc")]
+    Jemalloc,
+    #[cfg(feature = "allocator-mimalloc")]
+    Mimalloc,
+}
+
+impl Allocator {
+    /// The allocator used unless another one is selected, which is the first one
+    /// built in.
+    const DEFAULT: Allocator = {
+        #[cfg(feature = "allocator-jemalloc")]
+        let default = Allocator::Jemalloc;
+        #[cfg(all(feature = "allocator-mimalloc", not(feature = "allocator-jemalloc")))]
+        let default = Allocator::Mimalloc;
+        #[cfg(not(any(feature = "allocator-jemalloc", feature = "allocator-mimalloc")))]
+        let default = Allocator::System;
+
+        default
+    };
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Allocator::System => "system",
+            #[cfg(feature = "allocator-jemalloc")]
+            Allocator::Jemalloc => "jemalloc",
+            #[cfg(feature = "allocator-mimalloc")]
+            Allocator::Mimalloc => "mimalloc",
+        }
+    }
+
+    fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"system" => Some(Allocator::System),
+            #[cfg(feature = "allocator-jemalloc")]
+            b"jemalloc" => Some(Allocator::Jemalloc),
+            #[cfg(feature = "allocator-mimalloc")]
+            b"mimalloc" => Some(Allocator::Mimalloc),
+            _ => None,
+        }
+    }
+
+    fn id(self) -> u8 {
+        match self {
+            Allocator::System => 1,
+            #[cfg(feature = "allocator-jemalloc")]
+            Allocator::Jemalloc => 2,
+            #[cfg(feature = "allocator-mimalloc")]
+            Allocator::Mimalloc => 3,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Allocator::System),
+            #[cfg(feature = "allocator-jemalloc")]
+            2 => Some(Allocator::Jemalloc),
+            #[cfg(feature = "allocator-mimalloc")]
+            3 => Some(Allocator::Mimalloc),
+            _ => None,
+        }
+    }
+}
+
+/// The allocator selected by the first allocation, or zero before that.
+static SELECTED: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the allocator used by Vector.
+pub fn selected() -> Allocator {
+    Allocator::from_id(SELECTED.load(Ordering::Relaxed)).unwrap_or_else(select)
+}
+
+/// Returns the name of the allocator requested through `VECTOR_ALLOCATOR`, if
+/// it isn't built into Vector, and so the default allocator is used instead.
+pub fn unavailable_requested() -> Option<String> {
+    let name = std::env::var(ALLOCATOR_ENV_VAR).ok()?;
+
+    if Allocator::from_name(name.as_bytes()).is_some() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+#[cold]
+fn select() -> Allocator {
+    let allocator = requested().unwrap_or(Allocator::DEFAULT);
+
+    // Another thread may have raced this one to the first allocation, in which
+    // case its selection wins.
+    match SELECTED.compare_exchange(0, allocator.id(), Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => allocator,
+        Err(id) => Allocator::from_id(id).expect("Selected allocator is valid."),
+    }
+}
+
+/// Reads the allocator from the environment without allocating, as this runs
+/// within the first allocation.
+#[cfg(unix)]
+fn requested() -> Option<Allocator> {
+    use std::ffi::CStr;
+
+    // SAFETY: The name is nul-terminated, and the returned value, if any, is a
+    // nul-terminated string that isn't modified while it's read, as the
+    // environment is only changed after startup, if ever.
+    unsafe {
+        let value = libc::getenv(b"VECTOR_ALLOCATOR\0".as_ptr() as *const libc::c_char);
+        if value.is_null() {
+            None
+        } else {
+            Allocator::from_name(CStr::from_ptr(value).to_bytes())
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn requested() -> Option<Allocator> {
+    None
+}
+
+/// The global allocator, forwarding to the selected allocator.
+pub struct SelectedAllocator;
+
+#[cfg(feature = "allocator-jemalloc")]
+static JEMALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+
+#[cfg(feature = "allocator-mimalloc")]
+static MIMALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+macro_rules! forward {
+    ($method:ident($($arg:expr),*)) => {
+        match selected() {
+            Allocator::System => System.$method($($arg),*),
+            #[cfg(feature = "allocator-jemalloc")]
+            Allocator::Jemalloc => JEMALLOC.$method($($arg),*),
+            #[cfg(feature = "allocator-mimalloc")]
+            Allocator::Mimalloc => MIMALLOC.$method($($arg),*),
+        }
+    };
+}
+
+// SAFETY: All calls are forwarded to the same allocator, which never changes
+// once selected.
+unsafe impl GlobalAlloc for SelectedAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        forward!(alloc(layout))
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        forward!(dealloc(ptr, layout))
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        forward!(alloc_zeroed(layout))
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        forward!(realloc(ptr, layout, new_size))
+    }
+}
+
+/// Memory usage as reported by the allocator.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocatorStats {
+    /// Bytes allocated by Vector, if the allocator tracks them.
+    pub allocated: Option<u64>,
+    /// Bytes in the pages the allocator holds allocations in.
+    pub active: u64,
+    /// Bytes of physical memory the allocator holds.
+    pub resident: u64,
+}
+
+/// Reads the memory usage of the selected allocator, or returns `None` if it
+/// doesn't report any, as is the case for the system allocator.
+pub fn stats() -> Option<AllocatorStats> {
+    match selected() {
+        Allocator::System => None,
+        #[cfg(feature = "allocator-jemalloc")]
+        Allocator::Jemalloc => jemalloc_stats(),
+        #[cfg(feature = "allocator-mimalloc")]
+        Allocator::Mimalloc => mimalloc_stats(),
+    }
+}
+
+#[cfg(feature = "allocator-jemalloc")]
+fn jemalloc_stats() -> Option<AllocatorStats> {
+    use std::{mem, os::raw::c_void, ptr};
+
+    fn read(name: &[u8]) -> Option<u64> {
+        let mut value: usize = 0;
+        let mut len = mem::size_of::<usize>();
+
+        // SAFETY: The name is nul-terminated, and the value is a `size_t`,
+        // along with its length.
+        let result = unsafe {
+            jemalloc_sys::mallctl(
+                name.as_ptr() as *const _,
+                &mut value as *mut usize as *mut c_void,
+                &mut len,
+                ptr::null_mut(),
+                0,
+            )
+        };
+
+        if result == 0 {
+            Some(value as u64)
+        } else {
+            None
+        }
+    }
+
+    // The statistics are cached by jemalloc, and only refreshed when the epoch
+    // is advanced.
+    let mut epoch: u64 = 1;
+    // SAFETY: The name is nul-terminated, and the new value is a `u64`, along
+    // with its length.
+    let result = unsafe {
+        jemalloc_sys::mallctl(
+            b"epoch\0".as_ptr() as *const _,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut epoch as *mut u64 as *mut c_void,
+            mem::size_of::<u64>(),
+        )
+    };
+    if result != 0 {
+        return None;
+    }
+
+    Some(AllocatorStats {
+        allocated: Some(read(b"stats.allocated\0")?),
+        active: read(b"stats.active\0")?,
+        resident: read(b"stats.resident\0")?,
+    })
+}
+
+#[cfg(feature = "allocator-mimalloc")]
+fn mimalloc_stats() -> Option<AllocatorStats> {
+    let mut elapsed = 0;
+    let mut user = 0;
+    let mut system = 0;
+    let mut current_rss = 0;
+    let mut peak_rss = 0;
+    let mut current_commit = 0;
+    let mut peak_commit = 0;
+    let mut page_faults = 0;
+
+    // SAFETY: All the pointers are valid for writes of a `size_t`.
+    unsafe {
+        libmimalloc_sys::mi_process_info(
+            &mut elapsed,
+            &mut user,
+            &mut system,
+            &mut current_rss,
+            &mut peak_rss,
+            &mut current_commit,
+            &mut peak_commit,
+            &mut page_faults,
+        );
+    }
+
+    Some(AllocatorStats {
+        allocated: None,
+        active: current_commit as u64,
+        resident: current_rss as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_allocator_once() {
+        let allocator = selected();
+        assert_eq!(selected(), allocator);
+        assert_eq!(
+            Allocator::from_name(allocator.as_str().as_bytes()),
+            Some(allocator)
+        );
+    }
+
+    #[cfg(any(feature = "allocator-jemalloc", feature = "allocator-mimalloc"))]
+    #[test]
+    fn reports_stats() {
+        if selected() == Allocator::System {
+            return;
+        }
+
+        let stats = stats().expect("Allocator reports stats.");
+        assert!(stats.resident > 0);
+        assert!(stats.active > 0);
+    }
+}
@@ -1,8 +1,76 @@
+use super::schema::components::topology::{ComponentStatus, Topology};
 use serde_json::json;
-use warp::reply::json;
-use warp::{Rejection, Reply};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use warp::http::StatusCode;
+use warp::reply::{json, with_header, with_status};
+use warp::{reject::Reject, Rejection, Reply};
 
-// Health handler, responds with { ok: true }
+/// Rejection of requests without the configured bearer token
+#[derive(Debug)]
+pub struct Unauthorized;
+
+impl Reject for Unauthorized {}
+
+// Health handler, responds with { ok: true } and the status of each component for as long as
+// the API is serving. It only tells the process is alive, failed components are reported by
+// the readiness handler
 pub async fn health() -> Result<impl Reply, Rejection> {
-    Ok(json(&json!({"ok": true})))
+    Ok(status_reply(true, Topology::current().statuses()))
+}
+
+// Readiness handler, responds with { ok: true } and the status of each component once the
+// topology is running, and every component is running or healthy. Otherwise responds with
+// { ok: false } and a 503
+pub async fn ready(ready: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
+    let components = Topology::current().statuses();
+    let ok = ready.load(Ordering::Relaxed) && components.values().all(|status| status.is_ok());
+    Ok(status_reply(ok, components))
+}
+
+fn status_reply(ok: bool, components: BTreeMap<String, ComponentStatus>) -> impl Reply {
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    with_status(json(&json!({ "ok": ok, "components": components })), status)
+}
+
+// Unauthorized handler, responds with { ok: false } and a 401 to requests rejected as
+// `Unauthorized`, passing on other rejections
+pub async fn unauthorized(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(with_header(
+            with_status(json(&json!({ "ok": false })), StatusCode::UNAUTHORIZED),
+            "www-authenticate",
+            "Bearer",
+        ))
+    } else {
+        Err(rejection)
+    }
+}
+
+/// Compares `a` and `b` in time independent of where they differ, so that tokens can't be
+/// guessed byte by byte
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_tokens() {
+        assert!(constant_time_eq(b"Bearer secret", b"Bearer secret"));
+        assert!(!constant_time_eq(b"Bearer secret", b"Bearer secreT"));
+        assert!(!constant_time_eq(b"Bearer secret", b"Bearer secrets"));
+        assert!(!constant_time_eq(b"", b"Bearer secret"));
+    }
 }
@@ -1,6 +1,7 @@
 pub mod sink;
 pub mod source;
 pub mod state;
+pub mod topology;
 pub mod transform;
 
 use crate::{
@@ -207,6 +208,11 @@ impl ComponentsQuery {
     async fn component_by_name(&self, name: String) -> Option<Component> {
         component_by_name(&name)
     }
+
+    /// The running topology, with the status of each component and the edges between them
+    async fn topology(&self) -> topology::Topology {
+        topology::Topology::current()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -260,8 +266,8 @@ pub fn update_config(config: &Config) {
             name.to_owned(),
             Component::Source(source::Source(source::Data {
                 name: name.to_owned(),
-                component_type: source.source_type().to_string(),
-                output_type: source.output_type(),
+                component_type: source.inner.source_type().to_string(),
+                output_type: source.inner.output_type(),
             })),
         );
     }
@@ -111,6 +111,11 @@ impl Sink {
     pub async fn metrics(&self) -> metrics::SinkMetrics {
         metrics::by_component_name(self.get_name()).to_sink_metrics(self.get_component_type())
     }
+
+    /// Events received from upstream components, by component
+    pub async fn received_events(&self) -> Vec<metrics::EdgeEventsTotal> {
+        metrics::received_by_source(self.get_name())
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +109,11 @@ impl Source {
     pub async fn metrics(&self) -> metrics::SourceMetrics {
         metrics::by_component_name(&self.get_name()).to_source_metrics(&self.get_component_type())
     }
+
+    /// Events sent to downstream components, by component
+    pub async fn sent_events(&self) -> Vec<metrics::EdgeEventsTotal> {
+        metrics::sent_by_destination(self.get_name())
+    }
 }
 
 #[derive(Default, InputObject)]
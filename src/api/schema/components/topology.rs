@@ -0,0 +1,267 @@
+use super::{state, Component, ComponentKind};
+use crate::api::schema::metrics;
+use async_graphql::{Enum, Object};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Enum, Serialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    /// The component is running. Sources and transforms that haven't failed, as well as sinks
+    /// whose healthcheck is disabled or hasn't finished yet, are running
+    Running,
+    /// The sink passed its latest healthcheck
+    Healthy,
+    /// The sink failed its latest healthcheck
+    Unhealthy,
+    /// The component's task failed, e.g. because a source couldn't bind its address
+    Failed,
+}
+
+impl ComponentStatus {
+    fn new(
+        component: &Component,
+        healthchecks: &BTreeMap<String, bool>,
+        failures: &BTreeSet<String>,
+    ) -> Self {
+        if failures.contains(component.get_name()) {
+            return ComponentStatus::Failed;
+        }
+
+        match component {
+            Component::Sink(_) => match healthchecks.get(component.get_name()) {
+                Some(true) => ComponentStatus::Healthy,
+                Some(false) => ComponentStatus::Unhealthy,
+                None => ComponentStatus::Running,
+            },
+            _ => ComponentStatus::Running,
+        }
+    }
+
+    /// Whether the component can process events, as far as is known
+    pub fn is_ok(self) -> bool {
+        matches!(self, ComponentStatus::Running | ComponentStatus::Healthy)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TopologyNode {
+    component: Component,
+    status: ComponentStatus,
+}
+
+#[Object]
+impl TopologyNode {
+    /// Component name
+    async fn name(&self) -> &str {
+        self.component.get_name()
+    }
+
+    /// Component kind
+    async fn component_kind(&self) -> ComponentKind {
+        self.component.get_component_kind()
+    }
+
+    /// Component type, e.g. `file` or `blackhole`
+    async fn component_type(&self) -> &str {
+        match &self.component {
+            Component::Source(c) => c.get_component_type(),
+            Component::Transform(c) => c.get_component_type(),
+            Component::Sink(c) => c.get_component_type(),
+        }
+    }
+
+    /// Component status, as of its latest healthcheck
+    async fn status(&self) -> ComponentStatus {
+        self.status
+    }
+
+    /// The component itself
+    async fn component(&self) -> Component {
+        self.component.clone()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct TopologyEdge {
+    from: String,
+    to: String,
+}
+
+#[Object]
+impl TopologyEdge {
+    /// Name of the upstream component, sending events
+    async fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Name of the downstream component, receiving events
+    async fn to(&self) -> &str {
+        &self.to
+    }
+}
+
+/// The running topology, as a graph of components and the edges between them
+#[derive(Debug, Clone)]
+pub struct Topology {
+    nodes: Vec<TopologyNode>,
+    edges: Vec<TopologyEdge>,
+}
+
+impl Topology {
+    fn new(
+        mut components: Vec<Component>,
+        healthchecks: &BTreeMap<String, bool>,
+        failures: &BTreeSet<String>,
+    ) -> Self {
+        components.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+
+        let mut edges = components
+            .iter()
+            .flat_map(|component| {
+                let inputs = match component {
+                    Component::Source(_) => &[][..],
+                    Component::Transform(c) => &c.0.inputs[..],
+                    Component::Sink(c) => &c.0.inputs[..],
+                };
+                inputs.iter().map(move |input| TopologyEdge {
+                    from: input.clone(),
+                    to: component.get_name().to_owned(),
+                })
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+
+        let nodes = components
+            .into_iter()
+            .map(|component| TopologyNode {
+                status: ComponentStatus::new(&component, healthchecks, failures),
+                component,
+            })
+            .collect();
+
+        Self { nodes, edges }
+    }
+
+    /// Returns the topology described by the current component state.
+    pub fn current() -> Self {
+        Self::new(
+            state::get_components(),
+            &metrics::healthchecks(),
+            &metrics::failures(),
+        )
+    }
+
+    /// Returns the status of each component, by component name
+    pub fn statuses(&self) -> BTreeMap<String, ComponentStatus> {
+        self.nodes
+            .iter()
+            .map(|node| (node.component.get_name().to_owned(), node.status))
+            .collect()
+    }
+}
+
+#[Object]
+impl Topology {
+    /// Configured components, sorted by name
+    async fn components(&self) -> Vec<TopologyNode> {
+        self.nodes.clone()
+    }
+
+    /// Connections between components, each sending events from a component to one that has
+    /// it as an input
+    async fn edges(&self) -> Vec<TopologyEdge> {
+        self.edges.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::schema::components::{sink, source, transform},
+        config::DataType,
+    };
+
+    fn components() -> Vec<Component> {
+        vec![
+            Component::Sink(sink::Sink(sink::Data {
+                name: "out".to_string(),
+                component_type: "blackhole".to_string(),
+                inputs: vec!["in".to_string(), "parse".to_string()],
+            })),
+            Component::Transform(transform::Transform(transform::Data {
+                name: "parse".to_string(),
+                component_type: "json_parser".to_string(),
+                inputs: vec!["in".to_string()],
+            })),
+            Component::Source(source::Source(source::Data {
+                name: "in".to_string(),
+                component_type: "stdin".to_string(),
+                output_type: DataType::Log,
+            })),
+        ]
+    }
+
+    fn edge(from: &str, to: &str) -> TopologyEdge {
+        TopologyEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn topology_edges() {
+        let topology = Topology::new(components(), &BTreeMap::new(), &BTreeSet::new());
+
+        let names = topology
+            .nodes
+            .iter()
+            .map(|node| node.component.get_name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["in", "out", "parse"]);
+        assert_eq!(
+            topology.edges,
+            vec![edge("in", "out"), edge("in", "parse"), edge("parse", "out")]
+        );
+    }
+
+    #[test]
+    fn topology_status() {
+        let mut healthchecks = BTreeMap::new();
+        let mut failures = BTreeSet::new();
+        let topology = Topology::new(components(), &healthchecks, &failures);
+        assert!(topology
+            .nodes
+            .iter()
+            .all(|node| node.status == ComponentStatus::Running));
+
+        healthchecks.insert("out".to_string(), false);
+        failures.insert("in".to_string());
+        let topology = Topology::new(components(), &healthchecks, &failures);
+        let statuses = topology
+            .nodes
+            .iter()
+            .map(|node| node.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ComponentStatus::Failed,
+                ComponentStatus::Unhealthy,
+                ComponentStatus::Running
+            ]
+        );
+    }
+
+    #[test]
+    fn topology_statuses() {
+        let mut healthchecks = BTreeMap::new();
+        healthchecks.insert("out".to_string(), true);
+        let topology = Topology::new(components(), &healthchecks, &BTreeSet::new());
+
+        let statuses = topology.statuses();
+        assert_eq!(statuses["out"], ComponentStatus::Healthy);
+        assert!(statuses.values().all(|status| status.is_ok()));
+    }
+}
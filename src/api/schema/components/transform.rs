@@ -90,6 +90,16 @@ impl Transform {
     pub async fn metrics(&self) -> metrics::TransformMetrics {
         metrics::by_component_name(&self.0.name).to_transform_metrics(&self.get_component_type())
     }
+
+    /// Events received from upstream components, by component
+    pub async fn received_events(&self) -> Vec<metrics::EdgeEventsTotal> {
+        metrics::received_by_source(&self.0.name)
+    }
+
+    /// Events sent to downstream components, by component
+    pub async fn sent_events(&self) -> Vec<metrics::EdgeEventsTotal> {
+        metrics::sent_by_destination(&self.0.name)
+    }
 }
 
 #[derive(Default, InputObject)]
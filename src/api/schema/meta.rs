@@ -1,5 +1,50 @@
 use async_graphql::Object;
 
+#[derive(Default)]
+pub struct BuildInfo;
+
+#[Object]
+impl BuildInfo {
+    /// Vector version, without the build details
+    async fn version(&self) -> String {
+        crate::vector_version().to_string()
+    }
+
+    /// Git commit SHA that Vector was built from, if known
+    async fn git_sha(&self) -> Option<String> {
+        crate::built_info::GIT_COMMIT_HASH.map(Into::into)
+    }
+
+    /// Target triple that Vector was built for
+    async fn target(&self) -> String {
+        crate::built_info::TARGET.into()
+    }
+
+    /// Operating system that Vector was built for
+    async fn os(&self) -> String {
+        crate::built_info::CFG_OS.into()
+    }
+
+    /// CPU architecture that Vector was built for
+    async fn arch(&self) -> String {
+        crate::built_info::CFG_TARGET_ARCH.into()
+    }
+
+    /// Cargo features that Vector was built with, lowercased with underscores,
+    /// e.g. `sources_socket`
+    async fn features(&self) -> Vec<String> {
+        crate::built_info::FEATURES
+            .iter()
+            .map(|feature| feature.to_lowercase())
+            .collect()
+    }
+
+    /// Memory allocator used by Vector
+    async fn allocator(&self) -> String {
+        crate::allocator::selected().as_str().into()
+    }
+}
+
 #[derive(Default)]
 pub struct Meta;
 
@@ -14,6 +59,11 @@ impl Meta {
     async fn hostname(&self) -> Option<String> {
         crate::get_hostname().ok()
     }
+
+    /// Build information
+    async fn build(&self) -> BuildInfo {
+        BuildInfo
+    }
 }
 
 #[derive(Default)]
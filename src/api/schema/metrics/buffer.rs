@@ -0,0 +1,119 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+
+/// `buffer_*` metrics of a single component.
+pub struct BufferUsage(Vec<Metric>);
+
+impl BufferUsage {
+    pub fn new(metrics: Vec<Metric>) -> Self {
+        Self(metrics)
+    }
+
+    fn value(&self, name: &str) -> Option<f64> {
+        self.0
+            .iter()
+            .find(|m| m.name() == name)
+            .map(|m| match m.data.value {
+                MetricValue::Counter { value } | MetricValue::Gauge { value } => value,
+                _ => 0.00,
+            })
+    }
+
+    /// Percentage of the capacity of the buffer in use, by bytes for disk buffers and by events
+    /// for memory buffers
+    fn used_percentage(&self) -> Option<f64> {
+        let (used, max) = match self.value("buffer_max_byte_size") {
+            Some(max) => (self.value("buffer_byte_size")?, max),
+            None => (
+                self.value("buffer_events")?,
+                self.value("buffer_max_event_size")?,
+            ),
+        };
+
+        if max > 0.00 {
+            Some((used / max * 100.00).min(100.00))
+        } else {
+            None
+        }
+    }
+}
+
+#[Object]
+impl BufferUsage {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.iter().find_map(|m| m.data.timestamp)
+    }
+
+    /// Buffer type, either `memory` or `disk`
+    pub async fn buffer_type(&self) -> String {
+        self.0
+            .iter()
+            .find_map(|m| m.tag_value("buffer_type"))
+            .unwrap_or_default()
+    }
+
+    /// Events in the buffer, that the component hasn't read yet
+    pub async fn events(&self) -> f64 {
+        self.value("buffer_events").unwrap_or(0.00)
+    }
+
+    /// Events the buffer holds at most. Only reported by memory buffers
+    pub async fn max_events(&self) -> Option<f64> {
+        self.value("buffer_max_event_size")
+    }
+
+    /// Bytes of the events in the buffer. Only reported by disk buffers
+    pub async fn byte_size(&self) -> Option<f64> {
+        self.value("buffer_byte_size")
+    }
+
+    /// Bytes of events the buffer holds at most. Only reported by disk buffers
+    pub async fn max_byte_size(&self) -> Option<f64> {
+        self.value("buffer_max_byte_size")
+    }
+
+    /// Percentage of the capacity of the buffer in use, from 0 to 100. A full buffer makes its
+    /// upstream components wait, or drop events, depending on `when_full`
+    pub async fn utilization(&self) -> Option<f64> {
+        self.used_percentage()
+    }
+
+    /// Bytes the buffer uses on disk, including events awaiting compaction. Only reported by
+    /// disk buffers
+    pub async fn disk_usage(&self) -> Option<f64> {
+        self.value("buffer_disk_usage_bytes")
+    }
+
+    /// Events dropped because the buffer was full
+    pub async fn discarded_events_total(&self) -> f64 {
+        self.value("buffer_discarded_events_total").unwrap_or(0.00)
+    }
+}
+
+pub struct ComponentBufferUsage {
+    name: String,
+    metrics: Vec<Metric>,
+}
+
+impl ComponentBufferUsage {
+    /// Returns a new `ComponentBufferUsage` struct, which is a GraphQL type. The
+    /// component name is hoisted for clear field resolution in the resulting payload
+    pub fn new(name: String, metrics: Vec<Metric>) -> Self {
+        Self { name, metrics }
+    }
+}
+
+#[Object]
+impl ComponentBufferUsage {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Buffer usage metrics
+    async fn metric(&self) -> BufferUsage {
+        BufferUsage::new(self.metrics.clone())
+    }
+}
@@ -61,3 +61,107 @@ impl ComponentErrorsTotal {
         ErrorsTotal::new(self.metric.clone())
     }
 }
+
+pub struct ComponentErrorsThroughput {
+    name: String,
+    throughput: i64,
+}
+
+impl ComponentErrorsThroughput {
+    /// Returns a new `ComponentErrorsThroughput`, set to the provided name/throughput values
+    pub fn new(name: String, throughput: i64) -> Self {
+        Self { name, throughput }
+    }
+}
+
+#[Object]
+impl ComponentErrorsThroughput {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Errors throughput
+    async fn throughput(&self) -> i64 {
+        self.throughput
+    }
+}
+
+/// Errors of one type, such as `parse` or `request`, of a component.
+pub struct ErrorTypeTotal {
+    error_type: String,
+    metric: Metric,
+}
+
+impl ErrorTypeTotal {
+    pub fn new(error_type: String, metric: Metric) -> Self {
+        Self { error_type, metric }
+    }
+}
+
+#[Object]
+impl ErrorTypeTotal {
+    /// Error type, from the `error_type` tag of processing errors, or otherwise the name of
+    /// the `<type>_errors_total` metric
+    pub async fn error_type(&self) -> &str {
+        &self.error_type
+    }
+
+    /// Total error count
+    pub async fn errors_total(&self) -> f64 {
+        match self.metric.data.value {
+            MetricValue::Counter { value } => value,
+            _ => 0.00,
+        }
+    }
+}
+
+/// Returns the error type of an `*_errors_total` metric.
+pub fn error_type(metric: &Metric) -> String {
+    match metric.name() {
+        "processing_errors_total" => metric
+            .tag_value("error_type")
+            .unwrap_or_else(|| "processing".to_owned()),
+        name => name.trim_end_matches("_errors_total").to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{metric::MetricTags, MetricKind};
+
+    fn counter(name: &str, error_type: Option<&str>) -> Metric {
+        Metric::new(
+            name.into(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(error_type.map(|error_type| {
+            let mut tags = MetricTags::new();
+            tags.insert("error_type".to_owned(), error_type.to_owned());
+            tags
+        }))
+    }
+
+    #[test]
+    fn error_type_from_metric_name() {
+        assert_eq!(error_type(&counter("parse_errors_total", None)), "parse");
+        assert_eq!(
+            error_type(&counter("http_request_errors_total", Some("ignored"))),
+            "http_request"
+        );
+    }
+
+    #[test]
+    fn error_type_of_processing_errors() {
+        assert_eq!(
+            error_type(&counter("processing_errors_total", Some("template_failed"))),
+            "template_failed"
+        );
+        assert_eq!(
+            error_type(&counter("processing_errors_total", None)),
+            "processing"
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+
+pub struct EventsDiscardedTotal(Metric);
+
+impl EventsDiscardedTotal {
+    pub fn new(m: Metric) -> Self {
+        Self(m)
+    }
+}
+
+#[Object]
+impl EventsDiscardedTotal {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.data.timestamp
+    }
+
+    /// Total number of events dropped before reaching their destination
+    pub async fn discarded_events_total(&self) -> f64 {
+        match self.0.data.value {
+            MetricValue::Counter { value } => value,
+            _ => 0.00,
+        }
+    }
+}
+
+impl From<Metric> for EventsDiscardedTotal {
+    fn from(m: Metric) -> Self {
+        Self(m)
+    }
+}
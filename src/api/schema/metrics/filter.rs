@@ -1,11 +1,17 @@
-use super::{ProcessedBytesTotal, ProcessedEventsTotal};
+use super::{
+    errors::error_type, EdgeEventsTotal, ErrorTypeTotal, ErrorsTotal, EventsDiscardedTotal,
+    ProcessedBytesTotal, ProcessedEventsTotal,
+};
 use crate::{
     event::{Event, Metric, MetricValue},
     metrics::{capture_metrics, get_controller, Controller},
 };
 use async_stream::stream;
 use lazy_static::lazy_static;
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 use tokio::{
     stream::{Stream, StreamExt},
     time::Duration,
@@ -28,9 +34,52 @@ fn sum_metrics<'a, I: IntoIterator<Item = &'a Metric>>(metrics: I) -> Option<Met
     }))
 }
 
+/// Sums the `*_errors_total` metrics.
+fn errors_total<'a, I: IntoIterator<Item = &'a Metric>>(metrics: I) -> Option<ErrorsTotal> {
+    let sum = sum_metrics(
+        metrics
+            .into_iter()
+            .filter(|m| m.name().ends_with("_errors_total")),
+    )?;
+
+    Some(ErrorsTotal::new(sum))
+}
+
+/// Sums the `*_errors_total` metrics by error type.
+fn errors_by_type<'a, I: IntoIterator<Item = &'a Metric>>(metrics: I) -> Vec<ErrorTypeTotal> {
+    metrics
+        .into_iter()
+        .filter(|m| m.name().ends_with("_errors_total"))
+        .fold(BTreeMap::new(), |mut map, m| {
+            map.entry(error_type(m)).or_insert_with(Vec::new).push(m);
+            map
+        })
+        .into_iter()
+        .filter_map(|(error_type, metrics)| {
+            Some(ErrorTypeTotal::new(error_type, sum_metrics(metrics)?))
+        })
+        .collect()
+}
+
+/// Sums the `events_discarded_total` metrics, across all reasons.
+fn events_discarded_total<'a, I: IntoIterator<Item = &'a Metric>>(
+    metrics: I,
+) -> Option<EventsDiscardedTotal> {
+    let sum = sum_metrics(
+        metrics
+            .into_iter()
+            .filter(|m| m.name() == "events_discarded_total"),
+    )?;
+
+    Some(EventsDiscardedTotal::new(sum))
+}
+
 pub trait MetricsFilter<'a> {
     fn processed_events_total(&self) -> Option<ProcessedEventsTotal>;
     fn processed_bytes_total(&self) -> Option<ProcessedBytesTotal>;
+    fn errors_total(&self) -> Option<ErrorsTotal>;
+    fn errors_by_type(&self) -> Vec<ErrorTypeTotal>;
+    fn events_discarded_total(&self) -> Option<EventsDiscardedTotal>;
 }
 
 impl<'a> MetricsFilter<'a> for Vec<Metric> {
@@ -45,6 +94,18 @@ impl<'a> MetricsFilter<'a> for Vec<Metric> {
 
         Some(ProcessedBytesTotal::new(sum))
     }
+
+    fn errors_total(&self) -> Option<ErrorsTotal> {
+        errors_total(self)
+    }
+
+    fn errors_by_type(&self) -> Vec<ErrorTypeTotal> {
+        errors_by_type(self)
+    }
+
+    fn events_discarded_total(&self) -> Option<EventsDiscardedTotal> {
+        events_discarded_total(self)
+    }
 }
 
 impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
@@ -67,6 +128,18 @@ impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
 
         Some(ProcessedBytesTotal::new(sum))
     }
+
+    fn errors_total(&self) -> Option<ErrorsTotal> {
+        errors_total(self.iter().copied())
+    }
+
+    fn errors_by_type(&self) -> Vec<ErrorTypeTotal> {
+        errors_by_type(self.iter().copied())
+    }
+
+    fn events_discarded_total(&self) -> Option<EventsDiscardedTotal> {
+        events_discarded_total(self.iter().copied())
+    }
 }
 
 /// Returns a stream of `Metric`s, collected at the provided millisecond interval.
@@ -113,6 +186,72 @@ pub fn by_component_name(component_name: &str) -> Vec<Metric> {
         .collect()
 }
 
+/// Groups the `sent_*` metrics matching `filter_fn` into `EdgeEventsTotal`s, by the value of the
+/// `tag`, naming the component at the other end of each edge.
+fn edge_metrics(filter_fn: impl Fn(&Metric) -> bool, tag: &str) -> Vec<EdgeEventsTotal> {
+    capture_metrics(&GLOBAL_CONTROLLER)
+        .filter_map(|ev| match ev {
+            Event::Metric(m) if m.name().starts_with("sent_event") && filter_fn(&m) => {
+                Some((m.tag_value(tag)?, m))
+            }
+            _ => None,
+        })
+        .fold(BTreeMap::new(), |mut map, (name, m)| {
+            map.entry(name).or_insert_with(Vec::new).push(m);
+            map
+        })
+        .into_iter()
+        .map(|(name, metrics)| EdgeEventsTotal::new(name, metrics))
+        .collect()
+}
+
+/// Returns the current metrics, or none if the metrics system is disabled. Unlike the
+/// subscriptions, the health and readiness probes rely on these, so they can't panic.
+fn component_metrics() -> impl Iterator<Item = Event> {
+    get_controller().ok().into_iter().flat_map(capture_metrics)
+}
+
+/// Returns the events sent by `component_name`, by downstream component.
+pub fn sent_by_destination(component_name: &str) -> Vec<EdgeEventsTotal> {
+    edge_metrics(|m| m.tag_matches("component_name", component_name), "to")
+}
+
+/// Returns the events received by `component_name`, by upstream component.
+pub fn received_by_source(component_name: &str) -> Vec<EdgeEventsTotal> {
+    edge_metrics(|m| m.tag_matches("to", component_name), "component_name")
+}
+
+/// Returns the result of the latest healthcheck of each sink, by sink name. Sinks whose
+/// healthcheck is disabled or still running are missing.
+pub fn healthchecks() -> BTreeMap<String, bool> {
+    component_metrics()
+        .filter_map(|ev| match ev {
+            Event::Metric(m) if m.name() == "healthcheck_healthy" => {
+                let healthy = match m.data.value {
+                    MetricValue::Gauge { value } => value > 0.0,
+                    _ => return None,
+                };
+                Some((m.tag_value("component_name")?, healthy))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the names of the components whose current task failed, e.g. sources that couldn't
+/// bind their address. A component is no longer failed once its task is spawned again.
+pub fn failures() -> BTreeSet<String> {
+    component_metrics()
+        .filter_map(|ev| match ev {
+            Event::Metric(m) if m.name() == "component_failed" => match m.data.value {
+                MetricValue::Gauge { value } if value > 0.0 => m.tag_value("component_name"),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 type MetricFilterFn = dyn Fn(&Metric) -> bool + Send + Sync;
 
 /// Returns a stream of `Vec<Metric>`, where `metric_name` matches the name of the metric
@@ -224,3 +363,114 @@ pub fn component_counter_throughputs(
         // Ignore the first, since we only care about sampling between `interval`
         .skip(1)
 }
+
+/// Returns a stream of `Vec<Metric>`, collected at the provided millisecond interval, where
+/// `metric_name` matches the name of 'gauge' metrics of components. Unlike counters, gauges
+/// aren't aggregated, as their tags (e.g. a Kafka partition) tell them apart.
+pub fn component_gauge_metrics(
+    interval: i32,
+    metric_name: &'static str,
+) -> impl Stream<Item = Vec<Metric>> {
+    get_all_metrics(interval).map(move |m| {
+        m.into_iter()
+            .filter(|m| {
+                m.name() == metric_name
+                    && matches!(m.data.value, MetricValue::Gauge { .. })
+                    && m.tag_value("component_name").is_some()
+            })
+            .collect()
+    })
+}
+
+/// Returns a stream of the `buffer_*` metrics of each component reading from a buffer, keyed by
+/// component name, collected at the provided millisecond interval.
+pub fn component_buffer_metrics(
+    interval: i32,
+) -> impl Stream<Item = BTreeMap<String, Vec<Metric>>> {
+    get_all_metrics(interval).map(|m| {
+        m.into_iter()
+            .filter(|m| m.name().starts_with("buffer_"))
+            .filter_map(|m| Some((m.tag_value("component_name")?, m)))
+            .fold(BTreeMap::new(), |mut map, (name, m)| {
+                map.entry(name).or_insert_with(Vec::new).push(m);
+                map
+            })
+    })
+}
+
+/// Returns a stream of the `buffer_*` metrics of the components whose buffer depth, in events or
+/// bytes, changed since the previous interval. This is useful for the client to be notified as
+/// buffers fill up or drain, without returning identical results.
+pub fn component_buffer_metrics_changes(
+    interval: i32,
+) -> impl Stream<Item = BTreeMap<String, Vec<Metric>>> {
+    let mut cache = BTreeMap::new();
+
+    component_buffer_metrics(interval).map(move |m| {
+        m.into_iter()
+            .filter(|(name, metrics)| {
+                let depth = metrics
+                    .iter()
+                    .filter_map(|m| match (m.name(), &m.data.value) {
+                        ("buffer_events", MetricValue::Gauge { value })
+                        | ("buffer_byte_size", MetricValue::Gauge { value }) => {
+                            Some((m.name().to_owned(), *value))
+                        }
+                        _ => None,
+                    })
+                    .collect::<BTreeMap<_, _>>();
+
+                cache.insert(name.clone(), depth.clone()).as_ref() != Some(&depth)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::MetricKind;
+
+    fn counter(name: &str, value: f64) -> Metric {
+        Metric::new(
+            name.into(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value },
+        )
+    }
+
+    #[tokio::test]
+    async fn sums_errors_total() {
+        let metrics = vec![
+            counter("parse_errors_total", 2.0),
+            counter("processed_events_total", 10.0),
+            counter("http_request_errors_total", 3.0),
+        ];
+
+        let total = errors_total(&metrics).unwrap();
+        assert_eq!(Some(total.errors_total().await), Some(5.0));
+        assert!(errors_total(&metrics[1..2]).is_none());
+    }
+
+    #[tokio::test]
+    async fn sums_errors_by_type() {
+        let metrics = vec![
+            counter("parse_errors_total", 2.0),
+            counter("processed_events_total", 10.0),
+            counter("http_request_errors_total", 3.0),
+            counter("parse_errors_total", 4.0),
+        ];
+
+        let mut totals = Vec::new();
+        for total in errors_by_type(&metrics) {
+            totals.push((
+                total.error_type().await.to_owned(),
+                total.errors_total().await,
+            ));
+        }
+        assert_eq!(
+            totals,
+            vec![("http_request".to_owned(), 3.0), ("parse".to_owned(), 6.0)]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+use chrono::{DateTime, Utc};
+
+fn gauge_value(metric: &Metric) -> f64 {
+    match metric.data.value {
+        MetricValue::Gauge { value } => value,
+        _ => 0.00,
+    }
+}
+
+fn component_name(metric: &Metric) -> String {
+    metric.tag_value("component_name").expect(
+        "Returned a metric without a `component_name`, which shouldn't happen. Please report.",
+    )
+}
+
+pub struct KafkaConsumerLag(Metric);
+
+impl KafkaConsumerLag {
+    pub fn new(m: Metric) -> Self {
+        Self(m)
+    }
+}
+
+#[Object]
+impl KafkaConsumerLag {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.data.timestamp
+    }
+
+    /// Kafka topic
+    pub async fn topic(&self) -> String {
+        self.0.tag_value("topic").unwrap_or_default()
+    }
+
+    /// Kafka partition of the topic
+    pub async fn partition(&self) -> i64 {
+        self.0
+            .tag_value("partition")
+            .and_then(|partition| partition.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Number of messages the consumer is behind the end of the partition
+    pub async fn lag(&self) -> f64 {
+        gauge_value(&self.0)
+    }
+}
+
+pub struct ComponentKafkaConsumerLag {
+    name: String,
+    metric: Metric,
+}
+
+impl ComponentKafkaConsumerLag {
+    /// Returns a new `ComponentKafkaConsumerLag` struct, which is a GraphQL type. The
+    /// component name is hoisted for clear field resolution in the resulting payload
+    pub fn new(metric: Metric) -> Self {
+        let name = component_name(&metric);
+
+        Self { name, metric }
+    }
+}
+
+#[Object]
+impl ComponentKafkaConsumerLag {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Consumer lag metric
+    async fn metric(&self) -> KafkaConsumerLag {
+        KafkaConsumerLag::new(self.metric.clone())
+    }
+}
+
+pub struct KafkaBrokerConnection(Metric);
+
+impl KafkaBrokerConnection {
+    pub fn new(m: Metric) -> Self {
+        Self(m)
+    }
+}
+
+#[Object]
+impl KafkaBrokerConnection {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.data.timestamp
+    }
+
+    /// Kafka broker, as reported by librdkafka
+    pub async fn broker(&self) -> String {
+        self.0.tag_value("broker").unwrap_or_default()
+    }
+
+    /// Whether the connection to the broker is up
+    pub async fn connected(&self) -> bool {
+        gauge_value(&self.0) > 0.0
+    }
+}
+
+pub struct ComponentKafkaBrokerConnection {
+    name: String,
+    metric: Metric,
+}
+
+impl ComponentKafkaBrokerConnection {
+    /// Returns a new `ComponentKafkaBrokerConnection` struct, which is a GraphQL type. The
+    /// component name is hoisted for clear field resolution in the resulting payload
+    pub fn new(metric: Metric) -> Self {
+        let name = component_name(&metric);
+
+        Self { name, metric }
+    }
+}
+
+#[Object]
+impl ComponentKafkaBrokerConnection {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Broker connection metric
+    async fn metric(&self) -> KafkaBrokerConnection {
+        KafkaBrokerConnection::new(self.metric.clone())
+    }
+}
@@ -1,8 +1,12 @@
+mod buffer;
 mod errors;
+mod events_discarded;
 pub mod filter;
 mod host;
+mod kafka;
 mod processed_bytes;
 mod processed_events;
+mod sent_events;
 mod sink;
 pub mod source;
 mod transform;
@@ -12,15 +16,22 @@ use async_graphql::{validators::IntRange, Interface, Object, Subscription};
 use chrono::{DateTime, Utc};
 use tokio::stream::{Stream, StreamExt};
 
-pub use errors::{ComponentErrorsTotal, ErrorsTotal};
+pub use buffer::{BufferUsage, ComponentBufferUsage};
+pub use errors::{ComponentErrorsThroughput, ComponentErrorsTotal, ErrorTypeTotal, ErrorsTotal};
+pub use events_discarded::EventsDiscardedTotal;
 pub use filter::*;
 pub use host::HostMetrics;
+pub use kafka::{
+    ComponentKafkaBrokerConnection, ComponentKafkaConsumerLag, KafkaBrokerConnection,
+    KafkaConsumerLag,
+};
 pub use processed_bytes::{
     ComponentProcessedBytesThroughput, ComponentProcessedBytesTotal, ProcessedBytesTotal,
 };
 pub use processed_events::{
     ComponentProcessedEventsThroughput, ComponentProcessedEventsTotal, ProcessedEventsTotal,
 };
+pub use sent_events::EdgeEventsTotal;
 pub use sink::{IntoSinkMetrics, SinkMetrics};
 pub use source::{IntoSourceMetrics, SourceMetrics};
 pub use transform::{IntoTransformMetrics, TransformMetrics};
@@ -180,6 +191,68 @@ impl MetricsSubscription {
             .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
     }
 
+    /// Component error throughput over `interval`.
+    async fn component_errors_throughputs(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentErrorsThroughput>> {
+        component_counter_throughputs(interval, &|m| m.name().ends_with("_errors_total")).map(|m| {
+            m.into_iter()
+                .map(|(m, throughput)| {
+                    ComponentErrorsThroughput::new(
+                        m.tag_value("component_name").unwrap(),
+                        throughput as i64,
+                    )
+                })
+                .collect()
+        })
+    }
+
+    /// Buffer usage of the components reading from a buffer, over `interval`.
+    async fn component_buffer_usages(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferUsage>> {
+        component_buffer_metrics(interval).map(|m| {
+            m.into_iter()
+                .map(|(name, metrics)| ComponentBufferUsage::new(name, metrics))
+                .collect()
+        })
+    }
+
+    /// Buffer usage of the components whose buffer depth changed, checked every `interval`.
+    async fn component_buffer_usage_changes(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferUsage>> {
+        component_buffer_metrics_changes(interval).map(|m| {
+            m.into_iter()
+                .map(|(name, metrics)| ComponentBufferUsage::new(name, metrics))
+                .collect()
+        })
+    }
+
+    /// Kafka consumer lag per topic partition, of the components consuming from Kafka.
+    async fn component_kafka_consumer_lags(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentKafkaConsumerLag>> {
+        component_gauge_metrics(interval, "kafka_consumer_lag")
+            .map(|m| m.into_iter().map(ComponentKafkaConsumerLag::new).collect())
+    }
+
+    /// Kafka broker connection states, of the components connecting to Kafka.
+    async fn component_kafka_broker_connections(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentKafkaBrokerConnection>> {
+        component_gauge_metrics(interval, "kafka_broker_connected").map(|m| {
+            m.into_iter()
+                .map(ComponentKafkaBrokerConnection::new)
+                .collect()
+        })
+    }
+
     /// All metrics.
     async fn metrics(
         &self,
@@ -0,0 +1,57 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+
+/// Events passed along one edge of the topology, between the current component and one of its
+/// upstream or downstream components.
+pub struct EdgeEventsTotal {
+    name: String,
+    events: Option<Metric>,
+    bytes: Option<Metric>,
+}
+
+impl EdgeEventsTotal {
+    /// Returns a new `EdgeEventsTotal` for the component at the other end of the edge, from
+    /// its `sent_events_total` and `sent_event_bytes_total` metrics.
+    pub fn new(name: String, metrics: Vec<Metric>) -> Self {
+        let mut events = None;
+        let mut bytes = None;
+        for metric in metrics {
+            match metric.name() {
+                "sent_events_total" => events = Some(metric),
+                "sent_event_bytes_total" => bytes = Some(metric),
+                _ => {}
+            }
+        }
+
+        Self {
+            name,
+            events,
+            bytes,
+        }
+    }
+}
+
+fn counter_value(metric: &Option<Metric>) -> f64 {
+    match metric.as_ref().map(|m| &m.data.value) {
+        Some(MetricValue::Counter { value }) => *value,
+        _ => 0.00,
+    }
+}
+
+#[Object]
+impl EdgeEventsTotal {
+    /// Name of the component at the other end of the edge
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Total number of events passed along the edge
+    async fn sent_events_total(&self) -> f64 {
+        counter_value(&self.events)
+    }
+
+    /// Estimated total number of bytes of the events passed along the edge
+    async fn sent_event_bytes_total(&self) -> f64 {
+        counter_value(&self.bytes)
+    }
+}
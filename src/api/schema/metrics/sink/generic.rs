@@ -24,4 +24,19 @@ impl GenericSinkMetrics {
     pub async fn processed_bytes_total(&self) -> Option<metrics::ProcessedBytesTotal> {
         self.0.processed_bytes_total()
     }
+
+    /// Errors for the current sink, across all error types
+    pub async fn errors_total(&self) -> Option<metrics::ErrorsTotal> {
+        self.0.errors_total()
+    }
+
+    /// Errors for the current sink, by error type
+    pub async fn errors_by_type(&self) -> Vec<metrics::ErrorTypeTotal> {
+        self.0.errors_by_type()
+    }
+
+    /// Events discarded by the current sink
+    pub async fn discarded_events_total(&self) -> Option<metrics::EventsDiscardedTotal> {
+        self.0.events_discarded_total()
+    }
 }
@@ -101,6 +101,21 @@ impl FileSourceMetrics {
     pub async fn processed_bytes_total(&self) -> Option<metrics::ProcessedBytesTotal> {
         self.0.processed_bytes_total()
     }
+
+    /// Errors for the current file source, across all error types
+    pub async fn errors_total(&self) -> Option<metrics::ErrorsTotal> {
+        self.0.errors_total()
+    }
+
+    /// Errors for the current file source, by error type
+    pub async fn errors_by_type(&self) -> Vec<metrics::ErrorTypeTotal> {
+        self.0.errors_by_type()
+    }
+
+    /// Events discarded by the current file source
+    pub async fn discarded_events_total(&self) -> Option<metrics::EventsDiscardedTotal> {
+        self.0.events_discarded_total()
+    }
 }
 
 #[derive(Default, InputObject)]
@@ -1,13 +1,18 @@
 mod generic;
 
-use super::{ProcessedBytesTotal, ProcessedEventsTotal};
+use super::{
+    ErrorTypeTotal, ErrorsTotal, EventsDiscardedTotal, ProcessedBytesTotal, ProcessedEventsTotal,
+};
 use crate::event::Metric;
 use async_graphql::Interface;
 
 #[derive(Debug, Clone, Interface)]
 #[graphql(
     field(name = "processed_events_total", type = "Option<ProcessedEventsTotal>"),
-    field(name = "processed_bytes_total", type = "Option<ProcessedBytesTotal>")
+    field(name = "processed_bytes_total", type = "Option<ProcessedBytesTotal>"),
+    field(name = "errors_total", type = "Option<ErrorsTotal>"),
+    field(name = "errors_by_type", type = "Vec<ErrorTypeTotal>"),
+    field(name = "discarded_events_total", type = "Option<EventsDiscardedTotal>")
 )]
 pub enum TransformMetrics {
     GenericTransformMetrics(generic::GenericTransformMetrics),
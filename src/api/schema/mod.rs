@@ -5,6 +5,7 @@ mod meta;
 mod metrics;
 mod relay;
 pub mod sort;
+pub mod tap;
 
 use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
 
@@ -21,6 +22,7 @@ pub struct Subscription(
     health::HealthSubscription,
     metrics::MetricsSubscription,
     components::ComponentsSubscription,
+    tap::TapSubscription,
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
@@ -0,0 +1,297 @@
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Metric},
+    topology::{TapDirection, TapOutputs},
+};
+use async_graphql::{validators::IntRange, Enum, Object, Subscription, Union};
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use rand::{thread_rng, Rng};
+use std::sync::RwLock;
+use tokio::{
+    stream::{Stream, StreamExt},
+    sync::{mpsc, watch},
+    time::{interval, Duration},
+};
+
+pub const INVARIANT: &str = "Couldn't acquire lock on Vector tap outputs. Please report this.";
+
+lazy_static! {
+    static ref TAP_OUTPUTS: RwLock<Option<watch::Receiver<TapOutputs>>> = RwLock::new(None);
+}
+
+/// Sets the outputs of the running topology, which tap subscriptions attach to
+pub fn update_outputs(outputs: watch::Receiver<TapOutputs>) {
+    *TAP_OUTPUTS.write().expect(INVARIANT) = Some(outputs);
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum EventDirection {
+    /// Events received by the component, from its inputs
+    Input,
+    /// Events sent by the component, to downstream components
+    Output,
+}
+
+impl From<EventDirection> for TapDirection {
+    fn from(direction: EventDirection) -> Self {
+        match direction {
+            EventDirection::Input => TapDirection::Input,
+            EventDirection::Output => TapDirection::Output,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum TapNotificationKind {
+    /// The component exists, and its events are tapped
+    Matched,
+    /// There's no component with the given name. It's tapped once it's added
+    NotMatched,
+    /// The component was rebuilt, e.g. on reload, and its events are tapped again
+    Recreated,
+}
+
+pub struct TapNotification {
+    component_name: String,
+    notification: TapNotificationKind,
+}
+
+#[Object]
+impl TapNotification {
+    /// Name of the tapped component
+    async fn component_name(&self) -> &str {
+        &self.component_name
+    }
+
+    /// What changed about the tapped component
+    async fn notification(&self) -> TapNotificationKind {
+        self.notification
+    }
+}
+
+pub struct TapLogEvent {
+    component_name: String,
+    log: LogEvent,
+}
+
+#[Object]
+impl TapLogEvent {
+    /// Name of the tapped component
+    async fn component_name(&self) -> &str {
+        &self.component_name
+    }
+
+    /// Log message, if the event has one
+    async fn message(&self) -> Option<String> {
+        self.log
+            .get(log_schema().message_key())
+            .map(|message| message.to_string_lossy())
+    }
+
+    /// Log timestamp, if the event has one
+    async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.log
+            .get(log_schema().timestamp_key())
+            .and_then(|timestamp| timestamp.as_timestamp())
+            .copied()
+    }
+
+    /// Log event, encoded as JSON
+    async fn json(&self) -> String {
+        serde_json::to_string(&self.log).unwrap_or_default()
+    }
+}
+
+pub struct TapMetricEvent {
+    component_name: String,
+    metric: Metric,
+}
+
+#[Object]
+impl TapMetricEvent {
+    /// Name of the tapped component
+    async fn component_name(&self) -> &str {
+        &self.component_name
+    }
+
+    /// Metric name
+    async fn name(&self) -> &str {
+        self.metric.name()
+    }
+
+    /// Metric timestamp, if the metric has one
+    async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.metric.data.timestamp
+    }
+
+    /// Metric event, encoded as JSON
+    async fn json(&self) -> String {
+        serde_json::to_string(&self.metric).unwrap_or_default()
+    }
+}
+
+#[derive(Union)]
+pub enum TapPayload {
+    Log(TapLogEvent),
+    Metric(TapMetricEvent),
+    Notification(TapNotification),
+}
+
+impl TapPayload {
+    fn event(component_name: &str, event: Event) -> Self {
+        let component_name = component_name.to_owned();
+        match event {
+            Event::Log(log) => TapPayload::Log(TapLogEvent {
+                component_name,
+                log,
+            }),
+            Event::Metric(metric) => TapPayload::Metric(TapMetricEvent {
+                component_name,
+                metric,
+            }),
+        }
+    }
+
+    fn notification(component_name: &str, notification: TapNotificationKind) -> Self {
+        TapPayload::Notification(TapNotification {
+            component_name: component_name.to_owned(),
+            notification,
+        })
+    }
+}
+
+/// What a tap subscription waits on.
+enum TapUpdate {
+    Outputs(Option<TapOutputs>),
+    Event(Event),
+    Flush,
+}
+
+/// A uniform random sample of the events tapped since the last flush, so a busy component
+/// doesn't flood the subscriber.
+struct Sample {
+    events: Vec<Event>,
+    seen: usize,
+    limit: usize,
+}
+
+impl Sample {
+    fn new(limit: usize) -> Self {
+        Self {
+            events: Vec::with_capacity(limit),
+            seen: 0,
+            limit,
+        }
+    }
+
+    fn record(&mut self, event: Event) {
+        self.seen += 1;
+
+        if self.events.len() < self.limit {
+            self.events.push(event);
+        } else {
+            let index = thread_rng().gen_range(0..self.seen);
+            if index < self.limit {
+                self.events[index] = event;
+            }
+        }
+    }
+
+    fn take(&mut self) -> Vec<Event> {
+        self.seen = 0;
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// Returns a stream of batches of events tapped from `component_name`, sampled to at most
+/// `limit` every `interval` milliseconds, along with notifications when the component is
+/// added, removed, or rebuilt.
+fn tap(
+    component_name: String,
+    direction: TapDirection,
+    interval_ms: i32,
+    limit: i32,
+) -> impl Stream<Item = Vec<TapPayload>> {
+    let outputs = TAP_OUTPUTS.read().expect(INVARIANT).clone();
+    let limit = limit as usize;
+
+    stream! {
+        let mut outputs = match outputs {
+            Some(outputs) => outputs,
+            None => {
+                yield vec![TapPayload::notification(&component_name, TapNotificationKind::NotMatched)];
+                return;
+            }
+        };
+
+        let (tx, mut events) = mpsc::channel(limit);
+        let mut interval = interval(Duration::from_millis(interval_ms as u64));
+        let mut sample = Sample::new(limit);
+        let mut notifications = Vec::new();
+
+        // `None` until the outputs are first received, so that the first notification
+        // tells whether the component exists
+        let mut targets = None;
+        let mut _guards = Vec::new();
+
+        loop {
+            let update = tokio::select! {
+                current = outputs.recv() => TapUpdate::Outputs(current),
+                Some(event) = events.next() => TapUpdate::Event(event),
+                _ = interval.tick() => TapUpdate::Flush,
+            };
+
+            match update {
+                // The topology stopped, so there's nothing more to tap
+                TapUpdate::Outputs(None) => break,
+                TapUpdate::Outputs(Some(current)) => {
+                    let new_targets = current.targets(&component_name, direction);
+                    if targets.as_ref() != Some(&new_targets) {
+                        let notification = match (&targets, &new_targets) {
+                            (_, None) => TapNotificationKind::NotMatched,
+                            (None, _) | (Some(None), _) => TapNotificationKind::Matched,
+                            (Some(Some(_)), Some(_)) => TapNotificationKind::Recreated,
+                        };
+                        notifications.push(TapPayload::notification(&component_name, notification));
+
+                        // Replacing the guards detaches the tap from the previous outputs
+                        _guards = current.tap(&component_name, direction, tx.clone());
+                        targets = Some(new_targets);
+                    }
+                }
+                TapUpdate::Event(event) => sample.record(event),
+                TapUpdate::Flush => {
+                    let sampled = sample.take();
+                    if !notifications.is_empty() || !sampled.is_empty() {
+                        yield notifications
+                            .drain(..)
+                            .chain(sampled.into_iter().map(|event| TapPayload::event(&component_name, event)))
+                            .collect();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TapSubscription;
+
+#[Subscription]
+impl TapSubscription {
+    /// Samples of the events flowing into or out of the component `component_name`, by
+    /// default its output. Sends at most `limit` events every `interval` milliseconds, along
+    /// with notifications when the component doesn't exist, or is added, removed or rebuilt.
+    async fn tap(
+        &self,
+        component_name: String,
+        direction: Option<EventDirection>,
+        #[graphql(default = 500, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+        #[graphql(default = 100, validator(IntRange(min = "1", max = "10_000")))] limit: i32,
+    ) -> impl Stream<Item = Vec<TapPayload>> {
+        let direction = direction.unwrap_or(EventDirection::Output).into();
+        tap(component_name, direction, interval, limit)
+    }
+}
@@ -1,29 +1,47 @@
 use super::{handler, schema};
-use crate::config;
+use crate::{config, tls::MaybeTlsSettings, topology::TapOutputs};
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
     Request, Schema,
 };
 use async_graphql_warp::{graphql_subscription, Response as GQLResponse};
-use std::{convert::Infallible, net::SocketAddr};
-use tokio::sync::oneshot;
-use warp::filters::BoxedFilter;
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::{oneshot, watch};
+use warp::filters::{path::FullPath, BoxedFilter};
 use warp::{http::Response, Filter, Reply};
 
 pub struct Server {
     _shutdown: oneshot::Sender<()>,
     addr: SocketAddr,
+    tls: bool,
+    ready: Arc<AtomicBool>,
 }
 
 impl Server {
     /// Start the API server. This creates the routes and spawns a Warp server. The server is
-    /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing
-    pub fn start(config: &config::Config) -> Self {
-        let routes = make_routes(config.api.playground);
+    /// gracefully shut down when Self falls out of scope by way of the oneshot sender closing.
+    /// The server starts out unready, until `set_ready` is called once the topology is running.
+    /// Fails if the address can't be bound, or the TLS settings are invalid
+    pub fn start(config: &config::Config) -> crate::Result<Self> {
+        let ready = Arc::new(AtomicBool::new(false));
+        let routes = make_routes(&config.api, Arc::clone(&ready));
+
+        let tls = MaybeTlsSettings::from_config(&config.api.tls, true)?;
+        let listener = std::net::TcpListener::bind(config.api.address.expect("No socket address"))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let listener = tls.listener(tokio::net::TcpListener::from_std(listener)?)?;
 
         let (_shutdown, rx) = oneshot::channel();
-        let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
-            config.api.address.expect("No socket address"),
+        let server = warp::serve(routes).serve_incoming_with_graceful_shutdown(
+            listener.accept_stream(),
             async {
                 rx.await.ok();
             },
@@ -35,7 +53,12 @@ impl Server {
         // Spawn the server in the background
         tokio::spawn(server);
 
-        Self { addr, _shutdown }
+        Ok(Self {
+            addr,
+            tls: tls.is_tls(),
+            _shutdown,
+            ready,
+        })
     }
 
     /// Returns a copy of the SocketAddr that the server was started on
@@ -43,37 +66,119 @@ impl Server {
         self.addr
     }
 
+    /// Whether the server is served over HTTPS
+    pub fn tls(&self) -> bool {
+        self.tls
+    }
+
     /// Update the configuration of a running server. While this instance method doesn't
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly
     pub fn update_config(&self, config: &config::Config) {
         schema::components::update_config(config)
     }
+
+    /// Sets the outputs of the running topology, which `tap` subscriptions attach to
+    pub fn set_tap_outputs(&self, outputs: watch::Receiver<TapOutputs>) {
+        schema::tap::update_outputs(outputs)
+    }
+
+    /// Sets whether the readiness probe succeeds, i.e. whether the topology is built, its
+    /// healthchecks passed and its buffers are recovered
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::Relaxed);
+    }
+}
+
+/// Matches exactly the given path, ignoring leading and trailing slashes
+fn exact_path(path: &str) -> BoxedFilter<()> {
+    let path = path.trim_matches('/').to_owned();
+    warp::path::full()
+        .and_then(move |full: FullPath| {
+            let matches = full.as_str().trim_matches('/') == path;
+            async move {
+                if matches {
+                    Ok(())
+                } else {
+                    Err(warp::reject::not_found())
+                }
+            }
+        })
+        .untuple_one()
+        .boxed()
+}
+
+/// Passes requests authenticated with the configured bearer token, if any, and rejects
+/// others as unauthorized
+fn authorized(auth: &Option<config::api::AuthOptions>) -> BoxedFilter<()> {
+    let expected = match auth {
+        Some(auth) => format!("Bearer {}", auth.token),
+        None => return warp::any().boxed(),
+    };
+
+    warp::header::optional::<String>("authorization")
+        .and_then(move |header: Option<String>| {
+            let valid = header.map_or(false, |header| {
+                handler::constant_time_eq(header.as_bytes(), expected.as_bytes())
+            });
+            async move {
+                if valid {
+                    Ok(())
+                } else {
+                    Err(warp::reject::custom(handler::Unauthorized))
+                }
+            }
+        })
+        .untuple_one()
+        .boxed()
 }
 
-fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
+fn make_routes(
+    config: &config::api::Options,
+    ready: Arc<AtomicBool>,
+) -> BoxedFilter<(impl Reply,)> {
     // Build the GraphQL schema
     let schema = schema::build_schema().finish();
 
     // Routes...
 
-    // Health
-    let health = warp::path("health").and_then(handler::health);
+    // Requests to the GraphQL endpoint always need to be authorized, when authentication is
+    // configured. The probes only need to be if they aren't exempt
+    let authorized_graphql = authorized(&config.auth);
+    let authorized_probes = match &config.auth {
+        Some(auth) if auth.exempt_probes => authorized(&None),
+        auth => authorized(auth),
+    };
+
+    // Liveness, which succeeds as long as the server is up
+    let health = exact_path(&config.health_path)
+        .and(authorized_probes.clone())
+        .and_then(handler::health);
+
+    // Readiness, which only succeeds once the topology is running
+    let ready = exact_path(&config.ready_path)
+        .and(authorized_probes)
+        .and(warp::any().map(move || Arc::clone(&ready)))
+        .and_then(handler::ready);
 
     // 404
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
     // GraphQL query and subscription handler
-    let graphql_handler = warp::path("graphql").and(graphql_subscription(schema.clone()).or(
-        async_graphql_warp::graphql(schema).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
-            },
-        ),
-    ));
+    let graphql_handler =
+        warp::path("graphql")
+            .and(authorized_graphql)
+            .and(graphql_subscription(schema.clone()).or(
+                async_graphql_warp::graphql(schema).and_then(
+                    |(schema, request): (Schema<_, _, _>, Request)| async move {
+                        Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
+                    },
+                ),
+            ));
 
-    // GraphQL playground
-    let graphql_playground = if playground {
+    // GraphQL playground. The page itself doesn't need to be authorized, since a token can be
+    // set in its settings for the queries it sends
+    let graphql_playground = if config.playground {
         warp::path("playground")
             .map(move || {
                 Response::builder()
@@ -88,9 +193,11 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
     };
 
     health
+        .or(ready)
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
+        .recover(handler::unauthorized)
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -103,6 +210,7 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
                     "Content-Type",
+                    "Authorization",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
                     "Host",
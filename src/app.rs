@@ -2,7 +2,8 @@ use crate::cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubComm
 use crate::signal::SignalTo;
 use crate::topology::RunningTopology;
 use crate::{
-    config, generate, heartbeat, list, metrics, signal, topology, trace, unit_test, validate,
+    allocator, config, generate, heartbeat, list, metrics, signal, topology, trace, unit_test,
+    validate,
 };
 use std::cmp::max;
 use std::collections::HashMap;
@@ -11,12 +12,14 @@ use std::path::PathBuf;
 use futures::StreamExt;
 use tokio::sync::mpsc;
 
+#[cfg(feature = "benchmark-cli")]
+use crate::benchmark;
 #[cfg(feature = "sources-host_metrics")]
 use crate::sources::host_metrics;
-#[cfg(feature = "api-client")]
-use crate::top;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
+#[cfg(feature = "api-client")]
+use crate::{tap, top};
 
 #[cfg(windows)]
 use crate::service;
@@ -33,7 +36,7 @@ pub struct ApplicationConfig {
     pub topology: RunningTopology,
     pub graceful_crash: mpsc::UnboundedReceiver<()>,
     #[cfg(feature = "api")]
-    pub api: config::api::Options,
+    pub api_server: Option<api::Server>,
 }
 
 pub struct Application {
@@ -106,6 +109,7 @@ impl Application {
             let config_paths = root_opts.config_paths_with_formats();
             let watch_config = root_opts.watch_config;
             let require_healthy = root_opts.require_healthy;
+            let expose_metrics_port = root_opts.expose_metrics_port;
 
             rt.block_on(async move {
                 if let Some(s) = sub_command {
@@ -114,8 +118,14 @@ impl Application {
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t).await,
                         SubCommand::Generate(g) => generate::cmd(&g),
+                        #[cfg(feature = "benchmark-cli")]
+                        SubCommand::Benchmark(b) => benchmark::cmd(&b).await,
                         #[cfg(feature = "api-client")]
                         SubCommand::Top(t) => top::cmd(&t).await,
+                        #[cfg(feature = "api-client")]
+                        SubCommand::Buffers(b) => top::buffers_cmd(&b).await,
+                        #[cfg(feature = "api-client")]
+                        SubCommand::Tap(t) => tap::cmd(&t).await,
                         #[cfg(windows)]
                         SubCommand::Service(s) => service::cmd(&s),
                         #[cfg(feature = "vrl-cli")]
@@ -146,8 +156,8 @@ impl Application {
                     path = ?config_paths
                 );
 
-                let mut config =
-                    config::load_from_paths(&config_paths, false).map_err(handle_config_errors)?;
+                let mut config = load_config(&config_paths, expose_metrics_port)
+                    .map_err(handle_config_errors)?;
 
                 config::LOG_SCHEMA
                     .set(config.global.log_schema.clone())
@@ -158,23 +168,48 @@ impl Application {
                 }
                 config.healthchecks.set_require_healthy(require_healthy);
 
+                // The API starts before the topology is built, so that its liveness probe
+                // answers while buffers are recovered, with readiness following once the
+                // topology is running.
+                #[cfg(feature = "api")]
+                let api_server = if config.api.enabled {
+                    let api_server = api::Server::start(&config).map_err(|error| {
+                        error!(message = "Failed to start the API server.", %error);
+                        exitcode::CONFIG
+                    })?;
+
+                    emit!(ApiStarted {
+                        addr: api_server.addr(),
+                        playground: config.api.playground,
+                        tls: api_server.tls(),
+                    });
+
+                    Some(api_server)
+                } else {
+                    info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
+                    None
+                };
+
                 let diff = config::ConfigDiff::initial(&config);
                 let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
                     .await
                     .ok_or(exitcode::CONFIG)?;
 
-                #[cfg(feature = "api")]
-                let api = config.api;
-
                 let result = topology::start_validated(config, diff, pieces).await;
                 let (topology, graceful_crash) = result.ok_or(exitcode::CONFIG)?;
 
+                #[cfg(feature = "api")]
+                if let Some(ref api_server) = api_server {
+                    api_server.set_tap_outputs(topology.watch_tap_outputs());
+                    api_server.set_ready(true);
+                }
+
                 Ok(ApplicationConfig {
                     config_paths,
                     topology,
                     graceful_crash,
                     #[cfg(feature = "api")]
-                    api,
+                    api_server,
                 })
             })
         }?;
@@ -197,7 +232,8 @@ impl Application {
         let opts = self.opts;
 
         #[cfg(feature = "api")]
-        let api_config = self.config.api;
+        // assigned to prevent the API terminating when falling out of scope
+        let api_server = self.config.api_server;
 
         // Any internal_logs sources will have grabbed a copy of the
         // early buffer by this point and set up a subscriber.
@@ -205,22 +241,15 @@ impl Application {
 
         rt.block_on(async move {
             emit!(VectorStarted);
+            if let Some(name) = allocator::unavailable_requested() {
+                warn!(
+                    message = "Requested allocator isn't built in, using the default one instead.",
+                    allocator = %name,
+                );
+            }
+            info!(message = "Using allocator.", allocator = allocator::selected().as_str());
             tokio::spawn(heartbeat::heartbeat());
 
-            #[cfg(feature = "api")]
-            // assigned to prevent the API terminating when falling out of scope
-            let api_server = if api_config.enabled {
-                emit!(ApiStarted {
-                    addr: api_config.address.unwrap(),
-                    playground: api_config.playground
-                });
-
-                Some(api::Server::start(topology.config()))
-            } else {
-                info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
-                None
-            };
-
             let signals = signal::signals();
             tokio::pin!(signals);
             let mut sources_finished = topology.sources_finished();
@@ -232,7 +261,7 @@ impl Application {
                         // Reload paths
                         config_paths = config::process_paths(&opts.config_paths_with_formats()).unwrap_or(config_paths);
                         // Reload config
-                        let new_config = config::load_from_paths(&config_paths, false).map_err(handle_config_errors).ok();
+                        let new_config = load_config(&config_paths, opts.expose_metrics_port).map_err(handle_config_errors).ok();
 
                         if let Some(mut new_config) = new_config {
                             new_config.healthchecks.set_require_healthy(opts.require_healthy);
@@ -271,6 +300,11 @@ impl Application {
             }
             };
 
+            #[cfg(feature = "api")]
+            if let Some(ref api_server) = api_server {
+                api_server.set_ready(false);
+            }
+
             match signal {
                 SignalTo::Shutdown => {
                     emit!(VectorStopped);
@@ -293,3 +327,16 @@ impl Application {
         });
     }
 }
+
+/// Loads the configuration, along with the components added by
+/// `--expose-metrics-port`.
+fn load_config(
+    config_paths: &[(PathBuf, config::FormatHint)],
+    expose_metrics_port: Option<u16>,
+) -> Result<config::Config, Vec<String>> {
+    let mut builder = config::load_builder_from_paths(config_paths, false)?;
+    if let Some(port) = expose_metrics_port {
+        builder.expose_internal_metrics(port)?;
+    }
+    builder.build()
+}
@@ -0,0 +1,427 @@
+//! Measure how a config performs under synthetic load.
+//!
+//! The sources of the config are replaced by `generator` sources, which emit
+//! events as fast as the pipeline accepts them, and its sinks are replaced by
+//! stubs that count the events they receive. After running for a fixed
+//! duration, the throughput and latency of each sink are reported, along with
+//! the CPU and memory used by the process.
+
+use crate::{
+    buffers::Acker,
+    cli::handle_config_errors,
+    config::{self, log_schema, DataType, SinkConfig, SinkContext},
+    event::{Event, Value},
+    sinks::{util::StreamSink, Healthcheck, VectorSink},
+    sources::generator::{GeneratorConfig, OutputFormat},
+    topology,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::{future, stream::BoxStream, FutureExt, StreamExt};
+use heim::units::{information::byte, time::second};
+use indexmap::IndexMap;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use structopt::StructOpt;
+
+/// Number of latencies kept per sink to compute percentiles.
+const LATENCY_SAMPLES: usize = 10_000;
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// How long to run the benchmark for, in seconds.
+    #[structopt(short, long, default_value = "10")]
+    duration: u64,
+
+    /// Format of the events generated in place of the sources.
+    #[structopt(
+        short,
+        long,
+        default_value = "json",
+        possible_values = &["apache_common", "apache_error", "syslog", "bsd_syslog", "json"]
+    )]
+    format: String,
+
+    /// Vector config files in TOML format to benchmark.
+    #[structopt(name = "config-toml", long)]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format to benchmark.
+    #[structopt(name = "config-json", long)]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format to benchmark.
+    #[structopt(name = "config-yaml", long)]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Any number of Vector config files to benchmark.
+    /// Format is detected from the file name.
+    /// If none are specified the default config path `/etc/vector/vector.toml`
+    /// will be targeted.
+    paths: Vec<PathBuf>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<(PathBuf, config::FormatHint)> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::TOML)),
+            (&self.paths_json, Some(config::Format::JSON)),
+            (&self.paths_yaml, Some(config::Format::YAML)),
+        ])
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        match self.format.as_str() {
+            "apache_common" => OutputFormat::ApacheCommon,
+            "apache_error" => OutputFormat::ApacheError,
+            "syslog" => OutputFormat::Syslog,
+            "bsd_syslog" => OutputFormat::BsdSyslog,
+            _ => OutputFormat::Json,
+        }
+    }
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let mut config = match config::load_from_paths(&paths, false) {
+        Ok(config) => config,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    config::LOG_SCHEMA
+        .set(config.global.log_schema.clone())
+        .expect("Couldn't set schema");
+
+    let stats = instrument(&mut config, opts.output_format());
+
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+
+    let (topology, _crash) = match topology::start_validated(config, diff, pieces).await {
+        Some(started) => started,
+        None => return exitcode::CONFIG,
+    };
+
+    let paths = paths.iter().map(|(path, _)| path).collect::<Vec<_>>();
+    println!("Benchmarking {:?} for {}s", paths, opts.duration);
+
+    let process = heim::process::current().await.ok();
+    let cpu_start = cpu_seconds(process.as_ref()).await;
+    let start = Instant::now();
+    let end = start + Duration::from_secs(opts.duration);
+
+    // Sample the memory use every second, to report the peak.
+    let mut max_rss = 0;
+    while Instant::now() < end {
+        max_rss = max_rss.max(rss_bytes(process.as_ref()).await.unwrap_or(0));
+        let remaining = end.saturating_duration_since(Instant::now());
+        tokio::time::delay_for(remaining.min(Duration::from_secs(1))).await;
+    }
+
+    let elapsed = start.elapsed();
+    let cpu_end = cpu_seconds(process.as_ref()).await;
+    let report = Report::new(&stats, elapsed);
+
+    topology.stop().await;
+
+    report.print();
+    match (cpu_start, cpu_end) {
+        (Some(start), Some(end)) => println!(
+            "CPU: {:.1}% of one core",
+            (end - start) / elapsed.as_secs_f64() * 100.0
+        ),
+        _ => println!("CPU: unavailable"),
+    }
+    println!("Peak memory: {:.1} MiB", max_rss as f64 / (1024.0 * 1024.0));
+
+    exitcode::OK
+}
+
+/// Replace the sources of the config with generators, and its sinks with
+/// stubs. Sources that only emit metrics are kept, since the generator only
+/// emits logs.
+fn instrument(
+    config: &mut config::Config,
+    format: OutputFormat,
+) -> IndexMap<String, Arc<Mutex<SinkStats>>> {
+    for source in config.sources.values_mut() {
+        if source.inner.output_type() != DataType::Metric {
+            source.inner = Box::new(GeneratorConfig::with_format(format.clone()));
+        }
+    }
+
+    config
+        .sinks
+        .iter_mut()
+        .map(|(name, sink)| {
+            let stub = StubSinkConfig::default();
+            let stats = Arc::clone(&stub.stats);
+            sink.inner = Box::new(stub);
+            (name.clone(), stats)
+        })
+        .collect()
+}
+
+async fn cpu_seconds(process: Option<&heim::process::Process>) -> Option<f64> {
+    let time = process?.cpu_time().await.ok()?;
+    Some(time.user().get::<second>() + time.system().get::<second>())
+}
+
+async fn rss_bytes(process: Option<&heim::process::Process>) -> Option<u64> {
+    let memory = process?.memory().await.ok()?;
+    Some(memory.rss().get::<byte>())
+}
+
+/// What a stub sink measured.
+#[derive(Debug, Default)]
+struct SinkStats {
+    events: usize,
+    latencies: Latencies,
+}
+
+/// A uniform random sample of the latencies of the events received, so memory
+/// use stays the same however many events are received.
+#[derive(Debug, Default)]
+struct Latencies {
+    samples: Vec<Duration>,
+    seen: usize,
+    max: Duration,
+}
+
+impl Latencies {
+    fn record(&mut self, latency: Duration) {
+        self.seen += 1;
+        self.max = self.max.max(latency);
+
+        if self.samples.len() < LATENCY_SAMPLES {
+            self.samples.push(latency);
+        } else {
+            let index = thread_rng().gen_range(0..self.seen);
+            if index < LATENCY_SAMPLES {
+                self.samples[index] = latency;
+            }
+        }
+    }
+
+    /// The latencies at the given percentiles, or `None` if no latency was
+    /// recorded.
+    fn percentiles(&self, percentiles: &[f64]) -> Option<Vec<Duration>> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut samples = self.samples.clone();
+        samples.sort();
+
+        Some(
+            percentiles
+                .iter()
+                .map(|percentile| {
+                    let rank = (percentile / 100.0 * samples.len() as f64).ceil() as usize;
+                    samples[rank.saturating_sub(1).min(samples.len() - 1)]
+                })
+                .collect(),
+        )
+    }
+}
+
+struct SinkReport {
+    name: String,
+    events: usize,
+    /// p50, p90, p99 and max.
+    latencies: Option<Vec<Duration>>,
+}
+
+struct Report {
+    elapsed: Duration,
+    sinks: Vec<SinkReport>,
+}
+
+impl Report {
+    fn new(stats: &IndexMap<String, Arc<Mutex<SinkStats>>>, elapsed: Duration) -> Self {
+        let sinks = stats
+            .iter()
+            .map(|(name, stats)| {
+                let stats = stats.lock().expect("stats lock poisoned");
+                let latencies =
+                    stats
+                        .latencies
+                        .percentiles(&[50.0, 90.0, 99.0])
+                        .map(|mut latencies| {
+                            latencies.push(stats.latencies.max);
+                            latencies
+                        });
+
+                SinkReport {
+                    name: name.clone(),
+                    events: stats.events,
+                    latencies,
+                }
+            })
+            .collect();
+
+        Self { elapsed, sinks }
+    }
+
+    fn print(&self) {
+        println!();
+        for sink in &self.sinks {
+            println!("Sink `{}`", sink.name);
+            println!("  Events: {}", sink.events);
+            println!(
+                "  Throughput: {:.0} events/s",
+                sink.events as f64 / self.elapsed.as_secs_f64()
+            );
+            match &sink.latencies {
+                Some(latencies) => println!(
+                    "  Latency: p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+                    latencies[0], latencies[1], latencies[2], latencies[3]
+                ),
+                None => println!("  Latency: unavailable"),
+            }
+            println!();
+        }
+    }
+}
+
+/// A sink that counts the events it receives, and measures their latency from
+/// their timestamp.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct StubSinkConfig {
+    #[serde(skip)]
+    stats: Arc<Mutex<SinkStats>>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "benchmark_stub")]
+impl SinkConfig for StubSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = StubSink {
+            stats: Arc::clone(&self.stats),
+            acker: cx.acker(),
+        };
+
+        Ok((VectorSink::Stream(Box::new(sink)), future::ok(()).boxed()))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "benchmark_stub"
+    }
+}
+
+struct StubSink {
+    stats: Arc<Mutex<SinkStats>>,
+    acker: Acker,
+}
+
+#[async_trait]
+impl StreamSink for StubSink {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        while let Some(event) = input.next().await {
+            let timestamp = match &event {
+                Event::Log(log) => match log.get(log_schema().timestamp_key()) {
+                    Some(Value::Timestamp(timestamp)) => Some(*timestamp),
+                    _ => None,
+                },
+                Event::Metric(metric) => metric.data.timestamp,
+            };
+
+            {
+                let mut stats = self.stats.lock().expect("stats lock poisoned");
+                stats.events += 1;
+                if let Some(latency) = timestamp
+                    .and_then(|timestamp| Utc::now().signed_duration_since(timestamp).to_std().ok())
+                {
+                    stats.latencies.record(latency);
+                }
+            }
+
+            self.acker.ack(1);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_percentiles() {
+        let mut latencies = Latencies::default();
+        for millis in 1..=100 {
+            latencies.record(Duration::from_millis(millis));
+        }
+
+        assert_eq!(
+            latencies.percentiles(&[50.0, 90.0, 99.0]),
+            Some(vec![
+                Duration::from_millis(50),
+                Duration::from_millis(90),
+                Duration::from_millis(99),
+            ])
+        );
+        assert_eq!(latencies.max, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn latency_samples_are_bounded() {
+        let mut latencies = Latencies::default();
+        for millis in 0..(LATENCY_SAMPLES as u64 * 2) {
+            latencies.record(Duration::from_millis(millis));
+        }
+
+        assert_eq!(latencies.samples.len(), LATENCY_SAMPLES);
+        assert_eq!(latencies.seen, LATENCY_SAMPLES * 2);
+    }
+
+    #[test]
+    fn no_latencies() {
+        assert_eq!(Latencies::default().percentiles(&[50.0]), None);
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-console"))]
+    #[test]
+    fn instruments_config() {
+        let mut config = config::load_from_str(
+            r#"
+            [sources.in]
+              type = "stdin"
+
+            [sinks.out]
+              type = "console"
+              inputs = ["in"]
+              encoding = "json"
+            "#,
+            Some(config::Format::TOML),
+        )
+        .unwrap();
+
+        let stats = instrument(&mut config, OutputFormat::Json);
+
+        assert_eq!(config.sources["in"].inner.source_type(), "generator");
+        assert_eq!(config.sinks["out"].inner.sink_type(), "benchmark_stub");
+        assert_eq!(stats.keys().collect::<Vec<_>>(), vec!["out"]);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::event::{proto, Event};
+use crate::event::{
+    finalization::{EventFinalizers, EventStatus},
+    proto, Event,
+};
 use bytes::Bytes;
 use futures01::{
     task::{self, AtomicTask, Task},
@@ -54,8 +57,11 @@ pub struct Writer {
     blocked_write_tasks: Arc<Mutex<Vec<Task>>>,
     writebatch: Writebatch<Key>,
     batch_size: usize,
+    /// The finalizers of the events in the write batch.
+    finalizers: EventFinalizers,
     max_size: usize,
     current_size: Arc<AtomicUsize>,
+    current_events: Arc<AtomicUsize>,
 }
 
 // Writebatch isn't Send, but the leveldb docs explicitly say that it's okay to share across threads
@@ -70,8 +76,10 @@ impl Clone for Writer {
             blocked_write_tasks: Arc::clone(&self.blocked_write_tasks),
             writebatch: Writebatch::new(),
             batch_size: 0,
+            finalizers: Default::default(),
             max_size: self.max_size,
             current_size: Arc::clone(&self.current_size),
+            current_events: Arc::clone(&self.current_events),
         }
     }
 }
@@ -82,8 +90,9 @@ impl Sink for Writer {
 
     fn start_send(
         &mut self,
-        event: Self::SinkItem,
+        mut event: Self::SinkItem,
     ) -> Result<AsyncSink<Self::SinkItem>, Self::SinkError> {
+        let finalizers = event.take_finalizers();
         let mut value = vec![];
         proto::EventWrapper::from(event).encode(&mut value).unwrap(); // This will not error when writing to a Vec
         let event_size = value.len();
@@ -101,14 +110,18 @@ impl Sink for Writer {
             self.poll_complete()?;
 
             let buf = Bytes::from(value);
-            let event = proto::EventWrapper::decode(buf).unwrap().into();
+            let mut event: Event = proto::EventWrapper::decode(buf).unwrap().into();
+            event.add_finalizers(finalizers);
             return Ok(AsyncSink::NotReady(event));
         }
 
+        self.finalizers.merge(finalizers);
+
         let key = self.offset.fetch_add(1, Ordering::Relaxed);
 
         self.writebatch.put(Key(key), &value);
         self.batch_size += 1;
+        self.current_events.fetch_add(1, Ordering::Relaxed);
 
         if self.batch_size >= 100 {
             self.poll_complete()?;
@@ -130,12 +143,23 @@ impl Sink for Writer {
 }
 
 impl Writer {
+    pub fn events(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.current_events)
+    }
+
+    pub fn byte_size(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.current_size)
+    }
+
     fn write_batch(&mut self) {
         self.db
             .write(WriteOptions::new(), &self.writebatch)
             .unwrap();
         self.writebatch = Writebatch::new();
         self.batch_size = 0;
+        // The events are delivered as far as their sources are concerned once
+        // they're written to the buffer.
+        self.finalizers.take().update_status(EventStatus::Delivered);
         self.write_notifier.notify();
     }
 }
@@ -158,6 +182,7 @@ pub struct Reader {
     write_notifier: Arc<AtomicTask>,
     blocked_write_tasks: Arc<Mutex<Vec<Task>>>,
     current_size: Arc<AtomicUsize>,
+    current_events: Arc<AtomicUsize>,
     ack_counter: Arc<AtomicUsize>,
     uncompacted_size: usize,
     unacked_sizes: VecDeque<usize>,
@@ -196,6 +221,7 @@ impl Stream for Reader {
         if let Some(value) = self.buffer.pop() {
             self.unacked_sizes.push_back(value.len());
             self.read_offset += 1;
+            self.current_events.fetch_sub(1, Ordering::Relaxed);
 
             let buf = Bytes::from(value);
             match proto::EventWrapper::decode(buf) {
@@ -303,6 +329,7 @@ impl super::DiskBuffer for Buffer {
 
         let initial_size = db.value_iter(ReadOptions::new()).map(|v| v.len()).sum();
         let current_size = Arc::new(AtomicUsize::new(initial_size));
+        let current_events = Arc::new(AtomicUsize::new(tail - head));
 
         let write_notifier = Arc::new(AtomicTask::new());
 
@@ -318,8 +345,10 @@ impl super::DiskBuffer for Buffer {
             offset: Arc::new(AtomicUsize::new(tail)),
             writebatch: Writebatch::new(),
             batch_size: 0,
+            finalizers: Default::default(),
             max_size,
             current_size: Arc::clone(&current_size),
+            current_events: Arc::clone(&current_events),
         };
 
         let mut reader = Reader {
@@ -329,6 +358,7 @@ impl super::DiskBuffer for Buffer {
             read_offset: head,
             delete_offset: head,
             current_size,
+            current_events,
             ack_counter,
             max_uncompacted_size,
             uncompacted_size: 1,
@@ -5,6 +5,7 @@ use futures01::{Async, AsyncSink, Poll, Sink, Stream};
 use snafu::Snafu;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::{atomic::AtomicUsize, Arc};
 
 pub mod leveldb_buffer;
 
@@ -41,6 +42,18 @@ pub struct Writer {
     inner: leveldb_buffer::Writer,
 }
 
+impl Writer {
+    /// Events written to the buffer, that haven't been read yet.
+    pub fn events(&self) -> Arc<AtomicUsize> {
+        self.inner.events()
+    }
+
+    /// Bytes of the events in the buffer, that haven't been acknowledged yet.
+    pub fn byte_size(&self) -> Arc<AtomicUsize> {
+        self.inner.byte_size()
+    }
+}
+
 impl Sink for Writer {
     type SinkItem = Event;
     type SinkError = ();
@@ -0,0 +1,996 @@
+//! A disk buffer storing events in a series of size-capped data files, without
+//! LevelDB.
+//!
+//! Writers append events to the newest data file, as records framed with the
+//! length and CRC32 checksum of their encoding, and move on to a new data file
+//! once it's full. The reader goes through the data files in order, and a data
+//! file is deleted once all of its events are acknowledged. The position past
+//! the last acknowledged event is stored in a ledger file, for the reader to
+//! resume from on startup.
+//!
+//! Data that isn't synced to disk yet, as controlled by the `fsync` policy, is
+//! lost on a crash. A record cut short by a crash is truncated from the last
+//! data file on startup, and the rest of a data file is skipped when one of its
+//! records doesn't match its checksum.
+
+mod record;
+
+use self::record::{Record, RecordReader};
+use super::Acker;
+use crate::{
+    event::{
+        finalization::{EventFinalizers, EventStatus},
+        proto, Event,
+    },
+    internal_events::{DiskBufferCorrupted, DiskBufferIoError},
+};
+use bytes::Bytes;
+use futures::{task::AtomicWaker, Sink, Stream};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryInto,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+/// The largest size of data files. Data files of smaller buffers are capped to
+/// a quarter of the buffer, as acknowledged events are only deleted along with
+/// their data file.
+const MAX_DATA_FILE_SIZE: u64 = 128 * 1024 * 1024;
+
+/// Writes are buffered in memory up to this size, or until flushed.
+const WRITE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// How often the `periodic` policy syncs data to disk.
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+const LEDGER_FILE: &str = "buffer.ledger";
+
+/// When the buffer syncs the data written to it to disk.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum FsyncPolicy {
+    /// After each batch of events written, and each acknowledgement.
+    Always,
+    /// At most once a second.
+    Periodic,
+    /// Only when a data file is full, leaving the rest to the operating system.
+    Never,
+}
+
+impl Default for FsyncPolicy {
+    fn default() -> Self {
+        FsyncPolicy::Periodic
+    }
+}
+
+impl FsyncPolicy {
+    fn is_due(self, synced_at: Instant) -> bool {
+        match self {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Periodic => synced_at.elapsed() >= SYNC_INTERVAL,
+            FsyncPolicy::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The configured data_dir {:?} does not exist, please create it and make sure the vector process can write to it", data_dir))]
+    DataDirNotFound { data_dir: PathBuf },
+    #[snafu(display("The configured data_dir {:?} is not writable by the vector process, please ensure vector can write to that directory", data_dir))]
+    DataDirNotWritable { data_dir: PathBuf },
+    #[snafu(display("Unable to look up data_dir {:?}", data_dir))]
+    DataDirMetadataError {
+        data_dir: PathBuf,
+        source: io::Error,
+    },
+    #[snafu(display("Unable to open the disk buffer in {:?}: {}", path, source))]
+    BufferOpenError { path: PathBuf, source: io::Error },
+}
+
+/// A position in the data files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Position {
+    file_id: u64,
+    offset: u64,
+}
+
+/// The events and bytes of the records of a data file.
+#[derive(Clone, Copy, Debug, Default)]
+struct FileStats {
+    events: usize,
+    bytes: usize,
+}
+
+struct Shared {
+    dir: PathBuf,
+    max_size: usize,
+    max_data_file_size: u64,
+    fsync: FsyncPolicy,
+    /// Events written to the buffer, that haven't been read yet.
+    events: Arc<AtomicUsize>,
+    /// Bytes of the records that haven't been acknowledged yet.
+    byte_size: Arc<AtomicUsize>,
+    writer: Mutex<WriterState>,
+    acks: Mutex<AckState>,
+    writers: AtomicUsize,
+    /// Wakes the reader once more records are written, or the writers are gone.
+    read_waker: AtomicWaker,
+    /// Writers waiting for room in the buffer.
+    blocked_writers: Mutex<Vec<Waker>>,
+}
+
+impl Shared {
+    fn data_file(&self, file_id: u64) -> PathBuf {
+        data_file(&self.dir, file_id)
+    }
+
+    fn lock_writer(&self) -> MutexGuard<'_, WriterState> {
+        self.writer.lock().expect("Disk buffer writer poisoned.")
+    }
+
+    fn lock_acks(&self) -> MutexGuard<'_, AckState> {
+        self.acks
+            .lock()
+            .expect("Disk buffer acknowledgements poisoned.")
+    }
+
+    fn is_full(&self) -> bool {
+        self.byte_size.load(Ordering::Acquire) >= self.max_size
+    }
+
+    fn ack(&self, num: usize) {
+        let mut acks = self.lock_acks();
+        let mut size = 0;
+        let mut position = None;
+        for _ in 0..num {
+            match acks.unacked.pop_front() {
+                Some((end, record_size)) => {
+                    size += record_size;
+                    position = Some(end);
+                }
+                None => break,
+            }
+        }
+
+        if let Some(position) = position {
+            if let Err(error) = acks.advance(self, position) {
+                emit!(DiskBufferIoError {
+                    path: &self.dir,
+                    error: &error,
+                });
+            }
+        }
+        drop(acks);
+
+        self.release(size);
+    }
+
+    /// Frees the room of records that won't be acknowledged anymore.
+    fn release(&self, size: usize) {
+        if size > 0 {
+            self.byte_size.fetch_sub(size, Ordering::AcqRel);
+            for waker in self
+                .blocked_writers
+                .lock()
+                .expect("Disk buffer writers poisoned.")
+                .drain(..)
+            {
+                waker.wake();
+            }
+        }
+    }
+}
+
+struct WriterState {
+    file_id: u64,
+    file: File,
+    /// The size of the data file, including the pending writes.
+    file_size: u64,
+    pending: Vec<u8>,
+    /// The finalizers of the events of the pending writes.
+    finalizers: EventFinalizers,
+    /// The events and bytes written to the data files the reader isn't done
+    /// with, to account for the records skipped when a data file is corrupted.
+    files: BTreeMap<u64, FileStats>,
+    synced_at: Instant,
+    needs_sync: bool,
+}
+
+impl WriterState {
+    fn write_pending(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            self.file.write_all(&self.pending)?;
+            self.pending.clear();
+            self.needs_sync = true;
+        }
+        // The events are delivered as far as their sources are concerned once
+        // they're written to the buffer.
+        self.finalizers.take().update_status(EventStatus::Delivered);
+        Ok(())
+    }
+
+    fn sync(&mut self, fsync: FsyncPolicy) -> io::Result<()> {
+        if self.needs_sync && fsync.is_due(self.synced_at) {
+            self.file.sync_data()?;
+            self.needs_sync = false;
+            self.synced_at = Instant::now();
+        }
+        Ok(())
+    }
+
+    /// Moves on to a new data file. Full data files are always synced, so the
+    /// records of the data files before the last one are all on disk.
+    fn roll(&mut self, dir: &Path) -> io::Result<()> {
+        self.write_pending()?;
+        self.file.sync_data()?;
+
+        let file_id = self.file_id + 1;
+        self.file = open_data_file(dir, file_id)?;
+        self.file_id = file_id;
+        self.file_size = 0;
+        self.needs_sync = false;
+        self.synced_at = Instant::now();
+        Ok(())
+    }
+}
+
+struct AckState {
+    /// The position past each record read and not acknowledged yet, along with
+    /// the size of the record.
+    unacked: VecDeque<(Position, usize)>,
+    ledger: File,
+    /// The oldest data file that may not be deleted yet.
+    oldest_file_id: u64,
+    synced_at: Instant,
+}
+
+impl AckState {
+    /// Stores the position past the acknowledged records in the ledger, and
+    /// deletes the data files before it.
+    fn advance(&mut self, shared: &Shared, position: Position) -> io::Result<()> {
+        self.ledger.seek(SeekFrom::Start(0))?;
+        self.ledger.write_all(&encode_ledger(position))?;
+        if shared.fsync.is_due(self.synced_at) {
+            self.ledger.sync_data()?;
+            self.synced_at = Instant::now();
+        }
+
+        while self.oldest_file_id < position.file_id {
+            remove_data_file(&shared.data_file(self.oldest_file_id))?;
+            self.oldest_file_id += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Acknowledges the events read from the buffer.
+#[derive(Clone)]
+pub struct Acks(Arc<Shared>);
+
+impl Acks {
+    pub(super) fn ack(&self, num: usize) {
+        self.0.ack(num);
+    }
+}
+
+impl fmt::Debug for Acks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Acks").field(&self.0.dir).finish()
+    }
+}
+
+pub struct Writer {
+    shared: Arc<Shared>,
+}
+
+impl Writer {
+    /// Events written to the buffer, that haven't been read yet.
+    pub fn events(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.shared.events)
+    }
+
+    /// Bytes of the events in the buffer, that haven't been acknowledged yet.
+    pub fn byte_size(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.shared.byte_size)
+    }
+
+    fn write_failed(&self, error: io::Error) {
+        emit!(DiskBufferIoError {
+            path: &self.shared.dir,
+            error: &error,
+        });
+    }
+}
+
+impl Clone for Writer {
+    fn clone(&self) -> Self {
+        self.shared.writers.fetch_add(1, Ordering::AcqRel);
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        let result = self.shared.lock_writer().write_pending();
+        if let Err(error) = result {
+            self.write_failed(error);
+        }
+
+        // The reader is woken up to end once there are no writers left.
+        self.shared.writers.fetch_sub(1, Ordering::AcqRel);
+        self.shared.read_waker.wake();
+    }
+}
+
+impl Sink<Event> for Writer {
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let shared = &self.shared;
+        if !shared.is_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        shared
+            .blocked_writers
+            .lock()
+            .expect("Disk buffer writers poisoned.")
+            .push(cx.waker().clone());
+
+        // Events may have been acknowledged in the meantime.
+        if shared.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, mut event: Event) -> Result<(), Self::Error> {
+        let finalizers = event.take_finalizers();
+        let mut payload = Vec::new();
+        proto::EventWrapper::from(event)
+            .encode(&mut payload)
+            .expect("Encoding to a Vec can't fail.");
+        let size = record::encoded_len(payload.len());
+
+        let shared = &self.shared;
+        let mut state = shared.lock_writer();
+        if state.file_size > 0 && state.file_size + size as u64 > shared.max_data_file_size {
+            state
+                .roll(&shared.dir)
+                .map_err(|error| self.write_failed(error))?;
+            shared.read_waker.wake();
+        }
+
+        record::encode(&payload, &mut state.pending);
+        state.finalizers.merge(finalizers);
+        state.file_size += size as u64;
+        let file_id = state.file_id;
+        let stats = state.files.entry(file_id).or_default();
+        stats.events += 1;
+        stats.bytes += size;
+
+        // The event is counted before it can be read, so the counts can't drop
+        // below zero.
+        shared.events.fetch_add(1, Ordering::AcqRel);
+        shared.byte_size.fetch_add(size, Ordering::AcqRel);
+
+        if state.pending.len() >= WRITE_BUFFER_SIZE {
+            state
+                .write_pending()
+                .map_err(|error| self.write_failed(error))?;
+            shared.read_waker.wake();
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let shared = &self.shared;
+        let result = {
+            let mut state = shared.lock_writer();
+            state
+                .write_pending()
+                .and_then(|()| state.sync(shared.fsync))
+        };
+        shared.read_waker.wake();
+
+        Poll::Ready(result.map_err(|error| self.write_failed(error)))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+}
+
+pub struct Reader {
+    shared: Arc<Shared>,
+    file_id: u64,
+    records: RecordReader,
+    /// Whether the writers moved on to a later data file, in which case the
+    /// current one is done once read to its end.
+    file_complete: bool,
+    /// Whether there were no writers left once the reader got to the end of
+    /// the data written.
+    writers_gone: bool,
+    /// The events and bytes read from the current data file.
+    read: FileStats,
+}
+
+impl Reader {
+    /// Moves on to the next data file, skipping the records of the current one
+    /// that weren't read, if it's corrupted.
+    fn next_file(&mut self) {
+        let stats = {
+            let mut state = self.shared.lock_writer();
+            // The writers can't append to a corrupted data file anymore.
+            if state.file_id == self.file_id {
+                if let Err(error) = state.roll(&self.shared.dir) {
+                    emit!(DiskBufferIoError {
+                        path: &self.shared.dir,
+                        error: &error,
+                    });
+                }
+            }
+            state.files.remove(&self.file_id).unwrap_or_default()
+        };
+
+        self.shared.events.fetch_sub(
+            stats.events.saturating_sub(self.read.events),
+            Ordering::AcqRel,
+        );
+        self.shared
+            .release(stats.bytes.saturating_sub(self.read.bytes));
+
+        self.file_id += 1;
+        self.file_complete = false;
+        self.read = FileStats::default();
+        let path = self.shared.data_file(self.file_id);
+        self.records = RecordReader::open(&path, 0).unwrap_or_else(|error| {
+            // The reader moves on from the missing file once the writers are
+            // past it.
+            emit!(DiskBufferIoError {
+                path: &path,
+                error: &error,
+            });
+            RecordReader::empty()
+        });
+    }
+
+    fn skip_corrupted(&mut self, reason: &str) {
+        emit!(DiskBufferCorrupted {
+            path: &self.shared.data_file(self.file_id),
+            reason,
+        });
+        self.next_file();
+    }
+}
+
+impl Stream for Reader {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut registered = false;
+
+        loop {
+            match this.records.next() {
+                Ok(Record::Payload(payload)) => {
+                    let size = record::encoded_len(payload.len());
+                    this.read.events += 1;
+                    this.read.bytes += size;
+                    this.shared.events.fetch_sub(1, Ordering::AcqRel);
+
+                    match proto::EventWrapper::decode(Bytes::from(payload)) {
+                        Ok(event) => {
+                            let end = Position {
+                                file_id: this.file_id,
+                                offset: this.records.offset(),
+                            };
+                            this.shared.lock_acks().unacked.push_back((end, size));
+                            return Poll::Ready(Some(event.into()));
+                        }
+                        Err(error) => {
+                            emit!(DiskBufferCorrupted {
+                                path: &this.shared.data_file(this.file_id),
+                                reason: &error.to_string(),
+                            });
+                            this.shared.release(size);
+                            continue;
+                        }
+                    }
+                }
+                Ok(Record::Corrupted) => {
+                    this.skip_corrupted("checksum mismatch");
+                    continue;
+                }
+                Ok(Record::End) => {}
+                Err(error) => {
+                    emit!(DiskBufferIoError {
+                        path: &this.shared.data_file(this.file_id),
+                        error: &error,
+                    });
+                    this.skip_corrupted("read failed");
+                    continue;
+                }
+            }
+
+            // At the end of the records written so far.
+            if !this.file_complete {
+                // Records written before the reader registers to be woken up
+                // are read before waiting.
+                if !registered {
+                    this.shared.read_waker.register(cx.waker());
+                    registered = true;
+                    continue;
+                }
+
+                if this.shared.lock_writer().file_id <= this.file_id {
+                    if this.shared.writers.load(Ordering::Acquire) > 0 {
+                        return Poll::Pending;
+                    }
+                    // The last writer may have written its pending records
+                    // since, which are read before ending.
+                    if this.writers_gone {
+                        return Poll::Ready(None);
+                    }
+                    this.writers_gone = true;
+                    continue;
+                }
+
+                // The writers may have appended to the file before moving on.
+                this.file_complete = true;
+                continue;
+            }
+
+            if this.records.partial_len() > 0 {
+                this.skip_corrupted("truncated record");
+            } else {
+                this.next_file();
+            }
+        }
+    }
+}
+
+/// Checks the data directory, and opens the buffer in its `name` directory.
+pub fn open(
+    data_dir: &Path,
+    name: &str,
+    max_size: usize,
+    fsync: FsyncPolicy,
+) -> Result<(Writer, Reader, Acker), Error> {
+    std::fs::metadata(&data_dir)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::PermissionDenied => Error::DataDirNotWritable {
+                data_dir: data_dir.into(),
+            },
+            io::ErrorKind::NotFound => Error::DataDirNotFound {
+                data_dir: data_dir.into(),
+            },
+            _ => Error::DataDirMetadataError {
+                data_dir: data_dir.into(),
+                source: e,
+            },
+        })
+        .and_then(|m| {
+            if m.permissions().readonly() {
+                Err(Error::DataDirNotWritable {
+                    data_dir: data_dir.into(),
+                })
+            } else {
+                Ok(())
+            }
+        })?;
+
+    let path = data_dir.join(name);
+    build(path.clone(), max_size, fsync).context(BufferOpenError { path })
+}
+
+fn build(dir: PathBuf, max_size: usize, fsync: FsyncPolicy) -> io::Result<(Writer, Reader, Acker)> {
+    fs::create_dir_all(&dir)?;
+
+    let ledger_path = dir.join(LEDGER_FILE);
+    let acked = match fs::read(&ledger_path) {
+        Ok(ledger) => decode_ledger(&ledger),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+        Err(error) => return Err(error),
+    };
+
+    let mut file_ids = data_file_ids(&dir)?;
+    let mut start = acked.unwrap_or(Position {
+        file_id: file_ids.first().copied().unwrap_or(0),
+        offset: 0,
+    });
+
+    // The data files before the acknowledged position may be left over from a
+    // crash.
+    for &file_id in file_ids.iter().filter(|&&file_id| file_id < start.file_id) {
+        remove_data_file(&data_file(&dir, file_id))?;
+    }
+    file_ids.retain(|&file_id| file_id >= start.file_id);
+    if file_ids.first() != Some(&start.file_id) {
+        start = Position {
+            file_id: file_ids.first().copied().unwrap_or(start.file_id),
+            offset: 0,
+        };
+    }
+
+    let last_file_id = file_ids.last().copied().unwrap_or(start.file_id);
+    let mut files = BTreeMap::new();
+    let mut total = FileStats::default();
+    let mut last_file_size = 0;
+    for &file_id in &file_ids {
+        let path = data_file(&dir, file_id);
+        let offset = if file_id == start.file_id {
+            start.offset
+        } else {
+            0
+        };
+        let scan = scan_data_file(&path, offset)?;
+
+        if file_id == start.file_id {
+            start.offset = start.offset.min(scan.end);
+        }
+        if file_id == last_file_id {
+            // Writers append to the last data file, past its last record.
+            if let Some(reason) = scan.corruption {
+                emit!(DiskBufferCorrupted {
+                    path: &path,
+                    reason,
+                });
+                OpenOptions::new()
+                    .write(true)
+                    .open(&path)?
+                    .set_len(scan.end)?;
+            }
+            last_file_size = scan.end;
+        }
+
+        files.insert(file_id, scan.stats);
+        total.events += scan.stats.events;
+        total.bytes += scan.stats.bytes;
+    }
+
+    let writer_state = WriterState {
+        file_id: last_file_id,
+        file: open_data_file(&dir, last_file_id)?,
+        file_size: last_file_size,
+        pending: Vec::new(),
+        finalizers: Default::default(),
+        files,
+        synced_at: Instant::now(),
+        needs_sync: false,
+    };
+    let records = RecordReader::open(&data_file(&dir, start.file_id), start.offset)?;
+    let mut ledger = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&ledger_path)?;
+    ledger.write_all(&encode_ledger(start))?;
+    ledger.sync_data()?;
+    let ack_state = AckState {
+        unacked: VecDeque::new(),
+        ledger,
+        oldest_file_id: start.file_id,
+        synced_at: Instant::now(),
+    };
+
+    let shared = Arc::new(Shared {
+        max_size,
+        max_data_file_size: (max_size as u64 / 4).min(MAX_DATA_FILE_SIZE),
+        fsync,
+        events: Arc::new(AtomicUsize::new(total.events)),
+        byte_size: Arc::new(AtomicUsize::new(total.bytes)),
+        writer: Mutex::new(writer_state),
+        acks: Mutex::new(ack_state),
+        writers: AtomicUsize::new(1),
+        read_waker: AtomicWaker::new(),
+        blocked_writers: Mutex::new(Vec::new()),
+        dir,
+    });
+
+    let writer = Writer {
+        shared: Arc::clone(&shared),
+    };
+    let reader = Reader {
+        shared: Arc::clone(&shared),
+        file_id: start.file_id,
+        records,
+        file_complete: false,
+        writers_gone: false,
+        read: FileStats::default(),
+    };
+    let acker = Acker::DiskV2(Acks(shared));
+
+    Ok((writer, reader, acker))
+}
+
+struct Scan {
+    stats: FileStats,
+    /// The offset past the last valid record.
+    end: u64,
+    corruption: Option<&'static str>,
+}
+
+fn scan_data_file(path: &Path, offset: u64) -> io::Result<Scan> {
+    let offset = offset.min(fs::metadata(path)?.len());
+    let mut records = RecordReader::open(path, offset)?;
+    let mut stats = FileStats::default();
+
+    let corruption = loop {
+        match records.next()? {
+            Record::Payload(payload) => {
+                stats.events += 1;
+                stats.bytes += record::encoded_len(payload.len());
+            }
+            Record::Corrupted => break Some("checksum mismatch"),
+            Record::End if records.partial_len() > 0 => break Some("truncated record"),
+            Record::End => break None,
+        }
+    };
+
+    Ok(Scan {
+        stats,
+        end: records.offset(),
+        corruption,
+    })
+}
+
+fn data_file(dir: &Path, file_id: u64) -> PathBuf {
+    dir.join(format!("buffer-data-{:020}.dat", file_id))
+}
+
+fn data_file_ids(dir: &Path) -> io::Result<Vec<u64>> {
+    let mut file_ids = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let file_id = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("buffer-data-"))
+            .and_then(|name| name.strip_suffix(".dat"))
+            .and_then(|file_id| file_id.parse().ok());
+        if let Some(file_id) = file_id {
+            file_ids.push(file_id);
+        }
+    }
+
+    file_ids.sort_unstable();
+    Ok(file_ids)
+}
+
+fn open_data_file(dir: &Path, file_id: u64) -> io::Result<File> {
+    OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(data_file(dir, file_id))
+}
+
+fn remove_data_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// The ledger holds the position past the acknowledged events, as little-endian
+/// `u64`, followed by their CRC32 checksum.
+fn encode_ledger(position: Position) -> [u8; 20] {
+    let mut ledger = [0; 20];
+    ledger[0..8].copy_from_slice(&position.file_id.to_le_bytes());
+    ledger[8..16].copy_from_slice(&position.offset.to_le_bytes());
+    let checksum = crc32fast::hash(&ledger[0..16]);
+    ledger[16..20].copy_from_slice(&checksum.to_le_bytes());
+    ledger
+}
+
+fn decode_ledger(ledger: &[u8]) -> Option<Position> {
+    if ledger.len() != 20 {
+        return None;
+    }
+
+    let checksum = u32::from_le_bytes(ledger[16..20].try_into().ok()?);
+    if crc32fast::hash(&ledger[0..16]) != checksum {
+        return None;
+    }
+
+    Some(Position {
+        file_id: u64::from_le_bytes(ledger[0..8].try_into().ok()?),
+        offset: u64::from_le_bytes(ledger[8..16].try_into().ok()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{future, stream, SinkExt, StreamExt};
+    use tempfile::tempdir;
+
+    fn events(messages: std::ops::Range<usize>) -> Vec<Event> {
+        messages
+            .map(|i| Event::from(format!("event {}", i)))
+            .collect()
+    }
+
+    fn messages(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect()
+    }
+
+    async fn write(writer: &mut Writer, events: Vec<Event>) {
+        writer
+            .send_all(&mut stream::iter(events).map(Ok))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reads_written_events() {
+        let dir = tempdir().unwrap();
+        let (mut writer, reader, acker) =
+            open(dir.path(), "buffer", 10_000, FsyncPolicy::Always).unwrap();
+
+        write(&mut writer, events(0..10)).await;
+        assert_eq!(writer.events().load(Ordering::Acquire), 10);
+        let byte_size = writer.byte_size();
+        drop(writer);
+
+        let read = reader.collect::<Vec<_>>().await;
+        assert_eq!(messages(&read), messages(&events(0..10)));
+
+        acker.ack(10);
+        assert_eq!(byte_size.load(Ordering::Acquire), 0);
+    }
+
+    #[tokio::test]
+    async fn resumes_after_acknowledged_events() {
+        let dir = tempdir().unwrap();
+        {
+            let (mut writer, mut reader, acker) =
+                open(dir.path(), "buffer", 10_000, FsyncPolicy::Always).unwrap();
+            write(&mut writer, events(0..10)).await;
+            for _ in 0..5 {
+                reader.next().await.unwrap();
+            }
+            acker.ack(4);
+        }
+
+        let (writer, reader, _acker) =
+            open(dir.path(), "buffer", 10_000, FsyncPolicy::Always).unwrap();
+        assert_eq!(writer.events().load(Ordering::Acquire), 6);
+        drop(writer);
+
+        let read = reader.collect::<Vec<_>>().await;
+        assert_eq!(messages(&read), messages(&events(4..10)));
+    }
+
+    #[tokio::test]
+    async fn deletes_acknowledged_data_files() {
+        let dir = tempdir().unwrap();
+        let (mut writer, reader, acker) =
+            open(dir.path(), "buffer", 2_000, FsyncPolicy::Never).unwrap();
+
+        write(&mut writer, events(0..20)).await;
+        let byte_size = writer.byte_size();
+        drop(writer);
+        let buffer_dir = dir.path().join("buffer");
+        assert!(data_file_ids(&buffer_dir).unwrap().len() > 2);
+
+        let read = reader.collect::<Vec<_>>().await;
+        assert_eq!(read.len(), 20);
+        acker.ack(20);
+        assert_eq!(data_file_ids(&buffer_dir).unwrap().len(), 1);
+        assert_eq!(byte_size.load(Ordering::Acquire), 0);
+    }
+
+    #[tokio::test]
+    async fn truncates_partial_records() {
+        let dir = tempdir().unwrap();
+        {
+            let (mut writer, _reader, _acker) =
+                open(dir.path(), "buffer", 10_000, FsyncPolicy::Always).unwrap();
+            write(&mut writer, events(0..3)).await;
+        }
+
+        let path = data_file(&dir.path().join("buffer"), 0);
+        let size = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[1, 2, 3])
+            .unwrap();
+
+        let (mut writer, reader, _acker) =
+            open(dir.path(), "buffer", 10_000, FsyncPolicy::Always).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), size);
+        write(&mut writer, events(3..4)).await;
+        drop(writer);
+
+        let read = reader.collect::<Vec<_>>().await;
+        assert_eq!(messages(&read), messages(&events(0..4)));
+    }
+
+    #[tokio::test]
+    async fn skips_corrupted_data_files() {
+        let dir = tempdir().unwrap();
+        {
+            let (mut writer, _reader, _acker) =
+                open(dir.path(), "buffer", 1_000, FsyncPolicy::Always).unwrap();
+            write(&mut writer, events(0..10)).await;
+        }
+
+        let buffer_dir = dir.path().join("buffer");
+        let file_ids = data_file_ids(&buffer_dir).unwrap();
+        assert!(file_ids.len() > 1);
+
+        let path = data_file(&buffer_dir, file_ids[0]);
+        let first_file_events = {
+            let mut records = RecordReader::open(&path, 0).unwrap();
+            let mut count = 0;
+            while let Record::Payload(_) = records.next().unwrap() {
+                count += 1;
+            }
+            count
+        };
+        assert!(first_file_events > 1);
+
+        // Corrupt the second record of the first data file.
+        let mut data = fs::read(&path).unwrap();
+        let first_len = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
+        data[record::encoded_len(first_len) + 10] ^= 1;
+        fs::write(&path, &data).unwrap();
+
+        let (writer, reader, acker) =
+            open(dir.path(), "buffer", 1_000, FsyncPolicy::Always).unwrap();
+        let byte_size = writer.byte_size();
+        drop(writer);
+
+        let read = reader.collect::<Vec<_>>().await;
+        let mut expected = events(0..1);
+        expected.extend(events(first_file_events..10));
+        assert_eq!(messages(&read), messages(&expected));
+
+        acker.ack(read.len());
+        assert_eq!(byte_size.load(Ordering::Acquire), 0);
+    }
+
+    #[tokio::test]
+    async fn blocks_when_full() {
+        let dir = tempdir().unwrap();
+        let (mut writer, mut reader, acker) =
+            open(dir.path(), "buffer", 100, FsyncPolicy::Never).unwrap();
+
+        write(&mut writer, events(0..2)).await;
+        future::lazy(|cx| {
+            assert_eq!(Pin::new(&mut writer).poll_ready(cx), Poll::Pending);
+        })
+        .await;
+
+        reader.next().await.unwrap();
+        reader.next().await.unwrap();
+        acker.ack(2);
+        future::lazy(|cx| {
+            assert_eq!(Pin::new(&mut writer).poll_ready(cx), Poll::Ready(Ok(())));
+        })
+        .await;
+    }
+}
@@ -0,0 +1,184 @@
+//! The framing of the records of data files: the length of the payload and its
+//! CRC32 checksum, both as little-endian `u32`, followed by the payload.
+
+use std::{
+    convert::TryInto,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+const HEADER_LEN: usize = 8;
+
+/// How much is read from data files at once.
+const READ_SIZE: usize = 64 * 1024;
+
+/// The size of the record of a payload of `len` bytes.
+pub(super) fn encoded_len(len: usize) -> usize {
+    HEADER_LEN + len
+}
+
+pub(super) fn encode(payload: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    out.extend_from_slice(&crc32fast::hash(payload).to_le_bytes());
+    out.extend_from_slice(payload);
+}
+
+#[derive(Debug, PartialEq)]
+enum Decoded<'a> {
+    Record(&'a [u8]),
+    Corrupted,
+    Incomplete,
+}
+
+fn decode(buf: &[u8]) -> Decoded<'_> {
+    if buf.len() < HEADER_LEN {
+        return Decoded::Incomplete;
+    }
+
+    let len = u32::from_le_bytes(buf[0..4].try_into().expect("Slice is 4 bytes.")) as usize;
+    let checksum = u32::from_le_bytes(buf[4..8].try_into().expect("Slice is 4 bytes."));
+    match buf[HEADER_LEN..].get(..len) {
+        Some(payload) if crc32fast::hash(payload) == checksum => Decoded::Record(payload),
+        Some(_) => Decoded::Corrupted,
+        None => Decoded::Incomplete,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Record {
+    Payload(Vec<u8>),
+    /// The next record doesn't match its checksum, and the records after it
+    /// can't be found.
+    Corrupted,
+    /// No more complete records were written so far.
+    End,
+}
+
+/// Reads the records of a data file, including the ones appended to it while
+/// it's read.
+pub(super) struct RecordReader {
+    file: Option<File>,
+    buf: Vec<u8>,
+    start: usize,
+    offset: u64,
+}
+
+impl RecordReader {
+    pub(super) fn open(path: &Path, offset: u64) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+
+        Ok(Self {
+            file: Some(file),
+            buf: Vec::new(),
+            start: 0,
+            offset,
+        })
+    }
+
+    /// A reader of a data file that couldn't be opened, which has no records.
+    pub(super) fn empty() -> Self {
+        Self {
+            file: None,
+            buf: Vec::new(),
+            start: 0,
+            offset: 0,
+        }
+    }
+
+    /// The offset in the file past the records read so far.
+    pub(super) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// The bytes read past the last record, which don't make a complete record.
+    pub(super) fn partial_len(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    pub(super) fn next(&mut self) -> io::Result<Record> {
+        loop {
+            match decode(&self.buf[self.start..]) {
+                Decoded::Record(payload) => {
+                    let payload = payload.to_vec();
+                    let len = encoded_len(payload.len());
+                    self.start += len;
+                    self.offset += len as u64;
+                    return Ok(Record::Payload(payload));
+                }
+                Decoded::Corrupted => return Ok(Record::Corrupted),
+                Decoded::Incomplete => {}
+            }
+
+            let file = match &mut self.file {
+                Some(file) => file,
+                None => return Ok(Record::End),
+            };
+
+            // Only the partial record is kept.
+            self.buf.drain(..self.start);
+            self.start = 0;
+
+            let len = self.buf.len();
+            self.buf.resize(len + READ_SIZE, 0);
+            let read = match file.read(&mut self.buf[len..]) {
+                Ok(read) => read,
+                Err(error) => {
+                    self.buf.truncate(len);
+                    return Err(error);
+                }
+            };
+            self.buf.truncate(len + read);
+            if read == 0 {
+                return Ok(Record::End);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn decodes_encoded_records() {
+        let mut buf = Vec::new();
+        encode(b"first", &mut buf);
+        encode(b"", &mut buf);
+        assert_eq!(buf.len(), encoded_len(5) + encoded_len(0));
+
+        assert_eq!(decode(&buf), Decoded::Record(b"first"));
+        assert_eq!(decode(&buf[encoded_len(5)..]), Decoded::Record(b""));
+        assert_eq!(decode(&buf[..encoded_len(5) - 1]), Decoded::Incomplete);
+
+        buf[HEADER_LEN] ^= 1;
+        assert_eq!(decode(&buf), Decoded::Corrupted);
+    }
+
+    #[test]
+    fn reads_appended_records() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data");
+        let mut file = File::create(&path).unwrap();
+
+        let mut buf = Vec::new();
+        encode(b"first", &mut buf);
+        encode(b"second", &mut buf);
+        let (first, second) = buf.split_at(encoded_len(5) + 3);
+        file.write_all(first).unwrap();
+
+        let mut reader = RecordReader::open(&path, 0).unwrap();
+        assert_eq!(reader.next().unwrap(), Record::Payload(b"first".to_vec()));
+        assert_eq!(reader.next().unwrap(), Record::End);
+        assert_eq!(reader.partial_len(), 3);
+        assert_eq!(reader.offset(), encoded_len(5) as u64);
+
+        file.write_all(second).unwrap();
+        assert_eq!(reader.next().unwrap(), Record::Payload(b"second".to_vec()));
+        assert_eq!(reader.next().unwrap(), Record::End);
+        assert_eq!(reader.partial_len(), 0);
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{config::Resource, sink::BoundedSink, Event};
+use crate::{
+    config::Resource,
+    internal_events::{DiscardReason, EventsDiscarded},
+    sink::BoundedSink,
+    Event,
+};
 #[cfg(feature = "leveldb")]
 use futures::compat::{Sink01CompatExt, Stream01CompatExt};
 use futures::{Sink, Stream};
@@ -20,6 +25,10 @@ use tokio::sync::mpsc;
 
 #[cfg(feature = "leveldb")]
 pub mod disk;
+pub mod disk_v2;
+mod usage;
+
+pub use usage::{report_usage, BufferUsage, CountingSink, CountingStream};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
@@ -37,6 +46,13 @@ pub enum BufferConfig {
         #[serde(default)]
         when_full: WhenFull,
     },
+    DiskV2 {
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+        #[serde(default)]
+        fsync: disk_v2::FsyncPolicy,
+    },
 }
 
 impl Default for BufferConfig {
@@ -63,34 +79,66 @@ impl Default for WhenFull {
 
 #[derive(Clone)]
 pub enum BufferInputCloner {
-    Memory(mpsc::Sender<Event>, WhenFull),
+    Memory(mpsc::Sender<Event>, WhenFull, Arc<BufferUsage>),
     #[cfg(feature = "leveldb")]
-    Disk(disk::Writer, WhenFull),
+    Disk(disk::Writer, WhenFull, Arc<BufferUsage>),
+    DiskV2(disk_v2::Writer, WhenFull, Arc<BufferUsage>),
 }
 
 impl BufferInputCloner {
     pub fn get(&self) -> Box<dyn Sink<Event, Error = ()> + Send> {
         match self {
-            BufferInputCloner::Memory(tx, when_full) => {
-                let inner = BoundedSink::new(tx.clone());
+            BufferInputCloner::Memory(tx, when_full, usage) => {
+                let inner = CountingSink::new(BoundedSink::new(tx.clone()), usage);
                 if when_full == &WhenFull::DropNewest {
-                    Box::new(DropWhenFull::new(inner))
+                    Box::new(DropWhenFull::new(inner, Arc::clone(usage)))
                 } else {
                     Box::new(inner)
                 }
             }
 
             #[cfg(feature = "leveldb")]
-            BufferInputCloner::Disk(writer, when_full) => {
+            BufferInputCloner::Disk(writer, when_full, usage) => {
                 let inner = writer.clone().sink_compat();
                 if when_full == &WhenFull::DropNewest {
-                    Box::new(DropWhenFull::new(inner))
+                    Box::new(DropWhenFull::new(inner, Arc::clone(usage)))
+                } else {
+                    Box::new(inner)
+                }
+            }
+
+            BufferInputCloner::DiskV2(writer, when_full, usage) => {
+                let inner = writer.clone();
+                if when_full == &WhenFull::DropNewest {
+                    Box::new(DropWhenFull::new(inner, Arc::clone(usage)))
                 } else {
                     Box::new(inner)
                 }
             }
         }
     }
+
+    /// How full the buffer is, to report by the component reading from it.
+    pub fn usage(&self) -> Arc<BufferUsage> {
+        match self {
+            BufferInputCloner::Memory(_, _, usage) => Arc::clone(usage),
+            #[cfg(feature = "leveldb")]
+            BufferInputCloner::Disk(_, _, usage) => Arc::clone(usage),
+            BufferInputCloner::DiskV2(_, _, usage) => Arc::clone(usage),
+        }
+    }
+}
+
+/// Builds a memory buffer, holding up to `max_events`.
+pub fn memory(
+    max_events: usize,
+    when_full: WhenFull,
+) -> (BufferInputCloner, CountingStream<mpsc::Receiver<Event>>) {
+    let usage = Arc::new(BufferUsage::memory(max_events));
+    let (tx, rx) = mpsc::channel(max_events);
+    let rx = CountingStream::new(rx, &usage);
+
+    (BufferInputCloner::Memory(tx, when_full, usage), rx)
 }
 
 impl BufferConfig {
@@ -99,7 +147,6 @@ impl BufferConfig {
         500
     }
 
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
     pub fn build(
         &self,
         data_dir: &Option<PathBuf>,
@@ -117,8 +164,7 @@ impl BufferConfig {
                 max_events,
                 when_full,
             } => {
-                let (tx, rx) = mpsc::channel(*max_events);
-                let tx = BufferInputCloner::Memory(tx, *when_full);
+                let (tx, rx) = memory(*max_events, *when_full);
                 let rx = Box::new(rx);
                 Ok((tx, rx, Acker::Null))
             }
@@ -135,7 +181,13 @@ impl BufferConfig {
 
                 let (tx, rx, acker) = disk::open(&data_dir, buffer_dir.as_ref(), *max_size)
                     .map_err(|error| error.to_string())?;
-                let tx = BufferInputCloner::Disk(tx, *when_full);
+                let usage = BufferUsage::disk(
+                    tx.events(),
+                    tx.byte_size(),
+                    *max_size,
+                    data_dir.join(buffer_dir),
+                );
+                let tx = BufferInputCloner::Disk(tx, *when_full, Arc::new(usage));
                 let rx = Box::new(
                     rx.compat()
                         .take_while(|event| event.is_ok())
@@ -143,16 +195,39 @@ impl BufferConfig {
                 );
                 Ok((tx, rx, acker))
             }
+
+            BufferConfig::DiskV2 {
+                max_size,
+                when_full,
+                fsync,
+            } => {
+                let data_dir = data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?;
+                let buffer_dir = format!("{}_buffer_v2", sink_name);
+
+                let (tx, rx, acker) =
+                    disk_v2::open(&data_dir, buffer_dir.as_ref(), *max_size, *fsync)
+                        .map_err(|error| error.to_string())?;
+                let usage = BufferUsage::disk(
+                    tx.events(),
+                    tx.byte_size(),
+                    *max_size,
+                    data_dir.join(buffer_dir),
+                );
+                let tx = BufferInputCloner::DiskV2(tx, *when_full, Arc::new(usage));
+                Ok((tx, Box::new(rx), acker))
+            }
         }
     }
 
     /// Resources that the sink is using.
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
     pub fn resources(&self, sink_name: &str) -> Vec<Resource> {
         match self {
             BufferConfig::Memory { .. } => Vec::new(),
             #[cfg(feature = "leveldb")]
             BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
+            BufferConfig::DiskV2 { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
         }
     }
 }
@@ -160,6 +235,7 @@ impl BufferConfig {
 #[derive(Debug, Clone)]
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicTask>),
+    DiskV2(disk_v2::Acks),
     Null,
 }
 
@@ -179,6 +255,7 @@ impl Acker {
                     counter.fetch_add(num, Ordering::Relaxed);
                     notifier.notify();
                 }
+                Acker::DiskV2(acks) => acks.ack(num),
             }
         }
     }
@@ -197,11 +274,16 @@ pub struct DropWhenFull<S> {
     #[pin]
     inner: S,
     drop: bool,
+    usage: Arc<BufferUsage>,
 }
 
 impl<S> DropWhenFull<S> {
-    pub fn new(inner: S) -> Self {
-        Self { inner, drop: false }
+    pub fn new(inner: S, usage: Arc<BufferUsage>) -> Self {
+        Self {
+            inner,
+            drop: false,
+            usage,
+        }
     }
 }
 
@@ -229,6 +311,11 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
                 message = "Shedding load; dropping event.",
                 internal_log_rate_secs = 10
             );
+            emit!(EventsDiscarded {
+                count: 1,
+                reason: DiscardReason::BufferFull,
+            });
+            self.usage.discarded(1);
             Ok(())
         } else {
             self.project().inner.start_send(item)
@@ -246,15 +333,20 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
 
 #[cfg(test)]
 mod test {
-    use super::{Acker, BufferConfig, DropWhenFull, WhenFull};
-    use crate::sink::BoundedSink;
-    use futures::{future, Sink, Stream};
+    use super::{Acker, BufferConfig, BufferUsage, DropWhenFull, WhenFull};
+    use crate::{
+        event::finalization::{BatchNotifier, BatchStatus, EventStatus},
+        sink::BoundedSink,
+        Event,
+    };
+    use futures::{future, Sink, SinkExt, Stream, StreamExt};
     use futures01::task::AtomicTask;
     use std::{
+        pin::Pin,
         sync::{atomic::AtomicUsize, Arc},
         task::Poll,
     };
-    use tokio::sync::mpsc;
+    use tokio::sync::{mpsc, oneshot::error::TryRecvError};
     use tokio01_test::task::MockTask;
 
     #[tokio::test]
@@ -262,7 +354,8 @@ mod test {
         future::lazy(|cx| {
             let (tx, rx) = mpsc::channel(3);
 
-            let mut tx = Box::pin(DropWhenFull::new(BoundedSink::new(tx)));
+            let usage = Arc::new(BufferUsage::default());
+            let mut tx = Box::pin(DropWhenFull::new(BoundedSink::new(tx), Arc::clone(&usage)));
 
             assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
             assert_eq!(tx.as_mut().start_send(1), Ok(()));
@@ -279,6 +372,7 @@ mod test {
             assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(2)));
             assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(3)));
             assert_eq!(rx.as_mut().poll_next(cx), Poll::Pending);
+            assert_eq!(usage.discarded_events(), 1);
         })
         .await;
     }
@@ -300,6 +394,25 @@ mod test {
         assert!(mock.is_notified());
     }
 
+    #[tokio::test]
+    async fn memory_buffer_keeps_finalizers() {
+        let (tx, rx, _) = BufferConfig::default().build(&None, "sink").unwrap();
+        let mut tx = Pin::from(tx.get());
+        let mut rx = Pin::from(rx);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        tx.send(Event::from("acknowledged").with_batch_notifier(&batch))
+            .await
+            .unwrap();
+        drop(batch);
+
+        // The sink finalizes the events it reads once they're delivered.
+        let mut event = rx.next().await.unwrap();
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+        event.take_finalizers().update_status(EventStatus::Errored);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
+    }
+
     #[test]
     fn config_default_values() {
         fn check(source: &str, config: BufferConfig) {
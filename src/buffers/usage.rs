@@ -0,0 +1,244 @@
+use crate::internal_events::BufferUsageReported;
+use futures::{Sink, Stream};
+use pin_project::pin_project;
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::time::{interval, Duration};
+
+/// How full a buffer is. Shared by the writers and the reader of the buffer, and reported as
+/// internal metrics of the component reading from it.
+#[derive(Debug, Default)]
+pub struct BufferUsage {
+    /// Events written to the buffer, that haven't been read yet.
+    events: Arc<AtomicUsize>,
+    /// Events dropped since the last report, because the buffer was full.
+    discarded_events: AtomicUsize,
+    /// Events a memory buffer holds at most.
+    max_events: Option<usize>,
+    disk: Option<DiskUsage>,
+}
+
+#[derive(Debug)]
+struct DiskUsage {
+    /// Bytes of the events that haven't been acknowledged yet.
+    byte_size: Arc<AtomicUsize>,
+    /// Bytes of events the buffer holds at most.
+    max_size: usize,
+    /// Directory of the buffer.
+    path: PathBuf,
+}
+
+impl BufferUsage {
+    pub(super) fn memory(max_events: usize) -> Self {
+        Self {
+            max_events: Some(max_events),
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn disk(
+        events: Arc<AtomicUsize>,
+        byte_size: Arc<AtomicUsize>,
+        max_size: usize,
+        path: PathBuf,
+    ) -> Self {
+        Self {
+            events,
+            discarded_events: AtomicUsize::new(0),
+            max_events: None,
+            disk: Some(DiskUsage {
+                byte_size,
+                max_size,
+                path,
+            }),
+        }
+    }
+
+    pub(super) fn discarded(&self, count: usize) {
+        self.discarded_events.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn events(&self) -> usize {
+        self.events.load(Ordering::Relaxed)
+    }
+
+    #[cfg(test)]
+    pub(super) fn discarded_events(&self) -> usize {
+        self.discarded_events.load(Ordering::Relaxed)
+    }
+
+    fn buffer_type(&self) -> &'static str {
+        if self.disk.is_some() {
+            "disk"
+        } else {
+            "memory"
+        }
+    }
+
+    async fn report(&self) {
+        let (byte_size, max_size, disk_usage) = match &self.disk {
+            Some(disk) => (
+                Some(disk.byte_size.load(Ordering::Relaxed)),
+                Some(disk.max_size),
+                directory_size(&disk.path).await,
+            ),
+            None => (None, None, None),
+        };
+
+        emit!(BufferUsageReported {
+            buffer_type: self.buffer_type(),
+            events: self.events(),
+            max_events: self.max_events,
+            byte_size,
+            max_size,
+            disk_usage,
+            discarded_events: self.discarded_events.swap(0, Ordering::Relaxed),
+        });
+    }
+}
+
+/// Sums the size of the files in `path`, or returns `None` if they can't be listed.
+async fn directory_size(path: &Path) -> Option<u64> {
+    let mut entries = tokio::fs::read_dir(path).await.ok()?;
+    let mut size = 0;
+
+    while let Some(entry) = entries.next_entry().await.ok()? {
+        size += entry.metadata().await.ok()?.len();
+    }
+
+    Some(size)
+}
+
+/// Runs `future`, the task of a component, while reporting the usage of the buffer the component
+/// reads from every second.
+pub async fn report_usage<F: Future>(usage: Arc<BufferUsage>, future: F) -> F::Output {
+    let report = async move {
+        let mut interval = interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            usage.report().await;
+        }
+    };
+
+    tokio::select! {
+        output = future => output,
+        _ = report => unreachable!("Reporting buffer usage never finishes."),
+    }
+}
+
+/// Counts the events written to a memory buffer.
+#[pin_project]
+pub struct CountingSink<S> {
+    #[pin]
+    inner: S,
+    events: Arc<AtomicUsize>,
+}
+
+impl<S> CountingSink<S> {
+    pub fn new(inner: S, usage: &BufferUsage) -> Self {
+        Self {
+            inner,
+            events: Arc::clone(&usage.events),
+        }
+    }
+}
+
+impl<T, S: Sink<T>> Sink<T> for CountingSink<S> {
+    type Error = S::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.project();
+
+        // Count the event before it can be read, so the count can't drop below zero.
+        this.events.fetch_add(1, Ordering::Relaxed);
+        this.inner.start_send(item).map_err(|error| {
+            this.events.fetch_sub(1, Ordering::Relaxed);
+            error
+        })
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+/// Counts the events read from a memory buffer.
+#[pin_project]
+pub struct CountingStream<S> {
+    #[pin]
+    inner: S,
+    events: Arc<AtomicUsize>,
+}
+
+impl<S> CountingStream<S> {
+    pub fn new(inner: S, usage: &BufferUsage) -> Self {
+        Self {
+            inner,
+            events: Arc::clone(&usage.events),
+        }
+    }
+}
+
+impl<S: Stream> Stream for CountingStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let poll = this.inner.poll_next(cx);
+
+        if let Poll::Ready(Some(_)) = poll {
+            this.events.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        poll
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BufferUsage, CountingSink, CountingStream};
+    use crate::sink::BoundedSink;
+    use futures::{future, Sink, Stream};
+    use std::task::Poll;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn counts_buffered_events() {
+        future::lazy(|cx| {
+            let usage = BufferUsage::default();
+            let (tx, rx) = mpsc::channel(3);
+
+            let mut tx = Box::pin(CountingSink::new(BoundedSink::new(tx), &usage));
+            let mut rx = Box::pin(CountingStream::new(rx, &usage));
+
+            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+            assert_eq!(tx.as_mut().start_send(1), Ok(()));
+            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+            assert_eq!(tx.as_mut().start_send(2), Ok(()));
+            assert_eq!(usage.events(), 2);
+
+            assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(1)));
+            assert_eq!(usage.events(), 1);
+            assert_eq!(rx.as_mut().poll_next(cx), Poll::Ready(Some(2)));
+            assert_eq!(rx.as_mut().poll_next(cx), Poll::Pending);
+            assert_eq!(usage.events(), 0);
+        })
+        .await;
+    }
+}
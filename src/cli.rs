@@ -2,8 +2,10 @@ use crate::{config, generate, get_version, list, unit_test, validate};
 use std::path::PathBuf;
 use structopt::{clap::AppSettings, StructOpt};
 
+#[cfg(feature = "benchmark-cli")]
+use crate::benchmark;
 #[cfg(feature = "api-client")]
-use crate::top;
+use crate::{tap, top};
 
 #[cfg(windows)]
 use crate::service;
@@ -113,6 +115,11 @@ pub struct RootOpts {
     /// Watch for changes in configuration file, and reload accordingly.
     #[structopt(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
+
+    /// Expose the internal metrics of Vector in the Prometheus format on the given port,
+    /// in addition to the components of the configuration.
+    #[structopt(long, env = "VECTOR_EXPOSE_METRICS_PORT")]
+    pub expose_metrics_port: Option<u16>,
 }
 
 impl RootOpts {
@@ -143,10 +150,25 @@ pub enum SubCommand {
     /// For guidance on how to write unit tests check out: https://vector.dev/docs/setup/guides/unit-testing/
     Test(unit_test::Opts),
 
+    /// Run a config with generated events in place of its sources, and report its throughput,
+    /// latency and resource use, then exit.
+    #[cfg(feature = "benchmark-cli")]
+    Benchmark(benchmark::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
 
+    /// Display the buffers of components and how full they are in the console, for a local or
+    /// remote Vector instance
+    #[cfg(feature = "api-client")]
+    Buffers(top::Opts),
+
+    /// Print samples of the events flowing into or out of a component, for a local or remote
+    /// Vector instance
+    #[cfg(feature = "api-client")]
+    Tap(tap::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
@@ -40,12 +40,21 @@ impl ConditionConfig for RemapConfig {
             .filter(|f| f.identifier() != "only_fields")
             .collect::<Vec<_>>();
 
-        let (program, _) = Program::new(self.source.clone(), &functions, Some(constraint), false)
-            .map_err(|diagnostics| {
-            remap::Formatter::new(&self.source, diagnostics)
-                .colored()
-                .to_string()
-        })?;
+        let (program, diagnostics) =
+            Program::new(self.source.clone(), &functions, Some(constraint), false).map_err(
+                |diagnostics| {
+                    remap::Formatter::new(&self.source, diagnostics)
+                        .colored()
+                        .to_string()
+                },
+            )?;
+
+        if diagnostics.has_warnings() {
+            warn!(
+                message = "Remap condition compiled with warnings.",
+                diagnostics = %remap::Formatter::new(&self.source, diagnostics),
+            );
+        }
 
         Ok(Box::new(Remap { program }))
     }
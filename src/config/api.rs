@@ -1,7 +1,8 @@
+use crate::tls::TlsConfig;
 use serde::{Deserialize, Serialize};
 use std::net::{Ipv4Addr, SocketAddr};
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     #[serde(default = "default_enabled")]
@@ -12,6 +13,33 @@ pub struct Options {
 
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// The path of the liveness probe, which succeeds as soon as the API is up.
+    #[serde(default = "default_health_path")]
+    pub health_path: String,
+
+    /// The path of the readiness probe, which only succeeds once the topology
+    /// is running.
+    #[serde(default = "default_ready_path")]
+    pub ready_path: String,
+
+    /// Serves the API over HTTPS.
+    pub tls: Option<TlsConfig>,
+
+    /// Requires requests to authenticate with a bearer token.
+    pub auth: Option<AuthOptions>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AuthOptions {
+    /// The token requests have to send in an `Authorization: Bearer <token>` header.
+    pub token: String,
+
+    /// Whether the liveness and readiness probes answer without a token, for
+    /// orchestrators that can't send one.
+    #[serde(default = "default_exempt_probes")]
+    pub exempt_probes: bool,
 }
 
 impl Default for Options {
@@ -20,6 +48,10 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            health_path: default_health_path(),
+            ready_path: default_ready_path(),
+            tls: None,
+            auth: None,
         }
     }
 }
@@ -39,6 +71,40 @@ fn default_playground() -> bool {
     true
 }
 
+fn default_health_path() -> String {
+    "/health".into()
+}
+
+fn default_ready_path() -> String {
+    "/ready".into()
+}
+
+fn default_exempt_probes() -> bool {
+    true
+}
+
+/// Merges an optional section, which can only be set once.
+fn merge_section<T: PartialEq>(
+    name: &str,
+    a: Option<T>,
+    b: Option<T>,
+) -> Result<Option<T>, String> {
+    match (a, b) {
+        (Some(a), Some(b)) if a != b => Err(format!("Conflicting `api.{}` sections.", name)),
+        (Some(a), _) => Ok(Some(a)),
+        (None, b) => Ok(b),
+    }
+}
+
+/// Merges a path, preferring the one that isn't the default.
+fn merge_path(name: &str, a: String, b: String, default: String) -> Result<String, String> {
+    match (a == default, b == default) {
+        (false, false) if a != b => Err(format!("Conflicting `api` {}: {}, {} .", name, a, b)),
+        (false, _) => Ok(a),
+        (true, _) => Ok(b),
+    }
+}
+
 impl Options {
     pub fn merge(&mut self, other: Self) -> Result<(), String> {
         // Merge options
@@ -60,10 +126,30 @@ impl Options {
             }
         };
 
+        let health_path = merge_path(
+            "health_path",
+            self.health_path.clone(),
+            other.health_path,
+            default_health_path(),
+        )?;
+        let ready_path = merge_path(
+            "ready_path",
+            self.ready_path.clone(),
+            other.ready_path,
+            default_ready_path(),
+        )?;
+
+        let tls = merge_section("tls", self.tls.clone(), other.tls)?;
+        let auth = merge_section("auth", self.auth.clone(), other.auth)?;
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            health_path,
+            ready_path,
+            tls,
+            auth,
         };
 
         *self = options;
@@ -77,6 +163,7 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -87,6 +174,7 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            ..Options::default()
         }
     );
 }
@@ -98,6 +186,7 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -108,6 +197,7 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            ..Options::default()
         }
     );
 }
@@ -126,3 +216,44 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn path_merge() {
+    let mut a = Options {
+        ready_path: "/readyz".into(),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.ready_path, "/readyz");
+
+    let b = Options {
+        ready_path: "/ready/".into(),
+        ..Options::default()
+    };
+    assert!(a.merge(b).is_err());
+}
+
+#[test]
+fn auth_merge() {
+    let auth = AuthOptions {
+        token: "secret".into(),
+        exempt_probes: true,
+    };
+    let mut a = Options {
+        auth: Some(auth.clone()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.auth, Some(auth));
+
+    let b = Options {
+        auth: Some(AuthOptions {
+            token: "other".into(),
+            exempt_probes: true,
+        }),
+        ..Options::default()
+    };
+    assert!(a.merge(b).is_err());
+}
@@ -2,10 +2,16 @@
 use super::api;
 use super::{
     compiler, default_data_dir, Config, GlobalOptions, HealthcheckOptions, SinkConfig, SinkOuter,
-    SourceConfig, TestDefinition, TransformConfig, TransformOuter,
+    SourceConfig, SourceOuter, TestDefinition, TransformConfig, TransformOuter,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use shared::datetime::TimeZone;
+
+/// Name of the source added by `--expose-metrics-port`.
+const EXPOSED_METRICS_SOURCE: &str = "vector_internal_metrics";
+/// Name of the sink added by `--expose-metrics-port`.
+const EXPOSED_METRICS_SINK: &str = "vector_prometheus_exporter";
 
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(deny_unknown_fields)]
@@ -18,7 +24,7 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
     #[serde(default)]
-    pub sources: IndexMap<String, Box<dyn SourceConfig>>,
+    pub sources: IndexMap<String, SourceOuter>,
     #[serde(default)]
     pub sinks: IndexMap<String, SinkOuter>,
     #[serde(default)]
@@ -63,8 +69,49 @@ impl ConfigBuilder {
         compiler::compile(self, deny_warnings)
     }
 
+    /// Adds an `internal_metrics` source and a `prometheus_exporter` sink
+    /// serving its metrics on the given port, so the internal metrics of
+    /// Vector are available whatever the rest of the configuration is.
+    #[cfg(all(feature = "sources-internal_metrics", feature = "sinks-prometheus"))]
+    pub fn expose_internal_metrics(&mut self, port: u16) -> Result<(), Vec<String>> {
+        use crate::{
+            sinks::prometheus::exporter::PrometheusExporterConfig,
+            sources::internal_metrics::InternalMetricsConfig,
+        };
+        use std::net::{Ipv4Addr, SocketAddr};
+
+        if self.sources.contains_key(EXPOSED_METRICS_SOURCE)
+            || self.sinks.contains_key(EXPOSED_METRICS_SINK)
+        {
+            return Err(vec![format!(
+                "Components named `{}` and `{}` are reserved by `--expose-metrics-port`.",
+                EXPOSED_METRICS_SOURCE, EXPOSED_METRICS_SINK
+            )]);
+        }
+
+        self.add_source(EXPOSED_METRICS_SOURCE, InternalMetricsConfig::default());
+        self.add_sink(
+            EXPOSED_METRICS_SINK,
+            &[EXPOSED_METRICS_SOURCE],
+            PrometheusExporterConfig {
+                address: SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), port),
+                ..Default::default()
+            },
+        );
+
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "sources-internal_metrics", feature = "sinks-prometheus")))]
+    pub fn expose_internal_metrics(&mut self, _port: u16) -> Result<(), Vec<String>> {
+        Err(vec![
+            "`--expose-metrics-port` requires the `internal_metrics` source and the `prometheus_exporter` sink.".to_owned(),
+        ])
+    }
+
     pub fn add_source<S: SourceConfig + 'static, T: Into<String>>(&mut self, name: T, source: S) {
-        self.sources.insert(name.into(), Box::new(source));
+        self.sources
+            .insert(name.into(), SourceOuter::new(Box::new(source)));
     }
 
     pub fn add_sink<S: SinkConfig + 'static, T: Into<String>>(
@@ -89,6 +136,7 @@ impl ConfigBuilder {
         let transform = TransformOuter {
             inner: Box::new(transform),
             inputs,
+            runtime: None,
         };
 
         self.transforms.insert(name.into(), transform);
@@ -112,6 +160,14 @@ impl ConfigBuilder {
             errors.push("conflicting values for 'data_dir' found".to_owned());
         }
 
+        if self.global.timezone == TimeZone::default() {
+            self.global.timezone = with.global.timezone;
+        } else if with.global.timezone != TimeZone::default()
+            && self.global.timezone != with.global.timezone
+        {
+            errors.push("conflicting values for 'timezone' found".to_owned());
+        }
+
         // If the user has multiple config files, we must *merge* log schemas until we meet a
         // conflict, then we are allowed to error.
         if let Err(merge_errors) = self.global.log_schema.merge(with.global.log_schema) {
@@ -0,0 +1,188 @@
+use crate::{
+    config::log_schema,
+    event::{Event, Value},
+    internal_events::EventClockSkewed,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Checks the timestamps of the events of a source against the time Vector
+/// received them, so that agents with skewed clocks can't send events into
+/// time partitions far away from the others.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ClockSkewOptions {
+    /// How far, in seconds, a timestamp may be in the future or in the past
+    /// of the receive time.
+    #[serde(default = "default_tolerance_secs")]
+    pub tolerance_secs: u64,
+    #[serde(default)]
+    pub action: ClockSkewAction,
+    /// The field of logs, or the tag of metrics, set to `true` by the `flag`
+    /// action.
+    #[serde(default = "default_flag_key")]
+    pub flag_key: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockSkewAction {
+    /// Replaces the timestamp with the receive time, keeping the original one
+    /// in the `vector.original_timestamp` metadata field of logs.
+    #[derivative(Default)]
+    Correct,
+    /// Keeps the timestamp, and sets the `flag_key` of the event.
+    Flag,
+}
+
+impl ClockSkewAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClockSkewAction::Correct => "correct",
+            ClockSkewAction::Flag => "flag",
+        }
+    }
+}
+
+fn default_tolerance_secs() -> u64 {
+    300
+}
+
+fn default_flag_key() -> String {
+    "clock_skewed".into()
+}
+
+impl Default for ClockSkewOptions {
+    fn default() -> Self {
+        Self {
+            tolerance_secs: default_tolerance_secs(),
+            action: ClockSkewAction::default(),
+            flag_key: default_flag_key(),
+        }
+    }
+}
+
+impl ClockSkewOptions {
+    /// Corrects or flags the event if its timestamp is further from `received`
+    /// than the tolerance. Events without a timestamp are left alone.
+    pub fn apply(&self, event: &mut Event, received: DateTime<Utc>) {
+        let timestamp = match event {
+            Event::Log(log) => match log.get(log_schema().timestamp_key()) {
+                Some(Value::Timestamp(timestamp)) => *timestamp,
+                _ => return,
+            },
+            Event::Metric(metric) => match metric.data.timestamp {
+                Some(timestamp) => timestamp,
+                None => return,
+            },
+        };
+
+        let skew = timestamp - received;
+        let tolerance = Duration::seconds(self.tolerance_secs as i64);
+        if skew <= tolerance && skew >= -tolerance {
+            return;
+        }
+
+        emit!(EventClockSkewed {
+            skew,
+            action: self.action.as_str(),
+        });
+
+        match (self.action, event) {
+            (ClockSkewAction::Correct, Event::Log(log)) => {
+                log.insert(log_schema().timestamp_key(), received);
+                log.insert_metadata("vector.original_timestamp", timestamp);
+            }
+            (ClockSkewAction::Correct, Event::Metric(metric)) => {
+                metric.data.timestamp = Some(received);
+            }
+            (ClockSkewAction::Flag, Event::Log(log)) => {
+                log.insert(&self.flag_key, true);
+            }
+            (ClockSkewAction::Flag, Event::Metric(metric)) => {
+                metric.set_tag_value(self.flag_key.clone(), "true".into());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::event::{Metric, MetricKind, MetricValue};
+    use chrono::TimeZone;
+
+    fn log(timestamp: DateTime<Utc>) -> Event {
+        let mut event = Event::from("message");
+        event
+            .as_mut_log()
+            .insert(log_schema().timestamp_key(), timestamp);
+        event
+    }
+
+    fn metric(timestamp: DateTime<Utc>) -> Event {
+        Metric::new(
+            "requests".into(),
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_timestamp(Some(timestamp))
+        .into()
+    }
+
+    #[test]
+    fn leaves_timestamps_within_tolerance() {
+        let received = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+        let options = ClockSkewOptions::default();
+
+        for timestamp in &[
+            received + Duration::seconds(300),
+            received - Duration::seconds(300),
+        ] {
+            let mut event = log(*timestamp);
+            options.apply(&mut event, received);
+            assert_eq!(event, log(*timestamp));
+        }
+    }
+
+    #[test]
+    fn corrects_skewed_timestamps() {
+        let received = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+        let skewed = received + Duration::hours(1);
+        let options = ClockSkewOptions::default();
+
+        let mut event = log(skewed);
+        options.apply(&mut event, received);
+        assert_eq!(event, log(received));
+        assert_eq!(
+            event.as_log().get_metadata("vector.original_timestamp"),
+            Some(&Value::Timestamp(skewed))
+        );
+
+        let mut event = metric(received - Duration::days(1));
+        options.apply(&mut event, received);
+        assert_eq!(event, metric(received));
+    }
+
+    #[test]
+    fn flags_skewed_timestamps() {
+        let received = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+        let skewed = received - Duration::hours(1);
+        let options = ClockSkewOptions {
+            action: ClockSkewAction::Flag,
+            ..Default::default()
+        };
+
+        let mut event = log(skewed);
+        options.apply(&mut event, received);
+        assert_eq!(event.as_log()[log_schema().timestamp_key()], skewed.into());
+        assert_eq!(event.as_log()["clock_skewed"], true.into());
+
+        let mut event = metric(skewed);
+        options.apply(&mut event, received);
+        let metric = event.into_metric();
+        assert_eq!(metric.data.timestamp, Some(skewed));
+        assert_eq!(metric.tag_value("clock_skewed"), Some("true".into()));
+    }
+}
@@ -1,10 +1,13 @@
 use super::{builder::ConfigBuilder, handle_warnings, validation, Config, TransformOuter};
 use indexmap::IndexMap;
+use std::collections::HashSet;
 
 pub fn compile(mut builder: ConfigBuilder, deny_warnings: bool) -> Result<Config, Vec<String>> {
     let mut errors = Vec::new();
 
-    expand_wildcards(&mut builder);
+    if let Err(glob_errors) = expand_globs(&mut builder) {
+        errors.extend(glob_errors);
+    }
 
     let expansions = expand_macros(&mut builder)?;
 
@@ -65,6 +68,7 @@ pub(super) fn expand_macros(
                     full_name.clone(),
                     TransformOuter {
                         inputs: t.inputs.clone(),
+                        runtime: t.runtime,
                         inner: child,
                     },
                 );
@@ -84,8 +88,12 @@ pub(super) fn expand_macros(
     }
 }
 
-/// Expand trailing `*` wildcards in input lists
-fn expand_wildcards(config: &mut ConfigBuilder) {
+/// Expand glob patterns (`app_*`, `app_?`, `app_[0-9]`) in input lists.
+///
+/// A pattern never matches the component it is an input of. Patterns that
+/// don't match any component, or that would make a component depend on
+/// itself, are reported as errors.
+fn expand_globs(config: &mut ConfigBuilder) -> Result<(), Vec<String>> {
     let candidates = config
         .sources
         .keys()
@@ -93,29 +101,121 @@ fn expand_wildcards(config: &mut ConfigBuilder) {
         .cloned()
         .collect::<Vec<String>>();
 
+    let mut errors = Vec::new();
+    let mut transform_matches = Vec::new();
+
     for (name, transform) in config.transforms.iter_mut() {
-        expand_wildcards_inner(&mut transform.inputs, name, &candidates);
+        match expand_globs_inner(&mut transform.inputs, "transform", name, &candidates) {
+            Ok(matches) => transform_matches.extend(
+                matches
+                    .into_iter()
+                    .map(|(pattern, input)| (name.clone(), pattern, input)),
+            ),
+            Err(errs) => errors.extend(errs),
+        }
     }
 
     for (name, sink) in config.sinks.iter_mut() {
-        expand_wildcards_inner(&mut sink.inputs, name, &candidates);
+        if let Err(errs) = expand_globs_inner(&mut sink.inputs, "sink", name, &candidates) {
+            errors.extend(errs);
+        }
+    }
+
+    // Sinks can't be the input of another component, so only transforms can
+    // end up depending on themselves.
+    for (name, pattern, input) in transform_matches {
+        if depends_on(&config.transforms, &input, &name) {
+            errors.push(format!(
+                "Input pattern {:?} for transform {:?} matches {:?}, which creates a cycle.",
+                pattern, name, input
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
     }
 }
 
-fn expand_wildcards_inner(inputs: &mut Vec<String>, name: &str, candidates: &[String]) {
+/// Expand the patterns in `inputs` in place, returning the `(pattern, input)`
+/// pairs that were added.
+fn expand_globs_inner(
+    inputs: &mut Vec<String>,
+    kind: &str,
+    name: &str,
+    candidates: &[String],
+) -> Result<Vec<(String, String)>, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut matches = Vec::new();
+
     let raw_inputs = std::mem::take(inputs);
     for raw_input in raw_inputs {
-        if raw_input.ends_with('*') {
-            let prefix = &raw_input[0..raw_input.len() - 1];
-            for input in candidates {
-                if input.starts_with(prefix) && input != name {
-                    inputs.push(input.clone())
+        if !is_glob(&raw_input) {
+            inputs.push(raw_input);
+            continue;
+        }
+
+        let pattern = match glob::Pattern::new(&raw_input) {
+            Ok(pattern) => pattern,
+            Err(error) => {
+                errors.push(format!(
+                    "Input pattern {:?} for {} {:?} is invalid: {}.",
+                    raw_input, kind, name, error
+                ));
+                continue;
+            }
+        };
+
+        let mut matched = false;
+        for input in candidates {
+            if input != name && pattern.matches(input) {
+                matched = true;
+                if !inputs.contains(input) {
+                    inputs.push(input.clone());
+                    matches.push((raw_input.clone(), input.clone()));
                 }
             }
-        } else {
-            inputs.push(raw_input);
+        }
+
+        if !matched {
+            errors.push(format!(
+                "Input pattern {:?} for {} {:?} doesn't match any components.",
+                raw_input, kind, name
+            ));
         }
     }
+
+    if errors.is_empty() {
+        Ok(matches)
+    } else {
+        Err(errors)
+    }
+}
+
+fn is_glob(input: &str) -> bool {
+    input.contains(|c: char| matches!(c, '*' | '?' | '['))
+}
+
+/// Returns `true` if `target` is reachable by following the inputs of `node`.
+fn depends_on(transforms: &IndexMap<String, TransformOuter>, node: &str, target: &str) -> bool {
+    let mut stack = vec![node];
+    let mut visited = HashSet::new();
+
+    while let Some(node) = stack.pop() {
+        if node == target {
+            return true;
+        }
+
+        if visited.insert(node) {
+            if let Some(transform) = transforms.get(node) {
+                stack.extend(transform.inputs.iter().map(String::as_str));
+            }
+        }
+    }
+
+    false
 }
 
 #[cfg(test)]
@@ -166,7 +266,7 @@ mod test {
     #[async_trait]
     #[typetag::serde(name = "mock")]
     impl TransformConfig for MockTransformConfig {
-        async fn build(&self) -> crate::Result<Transform> {
+        async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
             unimplemented!()
         }
 
@@ -218,4 +318,53 @@ mod test {
             vec!["foo1", "foo2", "bar", "foos"]
         );
     }
+
+    #[test]
+    fn glob_expansion() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("app_1", MockSourceConfig);
+        builder.add_source("app_2", MockSourceConfig);
+        builder.add_source("app_10", MockSourceConfig);
+        builder.add_source("db", MockSourceConfig);
+        builder.add_transform("apps", &["app_?"], MockTransformConfig);
+        builder.add_sink("out", &["app_[0-9]*", "d?"], MockSinkConfig);
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.transforms["apps"].inputs, vec!["app_1", "app_2"]);
+        assert_eq!(
+            config.sinks["out"].inputs,
+            vec!["app_1", "app_2", "app_10", "db"]
+        );
+    }
+
+    #[test]
+    fn glob_without_matches() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("app_1", MockSourceConfig);
+        builder.add_sink("out", &["app_1", "web_*"], MockSinkConfig);
+
+        let errors = builder.build().expect_err("build should fail");
+
+        assert_eq!(
+            errors,
+            vec![r#"Input pattern "web_*" for sink "out" doesn't match any components."#]
+        );
+    }
+
+    #[test]
+    fn glob_cycle() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        builder.add_transform("app_parse", &["in", "app_*"], MockTransformConfig);
+        builder.add_transform("app_filter", &["app_parse"], MockTransformConfig);
+        builder.add_sink("out", &["app_filter"], MockSinkConfig);
+
+        let errors = builder.build().expect_err("build should fail");
+
+        assert!(errors.contains(
+            &r#"Input pattern "app_*" for transform "app_parse" matches "app_filter", which creates a cycle."#
+                .to_owned()
+        ));
+    }
 }
@@ -1,4 +1,9 @@
-use super::{builder::ConfigBuilder, format, handle_warnings, vars, Config, Format, FormatHint};
+use super::{
+    builder::ConfigBuilder,
+    format, handle_warnings,
+    templates::{self, Templates},
+    vars, Config, Format, FormatHint,
+};
 use glob::glob;
 use lazy_static::lazy_static;
 use std::{
@@ -92,7 +97,11 @@ pub fn load_builder_from_paths(
 
     for (path, format) in config_paths {
         if let Some(file) = open_config(&path) {
-            inputs.push((file, format.or_else(move || Format::from_path(&path).ok())));
+            inputs.push((
+                file,
+                format.or_else(move || Format::from_path(&path).ok()),
+                Some(path.as_path()),
+            ));
         } else {
             errors.push(format!("Config file not found in path: {:?}.", path));
         };
@@ -106,18 +115,51 @@ pub fn load_builder_from_paths(
 }
 
 pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<String>> {
-    load_from_inputs(std::iter::once((input.as_bytes(), format)), false)?.build()
+    load_builder_from_str(input, format)?.build()
 }
 
-fn load_from_inputs(
-    inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint)>,
+pub fn load_builder_from_str(
+    input: &str,
+    format: FormatHint,
+) -> Result<ConfigBuilder, Vec<String>> {
+    load_from_inputs(std::iter::once((input.as_bytes(), format, None)), false)
+}
+
+/// Loads the config from `inputs`, along with the path of the file each one was
+/// read from, if any.
+fn load_from_inputs<'a>(
+    inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint, Option<&'a Path>)>,
     deny_warnings: bool,
 ) -> Result<ConfigBuilder, Vec<String>> {
     let mut config = Config::builder();
     let mut errors = Vec::new();
+    let mut documents = Vec::new();
+    let mut templates = Templates::default();
+
+    // Templates can be used in any of the config files, so collect them from
+    // all the files before loading the components.
+    for (input, format, path) in inputs {
+        match read(input, deny_warnings) {
+            Ok(source) => {
+                let mut document = templates::parse(&source, format);
+                if let Some(document) = document.as_mut() {
+                    if let Err(errs) = templates.extract(document) {
+                        errors.extend(errs);
+                    }
+                }
+                documents.push((source, format, document, path));
+            }
+            Err(errs) => errors.extend(errs),
+        }
+    }
 
-    for (input, format) in inputs {
-        if let Err(errs) = load(input, format, deny_warnings).and_then(|n| config.append(n)) {
+    for (source, format, document, path) in documents {
+        if let Err(errs) = load(&source, format, document, &templates).and_then(|mut n| {
+            if let Some(path) = path {
+                resolve_test_paths(&mut n, path);
+            }
+            config.append(n)
+        }) {
             // TODO: add back paths
             errors.extend(errs.iter().map(|e| e.to_string()));
         }
@@ -130,6 +172,25 @@ fn load_from_inputs(
     }
 }
 
+/// Resolves the relative paths of the unit tests against the directory of the
+/// config file defining them.
+fn resolve_test_paths(config: &mut ConfigBuilder, path: &Path) {
+    let dir = match path.parent() {
+        Some(dir) => dir,
+        None => return,
+    };
+
+    for payload in config
+        .tests
+        .iter_mut()
+        .flat_map(|test| test.payloads.iter_mut())
+    {
+        if payload.golden_file.is_relative() {
+            payload.golden_file = dir.join(&payload.golden_file);
+        }
+    }
+}
+
 fn open_config(path: &Path) -> Option<File> {
     match File::open(path) {
         Ok(f) => Some(f),
@@ -145,11 +206,7 @@ fn open_config(path: &Path) -> Option<File> {
     }
 }
 
-fn load(
-    mut input: impl std::io::Read,
-    format: FormatHint,
-    deny_warnings: bool,
-) -> Result<ConfigBuilder, Vec<String>> {
+fn read(mut input: impl std::io::Read, deny_warnings: bool) -> Result<String, Vec<String>> {
     let mut source_string = String::new();
     input
         .read_to_string(&mut source_string)
@@ -164,5 +221,20 @@ fn load(
     let (with_vars, warnings) = vars::interpolate(&source_string, &vars);
     handle_warnings(warnings, deny_warnings)?;
 
-    format::deserialize(&with_vars, format)
+    Ok(with_vars)
+}
+
+fn load(
+    source: &str,
+    format: FormatHint,
+    document: Option<serde_json::Value>,
+    templates: &Templates,
+) -> Result<ConfigBuilder, Vec<String>> {
+    match document {
+        Some(mut document) => {
+            templates.apply(&mut document)?;
+            serde_json::from_value(document).map_err(|e| vec![e.to_string()])
+        }
+        None => format::deserialize(source, format),
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     buffers::Acker,
     conditions,
-    event::Metric,
+    event::{Event, Metric},
     shutdown::ShutdownSignal,
     sinks::{self, util::UriSerde},
     sources, transforms, Pipeline,
@@ -10,31 +10,39 @@ use async_trait::async_trait;
 use component::ComponentDescription;
 use indexmap::IndexMap; // IndexMap preserves insertion order, allowing us to output errors in the same order they are present in the file
 use serde::{Deserialize, Serialize};
+use shared::datetime::TimeZone;
 use snafu::{ResultExt, Snafu};
 use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
 use std::fs::DirBuilder;
 use std::hash::Hash;
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 pub mod api;
 mod builder;
+mod clock_skew;
 mod compiler;
 pub mod component;
 mod diff;
 pub mod format;
 mod loading;
 mod log_schema;
+mod templates;
 mod unit_test;
 mod validation;
 mod vars;
 pub mod watcher;
 
 pub use builder::ConfigBuilder;
+pub use clock_skew::{ClockSkewAction, ClockSkewOptions};
 pub use diff::ConfigDiff;
 pub use format::{Format, FormatHint};
-pub use loading::{load_from_paths, load_from_str, merge_path_lists, process_paths, CONFIG_PATHS};
+pub use loading::{
+    load_builder_from_paths, load_builder_from_str, load_from_paths, load_from_str,
+    merge_path_lists, process_paths, CONFIG_PATHS,
+};
 pub use log_schema::{log_schema, LogSchema, LOG_SCHEMA};
 pub use unit_test::build_unit_tests_main as build_unit_tests;
 pub use validation::warnings;
@@ -45,14 +53,14 @@ pub struct Config {
     #[cfg(feature = "api")]
     pub api: api::Options,
     pub healthchecks: HealthcheckOptions,
-    pub sources: IndexMap<String, Box<dyn SourceConfig>>,
+    pub sources: IndexMap<String, SourceOuter>,
     pub sinks: IndexMap<String, SinkOuter>,
     pub transforms: IndexMap<String, TransformOuter>,
     tests: Vec<TestDefinition>,
     expansions: IndexMap<String, Vec<String>>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct GlobalOptions {
     #[serde(default = "default_data_dir")]
     pub data_dir: Option<PathBuf>,
@@ -61,6 +69,11 @@ pub struct GlobalOptions {
         default
     )]
     pub log_schema: LogSchema,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    pub timezone: TimeZone,
 }
 
 pub fn default_data_dir() -> Option<PathBuf> {
@@ -210,6 +223,24 @@ pub type SourceDescription = ComponentDescription<Box<dyn SourceConfig>>;
 
 inventory::collect!(SourceDescription);
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SourceOuter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock_skew: Option<ClockSkewOptions>,
+
+    #[serde(flatten)]
+    pub inner: Box<dyn SourceConfig>,
+}
+
+impl SourceOuter {
+    pub fn new(inner: Box<dyn SourceConfig>) -> Self {
+        SourceOuter {
+            clock_skew: None,
+            inner,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter {
     pub inputs: Vec<String>,
@@ -225,6 +256,9 @@ pub struct SinkOuter {
     #[serde(default)]
     pub buffer: crate::buffers::BufferConfig,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<RuntimeOptions>,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -235,6 +269,7 @@ impl SinkOuter {
             buffer: Default::default(),
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
+            runtime: None,
             inner,
             inputs,
         }
@@ -263,33 +298,40 @@ impl SinkOuter {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+/// Healthcheck options of a single sink. Unset options fall back to the
+/// global `healthchecks` section.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 #[serde(default)]
 pub struct SinkHealthcheckOptions {
-    pub enabled: bool,
+    pub enabled: Option<bool>,
+    pub require_healthy: Option<bool>,
     pub uri: Option<UriSerde>,
 }
 
-impl Default for SinkHealthcheckOptions {
-    fn default() -> Self {
-        Self {
-            enabled: true,
-            uri: None,
+impl SinkHealthcheckOptions {
+    /// Resolves the options of the sink against the global defaults.
+    pub fn resolve(&self, defaults: HealthcheckOptions) -> HealthcheckOptions {
+        HealthcheckOptions {
+            enabled: self.enabled.unwrap_or(defaults.enabled),
+            require_healthy: self.require_healthy.unwrap_or(defaults.require_healthy),
         }
     }
 }
 
 impl From<bool> for SinkHealthcheckOptions {
     fn from(enabled: bool) -> Self {
-        Self { enabled, uri: None }
+        Self {
+            enabled: Some(enabled),
+            ..Default::default()
+        }
     }
 }
 
 impl From<UriSerde> for SinkHealthcheckOptions {
     fn from(uri: UriSerde) -> Self {
         Self {
-            enabled: true,
             uri: Some(uri),
+            ..Default::default()
         }
     }
 }
@@ -310,6 +352,18 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
     fn resources(&self) -> Vec<Resource> {
         Vec::new()
     }
+
+    /// Encodes `events` into the bodies of the requests the sink would send
+    /// for them, for unit tests checking them against golden files. Bodies
+    /// are left uncompressed, as compressed output can change across versions
+    /// of the compression libraries. Returns `None` if the sink doesn't
+    /// support it.
+    async fn encode_test_payloads(
+        &self,
+        _events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -319,7 +373,6 @@ pub struct SinkContext {
 }
 
 impl SinkContext {
-    #[cfg(test)]
     pub fn new_test() -> Self {
         Self {
             acker: Acker::Null,
@@ -339,14 +392,41 @@ inventory::collect!(SinkDescription);
 #[derive(Deserialize, Serialize, Debug)]
 pub struct TransformOuter {
     pub inputs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<RuntimeOptions>,
     #[serde(flatten)]
     pub inner: Box<dyn TransformConfig>,
 }
 
+impl TransformOuter {
+    /// The names other components consume the named outputs of the transform
+    /// by, `<transform>.<output>`.
+    pub fn named_outputs(&self, name: &str) -> Vec<String> {
+        self.inner
+            .named_outputs()
+            .into_iter()
+            .map(|(output, _)| format!("{}.{}", name, output))
+            .collect()
+    }
+}
+
+/// Places a transform or sink on a runtime of its own, with dedicated worker
+/// threads, so it can't starve the other components of CPU time.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeOptions {
+    #[serde(default = "default_runtime_threads")]
+    pub threads: NonZeroUsize,
+}
+
+fn default_runtime_threads() -> NonZeroUsize {
+    NonZeroUsize::new(1).unwrap()
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
-    async fn build(&self) -> crate::Result<transforms::Transform>;
+    async fn build(&self, globals: &GlobalOptions) -> crate::Result<transforms::Transform>;
 
     fn input_type(&self) -> DataType;
 
@@ -354,6 +434,20 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
 
     fn transform_type(&self) -> &'static str;
 
+    /// Describes the fields of the events emitted by the transform, and the
+    /// kinds of values they hold, if that can be inferred from the
+    /// configuration. Used by `vector validate --verbose`.
+    fn describe_output(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// The outputs the transform emits events to besides its default one,
+    /// along with the type of their events. The transform emits to them with
+    /// `FunctionTransform::transform_named`.
+    fn named_outputs(&self) -> Vec<(&'static str, DataType)> {
+        Vec::new()
+    }
+
     /// Allows a transform configuration to expand itself into multiple "child"
     /// transformations to replace it. This allows a transform to act as a macro
     /// for various patterns.
@@ -465,6 +559,8 @@ pub struct TestDefinition {
     pub outputs: Vec<TestOutput>,
     #[serde(default)]
     pub no_outputs_from: Vec<String>,
+    #[serde(default)]
+    pub payloads: Vec<TestPayload>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -498,6 +594,15 @@ pub struct TestOutput {
     pub conditions: Option<Vec<conditions::AnyCondition>>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TestPayload {
+    pub extract_from: String,
+    pub golden_file: PathBuf,
+    #[serde(default)]
+    pub redact: Vec<String>,
+}
+
 impl Config {
     pub fn builder() -> builder::ConfigBuilder {
         Default::default()
@@ -560,6 +665,37 @@ mod test {
         )
     }
 
+    #[test]
+    fn source_clock_skew() {
+        let config = load_from_str(
+            r#"
+            [sources.in]
+            type = "file"
+            include = ["/var/log/messages"]
+            clock_skew.tolerance_secs = 60
+            clock_skew.action = "flag"
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+            "#,
+            Some(Format::TOML),
+        )
+        .unwrap();
+
+        let source = &config.sources["in"];
+        assert_eq!(source.inner.source_type(), "file");
+        assert_eq!(
+            source.clock_skew,
+            Some(super::ClockSkewOptions {
+                tolerance_secs: 60,
+                action: super::ClockSkewAction::Flag,
+                flag_key: "clock_skewed".into(),
+            })
+        );
+    }
+
     #[test]
     fn default_schema() {
         let config = load_from_str(
@@ -615,6 +751,80 @@ mod test {
         assert_eq!("then", config.global.log_schema.timestamp_key().to_string());
     }
 
+    #[test]
+    fn sink_healthcheck_overrides_global() {
+        let config = load_from_str(
+            r#"
+            [healthchecks]
+            enabled = false
+            require_healthy = true
+
+            [sources.in]
+            type = "file"
+            include = ["/var/log/messages"]
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+
+            [sinks.checked]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+            healthcheck.enabled = true
+            healthcheck.require_healthy = false
+            "#,
+            Some(Format::TOML),
+        )
+        .unwrap();
+
+        let out = config.sinks["out"]
+            .healthcheck()
+            .resolve(config.healthchecks);
+        assert!(!out.enabled);
+        assert!(out.require_healthy);
+
+        let checked = config.sinks["checked"]
+            .healthcheck()
+            .resolve(config.healthchecks);
+        assert!(checked.enabled);
+        assert!(!checked.require_healthy);
+    }
+
+    #[test]
+    #[cfg(all(feature = "sources-internal_metrics", feature = "sinks-prometheus"))]
+    fn expose_internal_metrics() {
+        let mut config: ConfigBuilder = format::deserialize(
+            r#"
+            [sources.in]
+            type = "file"
+            include = ["/var/log/messages"]
+
+            [sinks.out]
+            type = "console"
+            inputs = ["in"]
+            encoding = "json"
+            "#,
+            Some(Format::TOML),
+        )
+        .unwrap();
+
+        config.expose_internal_metrics(9598).unwrap();
+        assert!(config.expose_internal_metrics(9599).is_err());
+
+        let config = config.build().unwrap();
+        assert_eq!(
+            config.sources["vector_internal_metrics"]
+                .inner
+                .source_type(),
+            "internal_metrics"
+        );
+        let sink = &config.sinks["vector_prometheus_exporter"];
+        assert_eq!(sink.inner.sink_type(), "prometheus_exporter");
+        assert_eq!(sink.inputs, vec!["vector_internal_metrics".to_owned()]);
+    }
+
     #[test]
     fn config_append() {
         let mut config: ConfigBuilder = format::deserialize(
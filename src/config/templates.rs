@@ -0,0 +1,333 @@
+//! Reusable blocks of component options.
+//!
+//! A template is a table in the top-level `templates` section. Components
+//! reference templates by name with the `use` option, and inherit their
+//! options:
+//!
+//! ```toml
+//! [templates.standard_batch.batch]
+//!   max_bytes = 1049000
+//!   timeout_secs = 1
+//!
+//! [sinks.out]
+//!   use = "standard_batch"
+//!   type = "console"
+//!   inputs = ["in"]
+//!   batch.timeout_secs = 5
+//! ```
+//!
+//! Templates are applied in the order they are listed, and the options of the
+//! component itself are applied last. Tables are merged recursively, so a
+//! component can override a single option of a shared table. Templates can
+//! `use` other templates.
+//!
+//! Templates are applied to the raw config documents, before the components
+//! are deserialized, so they work the same for any component and format.
+
+use super::{format, FormatHint};
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+const TEMPLATES_KEY: &str = "templates";
+const USE_KEY: &str = "use";
+const COMPONENT_KINDS: &[&str] = &["sources", "transforms", "sinks"];
+
+#[derive(Debug, Default)]
+pub struct Templates {
+    templates: IndexMap<String, Map<String, Value>>,
+}
+
+/// Parse the config source into a generic document, if it defines or uses
+/// templates.
+///
+/// Other documents are best deserialized from the source directly, which
+/// gives better error messages.
+pub fn parse(source: &str, format: FormatHint) -> Option<Value> {
+    format::deserialize::<Value>(source, format)
+        .ok()
+        .filter(is_used)
+}
+
+/// Returns `true` if the config document defines or uses templates.
+fn is_used(document: &Value) -> bool {
+    let document = match document.as_object() {
+        Some(document) => document,
+        None => return false,
+    };
+
+    document.contains_key(TEMPLATES_KEY)
+        || COMPONENT_KINDS.iter().any(|kind| {
+            document
+                .get(*kind)
+                .and_then(Value::as_object)
+                .map_or(false, |components| {
+                    components
+                        .values()
+                        .any(|component| component.get(USE_KEY).is_some())
+                })
+        })
+}
+
+impl Templates {
+    /// Move the templates defined in the config document into this set.
+    pub fn extract(&mut self, document: &mut Value) -> Result<(), Vec<String>> {
+        let templates = match document
+            .as_object_mut()
+            .and_then(|document| document.remove(TEMPLATES_KEY))
+        {
+            Some(Value::Object(templates)) => templates,
+            Some(_) => return Err(vec!["The `templates` section must be a table.".to_owned()]),
+            None => return Ok(()),
+        };
+
+        let mut errors = Vec::new();
+
+        for (name, template) in templates {
+            match template {
+                Value::Object(template) => {
+                    if self.templates.insert(name.clone(), template).is_some() {
+                        errors.push(format!("More than one template with name {:?}.", name));
+                    }
+                }
+                _ => errors.push(format!("Template {:?} must be a table.", name)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Apply the templates used by the components of the config document.
+    pub fn apply(&self, document: &mut Value) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        for kind in COMPONENT_KINDS {
+            let components = match document.get_mut(*kind).and_then(Value::as_object_mut) {
+                Some(components) => components,
+                None => continue,
+            };
+
+            for (name, component) in components.iter_mut() {
+                if let Value::Object(component) = component {
+                    if let Err(error) = self.resolve(component, &mut Vec::new()) {
+                        errors.push(format!("Component {:?}: {}", name, error));
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Replace the `use` option of the table with the options of the
+    /// templates it names. `chain` holds the templates being resolved, to
+    /// detect cycles.
+    fn resolve(
+        &self,
+        table: &mut Map<String, Value>,
+        chain: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let names = match table.remove(USE_KEY) {
+            None => return Ok(()),
+            Some(Value::String(name)) => vec![name],
+            Some(Value::Array(names)) => names
+                .into_iter()
+                .map(|name| match name {
+                    Value::String(name) => Ok(name),
+                    _ => Err(invalid_use()),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => return Err(invalid_use()),
+        };
+
+        let mut options = Map::new();
+
+        for name in names {
+            if chain.contains(&name) {
+                return Err(format!(
+                    "Cyclic template chain [ {} -> {} ].",
+                    chain.join(" -> "),
+                    name
+                ));
+            }
+
+            let mut template = self
+                .templates
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| format!("Unknown template {:?}.", name))?;
+
+            chain.push(name);
+            self.resolve(&mut template, chain)?;
+            chain.pop();
+
+            merge(&mut options, template);
+        }
+
+        let own = std::mem::replace(table, options);
+        merge(table, own);
+
+        Ok(())
+    }
+}
+
+fn invalid_use() -> String {
+    "The `use` option must be a template name or a list of template names.".to_owned()
+}
+
+/// Merge `overrides` into `base`. Tables are merged key by key, any other
+/// value replaces the one in `base`.
+fn merge(base: &mut Map<String, Value>, overrides: Map<String, Value>) {
+    for (key, value) in overrides {
+        match value {
+            Value::Object(value) => match base.get_mut(&key) {
+                Some(Value::Object(base)) => merge(base, value),
+                _ => {
+                    base.insert(key, Value::Object(value));
+                }
+            },
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn apply(mut document: Value) -> Result<Value, Vec<String>> {
+        let mut templates = Templates::default();
+        templates.extract(&mut document)?;
+        templates.apply(&mut document)?;
+        Ok(document)
+    }
+
+    #[test]
+    fn merges_templates_in_order() {
+        let document = json!({
+            "templates": {
+                "batch": { "batch": { "max_bytes": 100, "timeout_secs": 1 } },
+                "tls": { "tls": { "verify_certificate": true } },
+                "fast": { "batch": { "timeout_secs": 2 } },
+            },
+            "sinks": {
+                "out": {
+                    "use": ["batch", "tls", "fast"],
+                    "type": "console",
+                    "batch": { "max_bytes": 200 },
+                },
+            },
+        });
+
+        assert_eq!(
+            apply(document),
+            Ok(json!({
+                "sinks": {
+                    "out": {
+                        "type": "console",
+                        "batch": { "max_bytes": 200, "timeout_secs": 2 },
+                        "tls": { "verify_certificate": true },
+                    },
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn chained_templates() {
+        let document = json!({
+            "templates": {
+                "base": { "encoding": "json", "healthcheck": false },
+                "standard": { "use": "base", "healthcheck": true },
+            },
+            "sinks": { "out": { "use": "standard", "type": "console" } },
+        });
+
+        assert_eq!(
+            apply(document),
+            Ok(json!({
+                "sinks": {
+                    "out": { "type": "console", "encoding": "json", "healthcheck": true },
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn arrays_are_replaced() {
+        let document = json!({
+            "templates": { "inputs": { "inputs": ["a", "b"] } },
+            "transforms": { "t": { "use": "inputs", "inputs": ["c"] } },
+        });
+
+        assert_eq!(
+            apply(document),
+            Ok(json!({ "transforms": { "t": { "inputs": ["c"] } } }))
+        );
+    }
+
+    #[test]
+    fn unknown_template() {
+        let document = json!({
+            "sources": { "in": { "use": "nope", "type": "stdin" } },
+        });
+
+        assert_eq!(
+            apply(document),
+            Err(vec![
+                r#"Component "in": Unknown template "nope"."#.to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn cyclic_templates() {
+        let document = json!({
+            "templates": {
+                "a": { "use": "b" },
+                "b": { "use": "a" },
+            },
+            "sinks": { "out": { "use": "a" } },
+        });
+
+        assert_eq!(
+            apply(document),
+            Err(vec![
+                r#"Component "out": Cyclic template chain [ a -> b -> a ]."#.to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn use_not_a_name() {
+        let document = json!({
+            "sinks": { "out": { "use": 1 } },
+        });
+
+        assert_eq!(
+            apply(document),
+            Err(vec![
+                r#"Component "out": The `use` option must be a template name or a list of template names."#.to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn detects_use() {
+        assert!(!is_used(
+            &json!({ "sinks": { "out": { "type": "console" } } })
+        ));
+        assert!(is_used(&json!({ "templates": {} })));
+        assert!(is_used(&json!({ "sources": { "in": { "use": "a" } } })));
+    }
+}
@@ -1,12 +1,18 @@
 use super::{Config, ConfigBuilder, TestDefinition, TestInput, TestInputValue};
-use crate::config::{self, TransformConfig};
+use crate::config::{self, DataType, GlobalOptions, SinkConfig, TransformConfig};
 use crate::{
     conditions::Condition,
     event::{Event, Value},
-    transforms::Transform,
+    transforms::{FunctionTransform, Transform},
 };
 use indexmap::IndexMap;
-use std::{collections::HashMap, path::PathBuf};
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 pub async fn build_unit_tests_main(
     paths: &[(PathBuf, config::FormatHint)],
@@ -27,6 +33,14 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
 
     let expansions = super::compiler::expand_macros(&mut builder)?;
 
+    // Sinks are only ever used to encode payloads, and shared between the tests
+    // doing so.
+    let sinks = builder
+        .sinks
+        .into_iter()
+        .map(|(name, sink)| (name, (sink.inputs, Arc::from(sink.inner))))
+        .collect::<IndexMap<_, _>>();
+
     // Don't let this escape since it's not validated
     let config = Config {
         global: builder.global,
@@ -34,14 +48,14 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
         api: builder.api,
         healthchecks: builder.healthchecks,
         sources: builder.sources,
-        sinks: builder.sinks,
+        sinks: IndexMap::new(),
         transforms: builder.transforms,
         tests: builder.tests,
         expansions,
     };
 
     for test in &config.tests {
-        match build_unit_test(test, &config).await {
+        match build_unit_test(test, &config, &sinks).await {
             Ok(t) => tests.push(t),
             Err(errs) => {
                 let mut test_err = errs.join("\n");
@@ -62,16 +76,33 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
 
 pub struct UnitTest {
     pub name: String,
+    globals: GlobalOptions,
     inputs: Vec<(Vec<String>, Event)>,
     transforms: IndexMap<String, UnitTestTransform>,
     checks: Vec<UnitTestCheck>,
     no_outputs_from: Vec<String>,
+    payloads: Vec<UnitTestPayload>,
+    update_golden_files: bool,
 }
 
 struct UnitTestTransform {
     transform: Transform,
     config: Box<dyn TransformConfig>,
     next: Vec<String>,
+    /// The names of the named outputs of the transform among `next`, along
+    /// with the output they are.
+    named_outputs: Vec<(String, &'static str)>,
+}
+
+/// Stands in for a named output of a transform, passing on the events the
+/// transform emits to it.
+#[derive(Clone)]
+struct NamedOutput;
+
+impl FunctionTransform for NamedOutput {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        output.push(event)
+    }
 }
 
 struct UnitTestCheck {
@@ -79,6 +110,38 @@ struct UnitTestCheck {
     conditions: Vec<Box<dyn Condition>>,
 }
 
+struct UnitTestPayload {
+    extract_from: String,
+    sink: Arc<dyn SinkConfig>,
+    // The transforms feeding the sink.
+    inputs: Vec<String>,
+    golden_file: PathBuf,
+    redact: Vec<Regex>,
+}
+
+fn render_payloads(bodies: &[Vec<u8>], redact: &[Regex]) -> String {
+    bodies
+        .iter()
+        .enumerate()
+        .map(|(i, body)| {
+            let body = String::from_utf8_lossy(body);
+            let body = redact.iter().fold(body.into_owned(), |body, re| {
+                re.replace_all(&body, "<redacted>").into_owned()
+            });
+            format!("--- request {} ---\n{}\n", i, body)
+        })
+        .collect()
+}
+
+fn accepts(input_type: DataType, event: &Event) -> bool {
+    match (input_type, event) {
+        (DataType::Any, _)
+        | (DataType::Log, Event::Log(_))
+        | (DataType::Metric, Event::Metric(_)) => true,
+        _ => false,
+    }
+}
+
 fn event_to_string(event: &Event) -> String {
     match event {
         Event::Log(log) => serde_json::to_string(&log).unwrap_or_else(|_| "{}".into()),
@@ -108,20 +171,24 @@ fn events_to_string(name: &str, events: &[Event]) -> String {
 fn walk(
     node: &str,
     mut inputs: Vec<Event>,
+    globals: &GlobalOptions,
     transforms: &mut IndexMap<String, UnitTestTransform>,
     aggregated_results: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
 ) {
     let mut results = Vec::new();
+    let mut named = Vec::new();
     let mut targets = Vec::new();
+    let mut named_outputs = Vec::new();
 
     // Use `remove` to take ownership.
     if let Some((key, mut target)) = transforms.remove_entry(node) {
         match target.transform {
             Transform::Function(ref mut t) => {
                 for input in inputs.clone() {
-                    t.transform(&mut results, input)
+                    t.transform_named(&mut results, &mut named, input)
                 }
                 targets = target.next.clone();
+                named_outputs = target.named_outputs.clone();
                 transforms.insert(key, target);
             }
             Transform::Task(t) => {
@@ -135,17 +202,33 @@ fn walk(
                 // TODO: This is a hack.
                 // Our tasktransforms must consume the transform to attach it to an input stream, so we rebuild it between input streams.
                 transforms.insert(key, UnitTestTransform {
-                    transform:  futures::executor::block_on(target.config.clone().build())
+                    transform:  futures::executor::block_on(target.config.clone().build(globals))
                         .expect("Failed to build a known valid transform config. Things may have changed during runtime."),
                     config: target.config,
-                    next: target.next
+                    next: target.next,
+                    named_outputs: target.named_outputs,
                 });
             }
         }
     }
 
     for child in targets {
-        walk(&child, results.clone(), transforms, aggregated_results);
+        // Named outputs only receive the events emitted to them.
+        let child_inputs = match named_outputs.iter().find(|(name, _)| *name == child) {
+            Some((_, output)) => named
+                .iter()
+                .filter(|(name, _)| name == output)
+                .map(|(_, event)| event.clone())
+                .collect(),
+            None => results.clone(),
+        };
+        walk(
+            &child,
+            child_inputs,
+            globals,
+            transforms,
+            aggregated_results,
+        );
     }
 
     if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(node) {
@@ -156,6 +239,12 @@ fn walk(
 }
 
 impl UnitTest {
+    /// Makes the test write the payloads of sinks to their golden files,
+    /// instead of checking them against the files.
+    pub fn update_golden_files(&mut self, update: bool) {
+        self.update_golden_files = update;
+    }
+
     // Executes each test and provides a tuple of inspections and error lists.
     pub fn run(&mut self) -> (Vec<String>, Vec<String>) {
         let mut errors = Vec::new();
@@ -173,7 +262,13 @@ impl UnitTest {
         }
 
         for (target, inputs) in inputs_by_target {
-            walk(&target, inputs, &mut self.transforms, &mut results);
+            walk(
+                &target,
+                inputs,
+                &self.globals,
+                &mut self.transforms,
+                &mut results,
+            );
         }
 
         for check in &self.checks {
@@ -249,10 +344,85 @@ impl UnitTest {
             }
         }
 
+        for payload in &self.payloads {
+            let input_type = payload.sink.input_type();
+            let events = payload
+                .inputs
+                .iter()
+                .filter_map(|input| results.get(input))
+                .flat_map(|(_, outputs)| outputs.iter())
+                .filter(|event| accepts(input_type, event))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if events.is_empty() {
+                errors.push(format!(
+                    "check sink '{}' payloads failed: received zero events.",
+                    payload.extract_from,
+                ));
+                continue;
+            }
+
+            let bodies = match futures::executor::block_on(
+                payload.sink.encode_test_payloads(events),
+            ) {
+                Some(Ok(bodies)) => bodies,
+                Some(Err(error)) => {
+                    errors.push(format!(
+                        "check sink '{}' payloads failed: unable to encode events: {}",
+                        payload.extract_from, error,
+                    ));
+                    continue;
+                }
+                None => {
+                    errors.push(format!(
+                        "check sink '{}' payloads failed: sinks of type '{}' don't support payload checks.",
+                        payload.extract_from,
+                        payload.sink.sink_type(),
+                    ));
+                    continue;
+                }
+            };
+            let actual = render_payloads(&bodies, &payload.redact);
+
+            if self.update_golden_files {
+                match write_golden_file(&payload.golden_file, &actual) {
+                    Ok(()) => inspections.push(format!(
+                        "updated golden file {:?} with sink '{}' payloads.",
+                        payload.golden_file, payload.extract_from,
+                    )),
+                    Err(error) => errors.push(format!(
+                        "check sink '{}' payloads failed: unable to write golden file {:?}: {}",
+                        payload.extract_from, payload.golden_file, error,
+                    )),
+                }
+                continue;
+            }
+
+            match fs::read_to_string(&payload.golden_file) {
+                Ok(expected) if expected == actual => (),
+                Ok(expected) => errors.push(format!(
+                    "check sink '{}' payloads failed: payloads don't match golden file {:?}.\nexpected:\n{}\nactual:\n{}\nrun `vector test --update-golden-files` to accept the new payloads.",
+                    payload.extract_from, payload.golden_file, expected, actual,
+                )),
+                Err(error) => errors.push(format!(
+                    "check sink '{}' payloads failed: unable to read golden file {:?}: {}\nactual:\n{}\nrun `vector test --update-golden-files` to create it.",
+                    payload.extract_from, payload.golden_file, error, actual,
+                )),
+            }
+        }
+
         (inspections, errors)
     }
 }
 
+fn write_golden_file(path: &Path, payloads: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, payloads)
+}
+
 //------------------------------------------------------------------------------
 
 fn links_to_a_leaf(
@@ -380,6 +550,7 @@ fn build_inputs(
 async fn build_unit_test(
     definition: &TestDefinition,
     config: &Config,
+    sinks: &IndexMap<String, (Vec<String>, Arc<dyn SinkConfig>)>,
 ) -> Result<UnitTest, Vec<String>> {
     let mut errors = vec![];
 
@@ -391,12 +562,13 @@ async fn build_unit_test(
         }
     };
 
-    // Maps transform names with their output targets (transforms that use it as
-    // an input).
+    // Maps transform names, and the names of their named outputs, with their
+    // output targets (transforms that use it as an input).
     let mut transform_outputs: IndexMap<String, IndexMap<String, ()>> = config
         .transforms
         .iter()
-        .map(|(k, _)| (k.clone(), IndexMap::new()))
+        .flat_map(|(k, t)| std::iter::once(k.clone()).chain(t.named_outputs(k)))
+        .map(|k| (k, IndexMap::new()))
         .collect();
 
     config.transforms.iter().for_each(|(k, t)| {
@@ -404,7 +576,11 @@ async fn build_unit_test(
             if let Some(outputs) = transform_outputs.get_mut(i) {
                 outputs.insert(k.to_string(), ());
             }
-        })
+        });
+        // The named outputs of a transform are fed by the transform.
+        for output in t.named_outputs(k) {
+            transform_outputs[k].insert(output, ());
+        }
     });
 
     for (i, (input_target, _)) in inputs.iter().enumerate() {
@@ -429,6 +605,53 @@ async fn build_unit_test(
         leaves.insert(o.clone(), ());
     });
 
+    // Sinks aren't part of the walked topology, so the transforms feeding them
+    // are the leaves instead.
+    let mut payloads = Vec::new();
+    for (i, p) in definition.payloads.iter().enumerate() {
+        let (sink_inputs, sink) = match sinks.get(&p.extract_from) {
+            Some(sink) => sink,
+            None => {
+                errors.push(format!(
+                    "payloads[{}]: unable to locate sink '{}'",
+                    i, p.extract_from
+                ));
+                continue;
+            }
+        };
+
+        let inputs = sink_inputs
+            .iter()
+            .flat_map(|input| config.get_inputs(input))
+            .filter(|input| transform_outputs.contains_key(input))
+            .collect::<Vec<_>>();
+        inputs.iter().for_each(|input| {
+            leaves.insert(input.clone(), ());
+        });
+
+        let mut redact = Vec::new();
+        for pattern in &p.redact {
+            match Regex::new(pattern) {
+                Ok(re) => redact.push(re),
+                Err(error) => errors.push(format!(
+                    "payloads[{}]: invalid redact pattern '{}': {}",
+                    i, pattern, error
+                )),
+            }
+        }
+
+        payloads.push(UnitTestPayload {
+            extract_from: p.extract_from.clone(),
+            sink: Arc::clone(sink),
+            inputs,
+            golden_file: p.golden_file.clone(),
+            redact,
+        });
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
     // Reduce the configured transforms into just the ones connecting our test
     // target with output targets.
     reduce_transforms(
@@ -445,8 +668,28 @@ async fn build_unit_test(
     // Build reduced transforms.
     let mut transforms: IndexMap<String, UnitTestTransform> = IndexMap::new();
     for (name, transform_config) in &config.transforms {
+        let named_outputs = transform_config
+            .named_outputs(name)
+            .into_iter()
+            .zip(transform_config.inner.named_outputs())
+            .map(|(full_name, (output, _))| (full_name, output))
+            .collect::<Vec<_>>();
+        for (full_name, _) in &named_outputs {
+            if let Some(outputs) = transform_outputs.remove(full_name) {
+                transforms.insert(
+                    full_name.clone(),
+                    UnitTestTransform {
+                        transform: Transform::function(NamedOutput),
+                        config: transform_config.inner.clone(),
+                        next: outputs.into_iter().map(|(k, _)| k).collect(),
+                        named_outputs: Vec::new(),
+                    },
+                );
+            }
+        }
+
         if let Some(outputs) = transform_outputs.remove(name) {
-            match transform_config.inner.build().await {
+            match transform_config.inner.build(&config.global).await {
                 Ok(transform) => {
                     transforms.insert(
                         name.clone(),
@@ -454,6 +697,7 @@ async fn build_unit_test(
                             transform,
                             config: transform_config.inner.clone(),
                             next: outputs.into_iter().map(|(k, _)| k).collect(),
+                            named_outputs,
                         },
                     );
                 }
@@ -518,9 +762,13 @@ async fn build_unit_test(
         })
         .collect();
 
-    if definition.outputs.is_empty() && definition.no_outputs_from.is_empty() {
+    if definition.outputs.is_empty()
+        && definition.no_outputs_from.is_empty()
+        && definition.payloads.is_empty()
+    {
         errors.push(
-            "unit test must contain at least one of `outputs` or `no_outputs_from`.".to_owned(),
+            "unit test must contain at least one of `outputs`, `no_outputs_from` or `payloads`."
+                .to_owned(),
         );
     }
 
@@ -529,10 +777,13 @@ async fn build_unit_test(
     } else {
         Ok(UnitTest {
             name: definition.name.clone(),
+            globals: config.global.clone(),
             inputs,
             transforms,
             checks,
             no_outputs_from: definition.no_outputs_from.clone(),
+            payloads,
+            update_golden_files: false,
         })
     }
 }
@@ -665,7 +916,7 @@ mod tests {
         assert_eq!(
             errs,
             vec![r#"Failed to build test 'broken test':
-  unit test must contain at least one of `outputs` or `no_outputs_from`."#
+  unit test must contain at least one of `outputs`, `no_outputs_from` or `payloads`."#
                 .to_owned(),]
         );
     }
@@ -1018,6 +1269,45 @@ mod tests {
         assert_eq!(tests[0].run().1, Vec::<String>::new());
     }
 
+    #[cfg(feature = "transforms-remap")]
+    #[tokio::test]
+    async fn test_named_output() {
+        let config: ConfigBuilder = toml::from_str(
+            r#"
+[transforms.foo]
+  inputs = ["ignored"]
+  type = "remap"
+  source = "abort"
+  reroute_dropped = true
+
+[transforms.bar]
+  inputs = ["foo.dropped"]
+  type = "add_fields"
+  [transforms.bar.fields]
+    new_field = "new field added"
+
+[[tests]]
+  name = "dropped events test"
+  no_outputs_from = ["foo"]
+
+  [tests.input]
+    insert_at = "foo"
+    value = "dropped"
+
+  [[tests.outputs]]
+    extract_from = "bar"
+    [[tests.outputs.conditions]]
+      type = "check_fields"
+      "message.equals" = "dropped"
+      "new_field.equals" = "new field added"
+      "#,
+        )
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+    }
+
     #[tokio::test]
     async fn test_fail_no_outputs() {
         let config: ConfigBuilder = toml::from_str(
@@ -1467,4 +1757,141 @@ mod tests {
                     ]);
                 */
     }
+
+    #[cfg(feature = "sinks-http")]
+    #[tokio::test]
+    async fn check_sink_payloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let golden_file = dir.path().join("golden/http.txt");
+
+        let config: ConfigBuilder = toml::from_str(&format!(
+            r#"
+[transforms.foo]
+  inputs = ["ignored"]
+  type = "add_fields"
+  [transforms.foo.fields]
+    new_field = "string value"
+
+[sinks.out]
+  inputs = ["foo"]
+  type = "http"
+  uri = "http://localhost:9000"
+  encoding.codec = "ndjson"
+
+[[tests]]
+  name = "sink payloads"
+
+  [[tests.inputs]]
+    insert_at = "foo"
+    value = "first"
+
+  [[tests.inputs]]
+    insert_at = "foo"
+    value = "second"
+
+  [[tests.payloads]]
+    extract_from = "out"
+    golden_file = {:?}
+    redact = ['"timestamp":"[^"]*"']
+      "#,
+            golden_file
+        ))
+        .unwrap();
+
+        let mut tests = build_unit_tests(config).await.unwrap();
+        assert_ne!(tests[0].run().1, Vec::<String>::new());
+
+        tests[0].update_golden_files(true);
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+        assert_eq!(
+            fs::read_to_string(&golden_file).unwrap(),
+            r#"--- request 0 ---
+{"message":"first","new_field":"string value",<redacted>}
+{"message":"second","new_field":"string value",<redacted>}
+
+"#
+        );
+
+        tests[0].update_golden_files(false);
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+
+        fs::write(&golden_file, "something else").unwrap();
+        assert_ne!(tests[0].run().1, Vec::<String>::new());
+    }
+
+    #[cfg(feature = "sinks-http")]
+    #[tokio::test]
+    async fn golden_files_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vector.toml");
+        fs::write(
+            &path,
+            r#"
+[transforms.foo]
+  inputs = ["ignored"]
+  type = "add_fields"
+  [transforms.foo.fields]
+    new_field = "string value"
+
+[sinks.out]
+  inputs = ["foo"]
+  type = "http"
+  uri = "http://localhost:9000"
+  encoding.codec = "ndjson"
+
+[[tests]]
+  name = "sink payloads"
+
+  [[tests.inputs]]
+    insert_at = "foo"
+    value = "first"
+
+  [[tests.payloads]]
+    extract_from = "out"
+    golden_file = "golden/http.txt"
+    redact = ['"timestamp":"[^"]*"']
+      "#,
+        )
+        .unwrap();
+
+        let mut tests = build_unit_tests_main(&[(path, Some(config::Format::TOML))])
+            .await
+            .unwrap();
+        tests[0].update_golden_files(true);
+        assert_eq!(tests[0].run().1, Vec::<String>::new());
+        assert!(dir.path().join("golden/http.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn payloads_from_unknown_sink() {
+        let config: ConfigBuilder = toml::from_str(
+            r#"
+[transforms.foo]
+  inputs = ["ignored"]
+  type = "add_fields"
+  [transforms.foo.fields]
+    new_field = "string value"
+
+[[tests]]
+  name = "broken test"
+
+  [tests.input]
+    insert_at = "foo"
+    value = "nah this doesnt matter"
+
+  [[tests.payloads]]
+    extract_from = "out"
+    golden_file = "out.txt"
+      "#,
+        )
+        .unwrap();
+
+        let errs = build_unit_tests(config).await.err().unwrap();
+        assert_eq!(
+            errs,
+            vec![r#"Failed to build test 'broken test':
+  payloads[0]: unable to locate sink 'out'"#
+                .to_owned(),]
+        );
+    }
 }
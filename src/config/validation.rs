@@ -20,10 +20,18 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         iter.map(move |x| (tag, x))
     }
 
-    // Check for non-unique names across sources, sinks, and transforms
+    let named_outputs = config
+        .transforms
+        .iter()
+        .flat_map(|(name, transform)| transform.named_outputs(name))
+        .collect::<Vec<_>>();
+
+    // Check for non-unique names across sources, sinks, transforms, and the
+    // named outputs of transforms
     let mut name_uses = HashMap::<&str, Vec<&'static str>>::new();
     for (ctype, name) in tagged("source", config.sources.keys())
         .chain(tagged("transform", config.transforms.keys()))
+        .chain(tagged("transform output", named_outputs.iter()))
         .chain(tagged("sink", config.sinks.keys()))
     {
         let uses = name_uses.entry(name).or_default();
@@ -57,7 +65,10 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
 
         for input in inputs {
-            if !config.sources.contains_key(&input) && !config.transforms.contains_key(&input) {
+            if !config.sources.contains_key(&input)
+                && !config.transforms.contains_key(&input)
+                && !named_outputs.contains(&input)
+            {
                 errors.push(format!(
                     "Input {:?} for {} {:?} doesn't exist.",
                     input, output_type, name
@@ -77,7 +88,7 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let source_resources = config
         .sources
         .iter()
-        .map(|(name, config)| (name, config.resources()));
+        .map(|(name, config)| (name, config.inner.resources()));
     let sink_resources = config
         .sinks
         .iter()
@@ -256,7 +267,7 @@ impl From<&ConfigBuilder> for Graph {
 
         // TODO: validate that node names are unique across sources/transforms/sinks?
         for (name, config) in config.sources.iter() {
-            graph.add_source(name, config.output_type());
+            graph.add_source(name, config.inner.output_type());
         }
 
         for (name, config) in config.transforms.iter() {
@@ -266,6 +277,17 @@ impl From<&ConfigBuilder> for Graph {
                 config.inner.output_type(),
                 config.inputs.clone(),
             );
+
+            // Named outputs are typed by the events they emit, regardless of
+            // the output type of the transform.
+            for (output, ty) in config.inner.named_outputs() {
+                graph.add_transform(
+                    &format!("{}.{}", name, output),
+                    DataType::Any,
+                    ty,
+                    vec![name.clone()],
+                );
+            }
         }
 
         for (name, config) in config.sinks.iter() {
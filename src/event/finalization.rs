@@ -0,0 +1,224 @@
+//! Tracking of the delivery of events, so sources can acknowledge the data
+//! they read once it's delivered.
+//!
+//! A source wanting to know when events are delivered attaches them to a
+//! `BatchNotifier`, which adds an `EventFinalizer` to each of them. Clones of
+//! an event share its finalizers, so the finalizer of an event fanned out to
+//! several sinks is only done when all of the sinks are. Sinks update the
+//! status of the events they deliver, or fail to. Once all the finalizers of a
+//! batch are dropped, the source receives the status of the whole batch.
+
+use std::{
+    fmt, mem,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::oneshot;
+
+/// The delivery status of an event.
+///
+/// When an event has several statuses, as when one of its clones is delivered
+/// and another one fails, the one listed last wins.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum EventStatus {
+    /// The event was dropped without being delivered, as transforms and
+    /// sinks do with events they intentionally discard. Also the status of
+    /// events that no component reported a status for.
+    Dropped,
+    /// The event was delivered.
+    Delivered,
+    /// The event couldn't be delivered, but may be if sent again.
+    Errored,
+    /// The event was rejected, and won't be delivered if sent again.
+    Failed,
+}
+
+impl EventStatus {
+    fn from_u8(status: u8) -> Self {
+        match status {
+            0 => EventStatus::Dropped,
+            1 => EventStatus::Delivered,
+            2 => EventStatus::Errored,
+            _ => EventStatus::Failed,
+        }
+    }
+}
+
+/// The delivery status of a batch of events, as received by their source.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchStatus {
+    /// All of the events were delivered, or intentionally dropped.
+    Delivered,
+    /// Some events couldn't be delivered, but may be if sent again.
+    Errored,
+    /// Some events were rejected.
+    Failed,
+}
+
+impl From<EventStatus> for BatchStatus {
+    fn from(status: EventStatus) -> Self {
+        match status {
+            EventStatus::Dropped | EventStatus::Delivered => BatchStatus::Delivered,
+            EventStatus::Errored => BatchStatus::Errored,
+            EventStatus::Failed => BatchStatus::Failed,
+        }
+    }
+}
+
+/// The receiving end of the status of a batch.
+pub type BatchStatusReceiver = oneshot::Receiver<BatchStatus>;
+
+/// Notifies a source of the status of a batch of events, once all of the
+/// events of the batch are finalized.
+pub struct BatchNotifier {
+    status: AtomicU8,
+    notifier: Option<oneshot::Sender<BatchStatus>>,
+}
+
+impl BatchNotifier {
+    pub fn new_with_receiver() -> (Arc<Self>, BatchStatusReceiver) {
+        let (tx, rx) = oneshot::channel();
+        let notifier = Self {
+            status: AtomicU8::new(EventStatus::Dropped as u8),
+            notifier: Some(tx),
+        };
+
+        (Arc::new(notifier), rx)
+    }
+
+    fn update_status(&self, status: EventStatus) {
+        self.status.fetch_max(status as u8, Ordering::AcqRel);
+    }
+}
+
+impl Drop for BatchNotifier {
+    fn drop(&mut self) {
+        let status = EventStatus::from_u8(*self.status.get_mut());
+        if let Some(notifier) = self.notifier.take() {
+            // The source may not be waiting for the status anymore, as when
+            // it's shutting down.
+            let _ = notifier.send(status.into());
+        }
+    }
+}
+
+impl fmt::Debug for BatchNotifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchNotifier")
+            .field(
+                "status",
+                &EventStatus::from_u8(self.status.load(Ordering::Acquire)),
+            )
+            .finish()
+    }
+}
+
+/// Tracks the status of an event and all of its clones, and reports it to the
+/// batch of the event once they're all dropped.
+#[derive(Debug)]
+pub struct EventFinalizer {
+    status: AtomicU8,
+    batch: Arc<BatchNotifier>,
+}
+
+impl EventFinalizer {
+    pub fn new(batch: Arc<BatchNotifier>) -> Self {
+        Self {
+            status: AtomicU8::new(EventStatus::Dropped as u8),
+            batch,
+        }
+    }
+
+    pub fn update_status(&self, status: EventStatus) {
+        self.status.fetch_max(status as u8, Ordering::AcqRel);
+    }
+}
+
+impl Drop for EventFinalizer {
+    fn drop(&mut self) {
+        self.batch
+            .update_status(EventStatus::from_u8(*self.status.get_mut()));
+    }
+}
+
+/// The finalizers of an event, more than one if the event was merged out of
+/// several events.
+#[derive(Clone, Debug, Default)]
+pub struct EventFinalizers(Vec<Arc<EventFinalizer>>);
+
+impl EventFinalizers {
+    pub fn new(finalizer: EventFinalizer) -> Self {
+        Self(vec![Arc::new(finalizer)])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+
+    pub fn take(&mut self) -> Self {
+        Self(mem::take(&mut self.0))
+    }
+
+    pub fn update_status(&self, status: EventStatus) {
+        for finalizer in &self.0 {
+            finalizer.update_status(status);
+        }
+    }
+}
+
+// Events compare equal regardless of how their delivery is tracked.
+impl PartialEq for EventFinalizers {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot::error::TryRecvError;
+
+    #[test]
+    fn notifies_once_all_finalizers_are_dropped() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let first = EventFinalizers::new(EventFinalizer::new(Arc::clone(&batch)));
+        let second = EventFinalizers::new(EventFinalizer::new(batch));
+        let clone = first.clone();
+
+        first.update_status(EventStatus::Delivered);
+        drop(first);
+        drop(second);
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        drop(clone);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[test]
+    fn reports_the_worst_status() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+        let clone = finalizers.clone();
+
+        finalizers.update_status(EventStatus::Errored);
+        clone.update_status(EventStatus::Delivered);
+        drop((finalizers, clone));
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
+    }
+
+    #[test]
+    fn dropped_events_are_delivered() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        drop(EventFinalizers::new(EventFinalizer::new(batch)));
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+}
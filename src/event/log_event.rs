@@ -1,4 +1,6 @@
-use crate::event::{lookup::Segment, util, Lookup, PathComponent, Value};
+use crate::event::{
+    finalization::EventFinalizers, lookup::Segment, util, Lookup, PathComponent, Value,
+};
 use remap::{Object, Path};
 use serde::{Serialize, Serializer};
 use std::{
@@ -8,9 +10,21 @@ use std::{
     iter::FromIterator,
 };
 
-#[derive(PartialEq, Debug, Clone, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct LogEvent {
     fields: BTreeMap<String, Value>,
+    /// Transport-level information about the event, like its source type or
+    /// the headers of the request it arrived in. It isn't part of the event
+    /// itself, so it isn't serialized and is ignored when comparing events.
+    metadata: BTreeMap<String, Value>,
+    /// Tracks the delivery of the event, for the source to acknowledge it.
+    finalizers: EventFinalizers,
+}
+
+impl PartialEq for LogEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+    }
 }
 
 impl LogEvent {
@@ -98,6 +112,30 @@ impl LogEvent {
         &self.fields
     }
 
+    pub fn get_metadata(&self, key: impl AsRef<str>) -> Option<&Value> {
+        self.metadata.get(key.as_ref())
+    }
+
+    pub fn insert_metadata(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    pub fn metadata(&self) -> &BTreeMap<String, Value> {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: BTreeMap<String, Value>) {
+        self.metadata = metadata;
+    }
+
+    pub fn add_finalizers(&mut self, finalizers: EventFinalizers) {
+        self.finalizers.merge(finalizers);
+    }
+
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take()
+    }
+
     #[instrument(level = "trace", skip(self, lookup), fields(lookup = %lookup), err)]
     fn entry(&mut self, lookup: Lookup) -> crate::Result<Entry<String, Value>> {
         trace!("Seeking to entry.");
@@ -140,14 +178,17 @@ impl LogEvent {
 
 impl From<BTreeMap<String, Value>> for LogEvent {
     fn from(map: BTreeMap<String, Value>) -> Self {
-        LogEvent { fields: map }
+        LogEvent {
+            fields: map,
+            metadata: BTreeMap::new(),
+            finalizers: Default::default(),
+        }
     }
 }
 
 impl Into<BTreeMap<String, Value>> for LogEvent {
     fn into(self) -> BTreeMap<String, Value> {
-        let Self { fields } = self;
-        fields
+        self.fields
     }
 }
 
@@ -155,6 +196,8 @@ impl From<HashMap<String, Value>> for LogEvent {
     fn from(map: HashMap<String, Value>) -> Self {
         LogEvent {
             fields: map.into_iter().collect(),
+            metadata: BTreeMap::new(),
+            finalizers: Default::default(),
         }
     }
 }
@@ -187,8 +230,7 @@ impl TryInto<serde_json::Value> for LogEvent {
     type Error = crate::Error;
 
     fn try_into(self) -> Result<serde_json::Value, Self::Error> {
-        let Self { fields } = self;
-        Ok(serde_json::to_value(fields)?)
+        Ok(serde_json::to_value(self.fields)?)
     }
 }
 
@@ -245,6 +287,10 @@ impl Serialize for LogEvent {
 }
 
 impl Object for LogEvent {
+    fn get_metadata(&self, key: &str) -> Option<remap::Value> {
+        self.get_metadata(key).cloned().map(Into::into)
+    }
+
     fn get(&self, path: &remap::Path) -> Result<Option<remap::Value>, String> {
         if path.is_root() {
             let iter = self
@@ -293,11 +339,7 @@ impl Object for LogEvent {
         if path.is_root() {
             match value {
                 remap::Value::Map(map) => {
-                    *self = map
-                        .into_iter()
-                        .map(|(k, v)| (k, v.into()))
-                        .collect::<BTreeMap<_, _>>()
-                        .into();
+                    self.fields = map.into_iter().map(|(k, v)| (k, v.into())).collect();
 
                     return Ok(());
                 }
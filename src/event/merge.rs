@@ -3,6 +3,7 @@ use bytes::BytesMut;
 
 /// Merges all fields specified at `fields` from `incoming` to `current`.
 pub fn merge_log_event(current: &mut LogEvent, mut incoming: LogEvent, fields: &[impl AsRef<str>]) {
+    current.add_finalizers(incoming.take_finalizers());
     for field in fields {
         let incoming_val = match incoming.remove(field) {
             None => continue,
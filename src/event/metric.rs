@@ -1,3 +1,4 @@
+use super::finalization::EventFinalizers;
 use chrono::{DateTime, Utc};
 use derive_is_enum_variant::is_enum_variant;
 use remap::{Object, Segment};
@@ -10,12 +11,24 @@ use std::{
     iter::FromIterator,
 };
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Metric {
     #[serde(flatten)]
     pub series: MetricSeries,
     #[serde(flatten)]
     pub data: MetricData,
+    /// Tracks the delivery of the metric, for the source to acknowledge it.
+    #[serde(skip)]
+    finalizers: EventFinalizers,
+    /// When the metric was received by Vector.
+    #[serde(skip)]
+    ingest_timestamp: Option<DateTime<Utc>>,
+}
+
+impl PartialEq for Metric {
+    fn eq(&self, other: &Self) -> bool {
+        self.series == other.series && self.data == other.data
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -239,6 +252,8 @@ impl Metric {
                 kind,
                 value,
             },
+            finalizers: Default::default(),
+            ingest_timestamp: None,
         }
     }
 
@@ -257,11 +272,34 @@ impl Metric {
         self
     }
 
+    pub fn with_value(mut self, value: MetricValue) -> Self {
+        self.data.value = value;
+        self
+    }
+
     /// Rewrite this into a Metric with the data marked as absolute.
+    pub fn add_finalizers(&mut self, finalizers: EventFinalizers) {
+        self.finalizers.merge(finalizers);
+    }
+
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take()
+    }
+
+    pub fn ingest_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.ingest_timestamp
+    }
+
+    pub fn set_ingest_timestamp(&mut self, timestamp: DateTime<Utc>) {
+        self.ingest_timestamp = Some(timestamp);
+    }
+
     pub fn into_absolute(self) -> Self {
         Self {
             series: self.series,
             data: self.data.into_absolute(),
+            finalizers: self.finalizers,
+            ingest_timestamp: self.ingest_timestamp,
         }
     }
 
@@ -270,6 +308,8 @@ impl Metric {
         Self {
             series: self.series,
             data: self.data.into_incremental(),
+            finalizers: self.finalizers,
+            ingest_timestamp: self.ingest_timestamp,
         }
     }
 
@@ -361,6 +401,8 @@ impl Metric {
         Self {
             series: self.series.clone(),
             data: self.data.zero(),
+            finalizers: Default::default(),
+            ingest_timestamp: self.ingest_timestamp,
         }
     }
 }
@@ -715,6 +757,13 @@ enum MetricPathError<'a> {
 }
 
 impl Object for Metric {
+    fn get_metadata(&self, key: &str) -> Option<remap::Value> {
+        match key {
+            "vector.ingest_timestamp" => self.ingest_timestamp.map(Into::into),
+            _ => None,
+        }
+    }
+
     fn insert(&mut self, path: &remap::Path, value: remap::Value) -> Result<(), String> {
         if path.is_root() {
             return Err(MetricPathError::SetPathError.to_string());
@@ -1222,6 +1271,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn object_metric_ingest_timestamp() {
+        let mut metric = Metric::new(
+            "name".into(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.23 },
+        );
+        assert_eq!(metric.get_metadata("vector.ingest_timestamp"), None);
+
+        metric.set_ingest_timestamp(Utc.ymd(2020, 12, 8).and_hms(12, 0, 0));
+        assert_eq!(
+            metric.get_metadata("vector.ingest_timestamp"),
+            Some(Utc.ymd(2020, 12, 8).and_hms(12, 0, 0).into())
+        );
+        assert_eq!(metric.get_metadata("vector.source_type"), None);
+    }
+
     #[test]
     fn object_metric_invalid_paths() {
         let mut metric = Metric::new(
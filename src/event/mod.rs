@@ -1,10 +1,13 @@
+use self::finalization::{BatchNotifier, EventFinalizer, EventFinalizers};
 use self::proto::{event_wrapper::Event as EventProto, metric::Value as MetricProto, Log};
 use crate::config::log_schema;
 use bytes::Bytes;
 use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
 use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 pub mod discriminant;
+pub mod finalization;
 pub mod merge;
 pub mod merge_state;
 pub mod metric;
@@ -80,6 +83,55 @@ impl Event {
             _ => panic!("Failed type coercion, {:?} is not a metric", self),
         }
     }
+
+    /// Returns an estimate of the size of the event, in bytes, see
+    /// `Value::size_estimate`.
+    pub fn size_estimate(&self) -> usize {
+        match self {
+            Event::Log(log) => log
+                .as_map()
+                .iter()
+                .map(|(k, v)| k.len() + v.size_estimate())
+                .sum(),
+            Event::Metric(metric) => {
+                metric.name().len()
+                    + metric.namespace().map_or(0, str::len)
+                    + metric
+                        .tags()
+                        .map_or(0, |tags| tags.iter().map(|(k, v)| k.len() + v.len()).sum())
+                    + 8
+            }
+        }
+    }
+
+    /// Adds the event to the batch of `notifier`, which reports the status of
+    /// the batch once all of its events are delivered or dropped.
+    pub fn with_batch_notifier(mut self, notifier: &Arc<BatchNotifier>) -> Self {
+        let finalizers = EventFinalizers::new(EventFinalizer::new(Arc::clone(notifier)));
+        match &mut self {
+            Event::Log(log) => log.add_finalizers(finalizers),
+            Event::Metric(metric) => metric.add_finalizers(finalizers),
+        }
+        self
+    }
+
+    /// Takes the finalizers out of the event, for the sink to update their
+    /// status once the event is delivered.
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        match self {
+            Event::Log(log) => log.take_finalizers(),
+            Event::Metric(metric) => metric.take_finalizers(),
+        }
+    }
+
+    /// Adds finalizers to the event, as when it replaces the events they were
+    /// taken from.
+    pub fn add_finalizers(&mut self, finalizers: EventFinalizers) {
+        match self {
+            Event::Log(log) => log.add_finalizers(finalizers),
+            Event::Metric(metric) => metric.add_finalizers(finalizers),
+        }
+    }
 }
 
 fn timestamp_to_string(timestamp: &DateTime<Utc>) -> String {
@@ -294,7 +346,7 @@ impl From<Event> for proto::EventWrapper {
 
                 proto::EventWrapper { event: Some(event) }
             }
-            Event::Metric(Metric { series, data }) => {
+            Event::Metric(Metric { series, data, .. }) => {
                 let name = series.name.name;
                 let namespace = series.name.namespace.unwrap_or_default();
 
@@ -278,6 +278,20 @@ impl Value {
         }
     }
 
+    /// Returns an estimate of the size of the value, in bytes: the length of
+    /// strings and map keys, and the in-memory size of other values. Much
+    /// cheaper than encoding the value.
+    pub fn size_estimate(&self) -> usize {
+        match self {
+            Value::Bytes(bytes) => bytes.len(),
+            Value::Map(map) => map.iter().map(|(k, v)| k.len() + v.size_estimate()).sum(),
+            Value::Array(arr) => arr.iter().map(Value::size_estimate).sum(),
+            Value::Integer(_) | Value::Float(_) | Value::Timestamp(_) => 8,
+            Value::Boolean(_) => 1,
+            Value::Null => 0,
+        }
+    }
+
     pub fn kind(&self) -> &str {
         match self {
             Value::Bytes(_) => "string",
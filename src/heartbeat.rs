@@ -1,13 +1,24 @@
-use crate::internal_events::Heartbeat;
+use crate::{
+    allocator,
+    internal_events::{AllocatorStatsReported, Heartbeat},
+};
 use std::time::{Duration, Instant};
 use tokio::time::interval;
 
-/// Emits Heartbeat event every second.
+/// Emits Heartbeat event every second, along with the memory usage reported by
+/// the allocator.
 pub async fn heartbeat() {
     let since = Instant::now();
     let mut interval = interval(Duration::from_secs(1));
     loop {
         interval.tick().await;
         emit!(Heartbeat { since });
+
+        if let Some(stats) = allocator::stats() {
+            emit!(AllocatorStatsReported {
+                allocator: allocator::selected().as_str(),
+                stats,
+            });
+        }
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -12,7 +12,7 @@ impl<'a> InternalEvent for AddFieldsTemplateRenderingError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "render_error", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -28,7 +28,7 @@ impl<'a> InternalEvent for AddFieldsTemplateInvalid<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "template_error", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -0,0 +1,35 @@
+use super::InternalEvent;
+use crate::allocator::AllocatorStats;
+use metrics::gauge;
+
+#[derive(Debug)]
+pub struct AllocatorStatsReported {
+    pub allocator: &'static str,
+    pub stats: AllocatorStats,
+}
+
+impl InternalEvent for AllocatorStatsReported {
+    fn emit_metrics(&self) {
+        gauge!(
+            "allocator_active_bytes", self.stats.active as f64,
+            "allocator" => self.allocator,
+        );
+        gauge!(
+            "allocator_resident_bytes", self.stats.resident as f64,
+            "allocator" => self.allocator,
+        );
+
+        if let Some(allocated) = self.stats.allocated {
+            gauge!(
+                "allocator_allocated_bytes", allocated as f64,
+                "allocator" => self.allocator,
+            );
+            if allocated > 0 {
+                gauge!(
+                    "allocator_fragmentation_ratio", self.stats.active as f64 / allocated as f64,
+                    "allocator" => self.allocator,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
+use crate::event::finalization::BatchStatus;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct AmqpEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AmqpEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.", internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpReadFailed {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpReadFailed {
+    fn emit_logs(&self) {
+        error!(message = "Failed to read message.", error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpAcknowledgementFailed {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpAcknowledgementFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_acknowledgements_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventNotDelivered<'a> {
+    pub queue: &'a str,
+    pub status: BatchStatus,
+}
+
+impl InternalEvent for AmqpEventNotDelivered<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Events of message weren't delivered; negatively acknowledging it for redelivery.",
+            queue = %self.queue,
+            status = ?self.status,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_negative_acknowledgements_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventSendSuccess {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AmqpEventSendSuccess {
+    fn emit_logs(&self) {
+        trace!(message = "Processed one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventSendFail {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpEventSendFail {
+    fn emit_logs(&self) {
+        error!(message = "Failed to send message.", error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1, "stage" => error_stage::SENDING);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventNotConfirmed;
+
+impl InternalEvent for AmqpEventNotConfirmed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Broker negatively acknowledged message; it wasn't stored.",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1, "stage" => error_stage::SENDING);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventMissingKeys<'a> {
+    pub keys: &'a [String],
+}
+
+impl<'a> InternalEvent for AmqpEventMissingKeys<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Keys do not exist on the event; dropping event.",
+            missing_keys = ?self.keys,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("missing_keys_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::MissingFields,
+        }
+        .emit_metrics();
+    }
+}
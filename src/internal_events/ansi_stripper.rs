@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -16,7 +16,7 @@ impl InternalEvent for ANSIStripperFieldMissing<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "field_missing");
+        counter!("processing_errors_total", 1, "error_type" => "field_missing", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -35,7 +35,7 @@ impl InternalEvent for ANSIStripperFieldInvalid<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "value_invalid");
+        counter!("processing_errors_total", 1, "error_type" => "value_invalid", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -56,6 +56,6 @@ impl InternalEvent for ANSIStripperFailed<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "strip_failed", "stage" => error_stage::PROCESSING);
     }
 }
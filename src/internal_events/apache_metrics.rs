@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use crate::sources::apache_metrics;
 use metrics::{counter, histogram};
 use std::time::Instant;
@@ -54,7 +54,7 @@ impl InternalEvent for ApacheMetricsParseError<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -86,6 +86,6 @@ impl InternalEvent for ApacheMetricsHttpError<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("http_request_errors_total", 1);
+        counter!("http_request_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
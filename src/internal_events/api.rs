@@ -6,11 +6,18 @@ use std::net::SocketAddr;
 pub struct ApiStarted {
     pub addr: SocketAddr,
     pub playground: bool,
+    pub tls: bool,
 }
 
 impl InternalEvent for ApiStarted {
     fn emit_logs(&self) {
-        let playground = &*format!("http://{}:{}/playground", self.addr.ip(), self.addr.port());
+        let scheme = if self.tls { "https" } else { "http" };
+        let playground = &*format!(
+            "{}://{}:{}/playground",
+            scheme,
+            self.addr.ip(),
+            self.addr.port()
+        );
         info!(
             message="API server running.",
             address = ?self.addr,
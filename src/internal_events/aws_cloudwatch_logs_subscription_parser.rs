@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -18,6 +18,7 @@ impl InternalEvent for AwsCloudwatchLogsSubscriptionParserFailedParse {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "failed_parse",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::{counter, histogram};
 use std::borrow::Cow;
 use std::time::Instant;
@@ -55,7 +55,7 @@ impl<'a> InternalEvent for AwsEcsMetricsParseError<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -87,6 +87,6 @@ impl InternalEvent for AwsEcsMetricsHttpError<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("http_request_errors_total", 1);
+        counter!("http_request_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -38,6 +38,6 @@ impl<'a> InternalEvent for AwsKinesisFirehoseRequestError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("request_read_errors_total", 1);
+        counter!("request_read_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,5 +1,6 @@
 #[cfg(feature = "sources-aws_s3")]
 pub mod source {
+    use crate::event::finalization::BatchStatus;
     use crate::internal_events::InternalEvent;
     use crate::sources::aws_s3::sqs::ProcessingError;
     use metrics::counter;
@@ -99,6 +100,27 @@ pub mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub(crate) struct SqsMessageNotDelivered<'a> {
+        pub message_id: &'a str,
+        pub status: BatchStatus,
+    }
+
+    impl<'a> InternalEvent for SqsMessageNotDelivered<'a> {
+        fn emit_logs(&self) {
+            warn!(
+                message = "Events of SQS message weren't delivered, not deleting it.",
+                %self.message_id,
+                status = ?self.status,
+                internal_log_rate_secs = 10,
+            );
+        }
+
+        fn emit_metrics(&self) {
+            counter!("sqs_message_delivery_failed_total", 1);
+        }
+    }
+
     #[derive(Debug)]
     pub(crate) struct SqsS3EventRecordInvalidEventIgnored<'a> {
         pub bucket: &'a str,
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -34,5 +34,10 @@ impl<'a> InternalEvent for AwsSqsMessageGroupIdMissingKeys<'a> {
 
     fn emit_metrics(&self) {
         counter!("missing_keys_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::MissingFields,
+        }
+        .emit_metrics();
     }
 }
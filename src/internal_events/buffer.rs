@@ -0,0 +1,77 @@
+use super::{error_stage, InternalEvent};
+use metrics::{counter, gauge};
+use std::{io, path::Path};
+
+#[derive(Debug)]
+pub struct BufferUsageReported {
+    pub buffer_type: &'static str,
+    pub events: usize,
+    pub max_events: Option<usize>,
+    pub byte_size: Option<usize>,
+    pub max_size: Option<usize>,
+    pub disk_usage: Option<u64>,
+    pub discarded_events: usize,
+}
+
+impl InternalEvent for BufferUsageReported {
+    fn emit_metrics(&self) {
+        gauge!("buffer_events", self.events as f64, "buffer_type" => self.buffer_type);
+        if let Some(max_events) = self.max_events {
+            gauge!("buffer_max_event_size", max_events as f64, "buffer_type" => self.buffer_type);
+        }
+        if let Some(byte_size) = self.byte_size {
+            gauge!("buffer_byte_size", byte_size as f64, "buffer_type" => self.buffer_type);
+        }
+        if let Some(max_size) = self.max_size {
+            gauge!("buffer_max_byte_size", max_size as f64, "buffer_type" => self.buffer_type);
+        }
+        if let Some(disk_usage) = self.disk_usage {
+            gauge!("buffer_disk_usage_bytes", disk_usage as f64, "buffer_type" => self.buffer_type);
+        }
+        counter!(
+            "buffer_discarded_events_total", self.discarded_events as u64,
+            "buffer_type" => self.buffer_type,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DiskBufferCorrupted<'a> {
+    pub path: &'a Path,
+    pub reason: &'a str,
+}
+
+impl<'a> InternalEvent for DiskBufferCorrupted<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Disk buffer data file is corrupted; skipping the rest of it.",
+            path = ?self.path,
+            reason = %self.reason,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("buffer_corruptions_total", 1, "buffer_type" => "disk");
+    }
+}
+
+#[derive(Debug)]
+pub struct DiskBufferIoError<'a> {
+    pub path: &'a Path,
+    pub error: &'a io::Error,
+}
+
+impl<'a> InternalEvent for DiskBufferIoError<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Disk buffer I/O failed.",
+            path = ?self.path,
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("buffer_io_errors_total", 1, "buffer_type" => "disk", "stage" => error_stage::SENDING);
+    }
+}
@@ -0,0 +1,30 @@
+use super::InternalEvent;
+use chrono::Duration;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct EventClockSkewed {
+    /// How far the timestamp of the event is ahead of the receive time, or
+    /// behind it if negative.
+    pub skew: Duration,
+    pub action: &'static str,
+}
+
+impl InternalEvent for EventClockSkewed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event timestamp is too far from the receive time.",
+            skew_secs = %self.skew.num_seconds(),
+            action = %self.action,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "clock_skewed_events_total", 1,
+            "direction" => if self.skew > Duration::zero() { "future" } else { "past" },
+            "action" => self.action,
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -18,6 +18,6 @@ impl<'a> InternalEvent for CoercerConversionFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "type_conversion_failed");
+        counter!("processing_errors_total", 1, "error_type" => "type_conversion_failed", "stage" => error_stage::PROCESSING);
     }
 }
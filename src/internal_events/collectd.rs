@@ -0,0 +1,84 @@
+use super::{error_stage, InternalEvent};
+use crate::sources::collectd::parser::ParseError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct CollectdEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for CollectdEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received packet.", count = %self.count, byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct CollectdInvalidPacket {
+    pub error: ParseError,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for CollectdInvalidPacket {
+    fn emit_logs(&self) {
+        error!(
+            message = "Invalid packet from collectd, discarding.",
+            error = %self.error,
+            byte_size = %self.byte_size,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("invalid_record_total", 1);
+        counter!("invalid_record_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+enum CollectdSocketErrorType {
+    Bind,
+    Read,
+}
+
+#[derive(Debug)]
+pub struct CollectdSocketError<T> {
+    r#type: CollectdSocketErrorType,
+    pub error: T,
+}
+
+impl<T> CollectdSocketError<T> {
+    pub fn bind(error: T) -> Self {
+        Self {
+            r#type: CollectdSocketErrorType::Bind,
+            error,
+        }
+    }
+
+    pub fn read(error: T) -> Self {
+        Self {
+            r#type: CollectdSocketErrorType::Read,
+            error,
+        }
+    }
+}
+
+impl<T: std::fmt::Debug + std::fmt::Display> InternalEvent for CollectdSocketError<T> {
+    fn emit_logs(&self) {
+        let message = match self.r#type {
+            CollectdSocketErrorType::Bind => "Failed to bind to UDP listener socket.",
+            CollectdSocketErrorType::Read => "Failed to read UDP datagram.",
+        };
+        error!(message, error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "stage" => error_stage::RECEIVING);
+    }
+}
@@ -0,0 +1,31 @@
+//! Events emitted for every component, whatever its type, by the topology,
+//! along with the tags shared by the errors of all components.
+
+use super::InternalEvent;
+use metrics::counter;
+
+/// Values of the `stage` tag of errors, telling where in the handling of
+/// events the error happened.
+pub mod error_stage {
+    /// While receiving events, such as a source reading from its origin.
+    pub const RECEIVING: &str = "receiving";
+    /// While processing events, such as a transform parsing a field.
+    pub const PROCESSING: &str = "processing";
+    /// While sending events, such as a sink writing to its destination.
+    pub const SENDING: &str = "sending";
+}
+
+/// Events received by the current component, either from the origin of a
+/// source, or from the inputs of a transform or sink.
+#[derive(Debug)]
+pub struct EventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for EventsReceived {
+    fn emit_metrics(&self) {
+        counter!("received_events_total", self.count as u64);
+        counter!("received_event_bytes_total", self.byte_size as u64);
+    }
+}
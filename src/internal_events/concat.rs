@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -24,7 +24,7 @@ impl<'a> InternalEvent for ConcatSubstringError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "substring_failed", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -28,6 +28,11 @@ impl<'a> InternalEvent for ConsoleFieldNotFound<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "field_not_found");
+        counter!("processing_errors_total", 1, "error_type" => "field_not_found", "stage" => error_stage::SENDING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::MissingFields,
+        }
+        .emit_metrics();
     }
 }
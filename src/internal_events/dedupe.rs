@@ -1,5 +1,4 @@
-use super::InternalEvent;
-use metrics::counter;
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
 
 #[derive(Debug)]
 pub(crate) struct DedupeEventDiscarded {
@@ -16,6 +15,10 @@ impl InternalEvent for DedupeEventDiscarded {
     }
 
     fn emit_metrics(&self) {
-        counter!("events_discarded_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::Duplicate,
+        }
+        .emit_metrics();
     }
 }
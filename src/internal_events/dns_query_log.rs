@@ -0,0 +1,38 @@
+use super::{error_stage, InternalEvent};
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct DnsQueryLogLineSkipped<'a> {
+    pub format: &'a str,
+}
+
+impl<'a> InternalEvent for DnsQueryLogLineSkipped<'a> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Skipped line that is neither a query nor a response.",
+            format = %self.format,
+            internal_log_rate_secs = 30,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DnsQueryLogTimestampParseError<'a> {
+    pub format: &'a str,
+    pub timestamp: &'a str,
+}
+
+impl<'a> InternalEvent for DnsQueryLogTimestampParseError<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Invalid timestamp; leaving the timestamp of the event unset.",
+            format = %self.format,
+            timestamp = %self.timestamp,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "parse_failed", "stage" => error_stage::RECEIVING);
+    }
+}
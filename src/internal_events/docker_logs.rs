@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use bollard::errors::Error;
 use chrono::ParseError;
 use metrics::counter;
@@ -97,7 +97,7 @@ impl<'a> InternalEvent for DockerLogsCommunicationError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("communication_errors_total", 1);
+        counter!("communication_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -118,7 +118,7 @@ impl<'a> InternalEvent for DockerLogsContainerMetadataFetchFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("container_metadata_fetch_errors_total", 1);
+        counter!("container_metadata_fetch_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -139,7 +139,7 @@ impl<'a> InternalEvent for DockerLogsTimestampParseFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("timestamp_parse_errors_total", 1);
+        counter!("timestamp_parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -162,6 +162,6 @@ impl<'a> InternalEvent for DockerLogsLoggingDriverUnsupported<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("logging_driver_errors_total", 1);
+        counter!("logging_driver_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
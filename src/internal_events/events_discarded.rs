@@ -0,0 +1,72 @@
+use super::InternalEvent;
+use metrics::counter;
+
+/// Why events were dropped before reaching their destination.
+///
+/// Used as the `reason` tag of the `events_discarded_total` counter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiscardReason {
+    /// The buffer of a sink was full, and configured to drop new events.
+    BufferFull,
+    /// The event didn't match a condition, like in the `filter` transform.
+    Filtered,
+    /// The event was left out by sampling.
+    Sampled,
+    /// The event was a duplicate of an event seen before.
+    Duplicate,
+    /// The event would have exceeded a configured limit.
+    LimitExceeded,
+    /// The event was older than events already sent for the same stream.
+    OutOfOrder,
+    /// The event is missing fields it needs to be processed or sent.
+    MissingFields,
+    /// The event couldn't be processed by the component.
+    ProcessingFailed,
+    /// The event couldn't be encoded for the destination.
+    EncodingFailed,
+    /// The event is larger than the maximum batch size.
+    TooLarge,
+    /// The request containing the event failed, or exhausted its retries.
+    RequestFailed,
+}
+
+impl DiscardReason {
+    pub fn as_str(self) -> &'static str {
+        use DiscardReason::*;
+
+        match self {
+            BufferFull => "buffer_full",
+            Filtered => "filtered",
+            Sampled => "sampled",
+            Duplicate => "duplicate",
+            LimitExceeded => "limit_exceeded",
+            OutOfOrder => "out_of_order",
+            MissingFields => "missing_fields",
+            ProcessingFailed => "processing_failed",
+            EncodingFailed => "encoding_failed",
+            TooLarge => "too_large",
+            RequestFailed => "request_failed",
+        }
+    }
+}
+
+/// Counts events that are dropped, and never reach their destination.
+///
+/// Every component counts the events it drops through this one metric, so
+/// data loss can be audited without knowing the metrics of each component.
+/// Components still emit their own internal events to log why events were
+/// dropped.
+#[derive(Debug)]
+pub struct EventsDiscarded {
+    pub count: usize,
+    pub reason: DiscardReason,
+}
+
+impl InternalEvent for EventsDiscarded {
+    fn emit_metrics(&self) {
+        counter!(
+            "events_discarded_total", self.count as u64,
+            "reason" => self.reason.as_str(),
+        );
+    }
+}
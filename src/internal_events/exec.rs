@@ -0,0 +1,135 @@
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
+use metrics::counter;
+use std::process::ExitStatus;
+
+#[derive(Debug)]
+pub struct ExecEventProcessed {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ExecEventProcessed {
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecFieldNotFound<'a> {
+    pub missing_field: &'a str,
+}
+
+impl<'a> InternalEvent for ExecFieldNotFound<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Field not found; dropping event.",
+            missing_field = ?self.missing_field,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "field_not_found", "stage" => error_stage::PROCESSING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::MissingFields,
+        }
+        .emit_metrics();
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandStarted<'a> {
+    pub command: &'a str,
+    pub pid: u32,
+}
+
+impl<'a> InternalEvent for ExecCommandStarted<'a> {
+    fn emit_logs(&self) {
+        info!(message = "Started command.", command = %self.command, pid = self.pid);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("command_started_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandSpawnFailed<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl<'a> InternalEvent for ExecCommandSpawnFailed<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to start command.",
+            command = %self.command,
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "spawn_failed", "stage" => error_stage::PROCESSING);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandExited<'a> {
+    pub command: &'a str,
+    pub status: ExitStatus,
+}
+
+impl<'a> InternalEvent for ExecCommandExited<'a> {
+    fn emit_logs(&self) {
+        match self.status.code() {
+            Some(0) => debug!(message = "Command exited.", command = %self.command),
+            Some(code) => warn!(
+                message = "Command exited with an error.",
+                command = %self.command,
+                code,
+                internal_log_rate_secs = 10,
+            ),
+            None => warn!(
+                message = "Command was terminated by a signal.",
+                command = %self.command,
+                internal_log_rate_secs = 10,
+            ),
+        }
+    }
+
+    fn emit_metrics(&self) {
+        counter!("command_exited_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecWriteFailed {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecWriteFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Unable to write to command; restarting it.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "write_failed", "stage" => error_stage::PROCESSING);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandStderr<'a> {
+    pub line: &'a str,
+}
+
+impl<'a> InternalEvent for ExecCommandStderr<'a> {
+    fn emit_logs(&self) {
+        info!(message = "Command wrote to stderr.", line = %self.line);
+    }
+}
@@ -18,6 +18,7 @@ impl InternalEvent for FileOpen {
 #[cfg(any(feature = "sources-file", feature = "sources-kubernetes-logs"))]
 mod source {
     use super::{FileOpen, InternalEvent};
+    use crate::{event::finalization::BatchStatus, internal_events::error_stage};
     use file_source::FileSourceInternalEvents;
     use metrics::counter;
     use std::{io::Error, path::Path, time::Duration};
@@ -49,6 +50,29 @@ mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub struct FileEventNotDelivered<'a> {
+        pub file: &'a str,
+        pub offset: u64,
+        pub status: BatchStatus,
+    }
+
+    impl InternalEvent for FileEventNotDelivered<'_> {
+        fn emit_logs(&self) {
+            warn!(
+                message = "Event of line wasn't delivered; not updating the checkpoint of its file anymore.",
+                file = %self.file,
+                offset = %self.offset,
+                status = ?self.status,
+                internal_log_rate_secs = 10,
+            );
+        }
+
+        fn emit_metrics(&self) {
+            counter!("checkpoint_updates_skipped_total", 1);
+        }
+    }
+
     #[derive(Debug)]
     pub struct FileChecksumFailed<'a> {
         pub path: &'a Path,
@@ -66,6 +90,7 @@ mod source {
             counter!(
                 "checksum_errors_total", 1,
                 "file" => self.path.to_string_lossy().into_owned(),
+                "stage" => error_stage::RECEIVING,
             );
         }
     }
@@ -89,6 +114,7 @@ mod source {
             counter!(
                 "fingerprint_read_errors_total", 1,
                 "file" => self.path.to_string_lossy().into_owned(),
+                "stage" => error_stage::RECEIVING,
             );
         }
     }
@@ -113,6 +139,7 @@ mod source {
             counter!(
                 "file_delete_errors_total", 1,
                 "file" => self.path.to_string_lossy().into_owned(),
+                "stage" => error_stage::RECEIVING,
             );
         }
     }
@@ -178,6 +205,7 @@ mod source {
             counter!(
                 "file_watch_errors_total", 1,
                 "file" => self.path.to_string_lossy().into_owned(),
+                "stage" => error_stage::RECEIVING,
             );
         }
     }
@@ -257,7 +285,7 @@ mod source {
         }
 
         fn emit_metrics(&self) {
-            counter!("checkpoint_write_errors_total", 1);
+            counter!("checkpoint_write_errors_total", 1, "stage" => error_stage::RECEIVING);
         }
     }
 
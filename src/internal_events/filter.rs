@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -15,6 +15,10 @@ pub struct FilterEventDiscarded;
 
 impl InternalEvent for FilterEventDiscarded {
     fn emit_metrics(&self) {
-        counter!("events_discarded_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::Filtered,
+        }
+        .emit_metrics();
     }
 }
@@ -0,0 +1,50 @@
+use super::{error_stage, InternalEvent};
+use crate::sources::fluent::FluentError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct FluentMessageReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for FluentMessageReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received fluent message.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FluentEventReceived;
+
+impl InternalEvent for FluentEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FluentConnectionError {
+    pub error: FluentError,
+}
+
+impl InternalEvent for FluentConnectionError {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Closing fluent connection.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "mode" => "tcp", "stage" => error_stage::RECEIVING);
+    }
+}
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -16,7 +16,7 @@ impl<'a> InternalEvent for GeoipIpAddressParseError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "type_ip_address_parse_error");
+        counter!("processing_errors_total", 1, "error_type" => "type_ip_address_parse_error", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -35,6 +35,6 @@ impl<'a> InternalEvent for GeoipFieldDoesNotExist<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "type_field_does_not_exist");
+        counter!("processing_errors_total", 1, "error_type" => "type_field_does_not_exist", "stage" => error_stage::PROCESSING);
     }
 }
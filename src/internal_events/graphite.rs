@@ -0,0 +1,105 @@
+use super::{error_stage, InternalEvent};
+use crate::sources::graphite::parser::ParseError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct GraphiteEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for GraphiteEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received metric.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphiteInvalidRecord<'a> {
+    pub error: ParseError,
+    pub text: &'a str,
+}
+
+impl InternalEvent for GraphiteInvalidRecord<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Invalid metric from graphite, discarding.",
+            error = %self.error,
+            text = %self.text,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("invalid_record_total", 1);
+        counter!("invalid_record_bytes_total", self.text.len() as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphiteInvalidPayload {
+    pub error: ParseError,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for GraphiteInvalidPayload {
+    fn emit_logs(&self) {
+        error!(
+            message = "Invalid pickled message from graphite, discarding.",
+            error = %self.error,
+            byte_size = %self.byte_size,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("invalid_record_total", 1);
+        counter!("invalid_record_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+enum GraphiteSocketErrorType {
+    Bind,
+    Read,
+}
+
+#[derive(Debug)]
+pub struct GraphiteSocketError<T> {
+    r#type: GraphiteSocketErrorType,
+    pub error: T,
+}
+
+impl<T> GraphiteSocketError<T> {
+    pub fn bind(error: T) -> Self {
+        Self {
+            r#type: GraphiteSocketErrorType::Bind,
+            error,
+        }
+    }
+
+    pub fn read(error: T) -> Self {
+        Self {
+            r#type: GraphiteSocketErrorType::Read,
+            error,
+        }
+    }
+}
+
+impl<T: std::fmt::Debug + std::fmt::Display> InternalEvent for GraphiteSocketError<T> {
+    fn emit_logs(&self) {
+        let message = match self.r#type {
+            GraphiteSocketErrorType::Bind => "Failed to bind to UDP listener socket.",
+            GraphiteSocketErrorType::Read => "Failed to read UDP datagram.",
+        };
+        error!(message, error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "stage" => error_stage::RECEIVING);
+    }
+}
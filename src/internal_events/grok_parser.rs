@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -18,6 +18,7 @@ impl InternalEvent for GrokParserFailedMatch<'_> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "failed_match",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -35,6 +36,7 @@ impl InternalEvent for GrokParserMissingField<'_> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "missing_field",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -58,6 +60,7 @@ impl<'a> InternalEvent for GrokParserConversionFailed<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "type_conversion_failed",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::counter;
 use std::error::Error;
 
@@ -56,7 +56,11 @@ impl InternalEvent for HTTPEventMissingMessage {
     }
 
     fn emit_metrics(&self) {
-        counter!("events_discarded_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::MissingFields,
+        }
+        .emit_metrics();
     }
 }
 
@@ -93,6 +97,6 @@ impl<'a> InternalEvent for HTTPDecompressError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
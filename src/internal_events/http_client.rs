@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use http::{
     header::{self, HeaderMap, HeaderValue},
     Request, Response,
@@ -83,7 +83,7 @@ impl<'a> InternalEvent for GotHTTPError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("http_client_errors_total", 1, "error_kind" => self.error.to_string());
+        counter!("http_client_errors_total", 1, "error_kind" => self.error.to_string(), "stage" => error_stage::SENDING);
         histogram!("http_client_rtt_ns", self.roundtrip);
         histogram!("http_client_error_rtt_ns", self.roundtrip, "error_kind" => self.error.to_string());
     }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 use serde_json::Error;
 
@@ -34,6 +34,7 @@ impl<'a> InternalEvent for JsonParserFailedParse<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "failed_parse",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -55,6 +56,7 @@ impl<'a> InternalEvent for JsonParserTargetExists<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "target_field_exists",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -1,5 +1,7 @@
-use super::InternalEvent;
-use metrics::counter;
+use super::{error_stage, InternalEvent};
+use crate::event::finalization::BatchStatus;
+use metrics::{counter, gauge};
+use rdkafka::{statistics::Broker, Statistics};
 
 #[derive(Debug)]
 pub struct KafkaEventReceived {
@@ -32,6 +34,31 @@ impl InternalEvent for KafkaOffsetUpdateFailed {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaEventNotDelivered<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub offset: i64,
+    pub status: BatchStatus,
+}
+
+impl InternalEvent for KafkaEventNotDelivered<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Events of message weren't delivered; not storing offsets of its partition anymore.",
+            topic = %self.topic,
+            partition = %self.partition,
+            offset = %self.offset,
+            status = ?self.status,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_offset_updates_skipped_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaEventFailed {
     pub error: rdkafka::error::KafkaError,
@@ -57,3 +84,142 @@ impl InternalEvent for KafkaKeyExtractionFailed<'_> {
         error!(message = "Failed to extract key.", key_field = %self.key_field);
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaDeliveryFailed {
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaDeliveryFailed {
+    fn emit_logs(&self) {
+        error!(message = "Failed to deliver message.", error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("delivery_errors_total", 1, "stage" => error_stage::SENDING);
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaStatisticsReceived<'a> {
+    pub statistics: &'a Statistics,
+    /// The statistics received before from the same client. The totals
+    /// reported by librdkafka are counted from the start of the client, so
+    /// only the increase since then is added to the counters.
+    pub previous: Option<&'a Statistics>,
+}
+
+impl InternalEvent for KafkaStatisticsReceived<'_> {
+    fn emit_logs(&self) {
+        trace!(message = "Received Kafka client statistics.");
+    }
+
+    fn emit_metrics(&self) {
+        let (current, previous) = (self.statistics, self.previous);
+
+        gauge!("kafka_queue_messages", current.msg_cnt as f64);
+        gauge!("kafka_queue_messages_bytes", current.msg_size as f64);
+
+        let total = |field: fn(&Statistics) -> i64| increase(field(current), previous.map(field));
+        counter!("kafka_requests_total", total(|s| s.tx as i64));
+        counter!("kafka_requests_bytes_total", total(|s| s.tx_bytes as i64));
+        counter!("kafka_responses_total", total(|s| s.rx as i64));
+        counter!("kafka_responses_bytes_total", total(|s| s.rx_bytes as i64));
+        counter!("kafka_produced_messages_total", total(|s| s.txmsgs as i64));
+        counter!(
+            "kafka_produced_messages_bytes_total",
+            total(|s| s.txmsg_bytes as i64)
+        );
+        counter!("kafka_consumed_messages_total", total(|s| s.rxmsgs as i64));
+        counter!(
+            "kafka_consumed_messages_bytes_total",
+            total(|s| s.rxmsg_bytes as i64)
+        );
+
+        // The internal broker of librdkafka isn't a connection.
+        for (name, broker) in current
+            .brokers
+            .iter()
+            .filter(|(name, _)| !name.ends_with("/internal"))
+        {
+            let previous = previous.and_then(|previous| previous.brokers.get(name));
+            let total = |field: fn(&Broker) -> i64| increase(field(broker), previous.map(field));
+
+            let connected = if broker.state == "UP" { 1.0 } else { 0.0 };
+            gauge!("kafka_broker_connected", connected, "broker" => name.clone());
+
+            counter!(
+                "kafka_broker_errors_total",
+                total(|b| b.txerrs as i64),
+                "broker" => name.clone(),
+                "error_type" => "transmit",
+                "stage" => error_stage::SENDING,
+            );
+            counter!(
+                "kafka_broker_errors_total",
+                total(|b| b.rxerrs as i64),
+                "broker" => name.clone(),
+                "error_type" => "receive",
+                "stage" => error_stage::RECEIVING,
+            );
+            counter!(
+                "kafka_broker_request_timeouts_total",
+                total(|b| b.req_timeouts as i64),
+                "broker" => name.clone()
+            );
+        }
+
+        // Partitions below zero are internal to librdkafka, and the lag is
+        // negative while it's unknown.
+        for (topic_name, topic) in &current.topics {
+            for partition in topic
+                .partitions
+                .values()
+                .filter(|partition| partition.partition >= 0 && partition.consumer_lag >= 0)
+            {
+                gauge!(
+                    "kafka_consumer_lag",
+                    partition.consumer_lag as f64,
+                    "topic" => topic_name.clone(),
+                    "partition" => partition.partition.to_string()
+                );
+            }
+        }
+    }
+}
+
+/// The increase of a total since the previous statistics.
+fn increase(current: i64, previous: Option<i64>) -> u64 {
+    (current - previous.unwrap_or(0)).max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::increase;
+
+    #[test]
+    fn increase_of_totals() {
+        assert_eq!(increase(5, None), 5);
+        assert_eq!(increase(7, Some(5)), 2);
+        assert_eq!(increase(3, Some(5)), 0);
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaTokenRefreshFailed<'a> {
+    pub error: &'a crate::Error,
+}
+
+impl InternalEvent for KafkaTokenRefreshFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to refresh SASL token.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("kafka_token_refresh_failures_total", 1);
+    }
+}
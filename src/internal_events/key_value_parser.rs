@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -20,6 +20,7 @@ impl InternalEvent for KeyValueParseFailed {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "failed_parse",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -41,6 +42,7 @@ impl<'a> InternalEvent for KeyValueTargetExists<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "target_field_exists",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -62,6 +64,7 @@ impl InternalEvent for KeyValueFieldDoesNotExist {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "failed_parse",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -1,4 +1,5 @@
 use super::InternalEvent;
+use crate::internal_events::error_stage;
 use metrics::counter;
 use std::fmt::Debug;
 
@@ -46,6 +47,6 @@ impl<E: Debug> InternalEvent for WatchStreamErrored<E> {
     }
 
     fn emit_metrics(&self) {
-        counter!("k8s_watch_stream_errors_total", 1);
+        counter!("k8s_watch_stream_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 use std::num::ParseFloatError;
 
@@ -18,6 +18,7 @@ impl<'a> InternalEvent for LogToMetricFieldNotFound<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
                  "error_type" => "field_not_found",
+                 "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -40,6 +41,7 @@ impl<'a> InternalEvent for LogToMetricParseFloatError<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
                  "error_type" => "parse_error",
+                 "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -61,6 +63,7 @@ impl InternalEvent for LogToMetricTemplateRenderError {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
                  "error_type" => "render_error",
+                 "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -77,6 +80,7 @@ impl InternalEvent for LogToMetricTemplateParseError {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
                  "error_type" => "template_error",
+                 "stage" => error_stage::PROCESSING,
         );
     }
 }
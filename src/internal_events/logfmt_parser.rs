@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -14,6 +14,7 @@ impl InternalEvent for LogfmtParserMissingField<'_> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "missing_field",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -37,6 +38,7 @@ impl<'a> InternalEvent for LogfmtParserConversionFailed<'a> {
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
             "error_type" => "type_conversion_failed",
+            "stage" => error_stage::PROCESSING,
         );
     }
 }
@@ -0,0 +1,38 @@
+use super::{error_stage, InternalEvent};
+use crate::sources::logstash::LogstashError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct LogstashEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for LogstashEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LogstashConnectionError {
+    pub error: LogstashError,
+}
+
+impl InternalEvent for LogstashConnectionError {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Closing logstash connection.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "mode" => "tcp", "stage" => error_stage::RECEIVING);
+    }
+}
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::{counter, gauge};
 
 #[derive(Debug)]
@@ -23,7 +23,12 @@ impl InternalEvent for LuaScriptError {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "script_failed", "stage" => error_stage::PROCESSING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::ProcessingFailed,
+        }
+        .emit_metrics();
     }
 }
 
@@ -38,6 +43,6 @@ impl InternalEvent for LuaBuildError {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "build_failed", "stage" => error_stage::PROCESSING);
     }
 }
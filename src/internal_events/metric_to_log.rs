@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 use serde_json::Error;
 
@@ -17,6 +17,6 @@ impl<'a> InternalEvent for MetricToLogFailedSerialize {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "failed_serialize");
+        counter!("processing_errors_total", 1, "error_type" => "failed_serialize", "stage" => error_stage::PROCESSING);
     }
 }
@@ -3,6 +3,9 @@ use std::borrow::Cow;
 mod adaptive_concurrency;
 mod add_fields;
 mod add_tags;
+mod allocator;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+mod amqp;
 mod ansi_stripper;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
@@ -23,24 +26,38 @@ pub(crate) mod aws_s3;
 #[cfg(feature = "sinks-aws_sqs")]
 mod aws_sqs;
 mod blackhole;
+mod buffer;
+mod clock_skew;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
+#[cfg(feature = "sources-collectd")]
+mod collectd;
+mod common;
 #[cfg(feature = "transforms-concat")]
 mod concat;
 #[cfg(feature = "sinks-console")]
 mod console;
 #[cfg(feature = "transforms-dedupe")]
 mod dedupe;
+#[cfg(feature = "sources-dns_query_log")]
+mod dns_query_log;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
 mod elasticsearch;
 mod encoding_transcode;
+mod events_discarded;
+#[cfg(feature = "sinks-exec")]
+mod exec;
 #[cfg(feature = "transforms-filter")]
 mod filter;
+#[cfg(feature = "sources-fluent")]
+mod fluent;
 #[cfg(feature = "sources-generator")]
 mod generator;
 #[cfg(feature = "transforms-geoip")]
 mod geoip;
+#[cfg(feature = "sources-graphite")]
+mod graphite;
 #[cfg(feature = "transforms-grok_parser")]
 mod grok_parser;
 mod heartbeat;
@@ -52,7 +69,7 @@ pub mod http_client;
 mod journald;
 #[cfg(feature = "transforms-json_parser")]
 mod json_parser;
-#[cfg(feature = "sources-kafka")]
+#[cfg(feature = "rdkafka")]
 mod kafka;
 #[cfg(feature = "transforms-key_value_parser")]
 mod key_value_parser;
@@ -63,17 +80,23 @@ mod log_to_metric;
 #[cfg(feature = "transforms-logfmt_parser")]
 mod logfmt_parser;
 mod logplex;
+#[cfg(feature = "sources-logstash")]
+mod logstash;
 #[cfg(feature = "transforms-lua")]
 mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+mod opentelemetry;
+#[cfg(feature = "tonic")]
+mod plugin;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -94,6 +117,7 @@ mod route;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+mod sink_request;
 mod socket;
 mod split;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
@@ -107,6 +131,8 @@ mod syslog;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
 mod tcp;
+#[cfg(feature = "transforms-tenant_quota")]
+mod tenant_quota;
 #[cfg(feature = "transforms-tokenizer")]
 mod tokenizer;
 mod topology;
@@ -121,6 +147,9 @@ pub mod kubernetes;
 pub use self::adaptive_concurrency::*;
 pub use self::add_fields::*;
 pub use self::add_tags::*;
+pub use self::allocator::*;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+pub use self::amqp::*;
 pub use self::ansi_stripper::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub use self::apache_metrics::*;
@@ -139,18 +168,28 @@ pub use self::aws_kinesis_streams::*;
 #[cfg(feature = "sinks-aws_sqs")]
 pub use self::aws_sqs::*;
 pub use self::blackhole::*;
+pub use self::buffer::*;
+pub use self::clock_skew::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
+#[cfg(feature = "sources-collectd")]
+pub use self::collectd::*;
+pub use self::common::*;
 #[cfg(feature = "transforms-concat")]
 pub use self::concat::*;
 #[cfg(feature = "sinks-console")]
 pub use self::console::*;
 #[cfg(feature = "transforms-dedupe")]
 pub(crate) use self::dedupe::*;
+#[cfg(feature = "sources-dns_query_log")]
+pub use self::dns_query_log::*;
 #[cfg(feature = "sources-docker_logs")]
 pub use self::docker_logs::*;
 pub use self::elasticsearch::*;
 pub use self::encoding_transcode::*;
+pub use self::events_discarded::*;
+#[cfg(feature = "sinks-exec")]
+pub use self::exec::*;
 #[cfg(any(
     feature = "sources-file",
     feature = "sources-kubernetes-logs",
@@ -159,10 +198,14 @@ pub use self::encoding_transcode::*;
 pub use self::file::*;
 #[cfg(feature = "transforms-filter")]
 pub use self::filter::*;
+#[cfg(feature = "sources-fluent")]
+pub(crate) use self::fluent::*;
 #[cfg(feature = "sources-generator")]
 pub use self::generator::*;
 #[cfg(feature = "transforms-geoip")]
 pub(crate) use self::geoip::*;
+#[cfg(feature = "sources-graphite")]
+pub use self::graphite::*;
 #[cfg(feature = "transforms-grok_parser")]
 pub(crate) use self::grok_parser::*;
 pub use self::heartbeat::*;
@@ -174,7 +217,7 @@ pub(crate) use self::http::*;
 pub(crate) use self::journald::*;
 #[cfg(feature = "transforms-json_parser")]
 pub(crate) use self::json_parser::*;
-#[cfg(feature = "sources-kafka")]
+#[cfg(feature = "rdkafka")]
 pub use self::kafka::*;
 #[cfg(feature = "transforms-key_value_parser")]
 pub(crate) use self::key_value_parser::*;
@@ -185,15 +228,21 @@ pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub use self::logfmt_parser::*;
 pub use self::logplex::*;
+#[cfg(feature = "sources-logstash")]
+pub(crate) use self::logstash::*;
 #[cfg(feature = "transforms-lua")]
 pub use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub use self::nats::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub use self::open::*;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+pub(crate) use self::opentelemetry::*;
+#[cfg(feature = "tonic")]
+pub use self::plugin::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 pub use self::process::*;
@@ -214,6 +263,7 @@ pub use self::route::*;
 pub use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub use self::sematext_metrics::*;
+pub use self::sink_request::*;
 pub(crate) use self::socket::*;
 pub use self::split::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
@@ -227,6 +277,8 @@ pub use self::syslog::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
 pub use self::tcp::*;
+#[cfg(feature = "transforms-tenant_quota")]
+pub(crate) use self::tenant_quota::*;
 #[cfg(feature = "transforms-tokenizer")]
 pub(crate) use self::tokenizer::*;
 pub use self::topology::*;
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::{counter, histogram};
 use mongodb::{bson, error::Error as MongoError};
 use std::time::Instant;
@@ -31,7 +31,7 @@ impl<'a> InternalEvent for MongoDBMetricsRequestError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("request_errors_total", 1);
+        counter!("request_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -46,6 +46,6 @@ impl<'a> InternalEvent for MongoDBMetricsBsonParseError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,7 +1,64 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
+use crate::event::finalization::BatchStatus;
 use metrics::counter;
 use std::io::Error;
 
+#[derive(Debug)]
+pub struct NatsEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for NatsEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.", internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsAcknowledgementFailed {
+    pub error: Error,
+}
+
+impl InternalEvent for NatsAcknowledgementFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_acknowledgements_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsEventNotDelivered<'a> {
+    pub subject: &'a str,
+    pub status: BatchStatus,
+}
+
+impl InternalEvent for NatsEventNotDelivered<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Events of message weren't delivered; negatively acknowledging it for redelivery.",
+            subject = %self.subject,
+            status = ?self.status,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_negative_acknowledgements_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct NatsEventSendSuccess {
     pub byte_size: usize,
@@ -29,7 +86,7 @@ impl InternalEvent for NatsEventSendFail {
     }
 
     fn emit_metrics(&self) {
-        counter!("send_errors_total", 1);
+        counter!("send_errors_total", 1, "stage" => error_stage::SENDING);
     }
 }
 
@@ -49,5 +106,10 @@ impl<'a> InternalEvent for NatsEventMissingKeys<'a> {
 
     fn emit_metrics(&self) {
         counter!("missing_keys_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::MissingFields,
+        }
+        .emit_metrics();
     }
 }
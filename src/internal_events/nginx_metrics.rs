@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use crate::sources::nginx_metrics::parser::ParseError;
 use metrics::{counter, histogram};
 use std::time::Instant;
@@ -31,7 +31,7 @@ impl<'a> InternalEvent for NginxMetricsRequestError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("http_request_errors_total", 1);
+        counter!("http_request_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -46,6 +46,6 @@ impl<'a> InternalEvent for NginxMetricsStubStatusParseError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
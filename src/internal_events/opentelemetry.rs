@@ -0,0 +1,51 @@
+use super::{error_stage, InternalEvent};
+use metrics::counter;
+
+#[derive(Debug)]
+pub(crate) struct OpentelemetryEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for OpentelemetryEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received events.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OpentelemetryDecodeError {
+    pub error: prost::DecodeError,
+}
+
+impl InternalEvent for OpentelemetryDecodeError {
+    fn emit_logs(&self) {
+        error!(message = "Could not decode request body.", error = ?self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OpentelemetryEventsSent {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for OpentelemetryEventsSent {
+    fn emit_logs(&self) {
+        trace!(message = "Sent events.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
@@ -0,0 +1,92 @@
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct PluginConnected<'a> {
+    pub plugin: &'a str,
+    pub version: &'a str,
+}
+
+impl<'a> InternalEvent for PluginConnected<'a> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Connected to plugin.",
+            plugin = %self.plugin,
+            version = %self.version,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PluginEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for PluginEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct PluginEventSent {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for PluginEventSent {
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct PluginEventDropped<'a> {
+    pub component: &'a str,
+}
+
+impl<'a> InternalEvent for PluginEventDropped<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event type not accepted by plugin component; dropping event.",
+            component = %self.component,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "type_not_accepted", "stage" => error_stage::PROCESSING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::ProcessingFailed,
+        }
+        .emit_metrics();
+    }
+}
+
+#[derive(Debug)]
+pub struct PluginRequestFailed {
+    pub error: tonic::Status,
+    /// The stage of the component the plugin provides.
+    pub stage: &'static str,
+}
+
+impl InternalEvent for PluginRequestFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Plugin request failed.",
+            code = ?self.error.code(),
+            error = %self.error.message(),
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("request_errors_total", 1, "stage" => self.stage);
+    }
+}
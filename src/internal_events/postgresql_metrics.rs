@@ -1,4 +1,4 @@
-use crate::internal_events::InternalEvent;
+use crate::internal_events::{error_stage, InternalEvent};
 use metrics::{counter, histogram};
 use std::time::Instant;
 
@@ -35,6 +35,6 @@ impl<'a> InternalEvent for PostgresqlMetricsCollectFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("request_errors_total", 1);
+        counter!("request_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 #[cfg(feature = "sources-prometheus")]
 use crate::sources::prometheus::parser::ParserError;
 use hyper::StatusCode;
@@ -61,7 +61,30 @@ impl<'a> InternalEvent for PrometheusParseError<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
+    }
+}
+
+#[cfg(feature = "sources-prometheus")]
+#[derive(Debug)]
+pub struct PrometheusTargetFileError<'a> {
+    pub error: String,
+    pub path: &'a std::path::Path,
+}
+
+#[cfg(feature = "sources-prometheus")]
+impl<'a> InternalEvent for PrometheusTargetFileError<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed loading targets file, keeping its previous targets.",
+            path = ?self.path,
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("target_file_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -93,7 +116,7 @@ impl InternalEvent for PrometheusHttpError {
     }
 
     fn emit_metrics(&self) {
-        counter!("http_request_errors_total", 1);
+        counter!("http_request_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -108,7 +131,7 @@ impl InternalEvent for PrometheusRemoteWriteParseError {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -135,7 +158,7 @@ impl InternalEvent for PrometheusNoNameError {
     }
 
     fn emit_metrics(&self) {
-        counter!("parse_errors_total", 1);
+        counter!("parse_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -174,6 +197,6 @@ impl InternalEvent for PrometheusTemplateRenderingError {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "render_error", "stage" => error_stage::PROCESSING);
     }
 }
@@ -1,4 +1,5 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
+use crate::event::finalization::BatchStatus;
 use metrics::counter;
 
 #[derive(Debug)]
@@ -16,6 +17,86 @@ impl<'a> InternalEvent for PulsarEncodeEventFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("encode_errors_total", 1);
+        counter!("encode_errors_total", 1, "stage" => error_stage::SENDING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::EncodingFailed,
+        }
+        .emit_metrics();
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for PulsarEventReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.", internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarReadFailed<'a> {
+    pub error: &'a str,
+}
+
+impl InternalEvent for PulsarReadFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to read message.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarAcknowledgementFailed<'a> {
+    pub error: &'a str,
+}
+
+impl InternalEvent for PulsarAcknowledgementFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_acknowledgements_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarEventNotDelivered<'a> {
+    pub topic: &'a str,
+    pub status: BatchStatus,
+}
+
+impl InternalEvent for PulsarEventNotDelivered<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Events of message weren't delivered; negatively acknowledging it for redelivery.",
+            topic = %self.topic,
+            status = ?self.status,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_negative_acknowledgements_total", 1);
     }
 }
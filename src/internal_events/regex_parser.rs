@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -16,7 +16,7 @@ impl InternalEvent for RegexParserFailedMatch<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "failed_match");
+        counter!("processing_errors_total", 1, "error_type" => "failed_match", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -31,7 +31,7 @@ impl InternalEvent for RegexParserMissingField<'_> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "missing_field");
+        counter!("processing_errors_total", 1, "error_type" => "missing_field", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -50,7 +50,7 @@ impl<'a> InternalEvent for RegexParserTargetExists<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "target_field_exists");
+        counter!("processing_errors_total", 1, "error_type" => "target_field_exists", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -71,6 +71,6 @@ impl<'a> InternalEvent for RegexParserConversionFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "type_conversion_failed");
+        counter!("processing_errors_total", 1, "error_type" => "type_conversion_failed", "stage" => error_stage::PROCESSING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -26,7 +26,45 @@ impl InternalEvent for RemapMappingError {
 
     fn emit_metrics(&self) {
         counter!("processing_errors_total", 1,
+                 "stage" => error_stage::PROCESSING,
                  "error_type" => "failed_mapping");
+
+        if self.event_dropped {
+            EventsDiscarded {
+                count: 1,
+                reason: DiscardReason::ProcessingFailed,
+            }
+            .emit_metrics();
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct RemapMappingAbort {
+    /// If set to true, the remap transform has dropped the event after an
+    /// aborted mapping.
+    pub event_dropped: bool,
+}
+
+impl InternalEvent for RemapMappingAbort {
+    fn emit_logs(&self) {
+        let message = if self.event_dropped {
+            "Event mapping aborted; discarding event."
+        } else {
+            "Event mapping aborted."
+        };
+
+        debug!(message, internal_log_rate_secs = 30)
+    }
+
+    fn emit_metrics(&self) {
+        if self.event_dropped {
+            EventsDiscarded {
+                count: 1,
+                reason: DiscardReason::Filtered,
+            }
+            .emit_metrics();
+        }
     }
 }
 
@@ -42,6 +80,6 @@ impl InternalEvent for RemapConditionExecutionError {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", 1, "error_type" => "failed_condition", "stage" => error_stage::PROCESSING);
     }
 }
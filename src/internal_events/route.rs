@@ -1,11 +1,14 @@
-use super::InternalEvent;
-use metrics::counter;
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
 
 #[derive(Debug)]
 pub struct RouteEventDiscarded;
 
 impl InternalEvent for RouteEventDiscarded {
     fn emit_metrics(&self) {
-        counter!("events_discarded_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::Filtered,
+        }
+        .emit_metrics();
     }
 }
@@ -1,11 +1,14 @@
-use super::InternalEvent;
-use metrics::counter;
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
 
 #[derive(Debug)]
 pub struct SampleEventDiscarded;
 
 impl InternalEvent for SampleEventDiscarded {
     fn emit_metrics(&self) {
-        counter!("events_discarded_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::Sampled,
+        }
+        .emit_metrics();
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
 use crate::event::metric::Metric;
 use metrics::counter;
 
@@ -21,7 +21,13 @@ impl<'a> InternalEvent for SematextMetricsInvalidMetricReceived<'a> {
         counter!(
             "processing_errors_total", 1,
             "error_type" => "invalid_metric",
+            "stage" => error_stage::SENDING,
         );
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::ProcessingFailed,
+        }
+        .emit_metrics();
     }
 }
 
@@ -40,6 +46,11 @@ impl InternalEvent for SematextMetricsEncodeEventFailed {
     }
 
     fn emit_metrics(&self) {
-        counter!("encode_errors_total", 1);
+        counter!("encode_errors_total", 1, "stage" => error_stage::SENDING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::EncodingFailed,
+        }
+        .emit_metrics();
     }
 }
@@ -0,0 +1,17 @@
+use super::InternalEvent;
+use metrics::histogram;
+use std::time::Duration;
+
+/// A request of a batching sink finished, successfully or not.
+#[derive(Debug)]
+pub struct SinkRequestCompleted {
+    pub batch_size: usize,
+    pub duration: Duration,
+}
+
+impl InternalEvent for SinkRequestCompleted {
+    fn emit_metrics(&self) {
+        histogram!("request_duration_nanoseconds", self.duration);
+        histogram!("batch_size_events", self.batch_size as f64);
+    }
+}
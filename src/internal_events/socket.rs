@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug, Clone, Copy)]
@@ -65,6 +65,6 @@ impl InternalEvent for SocketReceiveError {
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_errors_total", 1, "mode" => self.mode.as_str());
+        counter!("connection_errors_total", 1, "mode" => self.mode.as_str(), "stage" => error_stage::RECEIVING);
     }
 }
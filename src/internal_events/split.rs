@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -16,7 +16,7 @@ impl<'a> InternalEvent for SplitFieldMissing<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "field_missing");
+        counter!("processing_errors_total", 1, "error_type" => "field_missing", "stage" => error_stage::PROCESSING);
     }
 }
 
@@ -37,6 +37,6 @@ impl<'a> InternalEvent for SplitConvertFailed<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "convert_failed");
+        counter!("processing_errors_total", 1, "error_type" => "convert_failed", "stage" => error_stage::PROCESSING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 use serde_json::Error;
 
@@ -32,7 +32,7 @@ impl InternalEvent for SplunkEventEncodeError {
     }
 
     fn emit_metrics(&self) {
-        counter!("encode_errors_total", 1);
+        counter!("encode_errors_total", 1, "stage" => error_stage::SENDING);
     }
 }
 
@@ -60,7 +60,7 @@ impl<'a> InternalEvent for SplunkMissingKeys<'a> {
 #[cfg(feature = "sources-splunk_hec")]
 mod source {
     use super::InternalEvent;
-    use crate::sources::splunk_hec::ApiError;
+    use crate::{event::finalization::BatchStatus, sources::splunk_hec::ApiError};
     use metrics::counter;
 
     #[derive(Debug)]
@@ -127,7 +127,30 @@ mod source {
         }
 
         fn emit_metrics(&self) {
-            counter!("http_request_errors_total", 1);
+            counter!("http_request_errors_total", 1, "stage" => error_stage::RECEIVING);
+        }
+    }
+
+    #[derive(Debug)]
+    pub(crate) struct SplunkHECEventsNotDelivered<'a> {
+        pub channel: &'a str,
+        pub ack_id: u64,
+        pub status: BatchStatus,
+    }
+
+    impl InternalEvent for SplunkHECEventsNotDelivered<'_> {
+        fn emit_logs(&self) {
+            warn!(
+                message = "Events of request weren't delivered; not acknowledging them.",
+                channel = %self.channel,
+                ack_id = %self.ack_id,
+                status = ?self.status,
+                internal_log_rate_secs = 10,
+            );
+        }
+
+        fn emit_metrics(&self) {
+            counter!("requests_not_acknowledged_total", 1);
         }
     }
 }
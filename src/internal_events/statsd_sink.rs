@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, DiscardReason, EventsDiscarded, InternalEvent};
 use crate::event::metric::{MetricKind, MetricValue};
 use metrics::counter;
 
@@ -19,6 +19,11 @@ impl<'a> InternalEvent for StatsdInvalidMetricReceived<'a> {
     }
 
     fn emit_metrics(&self) {
-        counter!("processing_errors_total", 1, "error_type" => "invalid_metric");
+        counter!("processing_errors_total", 1, "error_type" => "invalid_metric", "stage" => error_stage::SENDING);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::ProcessingFailed,
+        }
+        .emit_metrics();
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -70,6 +70,6 @@ impl<T: std::fmt::Debug + std::fmt::Display> InternalEvent for StatsdSocketError
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_errors_total", 1);
+        counter!("connection_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -31,3 +31,43 @@ impl InternalEvent for StdinReadFailed {
         counter!("stdin_reads_failed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct StdinFrameTooLong {
+    pub length: usize,
+    pub max_length: usize,
+}
+
+impl InternalEvent for StdinFrameTooLong {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Discarding frame larger than max_length.",
+            length = %self.length,
+            max_length = %self.max_length,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "frame_too_long", "stage" => error_stage::RECEIVING);
+    }
+}
+
+#[derive(Debug)]
+pub struct StdinDecodeFailed {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for StdinDecodeFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Unable to decode event from JSON; dropping it.",
+            error = %self.error,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1, "error_type" => "parse_failed", "stage" => error_stage::RECEIVING);
+    }
+}
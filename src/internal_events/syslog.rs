@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -28,7 +28,7 @@ impl InternalEvent for SyslogUdpReadError {
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_read_errors_total", 1, "mode" => "udp");
+        counter!("connection_read_errors_total", 1, "mode" => "udp", "stage" => error_stage::RECEIVING);
     }
 }
 
@@ -43,6 +43,6 @@ impl InternalEvent for SyslogUdpUtf8Error {
     }
 
     fn emit_metrics(&self) {
-        counter!("utf8_convert_errors_total", 1, "mode" => "udp");
+        counter!("utf8_convert_errors_total", 1, "mode" => "udp", "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
 use metrics::counter;
 
 pub(crate) struct TagCardinalityLimitRejectingEvent<'a> {
@@ -18,6 +18,11 @@ impl<'a> InternalEvent for TagCardinalityLimitRejectingEvent<'a> {
 
     fn emit_metrics(&self) {
         counter!("tag_value_limit_exceeded_total", 1);
+        EventsDiscarded {
+            count: 1,
+            reason: DiscardReason::LimitExceeded,
+        }
+        .emit_metrics();
     }
 }
 
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -62,7 +62,7 @@ impl InternalEvent for TcpSocketConnectionError {
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_errors_total", 1, "mode" => "tcp");
+        counter!("connection_errors_total", 1, "mode" => "tcp", "stage" => error_stage::SENDING);
     }
 }
 
@@ -77,6 +77,6 @@ impl InternalEvent for TcpSocketError {
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_errors_total", 1, "mode" => "tcp");
+        counter!("connection_errors_total", 1, "mode" => "tcp", "stage" => error_stage::SENDING);
     }
 }
@@ -0,0 +1,67 @@
+use super::{DiscardReason, EventsDiscarded, InternalEvent};
+use metrics::{counter, gauge};
+
+#[derive(Debug)]
+pub(crate) struct TenantQuotaEventProcessed<'a> {
+    pub tenant: &'a str,
+    pub byte_size: u64,
+    pub over_quota: bool,
+    pub window_events: u64,
+    pub window_bytes: u64,
+}
+
+impl<'a> InternalEvent for TenantQuotaEventProcessed<'a> {
+    fn emit_metrics(&self) {
+        let status = if self.over_quota {
+            "over_quota"
+        } else {
+            "within_quota"
+        };
+
+        counter!(
+            "tenant_events_total", 1,
+            "tenant" => self.tenant.to_owned(),
+            "status" => status,
+        );
+        counter!(
+            "tenant_bytes_total", self.byte_size,
+            "tenant" => self.tenant.to_owned(),
+            "status" => status,
+        );
+        gauge!(
+            "tenant_quota_window_events", self.window_events as f64,
+            "tenant" => self.tenant.to_owned(),
+        );
+        gauge!(
+            "tenant_quota_window_bytes", self.window_bytes as f64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TenantQuotaExceeded<'a> {
+    pub tenant: &'a str,
+    pub event_dropped: bool,
+}
+
+impl<'a> InternalEvent for TenantQuotaExceeded<'a> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Event over the quota of its tenant.",
+            tenant = self.tenant,
+            event_dropped = self.event_dropped,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        if self.event_dropped {
+            EventsDiscarded {
+                count: 1,
+                reason: DiscardReason::LimitExceeded,
+            }
+            .emit_metrics();
+        }
+    }
+}
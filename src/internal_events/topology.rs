@@ -1,5 +1,5 @@
 use super::InternalEvent;
-use metrics::counter;
+use metrics::{counter, gauge};
 
 #[derive(Debug)]
 pub struct EventProcessed;
@@ -9,3 +9,52 @@ impl InternalEvent for EventProcessed {
         counter!("processed_events_total", 1);
     }
 }
+
+/// Events passed from the current component to the downstream component
+/// `to`.
+#[derive(Debug)]
+pub struct EventsSent<'a> {
+    pub to: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for EventsSent<'_> {
+    fn emit_metrics(&self) {
+        counter!("sent_events_total", self.count as u64, "to" => self.to.to_owned());
+        counter!("sent_event_bytes_total", self.byte_size as u64, "to" => self.to.to_owned());
+    }
+}
+
+/// A sink healthcheck finished, either passing or failing.
+#[derive(Debug)]
+pub struct HealthcheckCompleted {
+    pub healthy: bool,
+}
+
+impl InternalEvent for HealthcheckCompleted {
+    fn emit_metrics(&self) {
+        gauge!("healthcheck_healthy", if self.healthy { 1.0 } else { 0.0 });
+    }
+}
+
+/// A component's task failed, e.g. because a source couldn't bind its address.
+#[derive(Debug)]
+pub struct ComponentFailed;
+
+impl InternalEvent for ComponentFailed {
+    fn emit_metrics(&self) {
+        counter!("component_failures_total", 1);
+        gauge!("component_failed", 1.0);
+    }
+}
+
+/// A component's task was spawned, clearing a failure of its previous task.
+#[derive(Debug)]
+pub struct ComponentStarted;
+
+impl InternalEvent for ComponentStarted {
+    fn emit_metrics(&self) {
+        gauge!("component_failed", 0.0);
+    }
+}
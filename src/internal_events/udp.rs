@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 
 #[derive(Debug)]
@@ -43,7 +43,7 @@ impl InternalEvent for UdpSocketError {
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_errors_total", 1, "mode" => "udp");
+        counter!("connection_errors_total", 1, "mode" => "udp", "stage" => error_stage::SENDING);
     }
 }
 
@@ -65,6 +65,6 @@ impl InternalEvent for UdpSendIncomplete {
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_send_errors_total", 1, "mode" => "udp");
+        counter!("connection_send_errors_total", 1, "mode" => "udp", "stage" => error_stage::SENDING);
     }
 }
@@ -43,6 +43,8 @@ where
 pub struct UnixSocketError<'a, E> {
     pub error: E,
     pub path: &'a std::path::Path,
+    /// Sources fail while receiving, and sinks while sending.
+    pub stage: &'static str,
 }
 
 impl<E> InternalEvent for UnixSocketError<'_, E>
@@ -58,6 +60,6 @@ where
     }
 
     fn emit_metrics(&self) {
-        counter!("connection_errors_total", 1, "mode" => "unix");
+        counter!("connection_errors_total", 1, "mode" => "unix", "stage" => self.stage);
     }
 }
@@ -1,4 +1,4 @@
-use super::InternalEvent;
+use super::{error_stage, InternalEvent};
 use metrics::counter;
 use prost::DecodeError;
 
@@ -29,6 +29,6 @@ impl InternalEvent for VectorProtoDecodeError {
     }
 
     fn emit_metrics(&self) {
-        counter!("protobuf_decode_errors_total", 1);
+        counter!("protobuf_decode_errors_total", 1, "stage" => error_stage::RECEIVING);
     }
 }
@@ -1,5 +1,8 @@
 use super::State;
-use crate::{emit, internal_events::InternalEvent};
+use crate::{
+    emit,
+    internal_events::{error_stage, InternalEvent},
+};
 use metrics::counter;
 use std::time::{Duration, Instant};
 use vector_wasm::Role;
@@ -75,6 +78,8 @@ impl InternalEvent for EventProcessingProgress {
                 "component_role" => self.role.as_const_str(),
             ),
             State::Errored => counter!("processing_errors_total", 1,
+                "error_type" => "event_processing_failed",
+                "stage" => error_stage::PROCESSING,
                 "component_role" => self.role.as_const_str(),
             ),
             _ => (),
@@ -1,13 +1,70 @@
-use crate::tls::TlsOptions;
-use rdkafka::ClientConfig;
+use crate::{
+    http::HttpClient,
+    internal_events::{KafkaStatisticsReceived, KafkaTokenRefreshFailed},
+    rusoto::{AWSAuthentication, AwsCredentialsProvider},
+    tls::TlsOptions,
+};
+use futures::future::{abortable, AbortHandle};
+use headers::{Authorization, HeaderMapExt};
+use http::{Request, StatusCode};
+use hyper::Body;
+use rdkafka::{
+    bindings as rdsys,
+    consumer::{BaseConsumer, Consumer, ConsumerContext, StreamConsumer},
+    producer::{FutureProducer, Producer},
+    types::{RDKafka, RDKafkaRespErr},
+    ClientConfig, ClientContext, Statistics,
+};
+use rusoto_core::Region;
+use rusoto_credential::ProvideAwsCredentials;
+use rusoto_signature::SignedRequest;
 use serde::{Deserialize, Serialize};
-use snafu::Snafu;
-use std::path::PathBuf;
+use snafu::{OptionExt, Snafu};
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    path::PathBuf,
+    ptr,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::Span;
+use tracing_futures::Instrument;
+
+/// How often librdkafka reports the statistics of a client. This can be
+/// changed, or disabled with `0`, with the `statistics.interval.ms` option of
+/// `librdkafka_options`.
+pub(crate) const STATISTICS_INTERVAL_MS: &str = "1000";
+
+/// The SASL mechanism whose tokens are refreshed by Vector.
+const OAUTHBEARER: &str = "OAUTHBEARER";
+
+/// Authenticates with AWS MSK using IAM credentials, which is the
+/// `OAUTHBEARER` mechanism with tokens signed by Vector.
+const AWS_MSK_IAM: &str = "AWS_MSK_IAM";
+
+/// How long the tokens signed for AWS MSK are valid for.
+const MSK_IAM_TOKEN_LIFETIME: Duration = Duration::from_secs(900);
+
+/// Lifetime of the tokens of token endpoints that don't tell it.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// Bounds how often tokens are refreshed, or retried when refreshing failed.
+const MIN_TOKEN_REFRESH_DELAY: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Snafu)]
 enum KafkaError {
     #[snafu(display("invalid path: {:?}", path))]
     InvalidPath { path: PathBuf },
+    #[snafu(display("SASL mechanism {} requires the `sasl.{}` options", mechanism, option))]
+    MissingSaslOptions {
+        mechanism: &'static str,
+        option: &'static str,
+    },
+    #[snafu(display("token endpoint responded with {}", status))]
+    TokenRequestFailed { status: StatusCode },
+    #[snafu(display("could not set token: {}", reason))]
+    SetTokenFailed { reason: String },
 }
 
 #[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize)]
@@ -34,6 +91,28 @@ pub(crate) struct KafkaSaslConfig {
     pub username: Option<String>,
     pub password: Option<String>,
     pub mechanism: Option<String>,
+    pub oauthbearer: Option<KafkaOauthbearerConfig>,
+    pub aws_msk_iam: Option<KafkaMskIamConfig>,
+}
+
+/// Options of the `OAUTHBEARER` mechanism. Tokens are requested from an OAuth
+/// 2.0 token endpoint, with the client credentials grant.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct KafkaOauthbearerConfig {
+    pub token_endpoint: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scope: Option<String>,
+}
+
+/// Options of the `AWS_MSK_IAM` mechanism.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct KafkaMskIamConfig {
+    pub region: String,
+    #[serde(default)]
+    pub auth: AWSAuthentication,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -65,6 +144,10 @@ impl KafkaAuthConfig {
                 client.set("sasl.password", password);
             }
             if let Some(mechanism) = &sasl.mechanism {
+                let mechanism = match mechanism.as_str() {
+                    AWS_MSK_IAM => OAUTHBEARER,
+                    mechanism => mechanism,
+                };
                 client.set("sasl.mechanism", mechanism);
             }
         }
@@ -87,9 +170,376 @@ impl KafkaAuthConfig {
 
         Ok(())
     }
+
+    /// Returns the provider of the tokens of the configured SASL mechanism,
+    /// if Vector refreshes them. The `OAUTHBEARER` mechanism without
+    /// `oauthbearer` options is left to librdkafka, e.g. for its unsecured
+    /// tokens.
+    pub(crate) fn token_provider(&self) -> crate::Result<Option<KafkaTokenProvider>> {
+        let sasl = match &self.sasl {
+            Some(sasl) if sasl.enabled.unwrap_or(false) => sasl,
+            _ => return Ok(None),
+        };
+
+        match sasl.mechanism.as_deref() {
+            Some(AWS_MSK_IAM) => {
+                let config = sasl.aws_msk_iam.as_ref().context(MissingSaslOptions {
+                    mechanism: AWS_MSK_IAM,
+                    option: "aws_msk_iam",
+                })?;
+                let region = config.region.parse::<Region>()?;
+                let credentials = config.auth.build(&region, None)?;
+                Ok(Some(KafkaTokenProvider::AwsMskIam {
+                    region,
+                    credentials,
+                }))
+            }
+            Some(OAUTHBEARER) => match &sasl.oauthbearer {
+                Some(config) => Ok(Some(KafkaTokenProvider::Oauth {
+                    client: HttpClient::new(None)?,
+                    config: config.clone(),
+                })),
+                None => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
+/// A token of the `OAUTHBEARER` mechanism.
+#[derive(Debug)]
+struct KafkaToken {
+    value: String,
+    principal: String,
+    /// When the token expires, in milliseconds since the Unix epoch.
+    expires_ms: i64,
+}
+
+/// Provides the tokens of the SASL mechanisms whose tokens expire, and so
+/// have to be refreshed for as long as the client is running.
+pub(crate) enum KafkaTokenProvider {
+    Oauth {
+        client: HttpClient,
+        config: KafkaOauthbearerConfig,
+    },
+    AwsMskIam {
+        region: Region,
+        credentials: AwsCredentialsProvider,
+    },
+}
+
+impl KafkaTokenProvider {
+    /// Sets a new token on `client`, and returns when it expires, in
+    /// milliseconds since the Unix epoch.
+    pub(crate) async fn refresh(&self, client: &impl KafkaClient) -> crate::Result<i64> {
+        let token = match self {
+            Self::Oauth { client, config } => oauth_token(client, config).await?,
+            Self::AwsMskIam {
+                region,
+                credentials,
+            } => msk_iam_token(region, credentials).await?,
+        };
+        set_token(client, &token)?;
+        Ok(token.expires_ms)
+    }
+}
+
+#[derive(Deserialize)]
+struct OauthTokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+async fn oauth_token(
+    client: &HttpClient,
+    config: &KafkaOauthbearerConfig,
+) -> crate::Result<KafkaToken> {
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    form.append_pair("grant_type", "client_credentials");
+    if let Some(scope) = &config.scope {
+        form.append_pair("scope", scope);
+    }
+
+    let mut request = Request::post(&config.token_endpoint)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(Body::from(form.finish()))?;
+    request.headers_mut().typed_insert(Authorization::basic(
+        &config.client_id,
+        &config.client_secret,
+    ));
+
+    let response = client.send(request).await?;
+    let (parts, body) = response.into_parts();
+    if !parts.status.is_success() {
+        return Err(KafkaError::TokenRequestFailed {
+            status: parts.status,
+        }
+        .into());
+    }
+    let body = hyper::body::to_bytes(body).await?;
+    let response = serde_json::from_slice::<OauthTokenResponse>(&body)?;
+
+    let lifetime = response
+        .expires_in
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+    Ok(KafkaToken {
+        value: response.access_token,
+        principal: config.client_id.clone(),
+        expires_ms: now_ms() + lifetime.as_millis() as i64,
+    })
+}
+
+/// Returns a token for AWS MSK, which is a URL presigned for the
+/// `kafka-cluster:Connect` action, encoded as unpadded URL-safe base64.
+async fn msk_iam_token(
+    region: &Region,
+    credentials: &AwsCredentialsProvider,
+) -> crate::Result<KafkaToken> {
+    let credentials = credentials.credentials().await?;
+
+    let mut request = SignedRequest::new("GET", "kafka-cluster", region, "/");
+    request.set_hostname(Some(format!("kafka.{}.amazonaws.com", region.name())));
+    request.add_param("Action", "kafka-cluster:Connect");
+    let url = request.generate_presigned_url(&credentials, &MSK_IAM_TOKEN_LIFETIME, false);
+    // Like the signers of AWS, the user agent isn't signed.
+    let url = format!("{}&User-Agent=vector%2F{}", url, crate::get_version());
+
+    Ok(KafkaToken {
+        value: base64::encode_config(url, base64::URL_SAFE_NO_PAD),
+        principal: credentials.aws_access_key_id().to_owned(),
+        expires_ms: now_ms() + MSK_IAM_TOKEN_LIFETIME.as_millis() as i64,
+    })
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// A Kafka client, whose token can be set.
+pub(crate) trait KafkaClient: Send + Sync + 'static {
+    fn native_ptr(&self) -> *mut RDKafka;
+}
+
+impl<C: ClientContext + 'static> KafkaClient for FutureProducer<C> {
+    fn native_ptr(&self) -> *mut RDKafka {
+        self.client().native_ptr()
+    }
+}
+
+impl<C: ConsumerContext + 'static> KafkaClient for StreamConsumer<C> {
+    fn native_ptr(&self) -> *mut RDKafka {
+        self.client().native_ptr()
+    }
+}
+
+impl<C: ConsumerContext + 'static> KafkaClient for BaseConsumer<C> {
+    fn native_ptr(&self) -> *mut RDKafka {
+        self.client().native_ptr()
+    }
+}
+
+impl<T: KafkaClient> KafkaClient for Arc<T> {
+    fn native_ptr(&self) -> *mut RDKafka {
+        (**self).native_ptr()
+    }
 }
 
+fn set_token(client: &impl KafkaClient, token: &KafkaToken) -> crate::Result<()> {
+    let value = CString::new(token.value.as_str())?;
+    let principal = CString::new(token.principal.as_str())?;
+    let mut errstr = [0 as c_char; 512];
+
+    let err = unsafe {
+        rdsys::rd_kafka_oauthbearer_set_token(
+            client.native_ptr(),
+            value.as_ptr(),
+            token.expires_ms,
+            principal.as_ptr(),
+            ptr::null_mut(),
+            0,
+            errstr.as_mut_ptr(),
+            errstr.len(),
+        )
+    };
+
+    if err == RDKafkaRespErr::RD_KAFKA_RESP_ERR_NO_ERROR {
+        Ok(())
+    } else {
+        let reason = unsafe { CStr::from_ptr(errstr.as_ptr()) };
+        Err(KafkaError::SetTokenFailed {
+            reason: reason.to_string_lossy().into_owned(),
+        }
+        .into())
+    }
+}
+
+/// Tells librdkafka that the token couldn't be refreshed, which it reports
+/// when connecting.
+fn set_token_failure(client: &impl KafkaClient, error: &str) {
+    if let Ok(error) = CString::new(error) {
+        unsafe {
+            rdsys::rd_kafka_oauthbearer_set_token_failure(client.native_ptr(), error.as_ptr())
+        };
+    }
+}
+
+/// Refreshing a token starts once 80% of its remaining lifetime has passed,
+/// like librdkafka does.
+fn refresh_delay(expires_ms: i64, now_ms: i64) -> Duration {
+    let remaining = (expires_ms - now_ms).max(0) as u64;
+    Duration::from_millis(remaining / 5 * 4).max(MIN_TOKEN_REFRESH_DELAY)
+}
+
+/// Keeps the token of a Kafka client fresh until dropped, refreshing it
+/// ahead of its expiry. Connections wait for the first token.
+pub(crate) struct KafkaTokenRefresh {
+    abort: AbortHandle,
+}
+
+impl KafkaTokenRefresh {
+    pub(crate) fn spawn(provider: KafkaTokenProvider, client: impl KafkaClient) -> Self {
+        let (task, abort) = abortable(
+            async move {
+                loop {
+                    let delay = match provider.refresh(&client).await {
+                        Ok(expires_ms) => refresh_delay(expires_ms, now_ms()),
+                        Err(error) => {
+                            emit!(KafkaTokenRefreshFailed { error: &error });
+                            set_token_failure(&client, &error.to_string());
+                            MIN_TOKEN_REFRESH_DELAY
+                        }
+                    };
+                    tokio::time::delay_for(delay).await;
+                }
+            }
+            .in_current_span(),
+        );
+        tokio::spawn(task);
+
+        Self { abort }
+    }
+}
+
+impl Drop for KafkaTokenRefresh {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+/// The context of the Kafka clients of a component, which reports the
+/// statistics of librdkafka as internal metrics.
+#[derive(Clone, Default)]
+pub(crate) struct KafkaStatisticsContext {
+    state: Arc<Mutex<StatisticsState>>,
+}
+
+struct StatisticsState {
+    /// Statistics are reported from a thread of librdkafka, outside of the
+    /// span of the component, which is entered to tag the metrics.
+    span: Span,
+    previous: Option<Statistics>,
+}
+
+impl Default for StatisticsState {
+    fn default() -> Self {
+        Self {
+            span: Span::none(),
+            previous: None,
+        }
+    }
+}
+
+impl KafkaStatisticsContext {
+    /// Tag the metrics reported from now on with the current span. Called
+    /// from the task of the component.
+    pub(crate) fn use_current_span(&self) {
+        self.state.lock().expect("poisoned lock").span = Span::current();
+    }
+}
+
+impl ClientContext for KafkaStatisticsContext {
+    fn stats(&self, statistics: Statistics) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        let span = state.span.clone();
+        let _enter = span.enter();
+
+        emit!(KafkaStatisticsReceived {
+            statistics: &statistics,
+            previous: state.previous.as_ref(),
+        });
+
+        state.previous = Some(statistics);
+    }
+}
+
+impl ConsumerContext for KafkaStatisticsContext {}
+
 fn pathbuf_to_string(path: &PathBuf) -> crate::Result<&str> {
     path.to_str()
         .ok_or_else(|| KafkaError::InvalidPath { path: path.into() }.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_refresh_delay() {
+        assert_eq!(refresh_delay(100_000, 0), Duration::from_secs(80));
+        assert_eq!(refresh_delay(0, 100_000), MIN_TOKEN_REFRESH_DELAY);
+    }
+
+    #[test]
+    fn msk_iam_uses_oauthbearer() {
+        let config: KafkaAuthConfig = toml::from_str(
+            r#"
+            sasl.enabled = true
+            sasl.mechanism = "AWS_MSK_IAM"
+            sasl.aws_msk_iam.region = "us-east-1"
+            sasl.aws_msk_iam.auth.access_key_id = "key"
+            sasl.aws_msk_iam.auth.secret_access_key = "secret"
+            "#,
+        )
+        .unwrap();
+
+        let mut client = ClientConfig::new();
+        config.apply(&mut client).unwrap();
+        assert_eq!(client.get("sasl.mechanism"), Some("OAUTHBEARER"));
+        assert!(matches!(
+            config.token_provider().unwrap(),
+            Some(KafkaTokenProvider::AwsMskIam { .. })
+        ));
+    }
+
+    #[test]
+    fn msk_iam_requires_options() {
+        let config: KafkaAuthConfig = toml::from_str(
+            r#"
+            sasl.enabled = true
+            sasl.mechanism = "AWS_MSK_IAM"
+            "#,
+        )
+        .unwrap();
+        assert!(config.token_provider().is_err());
+    }
+
+    #[tokio::test]
+    async fn msk_iam_token_is_presigned_url() {
+        let credentials = AwsCredentialsProvider::new_minimal("key", "secret");
+        let token = msk_iam_token(&Region::UsEast1, &credentials).await.unwrap();
+
+        let url = base64::decode_config(&token.value, base64::URL_SAFE_NO_PAD).unwrap();
+        let url = url::Url::parse(std::str::from_utf8(&url).unwrap()).unwrap();
+        assert_eq!(url.host_str(), Some("kafka.us-east-1.amazonaws.com"));
+
+        let params = url.query_pairs().into_owned().collect::<Vec<_>>();
+        assert!(params.contains(&("Action".into(), "kafka-cluster:Connect".into())));
+        assert!(params.contains(&("X-Amz-Expires".into(), "900".into())));
+        assert!(params.iter().any(|(name, _)| name == "X-Amz-Signature"));
+        assert_eq!(token.principal, "key");
+    }
+}
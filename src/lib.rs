@@ -20,12 +20,13 @@ extern crate pest_derive;
 #[cfg(feature = "vrl-cli")]
 extern crate remap_cli;
 
-#[cfg(feature = "jemallocator")]
+#[cfg(any(feature = "allocator-jemalloc", feature = "allocator-mimalloc"))]
 #[global_allocator]
-static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+static ALLOC: allocator::SelectedAllocator = allocator::SelectedAllocator;
 
 #[macro_use]
 pub mod config;
+pub mod allocator;
 pub mod buffers;
 pub mod cli;
 pub mod conditions;
@@ -37,23 +38,40 @@ pub mod generate;
 pub mod wasm;
 #[macro_use]
 pub mod internal_events;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+pub mod amqp;
 #[cfg(feature = "api")]
 pub mod api;
 pub mod app;
 pub mod async_read;
+#[cfg(feature = "benchmark-cli")]
+pub mod benchmark;
 pub mod encoding_transcode;
 pub mod heartbeat;
 pub mod http;
-#[cfg(feature = "rdkafka")]
+#[cfg(all(
+    feature = "rdkafka",
+    any(feature = "sources-kafka", feature = "sinks-kafka")
+))]
 pub mod kafka;
 pub mod kubernetes;
 pub mod line_agg;
 pub mod list;
 pub mod mapping;
 pub mod metrics;
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
+pub mod nats;
+#[cfg(any(feature = "sinks-opentelemetry", feature = "sources-opentelemetry"))]
+pub(crate) mod opentelemetry;
 pub(crate) mod pipeline;
+#[cfg(feature = "tonic")]
+pub mod plugin;
 #[cfg(any(feature = "sinks-prometheus", feature = "sources-prometheus"))]
 pub(crate) mod prometheus;
+#[cfg(any(feature = "sources-pulsar", feature = "sinks-pulsar"))]
+pub mod pulsar;
+#[cfg(feature = "api-client")]
+pub mod remote;
 #[cfg(feature = "rusoto_core")]
 pub mod rusoto;
 pub mod serde;
@@ -64,6 +82,8 @@ pub mod sink;
 pub mod sinks;
 pub mod sources;
 pub mod stream;
+#[cfg(feature = "api-client")]
+pub mod tap;
 pub mod tcp;
 pub mod template;
 pub mod test_util;
@@ -0,0 +1,184 @@
+//! JetStream, the persistence layer of NATS, as used by the `nats` source and
+//! sink. JetStream is driven by its JSON API, over regular NATS requests.
+
+use nats::asynk::Connection;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use std::{io, time::Duration};
+
+/// Acknowledges a JetStream message, when published to its reply subject.
+pub const ACK: &[u8] = b"+ACK";
+
+/// Negatively acknowledges a JetStream message, so that it's redelivered.
+pub const NAK: &[u8] = b"-NAK";
+
+/// Requests to subjects without a stream are never answered, which this bounds.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+pub enum JetStreamError {
+    #[snafu(display("JetStream request failed: {}", source))]
+    Request { source: io::Error },
+    #[snafu(display(
+        "JetStream didn't respond; is there a stream for subject {:?}?",
+        subject
+    ))]
+    Timeout { subject: String },
+    #[snafu(display("invalid JetStream response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("JetStream error {}: {}", code, description))]
+    Api { code: u16, description: String },
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    code: u16,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: Option<ApiError>,
+}
+
+/// The acknowledgement of a message published to a stream.
+#[derive(Debug, Deserialize)]
+pub struct PubAck {
+    pub stream: String,
+    pub seq: u64,
+}
+
+#[derive(Deserialize)]
+struct ConsumerInfo {
+    config: ConsumerConfig,
+}
+
+#[derive(Deserialize)]
+struct ConsumerConfig {
+    deliver_subject: Option<String>,
+}
+
+fn parse_response<T: DeserializeOwned>(data: &[u8]) -> Result<T, JetStreamError> {
+    if let Some(error) = serde_json::from_slice::<ErrorResponse>(data)
+        .context(InvalidResponse)?
+        .error
+    {
+        return Err(JetStreamError::Api {
+            code: error.code,
+            description: error.description,
+        });
+    }
+    serde_json::from_slice(data).context(InvalidResponse)
+}
+
+async fn request<T: DeserializeOwned>(
+    connection: &Connection,
+    subject: &str,
+    payload: impl AsRef<[u8]>,
+) -> Result<T, JetStreamError> {
+    let response = tokio::time::timeout(REQUEST_TIMEOUT, connection.request(subject, payload))
+        .await
+        .map_err(|_| JetStreamError::Timeout {
+            subject: subject.to_owned(),
+        })?
+        .context(Request)?;
+    parse_response(&response.data)
+}
+
+/// Publishes `payload` to the stream of `subject`, and waits for it to be
+/// stored.
+pub async fn publish(
+    connection: &Connection,
+    subject: &str,
+    payload: impl AsRef<[u8]>,
+) -> Result<PubAck, JetStreamError> {
+    request(connection, subject, payload).await
+}
+
+/// The subject the messages of a durable consumer created by Vector are
+/// delivered to.
+fn deliver_subject(stream: &str, durable_name: &str) -> String {
+    format!("_vector.deliver.{}.{}", stream, durable_name)
+}
+
+/// Creates the durable push consumer `durable_name` of `stream`, with
+/// explicit acknowledgements, unless it already exists. Returns the subject
+/// its messages are delivered to.
+pub async fn create_durable_consumer(
+    connection: &Connection,
+    stream: &str,
+    durable_name: &str,
+    filter_subject: &str,
+) -> Result<String, JetStreamError> {
+    let subject = format!(
+        "$JS.API.CONSUMER.DURABLE.CREATE.{}.{}",
+        stream, durable_name
+    );
+    let config = json!({
+        "stream_name": stream,
+        "config": {
+            "durable_name": durable_name,
+            "deliver_subject": deliver_subject(stream, durable_name),
+            "deliver_policy": "all",
+            "ack_policy": "explicit",
+            "filter_subject": filter_subject,
+        },
+    });
+
+    let info: ConsumerInfo = request(connection, &subject, config.to_string()).await?;
+    info.config.deliver_subject.ok_or(JetStreamError::Api {
+        code: 400,
+        description: format!("consumer {:?} isn't a push consumer", durable_name),
+    })
+}
+
+/// Creates the stream `name`, storing the messages published to `subject`.
+#[cfg(all(test, feature = "nats-integration-tests"))]
+pub(crate) async fn create_stream(
+    connection: &Connection,
+    name: &str,
+    subject: &str,
+) -> Result<(), JetStreamError> {
+    let config = json!({ "name": name, "subjects": [subject] });
+    request::<serde_json::Value>(
+        connection,
+        &format!("$JS.API.STREAM.CREATE.{}", name),
+        config.to_string(),
+    )
+    .await
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pub_ack() {
+        let ack: PubAck = parse_response(br#"{"stream":"logs","seq":42}"#).unwrap();
+        assert_eq!(ack.stream, "logs");
+        assert_eq!(ack.seq, 42);
+    }
+
+    #[test]
+    fn parses_api_error() {
+        let error = parse_response::<PubAck>(
+            br#"{"error":{"code":503,"description":"JetStream not enabled"}}"#,
+        )
+        .unwrap_err();
+        assert!(matches!(error, JetStreamError::Api { code: 503, .. }));
+    }
+
+    #[test]
+    fn parses_consumer_info() {
+        let info: ConsumerInfo = parse_response(
+            br#"{"stream_name":"logs","name":"vector","config":{"durable_name":"vector","deliver_subject":"_vector.deliver.logs.vector","ack_policy":"explicit"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            info.config.deliver_subject,
+            Some(deliver_subject("logs", "vector"))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! The OpenTelemetry protocol (OTLP), shared by the `opentelemetry` source
+//! and sink.
+
+use crate::event::Value;
+use std::collections::BTreeMap;
+
+use proto::common::v1::{any_value, AnyValue, ArrayValue, KeyValue, KeyValueList};
+
+/// The modules mirror the protobuf packages under `opentelemetry.proto`,
+/// which the generated code refers to with relative paths.
+pub mod proto {
+    pub mod common {
+        pub mod v1 {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/opentelemetry.proto.common.v1.rs"
+            ));
+        }
+    }
+
+    pub mod resource {
+        pub mod v1 {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/opentelemetry.proto.resource.v1.rs"
+            ));
+        }
+    }
+
+    pub mod logs {
+        pub mod v1 {
+            include!(concat!(env!("OUT_DIR"), "/opentelemetry.proto.logs.v1.rs"));
+        }
+    }
+
+    pub mod metrics {
+        pub mod v1 {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/opentelemetry.proto.metrics.v1.rs"
+            ));
+        }
+    }
+
+    pub mod collector {
+        pub mod logs {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.collector.logs.v1.rs"
+                ));
+            }
+        }
+
+        pub mod metrics {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.collector.metrics.v1.rs"
+                ));
+            }
+        }
+    }
+}
+
+pub fn decode_value(value: AnyValue) -> Value {
+    match value.value {
+        Some(any_value::Value::StringValue(value)) => Value::from(value),
+        Some(any_value::Value::BoolValue(value)) => Value::Boolean(value),
+        Some(any_value::Value::IntValue(value)) => Value::Integer(value),
+        Some(any_value::Value::DoubleValue(value)) => Value::Float(value),
+        Some(any_value::Value::BytesValue(value)) => Value::Bytes(value.into()),
+        Some(any_value::Value::ArrayValue(array)) => {
+            Value::Array(array.values.into_iter().map(decode_value).collect())
+        }
+        Some(any_value::Value::KvlistValue(list)) => Value::Map(decode_attributes(list.values)),
+        None => Value::Null,
+    }
+}
+
+pub fn decode_attributes(attributes: Vec<KeyValue>) -> BTreeMap<String, Value> {
+    attributes
+        .into_iter()
+        .map(|KeyValue { key, value }| (key, value.map_or(Value::Null, decode_value)))
+        .collect()
+}
+
+/// Vector's values have no string type, so bytes, which usually hold text,
+/// are encoded as strings if they are valid UTF-8.
+pub fn encode_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => Some(any_value::Value::StringValue(string)),
+            Err(error) => Some(any_value::Value::BytesValue(error.into_bytes())),
+        },
+        Value::Integer(value) => Some(any_value::Value::IntValue(value)),
+        Value::Float(value) => Some(any_value::Value::DoubleValue(value)),
+        Value::Boolean(value) => Some(any_value::Value::BoolValue(value)),
+        Value::Timestamp(timestamp) => Some(any_value::Value::StringValue(
+            timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+        )),
+        Value::Map(map) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: encode_attributes(map),
+        })),
+        Value::Array(values) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: values.into_iter().map(encode_value).collect(),
+        })),
+        Value::Null => None,
+    };
+    AnyValue { value }
+}
+
+pub fn encode_attributes(attributes: impl IntoIterator<Item = (String, Value)>) -> Vec<KeyValue> {
+    attributes
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(encode_value(value)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn converts_any_values() {
+        let value = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![
+                    KeyValue {
+                        key: "list".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::ArrayValue(ArrayValue {
+                                values: vec![
+                                    AnyValue {
+                                        value: Some(any_value::Value::IntValue(1)),
+                                    },
+                                    AnyValue {
+                                        value: Some(any_value::Value::BoolValue(true)),
+                                    },
+                                ],
+                            })),
+                        }),
+                    },
+                    KeyValue {
+                        key: "unset".into(),
+                        value: None,
+                    },
+                ],
+            })),
+        };
+
+        let mut expected = BTreeMap::new();
+        expected.insert(
+            "list".to_string(),
+            Value::Array(vec![Value::Integer(1), Value::Boolean(true)]),
+        );
+        expected.insert("unset".to_string(), Value::Null);
+        assert_eq!(decode_value(value), Value::Map(expected));
+    }
+
+    #[test]
+    fn round_trips_values() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "list".to_string(),
+            Value::Array(vec![Value::Integer(1), Value::Boolean(true)]),
+        );
+        map.insert("float".to_string(), Value::Float(1.5));
+        map.insert("string".to_string(), Value::from("text"));
+        map.insert("unset".to_string(), Value::Null);
+        let value = Value::Map(map);
+
+        assert_eq!(decode_value(encode_value(value.clone())), value);
+    }
+
+    #[test]
+    fn encodes_other_values() {
+        assert_eq!(
+            encode_value(Value::Bytes(vec![0xff, 0xfe].into())).value,
+            Some(any_value::Value::BytesValue(vec![0xff, 0xfe]))
+        );
+        assert_eq!(
+            encode_value(Value::Timestamp(Utc.timestamp(1_600_000_000, 0))).value,
+            Some(any_value::Value::StringValue(
+                "2020-09-13T12:26:40Z".to_string()
+            ))
+        );
+    }
+}
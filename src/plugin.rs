@@ -0,0 +1,314 @@
+//! Components provided by out-of-process plugins.
+//!
+//! A plugin is a gRPC server implementing the `Plugin` service of
+//! `proto/plugin.proto`. The `plugin` source, transform and sink connect to it
+//! and run one of the components it declares.
+
+use crate::{
+    config::GenerateConfig,
+    event::{self, Event},
+    internal_events::{PluginConnected, PluginEventDropped, PluginRequestFailed},
+    sinks::util::retries::ExponentialBackoff,
+};
+use futures::future::{abortable, AbortHandle};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::time::Duration;
+use tokio::{
+    sync::mpsc,
+    time::{delay_for, Delay},
+};
+use tonic::{
+    transport::{Channel, Endpoint},
+    Streaming,
+};
+use tracing_futures::Instrument;
+
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/plugin.rs"));
+}
+
+use proto::{plugin_client::PluginClient, ComponentKind};
+
+#[derive(Debug, Snafu)]
+enum PluginError {
+    #[snafu(display("Invalid plugin endpoint: {}", source))]
+    InvalidEndpoint { source: http::uri::InvalidUri },
+    #[snafu(display("Unable to connect to plugin: {}", source))]
+    ConnectFailed { source: tonic::transport::Error },
+    #[snafu(display("Plugin request failed: {}", source))]
+    RequestFailed { source: tonic::Status },
+    #[snafu(display(
+        "Plugin {:?} doesn't provide a {} named {:?}.",
+        plugin,
+        kind,
+        component
+    ))]
+    UnknownComponent {
+        plugin: String,
+        kind: &'static str,
+        component: String,
+    },
+    #[snafu(display("Plugin component is unhealthy: {}", message))]
+    Unhealthy { message: String },
+}
+
+/// The configuration shared by the `plugin` source, transform and sink.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PluginConfig {
+    pub endpoint: String,
+    pub component: String,
+    #[serde(default)]
+    pub options: serde_json::Map<String, serde_json::Value>,
+}
+
+impl GenerateConfig for PluginConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://127.0.0.1:50051"
+            component = "my_component""#,
+        )
+        .unwrap()
+    }
+}
+
+impl PluginConfig {
+    /// Connect to the plugin and check that it provides the configured
+    /// component.
+    pub(crate) async fn connect(&self, kind: ComponentKind) -> crate::Result<Plugin> {
+        let channel = Endpoint::from_shared(self.endpoint.clone())
+            .context(InvalidEndpoint)?
+            .connect()
+            .await
+            .context(ConnectFailed)?;
+        let mut client = PluginClient::new(channel);
+
+        let description = client
+            .describe(proto::DescribeRequest {})
+            .await
+            .context(RequestFailed)?
+            .into_inner();
+
+        let schema = description
+            .components
+            .into_iter()
+            .find(|schema| schema.name == self.component && schema.kind == kind as i32)
+            .ok_or_else(|| PluginError::UnknownComponent {
+                plugin: description.name.clone(),
+                kind: kind_name(kind),
+                component: self.component.clone(),
+            })?;
+
+        emit!(PluginConnected {
+            plugin: &description.name,
+            version: &description.version,
+        });
+
+        Ok(Plugin {
+            client,
+            schema,
+            config: proto::ComponentConfig {
+                component: self.component.clone(),
+                options: serde_json::to_string(&self.options)?,
+            },
+        })
+    }
+}
+
+fn kind_name(kind: ComponentKind) -> &'static str {
+    match kind {
+        ComponentKind::Source => "source",
+        ComponentKind::Transform => "transform",
+        ComponentKind::Sink => "sink",
+    }
+}
+
+/// A connection to a component of a plugin.
+#[derive(Clone, Debug)]
+pub(crate) struct Plugin {
+    pub(crate) client: PluginClient<Channel>,
+    schema: proto::Component,
+    config: proto::ComponentConfig,
+}
+
+impl Plugin {
+    /// The configuration sent to the plugin when a stream is opened.
+    pub(crate) fn config(&self) -> proto::ComponentConfig {
+        self.config.clone()
+    }
+
+    pub(crate) async fn healthcheck(mut self) -> crate::Result<()> {
+        let response = self
+            .client
+            .health(self.config.clone())
+            .await
+            .context(RequestFailed)?
+            .into_inner();
+
+        if response.healthy {
+            Ok(())
+        } else {
+            Err(PluginError::Unhealthy {
+                message: response.message,
+            }
+            .into())
+        }
+    }
+
+    /// Whether the event is of the type the component declared as its input.
+    /// Other events are dropped.
+    pub(crate) fn accepts(&self, event: &Event) -> bool {
+        let accepted = match proto::DataType::from_i32(self.schema.input) {
+            Some(proto::DataType::Log) => matches!(event, Event::Log(_)),
+            Some(proto::DataType::Metric) => matches!(event, Event::Metric(_)),
+            Some(proto::DataType::Any) | None => true,
+        };
+
+        if !accepted {
+            emit!(PluginEventDropped {
+                component: &self.config.component,
+            });
+        }
+
+        accepted
+    }
+}
+
+/// Why a stream with a plugin ended before all of its events were handled.
+pub(crate) enum SessionError {
+    Closed,
+    Failed(tonic::Status),
+}
+
+impl SessionError {
+    /// Reports the error, tagged with the `stage` of the component that had
+    /// the stream open.
+    pub(crate) fn report(self, stage: &'static str) {
+        match self {
+            SessionError::Closed => warn!(message = "Plugin closed the stream early."),
+            SessionError::Failed(error) => emit!(PluginRequestFailed { error, stage }),
+        }
+    }
+}
+
+/// Paces the streams opened with a plugin again after one of them ended
+/// early, backing off while the plugin keeps failing.
+pub(crate) struct Reconnect {
+    backoff: ExponentialBackoff,
+    stage: &'static str,
+}
+
+impl Reconnect {
+    pub(crate) fn new(stage: &'static str) -> Self {
+        Self {
+            backoff: Self::fresh_backoff(),
+            stage,
+        }
+    }
+
+    /// Reports why the stream ended, and returns the delay to wait before
+    /// opening the next one.
+    pub(crate) fn failed(&mut self, error: SessionError) -> Delay {
+        error.report(self.stage);
+        delay_for(self.backoff.next().unwrap())
+    }
+
+    /// Starts the backoff over once the plugin is reachable again.
+    pub(crate) fn reset(&mut self) {
+        self.backoff = Self::fresh_backoff();
+    }
+
+    fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+}
+
+/// What a stream with a plugin does next: send the next input event, or
+/// handle the next response.
+pub(crate) enum Step<T> {
+    Input(Option<Event>),
+    Response(Result<Option<T>, tonic::Status>),
+}
+
+/// The responses of a stream with a plugin, read by a task of their own so
+/// that they keep being read while requests wait to be sent. The task is
+/// aborted once dropped.
+pub(crate) struct Responses<T> {
+    rx: mpsc::Receiver<Result<T, tonic::Status>>,
+    abort: AbortHandle,
+}
+
+impl<T: Send + 'static> Responses<T> {
+    pub(crate) fn spawn(mut responses: Streaming<T>, buffer: usize) -> Self {
+        let (mut tx, rx) = mpsc::channel(buffer);
+        let (task, abort) = abortable(
+            async move {
+                while let Some(response) = responses.message().await.transpose() {
+                    let failed = response.is_err();
+                    if tx.send(response).await.is_err() || failed {
+                        break;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+        tokio::spawn(task);
+
+        Self { rx, abort }
+    }
+
+    /// The next response, or `None` once the plugin ended the stream.
+    pub(crate) async fn message(&mut self) -> Result<Option<T>, tonic::Status> {
+        self.rx.recv().await.transpose()
+    }
+}
+
+impl<T> Drop for Responses<T> {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+/// Convert an event received from a plugin, which may be empty.
+pub(crate) fn decode_event(event: Option<event::proto::EventWrapper>) -> Option<Event> {
+    event.filter(|event| event.event.is_some()).map(Event::from)
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::proto::{self, plugin_server::PluginServer};
+    use crate::test_util::next_addr;
+    use std::time::Duration;
+
+    /// Serve the plugin on a random local port and return its endpoint.
+    pub(crate) async fn serve(plugin: impl proto::plugin_server::Plugin) -> String {
+        let addr = next_addr();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(PluginServer::new(plugin))
+                .serve(addr),
+        );
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        format!("http://{}", addr)
+    }
+
+    pub(crate) fn describe(
+        kind: proto::ComponentKind,
+        input: proto::DataType,
+    ) -> proto::DescribeResponse {
+        proto::DescribeResponse {
+            name: "test".into(),
+            version: "1.0.0".into(),
+            components: vec![proto::Component {
+                name: "test".into(),
+                kind: kind as i32,
+                input: input as i32,
+                output: proto::DataType::Log as i32,
+            }],
+        }
+    }
+}
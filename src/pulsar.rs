@@ -0,0 +1,19 @@
+//! Options shared by the `pulsar` source and sink.
+
+use pulsar::Authentication;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuthConfig {
+    name: String,  // "token"
+    token: String, // <jwt token>
+}
+
+impl AuthConfig {
+    pub(crate) fn authentication(&self) -> Authentication {
+        Authentication {
+            name: self.name.clone(),
+            data: self.token.as_bytes().to_vec(),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Options for the CLI subcommands that talk to the API server of a local or remote Vector
+//! instance, such as `vector top` and `vector tap`.
+
+use crate::config;
+use indoc::indoc;
+use std::{fs, io, path::PathBuf};
+use structopt::StructOpt;
+use url::Url;
+use vector_api_client::{
+    connect_subscription_client_with_tls, gql::HealthQueryExt, Client, ConnectionError,
+    SubscriptionClient,
+};
+
+#[derive(StructOpt, Debug, Clone)]
+#[structopt(rename_all = "kebab-case")]
+pub struct ApiOpts {
+    /// Vector GraphQL API server endpoint. Use an `https://` URL to connect over TLS. Defaults
+    /// to the local API server
+    #[structopt(short, long)]
+    url: Option<Url>,
+
+    /// Bearer token to authenticate with, for API servers that require one
+    #[structopt(long, env = "VECTOR_API_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// PEM encoded CA certificate to verify an `https://` API server with, besides the system
+    /// ones. Useful for API servers with self-signed certificates
+    #[structopt(long, parse(from_os_str))]
+    ca_file: Option<PathBuf>,
+}
+
+impl ApiOpts {
+    /// Returns the provided URL of the Vector GraphQL API server, or defaults to the local port
+    /// provided by the API config. This works despite `api` and `api-client` being distinct
+    /// features; the config is available even if `api` is disabled
+    pub fn url(&self) -> Url {
+        self.url.clone().unwrap_or_else(|| {
+            let addr = config::api::default_address().unwrap();
+            Url::parse(&*format!("http://{}/graphql", addr))
+                .expect("Couldn't parse default API URL. Please report this.")
+        })
+    }
+
+    /// Returns the API URL, with the HTTP scheme changed to WebSockets
+    pub fn ws_url(&self) -> Url {
+        let url = self.url();
+        let mut ws_url = url.clone();
+        ws_url
+            .set_scheme(match url.scheme() {
+                "https" => "wss",
+                _ => "ws",
+            })
+            .expect("Couldn't build WebSocket URL. Please report.");
+        ws_url
+    }
+
+    /// Returns a new query client for the API server. Otherwise prints why the CA file can't
+    /// be used, and returns the exit code to exit with
+    pub fn client(&self) -> Result<Client, exitcode::ExitCode> {
+        let client = Client::new(self.url()).with_token(self.token.clone());
+        let pem = match self.ca_certificate() {
+            Ok(Some(pem)) => pem,
+            Ok(None) => return Ok(client),
+            Err(error) => {
+                eprintln!("Couldn't read the CA file: {}.", error);
+                return Err(exitcode::NOINPUT);
+            }
+        };
+
+        client.with_ca_certificate(&pem).map_err(|error| {
+            eprintln!("{:#}.", error);
+            exitcode::CONFIG
+        })
+    }
+
+    /// Connects a new subscription client to the API server, over WebSockets
+    pub async fn connect_subscription_client(&self) -> Result<SubscriptionClient, ConnectionError> {
+        let pem = self.ca_certificate().map_err(ConnectionError::Io)?;
+        connect_subscription_client_with_tls(self.ws_url(), self.token.as_deref(), pem.as_deref())
+            .await
+    }
+
+    /// Reads the CA certificate of `--ca-file`, if any
+    fn ca_certificate(&self) -> io::Result<Option<Vec<u8>>> {
+        self.ca_file.as_ref().map(fs::read).transpose()
+    }
+
+    /// Checks that the GraphQL server is reachable, and accepts the token, if any. Otherwise
+    /// prints why not, and returns the exit code to exit with
+    pub async fn check_reachable(&self, client: &Client) -> Result<(), exitcode::ExitCode> {
+        let error = match client.health_query().await {
+            Ok(_) => return Ok(()),
+            Err(error) => error,
+        };
+
+        let status = error
+            .downcast_ref::<reqwest::Error>()
+            .and_then(reqwest::Error::status);
+        if let Some(reqwest::StatusCode::UNAUTHORIZED) = status {
+            eprintln!(
+                indoc! {"
+                    Vector API server ({}) rejected the request as unauthorized.
+
+                    Pass the token the API server is configured with using `--token`, or the
+                    `VECTOR_API_TOKEN` environment variable."},
+                self.url()
+            );
+            return Err(exitcode::NOPERM);
+        }
+
+        eprintln!(
+            indoc! {"
+                Vector API server isn't reachable ({}).
+
+                Have you enabled the API?
+
+                To enable the API, add the following to your `vector.toml` config file:
+
+                [api]
+                  enabled = true"},
+            self.url()
+        );
+        Err(exitcode::UNAVAILABLE)
+    }
+}
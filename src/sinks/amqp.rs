@@ -0,0 +1,460 @@
+use crate::{
+    amqp::AmqpConfig,
+    buffers::Acker,
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    emit,
+    internal_events::{
+        AmqpEventMissingKeys, AmqpEventNotConfirmed, AmqpEventSendFail, AmqpEventSendSuccess,
+    },
+    sinks::util::{
+        encoding::{EncodingConfig, EncodingConfiguration},
+        retries::ExponentialBackoff,
+        StreamSink,
+    },
+    template::{Template, TemplateError},
+    Event,
+};
+use async_trait::async_trait;
+use futures::{
+    future::{self, BoxFuture},
+    stream::BoxStream,
+    FutureExt, StreamExt,
+};
+use lapin::{
+    options::{BasicPublishOptions, ConfirmSelectOptions},
+    publisher_confirm::Confirmation,
+    BasicProperties, Channel, Connection,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::VecDeque, convert::TryFrom, time::Duration};
+use tokio::time::delay_for;
+
+/// The most messages published without being confirmed by the broker yet.
+const MAX_UNCONFIRMED: usize = 128;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid exchange template: {}", source))]
+    ExchangeTemplate { source: TemplateError },
+    #[snafu(display("invalid routing key template: {}", source))]
+    RoutingKeyTemplate { source: TemplateError },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AmqpSinkConfig {
+    connection: AmqpConfig,
+    exchange: String,
+    routing_key: Option<String>,
+    encoding: EncodingConfig<Encoding>,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Text,
+    Json,
+}
+
+inventory::submit! {
+    SinkDescription::new::<AmqpSinkConfig>("amqp")
+}
+
+impl GenerateConfig for AmqpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            connection.connection_string = "amqp://127.0.0.1:5672/%2f"
+            exchange = "logs"
+            routing_key = "{{ host }}"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "amqp")]
+impl SinkConfig for AmqpSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = AmqpSink::new(self.clone(), cx.acker())?;
+        let healthcheck = healthcheck(self.connection.clone()).boxed();
+        Ok((super::VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "amqp"
+    }
+}
+
+async fn healthcheck(connection: AmqpConfig) -> crate::Result<()> {
+    let (connection, _channel) = connection.connect().await?;
+    connection.close(0, "Healthcheck done.").await?;
+    Ok(())
+}
+
+pub struct AmqpSink {
+    connection: AmqpConfig,
+    exchange: Template,
+    routing_key: Option<Template>,
+    encoding: EncodingConfig<Encoding>,
+    acker: Acker,
+}
+
+impl AmqpSink {
+    fn new(config: AmqpSinkConfig, acker: Acker) -> crate::Result<Self> {
+        Ok(AmqpSink {
+            connection: config.connection,
+            exchange: Template::try_from(config.exchange).context(ExchangeTemplate)?,
+            routing_key: config
+                .routing_key
+                .map(Template::try_from)
+                .transpose()
+                .context(RoutingKeyTemplate)?,
+            encoding: config.encoding,
+            acker,
+        })
+    }
+
+    /// Returns the exchange and the routing key to publish `event` with.
+    fn render(&self, event: &Event) -> Result<(String, String), Vec<String>> {
+        let exchange = self.exchange.render_string(event)?;
+        let routing_key = match &self.routing_key {
+            Some(routing_key) => routing_key.render_string(event)?,
+            None => String::new(),
+        };
+        Ok((exchange, routing_key))
+    }
+}
+
+/// A message published to the broker, or `None` for an event that was
+/// dropped, along with its publisher confirm.
+struct Unconfirmed {
+    message: Option<Message>,
+    confirm: BoxFuture<'static, Result<Confirmation, lapin::Error>>,
+}
+
+struct Message {
+    exchange: String,
+    routing_key: String,
+    payload: Vec<u8>,
+}
+
+enum Step {
+    Input(Option<Event>),
+    Confirmed(Result<Confirmation, lapin::Error>),
+}
+
+impl AmqpSink {
+    /// Connects to the broker, retrying until it succeeds.
+    async fn connect(&self) -> (Connection, Channel) {
+        let mut backoff = fresh_backoff();
+        loop {
+            match self.try_connect().await {
+                Ok(connected) => return connected,
+                Err(error) => {
+                    error!(message = "Failed to connect to AMQP broker; retrying.", %error);
+                    delay_for(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    async fn try_connect(&self) -> Result<(Connection, Channel), lapin::Error> {
+        let (connection, channel) = self.connection.connect().await?;
+        // Publisher confirms tell once the broker took responsibility for
+        // messages, which is when their events are acknowledged.
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await?;
+        Ok((connection, channel))
+    }
+
+    fn message(&self, event: Event) -> Option<Message> {
+        let (exchange, routing_key) = match self.render(&event) {
+            Ok(rendered) => rendered,
+            Err(missing_keys) => {
+                emit!(AmqpEventMissingKeys {
+                    keys: &missing_keys
+                });
+                return None;
+            }
+        };
+
+        Some(Message {
+            exchange,
+            routing_key,
+            payload: encode_event(event, &self.encoding),
+        })
+    }
+}
+
+/// Publishes the message, returning its confirm without waiting for it, so
+/// that confirms are awaited while further messages are published.
+async fn publish(
+    channel: &Channel,
+    message: &Message,
+) -> BoxFuture<'static, Result<Confirmation, lapin::Error>> {
+    let published = channel
+        .basic_publish(
+            &message.exchange,
+            &message.routing_key,
+            BasicPublishOptions::default(),
+            message.payload.clone(),
+            BasicProperties::default(),
+        )
+        .await;
+
+    match published {
+        Ok(confirm) => confirm.boxed(),
+        Err(error) => future::ready(Err(error)).boxed(),
+    }
+}
+
+async fn republish(channel: &Channel, unconfirmed: &mut Unconfirmed) {
+    if let Some(message) = &unconfirmed.message {
+        unconfirmed.confirm = publish(channel, message).await;
+    }
+}
+
+/// The confirm of the oldest unconfirmed message, as events are
+/// acknowledged in order.
+async fn next_confirm(
+    unconfirmed: &mut VecDeque<Unconfirmed>,
+) -> Result<Confirmation, lapin::Error> {
+    match unconfirmed.front_mut() {
+        Some(unconfirmed) => (&mut unconfirmed.confirm).await,
+        None => future::pending().await,
+    }
+}
+
+fn fresh_backoff() -> ExponentialBackoff {
+    ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(60))
+}
+
+#[async_trait]
+impl StreamSink for AmqpSink {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        // The connection is kept along with the channel, which it has to
+        // outlive.
+        let mut connected = self.connect().await;
+        let mut unconfirmed = VecDeque::new();
+        let mut input_done = false;
+        let mut backoff = fresh_backoff();
+
+        while !input_done || !unconfirmed.is_empty() {
+            let step = tokio::select! {
+                event = input.next(), if !input_done && unconfirmed.len() < MAX_UNCONFIRMED => {
+                    Step::Input(event)
+                }
+                confirmation = next_confirm(&mut unconfirmed) => Step::Confirmed(confirmation),
+            };
+
+            match step {
+                Step::Input(None) => input_done = true,
+                Step::Input(Some(event)) => {
+                    let message = self.message(event);
+                    let confirm = match &message {
+                        Some(message) => publish(&connected.1, message).await,
+                        None => future::ready(Ok(Confirmation::NotRequested)).boxed(),
+                    };
+                    unconfirmed.push_back(Unconfirmed { message, confirm });
+                }
+                Step::Confirmed(Ok(Confirmation::Nack(_))) => {
+                    // The broker failed to store the message, so it's
+                    // published again in its place.
+                    emit!(AmqpEventNotConfirmed);
+                    delay_for(backoff.next().unwrap()).await;
+                    let head = unconfirmed.front_mut().expect("a message is unconfirmed");
+                    republish(&connected.1, head).await;
+                }
+                Step::Confirmed(Ok(_)) => {
+                    backoff = fresh_backoff();
+                    let confirmed = unconfirmed.pop_front().expect("a message is unconfirmed");
+                    if let Some(message) = confirmed.message {
+                        emit!(AmqpEventSendSuccess {
+                            byte_size: message.payload.len()
+                        });
+                    }
+                    self.acker.ack(1);
+                }
+                Step::Confirmed(Err(error)) => {
+                    // The channel is closed once it fails, so the unconfirmed
+                    // messages are published again on a new one.
+                    emit!(AmqpEventSendFail { error });
+                    delay_for(backoff.next().unwrap()).await;
+                    connected = self.connect().await;
+                    for message in unconfirmed.iter_mut() {
+                        republish(&connected.1, message).await;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Vec<u8> {
+    encoding.apply_rules(&mut event);
+
+    match encoding.codec() {
+        Encoding::Json => serde_json::to_vec(event.as_log()).unwrap(),
+        Encoding::Text => event
+            .as_log()
+            .get(log_schema().message_key())
+            .map(|v| v.as_bytes().to_vec())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Value;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AmqpSinkConfig>();
+    }
+
+    #[test]
+    fn encodes_raw_logs() {
+        let event = Event::from("foo");
+        assert_eq!(
+            b"foo".to_vec(),
+            encode_event(event, &EncodingConfig::from(Encoding::Text))
+        );
+    }
+
+    #[test]
+    fn encodes_log_events() {
+        let mut event = Event::new_empty_log();
+        let log = event.as_mut_log();
+        log.insert("x", Value::from("23"));
+        log.insert("z", Value::from(25));
+
+        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        assert_eq!(encoded, br#"{"x":"23","z":25}"#.to_vec());
+    }
+
+    #[test]
+    fn renders_exchange_and_routing_key() {
+        let config: AmqpSinkConfig = toml::from_str(
+            r#"
+            connection.connection_string = "amqp://127.0.0.1:5672/%2f"
+            exchange = "logs-{{ app }}"
+            routing_key = "{{ host }}"
+            encoding.codec = "text"
+            "#,
+        )
+        .unwrap();
+        let (acker, _) = Acker::new_for_testing();
+        let sink = AmqpSink::new(config, acker).unwrap();
+
+        let mut event = Event::from("foo");
+        event.as_mut_log().insert("app", "api");
+        event.as_mut_log().insert("host", "web-1");
+        assert_eq!(
+            sink.render(&event),
+            Ok(("logs-api".to_owned(), "web-1".to_owned()))
+        );
+
+        let event = Event::from("foo");
+        assert!(sink.render(&event).is_err());
+    }
+}
+
+#[cfg(feature = "amqp-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{random_lines_with_stream, random_string, trace_init};
+    use lapin::{
+        options::{
+            BasicConsumeOptions, ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions,
+        },
+        types::FieldTable,
+        ExchangeKind,
+    };
+    use std::sync::atomic::Ordering;
+
+    #[tokio::test]
+    async fn amqp_happy() {
+        trace_init();
+
+        let exchange = format!("test-{}", random_string(10));
+        let queue = format!("test-{}", random_string(10));
+        let config = AmqpSinkConfig {
+            connection: AmqpConfig::new("amqp://127.0.0.1:5672/%2f".to_owned()),
+            exchange: exchange.clone(),
+            routing_key: Some("logs".to_owned()),
+            encoding: EncodingConfig::from(Encoding::Text),
+        };
+
+        // Route the messages of the exchange to a queue to check them.
+        let (_connection, channel) = config.connection.connect().await.unwrap();
+        channel
+            .exchange_declare(
+                &exchange,
+                ExchangeKind::Direct,
+                ExchangeDeclareOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .unwrap();
+        channel
+            .queue_declare(
+                &queue,
+                QueueDeclareOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .unwrap();
+        channel
+            .queue_bind(
+                &queue,
+                &exchange,
+                "logs",
+                QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await
+            .unwrap();
+
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut sink = AmqpSink::new(config, acker).unwrap();
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events);
+        sink.run(Box::pin(events)).await.unwrap();
+        assert_eq!(ack_counter.load(Ordering::Relaxed), num_events);
+
+        let output = channel
+            .basic_consume(
+                &queue,
+                "test",
+                BasicConsumeOptions {
+                    no_ack: true,
+                    ..BasicConsumeOptions::default()
+                },
+                FieldTable::default(),
+            )
+            .await
+            .unwrap()
+            .take(num_events)
+            .map(|delivery| String::from_utf8(delivery.unwrap().1.data).unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output, input);
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
         retries::{FixedRetryPolicy, RetryLogic},
-        BatchConfig, BatchSettings, Compression, EncodedLength, PartitionBatchSink,
+        BatchConfig, BatchSettings, Compression, EncodedEvent, EncodedLength, PartitionBatchSink,
         PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig, TowerRequestSettings, VecBuffer,
     },
     template::Template,
@@ -19,6 +19,7 @@ use rusoto_core::{request::BufferedHttpResponse, RusotoError};
 use rusoto_logs::{
     CloudWatchLogs, CloudWatchLogsClient, CreateLogGroupError, CreateLogStreamError,
     DescribeLogGroupsRequest, DescribeLogStreamsError, InputLogEvent, PutLogEventsError,
+    PutRetentionPolicyError,
 };
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
@@ -41,6 +42,13 @@ use tower::{
 const EVENT_SIZE_OVERHEAD: usize = 50;
 const MAX_EVENT_SIZE: usize = 256 * 1024;
 const MAX_MESSAGE_SIZE: usize = MAX_EVENT_SIZE - EVENT_SIZE_OVERHEAD;
+// Limits of a single PutLogEvents request
+const MAX_BATCH_BYTES: usize = 1_048_576;
+const MAX_BATCH_EVENTS: usize = 10_000;
+// Retention periods accepted by PutRetentionPolicy
+const RETENTION_DAYS: &[i64] = &[
+    1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1827, 3653,
+];
 
 #[derive(Debug, Snafu)]
 pub(self) enum CloudwatchLogsError {
@@ -54,6 +62,12 @@ pub(self) enum CloudwatchLogsError {
     },
     #[snafu(display("Encoded event is too long, length={}", length))]
     EventTooLong { length: usize },
+    #[snafu(display(
+        "Invalid retention of {} days, must be one of {:?}",
+        days,
+        RETENTION_DAYS
+    ))]
+    InvalidRetention { days: i64 },
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -66,6 +80,9 @@ pub struct CloudwatchLogsSinkConfig {
     pub encoding: EncodingConfig<Encoding>,
     pub create_missing_group: Option<bool>,
     pub create_missing_stream: Option<bool>,
+    pub group_tags: Option<HashMap<String, String>>,
+    pub kms_key: Option<String>,
+    pub retention_days: Option<i64>,
     #[serde(default)]
     pub compression: Compression,
     #[serde(default)]
@@ -96,6 +113,9 @@ fn default_config(e: Encoding) -> CloudwatchLogsSinkConfig {
         encoding: e.into(),
         create_missing_group: Default::default(),
         create_missing_stream: Default::default(),
+        group_tags: Default::default(),
+        kms_key: Default::default(),
+        retention_days: Default::default(),
         compression: Default::default(),
         batch: Default::default(),
         request: Default::default(),
@@ -116,6 +136,7 @@ pub struct CloudwatchLogsSvc {
     group_name: String,
     create_missing_group: bool,
     create_missing_stream: bool,
+    group: request::GroupSettings,
     token: Option<String>,
     token_rx: Option<oneshot::Receiver<Option<String>>>,
 }
@@ -152,6 +173,7 @@ pub enum CloudwatchError {
     Describe(RusotoError<DescribeLogStreamsError>),
     CreateStream(RusotoError<CreateLogStreamError>),
     CreateGroup(RusotoError<CreateLogGroupError>),
+    PutRetentionPolicy(RusotoError<PutRetentionPolicyError>),
     NoStreamsFound,
     ServiceDropped,
     MakeService,
@@ -167,6 +189,44 @@ impl CloudwatchLogsSinkConfig {
         let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
         Ok(CloudWatchLogsClient::new_with_client(client, region))
     }
+
+    /// PutLogEvents rejects requests over 1MB or 10k events, so larger batch
+    /// sizes are lowered to those limits.
+    fn batch_settings(&self) -> crate::Result<BatchSettings<VecBuffer<InputLogEvent>>> {
+        let mut batch = BatchSettings::default()
+            .bytes(MAX_BATCH_BYTES as u64)
+            .events(MAX_BATCH_EVENTS)
+            .timeout(1)
+            .parse_config(self.batch)?;
+
+        if batch.size.bytes > MAX_BATCH_BYTES {
+            warn!(
+                message = "Batch size is over the PutLogEvents limit; using the limit.",
+                max_bytes = %batch.size.bytes,
+                limit = %MAX_BATCH_BYTES,
+            );
+            batch.size.bytes = MAX_BATCH_BYTES;
+        }
+        if batch.size.events > MAX_BATCH_EVENTS {
+            warn!(
+                message = "Batch size is over the PutLogEvents limit; using the limit.",
+                max_events = %batch.size.events,
+                limit = %MAX_BATCH_EVENTS,
+            );
+            batch.size.events = MAX_BATCH_EVENTS;
+        }
+
+        Ok(batch)
+    }
+
+    fn validate_retention(&self) -> Result<(), CloudwatchLogsError> {
+        match self.retention_days {
+            Some(days) if !RETENTION_DAYS.contains(&days) => {
+                Err(CloudwatchLogsError::InvalidRetention { days })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -176,11 +236,8 @@ impl SinkConfig for CloudwatchLogsSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let batch = BatchSettings::default()
-            .bytes(1_048_576)
-            .events(10_000)
-            .timeout(1)
-            .parse_config(self.batch)?;
+        self.validate_retention()?;
+        let batch = self.batch_settings()?;
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
 
         let log_group = self.group_name.clone();
@@ -199,7 +256,10 @@ impl SinkConfig for CloudwatchLogsSinkConfig {
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
             .sink_map_err(|error| error!(message = "Fatal cloudwatchlogs sink error.", %error))
             .with_flat_map(move |event| {
-                stream::iter(partition_encode(event, &encoding, &log_group, &log_stream)).map(Ok)
+                let encoded = EncodedEvent::encode(event, |event| {
+                    partition_encode(event, &encoding, &log_group, &log_stream)
+                });
+                stream::iter(encoded).map(Ok)
             });
 
         let healthcheck = healthcheck(self.clone(), client).boxed();
@@ -287,12 +347,19 @@ impl CloudwatchLogsSvc {
         let create_missing_group = config.create_missing_group.unwrap_or(true);
         let create_missing_stream = config.create_missing_stream.unwrap_or(true);
 
+        let group = request::GroupSettings {
+            tags: config.group_tags.clone(),
+            kms_key: config.kms_key.clone(),
+            retention_days: config.retention_days,
+        };
+
         CloudwatchLogsSvc {
             client,
             stream_name,
             group_name,
             create_missing_group,
             create_missing_stream,
+            group,
             token: None,
             token_rx: None,
         }
@@ -389,6 +456,7 @@ impl Service<Vec<InputLogEvent>> for CloudwatchLogsSvc {
                 self.group_name.clone(),
                 self.create_missing_group,
                 self.create_missing_stream,
+                self.group.clone(),
                 event_batches,
                 self.token.take(),
                 tx,
@@ -643,6 +711,9 @@ impl fmt::Display for CloudwatchError {
             CloudwatchError::CreateGroup(error) => {
                 write!(f, "CloudwatchError::CreateGroup: {}", error)
             }
+            CloudwatchError::PutRetentionPolicy(error) => {
+                write!(f, "CloudwatchError::PutRetentionPolicy: {}", error)
+            }
             CloudwatchError::NoStreamsFound => write!(f, "CloudwatchError: No Streams Found"),
             CloudwatchError::ServiceDropped => write!(
                 f,
@@ -850,6 +921,49 @@ mod tests {
         }
 
         assert_eq!(batches.len(), 5);
+        assert!(batches
+            .windows(2)
+            .all(|pair| pair[0].last().unwrap().timestamp <= pair[1][0].timestamp));
+    }
+
+    #[test]
+    fn batch_settings_are_limited() {
+        let config = CloudwatchLogsSinkConfig {
+            batch: BatchConfig {
+                max_bytes: Some(10_000_000),
+                max_events: Some(20_000),
+                ..Default::default()
+            },
+            ..default_config(Encoding::Text)
+        };
+
+        let batch = config.batch_settings().unwrap();
+        assert_eq!(batch.size.bytes, MAX_BATCH_BYTES);
+        assert_eq!(batch.size.events, MAX_BATCH_EVENTS);
+
+        let config = CloudwatchLogsSinkConfig {
+            batch: BatchConfig {
+                max_events: Some(100),
+                ..Default::default()
+            },
+            ..default_config(Encoding::Text)
+        };
+
+        let batch = config.batch_settings().unwrap();
+        assert_eq!(batch.size.bytes, MAX_BATCH_BYTES);
+        assert_eq!(batch.size.events, 100);
+    }
+
+    #[test]
+    fn retention_days() {
+        let mut config = default_config(Encoding::Text);
+        assert!(config.validate_retention().is_ok());
+
+        config.retention_days = Some(30);
+        assert!(config.validate_retention().is_ok());
+
+        config.retention_days = Some(31);
+        assert!(config.validate_retention().is_err());
     }
 }
 
@@ -884,6 +998,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -931,6 +1048,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -997,6 +1117,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -1069,6 +1192,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -1118,6 +1244,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: BatchConfig {
                 max_events: Some(2),
@@ -1169,6 +1298,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -1255,6 +1387,9 @@ mod integration_tests {
             encoding: Encoding::Text.into(),
             create_missing_group: None,
             create_missing_stream: None,
+            group_tags: None,
+            kms_key: None,
+            retention_days: None,
             compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -5,9 +5,10 @@ use rusoto_logs::{
     CloudWatchLogs, CloudWatchLogsClient, CreateLogGroupError, CreateLogGroupRequest,
     CreateLogStreamError, CreateLogStreamRequest, DescribeLogStreamsError,
     DescribeLogStreamsRequest, DescribeLogStreamsResponse, InputLogEvent, PutLogEventsError,
-    PutLogEventsRequest, PutLogEventsResponse,
+    PutLogEventsRequest, PutLogEventsResponse, PutRetentionPolicyError, PutRetentionPolicyRequest,
 };
 use std::{
+    collections::HashMap,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
@@ -19,6 +20,7 @@ pub struct CloudwatchFuture {
     state: State,
     create_missing_group: bool,
     create_missing_stream: bool,
+    group: GroupSettings,
     events: Vec<Vec<InputLogEvent>>,
     token_tx: Option<oneshot::Sender<Option<String>>>,
 }
@@ -29,10 +31,19 @@ struct Client {
     group_name: String,
 }
 
+/// Settings of the log groups created by the sink.
+#[derive(Clone, Debug, Default)]
+pub struct GroupSettings {
+    pub tags: Option<HashMap<String, String>>,
+    pub kms_key: Option<String>,
+    pub retention_days: Option<i64>,
+}
+
 type ClientResult<T, E> = BoxFuture<'static, RusotoResult<T, E>>;
 
 enum State {
     CreateGroup(ClientResult<(), CreateLogGroupError>),
+    PutRetentionPolicy(ClientResult<(), PutRetentionPolicyError>),
     CreateStream(ClientResult<(), CreateLogStreamError>),
     DescribeStream(ClientResult<DescribeLogStreamsResponse, DescribeLogStreamsError>),
     Put(ClientResult<PutLogEventsResponse, PutLogEventsError>),
@@ -40,12 +51,16 @@ enum State {
 
 impl CloudwatchFuture {
     /// Panics if events.is_empty()
+    ///
+    /// The event batches are sent in order, so the batches of a stream must
+    /// be ordered by timestamp.
     pub fn new(
         client: CloudWatchLogsClient,
         stream_name: String,
         group_name: String,
         create_missing_group: bool,
         create_missing_stream: bool,
+        group: GroupSettings,
         mut events: Vec<Vec<InputLogEvent>>,
         token: Option<String>,
         token_tx: oneshot::Sender<Option<String>>,
@@ -56,6 +71,9 @@ impl CloudwatchFuture {
             group_name,
         };
 
+        // Batches are popped from the back, reverse them to send the oldest first.
+        events.reverse();
+
         let state = if let Some(token) = token {
             State::Put(client.put_logs(Some(token), events.pop().expect("No Events to send")))
         } else {
@@ -69,6 +87,7 @@ impl CloudwatchFuture {
             token_tx: Some(token_tx),
             create_missing_group,
             create_missing_stream,
+            group,
         }
     }
 }
@@ -87,7 +106,8 @@ impl Future for CloudwatchFuture {
                         {
                             info!("Log group provided does not exist; creating a new one.");
 
-                            self.state = State::CreateGroup(self.client.create_log_group());
+                            self.state =
+                                State::CreateGroup(self.client.create_log_group(&self.group));
                             continue;
                         }
                         Err(err) => return Poll::Ready(Err(CloudwatchError::Describe(err))),
@@ -119,19 +139,37 @@ impl Future for CloudwatchFuture {
                 }
 
                 State::CreateGroup(fut) => {
-                    match ready!(fut.poll_unpin(cx)) {
-                        Ok(_) => {}
+                    let created = match ready!(fut.poll_unpin(cx)) {
+                        Ok(_) => true,
                         Err(RusotoError::Service(CreateLogGroupError::ResourceAlreadyExists(
                             _,
-                        ))) => {}
+                        ))) => false,
                         Err(err) => return Poll::Ready(Err(CloudwatchError::CreateGroup(err))),
                     };
 
                     info!(message = "Group created.", name = %self.client.group_name);
 
-                    // self does not abide by `create_missing_stream` since a group
-                    // never has any streams and thus we need to create one if a group
-                    // is created no matter what.
+                    // The retention policy is only set on groups created by
+                    // the sink, an existing group keeps its own.
+                    match self.group.retention_days {
+                        Some(days) if created => {
+                            self.state =
+                                State::PutRetentionPolicy(self.client.put_retention_policy(days));
+                        }
+                        // self does not abide by `create_missing_stream` since a group
+                        // never has any streams and thus we need to create one if a group
+                        // is created no matter what.
+                        _ => self.state = State::CreateStream(self.client.create_log_stream()),
+                    }
+                }
+
+                State::PutRetentionPolicy(fut) => {
+                    if let Err(err) = ready!(fut.poll_unpin(cx)) {
+                        return Poll::Ready(Err(CloudwatchError::PutRetentionPolicy(err)));
+                    }
+
+                    info!(message = "Retention policy set.", name = %self.client.group_name);
+
                     self.state = State::CreateStream(self.client.create_log_stream());
                 }
 
@@ -206,16 +244,30 @@ impl Client {
         Box::pin(async move { client.describe_log_streams(request).await })
     }
 
-    pub fn create_log_group(&self) -> ClientResult<(), CreateLogGroupError> {
+    pub fn create_log_group(&self, group: &GroupSettings) -> ClientResult<(), CreateLogGroupError> {
         let request = CreateLogGroupRequest {
             log_group_name: self.group_name.clone(),
-            ..Default::default()
+            kms_key_id: group.kms_key.clone(),
+            tags: group.tags.clone(),
         };
 
         let client = self.client.clone();
         Box::pin(async move { client.create_log_group(request).await })
     }
 
+    pub fn put_retention_policy(
+        &self,
+        retention_in_days: i64,
+    ) -> ClientResult<(), PutRetentionPolicyError> {
+        let request = PutRetentionPolicyRequest {
+            log_group_name: self.group_name.clone(),
+            retention_in_days,
+        };
+
+        let client = self.client.clone();
+        Box::pin(async move { client.put_retention_policy(request).await })
+    }
+
     pub fn create_log_stream(&self) -> ClientResult<(), CreateLogStreamError> {
         let request = CreateLogStreamRequest {
             log_group_name: self.group_name.clone(),
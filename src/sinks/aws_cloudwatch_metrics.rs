@@ -1,19 +1,21 @@
 use crate::{
     config::{DataType, SinkConfig, SinkContext, SinkDescription},
     event::{
-        metric::{Metric, MetricValue},
+        metric::{Metric, MetricValue, Sample},
         Event,
     },
     rusoto::{self, AWSAuthentication, RegionOrEndpoint},
     sinks::util::{
         buffer::metrics::{MetricNormalize, MetricSet, MetricsBuffer},
         retries::RetryLogic,
-        BatchConfig, BatchSettings, Compression, PartitionBatchSink, PartitionBuffer,
+        BatchConfig, BatchSettings, Compression, EncodedEvent, PartitionBatchSink, PartitionBuffer,
         PartitionInnerBuffer, TowerRequestConfig,
     },
+    template::Template,
 };
 use chrono::{DateTime, SecondsFormat, Utc};
-use futures::{future, future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
+use futures::{future, future::BoxFuture, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use rusoto_cloudwatch::{
     CloudWatch, CloudWatchClient, Dimension, MetricDatum, PutMetricDataError, PutMetricDataInput,
@@ -21,11 +23,16 @@ use rusoto_cloudwatch::{
 use rusoto_core::{Region, RusotoError};
 use serde::{Deserialize, Serialize};
 use std::{
+    cmp::Ordering,
     collections::BTreeMap,
     convert::TryInto,
     task::{Context, Poll},
 };
-use tower::Service;
+use tower::{buffer::Buffer, Service, ServiceExt};
+
+// Limits of a single PutMetricData request
+const MAX_METRICS_PER_REQUEST: usize = 1_000;
+const MAX_DISTRIBUTION_VALUES: usize = 150;
 
 #[derive(Clone)]
 pub struct CloudWatchMetricsSvc {
@@ -37,7 +44,9 @@ pub struct CloudWatchMetricsSvc {
 #[serde(deny_unknown_fields)]
 pub struct CloudWatchMetricsSinkConfig {
     #[serde(alias = "namespace")]
-    pub default_namespace: String,
+    pub default_namespace: Template,
+    #[serde(default)]
+    pub dimensions: IndexMap<String, Template>,
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
     #[serde(default)]
@@ -90,13 +99,18 @@ impl SinkConfig for CloudWatchMetricsSinkConfig {
 
 impl CloudWatchMetricsSinkConfig {
     async fn healthcheck(self, client: CloudWatchClient) -> crate::Result<()> {
+        if self.default_namespace.is_dynamic() {
+            info!("Cloudwatch default_namespace is dynamic; skipping healthcheck.");
+            return Ok(());
+        }
+
         let datum = MetricDatum {
             metric_name: "healthcheck".into(),
             value: Some(1.0),
             ..Default::default()
         };
         let request = PutMetricDataInput {
-            namespace: self.default_namespace.clone(),
+            namespace: self.default_namespace.get_ref().to_owned(),
             metric_data: vec![datum],
         };
 
@@ -133,6 +147,7 @@ impl CloudWatchMetricsSvc {
         cx: SinkContext,
     ) -> crate::Result<super::VectorSink> {
         let default_namespace = config.default_namespace.clone();
+        let dimensions = config.dimensions.clone();
         let batch = BatchSettings::default()
             .events(20)
             .timeout(1)
@@ -141,7 +156,13 @@ impl CloudWatchMetricsSvc {
 
         let cloudwatch_metrics = CloudWatchMetricsSvc { client, config };
 
-        let svc = request.service(CloudWatchMetricsRetryLogic, cloudwatch_metrics);
+        // The requests of a batch are retried on their own, so that retrying a
+        // failed request doesn't resend the others.
+        let svc = Buffer::new(
+            request.service(CloudWatchMetricsRetryLogic, cloudwatch_metrics),
+            request.concurrency.unwrap_or(1),
+        );
+        let svc = CloudWatchMetricsPartitionSvc { svc };
 
         let buffer = PartitionBuffer::new(MetricsBuffer::<AwsCloudwatchMetricNormalize>::new(
             batch.size,
@@ -149,66 +170,130 @@ impl CloudWatchMetricsSvc {
 
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
             .sink_map_err(|error| error!(message = "Fatal CloudwatchMetrics sink error.", %error))
-            .with_flat_map(move |mut event: Event| {
-                let namespace = event
-                    .as_mut_metric()
-                    .series
-                    .name
-                    .namespace
-                    .take()
-                    .unwrap_or_else(|| default_namespace.clone());
-                stream::iter(Some(PartitionInnerBuffer::new(event, namespace))).map(Ok)
+            .with_flat_map(move |event: Event| {
+                let encoded = EncodedEvent::encode(event, |event| {
+                    partition(event, &default_namespace, &dimensions)
+                });
+                stream::iter(encoded).map(Ok)
             });
 
         Ok(super::VectorSink::Sink(Box::new(sink)))
     }
+}
 
-    fn encode_events(&mut self, events: Vec<Metric>) -> Vec<MetricDatum> {
-        events
-            .into_iter()
-            .filter_map(|event| {
-                let metric_name = event.name().to_string();
-                let timestamp = event.data.timestamp.map(timestamp_to_string);
-                let dimensions = event.series.tags.clone().map(tags_to_dimensions);
-                // AwsCloudwatchMetricNormalize converts these to the right MetricKind
-                match event.data.value {
-                    MetricValue::Counter { value } => Some(MetricDatum {
-                        metric_name,
-                        value: Some(value),
-                        timestamp,
-                        dimensions,
-                        ..Default::default()
-                    }),
-                    MetricValue::Distribution {
-                        samples,
-                        statistic: _,
-                    } => Some(MetricDatum {
-                        metric_name,
-                        values: Some(samples.iter().map(|s| s.value).collect()),
-                        counts: Some(samples.iter().map(|s| f64::from(s.rate)).collect()),
-                        timestamp,
-                        dimensions,
-                        ..Default::default()
-                    }),
-                    MetricValue::Set { values } => Some(MetricDatum {
-                        metric_name,
-                        value: Some(values.len() as f64),
-                        timestamp,
-                        dimensions,
-                        ..Default::default()
-                    }),
-                    MetricValue::Gauge { value } => Some(MetricDatum {
-                        metric_name,
-                        value: Some(value),
-                        timestamp,
-                        dimensions,
-                        ..Default::default()
-                    }),
-                    _ => None,
-                }
-            })
-            .collect()
+fn encode_events(events: Vec<Metric>) -> Vec<MetricDatum> {
+    events
+        .into_iter()
+        .flat_map(|event| {
+            let datum = MetricDatum {
+                metric_name: event.name().to_string(),
+                timestamp: event.data.timestamp.map(timestamp_to_string),
+                dimensions: event.series.tags.clone().map(tags_to_dimensions),
+                ..Default::default()
+            };
+            // AwsCloudwatchMetricNormalize converts these to the right MetricKind
+            match event.data.value {
+                MetricValue::Counter { value } => vec![MetricDatum {
+                    value: Some(value),
+                    ..datum
+                }],
+                MetricValue::Distribution {
+                    samples,
+                    statistic: _,
+                } => encode_distribution(&samples)
+                    .into_iter()
+                    .map(|(values, counts)| MetricDatum {
+                        values: Some(values),
+                        counts: Some(counts),
+                        ..datum.clone()
+                    })
+                    .collect(),
+                MetricValue::Set { values } => vec![MetricDatum {
+                    value: Some(values.len() as f64),
+                    ..datum
+                }],
+                MetricValue::Gauge { value } => vec![MetricDatum {
+                    value: Some(value),
+                    ..datum
+                }],
+                _ => vec![],
+            }
+        })
+        .collect()
+}
+
+/// Returns the partition key of the metric, its namespace, and adds the
+/// configured dimensions to its tags.
+///
+/// The namespace of the metric takes precedence over the default namespace.
+fn partition(
+    event: Event,
+    default_namespace: &Template,
+    dimensions: &IndexMap<String, Template>,
+) -> Option<PartitionInnerBuffer<Event, String>> {
+    let namespace = match event.as_metric().namespace() {
+        Some(namespace) => namespace.to_owned(),
+        None => match default_namespace.render_string(&event) {
+            Ok(namespace) => namespace,
+            Err(missing_keys) => {
+                warn!(
+                    message =
+                        "Keys in namespace template do not exist on the metric; dropping event.",
+                    ?missing_keys,
+                    internal_log_rate_secs = 30
+                );
+                return None;
+            }
+        },
+    };
+
+    let dimensions = dimensions
+        .iter()
+        .filter_map(|(name, template)| match template.render_string(&event) {
+            Ok(value) => Some((name.clone(), value)),
+            Err(missing_keys) => {
+                warn!(
+                    message = "Keys in dimension template do not exist on the metric; skipping dimension.",
+                    dimension = %name,
+                    ?missing_keys,
+                    internal_log_rate_secs = 30
+                );
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut metric = event.into_metric();
+    metric.series.name.namespace = None;
+    for (name, value) in dimensions {
+        metric.set_tag_value(name, value);
+    }
+
+    Some(PartitionInnerBuffer::new(Event::Metric(metric), namespace))
+}
+
+/// PutMetricData accepts up to 150 distinct values for each metric, so samples
+/// with the same value are merged and the values are split into chunks.
+fn encode_distribution(samples: &[Sample]) -> Vec<(Vec<f64>, Vec<f64>)> {
+    let mut samples = samples
+        .iter()
+        .filter(|sample| sample.rate > 0)
+        .map(|sample| (sample.value, f64::from(sample.rate)))
+        .collect::<Vec<_>>();
+    samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(samples.len());
+    for (value, count) in samples {
+        match merged.last_mut() {
+            Some(last) if last.0 == value => last.1 += count,
+            _ => merged.push((value, count)),
+        }
     }
+
+    merged
+        .chunks(MAX_DISTRIBUTION_VALUES)
+        .map(|chunk| chunk.iter().copied().unzip())
+        .collect()
 }
 
 struct AwsCloudwatchMetricNormalize;
@@ -222,7 +307,7 @@ impl MetricNormalize for AwsCloudwatchMetricNormalize {
     }
 }
 
-impl Service<PartitionInnerBuffer<Vec<Metric>, String>> for CloudWatchMetricsSvc {
+impl Service<PutMetricDataInput> for CloudWatchMetricsSvc {
     type Response = ();
     type Error = RusotoError<PutMetricDataError>;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
@@ -231,24 +316,50 @@ impl Service<PartitionInnerBuffer<Vec<Metric>, String>> for CloudWatchMetricsSvc
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, items: PartitionInnerBuffer<Vec<Metric>, String>) -> Self::Future {
-        let (items, namespace) = items.into_parts();
-        let metric_data = self.encode_events(items);
-        if metric_data.is_empty() {
-            return future::ok(()).boxed();
-        }
-
-        let input = PutMetricDataInput {
-            namespace,
-            metric_data,
-        };
-
+    fn call(&mut self, input: PutMetricDataInput) -> Self::Future {
         debug!(message = "Sending data.", input = ?input);
         let client = self.client.clone();
         Box::pin(async move { client.put_metric_data(input).await })
     }
 }
 
+/// Sends the metrics of a batch in as many requests as the limits of a single
+/// PutMetricData request require.
+pub struct CloudWatchMetricsPartitionSvc<S> {
+    svc: S,
+}
+
+impl<S> Service<PartitionInnerBuffer<Vec<Metric>, String>> for CloudWatchMetricsPartitionSvc<S>
+where
+    S: Service<PutMetricDataInput, Response = ()> + Clone + Send + 'static,
+    S::Error: Into<crate::Error>,
+    S::Future: Send + 'static,
+{
+    type Response = ();
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, items: PartitionInnerBuffer<Vec<Metric>, String>) -> Self::Future {
+        let (items, namespace) = items.into_parts();
+        let requests = encode_events(items)
+            .chunks(MAX_METRICS_PER_REQUEST)
+            .map(|metric_data| {
+                let input = PutMetricDataInput {
+                    namespace: namespace.clone(),
+                    metric_data: metric_data.to_vec(),
+                };
+                self.svc.clone().oneshot(input).map_err(Into::into)
+            })
+            .collect::<Vec<_>>();
+
+        future::try_join_all(requests).map_ok(|_| ()).boxed()
+    }
+}
+
 #[derive(Debug, Clone)]
 struct CloudWatchMetricsRetryLogic;
 
@@ -285,24 +396,42 @@ mod tests {
     use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
     use chrono::offset::TimeZone;
     use pretty_assertions::assert_eq;
+    use std::{
+        convert::TryFrom,
+        sync::{Arc, Mutex},
+    };
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<CloudWatchMetricsSinkConfig>();
     }
 
-    fn config() -> CloudWatchMetricsSinkConfig {
-        CloudWatchMetricsSinkConfig {
-            default_namespace: "vector".into(),
-            region: RegionOrEndpoint::with_endpoint("local".to_owned()),
-            ..Default::default()
-        }
-    }
+    #[tokio::test]
+    async fn splits_batches_into_requests() {
+        let sizes = Arc::new(Mutex::new(Vec::new()));
+        let svc = {
+            let sizes = Arc::clone(&sizes);
+            tower::service_fn(move |input: PutMetricDataInput| {
+                sizes.lock().unwrap().push(input.metric_data.len());
+                future::ok::<_, RusotoError<PutMetricDataError>>(())
+            })
+        };
 
-    fn svc() -> CloudWatchMetricsSvc {
-        let config = config();
-        let client = config.create_client().unwrap();
-        CloudWatchMetricsSvc { client, config }
+        let metrics = (0..1500)
+            .map(|i| {
+                Metric::new(
+                    format!("counter-{}", i),
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 1.0 },
+                )
+            })
+            .collect();
+        CloudWatchMetricsPartitionSvc { svc }
+            .call(PartitionInnerBuffer::new(metrics, "vector".to_owned()))
+            .await
+            .unwrap();
+
+        assert_eq!(*sizes.lock().unwrap(), vec![1_000, 500]);
     }
 
     #[test]
@@ -337,7 +466,7 @@ mod tests {
         ];
 
         assert_eq!(
-            svc().encode_events(events),
+            encode_events(events),
             vec![
                 MetricDatum {
                     metric_name: "exception_total".into(),
@@ -373,7 +502,7 @@ mod tests {
         )];
 
         assert_eq!(
-            svc().encode_events(events),
+            encode_events(events),
             vec![MetricDatum {
                 metric_name: "temperature".into(),
                 value: Some(10.0),
@@ -394,7 +523,7 @@ mod tests {
         )];
 
         assert_eq!(
-            svc().encode_events(events),
+            encode_events(events),
             vec![MetricDatum {
                 metric_name: "latency".into(),
                 values: Some(vec![11.0, 12.0]),
@@ -415,7 +544,7 @@ mod tests {
         )];
 
         assert_eq!(
-            svc().encode_events(events),
+            encode_events(events),
             vec![MetricDatum {
                 metric_name: "users".into(),
                 value: Some(2.0),
@@ -423,6 +552,101 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn encode_distribution_merges_and_splits_values() {
+        let mut samples = (0..200)
+            .map(|i| Sample {
+                value: i as f64,
+                rate: 1,
+            })
+            .collect::<Vec<_>>();
+        samples.push(Sample {
+            value: 0.0,
+            rate: 2,
+        });
+        samples.push(Sample {
+            value: 300.0,
+            rate: 0,
+        });
+
+        let chunks = encode_distribution(&samples);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].0.len(), 150);
+        assert_eq!(chunks[1].0.len(), 50);
+        assert_eq!(chunks[0].0[0], 0.0);
+        assert_eq!(chunks[0].1[0], 3.0);
+        assert_eq!(chunks[1].0[49], 199.0);
+    }
+
+    #[test]
+    fn partition_templates() {
+        let dimensions = vec![(
+            "service".to_owned(),
+            Template::try_from("{{ tags.app }}-{{ name }}").unwrap(),
+        )]
+        .into_iter()
+        .collect();
+        let default_namespace = Template::try_from("ns-{{ tags.app }}").unwrap();
+
+        let event = Event::Metric(
+            Metric::new(
+                "requests".into(),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(
+                vec![("app".to_owned(), "api".to_owned())]
+                    .into_iter()
+                    .collect(),
+            )),
+        );
+
+        let (event, namespace) = partition(event, &default_namespace, &dimensions)
+            .unwrap()
+            .into_parts();
+
+        assert_eq!(namespace, "ns-api");
+        assert_eq!(
+            event.as_metric().tag_value("service"),
+            Some("api-requests".to_owned())
+        );
+    }
+
+    #[test]
+    fn partition_metric_namespace() {
+        let default_namespace = Template::try_from("ns-{{ tags.app }}").unwrap();
+
+        let event = Event::Metric(
+            Metric::new(
+                "requests".into(),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(Some("service".into())),
+        );
+
+        let (event, namespace) = partition(event, &default_namespace, &IndexMap::new())
+            .unwrap()
+            .into_parts();
+
+        assert_eq!(namespace, "service");
+        assert_eq!(event.as_metric().namespace(), None);
+    }
+
+    #[test]
+    fn partition_missing_namespace_key() {
+        let default_namespace = Template::try_from("ns-{{ tags.app }}").unwrap();
+
+        let event = Event::Metric(Metric::new(
+            "requests".into(),
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        assert!(partition(event, &default_namespace, &IndexMap::new()).is_none());
+    }
 }
 
 #[cfg(feature = "aws-cloudwatch-metrics-integration-tests")]
@@ -432,10 +656,11 @@ mod integration_tests {
     use crate::{event::metric::StatisticKind, event::MetricKind, test_util::random_string, Event};
     use chrono::offset::TimeZone;
     use rand::seq::SliceRandom;
+    use std::convert::TryFrom;
 
     fn config() -> CloudWatchMetricsSinkConfig {
         CloudWatchMetricsSinkConfig {
-            default_namespace: "vector".into(),
+            default_namespace: Template::try_from("vector").unwrap(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".to_owned()),
             ..Default::default()
         }
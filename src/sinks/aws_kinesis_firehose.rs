@@ -6,7 +6,8 @@ use crate::{
         encoding::{EncodingConfig, EncodingConfiguration},
         retries::RetryLogic,
         sink::Response,
-        BatchConfig, BatchSettings, Compression, EncodedLength, TowerRequestConfig, VecBuffer,
+        BatchConfig, BatchSettings, Compression, EncodedEvent, EncodedLength, TowerRequestConfig,
+        VecBuffer,
     },
 };
 use bytes::Bytes;
@@ -162,7 +163,9 @@ impl KinesisFirehoseService {
                 cx.acker(),
             )
             .sink_map_err(|error| error!(message = "Fatal kinesis firehose sink error.", %error))
-            .with_flat_map(move |e| stream::iter(encode_event(e, &encoding)).map(Ok));
+            .with_flat_map(move |e| {
+                stream::iter(EncodedEvent::encode(e, |e| encode_event(e, &encoding))).map(Ok)
+            });
 
         Ok(sink)
     }
@@ -7,7 +7,8 @@ use crate::{
         encoding::{EncodingConfig, EncodingConfiguration},
         retries::RetryLogic,
         sink::Response,
-        BatchConfig, BatchSettings, Compression, EncodedLength, TowerRequestConfig, VecBuffer,
+        BatchConfig, BatchSettings, Compression, EncodedEvent, EncodedLength, TowerRequestConfig,
+        VecBuffer,
     },
 };
 use bytes::Bytes;
@@ -167,7 +168,9 @@ impl KinesisService {
             )
             .sink_map_err(|error| error!(message = "Fatal kinesis streams sink error.", %error))
             .with_flat_map(move |e| {
-                stream::iter(encode_event(e, &partition_key_field, &encoding)).map(Ok)
+                let encoded =
+                    EncodedEvent::encode(e, |e| encode_event(e, &partition_key_field, &encoding));
+                stream::iter(encoded).map(Ok)
             });
 
         Ok(sink)
@@ -6,8 +6,9 @@ use crate::{
         encoding::{EncodingConfig, EncodingConfiguration},
         retries::RetryLogic,
         sink::Response,
-        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, PartitionBatchSink,
-        PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
+        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, EncodedEvent,
+        PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt,
+        TowerRequestConfig,
     },
     template::Template,
     Event,
@@ -231,7 +232,10 @@ impl S3SinkConfig {
         let buffer = PartitionBuffer::new(Buffer::new(batch.size, self.compression));
 
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok))
+            .with_flat_map(move |e| {
+                let encoded = EncodedEvent::encode(e, |e| encode_event(e, &key_prefix, &encoding));
+                stream::iter(encoded).map(Ok)
+            })
             .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
         Ok(super::VectorSink::Sink(Box::new(sink)))
@@ -390,7 +394,7 @@ impl RetryLogic for S3RetryLogic {
     }
 }
 
-fn encode_event(
+pub fn encode_event(
     mut event: Event,
     key_prefix: &Template,
     encoding: &EncodingConfig<Encoding>,
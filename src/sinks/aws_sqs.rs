@@ -6,7 +6,7 @@ use crate::{
         encoding::{EncodingConfig, EncodingConfiguration},
         retries::RetryLogic,
         sink::Response,
-        BatchSettings, EncodedLength, TowerRequestConfig, VecBuffer,
+        BatchSettings, EncodedEvent, EncodedLength, TowerRequestConfig, VecBuffer,
     },
     template::{Template, TemplateError},
     Event,
@@ -177,7 +177,10 @@ impl SqsSink {
             )
             .sink_map_err(|error| error!(message = "Fatal sqs sink error.", %error))
             .with_flat_map(move |event| {
-                stream::iter(encode_event(event, &encoding, message_group_id.as_ref())).map(Ok)
+                let encoded = EncodedEvent::encode(event, |event| {
+                    encode_event(event, &encoding, message_group_id.as_ref())
+                });
+                stream::iter(encoded).map(Ok)
             });
 
         Ok(sink)
@@ -4,7 +4,7 @@ use crate::{
     http::{Auth, HttpClient, MaybeAuth},
     sinks::util::{
         encoding::{EncodingConfigWithDefault, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpRetryLogic, HttpSink},
+        http::{encode_request_bodies, BatchedHttpSink, HttpRetryLogic, HttpSink},
         retries::{RetryAction, RetryLogic},
         BatchConfig, BatchSettings, Buffer, Compression, TowerRequestConfig, UriSerde,
     },
@@ -104,6 +104,26 @@ impl SinkConfig for ClickhouseConfig {
     fn sink_type(&self) -> &'static str {
         "clickhouse"
     }
+
+    async fn encode_test_payloads(
+        &self,
+        events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        let encode = async {
+            let config = ClickhouseConfig {
+                compression: Compression::None,
+                ..self.clone()
+            };
+            let batch = BatchSettings::default()
+                .bytes(bytesize::mib(10u64))
+                .timeout(1)
+                .parse_config(config.batch)?;
+
+            encode_request_bodies(&config, Buffer::new(batch.size, Compression::None), events).await
+        };
+
+        Some(encode.await)
+    }
 }
 
 #[async_trait::async_trait]
@@ -7,8 +7,8 @@ use crate::{
             buffer::metrics::{MetricNormalize, MetricSet, MetricsBuffer},
             encode_namespace,
             http::{HttpBatchService, HttpRetryLogic},
-            BatchConfig, BatchSettings, PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer,
-            TowerRequestConfig,
+            BatchConfig, BatchSettings, EncodedEvent, PartitionBatchSink, PartitionBuffer,
+            PartitionInnerBuffer, TowerRequestConfig,
         },
         Healthcheck, HealthcheckError, UriParseError, VectorSink,
     },
@@ -193,7 +193,9 @@ impl SinkConfig for DatadogConfig {
             .sink_map_err(|error| error!(message = "Fatal datadog metric sink error.", %error))
             .with_flat_map(move |event: Event| {
                 let ep = DatadogEndpoint::from_metric(&event);
-                stream::iter(Some(PartitionInnerBuffer::new(event, ep))).map(Ok)
+                let encoded =
+                    EncodedEvent::encode(event, |event| Some(PartitionInnerBuffer::new(event, ep)));
+                stream::iter(encoded).map(Ok)
             });
 
         Ok((VectorSink::Sink(Box::new(svc_sink)), healthcheck))
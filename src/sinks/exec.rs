@@ -0,0 +1,385 @@
+use crate::{
+    buffers::Acker,
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
+    internal_events::{
+        ExecCommandExited, ExecCommandSpawnFailed, ExecCommandStarted, ExecCommandStderr,
+        ExecEventProcessed, ExecFieldNotFound, ExecWriteFailed,
+    },
+    sinks::util::{
+        encoding::{EncodingConfig, EncodingConfiguration},
+        retries::ExponentialBackoff,
+        StreamSink,
+    },
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{
+    future,
+    stream::{BoxStream, StreamExt},
+    FutureExt,
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{collections::HashMap, io, path::PathBuf, process::Stdio, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::{Child, ChildStderr, ChildStdin, Command},
+    time::delay_for,
+};
+use tracing::Span;
+use tracing_futures::Instrument;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("A command must be given."))]
+    EmptyCommand,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExecSinkConfig {
+    pub command: Vec<String>,
+    pub working_directory: Option<PathBuf>,
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+    pub encoding: EncodingConfig<Encoding>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    Text,
+    Json,
+}
+
+inventory::submit! {
+    SinkDescription::new::<ExecSinkConfig>("exec")
+}
+
+impl GenerateConfig for ExecSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"command = ["/usr/local/bin/deliver.sh"]
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl SinkConfig for ExecSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if self.command.is_empty() {
+            return Err(BuildError::EmptyCommand.into());
+        }
+
+        let sink = ExecSink {
+            config: self.clone(),
+            acker: cx.acker(),
+        };
+
+        // Spawning the command can have side effects, so there is nothing to
+        // check ahead of time.
+        Ok((
+            super::VectorSink::Stream(Box::new(sink)),
+            future::ok(()).boxed(),
+        ))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> Option<Bytes> {
+    encoding.apply_rules(&mut event);
+    let mut encoded = match event {
+        Event::Log(log) => match encoding.codec() {
+            Encoding::Json => serde_json::to_string(&log)
+                .map_err(|error| {
+                    error!(message = "Error encoding json.", %error);
+                })
+                .ok()?,
+            Encoding::Text => {
+                let field = log_schema().message_key();
+                match log.get(field) {
+                    Some(v) => v.to_string_lossy(),
+                    None => {
+                        emit!(ExecFieldNotFound {
+                            missing_field: field,
+                        });
+                        return None;
+                    }
+                }
+            }
+        },
+        Event::Metric(metric) => match encoding.codec() {
+            Encoding::Json => serde_json::to_string(&metric)
+                .map_err(|error| {
+                    error!(message = "Error encoding json.", %error);
+                })
+                .ok()?,
+            Encoding::Text => format!("{}", metric),
+        },
+    };
+
+    encoded.push('\n');
+    Some(encoded.into())
+}
+
+struct ExecSink {
+    config: ExecSinkConfig,
+    acker: Acker,
+}
+
+/// A running instance of the command.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+}
+
+impl ExecSink {
+    fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+
+    fn spawn(&self) -> io::Result<Process> {
+        let mut command = Command::new(&self.config.command[0]);
+        command
+            .args(&self.config.command[1..])
+            .envs(&self.config.environment)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        if let Some(directory) = &self.config.working_directory {
+            command.current_dir(directory);
+        }
+
+        let mut child = command.spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(capture_stderr(stderr).instrument(Span::current()));
+        }
+
+        emit!(ExecCommandStarted {
+            command: &self.config.command[0],
+            pid: child.id(),
+        });
+
+        Ok(Process { child, stdin })
+    }
+
+    async fn stop(&self, mut process: Process, kill: bool) {
+        drop(process.stdin);
+        if kill {
+            // The command may still be running if it only closed its input.
+            let _ = process.child.kill();
+        }
+
+        match process.child.await {
+            Ok(status) => emit!(ExecCommandExited {
+                command: &self.config.command[0],
+                status
+            }),
+            Err(error) => error!(message = "Unable to wait for command to exit.", %error),
+        }
+    }
+}
+
+/// Forward every line the command writes to its standard error to the
+/// internal logs.
+async fn capture_stderr(stderr: ChildStderr) {
+    let mut lines = BufReader::new(stderr).lines();
+    while let Some(Ok(line)) = lines.next().await {
+        emit!(ExecCommandStderr { line: &line });
+    }
+}
+
+#[async_trait]
+impl StreamSink for ExecSink {
+    async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoding = self.config.encoding.clone();
+        let mut input = input.map(|event| encode_event(event, &encoding));
+
+        let mut backoff = Self::fresh_backoff();
+        let mut process: Option<Process> = None;
+        let mut pending: Option<Bytes> = None;
+
+        loop {
+            let buf = match pending.take() {
+                Some(buf) => buf,
+                None => match input.next().await {
+                    Some(Some(buf)) => buf,
+                    Some(None) => {
+                        self.acker.ack(1);
+                        continue;
+                    }
+                    None => break,
+                },
+            };
+
+            if process.is_none() {
+                match self.spawn() {
+                    Ok(spawned) => process = Some(spawned),
+                    Err(error) => {
+                        emit!(ExecCommandSpawnFailed {
+                            command: &self.config.command[0],
+                            error
+                        });
+                        pending = Some(buf);
+                        delay_for(backoff.next().unwrap()).await;
+                        continue;
+                    }
+                }
+            }
+
+            let running = process.as_mut().expect("process was spawned");
+            // Writing blocks while the pipe is full, which holds back the
+            // input until the command catches up.
+            match running.stdin.write_all(&buf).await {
+                Ok(()) => {
+                    self.acker.ack(1);
+                    emit!(ExecEventProcessed {
+                        byte_size: buf.len(),
+                    });
+                    backoff = Self::fresh_backoff();
+                }
+                Err(error) => {
+                    emit!(ExecWriteFailed { error });
+                    pending = Some(buf);
+                    if let Some(stopped) = process.take() {
+                        self.stop(stopped, true).await;
+                    }
+                    delay_for(backoff.next().unwrap()).await;
+                }
+            }
+        }
+
+        // Closing the input lets the command finish its work and exit.
+        if let Some(running) = process.take() {
+            self.stop(running, false).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{lines_from_file, random_lines_with_stream, temp_file, trace_init};
+    use futures::stream;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecSinkConfig>();
+    }
+
+    #[test]
+    fn encodes_raw_logs() {
+        let event = Event::from("foo");
+        assert_eq!(
+            Some(Bytes::from("foo\n")),
+            encode_event(event, &EncodingConfig::from(Encoding::Text))
+        );
+    }
+
+    #[test]
+    fn encodes_log_events() {
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("a", "0");
+
+        assert_eq!(
+            Some(Bytes::from("{\"a\":\"0\"}\n")),
+            encode_event(event, &EncodingConfig::from(Encoding::Json))
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_command() {
+        let config: ExecSinkConfig = toml::from_str(
+            r#"command = []
+            encoding.codec = "text""#,
+        )
+        .unwrap();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn writes_to_command() {
+        trace_init();
+
+        let path = temp_file();
+        let config = ExecSinkConfig {
+            command: vec!["sh".into(), "-c".into(), "cat > \"$OUTPUT\"".into()],
+            working_directory: None,
+            environment: vec![("OUTPUT".to_owned(), path.to_string_lossy().into_owned())]
+                .into_iter()
+                .collect(),
+            encoding: Encoding::Text.into(),
+        };
+
+        let mut sink = ExecSink {
+            config,
+            acker: Acker::Null,
+        };
+        let (input, _) = random_lines_with_stream(100, 64);
+
+        let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
+        sink.run(events).await.unwrap();
+
+        assert_eq!(lines_from_file(path), input);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn restarts_command() {
+        trace_init();
+
+        let path = temp_file();
+        // Every instance of the command handles a single event and exits.
+        let config = ExecSinkConfig {
+            command: vec![
+                "sh".into(),
+                "-c".into(),
+                "read line; echo \"$line\" >> \"$OUTPUT\"".into(),
+            ],
+            working_directory: None,
+            environment: vec![("OUTPUT".to_owned(), path.to_string_lossy().into_owned())]
+                .into_iter()
+                .collect(),
+            encoding: Encoding::Text.into(),
+        };
+
+        let mut sink = ExecSink {
+            config,
+            acker: Acker::Null,
+        };
+
+        // Give every instance time to exit before the next event arrives.
+        let events = stream::iter(vec!["first", "second"])
+            .then(|line| async move {
+                delay_for(Duration::from_millis(200)).await;
+                Event::from(line)
+            })
+            .boxed();
+        sink.run(events).await.unwrap();
+
+        assert_eq!(lines_from_file(path), vec!["first", "second"]);
+    }
+}
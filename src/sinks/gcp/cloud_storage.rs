@@ -7,8 +7,9 @@ use crate::{
         util::{
             encoding::{EncodingConfig, EncodingConfiguration},
             retries::{RetryAction, RetryLogic},
-            BatchConfig, BatchSettings, Buffer, Compression, Concurrency, PartitionBatchSink,
-            PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
+            BatchConfig, BatchSettings, Buffer, Compression, Concurrency, EncodedEvent,
+            PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -228,7 +229,10 @@ impl GcsSink {
 
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
             .sink_map_err(|error| error!(message = "Fatal gcp_cloud_storage error.", %error))
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok));
+            .with_flat_map(move |e| {
+                let encoded = EncodedEvent::encode(e, |e| encode_event(e, &key_prefix, &encoding));
+                stream::iter(encoded).map(Ok)
+            });
 
         Ok(VectorSink::Sink(Box::new(sink)))
     }
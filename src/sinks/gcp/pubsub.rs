@@ -20,6 +20,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use snafu::{ResultExt, Snafu};
 
+const WRAPPER_OVERHEAD: usize = r#"{"messages":[]}"#.len();
+
 #[derive(Debug, Snafu)]
 enum HealthcheckError {
     #[snafu(display("Configured topic not found"))]
@@ -93,7 +95,7 @@ impl SinkConfig for PubsubConfig {
 
         let sink = BatchedHttpSink::new(
             sink,
-            JsonArrayBuffer::new(batch_settings.size),
+            JsonArrayBuffer::with_wrapper(batch_settings.size, WRAPPER_OVERHEAD),
             request_settings,
             batch_settings.timeout,
             client,
@@ -132,7 +132,7 @@ impl SinkConfig for StackdriverConfig {
 
         let sink = BatchedHttpSink::new(
             sink,
-            JsonArrayBuffer::new(batch.size),
+            JsonArrayBuffer::with_wrapper(batch.size, self.request_body(json!([])).len()),
             request,
             batch.timeout,
             client,
@@ -184,16 +184,7 @@ impl HttpSink for StackdriverSink {
     }
 
     async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Vec<u8>>> {
-        let events = serde_json::json!({
-            "log_name": self.config.log_name(),
-            "entries": events,
-            "resource": {
-                "type": self.config.resource.type_,
-                "labels": self.config.resource.labels,
-            }
-        });
-
-        let body = serde_json::to_vec(&events).unwrap();
+        let body = self.config.request_body(events);
 
         let mut request = Request::post(URI.clone())
             .header("Content-Type", "application/json")
@@ -265,6 +256,19 @@ impl StackdriverConfig {
             Project(project) => format!("projects/{}/logs/{}", project, self.log_id),
         }
     }
+
+    fn request_body<T: Serialize>(&self, entries: T) -> Vec<u8> {
+        let body = serde_json::json!({
+            "log_name": self.log_name(),
+            "entries": entries,
+            "resource": {
+                "type": self.resource.type_,
+                "labels": self.resource.labels,
+            }
+        });
+
+        serde_json::to_vec(&body).unwrap()
+    }
 }
 
 #[cfg(test)]
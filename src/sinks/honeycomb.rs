@@ -3,7 +3,7 @@ use crate::{
     event::{Event, Value},
     http::HttpClient,
     sinks::util::{
-        http::{BatchedHttpSink, HttpSink},
+        http::{encode_request_bodies, BatchedHttpSink, HttpSink},
         BatchConfig, BatchSettings, BoxedRawValue, JsonArrayBuffer, TowerRequestConfig,
     },
 };
@@ -82,6 +82,22 @@ impl SinkConfig for HoneycombConfig {
     fn sink_type(&self) -> &'static str {
         "honeycomb"
     }
+
+    async fn encode_test_payloads(
+        &self,
+        events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        let encode = async {
+            let batch = BatchSettings::default()
+                .bytes(bytesize::kib(100u64))
+                .timeout(1)
+                .parse_config(self.batch)?;
+
+            encode_request_bodies(self, JsonArrayBuffer::new(batch.size), events).await
+        };
+
+        Some(encode.await)
+    }
 }
 
 #[async_trait::async_trait]
@@ -6,8 +6,9 @@ use crate::{
     sinks::util::{
         buffer::compression::GZIP_DEFAULT,
         encoding::{EncodingConfig, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpSink, RequestConfig},
-        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, TowerRequestConfig, UriSerde,
+        http::{encode_request_bodies, BatchedHttpSink, HttpSink, RequestConfig},
+        BatchConfig, BatchSettings, BatchSize, Buffer, Compression, Concurrency,
+        TowerRequestConfig, UriSerde,
     },
     tls::{TlsOptions, TlsSettings},
 };
@@ -143,15 +144,9 @@ impl SinkConfig for HttpSinkConfig {
             .parse_config(config.batch)?;
         let request = config.request.tower.unwrap_with(&REQUEST_DEFAULTS);
 
-        let sink = BatchedHttpSink::new(
-            config,
-            Buffer::new(batch.size, Compression::None),
-            request,
-            batch.timeout,
-            client,
-            cx.acker(),
-        )
-        .sink_map_err(|error| error!(message = "Fatal HTTP sink error.", %error));
+        let buffer = config.buffer(batch.size);
+        let sink = BatchedHttpSink::new(config, buffer, request, batch.timeout, client, cx.acker())
+            .sink_map_err(|error| error!(message = "Fatal HTTP sink error.", %error));
 
         let sink = super::VectorSink::Sink(Box::new(sink));
 
@@ -165,6 +160,38 @@ impl SinkConfig for HttpSinkConfig {
     fn sink_type(&self) -> &'static str {
         "http"
     }
+
+    async fn encode_test_payloads(
+        &self,
+        events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        let encode = async {
+            let config = HttpSinkConfig {
+                compression: Compression::None,
+                ..self.clone()
+            };
+            let batch = BatchSettings::default()
+                .bytes(bytesize::mib(10u64))
+                .timeout(1)
+                .parse_config(config.batch)?;
+
+            encode_request_bodies(&config, config.buffer(batch.size), events).await
+        };
+
+        Some(encode.await)
+    }
+}
+
+impl HttpSinkConfig {
+    /// The JSON encoding sends the events within brackets, in place of the
+    /// separator following the last event.
+    fn buffer(&self, size: BatchSize<Buffer>) -> Buffer {
+        let overhead_bytes = match self.encoding.codec() {
+            Encoding::Json => 1,
+            Encoding::Text | Encoding::Ndjson => 0,
+        };
+        Buffer::with_overhead(size, Compression::None, overhead_bytes)
+    }
 }
 
 #[async_trait::async_trait]
@@ -1,7 +1,7 @@
 use super::{default_host_key, Encoding};
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    sinks::splunk_hec::HecSinkConfig,
+    sinks::splunk_hec::{EndpointTarget, HecSinkConfig},
     sinks::util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
     sinks::{Healthcheck, VectorSink},
     template::Template,
@@ -87,6 +87,8 @@ impl HumioLogsConfig {
             index: None,
             sourcetype: self.event_type.clone(),
             source: self.source.clone(),
+            endpoint_target: EndpointTarget::Event,
+            channel: None,
             encoding: self.encoding.clone().into_encoding(),
             compression: self.compression,
             batch: self.batch,
@@ -128,7 +130,7 @@ mod tests {
         .unwrap();
         let config = config.build_hec_config();
 
-        let bytes = config.encode_event(event).unwrap();
+        let (bytes, _) = config.encode_event(event).unwrap().into_parts();
         let hec_event = serde_json::from_slice::<HecEventJson>(&bytes[..]).unwrap();
 
         let now = Utc::now().timestamp_millis() as f64 / 1000f64;
@@ -1,6 +1,9 @@
 use super::{default_host_key, logs::HumioLogsConfig, Encoding};
 use crate::{
-    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription, TransformConfig},
+    config::{
+        DataType, GenerateConfig, GlobalOptions, SinkConfig, SinkContext, SinkDescription,
+        TransformConfig,
+    },
     sinks::util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
     sinks::{Healthcheck, VectorSink},
     template::Template,
@@ -66,7 +69,11 @@ impl GenerateConfig for HumioMetricsConfig {
 #[typetag::serde(name = "humio_metrics")]
 impl SinkConfig for HumioMetricsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let mut transform = self.transform.clone().build().await?;
+        let mut transform = self
+            .transform
+            .clone()
+            .build(&GlobalOptions::default())
+            .await?;
         let sink = HumioLogsConfig {
             token: self.token.clone(),
             endpoint: self.endpoint.clone(),
@@ -12,14 +12,14 @@ use crate::{
             encode_namespace,
             http::{HttpBatchService, HttpRetryLogic},
             statistic::{validate_quantiles, DistributionStatistic},
-            BatchConfig, BatchSettings, TowerRequestConfig,
+            BatchConfig, BatchSettings, EncodedEvent, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
     tls::{TlsOptions, TlsSettings},
 };
 use bytes::Bytes;
-use futures::{future::BoxFuture, SinkExt};
+use futures::{future::BoxFuture, stream, SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -144,7 +144,8 @@ impl InfluxDBSvc {
                 batch.timeout,
                 cx.acker(),
             )
-            .sink_map_err(|error| error!(message = "Fatal influxdb sink error.", %error));
+            .sink_map_err(|error| error!(message = "Fatal influxdb sink error.", %error))
+            .with_flat_map(|event| stream::iter(EncodedEvent::encode(event, Some)).map(Ok));
 
         Ok(VectorSink::Sink(Box::new(sink)))
     }
@@ -1,14 +1,18 @@
 use crate::{
     buffers::Acker,
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
-    kafka::{KafkaAuthConfig, KafkaCompression},
+    internal_events::KafkaDeliveryFailed,
+    kafka::{
+        KafkaAuthConfig, KafkaCompression, KafkaStatisticsContext, KafkaTokenRefresh,
+        STATISTICS_INTERVAL_MS,
+    },
     serde::to_string,
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
         BatchConfig,
     },
     template::{Template, TemplateError},
-    Event,
+    Event, Value,
 };
 use futures::{
     channel::oneshot::Canceled, future::BoxFuture, ready, stream::FuturesUnordered, FutureExt,
@@ -17,13 +21,14 @@ use futures::{
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
     error::{KafkaError, RDKafkaError},
+    message::OwnedHeaders,
     producer::{DeliveryFuture, FutureProducer, FutureRecord},
     ClientConfig,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     pin::Pin,
     sync::Arc,
@@ -40,6 +45,12 @@ enum BuildError {
     KafkaCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateError },
+    #[snafu(display("invalid key template: {}", source))]
+    KeyTemplate { source: TemplateError },
+    #[snafu(display("invalid template for header {:?}: {}", name, source))]
+    HeaderTemplate { name: String, source: TemplateError },
+    #[snafu(display("`key` and `key_field` can't both be set"))]
+    KeyConflict,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -47,6 +58,13 @@ pub struct KafkaSinkConfig {
     bootstrap_servers: String,
     topic: String,
     key_field: Option<String>,
+    /// Template of the message key, as an alternative to `key_field`.
+    key: Option<String>,
+    /// Templates of message headers, by header name.
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    /// Field of the event holding a map of message headers, by header name.
+    headers_field: Option<String>,
     encoding: EncodingConfig<Encoding>,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
@@ -79,10 +97,18 @@ pub enum Encoding {
 }
 
 pub struct KafkaSink {
-    producer: Arc<FutureProducer>,
+    producer: Arc<FutureProducer<KafkaStatisticsContext>>,
+    /// Taken once the sink runs, to tag the statistics of the producer with
+    /// the span of the sink.
+    context: Option<KafkaStatisticsContext>,
     topic: Template,
     key_field: Option<String>,
+    key: Option<Template>,
+    headers: Vec<(String, Template)>,
+    headers_field: Option<String>,
     encoding: EncodingConfig<Encoding>,
+    /// Keeps the SASL token of the producer fresh, if it has one that expires.
+    _token_refresh: Option<KafkaTokenRefresh>,
     delivery_fut: FuturesUnordered<BoxFuture<'static, (usize, Result<DeliveryFuture, KafkaError>)>>,
     in_flight: FuturesUnordered<
         BoxFuture<'static, (usize, Result<Result<(i32, i64), KafkaError>, Canceled>)>,
@@ -152,6 +178,10 @@ impl KafkaSinkConfig {
 
         // All batch options are producer only.
         if kafka_role == KafkaRole::Producer {
+            // Statistics are only reported for the producer, the consumer of
+            // the healthcheck is short-lived.
+            client_config.set("statistics.interval.ms", STATISTICS_INTERVAL_MS);
+
             if let Some(value) = self.batch.timeout_secs {
                 // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                 // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -224,12 +254,45 @@ impl KafkaSinkConfig {
 
 impl KafkaSink {
     fn new(config: KafkaSinkConfig, acker: Acker) -> crate::Result<Self> {
+        if config.key.is_some() && config.key_field.is_some() {
+            return Err(BuildError::KeyConflict.into());
+        }
+        let key = config
+            .key
+            .as_deref()
+            .map(Template::try_from)
+            .transpose()
+            .context(KeyTemplate)?;
+        let headers = config
+            .headers
+            .iter()
+            .map(|(name, template)| {
+                let template = Template::try_from(template.as_str())
+                    .context(HeaderTemplate { name: name.clone() })?;
+                Ok((name.clone(), template))
+            })
+            .collect::<Result<_, BuildError>>()?;
+
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
-        let producer = producer_config.create().context(KafkaCreateFailed)?;
+        let context = KafkaStatisticsContext::default();
+        let producer = Arc::new(
+            producer_config
+                .create_with_context(context.clone())
+                .context(KafkaCreateFailed)?,
+        );
+        let token_refresh = config
+            .auth
+            .token_provider()?
+            .map(|provider| KafkaTokenRefresh::spawn(provider, Arc::clone(&producer)));
         Ok(KafkaSink {
-            producer: Arc::new(producer),
+            producer,
+            context: Some(context),
+            _token_refresh: token_refresh,
             topic: Template::try_from(config.topic).context(TopicTemplate)?,
             key_field: config.key_field,
+            key,
+            headers,
+            headers_field: config.headers_field,
             encoding: config.encoding,
             delivery_fut: FuturesUnordered::new(),
             in_flight: FuturesUnordered::new(),
@@ -265,6 +328,10 @@ impl Sink<Event> for KafkaSink {
     type Error = ();
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(context) = self.context.take() {
+            context.use_current_span();
+        }
+
         match self.poll_delivery_fut(cx) {
             Poll::Pending if self.delivery_fut.len() >= SEND_RESULT_LIMIT => Poll::Pending,
             _ => Poll::Ready(Ok(())),
@@ -288,7 +355,21 @@ impl Sink<Event> for KafkaSink {
             Event::Metric(metric) => metric.data.timestamp.as_ref(),
         }
         .map(|ts| ts.timestamp_millis());
-        let (key, body) = encode_event(item, &self.key_field, &self.encoding);
+        let key = self.key.as_ref().map(|key| {
+            key.render(&item)
+                .map(|key| key.to_vec())
+                .unwrap_or_else(|missing_keys| {
+                    warn!(
+                        message = "Missing keys for message key, sending with an empty key.",
+                        ?missing_keys,
+                        internal_log_rate_secs = 30
+                    );
+                    Vec::new()
+                })
+        });
+        let headers = render_headers(&item, &self.headers, &self.headers_field);
+        let (field_key, body) = encode_event(item, &self.key_field, &self.encoding);
+        let key = key.unwrap_or(field_key);
 
         let seqno = self.seq_head;
         self.seq_head += 1;
@@ -299,6 +380,9 @@ impl Sink<Event> for KafkaSink {
             if let Some(timestamp) = timestamp_ms {
                 record = record.timestamp(timestamp);
             }
+            if let Some(headers) = headers {
+                record = record.headers(headers);
+            }
 
             let result = loop {
                 debug!(message = "Sending event.", count = 1);
@@ -335,7 +419,7 @@ impl Sink<Event> for KafkaSink {
                         Ok((partition, offset)) => {
                             trace!(message = "Produced message.", ?partition, ?offset)
                         }
-                        Err(error) => error!(message = "Kafka error.", %error),
+                        Err(error) => emit!(KafkaDeliveryFailed { error }),
                     };
 
                     this.pending_acks.insert(seqno);
@@ -380,8 +464,12 @@ async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
         }
     };
 
+    let consumer: BaseConsumer = client.create()?;
+    if let Some(provider) = config.auth.token_provider()? {
+        provider.refresh(&consumer).await?;
+    }
+
     tokio::task::spawn_blocking(move || {
-        let consumer: BaseConsumer = client.create().unwrap();
         let topic = topic.as_ref().map(|topic| &topic[..]);
 
         consumer
@@ -393,6 +481,50 @@ async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
     Ok(())
 }
 
+/// Returns the headers of the message of `event`, from the `headers_field` of the event along
+/// with the rendered `headers` templates, which take precedence. Headers whose template can't
+/// be rendered are left out.
+fn render_headers(
+    event: &Event,
+    headers: &[(String, Template)],
+    headers_field: &Option<String>,
+) -> Option<OwnedHeaders> {
+    let mut rendered = BTreeMap::new();
+
+    if let (Some(field), Event::Log(log)) = (headers_field, event) {
+        if let Some(Value::Map(map)) = log.get(field) {
+            for (name, value) in map {
+                rendered.insert(name.clone(), value.as_bytes().to_vec());
+            }
+        }
+    }
+
+    for (name, template) in headers {
+        match template.render(event) {
+            Ok(value) => {
+                rendered.insert(name.clone(), value.to_vec());
+            }
+            Err(missing_keys) => warn!(
+                message = "Missing keys for header, sending without it.",
+                header = %name,
+                ?missing_keys,
+                internal_log_rate_secs = 30,
+            ),
+        }
+    }
+
+    if rendered.is_empty() {
+        return None;
+    }
+    Some(
+        rendered
+            .iter()
+            .fold(OwnedHeaders::new(), |headers, (name, value)| {
+                headers.add(name, &value[..])
+            }),
+    )
+}
+
 fn encode_event(
     mut event: Event,
     key_field: &Option<String>,
@@ -432,7 +564,7 @@ fn encode_event(
 mod tests {
     use super::*;
     use crate::event::{Metric, MetricKind, MetricValue};
-    use std::collections::BTreeMap;
+    use rdkafka::message::Headers;
 
     #[test]
     fn generate_config() {
@@ -513,6 +645,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn kafka_render_headers() {
+        let mut event = Event::from("hello");
+        let mut from_field = BTreeMap::new();
+        from_field.insert("route".to_owned(), Value::from("payments"));
+        from_field.insert("trace".to_owned(), Value::from("abc"));
+        event.as_mut_log().insert("headers", from_field);
+        event.as_mut_log().insert("tenant", "acme");
+
+        let templates = vec![
+            ("route".to_owned(), Template::try_from("billing").unwrap()),
+            (
+                "tenant".to_owned(),
+                Template::try_from("{{ tenant }}").unwrap(),
+            ),
+            (
+                "missing".to_owned(),
+                Template::try_from("{{ missing }}").unwrap(),
+            ),
+        ];
+        let headers = render_headers(&event, &templates, &Some("headers".into())).unwrap();
+
+        let headers = (0..headers.count())
+            .filter_map(|index| headers.get(index))
+            .map(|(name, value)| (name.to_owned(), String::from_utf8_lossy(value).into_owned()))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(headers.len(), 3);
+        assert_eq!(headers["route"], "billing");
+        assert_eq!(headers["trace"], "abc");
+        assert_eq!(headers["tenant"], "acme");
+    }
+
+    #[test]
+    fn kafka_render_no_headers() {
+        let event = Event::from("hello");
+        assert!(render_headers(&event, &[], &Some("headers".into())).is_none());
+    }
+
+    #[test]
+    fn kafka_key_conflict() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            key_field = "user_id"
+            key = "{{ user_id }}"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        let (acker, _) = Acker::new_for_testing();
+        assert!(KafkaSink::new(config, acker).is_err());
+    }
+
     #[test]
     fn kafka_encode_event_log_apply_rules() {
         crate::test_util::trace_init();
@@ -564,6 +750,9 @@ mod integration_test {
             bootstrap_servers: "localhost:9091".into(),
             topic: topic.clone(),
             key_field: None,
+            key: None,
+            headers: HashMap::new(),
+            headers_field: None,
             encoding: EncodingConfig::from(Encoding::Text),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
@@ -769,6 +958,9 @@ mod integration_test {
             bootstrap_servers: server.to_string(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
+            key: None,
+            headers: HashMap::new(),
+            headers_field: None,
             encoding: EncodingConfig::from(Encoding::Text),
             batch: BatchConfig::default(),
             compression,
@@ -4,7 +4,7 @@ use crate::{
     http::{Auth, HttpClient},
     sinks::util::{
         encoding::{EncodingConfigWithDefault, EncodingConfiguration},
-        http::{HttpSink, PartitionHttpSink},
+        http::{encode_partitioned_request_bodies, HttpSink, PartitionHttpSink},
         BatchConfig, BatchSettings, BoxedRawValue, JsonArrayBuffer, PartitionBuffer,
         PartitionInnerBuffer, TowerRequestConfig, UriSerde,
     },
@@ -21,6 +21,7 @@ lazy_static::lazy_static! {
 }
 
 const PATH: &str = "/logs/ingest";
+const WRAPPER_OVERHEAD: usize = r#"{"lines":[]}"#.len();
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LogdnaConfig {
@@ -88,7 +89,10 @@ impl SinkConfig for LogdnaConfig {
 
         let sink = PartitionHttpSink::new(
             self.clone(),
-            PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size)),
+            PartitionBuffer::new(JsonArrayBuffer::with_wrapper(
+                batch_settings.size,
+                WRAPPER_OVERHEAD,
+            )),
             request_settings,
             batch_settings.timeout,
             client.clone(),
@@ -108,6 +112,27 @@ impl SinkConfig for LogdnaConfig {
     fn sink_type(&self) -> &'static str {
         "logdna"
     }
+
+    async fn encode_test_payloads(
+        &self,
+        events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        let encode = async {
+            let batch = BatchSettings::default()
+                .bytes(bytesize::mib(10u64))
+                .timeout(1)
+                .parse_config(self.batch)?;
+
+            encode_partitioned_request_bodies(
+                self,
+                PartitionBuffer::new(JsonArrayBuffer::with_wrapper(batch.size, WRAPPER_OVERHEAD)),
+                events,
+            )
+            .await
+        };
+
+        Some(encode.await)
+    }
 }
 
 #[derive(Hash, Eq, PartialEq, Clone)]
@@ -19,7 +19,7 @@ use crate::{
     sinks::util::{
         buffer::loki::{GlobalTimestamps, LokiBuffer, LokiEvent, LokiRecord, PartitionKey},
         encoding::{EncodingConfig, EncodingConfiguration},
-        http::{HttpSink, PartitionHttpSink},
+        http::{encode_partitioned_request_bodies, HttpSink, PartitionHttpSink},
         service::ConcurrencyOption,
         BatchConfig, BatchSettings, PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig,
         UriSerde,
@@ -147,9 +147,35 @@ impl SinkConfig for LokiConfig {
     fn sink_type(&self) -> &'static str {
         "loki"
     }
+
+    async fn encode_test_payloads(
+        &self,
+        events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        let encode = async {
+            let batch = BatchSettings::default()
+                .bytes(102_400)
+                .events(100_000)
+                .timeout(1)
+                .parse_config(self.batch)?;
+
+            encode_partitioned_request_bodies(
+                &LokiSink::new(self.clone()),
+                PartitionBuffer::new(LokiBuffer::new(
+                    batch.size,
+                    GlobalTimestamps::default(),
+                    self.out_of_order_action.clone(),
+                )),
+                events,
+            )
+            .await
+        };
+
+        Some(encode.await)
+    }
 }
 
-struct LokiSink {
+pub struct LokiSink {
     endpoint: UriSerde,
     encoding: EncodingConfig<Encoding>,
 
@@ -163,7 +189,7 @@ struct LokiSink {
 }
 
 impl LokiSink {
-    fn new(config: LokiConfig) -> Self {
+    pub fn new(config: LokiConfig) -> Self {
         Self {
             endpoint: config.endpoint,
             encoding: config.encoding,
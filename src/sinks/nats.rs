@@ -3,6 +3,7 @@ use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     emit,
     internal_events::{NatsEventMissingKeys, NatsEventSendFail, NatsEventSendSuccess},
+    nats::publish,
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
         StreamSink,
@@ -14,7 +15,7 @@ use async_trait::async_trait;
 use futures::{stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, io};
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -33,6 +34,8 @@ pub struct NatsSinkConfig {
     name: String,
     subject: String,
     url: String,
+    #[serde(default)]
+    jetstream: bool,
 }
 
 fn default_name() -> String {
@@ -121,6 +124,7 @@ pub struct NatsSink {
     options: NatsOptions,
     subject: Template,
     url: String,
+    jetstream: bool,
     acker: Acker,
 }
 
@@ -131,6 +135,7 @@ impl NatsSink {
             encoding: config.encoding,
             subject: Template::try_from(config.subject).context(SubjectTemplate)?,
             url: config.url,
+            jetstream: config.jetstream,
             acker,
         })
     }
@@ -172,8 +177,19 @@ impl StreamSink for NatsSink {
             let log = encode_event(event, &self.encoding);
             let message_len = log.len();
 
-            match nc.publish(&subject, log).await {
-                Ok(_) => {
+            // JetStream acknowledges messages once they're stored in the
+            // stream of the subject.
+            let result = if self.jetstream {
+                publish(&nc, &subject, log)
+                    .await
+                    .map(|_| ())
+                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+            } else {
+                nc.publish(&subject, log).await
+            };
+
+            match result {
+                Ok(()) => {
                     emit!(NatsEventSendSuccess {
                         byte_size: message_len,
                     });
@@ -247,6 +263,15 @@ mod integration_tests {
 
     #[tokio::test]
     async fn nats_happy() {
+        publish_and_check(false).await;
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream() {
+        publish_and_check(true).await;
+    }
+
+    async fn publish_and_check(jetstream: bool) {
         // Publish `N` messages to NATS.
         //
         // Verify with a separate subscriber that the messages were
@@ -254,17 +279,24 @@ mod integration_tests {
 
         trace_init();
 
-        let subject = format!("test-{}", random_string(10));
+        let stream = format!("test-{}", random_string(10));
+        let subject = format!("{}.logs", stream);
 
         let cnf = NatsSinkConfig {
             encoding: EncodingConfig::from(Encoding::Text),
             name: "".to_owned(),
             subject: subject.clone(),
             url: "nats://127.0.0.1:4222".to_owned(),
+            jetstream,
         };
 
         // Establish the consumer subscription.
         let consumer = cnf.clone().connect().await.unwrap();
+        if jetstream {
+            crate::nats::create_stream(&consumer, &stream, &subject)
+                .await
+                .unwrap();
+        }
         let sub = consumer.subscribe(&subject).await.unwrap();
 
         // Publish events.
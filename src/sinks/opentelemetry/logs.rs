@@ -0,0 +1,207 @@
+use super::{unix_nanos, Record, RecordData};
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+    opentelemetry::{
+        encode_attributes, encode_value,
+        proto::{common::v1::InstrumentationScope, logs::v1::LogRecord, resource::v1::Resource},
+    },
+};
+use std::collections::BTreeMap;
+
+/// Encodes the log, mapping the fields set by the `opentelemetry` source
+/// back onto the record. Any other field is exported as an attribute.
+pub(super) fn encode(mut log: LogEvent, resource_attributes: BTreeMap<String, Value>) -> Record {
+    let body = log.remove(log_schema().message_key()).map(encode_value);
+    let time_unix_nano = log
+        .remove(log_schema().timestamp_key())
+        .map_or(0, timestamp);
+    log.remove(log_schema().source_type_key());
+
+    let mut fields: BTreeMap<String, Value> = log.into();
+    let observed_time_unix_nano = fields.remove("observed_timestamp").map_or(0, timestamp);
+    let severity_text = fields
+        .remove("severity_text")
+        .map(|value| value.to_string_lossy())
+        .unwrap_or_default();
+    let severity_number = integer(fields.remove("severity_number")) as i32;
+    let trace_id = id(fields.remove("trace_id"));
+    let span_id = id(fields.remove("span_id"));
+    let flags = integer(fields.remove("flags")) as u32;
+    let dropped_attributes_count = integer(fields.remove("dropped_attributes_count")) as u32;
+
+    let mut resource = take_map(&mut fields, "resources");
+    resource.extend(resource_attributes);
+    let scope = scope(take_map(&mut fields, "scope"));
+    let attributes = take_map(&mut fields, "attributes");
+    fields.extend(attributes);
+
+    Record {
+        resource: Resource {
+            attributes: encode_attributes(resource),
+            dropped_attributes_count: 0,
+        },
+        scope,
+        data: RecordData::Log(LogRecord {
+            time_unix_nano,
+            observed_time_unix_nano,
+            severity_number,
+            severity_text,
+            body,
+            attributes: encode_attributes(fields),
+            dropped_attributes_count,
+            flags,
+            trace_id,
+            span_id,
+        }),
+    }
+}
+
+fn scope(mut fields: BTreeMap<String, Value>) -> InstrumentationScope {
+    let string = |value: Option<Value>| {
+        value
+            .map(|value| value.to_string_lossy())
+            .unwrap_or_default()
+    };
+
+    InstrumentationScope {
+        name: string(fields.remove("name")),
+        version: string(fields.remove("version")),
+        attributes: encode_attributes(take_map(&mut fields, "attributes")),
+        dropped_attributes_count: 0,
+    }
+}
+
+/// Removes the field if it is a map, any other value is left in place.
+fn take_map(fields: &mut BTreeMap<String, Value>, key: &str) -> BTreeMap<String, Value> {
+    match fields.remove(key) {
+        Some(Value::Map(map)) => map,
+        Some(value) => {
+            fields.insert(key.into(), value);
+            BTreeMap::new()
+        }
+        None => BTreeMap::new(),
+    }
+}
+
+fn timestamp(value: Value) -> u64 {
+    match value {
+        Value::Timestamp(timestamp) => unix_nanos(timestamp),
+        _ => 0,
+    }
+}
+
+fn integer(value: Option<Value>) -> i64 {
+    match value {
+        Some(Value::Integer(value)) => value,
+        _ => 0,
+    }
+}
+
+/// Trace and span ids are hex encoded by the source, anything else is
+/// dropped.
+fn id(value: Option<Value>) -> Vec<u8> {
+    value
+        .and_then(|value| hex::decode(value.as_bytes()).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::Event,
+        opentelemetry::proto::common::v1::{any_value, AnyValue, KeyValue},
+    };
+    use chrono::{TimeZone, Utc};
+    use pretty_assertions::assert_eq;
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        })
+    }
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: string_value(value),
+        }
+    }
+
+    #[test]
+    fn encodes_log_records() {
+        let mut event = Event::from("request served");
+        let log = event.as_mut_log();
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.timestamp(1_600_000_000, 123),
+        );
+        log.insert(log_schema().source_type_key(), "opentelemetry");
+        log.insert("attributes.http\\.method", "GET");
+        log.insert("resources.service\\.name", "checkout");
+        log.insert("scope.name", "http");
+        log.insert("scope.version", "1.2.0");
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("trace_id", "ab".repeat(16));
+        log.insert("span_id", "0102030405060708");
+        log.insert("user", "alice");
+
+        let mut resource_attributes = BTreeMap::new();
+        resource_attributes.insert("host.name".to_string(), Value::from("web-1"));
+
+        let record = encode(event.into_log(), resource_attributes);
+        assert_eq!(
+            record.resource.attributes,
+            vec![
+                key_value("host.name", "web-1"),
+                key_value("service.name", "checkout"),
+            ]
+        );
+        assert_eq!(
+            record.scope,
+            InstrumentationScope {
+                name: "http".into(),
+                version: "1.2.0".into(),
+                ..Default::default()
+            }
+        );
+        match record.data {
+            RecordData::Log(record) => assert_eq!(
+                record,
+                LogRecord {
+                    time_unix_nano: 1_600_000_000_000_000_123,
+                    severity_number: 9,
+                    severity_text: "INFO".into(),
+                    body: string_value("request served"),
+                    attributes: vec![key_value("http.method", "GET"), key_value("user", "alice")],
+                    trace_id: vec![0xab; 16],
+                    span_id: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+                    ..Default::default()
+                }
+            ),
+            RecordData::Metric(_) => panic!("Expected a log record"),
+        }
+    }
+
+    #[test]
+    fn keeps_unexpected_fields() {
+        let mut event = Event::from("message");
+        let log = event.as_mut_log();
+        log.insert("scope", "not a map");
+        log.insert("trace_id", "not hex");
+
+        let record = encode(event.into_log(), BTreeMap::new());
+        assert_eq!(record.scope, InstrumentationScope::default());
+        match record.data {
+            RecordData::Log(record) => {
+                assert!(record.trace_id.is_empty());
+                // The event's own timestamp is exported as the record time.
+                assert_ne!(record.time_unix_nano, 0);
+                assert_eq!(record.attributes, vec![key_value("scope", "not a map")]);
+            }
+            RecordData::Metric(_) => panic!("Expected a log record"),
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use super::{unix_nanos, Record, RecordData};
+use crate::{
+    event::{
+        metric::{Bucket, Quantile, Sample},
+        Metric, MetricKind, MetricValue, Value,
+    },
+    opentelemetry::{
+        encode_attributes,
+        proto::{
+            common::v1::{InstrumentationScope, KeyValue},
+            metrics::v1::{
+                metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+                AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as OtlpMetric,
+                NumberDataPoint, Sum, Summary, SummaryDataPoint,
+            },
+            resource::v1::Resource,
+        },
+    },
+};
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+/// Encodes the metric, splitting its tags back into the resource, scope
+/// and point attributes the `opentelemetry` source derives them from.
+pub(super) fn encode(metric: Metric, resource_attributes: BTreeMap<String, Value>) -> Record {
+    let mut resource = BTreeMap::new();
+    let mut scope = InstrumentationScope::default();
+    let mut attributes = BTreeMap::new();
+    for (key, value) in metric.series.tags.unwrap_or_default() {
+        if let Some(key) = key.strip_prefix("resource.") {
+            resource.insert(key.to_string(), Value::from(value));
+        } else if key == "scope.name" {
+            scope.name = value;
+        } else if key == "scope.version" {
+            scope.version = value;
+        } else {
+            attributes.insert(key, Value::from(value));
+        }
+    }
+    resource.extend(resource_attributes);
+
+    let name = match metric.series.name.namespace {
+        Some(namespace) => format!("{}.{}", namespace, metric.series.name.name),
+        None => metric.series.name.name,
+    };
+    let time_unix_nano = unix_nanos(metric.data.timestamp.unwrap_or_else(Utc::now));
+    let point = Point {
+        attributes: encode_attributes(attributes),
+        time_unix_nano,
+    };
+
+    let data = encode_data(metric.data.kind, metric.data.value, point);
+
+    Record {
+        resource: Resource {
+            attributes: encode_attributes(resource),
+            dropped_attributes_count: 0,
+        },
+        scope,
+        data: RecordData::Metric(OtlpMetric {
+            name,
+            data: Some(data),
+            ..Default::default()
+        }),
+    }
+}
+
+/// The fields shared by every data point.
+struct Point {
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+}
+
+impl Point {
+    fn number(self, value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: self.attributes,
+            time_unix_nano: self.time_unix_nano,
+            value: Some(number_data_point::Value::AsDouble(value)),
+            ..Default::default()
+        }
+    }
+}
+
+fn encode_data(kind: MetricKind, value: MetricValue, point: Point) -> Data {
+    let temporality = aggregation_temporality(kind);
+    match value {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![point.number(value)],
+            aggregation_temporality: temporality,
+            is_monotonic: true,
+        }),
+        // Incremental gauges are changes of the value, which OTLP only has
+        // as non-monotonic sums.
+        MetricValue::Gauge { value } if kind == MetricKind::Incremental => Data::Sum(Sum {
+            data_points: vec![point.number(value)],
+            aggregation_temporality: temporality,
+            is_monotonic: false,
+        }),
+        MetricValue::Gauge { value } => Data::Gauge(Gauge {
+            data_points: vec![point.number(value)],
+        }),
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![point.number(values.len() as f64)],
+        }),
+        MetricValue::Distribution { samples, .. } => Data::Histogram(Histogram {
+            data_points: vec![distribution_point(&samples, point)],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+        }),
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => Data::Histogram(Histogram {
+            data_points: vec![histogram_point(&buckets, count, sum, point)],
+            aggregation_temporality: temporality,
+        }),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![summary_point(&quantiles, count, sum, point)],
+        }),
+    }
+}
+
+fn aggregation_temporality(kind: MetricKind) -> i32 {
+    match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta as i32,
+        MetricKind::Absolute => AggregationTemporality::Cumulative as i32,
+    }
+}
+
+/// Samples have no bounds, so they are all counted in the single
+/// `+Inf` bucket.
+fn distribution_point(samples: &[Sample], point: Point) -> HistogramDataPoint {
+    let count = samples.iter().map(|sample| sample.rate as u64).sum();
+    let sum = samples
+        .iter()
+        .map(|sample| sample.value * sample.rate as f64)
+        .sum();
+    HistogramDataPoint {
+        attributes: point.attributes,
+        time_unix_nano: point.time_unix_nano,
+        count,
+        sum,
+        bucket_counts: vec![count],
+        ..Default::default()
+    }
+}
+
+/// OTLP counts the observations above the last bound in a final bucket,
+/// which Vector only has as part of the total count.
+fn histogram_point(buckets: &[Bucket], count: u32, sum: f64, point: Point) -> HistogramDataPoint {
+    let mut bucket_counts = buckets
+        .iter()
+        .map(|bucket| bucket.count as u64)
+        .collect::<Vec<_>>();
+    let counted = bucket_counts.iter().sum::<u64>();
+    bucket_counts.push((count as u64).saturating_sub(counted));
+
+    HistogramDataPoint {
+        attributes: point.attributes,
+        time_unix_nano: point.time_unix_nano,
+        count: count as u64,
+        sum,
+        bucket_counts,
+        explicit_bounds: buckets.iter().map(|bucket| bucket.upper_limit).collect(),
+        ..Default::default()
+    }
+}
+
+fn summary_point(quantiles: &[Quantile], count: u32, sum: f64, point: Point) -> SummaryDataPoint {
+    SummaryDataPoint {
+        attributes: point.attributes,
+        time_unix_nano: point.time_unix_nano,
+        count: count as u64,
+        sum,
+        quantile_values: quantiles
+            .iter()
+            .map(|quantile| ValueAtQuantile {
+                quantile: quantile.upper_limit,
+                value: quantile.value,
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::metric::{MetricTags, StatisticKind},
+        opentelemetry::proto::common::v1::{any_value, AnyValue},
+    };
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.into())),
+            }),
+        }
+    }
+
+    fn metric(kind: MetricKind, value: MetricValue) -> Metric {
+        let mut tags = MetricTags::new();
+        tags.insert("resource.service.name".into(), "checkout".into());
+        tags.insert("scope.name".into(), "http".into());
+        tags.insert("code".into(), "200".into());
+
+        Metric::new("requests".into(), kind, value)
+            .with_namespace(Some("app".into()))
+            .with_tags(Some(tags))
+            .with_timestamp(Some(Utc.timestamp(1_600_000_000, 0)))
+    }
+
+    fn encode_metric(kind: MetricKind, value: MetricValue) -> OtlpMetric {
+        match encode(metric(kind, value), BTreeMap::new()).data {
+            RecordData::Metric(metric) => metric,
+            RecordData::Log(_) => panic!("Expected a metric"),
+        }
+    }
+
+    fn number_point(value: f64) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![key_value("code", "200")],
+            time_unix_nano: 1_600_000_000_000_000_000,
+            value: Some(number_data_point::Value::AsDouble(value)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn splits_tags() {
+        let mut resource_attributes = BTreeMap::new();
+        resource_attributes.insert("host.name".to_string(), Value::from("web-1"));
+        let record = encode(
+            metric(MetricKind::Absolute, MetricValue::Gauge { value: 1.0 }),
+            resource_attributes,
+        );
+
+        assert_eq!(
+            record.resource.attributes,
+            vec![
+                key_value("host.name", "web-1"),
+                key_value("service.name", "checkout"),
+            ]
+        );
+        assert_eq!(record.scope.name, "http");
+        match record.data {
+            RecordData::Metric(metric) => assert_eq!(metric.name, "app.requests"),
+            RecordData::Log(_) => panic!("Expected a metric"),
+        }
+    }
+
+    #[test]
+    fn encodes_number_metrics() {
+        let counter = encode_metric(
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 12.0 },
+        );
+        assert_eq!(
+            counter.data,
+            Some(Data::Sum(Sum {
+                data_points: vec![number_point(12.0)],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+                is_monotonic: true,
+            }))
+        );
+
+        let gauge = encode_metric(MetricKind::Absolute, MetricValue::Gauge { value: 21.5 });
+        assert_eq!(
+            gauge.data,
+            Some(Data::Gauge(Gauge {
+                data_points: vec![number_point(21.5)],
+            }))
+        );
+
+        let gauge = encode_metric(MetricKind::Incremental, MetricValue::Gauge { value: -2.0 });
+        assert_eq!(
+            gauge.data,
+            Some(Data::Sum(Sum {
+                data_points: vec![number_point(-2.0)],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+                is_monotonic: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn encodes_histograms() {
+        let histogram = encode_metric(
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.1,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 5,
+                    },
+                ],
+                count: 10,
+                sum: 4.5,
+            },
+        );
+        assert_eq!(
+            histogram.data,
+            Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: vec![key_value("code", "200")],
+                    time_unix_nano: 1_600_000_000_000_000_000,
+                    count: 10,
+                    sum: 4.5,
+                    bucket_counts: vec![2, 5, 3],
+                    explicit_bounds: vec![0.1, 1.0],
+                    ..Default::default()
+                }],
+                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            }))
+        );
+
+        let distribution = encode_metric(
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vec![
+                    Sample {
+                        value: 1.0,
+                        rate: 2,
+                    },
+                    Sample {
+                        value: 3.0,
+                        rate: 1,
+                    },
+                ],
+                statistic: StatisticKind::Histogram,
+            },
+        );
+        assert_eq!(
+            distribution.data,
+            Some(Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: vec![key_value("code", "200")],
+                    time_unix_nano: 1_600_000_000_000_000_000,
+                    count: 3,
+                    sum: 5.0,
+                    bucket_counts: vec![3],
+                    ..Default::default()
+                }],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+            }))
+        );
+    }
+
+    #[test]
+    fn encodes_summaries() {
+        let summary = encode_metric(
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles: vec![Quantile {
+                    upper_limit: 0.5,
+                    value: 4.0,
+                }],
+                count: 4,
+                sum: 20.0,
+            },
+        );
+        assert_eq!(
+            summary.data,
+            Some(Data::Summary(Summary {
+                data_points: vec![SummaryDataPoint {
+                    attributes: vec![key_value("code", "200")],
+                    time_unix_nano: 1_600_000_000_000_000_000,
+                    count: 4,
+                    sum: 20.0,
+                    quantile_values: vec![ValueAtQuantile {
+                        quantile: 0.5,
+                        value: 4.0,
+                    }],
+                    ..Default::default()
+                }],
+            }))
+        );
+    }
+}
@@ -0,0 +1,588 @@
+use crate::{
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    dns::Resolver,
+    event::{Event, Value},
+    internal_events::OpentelemetryEventsSent,
+    opentelemetry::proto::{
+        collector::{
+            logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+            metrics::v1::{
+                metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+            },
+        },
+        common::v1::InstrumentationScope,
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{Metric as OtlpMetric, ResourceMetrics, ScopeMetrics},
+        resource::v1::Resource,
+    },
+    sinks::util::{
+        retries::RetryLogic, BatchConfig, BatchSettings, EncodedEvent, EncodedLength,
+        PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig, VecBuffer,
+    },
+    template::Template,
+    tls::{tls_connector_builder, MaybeTlsSettings, TlsOptions, TlsSettings},
+};
+use chrono::{DateTime, Utc};
+use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::Mutex;
+use tonic::{
+    transport::{Channel, Endpoint},
+    Code,
+};
+use tower::Service;
+use tracing_futures::Instrument;
+
+mod logs;
+mod metrics;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetrySinkConfig {
+    pub endpoint: String,
+    #[serde(default)]
+    pub resource_attributes: IndexMap<String, Template>,
+    #[serde(default)]
+    pub batch: BatchConfig,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    pub tls: Option<TlsOptions>,
+}
+
+lazy_static! {
+    static ref REQUEST_DEFAULTS: TowerRequestConfig = TowerRequestConfig {
+        timeout_secs: Some(30),
+        ..Default::default()
+    };
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint: {}", source))]
+    InvalidEndpoint { source: http::uri::InvalidUri },
+    #[snafu(display("Failed to build HTTPS connector: {}", source))]
+    MakeConnector { source: openssl::error::ErrorStack },
+}
+
+#[derive(Debug, Snafu)]
+enum OpentelemetryError {
+    #[snafu(display("Unable to connect to endpoint: {}", source))]
+    Connect { source: tonic::transport::Error },
+    #[snafu(display("Export request failed: {}", source))]
+    Export { source: tonic::Status },
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetrySinkConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://127.0.0.1:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetrySinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let service = OpentelemetryService::new(self)?;
+        let healthcheck = service.clone().healthcheck().boxed();
+
+        let batch = BatchSettings::default()
+            .bytes(1_000_000)
+            .events(1_000)
+            .timeout(1)
+            .parse_config(self.batch)?;
+        let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
+        let resource_attributes = self.resource_attributes.clone();
+
+        // Logs and metrics are exported separately, so they are batched
+        // separately for a failed export not to retry the other signal.
+        let sink = request
+            .partition_sink(
+                OpentelemetryRetryLogic,
+                service,
+                PartitionBuffer::new(VecBuffer::new(batch.size)),
+                batch.timeout,
+                cx.acker(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal opentelemetry sink error.", %error))
+            .with_flat_map(move |event| {
+                let encoded = EncodedEvent::encode(event, |event| {
+                    let record = encode_event(event, &resource_attributes);
+                    let signal = record.signal();
+                    Some(PartitionInnerBuffer::new(record, signal))
+                });
+                stream::iter(encoded).map(Ok)
+            });
+
+        Ok((super::VectorSink::Sink(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
+
+/// An event encoded for export, with the resource and instrumentation
+/// scope it is grouped under.
+#[derive(Clone, Debug)]
+struct Record {
+    resource: Resource,
+    scope: InstrumentationScope,
+    data: RecordData,
+}
+
+#[derive(Clone, Debug)]
+enum RecordData {
+    Log(LogRecord),
+    Metric(OtlpMetric),
+}
+
+/// The kind of telemetry a record is, which is exported to a service of its
+/// own.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+}
+
+impl Record {
+    fn signal(&self) -> Signal {
+        match self.data {
+            RecordData::Log(_) => Signal::Logs,
+            RecordData::Metric(_) => Signal::Metrics,
+        }
+    }
+}
+
+impl EncodedLength for Record {
+    fn encoded_length(&self) -> usize {
+        let data = match &self.data {
+            RecordData::Log(record) => record.encoded_len(),
+            RecordData::Metric(metric) => metric.encoded_len(),
+        };
+        self.resource.encoded_len() + self.scope.encoded_len() + data
+    }
+}
+
+fn encode_event(event: Event, resource_attributes: &IndexMap<String, Template>) -> Record {
+    let resource_attributes = resource_attributes
+        .iter()
+        .filter_map(|(name, template)| match template.render_string(&event) {
+            Ok(value) => Some((name.clone(), Value::from(value))),
+            Err(missing_keys) => {
+                warn!(
+                    message = "Keys in resource attribute template do not exist on the event; skipping attribute.",
+                    attribute = %name,
+                    ?missing_keys,
+                    internal_log_rate_secs = 30
+                );
+                None
+            }
+        })
+        .collect();
+
+    match event {
+        Event::Log(log) => logs::encode(log, resource_attributes),
+        Event::Metric(metric) => metrics::encode(metric, resource_attributes),
+    }
+}
+
+/// OTLP timestamps are unsigned nanoseconds since the epoch, so timestamps
+/// before the epoch are clamped to it, which OTLP takes as unknown, and the
+/// ones after 2554 to the latest it can represent.
+fn unix_nanos(timestamp: DateTime<Utc>) -> u64 {
+    if timestamp.timestamp() < 0 {
+        return 0;
+    }
+    (timestamp.timestamp() as u64)
+        .checked_mul(1_000_000_000)
+        .and_then(|nanos| nanos.checked_add(u64::from(timestamp.timestamp_subsec_nanos())))
+        .unwrap_or(u64::MAX)
+}
+
+#[derive(Clone)]
+struct OpentelemetryService {
+    endpoint: Endpoint,
+    connector: HttpsConnector<HttpConnector<Resolver>>,
+    channel: Arc<Mutex<Option<Channel>>>,
+}
+
+impl OpentelemetryService {
+    fn new(config: &OpentelemetrySinkConfig) -> crate::Result<Self> {
+        let endpoint = Endpoint::from_shared(config.endpoint.clone()).context(InvalidEndpoint)?;
+
+        let mut http = HttpConnector::new_with_resolver(Resolver);
+        http.enforce_http(false);
+
+        let settings = MaybeTlsSettings::from(TlsSettings::from_options(&config.tls)?);
+        let mut tls = tls_connector_builder(&settings)?;
+        // gRPC is only served over HTTP/2.
+        tls.set_alpn_protos(b"\x02h2").context(MakeConnector)?;
+        let mut connector = HttpsConnector::with_connector(http, tls).context(MakeConnector)?;
+
+        let settings = settings.tls().cloned();
+        connector.set_callback(move |c, _uri| {
+            if let Some(settings) = &settings {
+                settings.apply_connect_configuration(c);
+            }
+
+            Ok(())
+        });
+
+        Ok(Self {
+            endpoint,
+            connector,
+            channel: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// The channel is connected on first use, after which it reconnects by
+    /// itself.
+    async fn channel(&self) -> Result<Channel, OpentelemetryError> {
+        let mut channel = self.channel.lock().await;
+        if let Some(channel) = channel.as_ref() {
+            return Ok(channel.clone());
+        }
+
+        let connected = self
+            .endpoint
+            .connect_with_connector(self.connector.clone())
+            .await
+            .context(Connect)?;
+        *channel = Some(connected.clone());
+        Ok(connected)
+    }
+
+    async fn healthcheck(self) -> crate::Result<()> {
+        self.channel().await?;
+        Ok(())
+    }
+
+    /// Export a batch of records, which are all of the same signal.
+    async fn export(self, records: Vec<Record>, signal: Signal) -> Result<(), OpentelemetryError> {
+        let count = records.len();
+        let byte_size = records.iter().map(Record::encoded_length).sum();
+        let (resource_logs, resource_metrics) = group(records);
+
+        let channel = self.channel().await?;
+        match signal {
+            Signal::Logs => {
+                LogsServiceClient::new(channel)
+                    .export(ExportLogsServiceRequest { resource_logs })
+                    .await
+                    .context(Export)?;
+            }
+            Signal::Metrics => {
+                MetricsServiceClient::new(channel)
+                    .export(ExportMetricsServiceRequest { resource_metrics })
+                    .await
+                    .context(Export)?;
+            }
+        }
+
+        emit!(OpentelemetryEventsSent { count, byte_size });
+        Ok(())
+    }
+}
+
+impl Service<PartitionInnerBuffer<Vec<Record>, Signal>> for OpentelemetryService {
+    type Response = ();
+    type Error = OpentelemetryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PartitionInnerBuffer<Vec<Record>, Signal>) -> Self::Future {
+        let (records, signal) = request.into_parts();
+        debug!(
+            message = "Sending records.",
+            events = %records.len(),
+            ?signal,
+        );
+
+        Box::pin(
+            self.clone()
+                .export(records, signal)
+                .instrument(info_span!("request")),
+        )
+    }
+}
+
+/// Groups the records by resource and scope, in the order they were
+/// received.
+fn group(records: Vec<Record>) -> (Vec<ResourceLogs>, Vec<ResourceMetrics>) {
+    let mut logs: Vec<ResourceLogs> = Vec::new();
+    let mut metrics: Vec<ResourceMetrics> = Vec::new();
+
+    for Record {
+        resource,
+        scope,
+        data,
+    } in records
+    {
+        match data {
+            RecordData::Log(record) => {
+                let resource_logs = entry(
+                    &mut logs,
+                    |logs| logs.resource.as_ref() == Some(&resource),
+                    || ResourceLogs {
+                        resource: Some(resource.clone()),
+                        ..Default::default()
+                    },
+                );
+                let scope_logs = entry(
+                    &mut resource_logs.scope_logs,
+                    |logs| logs.scope.as_ref() == Some(&scope),
+                    || ScopeLogs {
+                        scope: Some(scope.clone()),
+                        ..Default::default()
+                    },
+                );
+                scope_logs.log_records.push(record);
+            }
+            RecordData::Metric(metric) => {
+                let resource_metrics = entry(
+                    &mut metrics,
+                    |metrics| metrics.resource.as_ref() == Some(&resource),
+                    || ResourceMetrics {
+                        resource: Some(resource.clone()),
+                        ..Default::default()
+                    },
+                );
+                let scope_metrics = entry(
+                    &mut resource_metrics.scope_metrics,
+                    |metrics| metrics.scope.as_ref() == Some(&scope),
+                    || ScopeMetrics {
+                        scope: Some(scope.clone()),
+                        ..Default::default()
+                    },
+                );
+                scope_metrics.metrics.push(metric);
+            }
+        }
+    }
+
+    (logs, metrics)
+}
+
+/// Batches only hold a few distinct resources, so they are looked up
+/// linearly.
+fn entry<T>(items: &mut Vec<T>, matches: impl Fn(&T) -> bool, new: impl FnOnce() -> T) -> &mut T {
+    match items.iter().position(matches) {
+        Some(index) => &mut items[index],
+        None => {
+            items.push(new());
+            items.last_mut().expect("an item was just pushed")
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetryError;
+    type Response = ();
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            OpentelemetryError::Connect { .. } => true,
+            // The codes the OTLP specification lists as retryable.
+            OpentelemetryError::Export { source } => matches!(
+                source.code(),
+                Code::Cancelled
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+                    | Code::OutOfRange
+                    | Code::Unavailable
+                    | Code::DataLoss
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::{Metric, MetricKind, MetricValue},
+        opentelemetry::proto::collector::{
+            logs::v1::{
+                logs_service_server::{LogsService, LogsServiceServer},
+                ExportLogsServiceResponse,
+            },
+            metrics::v1::{
+                metrics_service_server::{MetricsService, MetricsServiceServer},
+                ExportMetricsServiceResponse,
+            },
+        },
+        test_util::{next_addr, wait_for_tcp},
+    };
+    use chrono::TimeZone;
+    use std::{convert::TryFrom, sync::Mutex as StdMutex};
+    use tonic::{Request, Response, Status};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetrySinkConfig>();
+    }
+
+    #[derive(Clone, Default)]
+    struct Collector {
+        logs: Arc<StdMutex<Vec<ExportLogsServiceRequest>>>,
+        metrics: Arc<StdMutex<Vec<ExportMetricsServiceRequest>>>,
+    }
+
+    #[tonic::async_trait]
+    impl LogsService for Collector {
+        async fn export(
+            &self,
+            request: Request<ExportLogsServiceRequest>,
+        ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+            self.logs.lock().unwrap().push(request.into_inner());
+            Ok(Response::new(ExportLogsServiceResponse {}))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl MetricsService for Collector {
+        async fn export(
+            &self,
+            request: Request<ExportMetricsServiceRequest>,
+        ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+            self.metrics.lock().unwrap().push(request.into_inner());
+            Ok(Response::new(ExportMetricsServiceResponse {}))
+        }
+    }
+
+    fn config(endpoint: String) -> OpentelemetrySinkConfig {
+        let mut resource_attributes = IndexMap::new();
+        resource_attributes.insert(
+            "service.name".to_string(),
+            Template::try_from("{{ service }}").unwrap(),
+        );
+
+        OpentelemetrySinkConfig {
+            endpoint,
+            resource_attributes,
+            batch: Default::default(),
+            request: Default::default(),
+            tls: None,
+        }
+    }
+
+    fn log(message: &str, service: &str) -> Event {
+        let mut event = Event::from(message);
+        event.as_mut_log().insert("service", service);
+        event
+    }
+
+    #[tokio::test]
+    async fn exports_logs_and_metrics() {
+        let collector = Collector::default();
+        let addr = next_addr();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(LogsServiceServer::new(collector.clone()))
+                .add_service(MetricsServiceServer::new(collector.clone()))
+                .serve(addr),
+        );
+        wait_for_tcp(addr).await;
+
+        let (sink, healthcheck) = config(format!("http://{}", addr))
+            .build(SinkContext::new_test())
+            .await
+            .unwrap();
+        healthcheck.await.unwrap();
+
+        let metric = Event::Metric(Metric::new(
+            "requests".into(),
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+        let events = vec![
+            log("one", "checkout"),
+            log("two", "payment"),
+            log("three", "checkout"),
+            metric,
+        ];
+        sink.run(stream::iter(events)).await.unwrap();
+
+        let logs = collector.logs.lock().unwrap();
+        assert_eq!(logs.len(), 1);
+        let resource_logs = &logs[0].resource_logs;
+        assert_eq!(resource_logs.len(), 2);
+        assert_eq!(resource_logs[0].scope_logs[0].log_records.len(), 2);
+        assert_eq!(resource_logs[1].scope_logs[0].log_records.len(), 1);
+
+        // Metrics without the templated field don't get the attribute.
+        let metrics = collector.metrics.lock().unwrap();
+        assert_eq!(metrics.len(), 1);
+        let resource_metrics = &metrics[0].resource_metrics;
+        assert_eq!(resource_metrics.len(), 1);
+        assert_eq!(resource_metrics[0].resource, Some(Resource::default()));
+        assert_eq!(
+            resource_metrics[0].scope_metrics[0].metrics[0].name,
+            "requests"
+        );
+    }
+
+    #[tokio::test]
+    async fn healthcheck_fails_without_endpoint() {
+        let (_, healthcheck) = config(format!("http://{}", next_addr()))
+            .build(SinkContext::new_test())
+            .await
+            .unwrap();
+        assert!(healthcheck.await.is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_endpoint() {
+        assert!(OpentelemetryService::new(&config("not a uri".into())).is_err());
+    }
+
+    #[test]
+    fn clamps_unix_nanos() {
+        assert_eq!(unix_nanos(Utc.timestamp(1, 2)), 1_000_000_002);
+        assert_eq!(unix_nanos(Utc.ymd(1969, 12, 31).and_hms(0, 0, 0)), 0);
+        assert_eq!(unix_nanos(Utc.ymd(1600, 1, 1).and_hms(0, 0, 0)), 0);
+        assert_eq!(unix_nanos(Utc.ymd(3000, 1, 1).and_hms(0, 0, 0)), u64::MAX);
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        let retriable = |code| {
+            OpentelemetryRetryLogic.is_retriable_error(&OpentelemetryError::Export {
+                source: Status::new(code, "error"),
+            })
+        };
+        assert!(retriable(Code::Unavailable));
+        assert!(retriable(Code::ResourceExhausted));
+        assert!(!retriable(Code::InvalidArgument));
+        assert!(!retriable(Code::Unauthenticated));
+    }
+}
@@ -22,6 +22,10 @@ pub struct PapertrailConfig {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: Option<TlsConfig>,
     send_buffer_bytes: Option<usize>,
+    receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    nodelay: bool,
+    user_timeout_secs: Option<u64>,
 }
 
 inventory::submit! {
@@ -63,7 +67,15 @@ impl SinkConfig for PapertrailConfig {
         let pid = std::process::id();
         let encoding = self.encoding.clone();
 
-        let sink_config = TcpSinkConfig::new(address, self.keepalive, tls, self.send_buffer_bytes);
+        let sink_config = TcpSinkConfig::new(
+            address,
+            self.keepalive,
+            tls,
+            self.send_buffer_bytes,
+            self.receive_buffer_bytes,
+            self.nodelay,
+            self.user_timeout_secs,
+        );
 
         sink_config.build(cx, move |event| encode_event(event, pid, &encoding))
     }
@@ -0,0 +1,397 @@
+use crate::{
+    buffers::Acker,
+    config::{DataType, SinkConfig, SinkContext, SinkDescription},
+    event::{proto::EventWrapper, Event},
+    internal_events::{error_stage, PluginEventSent},
+    plugin::{
+        proto::{sink_request::Message, ComponentKind, SinkEvent, SinkRequest, SinkResponse},
+        Plugin, PluginConfig, Reconnect, Responses, SessionError, Step,
+    },
+    sinks::util::StreamSink,
+};
+use async_trait::async_trait;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use prost::Message as _;
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+
+/// The number of events sent to the plugin that may wait for their
+/// acknowledgement at once.
+const MAX_UNACKED: usize = 1024;
+
+inventory::submit! {
+    SinkDescription::new::<PluginConfig>("plugin")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "plugin")]
+impl SinkConfig for PluginConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let plugin = self.connect(ComponentKind::Sink).await?;
+        let healthcheck = plugin.clone().healthcheck().boxed();
+
+        let sink = PluginSink {
+            plugin,
+            acker: cx.acker(),
+        };
+
+        Ok((super::VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "plugin"
+    }
+}
+
+struct PluginSink {
+    plugin: Plugin,
+    acker: Acker,
+}
+
+/// Tracks which events were acknowledged by the plugin.
+///
+/// Every event gets the next id, including the ones that are not accepted by
+/// the component. The acker is advanced to an id once every event sent up to
+/// it has been acknowledged.
+#[derive(Default)]
+struct Acks {
+    /// The id of the last event, whether it was sent or not.
+    last: u64,
+    /// The id of the last event sent to the plugin.
+    sent: u64,
+    /// The id up to which events were acknowledged.
+    acked: u64,
+    /// Events sent but not yet acknowledged, which are sent again if the
+    /// plugin has to be reconnected. No more than `MAX_UNACKED` events are
+    /// sent before being acknowledged.
+    unacked: VecDeque<SinkEvent>,
+}
+
+impl Acks {
+    fn send(&mut self, event: EventWrapper) -> SinkEvent {
+        self.last += 1;
+        self.sent = self.last;
+
+        let event = SinkEvent {
+            id: self.last,
+            event: Some(event),
+        };
+        self.unacked.push_back(event.clone());
+        event
+    }
+
+    fn skip(&mut self, acker: &Acker) {
+        self.last += 1;
+        if self.acked >= self.sent {
+            self.advance(self.last, acker);
+        }
+    }
+
+    fn ack(&mut self, id: u64, acker: &Acker) {
+        // Skipped events after the last sent one are done as well.
+        let id = if id >= self.sent { self.last } else { id };
+        while self.unacked.front().map_or(false, |event| event.id <= id) {
+            self.unacked.pop_front();
+        }
+        self.advance(id, acker);
+    }
+
+    fn advance(&mut self, id: u64, acker: &Acker) {
+        if id > self.acked {
+            acker.ack((id - self.acked) as usize);
+            self.acked = id;
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.acked >= self.last
+    }
+
+    fn is_full(&self) -> bool {
+        self.unacked.len() >= MAX_UNACKED
+    }
+}
+
+impl PluginSink {
+    /// Stream events to the plugin until the input ends and every event has
+    /// been acknowledged.
+    async fn session(
+        &mut self,
+        input: &mut BoxStream<'_, Event>,
+        input_done: &mut bool,
+        acks: &mut Acks,
+        reconnect: &mut Reconnect,
+    ) -> Result<(), SessionError> {
+        // The config and the unacknowledged events fit in the channel.
+        let (mut tx, rx) = mpsc::channel(MAX_UNACKED + 1);
+        let config = SinkRequest {
+            message: Some(Message::Config(self.plugin.config())),
+        };
+        tx.send(config).await.map_err(|_| SessionError::Closed)?;
+
+        let responses = self
+            .plugin
+            .client
+            .sink(rx)
+            .await
+            .map_err(SessionError::Failed)?
+            .into_inner();
+        let mut responses = Responses::spawn(responses, MAX_UNACKED);
+
+        for event in acks.unacked.clone() {
+            let request = SinkRequest {
+                message: Some(Message::Event(event)),
+            };
+            tx.send(request).await.map_err(|_| SessionError::Closed)?;
+        }
+
+        if *input_done && acks.is_done() {
+            return Ok(());
+        }
+
+        // Dropping the sender closes the request stream once the input has
+        // ended.
+        let mut tx = if *input_done { None } else { Some(tx) };
+
+        loop {
+            let step = tokio::select! {
+                event = input.next(), if tx.is_some() && !acks.is_full() => Step::Input(event),
+                response = responses.message() => Step::Response(response),
+            };
+
+            match step {
+                Step::Input(Some(event)) if self.plugin.accepts(&event) => {
+                    let event = EventWrapper::from(event);
+                    let byte_size = event.encoded_len();
+                    let event = acks.send(event);
+                    let request = SinkRequest {
+                        message: Some(Message::Event(event)),
+                    };
+                    tx.as_mut()
+                        .expect("input is open")
+                        .send(request)
+                        .await
+                        .map_err(|_| SessionError::Closed)?;
+                    emit!(PluginEventSent { byte_size });
+                }
+                Step::Input(Some(_)) => acks.skip(&self.acker),
+                Step::Input(None) => {
+                    *input_done = true;
+                    tx = None;
+                    if acks.is_done() {
+                        return Ok(());
+                    }
+                }
+                Step::Response(Ok(Some(SinkResponse { ack: Some(ack) }))) => {
+                    reconnect.reset();
+                    acks.ack(ack.id, &self.acker);
+                    if *input_done && acks.is_done() {
+                        return Ok(());
+                    }
+                }
+                Step::Response(Ok(Some(_))) => {}
+                Step::Response(Ok(None)) => return Err(SessionError::Closed),
+                Step::Response(Err(status)) => return Err(SessionError::Failed(status)),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink for PluginSink {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut acks = Acks::default();
+        let mut input_done = false;
+        let mut reconnect = Reconnect::new(error_stage::SENDING);
+
+        loop {
+            let session = self.session(&mut input, &mut input_done, &mut acks, &mut reconnect);
+            match session.await {
+                Ok(()) => return Ok(()),
+                // Events are sent again once the plugin is reachable.
+                Err(error) => reconnect.failed(error).await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        plugin::{
+            proto::{self, plugin_server},
+            test::{describe, serve},
+        },
+        test_util::random_lines,
+    };
+    use futures::stream;
+    use std::sync::{Arc, Mutex};
+    use tonic::{Request, Response, Status, Streaming};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PluginConfig>();
+    }
+
+    #[derive(Clone, Default)]
+    struct CollectingPlugin {
+        events: Arc<Mutex<Vec<Event>>>,
+    }
+
+    #[tonic::async_trait]
+    impl plugin_server::Plugin for CollectingPlugin {
+        async fn describe(
+            &self,
+            _: Request<proto::DescribeRequest>,
+        ) -> Result<Response<proto::DescribeResponse>, Status> {
+            Ok(Response::new(describe(
+                ComponentKind::Sink,
+                proto::DataType::Log,
+            )))
+        }
+
+        async fn health(
+            &self,
+            _: Request<proto::ComponentConfig>,
+        ) -> Result<Response<proto::HealthResponse>, Status> {
+            Ok(Response::new(proto::HealthResponse {
+                healthy: true,
+                message: String::new(),
+            }))
+        }
+
+        type SourceStream = mpsc::Receiver<Result<proto::SourceResponse, Status>>;
+
+        async fn source(
+            &self,
+            _: Request<Streaming<proto::SourceRequest>>,
+        ) -> Result<Response<Self::SourceStream>, Status> {
+            Err(Status::unimplemented("source"))
+        }
+
+        type TransformStream = mpsc::Receiver<Result<proto::TransformResponse, Status>>;
+
+        async fn transform(
+            &self,
+            _: Request<Streaming<proto::TransformRequest>>,
+        ) -> Result<Response<Self::TransformStream>, Status> {
+            Err(Status::unimplemented("transform"))
+        }
+
+        type SinkStream = mpsc::Receiver<Result<SinkResponse, Status>>;
+
+        async fn sink(
+            &self,
+            request: Request<Streaming<SinkRequest>>,
+        ) -> Result<Response<Self::SinkStream>, Status> {
+            let mut requests = request.into_inner();
+            let events = Arc::clone(&self.events);
+            let (mut tx, rx) = mpsc::channel(10);
+
+            tokio::spawn(async move {
+                while let Ok(Some(request)) = requests.message().await {
+                    if let Some(Message::Event(event)) = request.message {
+                        events
+                            .lock()
+                            .unwrap()
+                            .push(Event::from(event.event.unwrap()));
+                        let response = SinkResponse {
+                            ack: Some(proto::Ack { id: event.id }),
+                        };
+                        if tx.send(Ok(response)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+
+            Ok(Response::new(rx))
+        }
+    }
+
+    fn config(endpoint: String) -> PluginConfig {
+        PluginConfig {
+            endpoint,
+            component: "test".into(),
+            options: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_events_to_plugin() {
+        let plugin = CollectingPlugin::default();
+        let endpoint = serve(plugin.clone()).await;
+
+        let (sink, healthcheck) = config(endpoint)
+            .build(SinkContext::new_test())
+            .await
+            .unwrap();
+        healthcheck.await.unwrap();
+
+        let lines = random_lines(100).take(10).collect::<Vec<_>>();
+        let events = lines.iter().map(|line| Event::from(line.as_str()));
+        sink.run(stream::iter(events)).await.unwrap();
+
+        let received = plugin
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(received, lines);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_component() {
+        let endpoint = serve(CollectingPlugin::default()).await;
+        let mut config = config(endpoint);
+        config.component = "unknown".into();
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
+    #[test]
+    fn bounds_unacked_events() {
+        let acker = Acker::Null;
+        let mut acks = Acks::default();
+
+        for _ in 0..MAX_UNACKED {
+            assert!(!acks.is_full());
+            acks.send(Event::from("a").into());
+        }
+        assert!(acks.is_full());
+
+        acks.ack(1, &acker);
+        assert!(!acks.is_full());
+        assert_eq!(acks.unacked.len(), MAX_UNACKED - 1);
+    }
+
+    #[test]
+    fn acks_skipped_events() {
+        let acker = Acker::Null;
+        let mut acks = Acks::default();
+
+        acks.skip(&acker);
+        assert_eq!(acks.acked, 1);
+
+        acks.send(Event::from("a").into());
+        acks.skip(&acker);
+        assert_eq!(acks.acked, 1);
+        assert!(!acks.is_done());
+
+        acks.ack(2, &acker);
+        assert_eq!(acks.acked, 3);
+        assert!(acks.unacked.is_empty());
+        assert!(acks.is_done());
+    }
+}
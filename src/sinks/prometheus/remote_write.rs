@@ -9,8 +9,8 @@ use crate::{
         util::{
             buffer::metrics::{MetricNormalize, MetricSet, MetricsBuffer},
             http::HttpRetryLogic,
-            BatchConfig, BatchSettings, PartitionBatchSink, PartitionBuffer, PartitionInnerBuffer,
-            TowerRequestConfig,
+            BatchConfig, BatchSettings, EncodedEvent, PartitionBatchSink, PartitionBuffer,
+            PartitionInnerBuffer, TowerRequestConfig,
         },
     },
     template::Template,
@@ -111,8 +111,10 @@ impl SinkConfig for RemoteWriteConfig {
                             .ok()
                     });
                     let key = PartitionKey { tenant_id };
-                    let inner = PartitionInnerBuffer::new(event, key);
-                    stream::iter(Some(Ok(inner)))
+                    let encoded = EncodedEvent::encode(event, |event| {
+                        Some(PartitionInnerBuffer::new(event, key))
+                    });
+                    stream::iter(encoded.map(Ok))
                 })
                 .sink_map_err(
                     |error| error!(message = "Prometheus remote_write sink error.", %error),
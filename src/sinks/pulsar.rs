@@ -3,12 +3,13 @@ use crate::{
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::PulsarEncodeEventFailed,
+    pulsar::AuthConfig,
     sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
 };
 use futures::{future::BoxFuture, ready, stream::FuturesUnordered, FutureExt, Sink, Stream};
 use pulsar::{
-    message::proto, producer::SendFuture, proto::CommandSendReceipt, Authentication,
-    Error as PulsarError, Producer, Pulsar, TokioExecutor,
+    message::proto, producer::SendFuture, proto::CommandSendReceipt, Error as PulsarError,
+    Producer, Pulsar, TokioExecutor,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
@@ -34,12 +35,6 @@ pub struct PulsarSinkConfig {
     auth: Option<AuthConfig>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct AuthConfig {
-    name: String,  // "token"
-    token: String, // <jwt token>
-}
-
 #[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Encoding {
@@ -121,10 +116,7 @@ impl PulsarSinkConfig {
     async fn create_pulsar_producer(&self) -> Result<PulsarProducer, PulsarError> {
         let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
         if let Some(auth) = &self.auth {
-            builder = builder.with_auth(Authentication {
-                name: auth.name.clone(),
-                data: auth.token.as_bytes().to_vec(),
-            });
+            builder = builder.with_auth(auth.authentication());
         }
 
         if let Some(avro_schema) = &self.encoding.schema() {
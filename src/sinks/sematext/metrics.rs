@@ -8,12 +8,12 @@ use crate::{
     sinks::util::{
         buffer::metrics::{MetricNormalize, MetricSet, MetricsBuffer},
         http::{HttpBatchService, HttpRetryLogic},
-        BatchConfig, BatchSettings, TowerRequestConfig,
+        BatchConfig, BatchSettings, EncodedEvent, TowerRequestConfig,
     },
     sinks::{Healthcheck, HealthcheckError, VectorSink},
     vector_version, Result,
 };
-use futures::{future::BoxFuture, FutureExt, SinkExt};
+use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
 use http::{StatusCode, Uri};
 use hyper::{Body, Request};
 use lazy_static::lazy_static;
@@ -151,7 +151,8 @@ impl SematextMetricsService {
                 batch.timeout,
                 cx.acker(),
             )
-            .sink_map_err(|error| error!(message = "Fatal sematext metrics sink error.", %error));
+            .sink_map_err(|error| error!(message = "Fatal sematext metrics sink error.", %error))
+            .with_flat_map(|event| stream::iter(EncodedEvent::encode(event, Some)).map(Ok));
 
         Ok(VectorSink::Sink(Box::new(sink)))
     }
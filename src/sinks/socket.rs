@@ -223,6 +223,9 @@ mod test {
                     },
                 }),
                 None,
+                None,
+                false,
+                None,
             )),
             encoding: Encoding::Text.into(),
         };
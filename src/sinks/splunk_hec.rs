@@ -5,8 +5,9 @@ use crate::{
     internal_events::{SplunkEventEncodeError, SplunkEventSent, SplunkMissingKeys},
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpSink},
-        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, TowerRequestConfig,
+        http::{encode_partitioned_request_bodies, HttpSink, PartitionHttpSink},
+        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, PartitionBuffer,
+        PartitionInnerBuffer, TowerRequestConfig,
     },
     template::Template,
     tls::{TlsOptions, TlsSettings},
@@ -19,6 +20,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use snafu::{ResultExt, Snafu};
 use std::convert::TryFrom;
+use uuid::Uuid;
 
 #[derive(Debug, Snafu)]
 pub enum BuildError {
@@ -40,6 +42,9 @@ pub struct HecSinkConfig {
     pub index: Option<Template>,
     pub sourcetype: Option<Template>,
     pub source: Option<Template>,
+    #[serde(default)]
+    pub endpoint_target: EndpointTarget,
+    pub channel: Option<String>,
     pub encoding: EncodingConfig<Encoding>,
     #[serde(default)]
     pub compression: Compression,
@@ -65,6 +70,29 @@ pub enum Encoding {
     Json,
 }
 
+/// The HEC endpoint events are sent to.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointTarget {
+    /// `/services/collector/event`, which takes the metadata of each event
+    /// along with it.
+    #[derivative(Default)]
+    Event,
+    /// `/services/collector/raw`, which takes raw lines, and the metadata of
+    /// a whole request in its query.
+    Raw,
+}
+
+/// The metadata of the events of a request to the raw endpoint.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PartitionKey {
+    index: Option<String>,
+    source: Option<String>,
+    sourcetype: Option<String>,
+    host: Option<String>,
+}
+
 fn default_host_key() -> String {
     crate::config::LogSchema::default().host_key().to_string()
 }
@@ -83,6 +111,8 @@ impl GenerateConfig for HecSinkConfig {
             index: None,
             sourcetype: None,
             source: None,
+            endpoint_target: EndpointTarget::Event,
+            channel: None,
             encoding: Encoding::Text.into(),
             compression: Compression::default(),
             batch: BatchConfig::default(),
@@ -110,9 +140,16 @@ impl SinkConfig for HecSinkConfig {
         let tls_settings = TlsSettings::from_options(&self.tls)?;
         let client = HttpClient::new(tls_settings)?;
 
-        let sink = BatchedHttpSink::new(
-            self.clone(),
-            Buffer::new(batch.size, self.compression),
+        // Splunk requires a channel for the raw endpoint, and for tokens with
+        // indexer acknowledgements enabled.
+        let mut config = self.clone();
+        config
+            .channel
+            .get_or_insert_with(|| Uuid::new_v4().to_hyphenated().to_string());
+
+        let sink = PartitionHttpSink::new(
+            config,
+            PartitionBuffer::new(Buffer::new(batch.size, self.compression)),
             request,
             batch.timeout,
             client.clone(),
@@ -132,54 +169,127 @@ impl SinkConfig for HecSinkConfig {
     fn sink_type(&self) -> &'static str {
         "splunk_hec"
     }
+
+    async fn encode_test_payloads(
+        &self,
+        events: Vec<Event>,
+    ) -> Option<crate::Result<Vec<Vec<u8>>>> {
+        let encode = async {
+            let config = HecSinkConfig {
+                compression: Compression::None,
+                ..self.clone()
+            };
+            let batch = BatchSettings::default()
+                .bytes(bytesize::mib(1u64))
+                .timeout(1)
+                .parse_config(config.batch)?;
+
+            encode_partitioned_request_bodies(
+                &config,
+                PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
+                events,
+            )
+            .await
+        };
+
+        Some(encode.await)
+    }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for HecSinkConfig {
-    type Input = Vec<u8>;
-    type Output = Vec<u8>;
+    type Input = PartitionInnerBuffer<Vec<u8>, PartitionKey>;
+    type Output = PartitionInnerBuffer<Vec<u8>, PartitionKey>;
 
     fn encode_event(&self, event: Event) -> Option<Self::Input> {
-        let sourcetype = self.sourcetype.as_ref().and_then(|sourcetype| {
-            sourcetype
-                .render_string(&event)
-                .map_err(|missing_keys| {
-                    emit!(SplunkMissingKeys {
-                        field: "sourcetype",
-                        keys: &missing_keys
-                    });
-                })
-                .ok()
-        });
+        let sourcetype = render(&self.sourcetype, "sourcetype", &event);
+        let source = render(&self.source, "source", &event);
+        let index = render(&self.index, "index", &event);
 
-        let source = self.source.as_ref().and_then(|source| {
-            source
-                .render_string(&event)
-                .map_err(|missing_keys| {
-                    emit!(SplunkMissingKeys {
-                        field: "source",
-                        keys: &missing_keys
-                    });
-                })
-                .ok()
-        });
+        let event = event.into_log();
 
-        let index = self.index.as_ref().and_then(|index| {
-            index
-                .render_string(&event)
-                .map_err(|missing_keys| {
-                    emit!(SplunkMissingKeys {
-                        field: "index",
-                        keys: &missing_keys
-                    });
-                })
-                .ok()
-        });
+        let host = event
+            .get(self.host_key.to_owned())
+            .map(|host| host.to_string_lossy());
+
+        match self.endpoint_target {
+            EndpointTarget::Event => self
+                .encode_hec_event(event, host, index, source, sourcetype)
+                .map(|event| PartitionInnerBuffer::new(event, PartitionKey::default())),
+            EndpointTarget::Raw => {
+                let key = PartitionKey {
+                    index,
+                    source,
+                    sourcetype,
+                    host,
+                };
+                self.encode_raw_event(event)
+                    .map(|event| PartitionInnerBuffer::new(event, key))
+            }
+        }
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Vec<u8>>> {
+        let (events, key) = output.into_parts();
+
+        let uri = match self.endpoint_target {
+            EndpointTarget::Event => build_uri(&self.endpoint, "/services/collector/event"),
+            EndpointTarget::Raw => {
+                let mut query = url::form_urlencoded::Serializer::new(String::new());
+                let metadata = [
+                    ("index", &key.index),
+                    ("source", &key.source),
+                    ("sourcetype", &key.sourcetype),
+                    ("host", &key.host),
+                ];
+                for (name, value) in metadata.iter() {
+                    if let Some(value) = value {
+                        query.append_pair(name, value);
+                    }
+                }
+                let query = query.finish();
+
+                if query.is_empty() {
+                    build_uri(&self.endpoint, "/services/collector/raw")
+                } else {
+                    build_uri(
+                        &self.endpoint,
+                        &format!("/services/collector/raw?{}", query),
+                    )
+                }
+            }
+        }
+        .expect("Unable to parse URI");
+
+        let content_type = match self.endpoint_target {
+            EndpointTarget::Event => "application/json",
+            EndpointTarget::Raw => "text/plain",
+        };
+        let mut builder = Request::post(uri)
+            .header("Content-Type", content_type)
+            .header("Authorization", format!("Splunk {}", self.token));
 
-        let mut event = event.into_log();
+        if let Some(channel) = &self.channel {
+            builder = builder.header("X-Splunk-Request-Channel", channel);
+        }
 
-        let host = event.get(self.host_key.to_owned()).cloned();
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+
+        builder.body(events).map_err(Into::into)
+    }
+}
 
+impl HecSinkConfig {
+    fn encode_hec_event(
+        &self,
+        mut event: LogEvent,
+        host: Option<String>,
+        index: Option<String>,
+        source: Option<String>,
+        sourcetype: Option<String>,
+    ) -> Option<Vec<u8>> {
         let timestamp = match event.remove(log_schema().timestamp_key()) {
             Some(Value::Timestamp(ts)) => ts,
             _ => chrono::Utc::now(),
@@ -211,7 +321,6 @@ impl HttpSink for HecSinkConfig {
         });
 
         if let Some(host) = host {
-            let host = host.to_string_lossy();
             body["host"] = json!(host);
         }
 
@@ -241,22 +350,48 @@ impl HttpSink for HecSinkConfig {
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Vec<u8>>> {
-        let uri =
-            build_uri(&self.endpoint, "/services/collector/event").expect("Unable to parse URI");
-
-        let mut builder = Request::post(uri)
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Splunk {}", self.token));
+    /// Encodes the event as a line of a request to the raw endpoint.
+    fn encode_raw_event(&self, event: LogEvent) -> Option<Vec<u8>> {
+        let mut event = Event::Log(event);
+        self.encoding.apply_rules(&mut event);
+        let event = event.into_log();
 
-        if let Some(ce) = self.compression.content_encoding() {
-            builder = builder.header("Content-Encoding", ce);
-        }
+        let mut line = match self.encoding.codec() {
+            Encoding::Json => match serde_json::to_vec(&event) {
+                Ok(line) => line,
+                Err(error) => {
+                    emit!(SplunkEventEncodeError { error });
+                    return None;
+                }
+            },
+            Encoding::Text => event
+                .get(log_schema().message_key())
+                .map(|v| v.as_bytes().to_vec())
+                .unwrap_or_default(),
+        };
+        line.push(b'\n');
 
-        builder.body(events).map_err(Into::into)
+        emit!(SplunkEventSent {
+            byte_size: line.len()
+        });
+        Some(line)
     }
 }
 
+fn render(template: &Option<Template>, field: &'static str, event: &Event) -> Option<String> {
+    template.as_ref().and_then(|template| {
+        template
+            .render_string(event)
+            .map_err(|missing_keys| {
+                emit!(SplunkMissingKeys {
+                    field,
+                    keys: &missing_keys
+                });
+            })
+            .ok()
+    })
+}
+
 #[derive(Debug, Snafu)]
 enum HealthcheckError {
     #[snafu(display("Invalid HEC token"))]
@@ -346,7 +481,7 @@ mod tests {
         )
         .unwrap();
 
-        let bytes = config.encode_event(event).unwrap();
+        let (bytes, _) = config.encode_event(event).unwrap().into_parts();
 
         let hec_event = serde_json::from_slice::<HecEventJson>(&bytes[..]).unwrap();
 
@@ -396,7 +531,7 @@ mod tests {
         )
         .unwrap();
 
-        let bytes = config.encode_event(event).unwrap();
+        let (bytes, _) = config.encode_event(event).unwrap().into_parts();
 
         let hec_event = serde_json::from_slice::<HecEventText>(&bytes[..]).unwrap();
 
@@ -415,6 +550,73 @@ mod tests {
         assert_eq!((hec_event.time * 1000f64).fract(), 0f64);
     }
 
+    #[test]
+    fn splunk_encode_event_raw() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("host", "web-1");
+        event.as_mut_log().insert("splunk_index", "main");
+
+        let (config, _cx) = load_sink::<HecSinkConfig>(
+            r#"
+            host = "test.com"
+            token = "alksjdfo"
+            endpoint_target = "raw"
+            index = "{{ splunk_index }}"
+            sourcetype = "syslog"
+
+            [encoding]
+            codec = "text"
+        "#,
+        )
+        .unwrap();
+
+        let (bytes, key) = config.encode_event(event).unwrap().into_parts();
+
+        assert_eq!(bytes, b"hello world\n".to_vec());
+        assert_eq!(
+            key,
+            PartitionKey {
+                index: Some("main".to_owned()),
+                source: None,
+                sourcetype: Some("syslog".to_owned()),
+                host: Some("web-1".to_owned()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn splunk_build_request_raw() {
+        let (config, _cx) = load_sink::<HecSinkConfig>(
+            r#"
+            host = "http://test.com"
+            token = "alksjdfo"
+            endpoint_target = "raw"
+            channel = "guid"
+
+            [encoding]
+            codec = "text"
+        "#,
+        )
+        .unwrap();
+
+        let key = PartitionKey {
+            index: Some("main".to_owned()),
+            host: Some("web-1".to_owned()),
+            ..PartitionKey::default()
+        };
+        let request = config
+            .build_request(PartitionInnerBuffer::new(b"hello world\n".to_vec(), key))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            request.uri().to_string(),
+            "http://test.com/services/collector/raw?index=main&host=web-1"
+        );
+        assert_eq!(request.headers()["X-Splunk-Request-Channel"], "guid");
+        assert_eq!(request.body(), b"hello world\n");
+    }
+
     #[test]
     fn splunk_validate_host() {
         let valid = "http://localhost:8888".to_string();
@@ -489,6 +691,29 @@ mod integration_tests {
         assert!(entry.get("message").is_none());
     }
 
+    #[tokio::test]
+    async fn splunk_insert_raw_message() {
+        let cx = SinkContext::new_test();
+
+        let config = HecSinkConfig {
+            endpoint_target: EndpointTarget::Raw,
+            sourcetype: Some(Template::try_from("_json".to_string()).unwrap()),
+            ..config(Encoding::Json, vec![]).await
+        };
+        let (sink, _) = config.build(cx).await.unwrap();
+
+        let message = random_string(100);
+        let mut event = Event::from(message.clone());
+        event.as_mut_log().insert("asdf", "hello");
+        sink.run(stream::once(ready(event))).await.unwrap();
+
+        let entry = find_entry(message.as_str()).await;
+
+        assert_eq!(message, entry["message"].as_str().unwrap());
+        assert_eq!("hello", entry["asdf"].as_str().unwrap());
+        assert_eq!("_json", entry["sourcetype"].as_str().unwrap());
+    }
+
     #[tokio::test]
     async fn splunk_insert_source() {
         let cx = SinkContext::new_test();
@@ -769,6 +994,8 @@ mod integration_tests {
             index: None,
             sourcetype: None,
             source: None,
+            endpoint_target: EndpointTarget::Event,
+            channel: None,
             encoding: encoding.into(),
             compression: Compression::None,
             batch: BatchConfig {
@@ -5,18 +5,21 @@ use crate::{
     event::metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
     internal_events::StatsdInvalidMetricReceived,
     sinks::util::{
+        buffer::metrics::{CounterNormalizer, NormalizeCounters},
         encode_namespace,
         tcp::TcpSinkConfig,
         udp::{UdpService, UdpSinkConfig},
-        BatchConfig, BatchSettings, BatchSink, Buffer, Compression,
+        BatchConfig, BatchSettings, BatchSink, Buffer, Compression, EncodedEvent,
     },
     Event,
 };
+use bytes::Bytes;
 use futures::{future, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::Display,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Mutex,
     task::{Context, Poll},
 };
 use tower::{Service, ServiceBuilder};
@@ -33,6 +36,8 @@ pub struct StatsdSinkConfig {
     pub default_namespace: Option<String>,
     #[serde(flatten)]
     pub mode: Mode,
+    #[serde(default = "default_normalize_counters")]
+    pub normalize_counters: NormalizeCounters,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -57,6 +62,12 @@ inventory::submit! {
     SinkDescription::new::<StatsdSinkConfig>("statsd")
 }
 
+// Counters are sent as they are received unless asked otherwise, so that
+// existing configs keep their output.
+fn default_normalize_counters() -> NormalizeCounters {
+    NormalizeCounters::None
+}
+
 fn default_address() -> SocketAddr {
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8125)
 }
@@ -69,6 +80,7 @@ impl GenerateConfig for StatsdSinkConfig {
                 batch: Default::default(),
                 udp: UdpSinkConfig::from_address(default_address().to_string()),
             }),
+            normalize_counters: default_normalize_counters(),
         })
         .unwrap()
     }
@@ -82,12 +94,9 @@ impl SinkConfig for StatsdSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let default_namespace = self.default_namespace.clone();
+        let mut normalizer = CounterNormalizer::new(self.normalize_counters);
         match &self.mode {
-            Mode::Tcp(config) => {
-                let encode_event =
-                    move |event| encode_event(event, default_namespace.as_deref()).map(Into::into);
-                config.build(cx, encode_event)
-            }
+            Mode::Tcp(config) => config.build(cx, stream_encoder(normalizer, default_namespace)),
             Mode::Udp(config) => {
                 // 1432 bytes is a recommended packet size to fit into MTU
                 // https://github.com/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets
@@ -108,18 +117,19 @@ impl SinkConfig for StatsdSinkConfig {
                     cx.acker(),
                 )
                 .sink_map_err(|error| error!(message = "Fatal statsd sink error.", %error))
-                .with_flat_map(move |event| {
-                    stream::iter(encode_event(event, default_namespace.as_deref())).map(Ok)
+                .with_flat_map(move |event: Event| {
+                    let encoded = EncodedEvent::encode(event, |event| {
+                        normalizer.apply(event.into_metric()).and_then(|metric| {
+                            encode_event(metric.into(), default_namespace.as_deref())
+                        })
+                    });
+                    stream::iter(encoded).map(Ok)
                 });
 
                 Ok((super::VectorSink::Sink(Box::new(sink)), healthcheck))
             }
             #[cfg(unix)]
-            Mode::Unix(config) => {
-                let encode_event =
-                    move |event| encode_event(event, default_namespace.as_deref()).map(Into::into);
-                config.build(cx, encode_event)
-            }
+            Mode::Unix(config) => config.build(cx, stream_encoder(normalizer, default_namespace)),
         }
     }
 
@@ -132,6 +142,22 @@ impl SinkConfig for StatsdSinkConfig {
     }
 }
 
+/// The encoder of the stream based modes, which can't be mutable, so the
+/// counters are normalized behind a lock.
+fn stream_encoder(
+    normalizer: CounterNormalizer,
+    default_namespace: Option<String>,
+) -> impl Fn(Event) -> Option<Bytes> + Send + Sync + 'static {
+    let normalizer = Mutex::new(normalizer);
+    move |event| {
+        let metric = normalizer
+            .lock()
+            .expect("counter normalizer mutex poisoned")
+            .apply(event.into_metric())?;
+        encode_event(metric.into(), default_namespace.as_deref()).map(Into::into)
+    }
+}
+
 fn encode_tags(tags: &MetricTags) -> String {
     let parts: Vec<_> = tags
         .iter()
@@ -385,6 +411,34 @@ mod test {
         assert_eq!(metric1, metric2);
     }
 
+    #[test]
+    fn test_normalize_absolute_counters() {
+        let encode = stream_encoder(CounterNormalizer::new(NormalizeCounters::Incremental), None);
+        let counter = |value| {
+            Event::Metric(Metric::new(
+                "counter".to_owned(),
+                MetricKind::Absolute,
+                MetricValue::Counter { value },
+            ))
+        };
+
+        assert_eq!(encode(counter(10.0)), None);
+        assert_eq!(encode(counter(15.0)), Some(Bytes::from("counter:5|c\n")));
+    }
+
+    #[test]
+    fn test_normalize_counters_defaults_to_none() {
+        let config: StatsdSinkConfig = toml::from_str(
+            r#"
+            mode = "udp"
+            address = "127.0.0.1:8125"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.normalize_counters, NormalizeCounters::None);
+    }
+
     #[tokio::test]
     async fn test_send_to_statsd() {
         trace_init();
@@ -401,6 +455,7 @@ mod test {
                 },
                 udp: UdpSinkConfig::from_address(addr.to_string()),
             }),
+            normalize_counters: NormalizeCounters::Incremental,
         };
 
         let context = SinkContext::new_test();
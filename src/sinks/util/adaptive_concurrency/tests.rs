@@ -7,8 +7,8 @@ use crate::{
     metrics::{self, capture_metrics, get_controller},
     sinks::{
         util::{
-            retries::RetryLogic, BatchSettings, Concurrency, EncodedLength, TowerRequestConfig,
-            VecBuffer,
+            retries::RetryLogic, BatchSettings, Concurrency, EncodedEvent, EncodedLength,
+            TowerRequestConfig, VecBuffer,
         },
         Healthcheck, VectorSink,
     },
@@ -22,7 +22,7 @@ use crate::{
 use core::task::Context;
 use futures::{
     future::{self, BoxFuture},
-    FutureExt, SinkExt,
+    stream, FutureExt, SinkExt, StreamExt,
 };
 use rand::{thread_rng, Rng};
 use rand_distr::Exp1;
@@ -171,6 +171,8 @@ impl SinkConfig for TestConfig {
         );
         *self.controller_stats.lock().unwrap() = stats;
 
+        let sink =
+            sink.with_flat_map(|event| stream::iter(EncodedEvent::encode(event, Some)).map(Ok));
         Ok((VectorSink::Sink(Box::new(sink)), healthcheck))
     }
 
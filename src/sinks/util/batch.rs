@@ -1,3 +1,4 @@
+use crate::internal_events::{DiscardReason, EventsDiscarded};
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
@@ -154,6 +155,10 @@ impl<B> BatchSettings<B> {
 
 pub(super) fn err_event_too_large<T>(length: usize) -> PushResult<T> {
     error!(message = "Event larger than batch size, dropping.", length = %length, internal_log_rate_secs = 1);
+    emit!(EventsDiscarded {
+        count: 1,
+        reason: DiscardReason::TooLarge,
+    });
     PushResult::Ok(false)
 }
 
@@ -5,20 +5,38 @@ use serde_json::value::{to_raw_value, RawValue, Value};
 
 pub type BoxedRawValue = Box<RawValue>;
 
+const ARRAY_OVERHEAD: usize = "[]".len();
+
 /// A `batch` implementation for storing an array of json
 /// values.
+///
+/// The batch size is accounted as the size of the encoded payload,
+/// including the array brackets, the separating commas, and the
+/// payload wrapping the array, if any.
 #[derive(Debug)]
 pub struct JsonArrayBuffer {
     buffer: Vec<BoxedRawValue>,
     total_bytes: usize,
+    wrapper_bytes: usize,
     settings: BatchSize<Self>,
 }
 
 impl JsonArrayBuffer {
+    /// Creates a buffer whose output is sent as the whole payload, like the
+    /// requests of the `datadog_logs`, `honeycomb` and `azure_monitor_logs`
+    /// sinks.
     pub fn new(settings: BatchSize<Self>) -> Self {
+        Self::with_wrapper(settings, ARRAY_OVERHEAD)
+    }
+
+    /// Creates a buffer whose output is sent wrapped in another JSON
+    /// payload, `wrapper_bytes` being the encoded size of that payload
+    /// holding an empty array.
+    pub fn with_wrapper(settings: BatchSize<Self>, wrapper_bytes: usize) -> Self {
         Self {
             buffer: Vec::new(),
-            total_bytes: 0,
+            total_bytes: wrapper_bytes,
+            wrapper_bytes,
             settings,
         }
     }
@@ -39,9 +57,10 @@ impl Batch for JsonArrayBuffer {
 
     fn push(&mut self, item: Self::Input) -> PushResult<Self::Input> {
         let raw_item = to_raw_value(&item).expect("Value should be valid json");
-        let new_len = self.total_bytes + raw_item.get().len() + 1;
-        if self.is_empty() && new_len >= self.settings.bytes {
-            err_event_too_large(raw_item.get().len())
+        let separator = if self.is_empty() { 0 } else { 1 };
+        let new_len = self.total_bytes + raw_item.get().len() + separator;
+        if self.is_empty() && new_len > self.settings.bytes {
+            err_event_too_large(new_len)
         } else if self.buffer.len() >= self.settings.events || new_len > self.settings.bytes {
             PushResult::Overflow(item)
         } else {
@@ -58,7 +77,7 @@ impl Batch for JsonArrayBuffer {
     }
 
     fn fresh(&self) -> Self {
-        Self::new(self.settings)
+        Self::with_wrapper(self.settings, self.wrapper_bytes)
     }
 
     fn finish(self) -> Self::Output {
@@ -98,9 +117,10 @@ mod tests {
         assert!(matches!(buffer.push(json!({})), PushResult::Overflow(_)));
 
         assert_eq!(buffer.num_items(), 2);
-        assert_eq!(buffer.total_bytes, 36);
+        assert_eq!(buffer.total_bytes, 37);
 
         let json = buffer.finish();
+        assert_eq!(serde_json::to_vec(&json).unwrap().len(), 37);
 
         let wrapped = serde_json::to_string(&json!({
             "arr": json,
@@ -121,4 +141,38 @@ mod tests {
 
         assert_eq!(wrapped, expected);
     }
+
+    #[test]
+    fn accounts_wrapper_bytes() {
+        let wrapper_bytes = serde_json::to_vec(&json!({ "arr": [] })).unwrap().len();
+        let batch = BatchSettings::default().bytes(35).events(10).size;
+        let mut buffer = JsonArrayBuffer::with_wrapper(batch, wrapper_bytes);
+
+        assert_eq!(buffer.push(json!({ "k": "v" })), PushResult::Ok(false));
+        assert_eq!(buffer.push(json!({ "k": "v" })), PushResult::Ok(false));
+        assert!(matches!(
+            buffer.push(json!({ "k": "v" })),
+            PushResult::Overflow(_)
+        ));
+
+        assert_eq!(buffer.fresh().total_bytes, wrapper_bytes);
+        let total_bytes = buffer.total_bytes;
+
+        let json = buffer.finish();
+        let body = serde_json::to_vec(&json!({ "arr": json })).unwrap();
+        assert_eq!(body.len(), total_bytes);
+    }
+
+    #[test]
+    fn rejects_event_larger_than_batch() {
+        let wrapper_bytes = serde_json::to_vec(&json!({ "arr": [] })).unwrap().len();
+        let batch = BatchSettings::default().bytes(20).events(10).size;
+        let mut buffer = JsonArrayBuffer::with_wrapper(batch, wrapper_bytes);
+
+        assert_eq!(
+            buffer.push(json!({ "key": "value" })),
+            PushResult::Ok(false)
+        );
+        assert!(buffer.is_empty());
+    }
 }
@@ -8,7 +8,10 @@ use super::{
     err_event_too_large, json::BoxedRawValue, Batch, BatchConfig, BatchError, BatchSettings,
     BatchSize, PushResult,
 };
-use crate::sinks::loki::OutOfOrderAction;
+use crate::{
+    internal_events::{DiscardReason, EventsDiscarded},
+    sinks::loki::OutOfOrderAction,
+};
 use dashmap::DashMap;
 use serde_json::{json, value::to_raw_value};
 use std::collections::HashMap;
@@ -147,6 +150,10 @@ impl Batch for LokiBuffer {
                         msg = "Received out-of-order event; dropping event.",
                         internal_log_rate_secs = 30
                     );
+                    emit!(EventsDiscarded {
+                        count: 1,
+                        reason: DiscardReason::OutOfOrder,
+                    });
                     return PushResult::Ok(self.is_full());
                 }
                 OutOfOrderAction::RewriteTimestamp => {
@@ -3,6 +3,7 @@ use crate::{
     sinks::util::batch::{Batch, BatchConfig, BatchError, BatchSettings, BatchSize, PushResult},
     Event,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     collections::HashSet,
@@ -293,6 +294,50 @@ impl MetricSet {
     }
 }
 
+/// The kind counters are converted to before being sent by a sink.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizeCounters {
+    /// Counters are sent as they are received.
+    None,
+    /// Absolute counters are converted to the delta since their previous
+    /// value.
+    Incremental,
+    /// Incremental counters are summed into the total of their series.
+    Absolute,
+}
+
+/// Normalizes the counters of a stream of metrics, for sinks that don't
+/// batch them in a `MetricsBuffer`. The state of every series is kept
+/// between events, so absolute counters are dropped until their first value
+/// is known.
+#[derive(Clone)]
+pub struct CounterNormalizer {
+    mode: NormalizeCounters,
+    state: MetricSet,
+}
+
+impl CounterNormalizer {
+    pub fn new(mode: NormalizeCounters) -> Self {
+        Self {
+            mode,
+            state: MetricSet::default(),
+        }
+    }
+
+    pub fn apply(&mut self, metric: Metric) -> Option<Metric> {
+        match (&metric.data.value, self.mode) {
+            (MetricValue::Counter { .. }, NormalizeCounters::Incremental) => {
+                self.state.make_incremental(metric)
+            }
+            (MetricValue::Counter { .. }, NormalizeCounters::Absolute) => {
+                self.state.make_absolute(metric)
+            }
+            _ => Some(metric),
+        }
+    }
+}
+
 fn compress_distribution(mut samples: Vec<Sample>) -> Vec<Sample> {
     if samples.is_empty() {
         return Vec::new();
@@ -852,6 +897,60 @@ mod test {
         assert_eq!(buffer.len(), 1);
     }
 
+    #[test]
+    fn normalize_counters_incremental() {
+        let mut normalizer = CounterNormalizer::new(NormalizeCounters::Incremental);
+
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "production", Absolute, 10.0)),
+            None
+        );
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "production", Absolute, 15.0)),
+            Some(sample_counter(0, "production", Incremental, 5.0))
+        );
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "staging", Absolute, 1.0)),
+            None
+        );
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "production", Incremental, 2.0)),
+            Some(sample_counter(0, "production", Incremental, 2.0))
+        );
+        assert_eq!(
+            normalizer.apply(sample_gauge(0, Absolute, 1.0)),
+            Some(sample_gauge(0, Absolute, 1.0))
+        );
+    }
+
+    #[test]
+    fn normalize_counters_absolute() {
+        let mut normalizer = CounterNormalizer::new(NormalizeCounters::Absolute);
+
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "production", Incremental, 1.0)),
+            Some(sample_counter(0, "production", Absolute, 1.0))
+        );
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "production", Incremental, 2.0)),
+            Some(sample_counter(0, "production", Absolute, 3.0))
+        );
+        assert_eq!(
+            normalizer.apply(sample_gauge(0, Incremental, 1.0)),
+            Some(sample_gauge(0, Incremental, 1.0))
+        );
+    }
+
+    #[test]
+    fn normalize_counters_none() {
+        let mut normalizer = CounterNormalizer::new(NormalizeCounters::None);
+
+        assert_eq!(
+            normalizer.apply(sample_counter(0, "production", Absolute, 10.0)),
+            Some(sample_counter(0, "production", Absolute, 10.0))
+        );
+    }
+
     fn sample_counter(num: usize, tagstr: &str, kind: MetricKind, value: f64) -> Metric {
         Metric::new(
             format!("counter-{}", num),
@@ -20,6 +20,7 @@ pub struct Buffer {
     inner: InnerBuffer,
     num_items: usize,
     num_bytes: usize,
+    overhead_bytes: usize,
     settings: BatchSize<Self>,
     compression: Compression,
 }
@@ -32,6 +33,16 @@ pub enum InnerBuffer {
 
 impl Buffer {
     pub fn new(settings: BatchSize<Self>, compression: Compression) -> Self {
+        Self::with_overhead(settings, compression, 0)
+    }
+
+    /// Creates a buffer whose output is sent along with `overhead_bytes`
+    /// more bytes, which are accounted in the size of the batch.
+    pub fn with_overhead(
+        settings: BatchSize<Self>,
+        compression: Compression,
+        overhead_bytes: usize,
+    ) -> Self {
         let buffer = Vec::with_capacity(settings.bytes);
         let inner = match compression {
             Compression::None => InnerBuffer::Plain(buffer),
@@ -46,7 +57,8 @@ impl Buffer {
         Self {
             inner,
             num_items: 0,
-            num_bytes: 0,
+            num_bytes: overhead_bytes,
+            overhead_bytes,
             settings,
             compression,
         }
@@ -90,8 +102,8 @@ impl Batch for Buffer {
         // can't track compressed sizes. Keep a running count of the
         // number of bytes written instead.
         let new_bytes = self.num_bytes + item.len();
-        if self.is_empty() && item.len() > self.settings.bytes {
-            err_event_too_large(item.len())
+        if self.is_empty() && new_bytes > self.settings.bytes {
+            err_event_too_large(new_bytes)
         } else if self.num_items >= self.settings.events || new_bytes > self.settings.bytes {
             PushResult::Overflow(item)
         } else {
@@ -108,7 +120,7 @@ impl Batch for Buffer {
     }
 
     fn fresh(&self) -> Self {
-        Self::new(self.settings, self.compression)
+        Self::with_overhead(self.settings, self.compression, self.overhead_bytes)
     }
 
     fn finish(self) -> Self::Output {
@@ -130,7 +142,7 @@ mod test {
     use super::{Buffer, Compression};
     use crate::{
         buffers::Acker,
-        sinks::util::{BatchSettings, BatchSink},
+        sinks::util::{Batch, BatchSettings, BatchSink, EncodedEvent, PushResult},
     };
     use futures::{future, stream, SinkExt, StreamExt};
     use std::{
@@ -139,6 +151,33 @@ mod test {
     };
     use tokio::time::Duration;
 
+    #[test]
+    fn accounts_overhead_bytes() {
+        let batch_size = BatchSettings::default().bytes(10).events(10).size;
+        let mut buffer = Buffer::with_overhead(batch_size, Compression::None, 2);
+
+        assert_eq!(
+            Batch::push(&mut buffer, b"abcd".to_vec()),
+            PushResult::Ok(false)
+        );
+        assert!(matches!(
+            Batch::push(&mut buffer, b"abcde".to_vec()),
+            PushResult::Overflow(_)
+        ));
+        assert_eq!(
+            Batch::push(&mut buffer, b"ef".to_vec()),
+            PushResult::Ok(false)
+        );
+        assert_eq!(
+            Batch::push(&mut buffer, b"gh".to_vec()),
+            PushResult::Ok(true)
+        );
+
+        let fresh = buffer.fresh();
+        assert_eq!(fresh.num_bytes, 2);
+        assert_eq!(buffer.finish(), b"abcdefgh".to_vec());
+    }
+
     #[tokio::test]
     async fn gzip() {
         use flate2::read::GzDecoder;
@@ -168,7 +207,7 @@ mod test {
 
         let _ = buffered
             .sink_map_err(drop)
-            .send_all(&mut stream::iter(input).map(Ok))
+            .send_all(&mut stream::iter(input).map(EncodedEvent::new).map(Ok))
             .await
             .unwrap();
 
@@ -1,7 +1,7 @@
 use super::{
     retries::{RetryAction, RetryLogic},
-    sink, Batch, Partition, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
-    TowerRequestSettings,
+    sink, Batch, EncodedEvent, Partition, PushResult, TowerBatchedSink, TowerPartitionSink,
+    TowerRequestConfig, TowerRequestSettings,
 };
 use crate::{buffers::Acker, http::HttpClient, Event};
 use bytes::{Buf, Bytes};
@@ -31,6 +31,90 @@ pub trait HttpSink: Send + Sync + 'static {
     async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Vec<u8>>>;
 }
 
+/// Encodes `events` with `sink` into batches like the batched sinks do, and
+/// returns the bodies of the requests built out of the batches, in order.
+pub async fn encode_request_bodies<T, B>(
+    sink: &T,
+    batch: B,
+    events: Vec<Event>,
+) -> crate::Result<Vec<Vec<u8>>>
+where
+    B: Batch,
+    T: HttpSink<Input = B::Input, Output = B::Output>,
+{
+    build_request_bodies(sink, batch, events, |_| ()).await
+}
+
+/// Like `encode_request_bodies`, for the sinks batching the events of each
+/// partition separately.
+pub async fn encode_partitioned_request_bodies<T, B, K>(
+    sink: &T,
+    batch: B,
+    events: Vec<Event>,
+) -> crate::Result<Vec<Vec<u8>>>
+where
+    B: Batch,
+    B::Input: Partition<K>,
+    K: Hash + Eq,
+    T: HttpSink<Input = B::Input, Output = B::Output>,
+{
+    build_request_bodies(sink, batch, events, <B::Input as Partition<K>>::partition).await
+}
+
+async fn build_request_bodies<T, B, K>(
+    sink: &T,
+    empty: B,
+    events: Vec<Event>,
+    partition: impl Fn(&B::Input) -> K,
+) -> crate::Result<Vec<Vec<u8>>>
+where
+    B: Batch,
+    K: Hash + Eq,
+    T: HttpSink<Input = B::Input, Output = B::Output>,
+{
+    let mut batches = IndexMap::new();
+    let mut outputs = Vec::new();
+
+    for event in events {
+        let mut item = match sink.encode_event(event) {
+            Some(item) => item,
+            None => continue,
+        };
+        let batch = batches
+            .entry(partition(&item))
+            .or_insert_with(|| empty.fresh());
+
+        loop {
+            match batch.push(item) {
+                PushResult::Ok(false) => break,
+                PushResult::Ok(true) => {
+                    outputs.push(batch.fresh_replace().finish());
+                    break;
+                }
+                PushResult::Overflow(overflow) if !batch.is_empty() => {
+                    outputs.push(batch.fresh_replace().finish());
+                    item = overflow;
+                }
+                PushResult::Overflow(_) => return Err("Event is too large for a batch.".into()),
+            }
+        }
+    }
+
+    outputs.extend(
+        batches
+            .into_iter()
+            .map(|(_, batch)| batch)
+            .filter(|batch| !batch.is_empty())
+            .map(Batch::finish),
+    );
+
+    let mut bodies = Vec::with_capacity(outputs.len());
+    for output in outputs {
+        bodies.push(sink.build_request(output).await?.into_body());
+    }
+    Ok(bodies)
+}
+
 /// Provides a simple wrapper around internal tower and
 /// batching sinks for http.
 ///
@@ -62,7 +146,7 @@ where
     // An empty slot is needed to buffer an item where we encoded it but
     // the inner sink is applying back pressure. This trick is used in the `WithFlatMap`
     // sink combinator. https://docs.rs/futures/0.1.29/src/futures/sink/with_flat_map.rs.html#20
-    slot: Option<B::Input>,
+    slot: Option<EncodedEvent<B::Input>>,
 }
 
 impl<T, B> BatchedHttpSink<T, B, HttpRetryLogic>
@@ -153,7 +237,8 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        if let Some(item) = self.sink.encode_event(item) {
+        let encoded = EncodedEvent::encode(item, |event| self.sink.encode_event(event));
+        if let Some(item) = encoded {
             *self.project().slot = Some(item);
         }
 
@@ -195,7 +280,7 @@ where
         K,
         B::Output,
     >,
-    slot: Option<B::Input>,
+    slot: Option<EncodedEvent<B::Input>>,
 }
 
 impl<T, B, K> PartitionHttpSink<T, B, K, HttpRetryLogic>
@@ -292,7 +377,8 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        if let Some(item) = self.sink.encode_event(item) {
+        let encoded = EncodedEvent::encode(item, |event| self.sink.encode_event(event));
+        if let Some(item) = encoded {
             *self.project().slot = Some(item);
         }
 
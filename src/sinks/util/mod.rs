@@ -17,7 +17,7 @@ pub mod udp;
 pub mod unix;
 pub mod uri;
 
-use crate::event::Event;
+use crate::event::{finalization::EventFinalizers, Event};
 use bytes::Bytes;
 use encoding::{EncodingConfig, EncodingConfiguration};
 use serde::{Deserialize, Serialize};
@@ -37,6 +37,32 @@ pub use service::{
 pub use sink::{BatchSink, PartitionBatchSink, StreamSink};
 pub use uri::UriSerde;
 
+/// An item encoded out of an event for a batch, along with the finalizers of
+/// the event, which are updated once the request holding the item is done.
+#[derive(Debug)]
+pub struct EncodedEvent<I> {
+    pub item: I,
+    pub finalizers: EventFinalizers,
+}
+
+impl<I> EncodedEvent<I> {
+    /// An item whose delivery isn't tracked.
+    pub fn new(item: I) -> Self {
+        Self {
+            item,
+            finalizers: Default::default(),
+        }
+    }
+
+    /// Encodes the event with `encode`, keeping the finalizers of the event
+    /// with the item. Events that aren't encoded are dropped along with their
+    /// finalizers, which reports them as dropped to their source.
+    pub fn encode(mut event: Event, encode: impl FnOnce(Event) -> Option<I>) -> Option<Self> {
+        let finalizers = event.take_finalizers();
+        encode(event).map(|item| Self { item, finalizers })
+    }
+}
+
 #[derive(Debug, Snafu)]
 enum SinkBuildError {
     #[snafu(display("Missing host in address field"))]
@@ -34,8 +34,14 @@
 use super::{
     batch::{Batch, PushResult, StatefulBatch},
     buffer::partition::Partition,
+    EncodedEvent,
+};
+use crate::{
+    buffers::Acker,
+    event::finalization::{EventFinalizers, EventStatus},
+    internal_events::{DiscardReason, EventsDiscarded, SinkRequestCompleted},
+    Event,
 };
-use crate::{buffers::Acker, Event};
 use async_trait::async_trait;
 use futures::{
     future::BoxFuture,
@@ -51,6 +57,7 @@ use std::{
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
+    time::Instant,
 };
 use tokio::{
     sync::oneshot,
@@ -82,6 +89,9 @@ pub trait StreamSink {
 /// batches have been acked. This means if sequential requests r1, r2,
 /// and r3 are dispatched and r2 and r3 complete, all events contained
 /// in all requests will not be acked until r1 has completed.
+///
+/// The finalizers of the events are held along with their batch, and
+/// updated with the status of its request as soon as it completes.
 #[pin_project]
 pub struct BatchSink<S, B, Request>
 where
@@ -89,7 +99,8 @@ where
 {
     service: ServiceSink<S, Request>,
     batch: StatefulBatch<B>,
-    buffer: Option<B::Input>,
+    finalizers: EventFinalizers,
+    buffer: Option<EncodedEvent<B::Input>>,
     timeout: Duration,
     linger: Option<Delay>,
     closing: bool,
@@ -109,6 +120,7 @@ where
         Self {
             service,
             batch: batch.into(),
+            finalizers: Default::default(),
             buffer: None,
             timeout,
             linger: None,
@@ -126,7 +138,7 @@ where
     }
 }
 
-impl<S, B, Request> Sink<B::Input> for BatchSink<S, B, Request>
+impl<S, B, Request> Sink<EncodedEvent<B::Input>> for BatchSink<S, B, Request>
 where
     S: Service<Request>,
     S::Future: Send + 'static,
@@ -152,7 +164,10 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn start_send(mut self: Pin<&mut Self>, item: B::Input) -> Result<(), Self::Error> {
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        item: EncodedEvent<B::Input>,
+    ) -> Result<(), Self::Error> {
         if self.linger.is_none() {
             trace!("Starting new batch timer.");
             // We just inserted the first item of a new batch, so set our delay to the longest time
@@ -160,8 +175,10 @@ where
             self.linger = Some(delay_for(self.timeout));
         }
 
-        if let PushResult::Overflow(item) = self.batch.push(item) {
-            self.buffer = Some(item);
+        let EncodedEvent { item, finalizers } = item;
+        match self.batch.push(item) {
+            PushResult::Ok(_) => self.finalizers.merge(finalizers),
+            PushResult::Overflow(item) => self.buffer = Some(EncodedEvent { item, finalizers }),
         }
 
         Ok(())
@@ -193,11 +210,12 @@ where
                     trace!("Service ready; Sending batch.");
 
                     let batch = self.batch.fresh_replace();
+                    let finalizers = self.finalizers.take();
                     self.linger = None;
 
                     let batch_size = batch.num_items();
                     let request = batch.finish();
-                    tokio::spawn(self.service.call(request, batch_size));
+                    tokio::spawn(self.service.call(request, batch_size, finalizers));
 
                     continue;
                 }
@@ -258,15 +276,19 @@ where
 /// batches have been acked. This means if sequential requests r1, r2,
 /// and r3 are dispatched and r2 and r3 complete, all events contained
 /// in all requests will not be acked until r1 has completed.
+///
+/// As with `BatchSink`, the finalizers of the events are held along with
+/// the batch of their partition.
 #[pin_project]
 pub struct PartitionBatchSink<B, S, K, Request>
 where
     B: Batch<Output = Request>,
 {
     service: ServiceSink<S, Request>,
-    buffer: Option<(K, B::Input)>,
+    buffer: Option<(K, EncodedEvent<B::Input>)>,
     batch: StatefulBatch<B>,
     partitions: HashMap<K, StatefulBatch<B>>,
+    finalizers: HashMap<K, EventFinalizers>,
     timeout: Duration,
     lingers: HashMap<K, Delay>,
     closing: bool,
@@ -290,6 +312,7 @@ where
             buffer: None,
             batch: batch.into(),
             partitions: HashMap::new(),
+            finalizers: HashMap::new(),
             timeout,
             lingers: HashMap::new(),
             closing: false,
@@ -297,7 +320,7 @@ where
     }
 }
 
-impl<B, S, K, Request> Sink<B::Input> for PartitionBatchSink<B, S, K, Request>
+impl<B, S, K, Request> Sink<EncodedEvent<B::Input>> for PartitionBatchSink<B, S, K, Request>
 where
     B: Batch<Output = Request>,
     B::Input: Partition<K>,
@@ -325,7 +348,11 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn start_send(mut self: Pin<&mut Self>, item: B::Input) -> Result<(), Self::Error> {
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        item: EncodedEvent<B::Input>,
+    ) -> Result<(), Self::Error> {
+        let EncodedEvent { item, finalizers } = item;
         let partition = item.partition();
 
         let batch = loop {
@@ -340,8 +367,15 @@ where
             self.lingers.insert(partition.clone(), delay);
         };
 
-        if let PushResult::Overflow(item) = batch.push(item) {
-            self.buffer = Some((partition, item));
+        match batch.push(item) {
+            PushResult::Ok(_) => self
+                .finalizers
+                .entry(partition)
+                .or_default()
+                .merge(finalizers),
+            PushResult::Overflow(item) => {
+                self.buffer = Some((partition, EncodedEvent { item, finalizers }))
+            }
         }
 
         Ok(())
@@ -383,11 +417,12 @@ where
                     trace!("Service ready; Sending batch.");
 
                     let batch = self.partitions.remove(&partition).unwrap();
+                    let finalizers = self.finalizers.remove(&partition).unwrap_or_default();
                     self.lingers.remove(&partition);
 
                     let batch_size = batch.num_items();
                     let request = batch.finish();
-                    tokio::spawn(self.service.call(request, batch_size));
+                    tokio::spawn(self.service.call(request, batch_size, finalizers));
 
                     batch_consumed = true;
                 } else {
@@ -477,7 +512,12 @@ where
         self.service.poll_ready(cx).map_err(Into::into)
     }
 
-    fn call(&mut self, req: Request, batch_size: usize) -> BoxFuture<'static, ()> {
+    fn call(
+        &mut self,
+        req: Request,
+        batch_size: usize,
+        finalizers: EventFinalizers,
+    ) -> BoxFuture<'static, ()> {
         let seqno = self.seq_head;
         self.seq_head += 1;
 
@@ -492,21 +532,41 @@ where
             message = "Submitting service request.",
             in_flight_requests = self.in_flight.len()
         );
+        let start = Instant::now();
         self.service
             .call(req)
             .err_into()
             .map(move |result| {
-                match result {
+                emit!(SinkRequestCompleted {
+                    batch_size,
+                    duration: start.elapsed(),
+                });
+
+                let status = match result {
                     Ok(response) if response.is_successful() => {
                         trace!(message = "Response successful.", ?response);
+                        EventStatus::Delivered
                     }
                     Ok(response) => {
                         error!(message = "Response wasn't successful.", ?response);
+                        emit!(EventsDiscarded {
+                            count: batch_size,
+                            reason: DiscardReason::RequestFailed,
+                        });
+                        EventStatus::Errored
                     }
                     Err(error) => {
                         error!(message = "Request failed.", %error);
+                        emit!(EventsDiscarded {
+                            count: batch_size,
+                            reason: DiscardReason::RequestFailed,
+                        });
+                        EventStatus::Errored
                     }
-                }
+                };
+                // The events of the request are done with, whatever the
+                // status of the requests before it.
+                finalizers.update_status(status);
 
                 // If the rx end is dropped we still completed
                 // the request so this is a weird case that we can
@@ -571,6 +631,7 @@ mod tests {
     use super::*;
     use crate::{
         buffers::Acker,
+        event::finalization::{BatchNotifier, BatchStatus, EventFinalizer},
         sinks::util::{BatchSettings, EncodedLength, VecBuffer},
         test_util::trace_init,
     };
@@ -606,7 +667,7 @@ mod tests {
 
         let _ = buffered
             .sink_map_err(drop)
-            .send_all(&mut stream::iter(0..22).map(Ok))
+            .send_all(&mut stream::iter(0..22).map(EncodedEvent::new).map(Ok))
             .await
             .unwrap();
 
@@ -648,17 +709,26 @@ mod tests {
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(0), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(0)),
+            Ok(())
+        ));
         assert!(matches!(
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(1), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(1)),
+            Ok(())
+        ));
         assert!(matches!(
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(2), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(2)),
+            Ok(())
+        ));
 
         // Clear internal buffer
         assert!(matches!(sink.poll_flush_unpin(&mut cx), Poll::Pending));
@@ -687,17 +757,26 @@ mod tests {
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(3), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(3)),
+            Ok(())
+        ));
         assert!(matches!(
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(4), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(4)),
+            Ok(())
+        ));
         assert!(matches!(
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(5), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(5)),
+            Ok(())
+        ));
 
         // Clear internal buffer
         assert!(matches!(sink.poll_flush_unpin(&mut cx), Poll::Pending));
@@ -750,7 +829,7 @@ mod tests {
 
         let _ = buffered
             .sink_map_err(drop)
-            .send_all(&mut stream::iter(0..22).map(Ok))
+            .send_all(&mut stream::iter(0..22).map(EncodedEvent::new).map(Ok))
             .await
             .unwrap();
 
@@ -784,12 +863,18 @@ mod tests {
             buffered.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(buffered.start_send_unpin(0), Ok(())));
+        assert!(matches!(
+            buffered.start_send_unpin(EncodedEvent::new(0)),
+            Ok(())
+        ));
         assert!(matches!(
             buffered.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(buffered.start_send_unpin(1), Ok(())));
+        assert!(matches!(
+            buffered.start_send_unpin(EncodedEvent::new(1)),
+            Ok(())
+        ));
 
         buffered.close().await.unwrap();
 
@@ -816,12 +901,18 @@ mod tests {
             buffered.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(buffered.start_send_unpin(0), Ok(())));
+        assert!(matches!(
+            buffered.start_send_unpin(EncodedEvent::new(0)),
+            Ok(())
+        ));
         assert!(matches!(
             buffered.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(buffered.start_send_unpin(1), Ok(())));
+        assert!(matches!(
+            buffered.start_send_unpin(EncodedEvent::new(1)),
+            Ok(())
+        ));
 
         // Move clock forward by linger timeout + 1 sec
         advance_time(TIMEOUT + Duration::from_secs(1)).await;
@@ -851,7 +942,7 @@ mod tests {
         let sink = PartitionBatchSink::new(svc, VecBuffer::new(batch.size), TIMEOUT, acker);
 
         sink.sink_map_err(drop)
-            .send_all(&mut stream::iter(0..22).map(Ok))
+            .send_all(&mut stream::iter(0..22).map(EncodedEvent::new).map(Ok))
             .await
             .unwrap();
 
@@ -882,7 +973,7 @@ mod tests {
 
         let input = vec![Partitions::A, Partitions::B];
         sink.sink_map_err(drop)
-            .send_all(&mut stream::iter(input).map(Ok))
+            .send_all(&mut stream::iter(input).map(EncodedEvent::new).map(Ok))
             .await
             .unwrap();
 
@@ -907,7 +998,7 @@ mod tests {
 
         let input = vec![Partitions::A, Partitions::B, Partitions::A, Partitions::B];
         sink.sink_map_err(drop)
-            .send_all(&mut stream::iter(input).map(Ok))
+            .send_all(&mut stream::iter(input).map(EncodedEvent::new).map(Ok))
             .await
             .unwrap();
 
@@ -941,7 +1032,10 @@ mod tests {
             sink.poll_ready_unpin(&mut cx),
             Poll::Ready(Ok(()))
         ));
-        assert!(matches!(sink.start_send_unpin(1), Ok(())));
+        assert!(matches!(
+            sink.start_send_unpin(EncodedEvent::new(1)),
+            Ok(())
+        ));
         assert!(matches!(sink.poll_flush_unpin(&mut cx), Poll::Pending));
 
         advance_time(TIMEOUT + Duration::from_secs(1)).await;
@@ -973,8 +1067,8 @@ mod tests {
         let mut sink = ServiceSink::new(svc, acker);
 
         // send some initial requests
-        let mut fut1 = sink.call(1, 1);
-        let mut fut2 = sink.call(2, 2);
+        let mut fut1 = sink.call(1, 1, Default::default());
+        let mut fut2 = sink.call(2, 2, Default::default());
 
         assert_eq!(ack_counter.load(Relaxed), 0);
 
@@ -986,8 +1080,8 @@ mod tests {
         assert_eq!(ack_counter.load(Relaxed), 3);
 
         // send one request that will error and one normal
-        let mut fut3 = sink.call(3, 3); // i will error
-        let mut fut4 = sink.call(4, 4);
+        let mut fut3 = sink.call(3, 3, Default::default()); // i will error
+        let mut fut4 = sink.call(4, 4, Default::default());
 
         // make sure they all "worked"
         assert!(matches!(fut3.poll_unpin(&mut cx), Poll::Ready(())));
@@ -997,6 +1091,43 @@ mod tests {
         assert_eq!(ack_counter.load(Relaxed), 10);
     }
 
+    #[tokio::test]
+    async fn batch_sink_finalizes_events_with_request_status() {
+        let (acker, _) = Acker::new_for_testing();
+
+        let svc = tower::service_fn(|req: Vec<usize>| {
+            if req.contains(&3) {
+                future::err("bad")
+            } else {
+                future::ok("good")
+            }
+        });
+        let batch = BatchSettings::default().bytes(9999).events(2);
+        let sink = BatchSink::new(svc, VecBuffer::new(batch.size), TIMEOUT, acker);
+
+        let encoded = |item, batch: &Arc<BatchNotifier>| EncodedEvent {
+            item,
+            finalizers: EventFinalizers::new(EventFinalizer::new(Arc::clone(batch))),
+        };
+        let (delivered, delivered_receiver) = BatchNotifier::new_with_receiver();
+        let (errored, errored_receiver) = BatchNotifier::new_with_receiver();
+        let input = vec![
+            encoded(0, &delivered),
+            encoded(1, &delivered),
+            encoded(2, &errored),
+            encoded(3, &errored),
+        ];
+        drop((delivered, errored));
+
+        sink.sink_map_err(drop)
+            .send_all(&mut stream::iter(input).map(Ok))
+            .await
+            .unwrap();
+
+        assert_eq!(delivered_receiver.await, Ok(BatchStatus::Delivered));
+        assert_eq!(errored_receiver.await, Ok(BatchStatus::Errored));
+    }
+
     #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
     enum Partitions {
         A,
@@ -53,6 +53,10 @@ pub struct TcpSinkConfig {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: Option<TlsConfig>,
     send_buffer_bytes: Option<usize>,
+    receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    nodelay: bool,
+    user_timeout_secs: Option<u64>,
 }
 
 impl TcpSinkConfig {
@@ -61,22 +65,23 @@ impl TcpSinkConfig {
         keepalive: Option<TcpKeepaliveConfig>,
         tls: Option<TlsConfig>,
         send_buffer_bytes: Option<usize>,
+        receive_buffer_bytes: Option<usize>,
+        nodelay: bool,
+        user_timeout_secs: Option<u64>,
     ) -> Self {
         Self {
             address,
             keepalive,
             tls,
             send_buffer_bytes,
+            receive_buffer_bytes,
+            nodelay,
+            user_timeout_secs,
         }
     }
 
     pub fn from_address(address: String) -> Self {
-        Self {
-            address,
-            keepalive: None,
-            tls: None,
-            send_buffer_bytes: None,
-        }
+        Self::new(address, None, None, None, None, false, None)
     }
 
     pub fn build(
@@ -88,7 +93,16 @@ impl TcpSinkConfig {
         let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
         let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+        let connector = TcpConnector {
+            host,
+            port,
+            keepalive: self.keepalive,
+            tls,
+            send_buffer_bytes: self.send_buffer_bytes,
+            receive_buffer_bytes: self.receive_buffer_bytes,
+            nodelay: self.nodelay,
+            user_timeout: self.user_timeout_secs.map(Duration::from_secs),
+        };
         let sink = TcpSink::new(connector.clone(), cx.acker(), encode_event);
 
         Ok((
@@ -105,30 +119,26 @@ struct TcpConnector {
     keepalive: Option<TcpKeepaliveConfig>,
     tls: MaybeTlsSettings,
     send_buffer_bytes: Option<usize>,
+    receive_buffer_bytes: Option<usize>,
+    nodelay: bool,
+    user_timeout: Option<Duration>,
 }
 
 impl TcpConnector {
-    fn new(
-        host: String,
-        port: u16,
-        keepalive: Option<TcpKeepaliveConfig>,
-        tls: MaybeTlsSettings,
-        send_buffer_bytes: Option<usize>,
-    ) -> Self {
+    #[cfg(test)]
+    fn from_host_port(host: String, port: u16) -> Self {
         Self {
             host,
             port,
-            keepalive,
-            tls,
-            send_buffer_bytes,
+            keepalive: None,
+            tls: None.into(),
+            send_buffer_bytes: None,
+            receive_buffer_bytes: None,
+            nodelay: false,
+            user_timeout: None,
         }
     }
 
-    #[cfg(test)]
-    fn from_host_port(host: String, port: u16) -> Self {
-        Self::new(host, port, None, None.into(), None)
-    }
-
     fn fresh_backoff() -> ExponentialBackoff {
         // TODO: make configurable
         ExponentialBackoff::from_millis(2)
@@ -162,6 +172,24 @@ impl TcpConnector {
                     }
                 }
 
+                if let Some(receive_buffer_bytes) = self.receive_buffer_bytes {
+                    if let Err(error) = maybe_tls.set_receive_buffer_bytes(receive_buffer_bytes) {
+                        warn!(message = "Failed configuring receive buffer size on TCP socket.", %error);
+                    }
+                }
+
+                if self.nodelay {
+                    if let Err(error) = maybe_tls.set_nodelay(true) {
+                        warn!(message = "Failed configuring TCP nodelay.", %error);
+                    }
+                }
+
+                if let Some(user_timeout) = self.user_timeout {
+                    if let Err(error) = maybe_tls.set_user_timeout(user_timeout) {
+                        warn!(message = "Failed configuring TCP user timeout.", %error);
+                    }
+                }
+
                 maybe_tls
             })
     }
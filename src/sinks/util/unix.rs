@@ -2,7 +2,7 @@ use crate::{
     buffers::Acker,
     config::SinkContext,
     internal_events::{
-        ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionEstablished,
+        error_stage, ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionEstablished,
         UnixSocketConnectionFailed, UnixSocketError,
     },
     sink::VecSinkExt,
@@ -151,7 +151,8 @@ impl StreamSink for UnixSink {
             if let Err(error) = result {
                 emit!(UnixSocketError {
                     error,
-                    path: &self.connector.path
+                    path: &self.connector.path,
+                    stage: error_stage::SENDING,
                 });
             }
         }
@@ -20,6 +20,10 @@ pub struct VectorSinkConfig {
     #[set = "pub"]
     tls: Option<TlsConfig>,
     send_buffer_bytes: Option<usize>,
+    receive_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    nodelay: bool,
+    user_timeout_secs: Option<u64>,
 }
 
 impl VectorSinkConfig {
@@ -34,6 +38,9 @@ impl VectorSinkConfig {
             keepalive,
             tls,
             send_buffer_bytes,
+            receive_buffer_bytes: None,
+            nodelay: false,
+            user_timeout_secs: None,
         }
     }
 
@@ -72,6 +79,9 @@ impl SinkConfig for VectorSinkConfig {
             self.keepalive,
             self.tls.clone(),
             self.send_buffer_bytes,
+            self.receive_buffer_bytes,
+            self.nodelay,
+            self.user_timeout_secs,
         );
 
         sink_config.build(cx, encode_event)
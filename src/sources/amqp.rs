@@ -0,0 +1,342 @@
+use crate::{
+    amqp::AmqpConfig,
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::{finalization::BatchStatus, Event, Value},
+    internal_events::{
+        AmqpAcknowledgementFailed, AmqpEventNotDelivered, AmqpEventReceived, AmqpReadFailed,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::{run_acknowledged, AcknowledgedMessages},
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use lapin::{
+    message::Delivery,
+    options::{BasicAckOptions, BasicConsumeOptions, BasicNackOptions, BasicQosOptions},
+    types::FieldTable,
+    Channel, Connection, Consumer,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AmqpSourceConfig {
+    connection: AmqpConfig,
+    queue: String,
+    #[serde(default = "default_consumer")]
+    consumer: String,
+    #[serde(default = "default_prefetch_count")]
+    prefetch_count: u16,
+    routing_key_key: Option<String>,
+    exchange_key: Option<String>,
+    #[serde(default)]
+    acknowledgements: bool,
+}
+
+fn default_consumer() -> String {
+    "vector".to_owned()
+}
+
+/// Bounds the messages delivered but not acknowledged yet, which includes
+/// those waiting for their events to be delivered.
+const fn default_prefetch_count() -> u16 {
+    100
+}
+
+inventory::submit! {
+    SourceDescription::new::<AmqpSourceConfig>("amqp")
+}
+
+impl GenerateConfig for AmqpSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection: AmqpConfig::new("amqp://127.0.0.1:5672/%2f".to_owned()),
+            queue: "vector".to_owned(),
+            consumer: default_consumer(),
+            prefetch_count: default_prefetch_count(),
+            routing_key_key: None,
+            exchange_key: None,
+            acknowledgements: false,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "amqp")]
+impl SourceConfig for AmqpSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let (connection, channel, consumer) = self.consume().await?;
+        Ok(Box::pin(amqp_source(
+            connection,
+            channel,
+            consumer,
+            self.clone(),
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "amqp"
+    }
+}
+
+impl AmqpSourceConfig {
+    async fn consume(&self) -> crate::Result<(Connection, Channel, Consumer)> {
+        let (connection, channel) = self.connection.connect().await?;
+        channel
+            .basic_qos(self.prefetch_count, BasicQosOptions::default())
+            .await?;
+        // Messages are acknowledged manually, once their events are sent, or
+        // delivered with `acknowledgements`.
+        let consumer = channel
+            .basic_consume(
+                &self.queue,
+                &self.consumer,
+                BasicConsumeOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+        Ok((connection, channel, consumer))
+    }
+}
+
+async fn amqp_source(
+    connection: Connection,
+    channel: Channel,
+    consumer: Consumer,
+    config: AmqpSourceConfig,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> Result<(), ()> {
+    let acknowledgements = config.acknowledgements;
+    let deliveries = Deliveries {
+        _connection: connection,
+        channel,
+        consumer,
+        config,
+    };
+    // The broker requeues the messages that aren't acknowledged before
+    // shutting down once the channel is closed.
+    run_acknowledged(deliveries, acknowledgements, shutdown, out).await
+}
+
+struct Deliveries {
+    // Only kept so that the channel stays open.
+    _connection: Connection,
+    channel: Channel,
+    consumer: Consumer,
+    config: AmqpSourceConfig,
+}
+
+#[async_trait::async_trait]
+impl AcknowledgedMessages for Deliveries {
+    type Message = Delivery;
+
+    async fn next(&mut self) -> Option<Result<Delivery, ()>> {
+        Some(match self.consumer.next().await? {
+            Ok((_, delivery)) => {
+                emit!(AmqpEventReceived {
+                    byte_size: delivery.data.len()
+                });
+                Ok(delivery)
+            }
+            Err(error) => {
+                // The channel is closed along with the consumer.
+                emit!(AmqpReadFailed { error });
+                Err(())
+            }
+        })
+    }
+
+    fn to_event(&self, delivery: &Delivery) -> Event {
+        delivery_to_event(delivery, &self.config)
+    }
+
+    async fn ack(&mut self, delivery: Delivery) {
+        let result = self
+            .channel
+            .basic_ack(delivery.delivery_tag, BasicAckOptions::default())
+            .await;
+        if let Err(error) = result {
+            emit!(AmqpAcknowledgementFailed { error });
+        }
+    }
+
+    /// Requeues the message.
+    async fn nack(&mut self, delivery: Delivery, status: BatchStatus) {
+        emit!(AmqpEventNotDelivered {
+            queue: &self.config.queue,
+            status
+        });
+        let result = self
+            .channel
+            .basic_nack(
+                delivery.delivery_tag,
+                BasicNackOptions {
+                    multiple: false,
+                    requeue: true,
+                },
+            )
+            .await;
+        if let Err(error) = result {
+            emit!(AmqpAcknowledgementFailed { error });
+        }
+    }
+}
+
+fn delivery_to_event(delivery: &Delivery, config: &AmqpSourceConfig) -> Event {
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    log.insert(
+        log_schema().message_key(),
+        Value::from(Bytes::from(delivery.data.clone())),
+    );
+
+    // The timestamp property is optional, and in seconds.
+    let timestamp = delivery
+        .properties
+        .timestamp()
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp as i64, 0).latest())
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    log.insert(log_schema().source_type_key(), Bytes::from("amqp"));
+
+    if let Some(routing_key_key) = &config.routing_key_key {
+        log.insert(routing_key_key, delivery.routing_key.as_str().to_owned());
+    }
+
+    if let Some(exchange_key) = &config.exchange_key {
+        log.insert(exchange_key, delivery.exchange.as_str().to_owned());
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AmqpSourceConfig>();
+    }
+
+    #[test]
+    fn parse_config() {
+        let config: AmqpSourceConfig = toml::from_str(
+            r#"
+            connection.connection_string = "amqp://127.0.0.1:5672/%2f"
+            queue = "logs"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.consumer, "vector");
+        assert_eq!(config.prefetch_count, 100);
+        assert!(!config.acknowledgements);
+    }
+}
+
+#[cfg(feature = "amqp-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+    use lapin::{
+        options::{BasicPublishOptions, QueueDeclareOptions},
+        BasicProperties,
+    };
+
+    fn make_config(queue: &str, acknowledgements: bool) -> AmqpSourceConfig {
+        AmqpSourceConfig {
+            connection: AmqpConfig::new("amqp://127.0.0.1:5672/%2f".to_owned()),
+            queue: queue.to_owned(),
+            consumer: default_consumer(),
+            prefetch_count: default_prefetch_count(),
+            routing_key_key: Some("routing_key".to_owned()),
+            exchange_key: Some("exchange".to_owned()),
+            acknowledgements,
+        }
+    }
+
+    /// Declares `queue`, and publishes `messages` to it through the default
+    /// exchange.
+    async fn send_messages(config: &AmqpConfig, queue: &str, messages: &[&str]) {
+        let (_connection, channel) = config.connect().await.unwrap();
+        channel
+            .queue_declare(queue, QueueDeclareOptions::default(), FieldTable::default())
+            .await
+            .unwrap();
+        for message in messages {
+            channel
+                .basic_publish(
+                    "",
+                    queue,
+                    BasicPublishOptions::default(),
+                    message.as_bytes().to_vec(),
+                    BasicProperties::default(),
+                )
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn consume(acknowledgements: bool) {
+        trace_init();
+
+        let queue = format!("test-{}", random_string(10));
+        let config = make_config(&queue, acknowledgements);
+        send_messages(&config.connection, &queue, &["one", "two"]).await;
+        let (connection, channel, consumer) = config.consume().await.unwrap();
+
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        tokio::spawn(amqp_source(
+            connection, channel, consumer, config, shutdown, tx,
+        ));
+
+        let events = collect_n(rx, 2).await;
+        drop(trigger);
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two"]);
+        assert_eq!(events[0].as_log()["routing_key"], queue.into());
+        assert_eq!(events[0].as_log()["exchange"], "".into());
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "amqp".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn amqp_source_consumes() {
+        consume(false).await;
+    }
+
+    #[tokio::test]
+    async fn amqp_source_consumes_with_acknowledgements() {
+        consume(true).await;
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
     config::log_schema,
-    event::Event,
+    event::{
+        finalization::{BatchNotifier, BatchStatus},
+        Event,
+    },
     internal_events::aws_s3::source::{
-        SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageProcessingFailed,
-        SqsMessageProcessingSucceeded, SqsMessageReceiveFailed, SqsMessageReceiveSucceeded,
-        SqsS3EventRecordInvalidEventIgnored,
+        SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageNotDelivered,
+        SqsMessageProcessingFailed, SqsMessageProcessingSucceeded, SqsMessageReceiveFailed,
+        SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
     },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
@@ -18,12 +21,11 @@ use lazy_static::lazy_static;
 use rusoto_core::{Region, RusotoError};
 use rusoto_s3::{GetObjectError, GetObjectRequest, S3Client, S3};
 use rusoto_sqs::{
-    DeleteMessageError, DeleteMessageRequest, Message, ReceiveMessageError, ReceiveMessageRequest,
-    Sqs, SqsClient,
+    DeleteMessageRequest, Message, ReceiveMessageError, ReceiveMessageRequest, Sqs, SqsClient,
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
-use std::{future::ready, time::Duration};
+use std::{future::ready, sync::Arc, time::Duration};
 use tokio::time;
 use tokio_util::codec::FramedRead;
 
@@ -47,6 +49,8 @@ pub(super) struct Config {
     #[serde(default = "default_true")]
     #[derivative(Default(value = "default_true()"))]
     pub(super) delete_message: bool,
+    #[serde(default)]
+    pub(super) acknowledgements: bool,
 }
 
 const fn default_poll_interval_secs() -> u64 {
@@ -126,6 +130,7 @@ pub(super) struct Ingestor {
     poll_interval: Duration,
     visibility_timeout_secs: i64,
     delete_message: bool,
+    acknowledgements: bool,
 }
 
 impl Ingestor {
@@ -152,6 +157,7 @@ impl Ingestor {
             poll_interval: Duration::from_secs(config.poll_secs),
             visibility_timeout_secs,
             delete_message: config.delete_message,
+            acknowledgements: config.acknowledgements,
         })
     }
 
@@ -195,24 +201,43 @@ impl Ingestor {
                 .clone()
                 .unwrap_or_else(|| "<unknown>".to_owned());
 
-            match self.handle_sqs_message(message, out.clone()).await {
+            let (batch, receiver) = if self.acknowledgements {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                (Some(batch), Some(receiver))
+            } else {
+                (None, None)
+            };
+
+            match self.handle_sqs_message(message, out.clone(), batch).await {
                 Ok(()) => {
                     emit!(SqsMessageProcessingSucceeded {
                         message_id: &message_id
                     });
                     if self.delete_message {
-                        match self.delete_message(receipt_handle).await {
-                            Ok(_) => {
-                                emit!(SqsMessageDeleteSucceeded {
-                                    message_id: &message_id
-                                });
-                            }
-                            Err(err) => {
-                                emit!(SqsMessageDeleteFailed {
-                                    error: &err,
-                                    message_id: &message_id,
+                        let delete = delete_message(
+                            self.sqs_client.clone(),
+                            self.queue_url.clone(),
+                            receipt_handle,
+                            message_id.clone(),
+                        );
+                        match receiver {
+                            // Waiting for the events to be delivered doesn't
+                            // hold up the following messages.
+                            Some(receiver) => {
+                                tokio::spawn(async move {
+                                    match receiver.await {
+                                        Ok(BatchStatus::Delivered) => delete.await,
+                                        Ok(status) => emit!(SqsMessageNotDelivered {
+                                            message_id: &message_id,
+                                            status,
+                                        }),
+                                        // Notifiers always report a status
+                                        // before being dropped.
+                                        Err(_) => (),
+                                    }
                                 });
                             }
+                            None => delete.await,
                         }
                     }
                 }
@@ -230,22 +255,24 @@ impl Ingestor {
         &self,
         message: Message,
         out: Pipeline,
+        batch: Option<Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         let s3_event: S3Event = serde_json::from_str(message.body.unwrap_or_default().as_ref())
             .context(InvalidSqsMessage {
                 message_id: message.message_id.unwrap_or_else(|| "<empty>".to_owned()),
             })?;
 
-        self.handle_s3_event(s3_event, out).await
+        self.handle_s3_event(s3_event, out, batch.as_ref()).await
     }
 
     async fn handle_s3_event(
         &self,
         s3_event: S3Event,
         mut out: Pipeline,
+        batch: Option<&Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, &mut out).await?
+            self.handle_s3_event_record(record, &mut out, batch).await?
         }
         Ok(())
     }
@@ -254,6 +281,7 @@ impl Ingestor {
         &self,
         s3_event: S3EventRecord,
         out: &mut Pipeline,
+        batch: Option<&Arc<BatchNotifier>>,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3S_EVENT_VERSION.matches(&event_version) {
@@ -366,6 +394,10 @@ impl Ingestor {
                         }
                     }
 
+                    if let Some(batch) = batch {
+                        event = event.with_batch_notifier(batch);
+                    }
+
                     ready(Some(Ok(event)))
                 });
 
@@ -412,17 +444,33 @@ impl Ingestor {
             .map_ok(|res| res.messages.unwrap_or_default())
             .await
     }
+}
 
-    async fn delete_message(
-        &self,
-        receipt_handle: String,
-    ) -> Result<(), RusotoError<DeleteMessageError>> {
-        self.sqs_client
-            .delete_message(DeleteMessageRequest {
-                queue_url: self.queue_url.clone(),
-                receipt_handle,
-            })
-            .await
+async fn delete_message(
+    sqs_client: SqsClient,
+    queue_url: String,
+    receipt_handle: String,
+    message_id: String,
+) {
+    let result = sqs_client
+        .delete_message(DeleteMessageRequest {
+            queue_url,
+            receipt_handle,
+        })
+        .await;
+
+    match result {
+        Ok(_) => {
+            emit!(SqsMessageDeleteSucceeded {
+                message_id: &message_id
+            });
+        }
+        Err(err) => {
+            emit!(SqsMessageDeleteFailed {
+                error: &err,
+                message_id: &message_id,
+            });
+        }
     }
 }
 
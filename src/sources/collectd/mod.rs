@@ -0,0 +1,230 @@
+#[cfg(unix)]
+use crate::udp;
+use crate::{
+    config::{self, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
+    internal_events::{CollectdEventsReceived, CollectdInvalidPacket, CollectdSocketError},
+    shutdown::ShutdownSignal,
+    Event, Pipeline,
+};
+use futures::{stream, SinkExt, StreamExt, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    fs, io,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::PathBuf,
+};
+use tokio::net::UdpSocket;
+use tokio_util::{codec::BytesCodec, udp::UdpFramed};
+
+pub mod parser;
+
+use parser::{parse, TypesDb};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CollectdConfig {
+    address: SocketAddr,
+    /// The `types.db` files naming the values of collectd's types.
+    #[serde(default)]
+    typesdb: Vec<PathBuf>,
+    #[cfg(unix)]
+    receive_buffer_bytes: Option<usize>,
+}
+
+impl CollectdConfig {
+    pub fn from_address(address: SocketAddr) -> Self {
+        Self {
+            address,
+            typesdb: Vec::new(),
+            #[cfg(unix)]
+            receive_buffer_bytes: None,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read types database {:?}: {}", path, source))]
+    TypesDbReadError { path: PathBuf, source: io::Error },
+}
+
+inventory::submit! {
+    SourceDescription::new::<CollectdConfig>("collectd")
+}
+
+impl GenerateConfig for CollectdConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::from_address(SocketAddr::V4(SocketAddrV4::new(
+            Ipv4Addr::new(0, 0, 0, 0),
+            25826,
+        ))))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "collectd")]
+impl SourceConfig for CollectdConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let mut types_db = TypesDb::default();
+        for path in &self.typesdb {
+            let text = fs::read_to_string(path).context(TypesDbReadError { path })?;
+            types_db.add(&text);
+        }
+
+        Ok(Box::pin(collectd_udp(
+            self.clone(),
+            types_db,
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> config::DataType {
+        config::DataType::Metric
+    }
+
+    fn source_type(&self) -> &'static str {
+        "collectd"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+}
+
+async fn collectd_udp(
+    config: CollectdConfig,
+    types_db: TypesDb,
+    shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(&config.address)
+        .map_err(|error| emit!(CollectdSocketError::bind(error)))
+        .await?;
+
+    #[cfg(unix)]
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        udp::set_receive_buffer_size(&socket, receive_buffer_bytes);
+    }
+
+    info!(
+        message = "Listening.",
+        addr = %config.address,
+        r#type = "udp"
+    );
+
+    let mut stream = UdpFramed::new(socket, BytesCodec::new()).take_until(shutdown);
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok((bytes, _sock)) => {
+                let metrics = match parse(&bytes, &types_db) {
+                    Ok(metrics) => metrics,
+                    Err(error) => {
+                        emit!(CollectdInvalidPacket {
+                            error,
+                            byte_size: bytes.len(),
+                        });
+                        continue;
+                    }
+                };
+                emit!(CollectdEventsReceived {
+                    count: metrics.len(),
+                    byte_size: bytes.len(),
+                });
+
+                let mut metrics = stream::iter(metrics.into_iter().map(Event::Metric).map(Ok));
+                if let Err(error) = out.send_all(&mut metrics).await {
+                    error!(message = "Error sending metric.", %error);
+                    break;
+                }
+            }
+            Err(error) => {
+                emit!(CollectdSocketError::read(error));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{collect_n, next_addr};
+    use std::time::Duration;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CollectdConfig>();
+    }
+
+    #[tokio::test]
+    async fn fails_on_missing_types_db() {
+        let mut config = CollectdConfig::from_address(next_addr());
+        config.typesdb = vec!["/nonexistent/types.db".into()];
+        let (tx, _rx) = Pipeline::new_test();
+
+        assert!(config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_packets() {
+        let addr = next_addr();
+        let (tx, rx) = Pipeline::new_test();
+        let server = CollectdConfig::from_address(addr)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        let mut socket = UdpSocket::bind(next_addr()).await.unwrap();
+        let packet = parser::test::packet();
+        // The source may not be listening yet.
+        let mut interval = tokio::time::interval(Duration::from_millis(50));
+        let received = collect_n(rx, 4);
+        tokio::pin!(received);
+        let events = loop {
+            tokio::select! {
+                events = &mut received => break events,
+                _ = interval.tick() => {
+                    socket.send_to(&packet, &addr).await.unwrap();
+                }
+            }
+        };
+
+        let names = events
+            .into_iter()
+            .map(|event| event.into_metric().series.name.name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "load_0",
+                "load_1",
+                "interface_if_octets_0",
+                "interface_if_octets_1"
+            ]
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Parsing of the packets of collectd's binary network protocol,
+//! https://collectd.org/wiki/index.php/Binary_protocol
+
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+use std::{collections::HashMap, convert::TryInto, str};
+
+const PART_HOST: u16 = 0x0000;
+const PART_TIME: u16 = 0x0001;
+const PART_PLUGIN: u16 = 0x0002;
+const PART_PLUGIN_INSTANCE: u16 = 0x0003;
+const PART_TYPE: u16 = 0x0004;
+const PART_TYPE_INSTANCE: u16 = 0x0005;
+const PART_VALUES: u16 = 0x0006;
+const PART_TIME_HR: u16 = 0x0008;
+const PART_ENCRYPTION: u16 = 0x0210;
+
+const HEADER_LEN: usize = 4;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Packet is truncated"))]
+    Truncated,
+    #[snafu(display("Part {:#06x} has an invalid length", part_type))]
+    InvalidLength { part_type: u16 },
+    #[snafu(display("Part {:#06x} isn't a NUL-terminated UTF-8 string", part_type))]
+    InvalidString { part_type: u16 },
+    #[snafu(display("Unknown data source type {}", ds_type))]
+    UnknownDataSourceType { ds_type: u8 },
+    #[snafu(display("Encrypted packets aren't supported"))]
+    Encrypted,
+}
+
+/// The names of the data sources of types, as listed in `types.db` files.
+#[derive(Debug, Default)]
+pub struct TypesDb(HashMap<String, Vec<String>>);
+
+impl TypesDb {
+    /// Parses the lines of a `types.db` file, `<type> <name>:<type>:<min>:<max>, ...`,
+    /// adding to the types parsed so far.
+    pub fn add(&mut self, text: &str) {
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(2, char::is_whitespace);
+            if let (Some(type_), Some(data_sources)) = (fields.next(), fields.next()) {
+                let names = data_sources
+                    .split(',')
+                    .filter_map(|data_source| data_source.trim().split(':').next())
+                    .filter(|name| !name.is_empty())
+                    .map(Into::into)
+                    .collect();
+                self.0.insert(type_.into(), names);
+            }
+        }
+    }
+
+    fn data_source(&self, type_: &str, index: usize, count: usize) -> Option<String> {
+        match self.0.get(type_) {
+            Some(names) if names.len() == count => Some(names[index].clone()),
+            _ if count == 1 => None,
+            _ => Some(index.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Value {
+    Counter(u64),
+    Gauge(f64),
+    Derive(i64),
+    Absolute(u64),
+}
+
+/// The identifier of the values, carried over from the parts before them.
+#[derive(Debug, Default)]
+struct ValueList<'a> {
+    host: &'a str,
+    time: Option<DateTime<Utc>>,
+    plugin: &'a str,
+    plugin_instance: &'a str,
+    type_: &'a str,
+    type_instance: &'a str,
+}
+
+impl ValueList<'_> {
+    /// Names metrics as `<plugin>_<type>_<data source>`, leaving out the type
+    /// when it's the plugin, and the data source when it's the only value.
+    fn metric(&self, data_source: Option<String>, value: Value) -> Option<Metric> {
+        let mut name = self.plugin.to_owned();
+        if !self.type_.is_empty() && self.type_ != self.plugin {
+            name.push('_');
+            name.push_str(self.type_);
+        }
+        if let Some(data_source) = data_source.filter(|data_source| data_source != "value") {
+            name.push('_');
+            name.push_str(&data_source);
+        }
+
+        let (kind, value) = match value {
+            // Gauges of unknown values are sent as NaN.
+            Value::Gauge(value) if value.is_nan() => return None,
+            Value::Gauge(value) => (MetricKind::Absolute, MetricValue::Gauge { value }),
+            Value::Counter(value) => (
+                MetricKind::Absolute,
+                MetricValue::Counter {
+                    value: value as f64,
+                },
+            ),
+            Value::Derive(value) => (
+                MetricKind::Absolute,
+                MetricValue::Counter {
+                    value: value as f64,
+                },
+            ),
+            Value::Absolute(value) => (
+                MetricKind::Incremental,
+                MetricValue::Counter {
+                    value: value as f64,
+                },
+            ),
+        };
+
+        let tags = vec![
+            ("host", self.host),
+            ("plugin_instance", self.plugin_instance),
+            ("type_instance", self.type_instance),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect::<MetricTags>();
+
+        Some(
+            Metric::new(name, kind, value)
+                .with_tags(if tags.is_empty() { None } else { Some(tags) })
+                .with_timestamp(self.time),
+        )
+    }
+}
+
+/// Parses the metrics of a packet. Notifications, intervals and signatures
+/// are skipped, as are parts of unknown types.
+pub fn parse(mut packet: &[u8], types_db: &TypesDb) -> Result<Vec<Metric>, ParseError> {
+    let mut metrics = Vec::new();
+    let mut values = ValueList::default();
+
+    while !packet.is_empty() {
+        if packet.len() < HEADER_LEN {
+            return Err(ParseError::Truncated);
+        }
+        let part_type = u16::from_be_bytes([packet[0], packet[1]]);
+        let len = u16::from_be_bytes([packet[2], packet[3]]) as usize;
+        if len < HEADER_LEN || len > packet.len() {
+            return Err(ParseError::InvalidLength { part_type });
+        }
+        let body = &packet[HEADER_LEN..len];
+        packet = &packet[len..];
+
+        match part_type {
+            PART_HOST => values.host = string(part_type, body)?,
+            PART_PLUGIN => values.plugin = string(part_type, body)?,
+            PART_PLUGIN_INSTANCE => values.plugin_instance = string(part_type, body)?,
+            PART_TYPE => values.type_ = string(part_type, body)?,
+            PART_TYPE_INSTANCE => values.type_instance = string(part_type, body)?,
+            PART_TIME => {
+                let seconds = number(part_type, body)?;
+                values.time = timestamp(seconds as i64, 0);
+            }
+            PART_TIME_HR => {
+                // In units of 2^-30 seconds.
+                let time = number(part_type, body)?;
+                let nanos = ((time & 0x3fff_ffff) * 1_000_000_000) >> 30;
+                values.time = timestamp((time >> 30) as i64, nanos as u32);
+            }
+            PART_VALUES => {
+                let parsed = parse_values(part_type, body)?;
+                let count = parsed.len();
+                metrics.extend(parsed.into_iter().enumerate().filter_map(|(index, value)| {
+                    let data_source = types_db.data_source(values.type_, index, count);
+                    values.metric(data_source, value)
+                }));
+            }
+            PART_ENCRYPTION => return Err(ParseError::Encrypted),
+            _ => {}
+        }
+    }
+
+    Ok(metrics)
+}
+
+fn string(part_type: u16, body: &[u8]) -> Result<&str, ParseError> {
+    match body.split_last() {
+        Some((0, string)) => {
+            str::from_utf8(string).map_err(|_| ParseError::InvalidString { part_type })
+        }
+        _ => Err(ParseError::InvalidString { part_type }),
+    }
+}
+
+fn number(part_type: u16, body: &[u8]) -> Result<u64, ParseError> {
+    body.try_into()
+        .map(u64::from_be_bytes)
+        .map_err(|_| ParseError::InvalidLength { part_type })
+}
+
+fn timestamp(seconds: i64, nanos: u32) -> Option<DateTime<Utc>> {
+    if seconds == 0 {
+        None
+    } else {
+        Utc.timestamp_opt(seconds, nanos).single()
+    }
+}
+
+/// Parses a values part: the number of values, the type of each of them, and
+/// then their 8 bytes each. Gauges are little-endian, unlike everything else.
+fn parse_values(part_type: u16, body: &[u8]) -> Result<Vec<Value>, ParseError> {
+    if body.len() < 2 {
+        return Err(ParseError::InvalidLength { part_type });
+    }
+    let count = u16::from_be_bytes([body[0], body[1]]) as usize;
+    let body = &body[2..];
+    if body.len() != count * 9 {
+        return Err(ParseError::InvalidLength { part_type });
+    }
+
+    let (types, data) = body.split_at(count);
+    types
+        .iter()
+        .zip(data.chunks_exact(8))
+        .map(|(&ds_type, bytes)| {
+            let bytes: [u8; 8] = bytes.try_into().expect("Chunks are 8 bytes.");
+            match ds_type {
+                0 => Ok(Value::Counter(u64::from_be_bytes(bytes))),
+                1 => Ok(Value::Gauge(f64::from_le_bytes(bytes))),
+                2 => Ok(Value::Derive(i64::from_be_bytes(bytes))),
+                3 => Ok(Value::Absolute(u64::from_be_bytes(bytes))),
+                ds_type => Err(ParseError::UnknownDataSourceType { ds_type }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(super) mod test {
+    use super::*;
+
+    pub fn string_part(part_type: u16, value: &str) -> Vec<u8> {
+        let mut part = part_type.to_be_bytes().to_vec();
+        part.extend_from_slice(&((HEADER_LEN + value.len() + 1) as u16).to_be_bytes());
+        part.extend_from_slice(value.as_bytes());
+        part.push(0);
+        part
+    }
+
+    pub fn number_part(part_type: u16, value: u64) -> Vec<u8> {
+        let mut part = part_type.to_be_bytes().to_vec();
+        part.extend_from_slice(&12u16.to_be_bytes());
+        part.extend_from_slice(&value.to_be_bytes());
+        part
+    }
+
+    pub fn values_part(values: &[(u8, [u8; 8])]) -> Vec<u8> {
+        let mut part = PART_VALUES.to_be_bytes().to_vec();
+        part.extend_from_slice(&((HEADER_LEN + 2 + values.len() * 9) as u16).to_be_bytes());
+        part.extend_from_slice(&(values.len() as u16).to_be_bytes());
+        part.extend(values.iter().map(|(ds_type, _)| ds_type));
+        for (_, bytes) in values {
+            part.extend_from_slice(bytes);
+        }
+        part
+    }
+
+    /// A packet of the load of a host, followed by the octets of one of its
+    /// network interfaces.
+    pub fn packet() -> Vec<u8> {
+        vec![
+            string_part(PART_HOST, "web-1"),
+            number_part(PART_TIME_HR, 1609459200 << 30 | 1 << 29),
+            string_part(PART_PLUGIN, "load"),
+            string_part(PART_TYPE, "load"),
+            values_part(&[
+                (1, 0.5f64.to_le_bytes()),
+                (1, 0.25f64.to_le_bytes()),
+                (1, f64::NAN.to_le_bytes()),
+            ]),
+            string_part(PART_PLUGIN, "interface"),
+            string_part(PART_PLUGIN_INSTANCE, "eth0"),
+            string_part(PART_TYPE, "if_octets"),
+            values_part(&[(2, 100i64.to_be_bytes()), (0, 200u64.to_be_bytes())]),
+        ]
+        .concat()
+    }
+
+    fn metric(name: &str, kind: MetricKind, value: MetricValue, tags: &[(&str, &str)]) -> Metric {
+        Metric::new(name.into(), kind, value)
+            .with_tags(Some(
+                tags.iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1609459200, 500_000_000)))
+    }
+
+    #[test]
+    fn parses_values() {
+        let mut types_db = TypesDb::default();
+        types_db.add(
+            "# The load averages.\n\
+             load shortterm:GAUGE:0:5000, midterm:GAUGE:0:5000, longterm:GAUGE:0:5000\n\
+             if_octets\trx:DERIVE:0:U, tx:DERIVE:0:U\n",
+        );
+
+        assert_eq!(
+            parse(&packet(), &types_db),
+            Ok(vec![
+                metric(
+                    "load_shortterm",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 0.5 },
+                    &[("host", "web-1")]
+                ),
+                metric(
+                    "load_midterm",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 0.25 },
+                    &[("host", "web-1")]
+                ),
+                metric(
+                    "interface_if_octets_rx",
+                    MetricKind::Absolute,
+                    MetricValue::Counter { value: 100.0 },
+                    &[("host", "web-1"), ("plugin_instance", "eth0")]
+                ),
+                metric(
+                    "interface_if_octets_tx",
+                    MetricKind::Absolute,
+                    MetricValue::Counter { value: 200.0 },
+                    &[("host", "web-1"), ("plugin_instance", "eth0")]
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn names_values_without_types_db() {
+        let names = parse(&packet(), &TypesDb::default())
+            .unwrap()
+            .into_iter()
+            .map(|metric| metric.series.name.name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "load_0",
+                "load_1",
+                "interface_if_octets_0",
+                "interface_if_octets_1"
+            ]
+        );
+
+        let packet = [
+            string_part(PART_PLUGIN, "cpu"),
+            string_part(PART_TYPE, "percent"),
+            string_part(PART_TYPE_INSTANCE, "idle"),
+            values_part(&[(3, 5u64.to_be_bytes())]),
+        ]
+        .concat();
+        assert_eq!(
+            parse(&packet, &TypesDb::default()),
+            Ok(vec![Metric::new(
+                "cpu_percent".into(),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 5.0 },
+            )
+            .with_tags(Some(
+                vec![("type_instance".to_owned(), "idle".to_owned())]
+                    .into_iter()
+                    .collect()
+            ))])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_packets() {
+        let packet = packet();
+        assert_eq!(
+            parse(&packet[..packet.len() - 1], &TypesDb::default()),
+            Err(ParseError::InvalidLength {
+                part_type: PART_VALUES
+            })
+        );
+        assert_eq!(
+            parse(&packet[..2], &TypesDb::default()),
+            Err(ParseError::Truncated)
+        );
+        assert_eq!(
+            parse(&values_part(&[(7, [0; 8])]), &TypesDb::default()),
+            Err(ParseError::UnknownDataSourceType { ds_type: 7 })
+        );
+        assert_eq!(
+            parse(&number_part(PART_ENCRYPTION, 0), &TypesDb::default()),
+            Err(ParseError::Encrypted)
+        );
+
+        let mut unterminated = string_part(PART_HOST, "web-1");
+        unterminated.pop();
+        unterminated[3] -= 1;
+        assert_eq!(
+            parse(&unterminated, &TypesDb::default()),
+            Err(ParseError::InvalidString {
+                part_type: PART_HOST
+            })
+        );
+    }
+}
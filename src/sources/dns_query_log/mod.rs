@@ -0,0 +1,217 @@
+//! Tails the query logs of DNS resolvers and parses every query and response
+//! into a structured event, for resolvers that can't emit dnstap.
+
+use super::file::{file_source, FileConfig, FingerprintConfig, ReadFromConfig};
+use crate::{
+    config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
+    internal_events::DnsQueryLogLineSkipped,
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use futures::{future, FutureExt, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+mod parser;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// The `log-queries` output of dnsmasq.
+    Dnsmasq,
+    /// The `log-queries` and `log-replies` output of unbound.
+    Unbound,
+    /// The `queries` category of BIND.
+    Bind,
+}
+
+impl Format {
+    fn as_str(self) -> &'static str {
+        match self {
+            Format::Dnsmasq => "dnsmasq",
+            Format::Unbound => "unbound",
+            Format::Bind => "bind",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DnsQueryLogConfig {
+    pub include: Vec<PathBuf>,
+    #[serde(default)]
+    pub exclude: Vec<PathBuf>,
+    pub format: Format,
+    pub data_dir: Option<PathBuf>,
+    pub read_from: Option<ReadFromConfig>,
+    #[serde(default = "default_glob_minimum_cooldown")]
+    pub glob_minimum_cooldown: u64, // millis
+}
+
+fn default_glob_minimum_cooldown() -> u64 {
+    1000
+}
+
+impl Default for DnsQueryLogConfig {
+    fn default() -> Self {
+        Self {
+            include: vec![PathBuf::from("/var/log/dnsmasq.log")],
+            exclude: vec![],
+            format: Format::Dnsmasq,
+            data_dir: None,
+            read_from: None,
+            glob_minimum_cooldown: default_glob_minimum_cooldown(),
+        }
+    }
+}
+
+inventory::submit! {
+    SourceDescription::new::<DnsQueryLogConfig>("dns_query_log")
+}
+
+impl_generate_config_from_default!(DnsQueryLogConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dns_query_log")]
+impl SourceConfig for DnsQueryLogConfig {
+    async fn build(
+        &self,
+        name: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let data_dir = globals.resolve_and_make_data_subdir(self.data_dir.as_ref(), name)?;
+
+        Ok(dns_query_log_source(self, data_dir, shutdown, out))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "dns_query_log"
+    }
+}
+
+fn dns_query_log_source(
+    config: &DnsQueryLogConfig,
+    data_dir: PathBuf,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> super::Source {
+    let file_config = FileConfig {
+        include: config.include.clone(),
+        exclude: config.exclude.clone(),
+        read_from: config.read_from,
+        glob_minimum_cooldown: config.glob_minimum_cooldown,
+        // Query logs start with similar lines, like the startup banner of
+        // the resolver, so files are told apart by their inode instead.
+        fingerprint: FingerprintConfig::DevInode,
+        ..Default::default()
+    };
+
+    // The file source tails the files, and its lines are parsed before being
+    // forwarded downstream.
+    let (tx, rx) = Pipeline::new_with_buffer(1000, vec![]);
+    let files = file_source(&file_config, data_dir, shutdown, tx);
+
+    let format = config.format;
+    let events = rx
+        .filter_map(move |mut event| {
+            let log = event.as_mut_log();
+            let line = log[log_schema().message_key()].to_string_lossy();
+
+            let parsed = parser::parse(format, &line, log);
+            if parsed {
+                log.insert(log_schema().source_type_key(), "dns_query_log");
+            } else {
+                emit!(DnsQueryLogLineSkipped {
+                    format: format.as_str(),
+                });
+            }
+
+            future::ready(if parsed { Some(Ok(event)) } else { None })
+        })
+        .forward(out.sink_map_err(|error| error!(message = "Error sending event.", %error)));
+
+    Box::pin(future::join(files, events).map(|(files, _)| files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Value;
+    use std::{fs::File, io::Write};
+    use tempfile::tempdir;
+    use tokio::time::{delay_for, timeout, Duration};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DnsQueryLogConfig>();
+    }
+
+    #[tokio::test]
+    async fn parses_query_log() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("dnsmasq.log");
+        let mut file = File::create(&path).unwrap();
+
+        let config = DnsQueryLogConfig {
+            include: vec![path.clone()],
+            read_from: Some(ReadFromConfig::Beginning),
+            glob_minimum_cooldown: 0,
+            ..Default::default()
+        };
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+        tokio::spawn(dns_query_log_source(
+            &config,
+            dir.path().to_path_buf(),
+            shutdown,
+            tx,
+        ));
+
+        writeln!(
+            &mut file,
+            "Jan 12 10:00:00 dnsmasq[1234]: started, version 2.80"
+        )
+        .unwrap();
+        writeln!(
+            &mut file,
+            "Jan 12 10:00:01 dnsmasq[1234]: query[A] example.com from 192.168.1.10"
+        )
+        .unwrap();
+        writeln!(
+            &mut file,
+            "Jan 12 10:00:01 dnsmasq[1234]: reply example.com is 93.184.216.34"
+        )
+        .unwrap();
+
+        delay_for(Duration::from_millis(500)).await;
+        drop(trigger_shutdown);
+
+        let events = timeout(Duration::from_secs(5), rx.collect::<Vec<_>>())
+            .await
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        let query = events[0].as_log();
+        assert_eq!(query["type"], Value::from("query"));
+        assert_eq!(query["name"], Value::from("example.com"));
+        assert_eq!(query["client"], Value::from("192.168.1.10"));
+        assert_eq!(
+            query[log_schema().source_type_key()],
+            Value::from("dns_query_log")
+        );
+        assert_eq!(
+            query["file"].to_string_lossy(),
+            path.to_str().unwrap().to_owned()
+        );
+
+        let response = events[1].as_log();
+        assert_eq!(response["type"], Value::from("response"));
+        assert_eq!(response["answer"], Value::from("93.184.216.34"));
+    }
+}
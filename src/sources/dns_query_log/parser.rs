@@ -0,0 +1,405 @@
+//! Parsers for the query logs of DNS resolvers.
+//!
+//! Every parser recognizes the lines describing queries or their responses,
+//! and ignores everything else the resolver logs.
+
+use super::Format;
+use crate::{
+    event::{LogEvent, Value},
+    internal_events::DnsQueryLogTimestampParseError,
+};
+use chrono::{Local, TimeZone, Utc};
+use regex::{Captures, Regex};
+
+lazy_static! {
+    // `dnsmasq[1234]: query[A] example.com from 192.168.1.10`, optionally with
+    // the serial and client of `log-queries=extra` before the message.
+    static ref DNSMASQ: Regex =
+        Regex::new(r"\bdnsmasq(?:\[\d+\])?: (?:(?P<serial>\d+) (?P<peer>\S+) )?(?P<message>.*)$")
+            .unwrap();
+    static ref DNSMASQ_QUERY: Regex =
+        Regex::new(r"^query\[(?P<query_type>[^\]]+)\] (?P<name>\S+) from (?P<client>\S+)$")
+            .unwrap();
+    static ref DNSMASQ_FORWARD: Regex =
+        Regex::new(r"^forwarded (?P<name>\S+) to (?P<server>\S+)$").unwrap();
+    static ref DNSMASQ_ANSWER: Regex =
+        Regex::new(r"^(?P<origin>reply|cached|config|/\S+) (?P<name>\S+) is (?P<answer>.+)$")
+            .unwrap();
+
+    // `[1610445600] unbound[1234:0] info: 192.168.1.10 example.com. A IN`, or
+    // a `reply:` with the rcode, duration, cache flag and size appended.
+    static ref UNBOUND: Regex = Regex::new(
+        r"^(?:\[(?P<epoch>\d+)\] )?.*?\bunbound(?:\[[\d:]+\])?: (?:\[[\d:]+\] )?(?P<kind>info|reply): (?P<client>\S+) (?P<name>\S+) (?P<query_type>\S+) (?P<query_class>\S+)(?: (?P<rcode>\S+) (?P<duration>[\d.]+) (?P<cached>[01]) (?P<size>\d+))?$"
+    )
+    .unwrap();
+
+    // `12-Jan-2021 10:00:00.123 client @0x7f1c 192.168.1.10#52311 (example.com): query: example.com IN A +E(0)K (192.168.1.1)`
+    static ref BIND: Regex = Regex::new(
+        r"^(?:(?P<timestamp>\d{2}-[A-Za-z]{3}-\d{4} \d{2}:\d{2}:\d{2}\.\d{3}) )?.*?\bclient (?:@0x[0-9a-fA-F]+ )?(?P<client>\S+?)#(?P<client_port>\d+)(?: \([^)]*\))?: (?:view \S+: )?query: (?P<name>\S+) (?P<query_class>\S+) (?P<query_type>\S+) (?P<flags>\S*) \((?P<server>[^)]+)\)$"
+    )
+    .unwrap();
+}
+
+/// Parse a line of the query log into `log`, returning whether the line
+/// describes a query or a response.
+pub fn parse(format: Format, line: &str, log: &mut LogEvent) -> bool {
+    match format {
+        Format::Dnsmasq => parse_dnsmasq(line, log),
+        Format::Unbound => parse_unbound(line, log),
+        Format::Bind => parse_bind(line, log),
+    }
+}
+
+fn parse_dnsmasq(line: &str, log: &mut LogEvent) -> bool {
+    let captures = match DNSMASQ.captures(line) {
+        Some(captures) => captures,
+        None => return false,
+    };
+    let message = &captures["message"];
+
+    if let Some(query) = DNSMASQ_QUERY.captures(message) {
+        log.insert("type", "query");
+        insert_name(log, &query["name"]);
+        log.insert("query_type", &query["query_type"]);
+        log.insert("client", &query["client"]);
+    } else if let Some(forward) = DNSMASQ_FORWARD.captures(message) {
+        log.insert("type", "forward");
+        insert_name(log, &forward["name"]);
+        log.insert("server", &forward["server"]);
+    } else if let Some(answer) = DNSMASQ_ANSWER.captures(message) {
+        log.insert("type", "response");
+        insert_name(log, &answer["name"]);
+        log.insert("answer", &answer["answer"]);
+        log.insert("origin", &answer["origin"]);
+    } else {
+        return false;
+    }
+
+    if let Some(serial) = captures.name("serial") {
+        insert_integer(log, "id", serial.as_str());
+    }
+    if let Some(peer) = captures.name("peer") {
+        insert_address(log, peer.as_str(), '/');
+    }
+
+    true
+}
+
+fn parse_unbound(line: &str, log: &mut LogEvent) -> bool {
+    let captures = match UNBOUND.captures(line) {
+        Some(captures) => captures,
+        None => return false,
+    };
+
+    let kind = if captures.name("rcode").is_some() {
+        "response"
+    } else if &captures["kind"] == "info" {
+        "query"
+    } else {
+        return false;
+    };
+
+    log.insert("type", kind);
+    insert_name(log, &captures["name"]);
+    log.insert("query_type", &captures["query_type"]);
+    log.insert("query_class", &captures["query_class"]);
+    log.insert("client", &captures["client"]);
+
+    if let Some(rcode) = captures.name("rcode") {
+        log.insert("rcode", rcode.as_str());
+        if let Ok(duration) = captures["duration"].parse::<f64>() {
+            log.insert("duration_seconds", duration);
+        }
+        log.insert("cached", &captures["cached"] == "1");
+        insert_integer(log, "response_size", &captures["size"]);
+    }
+
+    if let Some(epoch) = captures.name("epoch") {
+        insert_unbound_timestamp(epoch.as_str(), log);
+    }
+
+    true
+}
+
+fn parse_bind(line: &str, log: &mut LogEvent) -> bool {
+    let captures = match BIND.captures(line) {
+        Some(captures) => captures,
+        None => return false,
+    };
+
+    log.insert("type", "query");
+    insert_name(log, &captures["name"]);
+    log.insert("query_type", &captures["query_type"]);
+    log.insert("query_class", &captures["query_class"]);
+    log.insert("client", &captures["client"]);
+    insert_integer(log, "client_port", &captures["client_port"]);
+    log.insert("flags", &captures["flags"]);
+    log.insert("server", &captures["server"]);
+
+    insert_bind_timestamp(&captures, log);
+
+    true
+}
+
+/// Unbound logs the seconds since the epoch, which may be out of range.
+fn insert_unbound_timestamp(epoch: &str, log: &mut LogEvent) {
+    let timestamp = epoch
+        .parse()
+        .ok()
+        .and_then(|epoch| Utc.timestamp_opt(epoch, 0).single());
+
+    match timestamp {
+        Some(timestamp) => {
+            log.insert("timestamp", timestamp);
+        }
+        None => emit!(DnsQueryLogTimestampParseError {
+            format: Format::Unbound.as_str(),
+            timestamp: epoch,
+        }),
+    }
+}
+
+/// BIND logs in the local time of the server.
+fn insert_bind_timestamp(captures: &Captures<'_>, log: &mut LogEvent) {
+    let timestamp = captures.name("timestamp").and_then(|m| {
+        Local
+            .datetime_from_str(m.as_str(), "%d-%b-%Y %H:%M:%S%.3f")
+            .ok()
+    });
+
+    if let Some(timestamp) = timestamp {
+        log.insert("timestamp", timestamp.with_timezone(&Utc));
+    }
+}
+
+/// Resolvers disagree on whether names end with the root label.
+fn insert_name(log: &mut LogEvent, name: &str) {
+    let name = match name.strip_suffix('.') {
+        Some(stripped) if !stripped.is_empty() => stripped,
+        _ => name,
+    };
+    log.insert("name", name);
+}
+
+fn insert_integer(log: &mut LogEvent, key: &str, value: &str) {
+    if let Ok(value) = value.parse::<i64>() {
+        log.insert(key, value);
+    }
+}
+
+fn insert_address(log: &mut LogEvent, address: &str, separator: char) {
+    match address.rfind(separator) {
+        Some(index) => {
+            log.insert("client", &address[..index]);
+            insert_integer(log, "client_port", &address[index + 1..]);
+        }
+        None => {
+            log.insert("client", address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    fn parse_line(format: Format, line: &str) -> Option<BTreeMap<String, Value>> {
+        let mut event = Event::new_empty_log();
+        if parse(format, line, event.as_mut_log()) {
+            Some(event.into_log().into_iter().collect())
+        } else {
+            None
+        }
+    }
+
+    fn fields(fields: Vec<(&str, Value)>) -> Option<BTreeMap<String, Value>> {
+        Some(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn dnsmasq_query() {
+        assert_eq!(
+            parse_line(
+                Format::Dnsmasq,
+                "Jan 12 10:00:00 dnsmasq[1234]: query[AAAA] example.com from 192.168.1.10"
+            ),
+            fields(vec![
+                ("type", "query".into()),
+                ("name", "example.com".into()),
+                ("query_type", "AAAA".into()),
+                ("client", "192.168.1.10".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn dnsmasq_extra() {
+        assert_eq!(
+            parse_line(
+                Format::Dnsmasq,
+                "Jan 12 10:00:00 dnsmasq[1234]: 17 192.168.1.10/52311 reply example.com is 93.184.216.34"
+            ),
+            fields(vec![
+                ("type", "response".into()),
+                ("name", "example.com".into()),
+                ("answer", "93.184.216.34".into()),
+                ("origin", "reply".into()),
+                ("id", 17.into()),
+                ("client", "192.168.1.10".into()),
+                ("client_port", 52311.into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn dnsmasq_forward_and_cache() {
+        assert_eq!(
+            parse_line(
+                Format::Dnsmasq,
+                "dnsmasq[1234]: forwarded example.com to 1.1.1.1"
+            ),
+            fields(vec![
+                ("type", "forward".into()),
+                ("name", "example.com".into()),
+                ("server", "1.1.1.1".into()),
+            ])
+        );
+        assert_eq!(
+            parse_line(
+                Format::Dnsmasq,
+                "dnsmasq[1234]: /etc/hosts router.lan is 192.168.1.1"
+            )
+            .unwrap()["origin"],
+            Value::from("/etc/hosts")
+        );
+    }
+
+    #[test]
+    fn dnsmasq_other_lines() {
+        assert_eq!(
+            parse_line(
+                Format::Dnsmasq,
+                "dnsmasq[1234]: started, version 2.80 cachesize 150"
+            ),
+            None
+        );
+        assert_eq!(parse_line(Format::Dnsmasq, "unrelated line"), None);
+    }
+
+    #[test]
+    fn unbound_query() {
+        assert_eq!(
+            parse_line(
+                Format::Unbound,
+                "[1610445600] unbound[1234:0] info: 192.168.1.10 example.com. A IN"
+            ),
+            fields(vec![
+                ("type", "query".into()),
+                ("name", "example.com".into()),
+                ("query_type", "A".into()),
+                ("query_class", "IN".into()),
+                ("client", "192.168.1.10".into()),
+                ("timestamp", Utc.timestamp(1610445600, 0).into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn unbound_reply_through_syslog() {
+        assert_eq!(
+            parse_line(
+                Format::Unbound,
+                "Jan 12 10:00:00 host unbound: [1234:0] reply: 192.168.1.10 example.com. A IN NOERROR 0.012503 0 45"
+            ),
+            fields(vec![
+                ("type", "response".into()),
+                ("name", "example.com".into()),
+                ("query_type", "A".into()),
+                ("query_class", "IN".into()),
+                ("client", "192.168.1.10".into()),
+                ("rcode", "NOERROR".into()),
+                ("duration_seconds", 0.012503.into()),
+                ("cached", false.into()),
+                ("response_size", 45.into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn unbound_out_of_range_timestamp() {
+        assert_eq!(
+            parse_line(
+                Format::Unbound,
+                "[99999999999999999] unbound[1234:0] info: 192.168.1.10 example.com. A IN"
+            ),
+            fields(vec![
+                ("type", "query".into()),
+                ("name", "example.com".into()),
+                ("query_type", "A".into()),
+                ("query_class", "IN".into()),
+                ("client", "192.168.1.10".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn unbound_other_lines() {
+        assert_eq!(
+            parse_line(
+                Format::Unbound,
+                "[1610445600] unbound[1234:0] info: start of service (unbound 1.13.0)."
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn bind_query() {
+        let parsed = parse_line(
+            Format::Bind,
+            "12-Jan-2021 10:00:00.123 queries: info: client @0x7f1c2c0a4b50 192.168.1.10#52311 (example.com): query: example.com IN A +E(0)K (192.168.1.1)",
+        )
+        .unwrap();
+
+        let timestamp = Local
+            .datetime_from_str("12-Jan-2021 10:00:00.123", "%d-%b-%Y %H:%M:%S%.3f")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            Some(parsed),
+            fields(vec![
+                ("type", "query".into()),
+                ("name", "example.com".into()),
+                ("query_type", "A".into()),
+                ("query_class", "IN".into()),
+                ("client", "192.168.1.10".into()),
+                ("client_port", 52311.into()),
+                ("flags", "+E(0)K".into()),
+                ("server", "192.168.1.1".into()),
+                ("timestamp", timestamp.into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn bind_view_without_timestamp() {
+        let parsed = parse_line(
+            Format::Bind,
+            "client 2001:db8::1#40000: view internal: query: example.org IN AAAA -EDC (2001:db8::53)",
+        )
+        .unwrap();
+
+        assert_eq!(parsed["client"], Value::from("2001:db8::1"));
+        assert_eq!(parsed["query_type"], Value::from("AAAA"));
+        assert_eq!(parsed["server"], Value::from("2001:db8::53"));
+        assert!(!parsed.contains_key("timestamp"));
+    }
+}
@@ -2,8 +2,13 @@ use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
     encoding_transcode::{Decoder, Encoder},
-    event::Event,
-    internal_events::{FileEventReceived, FileOpen, FileSourceInternalEventsEmitter},
+    event::{
+        finalization::{BatchNotifier, BatchStatus, BatchStatusReceiver},
+        Event,
+    },
+    internal_events::{
+        FileEventNotDelivered, FileEventReceived, FileOpen, FileSourceInternalEventsEmitter,
+    },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
     trace::{current_span, Instrument},
@@ -13,18 +18,22 @@ use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
-    FileServer, FingerprintStrategy, Fingerprinter, ReadFrom,
+    Checkpointer, CheckpointsView, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter,
+    Line, ReadFrom,
 };
 use futures::{
+    channel::mpsc,
     future::TryFutureExt,
-    stream::{Stream, StreamExt},
+    stream::{FuturesUnordered, Stream, StreamExt},
     SinkExt,
 };
 use regex::bytes::Regex;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::spawn_blocking;
 
@@ -55,6 +64,8 @@ enum BuildError {
         indicator: String,
         source: regex::Error,
     },
+    #[snafu(display("acknowledgements can't be used along with multiline aggregation"))]
+    AcknowledgementsWithMultiline,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
@@ -84,6 +95,7 @@ pub struct FileConfig {
     pub remove_after: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub acknowledgements: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -161,6 +173,7 @@ impl Default for FileConfig {
             remove_after: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            acknowledgements: false,
         }
     }
 }
@@ -198,6 +211,13 @@ impl SourceConfig for FileConfig {
                 Regex::new(indicator)
                     .with_context(|| InvalidMessageStartIndicator { indicator })?;
             }
+
+            // Aggregated events only carry the position of their first line.
+            if self.acknowledgements
+                && (self.multiline.is_some() || self.message_start_indicator.is_some())
+            {
+                return Err(BuildError::AcknowledgementsWithMultiline.into());
+            }
         }
 
         Ok(file_source(self, data_dir, shutdown, out))
@@ -248,7 +268,6 @@ pub fn file_source(
         ignore_before,
         max_line_bytes: config.max_line_bytes,
         line_delimiter: line_delimiter_as_bytes,
-        data_dir,
         glob_minimum_cooldown,
         fingerprinter: Fingerprinter {
             strategy: config.fingerprint.clone().into(),
@@ -257,6 +276,7 @@ pub fn file_source(
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after.map(Duration::from_secs),
+        acknowledgements: config.acknowledgements,
         emitter: FileSourceInternalEventsEmitter,
         handle: tokio::runtime::Handle::current(),
     };
@@ -273,6 +293,10 @@ pub fn file_source(
     let multiline_config = config.multiline.clone();
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
+    let acknowledgements = config.acknowledgements;
+
+    let checkpointer = Checkpointer::new(&data_dir);
+    let checkpoints = checkpointer.view();
 
     Box::pin(async move {
         info!(message = "Starting file server.", include = ?include, exclude = ?exclude);
@@ -280,19 +304,19 @@ pub fn file_source(
         let mut encoding_decoder = encoding_charset.map(|e| Decoder::new(e));
 
         // sizing here is just a guess
-        let (tx, rx) = futures::channel::mpsc::channel::<Vec<(Bytes, String)>>(2);
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
         let rx = rx
             .map(futures::stream::iter)
             .flatten()
-            .map(move |(line, src)| {
+            .map(move |mut line| {
                 // transcode each line from the file's encoding charset to utf8
-                match encoding_decoder.as_mut() {
-                    Some(d) => (d.decode_to_utf8(line), src),
-                    None => (line, src),
+                if let Some(d) = encoding_decoder.as_mut() {
+                    line.text = d.decode_to_utf8(line.text);
                 }
+                line
             });
 
-        let messages: Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin> =
+        let messages: Box<dyn Stream<Item = Line> + Send + std::marker::Unpin> =
             if let Some(ref multiline_config) = multiline_config {
                 wrap_with_line_agg(
                     rx,
@@ -314,18 +338,39 @@ pub fn file_source(
         // logs in the queue.
         let span = current_span();
         let span2 = span.clone();
+        let (pending_checkpoints, acks) = mpsc::unbounded();
         let mut messages = messages
-            .map(move |(msg, file): (Bytes, String)| {
+            .map(move |line: Line| {
                 let _enter = span2.enter();
-                create_event(msg, file, &host_key, &hostname, &file_key)
+                let batch = if acknowledgements {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    // The receiving end lives as long as the lines.
+                    let _ = pending_checkpoints.unbounded_send(PendingCheckpoint {
+                        receiver,
+                        filename: line.filename.clone(),
+                        file_id: line.file_id,
+                        offset: line.offset,
+                    });
+                    Some(batch)
+                } else {
+                    None
+                };
+                let event = create_event(line.text, line.filename, &host_key, &hostname, &file_key);
+                match batch {
+                    Some(batch) => event.with_batch_notifier(&batch),
+                    None => event,
+                }
             })
             .map(Ok);
+        if acknowledgements {
+            tokio::spawn(update_acked_checkpoints(checkpoints, acks).instrument(span.clone()));
+        }
         tokio::spawn(async move { out.send_all(&mut messages).instrument(span).await });
 
         let span = info_span!("file_server");
         spawn_blocking(move || {
             let _enter = span.enter();
-            let result = file_server.run(tx, shutdown);
+            let result = file_server.run(tx, shutdown, checkpointer);
             emit!(FileOpen { count: 0 });
             // Panic if we encounter any error originating from the file server.
             // We're at the `spawn_blocking` call, the panic will be caught and
@@ -361,16 +406,131 @@ fn reconcile_position_options(
 }
 
 fn wrap_with_line_agg(
-    rx: impl Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static,
+    rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
     config: line_agg::Config,
-) -> Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static> {
+) -> Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static> {
     let logic = line_agg::Logic::new(config);
     Box::new(
-        LineAgg::new(rx.map(|(line, src)| (src, line, ())), logic)
-            .map(|(src, line, _context)| (line, src)),
+        LineAgg::new(
+            rx.map(|line| (line.filename, line.text, (line.file_id, line.offset))),
+            logic,
+        )
+        .map(|(filename, text, (file_id, offset))| Line {
+            text,
+            filename,
+            file_id,
+            offset,
+        }),
     )
 }
 
+struct PendingCheckpoint {
+    receiver: BatchStatusReceiver,
+    filename: String,
+    file_id: FileFingerprint,
+    offset: u64,
+}
+
+/// Updates the checkpoints of files once the events of their lines are
+/// delivered. The events of lines may be delivered in any order, but the
+/// checkpoint of a file only moves past lines whose events were all
+/// delivered. Once the event of a line isn't, the checkpoint of its file
+/// isn't updated anymore, so the line is read again when the source restarts.
+async fn update_acked_checkpoints(
+    checkpoints: Arc<CheckpointsView>,
+    mut acks: mpsc::UnboundedReceiver<PendingCheckpoint>,
+) {
+    let mut files = HashMap::<FileFingerprint, FileAcks>::new();
+    let mut statuses = FuturesUnordered::new();
+    let mut reading = true;
+
+    while reading || !statuses.is_empty() {
+        tokio::select! {
+            ack = acks.next(), if reading => match ack {
+                Some(PendingCheckpoint { receiver, filename, file_id, offset }) => {
+                    // Nothing past a line that wasn't delivered is checkpointed.
+                    if let Some(seq) = files.entry(file_id).or_default().push(offset) {
+                        statuses.push(async move {
+                            // Notifiers always report a status before being
+                            // dropped.
+                            let status = receiver.await.unwrap_or(BatchStatus::Errored);
+                            (filename, file_id, seq, offset, status)
+                        });
+                    }
+                }
+                None => reading = false,
+            },
+            Some((filename, file_id, seq, offset, status)) = statuses.next() => {
+                if status != BatchStatus::Delivered {
+                    emit!(FileEventNotDelivered {
+                        file: &filename,
+                        offset,
+                        status,
+                    });
+                }
+
+                let position = files
+                    .get_mut(&file_id)
+                    .and_then(|acks| acks.update(seq, status));
+                if let Some(position) = position {
+                    checkpoints.update(file_id, position);
+                }
+            }
+        }
+    }
+}
+
+/// The statuses of the lines of a file that aren't checkpointed yet.
+#[derive(Default)]
+struct FileAcks {
+    /// The position after each line, and its status once received, in the
+    /// order the lines were read.
+    pending: BTreeMap<u64, (u64, Option<BatchStatus>)>,
+    /// The sequence number of the next line read.
+    next_seq: u64,
+    /// Whether the event of a line wasn't delivered, which holds the
+    /// checkpoint of the file back for good.
+    blocked: bool,
+}
+
+impl FileAcks {
+    /// Records a line read up to `offset`, and returns its sequence number
+    /// unless the checkpoint of the file is held back.
+    fn push(&mut self, offset: u64) -> Option<u64> {
+        if self.blocked {
+            return None;
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.pending.insert(seq, (offset, None));
+        Some(seq)
+    }
+
+    /// Records the status of the line numbered `seq`, and returns the
+    /// position to checkpoint if the lines delivered before any pending one
+    /// moved it.
+    fn update(&mut self, seq: u64, status: BatchStatus) -> Option<u64> {
+        if self.blocked {
+            return None;
+        }
+        if let Some((_, line_status)) = self.pending.get_mut(&seq) {
+            *line_status = Some(status);
+        }
+
+        let mut position = None;
+        while let Some((&seq, &(offset, Some(status)))) = self.pending.iter().next() {
+            if status != BatchStatus::Delivered {
+                self.blocked = true;
+                self.pending.clear();
+                break;
+            }
+            self.pending.remove(&seq);
+            position = Some(offset);
+        }
+        position
+    }
+}
+
 fn create_event(
     line: Bytes,
     file: String,
@@ -404,7 +564,9 @@ fn create_event(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{config::Config, shutdown::ShutdownSignal, sources::file};
+    use crate::{
+        config::Config, event::finalization::EventStatus, shutdown::ShutdownSignal, sources::file,
+    };
     use encoding_rs::UTF_16LE;
     use pretty_assertions::assert_eq;
     use std::{
@@ -998,6 +1160,127 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_acknowledgements_hold_checkpoint_back() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            acknowledgements: true,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "first line").unwrap();
+        writeln!(&mut file, "second line").unwrap();
+        sleep_500_millis().await;
+
+        // The sink fails to deliver the second line, so the checkpoint stays
+        // after the first one, even once later lines are delivered.
+        {
+            let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+
+            let (tx, mut rx) = Pipeline::new_test();
+            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            tokio::spawn(source);
+
+            let mut received: Vec<Event> = timeout(
+                Duration::from_secs(5),
+                (&mut rx).take(2).collect::<Vec<_>>(),
+            )
+            .await
+            .unwrap();
+            received[0]
+                .take_finalizers()
+                .update_status(EventStatus::Delivered);
+            received[1]
+                .take_finalizers()
+                .update_status(EventStatus::Errored);
+            drop(received);
+
+            writeln!(&mut file, "third line").unwrap();
+            let mut third = timeout(Duration::from_secs(5), rx.next())
+                .await
+                .unwrap()
+                .unwrap();
+            third
+                .take_finalizers()
+                .update_status(EventStatus::Delivered);
+            drop(third);
+            sleep_500_millis().await;
+
+            drop(trigger_shutdown);
+            let received = wait_with_timeout(rx.collect::<Vec<_>>()).await;
+            assert!(received.is_empty());
+        }
+        // Restart server, read the lines which weren't all delivered again.
+        {
+            let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+
+            let (tx, rx) = Pipeline::new_test();
+            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            tokio::spawn(source);
+
+            sleep_500_millis().await;
+            drop(trigger_shutdown);
+
+            let received = wait_with_timeout(rx.collect::<Vec<_>>()).await;
+            let lines = received
+                .into_iter()
+                .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+                .collect::<Vec<_>>();
+            assert_eq!(lines, vec!["second line", "third line"]);
+        }
+    }
+
+    #[test]
+    fn file_acks_move_checkpoint_past_delivered_lines_in_order() {
+        let mut acks = FileAcks::default();
+        for offset in &[10, 20, 30, 40] {
+            acks.push(*offset);
+        }
+
+        assert_eq!(acks.update(1, BatchStatus::Delivered), None);
+        assert_eq!(acks.update(0, BatchStatus::Delivered), Some(20));
+        assert_eq!(acks.update(3, BatchStatus::Delivered), None);
+        assert_eq!(acks.update(2, BatchStatus::Delivered), Some(40));
+    }
+
+    #[test]
+    fn file_acks_dont_move_checkpoint_past_failed_lines() {
+        let mut acks = FileAcks::default();
+        for offset in &[10, 20, 30] {
+            acks.push(*offset);
+        }
+
+        assert_eq!(acks.update(1, BatchStatus::Errored), None);
+        assert_eq!(acks.update(2, BatchStatus::Delivered), None);
+        // The lines before the failed one are still checkpointed.
+        assert_eq!(acks.update(0, BatchStatus::Delivered), Some(10));
+        assert!(acks.blocked);
+        assert_eq!(acks.push(40), None);
+    }
+
+    #[tokio::test]
+    async fn file_acknowledgements_reject_multiline() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            acknowledgements: true,
+            message_start_indicator: Some("^[^\\s]".to_owned()),
+            ..test_default_file_config(&dir)
+        };
+        let result = config
+            .build(
+                "file",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                Pipeline::new_test().0,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn file_start_position_server_restart_with_file_rotation() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,1080 @@
+use super::util::{make_listener, SocketListenAddr};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{Event, Value},
+    internal_events::{
+        ConnectionOpen, FluentConnectionError, FluentEventReceived, FluentMessageReceived,
+        OpenGauge, TcpSocketConnectionError,
+    },
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::MultiGzDecoder;
+use futures::{future::ready, stream, SinkExt, StreamExt};
+use listenfd::ListenFd;
+use rmpv::Value as MsgpackValue;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use snafu::{ResultExt, Snafu};
+use std::{
+    convert::TryInto,
+    io::{self, Read},
+};
+use tokio::{io::AsyncWriteExt, net::TcpStream};
+use tokio_util::codec::{Decoder, FramedRead};
+use tracing_futures::Instrument;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FluentConfig {
+    address: SocketListenAddr,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsConfig>,
+    receive_buffer_bytes: Option<usize>,
+    /// Key that clients must prove they know in the handshake
+    shared_key: Option<String>,
+    /// Hostname sent to clients in the handshake
+    self_hostname: Option<String>,
+    /// The maximum size of a message, and of the entries of a compressed one
+    /// once decompressed
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+}
+
+fn default_max_length() -> usize {
+    16 * 1024 * 1024
+}
+
+/// The maximum nesting of the arrays and maps of a message.
+const MAX_DEPTH: usize = 64;
+
+inventory::submit! {
+    SourceDescription::new::<FluentConfig>("fluent")
+}
+
+impl GenerateConfig for FluentConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            shared_key: None,
+            self_hostname: None,
+            max_length: default_max_length(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SourceConfig for FluentConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let source = FluentSource {
+            keepalive: self.keepalive,
+            receive_buffer_bytes: self.receive_buffer_bytes,
+            shared_key: self.shared_key.clone(),
+            self_hostname: match &self.self_hostname {
+                Some(hostname) => hostname.clone(),
+                None => crate::get_hostname()?,
+            },
+            max_length: self.max_length,
+        };
+        let address = self.address;
+
+        Ok(Box::pin(async move {
+            let listener = make_listener(address, ListenFd::from_env(), &tls)
+                .await
+                .ok_or(())?;
+
+            info!(
+                message = "Listening.",
+                addr = %listener
+                    .local_addr()
+                    .map(SocketListenAddr::SocketAddr)
+                    .unwrap_or(address)
+            );
+
+            let connection_gauge = OpenGauge::new();
+
+            listener
+                .accept_stream()
+                .take_until(shutdown.clone())
+                .for_each(|connection| {
+                    let socket = match connection {
+                        Ok(socket) => socket,
+                        Err(error) => {
+                            error!(message = "Failed to accept socket.", %error);
+                            return ready(());
+                        }
+                    };
+
+                    let peer_addr = socket.peer_addr().ip().to_string();
+                    let span = info_span!("connection", %peer_addr);
+                    debug!(message = "Accepted a new connection.", %peer_addr);
+
+                    let open_token = connection_gauge.open(|count| emit!(ConnectionOpen { count }));
+                    let connection = source.clone().handle_connection(
+                        socket,
+                        peer_addr,
+                        out.clone(),
+                        shutdown.clone(),
+                    );
+
+                    tokio::spawn(
+                        async move {
+                            if let Err(error) = connection.await {
+                                emit!(FluentConnectionError { error });
+                            }
+                            debug!("Connection closed.");
+                            drop(open_token);
+                        }
+                        .instrument(span),
+                    );
+                    ready(())
+                })
+                .await;
+
+            Ok(())
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "fluent"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.into()]
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(crate) enum FluentError {
+    #[snafu(display("Connection failed: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Invalid msgpack: {}", source))]
+    Decode { source: io::Error },
+    #[snafu(display("Failed to decompress entries: {}", source))]
+    Decompress { source: io::Error },
+    #[snafu(display("Invalid message: {}", reason))]
+    InvalidMessage { reason: &'static str },
+    #[snafu(display("Message longer than max_length of {} bytes", max_length))]
+    TooLong { max_length: usize },
+    #[snafu(display("Client failed to authenticate: {}", reason))]
+    Unauthenticated { reason: &'static str },
+    #[snafu(display("Pipeline is closed"))]
+    PipelineClosed,
+}
+
+impl From<io::Error> for FluentError {
+    fn from(source: io::Error) -> Self {
+        FluentError::Io { source }
+    }
+}
+
+/// Implements the server side of the Fluentd forward protocol.
+#[derive(Clone, Debug)]
+struct FluentSource {
+    keepalive: Option<TcpKeepaliveConfig>,
+    receive_buffer_bytes: Option<usize>,
+    shared_key: Option<String>,
+    self_hostname: String,
+    max_length: usize,
+}
+
+impl FluentSource {
+    async fn handle_connection(
+        self,
+        mut socket: MaybeTlsIncomingStream<TcpStream>,
+        host: String,
+        mut out: Pipeline,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), FluentError> {
+        tokio::select! {
+            result = socket.handshake() => {
+                if let Err(error) = result {
+                    emit!(TcpSocketConnectionError { error });
+                    return Ok(());
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        if let Some(keepalive) = self.keepalive {
+            if let Err(error) = socket.set_keepalive(keepalive) {
+                warn!(message = "Failed configuring TCP keepalive.", %error);
+            }
+        }
+
+        if let Some(receive_buffer_bytes) = self.receive_buffer_bytes {
+            if let Err(error) = socket.set_receive_buffer_bytes(receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on TCP socket.", %error);
+            }
+        }
+
+        // With a shared key, the server greets clients with a nonce, which
+        // they must hash with the key to authenticate.
+        let mut handshake = match &self.shared_key {
+            Some(shared_key) => {
+                let nonce = rand::random::<[u8; 16]>();
+                write_message(&mut socket, &helo(&nonce)).await?;
+                Some((shared_key.as_str(), nonce))
+            }
+            None => None,
+        };
+
+        let mut messages = FramedRead::new(socket, FluentDecoder::new(self.max_length));
+        loop {
+            let (message, byte_size) = tokio::select! {
+                message = messages.next() => match message {
+                    Some(message) => message?,
+                    None => return Ok(()),
+                },
+                _ = &mut shutdown => return Ok(()),
+            };
+
+            if let Some((shared_key, nonce)) = handshake.take() {
+                let result = check_ping(message, &nonce, shared_key);
+                let pong = self.pong(&result, &nonce, shared_key);
+                write_message(messages.get_mut(), &pong).await?;
+                result?;
+                continue;
+            }
+
+            emit!(FluentMessageReceived { byte_size });
+            let (events, chunk) = decode_message(message, &host, self.max_length)?;
+            out.send_all(&mut stream::iter(events).map(Ok))
+                .await
+                .map_err(|_| FluentError::PipelineClosed)?;
+
+            // Clients asking for acknowledgements resend the chunk until
+            // they get one.
+            if let Some(chunk) = chunk {
+                let ack = MsgpackValue::Map(vec![(MsgpackValue::from("ack"), chunk)]);
+                write_message(messages.get_mut(), &ack).await?;
+            }
+        }
+    }
+
+    /// Answers the PING of a client with the result of its authentication.
+    /// Successful answers prove that the server knows the shared key too.
+    fn pong(
+        &self,
+        result: &Result<Vec<u8>, FluentError>,
+        nonce: &[u8],
+        shared_key: &str,
+    ) -> MsgpackValue {
+        let (authenticated, reason, digest) = match result {
+            Ok(salt) => (
+                true,
+                String::new(),
+                shared_key_digest(salt, self.self_hostname.as_bytes(), nonce, shared_key),
+            ),
+            Err(error) => (false, error.to_string(), String::new()),
+        };
+        MsgpackValue::Array(vec![
+            MsgpackValue::from("PONG"),
+            MsgpackValue::from(authenticated),
+            MsgpackValue::from(reason),
+            MsgpackValue::from(self.self_hostname.as_str()),
+            MsgpackValue::from(digest),
+        ])
+    }
+}
+
+fn helo(nonce: &[u8]) -> MsgpackValue {
+    MsgpackValue::Array(vec![
+        MsgpackValue::from("HELO"),
+        MsgpackValue::Map(vec![
+            (MsgpackValue::from("nonce"), MsgpackValue::from(nonce)),
+            // Clients aren't authenticated with usernames and passwords.
+            (MsgpackValue::from("auth"), MsgpackValue::from("")),
+            (MsgpackValue::from("keepalive"), MsgpackValue::from(true)),
+        ]),
+    ])
+}
+
+/// Checks the PING of a client, returning its salt if it knows the shared key.
+fn check_ping(ping: MsgpackValue, nonce: &[u8], shared_key: &str) -> Result<Vec<u8>, FluentError> {
+    let ping = match ping {
+        MsgpackValue::Array(ping) => ping,
+        _ => Vec::new(),
+    };
+
+    // ["PING", hostname, shared_key_salt, shared_key_hexdigest, username, password]
+    match ping.as_slice() {
+        [kind, hostname, salt, digest, ..] if kind.as_str() == Some("PING") => {
+            let (hostname, salt) = match (as_bytes(hostname), as_bytes(salt)) {
+                (Some(hostname), Some(salt)) => (hostname, salt),
+                _ => {
+                    return Err(FluentError::Unauthenticated {
+                        reason: "invalid PING",
+                    })
+                }
+            };
+            if digest.as_str()
+                == Some(shared_key_digest(salt, hostname, nonce, shared_key).as_str())
+            {
+                Ok(salt.to_vec())
+            } else {
+                Err(FluentError::Unauthenticated {
+                    reason: "shared_key mismatch",
+                })
+            }
+        }
+        _ => Err(FluentError::Unauthenticated {
+            reason: "expected a PING",
+        }),
+    }
+}
+
+fn shared_key_digest(salt: &[u8], hostname: &[u8], nonce: &[u8], shared_key: &str) -> String {
+    let digest = Sha512::new()
+        .chain(salt)
+        .chain(hostname)
+        .chain(nonce)
+        .chain(shared_key)
+        .finalize();
+    hex::encode(digest)
+}
+
+async fn write_message(
+    socket: &mut MaybeTlsIncomingStream<TcpStream>,
+    message: &MsgpackValue,
+) -> Result<(), FluentError> {
+    let mut buffer = Vec::new();
+    rmpv::encode::write_value(&mut buffer, message).expect("Writing to a Vec never fails.");
+    socket.write_all(&buffer).await.context(Io)
+}
+
+/// Splits the stream of a connection into msgpack values.
+struct FluentDecoder {
+    max_length: usize,
+    scanner: Scanner,
+}
+
+impl FluentDecoder {
+    fn new(max_length: usize) -> Self {
+        Self {
+            max_length,
+            scanner: Scanner::default(),
+        }
+    }
+}
+
+impl Decoder for FluentDecoder {
+    type Item = (MsgpackValue, usize);
+    type Error = FluentError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // The rest of the value may not have arrived yet.
+        let byte_size = match self.scanner.scan(&src[..], self.max_length)? {
+            Some(byte_size) => byte_size,
+            None => return Ok(None),
+        };
+
+        let value = read_value(&src[..byte_size])?;
+        src.advance(byte_size);
+        Ok(Some((value, byte_size)))
+    }
+}
+
+/// Reads a value found by a `Scanner`, which bounds its size and nesting.
+fn read_value(mut bytes: &[u8]) -> Result<MsgpackValue, FluentError> {
+    rmpv::decode::read_value(&mut bytes).map_err(|error| FluentError::Decode {
+        source: error.into(),
+    })
+}
+
+/// Finds where the msgpack value at the start of a buffer ends, without
+/// decoding it.
+///
+/// Values arrive in pieces, so the scan of a value that hasn't arrived whole
+/// resumes where the previous scan stopped, rather than reading the value
+/// from its start again.
+#[derive(Debug, Default)]
+struct Scanner {
+    /// The end of the scanned part of the value.
+    offset: usize,
+    /// The number of items left in each of the arrays and maps being scanned.
+    pending: Vec<u64>,
+}
+
+impl Scanner {
+    /// Returns the size of the value at the start of `buffer`, if it is whole.
+    fn scan(&mut self, buffer: &[u8], max_length: usize) -> Result<Option<usize>, FluentError> {
+        loop {
+            let (size, items) = match item_size(&buffer[self.offset..])? {
+                Some(item) => item,
+                None => return Ok(None),
+            };
+            let end = self.offset.saturating_add(size);
+            if end > max_length {
+                return Err(FluentError::TooLong { max_length });
+            }
+            if end > buffer.len() {
+                return Ok(None);
+            }
+            self.offset = end;
+
+            if items > 0 {
+                if self.pending.len() == MAX_DEPTH {
+                    return Err(FluentError::InvalidMessage {
+                        reason: "message nested too deeply",
+                    });
+                }
+                self.pending.push(items);
+                continue;
+            }
+
+            // The item completes the arrays and maps it is the last item of.
+            loop {
+                match self.pending.last_mut() {
+                    None => {
+                        let size = self.offset;
+                        *self = Self::default();
+                        return Ok(Some(size));
+                    }
+                    Some(left) if *left > 1 => {
+                        *left -= 1;
+                        break;
+                    }
+                    Some(_) => {
+                        self.pending.pop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the size of the msgpack item at the start of `bytes`, along with
+/// the number of items following it if it is an array or a map, once the
+/// header of the item has arrived. The size of arrays and maps excludes their
+/// items.
+fn item_size(bytes: &[u8]) -> Result<Option<(usize, u64)>, FluentError> {
+    let marker = match bytes.first() {
+        Some(marker) => *marker,
+        None => return Ok(None),
+    };
+    // The big endian length or count of `n` bytes following the marker.
+    let read = |n: usize| {
+        bytes.get(1..1 + n).map(|bytes| {
+            bytes
+                .iter()
+                .fold(0, |acc, byte| acc << 8 | u64::from(*byte))
+        })
+    };
+
+    let size = match marker {
+        // fixints, nil and booleans
+        0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => Some((1, 0)),
+        // fixmap and fixarray
+        0x80..=0x8f => Some((1, 2 * u64::from(marker & 0x0f))),
+        0x90..=0x9f => Some((1, u64::from(marker & 0x0f))),
+        // fixstr
+        0xa0..=0xbf => Some((1 + usize::from(marker & 0x1f), 0)),
+        // bin and str
+        0xc4 | 0xd9 => read(1).map(|len| (2 + len as usize, 0)),
+        0xc5 | 0xda => read(2).map(|len| (3 + len as usize, 0)),
+        0xc6 | 0xdb => read(4).map(|len| (5 + len as usize, 0)),
+        // ext, of which the type follows the length
+        0xc7 => read(1).map(|len| (3 + len as usize, 0)),
+        0xc8 => read(2).map(|len| (4 + len as usize, 0)),
+        0xc9 => read(4).map(|len| (6 + len as usize, 0)),
+        // floats and integers
+        0xcc | 0xd0 => Some((2, 0)),
+        0xcd | 0xd1 => Some((3, 0)),
+        0xca | 0xce | 0xd2 => Some((5, 0)),
+        0xcb | 0xcf | 0xd3 => Some((9, 0)),
+        // fixext
+        0xd4 => Some((3, 0)),
+        0xd5 => Some((4, 0)),
+        0xd6 => Some((6, 0)),
+        0xd7 => Some((10, 0)),
+        0xd8 => Some((18, 0)),
+        // array and map
+        0xdc => read(2).map(|count| (3, count)),
+        0xdd => read(4).map(|count| (5, count)),
+        0xde => read(2).map(|count| (3, 2 * count)),
+        0xdf => read(4).map(|count| (5, 2 * count)),
+        0xc1 => {
+            return Err(FluentError::InvalidMessage {
+                reason: "invalid msgpack marker",
+            })
+        }
+    };
+    Ok(size)
+}
+
+/// Decodes a message of any of the forward modes into its events, along with
+/// the chunk to acknowledge it with, if the client asked for one.
+fn decode_message(
+    message: MsgpackValue,
+    host: &str,
+    max_length: usize,
+) -> Result<(Vec<Event>, Option<MsgpackValue>), FluentError> {
+    let mut parts = match message {
+        MsgpackValue::Array(parts) => parts.into_iter(),
+        _ => {
+            return Err(FluentError::InvalidMessage {
+                reason: "message isn't an array",
+            })
+        }
+    };
+
+    let tag = match parts.next().as_ref().and_then(as_bytes) {
+        Some(tag) => String::from_utf8_lossy(tag).into_owned(),
+        None => {
+            return Err(FluentError::InvalidMessage {
+                reason: "missing tag",
+            })
+        }
+    };
+
+    let (entries, options) = match parts.next() {
+        // Forward mode: [tag, [[time, record], ...], options]
+        Some(MsgpackValue::Array(entries)) => (entries, parts.next()),
+        // PackedForward and CompressedPackedForward modes: [tag, entries, options]
+        Some(MsgpackValue::Binary(entries)) => {
+            let options = parts.next();
+            (unpack(entries, options.as_ref(), max_length)?, options)
+        }
+        Some(MsgpackValue::String(entries)) => {
+            let options = parts.next();
+            (
+                unpack(entries.into_bytes(), options.as_ref(), max_length)?,
+                options,
+            )
+        }
+        // Message mode: [tag, time, record, options]
+        Some(time) => match parts.next() {
+            Some(record) => (vec![MsgpackValue::Array(vec![time, record])], parts.next()),
+            None => {
+                return Err(FluentError::InvalidMessage {
+                    reason: "missing record",
+                })
+            }
+        },
+        None => {
+            return Err(FluentError::InvalidMessage {
+                reason: "missing entries",
+            })
+        }
+    };
+
+    let events = entries
+        .into_iter()
+        .map(|entry| build_event(&tag, entry, host))
+        .collect::<Result<Vec<_>, _>>()?;
+    let chunk = option(options.as_ref(), "chunk").cloned();
+
+    Ok((events, chunk))
+}
+
+fn option<'a>(options: Option<&'a MsgpackValue>, name: &str) -> Option<&'a MsgpackValue> {
+    options
+        .and_then(MsgpackValue::as_map)
+        .and_then(|options| options.iter().find(|(key, _)| key.as_str() == Some(name)))
+        .map(|(_, value)| value)
+}
+
+/// Reads the entries packed in the messages of the PackedForward modes.
+fn unpack(
+    entries: Vec<u8>,
+    options: Option<&MsgpackValue>,
+    max_length: usize,
+) -> Result<Vec<MsgpackValue>, FluentError> {
+    let entries = match option(options, "compressed").and_then(MsgpackValue::as_str) {
+        Some("gzip") => {
+            // Reading one byte more than allowed tells entries at the limit
+            // apart from longer ones.
+            let mut decompressed = Vec::new();
+            MultiGzDecoder::new(&entries[..])
+                .take(max_length as u64 + 1)
+                .read_to_end(&mut decompressed)
+                .context(Decompress)?;
+            if decompressed.len() > max_length {
+                return Err(FluentError::TooLong { max_length });
+            }
+            decompressed
+        }
+        Some("text") | None => entries,
+        Some(_) => {
+            return Err(FluentError::InvalidMessage {
+                reason: "unsupported compression",
+            })
+        }
+    };
+
+    let mut entries = &entries[..];
+    let mut values = Vec::new();
+    while !entries.is_empty() {
+        let size = match Scanner::default().scan(entries, max_length)? {
+            Some(size) => size,
+            None => {
+                return Err(FluentError::InvalidMessage {
+                    reason: "truncated entries",
+                })
+            }
+        };
+        values.push(read_value(&entries[..size])?);
+        entries = &entries[size..];
+    }
+    Ok(values)
+}
+
+fn build_event(tag: &str, entry: MsgpackValue, host: &str) -> Result<Event, FluentError> {
+    let mut entry = match entry {
+        MsgpackValue::Array(entry) => entry.into_iter(),
+        _ => {
+            return Err(FluentError::InvalidMessage {
+                reason: "entry isn't an array",
+            })
+        }
+    };
+    let (timestamp, record) = match (entry.next(), entry.next()) {
+        (Some(time), Some(MsgpackValue::Map(record))) => (decode_time(time)?, record),
+        _ => {
+            return Err(FluentError::InvalidMessage {
+                reason: "entry isn't a time and a record",
+            })
+        }
+    };
+
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    for (key, value) in record {
+        log.insert_flat(key_to_string(key), msgpack_to_value(value));
+    }
+
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert("tag", tag.to_owned());
+    log.try_insert(log_schema().host_key(), host.to_owned());
+    log.try_insert(log_schema().source_type_key(), Bytes::from("fluent"));
+
+    emit!(FluentEventReceived);
+
+    Ok(event)
+}
+
+/// Decodes the time of an entry, either in seconds or as an `EventTime`.
+fn decode_time(time: MsgpackValue) -> Result<DateTime<Utc>, FluentError> {
+    let timestamp = match time {
+        MsgpackValue::Integer(seconds) => seconds
+            .as_i64()
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single()),
+        MsgpackValue::F64(seconds) => Utc
+            .timestamp_opt(seconds.trunc() as i64, (seconds.fract() * 1e9) as u32)
+            .single(),
+        // `EventTime` is the extension type 0, of the seconds and the
+        // nanoseconds as big endian 32 bits integers.
+        MsgpackValue::Ext(0, data) if data.len() == 8 => {
+            let seconds = u32::from_be_bytes(data[..4].try_into().unwrap());
+            let nanoseconds = u32::from_be_bytes(data[4..].try_into().unwrap());
+            Utc.timestamp_opt(seconds.into(), nanoseconds).single()
+        }
+        _ => None,
+    };
+    timestamp.ok_or(FluentError::InvalidMessage {
+        reason: "invalid time",
+    })
+}
+
+fn as_bytes(value: &MsgpackValue) -> Option<&[u8]> {
+    match value {
+        MsgpackValue::String(string) => Some(string.as_bytes()),
+        MsgpackValue::Binary(bytes) => Some(bytes),
+        _ => None,
+    }
+}
+
+fn key_to_string(key: MsgpackValue) -> String {
+    match as_bytes(&key) {
+        Some(key) => String::from_utf8_lossy(key).into_owned(),
+        None => key.to_string(),
+    }
+}
+
+fn msgpack_to_value(value: MsgpackValue) -> Value {
+    match value {
+        MsgpackValue::Nil => Value::Null,
+        MsgpackValue::Boolean(boolean) => Value::Boolean(boolean),
+        MsgpackValue::Integer(integer) => match integer.as_i64() {
+            Some(integer) => Value::Integer(integer),
+            // Only unsigned integers too large for an `i64` are left.
+            None => Value::Float(integer.as_f64().unwrap_or_default()),
+        },
+        MsgpackValue::F32(float) => Value::Float(float.into()),
+        MsgpackValue::F64(float) => Value::Float(float),
+        MsgpackValue::String(string) => Value::Bytes(string.into_bytes().into()),
+        MsgpackValue::Binary(bytes) => Value::Bytes(bytes.into()),
+        MsgpackValue::Array(array) => {
+            Value::Array(array.into_iter().map(msgpack_to_value).collect())
+        }
+        MsgpackValue::Map(map) => Value::Map(
+            map.into_iter()
+                .map(|(key, value)| (key_to_string(key), msgpack_to_value(value)))
+                .collect(),
+        ),
+        MsgpackValue::Ext(_, data) => Value::Bytes(data.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_n, next_addr, trace_init, wait_for_tcp};
+    use flate2::{write::GzEncoder, Compression};
+    use std::{io::Write, net::SocketAddr};
+    use tokio::{io::AsyncReadExt, sync::mpsc};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentConfig>();
+    }
+
+    fn encode(value: &MsgpackValue) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        rmpv::encode::write_value(&mut buffer, value).unwrap();
+        buffer
+    }
+
+    fn map(entries: Vec<(&str, MsgpackValue)>) -> MsgpackValue {
+        MsgpackValue::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (MsgpackValue::from(key), value))
+                .collect(),
+        )
+    }
+
+    fn entry(time: MsgpackValue, message: &str) -> MsgpackValue {
+        MsgpackValue::Array(vec![
+            time,
+            map(vec![("message", MsgpackValue::from(message))]),
+        ])
+    }
+
+    fn event_time(seconds: u32, nanoseconds: u32) -> MsgpackValue {
+        let mut data = seconds.to_be_bytes().to_vec();
+        data.extend_from_slice(&nanoseconds.to_be_bytes());
+        MsgpackValue::Ext(0, data)
+    }
+
+    fn assert_events(events: &[Event], messages: &[&str]) {
+        assert_eq!(events.len(), messages.len());
+        for (event, message) in events.iter().zip(messages) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*message).into());
+            assert_eq!(log["tag"], "tag.app".into());
+            assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+            assert_eq!(log[log_schema().source_type_key()], "fluent".into());
+        }
+    }
+
+    #[test]
+    fn decodes_message_mode() {
+        let message = MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::from(1_440_000_000),
+            map(vec![
+                ("message", MsgpackValue::from("one")),
+                ("count", MsgpackValue::from(2)),
+                ("nested.key", map(vec![("a", MsgpackValue::Nil)])),
+            ]),
+        ]);
+
+        let (events, chunk) = decode_message(message, "127.0.0.1", default_max_length()).unwrap();
+        assert_events(&events, &["one"]);
+        assert_eq!(chunk, None);
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_440_000_000, 0).into()
+        );
+        assert_eq!(log["count"], 2.into());
+        assert_eq!(
+            log.get_flat("nested.key"),
+            Some(&Value::Map(
+                vec![("a".into(), Value::Null)].into_iter().collect()
+            ))
+        );
+    }
+
+    #[test]
+    fn decodes_forward_mode() {
+        let message = MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::Array(vec![
+                entry(event_time(1_440_000_000, 500), "one"),
+                entry(MsgpackValue::from(1_440_000_001), "two"),
+            ]),
+            map(vec![(
+                "chunk",
+                MsgpackValue::from("p8n9gmxTQVC8/nh2wlKKeQ=="),
+            )]),
+        ]);
+
+        let (events, chunk) = decode_message(message, "127.0.0.1", default_max_length()).unwrap();
+        assert_events(&events, &["one", "two"]);
+        assert_eq!(chunk, Some(MsgpackValue::from("p8n9gmxTQVC8/nh2wlKKeQ==")));
+        assert_eq!(
+            events[0].as_log()[log_schema().timestamp_key()],
+            Utc.timestamp(1_440_000_000, 500).into()
+        );
+    }
+
+    #[test]
+    fn decodes_packed_forward_modes() {
+        let mut entries = encode(&entry(MsgpackValue::from(1_440_000_000), "one"));
+        entries.extend(encode(&entry(MsgpackValue::from(1_440_000_001), "two")));
+
+        let message = MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::from(entries.clone()),
+        ]);
+        let (events, _) = decode_message(message, "127.0.0.1", default_max_length()).unwrap();
+        assert_events(&events, &["one", "two"]);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&entries).unwrap();
+        let message = MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::from(encoder.finish().unwrap()),
+            map(vec![
+                ("size", MsgpackValue::from(2)),
+                ("compressed", MsgpackValue::from("gzip")),
+            ]),
+        ]);
+        let (events, _) = decode_message(message, "127.0.0.1", default_max_length()).unwrap();
+        assert_events(&events, &["one", "two"]);
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        for message in vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::Array(vec![MsgpackValue::from("tag.app")]),
+            MsgpackValue::Array(vec![
+                MsgpackValue::from("tag.app"),
+                MsgpackValue::from(1_440_000_000),
+                MsgpackValue::from("not a record"),
+            ]),
+        ] {
+            assert!(decode_message(message, "127.0.0.1", default_max_length()).is_err());
+        }
+    }
+
+    #[test]
+    fn decoder_waits_for_whole_values() {
+        let message = encode(&MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::from(1_440_000_000),
+            map(vec![("message", MsgpackValue::from("one"))]),
+        ]));
+
+        let mut decoder = FluentDecoder::new(default_max_length());
+        let mut buffer = BytesMut::new();
+        for byte in &message[..message.len() - 1] {
+            buffer.extend_from_slice(&[*byte]);
+            assert!(decoder.decode(&mut buffer).unwrap().is_none());
+        }
+        buffer.extend_from_slice(&message[message.len() - 1..]);
+        let (value, byte_size) = decoder.decode(&mut buffer).unwrap().unwrap();
+        assert_eq!(byte_size, message.len());
+        assert_eq!(encode(&value), message);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn decoder_rejects_long_messages() {
+        let message = encode(&MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::from(1_440_000_000),
+            map(vec![("message", MsgpackValue::from("x".repeat(100)))]),
+        ]));
+
+        // The length of the message string is enough to tell it's too long.
+        let mut buffer = BytesMut::from(&message[..30]);
+        assert!(FluentDecoder::new(50).decode(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn decoder_rejects_deep_nesting() {
+        let mut buffer = BytesMut::from(&[0x91; MAX_DEPTH + 1][..]);
+        assert!(FluentDecoder::new(default_max_length())
+            .decode(&mut buffer)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_long_decompressed_entries() {
+        let entries = encode(&entry(MsgpackValue::from(1_440_000_000), &"x".repeat(100)));
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&entries).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let options = map(vec![("compressed", MsgpackValue::from("gzip"))]);
+
+        assert!(unpack(compressed.clone(), Some(&options), 50).is_err());
+        let values = unpack(compressed, Some(&options), entries.len()).unwrap();
+        assert_eq!(values.len(), 1);
+    }
+
+    async fn source(shared_key: Option<&str>) -> (mpsc::Receiver<Event>, SocketAddr) {
+        let (sender, recv) = Pipeline::new_test();
+        let address = next_addr();
+        let config = FluentConfig {
+            address: address.into(),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            shared_key: shared_key.map(Into::into),
+            self_hostname: Some("vector".into()),
+            max_length: default_max_length(),
+        };
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                sender,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn read_value(stream: &mut TcpStream) -> MsgpackValue {
+        let mut decoder = FluentDecoder::new(default_max_length());
+        let mut buffer = BytesMut::new();
+        loop {
+            if let Some((value, _)) = decoder.decode(&mut buffer).unwrap() {
+                return value;
+            }
+            let mut chunk = [0; 1024];
+            let read = stream.read(&mut chunk).await.unwrap();
+            assert!(read > 0, "Connection closed.");
+            buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+
+    fn message_with_chunk(message: &str, chunk: &str) -> Vec<u8> {
+        encode(&MsgpackValue::Array(vec![
+            MsgpackValue::from("tag.app"),
+            MsgpackValue::from(1_440_000_000),
+            map(vec![("message", MsgpackValue::from(message))]),
+            map(vec![("chunk", MsgpackValue::from(chunk))]),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn acknowledges_chunks() {
+        trace_init();
+
+        let (recv, address) = source(None).await;
+        let mut stream = TcpStream::connect(address).await.unwrap();
+
+        stream
+            .write_all(&message_with_chunk("one", "chunk-1"))
+            .await
+            .unwrap();
+        assert_eq!(
+            read_value(&mut stream).await,
+            map(vec![("ack", MsgpackValue::from("chunk-1"))])
+        );
+
+        let events = collect_n(recv, 1).await;
+        assert_events(&events, &["one"]);
+    }
+
+    fn ping(nonce: &[u8], shared_key: &str) -> Vec<u8> {
+        let digest = shared_key_digest(b"salt", b"client", nonce, shared_key);
+        encode(&MsgpackValue::Array(vec![
+            MsgpackValue::from("PING"),
+            MsgpackValue::from("client"),
+            MsgpackValue::from("salt"),
+            MsgpackValue::from(digest),
+            MsgpackValue::from(""),
+            MsgpackValue::from(""),
+        ]))
+    }
+
+    async fn helo_nonce(stream: &mut TcpStream) -> Vec<u8> {
+        let helo = read_value(stream).await;
+        assert_eq!(helo[0], MsgpackValue::from("HELO"));
+        let nonce = option(Some(&helo[1]), "nonce").and_then(as_bytes);
+        nonce.unwrap().to_vec()
+    }
+
+    #[tokio::test]
+    async fn authenticates_with_shared_key() {
+        trace_init();
+
+        let (recv, address) = source(Some("secret")).await;
+        let mut stream = TcpStream::connect(address).await.unwrap();
+
+        let nonce = helo_nonce(&mut stream).await;
+        stream.write_all(&ping(&nonce, "secret")).await.unwrap();
+
+        let pong = read_value(&mut stream).await;
+        assert_eq!(pong[0], MsgpackValue::from("PONG"));
+        assert_eq!(pong[1], MsgpackValue::from(true));
+        assert_eq!(pong[3], MsgpackValue::from("vector"));
+        assert_eq!(
+            pong[4],
+            MsgpackValue::from(shared_key_digest(b"salt", b"vector", &nonce, "secret"))
+        );
+
+        stream
+            .write_all(&message_with_chunk("one", "chunk-1"))
+            .await
+            .unwrap();
+        assert_eq!(
+            read_value(&mut stream).await,
+            map(vec![("ack", MsgpackValue::from("chunk-1"))])
+        );
+        assert_events(&collect_n(recv, 1).await, &["one"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_shared_key() {
+        trace_init();
+
+        let (_recv, address) = source(Some("secret")).await;
+        let mut stream = TcpStream::connect(address).await.unwrap();
+
+        let nonce = helo_nonce(&mut stream).await;
+        stream.write_all(&ping(&nonce, "wrong")).await.unwrap();
+
+        let pong = read_value(&mut stream).await;
+        assert_eq!(pong[1], MsgpackValue::from(false));
+
+        // The connection is closed after a failed authentication.
+        let mut rest = Vec::new();
+        assert_eq!(stream.read_to_end(&mut rest).await.unwrap(), 0);
+    }
+}
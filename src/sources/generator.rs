@@ -112,6 +112,17 @@ impl GeneratorConfig {
         }
     }
 
+    /// Generate events of the given format as fast as they are accepted, until
+    /// shut down.
+    #[allow(dead_code)] // to make check-component-features pass
+    pub fn with_format(format: OutputFormat) -> Self {
+        Self {
+            count: usize::max_value(),
+            interval: None,
+            format,
+        }
+    }
+
     async fn inner(self, mut shutdown: ShutdownSignal, mut out: Pipeline) -> Result<(), ()> {
         let mut interval = self.interval.map(|i| interval(Duration::from_secs_f64(i)));
 
@@ -0,0 +1,462 @@
+#[cfg(unix)]
+use crate::udp;
+use crate::{
+    config::{self, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
+    internal_events::{
+        GraphiteEventReceived, GraphiteInvalidPayload, GraphiteInvalidRecord, GraphiteSocketError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::{SocketListenAddr, TcpSource},
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Event, Pipeline,
+};
+use bytes::{Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use futures::{stream, SinkExt, StreamExt, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use serde_pickle::Value;
+use std::{collections::VecDeque, io, net::SocketAddr};
+use tokio::net::UdpSocket;
+use tokio_util::{
+    codec::{BytesCodec, Decoder, LengthDelimitedCodec},
+    udp::UdpFramed,
+};
+
+pub mod parser;
+
+/// The largest pickled message accepted, as carbon's `MAX_LENGTH`.
+const MAX_PICKLE_LENGTH: usize = 1024 * 1024;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+enum GraphiteConfig {
+    Tcp(TcpConfig),
+    Udp(UdpConfig),
+}
+
+/// The protocols of carbon's receivers.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    /// Lines of `<path> <value> <timestamp>`.
+    Plaintext,
+    /// Pickled lists of `(path, (timestamp, value))` datapoints, prefixed with
+    /// their length.
+    Pickle,
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        Protocol::Plaintext
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UdpConfig {
+    address: SocketAddr,
+    #[cfg(unix)]
+    receive_buffer_bytes: Option<usize>,
+}
+
+impl UdpConfig {
+    pub fn from_address(address: SocketAddr) -> Self {
+        Self {
+            address,
+            #[cfg(unix)]
+            receive_buffer_bytes: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TcpConfig {
+    address: SocketListenAddr,
+    #[serde(default)]
+    protocol: Protocol,
+    keepalive: Option<TcpKeepaliveConfig>,
+    #[serde(default)]
+    tls: Option<TlsConfig>,
+    #[serde(default = "default_shutdown_timeout_secs")]
+    shutdown_timeout_secs: u64,
+    receive_buffer_bytes: Option<usize>,
+}
+
+impl TcpConfig {
+    pub fn from_address(address: SocketListenAddr, protocol: Protocol) -> Self {
+        Self {
+            address,
+            protocol,
+            keepalive: None,
+            tls: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            receive_buffer_bytes: None,
+        }
+    }
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+inventory::submit! {
+    SourceDescription::new::<GraphiteConfig>("graphite")
+}
+
+impl GenerateConfig for GraphiteConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::Tcp(TcpConfig::from_address(
+            SocketListenAddr::SocketAddr("0.0.0.0:2003".parse().unwrap()),
+            Protocol::Plaintext,
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "graphite")]
+impl SourceConfig for GraphiteConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        match self {
+            GraphiteConfig::Udp(config) => {
+                Ok(Box::pin(graphite_udp(config.clone(), shutdown, out)))
+            }
+            GraphiteConfig::Tcp(config) => {
+                let tls = MaybeTlsSettings::from_config(&config.tls, true)?;
+                match config.protocol {
+                    Protocol::Plaintext => PlaintextTcpSource.run(
+                        config.address,
+                        config.keepalive,
+                        config.shutdown_timeout_secs,
+                        tls,
+                        config.receive_buffer_bytes,
+                        shutdown,
+                        out,
+                    ),
+                    Protocol::Pickle => PickleTcpSource.run(
+                        config.address,
+                        config.keepalive,
+                        config.shutdown_timeout_secs,
+                        tls,
+                        config.receive_buffer_bytes,
+                        shutdown,
+                        out,
+                    ),
+                }
+            }
+        }
+    }
+
+    fn output_type(&self) -> config::DataType {
+        config::DataType::Metric
+    }
+
+    fn source_type(&self) -> &'static str {
+        "graphite"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match self {
+            Self::Tcp(tcp) => vec![tcp.address.into()],
+            Self::Udp(udp) => vec![Resource::udp(udp.address)],
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Option<Event> {
+    match parser::parse(line) {
+        Ok(metric) => {
+            emit!(GraphiteEventReceived {
+                byte_size: line.len()
+            });
+            Some(Event::Metric(metric))
+        }
+        Err(error) => {
+            emit!(GraphiteInvalidRecord { error, text: line });
+            None
+        }
+    }
+}
+
+async fn graphite_udp(
+    config: UdpConfig,
+    shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(&config.address)
+        .map_err(|error| emit!(GraphiteSocketError::bind(error)))
+        .await?;
+
+    #[cfg(unix)]
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        udp::set_receive_buffer_size(&socket, receive_buffer_bytes);
+    }
+
+    info!(
+        message = "Listening.",
+        addr = %config.address,
+        r#type = "udp"
+    );
+
+    let mut stream = UdpFramed::new(socket, BytesCodec::new()).take_until(shutdown);
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok((bytes, _sock)) => {
+                let packet = String::from_utf8_lossy(bytes.as_ref());
+                let metrics = packet
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .filter_map(parse_line)
+                    .map(Ok);
+
+                // Need `boxed` to resolve a lifetime issue
+                // https://github.com/rust-lang/rust/issues/64552#issuecomment-669728225
+                let mut metrics = stream::iter(metrics).boxed();
+                if let Err(error) = out.send_all(&mut metrics).await {
+                    error!(message = "Error sending metric.", %error);
+                    break;
+                }
+            }
+            Err(error) => {
+                emit!(GraphiteSocketError::read(error));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct PlaintextTcpSource;
+
+impl TcpSource for PlaintextTcpSource {
+    type Error = io::Error;
+    type Decoder = BytesDelimitedCodec;
+
+    fn decoder(&self) -> Self::Decoder {
+        BytesDelimitedCodec::new(b'\n')
+    }
+
+    fn build_event(&self, line: Bytes, _host: Bytes) -> Option<Event> {
+        let line = String::from_utf8_lossy(line.as_ref());
+        if line.trim().is_empty() {
+            return None;
+        }
+        parse_line(&line)
+    }
+}
+
+#[derive(Clone)]
+struct PickleTcpSource;
+
+impl TcpSource for PickleTcpSource {
+    type Error = io::Error;
+    type Decoder = PickleDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        PickleDecoder::new()
+    }
+
+    fn build_event(&self, datapoint: Datapoint, _host: Bytes) -> Option<Event> {
+        match parser::parse_datapoint(&datapoint.value) {
+            Ok(metric) => {
+                emit!(GraphiteEventReceived {
+                    byte_size: datapoint.byte_size
+                });
+                Some(Event::Metric(metric))
+            }
+            Err(error) => {
+                emit!(GraphiteInvalidRecord {
+                    error,
+                    text: &format!("{:?}", datapoint.value),
+                });
+                None
+            }
+        }
+    }
+}
+
+/// A datapoint of a pickled message, along with its share of the size of the
+/// message.
+struct Datapoint {
+    value: Value,
+    byte_size: usize,
+}
+
+/// Decodes the datapoints of length-prefixed pickled messages, one at a time,
+/// as the TCP source builds one event per frame.
+struct PickleDecoder {
+    messages: LengthDelimitedCodec,
+    datapoints: VecDeque<Datapoint>,
+}
+
+impl PickleDecoder {
+    fn new() -> Self {
+        Self {
+            messages: LengthDelimitedCodec::builder()
+                .max_frame_length(MAX_PICKLE_LENGTH)
+                .new_codec(),
+            datapoints: VecDeque::new(),
+        }
+    }
+}
+
+impl Decoder for PickleDecoder {
+    type Item = Datapoint;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(datapoint) = self.datapoints.pop_front() {
+                return Ok(Some(datapoint));
+            }
+
+            let message = match self.messages.decode(src)? {
+                Some(message) => message,
+                None => return Ok(None),
+            };
+            match parser::unpickle(&message) {
+                Ok(values) => {
+                    let byte_size = message.len() / values.len().max(1);
+                    self.datapoints.extend(
+                        values
+                            .into_iter()
+                            .map(|value| Datapoint { value, byte_size }),
+                    );
+                }
+                Err(error) => emit!(GraphiteInvalidPayload {
+                    error,
+                    byte_size: message.len(),
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind, MetricValue},
+        test_util::{collect_n, next_addr, send_lines, wait_for_tcp},
+    };
+    use chrono::{TimeZone, Utc};
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GraphiteConfig>();
+    }
+
+    fn gauge(name: &str, value: f64) -> Event {
+        Metric::new(
+            name.into(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value },
+        )
+        .with_timestamp(Some(Utc.timestamp(1609459200, 0)))
+        .into()
+    }
+
+    async fn start(config: GraphiteConfig) -> tokio::sync::mpsc::Receiver<Event> {
+        let (tx, rx) = Pipeline::new_test();
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        rx
+    }
+
+    #[tokio::test]
+    async fn receives_plaintext_over_tcp() {
+        let addr = next_addr();
+        let rx = start(GraphiteConfig::Tcp(TcpConfig::from_address(
+            addr.into(),
+            Protocol::Plaintext,
+        )))
+        .await;
+
+        wait_for_tcp(addr).await;
+        let lines = vec![
+            "cpu.load 0.5 1609459200".to_owned(),
+            "not a metric".to_owned(),
+            "requests 3 1609459200".to_owned(),
+        ];
+        send_lines(addr, lines.into_iter()).await.unwrap();
+
+        assert_eq!(
+            collect_n(rx, 2).await,
+            vec![gauge("cpu.load", 0.5), gauge("requests", 3.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_plaintext_over_udp() {
+        let addr = next_addr();
+        let rx = start(GraphiteConfig::Udp(UdpConfig::from_address(addr))).await;
+
+        let mut socket = UdpSocket::bind(next_addr()).await.unwrap();
+        // The source may not be listening yet.
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(50));
+        let received = collect_n(rx, 2);
+        tokio::pin!(received);
+        let events = loop {
+            tokio::select! {
+                events = &mut received => break events,
+                _ = interval.tick() => {
+                    socket
+                        .send_to(b"cpu.load 0.5 1609459200\nrequests 3 1609459200\n", &addr)
+                        .await
+                        .unwrap();
+                }
+            }
+        };
+
+        assert_eq!(events, vec![gauge("cpu.load", 0.5), gauge("requests", 3.0)]);
+    }
+
+    #[tokio::test]
+    async fn receives_pickles() {
+        let addr = next_addr();
+        let rx = start(GraphiteConfig::Tcp(TcpConfig::from_address(
+            addr.into(),
+            Protocol::Pickle,
+        )))
+        .await;
+
+        // pickle.dumps([("cpu.load", (1609459200, 0.5)), ("requests", (1609459200.0, "3"))], protocol=2)
+        let payload: &[u8] = b"\x80\x02]q\x00(X\x08\x00\x00\x00cpu.loadq\x01J\x00f\xee_G?\xe0\x00\x00\x00\x00\x00\x00\x86q\x02\x86q\x03X\x08\x00\x00\x00requestsq\x04GA\xd7\xfb\x99\x80\x00\x00\x00X\x01\x00\x00\x003q\x05\x86q\x06\x86q\x07e.";
+        let mut message = (payload.len() as u32).to_be_bytes().to_vec();
+        message.extend_from_slice(payload);
+        // A message that doesn't unpickle is dropped.
+        message.extend_from_slice(&4u32.to_be_bytes());
+        message.extend_from_slice(b"oops");
+        message.extend_from_slice(&message.clone()[..4 + payload.len()]);
+
+        wait_for_tcp(addr).await;
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&message).await.unwrap();
+
+        assert_eq!(
+            collect_n(rx, 4).await,
+            vec![
+                gauge("cpu.load", 0.5),
+                gauge("requests", 3.0),
+                gauge("cpu.load", 0.5),
+                gauge("requests", 3.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
+use chrono::{DateTime, TimeZone, Utc};
+use serde_pickle::Value;
+use snafu::Snafu;
+use std::str;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Malformed metric: {}", reason))]
+    Malformed { reason: &'static str },
+    #[snafu(display("Invalid value: {}", value))]
+    InvalidValue { value: String },
+    #[snafu(display("Invalid timestamp: {}", timestamp))]
+    InvalidTimestamp { timestamp: String },
+    #[snafu(display("Invalid tag: {:?}", tag))]
+    InvalidTag { tag: String },
+    #[snafu(display("Unable to unpickle payload: {}", message))]
+    Unpickle { message: String },
+}
+
+/// Parses a line of the plaintext protocol, `<path> <value> <timestamp>`.
+pub fn parse(line: &str) -> Result<Metric, ParseError> {
+    let mut fields = line.split_whitespace();
+    let (path, value, timestamp) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(path), Some(value), Some(timestamp)) if fields.next().is_none() => {
+            (path, value, timestamp)
+        }
+        _ => {
+            return Err(ParseError::Malformed {
+                reason: "expected a path, a value and a timestamp separated by spaces",
+            })
+        }
+    };
+
+    let value = value.parse().map_err(|_| ParseError::InvalidValue {
+        value: value.into(),
+    })?;
+    let timestamp = timestamp
+        .parse()
+        .ok()
+        .and_then(parse_timestamp)
+        .ok_or_else(|| ParseError::InvalidTimestamp {
+            timestamp: timestamp.into(),
+        })?;
+
+    build_metric(path, value, timestamp)
+}
+
+/// Unpickles the payload of a message of the pickle protocol, a list of
+/// `(path, (timestamp, value))` datapoints.
+pub fn unpickle(payload: &[u8]) -> Result<Vec<Value>, ParseError> {
+    let value = serde_pickle::value_from_slice(payload).map_err(|error| ParseError::Unpickle {
+        message: error.to_string(),
+    })?;
+
+    match value {
+        Value::List(datapoints) | Value::Tuple(datapoints) => Ok(datapoints),
+        _ => Err(ParseError::Malformed {
+            reason: "expected a list of datapoints",
+        }),
+    }
+}
+
+/// Parses a datapoint of the pickle protocol.
+pub fn parse_datapoint(datapoint: &Value) -> Result<Metric, ParseError> {
+    let (path, point) = match pair(datapoint) {
+        Some((Value::String(path), point)) => (path.as_str(), point),
+        Some((Value::Bytes(path), point)) => match str::from_utf8(path) {
+            Ok(path) => (path, point),
+            Err(_) => {
+                return Err(ParseError::Malformed {
+                    reason: "path isn't valid UTF-8",
+                })
+            }
+        },
+        _ => {
+            return Err(ParseError::Malformed {
+                reason: "expected a path and a (timestamp, value) pair",
+            })
+        }
+    };
+    let (timestamp, value) = pair(point).ok_or(ParseError::Malformed {
+        reason: "expected a (timestamp, value) pair",
+    })?;
+
+    let value = number(value).ok_or_else(|| ParseError::InvalidValue {
+        value: format!("{:?}", value),
+    })?;
+    let timestamp = number(timestamp).and_then(parse_timestamp).ok_or_else(|| {
+        ParseError::InvalidTimestamp {
+            timestamp: format!("{:?}", timestamp),
+        }
+    })?;
+
+    build_metric(path, value, timestamp)
+}
+
+fn pair(value: &Value) -> Option<(&Value, &Value)> {
+    match value {
+        Value::List(values) | Value::Tuple(values) if values.len() == 2 => {
+            Some((&values[0], &values[1]))
+        }
+        _ => None,
+    }
+}
+
+/// Converts a number, or a string of one, as carbon does with `float()`.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::I64(value) => Some(*value as f64),
+        Value::F64(value) => Some(*value),
+        Value::String(value) => value.trim().parse().ok(),
+        Value::Bytes(value) => str::from_utf8(value).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Negative timestamps, as the `-1` some clients send, stand for the time the
+/// metric is received, left to downstream components like other metrics
+/// without a timestamp.
+fn parse_timestamp(seconds: f64) -> Option<Option<DateTime<Utc>>> {
+    if !seconds.is_finite() {
+        None
+    } else if seconds < 0.0 {
+        Some(None)
+    } else {
+        let nanos = (seconds.fract() * 1_000_000_000.0) as u32;
+        Utc.timestamp_opt(seconds.trunc() as i64, nanos)
+            .single()
+            .map(Some)
+    }
+}
+
+/// Builds a gauge out of a metric path, along with the tags of the tagged
+/// series format, `<name>;<tag>=<value>;...`.
+fn build_metric(
+    path: &str,
+    value: f64,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<Metric, ParseError> {
+    let mut parts = path.split(';');
+    let name = parts
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or(ParseError::Malformed {
+            reason: "empty metric path",
+        })?;
+
+    let tags = parts
+        .map(|tag| {
+            let mut pair = tag.splitn(2, '=');
+            match (pair.next(), pair.next()) {
+                (Some(key), Some(value)) if !key.is_empty() && !value.is_empty() => {
+                    Ok((key.to_owned(), value.to_owned()))
+                }
+                _ => Err(ParseError::InvalidTag { tag: tag.into() }),
+            }
+        })
+        .collect::<Result<MetricTags, _>>()?;
+
+    Ok(Metric::new(
+        name.into(),
+        MetricKind::Absolute,
+        MetricValue::Gauge { value },
+    )
+    .with_tags(if tags.is_empty() { None } else { Some(tags) })
+    .with_timestamp(timestamp))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gauge(name: &str, value: f64, timestamp: i64) -> Metric {
+        Metric::new(
+            name.into(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value },
+        )
+        .with_timestamp(Some(Utc.timestamp(timestamp, 0)))
+    }
+
+    #[test]
+    fn parses_plaintext_lines() {
+        assert_eq!(
+            parse("servers.web-1.cpu.load 0.5 1609459200"),
+            Ok(gauge("servers.web-1.cpu.load", 0.5, 1609459200))
+        );
+        assert_eq!(
+            parse("disk.used;host=web-1;mount=/var  42 1609459200.5\r"),
+            Ok(Metric::new(
+                "disk.used".into(),
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 42.0 },
+            )
+            .with_tags(Some(
+                vec![
+                    ("host".to_owned(), "web-1".to_owned()),
+                    ("mount".to_owned(), "/var".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1609459200, 500_000_000))))
+        );
+        assert_eq!(
+            parse("requests 3 -1").map(|metric| metric.data.timestamp),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(matches!(
+            parse("requests 3"),
+            Err(ParseError::Malformed { .. })
+        ));
+        assert!(matches!(
+            parse("requests 3 1609459200 extra"),
+            Err(ParseError::Malformed { .. })
+        ));
+        assert!(matches!(
+            parse("requests three 1609459200"),
+            Err(ParseError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            parse("requests 3 yesterday"),
+            Err(ParseError::InvalidTimestamp { .. })
+        ));
+        assert!(matches!(
+            parse("requests;host 3 1609459200"),
+            Err(ParseError::InvalidTag { .. })
+        ));
+        assert!(matches!(
+            parse(";host=web-1 3 1609459200"),
+            Err(ParseError::Malformed { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_pickled_datapoints() {
+        // pickle.dumps([("cpu.load", (1609459200, 0.5)), ("requests", (1609459200.0, "3"))], protocol=2)
+        let payload = b"\x80\x02]q\x00(X\x08\x00\x00\x00cpu.loadq\x01J\x00f\xee_G?\xe0\x00\x00\x00\x00\x00\x00\x86q\x02\x86q\x03X\x08\x00\x00\x00requestsq\x04GA\xd7\xfb\x99\x80\x00\x00\x00X\x01\x00\x00\x003q\x05\x86q\x06\x86q\x07e.";
+        let datapoints = unpickle(payload).unwrap();
+
+        assert_eq!(
+            datapoints
+                .iter()
+                .map(parse_datapoint)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec![
+                gauge("cpu.load", 0.5, 1609459200),
+                gauge("requests", 3.0, 1609459200),
+            ])
+        );
+
+        // Paths pickled by Python 2 are byte strings.
+        assert_eq!(
+            parse_datapoint(&Value::Tuple(vec![
+                Value::Bytes(b"cpu.load".to_vec()),
+                Value::Tuple(vec![Value::I64(1609459200), Value::I64(1)]),
+            ])),
+            Ok(gauge("cpu.load", 1.0, 1609459200))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_datapoints() {
+        assert!(matches!(
+            unpickle(b"not a pickle"),
+            Err(ParseError::Unpickle { .. })
+        ));
+        assert!(matches!(
+            parse_datapoint(&Value::Tuple(vec![Value::String("cpu.load".into())])),
+            Err(ParseError::Malformed { .. })
+        ));
+        assert!(matches!(
+            parse_datapoint(&Value::Tuple(vec![
+                Value::String("cpu.load".into()),
+                Value::Tuple(vec![Value::I64(1609459200), Value::None]),
+            ])),
+            Err(ParseError::InvalidValue { .. })
+        ));
+    }
+}
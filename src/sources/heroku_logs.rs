@@ -10,15 +10,10 @@ use crate::{
     tls::TlsConfig,
     Pipeline,
 };
-use bytes::{buf::BufExt, Bytes};
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    io::{BufRead, BufReader},
-    net::SocketAddr,
-    str::FromStr,
-};
+use std::{collections::HashMap, io, net::SocketAddr, str::FromStr};
 
 use warp::http::{HeaderMap, StatusCode};
 
@@ -29,6 +24,20 @@ pub struct LogplexConfig {
     query_parameters: Vec<String>,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    #[serde(default)]
+    drain_tokens: Vec<String>,
+    #[serde(default = "default_frame_id_key")]
+    frame_id_key: String,
+    #[serde(default = "default_drain_token_key")]
+    drain_token_key: String,
+}
+
+fn default_frame_id_key() -> String {
+    "frame_id".to_owned()
+}
+
+fn default_drain_token_key() -> String {
+    "drain_token".to_owned()
 }
 
 inventory::submit! {
@@ -46,6 +55,9 @@ impl GenerateConfig for LogplexConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            drain_tokens: Vec::new(),
+            frame_id_key: default_frame_id_key(),
+            drain_token_key: default_drain_token_key(),
         })
         .unwrap()
     }
@@ -54,6 +66,10 @@ impl GenerateConfig for LogplexConfig {
 #[derive(Clone, Default)]
 struct LogplexSource {
     query_parameters: Vec<String>,
+    /// The drains requests are accepted from, or any if empty.
+    drain_tokens: Vec<String>,
+    frame_id_key: String,
+    drain_token_key: String,
 }
 
 impl HttpSource for LogplexSource {
@@ -62,8 +78,9 @@ impl HttpSource for LogplexSource {
         body: Bytes,
         header_map: HeaderMap,
         query_parameters: HashMap<String, String>,
+        _path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_message(body, header_map)
+        decode_message(body, header_map, self)
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
     }
 }
@@ -80,6 +97,9 @@ impl SourceConfig for LogplexConfig {
     ) -> crate::Result<super::Source> {
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
+            drain_tokens: self.drain_tokens.clone(),
+            frame_id_key: self.frame_id_key.clone(),
+            drain_token_key: self.drain_token_key.clone(),
         };
         source.run(self.address, "events", &self.tls, &self.auth, out, shutdown)
     }
@@ -127,7 +147,11 @@ impl SourceConfig for LogplexCompatConfig {
     }
 }
 
-fn decode_message(body: Bytes, header_map: HeaderMap) -> Result<Vec<Event>, ErrorMessage> {
+fn decode_message(
+    body: Bytes,
+    header_map: HeaderMap,
+    source: &LogplexSource,
+) -> Result<Vec<Event>, ErrorMessage> {
     // Deal with headers
     let msg_count = match usize::from_str(get_header(&header_map, "Logplex-Msg-Count")?) {
         Ok(v) => v,
@@ -136,6 +160,14 @@ fn decode_message(body: Bytes, header_map: HeaderMap) -> Result<Vec<Event>, Erro
     let frame_id = get_header(&header_map, "Logplex-Frame-Id")?;
     let drain_token = get_header(&header_map, "Logplex-Drain-Token")?;
 
+    let drain_tokens = &source.drain_tokens;
+    if !drain_tokens.is_empty() && !drain_tokens.iter().any(|token| token == drain_token) {
+        return Err(ErrorMessage::new(
+            StatusCode::UNAUTHORIZED,
+            format!("Unknown drain token {:?}", drain_token),
+        ));
+    }
+
     emit!(HerokuLogplexRequestReceived {
         msg_count,
         frame_id,
@@ -143,7 +175,12 @@ fn decode_message(body: Bytes, header_map: HeaderMap) -> Result<Vec<Event>, Erro
     });
 
     // Deal with body
-    let events = body_to_events(body);
+    let mut events = body_to_events(body);
+    for event in &mut events {
+        let log = event.as_mut_log();
+        log.insert(source.frame_id_key.as_str(), frame_id.to_owned());
+        log.insert(source.drain_token_key.as_str(), drain_token.to_owned());
+    }
 
     if events.len() != msg_count {
         let error_msg = format!(
@@ -179,10 +216,14 @@ fn header_error_message(name: &str, msg: &str) -> ErrorMessage {
 }
 
 fn body_to_events(body: Bytes) -> Vec<Event> {
-    let rdr = BufReader::new(body.reader());
-    rdr.lines()
-        .filter_map(|res| {
-            res.map_err(|error| emit!(HerokuLogplexRequestReadError { error }))
+    split_frames(&body)
+        .into_iter()
+        .filter_map(|frame| {
+            String::from_utf8(frame.to_vec())
+                .map_err(|error| {
+                    let error = io::Error::new(io::ErrorKind::InvalidData, error);
+                    emit!(HerokuLogplexRequestReadError { error })
+                })
                 .ok()
         })
         .filter(|s| !s.is_empty())
@@ -190,6 +231,66 @@ fn body_to_events(body: Bytes) -> Vec<Event> {
         .collect()
 }
 
+/// Splits the body into its frames. Logplex frames messages with octet
+/// counting: each one is prefixed by its length in bytes, trailing newline
+/// included, so messages may contain newlines themselves. Bodies that aren't
+/// framed this way are split into lines instead, from the first frame whose
+/// length doesn't fit.
+fn split_frames(mut body: &[u8]) -> Vec<&[u8]> {
+    let mut frames = Vec::new();
+    loop {
+        while let Some((first, rest)) = body.split_first() {
+            if !first.is_ascii_whitespace() {
+                break;
+            }
+            body = rest;
+        }
+        if body.is_empty() {
+            break;
+        }
+
+        match frame_len(body) {
+            Some(len) if len <= body.len() => {
+                let (frame, rest) = body.split_at(len);
+                frames.push(trim_newline(frame));
+                body = rest;
+            }
+            _ => {
+                frames.extend(
+                    body.split(|&byte| byte == b'\n')
+                        .map(trim_newline)
+                        .filter(|line| !line.is_empty()),
+                );
+                break;
+            }
+        }
+    }
+    frames
+}
+
+/// The length of the frame at the start of `body`, including its length prefix.
+fn frame_len(body: &[u8]) -> Option<usize> {
+    let digits = body.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    if digits == 0 || body.get(digits) != Some(&b' ') {
+        return None;
+    }
+    let len = std::str::from_utf8(&body[..digits])
+        .ok()?
+        .parse::<usize>()
+        .ok()?;
+    digits.checked_add(1)?.checked_add(len)
+}
+
+fn trim_newline(mut frame: &[u8]) -> &[u8] {
+    while let Some((last, rest)) = frame.split_last() {
+        if *last != b'\n' && *last != b'\r' {
+            break;
+        }
+        frame = rest;
+    }
+    frame
+}
+
 fn line_to_event(line: String) -> Event {
     let parts = line.splitn(8, ' ').collect::<Vec<&str>>();
 
@@ -262,6 +363,9 @@ mod tests {
                 query_parameters,
                 tls: None,
                 auth,
+                drain_tokens: vec!["drain-bar".to_owned()],
+                frame_id_key: "frame".to_owned(),
+                drain_token_key: super::default_drain_token_key(),
             }
             .build(
                 "default",
@@ -341,6 +445,65 @@ mod tests {
         assert_eq!(log[log_schema().source_type_key()], "heroku_logs".into());
         assert_eq!(log["appname"], "lumberjack-store".into());
         assert_eq!(log["absent"], Value::Null);
+        assert_eq!(log["frame"], "frame-foo".into());
+        assert_eq!(log["drain_token"], "drain-bar".into());
+    }
+
+    #[tokio::test]
+    async fn logplex_rejects_unknown_drain_token() {
+        trace_init();
+
+        let (_rx, addr) = source(None, vec![]).await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/events", addr))
+            .header("Logplex-Msg-Count", 1)
+            .header("Logplex-Frame-Id", "frame-foo")
+            .header("Logplex-Drain-Token", "drain-unknown")
+            .body("foo")
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16();
+        assert_eq!(status, 401);
+    }
+
+    #[test]
+    fn logplex_splits_multiline_frames() {
+        let body = "83 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - State changed from starting to up\n\
+                    72 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - first line\nsecond line\n";
+        let frames = super::split_frames(body.as_bytes());
+        assert_eq!(
+            frames,
+            vec![
+                &b"83 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - State changed from starting to up"[..],
+                &b"72 <40>1 2012-11-30T06:45:29+00:00 host app web.3 - first line\nsecond line"[..],
+            ]
+        );
+
+        let events = super::body_to_events(body.into());
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[1].as_log()[log_schema().message_key()],
+            "first line\nsecond line".into()
+        );
+    }
+
+    #[test]
+    fn logplex_splits_unframed_lines() {
+        let frames = super::split_frames(b"foo\r\nbar\n");
+        assert_eq!(frames, vec![&b"foo"[..], &b"bar"[..]]);
+    }
+
+    #[test]
+    fn logplex_splits_lines_of_overflowing_frames() {
+        let body = format!("{} foo\nbar\n", usize::MAX);
+        let frames = super::split_frames(body.as_bytes());
+        assert_eq!(
+            frames,
+            vec![format!("{} foo", usize::MAX).as_bytes(), &b"bar"[..]]
+        );
     }
 
     #[test]
@@ -14,10 +14,13 @@ use chrono::Utc;
 use codec::BytesDelimitedCodec;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+};
 
 use tokio_util::codec::Decoder;
-use warp::http::{HeaderMap, HeaderValue, StatusCode};
+use warp::http::{HeaderMap, HeaderValue, Method, StatusCode};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SimpleHttpConfig {
@@ -30,6 +33,47 @@ pub struct SimpleHttpConfig {
     query_parameters: Vec<String>,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    #[serde(default = "default_path")]
+    path: String,
+    #[serde(default = "crate::serde::default_true")]
+    strict_path: bool,
+    path_key: Option<String>,
+    #[serde(default = "default_methods")]
+    methods: Vec<HttpMethod>,
+}
+
+fn default_path() -> String {
+    "/".to_owned()
+}
+
+fn default_methods() -> Vec<HttpMethod> {
+    vec![HttpMethod::Post]
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    Head,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Options,
+}
+
+impl From<HttpMethod> for Method {
+    fn from(method: HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => Method::GET,
+            HttpMethod::Head => Method::HEAD,
+            HttpMethod::Post => Method::POST,
+            HttpMethod::Put => Method::PUT,
+            HttpMethod::Patch => Method::PATCH,
+            HttpMethod::Delete => Method::DELETE,
+            HttpMethod::Options => Method::OPTIONS,
+        }
+    }
 }
 
 inventory::submit! {
@@ -45,6 +89,10 @@ impl GenerateConfig for SimpleHttpConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            path: default_path(),
+            strict_path: true,
+            path_key: None,
+            methods: default_methods(),
         })
         .unwrap()
     }
@@ -55,6 +103,9 @@ struct SimpleHttpSource {
     encoding: Encoding,
     headers: Vec<String>,
     query_parameters: Vec<String>,
+    strict_path: bool,
+    path_key: Option<String>,
+    methods: Vec<Method>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative, Copy)]
@@ -65,6 +116,8 @@ pub enum Encoding {
     Text,
     Ndjson,
     Json,
+    /// The whole body is the message of a single event.
+    Binary,
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -73,7 +126,9 @@ impl HttpSource for SimpleHttpSource {
         body: Bytes,
         header_map: HeaderMap,
         query_parameters: HashMap<String, String>,
+        path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
+        let metadata = headers_metadata(&header_map);
         decode_body(body, self.encoding)
             .map(|events| add_headers(events, &self.headers, header_map))
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
@@ -81,11 +136,24 @@ impl HttpSource for SimpleHttpSource {
                 // Add source type
                 let key = log_schema().source_type_key();
                 for event in events.iter_mut() {
-                    event.as_mut_log().try_insert(key, Bytes::from("http"));
+                    let log = event.as_mut_log();
+                    log.try_insert(key, Bytes::from("http"));
+                    log.insert_metadata("http.headers", metadata.clone());
+                    if let Some(path_key) = &self.path_key {
+                        log.insert(path_key, path.to_owned());
+                    }
                 }
                 events
             })
     }
+
+    fn methods(&self) -> Vec<Method> {
+        self.methods.clone()
+    }
+
+    fn strict_path(&self) -> bool {
+        self.strict_path
+    }
 }
 
 #[async_trait::async_trait]
@@ -102,8 +170,18 @@ impl SourceConfig for SimpleHttpConfig {
             encoding: self.encoding,
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
+            strict_path: self.strict_path,
+            path_key: self.path_key.clone(),
+            methods: self.methods.iter().copied().map(Into::into).collect(),
         };
-        source.run(self.address, "", &self.tls, &self.auth, out, shutdown)
+        source.run(
+            self.address,
+            &self.path,
+            &self.tls,
+            &self.auth,
+            out,
+            shutdown,
+        )
     }
 
     fn output_type(&self) -> DataType {
@@ -119,6 +197,20 @@ impl SourceConfig for SimpleHttpConfig {
     }
 }
 
+/// All the headers of the request, by their lowercase name, for remap
+/// programs to read with `get_metadata_field("http.headers")`.
+fn headers_metadata(headers: &HeaderMap) -> BTreeMap<String, Value> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().to_owned(),
+                Value::from(Bytes::copy_from_slice(value.as_bytes())),
+            )
+        })
+        .collect()
+}
+
 fn add_headers(
     mut events: Vec<Event>,
     headers_config: &[String],
@@ -177,6 +269,8 @@ fn decode_body(body: Bytes, enc: Encoding) -> Result<Vec<Event>, ErrorMessage> {
                 .map_err(|error| json_error(format!("Error parsing Json: {:?}", error)))?;
             json_parse_array_of_object(parsed_json)
         }
+        Encoding::Binary if body.is_empty() => Ok(Vec::new()),
+        Encoding::Binary => Ok(vec![Event::from(body)]),
     }
 }
 
@@ -232,7 +326,7 @@ fn json_value_to_type_string(value: &JsonValue) -> &'static str {
 
 #[cfg(test)]
 mod tests {
-    use super::{Encoding, SimpleHttpConfig};
+    use super::{default_methods, default_path, Encoding, HttpMethod, SimpleHttpConfig};
 
     use crate::shutdown::ShutdownSignal;
     use crate::{
@@ -245,7 +339,7 @@ mod tests {
         write::{DeflateEncoder, GzEncoder},
         Compression,
     };
-    use http::HeaderMap;
+    use http::{HeaderMap, Method};
     use pretty_assertions::assert_eq;
     use std::collections::BTreeMap;
     use std::io::Write;
@@ -257,32 +351,48 @@ mod tests {
         crate::test_util::test_generate_config::<SimpleHttpConfig>();
     }
 
+    fn config(
+        encoding: Encoding,
+        headers: Vec<String>,
+        query_parameters: Vec<String>,
+    ) -> SimpleHttpConfig {
+        SimpleHttpConfig {
+            address: next_addr(),
+            encoding,
+            headers,
+            query_parameters,
+            tls: None,
+            auth: None,
+            path: default_path(),
+            strict_path: true,
+            path_key: None,
+            methods: default_methods(),
+        }
+    }
+
     async fn source(
         encoding: Encoding,
         headers: Vec<String>,
         query_parameters: Vec<String>,
     ) -> (mpsc::Receiver<Event>, SocketAddr) {
+        source_with_config(config(encoding, headers, query_parameters)).await
+    }
+
+    async fn source_with_config(config: SimpleHttpConfig) -> (mpsc::Receiver<Event>, SocketAddr) {
         let (sender, recv) = Pipeline::new_test();
-        let address = next_addr();
+        let address = config.address;
         tokio::spawn(async move {
-            SimpleHttpConfig {
-                address,
-                encoding,
-                headers,
-                query_parameters,
-                tls: None,
-                auth: None,
-            }
-            .build(
-                "default",
-                &GlobalOptions::default(),
-                ShutdownSignal::noop(),
-                sender,
-            )
-            .await
-            .unwrap()
-            .await
-            .unwrap();
+            config
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    sender,
+                )
+                .await
+                .unwrap()
+                .await
+                .unwrap();
         });
         wait_for_tcp(address).await;
         (recv, address)
@@ -322,6 +432,17 @@ mod tests {
             .as_u16()
     }
 
+    async fn send_to(address: SocketAddr, method: Method, path: &str, body: &str) -> u16 {
+        reqwest::Client::new()
+            .request(method, &format!("http://{}{}", address, path))
+            .body(body.to_owned())
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
     async fn send_bytes(address: SocketAddr, body: Vec<u8>, headers: HeaderMap) -> u16 {
         reqwest::Client::new()
             .post(&format!("http://{}/", address))
@@ -531,6 +652,14 @@ mod tests {
             assert_eq!(log["AbsentHeader"], Value::Null);
             assert!(log.get(log_schema().timestamp_key()).is_some());
             assert_eq!(log[log_schema().source_type_key()], "http".into());
+
+            match log.get_metadata("http.headers") {
+                Some(Value::Map(metadata)) => {
+                    assert_eq!(metadata["user-agent"], "test_client".into());
+                    assert_eq!(metadata["upgrade-insecure-requests"], "false".into());
+                }
+                metadata => panic!("unexpected headers metadata: {:?}", metadata),
+            }
         }
     }
 
@@ -596,4 +725,161 @@ mod tests {
             assert_eq!(log[log_schema().source_type_key()], "http".into());
         }
     }
+
+    #[tokio::test]
+    async fn http_zstd_snappy() {
+        trace_init();
+
+        let body = zstd::stream::encode_all("test body".as_bytes(), 0).unwrap();
+        let body = snap::raw::Encoder::new().compress_vec(&body).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Encoding", "zstd, snappy".parse().unwrap());
+
+        let (rx, addr) = source(Encoding::default(), vec![], vec![]).await;
+
+        assert_eq!(200, send_bytes(addr, body, headers).await);
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn http_x_gzip() {
+        trace_init();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"test body").unwrap();
+        let body = encoder.finish().unwrap();
+
+        // Codings are case-insensitive.
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Encoding", "X-GZIP".parse().unwrap());
+
+        let (rx, addr) = source(Encoding::default(), vec![], vec![]).await;
+
+        assert_eq!(200, send_bytes(addr, body, headers).await);
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn http_unsupported_encoding() {
+        trace_init();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Encoding", "br".parse().unwrap());
+
+        let (_rx, addr) = source(Encoding::default(), vec![], vec![]).await;
+
+        assert_eq!(
+            415,
+            send_bytes(addr, b"test body".to_vec(), headers.clone()).await
+        );
+
+        headers.insert("Content-Encoding", "gzip".parse().unwrap());
+        assert_eq!(422, send_bytes(addr, b"test body".to_vec(), headers).await);
+    }
+
+    #[tokio::test]
+    async fn http_binary() {
+        trace_init();
+
+        let (rx, addr) = source(Encoding::Binary, vec![], vec![]).await;
+
+        assert_eq!(200, send(addr, "test body\ntest body 2").await);
+
+        let mut events = collect_n(rx, 1).await;
+        {
+            let event = events.remove(0);
+            let log = event.as_log();
+            assert_eq!(
+                log[log_schema().message_key()],
+                "test body\ntest body 2".into()
+            );
+            assert!(log.get(log_schema().timestamp_key()).is_some());
+            assert_eq!(log[log_schema().source_type_key()], "http".into());
+        }
+    }
+
+    #[tokio::test]
+    async fn http_path() {
+        trace_init();
+
+        let mut config = config(Encoding::default(), vec![], vec![]);
+        config.path = "/event/path".to_owned();
+        config.path_key = Some("vector_http_path".to_owned());
+        let (rx, addr) = source_with_config(config).await;
+
+        assert_eq!(404, send_to(addr, Method::POST, "/", "test body").await);
+        assert_eq!(
+            404,
+            send_to(addr, Method::POST, "/event/path/sub", "test body").await
+        );
+        assert_eq!(
+            200,
+            send_to(addr, Method::POST, "/event/path", "test body").await
+        );
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "test body".into());
+        assert_eq!(log["vector_http_path"], "/event/path".into());
+    }
+
+    #[tokio::test]
+    async fn http_subpaths() {
+        trace_init();
+
+        let mut config = config(Encoding::default(), vec![], vec![]);
+        config.path = "/event".to_owned();
+        config.strict_path = false;
+        config.path_key = Some("vector_http_path".to_owned());
+        let (rx, addr) = source_with_config(config).await;
+
+        assert_eq!(
+            404,
+            send_to(addr, Method::POST, "/other", "test body").await
+        );
+        assert_eq!(
+            200,
+            send_to(addr, Method::POST, "/event/github/push", "test body").await
+        );
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()["vector_http_path"],
+            "/event/github/push".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn http_methods() {
+        trace_init();
+
+        let mut config = config(Encoding::default(), vec![], vec![]);
+        config.methods = vec![HttpMethod::Put, HttpMethod::Patch];
+        let (rx, addr) = source_with_config(config).await;
+
+        assert_eq!(405, send_to(addr, Method::POST, "/", "test body").await);
+        assert_eq!(200, send_to(addr, Method::PUT, "/", "test body").await);
+        assert_eq!(200, send_to(addr, Method::PATCH, "/", "test body 2").await);
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "test body".into()
+        );
+        assert_eq!(
+            events[1].as_log()[log_schema().message_key()],
+            "test body 2".into()
+        );
+    }
 }
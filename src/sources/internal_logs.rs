@@ -102,6 +102,32 @@ mod tests {
         check_events(logs, start);
     }
 
+    #[tokio::test]
+    async fn receives_component_metadata() {
+        trace::init(false, false, "debug");
+
+        let rx = start_source().await;
+        error_span!(
+            "sink",
+            component_kind = "sink",
+            component_name = "out",
+            component_type = "blackhole",
+        )
+        .in_scope(|| {
+            info_span!("request").in_scope(|| error!(message = "In a component."));
+        });
+        let logs = collect_output(rx).await;
+
+        let log = logs
+            .iter()
+            .map(Event::as_log)
+            .find(|log| log["message"] == "In a component.".into())
+            .expect("log isn't received");
+        assert_eq!(log["metadata.component_kind"], "sink".into());
+        assert_eq!(log["metadata.component_name"], "out".into());
+        assert_eq!(log["metadata.component_type"], "blackhole".into());
+    }
+
     async fn start_source() -> Receiver<Event> {
         let (tx, rx) = Pipeline::new_test();
 
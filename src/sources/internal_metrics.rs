@@ -1,5 +1,6 @@
 use crate::{
     config::{DataType, GlobalOptions, SourceConfig, SourceDescription},
+    event::Event,
     metrics::Controller,
     metrics::{capture_metrics, get_controller},
     shutdown::ShutdownSignal,
@@ -9,12 +10,46 @@ use futures::{stream, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::time;
 
+/// Environment variable the pod name is read from, as set through the downward API by the
+/// Kubernetes manifests and Helm charts.
+const SELF_POD_NAME_ENV_KEY: &str = "VECTOR_SELF_POD_NAME";
+
 #[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
 #[derivative(Default)]
 #[serde(deny_unknown_fields, default)]
 pub struct InternalMetricsConfig {
     #[derivative(Default(value = "2"))]
     scrape_interval_secs: u64,
+    tags: TagsConfig,
+}
+
+/// Tags added to every internal metric, so the metrics of many Vector instances can be told
+/// apart. Each tag is only added if its key is set.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct TagsConfig {
+    host_key: Option<String>,
+    pod_name_key: Option<String>,
+    version_key: Option<String>,
+}
+
+impl TagsConfig {
+    /// Returns the configured tags, by key. Tags whose value isn't known, like the pod name
+    /// outside of Kubernetes, are left out.
+    fn resolve(&self) -> Vec<(String, String)> {
+        let host = || crate::get_hostname().ok();
+        let pod_name = || std::env::var(SELF_POD_NAME_ENV_KEY).ok();
+        let version = || Some(crate::vector_version().to_string());
+
+        vec![
+            (&self.host_key, host()),
+            (&self.pod_name_key, pod_name()),
+            (&self.version_key, version()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.clone()?, value?)))
+        .collect()
+    }
 }
 
 inventory::submit! {
@@ -39,7 +74,14 @@ impl SourceConfig for InternalMetricsConfig {
             );
         }
         let interval = time::Duration::from_secs(self.scrape_interval_secs);
-        Ok(Box::pin(run(get_controller()?, interval, out, shutdown)))
+        let tags = self.tags.resolve();
+        Ok(Box::pin(run(
+            get_controller()?,
+            interval,
+            tags,
+            out,
+            shutdown,
+        )))
     }
 
     fn output_type(&self) -> DataType {
@@ -54,6 +96,7 @@ impl SourceConfig for InternalMetricsConfig {
 async fn run(
     controller: &Controller,
     interval: time::Duration,
+    tags: Vec<(String, String)>,
     out: Pipeline,
     shutdown: ShutdownSignal,
 ) -> Result<(), ()> {
@@ -62,16 +105,32 @@ async fn run(
 
     let mut interval = time::interval(interval).take_until(shutdown);
     while interval.next().await.is_some() {
-        let metrics = capture_metrics(controller);
+        let metrics = capture_metrics(controller).map(|event| add_tags(event, &tags));
         out.send_all(&mut stream::iter(metrics).map(Ok)).await?;
     }
 
     Ok(())
 }
 
+/// Adds `tags` to the metric, keeping the values of the tags it already has.
+fn add_tags(mut event: Event, tags: &[(String, String)]) -> Event {
+    if !tags.is_empty() {
+        let metric_tags = event
+            .as_mut_metric()
+            .tags_mut()
+            .get_or_insert_with(Default::default);
+        for (key, value) in tags {
+            metric_tags
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+    event
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::event::metric::{Metric, MetricValue, StatisticKind};
+    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
     use crate::metrics::{capture_metrics, get_controller};
     use metrics::{counter, gauge, histogram};
     use std::collections::BTreeMap;
@@ -81,6 +140,48 @@ mod tests {
         crate::test_util::test_generate_config::<super::InternalMetricsConfig>();
     }
 
+    #[test]
+    fn resolves_tags() {
+        let config: super::InternalMetricsConfig = toml::from_str(
+            r#"
+            tags.host_key = "host"
+            tags.version_key = "version"
+            "#,
+        )
+        .unwrap();
+
+        let tags = config
+            .tags
+            .resolve()
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(tags.get("host"), crate::get_hostname().ok().as_ref());
+        assert_eq!(tags["version"], crate::vector_version().to_string());
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn adds_tags() {
+        let mut existing = BTreeMap::new();
+        existing.insert("host".to_owned(), "original".to_owned());
+        let metric = Metric::new(
+            "foo".into(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_tags(Some(existing));
+
+        let tags = vec![
+            ("host".to_owned(), "vector-0".to_owned()),
+            ("version".to_owned(), "1.0.0".to_owned()),
+        ];
+        let event = super::add_tags(metric.into(), &tags);
+
+        let tags = event.as_metric().tags().unwrap();
+        assert_eq!(tags["host"], "original");
+        assert_eq!(tags["version"], "1.0.0");
+    }
+
     #[test]
     fn captures_internal_metrics() {
         let _ = crate::metrics::init();
@@ -1,22 +1,31 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
-    event::{Event, Value},
-    internal_events::{KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed},
-    kafka::KafkaAuthConfig,
+    event::{
+        finalization::{BatchNotifier, BatchStatus, BatchStatusReceiver},
+        Event, Value,
+    },
+    internal_events::{
+        KafkaEventFailed, KafkaEventNotDelivered, KafkaEventReceived, KafkaOffsetUpdateFailed,
+    },
+    kafka::{KafkaAuthConfig, KafkaStatisticsContext, KafkaTokenRefresh, STATISTICS_INTERVAL_MS},
     shutdown::ShutdownSignal,
     Pipeline,
 };
 use bytes::Bytes;
 use chrono::{TimeZone, Utc};
-use futures::{SinkExt, StreamExt};
+use futures::{channel::mpsc, stream::FuturesUnordered, SinkExt, StreamExt};
 use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
-    message::Message,
+    message::{Headers, Message},
+    Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -46,9 +55,12 @@ pub struct KafkaSourceConfig {
     topic_key: Option<String>,
     partition_key: Option<String>,
     offset_key: Option<String>,
+    headers_key: Option<String>,
     librdkafka_options: Option<HashMap<String, String>>,
     #[serde(flatten)]
     auth: KafkaAuthConfig,
+    #[serde(default)]
+    acknowledgements: bool,
 }
 
 fn default_session_timeout_ms() -> u64 {
@@ -108,10 +120,23 @@ fn kafka_source(
     let topic_key = config.topic_key.clone();
     let partition_key = config.partition_key.clone();
     let offset_key = config.offset_key.clone();
-    let consumer = Arc::new(create_consumer(config)?);
+    let headers_key = config.headers_key.clone();
+    let context = KafkaStatisticsContext::default();
+    let consumer = Arc::new(create_consumer(config, context.clone())?);
+    let token_provider = config.auth.token_provider()?;
+    let acknowledgements = config.acknowledgements;
 
     Ok(Box::pin(async move {
-        Arc::clone(&consumer)
+        context.use_current_span();
+        let _token_refresh = token_provider
+            .map(|provider| KafkaTokenRefresh::spawn(provider, Arc::clone(&consumer)));
+
+        // The offsets of messages waiting for their events to be delivered, in
+        // the order the messages were read.
+        let (pending_acks, acks) = mpsc::unbounded();
+        let acked_consumer = Arc::clone(&consumer);
+
+        let messages = Arc::clone(&consumer)
             .start()
             .take_until(shutdown.clone())
             .then(move |message| {
@@ -119,6 +144,7 @@ fn kafka_source(
                 let topic_key = topic_key.clone();
                 let partition_key = partition_key.clone();
                 let offset_key = offset_key.clone();
+                let headers_key = headers_key.clone();
                 let consumer = Arc::clone(&consumer);
 
                 async move {
@@ -179,11 +205,33 @@ fn kafka_source(
                                 log.insert(offset_key, Value::from(msg.offset()));
                             }
 
+                            log.insert_metadata("kafka.topic", msg.topic().to_owned());
+                            log.insert_metadata("kafka.partition", msg.partition());
+                            log.insert_metadata("kafka.offset", msg.offset());
+                            let headers = msg.headers().map(headers_map).unwrap_or_default();
+                            if let Some(headers_key) = &headers_key {
+                                log.insert(headers_key, headers.clone());
+                            }
+                            log.insert_metadata("kafka.headers", headers);
+
+                            if acknowledgements {
+                                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                                let event = event.with_batch_notifier(&batch);
+                                let ack = PendingAck {
+                                    receiver,
+                                    topic: msg.topic().to_owned(),
+                                    partition: msg.partition(),
+                                    offset: msg.offset(),
+                                };
+
+                                return Ok((event, Some(ack)));
+                            }
+
                             consumer.store_offset(&msg).map_err(|error| {
                                 emit!(KafkaOffsetUpdateFailed { error });
                             })?;
 
-                            Ok(event)
+                            Ok((event, None))
                         }
                     }
                 }
@@ -194,22 +242,143 @@ fn kafka_source(
             //     out.sink_compat()
             //         .sink_map_err(|error| error!(message = "Error sending to sink.", %error)),
             // )
-            .for_each(|item| {
+            .for_each(move |item| {
                 let mut out = out.clone();
+                let pending_acks = pending_acks.clone();
                 async move {
-                    if let Ok(item) = item {
-                        if let Err(error) = out.send(item).await {
+                    if let Ok((event, ack)) = item {
+                        if let Err(error) = out.send(event).await {
                             error!(message = "Error sending to sink.", %error);
                         }
+                        if let Some(ack) = ack {
+                            // The receiving end lives as long as the messages.
+                            let _ = pending_acks.unbounded_send(ack);
+                        }
                     }
                 }
-            })
-            .await;
+            });
+
+        // The sender is dropped along with the stream of messages once it
+        // ends, so the offsets of all messages read are stored before the
+        // source stops.
+        futures::join!(messages, store_acked_offsets(&acked_consumer, acks));
+
         Ok(())
     }))
 }
 
-fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer> {
+/// Returns the headers of a message by name, with the value of the last header if a name is
+/// repeated.
+fn headers_map<H: Headers>(headers: &H) -> BTreeMap<String, Value> {
+    (0..headers.count())
+        .filter_map(|index| headers.get(index))
+        .map(|(name, value)| (name.to_owned(), Value::from(Bytes::from(value.to_owned()))))
+        .collect()
+}
+
+struct PendingAck {
+    receiver: BatchStatusReceiver,
+    topic: String,
+    partition: i32,
+    offset: i64,
+}
+
+/// Stores the offsets of messages once their events are delivered. The events
+/// of messages may be delivered in any order, but the offset of a partition
+/// only moves past messages whose events were all delivered. Once the events
+/// of a message aren't, no more offsets of its partition are stored, so the
+/// message is read again when the consumer restarts.
+async fn store_acked_offsets(
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    mut acks: mpsc::UnboundedReceiver<PendingAck>,
+) {
+    let mut partitions = HashMap::<(String, i32), PartitionAcks>::new();
+    let mut statuses = FuturesUnordered::new();
+    let mut reading = true;
+
+    while reading || !statuses.is_empty() {
+        tokio::select! {
+            ack = acks.next(), if reading => match ack {
+                Some(PendingAck { receiver, topic, partition, offset }) => {
+                    let key = (topic, partition);
+                    let partition_acks = partitions.entry(key.clone()).or_default();
+                    // Nothing past a message that wasn't delivered is stored.
+                    if !partition_acks.blocked {
+                        partition_acks.pending.insert(offset, None);
+                        statuses.push(async move {
+                            // Notifiers always report a status before being
+                            // dropped.
+                            let status = receiver.await.unwrap_or(BatchStatus::Errored);
+                            (key, offset, status)
+                        });
+                    }
+                }
+                None => reading = false,
+            },
+            Some(((topic, partition), offset, status)) = statuses.next() => {
+                if status != BatchStatus::Delivered {
+                    emit!(KafkaEventNotDelivered {
+                        topic: &topic,
+                        partition,
+                        offset,
+                        status,
+                    });
+                }
+
+                let stored = partitions
+                    .get_mut(&(topic.clone(), partition))
+                    .and_then(|acks| acks.update(offset, status));
+                if let Some(offset) = stored {
+                    let mut offsets = TopicPartitionList::new();
+                    let stored = offsets
+                        .add_partition_offset(&topic, partition, Offset::Offset(offset))
+                        .and_then(|()| consumer.store_offsets(&offsets));
+                    if let Err(error) = stored {
+                        emit!(KafkaOffsetUpdateFailed { error });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The statuses of the messages of a partition whose offsets aren't stored yet.
+#[derive(Default)]
+struct PartitionAcks {
+    /// The status of each message, by offset, once received.
+    pending: BTreeMap<i64, Option<BatchStatus>>,
+    /// Whether the events of a message weren't delivered, which holds the
+    /// offset of the partition back for good.
+    blocked: bool,
+}
+
+impl PartitionAcks {
+    /// Records the status of the message at `offset`, and returns the offset
+    /// to store if the messages delivered before any pending one moved it.
+    fn update(&mut self, offset: i64, status: BatchStatus) -> Option<i64> {
+        if self.blocked {
+            return None;
+        }
+        self.pending.insert(offset, Some(status));
+
+        let mut stored = None;
+        while let Some((&offset, &Some(status))) = self.pending.iter().next() {
+            if status != BatchStatus::Delivered {
+                self.blocked = true;
+                self.pending.clear();
+                break;
+            }
+            self.pending.remove(&offset);
+            stored = Some(offset + 1);
+        }
+        stored
+    }
+}
+
+fn create_consumer(
+    config: &KafkaSourceConfig,
+    context: KafkaStatisticsContext,
+) -> crate::Result<StreamConsumer<KafkaStatisticsContext>> {
     let mut client_config = ClientConfig::new();
     client_config
         .set("group.id", &config.group_id)
@@ -225,6 +394,7 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
             &config.commit_interval_ms.to_string(),
         )
         .set("enable.auto.offset.store", "false")
+        .set("statistics.interval.ms", STATISTICS_INTERVAL_MS)
         .set("client.id", "vector");
 
     config.auth.apply(&mut client_config)?;
@@ -235,7 +405,9 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
         }
     }
 
-    let consumer: StreamConsumer = client_config.create().context(KafkaCreateError)?;
+    let consumer: StreamConsumer<_> = client_config
+        .create_with_context(context)
+        .context(KafkaCreateError)?;
     let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
     consumer.subscribe(&topics).context(KafkaSubscribeError)?;
 
@@ -244,8 +416,9 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer>
 
 #[cfg(test)]
 mod test {
-    use super::{kafka_source, KafkaSourceConfig};
-    use crate::{shutdown::ShutdownSignal, Pipeline};
+    use super::{headers_map, kafka_source, KafkaSourceConfig, PartitionAcks};
+    use crate::{event::finalization::BatchStatus, shutdown::ShutdownSignal, Pipeline};
+    use rdkafka::message::OwnedHeaders;
 
     #[test]
     fn generate_config() {
@@ -276,6 +449,47 @@ mod test {
         assert!(kafka_source(&config, ShutdownSignal::noop(), Pipeline::new_test().0).is_ok());
     }
 
+    #[test]
+    fn headers_by_name() {
+        let headers = OwnedHeaders::new()
+            .add("route", "payments")
+            .add("trace", "abc")
+            .add("route", "billing");
+
+        let headers = headers_map(&headers);
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers["route"], "billing".into());
+        assert_eq!(headers["trace"], "abc".into());
+    }
+
+    #[test]
+    fn offsets_move_past_delivered_messages_in_order() {
+        let mut acks = PartitionAcks::default();
+        for offset in 0..4 {
+            acks.pending.insert(offset, None);
+        }
+
+        assert_eq!(acks.update(1, BatchStatus::Delivered), None);
+        assert_eq!(acks.update(0, BatchStatus::Delivered), Some(2));
+        assert_eq!(acks.update(3, BatchStatus::Delivered), None);
+        assert_eq!(acks.update(2, BatchStatus::Delivered), Some(4));
+    }
+
+    #[test]
+    fn offsets_dont_move_past_failed_messages() {
+        let mut acks = PartitionAcks::default();
+        for offset in 0..3 {
+            acks.pending.insert(offset, None);
+        }
+
+        assert_eq!(acks.update(1, BatchStatus::Errored), None);
+        assert_eq!(acks.update(2, BatchStatus::Delivered), None);
+        // The messages before the failed one are still stored.
+        assert_eq!(acks.update(0, BatchStatus::Delivered), Some(1));
+        assert!(acks.blocked);
+        assert_eq!(acks.update(3, BatchStatus::Delivered), None);
+    }
+
     #[test]
     fn kafka_source_create_incorrect_auto_offset_reset() {
         let config = KafkaSourceConfig {
@@ -298,6 +512,7 @@ mod integration_test {
     use chrono::{SubsecRound, Utc};
     use rdkafka::{
         config::ClientConfig,
+        message::OwnedHeaders,
         producer::{FutureProducer, FutureRecord},
         util::Timeout,
     };
@@ -315,7 +530,8 @@ mod integration_test {
         let record = FutureRecord::to(&topic)
             .payload(text)
             .key(key)
-            .timestamp(timestamp);
+            .timestamp(timestamp)
+            .headers(OwnedHeaders::new().add("route", "payments"));
 
         if let Err(error) = producer.send(record, Timeout::Never).await {
             panic!("Cannot send event to Kafka: {:?}", error);
@@ -340,6 +556,7 @@ mod integration_test {
             topic_key: Some("topic".to_string()),
             partition_key: Some("partition".to_string()),
             offset_key: Some("offset".to_string()),
+            headers_key: Some("headers".to_string()),
             socket_timeout_ms: 60000,
             fetch_wait_max_ms: 100,
             ..Default::default()
@@ -372,8 +589,14 @@ mod integration_test {
             events[0].as_log()[log_schema().timestamp_key()],
             now.trunc_subsecs(3).into()
         );
-        assert_eq!(events[0].as_log()["topic"], topic.into());
+        assert_eq!(events[0].as_log()["topic"], topic.clone().into());
         assert!(events[0].as_log().contains("partition"));
         assert!(events[0].as_log().contains("offset"));
+        assert_eq!(events[0].as_log()["headers.route"], "payments".into());
+        assert_eq!(
+            events[0].as_log().get_metadata("kafka.topic"),
+            Some(&topic.into())
+        );
+        assert!(events[0].as_log().get_metadata("kafka.offset").is_some());
     }
 }
@@ -0,0 +1,732 @@
+use super::util::{make_listener, SocketListenAddr};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{Event, Value},
+    internal_events::{
+        ConnectionOpen, LogstashConnectionError, LogstashEventReceived, OpenGauge,
+        TcpSocketConnectionError,
+    },
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use flate2::read::ZlibDecoder;
+use futures::{future::ready, SinkExt, StreamExt};
+use listenfd::ListenFd;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryInto,
+    io::{self, Read},
+};
+use tokio::{io::AsyncWriteExt, net::TcpStream};
+use tokio_util::codec::{Decoder, FramedRead};
+use tracing_futures::Instrument;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LogstashConfig {
+    address: SocketListenAddr,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsConfig>,
+    receive_buffer_bytes: Option<usize>,
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+}
+
+fn default_max_length() -> usize {
+    16 * 1024 * 1024
+}
+
+inventory::submit! {
+    SourceDescription::new::<LogstashConfig>("logstash")
+}
+
+impl GenerateConfig for LogstashConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:5044".parse().unwrap()),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            max_length: default_max_length(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "logstash")]
+impl SourceConfig for LogstashConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let keepalive = self.keepalive;
+        let receive_buffer_bytes = self.receive_buffer_bytes;
+        let max_length = self.max_length;
+        let address = self.address;
+
+        Ok(Box::pin(async move {
+            let listener = make_listener(address, ListenFd::from_env(), &tls)
+                .await
+                .ok_or(())?;
+
+            info!(
+                message = "Listening.",
+                addr = %listener
+                    .local_addr()
+                    .map(SocketListenAddr::SocketAddr)
+                    .unwrap_or(address)
+            );
+
+            let connection_gauge = OpenGauge::new();
+
+            listener
+                .accept_stream()
+                .take_until(shutdown.clone())
+                .for_each(|connection| {
+                    let socket = match connection {
+                        Ok(socket) => socket,
+                        Err(error) => {
+                            error!(message = "Failed to accept socket.", %error);
+                            return ready(());
+                        }
+                    };
+
+                    let peer_addr = socket.peer_addr().ip().to_string();
+                    let span = info_span!("connection", %peer_addr);
+                    debug!(message = "Accepted a new connection.", %peer_addr);
+
+                    let open_token = connection_gauge.open(|count| emit!(ConnectionOpen { count }));
+                    let connection = handle_connection(
+                        socket,
+                        peer_addr,
+                        keepalive,
+                        receive_buffer_bytes,
+                        max_length,
+                        out.clone(),
+                        shutdown.clone(),
+                    );
+
+                    tokio::spawn(
+                        async move {
+                            if let Err(error) = connection.await {
+                                emit!(LogstashConnectionError { error });
+                            }
+                            debug!("Connection closed.");
+                            drop(open_token);
+                        }
+                        .instrument(span),
+                    );
+                    ready(())
+                })
+                .await;
+
+            Ok(())
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "logstash"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.into()]
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub(crate) enum LogstashError {
+    #[snafu(display("Connection failed: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Unsupported protocol version: {}", version))]
+    UnsupportedVersion { version: u8 },
+    #[snafu(display("Unknown frame type: {}", frame_type))]
+    UnknownFrameType { frame_type: u8 },
+    #[snafu(display("Failed to decompress frames: {}", source))]
+    Decompress { source: io::Error },
+    #[snafu(display("Invalid JSON frame: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("Frame longer than max_length of {} bytes", max_length))]
+    TooLong { max_length: usize },
+    #[snafu(display("Invalid frame: {}", reason))]
+    InvalidFrame { reason: &'static str },
+    #[snafu(display("Pipeline is closed"))]
+    PipelineClosed,
+}
+
+impl From<io::Error> for LogstashError {
+    fn from(source: io::Error) -> Self {
+        LogstashError::Io { source }
+    }
+}
+
+/// Receives the frames of a Beats client, acknowledging each window of
+/// events once all of them have been sent to the pipeline.
+async fn handle_connection(
+    mut socket: MaybeTlsIncomingStream<TcpStream>,
+    host: String,
+    keepalive: Option<TcpKeepaliveConfig>,
+    receive_buffer_bytes: Option<usize>,
+    max_length: usize,
+    mut out: Pipeline,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), LogstashError> {
+    tokio::select! {
+        result = socket.handshake() => {
+            if let Err(error) = result {
+                emit!(TcpSocketConnectionError { error });
+                return Ok(());
+            }
+        },
+        _ = &mut shutdown => return Ok(()),
+    };
+
+    if let Some(keepalive) = keepalive {
+        if let Err(error) = socket.set_keepalive(keepalive) {
+            warn!(message = "Failed configuring TCP keepalive.", %error);
+        }
+    }
+
+    if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+        if let Err(error) = socket.set_receive_buffer_bytes(receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on TCP socket.", %error);
+        }
+    }
+
+    let mut frames = FramedRead::new(socket, LogstashDecoder::new(max_length));
+    let mut window_size = 0;
+    let mut unacknowledged = 0;
+    loop {
+        let (frame, byte_size) = tokio::select! {
+            frame = frames.next() => match frame {
+                Some(frame) => frame?,
+                None => return Ok(()),
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        match frame {
+            LogstashFrame::Window(size) => {
+                window_size = size;
+                unacknowledged = 0;
+            }
+            LogstashFrame::Event { sequence, fields } => {
+                let event = build_event(fields, &host, byte_size);
+                out.send(event)
+                    .await
+                    .map_err(|_| LogstashError::PipelineClosed)?;
+
+                unacknowledged += 1;
+                if unacknowledged >= window_size {
+                    frames.get_mut().write_all(&ack(sequence)).await?;
+                    unacknowledged = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Acknowledges all the events up to the given sequence number.
+fn ack(sequence: u32) -> Vec<u8> {
+    let mut ack = vec![PROTOCOL_VERSION, b'A'];
+    ack.extend_from_slice(&sequence.to_be_bytes());
+    ack
+}
+
+fn build_event(fields: BTreeMap<String, Value>, host: &str, byte_size: usize) -> Event {
+    let mut event = Event::from(fields);
+    let log = event.as_mut_log();
+
+    // Beats time their events with an `@timestamp` field.
+    let timestamp = log
+        .get_flat("@timestamp")
+        .and_then(|timestamp| match timestamp {
+            Value::Bytes(timestamp) => std::str::from_utf8(timestamp).ok(),
+            _ => None,
+        })
+        .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.try_insert(log_schema().host_key(), host.to_owned());
+    log.try_insert(log_schema().source_type_key(), Bytes::from("logstash"));
+
+    emit!(LogstashEventReceived { byte_size });
+
+    event
+}
+
+const PROTOCOL_VERSION: u8 = b'2';
+
+#[derive(Debug, PartialEq)]
+enum LogstashFrame {
+    /// The number of events the client sends before waiting for an ack.
+    Window(u32),
+    Event {
+        sequence: u32,
+        fields: BTreeMap<String, Value>,
+    },
+}
+
+/// Frames as they are read from the wire, before their payload is decoded.
+enum RawFrame<'a> {
+    Window(u32),
+    Data {
+        sequence: u32,
+        pairs: Vec<(&'a [u8], &'a [u8])>,
+    },
+    Json {
+        sequence: u32,
+        payload: &'a [u8],
+    },
+    Compressed(&'a [u8]),
+}
+
+struct FrameReader<'a> {
+    src: &'a [u8],
+    position: usize,
+    max_length: usize,
+    too_long: bool,
+}
+
+impl<'a> FrameReader<'a> {
+    fn new(src: &'a [u8], max_length: usize) -> Self {
+        Self {
+            src,
+            position: 0,
+            max_length,
+            too_long: false,
+        }
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        let byte = *self.src.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.src.get(self.position..self.position + 4)?;
+        self.position += 4;
+        Some(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Reads a payload prefixed with its length, giving up on payloads that
+    /// would make the frame longer than `max_length`.
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        let end = self.position.saturating_add(len);
+        if end > self.max_length {
+            self.too_long = true;
+            return None;
+        }
+        let bytes = self.src.get(self.position..end)?;
+        self.position = end;
+        Some(bytes)
+    }
+
+    /// Reads the next frame, or `None` if it's incomplete.
+    fn frame(&mut self) -> Option<Result<RawFrame<'a>, LogstashError>> {
+        match self.read_frame() {
+            None if self.too_long => Some(Err(LogstashError::TooLong {
+                max_length: self.max_length,
+            })),
+            frame => frame,
+        }
+    }
+
+    fn read_frame(&mut self) -> Option<Result<RawFrame<'a>, LogstashError>> {
+        let version = self.u8()?;
+        if version != PROTOCOL_VERSION {
+            return Some(Err(LogstashError::UnsupportedVersion { version }));
+        }
+
+        let frame = match self.u8()? {
+            b'W' => RawFrame::Window(self.u32()?),
+            b'D' => {
+                let sequence = self.u32()?;
+                let count = self.u32()?;
+                let mut pairs = Vec::new();
+                for _ in 0..count {
+                    pairs.push((self.bytes()?, self.bytes()?));
+                }
+                RawFrame::Data { sequence, pairs }
+            }
+            b'J' => RawFrame::Json {
+                sequence: self.u32()?,
+                payload: self.bytes()?,
+            },
+            b'C' => RawFrame::Compressed(self.bytes()?),
+            frame_type => return Some(Err(LogstashError::UnknownFrameType { frame_type })),
+        };
+        Some(Ok(frame))
+    }
+}
+
+/// Decodes the frames of the Lumberjack v2 protocol, unpacking the frames
+/// of compressed frames.
+struct LogstashDecoder {
+    max_length: usize,
+    pending: VecDeque<(LogstashFrame, usize)>,
+}
+
+impl LogstashDecoder {
+    fn new(max_length: usize) -> Self {
+        Self {
+            max_length,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl Decoder for LogstashDecoder {
+    type Item = (LogstashFrame, usize);
+    type Error = LogstashError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                return Ok(Some(frame));
+            }
+
+            let mut reader = FrameReader::new(&src[..], self.max_length);
+            let frame = match reader.frame() {
+                Some(frame) => frame?,
+                None => return Ok(None),
+            };
+            let byte_size = reader.position;
+
+            let frame = match frame {
+                RawFrame::Compressed(payload) => {
+                    let frames = decompress(payload, self.max_length)?;
+                    decode_frames(&frames, self.max_length, &mut self.pending)?;
+                    None
+                }
+                frame => Some(decode_frame(frame)?),
+            };
+            src.advance(byte_size);
+
+            if let Some(frame) = frame {
+                return Ok(Some((frame, byte_size)));
+            }
+        }
+    }
+}
+
+/// Decompresses the frames of a compressed frame, of which the decompressed
+/// size must not exceed `max_length`.
+fn decompress(payload: &[u8], max_length: usize) -> Result<Vec<u8>, LogstashError> {
+    let mut decompressed = Vec::new();
+    ZlibDecoder::new(payload)
+        .take(max_length as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .context(Decompress)?;
+    if decompressed.len() > max_length {
+        return Err(LogstashError::TooLong { max_length });
+    }
+    Ok(decompressed)
+}
+
+/// Decodes the frames packed in a compressed frame, which must all be whole
+/// and must not be compressed frames themselves.
+fn decode_frames(
+    mut src: &[u8],
+    max_length: usize,
+    frames: &mut VecDeque<(LogstashFrame, usize)>,
+) -> Result<(), LogstashError> {
+    while !src.is_empty() {
+        let mut reader = FrameReader::new(src, max_length);
+        let frame = reader.frame().ok_or(LogstashError::InvalidFrame {
+            reason: "truncated compressed frame",
+        })??;
+        let byte_size = reader.position;
+
+        match frame {
+            RawFrame::Compressed(_) => {
+                return Err(LogstashError::InvalidFrame {
+                    reason: "nested compressed frame",
+                })
+            }
+            frame => frames.push_back((decode_frame(frame)?, byte_size)),
+        }
+        src = &src[byte_size..];
+    }
+    Ok(())
+}
+
+fn decode_frame(frame: RawFrame<'_>) -> Result<LogstashFrame, LogstashError> {
+    match frame {
+        RawFrame::Window(size) => Ok(LogstashFrame::Window(size)),
+        RawFrame::Data { sequence, pairs } => {
+            let fields = pairs
+                .into_iter()
+                .map(|(key, value)| {
+                    (
+                        String::from_utf8_lossy(key).into_owned(),
+                        Value::Bytes(Bytes::copy_from_slice(value)),
+                    )
+                })
+                .collect();
+            Ok(LogstashFrame::Event { sequence, fields })
+        }
+        RawFrame::Json { sequence, payload } => {
+            match serde_json::from_slice(payload).context(InvalidJson)? {
+                serde_json::Value::Object(fields) => Ok(LogstashFrame::Event {
+                    sequence,
+                    fields: fields
+                        .into_iter()
+                        .map(|(key, value)| (key, value.into()))
+                        .collect(),
+                }),
+                _ => Err(LogstashError::InvalidFrame {
+                    reason: "JSON frame isn't an object",
+                }),
+            }
+        }
+        RawFrame::Compressed(_) => unreachable!("Compressed frames are unpacked by the decoder."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_n, next_addr, trace_init, wait_for_tcp};
+    use chrono::TimeZone;
+    use flate2::{write::ZlibEncoder, Compression};
+    use serde_json::json;
+    use std::io::Write;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogstashConfig>();
+    }
+
+    fn push_bytes(frame: &mut Vec<u8>, bytes: &[u8]) {
+        frame.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        frame.extend_from_slice(bytes);
+    }
+
+    fn window_frame(size: u32) -> Vec<u8> {
+        let mut frame = vec![PROTOCOL_VERSION, b'W'];
+        frame.extend_from_slice(&size.to_be_bytes());
+        frame
+    }
+
+    fn json_frame(sequence: u32, value: serde_json::Value) -> Vec<u8> {
+        let mut frame = vec![PROTOCOL_VERSION, b'J'];
+        frame.extend_from_slice(&sequence.to_be_bytes());
+        push_bytes(&mut frame, &serde_json::to_vec(&value).unwrap());
+        frame
+    }
+
+    fn data_frame(sequence: u32, pairs: &[(&str, &str)]) -> Vec<u8> {
+        let mut frame = vec![PROTOCOL_VERSION, b'D'];
+        frame.extend_from_slice(&sequence.to_be_bytes());
+        frame.extend_from_slice(&(pairs.len() as u32).to_be_bytes());
+        for (key, value) in pairs {
+            push_bytes(&mut frame, key.as_bytes());
+            push_bytes(&mut frame, value.as_bytes());
+        }
+        frame
+    }
+
+    fn compressed_frame(frames: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(frames).unwrap();
+        let mut frame = vec![PROTOCOL_VERSION, b'C'];
+        push_bytes(&mut frame, &encoder.finish().unwrap());
+        frame
+    }
+
+    fn decode_all(src: &[u8]) -> Vec<LogstashFrame> {
+        let mut decoder = LogstashDecoder::new(default_max_length());
+        let mut src = BytesMut::from(src);
+        let mut frames = Vec::new();
+        while let Some((frame, _)) = decoder.decode(&mut src).unwrap() {
+            frames.push(frame);
+        }
+        assert!(src.is_empty());
+        frames
+    }
+
+    fn event_frame(sequence: u32, fields: Vec<(&str, Value)>) -> LogstashFrame {
+        LogstashFrame::Event {
+            sequence,
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn decodes_frames() {
+        let mut src = window_frame(2);
+        src.extend(json_frame(1, json!({"message": "one", "count": 1})));
+        src.extend(data_frame(2, &[("message", "two"), ("line", "2")]));
+
+        assert_eq!(
+            decode_all(&src),
+            vec![
+                LogstashFrame::Window(2),
+                event_frame(1, vec![("message", "one".into()), ("count", 1.into())]),
+                event_frame(2, vec![("message", "two".into()), ("line", "2".into())]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_compressed_frames() {
+        let mut frames = json_frame(1, json!({"message": "one"}));
+        frames.extend(data_frame(2, &[("message", "two")]));
+        let mut src = window_frame(2);
+        src.extend(compressed_frame(&frames));
+
+        assert_eq!(
+            decode_all(&src),
+            vec![
+                LogstashFrame::Window(2),
+                event_frame(1, vec![("message", "one".into())]),
+                event_frame(2, vec![("message", "two".into())]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decoder_waits_for_whole_frames() {
+        let frame = json_frame(1, json!({"message": "one"}));
+        let mut decoder = LogstashDecoder::new(default_max_length());
+
+        let mut src = BytesMut::from(&frame[..frame.len() - 1]);
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+        src.extend_from_slice(&frame[frame.len() - 1..]);
+        let (_, byte_size) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(byte_size, frame.len());
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_frames() {
+        for src in vec![
+            vec![b'1', b'W', 0, 0, 0, 1],
+            vec![PROTOCOL_VERSION, b'X'],
+            json_frame(1, json!(["message"])),
+            {
+                let frame = json_frame(1, json!({"message": "one"}));
+                compressed_frame(&frame[..frame.len() - 1])
+            },
+            compressed_frame(&compressed_frame(&window_frame(1))),
+        ] {
+            assert!(LogstashDecoder::new(default_max_length())
+                .decode(&mut BytesMut::from(&src[..]))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_long_frames() {
+        let frame = json_frame(1, json!({ "message": "x".repeat(100) }));
+
+        // The length of the payload is enough to tell it's too long.
+        let mut src = BytesMut::from(&frame[..20]);
+        assert!(LogstashDecoder::new(50).decode(&mut src).is_err());
+
+        let mut src = BytesMut::from(&frame[..]);
+        assert!(LogstashDecoder::new(frame.len()).decode(&mut src).is_ok());
+    }
+
+    #[test]
+    fn rejects_long_decompressed_frames() {
+        let frames = json_frame(1, json!({ "message": "x".repeat(100) }));
+        let frame = compressed_frame(&frames);
+        assert!(frame.len() < frames.len());
+
+        let mut src = BytesMut::from(&frame[..]);
+        assert!(LogstashDecoder::new(frames.len() - 1)
+            .decode(&mut src)
+            .is_err());
+
+        let mut src = BytesMut::from(&frame[..]);
+        assert!(LogstashDecoder::new(frames.len())
+            .decode(&mut src)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn builds_events() {
+        let fields = vec![
+            ("message".to_owned(), "one".into()),
+            ("@timestamp".to_owned(), "2021-03-04T05:06:07.008Z".into()),
+        ];
+        let event = build_event(fields.into_iter().collect(), "127.0.0.1", 64);
+        let log = event.as_log();
+
+        assert_eq!(log[log_schema().message_key()], "one".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.ymd(2021, 3, 4).and_hms_milli(5, 6, 7, 8).into()
+        );
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "logstash".into());
+    }
+
+    #[tokio::test]
+    async fn acknowledges_windows() {
+        trace_init();
+
+        let (sender, recv) = Pipeline::new_test();
+        let address = next_addr();
+        let config = LogstashConfig {
+            address: address.into(),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            max_length: default_max_length(),
+        };
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                sender,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let mut frames = window_frame(2);
+        frames.extend(json_frame(1, json!({"message": "one"})));
+        frames.extend(compressed_frame(&json_frame(2, json!({"message": "two"}))));
+        stream.write_all(&frames).await.unwrap();
+
+        let mut response = [0; 6];
+        stream.read_exact(&mut response).await.unwrap();
+        assert_eq!(response.to_vec(), ack(2));
+
+        let events = collect_n(recv, 2).await;
+        assert_eq!(events[0].as_log()[log_schema().message_key()], "one".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+    }
+}
@@ -1,6 +1,8 @@
 use futures::future::BoxFuture;
 use snafu::Snafu;
 
+#[cfg(feature = "sources-amqp")]
+pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
 #[cfg(feature = "sources-aws_ecs_metrics")]
@@ -9,12 +11,20 @@ pub mod aws_ecs_metrics;
 pub mod aws_kinesis_firehose;
 #[cfg(feature = "sources-aws_s3")]
 pub mod aws_s3;
+#[cfg(feature = "sources-collectd")]
+pub mod collectd;
+#[cfg(feature = "sources-dns_query_log")]
+pub mod dns_query_log;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
 #[cfg(feature = "sources-file")]
 pub mod file;
+#[cfg(feature = "sources-fluent")]
+pub mod fluent;
 #[cfg(feature = "sources-generator")]
 pub mod generator;
+#[cfg(feature = "sources-graphite")]
+pub mod graphite;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -31,14 +41,24 @@ pub mod journald;
 pub mod kafka;
 #[cfg(feature = "sources-kubernetes-logs")]
 pub mod kubernetes_logs;
+#[cfg(feature = "sources-logstash")]
+pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(feature = "sources-nats")]
+pub mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
+#[cfg(feature = "sources-opentelemetry")]
+pub mod opentelemetry;
+#[cfg(feature = "sources-plugin")]
+pub mod plugin;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(feature = "sources-prometheus")]
 pub mod prometheus;
+#[cfg(feature = "sources-pulsar")]
+pub mod pulsar;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -0,0 +1,344 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::{finalization::BatchStatus, Event, Value},
+    internal_events::{NatsAcknowledgementFailed, NatsEventNotDelivered, NatsEventReceived},
+    nats::{create_durable_consumer, ACK, NAK},
+    shutdown::ShutdownSignal,
+    sources::util::{run_acknowledged, AcknowledgedMessages},
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::StreamExt;
+use nats::asynk::{Connection, Message, Subscription};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`acknowledgements` requires `jetstream`, core NATS has no acknowledgements"
+    ))]
+    AcknowledgementsWithoutJetStream,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NatsSourceConfig {
+    url: String,
+    #[serde(default = "default_name")]
+    name: String,
+    subject: String,
+    queue: Option<String>,
+    subject_key: Option<String>,
+    jetstream: Option<JetStreamConfig>,
+    #[serde(default)]
+    acknowledgements: bool,
+}
+
+/// Consumes the messages of a JetStream stream through a durable consumer,
+/// instead of those published to the subject while Vector is connected.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct JetStreamConfig {
+    stream: String,
+    #[serde(default = "default_name")]
+    durable_name: String,
+}
+
+fn default_name() -> String {
+    String::from("vector")
+}
+
+inventory::submit! {
+    SourceDescription::new::<NatsSourceConfig>("nats")
+}
+
+impl GenerateConfig for NatsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "from.vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "nats")]
+impl SourceConfig for NatsSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.acknowledgements && self.jetstream.is_none() {
+            return Err(BuildError::AcknowledgementsWithoutJetStream.into());
+        }
+
+        let (connection, subscription) = self.subscribe().await?;
+        Ok(Box::pin(nats_source(
+            connection,
+            subscription,
+            self.clone(),
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "nats"
+    }
+}
+
+impl NatsSourceConfig {
+    async fn connect(&self) -> crate::Result<Connection> {
+        Ok(nats::Options::new()
+            .with_name(&self.name)
+            .connect_async(&self.url)
+            .await?)
+    }
+
+    /// Subscribes to the subject, or to the messages of the durable consumer
+    /// when consuming from JetStream.
+    async fn subscribe(&self) -> crate::Result<(Connection, Subscription)> {
+        let connection = self.connect().await?;
+        let subject = match &self.jetstream {
+            Some(jetstream) => {
+                create_durable_consumer(
+                    &connection,
+                    &jetstream.stream,
+                    &jetstream.durable_name,
+                    &self.subject,
+                )
+                .await?
+            }
+            None => self.subject.clone(),
+        };
+
+        let subscription = match &self.queue {
+            Some(queue) => connection.queue_subscribe(&subject, queue).await?,
+            None => connection.subscribe(&subject).await?,
+        };
+        Ok((connection, subscription))
+    }
+}
+
+async fn nats_source(
+    connection: Connection,
+    subscription: Subscription,
+    config: NatsSourceConfig,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> Result<(), ()> {
+    let acknowledgements = config.acknowledgements;
+    let messages = NatsMessages {
+        connection,
+        subscription,
+        config,
+    };
+    run_acknowledged(messages, acknowledgements, shutdown, out).await
+}
+
+struct NatsMessages {
+    connection: Connection,
+    subscription: Subscription,
+    config: NatsSourceConfig,
+}
+
+impl NatsMessages {
+    /// Responds to JetStream messages, which JetStream redelivers unless
+    /// they are acknowledged.
+    async fn respond(&mut self, msg: &Message, response: &[u8]) {
+        let reply = match (&self.config.jetstream, &msg.reply) {
+            (Some(_), Some(reply)) => reply,
+            _ => return,
+        };
+        if let Err(error) = self.connection.publish(reply, response).await {
+            emit!(NatsAcknowledgementFailed { error });
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AcknowledgedMessages for NatsMessages {
+    type Message = Message;
+
+    async fn next(&mut self) -> Option<Result<Message, ()>> {
+        let msg = self.subscription.next().await?;
+        emit!(NatsEventReceived {
+            byte_size: msg.data.len()
+        });
+        Some(Ok(msg))
+    }
+
+    fn to_event(&self, msg: &Message) -> Event {
+        message_to_event(msg, &self.config)
+    }
+
+    async fn ack(&mut self, msg: Message) {
+        self.respond(&msg, ACK).await
+    }
+
+    async fn nack(&mut self, msg: Message, status: BatchStatus) {
+        emit!(NatsEventNotDelivered {
+            subject: &msg.subject,
+            status,
+        });
+        self.respond(&msg, NAK).await
+    }
+}
+
+fn message_to_event(msg: &Message, config: &NatsSourceConfig) -> Event {
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    log.insert(
+        log_schema().message_key(),
+        Value::from(Bytes::from(msg.data.clone())),
+    );
+    log.insert(log_schema().timestamp_key(), Utc::now());
+    log.insert(log_schema().source_type_key(), Bytes::from("nats"));
+
+    if let Some(subject_key) = &config.subject_key {
+        log.insert(subject_key, msg.subject.clone());
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NatsSourceConfig>();
+    }
+
+    #[test]
+    fn parse_jetstream() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "logs.>"
+            jetstream.stream = "logs"
+            acknowledgements = true
+            "#,
+        )
+        .unwrap();
+        let jetstream = config.jetstream.unwrap();
+        assert_eq!(jetstream.stream, "logs");
+        assert_eq!(jetstream.durable_name, "vector");
+    }
+
+    #[tokio::test]
+    async fn acknowledgements_require_jetstream() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "logs"
+            acknowledgements = true
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = Pipeline::new_test();
+        let error = config
+            .build(
+                "nats",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("requires `jetstream`"));
+    }
+}
+
+#[cfg(feature = "nats-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+
+    fn make_config(subject: &str, jetstream: Option<JetStreamConfig>) -> NatsSourceConfig {
+        NatsSourceConfig {
+            url: "nats://127.0.0.1:4222".to_owned(),
+            name: default_name(),
+            subject: subject.to_owned(),
+            queue: None,
+            subject_key: Some("subject".to_owned()),
+            acknowledgements: jetstream.is_some(),
+            jetstream,
+        }
+    }
+
+    async fn consume(config: NatsSourceConfig, subject: &str) {
+        trace_init();
+
+        let (connection, subscription) = config.subscribe().await.unwrap();
+        for message in &["one", "two"] {
+            if config.jetstream.is_some() {
+                crate::nats::publish(&connection, subject, message)
+                    .await
+                    .unwrap();
+            } else {
+                connection.publish(subject, message).await.unwrap();
+            }
+        }
+
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        tokio::spawn(nats_source(connection, subscription, config, shutdown, tx));
+
+        let events = collect_n(rx, 2).await;
+        drop(trigger);
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two"]);
+        assert_eq!(events[0].as_log()["subject"], subject.into());
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "nats".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn nats_source_consumes_subject() {
+        let subject = format!("test-{}", random_string(10));
+        consume(make_config(&subject, None), &subject).await;
+    }
+
+    #[tokio::test]
+    async fn nats_source_consumes_jetstream() {
+        let stream = format!("test-{}", random_string(10));
+        let subject = format!("{}.logs", stream);
+        let config = make_config(
+            &subject,
+            Some(JetStreamConfig {
+                stream: stream.clone(),
+                durable_name: default_name(),
+            }),
+        );
+        let connection = config.connect().await.unwrap();
+        crate::nats::create_stream(&connection, &stream, &subject)
+            .await
+            .unwrap();
+
+        consume(config, &subject).await;
+    }
+}
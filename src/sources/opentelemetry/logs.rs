@@ -0,0 +1,216 @@
+use super::{
+    proto::{
+        collector::logs::v1::ExportLogsServiceRequest, common::v1::InstrumentationScope,
+        logs::v1::LogRecord,
+    },
+    timestamp, SOURCE_NAME,
+};
+use crate::{
+    config::log_schema,
+    event::{Event, Value},
+    opentelemetry::{decode_attributes, decode_value},
+};
+use bytes::Bytes;
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+pub(super) fn decode(request: ExportLogsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    for resource_logs in request.resource_logs {
+        let resources = resource_logs
+            .resource
+            .map(|resource| decode_attributes(resource.attributes))
+            .filter(|resources| !resources.is_empty());
+
+        for scope_logs in resource_logs.scope_logs {
+            let scope = scope_logs.scope.and_then(scope_value);
+
+            for record in scope_logs.log_records {
+                events.push(build_event(record, &resources, &scope));
+            }
+        }
+    }
+
+    events
+}
+
+/// The instrumentation scope as a map, or `None` if it is empty.
+fn scope_value(scope: InstrumentationScope) -> Option<Value> {
+    let mut fields = BTreeMap::new();
+    if !scope.name.is_empty() {
+        fields.insert("name".to_string(), Value::from(scope.name));
+    }
+    if !scope.version.is_empty() {
+        fields.insert("version".to_string(), Value::from(scope.version));
+    }
+    if !scope.attributes.is_empty() {
+        fields.insert(
+            "attributes".to_string(),
+            Value::Map(decode_attributes(scope.attributes)),
+        );
+    }
+
+    if fields.is_empty() {
+        None
+    } else {
+        Some(Value::Map(fields))
+    }
+}
+
+fn build_event(
+    record: LogRecord,
+    resources: &Option<BTreeMap<String, Value>>,
+    scope: &Option<Value>,
+) -> Event {
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    if let Some(body) = record.body {
+        log.insert(log_schema().message_key(), decode_value(body));
+    }
+
+    let observed_timestamp = timestamp(record.observed_time_unix_nano);
+    let timestamp = timestamp(record.time_unix_nano)
+        .or(observed_timestamp)
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    if let Some(observed_timestamp) = observed_timestamp {
+        log.insert("observed_timestamp", observed_timestamp);
+    }
+
+    if !record.attributes.is_empty() {
+        log.insert(
+            "attributes",
+            Value::Map(decode_attributes(record.attributes)),
+        );
+    }
+    if let Some(resources) = resources {
+        log.insert("resources", Value::Map(resources.clone()));
+    }
+    if let Some(scope) = scope {
+        log.insert("scope", scope.clone());
+    }
+
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number as i64);
+    }
+    if !record.trace_id.is_empty() {
+        log.insert("trace_id", hex::encode(record.trace_id));
+    }
+    if !record.span_id.is_empty() {
+        log.insert("span_id", hex::encode(record.span_id));
+    }
+    if record.flags != 0 {
+        log.insert("flags", record.flags as i64);
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert(
+            "dropped_attributes_count",
+            record.dropped_attributes_count as i64,
+        );
+    }
+
+    log.insert(log_schema().source_type_key(), Bytes::from(SOURCE_NAME));
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::proto::{
+        common::v1::{any_value, AnyValue, KeyValue},
+        logs::v1::{ResourceLogs, ScopeLogs},
+        resource::v1::Resource,
+    };
+    use chrono::TimeZone;
+
+    fn string_value(value: &str) -> Option<AnyValue> {
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        })
+    }
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: string_value(value),
+        }
+    }
+
+    #[test]
+    fn decodes_log_records() {
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: Some(Resource {
+                    attributes: vec![key_value("service.name", "checkout")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(InstrumentationScope {
+                        name: "http".into(),
+                        version: "1.2.0".into(),
+                        attributes: vec![],
+                        dropped_attributes_count: 0,
+                    }),
+                    log_records: vec![
+                        LogRecord {
+                            time_unix_nano: 1_600_000_000_000_000_123,
+                            severity_number: 9,
+                            severity_text: "INFO".into(),
+                            body: string_value("request served"),
+                            attributes: vec![key_value("http.method", "GET")],
+                            trace_id: vec![0xab; 16],
+                            span_id: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+                            ..Default::default()
+                        },
+                        LogRecord {
+                            observed_time_unix_nano: 1_600_000_001_000_000_000,
+                            body: string_value("no timestamp"),
+                            ..Default::default()
+                        },
+                    ],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+
+        let events = decode(request);
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "request served".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(Utc.timestamp(1_600_000_000, 123))
+        );
+        assert_eq!(log["attributes.http\\.method"], "GET".into());
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+        assert_eq!(log["scope.name"], "http".into());
+        assert_eq!(log["scope.version"], "1.2.0".into());
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], Value::Integer(9));
+        assert_eq!(log["trace_id"], "ab".repeat(16).into());
+        assert_eq!(log["span_id"], "0102030405060708".into());
+        assert_eq!(log[log_schema().source_type_key()], "opentelemetry".into());
+        assert!(log.get("observed_timestamp").is_none());
+
+        // Records without a timestamp fall back to the observed one.
+        let log = events[1].as_log();
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(Utc.timestamp(1_600_000_001, 0))
+        );
+        assert_eq!(
+            log["observed_timestamp"],
+            Value::Timestamp(Utc.timestamp(1_600_000_001, 0))
+        );
+        assert!(log.get("severity_text").is_none());
+        assert!(log.get("trace_id").is_none());
+    }
+}
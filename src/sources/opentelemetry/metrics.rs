@@ -0,0 +1,397 @@
+use super::{
+    proto::{
+        collector::metrics::v1::ExportMetricsServiceRequest,
+        common::v1::KeyValue,
+        metrics::v1::{metric::Data, number_data_point, AggregationTemporality, NumberDataPoint},
+    },
+    timestamp,
+};
+use crate::{
+    event::{
+        metric::{Bucket, MetricTags, Quantile},
+        Event, Metric, MetricKind, MetricValue,
+    },
+    opentelemetry::decode_attributes,
+};
+
+pub(super) fn decode(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+
+    for resource_metrics in request.resource_metrics {
+        let mut resource_tags = MetricTags::new();
+        if let Some(resource) = resource_metrics.resource {
+            for (key, value) in decode_attributes(resource.attributes) {
+                resource_tags.insert(format!("resource.{}", key), value.to_string_lossy());
+            }
+        }
+
+        for scope_metrics in resource_metrics.scope_metrics {
+            let mut tags = resource_tags.clone();
+            if let Some(scope) = scope_metrics.scope {
+                if !scope.name.is_empty() {
+                    tags.insert("scope.name".into(), scope.name);
+                }
+                if !scope.version.is_empty() {
+                    tags.insert("scope.version".into(), scope.version);
+                }
+            }
+
+            for metric in scope_metrics.metrics {
+                decode_metric(metric, &tags, &mut events);
+            }
+        }
+    }
+
+    events
+}
+
+fn decode_metric(
+    metric: super::proto::metrics::v1::Metric,
+    tags: &MetricTags,
+    events: &mut Vec<Event>,
+) {
+    let name = metric.name;
+    let build = |kind, value, attributes, time_unix_nano| {
+        Event::Metric(
+            Metric::new(name.clone(), kind, value)
+                .with_tags(point_tags(tags, attributes))
+                .with_timestamp(timestamp(time_unix_nano)),
+        )
+    };
+
+    match metric.data {
+        Some(Data::Gauge(gauge)) => {
+            for point in gauge.data_points {
+                if let Some((value, attributes, time)) = number(point) {
+                    events.push(build(
+                        MetricKind::Absolute,
+                        MetricValue::Gauge { value },
+                        attributes,
+                        time,
+                    ));
+                }
+            }
+        }
+        Some(Data::Sum(sum)) => {
+            let kind = metric_kind(sum.aggregation_temporality);
+            for point in sum.data_points {
+                if let Some((value, attributes, time)) = number(point) {
+                    // Only monotonic sums are counters, others can go down.
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    events.push(build(kind, value, attributes, time));
+                }
+            }
+        }
+        Some(Data::Histogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            for point in histogram.data_points {
+                // The last bucket count is for the implicit `+Inf` bound,
+                // which is carried by `count` like for Prometheus.
+                let buckets = point
+                    .explicit_bounds
+                    .iter()
+                    .zip(point.bucket_counts.iter())
+                    .map(|(&upper_limit, &count)| Bucket {
+                        upper_limit,
+                        count: count as u32,
+                    })
+                    .collect();
+                let value = MetricValue::AggregatedHistogram {
+                    buckets,
+                    count: point.count as u32,
+                    sum: point.sum,
+                };
+                events.push(build(kind, value, point.attributes, point.time_unix_nano));
+            }
+        }
+        Some(Data::Summary(summary)) => {
+            for point in summary.data_points {
+                let quantiles = point
+                    .quantile_values
+                    .iter()
+                    .map(|quantile| Quantile {
+                        upper_limit: quantile.quantile,
+                        value: quantile.value,
+                    })
+                    .collect();
+                let value = MetricValue::AggregatedSummary {
+                    quantiles,
+                    count: point.count as u32,
+                    sum: point.sum,
+                };
+                events.push(build(
+                    MetricKind::Absolute,
+                    value,
+                    point.attributes,
+                    point.time_unix_nano,
+                ));
+            }
+        }
+        None => debug!(
+            message = "Dropping metric with unsupported data type.",
+            %name,
+            internal_log_rate_secs = 10
+        ),
+    }
+}
+
+/// Delta temporalities are increments, anything else is taken as cumulative.
+fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn number(point: NumberDataPoint) -> Option<(f64, Vec<KeyValue>, u64)> {
+    let value = match point.value? {
+        number_data_point::Value::AsDouble(value) => value,
+        number_data_point::Value::AsInt(value) => value as f64,
+    };
+    Some((value, point.attributes, point.time_unix_nano))
+}
+
+fn point_tags(tags: &MetricTags, point_attributes: Vec<KeyValue>) -> Option<MetricTags> {
+    let mut tags = tags.clone();
+    for (key, value) in decode_attributes(point_attributes) {
+        tags.insert(key, value.to_string_lossy());
+    }
+
+    if tags.is_empty() {
+        None
+    } else {
+        Some(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::proto::{
+        common::v1::{any_value, AnyValue, InstrumentationScope},
+        metrics::v1::{
+            summary_data_point::ValueAtQuantile, Gauge, Histogram, HistogramDataPoint,
+            Metric as OtlpMetric, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+    };
+    use chrono::{TimeZone, Utc};
+    use pretty_assertions::assert_eq;
+
+    fn key_value(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.into())),
+            }),
+        }
+    }
+
+    fn number_point(value: number_data_point::Value) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![key_value("code", "200")],
+            time_unix_nano: 1_600_000_000_000_000_000,
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+
+    fn request(metrics: Vec<OtlpMetric>) -> ExportMetricsServiceRequest {
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: vec![key_value("service.name", "checkout")],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: "http".into(),
+                        ..Default::default()
+                    }),
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    fn metric(name: &str, data: Data) -> OtlpMetric {
+        OtlpMetric {
+            name: name.into(),
+            data: Some(data),
+            ..Default::default()
+        }
+    }
+
+    fn expected_tags(code: bool) -> Option<MetricTags> {
+        let mut tags = MetricTags::new();
+        tags.insert("resource.service.name".into(), "checkout".into());
+        tags.insert("scope.name".into(), "http".into());
+        if code {
+            tags.insert("code".into(), "200".into());
+        }
+        Some(tags)
+    }
+
+    #[test]
+    fn decodes_number_metrics() {
+        let events = decode(request(vec![
+            metric(
+                "temperature",
+                Data::Gauge(Gauge {
+                    data_points: vec![number_point(number_data_point::Value::AsDouble(21.5))],
+                }),
+            ),
+            metric(
+                "requests",
+                Data::Sum(Sum {
+                    data_points: vec![number_point(number_data_point::Value::AsInt(12))],
+                    aggregation_temporality: AggregationTemporality::Delta as i32,
+                    is_monotonic: true,
+                }),
+            ),
+            metric(
+                "connections",
+                Data::Sum(Sum {
+                    data_points: vec![number_point(number_data_point::Value::AsInt(3))],
+                    aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                    is_monotonic: false,
+                }),
+            ),
+        ]));
+
+        let metrics = events
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        let timestamp = Some(Utc.timestamp(1_600_000_000, 0));
+        assert_eq!(
+            metrics,
+            vec![
+                Metric::new(
+                    "temperature".into(),
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 21.5 },
+                )
+                .with_tags(expected_tags(true))
+                .with_timestamp(timestamp),
+                Metric::new(
+                    "requests".into(),
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 12.0 },
+                )
+                .with_tags(expected_tags(true))
+                .with_timestamp(timestamp),
+                Metric::new(
+                    "connections".into(),
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 3.0 },
+                )
+                .with_tags(expected_tags(true))
+                .with_timestamp(timestamp),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_distribution_metrics() {
+        let events = decode(request(vec![
+            metric(
+                "latency",
+                Data::Histogram(Histogram {
+                    data_points: vec![HistogramDataPoint {
+                        count: 10,
+                        sum: 4.5,
+                        bucket_counts: vec![2, 5, 3],
+                        explicit_bounds: vec![0.1, 1.0],
+                        ..Default::default()
+                    }],
+                    aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                }),
+            ),
+            metric(
+                "size",
+                Data::Summary(Summary {
+                    data_points: vec![SummaryDataPoint {
+                        count: 4,
+                        sum: 20.0,
+                        quantile_values: vec![
+                            ValueAtQuantile {
+                                quantile: 0.5,
+                                value: 4.0,
+                            },
+                            ValueAtQuantile {
+                                quantile: 0.99,
+                                value: 9.0,
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                }),
+            ),
+        ]));
+
+        let metrics = events
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            metrics,
+            vec![
+                Metric::new(
+                    "latency".into(),
+                    MetricKind::Absolute,
+                    MetricValue::AggregatedHistogram {
+                        buckets: vec![
+                            Bucket {
+                                upper_limit: 0.1,
+                                count: 2,
+                            },
+                            Bucket {
+                                upper_limit: 1.0,
+                                count: 5,
+                            },
+                        ],
+                        count: 10,
+                        sum: 4.5,
+                    },
+                )
+                .with_tags(expected_tags(false)),
+                Metric::new(
+                    "size".into(),
+                    MetricKind::Absolute,
+                    MetricValue::AggregatedSummary {
+                        quantiles: vec![
+                            Quantile {
+                                upper_limit: 0.5,
+                                value: 4.0,
+                            },
+                            Quantile {
+                                upper_limit: 0.99,
+                                value: 9.0,
+                            },
+                        ],
+                        count: 4,
+                        sum: 20.0,
+                    },
+                )
+                .with_tags(expected_tags(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_unsupported_metrics() {
+        let events = decode(request(vec![OtlpMetric {
+            name: "exponential".into(),
+            ..Default::default()
+        }]));
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,363 @@
+mod logs;
+mod metrics;
+
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription},
+    internal_events::{OpentelemetryDecodeError, OpentelemetryEventsReceived},
+    shutdown::ShutdownSignal,
+    sources::{
+        self,
+        util::{ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    },
+    tls::TlsConfig,
+    Event, Pipeline,
+};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{future, stream, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{collections::HashMap, net::SocketAddr};
+use tonic::{Request, Response, Status};
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::opentelemetry::proto::{
+    self,
+    collector::{
+        logs::v1::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        metrics::v1::{
+            metrics_service_server::{MetricsService, MetricsServiceServer},
+            ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+    },
+};
+
+const SOURCE_NAME: &str = "opentelemetry";
+
+const LOGS_PATH: &str = "/v1/logs";
+const METRICS_PATH: &str = "/v1/metrics";
+
+#[derive(Debug, Snafu)]
+enum ConfigError {
+    #[snafu(display("At least one of `grpc` or `http` must be set"))]
+    NoListener,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    grpc: Option<GrpcConfig>,
+    http: Option<HttpConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct GrpcConfig {
+    address: SocketAddr,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct HttpConfig {
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+    auth: Option<HttpSourceAuthConfig>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>(SOURCE_NAME)
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: Some(GrpcConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+            }),
+            http: Some(HttpConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: None,
+                auth: None,
+            }),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<sources::Source> {
+        let mut servers: Vec<sources::Source> = Vec::new();
+        if let Some(grpc) = &self.grpc {
+            servers.push(Box::pin(run_grpc(
+                grpc.address,
+                shutdown.clone(),
+                out.clone(),
+            )));
+        }
+        if let Some(http) = &self.http {
+            servers.push(OpentelemetryHttpSource.run(
+                http.address,
+                "",
+                &http.tls,
+                &http.auth,
+                out,
+                shutdown,
+            )?);
+        }
+
+        if servers.is_empty() {
+            return Err(ConfigError::NoListener.into());
+        }
+        Ok(Box::pin(future::try_join_all(servers).map_ok(|_| ())))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        SOURCE_NAME
+    }
+}
+
+async fn run_grpc(address: SocketAddr, shutdown: ShutdownSignal, out: Pipeline) -> Result<(), ()> {
+    let service = GrpcService { out };
+    tonic::transport::Server::builder()
+        .add_service(LogsServiceServer::new(service.clone()))
+        .add_service(MetricsServiceServer::new(service))
+        .serve_with_shutdown(address, shutdown.map(|_| ()))
+        .await
+        .map_err(|error| error!(message = "OTLP gRPC server failed.", %error))
+}
+
+#[derive(Clone)]
+struct GrpcService {
+    out: Pipeline,
+}
+
+impl GrpcService {
+    async fn forward(&self, events: Vec<Event>, byte_size: usize) -> Result<(), Status> {
+        emit!(OpentelemetryEventsReceived {
+            count: events.len(),
+            byte_size,
+        });
+
+        let mut out = self.out.clone();
+        out.send_all(&mut stream::iter(events).map(Ok))
+            .await
+            .map_err(|_| {
+                error!(message = "Failed to forward events, downstream is closed.");
+                Status::unavailable("Vector is shutting down.")
+            })
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for GrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        self.forward(logs::decode(request), byte_size).await?;
+        Ok(Response::new(ExportLogsServiceResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for GrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        self.forward(metrics::decode(request), byte_size).await?;
+        Ok(Response::new(ExportMetricsServiceResponse {}))
+    }
+}
+
+/// Receives OTLP/HTTP requests, which carry the same protobuf messages
+/// as the gRPC service on fixed paths.
+#[derive(Clone)]
+struct OpentelemetryHttpSource;
+
+impl HttpSource for OpentelemetryHttpSource {
+    fn build_event(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        if let Some(content_type) = header_map.get("content-type") {
+            if content_type != "application/x-protobuf" {
+                return Err(ErrorMessage::new(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    format!(
+                        "Unsupported content type {}, only application/x-protobuf is accepted",
+                        String::from_utf8_lossy(content_type.as_bytes())
+                    ),
+                ));
+            }
+        }
+
+        match path {
+            LOGS_PATH => decode_body::<ExportLogsServiceRequest>(body).map(logs::decode),
+            METRICS_PATH => decode_body::<ExportMetricsServiceRequest>(body).map(metrics::decode),
+            path => Err(ErrorMessage::new(
+                StatusCode::NOT_FOUND,
+                format!("Unknown path {}", path),
+            )),
+        }
+    }
+
+    fn strict_path(&self) -> bool {
+        false
+    }
+}
+
+fn decode_body<T: Message + Default>(body: Bytes) -> Result<T, ErrorMessage> {
+    T::decode(body).map_err(|error| {
+        emit!(OpentelemetryDecodeError {
+            error: error.clone()
+        });
+        ErrorMessage::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not decode request: {}", error),
+        )
+    })
+}
+
+/// OTLP leaves unset timestamps at zero.
+fn timestamp(unix_nanos: u64) -> Option<DateTime<Utc>> {
+    if unix_nanos == 0 {
+        None
+    } else {
+        Some(Utc.timestamp_nanos(unix_nanos as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_n, next_addr, wait_for_tcp};
+    use proto::{
+        collector::logs::v1::logs_service_client::LogsServiceClient,
+        common::v1::{any_value, AnyValue},
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    };
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    fn log_request(message: &str) -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: None,
+                scope_logs: vec![ScopeLogs {
+                    scope: None,
+                    log_records: vec![LogRecord {
+                        body: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue(message.into())),
+                        }),
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    async fn start_source(config: OpentelemetryConfig) -> mpsc::Receiver<Event> {
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        rx
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_grpc() {
+        let address = next_addr();
+        let rx = start_source(OpentelemetryConfig {
+            grpc: Some(GrpcConfig { address }),
+            http: None,
+        })
+        .await;
+        wait_for_tcp(address).await;
+
+        let mut client = LogsServiceClient::connect(format!("http://{}", address))
+            .await
+            .unwrap();
+        client.export(log_request("over grpc")).await.unwrap();
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[crate::config::log_schema().message_key()],
+            "over grpc".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_http() {
+        let address = next_addr();
+        let rx = start_source(OpentelemetryConfig {
+            grpc: None,
+            http: Some(HttpConfig {
+                address,
+                tls: None,
+                auth: None,
+            }),
+        })
+        .await;
+        wait_for_tcp(address).await;
+
+        let client = reqwest::Client::new();
+        let send = |path: &str, body: Vec<u8>| {
+            client
+                .post(&format!("http://{}{}", address, path))
+                .header("content-type", "application/x-protobuf")
+                .body(body)
+                .send()
+        };
+
+        let mut body = Vec::new();
+        log_request("over http").encode(&mut body).unwrap();
+        let response = send(LOGS_PATH, body).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send("/v1/traces", vec![]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let events = collect_n(rx, 1).await;
+        assert_eq!(
+            events[0].as_log()[crate::config::log_schema().message_key()],
+            "over http".into()
+        );
+    }
+}
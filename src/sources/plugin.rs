@@ -0,0 +1,265 @@
+use crate::{
+    config::{DataType, GlobalOptions, SourceConfig, SourceDescription},
+    internal_events::{error_stage, PluginEventReceived},
+    plugin::{
+        decode_event,
+        proto::{source_request::Message, Ack, ComponentKind, SourceRequest, SourceResponse},
+        Plugin, PluginConfig, Reconnect, SessionError,
+    },
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use futures::SinkExt;
+use prost::Message as _;
+use tokio::sync::mpsc;
+
+inventory::submit! {
+    SourceDescription::new::<PluginConfig>("plugin")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "plugin")]
+impl SourceConfig for PluginConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let plugin = self.connect(ComponentKind::Source).await?;
+
+        Ok(Box::pin(run(plugin, shutdown, out)))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "plugin"
+    }
+}
+
+enum SessionEnd {
+    /// The plugin ended the stream, or Vector is shutting down.
+    Done,
+    /// The events can't be forwarded anymore.
+    Closed,
+    Failed(tonic::Status),
+}
+
+async fn run(
+    mut plugin: Plugin,
+    mut shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    let mut reconnect = Reconnect::new(error_stage::RECEIVING);
+
+    loop {
+        let delay = match session(&mut plugin, &mut shutdown, &mut out, &mut reconnect).await {
+            SessionEnd::Done => return Ok(()),
+            SessionEnd::Closed => {
+                error!(message = "Failed to forward events; downstream is closed.");
+                return Err(());
+            }
+            SessionEnd::Failed(error) => reconnect.failed(SessionError::Failed(error)),
+        };
+
+        tokio::select! {
+            _ = delay => {},
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+/// Forward the events of the plugin and acknowledge them once they are
+/// accepted by the pipeline.
+async fn session(
+    plugin: &mut Plugin,
+    shutdown: &mut ShutdownSignal,
+    out: &mut Pipeline,
+    reconnect: &mut Reconnect,
+) -> SessionEnd {
+    let (mut tx, rx) = mpsc::channel(1024);
+    let config = SourceRequest {
+        message: Some(Message::Config(plugin.config())),
+    };
+    if tx.send(config).await.is_err() {
+        return SessionEnd::Closed;
+    }
+
+    let mut responses = match plugin.client.source(rx).await {
+        Ok(response) => response.into_inner(),
+        Err(status) => return SessionEnd::Failed(status),
+    };
+
+    loop {
+        let response = tokio::select! {
+            response = responses.message() => response,
+            _ = &mut *shutdown => return SessionEnd::Done,
+        };
+
+        match response {
+            Ok(Some(SourceResponse { id, event })) => {
+                reconnect.reset();
+                let byte_size = event.as_ref().map_or(0, |event| event.encoded_len());
+                if let Some(event) = decode_event(event) {
+                    emit!(PluginEventReceived { byte_size });
+                    if out.send(event).await.is_err() {
+                        return SessionEnd::Closed;
+                    }
+                }
+
+                let ack = SourceRequest {
+                    message: Some(Message::Ack(Ack { id })),
+                };
+                if tx.send(ack).await.is_err() {
+                    return SessionEnd::Failed(tonic::Status::aborted(
+                        "acknowledgement stream closed",
+                    ));
+                }
+            }
+            Ok(None) => return SessionEnd::Done,
+            Err(status) => return SessionEnd::Failed(status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::Event,
+        plugin::{
+            proto::{self, plugin_server},
+            test::{describe, serve},
+        },
+        test_util::collect_n,
+    };
+    use std::sync::{Arc, Mutex};
+    use tonic::{Request, Response, Status, Streaming};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PluginConfig>();
+    }
+
+    /// Emits the configured lines and records the acknowledged ids.
+    #[derive(Clone, Default)]
+    struct LinesPlugin {
+        lines: Vec<String>,
+        acked: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[tonic::async_trait]
+    impl plugin_server::Plugin for LinesPlugin {
+        async fn describe(
+            &self,
+            _: Request<proto::DescribeRequest>,
+        ) -> Result<Response<proto::DescribeResponse>, Status> {
+            Ok(Response::new(describe(
+                ComponentKind::Source,
+                proto::DataType::Any,
+            )))
+        }
+
+        async fn health(
+            &self,
+            _: Request<proto::ComponentConfig>,
+        ) -> Result<Response<proto::HealthResponse>, Status> {
+            Err(Status::unimplemented("health"))
+        }
+
+        type SourceStream = mpsc::Receiver<Result<SourceResponse, Status>>;
+
+        async fn source(
+            &self,
+            request: Request<Streaming<SourceRequest>>,
+        ) -> Result<Response<Self::SourceStream>, Status> {
+            let mut requests = request.into_inner();
+            let acked = Arc::clone(&self.acked);
+            tokio::spawn(async move {
+                while let Ok(Some(request)) = requests.message().await {
+                    if let Some(Message::Ack(ack)) = request.message {
+                        acked.lock().unwrap().push(ack.id);
+                    }
+                }
+            });
+
+            let (mut tx, rx) = mpsc::channel(10);
+            let lines = self.lines.clone();
+            tokio::spawn(async move {
+                for (id, line) in lines.into_iter().enumerate() {
+                    let response = SourceResponse {
+                        id: id as u64 + 1,
+                        event: Some(Event::from(line).into()),
+                    };
+                    if tx.send(Ok(response)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+
+            Ok(Response::new(rx))
+        }
+
+        type TransformStream = mpsc::Receiver<Result<proto::TransformResponse, Status>>;
+
+        async fn transform(
+            &self,
+            _: Request<Streaming<proto::TransformRequest>>,
+        ) -> Result<Response<Self::TransformStream>, Status> {
+            Err(Status::unimplemented("transform"))
+        }
+
+        type SinkStream = mpsc::Receiver<Result<proto::SinkResponse, Status>>;
+
+        async fn sink(
+            &self,
+            _: Request<Streaming<proto::SinkRequest>>,
+        ) -> Result<Response<Self::SinkStream>, Status> {
+            Err(Status::unimplemented("sink"))
+        }
+    }
+
+    #[tokio::test]
+    async fn receives_events_from_plugin() {
+        let plugin = LinesPlugin {
+            lines: vec!["one".into(), "two".into(), "three".into()],
+            ..Default::default()
+        };
+        let endpoint = serve(plugin.clone()).await;
+
+        let config = PluginConfig {
+            endpoint,
+            component: "test".into(),
+            options: Default::default(),
+        };
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two", "three"]);
+
+        crate::test_util::wait_for(|| {
+            let acked = plugin.acked.lock().unwrap().clone();
+            async move { acked == vec![1, 2, 3] }
+        })
+        .await;
+    }
+}
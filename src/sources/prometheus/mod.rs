@@ -1,3 +1,4 @@
 pub(crate) mod parser;
+mod relabel;
 mod remote_write;
 mod scrape;
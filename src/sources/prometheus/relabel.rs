@@ -0,0 +1,298 @@
+use crate::{
+    event::metric::{Metric, MetricTags},
+    prometheus::METRIC_NAME_LABEL,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum RelabelError {
+    #[snafu(display("Invalid relabel regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("Relabel action `replace` requires a `target_label`"))]
+    MissingTargetLabel,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    Replace,
+    Keep,
+    Drop,
+    LabelDrop,
+    LabelKeep,
+}
+
+impl Default for RelabelAction {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    #[serde(default)]
+    pub source_labels: Vec<String>,
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    #[serde(default = "default_regex")]
+    pub regex: String,
+    pub target_label: Option<String>,
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+    #[serde(default)]
+    pub action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".into()
+}
+
+fn default_regex() -> String {
+    "(.*)".into()
+}
+
+fn default_replacement() -> String {
+    "$1".into()
+}
+
+impl RelabelConfig {
+    pub fn build(&self) -> Result<Relabel, RelabelError> {
+        // Prometheus anchors relabel regexes on both ends.
+        let regex = Regex::new(&format!("^(?:{})$", self.regex)).context(InvalidRegex {
+            regex: self.regex.clone(),
+        })?;
+
+        if self.action == RelabelAction::Replace && self.target_label.is_none() {
+            return Err(RelabelError::MissingTargetLabel);
+        }
+
+        Ok(Relabel {
+            source_labels: self.source_labels.clone(),
+            separator: self.separator.clone(),
+            regex,
+            target_label: self.target_label.clone(),
+            replacement: self.replacement.clone(),
+            action: self.action,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Relabel {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    target_label: Option<String>,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl Relabel {
+    /// Applies this rule to the metric, returning `None` if the metric
+    /// should be dropped.
+    pub fn apply(&self, mut metric: Metric) -> Option<Metric> {
+        match self.action {
+            RelabelAction::Keep => Some(metric).filter(|metric| self.matches(metric)),
+            RelabelAction::Drop => Some(metric).filter(|metric| !self.matches(metric)),
+            RelabelAction::Replace => {
+                let value = self.source_value(&metric);
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut result = String::new();
+                    captures.expand(&self.replacement, &mut result);
+                    match self.target_label.as_deref() {
+                        Some(METRIC_NAME_LABEL) if !result.is_empty() => {
+                            metric.series.name.name = result
+                        }
+                        Some(METRIC_NAME_LABEL) => (),
+                        Some(target) if result.is_empty() => {
+                            metric.delete_tag(target);
+                        }
+                        Some(target) => metric.set_tag_value(target.into(), result),
+                        None => (),
+                    }
+                }
+                Some(metric)
+            }
+            RelabelAction::LabelDrop | RelabelAction::LabelKeep => {
+                let keep_matching = self.action == RelabelAction::LabelKeep;
+                let tags = metric.tags_mut();
+                *tags = tags
+                    .take()
+                    .map(|tags| {
+                        tags.into_iter()
+                            .filter(|(name, _)| self.regex.is_match(name) == keep_matching)
+                            .collect::<MetricTags>()
+                    })
+                    .filter(|tags| !tags.is_empty());
+                Some(metric)
+            }
+        }
+    }
+
+    fn matches(&self, metric: &Metric) -> bool {
+        self.regex.is_match(&self.source_value(metric))
+    }
+
+    fn source_value(&self, metric: &Metric) -> String {
+        self.source_labels
+            .iter()
+            .map(|label| match label.as_str() {
+                METRIC_NAME_LABEL => metric.name().to_string(),
+                label => metric.tag_value(label).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+/// Runs the metric through each rule in turn, stopping as soon as one
+/// of them drops it.
+pub fn relabel(rules: &[Relabel], metric: Metric) -> Option<Metric> {
+    rules
+        .iter()
+        .try_fold(metric, |metric, rule| rule.apply(metric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::metric::{MetricKind, MetricValue};
+    use pretty_assertions::assert_eq;
+
+    fn rule(toml: &str) -> Relabel {
+        toml::from_str::<RelabelConfig>(toml)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn metric(name: &str, tags: &[(&str, &str)]) -> Metric {
+        Metric::new(
+            name.into(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_tags(Some(
+            tags.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn keeps_and_drops_by_name() {
+        let keep = rule(
+            r#"
+            source_labels = ["__name__"]
+            regex = "go_.*"
+            action = "keep"
+            "#,
+        );
+        assert!(keep.apply(metric("go_goroutines", &[])).is_some());
+        assert!(keep.apply(metric("process_open_fds", &[])).is_none());
+
+        let drop = rule(
+            r#"
+            source_labels = ["__name__"]
+            regex = "go"
+            action = "drop"
+            "#,
+        );
+        // The regex is anchored, so a partial match does not drop.
+        assert!(drop.apply(metric("go_goroutines", &[])).is_some());
+        assert!(drop.apply(metric("go", &[])).is_none());
+    }
+
+    #[test]
+    fn replaces_joined_labels() {
+        let replace = rule(
+            r#"
+            source_labels = ["host", "port"]
+            regex = "(.+);(.+)"
+            target_label = "instance"
+            replacement = "$1:$2"
+            "#,
+        );
+        let replaced = replace
+            .apply(metric("up", &[("host", "example"), ("port", "9100")]))
+            .unwrap();
+        assert_eq!(replaced.tag_value("instance"), Some("example:9100".into()));
+
+        let unmatched = replace.apply(metric("up", &[("host", "example")])).unwrap();
+        assert_eq!(unmatched.tag_value("instance"), None);
+    }
+
+    #[test]
+    fn renames_metric() {
+        let rename = rule(
+            r#"
+            source_labels = ["__name__"]
+            regex = "node_(.*)"
+            target_label = "__name__"
+            replacement = "host_$1"
+            "#,
+        );
+        let metric = rename.apply(metric("node_load1", &[])).unwrap();
+        assert_eq!(metric.name(), "host_load1");
+    }
+
+    #[test]
+    fn drops_and_keeps_labels() {
+        let labeldrop = rule(
+            r#"
+            regex = "exported_.*"
+            action = "labeldrop"
+            "#,
+        );
+        let metric = labeldrop
+            .apply(metric("up", &[("exported_job", "a"), ("job", "b")]))
+            .unwrap();
+        assert_eq!(metric.tag_value("exported_job"), None);
+        assert_eq!(metric.tag_value("job"), Some("b".into()));
+
+        let labelkeep = rule(
+            r#"
+            regex = "code"
+            action = "labelkeep"
+            "#,
+        );
+        let metric = labelkeep.apply(metric).unwrap();
+        assert_eq!(metric.tags(), None);
+    }
+
+    #[test]
+    fn rules_run_in_order() {
+        let rules = vec![
+            rule(
+                r#"
+                source_labels = ["env"]
+                target_label = "stage"
+                "#,
+            ),
+            rule(
+                r#"
+                source_labels = ["stage"]
+                regex = "dev"
+                action = "drop"
+                "#,
+            ),
+        ];
+        assert!(relabel(&rules, metric("up", &[("env", "dev")])).is_none());
+        assert!(relabel(&rules, metric("up", &[("env", "prod")])).is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let config = toml::from_str::<RelabelConfig>(r#"regex = "(""#).unwrap();
+        assert!(config.build().is_err());
+
+        let config = toml::from_str::<RelabelConfig>(r#"action = "replace""#).unwrap();
+        assert!(matches!(
+            config.build(),
+            Err(RelabelError::MissingTargetLabel)
+        ));
+    }
+}
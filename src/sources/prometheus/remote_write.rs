@@ -8,7 +8,7 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::{
         self,
-        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig, MAX_DECODED_BYTES},
     },
     tls::TlsConfig,
     Event, Pipeline,
@@ -25,6 +25,12 @@ use warp::http::{HeaderMap, StatusCode};
 
 const SOURCE_NAME: &str = "prometheus_remote_write";
 
+/// The NaN Prometheus marks series that went stale with.
+const STALE_NAN: u64 = 0x7ff0_0000_0000_0002;
+
+/// The suffixes of the series of metric families, after their family name.
+const FAMILY_SUFFIXES: &[&str] = &["_total", "_bucket", "_sum", "_count", "_created"];
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct PrometheusRemoteWriteConfig {
     address: SocketAddr,
@@ -81,6 +87,7 @@ impl HttpSource for RemoteWriteSource {
         mut body: Bytes,
         header_map: HeaderMap,
         _query_parameters: HashMap<String, String>,
+        _path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
         // If `Content-Encoding` header isn't `snappy` HttpSource won't decode it for us
         // se we need to.
@@ -89,7 +96,7 @@ impl HttpSource for RemoteWriteSource {
             .map(|header| header.as_ref())
             != Some(b"snappy")
         {
-            body = decode(&Some("snappy".to_string()), body)?;
+            body = decode(&Some("snappy".to_string()), body, MAX_DECODED_BYTES)?;
         }
         let result = decode_body(body)?;
         let count = result.len();
@@ -112,29 +119,51 @@ fn decode_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
 }
 
 fn decode_request(request: proto::WriteRequest) -> Vec<Event> {
+    let types = request
+        .metadata
+        .iter()
+        .filter_map(|metadata| {
+            proto::MetricType::from_i32(metadata.r#type)
+                .map(|r#type| (metadata.metric_family_name.as_str(), r#type))
+        })
+        .collect::<HashMap<_, _>>();
+
     request
         .timeseries
         .into_iter()
-        .filter_map(decode_timeseries)
+        .filter_map(|timeseries| decode_timeseries(timeseries, &types))
         .flatten()
         .collect()
 }
 
-fn decode_timeseries(timeseries: proto::TimeSeries) -> Option<impl Iterator<Item = Event>> {
+fn decode_timeseries(
+    timeseries: proto::TimeSeries,
+    types: &HashMap<&str, proto::MetricType>,
+) -> Option<impl Iterator<Item = Event>> {
     let (name, tags) = parse_labels(timeseries.labels);
     match name {
-        Some(name) => Some(timeseries.samples.into_iter().map(move |sample| {
-            let value = sample.value;
-            let value = if name.ends_with("_total") {
-                MetricValue::Counter { value }
-            } else {
-                MetricValue::Gauge { value }
-            };
-            Metric::new(name.clone(), MetricKind::Absolute, value)
-                .with_tags(tags.clone())
-                .with_timestamp(parse_timestamp(sample.timestamp))
-                .into()
-        })),
+        Some(name) => {
+            let counter = is_counter(&name, &tags, types);
+            Some(
+                timeseries
+                    .samples
+                    .into_iter()
+                    // Stale markers end series, which metrics don't have.
+                    .filter(|sample| sample.value.to_bits() != STALE_NAN)
+                    .map(move |sample| {
+                        let value = sample.value;
+                        let value = if counter {
+                            MetricValue::Counter { value }
+                        } else {
+                            MetricValue::Gauge { value }
+                        };
+                        Metric::new(name.clone(), MetricKind::Absolute, value)
+                            .with_tags(tags.clone())
+                            .with_timestamp(parse_timestamp(sample.timestamp))
+                            .into()
+                    }),
+            )
+        }
         None => {
             emit!(PrometheusNoNameError);
             None
@@ -142,6 +171,33 @@ fn decode_timeseries(timeseries: proto::TimeSeries) -> Option<impl Iterator<Item
     }
 }
 
+/// Whether the samples of a series are those of a counter, from the type of its
+/// metric family if the request has metadata, or from its name otherwise.
+fn is_counter(
+    name: &str,
+    tags: &Option<BTreeMap<String, String>>,
+    types: &HashMap<&str, proto::MetricType>,
+) -> bool {
+    let family_type = std::iter::once(name)
+        .chain(
+            FAMILY_SUFFIXES
+                .iter()
+                .filter_map(|suffix| name.strip_suffix(suffix)),
+        )
+        .find_map(|family| types.get(family));
+
+    match family_type {
+        Some(proto::MetricType::Counter) => true,
+        // The buckets, sums and counts of histograms and summaries are
+        // counters, unlike the quantiles of summaries.
+        Some(proto::MetricType::Histogram) | Some(proto::MetricType::Summary) => !tags
+            .as_ref()
+            .map_or(false, |tags| tags.contains_key("quantile")),
+        Some(_) => false,
+        None => name.ends_with("_total"),
+    }
+}
+
 fn parse_labels(labels: Vec<proto::Label>) -> (Option<String>, Option<BTreeMap<String, String>>) {
     let mut tags = labels
         .into_iter()
@@ -228,6 +284,103 @@ mod test {
         assert_eq!(events, output);
     }
 
+    fn timeseries(labels: &[(&str, &str)], values: &[f64]) -> proto::TimeSeries {
+        proto::TimeSeries {
+            labels: labels
+                .iter()
+                .map(|(name, value)| proto::Label {
+                    name: (*name).into(),
+                    value: (*value).into(),
+                })
+                .collect(),
+            samples: values
+                .iter()
+                .map(|value| proto::Sample {
+                    value: *value,
+                    timestamp: 1_609_459_200_000,
+                })
+                .collect(),
+        }
+    }
+
+    fn metadata(name: &str, r#type: proto::MetricType) -> proto::MetricMetadata {
+        proto::MetricMetadata {
+            r#type: r#type as i32,
+            metric_family_name: name.into(),
+            help: String::new(),
+            unit: String::new(),
+        }
+    }
+
+    fn kinds(events: &[Event]) -> Vec<(&str, &str)> {
+        events
+            .iter()
+            .map(|event| {
+                let metric = event.as_metric();
+                let kind = match metric.data.value {
+                    MetricValue::Counter { .. } => "counter",
+                    MetricValue::Gauge { .. } => "gauge",
+                    _ => unreachable!(),
+                };
+                (metric.name(), kind)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn maps_types_from_metadata() {
+        let request = proto::WriteRequest {
+            timeseries: vec![
+                timeseries(&[("__name__", "requests")], &[1.0]),
+                timeseries(&[("__name__", "temperature_total")], &[2.0]),
+                timeseries(&[("__name__", "latency_bucket"), ("le", "1")], &[3.0]),
+                timeseries(&[("__name__", "latency_sum")], &[4.0]),
+                timeseries(&[("__name__", "size"), ("quantile", "0.5")], &[5.0]),
+                timeseries(&[("__name__", "size_count")], &[6.0]),
+                timeseries(&[("__name__", "errors_total")], &[7.0]),
+                timeseries(&[("__name__", "uptime")], &[8.0]),
+            ],
+            metadata: vec![
+                metadata("requests", proto::MetricType::Counter),
+                metadata("temperature_total", proto::MetricType::Gauge),
+                metadata("latency", proto::MetricType::Histogram),
+                metadata("size", proto::MetricType::Summary),
+            ],
+        };
+
+        assert_eq!(
+            kinds(&decode_request(request)),
+            vec![
+                ("requests", "counter"),
+                ("temperature_total", "gauge"),
+                ("latency_bucket", "counter"),
+                ("latency_sum", "counter"),
+                ("size", "gauge"),
+                ("size_count", "counter"),
+                ("errors_total", "counter"),
+                ("uptime", "gauge"),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_stale_markers() {
+        let request = proto::WriteRequest {
+            timeseries: vec![timeseries(
+                &[("__name__", "uptime")],
+                &[1.0, f64::from_bits(STALE_NAN), f64::NAN],
+            )],
+            metadata: vec![],
+        };
+
+        let events = decode_request(request);
+        assert_eq!(events.len(), 2);
+        match events[1].as_metric().data.value {
+            MetricValue::Gauge { value } => assert!(value.is_nan()),
+            _ => unreachable!(),
+        }
+    }
+
     fn make_events() -> Vec<Event> {
         (0..10)
             .map(|num| {
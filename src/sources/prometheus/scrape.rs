@@ -1,11 +1,17 @@
-use super::parser;
+use super::{
+    parser,
+    relabel::{self, Relabel, RelabelConfig},
+};
 use crate::{
-    config::{self, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription},
+    config::{
+        self, format, Format, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::Metric,
     http::Auth,
     http::HttpClient,
     internal_events::{
         PrometheusErrorResponse, PrometheusEventReceived, PrometheusHttpError,
-        PrometheusParseError, PrometheusRequestCompleted,
+        PrometheusParseError, PrometheusRequestCompleted, PrometheusTargetFileError,
     },
     shutdown::ShutdownSignal,
     sources,
@@ -17,29 +23,62 @@ use hyper::{Body, Request};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
+    collections::BTreeMap,
+    fs,
     future::ready,
-    time::{Duration, Instant},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
 };
 
 #[derive(Debug, Snafu)]
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display("At least one of `endpoints`, `targets` or `target_files` must be set"))]
+    NoTargets,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 struct PrometheusScrapeConfig {
     // Deprecated name
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
+    #[serde(default)]
+    targets: Vec<TargetConfig>,
+    #[serde(default)]
+    target_files: Vec<PathBuf>,
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
 
+    instance_tag: Option<String>,
+    endpoint_tag: Option<String>,
+    #[serde(default)]
+    honor_labels: bool,
+    #[serde(default)]
+    relabel_configs: Vec<RelabelConfig>,
+
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct TargetConfig {
+    endpoint: String,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+/// The contents of a file listed in `target_files`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct TargetFileConfig {
+    #[serde(default)]
+    targets: Vec<TargetConfig>,
+}
+
 pub fn default_scrape_interval_secs() -> u64 {
     15
 }
@@ -56,7 +95,13 @@ impl GenerateConfig for PrometheusScrapeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
+            targets: vec![],
+            target_files: vec![],
             scrape_interval_secs: default_scrape_interval_secs(),
+            instance_tag: Some("instance".to_string()),
+            endpoint_tag: Some("endpoint".to_string()),
+            honor_labels: false,
+            relabel_configs: vec![],
             tls: None,
             auth: None,
         })
@@ -74,17 +119,22 @@ impl SourceConfig for PrometheusScrapeConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<sources::Source> {
-        let urls = self
-            .endpoints
+        let targets = self.build_targets()?;
+        let relabels = self
+            .relabel_configs
             .iter()
-            .map(|s| s.parse::<http::Uri>().context(sources::UriParseError))
-            .collect::<Result<Vec<http::Uri>, sources::BuildError>>()?;
+            .map(RelabelConfig::build)
+            .collect::<Result<Vec<_>, _>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
         Ok(prometheus(
-            urls,
+            targets,
             tls,
             self.auth.clone(),
-            self.scrape_interval_secs,
+            Scrape {
+                interval: self.scrape_interval_secs,
+                honor_labels: self.honor_labels,
+                relabels: Arc::new(relabels),
+            },
             shutdown,
             out,
         ))
@@ -99,17 +149,169 @@ impl SourceConfig for PrometheusScrapeConfig {
     }
 }
 
+impl PrometheusScrapeConfig {
+    fn build_targets(&self) -> crate::Result<Targets> {
+        let tags = TargetTags {
+            instance: self.instance_tag.clone(),
+            endpoint: self.endpoint_tag.clone(),
+        };
+        let fixed = self
+            .endpoints
+            .iter()
+            .map(|endpoint| TargetConfig {
+                endpoint: endpoint.clone(),
+                labels: BTreeMap::new(),
+            })
+            .chain(self.targets.iter().cloned())
+            .map(|target| tags.build(target))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if fixed.is_empty() && self.target_files.is_empty() {
+            return Err(ConfigError::NoTargets.into());
+        }
+        Ok(Targets {
+            fixed,
+            files: self
+                .target_files
+                .iter()
+                .cloned()
+                .map(TargetFile::new)
+                .collect(),
+            tags,
+        })
+    }
+}
+
+/// The tags attached to the metrics of every target.
+#[derive(Clone, Debug)]
+struct TargetTags {
+    instance: Option<String>,
+    endpoint: Option<String>,
+}
+
+impl TargetTags {
+    fn build(&self, target: TargetConfig) -> Result<Target, sources::BuildError> {
+        let url = target
+            .endpoint
+            .parse::<http::Uri>()
+            .context(sources::UriParseError)?;
+        let mut labels = target.labels;
+        // Labels set on the target itself take precedence.
+        if let Some(tag) = &self.instance {
+            labels.entry(tag.clone()).or_insert_with(|| instance(&url));
+        }
+        if let Some(tag) = &self.endpoint {
+            labels.entry(tag.clone()).or_insert_with(|| url.to_string());
+        }
+        Ok(Target { url, labels })
+    }
+}
+
+/// The targets to scrape: the ones set in the config, and the ones listed in
+/// the `target_files`, which are reloaded whenever they change.
+#[derive(Debug)]
+struct Targets {
+    fixed: Vec<Target>,
+    files: Vec<TargetFile>,
+    tags: TargetTags,
+}
+
+impl Targets {
+    /// The targets of the next scrape, after reloading the changed files.
+    fn current(&mut self) -> Vec<Target> {
+        let tags = &self.tags;
+        for file in &mut self.files {
+            if let Err(error) = file.reload(tags) {
+                emit!(PrometheusTargetFileError {
+                    error,
+                    path: &file.path,
+                });
+            }
+        }
+
+        self.fixed
+            .iter()
+            .chain(self.files.iter().flat_map(|file| file.targets.iter()))
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct TargetFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    targets: Vec<Target>,
+}
+
+impl TargetFile {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Reloads the targets if the file was modified since it was last
+    /// loaded. On error the previous targets are kept.
+    fn reload(&mut self, tags: &TargetTags) -> Result<(), String> {
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|error| error.to_string())?;
+        if self.modified == Some(modified) {
+            return Ok(());
+        }
+
+        self.targets = load_target_file(&self.path)?
+            .into_iter()
+            .map(|target| tags.build(target))
+            .collect::<Result<_, _>>()
+            .map_err(|error| error.to_string())?;
+        self.modified = Some(modified);
+        Ok(())
+    }
+}
+
+fn load_target_file(path: &Path) -> Result<Vec<TargetConfig>, String> {
+    let content = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let config: TargetFileConfig = format::deserialize(&content, Format::from_path(path).ok())
+        .map_err(|errors| errors.join(", "))?;
+    Ok(config.targets)
+}
+
+/// The `host:port` of the endpoint, filling in the default port for the
+/// scheme as Prometheus does for its `instance` label.
+fn instance(url: &http::Uri) -> String {
+    let port = url.port_u16().unwrap_or_else(|| match url.scheme_str() {
+        Some("https") => 443,
+        _ => 80,
+    });
+    format!("{}:{}", url.host().unwrap_or_default(), port)
+}
+
 // Add a compatibility alias to avoid breaking existing configs
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct PrometheusCompatConfig {
     // Clone of PrometheusScrapeConfig to work around serde bug
     // https://github.com/serde-rs/serde/issues/1504
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
+    #[serde(default)]
+    targets: Vec<TargetConfig>,
+    #[serde(default)]
+    target_files: Vec<PathBuf>,
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
 
+    instance_tag: Option<String>,
+    endpoint_tag: Option<String>,
+    #[serde(default)]
+    honor_labels: bool,
+    #[serde(default)]
+    relabel_configs: Vec<RelabelConfig>,
+
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
@@ -129,7 +331,13 @@ impl SourceConfig for PrometheusCompatConfig {
         // https://github.com/serde-rs/serde/issues/1504
         PrometheusScrapeConfig {
             endpoints: self.endpoints.clone(),
+            targets: self.targets.clone(),
+            target_files: self.target_files.clone(),
             scrape_interval_secs: self.scrape_interval_secs,
+            instance_tag: self.instance_tag.clone(),
+            endpoint_tag: self.endpoint_tag.clone(),
+            honor_labels: self.honor_labels,
+            relabel_configs: self.relabel_configs.clone(),
             tls: self.tls.clone(),
             auth: self.auth.clone(),
         }
@@ -146,21 +354,64 @@ impl SourceConfig for PrometheusCompatConfig {
     }
 }
 
+#[derive(Clone, Debug)]
+struct Target {
+    url: http::Uri,
+    labels: BTreeMap<String, String>,
+}
+
+impl Target {
+    /// Attaches the target labels to a scraped metric. On conflict the
+    /// scraped value is kept if `honor_labels` is set, otherwise it is
+    /// moved to `exported_<label>` and the target label wins.
+    fn apply_labels(&self, metric: &mut Metric, honor_labels: bool) {
+        for (name, value) in &self.labels {
+            match metric.tag_value(name) {
+                Some(_) if honor_labels => continue,
+                Some(scraped) => metric.set_tag_value(format!("exported_{}", name), scraped),
+                None => (),
+            }
+            metric.set_tag_value(name.clone(), value.clone());
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Scrape {
+    interval: u64,
+    honor_labels: bool,
+    relabels: Arc<Vec<Relabel>>,
+}
+
+impl Scrape {
+    fn process(&self, target: &Target, metrics: Vec<Metric>) -> Vec<Metric> {
+        metrics
+            .into_iter()
+            .filter_map(|mut metric| {
+                target.apply_labels(&mut metric, self.honor_labels);
+                relabel::relabel(&self.relabels, metric)
+            })
+            .collect()
+    }
+}
+
 fn prometheus(
-    urls: Vec<http::Uri>,
+    mut targets: Targets,
     tls: TlsSettings,
     auth: Option<Auth>,
-    interval: u64,
+    scrape: Scrape,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> sources::Source {
     let out = out.sink_map_err(|error| error!(message = "Error sending metric.", %error));
 
-    Box::pin(tokio::time::interval(Duration::from_secs(interval))
+    Box::pin(tokio::time::interval(Duration::from_secs(scrape.interval))
         .take_until(shutdown)
-        .map(move |_| stream::iter(urls.clone()))
+        .map(move |_| stream::iter(targets.current()))
         .flatten()
-        .map(move |url| {
+        .map(move |target| {
+            let url = target.url.clone();
+            let scrape = scrape.clone();
             let client = HttpClient::new(tls.clone()).expect("Building HTTP client failed");
 
             let mut request = Request::get(&url)
@@ -193,6 +444,7 @@ fn prometheus(
 
                             match parser::parse(&body) {
                                 Ok(metrics) => {
+                                    let metrics = scrape.process(&target, metrics);
                                     emit!(PrometheusEventReceived {
                                         byte_size,
                                         count: metrics.len(),
@@ -251,6 +503,7 @@ mod test {
     use super::*;
     use crate::{
         config,
+        event::{MetricKind, MetricValue},
         sinks::prometheus::exporter::PrometheusExporterConfig,
         test_util::{next_addr, start_topology},
         Error,
@@ -267,6 +520,171 @@ mod test {
         crate::test_util::test_generate_config::<PrometheusScrapeConfig>();
     }
 
+    fn parse_config(config: &str) -> PrometheusScrapeConfig {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn builds_targets_with_labels() {
+        let config = parse_config(
+            r#"
+            endpoints = ["http://localhost/metrics"]
+            instance_tag = "instance"
+            endpoint_tag = "endpoint"
+
+            [[targets]]
+            endpoint = "https://example.com:8443/metrics"
+            labels = { env = "prod", instance = "example" }
+            "#,
+        );
+        let targets = config.build_targets().unwrap().current();
+        assert_eq!(targets.len(), 2);
+
+        assert_eq!(targets[0].labels["instance"], "localhost:80");
+        assert_eq!(targets[0].labels["endpoint"], "http://localhost/metrics");
+        assert_eq!(targets[0].labels.len(), 2);
+
+        // Labels configured on the target win over the generated ones.
+        assert_eq!(targets[1].labels["instance"], "example");
+        assert_eq!(targets[1].labels["env"], "prod");
+        assert_eq!(
+            targets[1].labels["endpoint"],
+            "https://example.com:8443/metrics"
+        );
+    }
+
+    #[test]
+    fn requires_targets() {
+        let config = parse_config("scrape_interval_secs = 1");
+        assert!(config.build_targets().is_err());
+    }
+
+    #[test]
+    fn reloads_target_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("targets.toml");
+        let mut config = parse_config(
+            r#"
+            endpoints = ["http://localhost/metrics"]
+            instance_tag = "instance"
+            "#,
+        );
+        config.target_files = vec![path.clone()];
+        let mut targets = config.build_targets().unwrap();
+
+        // A missing file doesn't stop the fixed targets from being scraped.
+        assert_eq!(targets.current().len(), 1);
+
+        fs::write(
+            &path,
+            r#"
+            [[targets]]
+            endpoint = "http://node-1:9100/metrics"
+            labels = { job = "node" }
+            "#,
+        )
+        .unwrap();
+        let current = targets.current();
+        assert_eq!(current.len(), 2);
+        assert_eq!(current[1].labels["job"], "node");
+        assert_eq!(current[1].labels["instance"], "node-1:9100");
+
+        // Let the modification time of the rewritten file differ.
+        std::thread::sleep(Duration::from_secs(1));
+        fs::write(
+            &path,
+            r#"
+            [[targets]]
+            endpoint = "http://node-1:9100/metrics"
+
+            [[targets]]
+            endpoint = "http://node-2:9100/metrics"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(targets.current().len(), 3);
+
+        // An invalid file keeps its previous targets.
+        std::thread::sleep(Duration::from_secs(1));
+        fs::write(&path, "targets = 1").unwrap();
+        assert_eq!(targets.current().len(), 3);
+    }
+
+    #[test]
+    fn target_labels_honor_labels() {
+        let target = Target {
+            url: "http://localhost:9090/metrics".parse().unwrap(),
+            labels: vec![("job".to_string(), "node".to_string())]
+                .into_iter()
+                .collect(),
+        };
+        let metric = Metric::new(
+            "up".into(),
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_tags(Some(
+            vec![("job".to_string(), "scraped".to_string())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let mut honored = metric.clone();
+        target.apply_labels(&mut honored, true);
+        assert_eq!(honored.tag_value("job"), Some("scraped".into()));
+        assert_eq!(honored.tag_value("exported_job"), None);
+
+        let mut overridden = metric;
+        target.apply_labels(&mut overridden, false);
+        assert_eq!(overridden.tag_value("job"), Some("node".into()));
+        assert_eq!(overridden.tag_value("exported_job"), Some("scraped".into()));
+    }
+
+    #[test]
+    fn relabels_after_target_labels() {
+        let config = parse_config(
+            r#"
+            endpoints = ["http://localhost:9090/metrics"]
+            instance_tag = "instance"
+
+            [[relabel_configs]]
+            source_labels = ["__name__", "instance"]
+            regex = "go_.*;localhost:9090"
+            action = "drop"
+            "#,
+        );
+        let target = config.build_targets().unwrap().remove(0);
+        let scrape = Scrape {
+            interval: 1,
+            honor_labels: false,
+            relabels: Arc::new(
+                config
+                    .relabel_configs
+                    .iter()
+                    .map(|config| config.build().unwrap())
+                    .collect(),
+            ),
+        };
+
+        let metrics = ["go_goroutines", "process_open_fds"]
+            .iter()
+            .map(|name| {
+                Metric::new(
+                    name.to_string(),
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 1.0 },
+                )
+            })
+            .collect();
+        let metrics = scrape.process(&target, metrics);
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name(), "process_open_fds");
+        assert_eq!(
+            metrics[0].tag_value("instance"),
+            Some("localhost:9090".into())
+        );
+    }
+
     #[tokio::test]
     async fn test_prometheus_routing() {
         let in_addr = next_addr();
@@ -318,7 +736,12 @@ mod test {
             "in",
             PrometheusScrapeConfig {
                 endpoints: vec![format!("http://{}", in_addr)],
+                targets: vec![],
                 scrape_interval_secs: 1,
+                instance_tag: None,
+                endpoint_tag: None,
+                honor_labels: false,
+                relabel_configs: vec![],
                 tls: None,
                 auth: None,
             },
@@ -398,7 +821,12 @@ mod integration_tests {
     async fn scrapes_metrics() {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://localhost:9090/metrics".into()],
+            targets: vec![],
             scrape_interval_secs: 1,
+            instance_tag: Some("instance".into()),
+            endpoint_tag: Some("endpoint".into()),
+            honor_labels: false,
+            relabel_configs: vec![],
             auth: None,
             tls: None,
         };
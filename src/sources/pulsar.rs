@@ -0,0 +1,374 @@
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::{finalization::BatchStatus, Event, Value},
+    internal_events::{
+        PulsarAcknowledgementFailed, PulsarEventNotDelivered, PulsarEventReceived, PulsarReadFailed,
+    },
+    pulsar::AuthConfig,
+    shutdown::ShutdownSignal,
+    sources::util::{run_acknowledged, AcknowledgedMessages},
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use pulsar::{
+    message::proto::command_subscribe::SubType, Consumer, Error as PulsarError, Message, Pulsar,
+    TokioExecutor,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::BTreeMap, path::PathBuf};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read TLS CA file {:?}: {}", path, source))]
+    ReadCaFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not create Pulsar consumer: {}", source))]
+    CreatePulsarConsumer { source: PulsarError },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PulsarSourceConfig {
+    endpoint: String,
+    topics: Vec<String>,
+    #[serde(default = "default_subscription_name")]
+    subscription_name: String,
+    #[serde(default)]
+    subscription_type: SubscriptionType,
+    consumer_name: Option<String>,
+    key_field: Option<String>,
+    topic_key: Option<String>,
+    properties_key: Option<String>,
+    auth: Option<AuthConfig>,
+    tls: Option<TlsConfig>,
+    #[serde(default)]
+    acknowledgements: bool,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionType {
+    /// Only one consumer of the subscription receives messages.
+    #[derivative(Default)]
+    Exclusive,
+    /// Messages are spread over the consumers of the subscription.
+    Shared,
+    /// One consumer of the subscription receives messages, others take over
+    /// if it disconnects.
+    Failover,
+}
+
+impl From<SubscriptionType> for SubType {
+    fn from(subscription_type: SubscriptionType) -> Self {
+        match subscription_type {
+            SubscriptionType::Exclusive => SubType::Exclusive,
+            SubscriptionType::Shared => SubType::Shared,
+            SubscriptionType::Failover => SubType::Failover,
+        }
+    }
+}
+
+/// TLS is used by `pulsar+ssl://` endpoints. The CA file verifies the
+/// certificate of the broker, instead of the system certificates.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    ca_file: PathBuf,
+}
+
+fn default_subscription_name() -> String {
+    "vector".to_owned()
+}
+
+inventory::submit! {
+    SourceDescription::new::<PulsarSourceConfig>("pulsar")
+}
+
+impl GenerateConfig for PulsarSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "pulsar://127.0.0.1:6650".to_string(),
+            topics: vec!["topic-1234".to_string()],
+            subscription_name: default_subscription_name(),
+            subscription_type: SubscriptionType::default(),
+            consumer_name: None,
+            key_field: None,
+            topic_key: None,
+            properties_key: None,
+            auth: None,
+            tls: None,
+            acknowledgements: false,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pulsar")]
+impl SourceConfig for PulsarSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let consumer = self.create_consumer().await?;
+        Ok(Box::pin(pulsar_source(
+            consumer,
+            self.clone(),
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "pulsar"
+    }
+}
+
+type PulsarConsumer = Consumer<Vec<u8>, TokioExecutor>;
+
+impl PulsarSourceConfig {
+    async fn create_consumer(&self) -> crate::Result<PulsarConsumer> {
+        let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
+        if let Some(auth) = &self.auth {
+            builder = builder.with_auth(auth.authentication());
+        }
+        if let Some(tls) = &self.tls {
+            builder = builder
+                .with_certificate_chain_file(&tls.ca_file)
+                .context(ReadCaFile {
+                    path: tls.ca_file.clone(),
+                })?;
+        }
+
+        let pulsar = builder.build().await.context(CreatePulsarConsumer)?;
+        let mut consumer = pulsar
+            .consumer()
+            .with_topics(&self.topics)
+            .with_subscription_type(self.subscription_type.into())
+            .with_subscription(&self.subscription_name);
+        if let Some(consumer_name) = &self.consumer_name {
+            consumer = consumer.with_consumer_name(consumer_name);
+        }
+
+        Ok(consumer.build().await.context(CreatePulsarConsumer)?)
+    }
+}
+
+async fn pulsar_source(
+    consumer: PulsarConsumer,
+    config: PulsarSourceConfig,
+    shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> Result<(), ()> {
+    let acknowledgements = config.acknowledgements;
+    let messages = PulsarMessages { consumer, config };
+    run_acknowledged(messages, acknowledgements, shutdown, out).await
+}
+
+struct PulsarMessages {
+    consumer: PulsarConsumer,
+    config: PulsarSourceConfig,
+}
+
+#[async_trait::async_trait]
+impl AcknowledgedMessages for PulsarMessages {
+    type Message = Message<Vec<u8>>;
+
+    async fn next(&mut self) -> Option<Result<Message<Vec<u8>>, ()>> {
+        loop {
+            match self.consumer.next().await? {
+                Ok(msg) => {
+                    emit!(PulsarEventReceived {
+                        byte_size: msg.payload.data.len()
+                    });
+                    return Some(Ok(msg));
+                }
+                Err(error) => emit!(PulsarReadFailed {
+                    error: &error.to_string()
+                }),
+            }
+        }
+    }
+
+    fn to_event(&self, msg: &Message<Vec<u8>>) -> Event {
+        message_to_event(msg, &self.config)
+    }
+
+    async fn ack(&mut self, msg: Message<Vec<u8>>) {
+        if let Err(error) = self.consumer.ack(&msg).await {
+            emit!(PulsarAcknowledgementFailed {
+                error: &error.to_string()
+            });
+        }
+    }
+
+    /// Pulsar redelivers the message.
+    async fn nack(&mut self, msg: Message<Vec<u8>>, status: BatchStatus) {
+        emit!(PulsarEventNotDelivered {
+            topic: &msg.topic,
+            status,
+        });
+        if let Err(error) = self.consumer.nack(&msg).await {
+            emit!(PulsarAcknowledgementFailed {
+                error: &error.to_string()
+            });
+        }
+    }
+}
+
+fn message_to_event(msg: &Message<Vec<u8>>, config: &PulsarSourceConfig) -> Event {
+    let metadata = msg.metadata();
+    let mut event = Event::new_empty_log();
+    let log = event.as_mut_log();
+
+    log.insert(
+        log_schema().message_key(),
+        Value::from(Bytes::from(msg.payload.data.clone())),
+    );
+
+    let timestamp = Utc
+        .timestamp_millis_opt(metadata.publish_time as i64)
+        .latest()
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    log.insert(log_schema().source_type_key(), Bytes::from("pulsar"));
+
+    if let (Some(key_field), Some(key)) = (&config.key_field, &metadata.partition_key) {
+        log.insert(key_field, key.clone());
+    }
+
+    if let Some(topic_key) = &config.topic_key {
+        log.insert(topic_key, msg.topic.clone());
+    }
+
+    if let Some(properties_key) = &config.properties_key {
+        let properties = metadata
+            .properties
+            .iter()
+            .map(|property| (property.key.clone(), Value::from(property.value.clone())))
+            .collect::<BTreeMap<_, _>>();
+        log.insert(properties_key, properties);
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PulsarSourceConfig>();
+    }
+
+    #[test]
+    fn parse_subscription_type() {
+        let config: PulsarSourceConfig = toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topics = ["logs"]
+            subscription_type = "failover"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.subscription_name, "vector");
+        assert_eq!(config.subscription_type, SubscriptionType::Failover);
+        assert!(!config.acknowledgements);
+    }
+}
+
+#[cfg(feature = "pulsar-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+
+    async fn send_messages(topic: &str, messages: &[&str]) {
+        let pulsar = Pulsar::<TokioExecutor>::builder("pulsar://127.0.0.1:6650", TokioExecutor)
+            .build()
+            .await
+            .unwrap();
+        let mut producer = pulsar.producer().with_topic(topic).build().await.unwrap();
+        for message in messages {
+            producer
+                .send(message.as_bytes().to_vec())
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+        }
+    }
+
+    fn make_config(topic: &str, acknowledgements: bool) -> PulsarSourceConfig {
+        PulsarSourceConfig {
+            endpoint: "pulsar://127.0.0.1:6650".to_owned(),
+            topics: vec![topic.to_owned()],
+            subscription_name: format!("sub-{}", random_string(10)),
+            subscription_type: SubscriptionType::Shared,
+            consumer_name: None,
+            key_field: None,
+            topic_key: Some("topic".to_owned()),
+            properties_key: None,
+            auth: None,
+            tls: None,
+            acknowledgements,
+        }
+    }
+
+    async fn consume(acknowledgements: bool) {
+        trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = make_config(&topic, acknowledgements);
+        let consumer = config.create_consumer().await.unwrap();
+        send_messages(&topic, &["one", "two"]).await;
+
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        tokio::spawn(pulsar_source(consumer, config, shutdown, tx));
+
+        let events = collect_n(rx, 2).await;
+        drop(trigger);
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two"]);
+        // Pulsar reports the fully qualified name of the topic.
+        assert!(events[0].as_log()["topic"]
+            .to_string_lossy()
+            .ends_with(&topic));
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "pulsar".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn pulsar_source_consumes() {
+        consume(false).await;
+    }
+
+    #[tokio::test]
+    async fn pulsar_source_consumes_with_acknowledgements() {
+        consume(true).await;
+    }
+}
@@ -125,6 +125,7 @@ impl SourceConfig for SocketConfig {
                 Ok(unix::unix_datagram(
                     config.path,
                     config.max_length,
+                    config.delimiter,
                     host_key,
                     shutdown,
                     out,
@@ -138,6 +139,7 @@ impl SourceConfig for SocketConfig {
                 Ok(unix::unix_stream(
                     config.path,
                     config.max_length,
+                    config.delimiter,
                     host_key,
                     shutdown,
                     out,
@@ -173,15 +175,19 @@ mod test {
         config::{log_schema, GlobalOptions, SinkContext, SourceConfig},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
+        sources::util::Delimiter,
         test_util::{
-            collect_n, next_addr, random_string, send_lines, send_lines_tls, wait_for_tcp,
+            collect_n, next_addr, random_string, send_encodable, send_lines, send_lines_tls,
+            wait_for_tcp,
         },
         tls::{self, TlsConfig, TlsOptions},
         Event, Pipeline,
     };
     use bytes::Bytes;
+    use codec::BytesDelimitedCodec;
     use futures::{stream, StreamExt};
     use std::{
+        convert::TryFrom,
         net::{SocketAddr, UdpSocket},
         sync::{
             atomic::{AtomicBool, Ordering},
@@ -303,6 +309,46 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn tcp_custom_delimiter() {
+        let (tx, mut rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_delimiter(Delimiter::try_from("\0".to_owned()).unwrap());
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        let records = vec!["first\nrecord".to_owned(), "second".to_owned()];
+
+        wait_for_tcp(addr).await;
+        send_encodable(
+            addr,
+            BytesDelimitedCodec::new_with_sequence(&b"\0"[..], usize::MAX),
+            records,
+        )
+        .await
+        .unwrap();
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "first\nrecord".into()
+        );
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(event.as_log()[log_schema().message_key()], "second".into());
+    }
+
     #[tokio::test]
     async fn tcp_with_tls() {
         let (tx, mut rx) = Pipeline::new_test();
@@ -1,7 +1,7 @@
 use crate::{
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
-    sources::util::{SocketListenAddr, TcpSource},
+    sources::util::{Delimiter, SocketListenAddr, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::TlsConfig,
 };
@@ -19,6 +19,9 @@ pub struct TcpConfig {
     #[serde(default = "default_max_length")]
     #[getset(get_copy = "pub", set = "pub")]
     max_length: usize,
+    #[serde(default)]
+    #[getset(get = "pub", set = "pub")]
+    delimiter: Delimiter,
     #[serde(default = "default_shutdown_timeout_secs")]
     #[getset(get_copy = "pub", set = "pub")]
     shutdown_timeout_secs: u64,
@@ -52,6 +55,7 @@ impl TcpConfig {
             address,
             keepalive,
             max_length,
+            delimiter: Delimiter::default(),
             shutdown_timeout_secs,
             host_key,
             tls,
@@ -64,6 +68,7 @@ impl TcpConfig {
             address,
             keepalive: None,
             max_length: default_max_length(),
+            delimiter: Delimiter::default(),
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             host_key: None,
             tls: None,
@@ -82,7 +87,7 @@ impl TcpSource for RawTcpSource {
     type Decoder = BytesDelimitedCodec;
 
     fn decoder(&self) -> Self::Decoder {
-        BytesDelimitedCodec::new_with_max_length(b'\n', self.config.max_length)
+        self.config.delimiter.bytes_codec(self.config.max_length)
     }
 
     fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
@@ -131,4 +136,25 @@ mod test {
         assert_eq!(with.max_length, 19);
         assert_eq!(without.max_length, super::default_max_length());
     }
+
+    #[test]
+    fn tcp_it_defaults_delimiter() {
+        let with: super::TcpConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:1234"
+            delimiter = "\r\n"
+            "#,
+        )
+        .unwrap();
+
+        let without: super::TcpConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:1234"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(with.delimiter().bytes_codec(1).delimiter(), b"\r\n");
+        assert_eq!(without.delimiter, super::Delimiter::default());
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     internal_events::{SocketEventReceived, SocketMode},
     shutdown::ShutdownSignal,
     sources::{
-        util::{build_unix_datagram_source, build_unix_stream_source},
+        util::{build_unix_datagram_source, build_unix_stream_source, Delimiter},
         Source,
     },
     Pipeline,
@@ -11,7 +11,6 @@ use crate::{
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tokio_util::codec::LinesCodec;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -19,6 +18,8 @@ pub struct UnixConfig {
     pub path: PathBuf,
     #[serde(default = "default_max_length")]
     pub max_length: usize,
+    #[serde(default)]
+    pub delimiter: Delimiter,
     pub host_key: Option<String>,
 }
 
@@ -31,6 +32,7 @@ impl UnixConfig {
         Self {
             path,
             max_length: default_max_length(),
+            delimiter: Delimiter::default(),
             host_key: None,
         }
     }
@@ -60,6 +62,7 @@ fn build_event(host_key: &str, received_from: Option<Bytes>, line: &str) -> Opti
 pub(super) fn unix_datagram(
     path: PathBuf,
     max_length: usize,
+    delimiter: Delimiter,
     host_key: String,
     shutdown: ShutdownSignal,
     out: Pipeline,
@@ -68,7 +71,7 @@ pub(super) fn unix_datagram(
         path,
         max_length,
         host_key,
-        LinesCodec::new_with_max_length(max_length),
+        delimiter.string_codec(max_length),
         shutdown,
         out,
         build_event,
@@ -78,13 +81,14 @@ pub(super) fn unix_datagram(
 pub(super) fn unix_stream(
     path: PathBuf,
     max_length: usize,
+    delimiter: Delimiter,
     host_key: String,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
     build_unix_stream_source(
         path,
-        LinesCodec::new_with_max_length(max_length),
+        delimiter.string_codec(max_length),
         host_key,
         shutdown,
         out,
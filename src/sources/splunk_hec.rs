@@ -1,9 +1,12 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{
+        finalization::{BatchNotifier, BatchStatus, BatchStatusReceiver},
+        Event, LogEvent, Value,
+    },
     internal_events::{
-        SplunkHECEventReceived, SplunkHECRequestBodyInvalid, SplunkHECRequestError,
-        SplunkHECRequestReceived,
+        SplunkHECEventReceived, SplunkHECEventsNotDelivered, SplunkHECRequestBodyInvalid,
+        SplunkHECRequestError, SplunkHECRequestReceived,
     },
     shutdown::ShutdownSignal,
     tls::{MaybeTlsSettings, TlsConfig},
@@ -12,16 +15,19 @@ use crate::{
 use bytes::{buf::BufExt, Bytes};
 use chrono::{DateTime, TimeZone, Utc};
 use flate2::read::GzDecoder;
-use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt, TryStreamExt};
 use futures01::{Async, Stream};
 use http::StatusCode;
 use serde::{de, Deserialize, Serialize};
 use serde_json::{de::IoRead, json, Deserializer, Value as JsonValue};
 use snafu::Snafu;
 use std::{
-    future,
+    collections::{BTreeMap, HashMap, HashSet},
+    future::{self, Future},
     io::Read,
     net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use warp::{filters::BoxedFilter, path, reject::Rejection, reply::Response, Filter, Reply};
@@ -42,6 +48,15 @@ pub struct SplunkConfig {
     /// Splunk HEC token
     token: Option<String>,
     tls: Option<TlsConfig>,
+    /// Emulate the indexer acknowledgements of Splunk
+    acknowledgements: bool,
+    /// The most channels acknowledgements are kept for at once
+    max_ack_channels: usize,
+    /// The most acknowledgement IDs a channel may have outstanding, until its
+    /// client queries them
+    max_pending_acks_per_channel: usize,
+    /// How long the acknowledgements of a channel without requests are kept
+    ack_idle_timeout_secs: u64,
 }
 
 inventory::submit! {
@@ -66,6 +81,10 @@ impl Default for SplunkConfig {
             address: default_socket_address(),
             token: None,
             tls: None,
+            acknowledgements: false,
+            max_ack_channels: 10_000,
+            max_pending_acks_per_channel: 1_000,
+            ack_idle_timeout_secs: 300,
         }
     }
 }
@@ -89,6 +108,7 @@ impl SourceConfig for SplunkConfig {
         let event_service = source.event_service(out.clone());
         let raw_service = source.raw_service(out.clone());
         let health_service = source.health_service();
+        let ack_service = source.ack_service();
         let options = SplunkSource::options();
 
         let services = path!("services" / "collector" / ..)
@@ -107,6 +127,8 @@ impl SourceConfig for SplunkConfig {
                     .unify()
                     .or(health_service)
                     .unify()
+                    .or(ack_service)
+                    .unify()
                     .or(options)
                     .unify(),
             )
@@ -144,6 +166,7 @@ impl SourceConfig for SplunkConfig {
 /// Shared data for responding to requests.
 struct SplunkSource {
     credentials: Option<Bytes>,
+    acknowledgements: Option<Arc<Acknowledgements>>,
 }
 
 impl SplunkSource {
@@ -153,10 +176,16 @@ impl SplunkSource {
                 .token
                 .as_ref()
                 .map(|token| format!("Splunk {}", token).into()),
+            acknowledgements: if config.acknowledgements {
+                Some(Arc::new(Acknowledgements::new(config)))
+            } else {
+                None
+            },
         }
     }
 
     fn event_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements.clone();
         warp::post()
             .and(path!("event").or(path!("event" / "1.0")))
             .and(self.authorization())
@@ -171,7 +200,11 @@ impl SplunkSource {
                       host: Option<String>,
                       gzip: bool,
                       body: Bytes| {
-                    process_service_request(out.clone(), channel, host, gzip, body)
+                    let out = out.clone();
+                    let acknowledgements = acknowledgements.clone();
+                    with_acknowledgement(acknowledgements, channel.clone(), move |batch| {
+                        process_service_request(out, channel, host, gzip, body, batch)
+                    })
                 },
             )
             .map(finish_ok)
@@ -179,42 +212,60 @@ impl SplunkSource {
     }
 
     fn raw_service(&self, out: Pipeline) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements.clone();
         warp::post()
             .and(path!("raw" / "1.0").or(path!("raw")))
             .and(self.authorization())
-            .and(
-                warp::header::optional::<String>("x-splunk-request-channel").and_then(
-                    |channel: Option<String>| async {
-                        if let Some(channel) = channel {
-                            Ok(channel)
-                        } else {
-                            Err(Rejection::from(ApiError::MissingChannel))
-                        }
-                    },
-                ),
-            )
+            .and(Self::required_channel())
             .and(warp::header::optional::<String>("host"))
             .and(self.gzip())
             .and(warp::body::bytes())
             .and_then(
                 move |_, _, channel: String, host: Option<String>, gzip: bool, body: Bytes| {
                     let out = out.clone();
-                    async move {
-                        // Construct event parser
-                        let event = future::ready(raw_event(body, gzip, channel, host));
-                        futures::stream::once(event)
-                            .forward(
-                                out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
-                            )
-                            .map_ok(|_| ())
-                            .await
-                    }
+                    let acknowledgements = acknowledgements.clone();
+                    with_acknowledgement(acknowledgements, Some(channel.clone()), move |batch| {
+                        async move {
+                            // Construct event parser
+                            let event = raw_event(body, gzip, channel, host)
+                                .map(|event| add_batch_notifier(event, &batch));
+                            futures::stream::once(future::ready(event))
+                                .forward(
+                                    out.sink_map_err(|_| Rejection::from(ApiError::ServerShutdown)),
+                                )
+                                .map_ok(|_| ())
+                                .await
+                        }
+                    })
                 },
             )
             .map(finish_ok)
             .boxed()
     }
 
+    /// Reports which requests of a channel are acknowledged, as Splunk
+    /// forwarders query with `useACK` enabled.
+    fn ack_service(&self) -> BoxedFilter<(Response,)> {
+        let acknowledgements = self.acknowledgements.clone();
+        warp::post()
+            .and(path!("ack"))
+            .and(self.authorization())
+            .and(Self::required_channel())
+            .and(warp::body::bytes())
+            .and_then(move |_, channel: String, body: Bytes| {
+                let acknowledgements = acknowledgements.clone();
+                async move {
+                    let acknowledgements =
+                        acknowledgements.ok_or_else(|| Rejection::from(ApiError::AckIsDisabled))?;
+                    let request = serde_json::from_slice::<AckRequest>(&body)
+                        .map_err(|_| Rejection::from(ApiError::InvalidDataFormat { event: 0 }))?;
+                    let acks = acknowledgements.query(&channel, &request.acks);
+                    Ok::<_, Rejection>(response_json(StatusCode::OK, json!({ "acks": acks })))
+                }
+            })
+            .boxed()
+    }
+
     fn health_service(&self) -> BoxedFilter<(Response,)> {
         let credentials = self.credentials.clone();
         let authorize =
@@ -244,7 +295,8 @@ impl SplunkSource {
                 path!("event")
                     .or(path!("event" / "1.0"))
                     .or(path!("raw" / "1.0"))
-                    .or(path!("raw")),
+                    .or(path!("raw"))
+                    .or(path!("ack")),
             )
             .map(|_| warp::reply::with_header(warp::reply(), "Allow", "POST").into_response());
 
@@ -275,6 +327,19 @@ impl SplunkSource {
             .boxed()
     }
 
+    /// Channel of the request, which is required
+    fn required_channel() -> BoxedFilter<(String,)> {
+        warp::header::optional::<String>("x-splunk-request-channel")
+            .and_then(|channel: Option<String>| async {
+                if let Some(channel) = channel {
+                    Ok(channel)
+                } else {
+                    Err(Rejection::from(ApiError::MissingChannel))
+                }
+            })
+            .boxed()
+    }
+
     /// Is body encoded with gzip
     fn gzip(&self) -> BoxedFilter<(bool,)> {
         warp::header::optional::<String>("Content-Encoding")
@@ -295,6 +360,7 @@ async fn process_service_request(
     host: Option<String>,
     gzip: bool,
     body: Bytes,
+    batch: Option<Arc<BatchNotifier>>,
 ) -> Result<(), Rejection> {
     use futures::compat::Stream01CompatExt;
 
@@ -306,7 +372,9 @@ async fn process_service_request(
         Box::new(body.reader())
     };
 
-    let stream = EventStream::new(reader, channel, host).compat();
+    let stream = EventStream::new(reader, channel, host)
+        .compat()
+        .map_ok(move |event| add_batch_notifier(event, &batch));
 
     let res = stream.forward(&mut out).await;
 
@@ -317,6 +385,198 @@ async fn process_service_request(
     res.map(|_| ())
 }
 
+/// Sends the events of a request with `send`, which attaches them to the
+/// batch it's given. With acknowledgements enabled, the batch gets an
+/// acknowledgement ID of the channel of the request, which is returned.
+async fn with_acknowledgement<F, Fut>(
+    acknowledgements: Option<Arc<Acknowledgements>>,
+    channel: Option<String>,
+    send: F,
+) -> Result<Option<u64>, Rejection>
+where
+    F: FnOnce(Option<Arc<BatchNotifier>>) -> Fut,
+    Fut: Future<Output = Result<(), Rejection>>,
+{
+    let acknowledgements = match acknowledgements {
+        Some(acknowledgements) => acknowledgements,
+        None => return send(None).await.map(|()| None),
+    };
+    let channel = channel.ok_or_else(|| Rejection::from(ApiError::MissingChannel))?;
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let ack_id = acknowledgements.register(&channel, receiver)?;
+    match send(Some(batch)).await {
+        Ok(()) => Ok(Some(ack_id)),
+        Err(rejection) => {
+            // The client won't query the ID of a failed request.
+            acknowledgements.forget(&channel, ack_id);
+            Err(rejection)
+        }
+    }
+}
+
+fn add_batch_notifier(event: Event, batch: &Option<Arc<BatchNotifier>>) -> Event {
+    match batch {
+        Some(batch) => event.with_batch_notifier(batch),
+        None => event,
+    }
+}
+
+#[derive(Deserialize)]
+struct AckRequest {
+    acks: Vec<u64>,
+}
+
+/// Emulates the indexer acknowledgements of Splunk. Each request to a channel
+/// gets an acknowledgement ID, which is acknowledged once the events of the
+/// request are delivered, until the client of the channel queries it.
+///
+/// Channels are named by clients, so their number is bounded, and channels
+/// without requests for `idle_timeout` are forgotten along with their IDs.
+struct Acknowledgements {
+    channels: Mutex<AckChannels>,
+    max_channels: usize,
+    max_pending_per_channel: usize,
+    idle_timeout: Duration,
+}
+
+struct AckChannels {
+    channels: HashMap<String, AckChannel>,
+    /// When idle channels were last forgotten
+    swept: Instant,
+}
+
+struct AckChannel {
+    next_id: u64,
+    /// IDs whose events aren't finalized yet
+    pending: HashSet<u64>,
+    /// IDs whose events were delivered, until they're queried
+    delivered: HashSet<u64>,
+    /// When the channel last got a request
+    last_used: Instant,
+}
+
+impl AckChannel {
+    fn new(now: Instant) -> Self {
+        Self {
+            next_id: 0,
+            pending: HashSet::new(),
+            delivered: HashSet::new(),
+            last_used: now,
+        }
+    }
+}
+
+impl AckChannels {
+    /// Forgets the channels idle for `idle_timeout`, at most once per
+    /// `idle_timeout`.
+    fn sweep(&mut self, now: Instant, idle_timeout: Duration) {
+        if now.duration_since(self.swept) < idle_timeout {
+            return;
+        }
+        self.swept = now;
+        self.channels
+            .retain(|_, channel| now.duration_since(channel.last_used) < idle_timeout);
+    }
+}
+
+impl Acknowledgements {
+    fn new(config: &SplunkConfig) -> Self {
+        Self {
+            channels: Mutex::new(AckChannels {
+                channels: HashMap::new(),
+                swept: Instant::now(),
+            }),
+            max_channels: config.max_ack_channels,
+            max_pending_per_channel: config.max_pending_acks_per_channel,
+            idle_timeout: Duration::from_secs(config.ack_idle_timeout_secs),
+        }
+    }
+
+    /// Gets the next ID of `channel`, which is acknowledged once `receiver`
+    /// reports that its events are delivered.
+    fn register(
+        self: &Arc<Self>,
+        channel: &str,
+        receiver: BatchStatusReceiver,
+    ) -> Result<u64, Rejection> {
+        let ack_id = {
+            let now = Instant::now();
+            let mut channels = self.channels.lock().unwrap();
+            channels.sweep(now, self.idle_timeout);
+            let channels = &mut channels.channels;
+            if !channels.contains_key(channel) && channels.len() >= self.max_channels {
+                return Err(ApiError::ServerBusy.into());
+            }
+            let ack_channel = channels
+                .entry(channel.to_owned())
+                .or_insert_with(|| AckChannel::new(now));
+            ack_channel.last_used = now;
+            if ack_channel.pending.len() + ack_channel.delivered.len()
+                >= self.max_pending_per_channel
+            {
+                return Err(ApiError::ServerBusy.into());
+            }
+            let ack_id = ack_channel.next_id;
+            ack_channel.next_id += 1;
+            ack_channel.pending.insert(ack_id);
+            ack_id
+        };
+
+        let acknowledgements = Arc::clone(self);
+        let channel = channel.to_owned();
+        tokio::spawn(async move {
+            // Notifiers always report a status before being dropped.
+            let status = receiver.await.unwrap_or(BatchStatus::Errored);
+            if status != BatchStatus::Delivered {
+                emit!(SplunkHECEventsNotDelivered {
+                    channel: &channel,
+                    ack_id,
+                    status,
+                });
+            }
+
+            let mut channels = acknowledgements.channels.lock().unwrap();
+            if let Some(ack_channel) = channels.channels.get_mut(&channel) {
+                // Undelivered IDs are never acknowledged, so that the client
+                // eventually sends their events again.
+                if ack_channel.pending.remove(&ack_id) && status == BatchStatus::Delivered {
+                    ack_channel.delivered.insert(ack_id);
+                }
+            }
+        });
+
+        Ok(ack_id)
+    }
+
+    fn forget(&self, channel: &str, ack_id: u64) {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(ack_channel) = channels.channels.get_mut(channel) {
+            ack_channel.pending.remove(&ack_id);
+            ack_channel.delivered.remove(&ack_id);
+        }
+    }
+
+    /// Returns which of `ack_ids` are acknowledged. Those are forgotten, as
+    /// clients stop querying them.
+    fn query(&self, channel: &str, ack_ids: &[u64]) -> BTreeMap<u64, bool> {
+        let mut channels = self.channels.lock().unwrap();
+        let mut ack_channel = channels.channels.get_mut(channel);
+        if let Some(ack_channel) = ack_channel.as_mut() {
+            ack_channel.last_used = Instant::now();
+        }
+        ack_ids
+            .iter()
+            .map(|ack_id| {
+                let acknowledged = ack_channel
+                    .as_mut()
+                    .map_or(false, |ack_channel| ack_channel.delivered.remove(ack_id));
+                (*ack_id, acknowledged)
+            })
+            .collect()
+    }
+}
+
 /// Constructs one ore more events from json-s coming from reader.
 /// If errors, it's done with input.
 struct EventStream<R: Read> {
@@ -630,6 +890,8 @@ pub(crate) enum ApiError {
     EmptyEventField { event: usize },
     MissingEventField { event: usize },
     BadRequest,
+    ServerBusy,
+    AckIsDisabled,
 }
 
 impl From<ApiError> for Rejection {
@@ -665,11 +927,21 @@ mod splunk_response {
             json_to_bytes(json!({"text":"unsupported content encoding"}));
         pub static ref NO_CHANNEL: Bytes =
             json_to_bytes(json!({"text":"Data channel is missing","code":10}));
+        pub static ref SERVER_BUSY: Bytes =
+            json_to_bytes(json!({"text":"Server is busy","code":9}));
+        pub static ref ACK_IS_DISABLED: Bytes =
+            json_to_bytes(json!({"text":"ACK is disabled","code":14}));
     }
 }
 
-fn finish_ok(_: ()) -> Response {
-    response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref())
+fn finish_ok(ack_id: Option<u64>) -> Response {
+    match ack_id {
+        Some(ack_id) => response_json(
+            StatusCode::OK,
+            json!({"text":"Success","code":0,"ackId":ack_id}),
+        ),
+        None => response_json(StatusCode::OK, splunk_response::SUCCESS.as_ref()),
+    }
 }
 
 async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
@@ -707,6 +979,14 @@ async fn finish_err(rejection: Rejection) -> Result<(Response,), Rejection> {
                 event_error("Event field is required", 12, event)
             }
             ApiError::BadRequest => empty_response(StatusCode::BAD_REQUEST),
+            ApiError::ServerBusy => response_json(
+                StatusCode::SERVICE_UNAVAILABLE,
+                splunk_response::SERVER_BUSY.as_ref(),
+            ),
+            ApiError::AckIsDisabled => response_json(
+                StatusCode::BAD_REQUEST,
+                splunk_response::ACK_IS_DISABLED.as_ref(),
+            ),
         },))
     } else {
         Err(rejection)
@@ -748,13 +1028,16 @@ fn event_error(text: &str, code: u16, event: usize) -> Response {
 #[cfg(feature = "sinks-splunk_hec")]
 #[cfg(test)]
 mod tests {
-    use super::{parse_timestamp, SplunkConfig};
+    use super::{parse_timestamp, Acknowledgements, SplunkConfig};
     use crate::{
         config::{log_schema, GlobalOptions, SinkConfig, SinkContext, SourceConfig},
-        event::Event,
+        event::{
+            finalization::{BatchNotifier, EventStatus},
+            Event,
+        },
         shutdown::ShutdownSignal,
         sinks::{
-            splunk_hec::{Encoding, HecSinkConfig},
+            splunk_hec::{Encoding, EndpointTarget, HecSinkConfig},
             util::{encoding::EncodingConfig, BatchConfig, Compression, TowerRequestConfig},
             Healthcheck, VectorSink,
         },
@@ -763,7 +1046,8 @@ mod tests {
     };
     use chrono::{TimeZone, Utc};
     use futures::{stream, StreamExt};
-    use std::{future::ready, net::SocketAddr};
+    use serde_json::{json, Value as JsonValue};
+    use std::{future::ready, net::SocketAddr, sync::Arc, time::Duration};
     use tokio::sync::mpsc;
 
     #[test]
@@ -775,10 +1059,13 @@ mod tests {
     const TOKEN: &str = "token";
 
     async fn source() -> (mpsc::Receiver<Event>, SocketAddr) {
-        source_with(Some(TOKEN.to_owned())).await
+        source_with(Some(TOKEN.to_owned()), false).await
     }
 
-    async fn source_with(token: Option<String>) -> (mpsc::Receiver<Event>, SocketAddr) {
+    async fn source_with(
+        token: Option<String>,
+        acknowledgements: bool,
+    ) -> (mpsc::Receiver<Event>, SocketAddr) {
         let (sender, recv) = Pipeline::new_test();
         let address = next_addr();
         tokio::spawn(async move {
@@ -786,6 +1073,8 @@ mod tests {
                 address,
                 token,
                 tls: None,
+                acknowledgements,
+                ..SplunkConfig::default()
             }
             .build(
                 "default",
@@ -815,6 +1104,8 @@ mod tests {
             index: None,
             sourcetype: None,
             source: None,
+            endpoint_target: EndpointTarget::Event,
+            channel: None,
             encoding: encoding.into(),
             compression,
             batch: BatchConfig::default(),
@@ -872,6 +1163,19 @@ mod tests {
             .as_u16()
     }
 
+    async fn post_json(address: SocketAddr, api: &str, message: &str) -> (u16, JsonValue) {
+        let response = reqwest::Client::new()
+            .post(&format!("http://{}/{}", address, api))
+            .header("Authorization", format!("Splunk {}", TOKEN))
+            .header("x-splunk-request-channel", "guid")
+            .body(message.to_owned())
+            .send()
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+
     #[tokio::test]
     async fn no_compression_text_event() {
         trace_init();
@@ -1047,7 +1351,7 @@ mod tests {
         trace_init();
 
         let message = "no_authorization";
-        let (source, address) = source_with(None).await;
+        let (source, address) = source_with(None, false).await;
         let (sink, health) = sink(address, Encoding::Text, Compression::gzip_default()).await;
         assert!(health.await.is_ok());
 
@@ -1110,6 +1414,111 @@ mod tests {
         assert_eq!(events[2].as_log()[&super::SOURCE], "secondary".into());
     }
 
+    #[tokio::test]
+    async fn acknowledgements() {
+        trace_init();
+
+        let (mut source, address) = source_with(Some(TOKEN.to_owned()), true).await;
+
+        let (status, body) =
+            post_json(address, "services/collector/event", r#"{"event":"first"}"#).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["ackId"], json!(0));
+        let (status, body) = post_json(address, "services/collector/raw", "second").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["ackId"], json!(1));
+
+        // Only the delivered request is acknowledged.
+        drop(source.recv().await.unwrap());
+        let mut event = source.recv().await.unwrap();
+        event.take_finalizers().update_status(EventStatus::Errored);
+        drop(event);
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let (status, body) =
+            post_json(address, "services/collector/ack", r#"{"acks":[0,1,2]}"#).await;
+        assert_eq!(status, 200);
+        assert_eq!(body, json!({"acks": {"0": true, "1": false, "2": false}}));
+
+        // Acknowledged IDs are forgotten once queried.
+        let (_, body) = post_json(address, "services/collector/ack", r#"{"acks":[0]}"#).await;
+        assert_eq!(body, json!({"acks": {"0": false}}));
+    }
+
+    fn acknowledgements_with(
+        max_ack_channels: usize,
+        max_pending_acks_per_channel: usize,
+        ack_idle_timeout_secs: u64,
+    ) -> Arc<Acknowledgements> {
+        Arc::new(Acknowledgements::new(&SplunkConfig {
+            acknowledgements: true,
+            max_ack_channels,
+            max_pending_acks_per_channel,
+            ack_idle_timeout_secs,
+            ..SplunkConfig::default()
+        }))
+    }
+
+    fn register(acknowledgements: &Arc<Acknowledgements>, channel: &str) -> bool {
+        let (_batch, receiver) = BatchNotifier::new_with_receiver();
+        acknowledgements.register(channel, receiver).is_ok()
+    }
+
+    #[tokio::test]
+    async fn acknowledgements_bound_channels_and_ids() {
+        let acknowledgements = acknowledgements_with(2, 2, 300);
+
+        assert!(register(&acknowledgements, "a"));
+        assert!(register(&acknowledgements, "a"));
+        assert!(!register(&acknowledgements, "a"));
+
+        assert!(register(&acknowledgements, "b"));
+        assert!(!register(&acknowledgements, "c"));
+    }
+
+    #[tokio::test]
+    async fn acknowledgements_forget_idle_channels() {
+        let acknowledgements = acknowledgements_with(1, 10, 0);
+
+        assert!(register(&acknowledgements, "a"));
+        assert!(register(&acknowledgements, "b"));
+        let channels = acknowledgements.channels.lock().unwrap();
+        assert_eq!(
+            channels.channels.keys().collect::<Vec<_>>(),
+            vec![&"b".to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn acknowledgements_disabled() {
+        trace_init();
+
+        let (_source, address) = source().await;
+
+        assert_eq!(
+            400,
+            post(address, "services/collector/ack", r#"{"acks":[0]}"#).await
+        );
+    }
+
+    #[tokio::test]
+    async fn acknowledgements_require_channel() {
+        trace_init();
+
+        let (_source, address) = source_with(Some(TOKEN.to_owned()), true).await;
+
+        let status = reqwest::Client::new()
+            .post(&format!("http://{}/services/collector/event", address))
+            .header("Authorization", format!("Splunk {}", TOKEN))
+            .body(r#"{"event":"first"}"#)
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16();
+        assert_eq!(status, 400);
+    }
+
     #[test]
     fn parse_timestamps() {
         let cases = vec![
@@ -21,7 +21,7 @@ pub mod parser;
 #[cfg(unix)]
 mod unix;
 
-use parser::parse;
+use parser::parse_line;
 #[cfg(unix)]
 use unix::{statsd_unix, UnixConfig};
 
@@ -122,7 +122,8 @@ impl SourceConfig for StatsdConfig {
     }
 
     fn output_type(&self) -> config::DataType {
-        config::DataType::Metric
+        // DogStatsD events and service checks are logs.
+        config::DataType::Any
     }
 
     fn source_type(&self) -> &'static str {
@@ -140,12 +141,12 @@ impl SourceConfig for StatsdConfig {
 }
 
 pub(self) fn parse_event(line: &str) -> Option<Event> {
-    match parse(line) {
-        Ok(metric) => {
+    match parse_line(line) {
+        Ok(event) => {
             emit!(StatsdEventReceived {
                 byte_size: line.len()
             });
-            Some(Event::Metric(metric))
+            Some(event)
         }
         Err(error) => {
             emit!(StatsdInvalidRecord { error, text: line });
@@ -222,7 +223,7 @@ mod test {
     use crate::{
         config,
         sinks::prometheus::exporter::PrometheusExporterConfig,
-        test_util::{next_addr, start_topology},
+        test_util::{collect_n, next_addr, start_topology},
     };
     use hyper::body::to_bytes as body_to_bytes;
     use tokio::io::AsyncWriteExt;
@@ -234,6 +235,45 @@ mod test {
         crate::test_util::test_generate_config::<StatsdConfig>();
     }
 
+    #[tokio::test]
+    async fn outputs_events_and_service_checks_as_logs() {
+        let addr = next_addr();
+        let (tx, rx) = Pipeline::new_test();
+        let server = StatsdConfig::Udp(UdpConfig::from_address(addr))
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        let mut socket = UdpSocket::bind(next_addr()).await.unwrap();
+        let packet = b"_e{6,9}:Deploy|Version 2|t:info\n_sc|redis.can_connect|2|m:Timed out\n";
+        // The source may not be listening yet.
+        let mut interval = tokio::time::interval(Duration::from_millis(50));
+        let received = collect_n(rx, 2);
+        tokio::pin!(received);
+        let events = loop {
+            tokio::select! {
+                events = &mut received => break events,
+                _ = interval.tick() => {
+                    socket.send_to(packet, &addr).await.unwrap();
+                }
+            }
+        };
+
+        let event = events[0].as_log();
+        assert_eq!(event["type"], "event".into());
+        assert_eq!(event["title"], "Deploy".into());
+        let check = events[1].as_log();
+        assert_eq!(check["type"], "service_check".into());
+        assert_eq!(check["name"], "redis.can_connect".into());
+        assert_eq!(check["status"], 2.into());
+    }
+
     fn parse_count(lines: &[&str], prefix: &str) -> usize {
         lines
             .iter()
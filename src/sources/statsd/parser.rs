@@ -1,4 +1,11 @@
-use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+use crate::{
+    config::log_schema,
+    event::{
+        metric::{Metric, MetricKind, MetricValue, Sample, StatisticKind},
+        Event, LogEvent, Value,
+    },
+};
+use chrono::{DateTime, TimeZone, Utc};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::{
@@ -12,6 +19,18 @@ lazy_static! {
     static ref NONALPHANUM: Regex = Regex::new(r"[^a-zA-Z_\-0-9\.]").unwrap();
 }
 
+/// Parses a line of the DogStatsD protocol, which is either a metric, an
+/// event or a service check.
+pub fn parse_line(line: &str) -> Result<Event, ParseError> {
+    if let Some(event) = line.strip_prefix("_e{") {
+        parse_dogstatsd_event(event).map(Event::Log)
+    } else if let Some(service_check) = line.strip_prefix("_sc|") {
+        parse_service_check(service_check).map(Event::Log)
+    } else {
+        parse(line).map(Event::Metric)
+    }
+}
+
 pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     // https://docs.datadoghq.com/developers/dogstatsd/datagram_shell/#datagram-format
     let key_and_body = packet.splitn(2, ':').collect::<Vec<_>>();
@@ -31,85 +50,238 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
 
     let name = sanitize_key(key);
     let metric_type = parts[1];
-
-    // sampling part is optional and comes after metric type part
-    let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-    let sample_rate = if let Some(s) = sampling {
-        1.0 / sanitize_sampling(parse_sampling(s)?)
-    } else {
-        1.0
-    };
-
-    // tags are optional and could be found either after sampling of after metric type part
-    let tags = if sampling.is_none() {
-        parts.get(2)
-    } else {
-        parts.get(3)
-    };
-    let tags = tags.filter(|s| s.starts_with('#'));
-    let tags = if let Some(t) = tags {
-        Some(parse_tags(t)?)
-    } else {
-        None
-    };
+    // DogStatsD packets can carry several values of a metric, `<key>:<value>:<value>|<type>`.
+    let values = parts[0].split(':').collect::<Vec<_>>();
+
+    // the optional sampling, tags and timestamp parts come after the metric
+    // type part, in any order
+    let mut sample_rate = 1.0;
+    let mut tags = None;
+    let mut timestamp = None;
+    for part in &parts[2..] {
+        if part.starts_with('@') {
+            sample_rate = 1.0 / sanitize_sampling(parse_sampling(part)?);
+        } else if part.starts_with('#') {
+            tags = Some(parse_tags(part)?);
+        } else if let Some(seconds) = part.strip_prefix('T') {
+            timestamp = Some(parse_timestamp(seconds)?);
+        }
+    }
 
     let metric = match metric_type {
         "c" => {
-            let val: f64 = parts[0].parse()?;
+            let mut value = 0.0;
+            for val in values {
+                value += val.parse::<f64>()? * sample_rate;
+            }
             Metric::new(
                 name,
                 MetricKind::Incremental,
-                MetricValue::Counter {
-                    value: val * sample_rate,
-                },
+                MetricValue::Counter { value },
             )
-            .with_tags(tags)
         }
         unit @ "h" | unit @ "ms" | unit @ "d" => {
-            let val: f64 = parts[0].parse()?;
+            let samples = values
+                .into_iter()
+                .map(|val| {
+                    Ok(Sample {
+                        value: convert_to_base_units(unit, val.parse()?),
+                        rate: sample_rate as u32,
+                    })
+                })
+                .collect::<Result<_, ParseError>>()?;
             Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
-                    samples: crate::samples![convert_to_base_units(unit, val) => sample_rate as u32],
+                name,
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples,
                     statistic: convert_to_statistic(unit),
                 },
-            ).with_tags(tags)
+            )
         }
         "g" => {
-            let value = if parts[0]
-                .chars()
-                .next()
-                .map(|c| c.is_ascii_digit())
-                .ok_or(ParseError::Malformed("empty first body component"))?
-            {
-                parts[0].parse()?
+            // Values are applied in order, as a statsd server would: a signed
+            // value changes the gauge, an unsigned one sets it.
+            let mut absolute = false;
+            let mut value = 0.0;
+            for val in values {
+                match parse_direction(val)? {
+                    None => {
+                        absolute = true;
+                        value = val.parse()?;
+                    }
+                    Some(sign) => value += val[1..].parse::<f64>()? * sign,
+                }
+            }
+
+            if absolute {
+                Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
             } else {
-                parts[0][1..].parse()?
-            };
-
-            match parse_direction(parts[0])? {
-                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                    .with_tags(tags),
-                Some(sign) => Metric::new(
-                    name,
-                    MetricKind::Incremental,
-                    MetricValue::Gauge {
-                        value: value * sign,
-                    },
-                )
-                .with_tags(tags),
+                Metric::new(name, MetricKind::Incremental, MetricValue::Gauge { value })
             }
         }
         "s" => Metric::new(
             name,
             MetricKind::Incremental,
             MetricValue::Set {
-                values: vec![parts[0].into()].into_iter().collect(),
+                values: values.into_iter().map(Into::into).collect(),
             },
-        )
-        .with_tags(tags),
+        ),
         other => return Err(ParseError::UnknownMetricType(other.into())),
     };
-    Ok(metric)
+    Ok(metric.with_tags(tags).with_timestamp(timestamp))
+}
+
+/// Parses an event, `_e{<title length>,<text length>}:<title>|<text>|<fields>`,
+/// of which the lengths are in bytes.
+fn parse_dogstatsd_event(event: &str) -> Result<LogEvent, ParseError> {
+    let lengths_and_rest = event.splitn(2, "}:").collect::<Vec<_>>();
+    if lengths_and_rest.len() != 2 {
+        return Err(ParseError::Malformed(
+            "event should start with lengths enclosed in '{}' and a ':' separator",
+        ));
+    }
+    let (lengths, rest) = (lengths_and_rest[0], lengths_and_rest[1]);
+
+    let lengths = lengths.split(',').collect::<Vec<_>>();
+    if lengths.len() != 2 {
+        return Err(ParseError::Malformed(
+            "event should have a title length and a text length",
+        ));
+    }
+    let title_len: usize = lengths[0].parse()?;
+    let text_len: usize = lengths[1].parse()?;
+
+    let title = rest.get(..title_len).ok_or(ParseError::Malformed(
+        "event is shorter than its title length",
+    ))?;
+    let text = rest
+        .get(title_len..)
+        .and_then(|rest| rest.strip_prefix('|'))
+        .and_then(|rest| rest.get(..text_len))
+        .ok_or(ParseError::Malformed(
+            "event is shorter than its text length",
+        ))?;
+    let fields = &rest[title_len + 1 + text_len..];
+
+    let mut log = LogEvent::default();
+    log.insert("type", "event");
+    log.insert("title", title);
+    log.insert(log_schema().message_key(), text.replace("\\n", "\n"));
+
+    for field in fields.split('|').skip(1) {
+        let (name, value) = split_field(field)?;
+        match name {
+            "d" => {
+                log.insert(log_schema().timestamp_key(), parse_timestamp(value)?);
+            }
+            "h" => {
+                log.insert(log_schema().host_key(), value);
+            }
+            "p" => {
+                log.insert("priority", value);
+            }
+            "t" => {
+                log.insert("alert_type", value);
+            }
+            "k" => {
+                log.insert("aggregation_key", value);
+            }
+            "s" => {
+                log.insert("source_type_name", value);
+            }
+            "#" => {
+                log.insert("tags", tags_value(parse_tags(field)?));
+            }
+            _ => return Err(ParseError::Malformed("unknown event field")),
+        }
+    }
+
+    Ok(finish_log(log))
+}
+
+/// Parses a service check, `_sc|<name>|<status>|<fields>`, of which the
+/// message field must come last.
+fn parse_service_check(service_check: &str) -> Result<LogEvent, ParseError> {
+    let mut fields_and_message = service_check.splitn(2, "|m:");
+    let fields = fields_and_message.next().unwrap_or_default();
+    let message = fields_and_message.next();
+
+    let parts = fields.split('|').collect::<Vec<_>>();
+    if parts.len() < 2 {
+        return Err(ParseError::Malformed(
+            "service check should have a name and a status",
+        ));
+    }
+    let status: u8 = parts[1].parse()?;
+    if status > 3 {
+        return Err(ParseError::Malformed(
+            "service check status should be between 0 and 3",
+        ));
+    }
+
+    let mut log = LogEvent::default();
+    log.insert("type", "service_check");
+    log.insert("name", parts[0]);
+    log.insert("status", status as i64);
+    if let Some(message) = message {
+        log.insert(log_schema().message_key(), message.replace("\\n", "\n"));
+    }
+
+    for field in &parts[2..] {
+        let (name, value) = split_field(field)?;
+        match name {
+            "d" => {
+                log.insert(log_schema().timestamp_key(), parse_timestamp(value)?);
+            }
+            "h" => {
+                log.insert(log_schema().host_key(), value);
+            }
+            "#" => {
+                log.insert("tags", tags_value(parse_tags(field)?));
+            }
+            _ => return Err(ParseError::Malformed("unknown service check field")),
+        }
+    }
+
+    Ok(finish_log(log))
+}
+
+/// Splits a field of events and service checks into its name and value, as
+/// `<name>:<value>`, or `#` and the tags.
+fn split_field(field: &str) -> Result<(&str, &str), ParseError> {
+    if let Some(tags) = field.strip_prefix('#') {
+        return Ok(("#", tags));
+    }
+    let name_and_value = field.splitn(2, ':').collect::<Vec<_>>();
+    if name_and_value.len() != 2 {
+        return Err(ParseError::Malformed(
+            "field should be name and value with ':' separator",
+        ));
+    }
+    Ok((name_and_value[0], name_and_value[1]))
+}
+
+fn finish_log(mut log: LogEvent) -> LogEvent {
+    if !log.contains(log_schema().timestamp_key()) {
+        log.insert(log_schema().timestamp_key(), Utc::now());
+    }
+    log.insert(log_schema().source_type_key(), "statsd");
+    log
+}
+
+fn tags_value(tags: BTreeMap<String, String>) -> Value {
+    Value::Map(
+        tags.into_iter()
+            .map(|(key, value)| (key, value.into()))
+            .collect(),
+    )
+}
+
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, ParseError> {
+    Utc.timestamp_opt(input.parse()?, 0)
+        .single()
+        .ok_or(ParseError::Malformed("timestamp is out of range"))
 }
 
 fn parse_sampling(input: &str) -> Result<f64, ParseError> {
@@ -222,8 +394,15 @@ impl From<ParseFloatError> for ParseError {
 
 #[cfg(test)]
 mod test {
-    use super::{parse, sanitize_key, sanitize_sampling};
-    use crate::event::metric::{Metric, MetricKind, MetricValue, StatisticKind};
+    use super::{parse, parse_line, sanitize_key, sanitize_sampling};
+    use crate::{
+        config::log_schema,
+        event::{
+            metric::{Metric, MetricKind, MetricValue, StatisticKind},
+            Event, Value,
+        },
+    };
+    use chrono::{TimeZone, Utc};
 
     #[test]
     fn basic_counter() {
@@ -403,4 +582,148 @@ mod test {
         assert_eq!(2.5, sanitize_sampling(2.5));
         assert_eq!(-5.0, sanitize_sampling(-5.0));
     }
+
+    #[test]
+    fn multi_value_packets() {
+        assert_eq!(
+            parse("foo:1:2:3|c|@0.5"),
+            Ok(Metric::new(
+                "foo".into(),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 12.0 },
+            )),
+        );
+        assert_eq!(
+            parse("glork:320:640|ms"),
+            Ok(Metric::new(
+                "glork".into(),
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: crate::samples![0.320 => 1, 0.640 => 1],
+                    statistic: StatisticKind::Histogram
+                },
+            )),
+        );
+        assert_eq!(
+            parse("gaugor:+4:-1|g"),
+            Ok(Metric::new(
+                "gaugor".into(),
+                MetricKind::Incremental,
+                MetricValue::Gauge { value: 3.0 },
+            )),
+        );
+        assert_eq!(
+            parse("gaugor:+4:10:-1|g"),
+            Ok(Metric::new(
+                "gaugor".into(),
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 9.0 },
+            )),
+        );
+        assert_eq!(
+            parse("uniques:765:766|s"),
+            Ok(Metric::new(
+                "uniques".into(),
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["765".into(), "766".into()].into_iter().collect()
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn fields_in_any_order() {
+        assert_eq!(
+            parse("glork:320|d|#region:us-west1|c:container|@0.1|T1609459200"),
+            Ok(Metric::new(
+                "glork".into(),
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: crate::samples![320.0 => 10],
+                    statistic: StatisticKind::Summary
+                },
+            )
+            .with_tags(Some(
+                vec![("region".to_owned(), "us-west1".to_owned())]
+                    .into_iter()
+                    .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1609459200, 0)))),
+        );
+    }
+
+    fn tags(tags: &[(&str, &str)]) -> Value {
+        Value::Map(
+            tags.iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).into()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn events() {
+        let event = parse_line(
+            "_e{9,13}:Deploy|ed|Version 2\\nok|d:1609459200|h:web-1|p:low|t:success|k:deploys|s:ci|#env:prod,canary",
+        )
+        .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["type"], "event".into());
+        assert_eq!(log["title"], "Deploy|ed".into());
+        assert_eq!(log[log_schema().message_key()], "Version 2\nok".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1609459200, 0).into()
+        );
+        assert_eq!(log[log_schema().host_key()], "web-1".into());
+        assert_eq!(log["priority"], "low".into());
+        assert_eq!(log["alert_type"], "success".into());
+        assert_eq!(log["aggregation_key"], "deploys".into());
+        assert_eq!(log["source_type_name"], "ci".into());
+        assert_eq!(log["tags"], tags(&[("env", "prod"), ("canary", "true")]));
+        assert_eq!(log[log_schema().source_type_key()], "statsd".into());
+
+        let event = parse_line("_e{5,4}:title|text").unwrap();
+        assert_eq!(event.as_log()["title"], "title".into());
+        assert!(event.as_log().contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn service_checks() {
+        let event = parse_line(
+            "_sc|redis.can_connect|2|d:1609459200|h:web-1|#env:prod|m:Timed out | retrying",
+        )
+        .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["type"], "service_check".into());
+        assert_eq!(log["name"], "redis.can_connect".into());
+        assert_eq!(log["status"], 2.into());
+        assert_eq!(
+            log[log_schema().message_key()],
+            "Timed out | retrying".into()
+        );
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1609459200, 0).into()
+        );
+        assert_eq!(log[log_schema().host_key()], "web-1".into());
+        assert_eq!(log["tags"], tags(&[("env", "prod")]));
+
+        assert!(matches!(
+            parse_line("_sc|redis.can_connect|0"),
+            Ok(Event::Log(_))
+        ));
+    }
+
+    #[test]
+    fn malformed_events_and_service_checks() {
+        assert!(parse_line("_e{5,40}:title|text").is_err());
+        assert!(parse_line("_e{5}:title|text").is_err());
+        assert!(parse_line("_e{5,4}:title|text|x:unknown").is_err());
+        assert!(parse_line("_sc|redis.can_connect").is_err());
+        assert!(parse_line("_sc|redis.can_connect|4").is_err());
+        assert!(parse_line("_sc|redis.can_connect|0|d:yesterday").is_err());
+    }
 }
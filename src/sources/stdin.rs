@@ -1,15 +1,18 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
     event::Event,
-    internal_events::{StdinEventReceived, StdinReadFailed},
+    internal_events::{StdinDecodeFailed, StdinEventReceived, StdinFrameTooLong, StdinReadFailed},
     shutdown::ShutdownSignal,
     Pipeline,
 };
-use bytes::Bytes;
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::Utc;
+use codec::BytesDelimitedCodec;
 use futures::{executor, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use std::{io, thread};
-use tokio::sync::mpsc::channel;
+use tokio::sync::mpsc::{channel, Sender};
+use tokio_util::codec::Decoder;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
@@ -17,6 +20,9 @@ pub struct StdinConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub framing: Framing,
+    pub decoding: Decoding,
+    pub end_of_input: EndOfInput,
 }
 
 impl Default for StdinConfig {
@@ -24,6 +30,9 @@ impl Default for StdinConfig {
         StdinConfig {
             max_length: default_max_length(),
             host_key: None,
+            framing: Framing::default(),
+            decoding: Decoding::default(),
+            end_of_input: EndOfInput::default(),
         }
     }
 }
@@ -32,6 +41,43 @@ fn default_max_length() -> usize {
     bytesize::kib(100u64) as usize
 }
 
+/// How the input is split into events.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Framing {
+    /// Every line is an event.
+    #[derivative(Default)]
+    NewlineDelimited,
+    /// Every event is prefixed by its length, as a 4 bytes big-endian integer.
+    LengthDelimited,
+}
+
+/// How the events are decoded from their frame.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Decoding {
+    /// The frame is the message of the event, as is.
+    #[derivative(Default)]
+    Bytes,
+    /// The frame is a JSON object holding the fields of the event.
+    Json,
+}
+
+/// What to do once all the input was read.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum EndOfInput {
+    /// Finish the source, which shuts Vector down once all its sources
+    /// finished.
+    #[derivative(Default)]
+    Shutdown,
+    /// Keep the source running until Vector is shut down.
+    StayAlive,
+}
+
 inventory::submit! {
     SourceDescription::new::<StdinConfig>("stdin")
 }
@@ -48,7 +94,7 @@ impl SourceConfig for StdinConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        stdin_source(io::BufReader::new(io::stdin()), self.clone(), shutdown, out)
+        stdin_source(io::stdin(), self.clone(), shutdown, out)
     }
 
     fn output_type(&self) -> DataType {
@@ -64,6 +110,156 @@ impl SourceConfig for StdinConfig {
     }
 }
 
+/// Splits the input into frames according to the `framing` option.
+enum FrameDecoder {
+    NewlineDelimited(BytesDelimitedCodec),
+    LengthDelimited(LengthDelimitedDecoder),
+}
+
+impl FrameDecoder {
+    fn new(framing: Framing, max_length: usize) -> Self {
+        match framing {
+            Framing::NewlineDelimited => FrameDecoder::NewlineDelimited(
+                BytesDelimitedCodec::new_with_max_length(b'\n', max_length),
+            ),
+            Framing::LengthDelimited => FrameDecoder::LengthDelimited(LengthDelimitedDecoder {
+                max_length,
+                discarding: 0,
+            }),
+        }
+    }
+}
+
+/// Bytes of the length prefix of a length delimited frame.
+const LENGTH_BYTES: usize = 4;
+
+/// Splits length delimited frames, discarding the ones longer than
+/// `max_length` rather than failing the whole input.
+struct LengthDelimitedDecoder {
+    max_length: usize,
+    /// Bytes of an oversized frame that are still to be discarded.
+    discarding: usize,
+}
+
+impl Decoder for LengthDelimitedDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        loop {
+            if self.discarding > 0 {
+                let discard = self.discarding.min(buf.len());
+                buf.advance(discard);
+                self.discarding -= discard;
+                if self.discarding > 0 {
+                    return Ok(None);
+                }
+            }
+
+            if buf.len() < LENGTH_BYTES {
+                return Ok(None);
+            }
+            let length = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+
+            if length > self.max_length {
+                emit!(StdinFrameTooLong {
+                    length,
+                    max_length: self.max_length,
+                });
+                self.discarding = LENGTH_BYTES + length;
+                continue;
+            }
+
+            if buf.len() < LENGTH_BYTES + length {
+                buf.reserve(LENGTH_BYTES + length - buf.len());
+                return Ok(None);
+            }
+
+            buf.advance(LENGTH_BYTES);
+            return Ok(Some(buf.split_to(length).freeze()));
+        }
+    }
+}
+
+impl Decoder for FrameDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            FrameDecoder::NewlineDelimited(codec) => codec.decode(buf).map(trim_carriage_return),
+            FrameDecoder::LengthDelimited(decoder) => decoder.decode(buf),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            FrameDecoder::NewlineDelimited(codec) => {
+                codec.decode_eof(buf).map(trim_carriage_return)
+            }
+            FrameDecoder::LengthDelimited(decoder) => decoder.decode_eof(buf),
+        }
+    }
+}
+
+/// Lines ending with `\r\n` are split on their `\n` only.
+fn trim_carriage_return(frame: Option<Bytes>) -> Option<Bytes> {
+    frame.map(|mut frame| {
+        if frame.ends_with(b"\r") {
+            frame.truncate(frame.len() - 1);
+        }
+        frame
+    })
+}
+
+/// Reads the frames of the input until its end, or until the receiver closes.
+fn read_frames<R: io::Read>(
+    mut stdin: R,
+    mut decoder: FrameDecoder,
+    mut sender: Sender<io::Result<Bytes>>,
+) {
+    let mut buf = BytesMut::new();
+    let mut chunk = [0; 8 * 1024];
+
+    loop {
+        let read = match stdin.read(&mut chunk) {
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                let _ = executor::block_on(sender.send(Err(error)));
+                return;
+            }
+        };
+        buf.extend_from_slice(&chunk[..read]);
+
+        loop {
+            let frame = if read == 0 {
+                decoder.decode_eof(&mut buf)
+            } else {
+                decoder.decode(&mut buf)
+            };
+
+            match frame {
+                Ok(Some(frame)) => {
+                    if executor::block_on(sender.send(Ok(frame))).is_err() {
+                        // receiver has closed so we should shutdown
+                        return;
+                    }
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    let _ = executor::block_on(sender.send(Err(error)));
+                    return;
+                }
+            }
+        }
+
+        if read == 0 {
+            return;
+        }
+    }
+}
+
 pub fn stdin_source<R>(
     stdin: R,
     config: StdinConfig,
@@ -71,25 +267,23 @@ pub fn stdin_source<R>(
     out: Pipeline,
 ) -> crate::Result<super::Source>
 where
-    R: Send + io::BufRead + 'static,
+    R: Send + io::Read + 'static,
 {
     let host_key = config
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
     let hostname = crate::get_hostname().ok();
+    let decoder = FrameDecoder::new(config.framing, config.max_length);
+    let decoding = config.decoding;
+    let end_of_input = config.end_of_input;
 
-    let (mut sender, receiver) = channel(1024);
+    let (sender, receiver) = channel(1024);
 
     // Start the background thread
     thread::spawn(move || {
         info!("Capturing STDIN.");
 
-        for line in stdin.lines() {
-            if executor::block_on(sender.send(line)).is_err() {
-                // receiver has closed so we should shutdown
-                return;
-            }
-        }
+        read_frames(stdin, decoder, sender);
     });
 
     Ok(Box::pin(async move {
@@ -97,13 +291,14 @@ where
             out.sink_map_err(|error| error!(message = "Unable to send event to out.", %error));
 
         let res = receiver
-            .take_until(shutdown)
+            .take_until(shutdown.clone())
             .map_err(|error| emit!(StdinReadFailed { error }))
-            .map_ok(move |line| {
+            .try_filter_map(move |frame| {
                 emit!(StdinEventReceived {
-                    byte_size: line.len()
+                    byte_size: frame.len()
                 });
-                create_event(Bytes::from(line), &host_key, &hostname)
+                let event = create_event(frame, decoding, &host_key, &hostname);
+                futures::future::ok(event)
             })
             .forward(&mut out)
             .inspect(|_| info!("Finished sending."))
@@ -111,12 +306,36 @@ where
 
         let _ = out.flush().await; // error emitted by sink_map_err
 
+        if res.is_ok() && end_of_input == EndOfInput::StayAlive {
+            shutdown.await;
+        }
+
         res
     }))
 }
 
-fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event {
-    let mut event = Event::from(line);
+fn create_event(
+    frame: Bytes,
+    decoding: Decoding,
+    host_key: &str,
+    hostname: &Option<String>,
+) -> Option<Event> {
+    let mut event = match decoding {
+        Decoding::Bytes => Event::from(frame),
+        Decoding::Json => {
+            let fields = serde_json::from_slice::<serde_json::Map<_, _>>(&frame)
+                .map_err(|error| emit!(StdinDecodeFailed { error }))
+                .ok()?;
+
+            let mut event = Event::new_empty_log();
+            let log = event.as_mut_log();
+            log.insert(log_schema().timestamp_key(), Utc::now());
+            for (key, value) in fields {
+                log.insert_flat(key, value);
+            }
+            event
+        }
+    };
 
     // Add source type
     event
@@ -127,7 +346,7 @@ fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event
         event.as_mut_log().insert(host_key, hostname.clone());
     }
 
-    event
+    Some(event)
 }
 
 #[cfg(test)]
@@ -135,7 +354,7 @@ mod tests {
     use super::*;
     use crate::{test_util::trace_init, Pipeline};
     use std::io::Cursor;
-    use tokio::sync::mpsc;
+    use tokio::{sync::mpsc, time::Duration};
 
     #[test]
     fn generate_config() {
@@ -148,7 +367,7 @@ mod tests {
         let host_key = "host".to_string();
         let hostname = Some("Some.Machine".to_string());
 
-        let event = create_event(line, &host_key, &hostname);
+        let event = create_event(line, Decoding::Bytes, &host_key, &hostname).unwrap();
         let log = event.into_log();
 
         assert_eq!(log["host"], "Some.Machine".into());
@@ -188,4 +407,131 @@ mod tests {
         assert!(event.is_err());
         assert_eq!(Err(mpsc::error::TryRecvError::Closed), event);
     }
+
+    #[test]
+    fn stdin_create_event_from_json() {
+        let frame = Bytes::from(r#"{"message":"hello world","level":"info"}"#);
+        let hostname = Some("Some.Machine".to_string());
+
+        let event = create_event(frame, Decoding::Json, "host", &hostname).unwrap();
+        let log = event.into_log();
+
+        assert_eq!(log[log_schema().message_key()], "hello world".into());
+        assert_eq!(log["level"], "info".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+        assert_eq!(log[log_schema().source_type_key()], "stdin".into());
+
+        assert!(create_event(Bytes::from("[1, 2]"), Decoding::Json, "host", &hostname).is_none());
+    }
+
+    async fn collect_messages(input: &'static [u8], config: StdinConfig) -> Vec<Bytes> {
+        let (tx, rx) = Pipeline::new_test();
+
+        stdin_source(Cursor::new(input), config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        rx.map(|event| event.as_log()[log_schema().message_key()].as_bytes())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn stdin_decodes_crlf_lines_and_discards_long_ones() {
+        trace_init();
+
+        let config = StdinConfig {
+            max_length: 8,
+            ..Default::default()
+        };
+        let messages = collect_messages(b"one\r\nway too long\ntwo", config).await;
+
+        assert_eq!(messages, vec![Bytes::from("one"), Bytes::from("two")]);
+    }
+
+    #[tokio::test]
+    async fn stdin_decodes_binary_length_delimited_frames() {
+        trace_init();
+
+        let config = StdinConfig {
+            framing: Framing::LengthDelimited,
+            ..Default::default()
+        };
+        let messages =
+            collect_messages(b"\x00\x00\x00\x03\xff\n\x00\x00\x00\x00\x02ok", config).await;
+
+        assert_eq!(
+            messages,
+            vec![Bytes::from(&b"\xff\n\x00"[..]), Bytes::from("ok")]
+        );
+    }
+
+    #[tokio::test]
+    async fn stdin_discards_long_length_delimited_frames() {
+        trace_init();
+
+        let config = StdinConfig {
+            framing: Framing::LengthDelimited,
+            max_length: 2,
+            ..Default::default()
+        };
+        let messages = collect_messages(
+            b"\x00\x00\x00\x02ok\x00\x00\x00\x03big\x00\x00\x00\x02ok",
+            config,
+        )
+        .await;
+
+        assert_eq!(messages, vec![Bytes::from("ok"), Bytes::from("ok")]);
+    }
+
+    #[tokio::test]
+    async fn stdin_decodes_ndjson() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            decoding: Decoding::Json,
+            ..Default::default()
+        };
+        let buf = Cursor::new("{\"id\":1}\nnot json\n{\"id\":2}\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let ids = rx
+            .map(|event| event.as_log()["id"].clone())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(ids, vec![1.into(), 2.into()]);
+    }
+
+    #[tokio::test]
+    async fn stdin_stays_alive_until_shutdown() {
+        trace_init();
+
+        let (tx, mut rx) = Pipeline::new_test();
+        let config = StdinConfig {
+            end_of_input: EndOfInput::StayAlive,
+            ..Default::default()
+        };
+        let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+        let mut source =
+            tokio::spawn(stdin_source(Cursor::new("hello world"), config, shutdown, tx).unwrap());
+
+        let event = rx.next().await.unwrap();
+        assert_eq!(
+            event.as_log()[log_schema().message_key()],
+            "hello world".into()
+        );
+
+        // The source keeps running once the input is read.
+        let timeout = Duration::from_millis(100);
+        assert!(tokio::time::timeout(timeout, &mut source).await.is_err());
+
+        drop(trigger_shutdown);
+        source.await.unwrap().unwrap();
+    }
 }
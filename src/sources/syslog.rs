@@ -1,4 +1,4 @@
-use super::util::{SocketListenAddr, TcpSource};
+use super::util::{Delimiter, SocketListenAddr, TcpSource};
 #[cfg(unix)]
 use crate::sources::util::build_unix_stream_source;
 #[cfg(unix)]
@@ -17,9 +17,12 @@ use crate::{
 };
 use bytes::{Buf, Bytes, BytesMut};
 use chrono::{Datelike, Utc};
+use codec::StringDelimitedCodec;
 use derive_is_enum_variant::is_enum_variant;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use shared::datetime::TimeZone;
+use std::cell::Cell;
 use std::io;
 use std::net::SocketAddr;
 #[cfg(unix)]
@@ -27,7 +30,7 @@ use std::path::PathBuf;
 use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
 use tokio::net::UdpSocket;
 use tokio_util::{
-    codec::{BytesCodec, Decoder, LinesCodec, LinesCodecError},
+    codec::{BytesCodec, Decoder, LinesCodecError},
     udp::UdpFramed,
 };
 
@@ -39,8 +42,13 @@ pub struct SyslogConfig {
     mode: Mode,
     #[serde(default = "default_max_length")]
     max_length: usize,
+    /// The delimiter of the messages that aren't octet counted.
+    #[serde(default)]
+    delimiter: Delimiter,
     /// The host key of the log. (This differs from `hostname`)
     host_key: Option<String>,
+    /// The time zone of the timestamps without an offset.
+    timezone: Option<TimeZone>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, is_enum_variant)]
@@ -71,6 +79,8 @@ impl SyslogConfig {
             mode,
             host_key: None,
             max_length: default_max_length(),
+            delimiter: Delimiter::default(),
+            timezone: None,
         }
     }
 }
@@ -90,6 +100,8 @@ impl GenerateConfig for SyslogConfig {
             },
             host_key: None,
             max_length: default_max_length(),
+            delimiter: Delimiter::default(),
+            timezone: None,
         })
         .unwrap()
     }
@@ -101,7 +113,7 @@ impl SourceConfig for SyslogConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
@@ -109,6 +121,7 @@ impl SourceConfig for SyslogConfig {
             .host_key
             .clone()
             .unwrap_or_else(|| log_schema().host_key().to_string());
+        let timezone = self.timezone.unwrap_or(globals.timezone);
 
         match self.mode.clone() {
             Mode::Tcp {
@@ -119,7 +132,9 @@ impl SourceConfig for SyslogConfig {
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
+                    delimiter: self.delimiter.clone(),
                     host_key,
+                    timezone,
                 };
                 let shutdown_secs = 30;
                 let tls = MaybeTlsSettings::from_config(&tls, true)?;
@@ -141,20 +156,30 @@ impl SourceConfig for SyslogConfig {
                 address,
                 self.max_length,
                 host_key,
+                timezone,
                 receive_buffer_bytes,
                 shutdown,
                 out,
             )),
             #[cfg(not(unix))]
-            Mode::Udp { address } => Ok(udp(address, self.max_length, host_key, shutdown, out)),
+            Mode::Udp { address } => Ok(udp(
+                address,
+                self.max_length,
+                host_key,
+                timezone,
+                shutdown,
+                out,
+            )),
             #[cfg(unix)]
             Mode::Unix { path } => Ok(build_unix_stream_source(
                 path,
-                SyslogDecoder::new(self.max_length),
+                SyslogDecoder::new(self.max_length, &self.delimiter),
                 host_key,
                 shutdown,
                 out,
-                event_from_str,
+                move |host_key, default_host, line| {
+                    event_from_str(host_key, default_host, line, timezone)
+                },
             )),
         }
     }
@@ -180,7 +205,9 @@ impl SourceConfig for SyslogConfig {
 #[derive(Debug, Clone)]
 struct SyslogTcpSource {
     max_length: usize,
+    delimiter: Delimiter,
     host_key: String,
+    timezone: TimeZone,
 }
 
 impl TcpSource for SyslogTcpSource {
@@ -188,24 +215,24 @@ impl TcpSource for SyslogTcpSource {
     type Decoder = SyslogDecoder;
 
     fn decoder(&self) -> Self::Decoder {
-        SyslogDecoder::new(self.max_length)
+        SyslogDecoder::new(self.max_length, &self.delimiter)
     }
 
     fn build_event(&self, frame: String, host: Bytes) -> Option<Event> {
-        event_from_str(&self.host_key, Some(host), &frame)
+        event_from_str(&self.host_key, Some(host), &frame, self.timezone)
     }
 }
 
 /// Decodes according to `Octet Counting` in https://tools.ietf.org/html/rfc6587
 #[derive(Clone, Debug)]
 struct SyslogDecoder {
-    other: LinesCodec,
+    other: StringDelimitedCodec,
 }
 
 impl SyslogDecoder {
-    fn new(max_length: usize) -> Self {
+    fn new(max_length: usize, delimiter: &Delimiter) -> Self {
         Self {
-            other: LinesCodec::new_with_max_length(max_length),
+            other: delimiter.string_codec(max_length),
         }
     }
 
@@ -283,7 +310,7 @@ impl Decoder for SyslogDecoder {
         if let Some(ret) = self.checked_decode(src) {
             ret
         } else {
-            // Octet counting isn't used so fallback to newline codec.
+            // Octet counting isn't used so fallback to the delimited codec.
             self.other.decode(src)
         }
     }
@@ -292,7 +319,7 @@ impl Decoder for SyslogDecoder {
         if let Some(ret) = self.checked_decode(buf) {
             ret
         } else {
-            // Octet counting isn't used so fallback to newline codec.
+            // Octet counting isn't used so fallback to the delimited codec.
             self.other.decode_eof(buf)
         }
     }
@@ -302,6 +329,7 @@ pub fn udp(
     addr: SocketAddr,
     _max_length: usize,
     host_key: String,
+    timezone: TimeZone,
     #[cfg(unix)] receive_buffer_bytes: Option<usize>,
     shutdown: ShutdownSignal,
     out: Pipeline,
@@ -337,7 +365,8 @@ pub fn udp(
                                 .map_err(|error| emit!(SyslogUdpUtf8Error { error }))
                                 .ok()
                                 .and_then(|s| {
-                                    event_from_str(&host_key, Some(received_from), s).map(Ok)
+                                    event_from_str(&host_key, Some(received_from), s, timezone)
+                                        .map(Ok)
                                 })
                         }
                         Err(error) => {
@@ -373,10 +402,20 @@ fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
 **/
 // TODO: many more cases to handle:
 // octet framing (i.e. num bytes as ascii string prefix) with and without delimiters
-// null byte delimiter in place of newline
-fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Option<Event> {
+fn event_from_str(
+    host_key: &str,
+    default_host: Option<Bytes>,
+    line: &str,
+    timezone: TimeZone,
+) -> Option<Event> {
     let line = line.trim();
-    let parsed = syslog_loose::parse_message_with_year(line, resolve_year);
+    // Only the RFC 3164 timestamps lack the year, and they lack an offset as
+    // well.
+    let local = Cell::new(false);
+    let parsed = syslog_loose::parse_message_with_year(line, |date| {
+        local.set(true);
+        resolve_year(date)
+    });
     let mut event = Event::from(&parsed.msg[..]);
 
     // Add source type
@@ -395,7 +434,15 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Op
 
     let timestamp = parsed
         .timestamp
-        .map(|ts| ts.into())
+        .map(|ts| {
+            if local.get() {
+                timezone
+                    .datetime_from_local(&ts.naive_local())
+                    .unwrap_or_else(|| ts.into())
+            } else {
+                ts.into()
+            }
+        })
         .unwrap_or_else(Utc::now);
     event
         .as_mut_log()
@@ -454,9 +501,20 @@ fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
 
 #[cfg(test)]
 mod test {
-    use super::{event_from_str, Mode, SyslogConfig};
+    use super::{Mode, SyslogConfig, SyslogDecoder};
     use crate::{config::log_schema, event::Event};
+    use bytes::{Bytes, BytesMut};
     use chrono::prelude::*;
+    use tokio_util::codec::Decoder;
+
+    fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Option<Event> {
+        super::event_from_str(
+            host_key,
+            default_host,
+            line,
+            shared::datetime::TimeZone::Local,
+        )
+    }
 
     #[test]
     fn generate_config() {
@@ -586,6 +644,32 @@ mod test {
         assert!(config.mode.is_unix());
     }
 
+    #[test]
+    fn decodes_custom_delimiter() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            delimiter = "\u0000"
+          "#,
+        )
+        .unwrap();
+
+        let mut decoder = SyslogDecoder::new(config.max_length, &config.delimiter);
+        let mut buf = BytesMut::from(&b"<13>Jan 1 00:00:00 host a\nb\x00<14>x\x00<15>c"[..]);
+
+        assert_eq!(
+            decoder.decode(&mut buf).unwrap(),
+            Some("<13>Jan 1 00:00:00 host a\nb".to_owned())
+        );
+        assert_eq!(decoder.decode(&mut buf).unwrap(), Some("<14>x".to_owned()));
+        assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+        assert_eq!(
+            decoder.decode_eof(&mut buf).unwrap(),
+            Some("<15>c".to_owned())
+        );
+    }
+
     #[test]
     fn syslog_ng_network_syslog_protocol() {
         // this should also match rsyslog omfwd with template=RSYSLOG_SyslogProtocol23Format
@@ -749,6 +833,26 @@ mod test {
         assert_eq!(event, expected);
     }
 
+    #[test]
+    fn reads_timestamps_without_offset_in_timezone() {
+        let timezone = shared::datetime::TimeZone::parse("America/New_York").unwrap();
+        let timestamp = |raw: &str| {
+            super::event_from_str("host", None, raw, timezone)
+                .unwrap()
+                .as_log()[log_schema().timestamp_key()]
+            .clone()
+        };
+
+        let value = timestamp("<13>Jan 15 08:30:00 74794bfb6795 root[8539]: i am foobar");
+        let year = value.as_timestamp().unwrap().year();
+        assert_eq!(value, Utc.ymd(year, 1, 15).and_hms(13, 30, 0).into());
+
+        // An explicit offset is kept.
+        let value =
+            timestamp("<13>1 2019-02-13T19:48:34+00:00 74794bfb6795 root 8449 - - i am foobar");
+        assert_eq!(value, Utc.ymd(2019, 2, 13).and_hms(19, 48, 34).into());
+    }
+
     #[test]
     fn rsyslog_omfwd_tcp_default() {
         let msg = "start";
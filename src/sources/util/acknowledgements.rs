@@ -0,0 +1,105 @@
+use crate::{
+    event::{
+        finalization::{BatchNotifier, BatchStatus},
+        Event,
+    },
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use async_trait::async_trait;
+use futures::{stream::FuturesUnordered, FutureExt, SinkExt, StreamExt};
+
+/// The messages of a broker that redelivers the messages it isn't
+/// acknowledged.
+#[async_trait]
+pub(crate) trait AcknowledgedMessages: Send {
+    type Message: Send + 'static;
+
+    /// Reads the next message, `None` once there are no more messages, or
+    /// `Err` if reading failed for good.
+    async fn next(&mut self) -> Option<Result<Self::Message, ()>>;
+
+    fn to_event(&self, message: &Self::Message) -> Event;
+
+    /// Acknowledges a message of which the events were delivered.
+    async fn ack(&mut self, message: Self::Message);
+
+    /// Negatively acknowledges a message of which the events weren't
+    /// delivered, so that the broker redelivers it.
+    async fn nack(&mut self, message: Self::Message, status: BatchStatus);
+}
+
+/// Sends the events of messages to `out`, and finalizes the messages once
+/// their events are, or right away without `acknowledgements`.
+pub(crate) async fn run_acknowledged<M: AcknowledgedMessages>(
+    mut messages: M,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    // Messages waiting for their events to be delivered, along with the
+    // status of their delivery.
+    let mut pending_acks = FuturesUnordered::new();
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            Some((status, message)) = pending_acks.next() => {
+                finalize(&mut messages, status, message).await;
+            }
+            message = messages.next() => {
+                let message = match message {
+                    None => break,
+                    Some(Err(())) => return Err(()),
+                    Some(Ok(message)) => message,
+                };
+
+                let mut event = messages.to_event(&message);
+                let batch = if acknowledgements {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    event = event.with_batch_notifier(&batch);
+                    Some((batch, receiver))
+                } else {
+                    None
+                };
+
+                if let Err(error) = out.send(event).await {
+                    error!(message = "Error sending to sink.", %error);
+                    return Err(());
+                }
+
+                match batch {
+                    Some((batch, receiver)) => {
+                        // The status is known once the batch is dropped
+                        // along with all of its events.
+                        drop(batch);
+                        pending_acks.push(receiver.map(|status| {
+                            (status.unwrap_or(BatchStatus::Errored), message)
+                        }));
+                    }
+                    None => messages.ack(message).await,
+                }
+            }
+        }
+    }
+
+    // Acknowledge the messages read before shutting down, as far as they get
+    // delivered.
+    while let Some((status, message)) = pending_acks.next().await {
+        finalize(&mut messages, status, message).await;
+    }
+
+    Ok(())
+}
+
+async fn finalize<M: AcknowledgedMessages>(
+    messages: &mut M,
+    status: BatchStatus,
+    message: M::Message,
+) {
+    if status == BatchStatus::Delivered {
+        messages.ack(message).await
+    } else {
+        messages.nack(message, status).await
+    }
+}
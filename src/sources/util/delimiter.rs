@@ -0,0 +1,69 @@
+use codec::{BytesDelimitedCodec, StringDelimitedCodec};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// The non-empty sequence of bytes separating the frames of a stream, like
+/// `"\n"`, `"\r\n"` or `"\u0000"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Delimiter(String);
+
+impl Delimiter {
+    #[cfg(feature = "sources-socket")]
+    pub fn bytes_codec(&self, max_length: usize) -> BytesDelimitedCodec {
+        BytesDelimitedCodec::new_with_sequence(self.0.clone().into_bytes(), max_length)
+    }
+
+    pub fn string_codec(&self, max_length: usize) -> StringDelimitedCodec {
+        StringDelimitedCodec::new_with_sequence(self.0.clone().into_bytes(), max_length)
+    }
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Self("\n".to_owned())
+    }
+}
+
+impl TryFrom<String> for Delimiter {
+    type Error = &'static str;
+
+    fn try_from(delimiter: String) -> Result<Self, Self::Error> {
+        if delimiter.is_empty() {
+            Err("delimiter must not be empty")
+        } else {
+            Ok(Self(delimiter))
+        }
+    }
+}
+
+impl From<Delimiter> for String {
+    fn from(delimiter: Delimiter) -> Self {
+        delimiter.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Config {
+        #[serde(default)]
+        delimiter: Delimiter,
+    }
+
+    #[test]
+    fn parses_delimiter() {
+        let config: Config = toml::from_str(r#"delimiter = "\r\n""#).unwrap();
+        assert_eq!(config.delimiter, Delimiter("\r\n".to_owned()));
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.delimiter, Delimiter::default());
+    }
+
+    #[test]
+    fn rejects_empty_delimiter() {
+        assert!(toml::from_str::<Config>(r#"delimiter = """#).is_err());
+    }
+}
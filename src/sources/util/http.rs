@@ -11,12 +11,12 @@ use flate2::read::{DeflateDecoder, GzDecoder};
 use futures::{FutureExt, SinkExt, StreamExt, TryFutureExt};
 use headers::{Authorization, HeaderMapExt};
 use serde::{Deserialize, Serialize};
-use snap::raw::Decoder as SnappyDecoder;
+use snap::raw::{decompress_len, Decoder as SnappyDecoder};
 use std::{collections::HashMap, convert::TryFrom, error::Error, fmt, io::Read, net::SocketAddr};
 use tracing_futures::Instrument;
 use warp::{
-    filters::BoxedFilter,
-    http::{HeaderMap, StatusCode},
+    filters::{path::FullPath, BoxedFilter},
+    http::{HeaderMap, Method, StatusCode},
     reject::Rejection,
     Filter,
 };
@@ -126,29 +126,52 @@ impl HttpSourceAuth {
     }
 }
 
-pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMessage> {
+/// The most bytes a request body is decompressed to, so that small
+/// compressed bodies can't exhaust the memory of the sources.
+pub const MAX_DECODED_BYTES: usize = 100 * 1024 * 1024;
+
+/// Decompresses `body` with the codings of its `Content-Encoding` header, in
+/// the reverse order they were applied in. Bodies decompressing to more than
+/// `max_length` bytes are rejected.
+pub fn decode(
+    header: &Option<String>,
+    mut body: Bytes,
+    max_length: usize,
+) -> Result<Bytes, ErrorMessage> {
     if let Some(encodings) = header {
-        for encoding in encodings.rsplit(',').map(str::trim) {
+        for encoding in encodings
+            .rsplit(',')
+            .map(|encoding| encoding.trim().to_lowercase())
+            .filter(|encoding| !encoding.is_empty())
+        {
+            let encoding = encoding.as_str();
             body = match encoding {
                 "identity" => body,
-                "gzip" => {
-                    let mut decoded = Vec::new();
-                    GzDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
-                        .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
+                // `x-gzip` is an alias of `gzip`, which recipients should
+                // treat as such.
+                "gzip" | "x-gzip" => {
+                    read_limited(encoding, GzDecoder::new(body.reader()), max_length)?
                 }
                 "deflate" => {
-                    let mut decoded = Vec::new();
-                    DeflateDecoder::new(body.reader())
-                        .read_to_end(&mut decoded)
+                    read_limited(encoding, DeflateDecoder::new(body.reader()), max_length)?
+                }
+                "snappy" => {
+                    // The decompressed length is known upfront.
+                    let length = decompress_len(&body)
                         .map_err(|error| handle_decode_error(encoding, error))?;
-                    decoded.into()
+                    if length > max_length {
+                        return Err(too_large(max_length));
+                    }
+                    SnappyDecoder::new()
+                        .decompress_vec(&body)
+                        .map_err(|error| handle_decode_error(encoding, error))?
+                        .into()
+                }
+                "zstd" => {
+                    let decoder = zstd::stream::read::Decoder::new(body.reader())
+                        .map_err(|error| handle_decode_error(encoding, error))?;
+                    read_limited(encoding, decoder, max_length)?
                 }
-                "snappy" => SnappyDecoder::new()
-                    .decompress_vec(&body)
-                    .map_err(|error| handle_decode_error(encoding, error))?
-                    .into(),
                 encoding => {
                     return Err(ErrorMessage::new(
                         StatusCode::UNSUPPORTED_MEDIA_TYPE,
@@ -162,6 +185,31 @@ pub fn decode(header: &Option<String>, mut body: Bytes) -> Result<Bytes, ErrorMe
     Ok(body)
 }
 
+fn read_limited(
+    encoding: &str,
+    decoder: impl Read,
+    max_length: usize,
+) -> Result<Bytes, ErrorMessage> {
+    // Reading one byte more than allowed tells bodies at the limit apart
+    // from longer ones.
+    let mut decoded = Vec::new();
+    decoder
+        .take(max_length as u64 + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|error| handle_decode_error(encoding, error))?;
+    if decoded.len() > max_length {
+        return Err(too_large(max_length));
+    }
+    Ok(decoded.into())
+}
+
+fn too_large(max_length: usize) -> ErrorMessage {
+    ErrorMessage::new(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        format!("Decompressed payload is larger than {} bytes.", max_length),
+    )
+}
+
 fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMessage {
     emit!(HTTPDecompressError {
         encoding,
@@ -180,12 +228,25 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         body: Bytes,
         header_map: HeaderMap,
         query_parameters: HashMap<String, String>,
+        path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// The methods requests are accepted with. Requests with other methods
+    /// are rejected with `405 Method Not Allowed`.
+    fn methods(&self) -> Vec<Method> {
+        vec![Method::POST]
+    }
+
+    /// Whether requests are only accepted on the path of the source, rather
+    /// than on it and any of its subpaths.
+    fn strict_path(&self) -> bool {
+        true
+    }
+
     fn run(
         self,
         address: SocketAddr,
-        path: &'static str,
+        path: &str,
         tls: &Option<TlsConfig>,
         auth: &Option<HttpSourceAuthConfig>,
         out: Pipeline,
@@ -193,24 +254,52 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     ) -> crate::Result<crate::sources::Source> {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
         Ok(Box::pin(async move {
             let span = crate::trace::current_span();
 
-            let mut filter: BoxedFilter<()> = warp::post().boxed();
-            if !path.is_empty() && path != "/" {
-                for s in path.split('/') {
-                    filter = filter.and(warp::path(s)).boxed();
-                }
+            let mut filter: BoxedFilter<()> = warp::any().boxed();
+            for segment in segments {
+                filter = filter.and(warp::path(segment)).boxed();
+            }
+            if self.strict_path() {
+                filter = filter.and(warp::path::end()).boxed();
             }
+
+            // Checked after the path, so that requests to other paths are
+            // rejected with `404 Not Found` regardless of their method.
+            let methods = self.methods();
+            let method = warp::method()
+                .and_then(move |method: Method| {
+                    let allowed = methods.contains(&method);
+                    async move {
+                        if allowed {
+                            Ok(())
+                        } else {
+                            Err(warp::reject::custom(ErrorMessage::new(
+                                StatusCode::METHOD_NOT_ALLOWED,
+                                format!("Method {} not allowed", method),
+                            )))
+                        }
+                    }
+                })
+                .untuple_one();
+
             let svc = filter
-                .and(warp::path::end())
+                .and(method)
+                .and(warp::path::full())
                 .and(warp::header::optional::<String>("authorization"))
                 .and(warp::header::optional::<String>("content-encoding"))
                 .and(warp::header::headers_cloned())
                 .and(warp::body::bytes())
                 .and(warp::query::<HashMap<String, String>>())
                 .and_then(
-                    move |auth_header,
+                    move |path: FullPath,
+                          auth_header,
                           encoding_header,
                           headers: HeaderMap,
                           body: Bytes,
@@ -222,10 +311,10 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
                         let events = auth
                             .is_valid(&auth_header)
-                            .and_then(|()| decode(&encoding_header, body))
+                            .and_then(|()| decode(&encoding_header, body, MAX_DECODED_BYTES))
                             .and_then(|body| {
                                 let body_len=body.len();
-                                self.build_event(body, headers, query_parameters)
+                                self.build_event(body, headers, query_parameters, path.as_str())
                                     .map(|events| (events, body_len))
                             });
 
@@ -290,3 +379,52 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{
+        write::{DeflateEncoder, GzEncoder},
+        Compression,
+    };
+    use std::io::Write;
+
+    fn decoded_with(encoding: &str, body: Vec<u8>, max_length: usize) -> Result<Bytes, u16> {
+        decode(&Some(encoding.to_owned()), body.into(), max_length).map_err(|error| error.code)
+    }
+
+    #[test]
+    fn decode_limits_decompressed_size() {
+        let payload = vec![0; 1024];
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&payload).unwrap();
+        let gzip = encoder.finish().unwrap();
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&payload).unwrap();
+        let deflate = encoder.finish().unwrap();
+        let snappy = snap::raw::Encoder::new().compress_vec(&payload).unwrap();
+        let zstd = zstd::stream::encode_all(&payload[..], 0).unwrap();
+
+        for (encoding, body) in vec![
+            ("gzip", gzip),
+            ("deflate", deflate),
+            ("snappy", snappy),
+            ("zstd", zstd),
+        ] {
+            assert!(body.len() < 100, "{} is {} bytes", encoding, body.len());
+            assert_eq!(
+                decoded_with(encoding, body.clone(), 1024),
+                Ok(payload.clone().into()),
+                "{}",
+                encoding
+            );
+            assert_eq!(
+                decoded_with(encoding, body, 1023),
+                Err(StatusCode::PAYLOAD_TOO_LARGE.as_u16()),
+                "{}",
+                encoding
+            );
+        }
+    }
+}
@@ -1,3 +1,11 @@
+#[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-nats",
+    feature = "sources-pulsar"
+))]
+mod acknowledgements;
+#[cfg(any(feature = "sources-socket", feature = "sources-syslog"))]
+mod delimiter;
 mod encoding_config;
 #[cfg(feature = "sources-utils-fake")]
 pub mod fake;
@@ -11,16 +19,24 @@ mod unix_datagram;
 #[cfg(all(unix, feature = "sources-utils-unix"))]
 mod unix_stream;
 
+#[cfg(any(
+    feature = "sources-amqp",
+    feature = "sources-nats",
+    feature = "sources-pulsar"
+))]
+pub(crate) use self::acknowledgements::{run_acknowledged, AcknowledgedMessages};
 #[cfg(any(feature = "sources-http", feature = "sources-heroku_logs"))]
 pub(crate) use self::http::add_query_parameters;
 #[cfg(feature = "sources-prometheus")]
-pub(crate) use self::http::decode;
+pub(crate) use self::http::{decode, MAX_DECODED_BYTES};
 #[cfg(feature = "sources-utils-http")]
 pub(crate) use self::http::{ErrorMessage, HttpSource, HttpSourceAuthConfig};
+#[cfg(any(feature = "sources-socket", feature = "sources-syslog"))]
+pub use delimiter::Delimiter;
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
-pub use tcp::{SocketListenAddr, TcpSource};
+pub use tcp::{make_listener, SocketListenAddr, TcpSource};
 #[cfg(all(unix, feature = "sources-socket",))]
 pub use unix_datagram::build_unix_datagram_source;
 #[cfg(all(unix, feature = "sources-utils-unix",))]
@@ -2,7 +2,7 @@ use crate::{
     async_read::VecAsyncReadExt,
     emit,
     event::Event,
-    internal_events::{ConnectionOpen, OpenGauge, UnixSocketError},
+    internal_events::{error_stage, ConnectionOpen, OpenGauge, UnixSocketError},
     shutdown::ShutdownSignal,
     sources::Source,
     Pipeline,
@@ -75,7 +75,8 @@ where
                     Err(error) => {
                         emit!(UnixSocketError {
                             error,
-                            path: &listen_path
+                            path: &listen_path,
+                            stage: error_stage::RECEIVING,
                         });
                         None
                     }
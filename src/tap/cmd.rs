@@ -0,0 +1,96 @@
+use tokio::stream::StreamExt;
+use vector_api_client::gql::{
+    tap_subscription::{EventDirection, TapNotificationKind, TapSubscriptionTapOn},
+    TapSubscriptionExt,
+};
+
+/// CLI command func for printing the events flowing into or out of a component, communicating
+/// with a local/remote Vector API server via HTTP/WebSockets
+pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
+    // Check that the GraphQL server is reachable
+    let client = match opts.api.client() {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
+    if let Err(code) = opts.api.check_reachable(&client).await {
+        return code;
+    }
+
+    let subscription_client = match opts.api.connect_subscription_client().await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Couldn't connect to Vector API via WebSockets: {:?}", e);
+            return exitcode::UNAVAILABLE;
+        }
+    };
+
+    let direction = if opts.inputs {
+        EventDirection::INPUT
+    } else {
+        EventDirection::OUTPUT
+    };
+    let subscription = subscription_client.tap_subscription(
+        opts.component.clone(),
+        direction,
+        opts.interval as i64,
+        opts.limit as i64,
+    );
+
+    tokio::pin! {
+        let stream = subscription.stream();
+    };
+
+    loop {
+        tokio::select! {
+            res = stream.next() => match res {
+                Some(Some(res)) => {
+                    for payload in res.data.map(|d| d.tap).unwrap_or_default() {
+                        print_payload(payload.on, opts.compact);
+                    }
+                }
+                // The API server went away, or the topology stopped
+                _ => {
+                    eprintln!("Vector API server closed the tap.");
+                    return exitcode::OK;
+                }
+            },
+            _ = tokio::signal::ctrl_c() => return exitcode::OK,
+        }
+    }
+}
+
+fn print_payload(payload: TapSubscriptionTapOn, compact: bool) {
+    match payload {
+        TapSubscriptionTapOn::TapLogEvent(event) => print_json(&event.json, compact),
+        TapSubscriptionTapOn::TapMetricEvent(event) => print_json(&event.json, compact),
+        TapSubscriptionTapOn::TapNotification(n) => match n.notification {
+            TapNotificationKind::MATCHED => {
+                eprintln!("[tap] Tapping component \"{}\".", n.component_name)
+            }
+            TapNotificationKind::NOT_MATCHED => eprintln!(
+                "[tap] Component \"{}\" doesn't exist. Waiting for it to be added.",
+                n.component_name
+            ),
+            TapNotificationKind::RECREATED => eprintln!(
+                "[tap] Component \"{}\" was rebuilt. Tapping it again.",
+                n.component_name
+            ),
+            TapNotificationKind::Other(other) => {
+                eprintln!("[tap] {}: {}", n.component_name, other)
+            }
+        },
+    }
+}
+
+/// Prints an event encoded as JSON, pretty-printed unless `compact`.
+fn print_json(json: &str, compact: bool) {
+    let pretty = if compact {
+        None
+    } else {
+        serde_json::from_str::<serde_json::Value>(json)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+    };
+
+    println!("{}", pretty.as_deref().unwrap_or(json));
+}
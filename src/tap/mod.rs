@@ -0,0 +1,32 @@
+mod cmd;
+
+use crate::remote::ApiOpts;
+use structopt::StructOpt;
+
+pub use cmd::cmd;
+
+#[derive(StructOpt, Debug, Clone)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Name of the component to tap
+    component: String,
+
+    /// Tap the events received by the component from its inputs, rather than the events it sends
+    #[structopt(long)]
+    inputs: bool,
+
+    /// Interval to send batches of sampled events at, in milliseconds
+    #[structopt(default_value = "500", short = "i", long)]
+    interval: u32,
+
+    /// Maximum number of events to sample each interval
+    #[structopt(default_value = "100", short = "l", long)]
+    limit: u32,
+
+    /// Print each event as a single line of JSON, rather than pretty-printed
+    #[structopt(short, long)]
+    compact: bool,
+
+    #[structopt(flatten)]
+    api: ApiOpts,
+}
@@ -1,11 +1,104 @@
 use serde::{Deserialize, Serialize};
+use std::{io, time::Duration};
+use tokio::net::TcpStream;
 
 /// Configuration for keepalive probes in a TCP stream.
 ///
 /// This config's properties map to TCP keepalive properties in Tokio:
 /// https://github.com/tokio-rs/tokio/blob/tokio-0.2.22/tokio/src/net/tcp/stream.rs#L516-L537
+///
+/// The interval between probes and the number of probes are only configurable on Linux.
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TcpKeepaliveConfig {
     pub time_secs: Option<u64>,
+    pub interval_secs: Option<u64>,
+    pub retries: Option<u32>,
+}
+
+pub(crate) fn set_keepalive(stream: &TcpStream, keepalive: TcpKeepaliveConfig) -> io::Result<()> {
+    stream.set_keepalive(keepalive.time_secs.map(Duration::from_secs))?;
+
+    if let Some(interval_secs) = keepalive.interval_secs {
+        set_tcp_option(stream, TcpOption::KeepaliveInterval, interval_secs)?;
+    }
+
+    if let Some(retries) = keepalive.retries {
+        set_tcp_option(stream, TcpOption::KeepaliveRetries, retries as u64)?;
+    }
+
+    Ok(())
+}
+
+/// Sets how long sent data may remain unacknowledged before the connection is closed, so that
+/// writes to a half-open connection fail instead of being retransmitted for minutes.
+pub(crate) fn set_user_timeout(stream: &TcpStream, timeout: Duration) -> io::Result<()> {
+    set_tcp_option(stream, TcpOption::UserTimeout, timeout.as_millis() as u64)
+}
+
+enum TcpOption {
+    KeepaliveInterval,
+    KeepaliveRetries,
+    UserTimeout,
+}
+
+#[cfg(target_os = "linux")]
+fn set_tcp_option(stream: &TcpStream, option: TcpOption, value: u64) -> io::Result<()> {
+    use std::{convert::TryFrom, os::unix::io::AsRawFd};
+
+    let name = match option {
+        TcpOption::KeepaliveInterval => libc::TCP_KEEPINTVL,
+        TcpOption::KeepaliveRetries => libc::TCP_KEEPCNT,
+        TcpOption::UserTimeout => libc::TCP_USER_TIMEOUT,
+    };
+    let value = libc::c_int::try_from(value)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "TCP option value too large."))?;
+
+    // SAFETY: The file descriptor is owned by `stream`, which outlives this call, and `value`
+    // is a valid `c_int` for the length passed along with it.
+    let result = unsafe {
+        libc::setsockopt(
+            stream.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            name,
+            &value as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_tcp_option(_: &TcpStream, _: TcpOption, _: u64) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "TCP option is only supported on Linux.",
+    ))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::*;
+    use crate::test_util::next_addr;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn sets_tcp_options() {
+        let addr = next_addr();
+        let _listener = TcpListener::bind(&addr).await.unwrap();
+        let stream = TcpStream::connect(&addr).await.unwrap();
+
+        let keepalive = TcpKeepaliveConfig {
+            time_secs: Some(30),
+            interval_secs: Some(5),
+            retries: Some(3),
+        };
+        assert!(set_keepalive(&stream, keepalive).is_ok());
+        assert!(set_user_timeout(&stream, Duration::from_secs(30)).is_ok());
+    }
 }
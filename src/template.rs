@@ -1,4 +1,8 @@
-use crate::{config::log_schema, event::Value, Event};
+use crate::{
+    config::log_schema,
+    event::{Metric, Value},
+    Event,
+};
 use bytes::Bytes;
 use chrono::{
     format::{strftime::StrftimeItems, Item},
@@ -150,10 +154,9 @@ fn render_fields(src: &str, event: &Event) -> Result<String, Vec<String>> {
                 .map(|s| s.as_str().trim())
                 .expect("src should match regex");
             match event {
-                Event::Metric(_) => None, // See issue #5985
-                Event::Log(log) => log.get(&key),
+                Event::Metric(metric) => render_metric_field(key, metric),
+                Event::Log(log) => log.get(&key).map(|val| val.to_string_lossy()),
             }
-            .map(|val| val.to_string_lossy())
             .unwrap_or_else(|| {
                 missing_fields.push(key.to_owned());
                 String::new()
@@ -167,12 +170,24 @@ fn render_fields(src: &str, event: &Event) -> Result<String, Vec<String>> {
     }
 }
 
+/// Metrics have no fields, so templates can use the `name` and `namespace` of
+/// a metric, and its tags as `tags.<name>`.
+fn render_metric_field(key: &str, metric: &Metric) -> Option<String> {
+    match key {
+        "name" => Some(metric.name().to_owned()),
+        "namespace" => metric.namespace().map(ToOwned::to_owned),
+        _ if key.starts_with("tags.") => metric.tag_value(&key["tags.".len()..]),
+        _ => None,
+    }
+}
+
 fn render_timestamp(src: &str, event: &Event) -> String {
     let timestamp = match event {
         Event::Log(log) => log
             .get(log_schema().timestamp_key())
-            .and_then(Value::as_timestamp),
-        _ => None,
+            .and_then(Value::as_timestamp)
+            .copied(),
+        Event::Metric(metric) => metric.data.timestamp,
     };
     if let Some(ts) = timestamp {
         ts.format(src).to_string()
@@ -221,6 +236,7 @@ impl Serialize for Template {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::event::metric::{MetricKind, MetricValue};
     use chrono::TimeZone;
 
     #[test]
@@ -413,6 +429,49 @@ mod tests {
         )
     }
 
+    #[test]
+    fn render_metric() {
+        let ts = Utc.ymd(2001, 2, 3).and_hms(4, 5, 6);
+
+        let event = Event::Metric(
+            Metric::new(
+                "requests".into(),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(Some("service".into()))
+            .with_tags(Some(
+                vec![("host".to_owned(), "localhost".to_owned())]
+                    .into_iter()
+                    .collect(),
+            ))
+            .with_timestamp(Some(ts)),
+        );
+
+        let template = Template::try_from("{{ namespace }}-{{ name }}-{{ tags.host }}-%F").unwrap();
+
+        assert_eq!(
+            Ok(Bytes::from("service-requests-localhost-2001-02-03")),
+            template.render(&event)
+        )
+    }
+
+    #[test]
+    fn render_metric_missing_tag() {
+        let event = Event::Metric(Metric::new(
+            "requests".into(),
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        let template = Template::try_from("{{ namespace }}-{{ tags.host }}").unwrap();
+
+        assert_eq!(
+            Err(vec!["namespace".to_owned(), "tags.host".to_owned()]),
+            template.render(&event)
+        )
+    }
+
     #[test]
     fn strftime_error() {
         assert_eq!(
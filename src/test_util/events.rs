@@ -0,0 +1,465 @@
+//! Generators of synthetic events, for benchmarks and soak tests.
+//!
+//! The generators are seeded, and timestamp the events from a fixed start, so
+//! a benchmark sees the same events on every run. They are iterators of
+//! events, meant to be used with `take`:
+//!
+//! ```
+//! use vector::test_util::events::{LogGenerator, LogShape};
+//!
+//! let events = LogGenerator::new(LogShape::AccessLog)
+//!     .fields(5, 10)
+//!     .cardinality(100)
+//!     .take(1000)
+//!     .collect::<Vec<_>>();
+//! ```
+
+use crate::event::{
+    metric::{Bucket, Metric, MetricKind, MetricValue, Quantile, Sample, StatisticKind},
+    Event, LogEvent,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::{distributions::Alphanumeric, rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use std::collections::{BTreeMap, BTreeSet};
+
+const SEED: u64 = 0x5eed;
+
+/// The timestamps of generated events, advancing by a fixed step from a fixed
+/// start.
+#[derive(Clone, Debug)]
+struct Clock {
+    next: DateTime<Utc>,
+    step: Duration,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            next: Utc.ymd(2021, 1, 1).and_hms(0, 0, 0),
+            step: Duration::milliseconds(1),
+        }
+    }
+}
+
+impl Clock {
+    fn tick(&mut self) -> DateTime<Utc> {
+        let now = self.next;
+        self.next = now + self.step;
+        now
+    }
+}
+
+const HOSTS: &[&str] = &["web-1", "web-2", "web-3", "api-1", "api-2"];
+const METHODS: &[&str] = &["GET", "GET", "GET", "POST", "PUT", "DELETE"];
+const PATHS: &[&str] = &[
+    "/",
+    "/index.html",
+    "/api/v1/users",
+    "/api/v1/orders",
+    "/static/app.js",
+    "/health",
+];
+const STATUSES: &[i64] = &[200, 200, 200, 201, 301, 404, 500];
+const USER_AGENTS: &[&str] = &[
+    "Mozilla/5.0 (X11; Linux x86_64; rv:84.0) Gecko/20100101 Firefox/84.0",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 11_1) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/14.0.2 Safari/605.1.15",
+    "curl/7.74.0",
+];
+
+/// The fields of generated log events.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogShape {
+    /// A `message` of random text, as read by line-oriented sources.
+    Message,
+    /// An HTTP access log, as parsed out of JSON logs, with the request and
+    /// response in nested objects.
+    AccessLog,
+}
+
+/// Generates log events of a given shape.
+#[derive(Clone, Debug)]
+pub struct LogGenerator {
+    shape: LogShape,
+    message_len: usize,
+    fields: usize,
+    field_len: usize,
+    cardinality: Option<usize>,
+    distinct: Vec<Event>,
+    clock: Clock,
+    rng: SmallRng,
+}
+
+impl LogGenerator {
+    pub fn new(shape: LogShape) -> Self {
+        Self {
+            shape,
+            message_len: 100,
+            fields: 0,
+            field_len: 10,
+            cardinality: None,
+            distinct: Vec::new(),
+            clock: Clock::default(),
+            rng: SmallRng::seed_from_u64(SEED),
+        }
+    }
+
+    /// Sets the length of the `message` field, which sizes events of the
+    /// `Message` shape. Defaults to 100.
+    pub fn message_len(mut self, len: usize) -> Self {
+        self.message_len = len;
+        self
+    }
+
+    /// Adds `count` top-level fields of random names and values of `len`
+    /// characters each.
+    pub fn fields(mut self, count: usize, len: usize) -> Self {
+        self.fields = count;
+        self.field_len = len;
+        self
+    }
+
+    /// Draws events out of `distinct` events only, for components whose cost
+    /// depends on how many distinct events or values they see.
+    pub fn cardinality(mut self, distinct: usize) -> Self {
+        self.cardinality = Some(distinct.max(1));
+        self
+    }
+
+    /// Timestamps the events from `start` on, `step` apart. Defaults to
+    /// 2021-01-01T00:00:00Z and a millisecond.
+    pub fn timestamps(mut self, start: DateTime<Utc>, step: Duration) -> Self {
+        self.clock = Clock { next: start, step };
+        self
+    }
+
+    fn generate(&mut self) -> Event {
+        let mut log = LogEvent::default();
+
+        match self.shape {
+            LogShape::Message => {
+                log.insert("message", random_string(&mut self.rng, self.message_len));
+            }
+            LogShape::AccessLog => {
+                let host = *HOSTS.choose(&mut self.rng).unwrap();
+                let method = *METHODS.choose(&mut self.rng).unwrap();
+                let path = *PATHS.choose(&mut self.rng).unwrap();
+                let status = *STATUSES.choose(&mut self.rng).unwrap();
+                let bytes = self.rng.gen_range(0..100_000i64);
+                let user_agent = *USER_AGENTS.choose(&mut self.rng).unwrap();
+                let client = format!(
+                    "10.{}.{}.{}",
+                    self.rng.gen::<u8>(),
+                    self.rng.gen::<u8>(),
+                    self.rng.gen::<u8>()
+                );
+
+                log.insert(
+                    "message",
+                    format!(
+                        "{} - - \"{} {} HTTP/1.1\" {} {}",
+                        client, method, path, status, bytes
+                    ),
+                );
+                log.insert("host", host);
+                log.insert("client", client);
+                log.insert("request.method", method);
+                log.insert("request.path", path);
+                log.insert("request.headers.user_agent", user_agent);
+                log.insert(
+                    "request.headers.request_id",
+                    random_string(&mut self.rng, 16),
+                );
+                log.insert("response.status", status);
+                log.insert("response.bytes", bytes);
+                log.insert("response.duration_ms", self.rng.gen_range(0.1..1000.0f64));
+            }
+        }
+
+        for _ in 0..self.fields {
+            let name = random_string(&mut self.rng, self.field_len);
+            let value = random_string(&mut self.rng, self.field_len);
+            log.insert(name, value);
+        }
+
+        log.insert(
+            crate::config::log_schema().timestamp_key(),
+            self.clock.tick(),
+        );
+
+        Event::Log(log)
+    }
+}
+
+impl Iterator for LogGenerator {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        match self.cardinality {
+            Some(distinct) if self.distinct.len() >= distinct => {
+                self.distinct.choose(&mut self.rng).cloned()
+            }
+            Some(_) => {
+                let event = self.generate();
+                self.distinct.push(event.clone());
+                Some(event)
+            }
+            None => Some(self.generate()),
+        }
+    }
+}
+
+/// The value of generated metrics.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetricShape {
+    Counter,
+    Gauge,
+    Set,
+    Distribution,
+    AggregatedHistogram,
+    AggregatedSummary,
+}
+
+impl MetricShape {
+    pub const ALL: &'static [MetricShape] = &[
+        MetricShape::Counter,
+        MetricShape::Gauge,
+        MetricShape::Set,
+        MetricShape::Distribution,
+        MetricShape::AggregatedHistogram,
+        MetricShape::AggregatedSummary,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MetricShape::Counter => "counter",
+            MetricShape::Gauge => "gauge",
+            MetricShape::Set => "set",
+            MetricShape::Distribution => "distribution",
+            MetricShape::AggregatedHistogram => "aggregated_histogram",
+            MetricShape::AggregatedSummary => "aggregated_summary",
+        }
+    }
+}
+
+/// Generates metrics with values of a given shape, spread over a number of
+/// series.
+#[derive(Clone, Debug)]
+pub struct MetricGenerator {
+    shape: MetricShape,
+    series: usize,
+    tags: usize,
+    clock: Clock,
+    rng: SmallRng,
+}
+
+impl MetricGenerator {
+    pub fn new(shape: MetricShape) -> Self {
+        Self {
+            shape,
+            series: 10,
+            tags: 2,
+            clock: Clock::default(),
+            rng: SmallRng::seed_from_u64(SEED),
+        }
+    }
+
+    /// Sets the number of distinct series, the cardinality of the metrics.
+    /// Defaults to 10.
+    pub fn series(mut self, series: usize) -> Self {
+        self.series = series.max(1);
+        self
+    }
+
+    /// Sets the number of tags of each series. Defaults to 2.
+    pub fn tags(mut self, tags: usize) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Timestamps the metrics from `start` on, `step` apart. Defaults to
+    /// 2021-01-01T00:00:00Z and a millisecond.
+    pub fn timestamps(mut self, start: DateTime<Utc>, step: Duration) -> Self {
+        self.clock = Clock { next: start, step };
+        self
+    }
+
+    fn value(&mut self) -> (MetricKind, MetricValue) {
+        let rng = &mut self.rng;
+
+        match self.shape {
+            MetricShape::Counter => (
+                MetricKind::Incremental,
+                MetricValue::Counter {
+                    value: rng.gen_range(1..100) as f64,
+                },
+            ),
+            MetricShape::Gauge => (
+                MetricKind::Absolute,
+                MetricValue::Gauge {
+                    value: rng.gen_range(0.0..100.0),
+                },
+            ),
+            MetricShape::Set => (
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: (0..rng.gen_range(1..10))
+                        .map(|_| format!("user-{}", rng.gen_range(0..1000)))
+                        .collect::<BTreeSet<_>>(),
+                },
+            ),
+            MetricShape::Distribution => (
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: (0..rng.gen_range(1..10))
+                        .map(|_| Sample {
+                            value: rng.gen_range(0.0..10.0),
+                            rate: 1,
+                        })
+                        .collect(),
+                    statistic: StatisticKind::Histogram,
+                },
+            ),
+            MetricShape::AggregatedHistogram => {
+                let counts = (0..4).map(|_| rng.gen_range(0..100)).collect::<Vec<u32>>();
+                (
+                    MetricKind::Absolute,
+                    MetricValue::AggregatedHistogram {
+                        buckets: [0.1, 1.0, 10.0, 100.0]
+                            .iter()
+                            .zip(&counts)
+                            .map(|(&upper_limit, &count)| Bucket { upper_limit, count })
+                            .collect(),
+                        count: counts.iter().sum(),
+                        sum: rng.gen_range(0.0..10_000.0),
+                    },
+                )
+            }
+            MetricShape::AggregatedSummary => (
+                MetricKind::Absolute,
+                MetricValue::AggregatedSummary {
+                    quantiles: [0.5, 0.9, 0.99]
+                        .iter()
+                        .map(|&upper_limit| Quantile {
+                            upper_limit,
+                            value: upper_limit * rng.gen_range(1.0..100.0),
+                        })
+                        .collect(),
+                    count: rng.gen_range(1..1000),
+                    sum: rng.gen_range(0.0..10_000.0),
+                },
+            ),
+        }
+    }
+}
+
+impl Iterator for MetricGenerator {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        let series = self.rng.gen_range(0..self.series);
+        let tags = (0..self.tags)
+            .map(|tag| (format!("tag{}", tag), format!("value{}", series)))
+            .collect::<BTreeMap<_, _>>();
+        let (kind, value) = self.value();
+
+        let metric = Metric::new(format!("{}_{}", self.shape.as_str(), series), kind, value)
+            .with_namespace(Some("synthetic".into()))
+            .with_tags(if tags.is_empty() { None } else { Some(tags) })
+            .with_timestamp(Some(self.clock.tick()));
+
+        Some(Event::Metric(metric))
+    }
+}
+
+fn random_string(rng: &mut SmallRng, len: usize) -> String {
+    rng.sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn generates_the_same_logs() {
+        let messages = |generator: LogGenerator| {
+            generator
+                .take(10)
+                .map(|event| event.as_log()["message"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            messages(LogGenerator::new(LogShape::AccessLog)),
+            messages(LogGenerator::new(LogShape::AccessLog))
+        );
+    }
+
+    #[test]
+    fn generates_the_same_timestamps() {
+        let logs = LogGenerator::new(LogShape::Message)
+            .timestamps(Utc.ymd(2021, 6, 1).and_hms(0, 0, 0), Duration::seconds(1))
+            .take(2)
+            .map(|event| event.as_log()["timestamp"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            logs,
+            vec![
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 0).into(),
+                Utc.ymd(2021, 6, 1).and_hms(0, 0, 1).into(),
+            ]
+        );
+
+        let timestamps = |generator: MetricGenerator| {
+            generator
+                .take(10)
+                .map(|event| event.into_metric().data.timestamp)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            timestamps(MetricGenerator::new(MetricShape::Gauge)),
+            timestamps(MetricGenerator::new(MetricShape::Gauge))
+        );
+    }
+
+    #[test]
+    fn sizes_logs() {
+        let event = LogGenerator::new(LogShape::Message)
+            .message_len(42)
+            .fields(3, 5)
+            .next()
+            .unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["message"].to_string_lossy().len(), 42);
+        // The message, the timestamp and the added fields.
+        assert_eq!(log.keys().count(), 5);
+    }
+
+    #[test]
+    fn bounds_log_cardinality() {
+        let messages = LogGenerator::new(LogShape::Message)
+            .cardinality(5)
+            .take(100)
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(messages.len(), 5);
+    }
+
+    #[test]
+    fn bounds_metric_series() {
+        for &shape in MetricShape::ALL {
+            let series = MetricGenerator::new(shape)
+                .series(3)
+                .take(100)
+                .map(|event| event.into_metric().series)
+                .collect::<HashSet<_>>();
+
+            assert_eq!(series.len(), 3, "{:?}", shape);
+        }
+    }
+}
@@ -40,7 +40,11 @@ impl TlsSettings {
 impl MaybeTlsSettings {
     pub(crate) async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBind)?;
+        self.listener(listener)
+    }
 
+    /// Wraps an already bound `listener`, accepting TLS connections if configured.
+    pub(crate) fn listener(&self, listener: TcpListener) -> crate::tls::Result<MaybeTlsListener> {
         let acceptor = match self {
             Self::Tls(tls) => Some(tls.acceptor()?),
             Self::Raw(()) => None,
@@ -175,7 +179,7 @@ impl MaybeTlsIncomingStream<TcpStream> {
             )
         })?;
 
-        stream.set_keepalive(keepalive.time_secs.map(std::time::Duration::from_secs))?;
+        crate::tcp::set_keepalive(stream, keepalive)?;
 
         Ok(())
     }
@@ -132,7 +132,29 @@ impl MaybeTlsStream<TcpStream> {
             Self::Tls(tls) => tls.get_ref(),
         };
 
-        stream.set_keepalive(keepalive.time_secs.map(Duration::from_secs))?;
+        crate::tcp::set_keepalive(stream, keepalive)?;
+
+        Ok(())
+    }
+
+    pub fn set_nodelay(&mut self, nodelay: bool) -> std::io::Result<()> {
+        let stream = match self {
+            Self::Raw(raw) => raw,
+            Self::Tls(tls) => tls.get_ref(),
+        };
+
+        stream.set_nodelay(nodelay)?;
+
+        Ok(())
+    }
+
+    pub fn set_user_timeout(&mut self, timeout: Duration) -> std::io::Result<()> {
+        let stream = match self {
+            Self::Raw(raw) => raw,
+            Self::Tls(tls) => tls.get_ref(),
+        };
+
+        crate::tcp::set_user_timeout(stream, timeout)?;
 
         Ok(())
     }
@@ -1,51 +1,41 @@
 use super::{
     dashboard::{init_dashboard, is_tty},
-    metrics, state,
+    metrics, state, View,
 };
-use crate::config;
-use indoc::indoc;
-use url::Url;
-use vector_api_client::{connect_subscription_client, gql::HealthQueryExt, Client};
+use crate::remote::ApiOpts;
+use std::{sync::Arc, time::Duration};
+use vector_api_client::{Client, SubscriptionClient};
+
+/// Delay between attempts to reconnect to the API server
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
 
 /// CLI command func for displaying Vector components, and communicating with a local/remote
 /// Vector API server via HTTP/WebSockets
 pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
+    run(opts, View::Components).await
+}
+
+/// CLI command func for displaying the buffers of Vector components, and how full they are
+pub async fn buffers_cmd(opts: &super::Opts) -> exitcode::ExitCode {
+    run(opts, View::Buffers).await
+}
+
+async fn run(opts: &super::Opts, view: View) -> exitcode::ExitCode {
     // Exit early if the terminal is not a teletype
     if !is_tty() {
         eprintln!("Terminal must be a teletype (TTY) to display a Vector dashboard.");
         return exitcode::IOERR;
     }
 
-    // Use the provided URL as the Vector GraphQL API server, or default to the local port
-    // provided by the API config. This will work despite `api` and `api-client` being distinct
-    // features; the config is available even if `api` is disabled
-    let url = opts.url.clone().unwrap_or_else(|| {
-        let addr = config::api::default_address().unwrap();
-        Url::parse(&*format!("http://{}/graphql", addr))
-            .expect("Couldn't parse default API URL. Please report this.")
-    });
-
     // Create a new API client for connecting to the local/remote Vector instance
-    let client = Client::new(url.clone());
+    let client = match opts.api.client() {
+        Ok(client) => client,
+        Err(code) => return code,
+    };
 
     // Check that the GraphQL server is reachable
-    match client.health_query().await {
-        Ok(_) => (),
-        _ => {
-            eprintln!(
-                indoc! {"
-                    Vector API server isn't reachable ({}).
-
-                    Have you enabled the API?
-
-                    To enable the API, add the following to your `vector.toml` config file:
-
-                    [api]
-                      enabled = true"},
-                url
-            );
-            return exitcode::UNAVAILABLE;
-        }
+    if let Err(code) = opts.api.check_reachable(&client).await {
+        return code;
     }
 
     // Create a metrics state updater
@@ -53,23 +43,14 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
 
     // Get the initial component state
     let sender = match metrics::init_components(&client).await {
-        Ok(state) => state::updater(state, rx).await,
+        Ok(components) => state::updater(state::State::new(components), rx).await,
         _ => {
             eprintln!("Couldn't query Vector components.");
             return exitcode::UNAVAILABLE;
         }
     };
 
-    // Change the HTTP schema to WebSockets
-    let mut ws_url = url.clone();
-    ws_url
-        .set_scheme(match url.scheme() {
-            "https" => "wss",
-            _ => "ws",
-        })
-        .expect("Couldn't build WebSocket URL. Please report.");
-
-    let subscription_client = match connect_subscription_client(ws_url).await {
+    let subscription_client = match opts.api.connect_subscription_client().await {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Couldn't connect to Vector API via WebSockets: {:?}", e);
@@ -77,11 +58,17 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         }
     };
 
-    // Subscribe to updated metrics
-    metrics::subscribe(subscription_client, tx.clone(), opts.interval as i64);
+    // Subscribe to updated metrics, reconnecting whenever the connection drops
+    tokio::spawn(subscribe_with_reconnect(
+        client,
+        opts.api.clone(),
+        subscription_client,
+        tx,
+        opts.interval as i64,
+    ));
 
     // Initialize the dashboard
-    match init_dashboard(url.as_str(), opts, sender).await {
+    match init_dashboard(opts.api.url().as_str(), opts, view, sender).await {
         Ok(_) => exitcode::OK,
         _ => {
             eprintln!("Your terminal doesn't support building a dashboard. Exiting.");
@@ -89,3 +76,37 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
         }
     }
 }
+
+/// Subscribes to metrics through `subscription_client`. Once the connection to the API server
+/// drops, marks the dashboard as disconnected, and keeps trying to reconnect. On reconnecting,
+/// the components are queried again, since they may have changed in the meantime.
+async fn subscribe_with_reconnect(
+    client: Client,
+    api: ApiOpts,
+    mut subscription_client: SubscriptionClient,
+    mut tx: state::EventTx,
+    interval: i64,
+) {
+    loop {
+        let shared = Arc::new(subscription_client);
+        metrics::subscribe(Arc::clone(&shared), tx.clone(), interval);
+        shared.closed().await;
+        drop(shared);
+
+        if tx.send(state::EventType::Disconnected).await.is_err() {
+            // The dashboard exited
+            return;
+        }
+
+        subscription_client = loop {
+            tokio::time::delay_for(RECONNECT_DELAY).await;
+
+            if let Ok(subscription_client) = api.connect_subscription_client().await {
+                if let Ok(components) = metrics::init_components(&client).await {
+                    let _ = tx.send(state::EventType::Reconnected(components)).await;
+                    break subscription_client;
+                }
+            }
+        };
+    }
+}
@@ -1,4 +1,4 @@
-use super::{events::capture_key_press, state};
+use super::{events::capture_key_press, state, SortField, View};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture, KeyCode},
     execute,
@@ -75,15 +75,81 @@ impl HumanFormatter for i64 {
 
 static HEADER: [&str; 6] = ["Name", "Kind", "Type", "Events", "Bytes", "Errors"];
 
+static BUFFERS_HEADER: [&str; 9] = [
+    "Name",
+    "Kind",
+    "Type",
+    "Buffer",
+    "Events",
+    "Size",
+    "Disk usage",
+    "Used",
+    "Discarded",
+];
+
+/// How the component rows are ordered, changed with key presses.
+#[derive(Debug, Clone, Copy)]
+struct Sort {
+    field: SortField,
+    descending: bool,
+}
+
+impl Sort {
+    /// Orders rows by `field`, numeric fields from highest to lowest.
+    fn new(field: SortField) -> Self {
+        Self {
+            field,
+            descending: !matches!(field, SortField::Name | SortField::Kind | SortField::Type),
+        }
+    }
+
+    /// Sorts by the next field, from its default direction.
+    fn next(self) -> Self {
+        Self::new(self.field.next())
+    }
+
+    fn reverse(self) -> Self {
+        Self {
+            descending: !self.descending,
+            ..self
+        }
+    }
+
+    /// Returns the rows of `state`, in order. Ties are broken by name.
+    fn rows(self, state: &state::State) -> Vec<&state::ComponentRow> {
+        let mut rows = state.components.values().collect::<Vec<_>>();
+        rows.sort_by(|a, b| {
+            let ordering = self.field.compare(a, b);
+            let ordering = if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| a.name.cmp(&b.name))
+        });
+        rows
+    }
+
+    fn title(self, view: View) -> String {
+        format!(
+            "{} (sorted by {} {})",
+            view.title(),
+            self.field.title(),
+            if self.descending { "▼" } else { "▲" }
+        )
+    }
+}
+
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
     opts: &'a super::Opts,
+    view: View,
 }
 
 impl<'a> Widgets<'a> {
     /// Creates a new Widgets, containing constraints to re-use across renders.
-    pub fn new(url_string: &'a str, opts: &'a super::Opts) -> Self {
+    pub fn new(url_string: &'a str, opts: &'a super::Opts, view: View) -> Self {
         let constraints = vec![
             Constraint::Length(3),
             Constraint::Max(90),
@@ -94,18 +160,45 @@ impl<'a> Widgets<'a> {
             constraints,
             url_string,
             opts,
+            view,
+        }
+    }
+
+    /// Formats a metric, humanized if requested.
+    fn format(&self, value: i64) -> String {
+        if self.opts.human_metrics {
+            value.human_format()
+        } else {
+            value.thousands_format()
+        }
+    }
+
+    /// Formats a bytes metric, humanized if requested.
+    fn format_bytes(&self, value: i64) -> String {
+        if self.opts.human_metrics {
+            value.human_format_bytes()
+        } else {
+            value.thousands_format()
         }
     }
 
-    /// Renders a title showing 'Vector', and the URL the dashboard is currently connected to.
-    fn title<B: Backend>(&'a self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from(vec![
+    /// Renders a title showing 'Vector', and the URL the dashboard is currently connected to,
+    /// or trying to reconnect to.
+    fn title<B: Backend>(&'a self, f: &mut Frame<B>, state: &state::State, area: Rect) {
+        let mut spans = vec![
             Span::from(self.url_string),
             Span::styled(
                 format!(" | Sampling @ {}ms", self.opts.interval.thousands_format()),
                 Style::default().fg(Color::Gray),
             ),
-        ])];
+        ];
+        if !state.connected {
+            spans.push(Span::styled(
+                " | Disconnected, reconnecting...",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        let text = vec![Spans::from(spans)];
 
         let block = Block::default().borders(Borders::ALL).title(Span::styled(
             "Vector",
@@ -119,8 +212,14 @@ impl<'a> Widgets<'a> {
     }
 
     /// Renders a components table, showing sources, transforms and sinks in tabular form, with
-    /// statistics pulled from `ComponentsState`,
-    fn components_table<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
+    /// statistics pulled from `ComponentsState`. Components currently erroring are highlighted.
+    fn components_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        sort: Sort,
+        area: Rect,
+    ) {
         // Header columns
         let header = HEADER
             .iter()
@@ -128,7 +227,7 @@ impl<'a> Widgets<'a> {
             .collect::<Vec<_>>();
 
         // Data columns
-        let items = state.iter().map(|(_, r)| {
+        let items = sort.rows(state).into_iter().map(|r| {
             let mut data = vec![r.name.clone(), r.kind.clone(), r.component_type.clone()];
 
             let formatted_metrics = [
@@ -156,20 +255,32 @@ impl<'a> Widgets<'a> {
                         r.processed_bytes_throughput_sec.human_format_bytes()
                     ),
                 },
-                if self.opts.human_metrics {
-                    r.errors.human_format()
-                } else {
-                    r.errors.thousands_format()
+                match r.errors_total {
+                    0 => "--".to_string(),
+                    v => format!(
+                        "{} ({}/s)",
+                        self.format(v),
+                        r.errors_throughput_sec.human_format()
+                    ),
                 },
             ];
 
             data.extend_from_slice(&formatted_metrics);
-            Row::new(data).style(Style::default())
+
+            let style = match r.errors_throughput_sec {
+                0 => Style::default(),
+                _ => Style::default().fg(Color::Red),
+            };
+            Row::new(data).style(style)
         });
 
         let w = Table::new(items)
             .header(Row::new(header).bottom_margin(1))
-            .block(Block::default().borders(Borders::ALL).title("Components"))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(sort.title(self.view)),
+            )
             .column_spacing(2)
             .widths(&[
                 Constraint::Percentage(20),
@@ -183,9 +294,84 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
+    /// Renders a buffers table, showing the buffers that sinks and transforms read from in
+    /// tabular form, with statistics pulled from `ComponentsState`. Buffers that dropped
+    /// events are highlighted.
+    fn buffers_table<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        state: &state::State,
+        sort: Sort,
+        area: Rect,
+    ) {
+        // Header columns
+        let header = BUFFERS_HEADER
+            .iter()
+            .map(|s| Cell::from(*s).style(Style::default().add_modifier(Modifier::BOLD)))
+            .collect::<Vec<_>>();
+
+        // Data columns
+        let items = sort.rows(state).into_iter().filter_map(|r| {
+            let buffer = r.buffer.as_ref()?;
+
+            let mut data = vec![
+                r.name.clone(),
+                r.kind.clone(),
+                r.component_type.clone(),
+                buffer.buffer_type.clone(),
+            ];
+
+            let formatted_metrics = [
+                self.format(buffer.events),
+                buffer
+                    .byte_size
+                    .map_or_else(|| "N/A".to_string(), |v| self.format_bytes(v)),
+                buffer
+                    .disk_usage
+                    .map_or_else(|| "N/A".to_string(), |v| self.format_bytes(v)),
+                buffer
+                    .utilization
+                    .map_or_else(|| "N/A".to_string(), |v| format!("{:.0}%", v)),
+                self.format(buffer.discarded_events_total),
+            ];
+
+            data.extend_from_slice(&formatted_metrics);
+
+            let style = match buffer.discarded_events_total {
+                0 => Style::default(),
+                _ => Style::default().fg(Color::Red),
+            };
+            Some(Row::new(data).style(style))
+        });
+
+        let w = Table::new(items)
+            .header(Row::new(header).bottom_margin(1))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(sort.title(self.view)),
+            )
+            .column_spacing(2)
+            .widths(&[
+                Constraint::Percentage(18),
+                Constraint::Percentage(10),
+                Constraint::Percentage(10),
+                Constraint::Percentage(8),
+                Constraint::Percentage(11),
+                Constraint::Percentage(11),
+                Constraint::Percentage(11),
+                Constraint::Percentage(7),
+                Constraint::Percentage(10),
+            ]);
+
+        f.render_widget(w, area);
+    }
+
     /// Alerts the user to resize the window to view columns
-    fn components_resize_window<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let block = Block::default().borders(Borders::ALL).title("Components");
+    fn resize_window<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.view.title());
         let w = Paragraph::new("Expand the window to > 80 chars to view metrics")
             .block(block)
             .wrap(Wrap { trim: true });
@@ -193,9 +379,11 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
-    /// Renders a box showing instructions on how to exit from `vector top`.
+    /// Renders a box showing instructions on how to sort the table, and exit from `vector top`.
     fn quit_box<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
-        let text = vec![Spans::from("To quit, press ESC or 'q'")];
+        let text = vec![Spans::from(
+            "To sort by the next column, press 's'. To reverse the order, press 'r'. To quit, press ESC or 'q'",
+        )];
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -209,19 +397,21 @@ impl<'a> Widgets<'a> {
     }
 
     /// Draw a single frame. Creates a layout and renders widgets into it.
-    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: state::State) {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, sort: Sort) {
         let size = f.size();
         let rects = Layout::default()
             .constraints(self.constraints.as_ref())
             .split(size);
 
-        self.title(f, rects[0]);
+        self.title(f, state, rects[0]);
 
         // Require a minimum of 80 chars of line width to display the table
-        if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+        if size.width < 80 {
+            self.resize_window(f, rects[1]);
+        } else if self.view == View::Buffers {
+            self.buffers_table(f, state, sort, rects[1]);
         } else {
-            self.components_resize_window(f, rects[1]);
+            self.components_table(f, state, sort, rects[1]);
         }
 
         self.quit_box(f, rects[2]);
@@ -240,6 +430,7 @@ pub fn is_tty() -> bool {
 pub async fn init_dashboard<'a>(
     url: &'a str,
     opts: &'a super::Opts,
+    view: View,
     mut state_rx: state::StateRx,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Capture key presses, to determine when to quit
@@ -261,20 +452,35 @@ pub async fn init_dashboard<'a>(
     // Clear the screen, readying it for output
     terminal.clear()?;
 
-    let widgets = Widgets::new(url, opts);
+    let widgets = Widgets::new(url, opts, view);
+    let mut sort = Sort::new(opts.sort);
+
+    // The latest state, kept to redraw when the sort order changes
+    let mut state = state_rx
+        .recv()
+        .await
+        .unwrap_or_else(|| state::State::new(state::ComponentsState::new()));
+    terminal.draw(|f| widgets.draw(f, &state, sort))?;
 
     loop {
         tokio::select! {
-            Some(state) = state_rx.recv() => {
-                terminal.draw(|f| widgets.draw(f, state))?;
+            Some(new_state) = state_rx.recv() => {
+                state = new_state;
             },
             k = key_press_rx.recv() => {
-                if let KeyCode::Esc | KeyCode::Char('q') = k.unwrap() {
-                    let _ = key_press_kill_tx.send(());
-                    break
+                match k.unwrap() {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        let _ = key_press_kill_tx.send(());
+                        break
+                    }
+                    KeyCode::Char('s') => sort = sort.next(),
+                    KeyCode::Char('r') => sort = sort.reverse(),
+                    _ => continue,
                 }
             }
         }
+
+        terminal.draw(|f| widgets.draw(f, &state, sort))?;
     }
 
     // Clean-up terminal
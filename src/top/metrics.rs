@@ -26,7 +26,9 @@ async fn component_added(client: Arc<SubscriptionClient>, mut tx: state::EventTx
                     processed_events_throughput_sec: 0,
                     processed_bytes_total: 0,
                     processed_bytes_throughput_sec: 0,
-                    errors: 0,
+                    errors_total: 0,
+                    errors_throughput_sec: 0,
+                    buffer: None,
                 }))
                 .await;
         }
@@ -147,11 +149,88 @@ async fn processed_bytes_throughputs(
     }
 }
 
-/// Subscribe to each metrics channel through a separate client. This is a temporary workaround
-/// until client multiplexing is fixed. In future, we should be able to use a single client
-pub fn subscribe(client: SubscriptionClient, tx: state::EventTx, interval: i64) {
-    let client = Arc::new(client);
+async fn errors_totals(client: Arc<SubscriptionClient>, mut tx: state::EventTx, interval: i64) {
+    let res = client.component_errors_totals_subscription(interval);
+
+    tokio::pin! {
+        let stream = res.stream();
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_errors_totals;
+            let _ = tx
+                .send(state::EventType::ErrorsTotals(
+                    c.into_iter()
+                        .map(|c| (c.name, c.metric.errors_total as i64))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn errors_throughputs(
+    client: Arc<SubscriptionClient>,
+    mut tx: state::EventTx,
+    interval: i64,
+) {
+    let res = client.component_errors_throughputs_subscription(interval);
+
+    tokio::pin! {
+        let stream = res.stream();
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_errors_throughputs;
+            let _ = tx
+                .send(state::EventType::ErrorsThroughputs(
+                    interval,
+                    c.into_iter().map(|c| (c.name, c.throughput)).collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+async fn buffer_usages(client: Arc<SubscriptionClient>, mut tx: state::EventTx, interval: i64) {
+    let res = client.component_buffer_usages_subscription(interval);
 
+    tokio::pin! {
+        let stream = res.stream();
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_buffer_usages;
+            let _ = tx
+                .send(state::EventType::BufferUsages(
+                    c.into_iter()
+                        .map(|c| {
+                            (
+                                c.name,
+                                state::BufferRow {
+                                    buffer_type: c.metric.buffer_type,
+                                    events: c.metric.events as i64,
+                                    byte_size: c.metric.byte_size.map(|b| b as i64),
+                                    disk_usage: c.metric.disk_usage.map(|b| b as i64),
+                                    utilization: c.metric.utilization,
+                                    discarded_events_total: c.metric.discarded_events_total as i64,
+                                },
+                            )
+                        })
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
+/// Subscribe to each metrics channel through a separate client. This is a temporary workaround
+/// until client multiplexing is fixed. In future, we should be able to use a single client.
+/// Each subscription ends once the connection to the API server closes
+pub fn subscribe(client: Arc<SubscriptionClient>, tx: state::EventTx, interval: i64) {
     tokio::spawn(component_added(Arc::clone(&client), tx.clone()));
     tokio::spawn(component_removed(Arc::clone(&client), tx.clone()));
     tokio::spawn(processed_events_totals(
@@ -171,14 +250,21 @@ pub fn subscribe(client: SubscriptionClient, tx: state::EventTx, interval: i64)
     ));
     tokio::spawn(processed_bytes_throughputs(
         Arc::clone(&client),
-        tx,
+        tx.clone(),
+        interval,
+    ));
+    tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval));
+    tokio::spawn(errors_throughputs(
+        Arc::clone(&client),
+        tx.clone(),
         interval,
     ));
+    tokio::spawn(buffer_usages(client, tx, interval));
 }
 
 /// Retrieve the initial components/metrics for first paint. Further updating the metrics
 /// will be handled by subscriptions.
-pub async fn init_components(client: &Client) -> Result<state::State, ()> {
+pub async fn init_components(client: &Client) -> Result<state::ComponentsState, ()> {
     // Execute a query to get the latest components, and aggregate metrics for each resource.
     // Since we don't know currently have a mechanism for scrolling/paging through results,
     // we're using an artificially high page size to capture all likely component configurations.
@@ -204,12 +290,14 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         processed_events_throughput_sec: 0,
                         processed_bytes_total: d.on.processed_bytes_total(),
                         processed_bytes_throughput_sec: 0,
-                        errors: 0,
+                        errors_total: 0,
+                        errors_throughput_sec: 0,
+                        buffer: None,
                     },
                 ))
             })
         })
-        .collect::<state::State>();
+        .collect::<state::ComponentsState>();
 
     Ok(rows)
 }
@@ -4,10 +4,11 @@ mod events;
 mod metrics;
 mod state;
 
+use crate::remote::ApiOpts;
+use std::{cmp::Ordering, str::FromStr};
 use structopt::StructOpt;
-use url::Url;
 
-pub use cmd::cmd;
+pub use cmd::{buffers_cmd, cmd};
 
 #[derive(StructOpt, Debug, Clone)]
 #[structopt(rename_all = "kebab-case")]
@@ -16,11 +17,152 @@ pub struct Opts {
     #[structopt(default_value = "500", short = "i", long)]
     interval: u64,
 
-    /// Vector GraphQL API server endpoint
-    #[structopt(short, long)]
-    url: Option<Url>,
-
     /// Humanize metrics, using numeric suffixes - e.g. 1,100 = 1.10 k, 1,000,000 = 1.00 M
     #[structopt(short, long)]
     human_metrics: bool,
+
+    /// Column to initially sort components by: name, kind, type, events, bytes, errors or
+    /// buffer. Press 's' in the dashboard to change it
+    #[structopt(default_value = "name", short, long)]
+    sort: SortField,
+
+    #[structopt(flatten)]
+    api: ApiOpts,
+}
+
+/// Column the dashboard sorts components by. Numeric columns sort by throughput, then total.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortField {
+    Name,
+    Kind,
+    Type,
+    Events,
+    Bytes,
+    Errors,
+    Buffer,
+}
+
+impl SortField {
+    const ALL: [SortField; 7] = [
+        SortField::Name,
+        SortField::Kind,
+        SortField::Type,
+        SortField::Events,
+        SortField::Bytes,
+        SortField::Errors,
+        SortField::Buffer,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SortField::Name => "name",
+            SortField::Kind => "kind",
+            SortField::Type => "type",
+            SortField::Events => "events",
+            SortField::Bytes => "bytes",
+            SortField::Errors => "errors",
+            SortField::Buffer => "buffer",
+        }
+    }
+
+    /// The field after this one, wrapping around.
+    fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|f| *f == self)
+            .unwrap_or_default();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn compare(self, a: &state::ComponentRow, b: &state::ComponentRow) -> Ordering {
+        match self {
+            SortField::Name => a.name.cmp(&b.name),
+            SortField::Kind => a.kind.cmp(&b.kind),
+            SortField::Type => a.component_type.cmp(&b.component_type),
+            SortField::Events => (a.processed_events_throughput_sec, a.processed_events_total)
+                .cmp(&(b.processed_events_throughput_sec, b.processed_events_total)),
+            SortField::Bytes => (a.processed_bytes_throughput_sec, a.processed_bytes_total)
+                .cmp(&(b.processed_bytes_throughput_sec, b.processed_bytes_total)),
+            SortField::Errors => (a.errors_throughput_sec, a.errors_total)
+                .cmp(&(b.errors_throughput_sec, b.errors_total)),
+            SortField::Buffer => {
+                let usage = |r: &state::ComponentRow| {
+                    r.buffer
+                        .as_ref()
+                        .map(|b| (b.utilization.unwrap_or_default(), b.events))
+                };
+                usage(a).partial_cmp(&usage(b)).unwrap_or(Ordering::Equal)
+            }
+        }
+    }
+}
+
+impl FromStr for SortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|f| f.title() == s)
+            .ok_or_else(|| format!("unknown sort column `{}`", s))
+    }
+}
+
+/// What the dashboard displays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum View {
+    /// Components, and the events and bytes they processed
+    Components,
+    /// Buffers of components, and how full they are
+    Buffers,
+}
+
+impl View {
+    fn title(self) -> &'static str {
+        match self {
+            View::Components => "Components",
+            View::Buffers => "Buffers",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, errors_throughput_sec: i64, errors_total: i64) -> state::ComponentRow {
+        state::ComponentRow {
+            name: name.to_owned(),
+            kind: "sink".to_owned(),
+            component_type: "blackhole".to_owned(),
+            processed_events_total: 0,
+            processed_events_throughput_sec: 0,
+            processed_bytes_total: 0,
+            processed_bytes_throughput_sec: 0,
+            errors_total,
+            errors_throughput_sec,
+            buffer: None,
+        }
+    }
+
+    #[test]
+    fn sort_field_cycles() {
+        let mut field = SortField::Name;
+        for _ in 0..SortField::ALL.len() {
+            field = field.next();
+        }
+        assert_eq!(field, SortField::Name);
+        assert_eq!("errors".parse::<SortField>(), Ok(SortField::Errors));
+        assert!("nope".parse::<SortField>().is_err());
+    }
+
+    #[test]
+    fn sort_errors_by_throughput_then_total() {
+        let (a, b, c) = (row("a", 0, 10), row("b", 5, 1), row("c", 0, 20));
+        let mut rows = vec![&a, &b, &c];
+        rows.sort_by(|x, y| SortField::Errors.compare(x, y));
+        let names = rows.iter().map(|r| r.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "c", "b"]);
+    }
 }
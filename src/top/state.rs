@@ -11,15 +11,39 @@ pub enum EventType {
     ProcessedBytesTotals(Vec<NamedMetric>),
     /// Interval + named metric
     ProcessedBytesThroughputs(i64, Vec<NamedMetric>),
+    ErrorsTotals(Vec<NamedMetric>),
+    /// Interval + named metric
+    ErrorsThroughputs(i64, Vec<NamedMetric>),
+    BufferUsages(Vec<(String, BufferRow)>),
     ComponentAdded(ComponentRow),
     ComponentRemoved(String),
+    /// The connection to the API server dropped, so metrics are stale until reconnected
+    Disconnected,
+    /// Reconnected to the API server, with the components as of reconnecting
+    Reconnected(ComponentsState),
 }
 
-pub type State = BTreeMap<String, ComponentRow>;
+pub type ComponentsState = BTreeMap<String, ComponentRow>;
 pub type EventTx = mpsc::Sender<EventType>;
 pub type EventRx = mpsc::Receiver<EventType>;
 pub type StateRx = mpsc::Receiver<State>;
 
+/// What the dashboard renders: the components, and whether their metrics are live.
+#[derive(Debug, Clone)]
+pub struct State {
+    pub components: ComponentsState,
+    pub connected: bool,
+}
+
+impl State {
+    pub fn new(components: ComponentsState) -> Self {
+        Self {
+            components,
+            connected: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ComponentRow {
     pub name: String,
@@ -29,7 +53,21 @@ pub struct ComponentRow {
     pub processed_events_throughput_sec: i64,
     pub processed_bytes_total: i64,
     pub processed_bytes_throughput_sec: i64,
-    pub errors: i64,
+    pub errors_total: i64,
+    pub errors_throughput_sec: i64,
+    pub buffer: Option<BufferRow>,
+}
+
+/// Usage of the buffer a component reads from. Only sinks and transforms read from a buffer.
+#[derive(Debug, Clone)]
+pub struct BufferRow {
+    pub buffer_type: String,
+    pub events: i64,
+    pub byte_size: Option<i64>,
+    pub disk_usage: Option<i64>,
+    /// Percentage of the capacity of the buffer in use.
+    pub utilization: Option<f64>,
+    pub discarded_events_total: i64,
 }
 
 /// Takes the receiver `EventRx` channel, and returns a `StateTx` state transmitter. This
@@ -47,14 +85,14 @@ pub async fn updater(mut state: State, mut event_rx: EventRx) -> StateRx {
                 match event_type {
                     EventType::ProcessedEventsTotals(rows) => {
                         for (name, v) in rows {
-                            if let Some(r) = state.get_mut(&name) {
+                            if let Some(r) = state.components.get_mut(&name) {
                                 r.processed_events_total = v;
                             }
                         }
                     }
                     EventType::ProcessedEventsThroughputs(interval, rows) => {
                         for (name, v) in rows {
-                            if let Some(r) = state.get_mut(&name) {
+                            if let Some(r) = state.components.get_mut(&name) {
                                 r.processed_events_throughput_sec =
                                     (v as f64 * (1000.0 / interval as f64)) as i64;
                             }
@@ -62,24 +100,52 @@ pub async fn updater(mut state: State, mut event_rx: EventRx) -> StateRx {
                     }
                     EventType::ProcessedBytesTotals(rows) => {
                         for (name, v) in rows {
-                            if let Some(r) = state.get_mut(&name) {
+                            if let Some(r) = state.components.get_mut(&name) {
                                 r.processed_bytes_total = v;
                             }
                         }
                     }
                     EventType::ProcessedBytesThroughputs(interval, rows) => {
                         for (name, v) in rows {
-                            if let Some(r) = state.get_mut(&name) {
+                            if let Some(r) = state.components.get_mut(&name) {
                                 r.processed_bytes_throughput_sec =
                                     (v as f64 * (1000.0 / interval as f64)) as i64;
                             }
                         }
                     }
+                    EventType::ErrorsTotals(rows) => {
+                        for (name, v) in rows {
+                            if let Some(r) = state.components.get_mut(&name) {
+                                r.errors_total = v;
+                            }
+                        }
+                    }
+                    EventType::ErrorsThroughputs(interval, rows) => {
+                        for (name, v) in rows {
+                            if let Some(r) = state.components.get_mut(&name) {
+                                r.errors_throughput_sec =
+                                    (v as f64 * (1000.0 / interval as f64)) as i64;
+                            }
+                        }
+                    }
+                    EventType::BufferUsages(rows) => {
+                        for (name, buffer) in rows {
+                            if let Some(r) = state.components.get_mut(&name) {
+                                r.buffer = Some(buffer);
+                            }
+                        }
+                    }
                     EventType::ComponentAdded(c) => {
-                        let _ = state.insert(c.name.clone(), c);
+                        let _ = state.components.insert(c.name.clone(), c);
                     }
                     EventType::ComponentRemoved(name) => {
-                        let _ = state.remove(&name);
+                        let _ = state.components.remove(&name);
+                    }
+                    EventType::Disconnected => {
+                        state.connected = false;
+                    }
+                    EventType::Reconnected(components) => {
+                        state = State::new(components);
                     }
                 }
 
@@ -5,18 +5,24 @@ use super::{
 };
 use crate::{
     buffers,
-    config::{DataType, SinkContext},
+    config::{DataType, RuntimeOptions, SinkContext},
     event::Event,
-    internal_events::EventProcessed,
+    internal_events::{EventProcessed, EventsReceived, HealthcheckCompleted},
     shutdown::SourceShutdownCoordinator,
     transforms::Transform,
     Pipeline,
 };
-use futures::{future, stream, FutureExt, StreamExt, TryFutureExt};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{future, stream, FutureExt, Sink, Stream, StreamExt, TryFutureExt};
+use pin_project::pin_project;
 use std::{
     collections::HashMap,
     future::ready,
+    iter,
+    pin::Pin,
     sync::{Arc, Mutex},
+    task::{Context, Poll},
 };
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
@@ -32,6 +38,11 @@ pub struct Pieces {
     pub healthchecks: HashMap<String, Task>,
     pub shutdown_coordinator: SourceShutdownCoordinator,
     pub detach_triggers: HashMap<String, Trigger>,
+    /// The transforms and sinks to place on runtimes of their own.
+    pub runtimes: HashMap<String, RuntimeOptions>,
+    /// The names of the named outputs of the transforms, which are part of
+    /// `outputs` as well.
+    pub named_outputs: HashMap<String, Vec<String>>,
 }
 
 /// Builds only the new pieces, and doesn't check their topology.
@@ -47,6 +58,8 @@ pub async fn build_pieces(
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
+    let mut runtimes = HashMap::new();
+    let mut named_outputs = HashMap::new();
 
     let mut errors = vec![];
 
@@ -59,11 +72,13 @@ pub async fn build_pieces(
         let (tx, rx) = mpsc::channel(1000);
         let pipeline = Pipeline::from_sender(tx, vec![]);
 
-        let typetag = source.source_type();
+        let typetag = source.inner.source_type();
+        let clock_skew = source.clock_skew.clone();
 
         let (shutdown_signal, force_shutdown_tripwire) = shutdown_coordinator.register_source(name);
 
         let server = match source
+            .inner
             .build(&name, &config.global, shutdown_signal, pipeline)
             .await
         {
@@ -75,7 +90,23 @@ pub async fn build_pieces(
         };
 
         let (output, control) = Fanout::new();
-        let pump = rx.map(Ok).forward(output).map_ok(|_| TaskOutput::Source);
+        let pump = CountReceived::new(rx)
+            .map(move |mut event| {
+                let received = Utc::now();
+                match &mut event {
+                    Event::Log(log) => {
+                        log.insert_metadata("vector.source_type", Bytes::from(typetag));
+                        log.insert_metadata("vector.ingest_timestamp", received);
+                    }
+                    Event::Metric(metric) => metric.set_ingest_timestamp(received),
+                }
+                if let Some(clock_skew) = &clock_skew {
+                    clock_skew.apply(&mut event, received);
+                }
+                Ok(event)
+            })
+            .forward(output)
+            .map_ok(|_| TaskOutput::Source);
         let pump = Task::new(name, typetag, pump);
 
         // The force_shutdown_tripwire is a Future that when it resolves means that this source
@@ -103,11 +134,13 @@ pub async fn build_pieces(
         .filter(|(name, _)| diff.transforms.contains_new(&name))
     {
         let trans_inputs = &transform.inputs;
+        let runtime = transform.runtime;
 
         let typetag = transform.inner.transform_type();
 
         let input_type = transform.inner.input_type();
-        let transform = match transform.inner.build().await {
+        let transform_outputs = transform.inner.named_outputs();
+        let transform = match transform.inner.build(&config.global).await {
             Err(error) => {
                 errors.push(format!("Transform \"{}\": {}", name, error));
                 continue;
@@ -115,26 +148,54 @@ pub async fn build_pieces(
             Ok(transform) => transform,
         };
 
-        let (input_tx, input_rx) = mpsc::channel(100);
-        let input_tx = buffers::BufferInputCloner::Memory(input_tx, buffers::WhenFull::Block);
+        let (input_tx, input_rx) = buffers::memory(100, buffers::WhenFull::Block);
+        let usage = input_tx.usage();
 
         let (output, control) = Fanout::new();
 
+        let mut named = Vec::new();
+        let mut named_controls = Vec::new();
+        for (output_name, _) in transform_outputs {
+            let (fanout, control) = Fanout::new();
+            named.push((output_name, fanout));
+            named_controls.push((format!("{}.{}", name, output_name), control));
+        }
+
         let transform = match transform {
-            Transform::Function(mut t) => input_rx
-                .filter(move |event| ready(filter_event_type(event, input_type)))
-                .inspect(|_| emit!(EventProcessed))
-                .flat_map(move |v| {
-                    let mut buf = Vec::with_capacity(1);
-                    t.transform(&mut buf, v);
-                    stream::iter(buf.into_iter()).map(Ok)
-                })
-                .forward(output)
-                .boxed(),
+            Transform::Function(mut t) if !named.is_empty() => CountReceived::new(
+                input_rx.filter(move |event| ready(filter_event_type(event, input_type))),
+            )
+            .inspect(|_| emit!(EventProcessed))
+            .flat_map(move |v| {
+                let mut buf = Vec::with_capacity(1);
+                let mut routed = Vec::new();
+                t.transform_named(&mut buf, &mut routed, v);
+                let routed = routed
+                    .into_iter()
+                    .map(|(output, event)| (Some(output), event));
+                stream::iter(buf.into_iter().map(|event| (None, event)).chain(routed)).map(Ok)
+            })
+            .forward(RoutedOutputs {
+                default: output,
+                named,
+            })
+            .boxed(),
+            Transform::Function(mut t) => CountReceived::new(
+                input_rx.filter(move |event| ready(filter_event_type(event, input_type))),
+            )
+            .inspect(|_| emit!(EventProcessed))
+            .flat_map(move |v| {
+                let mut buf = Vec::with_capacity(1);
+                t.transform(&mut buf, v);
+                stream::iter(buf.into_iter()).map(Ok)
+            })
+            .forward(output)
+            .boxed(),
             Transform::Task(t) => {
-                let filtered = input_rx
-                    .filter(move |event| ready(filter_event_type(event, input_type)))
-                    .inspect(|_| emit!(EventProcessed));
+                let filtered = CountReceived::new(
+                    input_rx.filter(move |event| ready(filter_event_type(event, input_type))),
+                )
+                .inspect(|_| emit!(EventProcessed));
                 t.transform(Box::pin(filtered))
                     .map(Ok)
                     .forward(output)
@@ -145,11 +206,22 @@ pub async fn build_pieces(
             debug!("Finished.");
             TaskOutput::Transform
         });
-        let task = Task::new(name, typetag, transform);
+        let task = Task::new(name, typetag, buffers::report_usage(usage, transform));
 
         inputs.insert(name.clone(), (input_tx, trans_inputs.clone()));
         outputs.insert(name.clone(), control);
+        if !named_controls.is_empty() {
+            let names = named_controls
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect();
+            named_outputs.insert(name.clone(), names);
+            outputs.extend(named_controls);
+        }
         tasks.insert(name.clone(), task);
+        if let Some(runtime) = runtime {
+            runtimes.insert(name.clone(), runtime);
+        }
     }
 
     // Build sinks
@@ -159,8 +231,9 @@ pub async fn build_pieces(
         .filter(|(name, _)| diff.sinks.contains_new(&name))
     {
         let sink_inputs = &sink.inputs;
+        let runtime = sink.runtime;
         let healthcheck = sink.healthcheck();
-        let enable_healthcheck = healthcheck.enabled && config.healthchecks.enabled;
+        let enable_healthcheck = healthcheck.resolve(config.healthchecks).enabled;
 
         let typetag = sink.inner.sink_type();
         let input_type = sink.inner.input_type();
@@ -178,6 +251,8 @@ pub async fn build_pieces(
             }
         };
 
+        let usage = tx.usage();
+
         let cx = SinkContext {
             acker: acker.clone(),
             healthcheck,
@@ -206,13 +281,15 @@ pub async fn build_pieces(
                 .take()
                 .expect("Task started but input has been taken.");
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+            let run = sink.run(
+                CountReceived::new(
+                    rx.by_ref()
+                        .filter(|event| ready(filter_event_type(event, input_type))),
+                )
+                .take_until_if(tripwire),
+            );
+
+            buffers::report_usage(usage, run).await.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -223,19 +300,25 @@ pub async fn build_pieces(
             if enable_healthcheck {
                 let duration = Duration::from_secs(10);
                 timeout(duration, healthcheck)
-                    .map(|result| match result {
-                        Ok(Ok(_)) => {
-                            info!("Healthcheck: Passed.");
-                            Ok(TaskOutput::Healthcheck)
-                        }
-                        Ok(Err(error)) => {
-                            error!(message = "Healthcheck: Failed Reason.", %error);
-                            Err(())
-                        }
-                        Err(_) => {
-                            error!("Healthcheck: timeout.");
-                            Err(())
-                        }
+                    .map(|result| {
+                        let result = match result {
+                            Ok(Ok(_)) => {
+                                info!("Healthcheck: Passed.");
+                                Ok(TaskOutput::Healthcheck)
+                            }
+                            Ok(Err(error)) => {
+                                error!(message = "Healthcheck: Failed Reason.", %error);
+                                Err(())
+                            }
+                            Err(_) => {
+                                error!("Healthcheck: timeout.");
+                                Err(())
+                            }
+                        };
+                        emit!(HealthcheckCompleted {
+                            healthy: result.is_ok()
+                        });
+                        result
                     })
                     .await
             } else {
@@ -249,6 +332,9 @@ pub async fn build_pieces(
         healthchecks.insert(name.clone(), healthcheck_task);
         tasks.insert(name.clone(), task);
         detach_triggers.insert(name.clone(), trigger);
+        if let Some(runtime) = runtime {
+            runtimes.insert(name.clone(), runtime);
+        }
     }
 
     if errors.is_empty() {
@@ -260,6 +346,8 @@ pub async fn build_pieces(
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
+            runtimes,
+            named_outputs,
         };
 
         Ok(pieces)
@@ -268,6 +356,115 @@ pub async fn build_pieces(
     }
 }
 
+/// The outputs of a transform with named outputs, which sends each event to
+/// the fanout of the output it is meant for, or of the default output.
+struct RoutedOutputs {
+    default: Fanout,
+    named: Vec<(&'static str, Fanout)>,
+}
+
+impl RoutedOutputs {
+    fn poll_all<F>(&mut self, cx: &mut Context<'_>, poll: F) -> Poll<Result<(), ()>>
+    where
+        F: Fn(Pin<&mut Fanout>, &mut Context<'_>) -> Poll<Result<(), ()>>,
+    {
+        let mut poll_result = Poll::Ready(Ok(()));
+        let named = self.named.iter_mut().map(|(_, fanout)| fanout);
+        for fanout in iter::once(&mut self.default).chain(named) {
+            match poll(Pin::new(fanout), cx) {
+                Poll::Pending => poll_result = Poll::Pending,
+                Poll::Ready(Ok(())) => (),
+                Poll::Ready(Err(())) => return Poll::Ready(Err(())),
+            }
+        }
+        poll_result
+    }
+}
+
+impl Sink<(Option<&'static str>, Event)> for RoutedOutputs {
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.get_mut()
+            .poll_all(cx, |fanout, cx| fanout.poll_ready(cx))
+    }
+
+    fn start_send(
+        self: Pin<&mut Self>,
+        (output, event): (Option<&'static str>, Event),
+    ) -> Result<(), ()> {
+        let this = self.get_mut();
+        let fanout = match output {
+            None => &mut this.default,
+            Some(output) => match this.named.iter_mut().find(|(name, _)| *name == output) {
+                Some((_, fanout)) => fanout,
+                // The transform didn't declare the output, so nothing
+                // consumes it.
+                None => return Ok(()),
+            },
+        };
+        Pin::new(fanout).start_send(event)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.get_mut()
+            .poll_all(cx, |fanout, cx| fanout.poll_flush(cx))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.get_mut()
+            .poll_all(cx, |fanout, cx| fanout.poll_close(cx))
+    }
+}
+
+/// The most events received by a component before they are counted.
+const MAX_UNCOUNTED: usize = 1024;
+
+/// Counts the events received by the current component, the same way for
+/// every component. The events are counted per batch of events that are
+/// ready, rather than per event.
+#[pin_project]
+struct CountReceived<S> {
+    #[pin]
+    inner: S,
+    count: usize,
+    byte_size: usize,
+}
+
+impl<S> CountReceived<S> {
+    fn new(inner: S) -> Self {
+        Self {
+            inner,
+            count: 0,
+            byte_size: 0,
+        }
+    }
+}
+
+impl<S: Stream<Item = Event>> Stream for CountReceived<S> {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let this = self.project();
+        let poll = this.inner.poll_next(cx);
+        if let Poll::Ready(Some(event)) = &poll {
+            *this.count += 1;
+            *this.byte_size += event.size_estimate();
+        }
+        if *this.count > 0
+            && (*this.count == MAX_UNCOUNTED || !matches!(poll, Poll::Ready(Some(_))))
+        {
+            emit!(EventsReceived {
+                count: *this.count,
+                byte_size: *this.byte_size,
+            });
+            *this.count = 0;
+            *this.byte_size = 0;
+        }
+        poll
+    }
+}
+
 fn filter_event_type(event: &Event, data_type: DataType) -> bool {
     match data_type {
         DataType::Any => true,
@@ -275,3 +472,32 @@ fn filter_event_type(event: &Event, data_type: DataType) -> bool {
         DataType::Metric => matches!(event, Event::Metric(_)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{channel::mpsc, task::noop_waker_ref};
+
+    #[test]
+    fn count_received_counts_batches_of_ready_events() {
+        let (tx, rx) = mpsc::unbounded();
+        let mut received = CountReceived::new(rx);
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        let events = vec![Event::from("one"), Event::from("two")];
+        let byte_size = events.iter().map(Event::size_estimate).sum();
+        for event in events {
+            tx.unbounded_send(event).unwrap();
+        }
+
+        for _ in 0..2 {
+            let poll = Pin::new(&mut received).poll_next(&mut cx);
+            assert!(matches!(poll, Poll::Ready(Some(_))));
+        }
+        assert_eq!((received.count, received.byte_size), (2, byte_size));
+
+        // The batch is counted once no more events are ready.
+        assert!(Pin::new(&mut received).poll_next(&mut cx).is_pending());
+        assert_eq!((received.count, received.byte_size), (0, 0));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Event;
+use super::tap::TAP_PREFIX;
+use crate::{internal_events::EventsSent, Event};
 use futures::{future, Sink, Stream};
 use std::{
     fmt,
@@ -29,8 +30,40 @@ impl fmt::Debug for ControlMessage {
 
 pub type ControlChannel = mpsc::UnboundedSender<ControlMessage>;
 
+/// The most events sent to a component before they are counted.
+const MAX_UNCOUNTED: usize = 1024;
+
+/// Events sent to a component since they were last counted, so that they are
+/// counted per batch rather than per event.
+#[derive(Debug, Default, PartialEq)]
+struct Sent {
+    count: usize,
+    byte_size: usize,
+}
+
+impl Sent {
+    fn add(&mut self, to: &str, byte_size: usize) {
+        self.count += 1;
+        self.byte_size += byte_size;
+        if self.count == MAX_UNCOUNTED {
+            self.emit(to);
+        }
+    }
+
+    fn emit(&mut self, to: &str) {
+        if self.count > 0 {
+            emit!(EventsSent {
+                to,
+                count: self.count,
+                byte_size: self.byte_size,
+            });
+            *self = Self::default();
+        }
+    }
+}
+
 pub struct Fanout {
-    sinks: Vec<(String, Option<Pin<RouterSink>>)>,
+    sinks: Vec<(String, Option<Pin<RouterSink>>, Sent)>,
     i: usize,
     control_channel: mpsc::UnboundedReceiver<ControlMessage>,
 }
@@ -50,18 +83,19 @@ impl Fanout {
 
     pub fn add(&mut self, name: String, sink: RouterSink) {
         assert!(
-            !self.sinks.iter().any(|(n, _)| n == &name),
+            !self.sinks.iter().any(|(n, _, _)| n == &name),
             "Duplicate output name in fanout"
         );
 
-        self.sinks.push((name, Some(sink.into())));
+        self.sinks.push((name, Some(sink.into()), Sent::default()));
     }
 
     fn remove(&mut self, name: &str) {
-        let i = self.sinks.iter().position(|(n, _)| n == name);
+        let i = self.sinks.iter().position(|(n, _, _)| n == name);
         let i = i.expect("Didn't find output in fanout");
 
-        let (_name, removed) = self.sinks.remove(i);
+        let (name, removed, mut sent) = self.sinks.remove(i);
+        sent.emit(&name);
 
         if let Some(mut removed) = removed {
             tokio::spawn(future::poll_fn(move |cx| removed.as_mut().poll_close(cx)));
@@ -73,7 +107,7 @@ impl Fanout {
     }
 
     fn replace(&mut self, name: String, sink: Option<RouterSink>) {
-        if let Some((_, existing)) = self.sinks.iter_mut().find(|(n, _)| n == &name) {
+        if let Some((_, existing, _)) = self.sinks.iter_mut().find(|(n, _, _)| n == &name) {
             *existing = sink.map(Into::into);
         } else {
             panic!("Tried to replace a sink that's not already present");
@@ -98,11 +132,18 @@ impl Fanout {
         if self.sinks.len() == 1 {
             Err(())
         } else {
-            self.sinks.remove(index);
+            let (name, _, mut sent) = self.sinks.remove(index);
+            sent.emit(&name);
             Ok(())
         }
     }
 
+    fn emit_sent(&mut self) {
+        for (name, _, sent) in &mut self.sinks {
+            sent.emit(name);
+        }
+    }
+
     fn poll_sinks<F>(&mut self, cx: &mut Context<'_>, poll: F) -> Poll<Result<(), ()>>
     where
         F: Fn(&mut Pin<RouterSink>, &mut Context<'_>) -> Poll<Result<(), ()>>,
@@ -112,7 +153,7 @@ impl Fanout {
         let mut poll_result = Poll::Ready(Ok(()));
 
         let mut i = 0;
-        while let Some((_, sink)) = self.sinks.get_mut(i) {
+        while let Some((_, sink, _)) = self.sinks.get_mut(i) {
             if let Some(sink) = sink {
                 match poll(sink, cx) {
                     Poll::Pending => poll_result = Poll::Pending,
@@ -130,6 +171,12 @@ impl Fanout {
     }
 }
 
+impl Drop for Fanout {
+    fn drop(&mut self) {
+        self.emit_sent();
+    }
+}
+
 impl Sink<Event> for Fanout {
     type Error = ();
 
@@ -138,10 +185,14 @@ impl Sink<Event> for Fanout {
 
         this.process_control_messages(cx);
 
-        while let Some((_, sink)) = this.sinks.get_mut(this.i) {
+        while let Some((_, sink, _)) = this.sinks.get_mut(this.i) {
             match sink.as_mut() {
                 Some(sink) => match sink.as_mut().poll_ready(cx) {
-                    Poll::Pending => return Poll::Pending,
+                    Poll::Pending => {
+                        // Count what was sent before waiting for the sink.
+                        this.emit_sent();
+                        return Poll::Pending;
+                    }
                     Poll::Ready(Ok(())) => this.i += 1,
                     Poll::Ready(Err(())) => this.handle_sink_error(this.i)?,
                 },
@@ -158,21 +209,37 @@ impl Sink<Event> for Fanout {
     }
 
     fn start_send(mut self: Pin<&mut Self>, item: Event) -> Result<(), ()> {
+        // The size of the event is estimated once, and only if it is sent to
+        // a component other than a tap.
+        let mut byte_size = None;
+        let mut size = |item: &Event| *byte_size.get_or_insert_with(|| item.size_estimate());
+
         let mut i = 1;
-        while let Some((_, sink)) = self.sinks.get_mut(i) {
+        while let Some((name, sink, sent)) = self.sinks.get_mut(i) {
             if let Some(sink) = sink.as_mut() {
                 if sink.as_mut().start_send(item.clone()).is_err() {
                     self.handle_sink_error(i)?;
                     continue;
                 }
+                if !name.starts_with(TAP_PREFIX) {
+                    sent.add(name, size(&item));
+                }
             }
             i += 1;
         }
 
-        if let Some((_, sink)) = self.sinks.first_mut() {
+        if let Some((name, sink, sent)) = self.sinks.first_mut() {
             if let Some(sink) = sink.as_mut() {
+                // The event is moved into the sink, so it is sized beforehand.
+                let byte_size = if name.starts_with(TAP_PREFIX) {
+                    None
+                } else {
+                    Some(size(&item))
+                };
                 if sink.as_mut().start_send(item).is_err() {
                     self.handle_sink_error(0)?;
+                } else if let Some(byte_size) = byte_size {
+                    sent.add(name, byte_size);
                 }
             }
         }
@@ -181,17 +248,20 @@ impl Sink<Event> for Fanout {
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.emit_sent();
         self.poll_sinks(cx, |sink, cx| sink.as_mut().poll_flush(cx))
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.emit_sent();
         self.poll_sinks(cx, |sink, cx| sink.as_mut().poll_close(cx))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ControlMessage, Fanout};
+    use super::{ControlMessage, Fanout, Sent};
+    use crate::topology::tap::TAP_PREFIX;
     use crate::{sink::BoundedSink, test_util::collect_ready, Event};
     use futures::{stream, Sink, SinkExt, StreamExt};
     use std::{
@@ -221,6 +291,37 @@ mod tests {
         assert_eq!(collect_ready(rx_b).await, recs);
     }
 
+    #[tokio::test]
+    async fn fanout_counts_sent_events_until_flushed() {
+        let (tx_a, _rx_a) = unbounded_channel();
+        let tx_a = Box::new(tx_a.sink_map_err(|_| unreachable!()));
+        let (tx_tap, _rx_tap) = unbounded_channel();
+        let tx_tap = Box::new(tx_tap.sink_map_err(|_| unreachable!()));
+
+        let mut fanout = Fanout::new().0;
+
+        fanout.add("a".to_string(), tx_a);
+        fanout.add(format!("{}a", TAP_PREFIX), tx_tap);
+
+        let recs = make_events(2);
+        let byte_size = recs.iter().map(Event::size_estimate).sum();
+        for rec in recs {
+            fanout.feed(rec).await.unwrap();
+        }
+
+        assert_eq!(
+            fanout.sinks[0].2,
+            Sent {
+                count: 2,
+                byte_size
+            }
+        );
+        assert_eq!(fanout.sinks[1].2, Sent::default());
+
+        fanout.flush().await.unwrap();
+        assert_eq!(fanout.sinks[0].2, Sent::default());
+    }
+
     #[tokio::test]
     async fn fanout_notready() {
         let (tx_a, rx_a) = channel(2);
@@ -8,12 +8,14 @@
 
 pub mod builder;
 mod fanout;
+mod tap;
 mod task;
 
 use crate::{
     buffers,
-    config::{Config, ConfigDiff, HealthcheckOptions, Resource},
+    config::{Config, ConfigDiff, Resource, RuntimeOptions},
     event::Event,
+    internal_events::{ComponentFailed, ComponentStarted},
     shutdown::SourceShutdownCoordinator,
     topology::{
         builder::Pieces,
@@ -27,13 +29,17 @@ use std::{
     panic::AssertUnwindSafe,
     pin::Pin,
     sync::{Arc, Mutex},
+    thread,
 };
 use tokio::{
-    sync::mpsc,
+    runtime,
+    sync::{mpsc, watch},
     time::{delay_until, interval, Duration, Instant},
 };
 use tracing_futures::Instrument;
 
+pub use tap::{TapDirection, TapGuard, TapOutputs};
+
 type TaskHandle = tokio::task::JoinHandle<Result<TaskOutput, ()>>;
 
 type BuiltBuffer = (
@@ -46,6 +52,9 @@ type BuiltBuffer = (
 pub struct RunningTopology {
     inputs: HashMap<String, buffers::BufferInputCloner>,
     outputs: HashMap<String, fanout::ControlChannel>,
+    output_generations: HashMap<String, usize>,
+    tap_outputs: watch::Sender<TapOutputs>,
+    tap_outputs_rx: watch::Receiver<TapOutputs>,
     source_tasks: HashMap<String, TaskHandle>,
     tasks: HashMap<String, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
@@ -60,10 +69,14 @@ pub async fn start_validated(
     mut pieces: Pieces,
 ) -> Option<(RunningTopology, mpsc::UnboundedReceiver<()>)> {
     let (abort_tx, abort_rx) = mpsc::unbounded_channel();
+    let (tap_outputs, tap_outputs_rx) = watch::channel(TapOutputs::default());
 
     let mut running_topology = RunningTopology {
         inputs: HashMap::new(),
         outputs: HashMap::new(),
+        output_generations: HashMap::new(),
+        tap_outputs,
+        tap_outputs_rx,
         config,
         shutdown_coordinator: SourceShutdownCoordinator::default(),
         detach_triggers: HashMap::new(),
@@ -72,14 +85,12 @@ pub async fn start_validated(
         abort_tx,
     };
 
-    if !running_topology
-        .run_healthchecks(&diff, &mut pieces, running_topology.config.healthchecks)
-        .await
-    {
+    if !RunningTopology::run_healthchecks(&running_topology.config, &diff, &mut pieces).await {
         return None;
     }
     running_topology.connect_diff(&diff, &mut pieces).await;
     running_topology.spawn_diff(&diff, pieces);
+    running_topology.publish_tap_outputs();
 
     Some((running_topology, abort_rx))
 }
@@ -228,13 +239,11 @@ impl RunningTopology {
         // Now let's actually build the new pieces.
         if let Some(mut new_pieces) = build_or_log_errors(&new_config, &diff, buffers.clone()).await
         {
-            if self
-                .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
-                .await
-            {
+            if Self::run_healthchecks(&new_config, &diff, &mut new_pieces).await {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
+                self.publish_tap_outputs();
                 // We have successfully changed to new config.
                 return Ok(true);
             }
@@ -244,12 +253,10 @@ impl RunningTopology {
         info!("Rebuilding old configuration.");
         let diff = diff.flip();
         if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
-            if self
-                .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
-                .await
-            {
+            if Self::run_healthchecks(&self.config, &diff, &mut new_pieces).await {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
+                self.publish_tap_outputs();
                 // We have successfully returned to old config.
                 return Ok(false);
             }
@@ -261,35 +268,49 @@ impl RunningTopology {
         Err(())
     }
 
-    async fn run_healthchecks(
-        &mut self,
-        diff: &ConfigDiff,
-        pieces: &mut Pieces,
-        options: HealthcheckOptions,
-    ) -> bool {
-        if options.enabled {
-            let healthchecks = take_healthchecks(diff, pieces)
-                .into_iter()
-                .map(|(_, task)| task);
-            let healthchecks = future::try_join_all(healthchecks);
+    /// Runs the healthchecks of the new sinks, each with its options resolved
+    /// against the global `healthchecks` section. Returns false if any sink
+    /// requiring to be healthy failed its healthcheck.
+    async fn run_healthchecks(config: &Config, diff: &ConfigDiff, pieces: &mut Pieces) -> bool {
+        let mut enabled = false;
+        let mut required = Vec::new();
+        let mut optional = Vec::new();
+        for (name, task) in take_healthchecks(diff, pieces) {
+            let options = config.sinks.get(&name).map_or(config.healthchecks, |sink| {
+                sink.healthcheck().resolve(config.healthchecks)
+            });
+            // Tags the healthcheck result metric with the sink it belongs to.
+            let span = error_span!(
+                "sink",
+                component_kind = "sink",
+                component_name = %task.name(),
+                component_type = %task.typetag(),
+            );
+            let task = task.instrument(span);
+            enabled |= options.enabled;
+            if options.enabled && options.require_healthy {
+                required.push(task);
+            } else {
+                optional.push(task);
+            }
+        }
 
+        if enabled {
             info!("Running healthchecks.");
-            if options.require_healthy {
-                let success = healthchecks.await;
+        }
+        if !optional.is_empty() {
+            tokio::spawn(future::try_join_all(optional));
+        }
+        if required.is_empty() {
+            return true;
+        }
 
-                if success.is_ok() {
-                    info!("All healthchecks passed.");
-                    true
-                } else {
-                    error!("Sinks unhealthy.");
-                    false
-                }
-            } else {
-                tokio::spawn(healthchecks);
-                true
-            }
-        } else {
+        if future::try_join_all(required).await.is_ok() {
+            info!("All healthchecks passed.");
             true
+        } else {
+            error!("Sinks unhealthy.");
+            false
         }
     }
 
@@ -361,6 +382,14 @@ impl RunningTopology {
             self.remove_outputs(&name);
         }
 
+        // The named outputs of changed transforms are set up again, unless
+        // the transform doesn't have them anymore.
+        for name in diff.transforms.removed_and_changed() {
+            for output in self.config.transforms[name].named_outputs(name) {
+                self.remove_outputs(&output);
+            }
+        }
+
         // Sinks
 
         // Resource conflicts
@@ -375,7 +404,7 @@ impl RunningTopology {
         let add_source = diff
             .sources
             .changed_and_added()
-            .map(|name| (name, new_config.sources[name].resources()));
+            .map(|name| (name, new_config.sources[name].inner.resources()));
         let add_sink = diff
             .sinks
             .changed_and_added()
@@ -479,6 +508,9 @@ impl RunningTopology {
         // it as an input
         for name in diff.transforms.changed_and_added() {
             self.setup_outputs(&name, new_pieces);
+            for output in new_pieces.named_outputs.remove(name).unwrap_or_default() {
+                self.setup_outputs(&output, new_pieces);
+            }
         }
 
         for name in &diff.transforms.to_change {
@@ -544,7 +576,7 @@ impl RunningTopology {
             component_type = %task.typetag(),
         );
         let task = handle_errors(task, self.abort_tx.clone()).instrument(span);
-        let spawned = tokio::spawn(task);
+        let spawned = spawn_on_runtime(name, new_pieces.runtimes.remove(name), task);
         if let Some(previous) = self.tasks.insert(name.to_string(), spawned) {
             drop(previous); // detach and forget
         }
@@ -559,7 +591,7 @@ impl RunningTopology {
             component_type = %task.typetag(),
         );
         let task = handle_errors(task, self.abort_tx.clone()).instrument(span);
-        let spawned = tokio::spawn(task);
+        let spawned = spawn_on_runtime(name, new_pieces.runtimes.remove(name), task);
         if let Some(previous) = self.tasks.insert(name.to_string(), spawned) {
             drop(previous); // detach and forget
         }
@@ -590,6 +622,7 @@ impl RunningTopology {
 
     fn remove_outputs(&mut self, name: &str) {
         self.outputs.remove(name);
+        self.output_generations.remove(name);
     }
 
     fn remove_inputs(&mut self, name: &str) {
@@ -639,6 +672,8 @@ impl RunningTopology {
         }
 
         self.outputs.insert(name.to_string(), output);
+        self.output_generations
+            .insert(name.to_string(), tap::next_id());
     }
 
     fn setup_inputs(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
@@ -717,6 +752,18 @@ impl RunningTopology {
         }
     }
 
+    /// Returns a receiver of the outputs of the components, which taps attach
+    /// to, updated whenever the topology changes.
+    pub fn watch_tap_outputs(&self) -> watch::Receiver<TapOutputs> {
+        self.tap_outputs_rx.clone()
+    }
+
+    fn publish_tap_outputs(&self) {
+        let outputs = TapOutputs::new(&self.config, &self.outputs, &self.output_generations);
+        // This can only fail if there are no receivers, in which case nothing is tapped.
+        let _ = self.tap_outputs.broadcast(outputs);
+    }
+
     /// Borrows the Config
     pub fn config(&self) -> &Config {
         &self.config
@@ -727,6 +774,8 @@ async fn handle_errors(
     task: impl Future<Output = Result<TaskOutput, ()>>,
     abort_tx: mpsc::UnboundedSender<()>,
 ) -> Result<TaskOutput, ()> {
+    emit!(ComponentStarted);
+
     AssertUnwindSafe(task)
         .catch_unwind()
         .await
@@ -734,10 +783,47 @@ async fn handle_errors(
         .and_then(|res| res)
         .map_err(|_| {
             error!("An error occurred that vector couldn't handle.");
+            emit!(ComponentFailed);
             let _ = abort_tx.send(());
         })
 }
 
+/// Spawns the task of a component on a runtime of its own if it's configured
+/// with one, or on the current runtime otherwise.
+fn spawn_on_runtime(
+    name: &str,
+    options: Option<RuntimeOptions>,
+    task: impl Future<Output = Result<TaskOutput, ()>> + Send + 'static,
+) -> TaskHandle {
+    let options = match options {
+        Some(options) => options,
+        None => return tokio::spawn(task),
+    };
+
+    info!(
+        message = "Placing component on a dedicated runtime.",
+        name = %name,
+        threads = options.threads.get(),
+    );
+    let runtime = runtime::Builder::new()
+        .threaded_scheduler()
+        .enable_all()
+        .core_threads(options.threads.get())
+        .thread_name(format!("vector-{}", name))
+        .build()
+        .expect("Unable to create async runtime");
+    let handle = runtime.spawn(task);
+
+    // The runtime is kept until the task finishes, even once the topology
+    // detaches from it, as dropping the runtime would cancel the task.
+    tokio::spawn(async move {
+        let output = handle.await.unwrap_or(Err(()));
+        // Dropping the runtime blocks until its threads exit.
+        thread::spawn(move || drop(runtime));
+        output
+    })
+}
+
 /// If the closure returns false, then the element is removed
 fn retain<T>(vec: &mut Vec<T>, mut retain_filter: impl FnMut(&mut T) -> bool) {
     let mut i = 0;
@@ -0,0 +1,223 @@
+//! Taps stream copies of the events flowing into or out of a component of the
+//! running topology, for inspecting it without changing its config.
+
+use super::fanout::{ControlChannel, ControlMessage};
+use crate::{config::Config, Event};
+use futures::Sink;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc;
+
+/// Prefix of the names taps are added to fanouts under, which tells them apart
+/// from downstream components.
+pub const TAP_PREFIX: &str = "_tap:";
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a new id, unique for the lifetime of the process.
+pub(super) fn next_id() -> usize {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Whether events are tapped on their way into or out of a component.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TapDirection {
+    Input,
+    Output,
+}
+
+/// Output of a component, which taps are attached to.
+#[derive(Debug, Clone)]
+struct TapOutput {
+    control: ControlChannel,
+    /// Changes whenever the component is rebuilt, which drops its taps.
+    generation: usize,
+}
+
+/// The outputs of the components of the running topology, along with the
+/// inputs of each, which are published whenever the topology changes.
+#[derive(Debug, Clone, Default)]
+pub struct TapOutputs {
+    outputs: HashMap<String, TapOutput>,
+    inputs: HashMap<String, Vec<String>>,
+}
+
+impl TapOutputs {
+    pub(super) fn new(
+        config: &Config,
+        outputs: &HashMap<String, ControlChannel>,
+        generations: &HashMap<String, usize>,
+    ) -> Self {
+        let outputs = outputs
+            .iter()
+            .map(|(name, control)| {
+                let output = TapOutput {
+                    control: control.clone(),
+                    generation: generations.get(name).copied().unwrap_or_default(),
+                };
+                (name.clone(), output)
+            })
+            .collect();
+
+        let inputs = config
+            .transforms
+            .iter()
+            .map(|(name, transform)| (name.clone(), transform.inputs.clone()))
+            .chain(
+                config
+                    .sinks
+                    .iter()
+                    .map(|(name, sink)| (name.clone(), sink.inputs.clone())),
+            )
+            .collect();
+
+        Self { outputs, inputs }
+    }
+
+    /// Whether the topology has a component named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.outputs.contains_key(name) || self.inputs.contains_key(name)
+    }
+
+    /// Returns the outputs a tap on `name` attaches to, along with their
+    /// generations, or `None` if there's no such component. The tap has to be
+    /// attached again whenever these change.
+    pub fn targets(&self, name: &str, direction: TapDirection) -> Option<Vec<(String, usize)>> {
+        if !self.contains(name) {
+            return None;
+        }
+
+        let names = match direction {
+            TapDirection::Output => vec![name.to_owned()],
+            TapDirection::Input => self.inputs.get(name).cloned().unwrap_or_default(),
+        };
+
+        Some(
+            names
+                .into_iter()
+                .filter_map(|name| {
+                    let generation = self.outputs.get(&name)?.generation;
+                    Some((name, generation))
+                })
+                .collect(),
+        )
+    }
+
+    /// Attaches a tap to the outputs returned by `targets`, sending copies of
+    /// the events passing through them to `tx`. Events are dropped while `tx`
+    /// is full, so a slow tap never holds back the topology. The tap stays
+    /// attached until the returned guards are dropped.
+    pub fn tap(
+        &self,
+        name: &str,
+        direction: TapDirection,
+        tx: mpsc::Sender<Event>,
+    ) -> Vec<TapGuard> {
+        self.targets(name, direction)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(output, _)| {
+                let control = self.outputs.get(&output)?.control.clone();
+                let name = format!("{}{}", TAP_PREFIX, next_id());
+                let sink = TapSink { tx: tx.clone() };
+
+                control
+                    .send(ControlMessage::Add(name.clone(), Box::new(sink)))
+                    .ok()?;
+
+                Some(TapGuard { control, name })
+            })
+            .collect()
+    }
+}
+
+/// Detaches its tap from the output when dropped.
+#[derive(Debug)]
+pub struct TapGuard {
+    control: ControlChannel,
+    name: String,
+}
+
+impl Drop for TapGuard {
+    fn drop(&mut self) {
+        // This can only fail if the component has already shut down, taking the tap with it.
+        let _ = self
+            .control
+            .send(ControlMessage::Remove(std::mem::take(&mut self.name)));
+    }
+}
+
+struct TapSink {
+    tx: mpsc::Sender<Event>,
+}
+
+impl Sink<Event> for TapSink {
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, event: Event) -> Result<(), ()> {
+        // Both a full and a closed channel only mean the tap misses the event.
+        let _ = self.get_mut().tx.try_send(event);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::fanout::Fanout;
+    use futures::{SinkExt, StreamExt};
+
+    #[tokio::test]
+    async fn taps_events_until_dropped() {
+        let (mut fanout, control) = Fanout::new();
+        let (sink_tx, mut sink_rx) = mpsc::channel(10);
+        fanout.add(
+            "sink".to_owned(),
+            Box::new(crate::Pipeline::from_sender(sink_tx, vec![]).sink_map_err(|_| ())),
+        );
+
+        let mut outputs = TapOutputs::default();
+        outputs.outputs.insert(
+            "source".to_owned(),
+            TapOutput {
+                control,
+                generation: 0,
+            },
+        );
+
+        let (tx, mut rx) = mpsc::channel(10);
+        let guards = outputs.tap("source", TapDirection::Output, tx);
+        assert_eq!(guards.len(), 1);
+
+        fanout.send(Event::from("tapped")).await.unwrap();
+        assert_eq!(rx.next().await, Some(Event::from("tapped")));
+        assert_eq!(sink_rx.next().await, Some(Event::from("tapped")));
+
+        drop(guards);
+        fanout.send(Event::from("untapped")).await.unwrap();
+        assert_eq!(sink_rx.next().await, Some(Event::from("untapped")));
+        assert_eq!(rx.next().await, None);
+    }
+
+    #[test]
+    fn targets_unknown_component() {
+        let outputs = TapOutputs::default();
+        assert!(outputs.targets("missing", TapDirection::Output).is_none());
+    }
+}
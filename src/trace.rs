@@ -1,10 +1,11 @@
 use crate::event::{Event, LogEvent, Value};
+use dashmap::DashMap;
 use metrics_tracing_context::MetricsLayer;
 use once_cell::sync::OnceCell;
 use std::{
     convert::TryInto,
     fmt::Debug,
-    sync::{Mutex, MutexGuard},
+    sync::{Arc, Mutex, MutexGuard},
 };
 use tokio::sync::broadcast::{self, Receiver, Sender};
 use tracing::{
@@ -29,6 +30,10 @@ static BUFFER: OnceCell<Mutex<Option<Vec<Event>>>> = OnceCell::new();
 /// initialized.
 static SENDER: OnceCell<Sender<Event>> = OnceCell::new();
 
+/// Fields of the component spans, which identify the component an internal
+/// log event was emitted by.
+const COMPONENT_FIELDS: [&str; 3] = ["component_kind", "component_name", "component_type"];
+
 pub use tracing_futures::Instrument;
 pub use tracing_tower::{InstrumentableService, InstrumentedService};
 
@@ -53,9 +58,9 @@ pub fn init(color: bool, json: bool, levels: &str) {
             .with(Limit::default());
         if metrics_layer_enabled {
             let formatter = formatter.with(MetricsLayer::new());
-            Dispatch::new(BroadcastSubscriber { formatter })
+            Dispatch::new(BroadcastSubscriber::new(formatter))
         } else {
-            Dispatch::new(BroadcastSubscriber { formatter })
+            Dispatch::new(BroadcastSubscriber::new(formatter))
         }
     } else {
         let formatter = FmtSubscriber::builder()
@@ -65,9 +70,9 @@ pub fn init(color: bool, json: bool, levels: &str) {
             .with(Limit::default());
         if metrics_layer_enabled {
             let formatter = formatter.with(MetricsLayer::new());
-            Dispatch::new(BroadcastSubscriber { formatter })
+            Dispatch::new(BroadcastSubscriber::new(formatter))
         } else {
-            Dispatch::new(BroadcastSubscriber { formatter })
+            Dispatch::new(BroadcastSubscriber::new(formatter))
         }
     };
 
@@ -110,8 +115,50 @@ pub fn subscribe() -> TraceSubscription {
     TraceSubscription { buffer, receiver }
 }
 
+/// The component fields of a span, either its own or those of its closest
+/// ancestor that has any.
+type ComponentFields = Arc<Vec<(&'static str, String)>>;
+
 struct BroadcastSubscriber<F> {
     formatter: F,
+    components: DashMap<Id, ComponentFields>,
+}
+
+impl<F: Subscriber + 'static> BroadcastSubscriber<F> {
+    fn new(formatter: F) -> Self {
+        Self {
+            formatter,
+            components: DashMap::new(),
+        }
+    }
+
+    /// Returns the span that `parent` and `contextual` describe, as for a
+    /// new span or an event.
+    fn parent(&self, parent: Option<&Id>, contextual: bool) -> Option<Id> {
+        match parent {
+            Some(parent) => Some(parent.clone()),
+            None if contextual => self.formatter.current_span().id().cloned(),
+            None => None,
+        }
+    }
+
+    fn component_fields(&self, span: Option<Id>) -> Option<ComponentFields> {
+        span.and_then(|id| self.components.get(&id).map(|fields| Arc::clone(&fields)))
+    }
+
+    /// Converts `event` into a log event, along with the fields of the
+    /// component it was emitted by, if any, under `metadata`.
+    fn log_event(&self, event: &tracing::Event<'_>) -> Event {
+        let mut log: Event = event.into();
+        let span = self.parent(event.parent(), event.is_contextual());
+        if let Some(fields) = self.component_fields(span) {
+            let log = log.as_mut_log();
+            for (name, value) in fields.iter() {
+                log.insert(format!("metadata.{}", name), value.clone());
+            }
+        }
+        log
+    }
 }
 
 impl<F: Subscriber + 'static> Subscriber for BroadcastSubscriber<F> {
@@ -122,7 +169,20 @@ impl<F: Subscriber + 'static> Subscriber for BroadcastSubscriber<F> {
 
     #[inline]
     fn new_span(&self, span: &tracing::span::Attributes<'_>) -> Id {
-        self.formatter.new_span(span)
+        let id = self.formatter.new_span(span);
+
+        let mut visitor = RecordComponentFields::default();
+        span.record(&mut visitor);
+        let fields = if visitor.0.is_empty() {
+            self.component_fields(self.parent(span.parent(), span.is_contextual()))
+        } else {
+            Some(Arc::new(visitor.0))
+        };
+        if let Some(fields) = fields {
+            self.components.insert(id.clone(), fields);
+        }
+
+        id
     }
 
     #[inline]
@@ -138,10 +198,10 @@ impl<F: Subscriber + 'static> Subscriber for BroadcastSubscriber<F> {
     #[inline]
     fn event(&self, event: &tracing::Event<'_>) {
         if let Some(buffer) = early_buffer().as_mut() {
-            buffer.push(event.into());
+            buffer.push(self.log_event(event));
         }
         if let Some(sender) = SENDER.get() {
-            let _ = sender.send(event.into()); // Ignore errors
+            let _ = sender.send(self.log_event(event)); // Ignore errors
         }
         self.formatter.event(event)
     }
@@ -168,7 +228,11 @@ impl<F: Subscriber + 'static> Subscriber for BroadcastSubscriber<F> {
 
     #[inline]
     fn try_close(&self, id: Id) -> bool {
-        self.formatter.try_close(id)
+        let closed = self.formatter.try_close(id.clone());
+        if closed {
+            self.components.remove(&id);
+        }
+        closed
     }
 
     #[inline]
@@ -249,3 +313,21 @@ impl Visit for MakeLogEvent {
         self.0.insert(field.name(), value);
     }
 }
+
+/// Records the component fields of a span.
+#[derive(Debug, Default)]
+struct RecordComponentFields(Vec<(&'static str, String)>);
+
+impl Visit for RecordComponentFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if COMPONENT_FIELDS.contains(&field.name()) {
+            self.0.push((field.name(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if COMPONENT_FIELDS.contains(&field.name()) {
+            self.0.push((field.name(), format!("{:?}", value)));
+        }
+    }
+}
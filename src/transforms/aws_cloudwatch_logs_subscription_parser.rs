@@ -1,6 +1,8 @@
 use super::Transform;
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, TransformConfig, TransformDescription},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
+    },
     event::Event,
     internal_events::AwsCloudwatchLogsSubscriptionParserFailedParse,
     transforms::FunctionTransform,
@@ -25,7 +27,7 @@ inventory::submit! {
 #[async_trait::async_trait]
 #[typetag::serde(name = "aws_cloudwatch_logs_subscription_parser")]
 impl TransformConfig for AwsCloudwatchLogsSubscriptionParserConfig {
-    async fn build(&self) -> crate::Result<Transform> {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
         Ok(Transform::function(
             AwsCloudwatchLogsSubscriptionParser::from(self.clone()),
         ))
@@ -1,11 +1,12 @@
 use crate::{
-    config::{DataType, TransformConfig, TransformDescription},
+    config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
     event::{Event, Value},
     internal_events::CoercerConversionFailed,
     transforms::{FunctionTransform, Transform},
     types::{parse_conversion_map, Conversion},
 };
 use serde::{Deserialize, Serialize};
+use shared::datetime::TimeZone;
 use std::collections::HashMap;
 use std::str;
 
@@ -15,6 +16,7 @@ use std::str;
 pub struct CoercerConfig {
     types: HashMap<String, String>,
     drop_unspecified: bool,
+    timezone: Option<TimeZone>,
 }
 
 inventory::submit! {
@@ -26,8 +28,9 @@ impl_generate_config_from_default!(CoercerConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "coercer")]
 impl TransformConfig for CoercerConfig {
-    async fn build(&self) -> crate::Result<Transform> {
-        let types = parse_conversion_map(&self.types)?;
+    async fn build(&self, globals: &GlobalOptions) -> crate::Result<Transform> {
+        let timezone = self.timezone.unwrap_or(globals.timezone);
+        let types = parse_conversion_map(&self.types, timezone)?;
         Ok(Transform::function(Coercer {
             types,
             drop_unspecified: self.drop_unspecified,
@@ -96,8 +99,13 @@ impl FunctionTransform for Coercer {
 mod tests {
     use super::CoercerConfig;
     use crate::event::{LogEvent, Value};
-    use crate::{config::TransformConfig, Event};
+    use crate::{
+        config::{GlobalOptions, TransformConfig},
+        Event,
+    };
+    use chrono::{TimeZone as _, Utc};
     use pretty_assertions::assert_eq;
+    use shared::datetime::TimeZone;
 
     #[test]
     fn generate_config() {
@@ -125,7 +133,7 @@ mod tests {
             extra
         ))
         .unwrap()
-        .build()
+        .build(&GlobalOptions::default())
         .await
         .unwrap();
         let coercer = coercer.as_function();
@@ -161,4 +169,49 @@ mod tests {
 
         assert_eq!(log, expected.into_log());
     }
+
+    async fn parse_timestamp(globals: &GlobalOptions, extra: &str) -> Value {
+        let event = Event::from("2021-01-15 08:30:00");
+
+        let mut coercer = toml::from_str::<CoercerConfig>(&format!(
+            r#"{}
+            [types]
+            message = "timestamp|%F %T"
+            "#,
+            extra
+        ))
+        .unwrap()
+        .build(globals)
+        .await
+        .unwrap();
+        let coercer = coercer.as_function();
+        let log = coercer.transform_one(event).unwrap().into_log();
+        log["message"].clone()
+    }
+
+    #[tokio::test]
+    async fn uses_global_timezone() {
+        let globals = GlobalOptions {
+            timezone: TimeZone::parse("America/New_York").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse_timestamp(&globals, "").await,
+            Value::Timestamp(Utc.ymd(2021, 1, 15).and_hms(13, 30, 0))
+        );
+    }
+
+    #[tokio::test]
+    async fn timezone_overrides_global() {
+        let globals = GlobalOptions {
+            timezone: TimeZone::parse("America/New_York").unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse_timestamp(&globals, r#"timezone = "Europe/Berlin""#).await,
+            Value::Timestamp(Utc.ymd(2021, 1, 15).and_hms(7, 30, 0))
+        );
+    }
 }
@@ -189,10 +189,14 @@ impl Lua {
     }
 
     #[cfg(test)]
-    fn process(&mut self, event: Event, output: &mut Vec<Event>) -> Result<(), rlua::Error> {
+    fn process(&mut self, mut event: Event, output: &mut Vec<Event>) -> Result<(), rlua::Error> {
+        // The events emitted by the hook are delivered on behalf of the event
+        // it processes, which loses its finalizers on its way through Lua.
+        let finalizers = event.take_finalizers();
         let result = self.lua.context(|ctx: rlua::Context<'_>| {
             ctx.scope(|scope| {
-                let emit = scope.create_function_mut(|_, event: Event| {
+                let emit = scope.create_function_mut(|_, mut event: Event| {
+                    event.add_finalizers(finalizers.clone());
                     output.push(event);
                     Ok(())
                 })?;
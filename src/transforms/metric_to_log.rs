@@ -1,5 +1,7 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, TransformConfig, TransformDescription},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription,
+    },
     event::{self, Event, LogEvent},
     internal_events::MetricToLogFailedSerialize,
     transforms::{FunctionTransform, Transform},
@@ -31,7 +33,7 @@ impl GenerateConfig for MetricToLogConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "metric_to_log")]
 impl TransformConfig for MetricToLogConfig {
-    async fn build(&self) -> crate::Result<Transform> {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
         Ok(Transform::function(MetricToLog::new(self.host_tag.clone())))
     }
 
@@ -68,7 +70,8 @@ impl MetricToLog {
 
 impl FunctionTransform for MetricToLog {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        let metric = event.into_metric();
+        let mut metric = event.into_metric();
+        let finalizers = metric.take_finalizers();
 
         let retval = serde_json::to_value(&metric)
             .map_err(|error| emit!(MetricToLogFailedSerialize { error }))
@@ -83,7 +86,11 @@ impl FunctionTransform for MetricToLog {
 
                     let timestamp = log
                         .remove(&self.timestamp_key)
-                        .and_then(|value| Conversion::Timestamp.convert(value.into_bytes()).ok())
+                        .and_then(|value| {
+                            Conversion::Timestamp(Default::default())
+                                .convert(value.into_bytes())
+                                .ok()
+                        })
                         .unwrap_or_else(|| event::Value::Timestamp(Utc::now()));
                     log.insert(&log_schema().timestamp_key(), timestamp);
 
@@ -91,6 +98,8 @@ impl FunctionTransform for MetricToLog {
                         log.insert(&log_schema().host_key(), host);
                     }
 
+                    // The log is delivered on behalf of the metric.
+                    log.add_finalizers(finalizers);
                     Some(log.into())
                 }
                 _ => None,
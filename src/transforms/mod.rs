@@ -43,6 +43,8 @@ pub mod lua;
 pub mod merge;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-plugin")]
+pub mod plugin;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-regex_parser")]
@@ -63,6 +65,8 @@ pub mod sample;
 pub mod split;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tenant_quota")]
+pub mod tenant_quota;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
 #[cfg(feature = "wasm")]
@@ -156,6 +160,21 @@ impl Transform {
 pub trait FunctionTransform: Send + dyn_clone::DynClone + Sync {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event);
 
+    /// Transforms the event like `transform`, pushing the events meant for
+    /// one of the named outputs of the transform to `named`, along with the
+    /// name of that output.
+    ///
+    /// Transforms declaring named outputs with `TransformConfig::named_outputs`
+    /// implement this, and make `transform` drop the events of named outputs.
+    fn transform_named(
+        &mut self,
+        output: &mut Vec<Event>,
+        _named: &mut Vec<(&'static str, Event)>,
+        event: Event,
+    ) {
+        self.transform(output, event)
+    }
+
     /// A handy test function that inputs and outputs only one event.
     ///
     /// In a prior time, Vector primarily used this API to handle events.
@@ -0,0 +1,247 @@
+use crate::{
+    config::{DataType, GlobalOptions, TransformConfig, TransformDescription},
+    event::Event,
+    internal_events::error_stage,
+    plugin::{
+        decode_event,
+        proto::{transform_request::Message, ComponentKind, TransformRequest, TransformResponse},
+        Plugin, PluginConfig, Reconnect, Responses, SessionError, Step,
+    },
+    transforms::{TaskTransform, Transform},
+};
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+use tokio::sync::mpsc;
+
+inventory::submit! {
+    TransformDescription::new::<PluginConfig>("plugin")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "plugin")]
+impl TransformConfig for PluginConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        let plugin = self.connect(ComponentKind::Transform).await?;
+
+        Ok(Transform::task(PluginTransform { plugin }))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "plugin"
+    }
+}
+
+struct PluginTransform {
+    plugin: Plugin,
+}
+
+type Session = (mpsc::Sender<TransformRequest>, Responses<TransformResponse>);
+
+/// Open a stream with the plugin, which transforms the events sent to it.
+async fn open(plugin: &mut Plugin) -> Result<Session, SessionError> {
+    let (mut tx, rx) = mpsc::channel(1024);
+    let config = TransformRequest {
+        message: Some(Message::Config(plugin.config())),
+    };
+    tx.send(config).await.map_err(|_| SessionError::Closed)?;
+
+    let responses = plugin
+        .client
+        .transform(rx)
+        .await
+        .map_err(SessionError::Failed)?
+        .into_inner();
+    Ok((tx, Responses::spawn(responses, 1024)))
+}
+
+impl TaskTransform for PluginTransform {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut plugin = self.plugin;
+
+        Box::pin(stream! {
+            let mut reconnect = Reconnect::new(error_stage::PROCESSING);
+
+            loop {
+                let (tx, mut responses) = match open(&mut plugin).await {
+                    Ok(session) => session,
+                    Err(error) => {
+                        reconnect.failed(error).await;
+                        continue;
+                    }
+                };
+                let mut tx = Some(tx);
+
+                let error = loop {
+                    let step = tokio::select! {
+                        event = input_rx.next(), if tx.is_some() => Step::Input(event),
+                        response = responses.message() => Step::Response(response),
+                    };
+
+                    match step {
+                        Step::Input(Some(event)) => {
+                            if plugin.accepts(&event) {
+                                let request = TransformRequest {
+                                    message: Some(Message::Event(event.into())),
+                                };
+                                let sender = tx.as_mut().expect("input is open");
+                                if sender.send(request).await.is_err() {
+                                    break SessionError::Closed;
+                                }
+                            }
+                        }
+                        // Closing the request stream lets the plugin flush
+                        // the remaining events and end its response.
+                        Step::Input(None) => tx = None,
+                        Step::Response(Ok(Some(response))) => {
+                            reconnect.reset();
+                            if let Some(event) = decode_event(response.event) {
+                                yield event;
+                            }
+                        }
+                        Step::Response(Ok(None)) if tx.is_none() => return,
+                        Step::Response(Ok(None)) => break SessionError::Closed,
+                        Step::Response(Err(status)) => break SessionError::Failed(status),
+                    }
+                };
+
+                // The events the plugin hadn't transformed yet are lost, but
+                // the rest of the input is sent to a new stream.
+                if tx.is_none() {
+                    error.report(error_stage::PROCESSING);
+                    return;
+                }
+                reconnect.failed(error).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::log_schema,
+        plugin::{
+            proto::{self, plugin_server},
+            test::{describe, serve},
+        },
+    };
+    use futures::stream;
+    use tonic::{Request, Response, Status, Streaming};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PluginConfig>();
+    }
+
+    /// Uppercases the message of every log.
+    #[derive(Clone)]
+    struct UppercasePlugin;
+
+    #[tonic::async_trait]
+    impl plugin_server::Plugin for UppercasePlugin {
+        async fn describe(
+            &self,
+            _: Request<proto::DescribeRequest>,
+        ) -> Result<Response<proto::DescribeResponse>, Status> {
+            Ok(Response::new(describe(
+                ComponentKind::Transform,
+                proto::DataType::Log,
+            )))
+        }
+
+        async fn health(
+            &self,
+            _: Request<proto::ComponentConfig>,
+        ) -> Result<Response<proto::HealthResponse>, Status> {
+            Err(Status::unimplemented("health"))
+        }
+
+        type SourceStream = mpsc::Receiver<Result<proto::SourceResponse, Status>>;
+
+        async fn source(
+            &self,
+            _: Request<Streaming<proto::SourceRequest>>,
+        ) -> Result<Response<Self::SourceStream>, Status> {
+            Err(Status::unimplemented("source"))
+        }
+
+        type TransformStream = mpsc::Receiver<Result<proto::TransformResponse, Status>>;
+
+        async fn transform(
+            &self,
+            request: Request<Streaming<TransformRequest>>,
+        ) -> Result<Response<Self::TransformStream>, Status> {
+            let mut requests = request.into_inner();
+            let (mut tx, rx) = mpsc::channel(10);
+
+            tokio::spawn(async move {
+                while let Ok(Some(request)) = requests.message().await {
+                    if let Some(Message::Event(event)) = request.message {
+                        let mut event = Event::from(event);
+                        let log = event.as_mut_log();
+                        let message = log[log_schema().message_key()].to_string_lossy();
+                        log.insert(log_schema().message_key(), message.to_uppercase());
+
+                        let response = proto::TransformResponse {
+                            event: Some(event.into()),
+                        };
+                        if tx.send(Ok(response)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+
+            Ok(Response::new(rx))
+        }
+
+        type SinkStream = mpsc::Receiver<Result<proto::SinkResponse, Status>>;
+
+        async fn sink(
+            &self,
+            _: Request<Streaming<proto::SinkRequest>>,
+        ) -> Result<Response<Self::SinkStream>, Status> {
+            Err(Status::unimplemented("sink"))
+        }
+    }
+
+    #[tokio::test]
+    async fn transforms_events_with_plugin() {
+        let endpoint = serve(UppercasePlugin).await;
+        let config = PluginConfig {
+            endpoint,
+            component: "test".into(),
+            options: Default::default(),
+        };
+
+        let transform = config.build(&GlobalOptions::default()).await.unwrap();
+        let input = stream::iter(vec![Event::from("one"), Event::from("two")]);
+        let output = transform
+            .into_task()
+            .transform(Box::pin(input))
+            .collect::<Vec<_>>()
+            .await;
+
+        let messages = output
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["ONE", "TWO"]);
+    }
+}
@@ -1,12 +1,14 @@
 use crate::{
-    config::{DataType, TransformConfig, TransformDescription},
-    event::Event,
-    internal_events::RemapMappingError,
+    config::{log_schema, DataType, GlobalOptions, TransformConfig, TransformDescription},
+    event::{Event, LogEvent, Metric, Value},
+    internal_events::{RemapMappingAbort, RemapMappingError},
     transforms::{FunctionTransform, Transform},
     Result,
 };
-use remap::{value, Program, Runtime, TypeConstraint, TypeDef};
+use remap::{state, value, Object, Program, Runtime, TypeConstraint, TypeDef};
 use serde::{Deserialize, Serialize};
+use shared::datetime::TimeZone;
+use std::collections::BTreeMap;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
 #[serde(deny_unknown_fields, default)]
@@ -14,6 +16,26 @@ use serde::{Deserialize, Serialize};
 pub struct RemapConfig {
     pub source: String,
     pub drop_on_err: bool,
+    #[derivative(Default(value = "true"))]
+    pub drop_on_abort: bool,
+    pub reroute_dropped: bool,
+    /// Converts the events to logs or metrics. Without it, events keep their
+    /// type.
+    pub output_type: Option<OutputType>,
+    pub timezone: Option<TimeZone>,
+}
+
+/// The type of the events emitted by a remap transform.
+///
+/// Metrics converted to logs have the fields of the metric, such as `name`,
+/// `tags` and `kind`, along with a `counter`, `gauge`, `set`, `distribution`,
+/// `aggregated_histogram` or `aggregated_summary` field holding the value.
+/// Logs are converted to metrics by reading the same structure.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputType {
+    Log,
+    Metric,
 }
 
 inventory::submit! {
@@ -25,8 +47,12 @@ impl_generate_config_from_default!(RemapConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "remap")]
 impl TransformConfig for RemapConfig {
-    async fn build(&self) -> Result<Transform> {
-        Remap::new(self.clone()).map(Transform::function)
+    async fn build(&self, globals: &GlobalOptions) -> Result<Transform> {
+        Remap::new(RemapConfig {
+            timezone: self.timezone.or(Some(globals.timezone)),
+            ..self.clone()
+        })
+        .map(Transform::function)
     }
 
     fn input_type(&self) -> DataType {
@@ -34,71 +60,388 @@ impl TransformConfig for RemapConfig {
     }
 
     fn output_type(&self) -> DataType {
-        DataType::Any
+        match self.output_type {
+            Some(OutputType::Log) => DataType::Log,
+            Some(OutputType::Metric) => DataType::Metric,
+            None => DataType::Any,
+        }
     }
 
     fn transform_type(&self) -> &'static str {
         "remap"
     }
+
+    fn describe_output(&self) -> Option<Vec<String>> {
+        let mut state = state::Compiler::default();
+        compile(&self.source, &mut state).ok()?;
+
+        let mut fields = state
+            .path_query_types()
+            .iter()
+            .map(|(path, type_def)| {
+                format!(
+                    "{}: {}{}",
+                    path,
+                    type_def.kind,
+                    if type_def.is_fallible() {
+                        " (fallible)"
+                    } else {
+                        ""
+                    }
+                )
+            })
+            .collect::<Vec<_>>();
+        fields.sort();
+
+        Some(fields)
+    }
+
+    /// With `reroute_dropped`, the events the program drops are emitted to
+    /// the `<transform>.dropped` output, as they were before the program ran,
+    /// which may be before they were converted.
+    fn named_outputs(&self) -> Vec<(&'static str, DataType)> {
+        if self.reroute_dropped {
+            vec![(DROPPED, DataType::Any)]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
+/// The named output of the events the program drops.
+const DROPPED: &str = "dropped";
+
 #[derive(Debug, Clone)]
 pub struct Remap {
     program: Program,
     drop_on_err: bool,
+    drop_on_abort: bool,
+    reroute_dropped: bool,
+    output_type: Option<OutputType>,
+    timezone: TimeZone,
 }
 
 impl Remap {
     pub fn new(config: RemapConfig) -> crate::Result<Self> {
-        let accepts = TypeConstraint {
-            allow_any: true,
-            type_def: TypeDef {
-                fallible: true,
-                kind: value::Kind::all(),
-                ..Default::default()
-            },
-        };
+        let mut state = state::Compiler::default();
+        let program = compile(&config.source, &mut state)?;
 
-        let (program, _) = Program::new(
-            config.source.clone(),
-            &remap_functions::all(),
-            Some(accepts),
-            false,
-        )
-        .map_err(|diagnostics| {
-            remap::Formatter::new(&config.source, diagnostics)
-                .colored()
-                .to_string()
-        })?;
+        if config.output_type == Some(OutputType::Metric) {
+            check_metric_fields(&state)?;
+        }
 
         Ok(Remap {
             program,
             drop_on_err: config.drop_on_err,
+            drop_on_abort: config.drop_on_abort,
+            reroute_dropped: config.reroute_dropped,
+            output_type: config.output_type,
+            timezone: config.timezone.unwrap_or_default(),
+        })
+    }
+
+    /// Returns `true` if the unmodified event might be emitted, in which case
+    /// it has to be copied before the program runs.
+    fn keeps_original(&self) -> bool {
+        self.reroute_dropped || !self.drop_on_abort
+    }
+}
+
+/// Compile the program, leaving the type definitions of the paths it assigns
+/// to in the compiler `state`.
+fn compile(source: &str, state: &mut state::Compiler) -> crate::Result<Program> {
+    let accepts = TypeConstraint {
+        allow_any: true,
+        type_def: TypeDef {
+            fallible: true,
+            kind: value::Kind::all(),
+            ..Default::default()
+        },
+    };
+
+    let (program, diagnostics) = Program::new_with_state(
+        source.to_owned(),
+        &remap_functions::all(),
+        Some(accepts),
+        false,
+        state,
+    )
+    .map_err(|diagnostics| {
+        remap::Formatter::new(source, diagnostics)
+            .colored()
+            .to_string()
+    })?;
+
+    if diagnostics.has_warnings() {
+        warn!(
+            message = "Remap program compiled with warnings.",
+            diagnostics = %remap::Formatter::new(source, diagnostics),
+        );
+    }
+
+    Ok(program)
+}
+
+/// Check the types of the metric fields the program assigns to, so programs
+/// that can't produce valid metrics are rejected before they run.
+fn check_metric_fields(state: &state::Compiler) -> crate::Result<()> {
+    use remap::{value::Kind, Segment};
+
+    let mut errors = state
+        .path_query_types()
+        .iter()
+        .filter_map(|(path, type_def)| {
+            let expected = match path.segments() {
+                [Segment::Field(field)] => match field.as_str() {
+                    "name" | "kind" => Kind::Bytes,
+                    "namespace" => Kind::Bytes | Kind::Null,
+                    "timestamp" => Kind::Timestamp | Kind::Bytes,
+                    "tags"
+                    | "counter"
+                    | "gauge"
+                    | "set"
+                    | "distribution"
+                    | "aggregated_histogram"
+                    | "aggregated_summary" => Kind::Map,
+                    _ => return None,
+                },
+                [Segment::Field(tags), Segment::Field(_)] if tags.as_str() == "tags" => Kind::Bytes,
+                _ => return None,
+            };
+
+            if expected.contains(type_def.kind) {
+                None
+            } else {
+                Some(format!(
+                    "metric field {} must be {}, but is {}",
+                    path, expected, type_def.kind
+                ))
+            }
         })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.sort();
+        Err(errors.join("; ").into())
     }
 }
 
 impl FunctionTransform for Remap {
-    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
-        let mut runtime = Runtime::default();
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.transform_named(output, &mut Vec::new(), event)
+    }
+
+    fn transform_named(
+        &mut self,
+        output: &mut Vec<Event>,
+        named: &mut Vec<(&'static str, Event)>,
+        mut event: Event,
+    ) {
+        // Metrics converted to logs are converted before the program runs, so
+        // the program can reshape them.
+        if self.output_type == Some(OutputType::Log) {
+            if let Event::Metric(metric) = event {
+                event = Event::Log(metric_to_log(metric));
+            }
+        }
+
+        let mut original = if self.keeps_original() {
+            Some(event.clone())
+        } else {
+            None
+        };
+
+        let mut runtime = Runtime::new(state::Program::with_timezone(self.timezone));
+        let mut unnested = None;
         let result = match event {
-            Event::Log(ref mut event) => runtime.run(event, &self.program),
+            Event::Log(ref mut log) => {
+                let mut target = LogTarget { log, array: None };
+                let result = runtime.run(&mut target, &self.program);
+                unnested = target.array;
+                result
+            }
             Event::Metric(ref mut event) => runtime.run(event, &self.program),
         };
 
-        if let Err(error) = result {
-            emit!(RemapMappingError {
-                error: error.to_string(),
-                event_dropped: self.drop_on_err,
-            });
+        let dropped = match result {
+            Ok(_) => false,
+            // An aborted program leaves the event as it was before the
+            // program ran.
+            Err(error) if error.is_explicit() => {
+                emit!(RemapMappingAbort {
+                    event_dropped: self.drop_on_abort,
+                });
+
+                if let Some(original) = original.take() {
+                    event = original;
+                    unnested = None;
+                }
+                self.drop_on_abort
+            }
+            Err(error) => {
+                emit!(RemapMappingError {
+                    error: error.to_string(),
+                    event_dropped: self.drop_on_err,
+                });
+
+                self.drop_on_err
+            }
+        };
+
+        if dropped {
+            if self.reroute_dropped {
+                named.push((DROPPED, original.unwrap_or(event)));
+            }
+            return;
+        }
 
-            if self.drop_on_err {
-                return;
+        match unnested {
+            Some(value) => {
+                // Events split off an array keep the metadata of the event
+                // they came from, and are delivered on its behalf.
+                let finalizers = event.take_finalizers();
+                let metadata = match &event {
+                    Event::Log(log) => log.metadata().clone(),
+                    Event::Metric(_) => Default::default(),
+                };
+                let values = match value {
+                    remap::Value::Array(values) => values,
+                    value => vec![value],
+                };
+                for value in values {
+                    let mut log = into_log(value, metadata.clone());
+                    log.add_finalizers(finalizers.clone());
+                    self.push_converted(output, Event::Log(log));
+                }
             }
+            None => self.push_converted(output, event),
+        }
+    }
+}
+
+impl Remap {
+    /// Push a processed event, converting logs to metrics if the output type
+    /// calls for it. Logs that don't describe a valid metric are dropped.
+    fn push_converted(&self, output: &mut Vec<Event>, event: Event) {
+        match (self.output_type, event) {
+            (Some(OutputType::Metric), Event::Log(log)) => match log_to_metric(log) {
+                Ok(metric) => output.push(Event::Metric(metric)),
+                Err(error) => emit!(RemapMappingError {
+                    error: format!("invalid metric: {}", error),
+                    event_dropped: true,
+                }),
+            },
+            (_, event) => output.push(event),
         }
+    }
+}
 
-        output.push(event);
+/// Converts a metric to a log event with the structure of its serialization.
+fn metric_to_log(mut metric: Metric) -> LogEvent {
+    let finalizers = metric.take_finalizers();
+    let mut log = LogEvent::default();
+
+    if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(&metric) {
+        for (key, value) in fields {
+            log.insert_flat(key, value);
+        }
     }
+    // The timestamp is serialized as a string.
+    if let Some(timestamp) = metric.data.timestamp {
+        log.insert_flat("timestamp", timestamp);
+    }
+
+    log.add_finalizers(finalizers);
+    log
+}
+
+/// Converts a log event with the structure of a serialized metric to that
+/// metric.
+fn log_to_metric(mut log: LogEvent) -> serde_json::Result<Metric> {
+    let finalizers = log.take_finalizers();
+    let mut metric: Metric = serde_json::from_value(serde_json::to_value(&log)?)?;
+    metric.add_finalizers(finalizers);
+    Ok(metric)
+}
+
+/// The log event a program runs against.
+///
+/// A program can assign an array to the root path, for example with
+/// `. = unnest!(.events)`. From then on, the program operates on that array,
+/// and each of its elements is emitted as a separate event.
+#[derive(Debug)]
+struct LogTarget<'a> {
+    log: &'a mut LogEvent,
+    array: Option<remap::Value>,
+}
+
+impl Object for LogTarget<'_> {
+    fn insert(
+        &mut self,
+        path: &remap::Path,
+        value: remap::Value,
+    ) -> std::result::Result<(), String> {
+        if path.is_root() {
+            if let remap::Value::Array(_) = value {
+                Object::remove(&mut *self.log, path, false)?;
+                self.array = Some(value);
+                return Ok(());
+            }
+
+            self.array = None;
+        }
+
+        match &mut self.array {
+            Some(array) => Object::insert(array, path, value),
+            None => Object::insert(&mut *self.log, path, value),
+        }
+    }
+
+    fn get(&self, path: &remap::Path) -> std::result::Result<Option<remap::Value>, String> {
+        match &self.array {
+            Some(array) => Object::get(array, path),
+            None => Object::get(&*self.log, path),
+        }
+    }
+
+    fn remove(
+        &mut self,
+        path: &remap::Path,
+        compact: bool,
+    ) -> std::result::Result<Option<remap::Value>, String> {
+        if path.is_root() && self.array.is_some() {
+            return Ok(self.array.take());
+        }
+
+        match &mut self.array {
+            Some(array) => Object::remove(array, path, compact),
+            None => Object::remove(&mut *self.log, path, compact),
+        }
+    }
+
+    fn get_metadata(&self, key: &str) -> Option<remap::Value> {
+        Object::get_metadata(&*self.log, key)
+    }
+}
+
+/// Turn an element of an array assigned to the root path into a log event.
+/// Elements that aren't maps are stored in the message field.
+fn into_log(value: remap::Value, metadata: BTreeMap<String, Value>) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.set_metadata(metadata);
+
+    match value {
+        remap::Value::Map(_) => {
+            Object::insert(&mut log, &remap::Path::root(), value).expect("map is a valid root")
+        }
+        value => {
+            log.insert(log_schema().message_key(), value);
+        }
+    }
+
+    log
 }
 
 #[cfg(test)]
@@ -108,7 +451,7 @@ mod tests {
         metric::{MetricKind, MetricValue},
         Metric,
     };
-    use std::collections::BTreeMap;
+    use chrono::{TimeZone as _, Utc};
 
     #[test]
     fn generate_config() {
@@ -134,6 +477,7 @@ mod tests {
 "#
             .to_string(),
             drop_on_err: true,
+            ..Default::default()
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -160,6 +504,7 @@ mod tests {
                        .kind = "incremental""#
                 .to_string(),
             drop_on_err: true,
+            ..Default::default()
         };
         let mut tform = Remap::new(conf).unwrap();
 
@@ -181,4 +526,355 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn remap_unnest() {
+        let mut event = Event::from("unnest me");
+        event.as_mut_log().insert("events[0].id", 1);
+        event.as_mut_log().insert("events[1].id", 2);
+
+        let conf = RemapConfig {
+            source: r#". = unnest!(.events)
+                       .[1].events.id = 3"#
+                .to_string(),
+            drop_on_err: true,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        tform.transform(&mut output, event);
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(get_field_string(&output[0], "message"), "unnest me");
+        assert_eq!(get_field_string(&output[0], "events.id"), "1");
+        assert_eq!(get_field_string(&output[1], "message"), "unnest me");
+        assert_eq!(get_field_string(&output[1], "events.id"), "3");
+    }
+
+    #[test]
+    fn remap_get_metadata_field() {
+        let mut event = Event::from("read my metadata");
+        event
+            .as_mut_log()
+            .insert_metadata("vector.source_type", "http");
+
+        let conf = RemapConfig {
+            source: r#".source_type = get_metadata_field("vector.source_type")
+                       .partition = get_metadata_field("kafka.partition")
+                       . = [., .]"#
+                .to_string(),
+            drop_on_err: true,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        tform.transform(&mut output, event);
+
+        assert_eq!(output.len(), 2);
+        for event in output {
+            let log = event.as_log();
+            assert_eq!(log["source_type"], "http".into());
+            assert_eq!(log["partition"], Value::Null);
+            assert_eq!(log.get_metadata("vector.source_type"), Some(&"http".into()));
+        }
+    }
+
+    #[test]
+    fn remap_root_array_of_scalars() {
+        let conf = RemapConfig {
+            source: r#". = ["foo", "bar"]"#.to_string(),
+            drop_on_err: true,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        tform.transform(&mut output, Event::from("split me"));
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(get_field_string(&output[0], "message"), "foo");
+        assert_eq!(get_field_string(&output[1], "message"), "bar");
+    }
+
+    #[test]
+    fn remap_split_json_array() {
+        let conf = RemapConfig {
+            source: ". = parse_json!(.message)".to_string(),
+            drop_on_err: true,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        tform.transform(
+            &mut output,
+            Event::from(r#"[{"id": 1}, {"id": 2}, {"id": 3}]"#),
+        );
+
+        let ids = output
+            .iter()
+            .map(|event| get_field_string(event, "id"))
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn remap_root_array_empty() {
+        let conf = RemapConfig {
+            source: ". = []".to_string(),
+            drop_on_err: true,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        tform.transform(&mut output, Event::from("drop me"));
+
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn remap_root_array_error() {
+        let conf = RemapConfig {
+            source: r#". = [{"id": 1}, {"id": 2}]
+                       assert!(false)"#
+                .to_string(),
+            drop_on_err: false,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        tform.transform(&mut output, Event::from("split me"));
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(get_field_string(&output[1], "id"), "2");
+    }
+
+    #[test]
+    fn remap_parses_timestamps_in_timezone() {
+        let conf = RemapConfig {
+            source: r#".ts = parse_timestamp(.message, "%F %T")"#.to_string(),
+            timezone: TimeZone::parse("America/New_York"),
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let result = tform
+            .transform_one(Event::from("2021-01-15 08:30:00"))
+            .unwrap();
+        assert_eq!(
+            result.as_log()["ts"],
+            Value::Timestamp(Utc.ymd(2021, 1, 15).and_hms(13, 30, 0))
+        );
+    }
+
+    #[test]
+    fn remap_abort() {
+        let conf = RemapConfig {
+            source: r#".foo = "bar"
+                       abort"#
+                .to_string(),
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        assert!(tform.transform_one(Event::from("drop me")).is_none());
+    }
+
+    #[test]
+    fn remap_abort_keeps_event() {
+        let conf = RemapConfig {
+            source: r#".foo = "bar"
+                       abort"#
+                .to_string(),
+            drop_on_abort: false,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let result = tform.transform_one(Event::from("keep me")).unwrap();
+        assert_eq!(get_field_string(&result, "message"), "keep me");
+        assert!(result.as_log().get("foo").is_none());
+    }
+
+    #[test]
+    fn remap_abort_is_not_handled() {
+        let conf = RemapConfig {
+            source: r#".foo = abort ?? "bar""#.to_string(),
+            drop_on_abort: false,
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        let result = tform.transform_one(Event::from("keep me")).unwrap();
+        assert!(result.as_log().get("foo").is_none());
+    }
+
+    #[test]
+    fn remap_reroute_dropped() {
+        let conf = RemapConfig {
+            source: r#"if .message == "abort" { abort }
+                       .foo = "bar"
+                       if .message == "error" { assert!(false) }"#
+                .to_string(),
+            drop_on_err: true,
+            reroute_dropped: true,
+            ..Default::default()
+        };
+
+        assert_eq!(conf.named_outputs(), vec![("dropped", DataType::Any)]);
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut output = Vec::new();
+        let mut named = Vec::new();
+        for message in &["abort", "keep", "error"] {
+            tform.transform_named(&mut output, &mut named, Event::from(*message));
+        }
+        assert!(named.iter().all(|(output, _)| *output == "dropped"));
+        let dropped_output = named
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(get_field_string(&output[0], "message"), "keep");
+        assert_eq!(get_field_string(&output[0], "foo"), "bar");
+
+        let dropped_messages = dropped_output
+            .iter()
+            .map(|event| get_field_string(event, "message"))
+            .collect::<Vec<_>>();
+        assert_eq!(dropped_messages, vec!["abort", "error"]);
+        assert!(dropped_output
+            .iter()
+            .all(|event| event.as_log().get("foo").is_none()));
+    }
+
+    #[test]
+    fn remap_log_to_metric() {
+        let conf = RemapConfig {
+            source: r#".name = "requests"
+                       .kind = "incremental"
+                       .tags.status = to_string!(.status)
+                       .counter.value = 1
+                       del(.message)
+                       del(.status)"#
+                .to_string(),
+            drop_on_err: true,
+            output_type: Some(OutputType::Metric),
+            ..Default::default()
+        };
+        assert_eq!(conf.output_type(), DataType::Metric);
+        let mut tform = Remap::new(conf).unwrap();
+
+        let mut event = Event::from("GET /");
+        event.as_mut_log().insert("status", 200);
+        // The timestamp of the log is the timestamp of the metric.
+        let timestamp = event.as_log()[log_schema().timestamp_key()].clone();
+
+        let result = tform.transform_one(event).unwrap();
+        let metric = result.as_metric();
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.data.kind, MetricKind::Incremental);
+        assert_eq!(metric.data.value, MetricValue::Counter { value: 1.0 });
+        assert_eq!(metric.tag_value("status"), Some("200".to_owned()));
+        assert_eq!(metric.data.timestamp.map(Value::from), Some(timestamp));
+    }
+
+    #[test]
+    fn remap_log_to_invalid_metric() {
+        let conf = RemapConfig {
+            source: r#".name = "requests""#.to_string(),
+            drop_on_err: false,
+            output_type: Some(OutputType::Metric),
+            ..Default::default()
+        };
+        let mut tform = Remap::new(conf).unwrap();
+
+        assert!(tform.transform_one(Event::from("no value")).is_none());
+    }
+
+    #[test]
+    fn remap_rejects_invalid_metric_fields() {
+        let conf = RemapConfig {
+            source: r#".name = 1
+                       .tags.host = true"#
+                .to_string(),
+            output_type: Some(OutputType::Metric),
+            ..Default::default()
+        };
+
+        let error = Remap::new(conf).unwrap_err().to_string();
+        assert!(error.contains("metric field .name must be string"));
+        assert!(error.contains("metric field .tags.host must be string"));
+    }
+
+    #[test]
+    fn remap_metric_to_log() {
+        let metric = Metric::new(
+            "counter".into(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            vec![("host".to_owned(), "localhost".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let conf = RemapConfig {
+            source: r#".host = del(.tags.host)
+                       .value = del(.counter.value)
+                       del(.counter)"#
+                .to_string(),
+            drop_on_err: true,
+            output_type: Some(OutputType::Log),
+            ..Default::default()
+        };
+        assert_eq!(conf.output_type(), DataType::Log);
+        let mut tform = Remap::new(conf).unwrap();
+
+        let result = tform.transform_one(Event::Metric(metric)).unwrap();
+        let log = result.as_log();
+        assert_eq!(log["name"], "counter".into());
+        assert_eq!(log["kind"], "absolute".into());
+        assert_eq!(log["host"], "localhost".into());
+        assert_eq!(log["value"], 1.0.into());
+        assert!(log.get("counter").is_none());
+    }
+
+    #[test]
+    fn describe_output() {
+        let conf = RemapConfig {
+            source: r#".foo = "bar"
+                       .a.b = 1
+                       .a.c = true"#
+                .to_string(),
+            drop_on_err: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            conf.describe_output(),
+            Some(vec![
+                ".a.b: integer".to_owned(),
+                ".a.c: boolean".to_owned(),
+                ".foo: string".to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn describe_output_invalid_program() {
+        let conf = RemapConfig {
+            source: ".foo = ".to_string(),
+            drop_on_err: false,
+            ..Default::default()
+        };
+
+        assert_eq!(conf.describe_output(), None);
+    }
 }
@@ -0,0 +1,526 @@
+use crate::{
+    config::{DataType, GenerateConfig, GlobalOptions, TransformConfig, TransformDescription},
+    event::Event,
+    internal_events::{TenantQuotaEventProcessed, TenantQuotaExceeded},
+    transforms::{FunctionTransform, Transform},
+};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TenantQuotaConfig {
+    /// The field of logs, or the tag of metrics, holding the tenant of an event.
+    pub tenant_key: String,
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    pub max_events: Option<u64>,
+    pub max_bytes: Option<u64>,
+    /// Budgets of specific tenants, replacing the default ones.
+    #[serde(default)]
+    pub tenants: IndexMap<String, QuotaLimits>,
+    #[serde(default)]
+    pub reroute_over_quota: bool,
+    /// The number of tenants the internal metrics of the transform are tagged
+    /// with, beyond which tenants are tagged as `other`. The tenants of
+    /// `tenants` are always tagged.
+    #[serde(default = "default_max_tagged_tenants")]
+    pub max_tagged_tenants: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaLimits {
+    pub max_events: Option<u64>,
+    pub max_bytes: Option<u64>,
+}
+
+fn default_window_secs() -> u64 {
+    60
+}
+
+fn default_max_tagged_tenants() -> usize {
+    100
+}
+
+inventory::submit! {
+    TransformDescription::new::<TenantQuotaConfig>("tenant_quota")
+}
+
+impl GenerateConfig for TenantQuotaConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            tenant_key: "tenant".to_owned(),
+            window_secs: default_window_secs(),
+            max_events: Some(10_000),
+            max_bytes: None,
+            tenants: IndexMap::new(),
+            reroute_over_quota: false,
+            max_tagged_tenants: default_max_tagged_tenants(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tenant_quota")]
+impl TransformConfig for TenantQuotaConfig {
+    async fn build(&self, _globals: &GlobalOptions) -> crate::Result<Transform> {
+        Ok(Transform::function(TenantQuota::new(self)))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "tenant_quota"
+    }
+
+    /// With `reroute_over_quota`, the events over quota are emitted to the
+    /// `<transform>.over_quota` output.
+    fn named_outputs(&self) -> Vec<(&'static str, DataType)> {
+        if self.reroute_over_quota {
+            vec![(OVER_QUOTA, DataType::Any)]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The named output of the events over quota.
+const OVER_QUOTA: &str = "over_quota";
+
+#[derive(Clone, Debug)]
+struct Quotas {
+    window: Duration,
+    default: QuotaLimits,
+    overrides: HashMap<String, QuotaLimits>,
+    usages: HashMap<String, Usage>,
+    swept: Option<Instant>,
+    tagged: HashSet<String>,
+    max_tagged: usize,
+}
+
+impl Quotas {
+    fn new(config: &TenantQuotaConfig) -> Self {
+        let default = QuotaLimits {
+            max_events: config.max_events,
+            max_bytes: config.max_bytes,
+        };
+
+        Self {
+            window: Duration::from_secs(config.window_secs.max(1)),
+            default,
+            overrides: config
+                .tenants
+                .iter()
+                .map(|(tenant, limits)| {
+                    let limits = QuotaLimits {
+                        max_events: limits.max_events.or(default.max_events),
+                        max_bytes: limits.max_bytes.or(default.max_bytes),
+                    };
+                    (tenant.clone(), limits)
+                })
+                .collect(),
+            usages: HashMap::new(),
+            swept: None,
+            tagged: HashSet::new(),
+            max_tagged: config.max_tagged_tenants,
+        }
+    }
+
+    /// Counts the event against the budgets of its tenant, unless that would
+    /// exceed them, in which case `false` is returned.
+    fn try_consume(&mut self, tenant: &str, byte_size: u64, now: Instant) -> bool {
+        self.sweep(now);
+        let tag = self.tag(tenant);
+
+        let limits = self.overrides.get(tenant).unwrap_or(&self.default);
+        let window = self.window;
+        let usage = self
+            .usages
+            .entry(tenant.to_owned())
+            .or_insert_with(|| Usage::new(now));
+        usage.expire(now, window);
+
+        let within_quota = limits.max_events.map_or(true, |max| usage.events < max)
+            && limits
+                .max_bytes
+                .map_or(true, |max| usage.bytes + byte_size <= max);
+
+        if within_quota {
+            usage.add(now, byte_size);
+        }
+
+        emit!(TenantQuotaEventProcessed {
+            tenant: tag,
+            byte_size,
+            over_quota: !within_quota,
+            window_events: usage.events,
+            window_bytes: usage.bytes,
+        });
+
+        within_quota
+    }
+
+    /// Forgets the tenants without usage within the window, once per window,
+    /// so tenants that stopped sending events don't hold on to memory.
+    fn sweep(&mut self, now: Instant) {
+        let window = self.window;
+        if let Some(swept) = self.swept {
+            if now.saturating_duration_since(swept) < window {
+                return;
+            }
+        }
+        self.swept = Some(now);
+
+        self.usages.retain(|_, usage| {
+            usage.expire(now, window);
+            usage.events > 0
+        });
+    }
+
+    /// The tag of the tenant in internal metrics. Tenants are tagged as they
+    /// are seen, up to `max_tagged_tenants`, and as `other` from then on.
+    fn tag<'a>(&mut self, tenant: &'a str) -> &'a str {
+        if self.overrides.contains_key(tenant) || self.tagged.contains(tenant) {
+            tenant
+        } else if self.tagged.len() < self.max_tagged {
+            self.tagged.insert(tenant.to_owned());
+            tenant
+        } else {
+            "other"
+        }
+    }
+}
+
+/// The usage of a tenant over the rolling window, counted in one-second
+/// buckets.
+#[derive(Clone, Debug)]
+struct Usage {
+    start: Instant,
+    buckets: VecDeque<Bucket>,
+    events: u64,
+    bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+struct Bucket {
+    second: u64,
+    events: u64,
+    bytes: u64,
+}
+
+impl Usage {
+    fn new(start: Instant) -> Self {
+        Self {
+            start,
+            buckets: VecDeque::new(),
+            events: 0,
+            bytes: 0,
+        }
+    }
+
+    fn second(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.start).as_secs()
+    }
+
+    fn expire(&mut self, now: Instant, window: Duration) {
+        let oldest = (self.second(now) + 1).saturating_sub(window.as_secs());
+
+        while let Some(bucket) = self.buckets.front() {
+            if bucket.second >= oldest {
+                break;
+            }
+
+            self.events -= bucket.events;
+            self.bytes -= bucket.bytes;
+            self.buckets.pop_front();
+        }
+    }
+
+    fn add(&mut self, now: Instant, byte_size: u64) {
+        let second = self.second(now);
+        match self.buckets.back_mut() {
+            Some(bucket) if bucket.second == second => {
+                bucket.events += 1;
+                bucket.bytes += byte_size;
+            }
+            _ => self.buckets.push_back(Bucket {
+                second,
+                events: 1,
+                bytes: byte_size,
+            }),
+        }
+
+        self.events += 1;
+        self.bytes += byte_size;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TenantQuota {
+    tenant_key: String,
+    quotas: Quotas,
+    reroute_over_quota: bool,
+}
+
+impl TenantQuota {
+    fn new(config: &TenantQuotaConfig) -> Self {
+        Self {
+            tenant_key: config.tenant_key.clone(),
+            quotas: Quotas::new(config),
+            reroute_over_quota: config.reroute_over_quota,
+        }
+    }
+
+    fn tenant(&self, event: &Event) -> Option<String> {
+        match event {
+            Event::Log(log) => log
+                .get(&self.tenant_key)
+                .map(|value| value.to_string_lossy()),
+            Event::Metric(metric) => metric.tag_value(&self.tenant_key),
+        }
+    }
+}
+
+/// Estimates the size of an event by its JSON encoding.
+fn byte_size(event: &Event) -> u64 {
+    match event {
+        Event::Log(log) => serde_json::to_vec(log),
+        Event::Metric(metric) => serde_json::to_vec(metric),
+    }
+    .map(|encoded| encoded.len() as u64)
+    .unwrap_or(0)
+}
+
+impl FunctionTransform for TenantQuota {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.transform_named(output, &mut Vec::new(), event)
+    }
+
+    fn transform_named(
+        &mut self,
+        output: &mut Vec<Event>,
+        named: &mut Vec<(&'static str, Event)>,
+        event: Event,
+    ) {
+        // Events without a tenant aren't subject to quotas.
+        let tenant = match self.tenant(&event) {
+            Some(tenant) => tenant,
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+
+        let byte_size = byte_size(&event);
+        if self.quotas.try_consume(&tenant, byte_size, Instant::now()) {
+            output.push(event);
+            return;
+        }
+
+        emit!(TenantQuotaExceeded {
+            tenant: &tenant,
+            event_dropped: !self.reroute_over_quota,
+        });
+        if self.reroute_over_quota {
+            named.push((OVER_QUOTA, event));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{
+        metric::{MetricKind, MetricValue},
+        Metric,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TenantQuotaConfig>();
+    }
+
+    fn config(source: &str) -> TenantQuotaConfig {
+        toml::from_str(source).unwrap()
+    }
+
+    fn log(tenant: &str) -> Event {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("tenant", tenant);
+        event
+    }
+
+    #[test]
+    fn counts_events_per_tenant() {
+        let mut quotas = Quotas::new(&config(
+            r#"
+            tenant_key = "tenant"
+            max_events = 2
+            [tenants.big]
+            max_events = 3
+            "#,
+        ));
+        let now = Instant::now();
+
+        assert!(quotas.try_consume("small", 10, now));
+        assert!(quotas.try_consume("small", 10, now));
+        assert!(!quotas.try_consume("small", 10, now));
+
+        assert!(quotas.try_consume("big", 10, now));
+        assert!(quotas.try_consume("big", 10, now));
+        assert!(quotas.try_consume("big", 10, now));
+        assert!(!quotas.try_consume("big", 10, now));
+    }
+
+    #[test]
+    fn counts_bytes_per_tenant() {
+        let mut quotas = Quotas::new(&config(
+            r#"
+            tenant_key = "tenant"
+            max_bytes = 100
+            "#,
+        ));
+        let now = Instant::now();
+
+        assert!(quotas.try_consume("tenant", 60, now));
+        assert!(!quotas.try_consume("tenant", 60, now));
+        assert!(quotas.try_consume("tenant", 40, now));
+        assert!(!quotas.try_consume("tenant", 1, now));
+    }
+
+    #[test]
+    fn expires_usage_after_window() {
+        let mut quotas = Quotas::new(&config(
+            r#"
+            tenant_key = "tenant"
+            window_secs = 10
+            max_events = 2
+            "#,
+        ));
+        let start = Instant::now();
+
+        assert!(quotas.try_consume("tenant", 1, start));
+        assert!(quotas.try_consume("tenant", 1, start + Duration::from_secs(5)));
+        assert!(!quotas.try_consume("tenant", 1, start + Duration::from_secs(9)));
+
+        // The first event left the window, but not the second one.
+        assert!(quotas.try_consume("tenant", 1, start + Duration::from_secs(10)));
+        assert!(!quotas.try_consume("tenant", 1, start + Duration::from_secs(14)));
+        assert!(quotas.try_consume("tenant", 1, start + Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn drops_events_over_quota() {
+        let mut transform = TenantQuota::new(&config(
+            r#"
+            tenant_key = "tenant"
+            max_events = 1
+            "#,
+        ));
+
+        let mut output = Vec::new();
+        transform.transform(&mut output, log("a"));
+        transform.transform(&mut output, log("a"));
+        transform.transform(&mut output, log("b"));
+        transform.transform(&mut output, Event::from("no tenant"));
+
+        let tenants = output
+            .iter()
+            .map(|event| transform.tenant(event))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tenants,
+            vec![Some("a".to_owned()), Some("b".to_owned()), None]
+        );
+    }
+
+    #[test]
+    fn reroutes_events_over_quota() {
+        let config = config(
+            r#"
+            tenant_key = "tenant"
+            max_events = 2
+            reroute_over_quota = true
+            "#,
+        );
+        assert_eq!(config.named_outputs(), vec![("over_quota", DataType::Any)]);
+        let mut transform = TenantQuota::new(&config);
+
+        let mut output = Vec::new();
+        let mut named = Vec::new();
+        for _ in 0..5 {
+            transform.transform_named(&mut output, &mut named, log("a"));
+        }
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(named.len(), 3);
+        assert!(named.iter().all(|(output, _)| *output == "over_quota"));
+    }
+
+    #[test]
+    fn forgets_idle_tenants() {
+        let mut quotas = Quotas::new(&config(
+            r#"
+            tenant_key = "tenant"
+            window_secs = 10
+            max_events = 1
+            "#,
+        ));
+        let start = Instant::now();
+
+        assert!(quotas.try_consume("a", 1, start));
+        assert!(quotas.try_consume("b", 1, start + Duration::from_secs(5)));
+        assert_eq!(quotas.usages.len(), 2);
+
+        assert!(quotas.try_consume("b", 1, start + Duration::from_secs(15)));
+        assert_eq!(quotas.usages.keys().collect::<Vec<_>>(), vec!["b"]);
+    }
+
+    #[test]
+    fn bounds_tagged_tenants() {
+        let mut quotas = Quotas::new(&config(
+            r#"
+            tenant_key = "tenant"
+            max_tagged_tenants = 1
+            [tenants.big]
+            max_events = 3
+            "#,
+        ));
+
+        assert_eq!(quotas.tag("a"), "a");
+        assert_eq!(quotas.tag("b"), "other");
+        assert_eq!(quotas.tag("big"), "big");
+        assert_eq!(quotas.tag("a"), "a");
+    }
+
+    #[test]
+    fn reads_tenant_of_metrics() {
+        let transform = TenantQuota::new(&config(r#"tenant_key = "tenant""#));
+        let metric = Event::Metric(
+            Metric::new(
+                "counter".into(),
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(
+                vec![("tenant".to_owned(), "a".to_owned())]
+                    .into_iter()
+                    .collect(),
+            )),
+        );
+
+        assert_eq!(transform.tenant(&metric), Some("a".to_owned()));
+    }
+}
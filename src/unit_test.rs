@@ -21,6 +21,11 @@ pub struct Opts {
     /// Any number of Vector config files to test. If none are specified the
     /// default config path `/etc/vector/vector.toml` will be targeted.
     paths: Vec<PathBuf>,
+
+    /// Write the payloads of sinks to the golden files of the tests, instead
+    /// of checking them against the files.
+    #[structopt(long)]
+    update_golden_files: bool,
 }
 
 impl Opts {
@@ -48,6 +53,7 @@ pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
     match config::build_unit_tests(&paths).await {
         Ok(mut tests) => {
             tests.iter_mut().for_each(|t| {
+                t.update_golden_files(opts.update_golden_files);
                 let (test_inspections, test_errors) = t.run();
                 if !test_inspections.is_empty() {
                     aggregated_test_inspections.push((t.name.clone(), test_inspections));
@@ -22,6 +22,11 @@ pub struct Opts {
     #[structopt(short, long)]
     deny_warnings: bool,
 
+    /// Print the inferred type of the events emitted by transforms that support it,
+    /// such as the fields assigned by `remap` programs and the kinds of their values.
+    #[structopt(short, long)]
+    verbose: bool,
+
     /// Vector config files in TOML format to validate.
     #[structopt(name = "config-toml", long)]
     paths_toml: Vec<PathBuf>,
@@ -63,6 +68,10 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
         None => return exitcode::CONFIG,
     };
 
+    if opts.verbose {
+        describe_transforms(&config, &mut fmt);
+    }
+
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
             validated &= validate_environment(opts, &config, &mut fmt).await;
@@ -106,6 +115,19 @@ fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
     }
 }
 
+fn describe_transforms(config: &Config, fmt: &mut Formatter) {
+    for (name, transform) in &config.transforms {
+        if let Some(fields) = transform.inner.describe_output() {
+            fmt.title(format!("Output of `{}`", name));
+            if fields.is_empty() {
+                fmt.sub("-", vec!["No fields are assigned"]);
+            } else {
+                fmt.sub("-", fields);
+            }
+        }
+    }
+}
+
 async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
     let diff = ConfigDiff::initial(config);
 
@@ -147,11 +169,6 @@ async fn validate_healthchecks(
     pieces: &mut Pieces,
     fmt: &mut Formatter,
 ) -> bool {
-    if config.healthchecks.enabled {
-        fmt.warning("Health checks are disabled");
-        return !opts.deny_warnings;
-    }
-
     let healthchecks = topology::take_healthchecks(diff, pieces);
     // We are running health checks in serial so it's easier for the users
     // to parse which errors/warnings/etc. belong to which healthcheck.
@@ -169,6 +186,7 @@ async fn validate_healthchecks(
                     .get(&name)
                     .expect("Sink not present")
                     .healthcheck()
+                    .resolve(config.healthchecks)
                     .enabled
                 {
                     fmt.success(format!("Health check `{}`", name.as_str()));
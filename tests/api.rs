@@ -89,7 +89,7 @@ mod tests {
     // Starts and returns the server
     fn start_server() -> Server {
         let config = api_enabled_config();
-        api::Server::start(&config)
+        api::Server::start(&config).unwrap()
     }
 
     fn make_client(addr: SocketAddr) -> Client {
@@ -104,7 +104,7 @@ mod tests {
         let addr = config.api.address.unwrap();
         let url = format!("http://{}:{}/{}", addr.ip(), addr.port(), url);
 
-        let _server = api::Server::start(&config);
+        let _server = api::Server::start(&config).unwrap();
 
         // Build the request
         let client = reqwest::Client::new();
@@ -244,6 +244,38 @@ mod tests {
         assert!(res.contains("ok"));
     }
 
+    #[tokio::test]
+    /// Tests the /health endpoint lists the status of each component
+    async fn api_health_components() {
+        let res: serde_json::Value = url_test(api_enabled_config(), "health")
+            .await
+            .json()
+            .await
+            .unwrap();
+
+        assert_eq!(res["components"]["in1"], "running");
+        assert_eq!(res["components"]["out1"], "running");
+    }
+
+    #[tokio::test]
+    /// Tests the liveness endpoint can be moved to another path
+    async fn api_health_path() {
+        let mut config = api_enabled_config();
+        config.api.health_path = "/healthz".into();
+
+        let res = url_test(config, "healthz").await.status();
+
+        assert!(res.is_success());
+    }
+
+    #[tokio::test]
+    /// Tests the /ready endpoint returns a 503 until the server is marked as ready
+    async fn api_ready() {
+        let res = url_test(api_enabled_config(), "ready").await.status();
+
+        assert_eq!(res, reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[tokio::test]
     /// Tests that the API playground is enabled when playground = true (implicit)
     async fn api_playground_enabled() {
@@ -278,6 +310,53 @@ mod tests {
         assert_eq!(res.errors, None);
     }
 
+    // Provides a config that requires requests to the API to be authorized with `token`
+    fn api_auth_config(exempt_probes: bool) -> Config {
+        let mut config = api_enabled_config();
+        config.api.auth = Some(config::api::AuthOptions {
+            token: "token".to_owned(),
+            exempt_probes,
+        });
+        config
+    }
+
+    #[tokio::test]
+    /// Tests that queries without the configured token are rejected as unauthorized
+    async fn api_graphql_auth_rejected() {
+        let server = api::Server::start(&api_auth_config(true)).unwrap();
+
+        let client = make_client(server.addr());
+        let error = client.health_query().await.unwrap_err();
+        let status = error
+            .downcast_ref::<reqwest::Error>()
+            .and_then(reqwest::Error::status);
+        assert_eq!(status, Some(reqwest::StatusCode::UNAUTHORIZED));
+
+        let client = make_client(server.addr()).with_token(Some("wrong".to_owned()));
+        assert!(client.health_query().await.is_err());
+    }
+
+    #[tokio::test]
+    /// Tests that queries with the configured token are served
+    async fn api_graphql_auth_accepted() {
+        let server = api::Server::start(&api_auth_config(true)).unwrap();
+        let client = make_client(server.addr()).with_token(Some("token".to_owned()));
+
+        let res = client.health_query().await.unwrap();
+
+        assert!(res.data.unwrap().health);
+    }
+
+    #[tokio::test]
+    /// Tests that the probes are only authorized when they aren't exempt
+    async fn api_auth_probes() {
+        let res = url_test(api_auth_config(true), "health").await.status();
+        assert!(res.is_success());
+
+        let res = url_test(api_auth_config(false), "health").await.status();
+        assert_eq!(res, reqwest::StatusCode::UNAUTHORIZED);
+    }
+
     #[test]
     /// Tests links between components
     fn api_graphql_component_links() {
@@ -291,7 +370,7 @@ mod tests {
             config_builder.api.address = Some(next_addr());
 
             let config = config_builder.build().unwrap();
-            let server = api::Server::start(&config);
+            let server = api::Server::start(&config).unwrap();
 
             let client = make_client(server.addr());
 
@@ -396,6 +475,21 @@ mod tests {
         assert_eq!(res.data.unwrap().meta.version_string, vector::get_version());
     }
 
+    #[tokio::test]
+    /// tests that the build meta describes the running Vector
+    async fn api_graphql_meta_build_info() {
+        let server = start_server();
+        let client = make_client(server.addr());
+
+        let res = client.meta_build_info().await.unwrap();
+        let build = res.data.unwrap().meta.build;
+
+        assert_eq!(build.version, vector::vector_version().to_string());
+        assert_eq!(build.os, std::env::consts::OS);
+        assert_eq!(build.arch, std::env::consts::ARCH);
+        assert!(build.features.iter().any(|feature| feature == "api"));
+    }
+
     #[test]
     /// Tests that the heartbeat subscription returns a UTC payload every 1/2 second
     fn api_graphql_heartbeat() {
@@ -472,7 +566,7 @@ mod tests {
 
                 tokio::time::delay_for(tokio::time::Duration::from_millis(500)).await;
 
-                let server = api::Server::start(topology.config());
+                let server = api::Server::start(topology.config()).unwrap();
                 let client = new_subscription_client(server.addr()).await;
                 let subscription = client.component_processed_events_totals_subscription(500);
 
@@ -522,7 +616,7 @@ mod tests {
 
                 let topology = from_str_config(conf).await;
 
-                let server = api::Server::start(topology.config());
+                let server = api::Server::start(topology.config()).unwrap();
                 let client = new_subscription_client(server.addr()).await;
                 let subscription = client.component_processed_bytes_totals_subscription(500);
 
@@ -565,7 +659,7 @@ mod tests {
 
             let mut topology = from_str_config(conf).await;
 
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
             let client = new_subscription_client(server.addr()).await;
 
             // Spawn a handler for listening to changes
@@ -655,7 +749,7 @@ mod tests {
 
             let mut topology = from_str_config(conf).await;
 
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
             let client = new_subscription_client(server.addr()).await;
 
             // Spawn a handler for listening to changes
@@ -733,7 +827,7 @@ mod tests {
 
             let topology = from_str_config(conf).await;
 
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
             let client = new_subscription_client(server.addr()).await;
 
             // Spawn a handler for listening to changes
@@ -788,7 +882,7 @@ mod tests {
 
             let topology = from_str_config(conf).await;
 
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
             let client = new_subscription_client(server.addr()).await;
 
             // Spawn a handler for listening to changes
@@ -859,7 +953,7 @@ mod tests {
             );
 
             let topology = from_str_config(&conf).await;
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
 
             // Short delay to ensure logs are picked up
             tokio::time::delay_for(tokio::time::Duration::from_millis(200)).await;
@@ -902,7 +996,7 @@ mod tests {
             "#;
 
             let topology = from_str_config(&conf).await;
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
             let client = make_client(server.addr());
 
             // Retrieving a component that doesn't exist should return None
@@ -958,7 +1052,7 @@ mod tests {
 
             let topology = from_str_config(&conf).await;
 
-            let server = api::Server::start(topology.config());
+            let server = api::Server::start(topology.config()).unwrap();
             let client = make_client(server.addr());
 
             // Test after/first with a page size of 2, exhausting all results
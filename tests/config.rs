@@ -589,6 +589,60 @@ async fn disabled_healthcheck() {
     .unwrap();
 }
 
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-sample",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn dedicated_runtime() {
+    load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1234"
+
+        [transforms.sample]
+        type = "sample"
+        inputs = ["in"]
+        rate = 10
+        runtime.threads = 2
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["sample"]
+        address = "127.0.0.1:9999"
+        encoding = "text"
+        runtime = {}
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap();
+
+    load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1234"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["in"]
+        address = "127.0.0.1:9999"
+        encoding = "text"
+        runtime.threads = 0
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap_err();
+}
+
 #[cfg(all(feature = "sources-stdin", feature = "sinks-http"))]
 #[tokio::test]
 async fn parses_sink_no_request() {
@@ -826,3 +880,68 @@ async fn route() {
 
     assert_eq!(0, warnings.len());
 }
+
+#[cfg(all(feature = "sources-stdin", feature = "sinks-http"))]
+#[tokio::test]
+async fn templates() {
+    load(
+        r#"
+        [templates.standard_batch.batch]
+        max_bytes = 1049000
+        timeout_secs = 1
+
+        [templates.standard_http]
+        use = "standard_batch"
+        type = "http"
+        encoding = "json"
+
+        [templates.standard_http.auth]
+        strategy = "basic"
+        user = "user"
+        password = "password"
+
+        [sources.in]
+        type = "stdin"
+
+        [sinks.out1]
+        use = "standard_http"
+        inputs = ["in"]
+        uri = "https://localhost"
+
+        [sinks.out2]
+        use = ["standard_http"]
+        inputs = ["in"]
+        uri = "https://localhost"
+        batch.timeout_secs = 5
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap();
+}
+
+#[cfg(all(feature = "sources-stdin", feature = "sinks-http"))]
+#[tokio::test]
+async fn bad_template() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "stdin"
+
+        [sinks.out]
+        use = "standard_http"
+        type = "http"
+        inputs = ["in"]
+        uri = "https://localhost"
+        encoding = "json"
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec![r#"Component "out": Unknown template "standard_http"."#]
+    );
+}
@@ -5,6 +5,7 @@ use futures::{future, stream, FutureExt, SinkExt, StreamExt};
 use std::{
     collections::HashMap,
     iter,
+    num::NonZeroUsize,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
@@ -12,7 +13,12 @@ use std::{
 };
 
 use tokio::time::{delay_for, Duration};
-use vector::{config::Config, event::Event, test_util::start_topology, topology};
+use vector::{
+    config::{Config, RuntimeOptions},
+    event::Event,
+    test_util::start_topology,
+    topology,
+};
 
 fn basic_config() -> Config {
     let mut config = Config::builder();
@@ -194,6 +200,35 @@ async fn topology_transform_chain() {
     assert_eq!(vec!["this first second"], res);
 }
 
+#[tokio::test]
+async fn topology_dedicated_runtimes() {
+    let (mut in1, source1) = source();
+    let transform1 = transform(" transformed", 0.0);
+    let (out1, sink1) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform("t1", &["in1"], transform1);
+    config.add_sink("out1", &["t1"], sink1);
+    let runtime = RuntimeOptions {
+        threads: NonZeroUsize::new(2).unwrap(),
+    };
+    config.transforms["t1"].runtime = Some(runtime);
+    config.sinks["out1"].runtime = Some(runtime);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    let event = Event::from("this");
+
+    in1.send(event).await.unwrap();
+
+    topology.stop().await;
+
+    let res = out1.map(into_message).collect::<Vec<_>>().await;
+
+    assert_eq!(vec!["this transformed"], res);
+}
+
 #[tokio::test]
 async fn topology_remove_one_source() {
     let (mut in1, source1) = source();
@@ -542,3 +577,18 @@ async fn topology_healthcheck_run_for_changes_on_reload() {
     config.healthchecks.require_healthy = true;
     assert!(!topology.reload_config_and_respawn(config).await.unwrap());
 }
+
+#[tokio::test]
+async fn topology_disabled_healthcheck_does_not_fail_start() {
+    let mut config = basic_config_with_sink_failing_healthcheck();
+    config.healthchecks.enabled = false;
+    config.healthchecks.require_healthy = true;
+    let diff = vector::config::ConfigDiff::initial(&config);
+    let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
+        .await
+        .unwrap();
+
+    assert!(topology::start_validated(config, diff, pieces)
+        .await
+        .is_some());
+}